@@ -28,6 +28,7 @@ object PrettyPrinter extends ByteStringPrettyPrinter {
       s"Insert(${ks.map(buildString).mkString(",")})"
     case Transform.TransformInstance.Failure(_)  => "TransformFailure"
     case Transform.TransformInstance.Identity(_) => "Read"
+    case Transform.TransformInstance.Prune(_)    => "Prune"
     case Transform.TransformInstance.Write(TransformWrite(mv)) =>
       mv match {
         case None    => "Write(Nothing)"
@@ -54,6 +54,8 @@ object ProcessedDeployResult {
             ExecutionSuccessful(deploy, effects, cost.fold(0L)(_.value.toLong))
           case ipc.DeployResult.ExecutionResult(None, None, _) => ???
         }
+      // The node doesn't set a block gas limit when executing, so no deploy is ever skipped.
+      case ipc.DeployResult(ipc.DeployResult.Value.SkippedBlockGasExhausted(_)) => ???
       case ipc.DeployResult(ipc.DeployResult.Value.Empty) => ???
     }
 
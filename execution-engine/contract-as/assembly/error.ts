@@ -96,7 +96,8 @@ export const enum ErrorCode {
  *
  * | Inclusive range | Variant(s)                                   |
  * | ----------------| ---------------------------------------------|
- * | [1, 65023]      | all except `Mint`, `ProofOfStake` and `User`. Can be created with [[Error.fromErrorCode]] |
+ * | [1, 64767]      | all except `Auction`, `Mint`, `ProofOfStake` and `User`. Can be created with [[Error.fromErrorCode]] |
+ * | [64768, 65023]  | `Auction` - instantiation currently unsupported |
  * | [65024, 65279]  | `Mint` - instantiation currently unsupported |
  * | [65280, 65535]  | `ProofOfStake` errors |
  * | [65536, 131071] | User error codes created with [[Error.fromUserError]] |
@@ -38,6 +38,10 @@ export const enum SystemContract {
    * Standard Payment contract.
    */
   StandardPayment = 2,
+  /**
+   * Auction contract, only available once installed by an upgrade.
+   */
+  Auction = 3,
 }
 
 /**
@@ -343,6 +347,10 @@ export enum Phase {
    * Set while finalizing payment at the end of a deploy.
    */
   FinalizePayment = 3,
+  /**
+   * Set while executing the authorization contract of the deploy's account, before payment.
+   */
+  Authorization = 4,
 }
 
 /**
@@ -8,7 +8,7 @@ use casperlabs_types::{
         ActionType, AddKeyFailure, PublicKey, RemoveKeyFailure, SetThresholdFailure,
         UpdateKeyFailure, Weight,
     },
-    bytesrepr, URef, UREF_SERIALIZED_LENGTH,
+    api_error, bytesrepr, ApiError, Key, URef, UREF_SERIALIZED_LENGTH,
 };
 
 use super::to_ptr;
@@ -28,6 +28,44 @@ pub fn get_main_purse() -> URef {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Replaces the account's main purse with a new purse, moving the full balance into it, and
+/// revokes the replaced purse so that any further transfer from or to it fails.  Returns the ID of
+/// the new main purse.
+///
+/// This is meant for recovering from the replaced purse's [`URef`] having leaked, and requires the
+/// deploy's authorization keys to meet the account's key management threshold, otherwise
+/// [`ApiError::PermissionDenied`] is returned.  Note that [`get_main_purse`] keeps returning the
+/// replaced purse for the rest of the deploy.
+pub fn rotate_main_purse() -> Result<URef, ApiError> {
+    let dest_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
+    let (ret, bytes) = unsafe {
+        let ret = ext_ffi::rotate_main_purse(dest_non_null_ptr.as_ptr());
+        let bytes = Vec::from_raw_parts(
+            dest_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+            UREF_SERIALIZED_LENGTH,
+        );
+        (ret, bytes)
+    };
+    api_error::result_from(ret)?;
+    Ok(bytesrepr::deserialize(bytes).unwrap_or_revert())
+}
+
+/// Sets the stored contract which must approve the account's deploys, or clears it if
+/// `authorization_contract` is `None`.
+///
+/// The contract is called before payment with the deploy hash, the account and the deploy's
+/// authorization keys, and the deploy is rejected unless it returns without reverting.  Deploys
+/// whose authorization keys meet the account's key management threshold skip the contract, so
+/// that it can always be replaced.  This requires the deploy's authorization keys to meet the key
+/// management threshold, otherwise [`ApiError::PermissionDenied`] is returned, and
+/// [`ApiError::InvalidArgument`] is returned if the key doesn't refer to a stored contract.
+pub fn set_authorization_contract(authorization_contract: Option<Key>) -> Result<(), ApiError> {
+    let (contract_ptr, contract_size, _bytes) = to_ptr(authorization_contract);
+    let result = unsafe { ext_ffi::set_authorization_contract(contract_ptr, contract_size) };
+    api_error::result_from(result)
+}
+
 /// Sets the given [`ActionType`]'s threshold to the provided value.
 pub fn set_action_threshold(
     action_type: ActionType,
@@ -4,23 +4,25 @@
 #[rustfmt::skip]
 use alloc::vec;
 use alloc::{collections::BTreeMap, string::String, vec::Vec};
-use core::mem::MaybeUninit;
+use core::{convert::TryFrom, mem::MaybeUninit};
 
 use casperlabs_types::{
     account::PublicKey,
     api_error,
-    bytesrepr::{self, FromBytes},
-    ApiError, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, URef,
-    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH,
+    bytesrepr::{self, FromBytes, ToBytes},
+    AccessRights, ApiError, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, ScheduleId, URef,
+    BLOCKTIME_SERIALIZED_LENGTH, PHASE_SERIALIZED_LENGTH, SCHEDULE_ID_LENGTH,
+    U64_SERIALIZED_LENGTH,
 };
 
 use crate::{args_parser::ArgsParser, contract_api, ext_ffi, unwrap_or_revert::UnwrapOrRevert};
 
 /// Returns the given [`CLValue`] to the host, terminating the currently running module.
 ///
-/// Note this function is only relevant to contracts stored on chain which are invoked via
-/// [`call_contract`] and can thus return a value to their caller.  The return value of a directly
-/// deployed contract is never used.
+/// A contract stored on chain which is invoked via [`call_contract`] returns the value to its
+/// caller.  Session code returns the value to the node as part of the deploy's result, e.g. to hand
+/// back the key of a contract it stored; the value must not exceed the size allowed by the
+/// chainspec.  The return value of directly deployed payment code is never used.
 pub fn ret(value: CLValue) -> ! {
     let (ptr, size, _bytes) = contract_api::to_ptr(value);
     unsafe {
@@ -38,11 +40,28 @@ pub fn revert<T: Into<ApiError>>(error: T) -> ! {
     }
 }
 
+/// Stops execution of a contract and reverts execution effects with a given [`ApiError`], attaching
+/// `data` which describes the failure.
+///
+/// The `ApiError` is returned as with [`revert`], and `data` is returned alongside it via the
+/// deploy response.  Data longer than the chainspec allows is truncated, and gas is charged for
+/// each byte kept.  See the `require!` and `require_eq!` macros, which attach the failed condition.
+pub fn revert_with_data<T: Into<ApiError>>(error: T, data: &[u8]) -> ! {
+    unsafe {
+        ext_ffi::revert_with_data(error.into().into(), data.as_ptr(), data.len());
+    }
+}
+
 /// Calls the given stored contract, passing the given arguments to it.
 ///
 /// If the stored contract calls [`ret`], then that value is returned from `call_contract`.  If the
 /// stored contract calls [`revert`], then execution stops and `call_contract` doesn't return.
 /// Otherwise `call_contract` returns `()`.
+///
+/// The stored contract may only modify its own named keys, never those of its caller.  Its
+/// modifications are visible to all subsequent reads within the same deploy, including reads by
+/// later calls into the same contract and by a call back into the caller, whether the contract is
+/// stored under a hash or a uref.
 #[allow(clippy::ptr_arg)]
 pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef, args: A) -> T {
     let contract_key: Key = c_ptr.into();
@@ -83,6 +102,106 @@ pub fn call_contract<A: ArgsParser, T: CLTyped + FromBytes>(c_ptr: ContractRef,
     bytesrepr::deserialize(serialized_result).unwrap_or_revert()
 }
 
+/// Calls the given stored contract without allowing it to modify global state, passing the given
+/// arguments to it.
+///
+/// The stored contract, and any contracts it calls in turn, are executed in a read-only context.
+/// If any of them attempts to modify global state, execution of the stored contract stops and
+/// [`ApiError::ReadOnlyContext`] is returned.  Otherwise this behaves like [`call_contract`].
+#[allow(clippy::ptr_arg)]
+pub fn call_contract_read_only<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    args: A,
+) -> Result<T, ApiError> {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
+        .map(contract_api::to_ptr)
+        .unwrap_or_revert();
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::call_contract_read_only(
+                key_ptr,
+                key_size,
+                args_ptr,
+                args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret)?;
+        unsafe { bytes_written.assume_init() }
+    };
+
+    let serialized_result = if bytes_written == 0 {
+        // If no bytes were written, the host buffer hasn't been set and hence shouldn't be read.
+        vec![]
+    } else {
+        // NOTE: this is a copy of the contents of `read_host_buffer()`, as in `call_contract()`.
+        let bytes_non_null_ptr = contract_api::alloc_bytes(bytes_written);
+        let mut dest: Vec<u8> = unsafe {
+            Vec::from_raw_parts(bytes_non_null_ptr.as_ptr(), bytes_written, bytes_written)
+        };
+        read_host_buffer_into(&mut dest)?;
+        dest
+    };
+
+    Ok(bytesrepr::deserialize(serialized_result).unwrap_or_revert())
+}
+
+/// Calls the given stored contract, passing the given arguments to it, without stopping execution
+/// if the stored contract reverts.
+///
+/// If the stored contract, or any contract it calls in turn, calls [`revert`], then all their
+/// modifications of global state are discarded and the [`ApiError`] it reverted with is returned,
+/// e.g. the [`ApiError::User`] variant for a contract-specific error.  Gas used by the call is
+/// still charged.  Otherwise this behaves like [`call_contract`].
+///
+/// Errors returned by the host itself, e.g. [`ApiError::HostBufferFull`], are returned in the same
+/// way.
+#[allow(clippy::ptr_arg)]
+pub fn try_call_contract<A: ArgsParser, T: CLTyped + FromBytes>(
+    c_ptr: ContractRef,
+    args: A,
+) -> Result<T, ApiError> {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
+        .map(contract_api::to_ptr)
+        .unwrap_or_revert();
+
+    let bytes_written = {
+        let mut bytes_written = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::try_call_contract(
+                key_ptr,
+                key_size,
+                args_ptr,
+                args_size,
+                bytes_written.as_mut_ptr(),
+            )
+        };
+        api_error::result_from(ret)?;
+        unsafe { bytes_written.assume_init() }
+    };
+
+    let serialized_result = if bytes_written == 0 {
+        // If no bytes were written, the host buffer hasn't been set and hence shouldn't be read.
+        vec![]
+    } else {
+        // NOTE: this is a copy of the contents of `read_host_buffer()`, as in `call_contract()`.
+        let bytes_non_null_ptr = contract_api::alloc_bytes(bytes_written);
+        let mut dest: Vec<u8> = unsafe {
+            Vec::from_raw_parts(bytes_non_null_ptr.as_ptr(), bytes_written, bytes_written)
+        };
+        read_host_buffer_into(&mut dest)?;
+        dest
+    };
+
+    Ok(bytesrepr::deserialize(serialized_result).unwrap_or_revert())
+}
+
 /// Takes the name of a (non-mangled) `extern "C"` function to store as a contract under the given
 /// [`URef`] which should already reference a stored contract.
 ///
@@ -134,6 +253,56 @@ pub fn get_arg<T: FromBytes>(i: u32) -> Option<Result<T, bytesrepr::Error>> {
     Some(bytesrepr::deserialize(arg_bytes))
 }
 
+fn get_named_arg_size(name: &str) -> Option<usize> {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let mut arg_size: usize = 0;
+    let ret =
+        unsafe { ext_ffi::get_named_arg_size(name_ptr, name_size, &mut arg_size as *mut usize) };
+    match api_error::result_from(ret) {
+        Ok(_) => Some(arg_size),
+        Err(ApiError::MissingArgument) => None,
+        Err(e) => revert(e),
+    }
+}
+
+/// Returns the argument named `name` in the deploy for the current module invocation.
+///
+/// Only the args of a deploy's session or payment code can be named, so `None` is returned in a
+/// called contract.  Named args can still be read by position with [`get_arg`].
+pub fn get_named_arg<T: FromBytes>(name: &str) -> Option<Result<T, bytesrepr::Error>> {
+    let arg_size = get_named_arg_size(name)?;
+    let arg_bytes = if arg_size > 0 {
+        let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::get_named_arg(name_ptr, name_size, data_non_null_ptr.as_ptr(), arg_size)
+        };
+        let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        // Assumed to be safe as `get_named_arg_size` checks the argument already
+        api_error::result_from(ret).map(|_| data).unwrap_or_revert()
+    } else {
+        // Avoids allocation with 0 bytes and a call to get_named_arg
+        Vec::new()
+    };
+    Some(bytesrepr::deserialize(arg_bytes))
+}
+
+/// Returns the i-th argument passed to the host for the current module invocation, checking first
+/// that it was passed as a `T`.
+///
+/// If the argument was passed with a different [`CLType`](casperlabs_types::CLType), execution
+/// stops with an error naming both the expected and the actual type.  Arguments passed without a
+/// type are read as with [`get_arg`].
+pub fn get_typed_arg<T: FromBytes + CLTyped>(i: u32) -> Option<Result<T, bytesrepr::Error>> {
+    let (cl_type_ptr, cl_type_size, _bytes) = contract_api::to_ptr(T::cl_type());
+    let ret = unsafe { ext_ffi::check_arg_type(i as usize, cl_type_ptr, cl_type_size) };
+    match api_error::result_from(ret) {
+        Ok(()) => get_arg(i),
+        Err(ApiError::MissingArgument) => None,
+        Err(e) => revert(e),
+    }
+}
+
 /// Returns the caller of the current context, i.e. the [`PublicKey`] of the account which made the
 /// deploy request.
 pub fn get_caller() -> PublicKey {
@@ -147,7 +316,23 @@ pub fn get_caller() -> PublicKey {
     bytesrepr::deserialize(buf).unwrap_or_revert()
 }
 
-/// Returns the current [`BlockTime`].
+/// Returns the [`Key`] of the account or stored contract which called the current context.
+///
+/// Unlike [`get_caller`], this is the immediate caller: when a stored contract calls another, the
+/// callee sees the calling contract's key rather than the deploy's account.
+pub fn get_caller_key() -> Key {
+    let output_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_caller_key(output_size.as_mut_ptr()) };
+        api_error::result_from(ret).unwrap_or_revert();
+        unsafe { output_size.assume_init() }
+    };
+    let buf = read_host_buffer(output_size).unwrap_or_revert();
+    bytesrepr::deserialize(buf).unwrap_or_revert()
+}
+
+/// Returns the current [`BlockTime`], i.e. the time of the block the deploy is executed in, in
+/// milliseconds since the Unix epoch.
 pub fn get_blocktime() -> BlockTime {
     let dest_non_null_ptr = contract_api::alloc_bytes(BLOCKTIME_SERIALIZED_LENGTH);
     let bytes = unsafe {
@@ -175,6 +360,23 @@ pub fn get_phase() -> Phase {
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
 
+/// Returns the gas consumed so far by the current deploy, including the cost of this call.
+///
+/// Contracts metering their own work can compare it against the gas their payment buys and stop
+/// before running out of gas.
+pub fn get_gas_counter() -> u64 {
+    let dest_non_null_ptr = contract_api::alloc_bytes(U64_SERIALIZED_LENGTH);
+    let bytes = unsafe {
+        ext_ffi::get_gas_counter(dest_non_null_ptr.as_ptr());
+        Vec::from_raw_parts(
+            dest_non_null_ptr.as_ptr(),
+            U64_SERIALIZED_LENGTH,
+            U64_SERIALIZED_LENGTH,
+        )
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
+
 /// Returns the requested named [`Key`] from the current context.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
@@ -212,23 +414,40 @@ pub fn has_key(name: &str) -> bool {
     result == 0
 }
 
-/// Stores the given [`Key`] under `name` in the current context's named keys.
+/// Stores the given [`Key`] under `name` in the current context's named keys, replacing any key
+/// already stored under `name`.  Returns `true` if a key was replaced.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
 /// currently-executing module is a direct call or a sub-call respectively.
-pub fn put_key(name: &str, key: Key) {
+pub fn put_key(name: &str, key: Key) -> bool {
+    let replaced = has_key(name);
     let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
     let (key_ptr, key_size, _bytes2) = contract_api::to_ptr(key);
     unsafe { ext_ffi::put_key(name_ptr, name_size, key_ptr, key_size) };
+    replaced
 }
 
-/// Removes the [`Key`] stored under `name` in the current context's named keys.
+/// Stores the given [`Key`] under `name` in the current context's named keys, unless a key is
+/// already stored under `name`, in which case [`ApiError::NamedKeyAlreadyExists`] is returned.
 ///
 /// The current context is either the caller's account or a stored contract depending on whether the
 /// currently-executing module is a direct call or a sub-call respectively.
-pub fn remove_key(name: &str) {
+pub fn put_key_if_absent(name: &str, key: Key) -> Result<(), ApiError> {
     let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
-    unsafe { ext_ffi::remove_key(name_ptr, name_size) }
+    let (key_ptr, key_size, _bytes2) = contract_api::to_ptr(key);
+    let result = unsafe { ext_ffi::put_key_if_absent(name_ptr, name_size, key_ptr, key_size) };
+    api_error::result_from(result)
+}
+
+/// Removes the [`Key`] stored under `name` in the current context's named keys.  Returns
+/// [`ApiError::NamedKeyNotFound`] if no key is stored under `name`.
+///
+/// The current context is either the caller's account or a stored contract depending on whether the
+/// currently-executing module is a direct call or a sub-call respectively.
+pub fn remove_key(name: &str) -> Result<(), ApiError> {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+    let result = unsafe { ext_ffi::remove_key_if_present(name_ptr, name_size) };
+    api_error::result_from(result)
 }
 
 /// Returns the named keys of the current context.
@@ -260,6 +479,85 @@ pub fn is_valid_uref(uref: URef) -> bool {
     result != 0
 }
 
+/// Returns the access rights which the current context holds for the address of `uref`, or `None`
+/// if the address is not known to the current context.
+///
+/// The access rights of `uref` itself are ignored, so this can be used to check the rights held on
+/// a [`URef`] received as an argument before attempting an operation on it.
+pub fn access_rights(uref: URef) -> Option<AccessRights> {
+    let (uref_ptr, uref_size, _bytes) = contract_api::to_ptr(uref);
+    let result = unsafe { ext_ffi::access_rights(uref_ptr, uref_size) };
+    u8::try_from(result).ok().and_then(AccessRights::from_bits)
+}
+
+/// Stores `value` under `name` in the transient storage of the current execution.
+///
+/// Transient storage is shared by all the contracts called during the execution of the session or
+/// payment code, but is never written to global state and is dropped when that execution ends.  Gas
+/// is charged for each byte of `name` and of the serialized `value`, and the execution fails if the
+/// total size stored exceeds the limit set by the chain's wasm costs.
+pub fn put_transient<T: CLTyped + ToBytes>(name: &str, value: T) {
+    let (name_ptr, name_size, _bytes1) = contract_api::to_ptr(name);
+    let cl_value = CLValue::from_t(value).unwrap_or_revert();
+    let (value_ptr, value_size, _bytes2) = contract_api::to_ptr(cl_value);
+    unsafe { ext_ffi::put_transient(name_ptr, name_size, value_ptr, value_size) };
+}
+
+/// Returns the value stored under `name` in the transient storage of the current execution, or
+/// `None` if there is no such value.  Reverts if the value can't be deserialized as `T`.
+pub fn get_transient<T: CLTyped + FromBytes>(name: &str) -> Option<T> {
+    let (name_ptr, name_size, _bytes) = contract_api::to_ptr(name);
+
+    let value_size = {
+        let mut value_size = MaybeUninit::uninit();
+        let ret = unsafe { ext_ffi::get_transient(name_ptr, name_size, value_size.as_mut_ptr()) };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { value_size.assume_init() },
+            Err(ApiError::ValueNotFound) => return None,
+            Err(e) => revert(e),
+        }
+    };
+
+    let value_bytes = read_host_buffer(value_size).unwrap_or_revert();
+    Some(bytesrepr::deserialize(value_bytes).unwrap_or_revert())
+}
+
+/// Schedules a call to the given stored contract, passing the given arguments to it, to be made
+/// once the block time reaches `not_before`.
+///
+/// The call is made at most once, by the engine rather than by a deploy.  A fixed amount of motes
+/// is moved from the main purse of the calling account into escrow up front to pay for the call,
+/// and whatever isn't spent is refunded to that purse once the call has been made.  Returns
+/// [`ApiError::InvalidArgument`] if `c_ptr` doesn't reference a stored contract, or
+/// [`ApiError::Transfer`] if the escrow can't be paid.
+pub fn schedule_call<A: ArgsParser>(
+    c_ptr: ContractRef,
+    args: A,
+    not_before: BlockTime,
+) -> Result<ScheduleId, ApiError> {
+    let contract_key: Key = c_ptr.into();
+    let (key_ptr, key_size, _bytes1) = contract_api::to_ptr(contract_key);
+    let (args_ptr, args_size, _bytes2) = ArgsParser::parse(args)
+        .map(contract_api::to_ptr)
+        .unwrap_or_revert();
+    let (not_before_ptr, not_before_size, _bytes3) = contract_api::to_ptr(not_before);
+
+    let mut schedule_id = [0u8; SCHEDULE_ID_LENGTH];
+    let ret = unsafe {
+        ext_ffi::schedule_call(
+            key_ptr,
+            key_size,
+            args_ptr,
+            args_size,
+            not_before_ptr,
+            not_before_size,
+            schedule_id.as_mut_ptr(),
+        )
+    };
+    api_error::result_from(ret)?;
+    Ok(ScheduleId::new(schedule_id))
+}
+
 fn read_host_buffer_into(dest: &mut [u8]) -> Result<usize, ApiError> {
     let mut bytes_written = MaybeUninit::uninit();
     let ret = unsafe {
@@ -6,7 +6,8 @@ use core::{convert::From, mem::MaybeUninit};
 use casperlabs_types::{
     api_error,
     bytesrepr::{self, FromBytes, ToBytes},
-    AccessRights, ApiError, CLTyped, CLValue, ContractRef, Key, URef, UREF_SERIALIZED_LENGTH,
+    AccessRights, ApiError, CLTyped, CLValue, ContractRef, FunctionSpec, Key, URef,
+    KEY_LOCAL_SEED_LENGTH, UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
@@ -114,6 +115,35 @@ pub fn add_local<K: ToBytes, V: CLTyped + ToBytes>(key: K, value: V) {
     }
 }
 
+/// Removes `key` and the value under it from the global state once the effects of the current
+/// execution are committed.
+///
+/// Only contracts executed by the engine itself may prune; the execution fails if this is called
+/// from a user deploy.
+pub fn prune(key: Key) {
+    let (key_ptr, key_size, _bytes) = contract_api::to_ptr(key);
+    unsafe {
+        ext_ffi::prune(key_ptr, key_size);
+    }
+}
+
+/// Copies the values under the local keys of `old_seed` to the same keys in the context-local
+/// partition of global state, and returns the number of values copied.
+///
+/// The local keys of a contract stored under a [`URef`] are seeded with the `URef`'s address, so
+/// they survive [`upgrade_contract_at_uref`](runtime::upgrade_contract_at_uref) unchanged.  This
+/// is for moving local data written under a different seed, e.g. by a contract the caller
+/// replaces: `old_seed` must be the address of a `URef` the caller holds with write access,
+/// otherwise [`ApiError::PermissionDenied`] is returned.  Keys already holding a value in the
+/// caller's partition are left as they are, and the values under `old_seed` are not removed.
+pub fn migrate_local_keys(old_seed: [u8; KEY_LOCAL_SEED_LENGTH]) -> Result<u32, ApiError> {
+    let mut migrated_count = MaybeUninit::uninit();
+    let ret =
+        unsafe { ext_ffi::migrate_local_keys(old_seed.as_ptr(), migrated_count.as_mut_ptr()) };
+    api_error::result_from(ret)?;
+    Ok(unsafe { migrated_count.assume_init() })
+}
+
 /// Stores the serialized bytes of an exported, non-mangled `extern "C"` function as a new contract
 /// under a [`URef`] generated by the host.
 pub fn store_function(name: &str, named_keys: BTreeMap<String, Key>) -> ContractRef {
@@ -138,6 +168,36 @@ pub fn store_function_at_hash(name: &str, named_keys: BTreeMap<String, Key>) ->
     ContractRef::Hash(addr)
 }
 
+/// Stores the serialized bytes of an exported, non-mangled `extern "C"` function as a new contract
+/// at an immutable address generated by the host, declaring the properties given by `spec`.
+///
+/// Calls to a function declared pure are memoized for the rest of the execute request in which
+/// they are made, so its result must depend only on its arguments and on the global state it
+/// reads.  A memoized result is no longer used once a value the call read has changed.  A call
+/// which modifies global state fails with an `ImpureDeclaredPure` error.
+pub fn store_function_with_spec(
+    name: &str,
+    named_keys: BTreeMap<String, Key>,
+    spec: FunctionSpec,
+) -> ContractRef {
+    let (fn_ptr, fn_size, _bytes1) = contract_api::to_ptr(name);
+    let (keys_ptr, keys_size, _bytes2) = contract_api::to_ptr(named_keys);
+    let (spec_ptr, spec_size, _bytes3) = contract_api::to_ptr(spec);
+    let mut addr = [0u8; 32];
+    unsafe {
+        ext_ffi::store_function_with_spec(
+            fn_ptr,
+            fn_size,
+            keys_ptr,
+            keys_size,
+            spec_ptr,
+            spec_size,
+            addr.as_mut_ptr(),
+        );
+    }
+    ContractRef::Hash(addr)
+}
+
 /// Returns a new unforgeable pointer, where the value is initialized to `init`.
 pub fn new_uref<T: CLTyped + ToBytes>(init: T) -> URef {
     let uref_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
@@ -153,3 +213,24 @@ pub fn new_uref<T: CLTyped + ToBytes>(init: T) -> URef {
     };
     bytesrepr::deserialize(bytes).unwrap_or_revert()
 }
+
+/// Returns a new unforgeable pointer flagged [`AccessRights::PUBLIC_READ`], where the value is
+/// initialized to `init`.
+///
+/// Any contract may [`read`] the value under the returned `URef`, even one which was never given
+/// it, but writing or adding to it still requires the `URef` to be known to the caller.
+pub fn new_public_uref<T: CLTyped + ToBytes>(init: T) -> URef {
+    let uref_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
+    let cl_value = CLValue::from_t(init).unwrap_or_revert();
+    let (cl_value_ptr, cl_value_size, _cl_value_bytes) = contract_api::to_ptr(cl_value);
+    let bytes = unsafe {
+        // URef has `READ_ADD_WRITE` and `PUBLIC_READ`
+        ext_ffi::new_public_uref(uref_non_null_ptr.as_ptr(), cl_value_ptr, cl_value_size);
+        Vec::from_raw_parts(
+            uref_non_null_ptr.as_ptr(),
+            UREF_SERIALIZED_LENGTH,
+            UREF_SERIALIZED_LENGTH,
+        )
+    };
+    bytesrepr::deserialize(bytes).unwrap_or_revert()
+}
@@ -4,20 +4,24 @@ use alloc::vec::Vec;
 use core::mem::MaybeUninit;
 
 use casperlabs_types::{
-    account::PublicKey, api_error, bytesrepr, ApiError, ContractRef, SystemContractType,
-    TransferResult, TransferredTo, URef, U512, UREF_SERIALIZED_LENGTH,
+    account::PublicKey,
+    api_error, bytesrepr,
+    system_contract_errors::mint,
+    system_contract_names::{
+        mint::{METHOD_ALLOWANCE, METHOD_APPROVE, METHOD_TRANSFER_FROM},
+        pos::METHOD_GET_PAYMENT_PURSE_BALANCE,
+    },
+    ApiError, ContractRef, Key, SystemContractType, TransferResult, TransferredTo, URef, U512,
+    UREF_SERIALIZED_LENGTH,
 };
 
 use crate::{
-    contract_api::{self, runtime},
+    contract_api::{self, account, runtime},
     ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 
-/// Name of the reference to the Mint contract in the named keys.
-pub const MINT_NAME: &str = "mint";
-/// Name of the reference to the Proof of Stake contract in the named keys.
-pub const POS_NAME: &str = "pos";
+pub use casperlabs_types::system_contract_names::{MINT_NAME, POS_NAME};
 
 fn get_system_contract(system_contract: SystemContractType) -> ContractRef {
     let system_contract_index = system_contract.into();
@@ -65,6 +69,14 @@ pub fn get_standard_payment() -> ContractRef {
     get_system_contract(SystemContractType::StandardPayment)
 }
 
+/// Returns a read-only pointer to the auction contract.
+///
+/// Reverts with [`ApiError::InvalidSystemContract`] if no auction contract has been installed, or
+/// with another appropriate [`ApiError`] on any other failure.
+pub fn get_auction() -> ContractRef {
+    get_system_contract(SystemContractType::Auction)
+}
+
 /// Creates a new empty purse and returns its [`URef`].
 pub fn create_purse() -> URef {
     let purse_non_null_ptr = contract_api::alloc_bytes(UREF_SERIALIZED_LENGTH);
@@ -83,7 +95,17 @@ pub fn create_purse() -> URef {
     }
 }
 
-/// Returns the balance in motes of the given purse.
+/// Returns `true` if `purse` was created by the Mint contract.
+///
+/// A transfer to a [`URef`] which isn't a purse fails, so this can be used to check the target of a
+/// transfer beforehand.
+pub fn purse_exists(purse: URef) -> bool {
+    let (purse_ptr, purse_size, _bytes) = contract_api::to_ptr(purse);
+    let result = unsafe { ext_ffi::purse_exists(purse_ptr, purse_size) };
+    result == 0
+}
+
+/// Returns the balance in motes of the given purse, or `None` if it isn't a purse.
 pub fn get_balance(purse: URef) -> Option<U512> {
     let (purse_ptr, purse_size, _bytes) = contract_api::to_ptr(purse);
 
@@ -101,6 +123,40 @@ pub fn get_balance(purse: URef) -> Option<U512> {
     Some(value)
 }
 
+/// Returns the balance in motes of the given purse, or `None` if it isn't a purse, as reported by
+/// the Mint contract.
+///
+/// Unlike [`get_balance`], `purse` must be known to the caller with the access rights it carries,
+/// which must include at least [`AccessRights::READ`](casperlabs_types::AccessRights::READ),
+/// otherwise execution is reverted.  A read-only `URef` to a purse suffices, so a contract can be
+/// given one to check the balance of a purse it can't transfer from.
+pub fn get_balance_by_uref(purse: URef) -> Option<U512> {
+    let (purse_ptr, purse_size, _bytes) = contract_api::to_ptr(purse);
+
+    let value_size = {
+        let mut output_size = MaybeUninit::uninit();
+        let ret = unsafe {
+            ext_ffi::get_balance_by_uref(purse_ptr, purse_size, output_size.as_mut_ptr())
+        };
+        match api_error::result_from(ret) {
+            Ok(_) => unsafe { output_size.assume_init() },
+            Err(ApiError::InvalidPurse) => return None,
+            Err(error) => runtime::revert(error),
+        }
+    };
+    let value_bytes = runtime::read_host_buffer(value_size).unwrap_or_revert();
+    let value: U512 = bytesrepr::deserialize(value_bytes).unwrap_or_revert();
+    Some(value)
+}
+
+/// Returns the balance in motes of the Proof of Stake contract's payment purse, i.e. the amount
+/// deposited so far as payment for the current deploy.
+///
+/// Must be called during the payment phase, otherwise execution is reverted.
+pub fn payment_purse_balance() -> U512 {
+    runtime::call_contract(get_proof_of_stake(), (METHOD_GET_PAYMENT_PURSE_BALANCE,))
+}
+
 /// Transfers `amount` of motes from the default purse of the account to `target`
 /// account.  If `target` does not exist it will be created.
 pub fn transfer_to_account(target: PublicKey, amount: U512) -> TransferResult {
@@ -135,7 +191,8 @@ pub fn transfer_from_purse_to_account(
 }
 
 /// Transfers `amount` of motes from `source` purse to `target` purse.  If `target` does not exist
-/// the transfer fails.
+/// the transfer fails with `ApiError::Mint(DestinationPurseNotFound)`,
+/// or with [`ApiError::Transfer`] under protocol versions before 1.1.0.
 pub fn transfer_from_purse_to_purse(
     source: URef,
     target: URef,
@@ -154,9 +211,35 @@ pub fn transfer_from_purse_to_purse(
             amount_size,
         )
     };
-    if result == 0 {
-        Ok(())
-    } else {
-        Err(ApiError::Transfer)
-    }
+    api_error::result_from(result)
+}
+
+/// Allows `spender` (an account or stored contract) to transfer up to `amount` of motes out of the
+/// main purse of the account via [`transfer_from_allowance`], replacing any previous allowance.
+/// Approving zero revokes the allowance.
+pub fn approve_spend(spender: Key, amount: U512) -> Result<(), ApiError> {
+    let purse = account::get_main_purse();
+    let result: Result<(), mint::Error> =
+        runtime::call_contract(get_mint(), (METHOD_APPROVE, purse, spender, amount));
+    result.map_err(ApiError::from)
+}
+
+/// Returns the amount of motes `spender` may still transfer out of `purse`.
+pub fn get_allowance(purse: URef, spender: Key) -> U512 {
+    runtime::call_contract(get_mint(), (METHOD_ALLOWANCE, purse, spender))
+}
+
+/// Transfers `amount` of motes from `owner_purse` to `target` purse on behalf of the current
+/// context, which must have been approved via [`approve_spend`].  If the remaining allowance is
+/// smaller than `amount` the transfer fails with `ApiError::Mint(InsufficientAllowance)`.
+pub fn transfer_from_allowance(
+    owner_purse: URef,
+    target: URef,
+    amount: U512,
+) -> Result<(), ApiError> {
+    let result: Result<(), mint::Error> = runtime::call_contract(
+        get_mint(),
+        (METHOD_TRANSFER_FROM, owner_purse, target, amount),
+    );
+    result.map_err(ApiError::from)
 }
@@ -14,7 +14,10 @@ extern "C" {
     );
     pub fn add(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
     pub fn add_local(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+    pub fn prune(key_ptr: *const u8, key_size: usize);
+    pub fn migrate_local_keys(old_seed_ptr: *const u8, migrated_count_ptr: *mut u32) -> i32;
     pub fn new_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize);
+    pub fn new_public_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize);
     pub fn store_function(
         function_name_ptr: *const u8,
         function_name_size: usize,
@@ -29,9 +32,26 @@ extern "C" {
         named_keys_size: usize,
         hash_ptr: *const u8,
     );
+    pub fn store_function_with_spec(
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        spec_ptr: *const u8,
+        spec_size: usize,
+        hash_ptr: *const u8,
+    );
     pub fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32;
     pub fn get_arg(index: usize, dest_ptr: *mut u8, dest_size: usize) -> i32;
     pub fn get_arg_size(index: usize, dest_size: *mut usize) -> i32;
+    pub fn check_arg_type(index: usize, cl_type_ptr: *const u8, cl_type_size: usize) -> i32;
+    pub fn get_named_arg(
+        name_ptr: *const u8,
+        name_size: usize,
+        dest_ptr: *mut u8,
+        dest_size: usize,
+    ) -> i32;
+    pub fn get_named_arg_size(name_ptr: *const u8, name_size: usize, dest_size: *mut usize) -> i32;
     pub fn ret(value_ptr: *const u8, value_size: usize) -> !;
     pub fn call_contract(
         key_ptr: *const u8,
@@ -40,6 +60,20 @@ extern "C" {
         args_size: usize,
         result_size: *mut usize,
     ) -> i32;
+    pub fn call_contract_read_only(
+        key_ptr: *const u8,
+        key_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
+    pub fn try_call_contract(
+        key_ptr: *const u8,
+        key_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
     pub fn get_key(
         name_ptr: *const u8,
         name_size: usize,
@@ -51,7 +85,25 @@ extern "C" {
     pub fn put_key(name_ptr: *const u8, name_size: usize, key_ptr: *const u8, key_size: usize);
     pub fn remove_key(name_ptr: *const u8, name_size: usize);
     pub fn revert(status: u32) -> !;
+    pub fn revert_with_data(status: u32, data_ptr: *const u8, data_size: usize) -> !;
     pub fn is_valid_uref(uref_ptr: *const u8, uref_size: usize) -> i32;
+    pub fn access_rights(uref_ptr: *const u8, uref_size: usize) -> i32;
+    pub fn put_transient(
+        name_ptr: *const u8,
+        name_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn get_transient(name_ptr: *const u8, name_size: usize, output_size: *mut usize) -> i32;
+    pub fn schedule_call(
+        key_ptr: *const u8,
+        key_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        not_before_ptr: *const u8,
+        not_before_size: usize,
+        schedule_id_ptr: *mut u8,
+    ) -> i32;
     pub fn add_associated_key(
         public_key_ptr: *const u8,
         public_key_size: usize,
@@ -65,6 +117,14 @@ extern "C" {
     ) -> i32;
     pub fn set_action_threshold(permission_level: u32, threshold: i32) -> i32;
     pub fn get_caller(output_size: *mut usize) -> i32;
+    pub fn get_caller_key(output_size: *mut usize) -> i32;
+    pub fn put_key_if_absent(
+        name_ptr: *const u8,
+        name_size: usize,
+        key_ptr: *const u8,
+        key_size: usize,
+    ) -> i32;
+    pub fn remove_key_if_present(name_ptr: *const u8, name_size: usize) -> i32;
     pub fn get_blocktime(dest_ptr: *const u8);
     pub fn create_purse(purse_ptr: *const u8, purse_size: usize) -> i32;
     pub fn transfer_to_account(
@@ -90,7 +150,14 @@ extern "C" {
         amount_size: usize,
     ) -> i32;
     pub fn get_balance(purse_ptr: *const u8, purse_size: usize, result_size: *mut usize) -> i32;
+    pub fn get_balance_by_uref(
+        purse_ptr: *const u8,
+        purse_size: usize,
+        result_size: *mut usize,
+    ) -> i32;
+    pub fn purse_exists(purse_ptr: *const u8, purse_size: usize) -> i32;
     pub fn get_phase(dest_ptr: *mut u8);
+    pub fn get_gas_counter(dest_ptr: *mut u8);
     pub fn upgrade_contract_at_uref(
         name_ptr: *const u8,
         name_size: usize,
@@ -103,6 +170,8 @@ extern "C" {
         dest_size: usize,
     ) -> i32;
     pub fn get_main_purse(dest_ptr: *mut u8);
+    pub fn rotate_main_purse(dest_ptr: *mut u8) -> i32;
+    pub fn set_authorization_contract(contract_ptr: *const u8, contract_size: usize) -> i32;
     pub fn read_host_buffer(dest_ptr: *mut u8, dest_size: usize, bytes_written: *mut usize) -> i32;
     #[cfg(feature = "test-support")]
     pub fn print(text_ptr: *const u8, text_size: usize);
@@ -53,6 +53,12 @@
 //!
 //! Support for writing smart contracts are contained in the [`contract_api`] module and its
 //! submodules.
+//!
+//! # Unit Testing
+//!
+//! Enabling the crate's `mock-host` feature, e.g. for a contract's dev-dependency on this crate,
+//! replaces the host functions with the `test_support` mock when building for a native target, so
+//! that a contract's logic can be covered by unit tests.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(
@@ -79,7 +85,13 @@ pub static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
 pub mod args_parser;
 pub mod contract_api;
+#[cfg(not(all(feature = "mock-host", not(target_arch = "wasm32"))))]
 pub mod ext_ffi;
 #[cfg(not(any(feature = "std", test)))]
 pub mod handlers;
+#[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+pub mod test_support;
 pub mod unwrap_or_revert;
+
+#[cfg(all(feature = "mock-host", not(target_arch = "wasm32")))]
+pub use test_support::ext_ffi;
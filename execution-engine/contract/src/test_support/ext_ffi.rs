@@ -0,0 +1,586 @@
+//! Native implementations of the host-side functions declared in the real `ext_ffi`, run against
+//! the current thread's [`MockRuntime`](super::MockRuntime).
+//!
+//! These take the same arguments as the Wasm host functions, so that the contract API works
+//! unchanged on top of them.  A revert or return unwinds the stack with a payload caught by
+//! [`execute`](super::execute).
+#![allow(missing_docs, clippy::missing_safety_doc)]
+
+use alloc::vec::Vec;
+use core::{mem, ptr, slice};
+use std::panic;
+
+use casperlabs_types::{
+    account::PublicKey,
+    api_error,
+    bytesrepr::{self, FromBytes, ToBytes},
+    system_contract_errors::mint,
+    ApiError, CLType, CLValue, Key, TransferredTo, URef, U512,
+};
+
+use super::{with_mock_runtime, Return, Revert};
+
+unsafe fn read_bytes(ptr: *const u8, size: usize) -> Vec<u8> {
+    if size == 0 {
+        return Vec::new();
+    }
+    slice::from_raw_parts(ptr, size).to_vec()
+}
+
+unsafe fn read_from<T: FromBytes>(ptr: *const u8, size: usize) -> T {
+    bytesrepr::deserialize(read_bytes(ptr, size)).expect("mock host should deserialize argument")
+}
+
+/// Reads a name, which is passed serialized like the host's other arguments.
+unsafe fn read_str(ptr: *const u8, size: usize) -> String {
+    read_from(ptr, size)
+}
+
+unsafe fn write_bytes(dest: *mut u8, bytes: &[u8]) {
+    ptr::copy_nonoverlapping(bytes.as_ptr(), dest, bytes.len());
+}
+
+unsafe fn write_to<T: ToBytes>(dest: *mut u8, value: T) {
+    write_bytes(
+        dest,
+        &value.into_bytes().expect("mock host should serialize"),
+    );
+}
+
+/// Puts `bytes` in the host buffer, writing their length to `size_ptr`.
+unsafe fn set_host_buffer(bytes: Vec<u8>, size_ptr: *mut usize) {
+    *size_ptr = bytes.len();
+    with_mock_runtime(|runtime| runtime.host_buffer = Some(bytes));
+}
+
+fn i32_from(result: Result<(), ApiError>) -> i32 {
+    api_error::i32_from(result)
+}
+
+pub unsafe fn read_value(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32 {
+    let key: Key = read_from(key_ptr, key_size);
+    match with_mock_runtime(|runtime| runtime.values.get(&key.normalize()).cloned()) {
+        Some(cl_value) => {
+            set_host_buffer(cl_value.inner_bytes().clone(), output_size);
+            0
+        }
+        None => i32_from(Err(ApiError::ValueNotFound)),
+    }
+}
+
+pub unsafe fn write(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize) {
+    let key: Key = read_from(key_ptr, key_size);
+    let cl_value: CLValue = read_from(value_ptr, value_size);
+    with_mock_runtime(|runtime| runtime.values.insert(key.normalize(), cl_value));
+}
+
+pub unsafe fn prune(key_ptr: *const u8, key_size: usize) {
+    let key: Key = read_from(key_ptr, key_size);
+    with_mock_runtime(|runtime| runtime.values.remove(&key.normalize()));
+}
+
+pub unsafe fn new_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize) {
+    let cl_value: CLValue = read_from(value_ptr, value_size);
+    let uref = with_mock_runtime(|runtime| runtime.store_new(cl_value));
+    write_to(uref_ptr, uref);
+}
+
+pub unsafe fn new_public_uref(uref_ptr: *mut u8, value_ptr: *const u8, value_size: usize) {
+    let cl_value: CLValue = read_from(value_ptr, value_size);
+    let uref = with_mock_runtime(|runtime| runtime.store_new(cl_value));
+    let access_rights = uref.access_rights() | casperlabs_types::AccessRights::PUBLIC_READ;
+    write_to(uref_ptr, uref.with_access_rights(access_rights));
+}
+
+pub unsafe fn load_named_keys(total_keys: *mut usize, result_size: *mut usize) -> i32 {
+    let named_keys = with_mock_runtime(|runtime| runtime.named_keys.clone());
+    *total_keys = named_keys.len();
+    if !named_keys.is_empty() {
+        let bytes = named_keys.into_bytes().expect("mock host should serialize");
+        set_host_buffer(bytes, result_size);
+    }
+    0
+}
+
+pub unsafe fn get_arg(index: usize, dest_ptr: *mut u8, dest_size: usize) -> i32 {
+    let arg = match with_mock_runtime(|runtime| runtime.args.get(index).cloned()) {
+        Some(arg) => arg,
+        None => return i32_from(Err(ApiError::MissingArgument)),
+    };
+    if arg.inner_bytes().len() > dest_size {
+        return i32_from(Err(ApiError::OutOfMemory));
+    }
+    write_bytes(dest_ptr, arg.inner_bytes());
+    0
+}
+
+pub unsafe fn get_arg_size(index: usize, dest_size: *mut usize) -> i32 {
+    match with_mock_runtime(|runtime| runtime.args.get(index).map(|arg| arg.inner_bytes().len())) {
+        Some(size) => {
+            *dest_size = size;
+            0
+        }
+        None => i32_from(Err(ApiError::MissingArgument)),
+    }
+}
+
+pub unsafe fn get_named_arg(
+    name_ptr: *const u8,
+    name_size: usize,
+    dest_ptr: *mut u8,
+    dest_size: usize,
+) -> i32 {
+    let name = read_str(name_ptr, name_size);
+    match with_mock_runtime(|runtime| runtime.arg_names.get(&name).copied()) {
+        Some(index) => get_arg(index, dest_ptr, dest_size),
+        None => i32_from(Err(ApiError::MissingArgument)),
+    }
+}
+
+pub unsafe fn get_named_arg_size(
+    name_ptr: *const u8,
+    name_size: usize,
+    dest_size: *mut usize,
+) -> i32 {
+    let name = read_str(name_ptr, name_size);
+    match with_mock_runtime(|runtime| runtime.arg_names.get(&name).copied()) {
+        Some(index) => get_arg_size(index, dest_size),
+        None => i32_from(Err(ApiError::MissingArgument)),
+    }
+}
+
+pub unsafe fn check_arg_type(index: usize, cl_type_ptr: *const u8, cl_type_size: usize) -> i32 {
+    let expected: CLType = read_from(cl_type_ptr, cl_type_size);
+    let found = match with_mock_runtime(|runtime| runtime.args.get(index).cloned()) {
+        Some(arg) => arg.cl_type().clone(),
+        None => return i32_from(Err(ApiError::MissingArgument)),
+    };
+    if found != CLType::Any && found != expected {
+        panic!(
+            "argument {} has type {:?}, but {:?} was expected",
+            index, found, expected
+        );
+    }
+    0
+}
+
+pub unsafe fn ret(value_ptr: *const u8, value_size: usize) -> ! {
+    let cl_value: CLValue = read_from(value_ptr, value_size);
+    panic::resume_unwind(Box::new(Return(cl_value)))
+}
+
+/// Runs the contract registered under the key at `key_ptr`, putting the value it returned in the
+/// host buffer.
+unsafe fn call(
+    key_ptr: *const u8,
+    key_size: usize,
+    args_ptr: *const u8,
+    args_size: usize,
+    result_size: *mut usize,
+) -> Result<(), Revert> {
+    let key: Key = read_from(key_ptr, key_size);
+    let args: Vec<CLValue> = read_from(args_ptr, args_size);
+    let entry_point = with_mock_runtime(|runtime| runtime.contracts.get(&key.normalize()).cloned())
+        .unwrap_or_else(|| panic!("no contract registered with the mock host under {}", key));
+
+    // only the args of the deploy are named
+    let (caller_args, caller_arg_names) = with_mock_runtime(|runtime| {
+        (
+            mem::replace(&mut runtime.args, args),
+            mem::take(&mut runtime.arg_names),
+        )
+    });
+    let result = super::execute(|| entry_point());
+    with_mock_runtime(|runtime| {
+        runtime.args = caller_args;
+        runtime.arg_names = caller_arg_names;
+    });
+
+    match result? {
+        Some(cl_value) => set_host_buffer(cl_value.inner_bytes().clone(), result_size),
+        None => *result_size = 0,
+    }
+    Ok(())
+}
+
+pub unsafe fn call_contract(
+    key_ptr: *const u8,
+    key_size: usize,
+    args_ptr: *const u8,
+    args_size: usize,
+    result_size: *mut usize,
+) -> i32 {
+    if let Err(revert) = call(key_ptr, key_size, args_ptr, args_size, result_size) {
+        // a revert stops the whole execution, not just the called contract
+        panic::resume_unwind(Box::new(revert));
+    }
+    0
+}
+
+/// Read-only calls aren't enforced by the mock host, so this is the same as [`call_contract`].
+pub unsafe fn call_contract_read_only(
+    key_ptr: *const u8,
+    key_size: usize,
+    args_ptr: *const u8,
+    args_size: usize,
+    result_size: *mut usize,
+) -> i32 {
+    call_contract(key_ptr, key_size, args_ptr, args_size, result_size)
+}
+
+pub unsafe fn try_call_contract(
+    key_ptr: *const u8,
+    key_size: usize,
+    args_ptr: *const u8,
+    args_size: usize,
+    result_size: *mut usize,
+) -> i32 {
+    let state = with_mock_runtime(|runtime| {
+        (
+            runtime.named_keys.clone(),
+            runtime.values.clone(),
+            runtime.balances.clone(),
+        )
+    });
+    match call(key_ptr, key_size, args_ptr, args_size, result_size) {
+        Ok(()) => 0,
+        // the changes made by the reverted contract are discarded, and the caller carries on
+        Err(revert) => {
+            let (named_keys, values, balances) = state;
+            with_mock_runtime(|runtime| {
+                runtime.named_keys = named_keys;
+                runtime.values = values;
+                runtime.balances = balances;
+            });
+            i32_from(Err(revert.error))
+        }
+    }
+}
+
+pub unsafe fn get_key(
+    name_ptr: *const u8,
+    name_size: usize,
+    output_ptr: *mut u8,
+    output_size: usize,
+    bytes_written_ptr: *mut usize,
+) -> i32 {
+    let name = read_str(name_ptr, name_size);
+    let key = match with_mock_runtime(|runtime| runtime.named_keys.get(&name).cloned()) {
+        Some(key) => key,
+        None => return i32_from(Err(ApiError::MissingKey)),
+    };
+    let key_bytes = key.into_bytes().expect("mock host should serialize");
+    if key_bytes.len() > output_size {
+        return i32_from(Err(ApiError::BufferTooSmall));
+    }
+    write_bytes(output_ptr, &key_bytes);
+    *bytes_written_ptr = key_bytes.len();
+    0
+}
+
+pub unsafe fn has_key(name_ptr: *const u8, name_size: usize) -> i32 {
+    let name = read_str(name_ptr, name_size);
+    if with_mock_runtime(|runtime| runtime.named_keys.contains_key(&name)) {
+        0
+    } else {
+        1
+    }
+}
+
+pub unsafe fn put_key(name_ptr: *const u8, name_size: usize, key_ptr: *const u8, key_size: usize) {
+    let name = read_str(name_ptr, name_size);
+    let key: Key = read_from(key_ptr, key_size);
+    with_mock_runtime(|runtime| runtime.named_keys.insert(name, key));
+}
+
+pub unsafe fn put_key_if_absent(
+    name_ptr: *const u8,
+    name_size: usize,
+    key_ptr: *const u8,
+    key_size: usize,
+) -> i32 {
+    let name = read_str(name_ptr, name_size);
+    let key: Key = read_from(key_ptr, key_size);
+    let result = with_mock_runtime(|runtime| {
+        if runtime.named_keys.contains_key(&name) {
+            return Err(ApiError::NamedKeyAlreadyExists);
+        }
+        runtime.named_keys.insert(name, key);
+        Ok(())
+    });
+    i32_from(result)
+}
+
+pub unsafe fn remove_key(name_ptr: *const u8, name_size: usize) {
+    let name = read_str(name_ptr, name_size);
+    with_mock_runtime(|runtime| runtime.named_keys.remove(&name));
+}
+
+pub unsafe fn remove_key_if_present(name_ptr: *const u8, name_size: usize) -> i32 {
+    let name = read_str(name_ptr, name_size);
+    match with_mock_runtime(|runtime| runtime.named_keys.remove(&name)) {
+        Some(_) => 0,
+        None => i32_from(Err(ApiError::NamedKeyNotFound)),
+    }
+}
+
+pub unsafe fn revert(status: u32) -> ! {
+    panic::resume_unwind(Box::new(Revert {
+        error: ApiError::from(status),
+        data: Vec::new(),
+    }))
+}
+
+pub unsafe fn revert_with_data(status: u32, data_ptr: *const u8, data_size: usize) -> ! {
+    panic::resume_unwind(Box::new(Revert {
+        error: ApiError::from(status),
+        data: read_bytes(data_ptr, data_size),
+    }))
+}
+
+pub unsafe fn get_caller(output_size: *mut usize) -> i32 {
+    let caller = with_mock_runtime(|runtime| runtime.caller);
+    set_host_buffer(
+        caller.into_bytes().expect("mock host should serialize"),
+        output_size,
+    );
+    0
+}
+
+pub unsafe fn get_caller_key(output_size: *mut usize) -> i32 {
+    let caller = with_mock_runtime(|runtime| runtime.caller);
+    let caller_key = Key::Account(caller);
+    set_host_buffer(
+        caller_key.into_bytes().expect("mock host should serialize"),
+        output_size,
+    );
+    0
+}
+
+pub unsafe fn get_blocktime(dest_ptr: *const u8) {
+    let blocktime = with_mock_runtime(|runtime| runtime.blocktime);
+    write_to(dest_ptr as *mut u8, blocktime);
+}
+
+pub unsafe fn get_phase(dest_ptr: *mut u8) {
+    let phase = with_mock_runtime(|runtime| runtime.phase);
+    write_to(dest_ptr, phase);
+}
+
+pub unsafe fn get_main_purse(dest_ptr: *mut u8) {
+    let main_purse = with_mock_runtime(|runtime| runtime.main_purse);
+    write_to(dest_ptr, main_purse);
+}
+
+pub unsafe fn create_purse(purse_ptr: *const u8, _purse_size: usize) -> i32 {
+    let purse = with_mock_runtime(|runtime| runtime.create_purse(U512::zero()));
+    write_to(purse_ptr as *mut u8, purse);
+    0
+}
+
+pub unsafe fn get_balance(purse_ptr: *const u8, purse_size: usize, result_size: *mut usize) -> i32 {
+    let purse: URef = read_from(purse_ptr, purse_size);
+    match with_mock_runtime(|runtime| runtime.balance(purse)) {
+        Some(balance) => {
+            set_host_buffer(
+                balance.into_bytes().expect("mock host should serialize"),
+                result_size,
+            );
+            0
+        }
+        None => i32_from(Err(ApiError::InvalidPurse)),
+    }
+}
+
+pub unsafe fn get_balance_by_uref(
+    purse_ptr: *const u8,
+    purse_size: usize,
+    result_size: *mut usize,
+) -> i32 {
+    let purse: URef = read_from(purse_ptr, purse_size);
+    if !purse.is_readable() {
+        return i32_from(Err(mint::Error::InvalidAccessRights.into()));
+    }
+    get_balance(purse_ptr, purse_size, result_size)
+}
+
+pub unsafe fn purse_exists(purse_ptr: *const u8, purse_size: usize) -> i32 {
+    let purse: URef = read_from(purse_ptr, purse_size);
+    match with_mock_runtime(|runtime| runtime.balance(purse)) {
+        Some(_) => 0,
+        None => i32_from(Err(ApiError::InvalidPurse)),
+    }
+}
+
+pub unsafe fn transfer_from_purse_to_purse(
+    source_ptr: *const u8,
+    source_size: usize,
+    target_ptr: *const u8,
+    target_size: usize,
+    amount_ptr: *const u8,
+    amount_size: usize,
+) -> i32 {
+    let source: URef = read_from(source_ptr, source_size);
+    let target: URef = read_from(target_ptr, target_size);
+    let amount: U512 = read_from(amount_ptr, amount_size);
+    i32_from(with_mock_runtime(|runtime| {
+        runtime.transfer(source, target, amount)
+    }))
+}
+
+/// Transfers to the main purse of `target`, which is created along with the account if it's the
+/// first transfer to it.
+fn transfer_to_account_from(source: URef, target: PublicKey, amount: U512) -> i32 {
+    let result = with_mock_runtime(|runtime| {
+        let (target_purse, transferred_to) = match runtime.accounts.get(&target) {
+            Some(target_purse) => (*target_purse, TransferredTo::ExistingAccount),
+            None => (
+                runtime.create_purse(U512::zero()),
+                TransferredTo::NewAccount,
+            ),
+        };
+        runtime.transfer(source, target_purse, amount)?;
+        runtime.accounts.insert(target, target_purse);
+        Ok(transferred_to)
+    });
+    TransferredTo::i32_from(result)
+}
+
+pub unsafe fn transfer_to_account(
+    target_ptr: *const u8,
+    target_size: usize,
+    amount_ptr: *const u8,
+    amount_size: usize,
+) -> i32 {
+    let target: PublicKey = read_from(target_ptr, target_size);
+    let amount: U512 = read_from(amount_ptr, amount_size);
+    let main_purse = with_mock_runtime(|runtime| runtime.main_purse);
+    transfer_to_account_from(main_purse, target, amount)
+}
+
+pub unsafe fn transfer_from_purse_to_account(
+    source_ptr: *const u8,
+    source_size: usize,
+    target_ptr: *const u8,
+    target_size: usize,
+    amount_ptr: *const u8,
+    amount_size: usize,
+) -> i32 {
+    let source: URef = read_from(source_ptr, source_size);
+    let target: PublicKey = read_from(target_ptr, target_size);
+    let amount: U512 = read_from(amount_ptr, amount_size);
+    transfer_to_account_from(source, target, amount)
+}
+
+pub unsafe fn read_host_buffer(
+    dest_ptr: *mut u8,
+    dest_size: usize,
+    bytes_written: *mut usize,
+) -> i32 {
+    let host_buffer = match with_mock_runtime(|runtime| runtime.host_buffer.take()) {
+        Some(host_buffer) => host_buffer,
+        None => return i32_from(Err(ApiError::HostBufferEmpty)),
+    };
+    if host_buffer.len() > dest_size {
+        return i32_from(Err(ApiError::BufferTooSmall));
+    }
+    write_bytes(dest_ptr, &host_buffer);
+    *bytes_written = host_buffer.len();
+    0
+}
+
+#[cfg(feature = "test-support")]
+pub unsafe fn print(text_ptr: *const u8, text_size: usize) {
+    std::println!("{}", read_str(text_ptr, text_size));
+}
+
+/// Declares host functions which the mock host doesn't support, and which panic when called.
+macro_rules! unsupported {
+    ($(pub fn $name:ident($($arg:ident: $arg_type:ty),* $(,)?) $(-> $ret:ty)?;)*) => {
+        $(
+            pub unsafe fn $name($(_: $arg_type),*) $(-> $ret)? {
+                panic!(concat!("`", stringify!($name), "` is not supported by the mock host"))
+            }
+        )*
+    };
+}
+
+unsupported! {
+    pub fn read_value_local(key_ptr: *const u8, key_size: usize, output_size: *mut usize) -> i32;
+    pub fn write_local(
+        key_ptr: *const u8,
+        key_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn add(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+    pub fn add_local(key_ptr: *const u8, key_size: usize, value_ptr: *const u8, value_size: usize);
+    pub fn migrate_local_keys(old_seed_ptr: *const u8, migrated_count_ptr: *mut u32) -> i32;
+    pub fn get_gas_counter(dest_ptr: *mut u8);
+    pub fn store_function(
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        uref_addr_ptr: *const u8,
+    );
+    pub fn store_function_at_hash(
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        hash_ptr: *const u8,
+    );
+    pub fn store_function_with_spec(
+        function_name_ptr: *const u8,
+        function_name_size: usize,
+        named_keys_ptr: *const u8,
+        named_keys_size: usize,
+        spec_ptr: *const u8,
+        spec_size: usize,
+        hash_ptr: *const u8,
+    );
+    pub fn is_valid_uref(uref_ptr: *const u8, uref_size: usize) -> i32;
+    pub fn access_rights(uref_ptr: *const u8, uref_size: usize) -> i32;
+    pub fn put_transient(
+        name_ptr: *const u8,
+        name_size: usize,
+        value_ptr: *const u8,
+        value_size: usize,
+    );
+    pub fn get_transient(name_ptr: *const u8, name_size: usize, output_size: *mut usize) -> i32;
+    pub fn schedule_call(
+        key_ptr: *const u8,
+        key_size: usize,
+        args_ptr: *const u8,
+        args_size: usize,
+        not_before_ptr: *const u8,
+        not_before_size: usize,
+        schedule_id_ptr: *mut u8,
+    ) -> i32;
+    pub fn add_associated_key(
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        weight: i32,
+    ) -> i32;
+    pub fn remove_associated_key(public_key_ptr: *const u8, public_key_size: usize) -> i32;
+    pub fn update_associated_key(
+        public_key_ptr: *const u8,
+        public_key_size: usize,
+        weight: i32,
+    ) -> i32;
+    pub fn set_action_threshold(permission_level: u32, threshold: i32) -> i32;
+    pub fn upgrade_contract_at_uref(
+        name_ptr: *const u8,
+        name_size: usize,
+        key_ptr: *const u8,
+        key_size: usize,
+    ) -> i32;
+    pub fn get_system_contract(
+        system_contract_index: u32,
+        dest_ptr: *mut u8,
+        dest_size: usize,
+    ) -> i32;
+    pub fn rotate_main_purse(dest_ptr: *mut u8) -> i32;
+    pub fn set_authorization_contract(contract_ptr: *const u8, contract_size: usize) -> i32;
+}
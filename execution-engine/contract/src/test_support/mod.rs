@@ -0,0 +1,477 @@
+//! An in-process mock of the host, for unit testing contracts natively.
+//!
+//! With the `mock-host` feature enabled on a native target, the functions of [`contract_api`] call
+//! into a [`MockRuntime`] held by the current thread instead of the Wasm host functions, so that a
+//! contract's logic can be exercised by ordinary `#[test]`s without the engine.  The feature has no
+//! effect on Wasm targets.
+//!
+//! The mock holds the arguments of the current call, the named keys of the current context, the
+//! values stored under [`URef`]s and the balances of purses.  Calls to other contracts run the
+//! closures registered with [`MockRuntime::with_contract`], which share the caller's named keys and
+//! state.  Host functions beyond these, e.g. account management, panic when called.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use casperlabs_contract::{
+//!     assert_reverts_with,
+//!     contract_api::runtime,
+//!     test_support::{self, MockRuntime},
+//! };
+//!
+//! #[test]
+//! fn should_store_purse_under_name() {
+//!     MockRuntime::new().with_args(("add", "savings")).install();
+//!     test_support::execute(dispatch).expect("should add purse");
+//!     assert!(test_support::with_mock_runtime(|runtime| {
+//!         runtime.named_keys().contains_key("savings")
+//!     }));
+//!
+//!     assert_reverts_with!(Error::PurseNameAlreadyExists, dispatch);
+//! }
+//! ```
+//!
+//! [`contract_api`]: crate::contract_api
+
+pub mod ext_ffi;
+
+use alloc::{collections::BTreeMap, rc::Rc, string::String, vec::Vec};
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+};
+
+use casperlabs_types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    AccessRights, BlockTime, CLTyped, CLValue, ContractRef, Key, Phase, URef, U512,
+};
+
+use crate::args_parser::ArgsParser;
+
+#[doc(hidden)]
+pub use casperlabs_types::ApiError;
+
+thread_local! {
+    static MOCK_RUNTIME: RefCell<MockRuntime> = RefCell::new(MockRuntime::new());
+}
+
+/// The mock host state which the current thread's contract API calls run against.
+pub struct MockRuntime {
+    args: Vec<CLValue>,
+    arg_names: BTreeMap<String, usize>,
+    caller: PublicKey,
+    blocktime: BlockTime,
+    phase: Phase,
+    named_keys: BTreeMap<String, Key>,
+    values: BTreeMap<Key, CLValue>,
+    balances: BTreeMap<[u8; 32], U512>,
+    main_purse: URef,
+    accounts: BTreeMap<PublicKey, URef>,
+    contracts: BTreeMap<Key, Rc<dyn Fn()>>,
+    host_buffer: Option<Vec<u8>>,
+    next_address: u64,
+}
+
+impl MockRuntime {
+    /// Creates a runtime for a call without arguments, made in the session phase by an account
+    /// whose main purse is empty.
+    pub fn new() -> Self {
+        let mut runtime = MockRuntime {
+            args: Vec::new(),
+            arg_names: BTreeMap::new(),
+            caller: PublicKey::ed25519_from([0; 32]),
+            blocktime: BlockTime::from_millis(0),
+            phase: Phase::Session,
+            named_keys: BTreeMap::new(),
+            values: BTreeMap::new(),
+            balances: BTreeMap::new(),
+            main_purse: URef::new([0; 32], AccessRights::READ_ADD_WRITE),
+            accounts: BTreeMap::new(),
+            contracts: BTreeMap::new(),
+            host_buffer: None,
+            next_address: 0,
+        };
+        runtime.main_purse = runtime.create_purse(U512::zero());
+        runtime.accounts.insert(runtime.caller, runtime.main_purse);
+        runtime
+    }
+
+    /// Sets the arguments of the call.
+    pub fn with_args<A: ArgsParser>(mut self, args: A) -> Self {
+        self.set_args(args);
+        self
+    }
+
+    /// Names the arguments of the call by position, an empty name leaving an argument unnamed.
+    pub fn with_arg_names(mut self, names: &[&str]) -> Self {
+        self.arg_names = names
+            .iter()
+            .enumerate()
+            .filter(|(_, name)| !name.is_empty())
+            .map(|(index, name)| (name.to_string(), index))
+            .collect();
+        self
+    }
+
+    /// Sets the account making the call.
+    pub fn with_caller(mut self, caller: PublicKey) -> Self {
+        self.accounts.remove(&self.caller);
+        self.caller = caller;
+        self.accounts.insert(caller, self.main_purse);
+        self
+    }
+
+    /// Sets the block time of the call.
+    pub fn with_blocktime(mut self, blocktime: BlockTime) -> Self {
+        self.blocktime = blocktime;
+        self
+    }
+
+    /// Sets the phase of the call.
+    pub fn with_phase(mut self, phase: Phase) -> Self {
+        self.phase = phase;
+        self
+    }
+
+    /// Adds `key` under `name` to the named keys of the current context.
+    pub fn with_named_key(mut self, name: &str, key: Key) -> Self {
+        self.named_keys.insert(name.into(), key);
+        self
+    }
+
+    /// Sets the balance of the caller's main purse.
+    pub fn with_main_purse_balance(mut self, balance: U512) -> Self {
+        self.balances.insert(self.main_purse.addr(), balance);
+        self
+    }
+
+    /// Registers `entry_point` as the code run by [`call_contract`] on `contract`.  The contract
+    /// returns a value by calling [`ret`], and its arguments are read with [`get_arg`].
+    ///
+    /// [`call_contract`]: crate::contract_api::runtime::call_contract
+    /// [`ret`]: crate::contract_api::runtime::ret
+    /// [`get_arg`]: crate::contract_api::runtime::get_arg
+    pub fn with_contract<F: Fn() + 'static>(
+        mut self,
+        contract: ContractRef,
+        entry_point: F,
+    ) -> Self {
+        let key = Key::from(contract).normalize();
+        self.contracts.insert(key, Rc::new(entry_point));
+        self
+    }
+
+    /// Makes this the runtime of the current thread, replacing the previous one.
+    pub fn install(self) {
+        MOCK_RUNTIME.with(|runtime| *runtime.borrow_mut() = self);
+    }
+
+    /// Replaces the arguments of the call.
+    pub fn set_args<A: ArgsParser>(&mut self, args: A) {
+        self.args = args.parse().expect("should parse arguments");
+    }
+
+    /// Creates a purse holding `balance`.
+    pub fn create_purse(&mut self, balance: U512) -> URef {
+        let purse = URef::new(self.next_address(), AccessRights::READ_ADD_WRITE);
+        self.balances.insert(purse.addr(), balance);
+        purse
+    }
+
+    /// Returns the named keys of the current context.
+    pub fn named_keys(&self) -> &BTreeMap<String, Key> {
+        &self.named_keys
+    }
+
+    /// Returns the caller's main purse.
+    pub fn main_purse(&self) -> URef {
+        self.main_purse
+    }
+
+    /// Returns the balance of `purse`, or `None` if it isn't a purse.
+    pub fn balance(&self, purse: URef) -> Option<U512> {
+        self.balances.get(&purse.addr()).cloned()
+    }
+
+    /// Returns the main purse of `account`, or `None` if nothing has been transferred to it.
+    pub fn account_purse(&self, account: PublicKey) -> Option<URef> {
+        self.accounts.get(&account).cloned()
+    }
+
+    /// Returns the value stored under `uref`, or `None` if there is none.  Panics if the value
+    /// isn't a `T`.
+    pub fn read<T: CLTyped + FromBytes>(&self, uref: URef) -> Option<T> {
+        self.values
+            .get(&Key::from(uref).normalize())
+            .map(|cl_value| {
+                cl_value
+                    .clone()
+                    .into_t()
+                    .expect("should have value of type")
+            })
+    }
+
+    /// Stores `value` under a new [`URef`].
+    pub fn new_uref<T: CLTyped + ToBytes>(&mut self, value: T) -> URef {
+        let cl_value = CLValue::from_t(value).expect("should create value");
+        self.store_new(cl_value)
+    }
+
+    fn next_address(&mut self) -> [u8; 32] {
+        self.next_address += 1;
+        let mut address = [0; 32];
+        address[..8].copy_from_slice(&self.next_address.to_le_bytes());
+        address
+    }
+
+    fn store_new(&mut self, cl_value: CLValue) -> URef {
+        let uref = URef::new(self.next_address(), AccessRights::READ_ADD_WRITE);
+        self.values.insert(Key::from(uref).normalize(), cl_value);
+        uref
+    }
+
+    fn transfer(&mut self, source: URef, target: URef, amount: U512) -> Result<(), ApiError> {
+        let source_balance = self.balance(source).ok_or(ApiError::InvalidPurse)?;
+        let target_balance = self.balance(target).ok_or(ApiError::InvalidPurse)?;
+        if source_balance < amount {
+            return Err(ApiError::Transfer);
+        }
+        self.balances.insert(source.addr(), source_balance - amount);
+        let target_balance = if source.addr() == target.addr() {
+            source_balance
+        } else {
+            target_balance + amount
+        };
+        self.balances.insert(target.addr(), target_balance);
+        Ok(())
+    }
+}
+
+impl Default for MockRuntime {
+    fn default() -> Self {
+        MockRuntime::new()
+    }
+}
+
+/// Runs `f` against the current thread's runtime, e.g. to check the effects of a call.
+pub fn with_mock_runtime<T, F: FnOnce(&mut MockRuntime) -> T>(f: F) -> T {
+    MOCK_RUNTIME.with(|runtime| f(&mut runtime.borrow_mut()))
+}
+
+/// The error and data a contract reverted with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revert {
+    error: ApiError,
+    data: Vec<u8>,
+}
+
+impl Revert {
+    /// Returns the error the contract reverted with.
+    pub fn error(&self) -> ApiError {
+        self.error
+    }
+
+    /// Returns the data attached to the revert, which is empty unless it was made with
+    /// [`revert_with_data`](crate::contract_api::runtime::revert_with_data).
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// The payload unwound with by [`ret`](crate::contract_api::runtime::ret).
+struct Return(CLValue);
+
+/// Runs the contract code `entry_point` against the current thread's runtime.
+///
+/// Returns the value the code passed to [`ret`], if any, or the revert which stopped it.  Other
+/// panics are propagated.  The effects of reverted code are kept, so that they can be inspected.
+///
+/// [`ret`]: crate::contract_api::runtime::ret
+pub fn execute<F: FnOnce()>(entry_point: F) -> Result<Option<CLValue>, Revert> {
+    let payload = match panic::catch_unwind(AssertUnwindSafe(entry_point)) {
+        Ok(()) => return Ok(None),
+        Err(payload) => payload,
+    };
+    let payload = match payload.downcast::<Revert>() {
+        Ok(revert) => return Err(*revert),
+        Err(payload) => payload,
+    };
+    match payload.downcast::<Return>() {
+        Ok(returned) => Ok(Some(returned.0)),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Asserts that running the contract code `entry_point` reverts with `error`, which may be anything
+/// convertible into an [`ApiError`](casperlabs_types::ApiError).
+///
+/// See [`execute`].
+#[macro_export]
+macro_rules! assert_reverts_with {
+    ($error:expr, $entry_point:expr) => {{
+        let expected: $crate::test_support::ApiError = ::core::convert::Into::into($error);
+        match $crate::test_support::execute($entry_point) {
+            Err(revert) => assert_eq!(revert.error(), expected, "{:?}", revert),
+            Ok(returned) => panic!(
+                "expected revert with {:?}, but execution completed with {:?}",
+                expected, returned
+            ),
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use casperlabs_types::{system_contract_errors::mint, ApiError, TransferredTo};
+
+    use super::*;
+    use crate::{
+        contract_api::{account, runtime, storage, system},
+        unwrap_or_revert::UnwrapOrRevert,
+    };
+
+    const NAME: &str = "name";
+
+    #[test]
+    fn should_read_args_and_named_keys() {
+        let key = Key::Hash([1; 32]);
+        MockRuntime::new()
+            .with_args((NAME, 5u64))
+            .with_named_key(NAME, key)
+            .install();
+        execute(|| {
+            let name: String = runtime::get_arg(0).unwrap().unwrap();
+            assert_eq!(runtime::get_key(&name), Some(key));
+            let value: u64 = runtime::get_typed_arg(1).unwrap().unwrap();
+            assert_eq!(value, 5);
+            assert!(runtime::get_arg::<u64>(2).is_none());
+            runtime::remove_key(NAME).unwrap();
+            assert!(!runtime::has_key(NAME));
+        })
+        .unwrap();
+        assert!(with_mock_runtime(|runtime| runtime.named_keys().is_empty()));
+    }
+
+    #[test]
+    fn should_read_named_args() {
+        MockRuntime::new()
+            .with_args((NAME, 5u64))
+            .with_arg_names(&["", "value"])
+            .install();
+        execute(|| {
+            let value: u64 = runtime::get_named_arg("value").unwrap().unwrap();
+            assert_eq!(Some(value), runtime::get_arg(1).map(Result::unwrap));
+            assert!(runtime::get_named_arg::<String>(NAME).is_none());
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn should_store_values() {
+        MockRuntime::new().install();
+        let uref = storage::new_uref(String::from("first"));
+        storage::write(uref, String::from("second"));
+        assert_eq!(
+            storage::read::<String>(uref).unwrap(),
+            Some("second".into())
+        );
+        assert_eq!(
+            with_mock_runtime(|runtime| runtime.read::<String>(uref)),
+            Some("second".into())
+        );
+    }
+
+    #[test]
+    fn should_transfer_between_purses() {
+        let amount = U512::from(10);
+        let account = PublicKey::ed25519_from([1; 32]);
+        MockRuntime::new()
+            .with_main_purse_balance(amount * 3)
+            .install();
+        let main_purse = account::get_main_purse();
+        let purse = system::create_purse();
+        system::transfer_from_purse_to_purse(main_purse, purse, amount).unwrap();
+        assert_eq!(system::get_balance(purse), Some(amount));
+        assert_eq!(
+            system::transfer_to_account(account, amount),
+            Ok(TransferredTo::NewAccount)
+        );
+        assert_eq!(
+            system::transfer_to_account(account, amount),
+            Ok(TransferredTo::ExistingAccount)
+        );
+        assert_eq!(system::get_balance(main_purse), Some(U512::zero()));
+        assert_eq!(
+            system::transfer_from_purse_to_purse(main_purse, purse, amount),
+            Err(ApiError::Transfer)
+        );
+        let account_purse = with_mock_runtime(|runtime| runtime.account_purse(account)).unwrap();
+        assert_eq!(system::get_balance(account_purse), Some(amount * 2));
+    }
+
+    #[test]
+    fn should_report_balance_by_readable_uref() {
+        MockRuntime::new().install();
+        let purse = system::create_purse();
+        assert_eq!(
+            system::get_balance_by_uref(purse.into_read()),
+            Some(U512::zero())
+        );
+        let not_a_purse = URef::new([9; 32], AccessRights::READ);
+        assert_eq!(system::get_balance_by_uref(not_a_purse), None);
+
+        assert_reverts_with!(mint::Error::InvalidAccessRights, || {
+            system::get_balance_by_uref(purse.with_access_rights(AccessRights::ADD));
+        });
+    }
+
+    #[test]
+    fn should_call_registered_contract() {
+        let contract = ContractRef::Hash([2; 32]);
+        MockRuntime::new()
+            .with_args((1u64,))
+            .with_contract(contract.clone(), || {
+                let value: u64 = runtime::get_arg(0).unwrap().unwrap();
+                runtime::ret(CLValue::from_t(value * 2).unwrap())
+            })
+            .install();
+        let doubled: u64 = runtime::call_contract(contract, (21u64,));
+        assert_eq!(doubled, 42);
+        // the caller's arguments are restored after the call
+        assert_eq!(runtime::get_arg::<u64>(0).unwrap(), Ok(1));
+    }
+
+    #[test]
+    fn should_discard_changes_of_reverted_try_call() {
+        let contract = ContractRef::Hash([3; 32]);
+        MockRuntime::new()
+            .with_contract(contract.clone(), || {
+                runtime::put_key(NAME, Key::Hash([4; 32]));
+                runtime::revert(ApiError::User(7))
+            })
+            .install();
+        let result: Result<(), ApiError> = runtime::try_call_contract(contract, ());
+        assert_eq!(result, Err(ApiError::User(7)));
+        assert!(!runtime::has_key(NAME));
+    }
+
+    #[test]
+    fn should_report_revert_with_data() {
+        MockRuntime::new().install();
+        let revert = execute(|| runtime::revert_with_data(ApiError::User(3), b"data")).unwrap_err();
+        assert_eq!(revert.error(), ApiError::User(3));
+        assert_eq!(revert.data(), b"data");
+
+        assert_reverts_with!(ApiError::MissingArgument, || {
+            runtime::get_arg::<u64>(0).unwrap_or_revert_with(ApiError::MissingArgument);
+        });
+    }
+
+    #[test]
+    fn should_report_returned_value() {
+        MockRuntime::new().install();
+        let returned = execute(|| runtime::ret(CLValue::from_t(7u8).unwrap())).unwrap();
+        assert_eq!(returned, Some(CLValue::from_t(7u8).unwrap()));
+    }
+}
@@ -1,4 +1,5 @@
-//! Home of [`UnwrapOrRevert`], a convenience trait for unwrapping values.
+//! Home of [`UnwrapOrRevert`] and [`OkOrRevert`], convenience traits for unwrapping values and
+//! checking conditions, and of the [`revert_on_error`], [`require`] and [`require_eq`] macros.
 
 use casperlabs_types::ApiError;
 
@@ -35,3 +36,131 @@ impl<T> UnwrapOrRevert<T> for Option<T> {
         self.unwrap_or_else(|| runtime::revert(error.into()))
     }
 }
+
+/// A trait which provides syntactic sugar for calling [`runtime::revert`] if a condition doesn't
+/// hold.  It is implemented for `bool`.
+pub trait OkOrRevert {
+    /// Does nothing if the condition holds, otherwise calls [`runtime::revert`] with the provided
+    /// `error`.
+    fn ok_or_revert<E: Into<ApiError>>(self, error: E);
+}
+
+impl OkOrRevert for bool {
+    fn ok_or_revert<E: Into<ApiError>>(self, error: E) {
+        if !self {
+            runtime::revert(error.into())
+        }
+    }
+}
+
+/// Unwraps the given `Result` or `Option` into its inner value, or calls [`runtime::revert`] with
+/// [`ApiError::User`] holding the given user error code on failure.
+///
+/// The code can be given as a `u16` or as a variant of a fieldless enum which is castable to
+/// `u16`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use casperlabs_contract::{contract_api::runtime, revert_on_error};
+/// use casperlabs_types::U512;
+///
+/// #[repr(u16)]
+/// enum Error {
+///     MissingAmount = 0,
+///     InvalidAmount = 1,
+/// }
+///
+/// fn get_amount() -> U512 {
+///     let maybe_amount = runtime::get_arg::<U512>(0);
+///     revert_on_error!(
+///         revert_on_error!(maybe_amount, Error::MissingAmount),
+///         Error::InvalidAmount
+///     )
+/// }
+/// # fn main() {
+/// #     let _ = get_amount;
+/// # }
+/// ```
+#[macro_export]
+macro_rules! revert_on_error {
+    ($value:expr, $user_code:expr) => {
+        $crate::unwrap_or_revert::UnwrapOrRevert::unwrap_or_revert_with(
+            $value,
+            $crate::unwrap_or_revert::user_error($user_code as u16),
+        )
+    };
+}
+
+/// Does nothing if the given condition holds, otherwise calls [`runtime::revert_with_data`] with
+/// [`ApiError::User`] holding the given user error code.
+///
+/// The code can be given as a `u16` or as a variant of a fieldless enum which is castable to
+/// `u16`.  The revert data names the failed condition and where it was checked, e.g.
+/// `src/main.rs:12: amount > U512::zero()`.  It's built at compile time, so a check which passes
+/// costs no more than evaluating the condition.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use casperlabs_contract::require;
+/// use casperlabs_types::U512;
+///
+/// #[repr(u16)]
+/// enum Error {
+///     ZeroAmount = 0,
+/// }
+///
+/// fn check_amount(amount: U512) {
+///     require!(amount > U512::zero(), Error::ZeroAmount);
+/// }
+/// # fn main() {
+/// #     let _ = check_amount;
+/// # }
+/// ```
+#[macro_export]
+macro_rules! require {
+    ($condition:expr, $user_code:expr) => {
+        if !$condition {
+            $crate::contract_api::runtime::revert_with_data(
+                $crate::unwrap_or_revert::user_error($user_code as u16),
+                concat!(file!(), ":", line!(), ": ", stringify!($condition)).as_bytes(),
+            )
+        }
+    };
+}
+
+/// Does nothing if the given values are equal, otherwise calls [`runtime::revert_with_data`] with
+/// [`ApiError::User`] holding the given user error code.
+///
+/// Like [`require`], except that the revert data names the compared expressions, e.g.
+/// `src/main.rs:12: version == VERSION`.  Each expression is evaluated once.
+#[macro_export]
+macro_rules! require_eq {
+    ($left:expr, $right:expr, $user_code:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if *left != *right {
+                    $crate::contract_api::runtime::revert_with_data(
+                        $crate::unwrap_or_revert::user_error($user_code as u16),
+                        concat!(
+                            file!(),
+                            ":",
+                            line!(),
+                            ": ",
+                            stringify!($left),
+                            " == ",
+                            stringify!($right)
+                        )
+                        .as_bytes(),
+                    )
+                }
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+pub fn user_error(code: u16) -> ApiError {
+    ApiError::User(code)
+}
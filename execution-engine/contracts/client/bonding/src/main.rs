@@ -5,9 +5,7 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, U512};
-
-const BOND_METHOD_NAME: &str = "bond";
+use types::{system_contract_names::pos::METHOD_BOND, ApiError, U512};
 
 // Bonding contract.
 //
@@ -25,5 +23,5 @@ pub extern "C" fn call() {
 
     system::transfer_from_purse_to_purse(source_purse, bonding_purse, bond_amount)
         .unwrap_or_revert();
-    runtime::call_contract(pos_pointer, (BOND_METHOD_NAME, bond_amount, bonding_purse))
+    runtime::call_contract(pos_pointer, (METHOD_BOND, bond_amount, bonding_purse))
 }
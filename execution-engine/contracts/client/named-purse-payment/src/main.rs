@@ -9,10 +9,10 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, URef, U512};
-
-const GET_PAYMENT_PURSE: &str = "get_payment_purse";
-const SET_REFUND_PURSE: &str = "set_refund_purse";
+use types::{
+    system_contract_names::pos::{METHOD_GET_PAYMENT_PURSE, METHOD_SET_REFUND_PURSE},
+    ApiError, URef, U512,
+};
 
 enum Arg {
     PurseName = 0,
@@ -36,9 +36,10 @@ pub extern "C" fn call() {
 
     let pos_pointer = system::get_proof_of_stake();
 
-    let payment_purse: URef = runtime::call_contract(pos_pointer.clone(), (GET_PAYMENT_PURSE,));
+    let payment_purse: URef =
+        runtime::call_contract(pos_pointer.clone(), (METHOD_GET_PAYMENT_PURSE,));
 
-    runtime::call_contract::<_, ()>(pos_pointer, (SET_REFUND_PURSE, purse));
+    runtime::call_contract::<_, ()>(pos_pointer, (METHOD_SET_REFUND_PURSE, purse));
 
     system::transfer_from_purse_to_purse(purse, payment_purse, amount).unwrap_or_revert();
 }
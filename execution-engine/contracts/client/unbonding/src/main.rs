@@ -5,9 +5,7 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, U512};
-
-const UNBOND_METHOD_NAME: &str = "unbond";
+use types::{system_contract_names::pos::METHOD_UNBOND, ApiError, U512};
 
 // Unbonding contract.
 //
@@ -23,5 +21,5 @@ pub extern "C" fn call() {
         .unwrap_or_revert_with(ApiError::InvalidArgument);
     let unbond_amount: Option<U512> = arg_0.map(Into::into);
 
-    runtime::call_contract(pos_pointer, (UNBOND_METHOD_NAME, unbond_amount))
+    runtime::call_contract(pos_pointer, (METHOD_UNBOND, unbond_amount))
 }
@@ -111,5 +111,5 @@ pub extern "C" fn call() {
     mailing_list_urefs.insert(key_name, list_key.into());
 
     let pointer = storage::store_function_at_hash(MAILING_LIST_EXT, mailing_list_urefs);
-    runtime::put_key(MAILING_KEY, pointer.into())
+    runtime::put_key(MAILING_KEY, pointer.into());
 }
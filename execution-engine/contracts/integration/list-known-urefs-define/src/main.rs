@@ -46,5 +46,5 @@ pub extern "C" fn call() {
     // We don't have to  pass `expected_urefs` to exercise this function but
     // it adds initial known urefs to the state of the contract.
     let pointer = storage::store_function_at_hash(LIST_NAMED_KEYS_EXT, expected_urefs);
-    runtime::put_key(LIST_NAMED_KEYS_KEY, pointer.into())
+    runtime::put_key(LIST_NAMED_KEYS_KEY, pointer.into());
 }
@@ -101,7 +101,7 @@ fn large_function() {
         runtime::revert(Error::GetKey);
     }
 
-    runtime::remove_key(&key_name);
+    runtime::remove_key(&key_name).unwrap_or_revert();
 
     let named_keys = runtime::list_named_keys();
     if named_keys.len() != NAMED_KEY_COUNT - 1 {
@@ -235,7 +235,7 @@ pub extern "C" fn call() {
         runtime::revert(Error::IsValidURef);
     }
 
-    if runtime::get_blocktime() != BlockTime::new(0) {
+    if runtime::get_blocktime() != BlockTime::from_millis(0) {
         runtime::revert(Error::GetBlockTime);
     }
 
@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey,
+    system_contract_errors::mint,
+    system_contract_names::{
+        auction::{BIDS_PURSE_KEY, VALIDATOR_SLOTS_KEY, WINNERS_KEY},
+        mint::METHOD_MINT,
+    },
+    AccessRights, ApiError, CLValue, ContractRef, Key, URef, U512,
+};
+
+const AUCTION_FUNCTION_NAME: &str = "auction_ext";
+
+#[repr(u32)]
+enum Args {
+    MintURef = 0,
+    ValidatorSlots = 1,
+}
+
+#[no_mangle]
+pub extern "C" fn auction_ext() {
+    auction::delegate();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mint_uref: URef = runtime::get_arg(Args::MintURef as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let mint = ContractRef::URef(URef::new(mint_uref.addr(), AccessRights::READ));
+
+    let validator_slots: u32 = runtime::get_arg(Args::ValidatorSlots as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let result: Result<URef, mint::Error> =
+        runtime::call_contract(mint, (METHOD_MINT, U512::zero()));
+    let bids_purse = result.unwrap_or_revert();
+    let winners: BTreeMap<PublicKey, U512> = BTreeMap::new();
+
+    let mut named_keys: BTreeMap<String, Key> = BTreeMap::new();
+    named_keys.insert(String::from(BIDS_PURSE_KEY), Key::URef(bids_purse));
+    named_keys.insert(
+        String::from(VALIDATOR_SLOTS_KEY),
+        storage::new_uref(validator_slots).into(),
+    );
+    named_keys.insert(String::from(WINNERS_KEY), storage::new_uref(winners).into());
+
+    let uref: URef = storage::store_function(AUCTION_FUNCTION_NAME, named_keys)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+    let return_value = CLValue::from_t(uref).unwrap_or_revert();
+
+    runtime::ret(return_value);
+}
@@ -0,0 +1,201 @@
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey,
+    system_contract_errors::auction::{Error, Result},
+    system_contract_names::{
+        auction::{
+            ADD_BID_ARG_AMOUNT_INDEX, ADD_BID_ARG_DELEGATION_RATE_INDEX, ADD_BID_ARG_PURSE_INDEX,
+            BIDS_PURSE_KEY, BID_ARG_VALIDATOR_INDEX, MAX_DELEGATION_RATE, METHOD_ADD_BID,
+            METHOD_RUN_AUCTION, METHOD_WITHDRAW_BID, RUN_AUCTION_ARG_ERA_END_TIMESTAMP_INDEX,
+            VALIDATOR_SLOTS_KEY, WINNERS_KEY,
+        },
+        METHOD_ARG_INDEX,
+    },
+    ApiError, URef, U512,
+};
+
+/// Local key of the list of validators holding a bid.
+const BIDDERS_KEY: u8 = 1;
+/// Local key of the end of the era the latest auction was run for.
+const LAST_ERA_END_KEY: u8 = 2;
+
+const SYSTEM_ACCOUNT: PublicKey = PublicKey::ed25519_from([0; 32]);
+
+/// A validator's bid: the motes held in the bids purse and the delegation rate.
+type Bid = (U512, u8);
+
+fn get_uref(name: &str) -> Result<URef> {
+    runtime::get_key(name)
+        .ok_or(Error::NamedKeyNotFound)?
+        .into_uref()
+        .ok_or(Error::NamedKeyUnexpectedType)
+}
+
+fn read_bidders() -> Result<Vec<PublicKey>> {
+    storage::read_local(&BIDDERS_KEY)
+        .map_err(|_| Error::InvalidBids)
+        .map(Option::unwrap_or_default)
+}
+
+/// Reads the bid of `validator`.  A withdrawn bid is kept with a zero amount, as local state can't
+/// be removed.
+fn read_bid(validator: PublicKey) -> Result<Option<Bid>> {
+    let bid: Option<Bid> = storage::read_local(&validator).map_err(|_| Error::InvalidBids)?;
+    Ok(bid.filter(|(bid_amount, _)| !bid_amount.is_zero()))
+}
+
+fn read_winners() -> Result<BTreeMap<PublicKey, U512>> {
+    storage::read(get_uref(WINNERS_KEY)?)
+        .map_err(|_| Error::NamedKeyUnexpectedType)?
+        .ok_or(Error::NamedKeyUnexpectedType)
+}
+
+/// Moves `amount` motes from `source` into the bids purse and adds them to the bid of `validator`,
+/// whose delegation rate is replaced by `delegation_rate`.
+pub fn add_bid(
+    validator: PublicKey,
+    amount: U512,
+    delegation_rate: u8,
+    source: URef,
+) -> Result<()> {
+    if validator != runtime::get_caller() {
+        return Err(Error::CallerNotValidator);
+    }
+    if amount.is_zero() {
+        return Err(Error::ZeroBid);
+    }
+    if delegation_rate > MAX_DELEGATION_RATE {
+        return Err(Error::DelegationRateTooHigh);
+    }
+
+    let bid_amount = match read_bid(validator)? {
+        Some((bid_amount, _)) => bid_amount
+            .checked_add(amount)
+            .ok_or(Error::ArithmeticOverflow)?,
+        None => {
+            let mut bidders = read_bidders()?;
+            bidders.push(validator);
+            storage::write_local(BIDDERS_KEY, bidders);
+            amount
+        }
+    };
+
+    let bids_purse = get_uref(BIDS_PURSE_KEY)?;
+    system::transfer_from_purse_to_purse(source, bids_purse, amount)
+        .map_err(|_| Error::BidTransferFailed)?;
+    storage::write_local(validator, (bid_amount, delegation_rate));
+    Ok(())
+}
+
+/// Pays the whole bid of `validator` back to their main purse.
+///
+/// A validator selected by the latest auction can't withdraw until the next auction is run.
+pub fn withdraw_bid(validator: PublicKey) -> Result<()> {
+    if validator != runtime::get_caller() {
+        return Err(Error::CallerNotValidator);
+    }
+    let (bid_amount, _) = read_bid(validator)?.ok_or(Error::BidNotFound)?;
+    if read_winners()?.contains_key(&validator) {
+        return Err(Error::BidLocked);
+    }
+
+    let bids_purse = get_uref(BIDS_PURSE_KEY)?;
+    system::transfer_from_purse_to_account(bids_purse, validator, bid_amount)
+        .map_err(|_| Error::WithdrawTransferFailed)?;
+
+    let bidders: Vec<PublicKey> = read_bidders()?
+        .into_iter()
+        .filter(|bidder| *bidder != validator)
+        .collect();
+    storage::write_local(BIDDERS_KEY, bidders);
+    storage::write_local(validator, (U512::zero(), 0u8));
+    Ok(())
+}
+
+/// Selects the validators of the era ending at `era_end_timestamp`: the bidders with the highest
+/// bids, as many as there are validator slots.  Equal bids are ordered by the bytes of the
+/// validators' public keys, so the selection doesn't depend on the order in which bids were
+/// placed.
+pub fn run_auction(era_end_timestamp: u64) -> Result<()> {
+    if runtime::get_caller() != SYSTEM_ACCOUNT {
+        return Err(Error::SystemFunctionCalledByUserAccount);
+    }
+    let last_era_end: Option<u64> =
+        storage::read_local(&LAST_ERA_END_KEY).map_err(|_| Error::InvalidBids)?;
+    if let Some(last_era_end) = last_era_end {
+        if era_end_timestamp <= last_era_end {
+            return Err(Error::EraEndNotIncreasing);
+        }
+    }
+
+    let validator_slots: u32 = storage::read(get_uref(VALIDATOR_SLOTS_KEY)?)
+        .map_err(|_| Error::NamedKeyUnexpectedType)?
+        .ok_or(Error::NamedKeyUnexpectedType)?;
+
+    let mut bids = Vec::new();
+    for validator in read_bidders()? {
+        let (bid_amount, _) = read_bid(validator)?.ok_or(Error::InvalidBids)?;
+        bids.push((validator, bid_amount));
+    }
+    bids.sort_by(|(validator_1, amount_1), (validator_2, amount_2)| {
+        amount_2
+            .cmp(amount_1)
+            .then_with(|| validator_1.value().cmp(&validator_2.value()))
+    });
+    let winners: BTreeMap<PublicKey, U512> =
+        bids.into_iter().take(validator_slots as usize).collect();
+
+    storage::write(get_uref(WINNERS_KEY)?, winners);
+    storage::write_local(LAST_ERA_END_KEY, era_end_timestamp);
+    Ok(())
+}
+
+pub fn delegate() {
+    let method_name: String = runtime::get_arg(METHOD_ARG_INDEX)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method_name.as_str() {
+        // Type of this method: `fn add_bid(validator: PublicKey, amount: U512, delegation_rate: u8,
+        // purse: URef)`
+        METHOD_ADD_BID => {
+            let validator: PublicKey = runtime::get_arg(BID_ARG_VALIDATOR_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(ADD_BID_ARG_AMOUNT_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let delegation_rate: u8 = runtime::get_arg(ADD_BID_ARG_DELEGATION_RATE_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let source_purse: URef = runtime::get_arg(ADD_BID_ARG_PURSE_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            add_bid(validator, amount, delegation_rate, source_purse).unwrap_or_revert();
+        }
+        // Type of this method: `fn withdraw_bid(validator: PublicKey)`
+        METHOD_WITHDRAW_BID => {
+            let validator: PublicKey = runtime::get_arg(BID_ARG_VALIDATOR_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            withdraw_bid(validator).unwrap_or_revert();
+        }
+        // Type of this method: `fn run_auction(era_end_timestamp: u64)`
+        METHOD_RUN_AUCTION => {
+            let era_end_timestamp: u64 = runtime::get_arg(RUN_AUCTION_ARG_ERA_END_TIMESTAMP_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            run_auction(era_end_timestamp).unwrap_or_revert();
+        }
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
@@ -13,14 +13,16 @@ use types::{
     account::PublicKey,
     bytesrepr::{FromBytes, ToBytes},
     system_contract_errors::mint::Error,
+    system_contract_names::{
+        mint::{
+            METHOD_ALLOWANCE, METHOD_APPROVE, METHOD_BALANCE, METHOD_CREATE, METHOD_MINT,
+            METHOD_REVOKE, METHOD_TRANSFER, METHOD_TRANSFER_FROM,
+        },
+        METHOD_ARG_INDEX,
+    },
     ApiError, CLTyped, CLValue, Key, URef, U512,
 };
 
-const METHOD_MINT: &str = "mint";
-const METHOD_CREATE: &str = "create";
-const METHOD_BALANCE: &str = "balance";
-const METHOD_TRANSFER: &str = "transfer";
-
 pub struct MintContract;
 
 impl RuntimeProvider for MintContract {
@@ -28,8 +30,12 @@ impl RuntimeProvider for MintContract {
         runtime::get_caller()
     }
 
+    fn get_caller_key(&self) -> Key {
+        runtime::get_caller_key()
+    }
+
     fn put_key(&mut self, name: &str, key: Key) {
-        runtime::put_key(name, key)
+        runtime::put_key(name, key);
     }
 }
 
@@ -69,7 +75,7 @@ impl Mint for MintContract {}
 pub fn delegate() {
     let mut mint_contract = MintContract;
 
-    let method_name: String = runtime::get_arg(0)
+    let method_name: String = runtime::get_arg(METHOD_ARG_INDEX)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
 
@@ -113,6 +119,58 @@ pub fn delegate() {
             let ret = CLValue::from_t(result).unwrap_or_revert();
             runtime::ret(ret);
         }
+        // Type: `fn revoke(purse: URef) -> Result<(), Error>`
+        METHOD_REVOKE => {
+            let purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<(), Error> = mint_contract.revoke(purse);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
+        // Type: `fn approve(purse: URef, spender: Key, amount: U512) -> Result<(), Error>`
+        METHOD_APPROVE => {
+            let purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let spender: Key = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<(), Error> = mint_contract.approve(purse, spender, amount);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
+        // Type: `fn allowance(purse: URef, spender: Key) -> U512`
+        METHOD_ALLOWANCE => {
+            let purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let spender: Key = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let allowance: U512 = mint_contract.allowance(purse, spender).unwrap_or_revert();
+            let ret = CLValue::from_t(allowance).unwrap_or_revert();
+            runtime::ret(ret);
+        }
+        // Type: `fn transfer_from(owner: URef, dest: URef, amount: U512) -> Result<(), Error>`
+        METHOD_TRANSFER_FROM => {
+            let owner_purse: URef = runtime::get_arg(1)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let target: URef = runtime::get_arg(2)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let amount: U512 = runtime::get_arg(3)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let result: Result<(), Error> =
+                mint_contract.transfer_from(owner_purse, target, amount);
+            let ret = CLValue::from_t(result).unwrap_or_revert();
+            runtime::ret(ret);
+        }
 
         _ => panic!("Unknown method name!"),
     }
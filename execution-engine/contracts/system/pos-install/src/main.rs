@@ -11,14 +11,15 @@ use contract::{
 };
 use proof_of_stake::Stakes;
 use types::{
-    account::PublicKey, system_contract_errors::mint, AccessRights, ApiError, CLValue, ContractRef,
-    Key, URef, U512,
+    account::PublicKey,
+    system_contract_errors::mint,
+    system_contract_names::{
+        mint::METHOD_MINT,
+        pos::{BONDING_PURSE_KEY, PAYMENT_PURSE_KEY, REWARDS_PURSE_KEY},
+    },
+    AccessRights, ApiError, CLValue, ContractRef, Key, URef, U512,
 };
 
-const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
-const POS_BONDING_PURSE: &str = "pos_bonding_purse";
-const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
-const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
 const POS_FUNCTION_NAME: &str = "pos_ext";
 
 #[repr(u32)]
@@ -48,12 +49,17 @@ pub extern "C" fn call() {
 
     // Add genesis validators to PoS contract object.
     // For now, we are storing validators in `named_keys` map of the PoS contract
-    // in the form: key: "v_{validator_pk}_{validator_stake}", value: doesn't
-    // matter.
-    let mut named_keys: BTreeMap<String, Key> =
-        stakes.strings().map(|key| (key, PLACEHOLDER_KEY)).collect();
-
-    let total_bonds: U512 = stakes.total_bonds();
+    // in the form: key: "v_{validator_pk}", value: a uref holding the validator's
+    // stake.
+    let mut named_keys: BTreeMap<String, Key> = stakes
+        .iter()
+        .map(|(validator, stake)| {
+            let name = proof_of_stake::stake_key_name(validator);
+            (name, storage::new_uref(*stake).into())
+        })
+        .collect();
+
+    let total_bonds: U512 = stakes.total_bonds().unwrap_or_revert();
 
     let bonding_purse = mint_purse(&mint, total_bonds);
     let payment_purse = mint_purse(&mint, U512::zero());
@@ -61,9 +67,9 @@ pub extern "C" fn call() {
 
     // Include PoS purses in its named_keys
     [
-        (POS_BONDING_PURSE, bonding_purse),
-        (POS_PAYMENT_PURSE, payment_purse),
-        (POS_REWARDS_PURSE, rewards_purse),
+        (BONDING_PURSE_KEY, bonding_purse),
+        (PAYMENT_PURSE_KEY, payment_purse),
+        (REWARDS_PURSE_KEY, rewards_purse),
     ]
     .iter()
     .for_each(|(name, uref)| {
@@ -79,7 +85,8 @@ pub extern "C" fn call() {
 }
 
 fn mint_purse(mint: &ContractRef, amount: U512) -> URef {
-    let result: Result<URef, mint::Error> = runtime::call_contract(mint.clone(), ("mint", amount));
+    let result: Result<URef, mint::Error> =
+        runtime::call_contract(mint.clone(), (METHOD_MINT, amount));
 
     result.unwrap_or_revert()
 }
@@ -0,0 +1,29 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{system_contract_names::pos::METHOD_MIGRATE_RESERVED_KEYS, ApiError};
+
+const POS_FUNCTION_NAME: &str = "pos_ext";
+
+#[no_mangle]
+pub extern "C" fn pos_ext() {
+    pos::delegate();
+}
+
+/// Upgrade installer which replaces the Proof of Stake contract with the version keeping its
+/// bookkeeping under reserved key names, and drops the entries left under the legacy names.
+#[no_mangle]
+pub extern "C" fn call() {
+    let pos_ref = system::get_proof_of_stake();
+    let pos_uref = pos_ref
+        .clone()
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    runtime::upgrade_contract_at_uref(POS_FUNCTION_NAME, pos_uref);
+    runtime::call_contract::<_, ()>(pos_ref, (METHOD_MIGRATE_RESERVED_KEYS,));
+}
@@ -0,0 +1,44 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{system_contract_names::pos::METHOD_MIGRATE_STAKES, ApiError};
+
+const POS_FUNCTION_NAME: &str = "pos_ext";
+
+#[repr(u32)]
+enum Args {
+    MaxCount = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn pos_ext() {
+    pos::delegate();
+}
+
+/// Upgrade installer which replaces the Proof of Stake contract with the version storing each stake
+/// under a uref, and moves the stakes which are still encoded in the names of its named keys.
+///
+/// If a maximum count is passed, at most that many stakes are moved, so that a large validator set
+/// can be migrated by running the installer in several upgrades.  Stakes in either format are read
+/// alike in the meantime.
+#[no_mangle]
+pub extern "C" fn call() {
+    let pos_ref = system::get_proof_of_stake();
+    let pos_uref = pos_ref
+        .clone()
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    runtime::upgrade_contract_at_uref(POS_FUNCTION_NAME, pos_uref);
+    let _remaining_count: u32 = match runtime::get_arg::<u32>(Args::MaxCount as u32) {
+        Some(max_count) => {
+            let max_count = max_count.unwrap_or_revert_with(ApiError::InvalidArgument);
+            runtime::call_contract(pos_ref, (METHOD_MIGRATE_STAKES, max_count))
+        }
+        None => runtime::call_contract(pos_ref, (METHOD_MIGRATE_STAKES,)),
+    };
+}
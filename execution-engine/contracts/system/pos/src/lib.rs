@@ -2,30 +2,33 @@
 
 extern crate alloc;
 
-use alloc::{
-    collections::{BTreeMap, BTreeSet},
-    string::String,
-};
+use alloc::{collections::BTreeMap, string::String};
 
 use contract::{
     contract_api::{runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, StakeKeyName, Stakes,
+    StakesProvider, STAKE_KEY_PREFIX,
 };
 use types::{
-    account::PublicKey, system_contract_errors::pos::Error, ApiError, BlockTime, CLValue, Key,
-    Phase, TransferResult, URef, U512,
+    account::PublicKey,
+    system_contract_errors::pos::Error,
+    system_contract_names::{
+        pos::{
+            BOND_ARG_AMOUNT_INDEX, BOND_ARG_PURSE_INDEX, FINALIZE_PAYMENT_ARG_ACCOUNT_INDEX,
+            FINALIZE_PAYMENT_ARG_AMOUNT_SPENT_INDEX, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
+            METHOD_GET_PAYMENT_PURSE, METHOD_GET_PAYMENT_PURSE_BALANCE, METHOD_GET_REFUND_PURSE,
+            METHOD_MIGRATE_RESERVED_KEYS, METHOD_MIGRATE_STAKES, METHOD_SET_REFUND_PURSE,
+            METHOD_SLASH, METHOD_UNBOND, MIGRATE_STAKES_ARG_MAX_COUNT_INDEX,
+            SET_REFUND_PURSE_ARG_PURSE_INDEX, SLASH_ARG_VALIDATOR_INDEX, UNBOND_ARG_AMOUNT_INDEX,
+        },
+        METHOD_ARG_INDEX,
+    },
+    AccessRights, ApiError, BlockTime, CLValue, Key, Phase, TransferResult, URef, U512,
 };
 
-const METHOD_BOND: &str = "bond";
-const METHOD_UNBOND: &str = "unbond";
-const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
-const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
-const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
-const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
-
 const BONDING_KEY: u8 = 1;
 const UNBONDING_KEY: u8 = 2;
 
@@ -87,11 +90,12 @@ impl RuntimeProvider for ProofOfStakeContract {
     }
 
     fn put_key(&mut self, name: &str, key: Key) {
-        runtime::put_key(name, key)
+        runtime::put_key(name, key);
     }
 
     fn remove_key(&mut self, name: &str) {
-        runtime::remove_key(name)
+        // proof of stake removes keys which may not be set, e.g. an unset refund purse
+        let _ = runtime::remove_key(name);
     }
 
     fn get_phase(&self) -> Phase {
@@ -105,33 +109,32 @@ impl RuntimeProvider for ProofOfStakeContract {
     fn get_caller(&self) -> PublicKey {
         runtime::get_caller()
     }
+
+    fn get_access_rights(&self, uref: URef) -> Option<AccessRights> {
+        runtime::access_rights(uref)
+    }
 }
 
 impl StakesProvider for ProofOfStakeContract {
     /// Reads the current stakes from the contract's known urefs.
-    fn read(&self) -> Result<Stakes, Error> {
+    fn read(&mut self) -> Result<Stakes, Error> {
         let mut stakes = BTreeMap::new();
-        for (name, _) in runtime::list_named_keys() {
-            let mut split_name = name.split('_');
-            if Some("v") != split_name.next() {
-                continue;
-            }
-            let hex_key = split_name
-                .next()
-                .ok_or(Error::StakesKeyDeserializationFailed)?;
-            if hex_key.len() != 64 {
-                return Err(Error::StakesKeyDeserializationFailed);
-            }
-            let mut key_bytes = [0u8; 32];
-            let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
-                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            debug_assert!(_bytes_written == key_bytes.len());
-            let pub_key = PublicKey::ed25519_from(key_bytes);
-            let balance = split_name
-                .next()
-                .and_then(|b| U512::from_dec_str(b).ok())
-                .ok_or(Error::StakesDeserializationFailed)?;
-            stakes.insert(pub_key, balance);
+        for (name, key) in runtime::list_named_keys() {
+            let (validator, balance) = match proof_of_stake::parse_stake_key_name(&name)? {
+                None => continue,
+                Some(StakeKeyName::Legacy(validator, balance)) => (validator, balance),
+                Some(StakeKeyName::Current(validator)) => {
+                    let uref = key
+                        .into_uref()
+                        .ok_or(Error::StakesKeyDeserializationFailed)?;
+                    let balance: U512 = storage::read(uref)
+                        .ok()
+                        .and_then(|maybe_balance| maybe_balance)
+                        .ok_or(Error::StakesDeserializationFailed)?;
+                    (validator, balance)
+                }
+            };
+            stakes.insert(validator, balance);
         }
         if stakes.is_empty() {
             return Err(Error::StakesNotFound);
@@ -141,18 +144,49 @@ impl StakesProvider for ProofOfStakeContract {
 
     /// Writes the current stakes to the contract's known urefs.
     fn write(&mut self, stakes: &Stakes) {
-        // Encode the stakes as a set of uref names.
-        let mut new_urefs: BTreeSet<String> = stakes.strings().collect();
-        // Remove and add urefs to update the contract's known urefs accordingly.
-        for (name, _) in runtime::list_named_keys() {
-            if name.starts_with("v_") && !new_urefs.remove(&name) {
-                runtime::remove_key(&name);
+        let mut new_stakes: BTreeMap<String, U512> = stakes
+            .iter()
+            .map(|(validator, balance)| (proof_of_stake::stake_key_name(validator), *balance))
+            .collect();
+        // Update the existing stakes in place, and remove the unbonded validators as well as the
+        // stakes stored in the legacy format.
+        for (name, key) in runtime::list_named_keys() {
+            if !name.starts_with(STAKE_KEY_PREFIX) {
+                continue;
+            }
+            match (new_stakes.remove(&name), key) {
+                (Some(balance), Key::URef(uref)) => storage::write(uref, balance),
+                (Some(balance), _) => {
+                    runtime::put_key(&name, storage::new_uref(balance).into());
+                }
+                (None, _) => runtime::remove_key(&name).unwrap_or_revert(),
             }
         }
-        for name in new_urefs {
-            runtime::put_key(&name, Key::Hash([0; 32]));
+        for (name, balance) in new_stakes {
+            runtime::put_key(&name, storage::new_uref(balance).into());
         }
     }
+
+    /// Moves stakes encoded in the names of the contract's named keys into new urefs.
+    fn migrate_legacy(&mut self, max_count: u32) -> Result<u32, Error> {
+        let mut migrated_count = 0;
+        let mut remaining_count = 0;
+        for (name, _) in runtime::list_named_keys() {
+            let (validator, balance) = match proof_of_stake::parse_stake_key_name(&name)? {
+                Some(StakeKeyName::Legacy(validator, balance)) => (validator, balance),
+                None | Some(StakeKeyName::Current(_)) => continue,
+            };
+            if migrated_count == max_count {
+                remaining_count += 1;
+                continue;
+            }
+            runtime::remove_key(&name).unwrap_or_revert();
+            let new_name = proof_of_stake::stake_key_name(&validator);
+            runtime::put_key(&new_name, storage::new_uref(balance).into());
+            migrated_count += 1;
+        }
+        Ok(remaining_count)
+    }
 }
 
 impl ProofOfStake for ProofOfStakeContract {}
@@ -160,7 +194,7 @@ impl ProofOfStake for ProofOfStakeContract {}
 pub fn delegate() {
     let mut pos_contract = ProofOfStakeContract;
 
-    let method_name: String = runtime::get_arg(0)
+    let method_name: String = runtime::get_arg(METHOD_ARG_INDEX)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
 
@@ -172,10 +206,10 @@ pub fn delegate() {
             }
 
             let validator = runtime::get_caller();
-            let amount: U512 = runtime::get_arg(1)
+            let amount: U512 = runtime::get_arg(BOND_ARG_AMOUNT_INDEX)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
-            let source_purse: URef = runtime::get_arg(2)
+            let source_purse: URef = runtime::get_arg(BOND_ARG_PURSE_INDEX)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
             pos_contract
@@ -189,22 +223,35 @@ pub fn delegate() {
             }
 
             let validator = runtime::get_caller();
-            let maybe_amount = runtime::get_arg(1)
+            let maybe_amount = runtime::get_arg(UNBOND_ARG_AMOUNT_INDEX)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
             pos_contract
                 .unbond(validator, maybe_amount)
                 .unwrap_or_revert();
         }
+        // Type of this method: `fn slash(validator: PublicKey)`
+        METHOD_SLASH => {
+            let validator: PublicKey = runtime::get_arg(SLASH_ARG_VALIDATOR_INDEX)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            pos_contract.slash(validator).unwrap_or_revert();
+        }
         // Type of this method: `fn get_payment_purse() -> URef`
         METHOD_GET_PAYMENT_PURSE => {
             let rights_controlled_purse = pos_contract.get_payment_purse().unwrap_or_revert();
             let return_value = CLValue::from_t(rights_controlled_purse).unwrap_or_revert();
             runtime::ret(return_value);
         }
+        // Type of this method: `fn get_payment_purse_balance() -> U512`
+        METHOD_GET_PAYMENT_PURSE_BALANCE => {
+            let balance = pos_contract.get_payment_purse_balance().unwrap_or_revert();
+            let return_value = CLValue::from_t(balance).unwrap_or_revert();
+            runtime::ret(return_value);
+        }
         // Type of this method: `fn set_refund_purse(purse: URef)`
         METHOD_SET_REFUND_PURSE => {
-            let refund_purse: URef = runtime::get_arg(1)
+            let refund_purse: URef = runtime::get_arg(SET_REFUND_PURSE_ARG_PURSE_INDEX)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
             pos_contract
@@ -222,16 +269,29 @@ pub fn delegate() {
         }
         // Type of this method: `fn finalize_payment()`
         METHOD_FINALIZE_PAYMENT => {
-            let amount_spent: U512 = runtime::get_arg(1)
-                .unwrap_or_revert_with(ApiError::MissingArgument)
-                .unwrap_or_revert_with(ApiError::InvalidArgument);
-            let account: PublicKey = runtime::get_arg(2)
+            let amount_spent: U512 =
+                runtime::get_typed_arg(FINALIZE_PAYMENT_ARG_AMOUNT_SPENT_INDEX)
+                    .unwrap_or_revert_with(ApiError::MissingArgument)
+                    .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let account: PublicKey = runtime::get_typed_arg(FINALIZE_PAYMENT_ARG_ACCOUNT_INDEX)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
             pos_contract
                 .finalize_payment(amount_spent, account)
                 .unwrap_or_revert();
         }
+        // Type of this method: `fn migrate_stakes(max_count: u32) -> u32`, where `max_count` may be
+        // omitted to migrate all stakes
+        METHOD_MIGRATE_STAKES => {
+            let max_count: u32 = runtime::get_arg(MIGRATE_STAKES_ARG_MAX_COUNT_INDEX)
+                .map(|arg| arg.unwrap_or_revert_with(ApiError::InvalidArgument))
+                .unwrap_or_else(u32::max_value);
+            let remaining_count = pos_contract.migrate_stakes(max_count).unwrap_or_revert();
+            let ret = CLValue::from_t(remaining_count).unwrap_or_revert();
+            runtime::ret(ret)
+        }
+        // Type of this method: `fn migrate_reserved_keys()`
+        METHOD_MIGRATE_RESERVED_KEYS => pos_contract.migrate_reserved_keys().unwrap_or_revert(),
         _ => {}
     }
 }
@@ -7,9 +7,10 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, URef, U512};
-
-const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+use types::{
+    system_contract_names::{pos::METHOD_GET_PAYMENT_PURSE, standard_payment::ARG_AMOUNT_INDEX},
+    ApiError, URef, U512,
+};
 
 struct StandardPaymentContract;
 
@@ -33,7 +34,7 @@ impl MintProvider for StandardPaymentContract {
 impl ProofOfStakeProvider for StandardPaymentContract {
     fn get_payment_purse(&mut self) -> Result<URef, ApiError> {
         let pos_pointer = system::get_proof_of_stake();
-        let payment_purse = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+        let payment_purse = runtime::call_contract(pos_pointer, (METHOD_GET_PAYMENT_PURSE,));
         Ok(payment_purse)
     }
 }
@@ -43,7 +44,7 @@ impl StandardPayment for StandardPaymentContract {}
 pub fn delegate() {
     let mut standard_payment_contract = StandardPaymentContract;
 
-    let amount: U512 = runtime::get_arg(0)
+    let amount: U512 = runtime::get_arg(ARG_AMOUNT_INDEX)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
 
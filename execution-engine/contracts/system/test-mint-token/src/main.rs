@@ -5,8 +5,14 @@ extern crate alloc;
 
 use alloc::string::String;
 
-use contract::contract_api::runtime;
-use types::{Key, U512};
+use contract::{
+    contract_api::runtime,
+    unwrap_or_revert::{OkOrRevert, UnwrapOrRevert},
+};
+use types::{
+    system_contract_names::mint::{METHOD_BALANCE, METHOD_CREATE, METHOD_TRANSFER},
+    ApiError, Key, U512,
+};
 
 #[no_mangle]
 pub extern "C" fn call() {
@@ -15,23 +21,25 @@ pub extern "C" fn call() {
         25, 5, 153, 113, 55, 255, 188, 176, 201, 7, 4, 42, 100,
     ])
     .to_contract_ref()
-    .unwrap();
+    .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
     //let x = contract_api::get_uref("mint");
 
     let amount1 = U512::from(100);
-    let purse1: Key = runtime::call_contract(mint.clone(), ("create", amount1));
+    let purse1: Key = runtime::call_contract(mint.clone(), (METHOD_CREATE, amount1));
 
     let amount2 = U512::from(300);
-    let purse2: Key = runtime::call_contract(mint.clone(), ("create", amount2));
+    let purse2: Key = runtime::call_contract(mint.clone(), (METHOD_CREATE, amount2));
 
-    let result: String =
-        runtime::call_contract(mint.clone(), ("transfer", purse1, purse2, U512::from(70)));
+    let result: String = runtime::call_contract(
+        mint.clone(),
+        (METHOD_TRANSFER, purse1, purse2, U512::from(70)),
+    );
 
-    assert!(&result == "Success!");
+    (result == "Success!").ok_or_revert(ApiError::Transfer);
 
-    let new_amount1: Option<U512> = runtime::call_contract(mint.clone(), ("balance", purse1));
-    let new_amount2: Option<U512> = runtime::call_contract(mint, ("balance", purse2));
+    let new_amount1: Option<U512> = runtime::call_contract(mint.clone(), (METHOD_BALANCE, purse1));
+    let new_amount2: Option<U512> = runtime::call_contract(mint, (METHOD_BALANCE, purse2));
 
-    assert!(new_amount1.unwrap() == U512::from(30));
-    assert!(new_amount2.unwrap() == U512::from(370));
+    (new_amount1.unwrap_or_revert() == U512::from(30)).ok_or_revert(ApiError::Transfer);
+    (new_amount2.unwrap_or_revert() == U512::from(370)).ok_or_revert(ApiError::Transfer);
 }
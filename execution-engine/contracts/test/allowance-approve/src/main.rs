@@ -0,0 +1,33 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const SPENDER_KEY_NAME: &str = "allowance_spender";
+
+#[repr(u16)]
+enum Args {
+    Amount = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingAmountArg = 0,
+    InvalidAmountArg = 1,
+    MissingSpenderKey = 2,
+}
+
+/// Approves the stored spender contract to transfer `amount` out of the account's main purse.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(Args::Amount as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingAmountArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidAmountArg as u16));
+    let spender = runtime::get_key(SPENDER_KEY_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingSpenderKey as u16));
+    system::approve_spend(spender, amount).unwrap_or_revert();
+}
@@ -0,0 +1,52 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef, U512};
+
+const SPENDER_KEY_NAME: &str = "allowance_spender";
+const TARGET_PURSE_NAME: &str = "allowance_target";
+
+#[repr(u16)]
+enum Args {
+    Amount = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingAmountArg = 0,
+    InvalidAmountArg = 1,
+    MissingSpenderKey = 2,
+    InvalidTargetPurse = 3,
+}
+
+/// Asks the stored spender contract to move `amount` out of the account's main purse into the
+/// account's target purse.  The spender only gets a read-only view of the main purse, so the
+/// transfer is authorized by the allowance alone.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(Args::Amount as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingAmountArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidAmountArg as u16));
+    let spender = runtime::get_key(SPENDER_KEY_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingSpenderKey as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    let target: URef = match runtime::get_key(TARGET_PURSE_NAME) {
+        Some(key) => key
+            .into_uref()
+            .unwrap_or_revert_with(ApiError::User(CustomError::InvalidTargetPurse as u16)),
+        None => {
+            let purse = system::create_purse();
+            runtime::put_key(TARGET_PURSE_NAME, purse.into());
+            purse
+        }
+    };
+
+    let owner_purse = account::get_main_purse().as_read_only();
+    runtime::call_contract::<_, ()>(spender, (owner_purse, target, amount));
+}
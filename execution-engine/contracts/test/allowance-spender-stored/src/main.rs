@@ -0,0 +1,55 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key, URef, U512};
+
+const SPEND_FUNCTION_NAME: &str = "spend";
+const SPENDER_KEY_NAME: &str = "allowance_spender";
+
+#[repr(u16)]
+enum Args {
+    OwnerPurse = 0,
+    Target = 1,
+    Amount = 2,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingOwnerPurseArg = 0,
+    InvalidOwnerPurseArg = 1,
+    MissingTargetArg = 2,
+    InvalidTargetArg = 3,
+    MissingAmountArg = 4,
+    InvalidAmountArg = 5,
+}
+
+/// Transfers `amount` out of the owner's purse into `target`, spending the allowance the owner
+/// approved for this contract.
+#[no_mangle]
+pub extern "C" fn spend() {
+    let owner_purse: URef = runtime::get_arg(Args::OwnerPurse as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingOwnerPurseArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidOwnerPurseArg as u16));
+    let target: URef = runtime::get_arg(Args::Target as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingTargetArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidTargetArg as u16));
+    let amount: U512 = runtime::get_arg(Args::Amount as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingAmountArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidAmountArg as u16));
+    system::transfer_from_allowance(owner_purse, target, amount).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let spender_key: Key =
+        storage::store_function_at_hash(SPEND_FUNCTION_NAME, BTreeMap::new()).into();
+    runtime::put_key(SPENDER_KEY_NAME, spender_key);
+}
@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    system_contract_names::auction::{METHOD_ADD_BID, METHOD_RUN_AUCTION, METHOD_WITHDRAW_BID},
+    ApiError, U512,
+};
+
+const ARG_METHOD_NAME: u32 = 0;
+const ADD_BID_ARG_AMOUNT: u32 = 1;
+const ADD_BID_ARG_DELEGATION_RATE: u32 = 2;
+const RUN_AUCTION_ARG_ERA_END: u32 = 1;
+
+#[repr(u16)]
+enum CustomError {
+    UnknownMethodName = 0,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let auction = system::get_auction();
+    let validator = runtime::get_caller();
+
+    let method_name: String = runtime::get_arg(ARG_METHOD_NAME)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    match method_name.as_str() {
+        METHOD_ADD_BID => {
+            let amount: U512 = runtime::get_arg(ADD_BID_ARG_AMOUNT)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            let delegation_rate: u8 = runtime::get_arg(ADD_BID_ARG_DELEGATION_RATE)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            runtime::call_contract::<_, ()>(
+                auction,
+                (
+                    METHOD_ADD_BID,
+                    validator,
+                    amount,
+                    delegation_rate,
+                    account::get_main_purse(),
+                ),
+            );
+        }
+        METHOD_WITHDRAW_BID => {
+            runtime::call_contract::<_, ()>(auction, (METHOD_WITHDRAW_BID, validator));
+        }
+        METHOD_RUN_AUCTION => {
+            let era_end: u64 = runtime::get_arg(RUN_AUCTION_ARG_ERA_END)
+                .unwrap_or_revert_with(ApiError::MissingArgument)
+                .unwrap_or_revert_with(ApiError::InvalidArgument);
+            runtime::call_contract::<_, ()>(auction, (METHOD_RUN_AUCTION, era_end));
+        }
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethodName as u16)),
+    }
+}
@@ -0,0 +1,115 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use contract::{
+    contract_api::{account, runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::{ActionType, PublicKey, Weight},
+    ApiError, BlockTime, Key, URef,
+};
+
+const METHOD_INSTALL: &str = "install";
+const METHOD_CLEAR: &str = "clear";
+const ENTRY_FUNCTION_NAME: &str = "authorize";
+const NOT_BEFORE_KEY: &str = "not_before";
+const ADMIN_WEIGHT: u8 = 2;
+const OPERATOR_WEIGHT: u8 = 1;
+
+#[repr(u16)]
+enum Error {
+    UnknownMethod = 1,
+    TooEarly = 2,
+    WrongAccount = 3,
+    MissingNotBefore = 4,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Method = 0,
+    Operator = 1,
+    NotBefore = 2,
+}
+
+enum AuthorizationArg {
+    DeployHash = 0,
+    Account = 1,
+    AuthorizationKeys = 2,
+}
+
+/// The authorization contract: rejects every deploy sent before the block time stored under
+/// `not_before`.
+#[no_mangle]
+pub extern "C" fn authorize() {
+    let _deploy_hash: [u8; 32] = runtime::get_arg(AuthorizationArg::DeployHash as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let account: PublicKey = runtime::get_arg(AuthorizationArg::Account as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let _authorization_keys: Vec<PublicKey> =
+        runtime::get_arg(AuthorizationArg::AuthorizationKeys as u32)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+    if account != runtime::get_caller() {
+        runtime::revert(Error::WrongAccount);
+    }
+
+    let not_before_uref: URef = runtime::get_key(NOT_BEFORE_KEY)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert_with(Error::MissingNotBefore);
+    let not_before: u64 = storage::read(not_before_uref)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(Error::MissingNotBefore);
+    if runtime::get_blocktime() < BlockTime::from_millis(not_before) {
+        runtime::revert(Error::TooEarly);
+    }
+}
+
+/// Makes the deploy's key an admin key able to manage keys on its own, adds `operator` as a key
+/// able to deploy but not to manage keys, and installs the authorization contract.
+fn install() {
+    let operator: PublicKey = runtime::get_arg(Arg::Operator as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let not_before: u64 = runtime::get_arg(Arg::NotBefore as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    account::update_associated_key(runtime::get_caller(), Weight::new(ADMIN_WEIGHT))
+        .unwrap_or_revert();
+    account::add_associated_key(operator, Weight::new(OPERATOR_WEIGHT)).unwrap_or_revert();
+    account::set_action_threshold(ActionType::KeyManagement, Weight::new(ADMIN_WEIGHT))
+        .unwrap_or_revert();
+
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(
+        String::from(NOT_BEFORE_KEY),
+        storage::new_uref(not_before).into(),
+    );
+    let contract = storage::store_function_at_hash(ENTRY_FUNCTION_NAME, named_keys);
+    account::set_authorization_contract(Some(contract.into())).unwrap_or_revert();
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(Arg::Method as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method.as_str() {
+        METHOD_INSTALL => install(),
+        METHOD_CLEAR => account::set_authorization_contract(None).unwrap_or_revert(),
+        _ => runtime::revert(Error::UnknownMethod),
+    }
+}
@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+use types::{U128, U256, U512};
+
+const FACTORIAL_KEY: &str = "factorial";
+const QUOTIENT_KEY: &str = "quotient";
+const REMAINDER_KEY: &str = "remainder";
+const OVERFLOW_KEY: &str = "overflow";
+const POWER_KEY: &str = "power";
+const NARROW_KEY: &str = "narrow";
+const FACTORIAL_OF: u64 = 60;
+const MODULUS: u64 = 1_000_000_007;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let factorial =
+        (1..=FACTORIAL_OF).fold(U512::one(), |product, factor| product * U512::from(factor));
+    let quotient = factorial / U512::from(MODULUS);
+    let remainder = factorial % U512::from(MODULUS);
+    let (wrapped, overflowed) = U512::max_value().overflowing_add(U512::one());
+    let power = U256::from(3).pow(U256::from(100));
+    let narrow = (U128::max_value() >> 7u32) ^ U128::from(u64::max_value());
+
+    runtime::put_key(FACTORIAL_KEY, storage::new_uref(factorial).into());
+    runtime::put_key(QUOTIENT_KEY, storage::new_uref(quotient).into());
+    runtime::put_key(REMAINDER_KEY, storage::new_uref(remainder).into());
+    runtime::put_key(
+        OVERFLOW_KEY,
+        storage::new_uref((wrapped, overflowed)).into(),
+    );
+    runtime::put_key(POWER_KEY, storage::new_uref(power).into());
+    runtime::put_key(NARROW_KEY, storage::new_uref(narrow).into());
+}
@@ -0,0 +1,97 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    account::PublicKey,
+    bytesrepr::{FromBytes, ToBytes},
+    ApiError, U512,
+};
+
+const BYTES_KEY: &str = "bytes";
+
+#[repr(u16)]
+enum Error {
+    RoundTripMismatch = 0,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Asserts that `value` survives a serialization round trip, appending its serialized form to
+/// `output`.
+fn round_trip<T: ToBytes + FromBytes + PartialEq>(value: &T, output: &mut Vec<u8>) {
+    let bytes = value.to_bytes().unwrap_or_revert();
+    let (decoded, remainder) = T::from_bytes(&bytes).unwrap_or_revert();
+    if !remainder.is_empty() || decoded != *value {
+        runtime::revert(Error::RoundTripMismatch);
+    }
+    output.extend(bytes);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut output = Vec::new();
+
+    round_trip(&(), &mut output);
+    round_trip(&String::new(), &mut output);
+    round_trip(&Vec::<u8>::new(), &mut output);
+    round_trip(&u64::max_value(), &mut output);
+    round_trip(&i32::min_value(), &mut output);
+    round_trip(&U512::zero(), &mut output);
+    round_trip(&U512::max_value(), &mut output);
+    round_trip(&Option::<U512>::None, &mut output);
+    round_trip(&Some(Some(false)), &mut output);
+    round_trip(
+        &Result::<(), String>::Err(String::from("error")),
+        &mut output,
+    );
+    round_trip(&vec![Vec::<u8>::new(), vec![0, 255]], &mut output);
+
+    let mut nested = BTreeMap::new();
+    nested.insert(String::new(), vec![Some(1u64), None]);
+    nested.insert(String::from("\u{1f980}"), Vec::new());
+    round_trip(&nested, &mut output);
+
+    let mut stakes = BTreeMap::new();
+    stakes.insert(
+        String::from("validator"),
+        Some((U512::from(1_000u64), PublicKey::ed25519_from([1; 32]))),
+    );
+    stakes.insert(String::new(), None);
+    round_trip(&stakes, &mut output);
+
+    let set: BTreeSet<u64> = vec![3, 1, 2].into_iter().collect();
+    round_trip(&set, &mut output);
+    round_trip(&[1u16, 2, 3], &mut output);
+    round_trip(
+        &(
+            1u8,
+            2u16,
+            3u32,
+            4u64,
+            -5i32,
+            -6i64,
+            true,
+            String::from("eight"),
+        ),
+        &mut output,
+    );
+
+    runtime::put_key(BYTES_KEY, storage::new_uref(output).into());
+}
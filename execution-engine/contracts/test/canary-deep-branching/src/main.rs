@@ -0,0 +1,45 @@
+#![no_std]
+#![no_main]
+
+use contract::contract_api::{runtime, storage};
+
+const BRANCH_RESULT_KEY: &str = "branch_result";
+const COLLATZ_STEPS_KEY: &str = "collatz_steps";
+const RECURSION_DEPTH: u32 = 200;
+const COLLATZ_LIMIT: u64 = 200;
+
+fn branch(depth: u32, value: u64) -> u64 {
+    if depth == 0 {
+        return value;
+    }
+    match value % 3 {
+        0 => branch(depth - 1, value.wrapping_mul(7).wrapping_add(1)),
+        1 => branch(depth - 1, value ^ 0x5555_5555),
+        _ => branch(depth - 1, value.rotate_left(5)),
+    }
+}
+
+fn collatz_steps(mut value: u64) -> u64 {
+    let mut steps = 0;
+    while value != 1 {
+        value = if value % 2 == 0 {
+            value / 2
+        } else {
+            3 * value + 1
+        };
+        steps += 1;
+    }
+    steps
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let branch_result = branch(RECURSION_DEPTH, 42);
+    let total_collatz_steps: u64 = (1..=COLLATZ_LIMIT).map(collatz_steps).sum();
+
+    runtime::put_key(BRANCH_RESULT_KEY, storage::new_uref(branch_result).into());
+    runtime::put_key(
+        COLLATZ_STEPS_KEY,
+        storage::new_uref(total_collatz_steps).into(),
+    );
+}
@@ -0,0 +1,50 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const BUFFER_KEY: &str = "buffer";
+const NAMED_KEYS_COUNT_KEY: &str = "named_keys_count";
+const BUFFER_LENGTH: u32 = 10_000;
+
+#[repr(u16)]
+enum Error {
+    ValueMismatch = 0,
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let value: Vec<u8> = (0..BUFFER_LENGTH)
+        .map(|index| (index % 256) as u8)
+        .collect();
+    let uref = storage::new_uref(value.clone());
+
+    // Reading the value back and listing the named keys both pass through the host buffer.
+    let read_back: Vec<u8> = storage::read(uref)
+        .unwrap_or_revert()
+        .unwrap_or_revert_with(ApiError::ValueNotFound);
+    if read_back != value {
+        runtime::revert(Error::ValueMismatch);
+    }
+
+    runtime::put_key(BUFFER_KEY, uref.into());
+    let named_keys_count = runtime::list_named_keys().len() as u64;
+    runtime::put_key(
+        NAMED_KEYS_COUNT_KEY,
+        storage::new_uref(named_keys_count).into(),
+    );
+}
@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use contract::contract_api::{runtime, storage};
+
+const CHECKSUM_KEY: &str = "checksum";
+const WASM_PAGE_SIZE: usize = 64 * 1024;
+const PAGE_COUNT: usize = 4;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // Allocating beyond the initial memory makes the allocator grow the Wasm memory.
+    let mut buffer = vec![0u8; PAGE_COUNT * WASM_PAGE_SIZE];
+    for (index, byte) in buffer.iter_mut().enumerate() {
+        *byte = (index % 251) as u8;
+    }
+
+    let checksum = buffer
+        .iter()
+        .enumerate()
+        .fold(0u64, |checksum, (index, byte)| {
+            checksum
+                .wrapping_mul(31)
+                .wrapping_add(u64::from(*byte) ^ index as u64)
+        });
+    runtime::put_key(CHECKSUM_KEY, storage::new_uref(checksum).into());
+}
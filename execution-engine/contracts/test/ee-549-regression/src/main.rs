@@ -2,14 +2,13 @@
 #![no_main]
 
 use contract::contract_api::{runtime, system};
-
-const SET_REFUND_PURSE: &str = "set_refund_purse";
+use types::system_contract_names::pos::METHOD_SET_REFUND_PURSE;
 
 fn malicious_revenue_stealing_contract() {
     let purse = system::create_purse();
     let pos_pointer = system::get_proof_of_stake();
 
-    runtime::call_contract::<_, ()>(pos_pointer, (SET_REFUND_PURSE, purse));
+    runtime::call_contract::<_, ()>(pos_pointer, (METHOD_SET_REFUND_PURSE, purse));
 }
 
 #[no_mangle]
@@ -23,5 +23,5 @@ pub extern "C" fn create() {
 #[no_mangle]
 pub extern "C" fn call() {
     let contract: Key = storage::store_function_at_hash(CONTRACT_NAME, Default::default()).into();
-    runtime::put_key(CONTRACT_NAME, contract)
+    runtime::put_key(CONTRACT_NAME, contract);
 }
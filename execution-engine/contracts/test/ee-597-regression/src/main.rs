@@ -2,12 +2,10 @@
 #![no_main]
 
 use contract::contract_api::{account, runtime, system};
-use types::{ContractRef, URef, U512};
-
-const POS_BOND: &str = "bond";
+use types::{system_contract_names::pos::METHOD_BOND, ContractRef, URef, U512};
 
 fn bond(pos: ContractRef, amount: &U512, source_purse: URef) {
-    runtime::call_contract::<_, ()>(pos, (POS_BOND, *amount, source_purse));
+    runtime::call_contract::<_, ()>(pos, (METHOD_BOND, *amount, source_purse));
 }
 
 #[no_mangle]
@@ -5,17 +5,17 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, ContractRef, URef, U512};
-
-const POS_BOND: &str = "bond";
-const POS_UNBOND: &str = "unbond";
+use types::{
+    system_contract_names::pos::{METHOD_BOND, METHOD_UNBOND},
+    ApiError, ContractRef, URef, U512,
+};
 
 fn bond(pos: ContractRef, amount: U512, source: URef) {
-    runtime::call_contract::<_, ()>(pos, (POS_BOND, amount, source));
+    runtime::call_contract::<_, ()>(pos, (METHOD_BOND, amount, source));
 }
 
 fn unbond(pos: ContractRef, amount: Option<U512>) {
-    runtime::call_contract::<_, ()>(pos, (POS_UNBOND, amount));
+    runtime::call_contract::<_, ()>(pos, (METHOD_UNBOND, amount));
 }
 
 #[no_mangle]
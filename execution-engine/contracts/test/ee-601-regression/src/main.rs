@@ -9,9 +9,8 @@ use contract::{
     contract_api::{account, runtime, storage, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, Phase, URef, U512};
+use types::{system_contract_names::pos::METHOD_GET_PAYMENT_PURSE, ApiError, Phase, URef, U512};
 
-const GET_PAYMENT_PURSE: &str = "get_payment_purse";
 const NEW_UREF_RESULT_UREF_NAME: &str = "new_uref_result";
 
 enum Arg {
@@ -35,7 +34,7 @@ pub extern "C" fn call() {
 
         let pos_pointer = system::get_proof_of_stake();
 
-        let payment_purse: URef = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+        let payment_purse: URef = runtime::call_contract(pos_pointer, (METHOD_GET_PAYMENT_PURSE,));
 
         system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
     }
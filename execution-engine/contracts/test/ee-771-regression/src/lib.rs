@@ -18,7 +18,11 @@ pub extern "C" fn contract_ext() {
     match runtime::get_key(CONTRACT_KEY) {
         Some(key) => {
             // Calls a stored contract if exists.
-            runtime::call_contract(key.to_contract_ref().unwrap(), ())
+            runtime::call_contract(
+                key.to_contract_ref()
+                    .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant),
+                (),
+            )
         }
         None => {
             // If given key doesn't exist it's the tail call, and an error is triggered.
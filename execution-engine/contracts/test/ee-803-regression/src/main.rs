@@ -9,20 +9,20 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, ContractRef, URef, U512};
-
-const POS_BOND: &str = "bond";
-const POS_UNBOND: &str = "unbond";
+use types::{
+    system_contract_names::pos::{METHOD_BOND, METHOD_UNBOND},
+    ApiError, ContractRef, URef, U512,
+};
 
 const COMMAND_BOND: &str = "bond";
 const COMMAND_UNBOND: &str = "unbond";
 
 fn bond(pos: &ContractRef, amount: &U512, source: URef) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_BOND, *amount, source));
+    runtime::call_contract::<_, ()>(pos.clone(), (METHOD_BOND, *amount, source));
 }
 
 fn unbond(pos: &ContractRef, amount: Option<U512>) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_UNBOND, amount));
+    runtime::call_contract::<_, ()>(pos.clone(), (METHOD_UNBOND, amount));
 }
 
 #[no_mangle]
@@ -0,0 +1,103 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{AccessRights, ApiError, CLValue, Key, URef, U512};
+
+const CHECKER_CONTRACT_NAME: &str = "balance_checker";
+const CHECKED_BALANCE_KEY_NAME: &str = "checked_balance";
+const METHOD_INSTALL: &str = "install";
+const METHOD_CHECK: &str = "check";
+const CASE_READ: &str = "read";
+const CASE_ADD: &str = "add";
+const CASE_NOT_A_PURSE: &str = "not_a_purse";
+
+#[repr(u16)]
+enum Args {
+    Method = 0,
+    Checker = 1,
+    Case = 2,
+    Amount = 3,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodArg = 0,
+    InvalidMethodArg = 1,
+    MissingCheckerArg = 2,
+    InvalidCheckerArg = 3,
+    MissingCaseArg = 4,
+    InvalidCaseArg = 5,
+    MissingAmountArg = 6,
+    InvalidAmountArg = 7,
+    MissingPurseArg = 8,
+    InvalidPurseArg = 9,
+    UnknownMethod = 10,
+    UnknownCase = 11,
+}
+
+/// Returns the balance of the purse passed as the only argument.
+#[no_mangle]
+pub extern "C" fn check_balance() {
+    let purse: URef = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingPurseArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidPurseArg as u16));
+    let balance = system::get_balance_by_uref(purse);
+    runtime::ret(CLValue::from_t(balance).unwrap_or_revert())
+}
+
+fn install() {
+    let contract_ref = storage::store_function_at_hash("check_balance", BTreeMap::new());
+    runtime::put_key(CHECKER_CONTRACT_NAME, contract_ref.into());
+}
+
+/// Funds a new purse with `amount` and asks the checker for its balance, passing the purse with
+/// the access rights named by `case`.
+fn check() {
+    let checker = runtime::get_arg::<Key>(Args::Checker as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingCheckerArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidCheckerArg as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidCheckerArg as u16));
+    let case: String = runtime::get_arg(Args::Case as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingCaseArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidCaseArg as u16));
+    let amount: U512 = runtime::get_arg(Args::Amount as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingAmountArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidAmountArg as u16));
+
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount)
+        .unwrap_or_revert();
+
+    let passed_purse = match case.as_str() {
+        CASE_READ => purse.into_read(),
+        CASE_ADD => purse.with_access_rights(AccessRights::ADD),
+        // a valid uref which the mint doesn't know
+        CASE_NOT_A_PURSE => storage::new_uref(()).into_read(),
+        _ => runtime::revert(ApiError::User(CustomError::UnknownCase as u16)),
+    };
+
+    let balance: Option<U512> = runtime::call_contract(checker, (passed_purse,));
+    runtime::put_key(CHECKED_BALANCE_KEY_NAME, storage::new_uref(balance).into());
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(Args::Method as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodArg as u16));
+
+    match method.as_str() {
+        METHOD_INSTALL => install(),
+        METHOD_CHECK => check(),
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethod as u16)),
+    }
+}
@@ -13,7 +13,7 @@ pub extern "C" fn call() {
 
     assert_eq!(
         actual_block_time,
-        BlockTime::new(known_block_time),
+        BlockTime::from_millis(known_block_time),
         "actual block time not known block time"
     );
 }
@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const GAS_COUNTER_READINGS_KEY_NAME: &str = "gas_counter_readings";
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let iterations: u32 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let mut readings: Vec<u64> = Vec::with_capacity(iterations as usize);
+    for _ in 0..iterations {
+        readings.push(runtime::get_gas_counter());
+    }
+
+    runtime::put_key(
+        GAS_COUNTER_READINGS_KEY_NAME,
+        storage::new_uref(readings).into(),
+    );
+}
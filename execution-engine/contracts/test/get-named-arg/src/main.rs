@@ -0,0 +1,42 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, U512};
+
+const AMOUNT_ARG_NAME: &str = "amount";
+
+#[repr(u16)]
+enum Error {
+    MissingArgument0 = 0,
+    MissingAmount,
+    InvalidArgument0,
+    InvalidAmount,
+    AmountMismatch,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    // the first arg is unnamed, so it can only be read by position
+    let value0: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::User(Error::MissingArgument0 as u16))
+        .unwrap_or_revert_with(ApiError::User(Error::InvalidArgument0 as u16));
+    assert_eq!(value0, "Hello, world!");
+
+    let amount: U512 = runtime::get_named_arg(AMOUNT_ARG_NAME)
+        .unwrap_or_revert_with(ApiError::User(Error::MissingAmount as u16))
+        .unwrap_or_revert_with(ApiError::User(Error::InvalidAmount as u16));
+    assert_eq!(amount, U512::from(42));
+
+    // a named arg is still at its position
+    let value1: U512 = runtime::get_arg(1)
+        .unwrap_or_revert_with(ApiError::User(Error::MissingAmount as u16))
+        .unwrap_or_revert_with(ApiError::User(Error::InvalidAmount as u16));
+    if value1 != amount {
+        runtime::revert(ApiError::User(Error::AmountMismatch as u16));
+    }
+}
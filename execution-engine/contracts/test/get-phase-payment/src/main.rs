@@ -5,16 +5,14 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, Phase, URef, U512};
-
-const GET_PAYMENT_PURSE: &str = "get_payment_purse";
+use types::{system_contract_names::pos::METHOD_GET_PAYMENT_PURSE, ApiError, Phase, URef, U512};
 
 fn standard_payment(amount: U512) {
     let main_purse = account::get_main_purse();
 
     let pos_pointer = system::get_proof_of_stake();
 
-    let payment_purse: URef = runtime::call_contract(pos_pointer, (GET_PAYMENT_PURSE,));
+    let payment_purse: URef = runtime::call_contract(pos_pointer, (METHOD_GET_PAYMENT_PURSE,));
 
     system::transfer_from_purse_to_purse(main_purse, payment_purse, amount).unwrap_or_revert()
 }
@@ -0,0 +1,13 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, U512};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_typed_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    assert_eq!(amount, U512::from(42));
+}
@@ -40,7 +40,7 @@ pub extern "C" fn call() {
     // Remove all named keys and check that removed keys aren't returned in `list_named_keys()`.
     let all_key_names: Vec<String> = expected_named_keys.keys().cloned().collect();
     for key in all_key_names {
-        runtime::remove_key(&key);
+        runtime::remove_key(&key).unwrap_or_revert();
         assert!(expected_named_keys.remove(&key).is_some());
         let actual_named_keys = runtime::list_named_keys();
         assert_eq!(expected_named_keys, actual_named_keys);
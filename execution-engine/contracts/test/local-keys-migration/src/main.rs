@@ -0,0 +1,124 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    require_eq,
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, KEY_LOCAL_SEED_LENGTH};
+
+const LEGACY_CONTRACT_NAME: &str = "local_keys_legacy";
+const MIGRATOR_CONTRACT_NAME: &str = "local_keys_migrator";
+const MIGRATED_COUNT_KEY_NAME: &str = "migrated_count";
+const METHOD_INSTALL: &str = "install";
+const METHOD_MIGRATE: &str = "migrate";
+const ENTRY_COUNT: u8 = 5;
+/// The index of the old seed among the arguments of `migrate`.
+const MIGRATE_OLD_SEED_ARG: u32 = 0;
+
+#[repr(u16)]
+enum Args {
+    Method = 0,
+    OldSeed = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodArg = 0,
+    InvalidMethodArg = 1,
+    MissingOldSeedArg = 2,
+    InvalidOldSeedArg = 3,
+    UnknownMethod = 4,
+    MissingContractKey = 5,
+    MissingEntry = 6,
+    InvalidEntry = 7,
+    WrongEntryValue = 8,
+}
+
+fn entry_value(entry: u8) -> u64 {
+    u64::from(entry) * 100
+}
+
+/// Writes `ENTRY_COUNT` entries under the local keys of the legacy contract.
+#[no_mangle]
+pub extern "C" fn write_entries() {
+    for entry in 0..ENTRY_COUNT {
+        storage::write_local(entry, entry_value(entry));
+    }
+}
+
+/// Migrates the local keys of the given seed, checks that the entries written by the legacy
+/// contract can be read and returns the number of values migrated.
+#[no_mangle]
+pub extern "C" fn migrate() {
+    let old_seed: [u8; KEY_LOCAL_SEED_LENGTH] = runtime::get_arg(MIGRATE_OLD_SEED_ARG)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingOldSeedArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidOldSeedArg as u16));
+
+    let migrated_count = storage::migrate_local_keys(old_seed).unwrap_or_revert();
+
+    for entry in 0..ENTRY_COUNT {
+        let value: u64 = storage::read_local(&entry)
+            .unwrap_or_revert_with(ApiError::User(CustomError::InvalidEntry as u16))
+            .unwrap_or_revert_with(ApiError::User(CustomError::MissingEntry as u16));
+        require_eq!(value, entry_value(entry), CustomError::WrongEntryValue);
+    }
+
+    runtime::ret(CLValue::from_t(migrated_count).unwrap_or_revert())
+}
+
+fn install() {
+    let legacy_contract = storage::store_function("write_entries", BTreeMap::new());
+    runtime::call_contract::<_, ()>(legacy_contract.clone(), ());
+
+    let legacy_key = legacy_contract
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant)
+        .into();
+    runtime::put_key(LEGACY_CONTRACT_NAME, legacy_key);
+
+    // the migrator holds the legacy contract's uref with write access, which entitles it to
+    // migrate the legacy contract's local keys
+    let mut migrator_named_keys = BTreeMap::new();
+    migrator_named_keys.insert(String::from(LEGACY_CONTRACT_NAME), legacy_key);
+    let migrator_key = storage::store_function("migrate", migrator_named_keys)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant)
+        .into();
+    runtime::put_key(MIGRATOR_CONTRACT_NAME, migrator_key);
+}
+
+fn migrate_with_seed(old_seed: [u8; KEY_LOCAL_SEED_LENGTH]) {
+    let migrator = runtime::get_key(MIGRATOR_CONTRACT_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKey as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+    let migrated_count: u32 = runtime::call_contract(migrator, (old_seed,));
+    runtime::put_key(
+        MIGRATED_COUNT_KEY_NAME,
+        storage::new_uref(migrated_count).into(),
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method: String = runtime::get_arg(Args::Method as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodArg as u16));
+
+    match method.as_str() {
+        METHOD_INSTALL => install(),
+        METHOD_MIGRATE => {
+            let old_seed: [u8; KEY_LOCAL_SEED_LENGTH] = runtime::get_arg(Args::OldSeed as u32)
+                .unwrap_or_revert_with(ApiError::User(CustomError::MissingOldSeedArg as u16))
+                .unwrap_or_revert_with(ApiError::User(CustomError::InvalidOldSeedArg as u16));
+            migrate_with_seed(old_seed)
+        }
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethod as u16)),
+    }
+}
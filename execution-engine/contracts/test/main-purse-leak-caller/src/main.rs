@@ -0,0 +1,30 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, Key, U512};
+
+const CONTRACT_NAME: &str = "main_purse_thief";
+
+#[repr(u16)]
+enum Args {
+    Amount = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingContractKey = 0,
+}
+
+/// Calls the contract the account's main purse was leaked to, to transfer `amount` from it.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(Args::Amount as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let contract_ref = runtime::get_key(CONTRACT_NAME)
+        .and_then(Key::to_contract_ref)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKey as u16));
+
+    runtime::call_contract::<_, ()>(contract_ref, (amount,));
+}
@@ -0,0 +1,47 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::ToString};
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key, U512};
+
+const CONTRACT_NAME: &str = "main_purse_thief";
+const ENTRY_FUNCTION_NAME: &str = "steal";
+const LEAKED_PURSE_NAME: &str = "leaked_purse";
+
+#[repr(u16)]
+enum CustomError {
+    MissingLeakedPurse = 0,
+}
+
+/// Transfers `amount` from the leaked purse to a new purse of the contract.
+#[no_mangle]
+pub extern "C" fn steal() {
+    let amount: U512 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let leaked_purse = runtime::get_key(LEAKED_PURSE_NAME)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingLeakedPurse as u16));
+
+    let target = system::create_purse();
+    system::transfer_from_purse_to_purse(leaked_purse, target, amount).unwrap_or_revert();
+}
+
+/// Stores `steal` at a hash, leaking the account's main purse to it with full access rights.
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(
+        LEAKED_PURSE_NAME.to_string(),
+        account::get_main_purse().into(),
+    );
+    let contract_ref = storage::store_function_at_hash(ENTRY_FUNCTION_NAME, named_keys);
+    runtime::put_key(CONTRACT_NAME, contract_ref.into());
+}
@@ -5,7 +5,11 @@ use contract::{
     contract_api::{runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{system_contract_errors::mint, ApiError, URef, U512};
+use types::{
+    system_contract_errors::mint,
+    system_contract_names::mint::{METHOD_BALANCE, METHOD_MINT},
+    ApiError, URef, U512,
+};
 
 #[repr(u16)]
 enum Error {
@@ -14,7 +18,7 @@ enum Error {
 }
 
 fn mint_purse(amount: U512) -> Result<URef, mint::Error> {
-    runtime::call_contract(system::get_mint(), ("mint", amount))
+    runtime::call_contract(system::get_mint(), (METHOD_MINT, amount))
 }
 
 #[no_mangle]
@@ -24,7 +28,7 @@ pub extern "C" fn call() {
 
     let mint = system::get_mint();
 
-    let balance: Option<U512> = runtime::call_contract(mint, ("balance", new_purse));
+    let balance: Option<U512> = runtime::call_contract(mint, (METHOD_BALANCE, new_purse));
 
     match balance {
         None => runtime::revert(ApiError::User(Error::BalanceNotFound as u16)),
@@ -7,20 +7,27 @@ use alloc::string::String;
 use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
 use mint::Mint;
 use mint_token::MintContract;
-use types::{system_contract_errors::mint::Error, ApiError, CLValue, URef, U512};
+use types::{
+    system_contract_errors::mint::Error,
+    system_contract_names::{
+        mint::{METHOD_BALANCE, METHOD_CREATE, METHOD_MINT, METHOD_TRANSFER},
+        METHOD_ARG_INDEX,
+    },
+    ApiError, CLValue, URef, U512,
+};
 
 const VERSION: &str = "1.1.0";
 
 pub fn delegate() {
     let mut mint = MintContract;
-    let method_name: String = runtime::get_arg(0)
+    let method_name: String = runtime::get_arg(METHOD_ARG_INDEX)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
 
     match method_name.as_str() {
         // argument: U512
         // return: Result<URef, mint::error::Error>
-        "mint" => {
+        METHOD_MINT => {
             let amount: U512 = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
@@ -29,7 +36,7 @@ pub fn delegate() {
             runtime::ret(ret)
         }
 
-        "create" => {
+        METHOD_CREATE => {
             let uref = mint
                 .mint(U512::zero())
                 .expect("Creating a zero balance purse should always be allowed.");
@@ -37,7 +44,7 @@ pub fn delegate() {
             runtime::ret(ret)
         }
 
-        "balance" => {
+        METHOD_BALANCE => {
             let uref: URef = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
@@ -46,7 +53,7 @@ pub fn delegate() {
             runtime::ret(ret)
         }
 
-        "transfer" => {
+        METHOD_TRANSFER => {
             let source: URef = runtime::get_arg(1)
                 .unwrap_or_revert_with(ApiError::MissingArgument)
                 .unwrap_or_revert_with(ApiError::InvalidArgument);
@@ -0,0 +1,54 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, Key, URef};
+
+const POS_FUNCTION_NAME: &str = "pos_ext";
+const METHOD_VERSION: &str = "version";
+const VERSION: &str = "1.1.0";
+
+#[no_mangle]
+pub extern "C" fn pos_ext() {
+    let method_name: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    match method_name.as_str() {
+        METHOD_VERSION => {
+            let version_uref = runtime::get_key(METHOD_VERSION)
+                .and_then(Key::into_uref)
+                .unwrap_or_revert_with(ApiError::GetKey);
+            let version: String = storage::read(version_uref)
+                .unwrap_or_revert()
+                .unwrap_or_revert_with(ApiError::ValueNotFound);
+            runtime::ret(CLValue::from_t(version).unwrap_or_revert())
+        }
+        _ => pos::delegate(),
+    }
+}
+
+/// Installs a Proof of Stake contract with an additional `version` entry point, which reads the
+/// version from a new named key.
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(
+        String::from(METHOD_VERSION),
+        storage::new_uref(String::from(VERSION)).into(),
+    );
+
+    let uref: URef = storage::store_function(POS_FUNCTION_NAME, named_keys)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+    let return_value = CLValue::from_t(uref).unwrap_or_revert();
+
+    runtime::ret(return_value);
+}
@@ -0,0 +1,82 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, Key};
+
+const METHOD_PUT: &str = "put";
+const METHOD_HAS: &str = "has";
+const METHOD_PUT_VIA: &str = "put_via";
+const METHOD_ADD: &str = "add";
+const ADDED_KEY: Key = Key::Hash([2; 32]);
+
+#[repr(u32)]
+enum Args {
+    MethodName = 0,
+    ContractName = 1,
+    KeyName = 2,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodNameArg = 0,
+    InvalidMethodNameArg = 1,
+    MissingContractNameArg = 2,
+    InvalidContractNameArg = 3,
+    MissingKeyNameArg = 4,
+    InvalidKeyNameArg = 5,
+    MissingContract = 6,
+    UnknownMethodName = 7,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodNameArg as u16));
+    let contract_name: String = runtime::get_arg(Args::ContractName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidContractNameArg as u16));
+    let key_name: String = runtime::get_arg(Args::KeyName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingKeyNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidKeyNameArg as u16));
+
+    let contract_key = runtime::get_key(&contract_name)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContract as u16));
+    let contract_ref = contract_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    match method_name.as_str() {
+        METHOD_PUT => {
+            runtime::call_contract::<_, ()>(contract_ref.clone(), (METHOD_PUT, key_name.as_str()));
+            // a later call into the contract sees the key, while the account doesn't get it
+            let contract_has_key: bool =
+                runtime::call_contract(contract_ref, (METHOD_HAS, key_name.as_str()));
+            let account_has_key = runtime::has_key(&key_name);
+            runtime::ret(CLValue::from_t((contract_has_key, account_has_key)).unwrap_or_revert())
+        }
+        METHOD_PUT_VIA => {
+            // the contract calls back into itself to put the key
+            let has_key: bool = runtime::call_contract(
+                contract_ref,
+                (METHOD_PUT_VIA, key_name.as_str(), contract_key),
+            );
+            runtime::ret(CLValue::from_t(has_key).unwrap_or_revert())
+        }
+        METHOD_ADD => {
+            let contract_uref = contract_key
+                .into_uref()
+                .unwrap_or_revert_with(ApiError::UnexpectedKeyVariant);
+            storage::add(contract_uref, (key_name, ADDED_KEY));
+        }
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethodName as u16)),
+    }
+}
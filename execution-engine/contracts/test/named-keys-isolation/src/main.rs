@@ -0,0 +1,83 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, Key};
+
+const ENTRY_FUNCTION_NAME: &str = "apply_method";
+const HASH_CONTRACT_NAME: &str = "isolation_hash";
+const UREF_CONTRACT_NAME: &str = "isolation_uref";
+const METHOD_PUT: &str = "put";
+const METHOD_HAS: &str = "has";
+const METHOD_PUT_VIA: &str = "put_via";
+const PUT_KEY: Key = Key::Hash([1; 32]);
+
+#[repr(u32)]
+enum Args {
+    MethodName = 0,
+    KeyName = 1,
+    Contract = 2,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodNameArg = 0,
+    InvalidMethodNameArg = 1,
+    MissingKeyNameArg = 2,
+    InvalidKeyNameArg = 3,
+    MissingContractArg = 4,
+    InvalidContractArg = 5,
+    UnknownMethodName = 6,
+}
+
+fn key_name() -> String {
+    runtime::get_arg(Args::KeyName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingKeyNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidKeyNameArg as u16))
+}
+
+#[no_mangle]
+pub extern "C" fn apply_method() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodNameArg as u16));
+    match method_name.as_str() {
+        METHOD_PUT => {
+            runtime::put_key(&key_name(), PUT_KEY);
+        }
+        METHOD_HAS => {
+            let has_key = runtime::has_key(&key_name());
+            runtime::ret(CLValue::from_t(has_key).unwrap_or_revert())
+        }
+        METHOD_PUT_VIA => {
+            let key_name = key_name();
+            let contract: Key = runtime::get_arg(Args::Contract as u32)
+                .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractArg as u16))
+                .unwrap_or_revert_with(ApiError::User(CustomError::InvalidContractArg as u16));
+            let contract_ref = contract
+                .to_contract_ref()
+                .unwrap_or_revert_with(ApiError::User(CustomError::InvalidContractArg as u16));
+            runtime::call_contract::<_, ()>(contract_ref, (METHOD_PUT, key_name.as_str()));
+            let has_key = runtime::has_key(&key_name);
+            runtime::ret(CLValue::from_t(has_key).unwrap_or_revert())
+        }
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethodName as u16)),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let hash_key: Key =
+        storage::store_function_at_hash(ENTRY_FUNCTION_NAME, BTreeMap::new()).into();
+    runtime::put_key(HASH_CONTRACT_NAME, hash_key);
+
+    let uref_key: Key = storage::store_function(ENTRY_FUNCTION_NAME, BTreeMap::new()).into();
+    runtime::put_key(UREF_CONTRACT_NAME, uref_key);
+}
@@ -35,8 +35,8 @@ pub extern "C" fn call() {
     match command.as_str() {
         COMMAND_CREATE_UREF1 => create_uref("hello-world", String::from("Hello, world!")),
         COMMAND_CREATE_UREF2 => create_uref("big-value", U512::max_value()),
-        COMMAND_REMOVE_UREF1 => runtime::remove_key("hello-world"),
-        COMMAND_REMOVE_UREF2 => runtime::remove_key("big-value"),
+        COMMAND_REMOVE_UREF1 => runtime::remove_key("hello-world").unwrap_or_revert(),
+        COMMAND_REMOVE_UREF2 => runtime::remove_key("big-value").unwrap_or_revert(),
         COMMAND_TEST_READ_UREF1 => {
             // Read data hidden behind `URef1` uref
             let hello_world: String = storage::read(
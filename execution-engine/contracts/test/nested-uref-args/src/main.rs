@@ -0,0 +1,61 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef};
+
+const ENTRY_FUNCTION_NAME: &str = "write_all";
+const UREF_NAMES: [&str; 3] = ["uref_a", "uref_b", "uref_c"];
+const INITIAL_VALUE: u64 = 0;
+const WRITTEN_VALUE: u64 = 42;
+
+#[repr(u16)]
+enum Args {
+    Narrow = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingNarrowArg = 0,
+    InvalidNarrowArg = 1,
+    MissingEntriesArg = 2,
+    InvalidEntriesArg = 3,
+}
+
+/// Writes to each of the urefs passed nested in its argument, none of which is listed separately.
+#[no_mangle]
+pub extern "C" fn write_all() {
+    let entries: Vec<(String, URef)> = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingEntriesArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidEntriesArg as u16));
+    for (_name, uref) in entries {
+        storage::write(uref, WRITTEN_VALUE);
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let narrow: bool = runtime::get_arg(Args::Narrow as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingNarrowArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidNarrowArg as u16));
+
+    let entries: Vec<(String, URef)> = UREF_NAMES
+        .iter()
+        .map(|name| {
+            let uref = storage::new_uref(INITIAL_VALUE);
+            runtime::put_key(name, uref.into());
+            let passed_uref = if narrow { uref.into_read() } else { uref };
+            (String::from(*name), passed_uref)
+        })
+        .collect();
+
+    let contract_ref = storage::store_function(ENTRY_FUNCTION_NAME, BTreeMap::new());
+    runtime::call_contract::<_, ()>(contract_ref, (entries,));
+}
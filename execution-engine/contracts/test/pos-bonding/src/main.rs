@@ -9,7 +9,11 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PublicKey, ApiError, ContractRef, URef, U512};
+use types::{
+    account::PublicKey,
+    system_contract_names::pos::{METHOD_BOND, METHOD_UNBOND},
+    ApiError, ContractRef, URef, U512,
+};
 
 #[repr(u16)]
 enum Error {
@@ -18,21 +22,21 @@ enum Error {
 }
 
 fn bond(pos: &ContractRef, amount: &U512, source: URef) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_BOND, *amount, source));
+    runtime::call_contract::<_, ()>(pos.clone(), (METHOD_BOND, *amount, source));
 }
 
 fn unbond(pos: &ContractRef, amount: Option<U512>) {
-    runtime::call_contract::<_, ()>(pos.clone(), (POS_UNBOND, amount));
+    runtime::call_contract::<_, ()>(pos.clone(), (METHOD_UNBOND, amount));
 }
 
-const POS_BOND: &str = "bond";
-const POS_UNBOND: &str = "unbond";
-
 const TEST_BOND: &str = "bond";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
+const TEST_BOND_FROM_READ_ONLY_PURSE: &str = "bond-from-read-only-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
 const TEST_UNBOND: &str = "unbond";
 
+const BONDING_PURSE_KEY: &str = "bonding_purse";
+
 #[no_mangle]
 pub extern "C" fn call() {
     let pos_pointer = system::get_proof_of_stake();
@@ -50,6 +54,8 @@ pub extern "C" fn call() {
 
         system::transfer_from_purse_to_purse(account::get_main_purse(), p1, amount)
             .unwrap_or_revert();
+        // Unbonded funds are paid back to this purse
+        runtime::put_key(BONDING_PURSE_KEY, p1.into());
 
         bond(&pos_pointer, &amount, p1);
     } else if command == TEST_BOND_FROM_MAIN_PURSE {
@@ -58,6 +64,16 @@ pub extern "C" fn call() {
             .unwrap_or_revert_with(ApiError::InvalidArgument);
 
         bond(&pos_pointer, &amount, account::get_main_purse());
+    } else if command == TEST_BOND_FROM_READ_ONLY_PURSE {
+        let amount = runtime::get_arg(1)
+            .unwrap_or_revert_with(ApiError::MissingArgument)
+            .unwrap_or_revert_with(ApiError::InvalidArgument);
+        let p1 = system::create_purse();
+
+        system::transfer_from_purse_to_purse(account::get_main_purse(), p1, amount)
+            .unwrap_or_revert();
+
+        bond(&pos_pointer, &amount, p1.into_read());
     } else if command == TEST_SEED_NEW_ACCOUNT {
         let account: PublicKey = runtime::get_arg(1)
             .unwrap_or_revert_with(ApiError::MissingArgument)
@@ -5,14 +5,20 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{account::PublicKey, ApiError, ContractRef, Key, URef, U512};
+use types::{
+    account::PublicKey,
+    system_contract_names::pos::{
+        METHOD_FINALIZE_PAYMENT, METHOD_GET_PAYMENT_PURSE, METHOD_SET_REFUND_PURSE,
+    },
+    ApiError, ContractRef, Key, URef, U512,
+};
 
 fn set_refund_purse(pos: &ContractRef, p: &URef) {
-    runtime::call_contract(pos.clone(), ("set_refund_purse", *p))
+    runtime::call_contract(pos.clone(), (METHOD_SET_REFUND_PURSE, *p))
 }
 
 fn get_payment_purse(pos: &ContractRef) -> URef {
-    runtime::call_contract(pos.clone(), ("get_payment_purse",))
+    runtime::call_contract(pos.clone(), (METHOD_GET_PAYMENT_PURSE,))
 }
 
 fn submit_payment(pos: &ContractRef, amount: U512) {
@@ -22,7 +28,10 @@ fn submit_payment(pos: &ContractRef, amount: U512) {
 }
 
 fn finalize_payment(pos: &ContractRef, amount_spent: U512, account: PublicKey) {
-    runtime::call_contract(pos.clone(), ("finalize_payment", amount_spent, account))
+    runtime::call_contract(
+        pos.clone(),
+        (METHOD_FINALIZE_PAYMENT, amount_spent, account),
+    )
 }
 
 #[no_mangle]
@@ -3,9 +3,10 @@
 
 use contract::{
     contract_api::{account, runtime, system},
-    unwrap_or_revert::UnwrapOrRevert,
+    revert_on_error,
+    unwrap_or_revert::{OkOrRevert, UnwrapOrRevert},
 };
-use types::{ApiError, URef, U512};
+use types::{system_contract_names::pos::METHOD_GET_PAYMENT_PURSE, ApiError, URef, U512};
 
 #[repr(u16)]
 enum Error {
@@ -24,21 +25,21 @@ pub extern "C" fn call() {
     let payment_fund: U512 = runtime::get_arg(0)
         .unwrap_or_revert_with(ApiError::MissingArgument)
         .unwrap_or_revert_with(ApiError::InvalidArgument);
-    let payment_purse: URef = runtime::call_contract(pos_pointer, ("get_payment_purse",));
+    let payment_purse: URef = runtime::call_contract(pos_pointer, (METHOD_GET_PAYMENT_PURSE,));
 
     // can deposit
-    system::transfer_from_purse_to_purse(source_purse, payment_purse, payment_amount)
-        .unwrap_or_revert_with(ApiError::User(Error::TransferFromSourceToPayment as u16));
+    revert_on_error!(
+        system::transfer_from_purse_to_purse(source_purse, payment_purse, payment_amount),
+        Error::TransferFromSourceToPayment
+    );
 
-    let payment_balance = system::get_balance(payment_purse)
-        .unwrap_or_revert_with(ApiError::User(Error::GetBalance as u16));
+    let payment_balance = revert_on_error!(system::get_balance(payment_purse), Error::GetBalance);
 
-    if payment_balance.saturating_sub(payment_fund) != payment_amount {
-        runtime::revert(ApiError::User(Error::CheckBalance as u16))
-    }
+    (payment_balance.saturating_sub(payment_fund) == payment_amount)
+        .ok_or_revert(ApiError::User(Error::CheckBalance as u16));
 
     // cannot withdraw
-    if system::transfer_from_purse_to_purse(payment_purse, source_purse, payment_amount).is_ok() {
-        runtime::revert(ApiError::User(Error::TransferFromPaymentToSource as u16));
-    }
+    system::transfer_from_purse_to_purse(payment_purse, source_purse, payment_amount)
+        .is_err()
+        .ok_or_revert(ApiError::User(Error::TransferFromPaymentToSource as u16));
 }
@@ -0,0 +1,46 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    system_contract_names::pos::{METHOD_GET_PAYMENT_PURSE, METHOD_SET_REFUND_PURSE},
+    AccessRights, ApiError, ContractRef, URef, U512,
+};
+
+enum Arg {
+    Amount = 0,
+    RefundPurseAccessRights,
+}
+
+fn get_payment_purse(pos: ContractRef) -> URef {
+    runtime::call_contract(pos, (METHOD_GET_PAYMENT_PURSE,))
+}
+
+fn set_refund_purse(pos: ContractRef, purse: URef) {
+    runtime::call_contract(pos, (METHOD_SET_REFUND_PURSE, purse))
+}
+
+/// Pays from the main purse, and sets a new purse as refund purse, passing only the given access
+/// rights on it to the PoS contract.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(Arg::Amount as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let access_rights_bits: u8 = runtime::get_arg(Arg::RefundPurseAccessRights as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let access_rights = AccessRights::from_bits(access_rights_bits)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let pos = system::get_proof_of_stake();
+    let payment_purse = get_payment_purse(pos.clone());
+    system::transfer_from_purse_to_purse(account::get_main_purse(), payment_purse, amount)
+        .unwrap_or_revert();
+
+    let refund_purse = system::create_purse();
+    set_refund_purse(pos, refund_purse.with_access_rights(access_rights));
+}
@@ -5,7 +5,12 @@ use contract::{
     contract_api::{account, runtime, system},
     unwrap_or_revert::UnwrapOrRevert,
 };
-use types::{ApiError, ContractRef, URef, U512};
+use types::{
+    system_contract_names::pos::{
+        METHOD_GET_PAYMENT_PURSE, METHOD_GET_REFUND_PURSE, METHOD_SET_REFUND_PURSE,
+    },
+    ApiError, ContractRef, URef, U512,
+};
 
 #[repr(u16)]
 enum Error {
@@ -16,15 +21,15 @@ enum Error {
 }
 
 fn set_refund_purse(pos: &ContractRef, p: &URef) {
-    runtime::call_contract(pos.clone(), ("set_refund_purse", *p))
+    runtime::call_contract(pos.clone(), (METHOD_SET_REFUND_PURSE, *p))
 }
 
 fn get_refund_purse(pos: &ContractRef) -> Option<URef> {
-    runtime::call_contract(pos.clone(), ("get_refund_purse",))
+    runtime::call_contract(pos.clone(), (METHOD_GET_REFUND_PURSE,))
 }
 
 fn get_payment_purse(pos: &ContractRef) -> URef {
-    runtime::call_contract(pos.clone(), ("get_payment_purse",))
+    runtime::call_contract(pos.clone(), (METHOD_GET_PAYMENT_PURSE,))
 }
 
 fn submit_payment(pos: &ContractRef, amount: U512) {
@@ -0,0 +1,18 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{account::PublicKey, system_contract_names::pos::METHOD_SLASH, ApiError};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let validator: PublicKey = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let pos = system::get_proof_of_stake();
+    runtime::call_contract::<_, ()>(pos, (METHOD_SLASH, validator));
+}
@@ -0,0 +1,16 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let key: Key = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    storage::prune(key);
+}
@@ -0,0 +1,36 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const PRICE_KEY: &str = "price";
+const SECRET_KEY: &str = "secret";
+
+#[repr(u16)]
+enum Args {
+    Price = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingPriceArg = 0,
+    InvalidPriceArg = 1,
+}
+
+/// Publishes the given price under a public uref, and keeps a copy of it under a private one.
+#[no_mangle]
+pub extern "C" fn call() {
+    let price: U512 = runtime::get_arg(Args::Price as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingPriceArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidPriceArg as u16));
+
+    let price_uref = storage::new_public_uref(price);
+    runtime::put_key(PRICE_KEY, price_uref.into());
+
+    let secret_uref = storage::new_uref(price);
+    runtime::put_key(SECRET_KEY, secret_uref.into());
+}
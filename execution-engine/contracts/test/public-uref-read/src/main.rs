@@ -0,0 +1,38 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef, U512};
+
+const PRICE_KEY: &str = "price";
+
+#[repr(u16)]
+enum Args {
+    URef = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingURefArg = 0,
+    InvalidURefArg = 1,
+    MissingPrice = 2,
+}
+
+/// Reads the price under the given uref, which this account was never given, and stores a copy of
+/// it under its own named key.
+#[no_mangle]
+pub extern "C" fn call() {
+    let uref: URef = runtime::get_arg(Args::URef as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingURefArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidURefArg as u16));
+
+    let price: U512 = storage::read(uref)
+        .unwrap_or_revert_with(ApiError::Read)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingPrice as u16));
+
+    let price_uref = storage::new_uref(price);
+    runtime::put_key(PRICE_KEY, price_uref.into());
+}
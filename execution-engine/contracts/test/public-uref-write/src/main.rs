@@ -0,0 +1,35 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef, U512};
+
+#[repr(u16)]
+enum Args {
+    URef = 0,
+    Price = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingURefArg = 0,
+    InvalidURefArg = 1,
+    MissingPriceArg = 2,
+    InvalidPriceArg = 3,
+}
+
+/// Tries to overwrite the price under the given uref, which this account was never given.
+#[no_mangle]
+pub extern "C" fn call() {
+    let uref: URef = runtime::get_arg(Args::URef as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingURefArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidURefArg as u16));
+    let price: U512 = runtime::get_arg(Args::Price as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingPriceArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidPriceArg as u16));
+
+    storage::write(uref, price);
+}
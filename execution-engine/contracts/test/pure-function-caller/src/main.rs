@@ -0,0 +1,59 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const RESULT_KEY_NAME: &str = "result";
+
+#[repr(u16)]
+enum Args {
+    FunctionName = 0,
+    CallCount = 1,
+    Base = 2,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingFunctionNameArg = 0,
+    InvalidFunctionNameArg = 1,
+    MissingCallCountArg = 2,
+    InvalidCallCountArg = 3,
+    MissingBaseArg = 4,
+    InvalidBaseArg = 5,
+    MissingFunctionKey = 6,
+}
+
+/// Calls the named stored function the given number of times with the same argument, and stores
+/// the result of the last call.
+#[no_mangle]
+pub extern "C" fn call() {
+    let function_name: String = runtime::get_arg(Args::FunctionName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingFunctionNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidFunctionNameArg as u16));
+    let call_count: u32 = runtime::get_arg(Args::CallCount as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingCallCountArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidCallCountArg as u16));
+    let base: u64 = runtime::get_arg(Args::Base as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingBaseArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidBaseArg as u16));
+
+    let contract_ref = runtime::get_key(&function_name)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingFunctionKey as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    let mut result: u64 = 0;
+    for _ in 0..call_count {
+        result = runtime::call_contract(contract_ref.clone(), (base,));
+    }
+
+    runtime::put_key(RESULT_KEY_NAME, storage::new_uref(result).into());
+}
@@ -0,0 +1,64 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, URef};
+
+const READING_FUNCTION_NAME: &str = "reading_function";
+const READ_VALUE_KEY_NAME: &str = "read_value";
+
+#[repr(u16)]
+enum Args {
+    Expected = 0,
+    NewValue = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingExpectedArg = 0,
+    InvalidExpectedArg = 1,
+    MissingNewValueArg = 2,
+    InvalidNewValueArg = 3,
+    MissingFunctionKey = 4,
+    MissingReadValueKey = 5,
+    UnexpectedValueBeforeWrite = 6,
+    UnexpectedValueAfterWrite = 7,
+}
+
+/// Reads the value under the `read_value` key through the stored pure function, checks it is the
+/// expected one, then writes the new value and checks the function returns that one.
+#[no_mangle]
+pub extern "C" fn call() {
+    let expected: u64 = runtime::get_arg(Args::Expected as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingExpectedArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidExpectedArg as u16));
+    let new_value: u64 = runtime::get_arg(Args::NewValue as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingNewValueArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidNewValueArg as u16));
+
+    let contract_ref = runtime::get_key(READING_FUNCTION_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingFunctionKey as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+    let uref: URef = runtime::get_key(READ_VALUE_KEY_NAME)
+        .and_then(|key| key.as_uref().cloned())
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingReadValueKey as u16));
+
+    let value: u64 = runtime::call_contract(contract_ref.clone(), (uref,));
+    if value != expected {
+        runtime::revert(ApiError::User(
+            CustomError::UnexpectedValueBeforeWrite as u16,
+        ));
+    }
+
+    storage::write(uref, new_value);
+    let value: u64 = runtime::call_contract(contract_ref, (uref,));
+    if value != new_value {
+        runtime::revert(ApiError::User(
+            CustomError::UnexpectedValueAfterWrite as u16,
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, FunctionSpec, URef};
+
+const PURE_FUNCTION_NAME: &str = "pure_function";
+const PLAIN_FUNCTION_NAME: &str = "plain_function";
+const IMPURE_FUNCTION_NAME: &str = "impure_function";
+const READING_FUNCTION_NAME: &str = "reading_function";
+const READ_VALUE_KEY_NAME: &str = "read_value";
+const WRITTEN_KEY_NAME: &str = "written";
+const LARGE_PRIME: u64 = 0xffff_fffb;
+const ROUNDS: u32 = 100_000;
+const VALUE: u64 = 42;
+
+#[repr(u16)]
+enum Args {
+    Base = 0,
+}
+
+#[repr(u16)]
+enum ReadArgs {
+    URef = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingBaseArg = 0,
+    InvalidBaseArg = 1,
+    MissingURefArg = 2,
+    InvalidURefArg = 3,
+}
+
+/// Returns `base^ROUNDS mod LARGE_PRIME`, computed the slow way.
+#[no_mangle]
+pub extern "C" fn power() {
+    let base: u64 = runtime::get_arg(Args::Base as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingBaseArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidBaseArg as u16));
+
+    let mut result: u64 = 1;
+    for _ in 0..ROUNDS {
+        result = result * (base % LARGE_PRIME) % LARGE_PRIME;
+    }
+
+    runtime::ret(CLValue::from_t(result).unwrap_or_revert())
+}
+
+/// Returns the value under the given `URef`.
+#[no_mangle]
+pub extern "C" fn read() {
+    let uref: URef = runtime::get_arg(ReadArgs::URef as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingURefArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidURefArg as u16));
+
+    let value: u64 = storage::read_or_revert(uref);
+    runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+}
+
+/// Modifies global state despite being declared pure.
+#[no_mangle]
+pub extern "C" fn write() {
+    let written_uref = storage::new_uref(VALUE);
+    runtime::put_key(WRITTEN_KEY_NAME, written_uref.into());
+    runtime::ret(CLValue::from_t(VALUE).unwrap_or_revert())
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let pure_function =
+        storage::store_function_with_spec("power", BTreeMap::new(), FunctionSpec::pure());
+    runtime::put_key(PURE_FUNCTION_NAME, pure_function.into());
+
+    let plain_function =
+        storage::store_function_with_spec("power", BTreeMap::new(), FunctionSpec::new());
+    runtime::put_key(PLAIN_FUNCTION_NAME, plain_function.into());
+
+    let impure_function =
+        storage::store_function_with_spec("write", BTreeMap::new(), FunctionSpec::pure());
+    runtime::put_key(IMPURE_FUNCTION_NAME, impure_function.into());
+
+    let reading_function =
+        storage::store_function_with_spec("read", BTreeMap::new(), FunctionSpec::pure());
+    runtime::put_key(READING_FUNCTION_NAME, reading_function.into());
+    runtime::put_key(READ_VALUE_KEY_NAME, storage::new_uref(VALUE).into());
+}
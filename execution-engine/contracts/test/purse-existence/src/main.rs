@@ -0,0 +1,77 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::format;
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{system_contract_errors::mint, URef, U512};
+
+const METHOD_TRANSFER: &str = "transfer";
+
+const MAIN_PURSE_EXISTS: &str = "main_purse_exists";
+const CREATED_PURSE_EXISTS: &str = "created_purse_exists";
+const CREATED_PURSE_BALANCE: &str = "created_purse_balance";
+const NOT_A_PURSE_EXISTS: &str = "not_a_purse_exists";
+const NOT_A_PURSE_BALANCE: &str = "not_a_purse_balance";
+const TRANSFER_RESULT: &str = "transfer_result";
+const MINT_TRANSFER_RESULT: &str = "mint_transfer_result";
+const NOT_A_PURSE_EXISTS_AFTER_TRANSFERS: &str = "not_a_purse_exists_after_transfers";
+
+/// Checks each of the mint's entry points against a new purse and against a uref which isn't a
+/// purse, storing the results under the account's named keys.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount = U512::one();
+    let main_purse = account::get_main_purse();
+    let created_purse = system::create_purse();
+    // a valid uref, with full access rights, which the mint doesn't know
+    let not_a_purse: URef = storage::new_uref(());
+
+    system::transfer_from_purse_to_purse(main_purse, created_purse, amount).unwrap_or_revert();
+
+    runtime::put_key(
+        MAIN_PURSE_EXISTS,
+        storage::new_uref(system::purse_exists(main_purse)).into(),
+    );
+    runtime::put_key(
+        CREATED_PURSE_EXISTS,
+        storage::new_uref(system::purse_exists(created_purse)).into(),
+    );
+    runtime::put_key(
+        CREATED_PURSE_BALANCE,
+        storage::new_uref(system::get_balance(created_purse)).into(),
+    );
+    runtime::put_key(
+        NOT_A_PURSE_EXISTS,
+        storage::new_uref(system::purse_exists(not_a_purse)).into(),
+    );
+    runtime::put_key(
+        NOT_A_PURSE_BALANCE,
+        storage::new_uref(system::get_balance(not_a_purse)).into(),
+    );
+
+    let transfer_result = system::transfer_from_purse_to_purse(main_purse, not_a_purse, amount);
+    runtime::put_key(
+        TRANSFER_RESULT,
+        storage::new_uref(format!("{:?}", transfer_result)).into(),
+    );
+
+    let mint_transfer_result: Result<(), mint::Error> = runtime::call_contract(
+        system::get_mint(),
+        (METHOD_TRANSFER, main_purse, not_a_purse, amount),
+    );
+    runtime::put_key(
+        MINT_TRANSFER_RESULT,
+        storage::new_uref(format!("{:?}", mint_transfer_result)).into(),
+    );
+
+    runtime::put_key(
+        NOT_A_PURSE_EXISTS_AFTER_TRANSFERS,
+        storage::new_uref(system::purse_exists(not_a_purse)).into(),
+    );
+}
@@ -1,5 +1,5 @@
 #![no_std]
-#![no_main]
+#![cfg_attr(not(test), no_main)]
 
 extern crate alloc;
 
@@ -7,6 +7,7 @@ use alloc::string::String;
 
 use contract::{
     contract_api::{runtime, storage, system},
+    require,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{ApiError, CLValue, URef};
@@ -37,6 +38,8 @@ enum CustomError {
     MissingPurseNameArg = 4,
     InvalidPurseNameArg = 5,
     UnknownMethodName = 6,
+    PurseNameAlreadyExists = 7,
+    PurseNameNotFound = 8,
 }
 
 impl From<CustomError> for ApiError {
@@ -51,26 +54,38 @@ fn purse_name() -> String {
         .unwrap_or_revert_with(CustomError::InvalidPurseNameArg)
 }
 
-#[no_mangle]
-pub extern "C" fn apply_method() {
+fn dispatch() {
     let method_name: String = runtime::get_arg(ApplyArgs::MethodName as u32)
         .unwrap_or_revert_with(CustomError::MissingMethodNameArg)
         .unwrap_or_revert_with(CustomError::InvalidMethodNameArg);
     match method_name.as_str() {
         METHOD_ADD => {
             let purse_name = purse_name();
+            require!(
+                !runtime::has_key(&purse_name),
+                CustomError::PurseNameAlreadyExists
+            );
             let purse = system::create_purse();
             runtime::put_key(&purse_name, purse.into());
         }
         METHOD_REMOVE => {
             let purse_name = purse_name();
-            runtime::remove_key(&purse_name);
+            require!(
+                runtime::has_key(&purse_name),
+                CustomError::PurseNameNotFound
+            );
+            runtime::remove_key(&purse_name).unwrap_or_revert();
         }
         METHOD_VERSION => runtime::ret(CLValue::from_t(VERSION).unwrap_or_revert()),
         _ => runtime::revert(CustomError::UnknownMethodName),
     }
 }
 
+#[no_mangle]
+pub extern "C" fn apply_method() {
+    dispatch()
+}
+
 #[no_mangle]
 pub extern "C" fn call() {
     let uref: URef = runtime::get_arg(CallArgs::PurseHolderURef as u32)
@@ -84,3 +99,80 @@ pub extern "C" fn call() {
     let version_key = storage::new_uref(VERSION).into();
     runtime::put_key(METHOD_VERSION, version_key);
 }
+
+#[cfg(test)]
+mod tests {
+    use contract::{
+        assert_reverts_with,
+        test_support::{self, MockRuntime},
+    };
+    use types::{AccessRights, Key};
+
+    use super::*;
+
+    const PURSE_NAME: &str = "savings";
+
+    fn named_purse() -> Option<Key> {
+        test_support::with_mock_runtime(|runtime| runtime.named_keys().get(PURSE_NAME).cloned())
+    }
+
+    #[test]
+    fn should_add_purse() {
+        MockRuntime::new()
+            .with_args((METHOD_ADD, PURSE_NAME))
+            .install();
+        test_support::execute(dispatch).expect("should add purse");
+
+        let purse = named_purse()
+            .and_then(Key::into_uref)
+            .expect("should have purse");
+        let balance = test_support::with_mock_runtime(|runtime| runtime.balance(purse));
+        assert_eq!(balance, Some(0.into()));
+    }
+
+    #[test]
+    fn should_not_add_existing_purse() {
+        let purse = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+        MockRuntime::new()
+            .with_args((METHOD_ADD, PURSE_NAME))
+            .with_named_key(PURSE_NAME, purse.into())
+            .install();
+        assert_reverts_with!(CustomError::PurseNameAlreadyExists, dispatch);
+        assert_eq!(named_purse(), Some(purse.into()));
+    }
+
+    #[test]
+    fn should_remove_purse() {
+        let purse = URef::new([1; 32], AccessRights::READ_ADD_WRITE);
+        MockRuntime::new()
+            .with_args((METHOD_REMOVE, PURSE_NAME))
+            .with_named_key(PURSE_NAME, purse.into())
+            .install();
+        test_support::execute(dispatch).expect("should remove purse");
+        assert_eq!(named_purse(), None);
+    }
+
+    #[test]
+    fn should_not_remove_missing_purse() {
+        MockRuntime::new()
+            .with_args((METHOD_REMOVE, PURSE_NAME))
+            .install();
+        assert_reverts_with!(CustomError::PurseNameNotFound, dispatch);
+    }
+
+    #[test]
+    fn should_return_version() {
+        MockRuntime::new().with_args((METHOD_VERSION,)).install();
+        let returned = test_support::execute(dispatch).expect("should return version");
+        assert_eq!(returned, Some(CLValue::from_t(VERSION).unwrap()));
+    }
+
+    #[test]
+    fn should_revert_on_unknown_or_missing_method() {
+        MockRuntime::new().with_args(("transfer",)).install();
+        assert_reverts_with!(CustomError::UnknownMethodName, dispatch);
+
+        MockRuntime::new().install();
+        assert_reverts_with!(CustomError::MissingMethodNameArg, dispatch);
+    }
+}
@@ -7,6 +7,7 @@ use alloc::{collections::BTreeMap, string::String};
 
 use contract::{
     contract_api::{runtime, storage, system},
+    require,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use types::{ApiError, CLValue};
@@ -30,6 +31,7 @@ enum CustomError {
     MissingPurseNameArg = 2,
     InvalidPurseNameArg = 3,
     UnknownMethodName = 4,
+    PurseNameAlreadyExists = 5,
 }
 
 fn purse_name() -> String {
@@ -46,6 +48,10 @@ pub extern "C" fn apply_method() {
     match method_name.as_str() {
         METHOD_ADD => {
             let purse_name = purse_name();
+            require!(
+                !runtime::has_key(&purse_name),
+                CustomError::PurseNameAlreadyExists
+            );
             let purse = system::create_purse();
             runtime::put_key(&purse_name, purse.into());
         }
@@ -66,4 +72,7 @@ pub extern "C" fn call() {
     // set version
     let version_key = storage::new_uref(VERSION).into();
     runtime::put_key(METHOD_VERSION, version_key);
+
+    // return the contract key to the caller, saving them from looking it up in the named keys
+    runtime::ret(CLValue::from_t(key).unwrap_or_revert())
 }
@@ -0,0 +1,22 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const VALUE: u64 = 42;
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let name: String = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    runtime::put_key(&name, storage::new_uref(VALUE).into());
+}
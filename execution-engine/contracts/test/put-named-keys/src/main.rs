@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::format;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, Key, KEY_HASH_LENGTH};
+
+enum Arg {
+    Start = 0,
+    Count,
+}
+
+/// Puts the named keys "key-{start:05}" through "key-{start + count - 1:05}" under the account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let start: u32 = runtime::get_arg(Arg::Start as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let count: u32 = runtime::get_arg(Arg::Count as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    for i in start..start + count {
+        let mut hash = [0u8; KEY_HASH_LENGTH];
+        hash[..4].copy_from_slice(&i.to_le_bytes());
+        runtime::put_key(&format!("key-{:05}", i), Key::Hash(hash));
+    }
+}
@@ -0,0 +1,9 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::account, unwrap_or_revert::UnwrapOrRevert};
+
+#[no_mangle]
+pub extern "C" fn call() {
+    account::rotate_main_purse().unwrap_or_revert();
+}
@@ -0,0 +1,56 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, BlockTime};
+
+const CONTRACT_NAME: &str = "scheduled_call_stored";
+const ENTRY_FUNCTION_NAME: &str = "callback";
+const CALLED_NAME: &str = "called";
+
+#[repr(u16)]
+enum Args {
+    NotBefore = 0,
+    Value = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    ZeroValue = 0,
+}
+
+/// Records `value` under a named key of the stored contract, or reverts if it is zero.
+#[no_mangle]
+pub extern "C" fn callback() {
+    let value: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    if value == 0 {
+        runtime::revert(ApiError::User(CustomError::ZeroValue as u16));
+    }
+    runtime::put_key(CALLED_NAME, storage::new_uref(value).into());
+}
+
+/// Stores `callback` at a hash, and schedules a call to it passing `value` at `not_before`.
+#[no_mangle]
+pub extern "C" fn call() {
+    let not_before: u64 = runtime::get_arg(Args::NotBefore as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let value: u64 = runtime::get_arg(Args::Value as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    let contract_ref = storage::store_function_at_hash(ENTRY_FUNCTION_NAME, BTreeMap::new());
+    runtime::put_key(CONTRACT_NAME, contract_ref.clone().into());
+
+    runtime::schedule_call(contract_ref, (value,), BlockTime::from_millis(not_before))
+        .unwrap_or_revert();
+}
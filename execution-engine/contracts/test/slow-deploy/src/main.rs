@@ -0,0 +1,31 @@
+#![no_std]
+#![no_main]
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::ApiError;
+
+const LARGE_PRIME: u64 = 0xffff_fffb;
+
+#[repr(u16)]
+enum Error {
+    MissingIterations = 0,
+    InvalidIterations,
+}
+
+/// Burns time by running a modular exponentiation loop for the requested number of iterations.
+#[no_mangle]
+pub extern "C" fn call() {
+    let iterations: u64 = runtime::get_arg(0)
+        .unwrap_or_revert_with(ApiError::User(Error::MissingIterations as u16))
+        .unwrap_or_revert_with(ApiError::User(Error::InvalidIterations as u16));
+
+    let mut result: u64 = 42;
+    for _ in 0..iterations {
+        result *= 42;
+        result %= LARGE_PRIME;
+    }
+
+    if result == 0 {
+        runtime::revert(ApiError::Unhandled);
+    }
+}
@@ -0,0 +1,86 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::collections::BTreeMap;
+use core::cmp;
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    system_contract_names::pos::{METHOD_GET_PAYMENT_PURSE, METHOD_SET_REFUND_PURSE},
+    ApiError, ContractRef, URef, U512,
+};
+
+const CONTRACT_NAME: &str = "sponsor_stored";
+const ENTRY_FUNCTION_NAME: &str = "pay";
+const SPONSOR_PURSE_NAME: &str = "sponsor_purse";
+
+#[repr(u16)]
+enum Error {
+    MissingSponsorPurse = 0,
+    InvalidSponsorPurse,
+    SponsorPurseBalanceNotFound,
+}
+
+impl Into<ApiError> for Error {
+    fn into(self) -> ApiError {
+        ApiError::User(self as u16)
+    }
+}
+
+enum Arg {
+    Amount = 0,
+}
+
+fn get_amount_arg() -> U512 {
+    runtime::get_arg(Arg::Amount as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument)
+}
+
+fn get_payment_purse(pos: ContractRef) -> URef {
+    runtime::call_contract(pos, (METHOD_GET_PAYMENT_PURSE,))
+}
+
+fn set_refund_purse(pos: ContractRef, purse: URef) {
+    runtime::call_contract(pos, (METHOD_SET_REFUND_PURSE, purse))
+}
+
+/// Pays for the deploy from the sponsor purse, up to the balance of the sponsor purse, and claims
+/// the refund for the sponsor purse.  Called as payment in the context of this contract.
+#[no_mangle]
+pub extern "C" fn pay() {
+    let amount = get_amount_arg();
+    let sponsor_purse = runtime::get_key(SPONSOR_PURSE_NAME)
+        .unwrap_or_revert_with(Error::MissingSponsorPurse)
+        .into_uref()
+        .unwrap_or_revert_with(Error::InvalidSponsorPurse);
+    let sponsor_balance = system::get_balance(sponsor_purse)
+        .unwrap_or_revert_with(Error::SponsorPurseBalanceNotFound);
+
+    let pos = system::get_proof_of_stake();
+    let payment_purse = get_payment_purse(pos.clone());
+    let payment_amount = cmp::min(amount, sponsor_balance);
+    system::transfer_from_purse_to_purse(sponsor_purse, payment_purse, payment_amount)
+        .unwrap_or_revert();
+    set_refund_purse(pos, sponsor_purse);
+}
+
+/// Stores the sponsor contract at a hash, with a sponsor purse funded with the given amount from
+/// the account's main purse.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount = get_amount_arg();
+    let sponsor_purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), sponsor_purse, amount)
+        .unwrap_or_revert();
+
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(SPONSOR_PURSE_NAME.into(), sponsor_purse.into());
+    let pointer = storage::store_function_at_hash(ENTRY_FUNCTION_NAME, named_keys);
+    runtime::put_key(CONTRACT_NAME, pointer.into());
+}
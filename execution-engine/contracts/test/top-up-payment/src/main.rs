@@ -0,0 +1,52 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{account, runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{
+    system_contract_names::pos::METHOD_GET_PAYMENT_PURSE, ApiError, ContractRef, URef, U512,
+};
+
+const PAYMENT_PURSE_BALANCE_KEY: &str = "payment_purse_balance";
+
+enum Arg {
+    Amount = 0,
+}
+
+fn get_payment_purse(pos: ContractRef) -> URef {
+    runtime::call_contract(pos, (METHOD_GET_PAYMENT_PURSE,))
+}
+
+/// Deposits into the payment purse only the difference between `amount` and the amount already
+/// deposited.
+fn top_up(amount: U512) {
+    let deposited = system::payment_purse_balance();
+    if deposited >= amount {
+        return;
+    }
+    let payment_purse = get_payment_purse(system::get_proof_of_stake());
+    system::transfer_from_purse_to_purse(
+        account::get_main_purse(),
+        payment_purse,
+        amount - deposited,
+    )
+    .unwrap_or_revert();
+}
+
+/// Pays `amount` in two installments, then tops up to `amount` once more, which must not deposit
+/// anything.  The final balance of the payment purse is stored under a named key of the account.
+#[no_mangle]
+pub extern "C" fn call() {
+    let amount: U512 = runtime::get_arg(Arg::Amount as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    top_up(amount / 2);
+    top_up(amount);
+    top_up(amount);
+
+    let balance = system::payment_purse_balance();
+    runtime::put_key(PAYMENT_PURSE_BALANCE_KEY, storage::new_uref(balance).into());
+}
@@ -0,0 +1,49 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use types::{ApiError, Key};
+
+const CONTRACT_NAME: &str = "transient_storage_stored";
+const VALUES_NAME: &str = "values";
+const SUM_NAME: &str = "sum";
+
+#[repr(u16)]
+enum Args {
+    Count = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingContractKey = 0,
+    MissingSum = 1,
+    WrongSum = 2,
+}
+
+/// Passes `count` values to the stored contract through transient storage, and checks the sum it
+/// passes back the same way.
+#[no_mangle]
+pub extern "C" fn call() {
+    let count: u64 = runtime::get_arg(Args::Count as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+    let contract_ref = runtime::get_key(CONTRACT_NAME)
+        .and_then(Key::to_contract_ref)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKey as u16));
+
+    let values: Vec<u64> = (0..count).collect();
+    let expected_sum: u64 = values.iter().sum();
+    runtime::put_transient(VALUES_NAME, values);
+
+    runtime::call_contract::<_, ()>(contract_ref, ());
+
+    let sum: u64 = runtime::get_transient(SUM_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingSum as u16));
+    if sum != expected_sum {
+        runtime::revert(ApiError::User(CustomError::WrongSum as u16));
+    }
+}
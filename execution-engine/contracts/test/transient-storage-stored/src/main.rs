@@ -0,0 +1,41 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+const ENTRY_FUNCTION_NAME: &str = "apply_method";
+const CONTRACT_NAME: &str = "transient_storage_stored";
+const VALUES_NAME: &str = "values";
+const SUM_NAME: &str = "sum";
+
+#[repr(u16)]
+enum CustomError {
+    MissingValues = 0,
+}
+
+/// Sums the values left in transient storage by the caller, and leaves the sum there in turn.
+#[no_mangle]
+pub extern "C" fn apply_method() {
+    let values: Vec<u64> = runtime::get_transient(VALUES_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingValues as u16));
+    let sum: u64 = values.iter().sum();
+    runtime::put_transient(SUM_NAME, sum);
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let key = storage::store_function(ENTRY_FUNCTION_NAME, BTreeMap::new())
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant)
+        .into();
+
+    runtime::put_key(CONTRACT_NAME, key);
+}
@@ -0,0 +1,46 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+const CONTRACT_NAME: &str = "try_call_contract_stored";
+const RESULT_KEY_NAME: &str = "result";
+const ERROR_KEY_NAME: &str = "error";
+
+#[repr(u16)]
+enum Args {
+    MethodName = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodNameArg = 0,
+    InvalidMethodNameArg = 1,
+    MissingContractKey = 2,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodNameArg as u16));
+    let contract_key: Key = runtime::get_key(CONTRACT_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKey as u16));
+    let contract_ref = contract_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    // The callee reverting doesn't prevent the caller from carrying on and modifying global state.
+    match runtime::try_call_contract::<_, u64>(contract_ref, (method_name,)) {
+        Ok(value) => runtime::put_key(RESULT_KEY_NAME, storage::new_uref(value).into()),
+        Err(error) => runtime::put_key(ERROR_KEY_NAME, storage::new_uref(u32::from(error)).into()),
+    }
+}
@@ -0,0 +1,71 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, Key};
+
+const ENTRY_FUNCTION_NAME: &str = "apply_method";
+const CONTRACT_NAME: &str = "try_call_contract_stored";
+const VALUE_KEY_NAME: &str = "value";
+const WRITTEN_KEY_NAME: &str = "written";
+const METHOD_SUCCEED: &str = "succeed";
+const METHOD_REVERT: &str = "revert";
+const INITIAL_VALUE: u64 = 0;
+const WRITTEN_VALUE: u64 = 42;
+const REVERT_ERROR: u16 = 100;
+
+#[repr(u16)]
+enum Args {
+    MethodName = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodNameArg = 0,
+    InvalidMethodNameArg = 1,
+    UnknownMethodName = 2,
+    MissingValueKey = 3,
+}
+
+#[no_mangle]
+pub extern "C" fn apply_method() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodNameArg as u16));
+
+    // Both methods modify global state before completing, so that a revert has something to discard
+    let value_uref = runtime::get_key(VALUE_KEY_NAME)
+        .and_then(Key::into_uref)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingValueKey as u16));
+    storage::write(value_uref, WRITTEN_VALUE);
+    runtime::put_key(WRITTEN_KEY_NAME, storage::new_uref(WRITTEN_VALUE).into());
+
+    match method_name.as_str() {
+        METHOD_SUCCEED => runtime::ret(CLValue::from_t(WRITTEN_VALUE).unwrap_or_revert()),
+        METHOD_REVERT => runtime::revert(ApiError::User(REVERT_ERROR)),
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethodName as u16)),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(
+        String::from(VALUE_KEY_NAME),
+        storage::new_uref(INITIAL_VALUE).into(),
+    );
+
+    let key = storage::store_function(ENTRY_FUNCTION_NAME, named_keys)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant)
+        .into();
+
+    runtime::put_key(CONTRACT_NAME, key);
+}
@@ -0,0 +1,57 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{account, runtime, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, U512};
+
+const TRANSFER_AMOUNT: u64 = 100;
+
+#[repr(u16)]
+enum Args {
+    ContractName = 0,
+    ReadOnly = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingContractNameArg = 0,
+    InvalidContractNameArg = 1,
+    MissingReadOnlyArg = 2,
+    InvalidReadOnlyArg = 3,
+    MissingContractKey = 4,
+}
+
+/// Funds a new purse and passes it to the named stored contract, either with full rights or as a
+/// read-only view.
+#[no_mangle]
+pub extern "C" fn call() {
+    let contract_name: String = runtime::get_arg(Args::ContractName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidContractNameArg as u16));
+    let read_only: bool = runtime::get_arg(Args::ReadOnly as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingReadOnlyArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidReadOnlyArg as u16));
+    let contract_ref = runtime::get_key(&contract_name)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKey as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    let amount = U512::from(TRANSFER_AMOUNT);
+    let purse = system::create_purse();
+    system::transfer_from_purse_to_purse(account::get_main_purse(), purse, amount)
+        .unwrap_or_revert();
+
+    let passed_purse = if read_only {
+        purse.as_read_only()
+    } else {
+        purse
+    };
+    runtime::call_contract::<_, ()>(contract_ref, (passed_purse, amount));
+}
@@ -0,0 +1,75 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{AccessRights, ApiError, Key, URef, U512};
+
+const TRANSFER_FUNCTION_NAME: &str = "transfer_from";
+const ESCALATE_FUNCTION_NAME: &str = "escalate";
+
+#[repr(u16)]
+enum Args {
+    Purse = 0,
+    Amount = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingPurseArg = 0,
+    InvalidPurseArg = 1,
+    MissingAmountArg = 2,
+    InvalidAmountArg = 3,
+    MissingTransferContract = 4,
+}
+
+fn purse_and_amount() -> (URef, U512) {
+    let purse: URef = runtime::get_arg(Args::Purse as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingPurseArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidPurseArg as u16));
+    let amount: U512 = runtime::get_arg(Args::Amount as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingAmountArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidAmountArg as u16));
+    (purse, amount)
+}
+
+/// Transfers `amount` out of the given purse into a new purse, using whatever rights the caller
+/// passed on the purse.
+#[no_mangle]
+pub extern "C" fn transfer_from() {
+    let (source, amount) = purse_and_amount();
+    let target = system::create_purse();
+    system::transfer_from_purse_to_purse(source, target, amount).unwrap_or_revert();
+}
+
+/// Passes the given purse on to `transfer_from`, claiming full rights on it regardless of the
+/// rights this contract was given.
+#[no_mangle]
+pub extern "C" fn escalate() {
+    let (purse, amount) = purse_and_amount();
+    let transfer_contract = runtime::get_key(TRANSFER_FUNCTION_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingTransferContract as u16))
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+    let escalated_purse = purse.with_access_rights(AccessRights::READ_ADD_WRITE);
+    runtime::call_contract::<_, ()>(transfer_contract, (escalated_purse, amount));
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let transfer_key: Key =
+        storage::store_function_at_hash(TRANSFER_FUNCTION_NAME, BTreeMap::new()).into();
+    runtime::put_key(TRANSFER_FUNCTION_NAME, transfer_key);
+
+    let mut escalate_named_keys: BTreeMap<String, Key> = BTreeMap::new();
+    escalate_named_keys.insert(String::from(TRANSFER_FUNCTION_NAME), transfer_key);
+    let escalate_key: Key =
+        storage::store_function_at_hash(ESCALATE_FUNCTION_NAME, escalate_named_keys).into();
+    runtime::put_key(ESCALATE_FUNCTION_NAME, escalate_key);
+}
@@ -0,0 +1,63 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, Key};
+
+const CONTRACT_NAME: &str = "view_function_stored";
+const RESULT_KEY_NAME: &str = "result";
+const ERROR_KEY_NAME: &str = "error";
+const METHOD_READ: &str = "read";
+const METHOD_NESTED_WRITE: &str = "nested_write";
+
+#[repr(u16)]
+enum Args {
+    MethodName = 0,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodNameArg = 0,
+    InvalidMethodNameArg = 1,
+    MissingContractKey = 2,
+    UnexpectedSuccess = 3,
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodNameArg as u16));
+    let contract_key: Key = runtime::get_key(CONTRACT_NAME)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKey as u16));
+    let contract_ref = contract_key
+        .to_contract_ref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant);
+
+    let result: Result<(), ApiError> = match method_name.as_str() {
+        METHOD_READ => {
+            let value: u64 =
+                runtime::call_contract_read_only(contract_ref, (method_name,)).unwrap_or_revert();
+            runtime::put_key(RESULT_KEY_NAME, storage::new_uref(value).into());
+            return;
+        }
+        METHOD_NESTED_WRITE => {
+            runtime::call_contract_read_only(contract_ref, (method_name, contract_key))
+        }
+        _ => runtime::call_contract_read_only(contract_ref, (method_name,)),
+    };
+
+    // The callee's failure to modify global state doesn't prevent the caller from doing so.
+    let error = match result {
+        Ok(()) => runtime::revert(ApiError::User(CustomError::UnexpectedSuccess as u16)),
+        Err(error) => error,
+    };
+    runtime::put_key(ERROR_KEY_NAME, storage::new_uref(u32::from(error)).into());
+}
@@ -0,0 +1,91 @@
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String};
+
+use contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::{ApiError, CLValue, ContractRef, Key};
+
+const ENTRY_FUNCTION_NAME: &str = "apply_method";
+const CONTRACT_NAME: &str = "view_function_stored";
+const VALUE_KEY_NAME: &str = "value";
+const WRITTEN_KEY_NAME: &str = "written";
+const METHOD_READ: &str = "read";
+const METHOD_WRITE: &str = "write";
+const METHOD_CREATE_PURSE: &str = "create_purse";
+const METHOD_NESTED_WRITE: &str = "nested_write";
+const VALUE: u64 = 42;
+
+#[repr(u16)]
+enum Args {
+    MethodName = 0,
+    ContractKey = 1,
+}
+
+#[repr(u16)]
+enum CustomError {
+    MissingMethodNameArg = 0,
+    InvalidMethodNameArg = 1,
+    MissingContractKeyArg = 2,
+    InvalidContractKeyArg = 3,
+    UnknownMethodName = 4,
+    MissingValueKey = 5,
+    MissingValue = 6,
+}
+
+#[no_mangle]
+pub extern "C" fn apply_method() {
+    let method_name: String = runtime::get_arg(Args::MethodName as u32)
+        .unwrap_or_revert_with(ApiError::User(CustomError::MissingMethodNameArg as u16))
+        .unwrap_or_revert_with(ApiError::User(CustomError::InvalidMethodNameArg as u16));
+    match method_name.as_str() {
+        METHOD_READ => {
+            let value_uref = runtime::get_key(VALUE_KEY_NAME)
+                .and_then(Key::into_uref)
+                .unwrap_or_revert_with(ApiError::User(CustomError::MissingValueKey as u16));
+            let value: u64 = storage::read(value_uref)
+                .unwrap_or_revert()
+                .unwrap_or_revert_with(ApiError::User(CustomError::MissingValue as u16));
+            runtime::ret(CLValue::from_t(value).unwrap_or_revert())
+        }
+        METHOD_WRITE => {
+            let written_uref = storage::new_uref(VALUE);
+            runtime::put_key(WRITTEN_KEY_NAME, written_uref.into());
+        }
+        METHOD_CREATE_PURSE => {
+            let _purse = system::create_purse();
+        }
+        METHOD_NESTED_WRITE => {
+            let contract_key: Key = runtime::get_arg(Args::ContractKey as u32)
+                .unwrap_or_revert_with(ApiError::User(CustomError::MissingContractKeyArg as u16))
+                .unwrap_or_revert_with(ApiError::User(CustomError::InvalidContractKeyArg as u16));
+            let contract_ref = contract_key
+                .to_contract_ref()
+                .unwrap_or_revert_with(ApiError::User(CustomError::InvalidContractKeyArg as u16));
+            // A plain call from a read-only context is still read-only.
+            runtime::call_contract::<_, ()>(contract_ref, (METHOD_WRITE,));
+        }
+        _ => runtime::revert(ApiError::User(CustomError::UnknownMethodName as u16)),
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert(
+        String::from(VALUE_KEY_NAME),
+        storage::new_uref(VALUE).into(),
+    );
+
+    let key = storage::store_function(ENTRY_FUNCTION_NAME, named_keys)
+        .into_uref()
+        .unwrap_or_revert_with(ApiError::UnexpectedContractRefVariant)
+        .into();
+
+    runtime::put_key(CONTRACT_NAME, key);
+}
@@ -0,0 +1,25 @@
+#![no_std]
+#![no_main]
+
+use contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use types::ApiError;
+
+#[repr(u16)]
+enum Args {
+    Count = 0,
+}
+
+/// Writes `count` values, each under a new URef.
+#[no_mangle]
+pub extern "C" fn call() {
+    let count: u64 = runtime::get_arg(Args::Count as u32)
+        .unwrap_or_revert_with(ApiError::MissingArgument)
+        .unwrap_or_revert_with(ApiError::InvalidArgument);
+
+    for value in 0..count {
+        storage::new_uref(value);
+    }
+}
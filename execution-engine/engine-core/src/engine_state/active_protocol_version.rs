@@ -0,0 +1,59 @@
+//! Every state root committed by genesis or an upgrade records the protocol version active at that
+//! root, so that the versioned configuration (e.g. the Wasm costs) used against a root can be
+//! resolved from the root itself rather than from whichever versions the node has seen so far.
+
+use lazy_static::lazy_static;
+
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue, TypeMismatch};
+use engine_storage::global_state::StateReader;
+use types::{CLValue, Key, ProtocolVersion};
+
+use crate::{engine_state, execution, tracking_copy::TrackingCopy};
+
+lazy_static! {
+    /// The key under which the active protocol version is stored.
+    pub static ref ACTIVE_PROTOCOL_VERSION_KEY: Key =
+        engine_state::engine_record_key(b"active-protocol-version");
+}
+
+/// Records `protocol_version` as the version active at the state root resulting from the effects
+/// of `tracking_copy`.
+pub(crate) fn write<R>(tracking_copy: &mut TrackingCopy<R>, protocol_version: ProtocolVersion)
+where
+    R: StateReader<Key, StoredValue>,
+{
+    let version = protocol_version.value();
+    let cl_value = CLValue::from_t((version.major, version.minor, version.patch))
+        .expect("should convert protocol version to CLValue");
+    tracking_copy.write(*ACTIVE_PROTOCOL_VERSION_KEY, StoredValue::CLValue(cl_value));
+}
+
+/// Returns the protocol version active at the state root read by `reader`, or `None` if the root
+/// doesn't record one, i.e. it was committed before versions were recorded.
+///
+/// This reads from the root directly rather than through a [`TrackingCopy`], so that the lookup
+/// doesn't show up in the effects of an execution.
+pub(crate) fn read<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+) -> Result<Option<ProtocolVersion>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let cl_value = match reader
+        .read(correlation_id, &*ACTIVE_PROTOCOL_VERSION_KEY)
+        .map_err(Into::into)?
+    {
+        Some(StoredValue::CLValue(cl_value)) => cl_value,
+        Some(other) => {
+            return Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "CLValue".to_string(),
+                other.type_name(),
+            )))
+        }
+        None => return Ok(None),
+    };
+    let (major, minor, patch) = cl_value.into_t::<(u32, u32, u32)>()?;
+    Ok(Some(ProtocolVersion::from_parts(major, minor, patch)))
+}
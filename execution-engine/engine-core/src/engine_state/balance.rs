@@ -0,0 +1,133 @@
+//! Reads the balance of an account's main purse in one request, rather than by querying the
+//! account, the mint's entry for its purse and the purse's balance one after another.
+
+use failure::Fail;
+
+use engine_shared::{
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_storage::global_state::StateReader;
+use types::{account::PublicKey, bytesrepr::ToBytes, CLType, Key, ProtocolVersion, URef, U512};
+
+use crate::{engine_state::error::Error, execution};
+
+/// A request for the balance of the main purse of an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceRequest {
+    state_hash: Blake2bHash,
+    public_key: PublicKey,
+    protocol_version: ProtocolVersion,
+}
+
+impl BalanceRequest {
+    pub fn new(
+        state_hash: Blake2bHash,
+        public_key: PublicKey,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        BalanceRequest {
+            state_hash,
+            public_key,
+            protocol_version,
+        }
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+}
+
+/// The read of the balance of an account's main purse failed.
+///
+/// Each of the variants but the first names the hop at which the read broke off, along with the
+/// key which couldn't be read.
+#[derive(Fail, Debug)]
+pub enum BalanceError {
+    #[fail(display = "Root not found: {}", _0)]
+    RootNotFound(Blake2bHash),
+    #[fail(display = "Account not found at {}", _0)]
+    AccountNotFound(Key),
+    /// The mint doesn't record the balance uref of the account's main purse.
+    #[fail(display = "Mint records no balance uref of the main purse at {}", _0)]
+    PurseURefMissing(Key),
+    /// The mint records the balance uref of the account's main purse, but it holds no balance.
+    #[fail(display = "Mint balance of the main purse not found at {}", _0)]
+    MintBalanceMissing(Key),
+    #[fail(display = "{}", _0)]
+    Engine(Error),
+}
+
+impl From<Error> for BalanceError {
+    fn from(error: Error) -> Self {
+        BalanceError::Engine(error)
+    }
+}
+
+impl From<execution::Error> for BalanceError {
+    fn from(error: execution::Error) -> Self {
+        BalanceError::Engine(Error::Exec(error))
+    }
+}
+
+/// Reads the balance of the main purse of the account of `public_key`, where `mint` is the mint
+/// contract.
+pub(crate) fn read_main_purse_balance<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+    mint: URef,
+    public_key: PublicKey,
+) -> Result<Motes, BalanceError>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let account_key = Key::Account(public_key);
+    let main_purse = match read(correlation_id, reader, &account_key)? {
+        Some(StoredValue::Account(account)) => account.main_purse(),
+        _ => return Err(BalanceError::AccountNotFound(account_key)),
+    };
+
+    let purse_bytes = main_purse
+        .addr()
+        .to_bytes()
+        .map_err(execution::Error::from)?;
+    let balance_mapping_key = Key::local(mint.addr(), &purse_bytes);
+    let balance_key: Key = match read(correlation_id, reader, &balance_mapping_key)? {
+        Some(StoredValue::CLValue(cl_value)) if *cl_value.cl_type() == CLType::Key => {
+            cl_value.into_t().map_err(execution::Error::from)?
+        }
+        _ => return Err(BalanceError::PurseURefMissing(balance_mapping_key)),
+    };
+
+    let balance_key = balance_key.normalize();
+    let balance: U512 = match read(correlation_id, reader, &balance_key)? {
+        Some(StoredValue::CLValue(cl_value)) if *cl_value.cl_type() == CLType::U512 => {
+            cl_value.into_t().map_err(execution::Error::from)?
+        }
+        _ => return Err(BalanceError::MintBalanceMissing(balance_key)),
+    };
+
+    Ok(Motes::new(balance))
+}
+
+fn read<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+    key: &Key,
+) -> Result<Option<StoredValue>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    reader.read(correlation_id, key).map_err(Into::into)
+}
@@ -0,0 +1,273 @@
+//! Notifications of successful commits for components embedded in the same process as the engine,
+//! e.g. indexers of balances or deploy results.
+//!
+//! Every subscriber has its own bounded queue.  Notifying never waits for a subscriber: when a
+//! subscriber's queue is full, its oldest notification is dropped and counted as lagged, so a slow
+//! subscriber can never delay a commit.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{Arc, Condvar, Mutex, MutexGuard, Weak},
+    time::{Duration, Instant},
+};
+
+use engine_shared::newtypes::Blake2bHash;
+use types::{account::PublicKey, Key, ProtocolVersion, U512};
+
+/// The default number of notifications queued for a subscriber before the oldest is dropped.
+pub const DEFAULT_COMMIT_NOTIFICATION_CAPACITY: usize = 64;
+
+/// A successful commit of effects onto global state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitNotification {
+    pub pre_state: Blake2bHash,
+    pub post_state: Blake2bHash,
+    /// The keys the committed effects apply to, in ascending order.
+    pub modified_keys: Vec<Key>,
+    /// The stakes of the validators bonded at `post_state`.
+    pub bonded_validators: BTreeMap<PublicKey, U512>,
+    pub protocol_version: ProtocolVersion,
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    notifications: VecDeque<Arc<CommitNotification>>,
+    lagged: u64,
+    closed: bool,
+}
+
+#[derive(Debug, Default)]
+struct Subscription {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+impl Subscription {
+    fn lock(&self) -> MutexGuard<Queue> {
+        self.queue
+            .lock()
+            .expect("should lock commit notification queue")
+    }
+}
+
+#[derive(Debug)]
+struct Subscriptions {
+    subscriptions: Mutex<Vec<Weak<Subscription>>>,
+    capacity: usize,
+}
+
+impl Subscriptions {
+    fn lock(&self) -> MutexGuard<Vec<Weak<Subscription>>> {
+        self.subscriptions
+            .lock()
+            .expect("should lock commit subscriptions")
+    }
+}
+
+/// Closes the queues of the remaining subscribers once the last engine sharing them is dropped.
+impl Drop for Subscriptions {
+    fn drop(&mut self) {
+        for subscription in self.lock().iter().filter_map(Weak::upgrade) {
+            subscription.lock().closed = true;
+            subscription.ready.notify_all();
+        }
+    }
+}
+
+/// Broadcasts [`CommitNotification`]s to the current subscribers.  Clones share the subscribers.
+#[derive(Clone, Debug)]
+pub struct CommitNotifier {
+    inner: Arc<Subscriptions>,
+}
+
+impl CommitNotifier {
+    /// Creates a notifier queuing at most `capacity` notifications for each subscriber.
+    pub fn new(capacity: usize) -> Self {
+        CommitNotifier {
+            inner: Arc::new(Subscriptions {
+                subscriptions: Default::default(),
+                capacity: capacity.max(1),
+            }),
+        }
+    }
+
+    /// Returns a receiver of the notifications of all commits from now on.
+    pub fn subscribe(&self) -> CommitReceiver {
+        let subscription = Arc::new(Subscription::default());
+        self.inner.lock().push(Arc::downgrade(&subscription));
+        CommitReceiver { subscription }
+    }
+
+    /// Returns `true` if any receiver is still subscribed, forgetting the dropped ones.
+    pub fn has_subscribers(&self) -> bool {
+        let mut subscriptions = self.inner.lock();
+        subscriptions.retain(|subscription| subscription.strong_count() > 0);
+        !subscriptions.is_empty()
+    }
+
+    /// Queues `notification` for every subscriber, dropping the oldest notification queued for a
+    /// subscriber whose queue is full.
+    pub fn notify(&self, notification: CommitNotification) {
+        let notification = Arc::new(notification);
+        let mut subscriptions = self.inner.lock();
+        subscriptions.retain(|subscription| match subscription.upgrade() {
+            Some(subscription) => {
+                let mut queue = subscription.lock();
+                if queue.notifications.len() >= self.inner.capacity {
+                    queue.notifications.pop_front();
+                    queue.lagged += 1;
+                }
+                queue.notifications.push_back(Arc::clone(&notification));
+                subscription.ready.notify_one();
+                true
+            }
+            None => false,
+        });
+    }
+}
+
+/// Receives the [`CommitNotification`]s of one subscriber, in the order of the commits.
+///
+/// Dropping the receiver unsubscribes it.
+#[derive(Debug)]
+pub struct CommitReceiver {
+    subscription: Arc<Subscription>,
+}
+
+impl CommitReceiver {
+    /// Returns the next notification, waiting for one if none is queued, or `None` once the
+    /// engine has been dropped and all queued notifications have been received.
+    pub fn recv(&self) -> Option<Arc<CommitNotification>> {
+        let mut queue = self.subscription.lock();
+        loop {
+            if let Some(notification) = queue.notifications.pop_front() {
+                return Some(notification);
+            }
+            if queue.closed {
+                return None;
+            }
+            queue = self
+                .subscription
+                .ready
+                .wait(queue)
+                .expect("should wait for commit notification");
+        }
+    }
+
+    /// Like [`recv`](CommitReceiver::recv), but returns `None` if no notification arrives within
+    /// `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Arc<CommitNotification>> {
+        let deadline = Instant::now() + timeout;
+        let mut queue = self.subscription.lock();
+        loop {
+            if let Some(notification) = queue.notifications.pop_front() {
+                return Some(notification);
+            }
+            let now = Instant::now();
+            if queue.closed || now >= deadline {
+                return None;
+            }
+            queue = self
+                .subscription
+                .ready
+                .wait_timeout(queue, deadline - now)
+                .expect("should wait for commit notification")
+                .0;
+        }
+    }
+
+    /// Returns the next notification if one is queued, without waiting.
+    pub fn try_recv(&self) -> Option<Arc<CommitNotification>> {
+        self.subscription.lock().notifications.pop_front()
+    }
+
+    /// Returns the number of notifications dropped so far because this receiver fell behind.
+    pub fn lagged(&self) -> u64 {
+        self.subscription.lock().lagged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, thread, time::Duration};
+
+    use engine_shared::newtypes::Blake2bHash;
+    use types::{Key, ProtocolVersion};
+
+    use super::{CommitNotification, CommitNotifier};
+
+    fn notification(n: u8) -> CommitNotification {
+        CommitNotification {
+            pre_state: Blake2bHash::new(&[n]),
+            post_state: Blake2bHash::new(&[n + 1]),
+            modified_keys: vec![Key::Hash([n; 32])],
+            bonded_validators: BTreeMap::new(),
+            protocol_version: ProtocolVersion::V1_0_0,
+        }
+    }
+
+    #[test]
+    fn should_receive_notifications_in_order() {
+        let notifier = CommitNotifier::new(4);
+        let receiver = notifier.subscribe();
+        notifier.notify(notification(1));
+        notifier.notify(notification(2));
+
+        assert_eq!(*receiver.try_recv().unwrap(), notification(1));
+        assert_eq!(*receiver.recv().unwrap(), notification(2));
+        assert_eq!(receiver.try_recv(), None);
+        assert_eq!(receiver.lagged(), 0);
+    }
+
+    #[test]
+    fn should_drop_oldest_notifications_of_slow_subscriber() {
+        let notifier = CommitNotifier::new(2);
+        let slow_receiver = notifier.subscribe();
+        let fast_receiver = notifier.subscribe();
+        for n in 0..5 {
+            notifier.notify(notification(n));
+            assert_eq!(*fast_receiver.try_recv().unwrap(), notification(n));
+        }
+
+        assert_eq!(*slow_receiver.try_recv().unwrap(), notification(3));
+        assert_eq!(*slow_receiver.try_recv().unwrap(), notification(4));
+        assert_eq!(slow_receiver.try_recv(), None);
+        assert_eq!(slow_receiver.lagged(), 3);
+        assert_eq!(fast_receiver.lagged(), 0);
+    }
+
+    #[test]
+    fn should_forget_dropped_subscribers() {
+        let notifier = CommitNotifier::new(1);
+        assert!(!notifier.has_subscribers());
+        let receiver = notifier.subscribe();
+        assert!(notifier.has_subscribers());
+        drop(receiver);
+        assert!(!notifier.has_subscribers());
+    }
+
+    #[test]
+    fn should_close_receiver_when_notifier_is_dropped() {
+        let notifier = CommitNotifier::new(1);
+        let receiver = notifier.subscribe();
+        notifier.notify(notification(1));
+        let handle = thread::spawn(move || {
+            let first = receiver.recv();
+            let second = receiver.recv();
+            (first, second)
+        });
+        drop(notifier.clone());
+        drop(notifier);
+
+        let (first, second) = handle.join().unwrap();
+        assert_eq!(*first.unwrap(), notification(1));
+        assert_eq!(second, None);
+    }
+
+    #[test]
+    fn should_time_out_without_notification() {
+        let notifier = CommitNotifier::new(1);
+        let receiver = notifier.subscribe();
+        assert_eq!(receiver.recv_timeout(Duration::from_millis(10)), None);
+    }
+}
@@ -1,8 +1,16 @@
 use std::collections::BTreeSet;
 
-use types::account::PublicKey;
+use engine_shared::{gas::Gas, motes::Motes};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLValue, U512,
+};
 
-use crate::{engine_state::executable_deploy_item::ExecutableDeployItem, DeployHash};
+use crate::{
+    engine_state::{executable_deploy_item::ExecutableDeployItem, CONV_RATE, MAX_PAYMENT},
+    DeployHash,
+};
 
 type GasPrice = u64;
 
@@ -15,6 +23,13 @@ pub struct DeployItem {
     pub gas_price: GasPrice,
     pub authorization_keys: BTreeSet<PublicKey>,
     pub deploy_hash: DeployHash,
+    /// Whether this deploy is run from the system account.  Such deploys have no payment phase and
+    /// are only accepted from the node itself, never from users.
+    pub system_deploy: bool,
+    /// The names of the session args by position, an empty name leaving an arg unnamed.
+    pub session_arg_names: Vec<String>,
+    /// The names of the payment args by position, an empty name leaving an arg unnamed.
+    pub payment_arg_names: Vec<String>,
 }
 
 impl DeployItem {
@@ -34,6 +49,103 @@ impl DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
+            system_deploy: false,
+            session_arg_names: Vec::new(),
+            payment_arg_names: Vec::new(),
         }
     }
+
+    /// Marks this deploy as a system deploy, see [`DeployItem::system_deploy`].
+    pub fn with_system_deploy(mut self, system_deploy: bool) -> Self {
+        self.system_deploy = system_deploy;
+        self
+    }
+
+    /// Names the args of the session and payment, so that they can be read by name as well as by
+    /// position.
+    pub fn with_arg_names(
+        mut self,
+        session_arg_names: Vec<String>,
+        payment_arg_names: Vec<String>,
+    ) -> Self {
+        self.session_arg_names = session_arg_names;
+        self.payment_arg_names = payment_arg_names;
+        self
+    }
+
+    /// Returns the most gas executing this deploy may use, as declared by its payment.
+    ///
+    /// For standard payment this is the payment amount converted to gas.  The amount transferred by
+    /// custom payment code isn't known before executing it, so [`MAX_PAYMENT`] is used instead.
+    ///
+    /// System deploys aren't metered, so they declare no gas.
+    pub fn declared_gas_limit(&self) -> Gas {
+        if self.system_deploy {
+            return Gas::default();
+        }
+        let payment_amount = match &self.payment {
+            ExecutableDeployItem::ModuleBytes { module_bytes, args } if module_bytes.is_empty() => {
+                bytesrepr::deserialize::<Vec<CLValue>>(args.clone())
+                    .ok()
+                    .and_then(|args| args.into_iter().next())
+                    .and_then(|amount| amount.into_t::<U512>().ok())
+            }
+            _ => None,
+        }
+        .unwrap_or_else(|| U512::from(MAX_PAYMENT));
+        Gas::from_motes(Motes::new(payment_amount), CONV_RATE).unwrap_or_default()
+    }
+}
+
+impl ToBytes for DeployItem {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.address.to_bytes()?);
+        ret.append(&mut self.session.to_bytes()?);
+        ret.append(&mut self.payment.to_bytes()?);
+        ret.append(&mut self.gas_price.to_bytes()?);
+        ret.append(&mut self.authorization_keys.to_bytes()?);
+        ret.append(&mut self.deploy_hash.to_bytes()?);
+        ret.append(&mut self.system_deploy.to_bytes()?);
+        ret.append(&mut self.session_arg_names.to_bytes()?);
+        ret.append(&mut self.payment_arg_names.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.address.serialized_length()
+            + self.session.serialized_length()
+            + self.payment.serialized_length()
+            + self.gas_price.serialized_length()
+            + self.authorization_keys.serialized_length()
+            + self.deploy_hash.serialized_length()
+            + self.system_deploy.serialized_length()
+            + self.session_arg_names.serialized_length()
+            + self.payment_arg_names.serialized_length()
+    }
+}
+
+impl FromBytes for DeployItem {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (address, rem) = PublicKey::from_bytes(bytes)?;
+        let (session, rem) = ExecutableDeployItem::from_bytes(rem)?;
+        let (payment, rem) = ExecutableDeployItem::from_bytes(rem)?;
+        let (gas_price, rem) = GasPrice::from_bytes(rem)?;
+        let (authorization_keys, rem) = BTreeSet::<PublicKey>::from_bytes(rem)?;
+        let (deploy_hash, rem) = DeployHash::from_bytes(rem)?;
+        let (system_deploy, rem) = bool::from_bytes(rem)?;
+        let (session_arg_names, rem) = Vec::<String>::from_bytes(rem)?;
+        let (payment_arg_names, rem) = Vec::<String>::from_bytes(rem)?;
+        let deploy_item = DeployItem::new(
+            address,
+            session,
+            payment,
+            gas_price,
+            authorization_keys,
+            deploy_hash,
+        )
+        .with_system_deploy(system_deploy)
+        .with_arg_names(session_arg_names, payment_arg_names);
+        Ok((deploy_item, rem))
+    }
 }
@@ -1,9 +1,20 @@
+use std::time::Duration;
+
+use engine_storage::global_state::durability::Durability;
+
+use crate::engine_state::commit_notifications::DEFAULT_COMMIT_NOTIFICATION_CAPACITY;
+
 /// The runtime configuration of the execution engine
 #[derive(Debug, Copy, Clone, Default)]
 pub struct EngineConfig {
     // feature flags go here
     use_system_contracts: bool,
     enable_bonding: bool,
+    max_block_time_skew: Option<Duration>,
+    query_cache_capacity: Option<usize>,
+    mint_invariant_checks: Option<u64>,
+    commit_notification_capacity: Option<usize>,
+    durability: Durability,
 }
 
 impl EngineConfig {
@@ -29,4 +40,60 @@ impl EngineConfig {
         self.enable_bonding = enable_bonding;
         self
     }
+
+    /// How far ahead of the engine's clock a block time may be, or `None` if block times aren't
+    /// checked against the clock.
+    pub fn max_block_time_skew(self) -> Option<Duration> {
+        self.max_block_time_skew
+    }
+
+    pub fn with_max_block_time_skew(mut self, max_block_time_skew: Duration) -> EngineConfig {
+        self.max_block_time_skew = Some(max_block_time_skew);
+        self
+    }
+
+    /// The capacity of the query result cache in bytes, or `None` if query results aren't cached.
+    pub fn query_cache_capacity(self) -> Option<usize> {
+        self.query_cache_capacity
+    }
+
+    pub fn with_query_cache_capacity(mut self, query_cache_capacity: usize) -> EngineConfig {
+        self.query_cache_capacity = Some(query_cache_capacity);
+        self
+    }
+
+    /// Every how many commits the mint's purses are checked to hold exactly the total supply, or
+    /// `None` if they're never checked.
+    pub fn mint_invariant_checks(self) -> Option<u64> {
+        self.mint_invariant_checks
+    }
+
+    /// Checks the mint's invariant after every `every_nth_commit`th commit.  Walking all purses is
+    /// expensive, so this is for debugging only.
+    pub fn with_mint_invariant_checks(mut self, every_nth_commit: u64) -> EngineConfig {
+        self.mint_invariant_checks = Some(every_nth_commit.max(1));
+        self
+    }
+
+    /// The number of commit notifications queued for a subscriber before the oldest is dropped.
+    pub fn commit_notification_capacity(self) -> usize {
+        self.commit_notification_capacity
+            .unwrap_or(DEFAULT_COMMIT_NOTIFICATION_CAPACITY)
+    }
+
+    pub fn with_commit_notification_capacity(mut self, capacity: usize) -> EngineConfig {
+        self.commit_notification_capacity = Some(capacity);
+        self
+    }
+
+    /// How durably commits are written to storage, unless a commit asks for a different
+    /// durability.
+    pub fn durability(self) -> Durability {
+        self.durability
+    }
+
+    pub fn with_durability(mut self, durability: Durability) -> EngineConfig {
+        self.durability = durability;
+        self
+    }
 }
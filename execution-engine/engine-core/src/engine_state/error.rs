@@ -1,9 +1,9 @@
 use failure::Fail;
 
 use engine_shared::newtypes::Blake2bHash;
-use types::{bytesrepr, system_contract_errors::mint};
+use types::{account::PublicKey, bytesrepr, system_contract_errors::mint, BlockTime};
 
-use crate::execution;
+use crate::{engine_state::mint_invariants::MintInvariantViolation, execution};
 use types::ProtocolVersion;
 
 #[derive(Fail, Debug)]
@@ -12,10 +12,27 @@ pub enum Error {
     InvalidHashLength { expected: usize, actual: usize },
     #[fail(display = "Invalid public key length: expected {}, actual {}", _0, _1)]
     InvalidPublicKeyLength { expected: usize, actual: usize },
+    #[fail(display = "Invalid deploy item: {}", _0)]
+    InvalidDeployItem(String),
     #[fail(display = "Invalid protocol version: {}", _0)]
     InvalidProtocolVersion(ProtocolVersion),
+    #[fail(
+        display = "No configuration of protocol version {} found at state root {}",
+        protocol_version, state_hash
+    )]
+    ConfigNotFound {
+        protocol_version: ProtocolVersion,
+        state_hash: Blake2bHash,
+    },
+    #[fail(
+        display = "State root {} doesn't record its active protocol version",
+        _0
+    )]
+    ProtocolVersionNotRecorded(Blake2bHash),
     #[fail(display = "Invalid upgrade config")]
     InvalidUpgradeConfig,
+    #[fail(display = "Invalid account config")]
+    InvalidAccountConfig,
     #[fail(display = "Wasm preprocessing error: {}", _0)]
     WasmPreprocessing(engine_wasm_prep::PreprocessingError),
     #[fail(display = "Wasm serialization error: {:?}", _0)]
@@ -38,6 +55,38 @@ pub enum Error {
     Serialization(bytesrepr::Error),
     #[fail(display = "Mint error: {}", _0)]
     Mint(mint::Error),
+    #[fail(display = "Skipped as the block gas limit would be exceeded")]
+    BlockGasLimitExhausted,
+    #[fail(display = "Sponsor contracts can only be used as payment")]
+    SponsorContractAsSession,
+    #[fail(display = "Sponsor contract didn't set a refund purse")]
+    SponsorRefundPurseNotSet,
+    #[fail(
+        display = "Block time {} is before the genesis timestamp {}",
+        block_time, genesis_timestamp
+    )]
+    BlockTimeBeforeGenesis {
+        block_time: BlockTime,
+        genesis_timestamp: BlockTime,
+    },
+    #[fail(
+        display = "Block time {} is later than the latest accepted time {}",
+        block_time, latest
+    )]
+    BlockTimeTooFarInFuture {
+        block_time: BlockTime,
+        latest: BlockTime,
+    },
+    #[fail(display = "Mint invariant violated: {}", _0)]
+    MintInvariantViolated(MintInvariantViolation),
+    #[fail(display = "Proof of stake holds more than one bond entry for {}", _0)]
+    DuplicateValidatorEntry(PublicKey),
+    #[fail(display = "Proof of stake holds an invalid bond entry: {}", _0)]
+    InvalidValidatorEntry(String),
+    #[fail(display = "Authorization contract rejected the deploy: {}", _0)]
+    AuthorizationContractRejected(execution::Error),
+    #[fail(display = "Only system deploys may run as the system account, and no other account")]
+    InvalidSystemDeploy,
 }
 
 impl From<engine_wasm_prep::PreprocessingError> for Error {
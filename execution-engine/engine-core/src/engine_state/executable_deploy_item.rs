@@ -1,3 +1,12 @@
+use types::bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH};
+
+const MODULE_BYTES_TAG: u8 = 0;
+const STORED_CONTRACT_BY_HASH_TAG: u8 = 1;
+const STORED_CONTRACT_BY_NAME_TAG: u8 = 2;
+const STORED_CONTRACT_BY_UREF_TAG: u8 = 3;
+const SPONSOR_CONTRACT_BY_HASH_TAG: u8 = 4;
+const SPONSOR_CONTRACT_BY_NAME_TAG: u8 = 5;
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum ExecutableDeployItem {
     ModuleBytes {
@@ -16,6 +25,19 @@ pub enum ExecutableDeployItem {
         uref: Vec<u8>,
         args: Vec<u8>,
     },
+    /// A stored contract paying for the deploy from its own purse.  Unlike
+    /// [`ExecutableDeployItem::StoredContractByHash`], the contract is called in its own context,
+    /// i.e. with its own named keys, as if via `call_contract`.  Only valid as payment.
+    SponsorContractByHash {
+        hash: Vec<u8>,
+        args: Vec<u8>,
+    },
+    /// As [`ExecutableDeployItem::SponsorContractByHash`], with the contract looked up by `name`
+    /// in the named keys of the deploy's account.
+    SponsorContractByName {
+        name: String,
+        args: Vec<u8>,
+    },
 }
 
 impl ExecutableDeployItem {
@@ -25,6 +47,144 @@ impl ExecutableDeployItem {
             ExecutableDeployItem::StoredContractByHash { args, .. } => args,
             ExecutableDeployItem::StoredContractByName { args, .. } => args,
             ExecutableDeployItem::StoredContractByURef { args, .. } => args,
+            ExecutableDeployItem::SponsorContractByHash { args, .. } => args,
+            ExecutableDeployItem::SponsorContractByName { args, .. } => args,
+        }
+    }
+
+    pub fn args(&self) -> &[u8] {
+        match self {
+            ExecutableDeployItem::ModuleBytes { args, .. } => args,
+            ExecutableDeployItem::StoredContractByHash { args, .. } => args,
+            ExecutableDeployItem::StoredContractByName { args, .. } => args,
+            ExecutableDeployItem::StoredContractByURef { args, .. } => args,
+            ExecutableDeployItem::SponsorContractByHash { args, .. } => args,
+            ExecutableDeployItem::SponsorContractByName { args, .. } => args,
+        }
+    }
+
+    /// Returns whether this is a sponsor contract, paying for the deploy on behalf of its account.
+    pub fn is_sponsor_contract(&self) -> bool {
+        match self {
+            ExecutableDeployItem::SponsorContractByHash { .. }
+            | ExecutableDeployItem::SponsorContractByName { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl ToBytes for ExecutableDeployItem {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        match self {
+            ExecutableDeployItem::ModuleBytes { module_bytes, args } => {
+                ret.push(MODULE_BYTES_TAG);
+                ret.append(&mut module_bytes.to_bytes()?);
+                ret.append(&mut args.to_bytes()?);
+            }
+            ExecutableDeployItem::StoredContractByHash { hash, args } => {
+                ret.push(STORED_CONTRACT_BY_HASH_TAG);
+                ret.append(&mut hash.to_bytes()?);
+                ret.append(&mut args.to_bytes()?);
+            }
+            ExecutableDeployItem::StoredContractByName { name, args } => {
+                ret.push(STORED_CONTRACT_BY_NAME_TAG);
+                ret.append(&mut name.to_bytes()?);
+                ret.append(&mut args.to_bytes()?);
+            }
+            ExecutableDeployItem::StoredContractByURef { uref, args } => {
+                ret.push(STORED_CONTRACT_BY_UREF_TAG);
+                ret.append(&mut uref.to_bytes()?);
+                ret.append(&mut args.to_bytes()?);
+            }
+            ExecutableDeployItem::SponsorContractByHash { hash, args } => {
+                ret.push(SPONSOR_CONTRACT_BY_HASH_TAG);
+                ret.append(&mut hash.to_bytes()?);
+                ret.append(&mut args.to_bytes()?);
+            }
+            ExecutableDeployItem::SponsorContractByName { name, args } => {
+                ret.push(SPONSOR_CONTRACT_BY_NAME_TAG);
+                ret.append(&mut name.to_bytes()?);
+                ret.append(&mut args.to_bytes()?);
+            }
+        }
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                ExecutableDeployItem::ModuleBytes { module_bytes, args } => {
+                    module_bytes.serialized_length() + args.serialized_length()
+                }
+                ExecutableDeployItem::StoredContractByHash { hash, args }
+                | ExecutableDeployItem::SponsorContractByHash { hash, args } => {
+                    hash.serialized_length() + args.serialized_length()
+                }
+                ExecutableDeployItem::StoredContractByName { name, args }
+                | ExecutableDeployItem::SponsorContractByName { name, args } => {
+                    name.serialized_length() + args.serialized_length()
+                }
+                ExecutableDeployItem::StoredContractByURef { uref, args } => {
+                    uref.serialized_length() + args.serialized_length()
+                }
+            }
+    }
+}
+
+impl FromBytes for ExecutableDeployItem {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            MODULE_BYTES_TAG => {
+                let (module_bytes, rem) = Vec::<u8>::from_bytes(rem)?;
+                let (args, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((
+                    ExecutableDeployItem::ModuleBytes { module_bytes, args },
+                    rem,
+                ))
+            }
+            STORED_CONTRACT_BY_HASH_TAG => {
+                let (hash, rem) = Vec::<u8>::from_bytes(rem)?;
+                let (args, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((
+                    ExecutableDeployItem::StoredContractByHash { hash, args },
+                    rem,
+                ))
+            }
+            STORED_CONTRACT_BY_NAME_TAG => {
+                let (name, rem) = String::from_bytes(rem)?;
+                let (args, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((
+                    ExecutableDeployItem::StoredContractByName { name, args },
+                    rem,
+                ))
+            }
+            STORED_CONTRACT_BY_UREF_TAG => {
+                let (uref, rem) = Vec::<u8>::from_bytes(rem)?;
+                let (args, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((
+                    ExecutableDeployItem::StoredContractByURef { uref, args },
+                    rem,
+                ))
+            }
+            SPONSOR_CONTRACT_BY_HASH_TAG => {
+                let (hash, rem) = Vec::<u8>::from_bytes(rem)?;
+                let (args, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((
+                    ExecutableDeployItem::SponsorContractByHash { hash, args },
+                    rem,
+                ))
+            }
+            SPONSOR_CONTRACT_BY_NAME_TAG => {
+                let (name, rem) = String::from_bytes(rem)?;
+                let (args, rem) = Vec::<u8>::from_bytes(rem)?;
+                Ok((
+                    ExecutableDeployItem::SponsorContractByName { name, args },
+                    rem,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
         }
     }
 }
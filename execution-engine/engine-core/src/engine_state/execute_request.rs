@@ -1,21 +1,26 @@
 use std::mem;
 
-use engine_shared::newtypes::Blake2bHash;
-use types::ProtocolVersion;
+use engine_shared::{gas::Gas, newtypes::Blake2bHash};
+use types::{BlockTime, ProtocolVersion};
 
 use super::{deploy_item::DeployItem, execution_result::ExecutionResult};
 
 pub struct ExecuteRequest {
     pub parent_state_hash: Blake2bHash,
-    pub block_time: u64,
+    pub block_time: BlockTime,
     pub deploys: Vec<Result<DeployItem, ExecutionResult>>,
     pub protocol_version: ProtocolVersion,
+    /// If set, deploys are skipped once executing them could exceed this much gas in total.
+    pub block_gas_limit: Option<Gas>,
+    /// If set, the host function calls and global state reads made by each deploy are returned
+    /// along with its result.
+    pub collect_stats: bool,
 }
 
 impl ExecuteRequest {
     pub fn new(
         parent_state_hash: Blake2bHash,
-        block_time: u64,
+        block_time: BlockTime,
         deploys: Vec<Result<DeployItem, ExecutionResult>>,
         protocol_version: ProtocolVersion,
     ) -> Self {
@@ -24,9 +29,21 @@ impl ExecuteRequest {
             block_time,
             deploys,
             protocol_version,
+            block_gas_limit: None,
+            collect_stats: false,
         }
     }
 
+    pub fn with_block_gas_limit(mut self, block_gas_limit: Gas) -> Self {
+        self.block_gas_limit = Some(block_gas_limit);
+        self
+    }
+
+    pub fn with_collect_stats(mut self, collect_stats: bool) -> Self {
+        self.collect_stats = collect_stats;
+        self
+    }
+
     pub fn take_deploys(&mut self) -> Vec<Result<DeployItem, ExecutionResult>> {
         mem::replace(&mut self.deploys, vec![])
     }
@@ -36,9 +53,11 @@ impl Default for ExecuteRequest {
     fn default() -> Self {
         Self {
             parent_state_hash: [0u8; 32].into(),
-            block_time: 0,
+            block_time: BlockTime::default(),
             deploys: vec![],
             protocol_version: Default::default(),
+            block_gas_limit: None,
+            collect_stats: false,
         }
     }
 }
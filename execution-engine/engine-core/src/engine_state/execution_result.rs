@@ -1,4 +1,6 @@
-use super::{error, execution_effect::ExecutionEffect, op::Op, CONV_RATE};
+use std::cmp;
+
+use super::{error, execution_effect::ExecutionEffect, exit_code::ExitCode, op::Op, CONV_RATE};
 use engine_shared::{
     additive_map::AdditiveMap, gas::Gas, motes::Motes, newtypes::CorrelationId,
     stored_value::StoredValue, transform::Transform,
@@ -6,8 +8,10 @@ use engine_shared::{
 use engine_storage::global_state::StateReader;
 use types::{CLValue, Key};
 
+use crate::execution;
+
 fn make_payment_error_effects(
-    max_payment_cost: Motes,
+    charged_cost: Motes,
     account_main_purse_balance: Motes,
     account_main_purse: Key,
     rewards_purse: Key,
@@ -15,7 +19,7 @@ fn make_payment_error_effects(
     let mut ops = AdditiveMap::new();
     let mut transforms = AdditiveMap::new();
 
-    let new_balance = account_main_purse_balance - max_payment_cost;
+    let new_balance = account_main_purse_balance - charged_cost;
     // from_t for U512 is assumed to never panic
     let new_balance_clvalue = CLValue::from_t(new_balance.value()).unwrap();
     let new_balance_value = StoredValue::CLValue(new_balance_clvalue);
@@ -32,7 +36,7 @@ fn make_payment_error_effects(
     ops.insert(rewards_purse_normalize, Op::Add);
     transforms.insert(
         rewards_purse_normalize,
-        Transform::AddUInt512(max_payment_cost.value()),
+        Transform::AddUInt512(charged_cost.value()),
     );
 
     ExecutionEffect::new(ops, transforms)
@@ -47,7 +51,12 @@ pub enum ExecutionResult {
         cost: Gas,
     },
     /// Execution was finished successfully
-    Success { effect: ExecutionEffect, cost: Gas },
+    Success {
+        effect: ExecutionEffect,
+        cost: Gas,
+        /// The value passed to `ret` by session code, if it returned one.
+        return_value: Option<CLValue>,
+    },
 }
 
 pub enum ForcedTransferResult {
@@ -83,11 +92,11 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns `true` for a failure without effects, i.e. a deploy rejected before anything was
+    /// collected from it.  It may still report the cost of loading its account.
     pub fn has_precondition_failure(&self) -> bool {
         match self {
-            ExecutionResult::Failure { cost, effect, .. } => {
-                cost.value() == 0.into() && *effect == Default::default()
-            }
+            ExecutionResult::Failure { effect, .. } => *effect == Default::default(),
             ExecutionResult::Success { .. } => false,
         }
     }
@@ -113,7 +122,15 @@ impl ExecutionResult {
                 effect,
                 cost,
             },
-            ExecutionResult::Success { effect, .. } => ExecutionResult::Success { effect, cost },
+            ExecutionResult::Success {
+                effect,
+                return_value,
+                ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                return_value,
+            },
         }
     }
 
@@ -124,7 +141,13 @@ impl ExecutionResult {
                 effect,
                 cost,
             },
-            ExecutionResult::Success { cost, .. } => ExecutionResult::Success { effect, cost },
+            ExecutionResult::Success {
+                cost, return_value, ..
+            } => ExecutionResult::Success {
+                effect,
+                cost,
+                return_value,
+            },
         }
     }
 
@@ -135,6 +158,22 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the classification of this result, which is derived from its error.
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            ExecutionResult::Failure { error, .. } => error.into(),
+            ExecutionResult::Success { .. } => ExitCode::Success,
+        }
+    }
+
+    /// Consumes [`ExecutionResult`] instance and returns its [`ExecutionEffect`].
+    pub fn take_effect(self) -> ExecutionEffect {
+        match self {
+            ExecutionResult::Failure { effect, .. } => effect,
+            ExecutionResult::Success { effect, .. } => effect,
+        }
+    }
+
     /// Consumes [`ExecutionResult`] instance and optionally returns [`error::Error`] instance for
     /// [`ExecutionResult::Failure`] variant.
     pub fn take_error(self) -> Option<error::Error> {
@@ -144,6 +183,36 @@ impl ExecutionResult {
         }
     }
 
+    /// Returns the value passed to `ret` by session code, if any, for an
+    /// [`ExecutionResult::Success`] variant.
+    pub fn return_value(&self) -> Option<&CLValue> {
+        match self {
+            ExecutionResult::Failure { .. } => None,
+            ExecutionResult::Success { return_value, .. } => return_value.as_ref(),
+        }
+    }
+
+    /// Consumes [`ExecutionResult`] instance and optionally returns the value passed to `ret` by
+    /// session code for [`ExecutionResult::Success`] variant.
+    pub fn take_return_value(self) -> Option<CLValue> {
+        match self {
+            ExecutionResult::Failure { .. } => None,
+            ExecutionResult::Success { return_value, .. } => return_value,
+        }
+    }
+
+    /// Returns the data attached to the revert which caused an [`ExecutionResult::Failure`], if the
+    /// contract reverted with data.
+    pub fn revert_data(&self) -> Option<&[u8]> {
+        match self {
+            ExecutionResult::Failure {
+                error: error::Error::Exec(execution::Error::RevertWithData(_, data)),
+                ..
+            } => Some(data),
+            _ => None,
+        }
+    }
+
     pub fn check_forced_transfer(
         &self,
         payment_purse_balance: Motes,
@@ -180,13 +249,16 @@ impl ExecutionResult {
         account_main_purse: Key,
         rewards_purse: Key,
     ) -> ExecutionResult {
+        // Only deploys paid for by a sponsor contract may come from an account unable to cover
+        // `max_payment_cost`, in which case the account's whole balance is charged.
+        let charged_cost = cmp::min(max_payment_cost, account_main_purse_balance);
         let effect = make_payment_error_effects(
-            max_payment_cost,
+            charged_cost,
             account_main_purse_balance,
             account_main_purse,
             rewards_purse,
         );
-        let cost = Gas::from_motes(max_payment_cost, CONV_RATE).unwrap_or_default();
+        let cost = Gas::from_motes(charged_cost, CONV_RATE).unwrap_or_default();
         ExecutionResult::Failure {
             error,
             effect,
@@ -270,6 +342,7 @@ impl ExecutionResultBuilder {
         let mut ret: ExecutionResult = ExecutionResult::Success {
             effect: Default::default(),
             cost,
+            return_value: None,
         };
 
         match self.payment_execution_result {
@@ -277,7 +350,7 @@ impl ExecutionResultBuilder {
                 if result.is_failure() {
                     return Ok(result);
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, result.take_effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingPaymentExecutionResult),
@@ -286,12 +359,20 @@ impl ExecutionResultBuilder {
         // session_code_spec_3: only include session exec effects if there is no session
         // exec error
         match self.session_execution_result {
-            Some(result) => {
-                if result.is_failure() {
-                    ret = result.with_cost(cost);
-                } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
-                }
+            Some(result @ ExecutionResult::Failure { .. }) => {
+                ret = result.with_cost(cost);
+            }
+            Some(ExecutionResult::Success {
+                effect,
+                return_value,
+                ..
+            }) => {
+                Self::add_effects(&mut ops, &mut transforms, effect);
+                ret = ExecutionResult::Success {
+                    effect: Default::default(),
+                    cost,
+                    return_value,
+                };
             }
             None => return Err(ExecutionResultBuilderError::MissingSessionExecutionResult),
         };
@@ -304,7 +385,7 @@ impl ExecutionResultBuilder {
                         error::Error::Finalization,
                     ));
                 } else {
-                    Self::add_effects(&mut ops, &mut transforms, result.effect());
+                    Self::add_effects(&mut ops, &mut transforms, result.take_effect());
                 }
             }
             None => return Err(ExecutionResultBuilderError::MissingFinalizeExecutionResult),
@@ -316,16 +397,17 @@ impl ExecutionResultBuilder {
         Ok(ret.with_effect(reduced_effect))
     }
 
+    /// Merges `effect` into `ops` and `transforms`, moving rather than copying the written values.
     fn add_effects(
         ops: &mut AdditiveMap<Key, Op>,
         transforms: &mut AdditiveMap<Key, Transform>,
-        effect: &ExecutionEffect,
+        effect: ExecutionEffect,
     ) {
-        for (k, op) in effect.ops.iter() {
-            ops.insert_add(*k, op.clone());
+        for (k, op) in effect.ops {
+            ops.insert_add(k, op);
         }
-        for (k, t) in effect.transforms.iter() {
-            transforms.insert_add(*k, t.clone())
+        for (k, t) in effect.transforms {
+            transforms.insert_add(k, t)
         }
     }
 
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use engine_shared::gas::Gas;
+use engine_storage::global_state::read_stats::ReadStats;
+use types::{DeployHash, Phase};
+
+use crate::execution::HostCallStats;
+
+/// The work done while executing a deploy, collected if its request
+/// [collects stats](crate::engine_state::execute_request::ExecuteRequest::collect_stats).
+///
+/// Collecting them changes neither the deploy's effects nor its cost, see [`HostCallStats`] and
+/// [`ReadStats`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExecutionStats {
+    /// The host function calls made by the deploy.
+    pub host_call_stats: HostCallStats,
+    /// The global state reads made by the deploy, including those of its payment code.
+    pub read_stats: ReadStats,
+}
+
+/// The wall time spent in each phase of executing deploys.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PhaseTimes {
+    /// Everything but the execution of the deploys' code, e.g. checking the deploys and loading
+    /// and preprocessing their Wasm.
+    pub preprocess: Duration,
+    /// The execution of payment code.
+    pub payment: Duration,
+    /// The execution of session code, including that of system deploys.
+    pub session: Duration,
+    /// The finalization of payment.
+    pub finalization: Duration,
+}
+
+impl PhaseTimes {
+    /// Adds `duration` to the time of the phase executing code in `phase`.  The authorization
+    /// contract is executed while preprocessing the deploy, so its time is left to preprocessing.
+    pub fn record(&mut self, phase: Phase, duration: Duration) {
+        match phase {
+            Phase::Payment => self.payment += duration,
+            Phase::Session | Phase::System => self.session += duration,
+            Phase::FinalizePayment => self.finalization += duration,
+            Phase::Authorization => self.preprocess += duration,
+        }
+    }
+
+    /// The time spent executing code, in payment, session and finalization.
+    pub fn execution(&self) -> Duration {
+        self.payment + self.session + self.finalization
+    }
+
+    /// The time spent in all phases.
+    pub fn total(&self) -> Duration {
+        self.preprocess + self.execution()
+    }
+
+    /// Adds the times of `other` to these.
+    pub fn merge(&mut self, other: &PhaseTimes) {
+        self.preprocess += other.preprocess;
+        self.payment += other.payment;
+        self.session += other.session;
+        self.finalization += other.finalization;
+    }
+}
+
+/// A summary of the execution of a whole request, collected if it
+/// [collects stats](crate::engine_state::execute_request::ExecuteRequest::collect_stats).
+///
+/// Like [`ExecutionStats`], these are only ever reported.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RequestStats {
+    /// The wall time spent handling the request.
+    pub total_time: Duration,
+    /// The time spent in each phase of executing its deploys.
+    pub phase_times: PhaseTimes,
+    /// The time spent converting the request and its results from and to protobuf, which is only
+    /// known to the gRPC server.
+    pub protobuf_conversion_time: Duration,
+    /// The number of deploys of the request.
+    pub deploy_count: u32,
+    /// The gas used by the deploys in total.
+    pub total_gas: Gas,
+    /// The deploy which took the longest to execute, with the time it took.
+    pub slowest_deploy: Option<(DeployHash, Duration)>,
+}
+
+impl RequestStats {
+    /// Records a deploy of the request which took `phase_times` to execute and used `gas`.
+    pub(crate) fn record_deploy(
+        &mut self,
+        deploy_hash: Option<DeployHash>,
+        phase_times: &PhaseTimes,
+        gas: Gas,
+    ) {
+        self.phase_times.merge(phase_times);
+        self.deploy_count += 1;
+        self.total_gas = self.total_gas + gas;
+
+        let duration = phase_times.total();
+        let is_slowest = match self.slowest_deploy {
+            Some((_, slowest_duration)) => duration > slowest_duration,
+            None => true,
+        };
+        if let (Some(deploy_hash), true) = (deploy_hash, is_slowest) {
+            self.slowest_deploy = Some((deploy_hash, duration));
+        }
+    }
+}
@@ -0,0 +1,425 @@
+//! Machine-readable classification of the outcome of a deploy.
+//!
+//! The numeric codes of [`PreconditionReason`] and [`SystemErrorKind`] are sent to clients and are
+//! part of the public API: existing codes must never be changed or reused.  New variants must be
+//! given a new code.
+
+use types::ApiError;
+
+use super::error::Error;
+use crate::execution;
+
+/// The reason a deploy was rejected without being charged for.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PreconditionReason {
+    /// The deploy hash or a public key of the deploy has an invalid length, or another field of
+    /// the deploy is out of range.
+    InvalidDeploy = 1,
+    /// The protocol version of the deploy is unsupported.
+    InvalidProtocolVersion = 2,
+    /// The upgrade or account config is invalid.
+    InvalidConfig = 3,
+    /// The Wasm of the deploy is invalid.
+    InvalidWasm = 4,
+    /// The authorization keys of the deploy don't meet the account's thresholds.
+    Authorization = 5,
+    /// A sponsor contract was used as session code.
+    SponsorContractAsSession = 6,
+    /// The deploy was skipped as the block gas limit would have been exceeded.
+    BlockGasLimitExhausted = 7,
+    /// The block time is before the genesis timestamp or too far in the future.
+    InvalidBlockTime = 8,
+    /// The authorization contract of the deploy's account reverted or ran out of gas.
+    AuthorizationContractRejected = 9,
+    /// A user deploy claimed the system account, or a system deploy claimed another account.
+    InvalidSystemDeploy = 10,
+}
+
+/// The kind of an error raised by the engine, rather than by a contract, during execution.
+#[repr(u32)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SystemErrorKind {
+    Storage = 1,
+    MissingSystemContract = 2,
+    InsufficientPayment = 3,
+    Deploy = 4,
+    Finalization = 5,
+    Serialization = 6,
+    Mint = 7,
+    SponsorRefundPurseNotSet = 8,
+    Interpreter = 9,
+    KeyNotFound = 10,
+    TypeMismatch = 11,
+    InvalidAccess = 12,
+    KeyManagement = 13,
+    SystemContract = 14,
+    /// A contract reverted with an [`ApiError`] other than [`ApiError::User`].
+    ApiRevert = 15,
+    ReturnValue = 16,
+    IncompatibleProtocolVersion = 17,
+    TransientStorage = 18,
+    EffectsTooLarge = 19,
+    /// A contract was to be stored under a hash which is already in use.
+    ContractHashCollision = 20,
+    /// A runtime argument was read as a type other than the one it was passed as.
+    ArgumentTypeMismatch = 21,
+    /// Session code returned a value larger than the chainspec allows.
+    ReturnValueTooLarge = 22,
+    /// A string passed to a host function was not valid UTF-8.
+    InvalidUtf8 = 23,
+    /// A string passed to a host function was longer than the chainspec allows.
+    StringTooLong = 24,
+    /// An argument passed to a called contract was too deeply nested or too large to be scanned
+    /// for urefs.
+    ArgumentTooComplex = 25,
+}
+
+/// Classification of the outcome of a deploy.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExitCode {
+    Success,
+    Precondition(PreconditionReason),
+    OutOfGas,
+    /// A contract reverted with [`ApiError::User`] and the given code.
+    UserRevert(u16),
+    SystemError(SystemErrorKind),
+}
+
+impl ExitCode {
+    /// Returns the numeric code of the reason, kind or user revert, or `0` for `Success` and
+    /// `OutOfGas`.
+    pub fn code(&self) -> u32 {
+        match self {
+            ExitCode::Success | ExitCode::OutOfGas => 0,
+            ExitCode::Precondition(reason) => *reason as u32,
+            ExitCode::UserRevert(code) => u32::from(*code),
+            ExitCode::SystemError(kind) => *kind as u32,
+        }
+    }
+}
+
+// The matches below deliberately have no wildcard arms, so that a new error variant fails to
+// compile until it is classified.
+
+impl From<&Error> for ExitCode {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::InvalidHashLength { .. }
+            | Error::InvalidPublicKeyLength { .. }
+            | Error::InvalidDeployItem(_) => {
+                ExitCode::Precondition(PreconditionReason::InvalidDeploy)
+            }
+            Error::InvalidProtocolVersion(_)
+            | Error::ConfigNotFound { .. }
+            | Error::ProtocolVersionNotRecorded(_) => {
+                ExitCode::Precondition(PreconditionReason::InvalidProtocolVersion)
+            }
+            Error::InvalidUpgradeConfig | Error::InvalidAccountConfig => {
+                ExitCode::Precondition(PreconditionReason::InvalidConfig)
+            }
+            Error::WasmPreprocessing(_) | Error::WasmSerialization(_) => {
+                ExitCode::Precondition(PreconditionReason::InvalidWasm)
+            }
+            Error::Authorization => ExitCode::Precondition(PreconditionReason::Authorization),
+            Error::SponsorContractAsSession => {
+                ExitCode::Precondition(PreconditionReason::SponsorContractAsSession)
+            }
+            Error::BlockGasLimitExhausted => {
+                ExitCode::Precondition(PreconditionReason::BlockGasLimitExhausted)
+            }
+            Error::BlockTimeBeforeGenesis { .. } | Error::BlockTimeTooFarInFuture { .. } => {
+                ExitCode::Precondition(PreconditionReason::InvalidBlockTime)
+            }
+            Error::AuthorizationContractRejected(_) => {
+                ExitCode::Precondition(PreconditionReason::AuthorizationContractRejected)
+            }
+            Error::InvalidSystemDeploy => {
+                ExitCode::Precondition(PreconditionReason::InvalidSystemDeploy)
+            }
+            Error::Exec(exec_error) => exec_error.into(),
+            Error::Storage(_) => ExitCode::SystemError(SystemErrorKind::Storage),
+            Error::MissingSystemContract(_) => {
+                ExitCode::SystemError(SystemErrorKind::MissingSystemContract)
+            }
+            Error::InsufficientPayment => {
+                ExitCode::SystemError(SystemErrorKind::InsufficientPayment)
+            }
+            Error::Deploy => ExitCode::SystemError(SystemErrorKind::Deploy),
+            Error::Finalization => ExitCode::SystemError(SystemErrorKind::Finalization),
+            Error::Serialization(_) => ExitCode::SystemError(SystemErrorKind::Serialization),
+            Error::Mint(_) | Error::MintInvariantViolated(_) => {
+                ExitCode::SystemError(SystemErrorKind::Mint)
+            }
+            Error::SponsorRefundPurseNotSet => {
+                ExitCode::SystemError(SystemErrorKind::SponsorRefundPurseNotSet)
+            }
+            Error::DuplicateValidatorEntry(_) | Error::InvalidValidatorEntry(_) => {
+                ExitCode::SystemError(SystemErrorKind::SystemContract)
+            }
+        }
+    }
+}
+
+impl From<&execution::Error> for ExitCode {
+    fn from(error: &execution::Error) -> Self {
+        match error {
+            execution::Error::GasLimit => ExitCode::OutOfGas,
+            execution::Error::Revert(ApiError::User(code)) => ExitCode::UserRevert(*code),
+            execution::Error::Revert(_) => ExitCode::SystemError(SystemErrorKind::ApiRevert),
+            execution::Error::RevertWithData(ApiError::User(code), _) => {
+                ExitCode::UserRevert(*code)
+            }
+            execution::Error::RevertWithData(..) => {
+                ExitCode::SystemError(SystemErrorKind::ApiRevert)
+            }
+            execution::Error::DeploymentAuthorizationFailure => {
+                ExitCode::Precondition(PreconditionReason::Authorization)
+            }
+            execution::Error::Storage(_) => ExitCode::SystemError(SystemErrorKind::Storage),
+            execution::Error::BytesRepr(_) | execution::Error::CLValue(_) => {
+                ExitCode::SystemError(SystemErrorKind::Serialization)
+            }
+            execution::Error::Interpreter(_)
+            | execution::Error::ParityWasm(_)
+            | execution::Error::Resolver(_)
+            | execution::Error::Rng(_)
+            | execution::Error::UnsupportedWasmStart => {
+                ExitCode::SystemError(SystemErrorKind::Interpreter)
+            }
+            execution::Error::KeyNotFound(_)
+            | execution::Error::AccountNotFound(_)
+            | execution::Error::URefNotFound(_)
+            | execution::Error::FunctionNotFound(_) => {
+                ExitCode::SystemError(SystemErrorKind::KeyNotFound)
+            }
+            execution::Error::TypeMismatch(_) => {
+                ExitCode::SystemError(SystemErrorKind::TypeMismatch)
+            }
+            execution::Error::InvalidAccess { .. }
+            | execution::Error::ForgedReference(_)
+            | execution::Error::InvalidContext
+            | execution::Error::ReadOnlyContext
+            | execution::Error::ForbiddenInSystemContext(_)
+            | execution::Error::ReservedKeyName(_)
+            | execution::Error::ImpureDeclaredPure(_) => {
+                ExitCode::SystemError(SystemErrorKind::InvalidAccess)
+            }
+            execution::Error::AddKeyFailure(_)
+            | execution::Error::RemoveKeyFailure(_)
+            | execution::Error::UpdateKeyFailure(_)
+            | execution::Error::SetThresholdFailure(_) => {
+                ExitCode::SystemError(SystemErrorKind::KeyManagement)
+            }
+            execution::Error::SystemContract(_) => {
+                ExitCode::SystemError(SystemErrorKind::SystemContract)
+            }
+            execution::Error::Ret(_)
+            | execution::Error::ExpectedReturnValue
+            | execution::Error::UnexpectedReturnValue
+            | execution::Error::HostBufferEmpty => {
+                ExitCode::SystemError(SystemErrorKind::ReturnValue)
+            }
+            execution::Error::IncompatibleProtocolMajorVersion { .. } => {
+                ExitCode::SystemError(SystemErrorKind::IncompatibleProtocolVersion)
+            }
+            execution::Error::TransientStorageExceeded { .. } => {
+                ExitCode::SystemError(SystemErrorKind::TransientStorage)
+            }
+            execution::Error::EffectsTooLarge => {
+                ExitCode::SystemError(SystemErrorKind::EffectsTooLarge)
+            }
+            execution::Error::ContractHashCollision(_) => {
+                ExitCode::SystemError(SystemErrorKind::ContractHashCollision)
+            }
+            execution::Error::ArgumentTypeMismatch { .. } => {
+                ExitCode::SystemError(SystemErrorKind::ArgumentTypeMismatch)
+            }
+            execution::Error::ReturnValueTooLarge { .. } => {
+                ExitCode::SystemError(SystemErrorKind::ReturnValueTooLarge)
+            }
+            execution::Error::InvalidUtf8 => ExitCode::SystemError(SystemErrorKind::InvalidUtf8),
+            execution::Error::StringTooLong { .. } => {
+                ExitCode::SystemError(SystemErrorKind::StringTooLong)
+            }
+            execution::Error::ArgumentTooComplex { .. } => {
+                ExitCode::SystemError(SystemErrorKind::ArgumentTooComplex)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{system_contract_errors::mint, AccessRights, CLType, Key, URef};
+
+    use super::*;
+
+    // Golden values: these codes are relied upon by clients and must not change.
+
+    #[test]
+    fn precondition_reason_codes_should_be_stable() {
+        assert_eq!(PreconditionReason::InvalidDeploy as u32, 1);
+        assert_eq!(PreconditionReason::InvalidProtocolVersion as u32, 2);
+        assert_eq!(PreconditionReason::InvalidConfig as u32, 3);
+        assert_eq!(PreconditionReason::InvalidWasm as u32, 4);
+        assert_eq!(PreconditionReason::Authorization as u32, 5);
+        assert_eq!(PreconditionReason::SponsorContractAsSession as u32, 6);
+        assert_eq!(PreconditionReason::BlockGasLimitExhausted as u32, 7);
+        assert_eq!(PreconditionReason::InvalidBlockTime as u32, 8);
+        assert_eq!(PreconditionReason::AuthorizationContractRejected as u32, 9);
+        assert_eq!(PreconditionReason::InvalidSystemDeploy as u32, 10);
+    }
+
+    #[test]
+    fn system_error_kind_codes_should_be_stable() {
+        assert_eq!(SystemErrorKind::Storage as u32, 1);
+        assert_eq!(SystemErrorKind::MissingSystemContract as u32, 2);
+        assert_eq!(SystemErrorKind::InsufficientPayment as u32, 3);
+        assert_eq!(SystemErrorKind::Deploy as u32, 4);
+        assert_eq!(SystemErrorKind::Finalization as u32, 5);
+        assert_eq!(SystemErrorKind::Serialization as u32, 6);
+        assert_eq!(SystemErrorKind::Mint as u32, 7);
+        assert_eq!(SystemErrorKind::SponsorRefundPurseNotSet as u32, 8);
+        assert_eq!(SystemErrorKind::Interpreter as u32, 9);
+        assert_eq!(SystemErrorKind::KeyNotFound as u32, 10);
+        assert_eq!(SystemErrorKind::TypeMismatch as u32, 11);
+        assert_eq!(SystemErrorKind::InvalidAccess as u32, 12);
+        assert_eq!(SystemErrorKind::KeyManagement as u32, 13);
+        assert_eq!(SystemErrorKind::SystemContract as u32, 14);
+        assert_eq!(SystemErrorKind::ApiRevert as u32, 15);
+        assert_eq!(SystemErrorKind::ReturnValue as u32, 16);
+        assert_eq!(SystemErrorKind::IncompatibleProtocolVersion as u32, 17);
+        assert_eq!(SystemErrorKind::TransientStorage as u32, 18);
+        assert_eq!(SystemErrorKind::EffectsTooLarge as u32, 19);
+        assert_eq!(SystemErrorKind::ContractHashCollision as u32, 20);
+        assert_eq!(SystemErrorKind::ArgumentTypeMismatch as u32, 21);
+        assert_eq!(SystemErrorKind::ReturnValueTooLarge as u32, 22);
+        assert_eq!(SystemErrorKind::InvalidUtf8 as u32, 23);
+        assert_eq!(SystemErrorKind::StringTooLong as u32, 24);
+        assert_eq!(SystemErrorKind::ArgumentTooComplex as u32, 25);
+    }
+
+    #[test]
+    fn should_classify_errors() {
+        let cases: Vec<(Error, ExitCode)> = vec![
+            (
+                Error::InvalidAccountConfig,
+                ExitCode::Precondition(PreconditionReason::InvalidConfig),
+            ),
+            (
+                Error::Authorization,
+                ExitCode::Precondition(PreconditionReason::Authorization),
+            ),
+            (
+                execution::Error::DeploymentAuthorizationFailure.into(),
+                ExitCode::Precondition(PreconditionReason::Authorization),
+            ),
+            (
+                Error::AuthorizationContractRejected(execution::Error::Revert(ApiError::User(1))),
+                ExitCode::Precondition(PreconditionReason::AuthorizationContractRejected),
+            ),
+            (
+                Error::BlockGasLimitExhausted,
+                ExitCode::Precondition(PreconditionReason::BlockGasLimitExhausted),
+            ),
+            (execution::Error::GasLimit.into(), ExitCode::OutOfGas),
+            (
+                execution::Error::Revert(ApiError::User(100)).into(),
+                ExitCode::UserRevert(100),
+            ),
+            (
+                execution::Error::Revert(ApiError::InvalidArgument).into(),
+                ExitCode::SystemError(SystemErrorKind::ApiRevert),
+            ),
+            (
+                execution::Error::RevertWithData(ApiError::User(100), b"data".to_vec()).into(),
+                ExitCode::UserRevert(100),
+            ),
+            (
+                execution::Error::RevertWithData(ApiError::InvalidArgument, Vec::new()).into(),
+                ExitCode::SystemError(SystemErrorKind::ApiRevert),
+            ),
+            (
+                execution::Error::ForgedReference(URef::new([1; 32], AccessRights::READ)).into(),
+                ExitCode::SystemError(SystemErrorKind::InvalidAccess),
+            ),
+            (
+                execution::Error::EffectsTooLarge.into(),
+                ExitCode::SystemError(SystemErrorKind::EffectsTooLarge),
+            ),
+            (
+                execution::Error::ReservedKeyName(String::from("__system/x")).into(),
+                ExitCode::SystemError(SystemErrorKind::InvalidAccess),
+            ),
+            (
+                execution::Error::ImpureDeclaredPure(Key::Hash([1; 32])).into(),
+                ExitCode::SystemError(SystemErrorKind::InvalidAccess),
+            ),
+            (
+                execution::Error::ContractHashCollision([1; 32]).into(),
+                ExitCode::SystemError(SystemErrorKind::ContractHashCollision),
+            ),
+            (
+                execution::Error::ArgumentTypeMismatch {
+                    index: 1,
+                    expected: CLType::U512,
+                    found: CLType::U64,
+                }
+                .into(),
+                ExitCode::SystemError(SystemErrorKind::ArgumentTypeMismatch),
+            ),
+            (
+                execution::Error::ReturnValueTooLarge {
+                    size: 101,
+                    max_size: 100,
+                }
+                .into(),
+                ExitCode::SystemError(SystemErrorKind::ReturnValueTooLarge),
+            ),
+            (
+                execution::Error::InvalidUtf8.into(),
+                ExitCode::SystemError(SystemErrorKind::InvalidUtf8),
+            ),
+            (
+                execution::Error::StringTooLong {
+                    length: 101,
+                    max_length: 100,
+                }
+                .into(),
+                ExitCode::SystemError(SystemErrorKind::StringTooLong),
+            ),
+            (
+                execution::Error::ArgumentTooComplex {
+                    max_depth: 8,
+                    max_elements: 10_000,
+                }
+                .into(),
+                ExitCode::SystemError(SystemErrorKind::ArgumentTooComplex),
+            ),
+            (
+                Error::InsufficientPayment,
+                ExitCode::SystemError(SystemErrorKind::InsufficientPayment),
+            ),
+            (
+                Error::Mint(mint::Error::InsufficientFunds),
+                ExitCode::SystemError(SystemErrorKind::Mint),
+            ),
+        ];
+        for (error, expected) in cases {
+            assert_eq!(ExitCode::from(&error), expected, "{:?}", error);
+        }
+    }
+
+    #[test]
+    fn code_should_be_the_reason_kind_or_user_code() {
+        assert_eq!(ExitCode::Success.code(), 0);
+        assert_eq!(ExitCode::OutOfGas.code(), 0);
+        assert_eq!(
+            ExitCode::Precondition(PreconditionReason::InvalidWasm).code(),
+            4
+        );
+        assert_eq!(ExitCode::UserRevert(u16::max_value()).code(), 65_535);
+        assert_eq!(ExitCode::SystemError(SystemErrorKind::ApiRevert).code(), 15);
+    }
+}
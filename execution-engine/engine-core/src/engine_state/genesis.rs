@@ -1,4 +1,7 @@
-use std::{fmt, iter};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, iter,
+};
 
 use num_traits::Zero;
 use rand::{
@@ -6,26 +9,53 @@ use rand::{
     Rng,
 };
 
-use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
+use engine_shared::{
+    account::AccountConfig, additive_map::AdditiveMap, motes::Motes, newtypes::Blake2bHash,
+    stored_value::StoredValue, transform::Transform, TypeMismatch,
+};
 use engine_storage::global_state::CommitResult;
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
+use proof_of_stake::StakeKeyName;
+use types::{
+    account::{PublicKey, Weight},
+    bytesrepr::{self, FromBytes, ToBytes},
+    system_contract_names::pos,
+    AccessRights, BlockTime, CLTyped, Key, ProtocolVersion, URef, U512,
+};
 
 use crate::engine_state::execution_effect::ExecutionEffect;
 
 pub const PLACEHOLDER_KEY: Key = Key::Hash([0u8; 32]);
-pub const POS_BONDING_PURSE: &str = "pos_bonding_purse";
-pub const POS_PAYMENT_PURSE: &str = "pos_payment_purse";
-pub const POS_REWARDS_PURSE: &str = "pos_rewards_purse";
+pub const POS_BONDING_PURSE: &str = pos::BONDING_PURSE_KEY;
+pub const POS_PAYMENT_PURSE: &str = pos::PAYMENT_PURSE_KEY;
+pub const POS_REWARDS_PURSE: &str = pos::REWARDS_PURSE_KEY;
+pub const POS_REFUND_PURSE: &str = pos::REFUND_PURSE_KEY;
 
 pub enum GenesisResult {
     RootNotFound,
     KeyNotFound(Key),
     TypeMismatch(TypeMismatch),
     Serialization(bytesrepr::Error),
+    /// The same public key was given for more than one genesis account.
+    DuplicateAccount(PublicKey),
+    /// The sum of the balances and bonded amounts of the genesis accounts exceeds `U512::MAX`.
+    TotalSupplyOverflow,
+    /// A genesis account is bonded for more than its balance, and
+    /// [genesis bonding is strict](ExecConfig::strict_genesis_bonding).
+    BondedAmountExceedsBalance {
+        public_key: PublicKey,
+        balance: Motes,
+        bonded_amount: Motes,
+    },
+    /// The effects of genesis don't create an account for one of the genesis accounts.
+    MissingAccount(PublicKey),
+    /// Genesis was already committed to the store, and the request doesn't force a fresh genesis.
+    StoreAlreadyInitialized(GenesisRecord),
     Success {
         post_state_hash: Blake2bHash,
         effect: ExecutionEffect,
+        account_reports: Vec<GenesisAccountReport>,
+        bonding_reports: Vec<GenesisBondingReport>,
     },
 }
 
@@ -38,25 +68,288 @@ impl fmt::Display for GenesisResult {
                 write!(f, "Type mismatch: {:?}", type_mismatch)
             }
             GenesisResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            GenesisResult::DuplicateAccount(public_key) => {
+                write!(f, "Duplicate genesis account: {}", public_key)
+            }
+            GenesisResult::TotalSupplyOverflow => write!(f, "Total supply overflow"),
+            GenesisResult::BondedAmountExceedsBalance {
+                public_key,
+                balance,
+                bonded_amount,
+            } => write!(
+                f,
+                "Bonded amount {} of genesis account {} exceeds its balance {}",
+                bonded_amount, public_key, balance
+            ),
+            GenesisResult::MissingAccount(public_key) => {
+                write!(f, "Genesis created no account for {}", public_key)
+            }
+            GenesisResult::StoreAlreadyInitialized(GenesisRecord { fingerprint, root }) => write!(
+                f,
+                "Store already initialized by genesis {} at root {}",
+                fingerprint, root
+            ),
             GenesisResult::Success {
                 post_state_hash,
                 effect,
+                ..
             } => write!(f, "Success: {} {:?}", post_state_hash, effect),
         }
     }
 }
 
 impl GenesisResult {
-    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+    pub fn from_commit_result(
+        commit_result: CommitResult,
+        effect: ExecutionEffect,
+        account_reports: Vec<GenesisAccountReport>,
+        bonding_reports: Vec<GenesisBondingReport>,
+    ) -> Self {
         match commit_result {
             CommitResult::RootNotFound => GenesisResult::RootNotFound,
             CommitResult::KeyNotFound(key) => GenesisResult::KeyNotFound(key),
             CommitResult::TypeMismatch(type_mismatch) => GenesisResult::TypeMismatch(type_mismatch),
             CommitResult::Serialization(error) => GenesisResult::Serialization(error),
+            CommitResult::StaleCommit { .. } => {
+                unreachable!("genesis commits are never fenced")
+            }
             CommitResult::Success { state_root, .. } => GenesisResult::Success {
                 post_state_hash: state_root,
                 effect,
+                account_reports,
+                bonding_reports,
+            },
+        }
+    }
+}
+
+/// A genesis committed to the store.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GenesisRecord {
+    /// The fingerprint of the genesis config, see [`ExecConfig::fingerprint`].
+    pub fingerprint: Blake2bHash,
+    /// The post-state of the genesis.
+    pub root: Blake2bHash,
+}
+
+/// Why the PoS installation didn't bond a genesis validator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GenesisBondingError {
+    /// The validator is bonded for more than its balance.
+    BondedAmountExceedsBalance {
+        balance: Motes,
+        bonded_amount: Motes,
+    },
+}
+
+impl fmt::Display for GenesisBondingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            GenesisBondingError::BondedAmountExceedsBalance {
+                balance,
+                bonded_amount,
+            } => write!(
+                f,
+                "Bonded amount {} exceeds balance {}",
+                bonded_amount, balance
+            ),
+        }
+    }
+}
+
+impl Distribution<GenesisBondingError> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GenesisBondingError {
+        GenesisBondingError::BondedAmountExceedsBalance {
+            balance: Motes::new(rng.gen::<u64>().into()),
+            bonded_amount: Motes::new(rng.gen::<u64>().into()),
+        }
+    }
+}
+
+/// What the PoS installation of genesis did with one of the genesis validators, i.e. the genesis
+/// accounts with a non-zero bonded amount.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GenesisBondingReport {
+    Bonded {
+        key: PublicKey,
+        amount: Motes,
+    },
+    /// Only reported if [genesis bonding isn't strict](ExecConfig::strict_genesis_bonding);
+    /// otherwise genesis fails instead.  The validator's account is still created.
+    Skipped {
+        key: PublicKey,
+        reason: GenesisBondingError,
+    },
+}
+
+impl GenesisBondingReport {
+    /// Returns the report of bonding `account`, or `None` if it isn't a validator.
+    fn of(account: &GenesisAccount) -> Option<Self> {
+        let key = account.public_key();
+        let balance = account.balance();
+        let bonded_amount = account.bonded_amount();
+        if bonded_amount.value().is_zero() {
+            None
+        } else if bonded_amount > balance {
+            let reason = GenesisBondingError::BondedAmountExceedsBalance {
+                balance,
+                bonded_amount,
+            };
+            Some(GenesisBondingReport::Skipped { key, reason })
+        } else {
+            Some(GenesisBondingReport::Bonded {
+                key,
+                amount: bonded_amount,
+            })
+        }
+    }
+
+    pub fn key(&self) -> PublicKey {
+        match self {
+            GenesisBondingReport::Bonded { key, .. }
+            | GenesisBondingReport::Skipped { key, .. } => *key,
+        }
+    }
+}
+
+impl Distribution<GenesisBondingReport> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GenesisBondingReport {
+        let key = PublicKey::ed25519_from(rng.gen());
+        if rng.gen() {
+            GenesisBondingReport::Bonded {
+                key,
+                amount: Motes::new(rng.gen::<u64>().into()),
+            }
+        } else {
+            GenesisBondingReport::Skipped {
+                key,
+                reason: rng.gen(),
+            }
+        }
+    }
+}
+
+/// What genesis created for one of the genesis accounts, as found in the effects of genesis.
+///
+/// The report is read back from the effects rather than copied from the [`GenesisAccount`], so
+/// comparing the two shows whether genesis did what the chainspec asked for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct GenesisAccountReport {
+    pub public_key: PublicKey,
+    pub account_key: Key,
+    pub main_purse_uref: URef,
+    /// The `URef` under which the mint stores the balance of the main purse.
+    pub balance_uref: URef,
+    pub initial_balance: Motes,
+    /// The amount bonded by the PoS contract, zero if the account isn't bonded.
+    pub bonded_amount: Motes,
+}
+
+impl GenesisAccountReport {
+    /// Builds the reports of `accounts` from `transforms`, the effects of genesis.
+    ///
+    /// Returns [`GenesisResult::MissingAccount`] if no account was written for one of `accounts`.
+    pub(crate) fn from_effects(
+        accounts: &[GenesisAccount],
+        transforms: &AdditiveMap<Key, Transform>,
+        mint: URef,
+        proof_of_stake: URef,
+    ) -> Result<Vec<GenesisAccountReport>, GenesisResult> {
+        let bonded_amounts = bonded_amounts(transforms, proof_of_stake);
+        accounts
+            .iter()
+            .map(|genesis_account| {
+                let public_key = genesis_account.public_key();
+                let account_key = Key::Account(public_key);
+                let main_purse_uref = match written_value(transforms, &account_key) {
+                    Some(StoredValue::Account(account)) => account.main_purse(),
+                    _ => return Err(GenesisResult::MissingAccount(public_key)),
+                };
+                let balance_uref = main_purse_uref
+                    .addr()
+                    .to_bytes()
+                    .ok()
+                    .map(|local_key_bytes| Key::local(mint.addr(), &local_key_bytes))
+                    .and_then(|balance_mapping_key| {
+                        written_t::<Key>(transforms, &balance_mapping_key)
+                    })
+                    .and_then(|balance_key| balance_key.into_uref())
+                    .ok_or(GenesisResult::MissingAccount(public_key))?;
+                let initial_balance =
+                    written_t::<U512>(transforms, &Key::URef(balance_uref).normalize())
+                        .map(Motes::new)
+                        .ok_or(GenesisResult::MissingAccount(public_key))?;
+                let bonded_amount = bonded_amounts
+                    .get(&public_key)
+                    .map(|bonded_amount| Motes::new(*bonded_amount))
+                    .unwrap_or_else(Motes::zero);
+                Ok(GenesisAccountReport {
+                    public_key,
+                    account_key,
+                    main_purse_uref,
+                    balance_uref,
+                    initial_balance,
+                    bonded_amount,
+                })
+            })
+            .collect()
+    }
+}
+
+fn written_value<'a>(
+    transforms: &'a AdditiveMap<Key, Transform>,
+    key: &Key,
+) -> Option<&'a StoredValue> {
+    match transforms.get(key) {
+        Some(Transform::Write(value)) => Some(value),
+        _ => None,
+    }
+}
+
+fn written_t<T: CLTyped + FromBytes>(
+    transforms: &AdditiveMap<Key, Transform>,
+    key: &Key,
+) -> Option<T> {
+    match written_value(transforms, key) {
+        Some(StoredValue::CLValue(cl_value)) => cl_value.clone().into_t().ok(),
+        _ => None,
+    }
+}
+
+/// Returns the stakes recorded in the named keys of the PoS contract written by genesis.
+fn bonded_amounts(
+    transforms: &AdditiveMap<Key, Transform>,
+    proof_of_stake: URef,
+) -> BTreeMap<PublicKey, U512> {
+    let contract = match written_value(transforms, &Key::URef(proof_of_stake).normalize()) {
+        Some(StoredValue::Contract(contract)) => contract,
+        _ => return BTreeMap::new(),
+    };
+    contract
+        .named_keys()
+        .iter()
+        .filter_map(
+            |(name, key)| match proof_of_stake::parse_stake_key_name(name) {
+                Ok(Some(StakeKeyName::Legacy(validator, stake))) => Some((validator, stake)),
+                Ok(Some(StakeKeyName::Current(validator))) => {
+                    written_t::<U512>(transforms, &key.normalize()).map(|stake| (validator, stake))
+                }
+                Ok(None) | Err(_) => None,
             },
+        )
+        .collect()
+}
+
+impl Distribution<GenesisAccountReport> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> GenesisAccountReport {
+        let genesis_account: GenesisAccount = rng.gen();
+        GenesisAccountReport {
+            public_key: genesis_account.public_key(),
+            account_key: Key::Account(genesis_account.public_key()),
+            main_purse_uref: URef::new(rng.gen(), AccessRights::READ_ADD_WRITE),
+            balance_uref: URef::new(rng.gen(), AccessRights::READ_ADD_WRITE),
+            initial_balance: genesis_account.balance(),
+            bonded_amount: genesis_account.bonded_amount(),
         }
     }
 }
@@ -156,6 +449,19 @@ impl GenesisConfig {
     pub fn take_ee_config(self) -> ExecConfig {
         self.ee_config
     }
+
+    /// Returns the hash of the canonical serialization of the config, so that nodes can check
+    /// they were started with the same chainspec before running genesis.
+    ///
+    /// Configs which differ only in the order of the genesis accounts, or in an equivalent opcode
+    /// cost ratio, have the same fingerprint.  See [`ExecConfig::canonical_bytes`].
+    pub fn fingerprint(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        let mut bytes = self.name.to_bytes()?;
+        bytes.append(&mut self.timestamp.to_bytes()?);
+        bytes.append(&mut self.protocol_version.to_bytes()?);
+        bytes.append(&mut self.ee_config.canonical_bytes()?);
+        Ok(Blake2bHash::new(&bytes))
+    }
 }
 
 impl Distribution<GenesisConfig> for Standard {
@@ -188,6 +494,9 @@ pub struct ExecConfig {
     standard_payment_installer_bytes: Vec<u8>,
     accounts: Vec<GenesisAccount>,
     wasm_costs: WasmCosts,
+    account_config: AccountConfig,
+    genesis_timestamp: BlockTime,
+    strict_genesis_bonding: bool,
 }
 
 impl ExecConfig {
@@ -197,6 +506,7 @@ impl ExecConfig {
         standard_payment_installer_bytes: Vec<u8>,
         accounts: Vec<GenesisAccount>,
         wasm_costs: WasmCosts,
+        account_config: AccountConfig,
     ) -> ExecConfig {
         ExecConfig {
             mint_installer_bytes,
@@ -204,8 +514,12 @@ impl ExecConfig {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            account_config,
+            genesis_timestamp: BlockTime::default(),
+            strict_genesis_bonding: true,
         }
     }
+
     pub fn mint_installer_bytes(&self) -> &[u8] {
         self.mint_installer_bytes.as_slice()
     }
@@ -222,18 +536,23 @@ impl ExecConfig {
         self.wasm_costs
     }
 
+    pub fn account_config(&self) -> AccountConfig {
+        self.account_config
+    }
+
+    /// Returns the validators the PoS installation bonds, with their bonded amounts.
     pub fn get_bonded_validators(&self) -> impl Iterator<Item = (PublicKey, Motes)> + '_ {
-        let zero = Motes::zero();
-        self.accounts.iter().filter_map(move |genesis_account| {
-            if genesis_account.bonded_amount() > zero {
-                Some((
-                    genesis_account.public_key(),
-                    genesis_account.bonded_amount(),
-                ))
-            } else {
-                None
-            }
-        })
+        self.bonding_reports()
+            .filter_map(|bonding_report| match bonding_report {
+                GenesisBondingReport::Bonded { key, amount } => Some((key, amount)),
+                GenesisBondingReport::Skipped { .. } => None,
+            })
+    }
+
+    /// Returns the reports of bonding the genesis validators, in the order the accounts were
+    /// given.
+    pub fn bonding_reports(&self) -> impl Iterator<Item = GenesisBondingReport> + '_ {
+        self.accounts.iter().filter_map(GenesisBondingReport::of)
     }
 
     pub fn accounts(&self) -> &[GenesisAccount] {
@@ -243,6 +562,122 @@ impl ExecConfig {
     pub fn push_account(&mut self, account: GenesisAccount) {
         self.accounts.push(account)
     }
+
+    pub fn set_account_config(&mut self, account_config: AccountConfig) {
+        self.account_config = account_config;
+    }
+
+    pub fn set_wasm_costs(&mut self, wasm_costs: WasmCosts) {
+        self.wasm_costs = wasm_costs;
+    }
+
+    /// The time of the genesis block.  Deploys can't be executed in blocks with an earlier time.
+    pub fn genesis_timestamp(&self) -> BlockTime {
+        self.genesis_timestamp
+    }
+
+    pub fn set_genesis_timestamp(&mut self, genesis_timestamp: BlockTime) {
+        self.genesis_timestamp = genesis_timestamp;
+    }
+
+    /// Whether genesis fails if any validator can't be bonded, rather than skipping the validators
+    /// which can't be bonded and bonding the rest.  Strict by default.
+    pub fn strict_genesis_bonding(&self) -> bool {
+        self.strict_genesis_bonding
+    }
+
+    pub fn set_strict_genesis_bonding(&mut self, strict_genesis_bonding: bool) {
+        self.strict_genesis_bonding = strict_genesis_bonding;
+    }
+
+    /// Returns the serialization of the config which is the same for any two configs with the same
+    /// meaning.
+    ///
+    /// The accounts are sorted by public key, then balance and bonded amount, and the opcode cost
+    /// ratio `opcodes_mul / opcodes_div` is reduced to lowest terms.  Every field is serialized,
+    /// so values left unset in the protobuf are covered by the defaults they were mapped to.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut accounts: Vec<(PublicKey, U512, U512)> = self
+            .accounts
+            .iter()
+            .map(|account| {
+                (
+                    account.public_key(),
+                    account.balance().value(),
+                    account.bonded_amount().value(),
+                )
+            })
+            .collect();
+        accounts.sort();
+
+        let mut wasm_costs = self.wasm_costs;
+        let divisor = gcd(wasm_costs.opcodes_mul, wasm_costs.opcodes_div);
+        if divisor > 1 {
+            wasm_costs.opcodes_mul /= divisor;
+            wasm_costs.opcodes_div /= divisor;
+        }
+
+        let mut ret = self.mint_installer_bytes.to_bytes()?;
+        ret.append(&mut self.proof_of_stake_installer_bytes.to_bytes()?);
+        ret.append(&mut self.standard_payment_installer_bytes.to_bytes()?);
+        ret.append(&mut accounts.to_bytes()?);
+        ret.append(&mut wasm_costs.to_bytes()?);
+        ret.append(&mut self.account_config.to_bytes()?);
+        ret.append(&mut self.genesis_timestamp.to_bytes()?);
+        ret.append(&mut self.strict_genesis_bonding.to_bytes()?);
+        Ok(ret)
+    }
+
+    /// Returns the hash of the [canonical serialization](ExecConfig::canonical_bytes) of the
+    /// config.
+    pub fn fingerprint(&self) -> Result<Blake2bHash, bytesrepr::Error> {
+        Ok(Blake2bHash::new(&self.canonical_bytes()?))
+    }
+
+    /// Checks that the genesis accounts have distinct public keys, that none is bonded for more
+    /// than its balance if [genesis bonding is strict](ExecConfig::strict_genesis_bonding), and
+    /// that the total supply they mint fits in a `U512`.
+    ///
+    /// The amounts of validators which are skipped rather than bonded aren't part of the total
+    /// supply.
+    ///
+    /// Returns the total supply, or the failed [`GenesisResult`].
+    pub fn validate_accounts(&self) -> Result<Motes, GenesisResult> {
+        let mut public_keys = BTreeSet::new();
+        let mut total_supply = Motes::zero();
+        for account in self.accounts.iter() {
+            let public_key = account.public_key();
+            if !public_keys.insert(public_key) {
+                return Err(GenesisResult::DuplicateAccount(public_key));
+            }
+            let bonded_amount = match GenesisBondingReport::of(account) {
+                Some(GenesisBondingReport::Bonded { amount, .. }) => amount,
+                Some(GenesisBondingReport::Skipped { .. }) if self.strict_genesis_bonding => {
+                    return Err(GenesisResult::BondedAmountExceedsBalance {
+                        public_key,
+                        balance: account.balance(),
+                        bonded_amount: account.bonded_amount(),
+                    });
+                }
+                Some(GenesisBondingReport::Skipped { .. }) | None => Motes::zero(),
+            };
+            total_supply = total_supply
+                .checked_add(account.balance())
+                .and_then(|total_supply| total_supply.checked_add(bonded_amount))
+                .ok_or(GenesisResult::TotalSupplyOverflow)?;
+        }
+        Ok(total_supply)
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`, or the other if either is zero.
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let remainder = a % b;
+        a = b;
+        b = remainder;
+    }
+    a
 }
 
 impl Distribution<ExecConfig> for Standard {
@@ -272,6 +707,27 @@ impl Distribution<ExecConfig> for Standard {
             max_stack_height: rng.gen(),
             opcodes_mul: rng.gen(),
             opcodes_div: rng.gen(),
+            transient_storage: rng.gen(),
+            max_transient_storage_size: rng.gen(),
+            max_transforms_per_deploy: rng.gen(),
+            max_effect_bytes_per_deploy: rng.gen(),
+            max_session_return_value_bytes: rng.gen(),
+            string_byte: rng.gen(),
+            max_string_length: rng.gen(),
+            max_revert_data_bytes: rng.gen(),
+            base_deploy_cost: rng.gen(),
+            rejected_deploy_cost: rng.gen(),
+            strict_session_exports: rng.gen(),
+        };
+
+        let account_config = {
+            let mut weights = [rng.gen(), rng.gen(), rng.gen()];
+            weights.sort();
+            AccountConfig::new(
+                Weight::new(weights[0]),
+                Weight::new(weights[1]),
+                Weight::new(weights[2]),
+            )
         };
 
         ExecConfig {
@@ -280,6 +736,239 @@ impl Distribution<ExecConfig> for Standard {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            account_config,
+            genesis_timestamp: BlockTime::from_millis(rng.gen()),
+            strict_genesis_bonding: rng.gen(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*, sample::Index};
+
+    use super::*;
+
+    const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1; 32]);
+    const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2; 32]);
+
+    fn exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
+        ExecConfig::new(
+            vec![],
+            vec![],
+            vec![],
+            accounts,
+            WasmCosts::default(),
+            AccountConfig::default(),
+        )
+    }
+
+    fn motes(value: u64) -> Motes {
+        Motes::new(value.into())
+    }
+
+    #[test]
+    fn should_return_total_supply_of_valid_accounts() {
+        let exec_config = exec_config(vec![
+            GenesisAccount::new(ACCOUNT_1_ADDR, motes(100), motes(10)),
+            GenesisAccount::new(ACCOUNT_2_ADDR, motes(200), Motes::zero()),
+        ]);
+        assert_eq!(exec_config.validate_accounts().ok(), Some(motes(310)));
+    }
+
+    #[test]
+    fn should_reject_duplicate_account() {
+        let exec_config = exec_config(vec![
+            GenesisAccount::new(ACCOUNT_1_ADDR, motes(100), Motes::zero()),
+            GenesisAccount::new(ACCOUNT_2_ADDR, motes(100), Motes::zero()),
+            GenesisAccount::new(ACCOUNT_1_ADDR, motes(200), Motes::zero()),
+        ]);
+        match exec_config.validate_accounts() {
+            Err(GenesisResult::DuplicateAccount(public_key)) => {
+                assert_eq!(public_key, ACCOUNT_1_ADDR)
+            }
+            Err(other) => panic!("expected duplicate account, got {}", other),
+            Ok(total_supply) => panic!("expected duplicate account, got {}", total_supply),
+        }
+    }
+
+    #[test]
+    fn should_reject_total_supply_overflow() {
+        let exec_config = exec_config(vec![
+            GenesisAccount::new(ACCOUNT_1_ADDR, Motes::new(U512::max_value()), Motes::zero()),
+            GenesisAccount::new(ACCOUNT_2_ADDR, motes(1), Motes::zero()),
+        ]);
+        match exec_config.validate_accounts() {
+            Err(GenesisResult::TotalSupplyOverflow) => {}
+            Err(other) => panic!("expected total supply overflow, got {}", other),
+            Ok(total_supply) => panic!("expected total supply overflow, got {}", total_supply),
+        }
+    }
+
+    #[test]
+    fn should_reject_total_supply_overflow_from_bonded_amount() {
+        let exec_config = exec_config(vec![GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            Motes::new(U512::max_value()),
+            motes(1),
+        )]);
+        match exec_config.validate_accounts() {
+            Err(GenesisResult::TotalSupplyOverflow) => {}
+            Err(other) => panic!("expected total supply overflow, got {}", other),
+            Ok(total_supply) => panic!("expected total supply overflow, got {}", total_supply),
+        }
+    }
+
+    #[test]
+    fn should_reject_bonded_amount_exceeding_balance() {
+        let exec_config = exec_config(vec![
+            GenesisAccount::new(ACCOUNT_1_ADDR, motes(100), motes(100)),
+            GenesisAccount::new(ACCOUNT_2_ADDR, motes(100), motes(101)),
+        ]);
+        match exec_config.validate_accounts() {
+            Err(GenesisResult::BondedAmountExceedsBalance {
+                public_key,
+                balance,
+                bonded_amount,
+            }) => {
+                assert_eq!(public_key, ACCOUNT_2_ADDR);
+                assert_eq!(balance, motes(100));
+                assert_eq!(bonded_amount, motes(101));
+            }
+            Err(other) => panic!("expected bonded amount exceeding balance, got {}", other),
+            Ok(total_supply) => panic!(
+                "expected bonded amount exceeding balance, got {}",
+                total_supply
+            ),
+        }
+    }
+
+    #[test]
+    fn should_skip_bonded_amount_exceeding_balance_unless_strict() {
+        let mut exec_config = exec_config(vec![
+            GenesisAccount::new(ACCOUNT_1_ADDR, motes(100), motes(10)),
+            GenesisAccount::new(ACCOUNT_2_ADDR, motes(100), motes(101)),
+        ]);
+        exec_config.set_strict_genesis_bonding(false);
+
+        // the skipped validator's bonded amount isn't minted
+        assert_eq!(exec_config.validate_accounts().ok(), Some(motes(210)));
+        let bonding_reports: Vec<GenesisBondingReport> = exec_config.bonding_reports().collect();
+        assert_eq!(
+            bonding_reports,
+            vec![
+                GenesisBondingReport::Bonded {
+                    key: ACCOUNT_1_ADDR,
+                    amount: motes(10),
+                },
+                GenesisBondingReport::Skipped {
+                    key: ACCOUNT_2_ADDR,
+                    reason: GenesisBondingError::BondedAmountExceedsBalance {
+                        balance: motes(100),
+                        bonded_amount: motes(101),
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            exec_config.get_bonded_validators().collect::<Vec<_>>(),
+            vec![(ACCOUNT_1_ADDR, motes(10))]
+        );
+    }
+
+    fn genesis_config(accounts: Vec<GenesisAccount>) -> GenesisConfig {
+        GenesisConfig::new(
+            "test-chain".to_string(),
+            1,
+            ProtocolVersion::V1_0_0,
+            exec_config(accounts),
+        )
+    }
+
+    fn fingerprint(genesis_config: &GenesisConfig) -> Blake2bHash {
+        genesis_config.fingerprint().expect("should fingerprint")
+    }
+
+    fn genesis_accounts_arb() -> impl Strategy<Value = Vec<GenesisAccount>> {
+        vec((any::<[u8; 32]>(), any::<u64>(), any::<u64>()), 1..10).prop_map(|accounts| {
+            accounts
+                .into_iter()
+                .map(|(public_key, balance, bonded_amount)| {
+                    GenesisAccount::new(
+                        PublicKey::ed25519_from(public_key),
+                        motes(balance),
+                        motes(bonded_amount),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn fingerprint_should_not_depend_on_account_order(
+            (accounts, shuffled) in genesis_accounts_arb()
+                .prop_flat_map(|accounts| (Just(accounts.clone()), Just(accounts).prop_shuffle()))
+        ) {
+            prop_assert_eq!(
+                fingerprint(&genesis_config(accounts)),
+                fingerprint(&genesis_config(shuffled))
+            );
+        }
+
+        #[test]
+        fn fingerprint_should_change_with_any_balance(
+            accounts in genesis_accounts_arb(),
+            index in any::<Index>(),
+            delta in 1..u64::max_value(),
+        ) {
+            let mut changed = accounts.clone();
+            let index = index.index(changed.len());
+            let account = changed[index];
+            changed[index] = GenesisAccount::new(
+                account.public_key(),
+                Motes::new(account.balance().value() + U512::from(delta)),
+                account.bonded_amount(),
+            );
+            prop_assert_ne!(
+                fingerprint(&genesis_config(accounts)),
+                fingerprint(&genesis_config(changed))
+            );
+        }
+    }
+
+    #[test]
+    fn fingerprint_should_reduce_opcode_cost_ratio() {
+        let mut reduced = genesis_config(vec![]);
+        reduced.ee_config_mut().wasm_costs.opcodes_mul = 3;
+        reduced.ee_config_mut().wasm_costs.opcodes_div = 8;
+
+        let mut scaled = reduced.clone();
+        scaled.ee_config_mut().wasm_costs.opcodes_mul = 6;
+        scaled.ee_config_mut().wasm_costs.opcodes_div = 16;
+        assert_eq!(fingerprint(&reduced), fingerprint(&scaled));
+
+        scaled.ee_config_mut().wasm_costs.opcodes_div = 15;
+        assert_ne!(fingerprint(&reduced), fingerprint(&scaled));
+    }
+
+    #[test]
+    fn fingerprint_should_cover_name_and_timestamp() {
+        let genesis_config = genesis_config(vec![GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            motes(100),
+            motes(10),
+        )]);
+        let mut renamed = genesis_config.clone();
+        renamed.name = "other-chain".to_string();
+        let mut retimed = genesis_config.clone();
+        retimed.timestamp += 1;
+
+        assert_ne!(fingerprint(&genesis_config), fingerprint(&renamed));
+        assert_ne!(fingerprint(&genesis_config), fingerprint(&retimed));
+        assert_eq!(
+            genesis_config.ee_config().fingerprint().unwrap(),
+            renamed.ee_config().fingerprint().unwrap()
+        );
+    }
+}
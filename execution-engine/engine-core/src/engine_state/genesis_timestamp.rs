@@ -0,0 +1,58 @@
+//! The state root committed by genesis records the genesis timestamp, so that deploys can be
+//! checked not to be executed in blocks older than the chain itself.  This is what catches a block
+//! time sent in the wrong unit: seconds since the Unix epoch read as milliseconds fall decades
+//! before any genesis.
+
+use lazy_static::lazy_static;
+
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue, TypeMismatch};
+use engine_storage::global_state::StateReader;
+use types::{BlockTime, CLValue, Key};
+
+use crate::{engine_state, execution, tracking_copy::TrackingCopy};
+
+lazy_static! {
+    /// The key under which the genesis timestamp is stored.
+    pub static ref GENESIS_TIMESTAMP_KEY: Key =
+        engine_state::engine_record_key(b"genesis-timestamp");
+}
+
+/// Records `genesis_timestamp` in the state root resulting from the effects of `tracking_copy`.
+pub(crate) fn write<R>(tracking_copy: &mut TrackingCopy<R>, genesis_timestamp: BlockTime)
+where
+    R: StateReader<Key, StoredValue>,
+{
+    let cl_value = CLValue::from_t(genesis_timestamp.as_millis())
+        .expect("should convert genesis timestamp to CLValue");
+    tracking_copy.write(*GENESIS_TIMESTAMP_KEY, StoredValue::CLValue(cl_value));
+}
+
+/// Returns the genesis timestamp recorded at the state root read by `reader`, or `None` if the
+/// root doesn't record one, i.e. its genesis was committed before timestamps were recorded.
+///
+/// This reads from the root directly rather than through a [`TrackingCopy`], so that the lookup
+/// doesn't show up in the effects of an execution.
+pub(crate) fn read<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+) -> Result<Option<BlockTime>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let cl_value = match reader
+        .read(correlation_id, &*GENESIS_TIMESTAMP_KEY)
+        .map_err(Into::into)?
+    {
+        Some(StoredValue::CLValue(cl_value)) => cl_value,
+        Some(other) => {
+            return Err(execution::Error::TypeMismatch(TypeMismatch::new(
+                "CLValue".to_string(),
+                other.type_name(),
+            )))
+        }
+        None => return Ok(None),
+    };
+    let millis = cl_value.into_t::<u64>()?;
+    Ok(Some(BlockTime::from_millis(millis)))
+}
@@ -0,0 +1,231 @@
+use std::{cmp, collections::BTreeMap, ops::Bound};
+
+use engine_shared::newtypes::Blake2bHash;
+use types::Key;
+
+use crate::tracking_copy::TrackingCopyQueryResult;
+
+/// Page size used when a request doesn't specify one.
+pub const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// Largest number of named keys returned in a single page, whatever the requested page size.
+pub const MAX_PAGE_SIZE: usize = 10_000;
+
+/// A request for one page of the named keys of an account or contract.
+///
+/// Named keys are listed in lexicographic order of their names.  Each request reads the named keys
+/// as of its own `state_hash`, i.e. every page is a snapshot of a single state root.  As the page
+/// token is the name of the last named key seen rather than an offset, walking the pages never
+/// returns a name twice nor skips a name which is present throughout, even if the entity is
+/// modified and later pages are requested against a newer state root.  Names added or removed
+/// before the token in the meantime aren't reflected by later pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListKeysRequest {
+    state_hash: Blake2bHash,
+    key: Key,
+    path: Vec<String>,
+    page_size: usize,
+    page_token: Option<String>,
+}
+
+impl ListKeysRequest {
+    /// Creates a request for the first page.  A `page_size` of zero is replaced by
+    /// [`DEFAULT_PAGE_SIZE`], and one above [`MAX_PAGE_SIZE`] is lowered to it.
+    pub fn new(state_hash: Blake2bHash, key: Key, path: Vec<String>, page_size: usize) -> Self {
+        let page_size = if page_size == 0 {
+            DEFAULT_PAGE_SIZE
+        } else {
+            cmp::min(page_size, MAX_PAGE_SIZE)
+        };
+        ListKeysRequest {
+            state_hash,
+            key,
+            path,
+            page_size,
+            page_token: None,
+        }
+    }
+
+    /// Requests the page following the named key called `page_token`.
+    pub fn with_page_token(mut self, page_token: String) -> Self {
+        self.page_token = Some(page_token);
+        self
+    }
+
+    pub fn state_hash(&self) -> Blake2bHash {
+        self.state_hash
+    }
+
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn page_token(&self) -> Option<&str> {
+        self.page_token.as_ref().map(String::as_str)
+    }
+}
+
+/// One page of named keys, in lexicographic order of their names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedKeysPage {
+    pub named_keys: Vec<(String, Key)>,
+    /// Token to request the following page with; `None` if this is the last page.
+    pub next_page_token: Option<String>,
+}
+
+impl NamedKeysPage {
+    /// Returns at most `page_size` entries of `named_keys` whose names follow `page_token`.
+    ///
+    /// Only the returned entries are cloned.
+    pub fn new(
+        named_keys: &BTreeMap<String, Key>,
+        page_size: usize,
+        page_token: Option<&str>,
+    ) -> Self {
+        let lower_bound = match page_token {
+            Some(page_token) => Bound::Excluded(page_token),
+            None => Bound::Unbounded,
+        };
+        let mut remaining = named_keys.range::<str, _>((lower_bound, Bound::Unbounded));
+        let page: Vec<(String, Key)> = remaining
+            .by_ref()
+            .take(page_size)
+            .map(|(name, key)| (name.clone(), *key))
+            .collect();
+        let next_page_token = match remaining.next() {
+            Some(_) => page.last().map(|(name, _)| name.clone()),
+            None => None,
+        };
+        NamedKeysPage {
+            named_keys: page,
+            next_page_token,
+        }
+    }
+}
+
+pub enum ListKeysResult {
+    RootNotFound,
+    ValueNotFound(String),
+    CircularReference(String),
+    /// The value found has no named keys, i.e. it is neither an account nor a contract.
+    NoNamedKeys(String),
+    Success(NamedKeysPage),
+}
+
+impl ListKeysResult {
+    pub(crate) fn from_query_result(
+        tracking_copy_query_result: TrackingCopyQueryResult,
+        page_size: usize,
+        page_token: Option<&str>,
+    ) -> Self {
+        match tracking_copy_query_result {
+            TrackingCopyQueryResult::ValueNotFound(message) => {
+                ListKeysResult::ValueNotFound(message)
+            }
+            TrackingCopyQueryResult::CircularReference(message) => {
+                ListKeysResult::CircularReference(message)
+            }
+            TrackingCopyQueryResult::Success(value) => {
+                let named_keys = match value.named_keys() {
+                    Some(named_keys) => named_keys,
+                    None => {
+                        return ListKeysResult::NoNamedKeys(format!(
+                            "{} has no named keys",
+                            value.type_name()
+                        ))
+                    }
+                };
+                ListKeysResult::Success(NamedKeysPage::new(named_keys, page_size, page_token))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn named_keys(count: u8) -> BTreeMap<String, Key> {
+        (0..count)
+            .map(|i| (format!("key-{:03}", i), Key::Hash([i; 32])))
+            .collect()
+    }
+
+    fn walk(named_keys: &BTreeMap<String, Key>, page_size: usize) -> Vec<(String, Key)> {
+        let mut result = Vec::new();
+        let mut page_token = None;
+        loop {
+            let page = NamedKeysPage::new(
+                named_keys,
+                page_size,
+                page_token.as_ref().map(String::as_str),
+            );
+            assert!(page.named_keys.len() <= page_size);
+            result.extend(page.named_keys);
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => return result,
+            }
+        }
+    }
+
+    #[test]
+    fn should_walk_all_named_keys_in_pages() {
+        let named_keys = named_keys(25);
+        let expected: Vec<(String, Key)> = named_keys.clone().into_iter().collect();
+        for page_size in &[1, 7, 25, 26] {
+            assert_eq!(walk(&named_keys, *page_size), expected);
+        }
+    }
+
+    #[test]
+    fn should_not_return_next_page_token_for_last_page() {
+        let named_keys = named_keys(10);
+        let page = NamedKeysPage::new(&named_keys, 10, None);
+        assert_eq!(page.named_keys.len(), 10);
+        assert!(page.next_page_token.is_none());
+
+        let page = NamedKeysPage::new(&BTreeMap::new(), 10, None);
+        assert!(page.named_keys.is_empty());
+        assert!(page.next_page_token.is_none());
+    }
+
+    #[test]
+    fn should_cap_page_size() {
+        let request = |page_size| {
+            ListKeysRequest::new(Blake2bHash::new(&[]), Key::Hash([0; 32]), vec![], page_size)
+                .page_size()
+        };
+        assert_eq!(request(0), DEFAULT_PAGE_SIZE);
+        assert_eq!(request(1), 1);
+        assert_eq!(request(MAX_PAGE_SIZE), MAX_PAGE_SIZE);
+        assert_eq!(request(MAX_PAGE_SIZE + 1), MAX_PAGE_SIZE);
+        assert_eq!(request(usize::max_value()), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn should_resume_after_removed_page_token() {
+        let mut named_keys = named_keys(10);
+        let first_page = NamedKeysPage::new(&named_keys, 4, None);
+        let page_token = first_page.next_page_token.expect("should have next page");
+        assert_eq!(page_token, "key-003");
+
+        // The last name of the first page is removed before the second page is requested.
+        named_keys.remove(&page_token);
+        let second_page = NamedKeysPage::new(&named_keys, 4, Some(&page_token));
+        let names: Vec<&str> = second_page
+            .named_keys
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+        assert_eq!(names, vec!["key-004", "key-005", "key-006", "key-007"]);
+    }
+}
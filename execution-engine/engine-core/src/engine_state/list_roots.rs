@@ -0,0 +1,71 @@
+use engine_shared::newtypes::Blake2bHash;
+use engine_storage::root_metadata_store::RootMetadata;
+
+/// Page size used when a request doesn't specify one.
+pub const DEFAULT_PAGE_SIZE: usize = 1000;
+
+/// A request for one page of the state roots recorded in global state.
+///
+/// Roots are listed in ascending order of their hashes rather than by creation time, so that the
+/// page token is simply the last hash seen.  Roots committed or pruned while walking the pages are
+/// reflected by later pages only if they follow the token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListRootsRequest {
+    page_size: usize,
+    page_token: Option<Blake2bHash>,
+}
+
+impl ListRootsRequest {
+    /// Creates a request for the first page.  A `page_size` of zero is replaced by
+    /// [`DEFAULT_PAGE_SIZE`].
+    pub fn new(page_size: usize) -> Self {
+        let page_size = if page_size == 0 {
+            DEFAULT_PAGE_SIZE
+        } else {
+            page_size
+        };
+        ListRootsRequest {
+            page_size,
+            page_token: None,
+        }
+    }
+
+    /// Requests the page following the root `page_token`.
+    pub fn with_page_token(mut self, page_token: Blake2bHash) -> Self {
+        self.page_token = Some(page_token);
+        self
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    pub fn page_token(&self) -> Option<Blake2bHash> {
+        self.page_token
+    }
+}
+
+/// One page of state roots, in ascending order of their hashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootsPage {
+    pub roots: Vec<(Blake2bHash, RootMetadata)>,
+    /// Token to request the following page with; `None` if this is the last page.
+    pub next_page_token: Option<Blake2bHash>,
+}
+
+impl RootsPage {
+    /// Builds a page of at most `page_size` roots from `roots`, which were read with a limit of
+    /// `page_size + 1` so that a following page can be detected.
+    pub(crate) fn new(mut roots: Vec<(Blake2bHash, RootMetadata)>, page_size: usize) -> Self {
+        let next_page_token = if roots.len() > page_size {
+            roots.truncate(page_size);
+            roots.last().map(|(root, _)| *root)
+        } else {
+            None
+        };
+        RootsPage {
+            roots,
+            next_page_token,
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! An opt-in check of the mint's double-entry invariant: the balances of all purses sum to exactly
+//! the total supply of motes recorded by the mint.
+//!
+//! Every purse's balance uref is recorded under a local key of the mint, so the balances are found
+//! by listing the keys with the mint's local key prefix.  Walking every purse is expensive for a
+//! large state, so the check is meant for tests and debug nodes, where it can also be sampled.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+};
+
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_storage::global_state::StateReader;
+use mint::TOTAL_SUPPLY_LOCAL_KEY;
+use types::{bytesrepr::ToBytes, CLType, Key, URef, U512};
+
+use crate::{
+    engine_state::genesis::{POS_BONDING_PURSE, POS_PAYMENT_PURSE, POS_REWARDS_PURSE},
+    execution,
+    runtime_context::local_key_prefix,
+};
+
+/// The PoS purses labelled by name in a [`MintInvariantViolation`].
+const POS_PURSE_NAMES: [&str; 3] = [POS_BONDING_PURSE, POS_PAYMENT_PURSE, POS_REWARDS_PURSE];
+
+/// The balance of a purse written by the commit which broke the invariant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PurseChange {
+    /// The key of the purse's balance uref.
+    pub balance_key: Key,
+    /// The name of the purse, if it is one of the PoS purses.
+    pub name: Option<String>,
+    /// The balance before the commit, or `None` if the purse didn't exist.
+    pub before: Option<U512>,
+    /// The balance after the commit.
+    pub after: Option<U512>,
+}
+
+impl Display for PurseChange {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let name = self.name.as_ref().map(String::as_str).unwrap_or("purse");
+        write!(
+            formatter,
+            "{} {}: {:?} -> {:?}",
+            name,
+            self.balance_key.as_string(),
+            self.before,
+            self.after
+        )
+    }
+}
+
+/// The purses' balances didn't sum to the total supply after a commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MintInvariantViolation {
+    /// The root committed.
+    pub state_hash: Blake2bHash,
+    /// The total supply recorded by the mint.
+    pub total_supply: U512,
+    /// The sum of the balances of all purses.
+    pub total_balance: U512,
+    /// The purses whose balances the commit wrote.
+    pub changed_purses: Vec<PurseChange>,
+}
+
+impl Display for MintInvariantViolation {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "purses at {} hold {} motes but the total supply is {}; changed purses: [",
+            self.state_hash, self.total_balance, self.total_supply
+        )?;
+        for (index, change) in self.changed_purses.iter().enumerate() {
+            if index > 0 {
+                write!(formatter, ", ")?;
+            }
+            write!(formatter, "{}", change)?;
+        }
+        write!(formatter, "]")
+    }
+}
+
+/// Checks the invariant at the root read by `post_state`, the result of committing effects which
+/// wrote `changed_keys` on top of the root read by `pre_state`.
+///
+/// Returns `None` if the invariant holds, or if the root doesn't record a total supply, i.e. its
+/// genesis was committed before the mint recorded one.
+pub(crate) fn check<R>(
+    correlation_id: CorrelationId,
+    pre_state: &R,
+    post_state: &R,
+    state_hash: Blake2bHash,
+    mint: URef,
+    proof_of_stake: URef,
+    changed_keys: &[Key],
+) -> Result<Option<MintInvariantViolation>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let total_supply_key = Key::local(mint.addr(), &TOTAL_SUPPLY_LOCAL_KEY.to_bytes()?);
+    let total_supply = match read_u512(correlation_id, post_state, &total_supply_key)? {
+        Some(total_supply) => total_supply,
+        None => return Ok(None),
+    };
+
+    let balance_keys = balance_keys(correlation_id, post_state, mint)?;
+    let mut total_balance = U512::zero();
+    for balance_key in &balance_keys {
+        let balance = read_u512(correlation_id, post_state, balance_key)?.unwrap_or_default();
+        total_balance = total_balance.saturating_add(balance);
+    }
+    if total_balance == total_supply {
+        return Ok(None);
+    }
+
+    let pos_purse_names = pos_purse_names(correlation_id, post_state, mint, proof_of_stake)?;
+    let mut changed_purses = Vec::new();
+    for changed_key in changed_keys {
+        let balance_key = changed_key.normalize();
+        if !balance_keys.contains(&balance_key) {
+            continue;
+        }
+        changed_purses.push(PurseChange {
+            balance_key,
+            name: pos_purse_names.get(&balance_key).map(ToString::to_string),
+            before: read_u512(correlation_id, pre_state, &balance_key)?,
+            after: read_u512(correlation_id, post_state, &balance_key)?,
+        });
+    }
+
+    Ok(Some(MintInvariantViolation {
+        state_hash,
+        total_supply,
+        total_balance,
+        changed_purses,
+    }))
+}
+
+/// Returns the keys of the balance urefs of all purses.
+fn balance_keys<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+    mint: URef,
+) -> Result<BTreeSet<Key>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let prefix = local_key_prefix(mint.addr())?;
+    let local_keys = reader
+        .keys_with_prefix(correlation_id, &prefix)
+        .map_err(Into::into)?;
+
+    let mut balance_keys = BTreeSet::new();
+    for local_key in local_keys {
+        // the mint's other local keys hold the total supply, revoked purse markers and allowances
+        if let Some(StoredValue::CLValue(cl_value)) = reader
+            .read(correlation_id, &local_key)
+            .map_err(Into::into)?
+        {
+            if *cl_value.cl_type() == CLType::Key {
+                let balance_key: Key = cl_value.into_t()?;
+                balance_keys.insert(balance_key.normalize());
+            }
+        }
+    }
+    Ok(balance_keys)
+}
+
+/// Returns the names of the PoS purses, keyed by the keys of their balance urefs.
+fn pos_purse_names<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+    mint: URef,
+    proof_of_stake: URef,
+) -> Result<BTreeMap<Key, &'static str>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let mut names = BTreeMap::new();
+    let contract = match reader
+        .read(correlation_id, &Key::URef(proof_of_stake).normalize())
+        .map_err(Into::into)?
+    {
+        Some(StoredValue::Contract(contract)) => contract,
+        _ => return Ok(names),
+    };
+    for name in POS_PURSE_NAMES.iter() {
+        let purse = match contract.named_keys().get(*name).and_then(Key::as_uref) {
+            Some(purse) => *purse,
+            None => continue,
+        };
+        let local_key = Key::local(mint.addr(), &purse.addr().to_bytes()?);
+        if let Some(StoredValue::CLValue(cl_value)) = reader
+            .read(correlation_id, &local_key)
+            .map_err(Into::into)?
+        {
+            let balance_key: Key = cl_value.into_t()?;
+            names.insert(balance_key.normalize(), *name);
+        }
+    }
+    Ok(names)
+}
+
+fn read_u512<R>(
+    correlation_id: CorrelationId,
+    reader: &R,
+    key: &Key,
+) -> Result<Option<U512>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    match reader.read(correlation_id, key).map_err(Into::into)? {
+        Some(StoredValue::CLValue(cl_value)) => Ok(Some(cl_value.into_t()?)),
+        _ => Ok(None),
+    }
+}
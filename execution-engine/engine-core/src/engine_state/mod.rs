@@ -1,3 +1,6 @@
+pub mod active_protocol_version;
+pub mod balance;
+pub mod commit_notifications;
 pub mod deploy_item;
 pub mod engine_config;
 mod error;
@@ -5,20 +8,42 @@ pub mod executable_deploy_item;
 pub mod execute_request;
 pub mod execution_effect;
 pub mod execution_result;
+pub mod execution_stats;
+pub mod exit_code;
 pub mod genesis;
+pub mod genesis_timestamp;
+pub mod list_keys;
+pub mod list_roots;
+pub mod mint_invariants;
+pub mod module_cache;
 pub mod op;
 pub mod query;
+pub mod query_cache;
+pub mod replay;
+pub mod repro_bundle;
 pub mod run_genesis_request;
+pub mod scheduled_calls;
+pub mod step;
 pub mod system_contract_cache;
+pub mod trie_nodes;
 pub mod upgrade;
-pub mod utils;
+pub mod validate_deploy;
 
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, BTreeSet, HashMap},
+    collections::{btree_map, BTreeMap, BTreeSet, HashMap},
     rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 
+use blake2::{
+    digest::{Input, VariableOutput},
+    VarBlake2b,
+};
 use num_traits::Zero;
 use parity_wasm::elements::Module;
 
@@ -26,6 +51,7 @@ use contract::args_parser::ArgsParser;
 use engine_shared::{
     account::Account,
     additive_map::AdditiveMap,
+    contract::Contract,
     gas::Gas,
     motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
@@ -34,14 +60,29 @@ use engine_shared::{
     wasm,
 };
 use engine_storage::{
-    global_state::{CommitResult, StateProvider, StateReader},
+    global_state::{
+        durability::Durability,
+        in_memory::InMemoryGlobalState,
+        read_stats::{CountingStateProvider, ReadStats},
+        BatchCommitResult, CommitResult, StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
+    trie_store::node_cache::TrieNodeCache,
 };
 use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+use proof_of_stake::StakeKeyName;
 use types::{
-    account::PublicKey, bytesrepr::ToBytes, system_contract_errors::mint,
-    system_contract_type::PROOF_OF_STAKE, AccessRights, BlockTime, Key, Phase, ProtocolVersion,
-    URef, KEY_HASH_LENGTH, U512, UREF_ADDR_LENGTH,
+    account::PublicKey,
+    bytesrepr::{self, ToBytes},
+    system_contract_errors::mint,
+    system_contract_names::{
+        auction::{METHOD_RUN_AUCTION, WINNERS_KEY},
+        mint::{METHOD_MINT, METHOD_TRANSFER},
+        METHOD_FINALIZE_PAYMENT,
+    },
+    system_contract_type::{AUCTION, PROOF_OF_STAKE},
+    AccessRights, BlockTime, CLValue, Key, Phase, ProtocolVersion, ScheduleId, URef,
+    KEY_HASH_LENGTH, U512, UREF_ADDR_LENGTH,
 };
 
 pub use self::{
@@ -50,20 +91,43 @@ pub use self::{
 };
 use crate::{
     engine_state::{
+        balance::{self, BalanceError},
+        commit_notifications::{CommitNotification, CommitNotifier, CommitReceiver},
         deploy_item::DeployItem,
         error::Error::MissingSystemContract,
         executable_deploy_item::ExecutableDeployItem,
         execute_request::ExecuteRequest,
+        execution_effect::ExecutionEffect,
         execution_result::{ExecutionResult, ForcedTransferResult},
+        execution_stats::{ExecutionStats, RequestStats},
         genesis::{
-            ExecConfig, GenesisAccount, GenesisResult, POS_PAYMENT_PURSE, POS_REWARDS_PURSE,
+            ExecConfig, GenesisAccount, GenesisAccountReport, GenesisBondingReport, GenesisRecord,
+            GenesisResult, POS_PAYMENT_PURSE, POS_REFUND_PURSE, POS_REWARDS_PURSE,
         },
-        query::{QueryRequest, QueryResult},
+        list_keys::{ListKeysRequest, ListKeysResult},
+        list_roots::{self, ListRootsRequest, RootsPage},
+        mint_invariants::{self, MintInvariantViolation},
+        module_cache::ModuleCache,
+        query::{MultiRootQueryRequest, QueryRequest, QueryResult},
+        query_cache::QueryCache,
+        replay::{self, ExecutionTrace, ReplayRequest, ReplayResult, ReplayedDeploy},
+        repro_bundle::{
+            BundleReplayResult, ExportReproBundleRequest, RecordingStateProvider, ReproBundle,
+        },
+        scheduled_calls::{ScheduledCall, SCHEDULED_CALL_ESCROW},
+        step::{ExecutedCall, StepRequest, StepResult},
         system_contract_cache::SystemContractCache,
-        upgrade::{UpgradeConfig, UpgradeResult},
+        trie_nodes::TrieNodesRequest,
+        upgrade::{
+            AuctionConfig, ConfigDiff, SystemContractCodeHashes, UpgradeConfig, UpgradeResult,
+        },
+        validate_deploy::{ValidateDeployRequest, ValidateDeployResult},
     },
-    execution::{self, AddressGenerator, AddressGeneratorBuilder, Executor, MINT_NAME, POS_NAME},
-    tracking_copy::{TrackingCopy, TrackingCopyExt},
+    execution::{
+        self, AddressGenerator, AddressGeneratorBuilder, Executor, HostCallStats, SystemContext,
+        MINT_NAME, POS_NAME,
+    },
+    tracking_copy::{EffectLimits, TrackingCopy, TrackingCopyExt},
     KnownKeys,
 };
 
@@ -71,19 +135,50 @@ use crate::{
 // TBD gas * CONV_RATE = motes
 pub const MAX_PAYMENT: u64 = 10_000_000;
 pub const CONV_RATE: u64 = 10;
+/// The gas available to an account's authorization contract, charged on top of the payment code's
+/// cost.
+pub const AUTHORIZATION_CONTRACT_GAS_LIMIT: u64 = 250_000;
 
 pub const SYSTEM_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
 
 const GENESIS_INITIAL_BLOCKTIME: u64 = 0;
-const MINT_METHOD_NAME: &str = "mint";
 
-#[derive(Debug)]
+/// Returns the key under which the engine keeps the record named `tag` in global state.
+///
+/// The key is the Blake2b hash of the tag, so records with different tags don't collide with each
+/// other, nor with stored contracts, whose hashes are derived from deploy hashes.
+pub(crate) fn engine_record_key(tag: &[u8]) -> Key {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.input(tag);
+    let mut hash_bytes = [0; 32];
+    hasher.variable_result(|hash| hash_bytes.clone_from_slice(hash));
+    Key::Hash(hash_bytes)
+}
+const ESCROW_PURSE_NAME: &str = "escrow_purse";
+const ACCOUNT_MAIN_PURSE_NAME: &str = "account_main_purse";
+
+#[derive(Clone, Debug)]
 pub struct EngineState<S> {
     config: EngineConfig,
     system_contract_cache: SystemContractCache,
+    module_cache: ModuleCache,
+    query_cache: Option<QueryCache>,
+    /// The number of successful commits, for sampling the mint invariant checks.
+    commit_count: Arc<AtomicU64>,
+    commit_notifier: CommitNotifier,
     state: S,
 }
 
+/// An upgrade whose installers have run on a tracking copy of its pre-state, but which is neither
+/// persisted nor committed yet.
+struct PreparedUpgrade<R> {
+    tracking_copy: Rc<RefCell<TrackingCopy<R>>>,
+    new_protocol_data: ProtocolData,
+    /// The system contracts whose code was replaced.
+    upgraded_system_contracts: Vec<URef>,
+    changes: ConfigDiff,
+}
+
 impl<S> EngineState<S>
 where
     S: StateProvider,
@@ -91,9 +186,16 @@ where
 {
     pub fn new(state: S, config: EngineConfig) -> EngineState<S> {
         let system_contract_cache = Default::default();
+        let module_cache = Default::default();
+        let query_cache = config.query_cache_capacity().map(QueryCache::new);
+        let commit_notifier = CommitNotifier::new(config.commit_notification_capacity());
         EngineState {
             config,
             system_contract_cache,
+            module_cache,
+            query_cache,
+            commit_count: Default::default(),
+            commit_notifier,
             state,
         }
     }
@@ -102,13 +204,97 @@ where
         &self.config
     }
 
+    /// Returns an engine running against `state` which shares this engine's config and caches.
+    fn with_state<T>(&self, state: T) -> EngineState<T> {
+        EngineState {
+            config: self.config,
+            system_contract_cache: self.system_contract_cache.clone(),
+            module_cache: self.module_cache.clone(),
+            query_cache: self.query_cache.clone(),
+            commit_count: Arc::clone(&self.commit_count),
+            commit_notifier: self.commit_notifier.clone(),
+            state,
+        }
+    }
+
+    /// Returns the global state the engine runs against.
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Returns the cache of preprocessed session and payment modules.
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
+
+    /// Returns the cache of deserialized system contracts.
+    pub fn system_contract_cache(&self) -> &SystemContractCache {
+        &self.system_contract_cache
+    }
+
+    /// Returns the cache of query results, or `None` if query results aren't cached.
+    pub fn query_cache(&self) -> Option<&QueryCache> {
+        self.query_cache.as_ref()
+    }
+
+    /// Returns a receiver of a notification for every commit succeeding from now on, whether of
+    /// genesis, an upgrade, a step or effects.
+    ///
+    /// A receiver which falls behind by more than
+    /// [`EngineConfig::commit_notification_capacity`] notifications has the oldest dropped rather
+    /// than delaying commits.
+    pub fn subscribe_commits(&self) -> CommitReceiver {
+        self.commit_notifier.subscribe()
+    }
+
+    /// Returns the Wasm costs of `protocol_version` as configured at `state_hash`, or `None` if
+    /// `state_hash` is not found.
+    ///
+    /// See [`get_protocol_data_at`](EngineState::get_protocol_data_at) for when
+    /// [`Error::ConfigNotFound`] is returned.
     pub fn wasm_costs(
         &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
         protocol_version: ProtocolVersion,
     ) -> Result<Option<WasmCosts>, Error> {
+        let protocol_data =
+            self.get_protocol_data_at(correlation_id, state_hash, protocol_version)?;
+        Ok(protocol_data.map(|protocol_data| *protocol_data.wasm_costs()))
+    }
+
+    /// Returns the protocol data of `protocol_version` as configured at `state_hash`, or `None` if
+    /// `state_hash` is not found.
+    ///
+    /// Returns [`Error::ConfigNotFound`] if no protocol data was ever stored for
+    /// `protocol_version`, or if `protocol_version` is newer than the
+    /// [version active](active_protocol_version) at `state_hash`, e.g. when the node has reverted
+    /// to a root preceding the upgrade to `protocol_version`.
+    pub fn get_protocol_data_at(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Error> {
+        let reader = match self.state.checkout(state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let config_not_found = Error::ConfigNotFound {
+            protocol_version,
+            state_hash,
+        };
+        // roots committed before versions were recorded accept any version with protocol data
+        if let Some(active_protocol_version) =
+            active_protocol_version::read(correlation_id, &reader)?
+        {
+            if protocol_version > active_protocol_version {
+                return Err(config_not_found);
+            }
+        }
         match self.get_protocol_data(protocol_version)? {
-            Some(protocol_data) => Ok(Some(*protocol_data.wasm_costs())),
-            None => Ok(None),
+            Some(protocol_data) => Ok(Some(protocol_data)),
+            None => Err(config_not_found),
         }
     }
 
@@ -123,18 +309,51 @@ where
         }
     }
 
+    /// Runs genesis with `ee_config` on top of the empty root and commits its effects.
+    ///
+    /// Unless `force_fresh_genesis` is set, genesis is refused with
+    /// [`GenesisResult::StoreAlreadyInitialized`] if the store already holds one, as a second
+    /// genesis creates a root unrelated to the existing ones.
     pub fn commit_genesis(
         &self,
         correlation_id: CorrelationId,
         genesis_config_hash: Blake2bHash,
         protocol_version: ProtocolVersion,
         ee_config: &ExecConfig,
-    ) -> Result<GenesisResult, Error> {
+        force_fresh_genesis: bool,
+    ) -> Result<GenesisResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        if !force_fresh_genesis {
+            if let Some(genesis_record) = self.genesis_record()? {
+                return Ok(GenesisResult::StoreAlreadyInitialized(genesis_record));
+            }
+        }
+        let genesis_fingerprint = ee_config.fingerprint()?;
+
         // Preliminaries
         let executor = Executor::new(self.config);
-        let blocktime = BlockTime::new(GENESIS_INITIAL_BLOCKTIME);
-        let gas_limit = Gas::new(std::u64::MAX.into());
         let phase = Phase::System;
+        let system_context = SystemContext::new(
+            phase,
+            BlockTime::from_millis(GENESIS_INITIAL_BLOCKTIME),
+            genesis_config_hash.into(),
+            BTreeSet::new(),
+        );
+
+        // accounts created by transfers must be usable by their own key
+        let account_config = ee_config.account_config();
+        if !account_config.is_valid() {
+            return Err(Error::InvalidAccountConfig);
+        }
+
+        // reject the accounts before the mint installer runs, so that a broken config never
+        // produces any effects
+        if let Err(genesis_result) = ee_config.validate_accounts() {
+            return Ok(genesis_result);
+        }
+        let bonding_reports: Vec<GenesisBondingReport> = ee_config.bonding_reports().collect();
 
         let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
         let initial_root_hash = self.state.empty_root();
@@ -180,8 +399,6 @@ where
             let mint_installer_module = preprocessor.preprocess(mint_installer_bytes)?;
             let args = Vec::new();
             let mut named_keys = BTreeMap::new();
-            let authorization_keys: BTreeSet<PublicKey> = BTreeSet::new();
-            let install_deploy_hash = genesis_config_hash.into();
             let address_generator = Rc::clone(&address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
@@ -192,15 +409,11 @@ where
                 &mut named_keys,
                 initial_base_key,
                 &virtual_system_account,
-                authorization_keys,
-                blocktime,
-                install_deploy_hash,
-                gas_limit,
+                &system_context,
                 address_generator,
                 protocol_version,
                 correlation_id,
                 tracking_copy,
-                phase,
                 ProtocolData::default(),
                 system_contract_cache,
             )?
@@ -210,7 +423,8 @@ where
         // argument
         let proof_of_stake_reference: URef = {
             // Spec #6: Compute initially bonded validators as the contents of accounts_path
-            // filtered to non-zero staked amounts.
+            // filtered to non-zero staked amounts, less the validators skipped because they can't
+            // be bonded.
             let bonded_validators: BTreeMap<PublicKey, U512> = ee_config
                 .get_bonded_validators()
                 .map(|(k, v)| (k, v.value()))
@@ -218,7 +432,6 @@ where
 
             let tracking_copy = Rc::clone(&tracking_copy);
             let address_generator = Rc::clone(&address_generator);
-            let install_deploy_hash = genesis_config_hash.into();
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
             // Constructs a partial protocol data with already known uref to pass the validation
@@ -236,7 +449,6 @@ where
                     .expect("args should serialize")
             };
             let mut named_keys = BTreeMap::new();
-            let authorization_keys: BTreeSet<PublicKey> = BTreeSet::new();
 
             executor.exec_system(
                 proof_of_stake_installer_module,
@@ -244,15 +456,11 @@ where
                 &mut named_keys,
                 initial_base_key,
                 &virtual_system_account,
-                authorization_keys,
-                blocktime,
-                install_deploy_hash,
-                gas_limit,
+                &system_context,
                 address_generator,
                 protocol_version,
                 correlation_id,
                 tracking_copy,
-                phase,
                 partial_protocol_data,
                 system_contract_cache,
             )?
@@ -284,8 +492,6 @@ where
                 preprocessor.preprocess(standard_payment_installer_bytes)?;
             let args = Vec::new();
             let mut named_keys = BTreeMap::new();
-            let authorization_keys = BTreeSet::new();
-            let install_deploy_hash = genesis_config_hash.into();
             let address_generator = Rc::clone(&address_generator);
             let tracking_copy = Rc::clone(&tracking_copy);
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
@@ -296,15 +502,11 @@ where
                 &mut named_keys,
                 initial_base_key,
                 &virtual_system_account,
-                authorization_keys,
-                blocktime,
-                install_deploy_hash,
-                gas_limit,
+                &system_context,
                 address_generator,
                 protocol_version,
                 correlation_id,
                 tracking_copy,
-                phase,
                 protocol_data,
                 system_contract_cache,
             )?
@@ -316,11 +518,14 @@ where
             mint_reference,
             proof_of_stake_reference,
             standard_payment_reference,
+            account_config,
         );
 
-        self.state
-            .put_protocol_data(protocol_version, &protocol_data)
-            .map_err(Into::into)?;
+        active_protocol_version::write(&mut *tracking_copy.borrow_mut(), protocol_version);
+        genesis_timestamp::write(
+            &mut *tracking_copy.borrow_mut(),
+            ee_config.genesis_timestamp(),
+        );
 
         //
         // NOTE: The following stanzas deviate from the implementation strategy described in the
@@ -353,22 +558,31 @@ where
             ret
         };
 
+        // Create the system account.  It has no purse and no associated keys, so no user deploy
+        // can be authorized by it; only system deploys run from it.
+        {
+            let system_account = Account::new(
+                SYSTEM_ACCOUNT_ADDR,
+                system_account_named_keys,
+                URef::new(Default::default(), AccessRights::READ_ADD_WRITE),
+                Default::default(),
+                Default::default(),
+            );
+            tracking_copy.borrow_mut().write(
+                Key::Account(SYSTEM_ACCOUNT_ADDR),
+                StoredValue::Account(system_account),
+            );
+        }
+
         // Create accounts
         {
-            // Collect chainspec accounts and their known keys with the genesis account and its
-            // known keys
-            let accounts = {
-                let mut ret: Vec<(GenesisAccount, KnownKeys)> = ee_config
-                    .accounts()
-                    .to_vec()
-                    .into_iter()
-                    .map(|account| (account, account_named_keys.clone()))
-                    .collect();
-                let system_account =
-                    GenesisAccount::new(SYSTEM_ACCOUNT_ADDR, Motes::zero(), Motes::zero());
-                ret.push((system_account, system_account_named_keys));
-                ret
-            };
+            // Collect chainspec accounts and their known keys
+            let accounts: Vec<(GenesisAccount, KnownKeys)> = ee_config
+                .accounts()
+                .to_vec()
+                .into_iter()
+                .map(|account| (account, account_named_keys.clone()))
+                .collect();
 
             // Get the mint module
             let module = {
@@ -384,7 +598,7 @@ where
                 let module = module.clone();
                 let args = {
                     let motes = account.balance().value();
-                    let args = (MINT_METHOD_NAME, motes);
+                    let args = (METHOD_MINT, motes);
                     ArgsParser::parse(args)
                         .expect("args should convert to `Vec<CLValue>`")
                         .into_bytes()
@@ -394,11 +608,15 @@ where
                 let tracking_copy_write = Rc::clone(&tracking_copy);
                 let mut named_keys_exec = BTreeMap::new();
                 let base_key = Key::URef(mint_reference);
-                let authorization_keys: BTreeSet<PublicKey> = BTreeSet::new();
                 let account_public_key = account.public_key();
                 // NOTE: As Ed25519 keys are currently supported by chainspec, PublicKey::value
                 // returns raw bytes of it
-                let purse_creation_deploy_hash = account_public_key.value();
+                let purse_creation_context = SystemContext::new(
+                    phase,
+                    system_context.blocktime(),
+                    account_public_key.value(),
+                    BTreeSet::new(),
+                );
                 let address_generator = {
                     let generator = AddressGeneratorBuilder::new()
                         .seed_with(&genesis_config_hash.value())
@@ -413,21 +631,17 @@ where
 
                 let mint_result: Result<URef, mint::Error> = {
                     // ...call the Mint's "mint" endpoint to create purse with tokens...
-                    let (_instance, mut runtime) = executor.create_runtime(
+                    let (_instance, mut runtime) = executor.create_system_runtime(
                         module,
                         args.clone(),
                         &mut named_keys_exec,
                         base_key,
                         &virtual_system_account,
-                        authorization_keys,
-                        blocktime,
-                        purse_creation_deploy_hash,
-                        gas_limit,
+                        &purse_creation_context,
                         address_generator,
                         protocol_version,
                         correlation_id,
                         tracking_copy_exec,
-                        phase,
                         protocol_data,
                         system_contract_cache,
                     )?;
@@ -455,6 +669,16 @@ where
         // Spec #15: Commit the transforms.
         let effects = tracking_copy.borrow().effect();
 
+        let account_reports = match GenesisAccountReport::from_effects(
+            ee_config.accounts(),
+            &effects.transforms,
+            mint_reference,
+            proof_of_stake_reference,
+        ) {
+            Ok(account_reports) => account_reports,
+            Err(genesis_result) => return Ok(genesis_result),
+        };
+
         let commit_result = self
             .state
             .commit(
@@ -463,9 +687,28 @@ where
                 effects.transforms.to_owned(),
             )
             .map_err(Into::into)?;
+        if let CommitResult::Success { state_root, .. } = commit_result {
+            self.state
+                .put_protocol_data(protocol_version, &protocol_data)
+                .map_err(Into::into)?;
+            self.state.record_genesis(state_root, genesis_fingerprint)?;
+        }
+        self.notify_effects_commit(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            initial_root_hash,
+            &effects,
+            &commit_result,
+        )?;
 
         // Return the result
-        let genesis_result = GenesisResult::from_commit_result(commit_result, effects);
+        let genesis_result = GenesisResult::from_commit_result(
+            commit_result,
+            effects,
+            account_reports,
+            bonding_reports,
+        );
 
         Ok(genesis_result)
     }
@@ -474,7 +717,102 @@ where
         &self,
         correlation_id: CorrelationId,
         upgrade_config: UpgradeConfig,
-    ) -> Result<UpgradeResult, Error> {
+    ) -> Result<UpgradeResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let PreparedUpgrade {
+            tracking_copy,
+            new_protocol_data,
+            upgraded_system_contracts,
+            changes,
+        } = match self.prepare_upgrade(correlation_id, &upgrade_config)? {
+            Some(prepared_upgrade) => prepared_upgrade,
+            None => return Ok(UpgradeResult::RootNotFound),
+        };
+        let pre_state_hash = upgrade_config.pre_state_hash();
+        let new_protocol_version = upgrade_config.new_protocol_version();
+
+        active_protocol_version::write(&mut *tracking_copy.borrow_mut(), new_protocol_version);
+
+        let effects = tracking_copy.borrow().effect();
+
+        // commit
+        let commit_result = self
+            .state
+            .commit(
+                correlation_id,
+                pre_state_hash,
+                effects.transforms.to_owned(),
+            )
+            .map_err(Into::into)?;
+
+        // 3.1.2.2 persist wasm CostTable, only once the upgrade's effects have been committed
+        if let CommitResult::Success { .. } = commit_result {
+            self.state
+                .put_protocol_data(new_protocol_version, &new_protocol_data)
+                .map_err(Into::into)?;
+        }
+
+        self.notify_effects_commit(
+            correlation_id,
+            new_protocol_version,
+            new_protocol_data,
+            pre_state_hash,
+            &effects,
+            &commit_result,
+        )?;
+
+        // the modules of the upgraded system contracts are cached under the new protocol version,
+        // leaving the ones used to execute against pre-upgrade roots in place
+        if let CommitResult::Success { .. } = commit_result {
+            for uref in upgraded_system_contracts {
+                let contract = tracking_copy
+                    .borrow_mut()
+                    .get_contract(correlation_id, Key::URef(uref))?;
+                let module = engine_wasm_prep::deserialize(contract.bytes())?;
+                self.system_contract_cache
+                    .insert(uref, new_protocol_version, module);
+            }
+        }
+
+        // return result and effects
+        Ok(UpgradeResult::from_commit_result(
+            commit_result,
+            effects,
+            changes,
+        ))
+    }
+
+    /// Diffs the configuration `upgrade_config` upgrades to against the configuration stored for
+    /// the current protocol version, running the upgrade's installers on its pre-state without
+    /// committing anything.
+    ///
+    /// Returns `None` if the pre-state or the current protocol version's data is not found.
+    pub fn diff_upgrade(
+        &self,
+        correlation_id: CorrelationId,
+        upgrade_config: &UpgradeConfig,
+    ) -> Result<Option<ConfigDiff>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let prepared_upgrade = self.prepare_upgrade(correlation_id, upgrade_config)?;
+        Ok(prepared_upgrade.map(|prepared_upgrade| prepared_upgrade.changes))
+    }
+
+    /// Validates `upgrade_config` and runs its installers on a tracking copy of its pre-state,
+    /// leaving it to the caller to persist the resulting protocol data and commit the effects.
+    ///
+    /// Returns `None` if the pre-state or the current protocol version's data is not found.
+    fn prepare_upgrade(
+        &self,
+        correlation_id: CorrelationId,
+        upgrade_config: &UpgradeConfig,
+    ) -> Result<Option<PreparedUpgrade<S::Reader>>, Error>
+    where
+        Error: From<S::Error>,
+    {
         // per specification:
         // https://casperlabs.atlassian.net/wiki/spaces/EN/pages/139854367/Upgrading+System+Contracts+Specification
 
@@ -483,20 +821,24 @@ where
         let pre_state_hash = upgrade_config.pre_state_hash();
         let tracking_copy = match self.tracking_copy(pre_state_hash)? {
             Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
-            None => return Ok(UpgradeResult::RootNotFound),
+            None => return Ok(None),
         };
 
         // 3.1.1.1.1.2 current protocol version is required
         let current_protocol_version = upgrade_config.current_protocol_version();
-        let current_protocol_data = match self.state.get_protocol_data(current_protocol_version) {
-            Ok(Some(protocol_data)) => protocol_data,
-            Ok(None) => {
-                return Err(Error::InvalidProtocolVersion(current_protocol_version));
-            }
-            Err(error) => {
-                return Err(Error::Exec(error.into()));
-            }
+        let current_protocol_data = match self.get_protocol_data_at(
+            correlation_id,
+            pre_state_hash,
+            current_protocol_version,
+        )? {
+            Some(protocol_data) => protocol_data,
+            None => return Ok(None),
         };
+        let old_code_hashes = self.system_contract_code_hashes(
+            correlation_id,
+            &mut tracking_copy.borrow_mut(),
+            &current_protocol_data,
+        )?;
 
         // 3.1.1.1.1.3 activation point is not currently used by EE; skipping
         // 3.1.1.1.1.4 upgrade point protocol version validation
@@ -515,17 +857,72 @@ where
             None => *current_protocol_data.wasm_costs(),
         };
 
-        // 3.1.2.2 persist wasm CostTable
-        let new_protocol_data = ProtocolData::new(
+        let new_account_config = match upgrade_config.account_config() {
+            Some(new_account_config) if !new_account_config.is_valid() => {
+                return Err(Error::InvalidAccountConfig);
+            }
+            Some(new_account_config) => new_account_config,
+            None => *current_protocol_data.account_config(),
+        };
+
+        // the auction contract can only be installed once, and can only be used once installed
+        let auction_config = upgrade_config.auction_config();
+        let installs_auction = auction_config
+            .and_then(AuctionConfig::installer_bytes)
+            .is_some();
+        let new_use_auction = match auction_config {
+            Some(_) if installs_auction && current_protocol_data.auction().is_some() => {
+                return Err(Error::InvalidUpgradeConfig);
+            }
+            Some(auction_config)
+                if auction_config.use_auction()
+                    && !installs_auction
+                    && current_protocol_data.auction().is_none() =>
+            {
+                return Err(Error::InvalidUpgradeConfig);
+            }
+            Some(auction_config) => auction_config.use_auction(),
+            None => current_protocol_data.use_auction(),
+        };
+
+        // persisted below, once the upgrade has succeeded
+        let mut new_protocol_data = ProtocolData::new(
             new_wasm_costs,
             current_protocol_data.mint(),
             current_protocol_data.proof_of_stake(),
             current_protocol_data.standard_payment(),
-        );
+            new_account_config,
+        )
+        .with_auction(current_protocol_data.auction(), new_use_auction);
+
+        let deploy_hash: [u8; 32] = {
+            // seeds address generator w/ protocol version
+            let bytes: Vec<u8> = upgrade_config
+                .new_protocol_version()
+                .value()
+                .into_bytes()?
+                .to_vec();
+            Blake2bHash::new(&bytes).into()
+        };
 
-        self.state
-            .put_protocol_data(new_protocol_version, &new_protocol_data)
-            .map_err(Into::into)?;
+        // all installers run as the system account
+        let system_context = {
+            let mut authorization_keys = BTreeSet::new();
+            authorization_keys.insert(SYSTEM_ACCOUNT_ADDR);
+            SystemContext::new(
+                Phase::System,
+                BlockTime::default(),
+                deploy_hash,
+                authorization_keys,
+            )
+        };
+
+        // shared by all installers run during the upgrade so that they can't generate colliding
+        // addresses
+        let address_generator = {
+            let generator = AddressGenerator::new(&pre_state_hash.value(), Phase::System);
+            Rc::new(RefCell::new(generator))
+        };
 
         // 3.1.1.1.1.5 upgrade installer is optional except on major version upgrades
         match upgrade_config.upgrade_installer_bytes() {
@@ -565,31 +962,7 @@ where
                 let mut keys = BTreeMap::new();
 
                 let initial_base_key = Key::Account(SYSTEM_ACCOUNT_ADDR);
-                let authorization_keys = {
-                    let mut ret = BTreeSet::new();
-                    ret.insert(SYSTEM_ACCOUNT_ADDR);
-                    ret
-                };
-
-                let blocktime = BlockTime::default();
-
-                let deploy_hash = {
-                    // seeds address generator w/ protocol version
-                    let bytes: Vec<u8> = upgrade_config
-                        .new_protocol_version()
-                        .value()
-                        .into_bytes()?
-                        .to_vec();
-                    Blake2bHash::new(&bytes).into()
-                };
-
-                // upgrade has no gas limit; approximating with MAX
-                let gas_limit = Gas::new(std::u64::MAX.into());
-                let phase = Phase::System;
-                let address_generator = {
-                    let generator = AddressGenerator::new(&pre_state_hash.value(), phase);
-                    Rc::new(RefCell::new(generator))
-                };
+                let address_generator = Rc::clone(&address_generator);
                 let state = Rc::clone(&tracking_copy);
                 let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
@@ -601,103 +974,648 @@ where
                     &mut keys,
                     initial_base_key,
                     &system_account,
-                    authorization_keys,
-                    blocktime,
-                    deploy_hash,
-                    gas_limit,
+                    &system_context,
                     address_generator,
                     new_protocol_version,
                     correlation_id,
                     state,
-                    phase,
                     new_protocol_data,
                     system_contract_cache,
                 )?
             }
         }
 
-        let effects = tracking_copy.borrow().effect();
-
-        // commit
-        let commit_result = self
-            .state
-            .commit(
+        // replace the code of the system contracts in place, keeping their urefs
+        let mut upgraded_system_contracts = Vec::new();
+        if let Some(bytes) = upgrade_config.new_mint_code() {
+            let mint_reference = new_protocol_data.mint();
+            self.upgrade_system_contract(
                 correlation_id,
-                pre_state_hash,
-                effects.transforms.to_owned(),
-            )
-            .map_err(Into::into)?;
+                Rc::clone(&tracking_copy),
+                bytes,
+                Vec::new(),
+                mint_reference,
+                new_protocol_version,
+                new_protocol_data,
+                &system_context,
+                Rc::clone(&address_generator),
+            )?;
+            upgraded_system_contracts.push(mint_reference);
+        }
+        if let Some(bytes) = upgrade_config.new_pos_code() {
+            // the installer is passed the same args as at genesis, but without any validators as
+            // the existing stakes are kept
+            let args = {
+                let bonded_validators: BTreeMap<PublicKey, U512> = BTreeMap::new();
+                ArgsParser::parse((new_protocol_data.mint(), bonded_validators))
+                    .expect("args should convert to `Vec<CLValue>`")
+                    .into_bytes()?
+            };
+            let proof_of_stake_reference = new_protocol_data.proof_of_stake();
+            self.upgrade_system_contract(
+                correlation_id,
+                Rc::clone(&tracking_copy),
+                bytes,
+                args,
+                proof_of_stake_reference,
+                new_protocol_version,
+                new_protocol_data,
+                &system_context,
+                Rc::clone(&address_generator),
+            )?;
+            upgraded_system_contracts.push(proof_of_stake_reference);
+        }
 
-        // return result and effects
-        Ok(UpgradeResult::from_commit_result(commit_result, effects))
+        if let Some(auction_config) = auction_config {
+            if let Some(bytes) = auction_config.installer_bytes() {
+                let auction_reference = self.install_auction(
+                    correlation_id,
+                    Rc::clone(&tracking_copy),
+                    bytes,
+                    auction_config.validator_slots(),
+                    new_protocol_version,
+                    new_protocol_data,
+                    &system_context,
+                    address_generator,
+                )?;
+                new_protocol_data =
+                    new_protocol_data.with_auction(Some(auction_reference), new_use_auction);
+            }
+        }
+
+        let new_code_hashes = self.system_contract_code_hashes(
+            correlation_id,
+            &mut tracking_copy.borrow_mut(),
+            &new_protocol_data,
+        )?;
+        let changes = ConfigDiff::new(
+            &current_protocol_data,
+            &old_code_hashes,
+            &new_protocol_data,
+            &new_code_hashes,
+        );
+
+        Ok(Some(PreparedUpgrade {
+            tracking_copy,
+            new_protocol_data,
+            upgraded_system_contracts,
+            changes,
+        }))
     }
 
-    pub fn tracking_copy(
+    /// Returns the hashes of the code of the system contracts an upgrade can replace or install,
+    /// as found in `tracking_copy`, without recording any reads.
+    fn system_contract_code_hashes(
         &self,
-        hash: Blake2bHash,
-    ) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
-        match self.state.checkout(hash).map_err(Into::into)? {
-            Some(tc) => Ok(Some(TrackingCopy::new(tc))),
-            None => Ok(None),
-        }
+        correlation_id: CorrelationId,
+        tracking_copy: &mut TrackingCopy<S::Reader>,
+        protocol_data: &ProtocolData,
+    ) -> Result<SystemContractCodeHashes, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let mut code_hash = |uref: URef| -> Result<Blake2bHash, Error> {
+            let key = Key::URef(uref);
+            match tracking_copy.get(correlation_id, &key)? {
+                Some(StoredValue::Contract(contract)) => Ok(Blake2bHash::new(contract.bytes())),
+                _ => Err(Error::Exec(execution::Error::KeyNotFound(key))),
+            }
+        };
+        Ok(SystemContractCodeHashes {
+            mint: code_hash(protocol_data.mint())?,
+            proof_of_stake: code_hash(protocol_data.proof_of_stake())?,
+            auction: protocol_data.auction().map(&mut code_hash).transpose()?,
+        })
     }
 
-    pub fn run_query(
+    /// Runs the installer for a new version of the system contract stored under `contract_uref`
+    /// and replaces the existing contract's code with that of the newly installed one.
+    ///
+    /// The existing contract's named keys are kept, and any named keys of the newly installed
+    /// contract which don't already exist are added to them.
+    #[allow(clippy::too_many_arguments)]
+    fn upgrade_system_contract(
         &self,
         correlation_id: CorrelationId,
-        query_request: QueryRequest,
-    ) -> Result<QueryResult, Error> {
-        let tracking_copy = match self.tracking_copy(query_request.state_hash())? {
-            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
-            None => return Ok(QueryResult::RootNotFound),
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+        installer_bytes: &[u8],
+        args: Vec<u8>,
+        contract_uref: URef,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        system_context: &SystemContext,
+        address_generator: Rc<RefCell<AddressGenerator>>,
+    ) -> Result<(), Error> {
+        let installer_module = {
+            let preprocessor = Preprocessor::new(*protocol_data.wasm_costs());
+            preprocessor.preprocess(installer_bytes)?
         };
 
-        let tracking_copy = tracking_copy.borrow();
+        let system_account = tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)?;
+        let mut named_keys = BTreeMap::new();
+
+        let installed_uref: URef = Executor::new(self.config).exec_system(
+            installer_module,
+            args,
+            &mut named_keys,
+            Key::Account(SYSTEM_ACCOUNT_ADDR),
+            &system_account,
+            system_context,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            protocol_data,
+            SystemContractCache::clone(&self.system_contract_cache),
+        )?;
+
+        let mut tracking_copy = tracking_copy.borrow_mut();
+        let installed_contract =
+            tracking_copy.get_contract(correlation_id, installed_uref.into())?;
+        let existing_contract = tracking_copy.get_contract(correlation_id, contract_uref.into())?;
+
+        let (bytes, installed_named_keys, _) = installed_contract.destructure();
+        let (_, mut named_keys, _) = existing_contract.destructure();
+        for (name, key) in installed_named_keys {
+            named_keys.entry(name).or_insert(key);
+        }
 
-        Ok(tracking_copy
-            .query(correlation_id, query_request.key(), query_request.path())
-            .map_err(|err| Error::Exec(err.into()))?
-            .into())
+        let contract = Contract::new(bytes, named_keys, protocol_version);
+        tracking_copy.write(contract_uref.into(), StoredValue::Contract(contract));
+        Ok(())
     }
 
-    pub fn run_execute(
+    /// Runs the installer of the auction contract as the system account, passing it the mint and
+    /// the number of validators each auction selects, and returns the uref of the installed
+    /// contract.
+    #[allow(clippy::too_many_arguments)]
+    fn install_auction(
         &self,
         correlation_id: CorrelationId,
-        mut exec_request: ExecuteRequest,
-    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
-        // TODO: do not unwrap
-        let wasm_costs = self
-            .wasm_costs(exec_request.protocol_version)
-            .unwrap()
-            .unwrap();
-        let executor = Executor::new(self.config);
-        let preprocessor = Preprocessor::new(wasm_costs);
+        tracking_copy: Rc<RefCell<TrackingCopy<S::Reader>>>,
+        installer_bytes: &[u8],
+        validator_slots: u32,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        system_context: &SystemContext,
+        address_generator: Rc<RefCell<AddressGenerator>>,
+    ) -> Result<URef, Error> {
+        let installer_module = {
+            let preprocessor = Preprocessor::new(*protocol_data.wasm_costs());
+            preprocessor.preprocess(installer_bytes)?
+        };
+        let args = ArgsParser::parse((protocol_data.mint(), validator_slots))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()?;
 
-        let mut results = Vec::new();
+        let system_account = tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)?;
+        let mut named_keys = BTreeMap::new();
+
+        let auction_reference = Executor::new(self.config).exec_system(
+            installer_module,
+            args,
+            &mut named_keys,
+            Key::Account(SYSTEM_ACCOUNT_ADDR),
+            &system_account,
+            system_context,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            protocol_data,
+            SystemContractCache::clone(&self.system_contract_cache),
+        )?;
+        Ok(auction_reference)
+    }
 
-        for deploy_item in exec_request.take_deploys() {
-            let result = match deploy_item {
-                Ok(deploy_item) => self.deploy(
-                    correlation_id,
-                    &executor,
-                    &preprocessor,
-                    exec_request.protocol_version,
-                    exec_request.parent_state_hash,
-                    BlockTime::new(exec_request.block_time),
-                    deploy_item,
-                ),
-                Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
-                                                      * below */
+    /// Loads the mint and proof-of-stake contracts of the protocol version active at `state_hash`
+    /// into the system contract cache, so that the first deploy executed doesn't pay for loading
+    /// them.  The system account is read too, warming the trie nodes along its path.
+    ///
+    /// Returns the protocol version whose system contracts were loaded, or `None` if `state_hash`
+    /// is not found or doesn't record the protocol version active at it.
+    pub fn preload_system_contracts(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<Option<ProtocolVersion>, Error> {
+        let reader = match self.state.checkout(state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let protocol_version = match active_protocol_version::read(correlation_id, &reader)? {
+            Some(protocol_version) => protocol_version,
+            None => return Ok(None),
+        };
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => {
+                return Err(Error::ConfigNotFound {
+                    protocol_version,
+                    state_hash,
+                })
+            }
+        };
+        let preprocessor = Preprocessor::new(*protocol_data.wasm_costs());
+        let mut tracking_copy = TrackingCopy::new(reader);
+
+        tracking_copy.get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)?;
+        for reference in &[protocol_data.mint(), protocol_data.proof_of_stake()] {
+            if !self.system_contract_cache.has(reference, protocol_version) {
+                let module = self.load_system_contract_module(
+                    &mut tracking_copy,
+                    correlation_id,
+                    *reference,
+                    &preprocessor,
+                )?;
+                self.system_contract_cache
+                    .insert(*reference, protocol_version, module);
+            }
+        }
+        Ok(Some(protocol_version))
+    }
+
+    /// Loads the module of the system contract stored under `reference`, or the do-nothing module
+    /// if system contracts are replaced by host-side logic.
+    fn load_system_contract_module(
+        &self,
+        tracking_copy: &mut TrackingCopy<S::Reader>,
+        correlation_id: CorrelationId,
+        reference: URef,
+        preprocessor: &Preprocessor,
+    ) -> Result<Module, Error> {
+        if self.config.use_system_contracts() {
+            let contract = tracking_copy.get_contract(correlation_id, Key::URef(reference))?;
+            Ok(engine_wasm_prep::deserialize(contract.bytes())?)
+        } else {
+            Ok(wasm::do_nothing_module(preprocessor)?)
+        }
+    }
+
+    pub fn tracking_copy(
+        &self,
+        hash: Blake2bHash,
+    ) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
+        match self.state.checkout(hash).map_err(Into::into)? {
+            Some(tc) => Ok(Some(TrackingCopy::new(tc))),
+            None => Ok(None),
+        }
+    }
+
+    /// Runs `query_request`, serving it from the [query cache](EngineState::query_cache) if
+    /// enabled.  A requested projection is applied to the value found, so cached values are
+    /// projected too.
+    pub fn run_query(
+        &self,
+        correlation_id: CorrelationId,
+        query_request: QueryRequest,
+    ) -> Result<QueryResult, Error> {
+        let result = self.query_value(
+            correlation_id,
+            query_request.state_hash(),
+            query_request.key(),
+            query_request.path(),
+        )?;
+        Ok(match query_request.projection() {
+            Some(projection) => projection.apply(result),
+            None => result,
+        })
+    }
+
+    fn query_value(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        key: Key,
+        path: &[String],
+    ) -> Result<QueryResult, Error> {
+        if let Some(value) = self
+            .query_cache
+            .as_ref()
+            .and_then(|query_cache| query_cache.get(state_hash, key, path))
+        {
+            return Ok(QueryResult::Success(value));
+        }
+
+        let tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(QueryResult::RootNotFound),
+        };
+
+        let tracking_copy = tracking_copy.borrow();
+
+        let result: QueryResult = tracking_copy
+            .query(correlation_id, key, path)
+            .map_err(|err| Error::Exec(err.into()))?
+            .into();
+
+        if let (Some(query_cache), QueryResult::Success(value)) = (&self.query_cache, &result) {
+            query_cache.insert(state_hash, key, path, value);
+        }
+
+        Ok(result)
+    }
+
+    /// Runs the query of `multi_root_query_request` at each of its state roots, returning one
+    /// result per root in the same order.
+    ///
+    /// The trie nodes read are cached across the roots, so that the upper nodes shared by the
+    /// roots are read once, and a root listed more than once is only queried the first time.
+    pub fn run_multi_root_query(
+        &self,
+        correlation_id: CorrelationId,
+        multi_root_query_request: MultiRootQueryRequest,
+    ) -> Result<Vec<QueryResult>, Error> {
+        let trie_node_cache = TrieNodeCache::new();
+        let mut results_by_root: HashMap<Blake2bHash, QueryResult> = HashMap::new();
+        let mut results = Vec::with_capacity(multi_root_query_request.state_hashes().len());
+
+        for state_hash in multi_root_query_request.state_hashes() {
+            if let Some(result) = results_by_root.get(state_hash) {
+                results.push(result.clone());
+                continue;
+            }
+
+            let result = match self
+                .state
+                .checkout_with_cache(*state_hash, &trie_node_cache)
+                .map_err(Into::into)?
+            {
+                Some(reader) => TrackingCopy::new(reader)
+                    .query(
+                        correlation_id,
+                        multi_root_query_request.key(),
+                        multi_root_query_request.path(),
+                    )
+                    .map_err(|err| Error::Exec(err.into()))?
+                    .into(),
+                None => QueryResult::RootNotFound,
+            };
+            let result = match multi_root_query_request.projection() {
+                Some(projection) => projection.apply(result),
+                None => result,
+            };
+            results_by_root.insert(*state_hash, result.clone());
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    /// Returns the balance of the main purse of the account of `public_key` at `state_hash`,
+    /// reading the account, the mint's balance uref of its main purse and the balance in one go.
+    ///
+    /// On failure, the [`BalanceError`] names the hop which broke off and the key it failed on.
+    pub fn get_main_purse_balance(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        state_hash: Blake2bHash,
+        public_key: PublicKey,
+    ) -> Result<Motes, BalanceError> {
+        let protocol_data =
+            match self.get_protocol_data_at(correlation_id, state_hash, protocol_version)? {
+                Some(protocol_data) => protocol_data,
+                None => return Err(BalanceError::RootNotFound(state_hash)),
+            };
+        let reader = match self
+            .state
+            .checkout(state_hash)
+            .map_err(Into::<execution::Error>::into)?
+        {
+            Some(reader) => reader,
+            None => return Err(BalanceError::RootNotFound(state_hash)),
+        };
+        balance::read_main_purse_balance(correlation_id, &reader, protocol_data.mint(), public_key)
+    }
+
+    /// Returns one page of the named keys of the account or contract found by `list_keys_request`.
+    ///
+    /// See [`ListKeysRequest`] for the ordering guarantees across pages.
+    pub fn run_list_keys(
+        &self,
+        correlation_id: CorrelationId,
+        list_keys_request: ListKeysRequest,
+    ) -> Result<ListKeysResult, Error> {
+        let tracking_copy = match self.tracking_copy(list_keys_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(ListKeysResult::RootNotFound),
+        };
+
+        let query_result = tracking_copy
+            .query(
+                correlation_id,
+                list_keys_request.key(),
+                list_keys_request.path(),
+            )
+            .map_err(|err| Error::Exec(err.into()))?;
+
+        Ok(ListKeysResult::from_query_result(
+            query_result,
+            list_keys_request.page_size(),
+            list_keys_request.page_token(),
+        ))
+    }
+
+    pub fn run_execute(
+        &self,
+        correlation_id: CorrelationId,
+        exec_request: ExecuteRequest,
+    ) -> Result<Vec<ExecutionResult>, RootNotFound> {
+        let mut results = Vec::new();
+        self.run_execute_with(correlation_id, exec_request, |result, _| {
+            results.push(result)
+        })?;
+        Ok(results)
+    }
+
+    /// Executes the deploys of `exec_request` in request order, passing each result to
+    /// `on_result` as soon as its execution completes.
+    ///
+    /// The host function calls and global state reads made by each deploy are passed along with
+    /// its result if the request [collects stats](ExecuteRequest::collect_stats).  The calls made
+    /// by the whole block are logged as metrics either way, and its reads if stats are collected.
+    /// Reads are only counted when stats are collected, so that they cost nothing otherwise.
+    ///
+    /// If the request has a block gas limit and the gas used so far plus a deploy's
+    /// [declared gas limit](DeployItem::declared_gas_limit) exceeds it, that deploy and all
+    /// subsequent ones are skipped.  Skipped deploys have a zero-cost
+    /// [`Error::BlockGasLimitExhausted`] result and no effects.
+    ///
+    /// If the block time of the request [isn't valid](EngineState::check_block_time), every deploy
+    /// fails its precondition.
+    ///
+    /// Returns [`RootNotFound`] if the parent state hash isn't found, including for an empty block
+    /// with no deploys to execute.
+    ///
+    /// If the request collects stats, returns a summary of the whole request, whose
+    /// [protobuf conversion time](RequestStats::protobuf_conversion_time) is left to the caller.
+    /// Its total time includes the time spent in `on_result`.
+    pub fn run_execute_with<F>(
+        &self,
+        correlation_id: CorrelationId,
+        mut exec_request: ExecuteRequest,
+        mut on_result: F,
+    ) -> Result<Option<RequestStats>, RootNotFound>
+    where
+        F: FnMut(ExecutionResult, Option<ExecutionStats>),
+    {
+        let start = Instant::now();
+        let executor = Executor::new(self.config);
+        let mut block_host_call_stats = HostCallStats::new();
+        let mut block_read_stats = ReadStats::new();
+        let counting_engine_state = if exec_request.collect_stats {
+            let counting_state = CountingStateProvider::new(&self.state);
+            let read_stats = counting_state.stats();
+            Some((self.with_state(counting_state), read_stats))
+        } else {
+            None
+        };
+
+        let mut request_stats = RequestStats::default();
+        let mut block_gas_used = Gas::default();
+        let mut block_gas_exhausted = false;
+
+        let deploys = exec_request.take_deploys();
+        // deploys check the parent as they execute, so an empty block has to check it up front
+        if deploys.is_empty() {
+            if let Ok(None) = self.state.checkout(exec_request.parent_state_hash) {
+                return Err(RootNotFound::new(exec_request.parent_state_hash));
+            }
+        }
+
+        for deploy_item in deploys {
+            if let (Some(block_gas_limit), Ok(deploy_item)) =
+                (exec_request.block_gas_limit, deploy_item.as_ref())
+            {
+                block_gas_exhausted = block_gas_exhausted
+                    || block_gas_used
+                        .checked_add(deploy_item.declared_gas_limit())
+                        .map_or(true, |gas| gas > block_gas_limit);
+            }
+            let deploy_start = Instant::now();
+            let deploy_hash = deploy_item
+                .as_ref()
+                .ok()
+                .map(|deploy_item| deploy_item.deploy_hash);
+            let result = match deploy_item {
+                _ if block_gas_exhausted => Ok(ExecutionResult::precondition_failure(
+                    Error::BlockGasLimitExhausted,
+                )),
+                // the costs are resolved per deploy so that each one that can't be executed
+                // fails its precondition with its own error
+                Ok(deploy_item) => match self.wasm_costs(
+                    correlation_id,
+                    exec_request.parent_state_hash,
+                    exec_request.protocol_version,
+                ) {
+                    Ok(Some(wasm_costs)) => match self.check_block_time(
+                        correlation_id,
+                        exec_request.parent_state_hash,
+                        exec_request.block_time,
+                    ) {
+                        Ok(()) => match &counting_engine_state {
+                            Some((counting_engine_state, _)) => counting_engine_state.deploy(
+                                correlation_id,
+                                &executor,
+                                &Preprocessor::new(wasm_costs),
+                                exec_request.protocol_version,
+                                exec_request.parent_state_hash,
+                                exec_request.block_time,
+                                deploy_item,
+                            ),
+                            None => self.deploy(
+                                correlation_id,
+                                &executor,
+                                &Preprocessor::new(wasm_costs),
+                                exec_request.protocol_version,
+                                exec_request.parent_state_hash,
+                                exec_request.block_time,
+                                deploy_item,
+                            ),
+                        },
+                        Err(error) => Ok(ExecutionResult::precondition_failure(error)),
+                    },
+                    Ok(None) => Err(RootNotFound::new(exec_request.parent_state_hash)),
+                    Err(error) => Ok(ExecutionResult::precondition_failure(error)),
+                },
+                Err(exec_result) => Ok(exec_result), /* this will get pushed into the results vec
+                                                      * below */
             };
             match result {
-                Ok(result) => results.push(result),
+                Ok(result) => {
+                    block_gas_used = block_gas_used + result.cost();
+                    let phase_times = executor.take_phase_times(deploy_start.elapsed());
+                    request_stats.record_deploy(deploy_hash, &phase_times, result.cost());
+                    let host_call_stats = executor.take_host_call_stats();
+                    block_host_call_stats.merge(&host_call_stats);
+                    let execution_stats =
+                        counting_engine_state
+                            .as_ref()
+                            .map(|(_, read_stats)| ExecutionStats {
+                                host_call_stats,
+                                read_stats: read_stats.replace(ReadStats::new()),
+                            });
+                    if let Some(execution_stats) = &execution_stats {
+                        block_read_stats.merge(&execution_stats.read_stats);
+                    }
+                    on_result(result, execution_stats);
+                }
                 Err(error) => {
                     return Err(error);
                 }
             };
         }
 
-        Ok(results)
+        block_host_call_stats.log_metrics(correlation_id);
+        if counting_engine_state.is_none() {
+            return Ok(None);
+        }
+        block_read_stats.log_metrics(correlation_id);
+
+        request_stats.total_time = start.elapsed();
+        Ok(Some(request_stats))
+    }
+
+    /// Checks `block_time` isn't before the genesis timestamp recorded at `state_hash`, nor further
+    /// ahead of the engine's clock than the configured
+    /// [maximum skew](EngineConfig::max_block_time_skew).
+    ///
+    /// Roots whose genesis didn't record a timestamp accept any time up to the skew.
+    fn check_block_time(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        block_time: BlockTime,
+    ) -> Result<(), Error> {
+        if let Some(reader) = self.state.checkout(state_hash).map_err(Into::into)? {
+            if let Some(genesis_timestamp) = genesis_timestamp::read(correlation_id, &reader)? {
+                if block_time < genesis_timestamp {
+                    return Err(Error::BlockTimeBeforeGenesis {
+                        block_time,
+                        genesis_timestamp,
+                    });
+                }
+            }
+        }
+
+        if let Some(max_block_time_skew) = self.config.max_block_time_skew() {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let latest = BlockTime::default()
+                .saturating_add(now)
+                .saturating_add(max_block_time_skew);
+            if block_time > latest {
+                return Err(Error::BlockTimeTooFarInFuture { block_time, latest });
+            }
+        }
+
+        Ok(())
     }
 
     pub fn get_module(
@@ -709,12 +1627,38 @@ where
         preprocessor: &Preprocessor,
         protocol_version: &ProtocolVersion,
     ) -> Result<Module, error::Error> {
+        if let ExecutableDeployItem::ModuleBytes { module_bytes, .. } = deploy_item {
+            let module = self
+                .module_cache
+                .get_or_preprocess(preprocessor, &module_bytes)?;
+            return Ok(module);
+        }
+        let stored_contract_key = self.get_stored_contract_key(deploy_item, account)?;
+        self.get_module_from_key(
+            tracking_copy,
+            stored_contract_key,
+            correlation_id,
+            protocol_version,
+        )
+    }
+
+    /// Returns the key of the stored contract referenced by `deploy_item`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `deploy_item` is [`ExecutableDeployItem::ModuleBytes`], which doesn't reference a
+    /// stored contract.
+    fn get_stored_contract_key(
+        &self,
+        deploy_item: &ExecutableDeployItem,
+        account: &Account,
+    ) -> Result<Key, error::Error> {
         let stored_contract_key = match deploy_item {
-            ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
-                let module = preprocessor.preprocess(&module_bytes)?;
-                return Ok(module);
+            ExecutableDeployItem::ModuleBytes { .. } => {
+                panic!("module bytes don't reference a stored contract")
             }
-            ExecutableDeployItem::StoredContractByHash { hash, .. } => {
+            ExecutableDeployItem::StoredContractByHash { hash, .. }
+            | ExecutableDeployItem::SponsorContractByHash { hash, .. } => {
                 let hash_len = hash.len();
                 if hash_len != KEY_HASH_LENGTH {
                     return Err(error::Error::InvalidHashLength {
@@ -726,7 +1670,8 @@ where
                 arr.copy_from_slice(&hash);
                 Key::Hash(arr)
             }
-            ExecutableDeployItem::StoredContractByName { name, .. } => {
+            ExecutableDeployItem::StoredContractByName { name, .. }
+            | ExecutableDeployItem::SponsorContractByName { name, .. } => {
                 let stored_contract_key = account.named_keys().get(name).ok_or_else(|| {
                     error::Error::Exec(execution::Error::URefNotFound(name.to_string()))
                 })?;
@@ -778,12 +1723,7 @@ where
                 }
             }
         };
-        self.get_module_from_key(
-            tracking_copy,
-            stored_contract_key,
-            correlation_id,
-            protocol_version,
-        )
+        Ok(stored_contract_key)
     }
 
     fn get_module_from_key(
@@ -813,6 +1753,175 @@ where
         Ok(module)
     }
 
+    /// Executes the deploy of `replay_request` against its parent state without committing the
+    /// resulting effects.
+    pub fn replay_deploy(
+        &self,
+        correlation_id: CorrelationId,
+        replay_request: ReplayRequest,
+    ) -> Result<ReplayResult, Error> {
+        let ReplayRequest {
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+            overrides,
+            recorded_effects_hash,
+        } = replay_request;
+
+        let wasm_costs = match overrides.wasm_costs {
+            Some(wasm_costs) => wasm_costs,
+            None => match self.wasm_costs(correlation_id, parent_state_hash, protocol_version)? {
+                Some(wasm_costs) => wasm_costs,
+                None => return Ok(ReplayResult::RootNotFound),
+            },
+        };
+        let executor = Executor::new(self.config);
+        let preprocessor = Preprocessor::new(wasm_costs);
+        let block_time = overrides.block_time.unwrap_or(block_time);
+        let mut trace = if overrides.collect_trace {
+            Some(ExecutionTrace::new())
+        } else {
+            None
+        };
+
+        let execution_result = match self.execute_deploy(
+            correlation_id,
+            &executor,
+            &preprocessor,
+            protocol_version,
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            trace.as_mut(),
+        ) {
+            Ok(execution_result) => execution_result,
+            Err(_) => return Ok(ReplayResult::RootNotFound),
+        };
+
+        let effects_hash = replay::effects_hash(&execution_result.effect().transforms)?;
+        let matches_recorded_effects =
+            recorded_effects_hash.map(|recorded_hash| recorded_hash == effects_hash);
+
+        Ok(ReplayResult::Replayed(ReplayedDeploy {
+            execution_result,
+            effects_hash,
+            trace,
+            matches_recorded_effects,
+        }))
+    }
+
+    /// Executes the deploy of `export_request` against its parent state like
+    /// [`replay_deploy`](EngineState::replay_deploy), and bundles it with every value it read and
+    /// the proofs of those values, so that it can be replayed without access to this node's global
+    /// state.  Returns `None` if the parent state is not found.
+    pub fn export_repro_bundle(
+        &self,
+        correlation_id: CorrelationId,
+        export_request: ExportReproBundleRequest,
+    ) -> Result<Option<ReproBundle>, Error> {
+        let ExportReproBundleRequest {
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        } = export_request;
+
+        let recording_state = RecordingStateProvider::new(&self.state);
+        let reads = recording_state.reads();
+        let recording_engine_state = EngineState::new(recording_state, self.config);
+        let replay_request = ReplayRequest::new(
+            parent_state_hash,
+            block_time,
+            deploy_item.clone(),
+            protocol_version,
+        );
+        let effects_hash =
+            match recording_engine_state.replay_deploy(correlation_id, replay_request)? {
+                ReplayResult::RootNotFound => return Ok(None),
+                ReplayResult::Replayed(replayed_deploy) => replayed_deploy.effects_hash,
+            };
+
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => {
+                return Err(Error::ConfigNotFound {
+                    protocol_version,
+                    state_hash: parent_state_hash,
+                })
+            }
+        };
+        let reader = match self.state.checkout(parent_state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let mut proofs = Vec::new();
+        let mut absent_keys = Vec::new();
+        for key in reads.borrow().keys() {
+            match reader
+                .read_with_proof(correlation_id, key)
+                .map_err(Into::into)?
+            {
+                Some(proof) => proofs.push(proof),
+                None => absent_keys.push(*key),
+            }
+        }
+
+        Ok(Some(ReproBundle {
+            parent_state_hash,
+            block_time,
+            protocol_version,
+            protocol_data,
+            deploy_item,
+            reads: proofs,
+            absent_keys,
+            effects_hash,
+        }))
+    }
+
+    /// Replays the deploy of `bundle` against a state built only from the values it holds, after
+    /// checking their proofs against the bundle's parent state hash.
+    pub fn replay_repro_bundle(
+        &self,
+        correlation_id: CorrelationId,
+        bundle: &ReproBundle,
+    ) -> Result<BundleReplayResult, Error> {
+        for proof in &bundle.reads {
+            if !proof.verify(&bundle.parent_state_hash)? {
+                return Ok(BundleReplayResult::InvalidProof(*proof.key()));
+            }
+        }
+
+        let pairs: Vec<(Key, StoredValue)> = bundle
+            .reads
+            .iter()
+            .map(|proof| (*proof.key(), proof.value().clone()))
+            .collect();
+        let (state, state_hash) = InMemoryGlobalState::from_pairs(correlation_id, &pairs)?;
+        state.put_protocol_data(bundle.protocol_version, &bundle.protocol_data)?;
+        let bundle_engine_state = EngineState::new(state, self.config);
+        let replay_request = ReplayRequest::new(
+            state_hash,
+            bundle.block_time,
+            bundle.deploy_item.clone(),
+            bundle.protocol_version,
+        )
+        .with_recorded_effects_hash(bundle.effects_hash);
+        let replayed_deploy =
+            match bundle_engine_state.replay_deploy(correlation_id, replay_request)? {
+                ReplayResult::Replayed(replayed_deploy) => replayed_deploy,
+                ReplayResult::RootNotFound => panic!("bundle state should hold its own root"),
+            };
+
+        if replayed_deploy.matches_recorded_effects == Some(false) {
+            return Ok(BundleReplayResult::EffectsMismatch {
+                recorded: bundle.effects_hash,
+                replayed: replayed_deploy.effects_hash,
+            });
+        }
+        Ok(BundleReplayResult::Replayed(replayed_deploy))
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn deploy(
         &self,
@@ -824,37 +1933,187 @@ where
         blocktime: BlockTime,
         deploy_item: DeployItem,
     ) -> Result<ExecutionResult, RootNotFound> {
-        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
-
-        let session = deploy_item.session;
-        let payment = deploy_item.payment;
-        let address = Key::Account(deploy_item.address);
-        let authorization_keys = deploy_item.authorization_keys;
-        let deploy_hash = deploy_item.deploy_hash;
+        self.execute_deploy(
+            correlation_id,
+            executor,
+            preprocessor,
+            protocol_version,
+            prestate_hash,
+            blocktime,
+            deploy_item,
+            None,
+        )
+    }
 
-        // Create tracking copy (which functions as a deploy context)
-        // validation_spec_2: prestate_hash check
-        let tracking_copy = match self.tracking_copy(prestate_hash) {
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
-            Ok(None) => return Err(RootNotFound::new(prestate_hash)),
-            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
-        };
+    /// Checks `deploy_item` of `validate_request` without reading any global state, and
+    /// preprocesses its Wasm into the module cache so that executing it doesn't repeat the work.
+    ///
+    /// The checks are the ones of [`execute_deploy`](EngineState::execute_deploy) which don't
+    /// depend on the deploy's account, and a failing check gives the error executing the deploy
+    /// would fail with.
+    pub fn validate_deploy(
+        &self,
+        validate_request: ValidateDeployRequest,
+    ) -> Result<ValidateDeployResult, Error> {
+        let ValidateDeployRequest {
+            deploy_item,
+            protocol_version,
+        } = validate_request;
 
-        // Get addr bytes from `address` (which is actually a Key)
-        // validation_spec_3: account validity
-        let account_addr = match address.into_account() {
-            Some(account_addr) => account_addr,
+        let wasm_costs = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => *protocol_data.wasm_costs(),
             None => {
-                return Ok(ExecutionResult::precondition_failure(
-                    error::Error::Authorization,
+                return Ok(ValidateDeployResult::Invalid(
+                    Error::InvalidProtocolVersion(protocol_version),
                 ))
             }
         };
 
-        // Get account from tracking copy
-        // validation_spec_3: account validity
-        let account: Account = match tracking_copy
-            .borrow_mut()
+        if deploy_item.system_deploy != (deploy_item.address == SYSTEM_ACCOUNT_ADDR) {
+            return Ok(ValidateDeployResult::Invalid(Error::InvalidSystemDeploy));
+        }
+
+        if deploy_item.authorization_keys.is_empty() && !deploy_item.system_deploy {
+            return Ok(ValidateDeployResult::Invalid(Error::Authorization));
+        }
+
+        if deploy_item.session.is_sponsor_contract() {
+            return Ok(ValidateDeployResult::Invalid(
+                Error::SponsorContractAsSession,
+            ));
+        }
+
+        let preprocessor = Preprocessor::new(wasm_costs);
+        for (executable_deploy_item, is_session) in
+            &[(deploy_item.session, true), (deploy_item.payment, false)]
+        {
+            if let Err(error) = self.validate_executable_deploy_item(
+                executable_deploy_item,
+                *is_session,
+                &preprocessor,
+            ) {
+                return Ok(ValidateDeployResult::Invalid(error));
+            }
+        }
+
+        Ok(ValidateDeployResult::Valid)
+    }
+
+    fn validate_executable_deploy_item(
+        &self,
+        executable_deploy_item: &ExecutableDeployItem,
+        is_session: bool,
+        preprocessor: &Preprocessor,
+    ) -> Result<(), Error> {
+        let args = executable_deploy_item.args();
+        if !args.is_empty() {
+            bytesrepr::deserialize::<Vec<CLValue>>(args.to_vec())?;
+        }
+
+        match executable_deploy_item {
+            // empty payment module bytes select the standard payment contract
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } if module_bytes.is_empty() => {}
+            ExecutableDeployItem::ModuleBytes { module_bytes, .. } => {
+                let module = self
+                    .module_cache
+                    .get_or_preprocess(preprocessor, module_bytes)?;
+                if is_session {
+                    preprocessor.validate_session_exports(&module)?;
+                }
+            }
+            ExecutableDeployItem::StoredContractByHash { hash, .. }
+            | ExecutableDeployItem::SponsorContractByHash { hash, .. } => {
+                if hash.len() != KEY_HASH_LENGTH {
+                    return Err(Error::InvalidHashLength {
+                        expected: KEY_HASH_LENGTH,
+                        actual: hash.len(),
+                    });
+                }
+            }
+            ExecutableDeployItem::StoredContractByURef { uref, .. } => {
+                if uref.len() != UREF_ADDR_LENGTH {
+                    return Err(Error::InvalidHashLength {
+                        expected: UREF_ADDR_LENGTH,
+                        actual: uref.len(),
+                    });
+                }
+            }
+            ExecutableDeployItem::StoredContractByName { .. }
+            | ExecutableDeployItem::SponsorContractByName { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// Executes `deploy_item`, recording the outcome of each execution phase into `trace` if
+    /// given.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_deploy(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        prestate_hash: Blake2bHash,
+        blocktime: BlockTime,
+        deploy_item: DeployItem,
+        mut trace: Option<&mut ExecutionTrace>,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        // spec: https://casperlabs.atlassian.net/wiki/spaces/EN/pages/123404576/Payment+code+execution+specification
+
+        let session = deploy_item.session;
+        let payment = deploy_item.payment;
+        let address = Key::Account(deploy_item.address);
+        let authorization_keys = deploy_item.authorization_keys;
+        let deploy_hash = deploy_item.deploy_hash.value();
+        let system_deploy = deploy_item.system_deploy;
+        let session_arg_names = deploy_item.session_arg_names;
+        let payment_arg_names = deploy_item.payment_arg_names;
+
+        // Create tracking copy (which functions as a deploy context)
+        // validation_spec_2: prestate_hash check
+        let tracking_copy = match self.tracking_copy(prestate_hash) {
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+            Ok(Some(tracking_copy)) => Rc::new(RefCell::new(tracking_copy)),
+        };
+
+        // Get addr bytes from `address` (which is actually a Key)
+        // validation_spec_3: account validity
+        let account_addr = match address.into_account() {
+            Some(account_addr) => account_addr,
+            None => {
+                return Ok(ExecutionResult::precondition_failure(
+                    error::Error::Authorization,
+                ))
+            }
+        };
+
+        // Only system deploys run as the system account, and they run as no other account
+        // validation_spec_3: account validity
+        if system_deploy != (account_addr == SYSTEM_ACCOUNT_ADDR) {
+            return Ok(ExecutionResult::precondition_failure(
+                Error::InvalidSystemDeploy,
+            ));
+        }
+        if system_deploy {
+            return self.execute_system_deploy(
+                correlation_id,
+                executor,
+                preprocessor,
+                protocol_version,
+                prestate_hash,
+                blocktime,
+                session,
+                deploy_hash,
+                tracking_copy,
+                trace,
+            );
+        }
+
+        // Get account from tracking copy
+        // validation_spec_3: account validity
+        let account: Account = match tracking_copy
+            .borrow_mut()
             .get_account(correlation_id, account_addr)
         {
             Ok(account) => account,
@@ -865,23 +2124,43 @@ where
             }
         };
 
+        // Obtain protocol data for given version as configured at the prestate
+        let protocol_data =
+            match self.get_protocol_data_at(correlation_id, prestate_hash, protocol_version) {
+                Ok(Some(protocol_data)) => protocol_data,
+                Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            };
+
+        // Loading the account has a cost, which is reported for a deploy failing its preconditions
+        // from here on but can't be collected, as no payment code has run
+        let rejected_deploy_cost =
+            Gas::new(U512::from(protocol_data.wasm_costs().rejected_deploy_cost));
+        let reject = |error: Error| {
+            ExecutionResult::precondition_failure(error).with_cost(rejected_deploy_cost)
+        };
+
         // Authorize using provided authorization keys
         // validation_spec_3: account validity
         if !account.can_authorize(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
-                crate::engine_state::error::Error::Authorization,
-            ));
+            return Ok(reject(crate::engine_state::error::Error::Authorization));
         }
 
         // Check total key weight against deploy threshold
         // validation_spec_4: deploy validity
         if !account.can_deploy_with(&authorization_keys) {
-            return Ok(ExecutionResult::precondition_failure(
+            return Ok(reject(
                 // TODO?:this doesn't happen in execution any longer, should error variant be moved
                 execution::Error::DeploymentAuthorizationFailure.into(),
             ));
         }
 
+        // Sponsor contracts pay for a deploy from their own purse, so they can't be its session
+        if session.is_sponsor_contract() {
+            return Ok(reject(Error::SponsorContractAsSession));
+        }
+        let is_sponsored = payment.is_sponsor_contract();
+
         // Create session code `A` from provided session bytes
         // validation_spec_1: valid wasm bytes
         let session_module = match self.get_module(
@@ -894,23 +2173,28 @@ where
         ) {
             Ok(module) => module,
             Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(error));
+                return Ok(reject(error));
             }
         };
 
-        // Obtain current protocol data for given version
-        let protocol_data = match self.state.get_protocol_data(protocol_version) {
-            Ok(Some(protocol_data)) => protocol_data,
-            Ok(None) => {
-                let error = Error::InvalidProtocolVersion(protocol_version);
-                return Ok(ExecutionResult::precondition_failure(error));
-            }
-            Err(error) => {
-                return Ok(ExecutionResult::precondition_failure(Error::Exec(
-                    error.into(),
-                )));
+        // Session code sent as module bytes may be restricted to exporting `call` only
+        // validation_spec_1: valid wasm bytes
+        if let ExecutableDeployItem::ModuleBytes { .. } = session {
+            if let Err(error) = preprocessor.validate_session_exports(&session_module) {
+                return Ok(reject(error.into()));
             }
+        }
+
+        // The effects of payment and session code are limited separately, as session code is
+        // executed against a fork of the tracking copy
+        let effect_limits = {
+            let wasm_costs = protocol_data.wasm_costs();
+            EffectLimits::new(
+                wasm_costs.max_transforms_per_deploy,
+                wasm_costs.max_effect_bytes_per_deploy,
+            )
         };
+        tracking_copy.borrow_mut().set_effect_limits(effect_limits);
 
         let max_payment_cost: Motes = Motes::new(U512::from(MAX_PAYMENT));
 
@@ -922,30 +2206,23 @@ where
             // payment_code_spec_6: system contract validity
             let mint_reference = protocol_data.mint();
 
-            if !self.system_contract_cache.has(&mint_reference) {
-                let mint_module = match {
-                    if self.config.use_system_contracts() {
-                        let mint_contract = match tracking_copy
-                            .borrow_mut()
-                            .get_contract(correlation_id, Key::URef(mint_reference))
-                        {
-                            Ok(contract) => contract,
-                            Err(error) => {
-                                return Ok(ExecutionResult::precondition_failure(error.into()))
-                            }
-                        };
-                        engine_wasm_prep::deserialize(mint_contract.bytes())
-                    } else {
-                        wasm::do_nothing_module(preprocessor)
-                    }
-                } {
+            if !self
+                .system_contract_cache
+                .has(&mint_reference, protocol_version)
+            {
+                let mint_module = match self.load_system_contract_module(
+                    &mut tracking_copy.borrow_mut(),
+                    correlation_id,
+                    mint_reference,
+                    preprocessor,
+                ) {
                     Ok(module) => module,
                     Err(error) => {
-                        return Ok(ExecutionResult::precondition_failure(error.into()));
+                        return Ok(reject(error));
                     }
                 };
                 self.system_contract_cache
-                    .insert(mint_reference, mint_module);
+                    .insert(mint_reference, protocol_version, mint_module);
             }
             mint_reference
         };
@@ -969,32 +2246,37 @@ where
             {
                 Ok(contract) => contract,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return Ok(reject(error.into()));
                 }
             };
 
-            let proof_of_stake_module =
-                match self.system_contract_cache.get(&proof_of_stake_reference) {
-                    Some(module) => module,
-                    None => {
-                        match {
-                            if self.config.use_system_contracts() {
-                                engine_wasm_prep::deserialize(proof_of_stake_contract.bytes())
-                            } else {
-                                wasm::do_nothing_module(preprocessor)
-                            }
-                        } {
-                            Ok(module) => {
-                                self.system_contract_cache
-                                    .insert(proof_of_stake_reference, module.clone());
-                                module
-                            }
-                            Err(error) => {
-                                return Ok(ExecutionResult::precondition_failure(error.into()));
-                            }
+            let proof_of_stake_module = match self
+                .system_contract_cache
+                .get(&proof_of_stake_reference, protocol_version)
+            {
+                Some(module) => module,
+                None => {
+                    match {
+                        if self.config.use_system_contracts() {
+                            engine_wasm_prep::deserialize(proof_of_stake_contract.bytes())
+                        } else {
+                            wasm::do_nothing_module(preprocessor)
+                        }
+                    } {
+                        Ok(module) => {
+                            self.system_contract_cache.insert(
+                                proof_of_stake_reference,
+                                protocol_version,
+                                module.clone(),
+                            );
+                            module
+                        }
+                        Err(error) => {
+                            return Ok(reject(error.into()));
                         }
                     }
-                };
+                }
+            };
 
             // Get rewards purse balance key
             // payment_code_spec_6: system contract validity
@@ -1005,7 +2287,7 @@ where
                     match proof_of_stake_contract.named_keys().get(POS_REWARDS_PURSE) {
                         Some(key) => *key,
                         None => {
-                            return Ok(ExecutionResult::precondition_failure(Error::Deploy));
+                            return Ok(reject(Error::Deploy));
                         }
                     };
 
@@ -1016,7 +2298,7 @@ where
                 ) {
                     Ok(key) => key,
                     Err(error) => {
-                        return Ok(ExecutionResult::precondition_failure(error.into()));
+                        return Ok(reject(error.into()));
                     }
                 }
             };
@@ -1026,7 +2308,7 @@ where
             let payment_purse_key: Key =
                 match proof_of_stake_contract.named_keys().get(POS_PAYMENT_PURSE) {
                     Some(key) => *key,
-                    None => return Ok(ExecutionResult::precondition_failure(Error::Deploy)),
+                    None => return Ok(reject(Error::Deploy)),
                 };
 
             (
@@ -1048,7 +2330,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return Ok(reject(error.into()));
                 }
             }
         };
@@ -1060,17 +2342,85 @@ where
             .get_purse_balance(correlation_id, account_main_purse_balance_key)
         {
             Ok(balance) => balance,
-            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+            Err(error) => return Ok(reject(error.into())),
         };
 
-        // Enforce minimum main purse balance validation
+        // Enforce minimum main purse balance validation, unless a sponsor contract pays for the
+        // deploy instead of the account
         // validation_spec_5: account main purse minimum balance
-        if account_main_purse_balance < max_payment_cost {
-            return Ok(ExecutionResult::precondition_failure(
-                Error::InsufficientPayment,
-            ));
+        if !is_sponsored && account_main_purse_balance < max_payment_cost {
+            return Ok(reject(Error::InsufficientPayment));
         }
 
+        // The account's authorization contract must approve the deploy, unless its authorization
+        // keys meet the key management threshold, so that a contract rejecting every deploy can
+        // still be cleared
+        let authorization_cost = match account.authorization_contract() {
+            Some(contract_key) if !account.can_manage_keys_with(&authorization_keys) => {
+                let contract_key = contract_key.normalize();
+                let named_keys = match tracking_copy
+                    .borrow_mut()
+                    .get_contract(correlation_id, contract_key)
+                {
+                    Ok(contract) => contract.take_named_keys(),
+                    Err(error) => return Ok(reject(error.into())),
+                };
+                let module = match self.get_module_from_key(
+                    Rc::clone(&tracking_copy),
+                    contract_key,
+                    correlation_id,
+                    &protocol_version,
+                ) {
+                    Ok(module) => module,
+                    Err(error) => return Ok(reject(error)),
+                };
+                let args = {
+                    let authorization_keys: Vec<PublicKey> =
+                        authorization_keys.iter().cloned().collect();
+                    ArgsParser::parse((deploy_hash, account_addr, authorization_keys))
+                        .expect("args should convert to `Vec<CLValue>`")
+                        .into_bytes()
+                        .expect("args should serialize")
+                };
+
+                // executed against a fork, although the context is read-only anyway
+                let authorization_result = {
+                    let authorization_tc = tracking_copy.borrow();
+                    executor.exec_authorization_contract(
+                        module,
+                        args,
+                        named_keys,
+                        contract_key,
+                        &account,
+                        authorization_keys.clone(),
+                        blocktime,
+                        deploy_hash,
+                        Gas::new(U512::from(AUTHORIZATION_CONTRACT_GAS_LIMIT)),
+                        protocol_version,
+                        correlation_id,
+                        Rc::new(RefCell::new(authorization_tc.fork())),
+                        protocol_data,
+                        SystemContractCache::clone(&self.system_contract_cache),
+                    )
+                };
+
+                if let Some(trace) = trace.as_mut() {
+                    trace.record(Phase::Authorization, &authorization_result);
+                }
+
+                let authorization_cost = authorization_result.cost();
+                if let Some(error) = authorization_result.take_error() {
+                    let error = match error {
+                        Error::Exec(exec_error) => Error::AuthorizationContractRejected(exec_error),
+                        error => error,
+                    };
+                    return Ok(reject(error));
+                }
+                authorization_cost
+            }
+            _ => Gas::default(),
+        };
+
         // Finalization is executed by system account (currently genesis account)
         // payment_code_spec_5: system executes finalization
         let system_account = Account::new(
@@ -1100,17 +2450,7 @@ where
             // Create payment code module from bytes
             // validation_spec_1: valid wasm bytes
             let maybe_payment_module = if module_bytes_is_empty {
-                let standard_payment = match self.state.get_protocol_data(protocol_version) {
-                    Ok(Some(protocol_data)) => {
-                        Key::URef(protocol_data.standard_payment()).normalize()
-                    }
-                    Ok(None) => {
-                        return Ok(ExecutionResult::precondition_failure(
-                            Error::InvalidProtocolVersion(protocol_version),
-                        ))
-                    }
-                    Err(_) => return Ok(ExecutionResult::precondition_failure(Error::Deploy)),
-                };
+                let standard_payment = Key::URef(protocol_data.standard_payment()).normalize();
                 // If not in "use-system-contracts" mode, the returned module is the "do_nothing"
                 // Wasm.
                 self.get_module_from_key(
@@ -1133,14 +2473,32 @@ where
             let payment_module = match maybe_payment_module {
                 Ok(module) => module,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error));
+                    return Ok(reject(error));
+                }
+            };
+
+            // A sponsor contract is called in its own context, so that it can access its purse
+            let sponsor_contract = if is_sponsored {
+                let sponsor_contract_key = match self.get_stored_contract_key(&payment, &account) {
+                    Ok(key) => key,
+                    Err(error) => return Ok(reject(error)),
+                };
+                match tracking_copy
+                    .borrow_mut()
+                    .get_contract(correlation_id, sponsor_contract_key)
+                {
+                    Ok(contract) => Some((sponsor_contract_key, contract.take_named_keys())),
+                    Err(error) => return Ok(reject(error.into())),
                 }
+            } else {
+                None
             };
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
             // payment_code_spec_2: execute payment code
             let phase = Phase::Payment;
-            if !self.config.use_system_contracts() && module_bytes_is_empty {
+            let payment_start = Instant::now();
+            let payment_result = if !self.config.use_system_contracts() && module_bytes_is_empty {
                 let mut named_keys = account.named_keys().clone();
                 let address_generator = AddressGenerator::new(&deploy_hash, phase);
 
@@ -1163,9 +2521,7 @@ where
                     system_contract_cache,
                 ) {
                     Ok((_instance, runtime)) => runtime,
-                    Err(error) => {
-                        return Ok(ExecutionResult::precondition_failure(Error::Exec(error)))
-                    }
+                    Err(error) => return Ok(reject(Error::Exec(error))),
                 };
 
                 let effects_snapshot = tracking_copy.borrow().effect();
@@ -1173,6 +2529,7 @@ where
                     Ok(()) => ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        return_value: None,
                     },
                     Err(error) => ExecutionResult::Failure {
                         error: error.into(),
@@ -1180,10 +2537,30 @@ where
                         cost: runtime.context().gas_counter(),
                     },
                 }
+            } else if let Some((sponsor_contract_key, sponsor_named_keys)) = sponsor_contract {
+                executor.exec_contract(
+                    payment_module,
+                    payment.take_args(),
+                    payment_arg_names,
+                    sponsor_named_keys,
+                    sponsor_contract_key.normalize(),
+                    &account,
+                    authorization_keys.clone(),
+                    blocktime,
+                    deploy_hash,
+                    pay_gas_limit,
+                    protocol_version,
+                    correlation_id,
+                    Rc::clone(&tracking_copy),
+                    phase,
+                    protocol_data,
+                    system_contract_cache,
+                )
             } else {
                 executor.exec(
                     payment_module,
                     payment.take_args(),
+                    payment_arg_names,
                     address,
                     &account,
                     authorization_keys.clone(),
@@ -1197,7 +2574,25 @@ where
                     protocol_data,
                     system_contract_cache,
                 )
-            }
+            };
+            executor.record_phase_time(phase, payment_start.elapsed());
+            payment_result
+        };
+
+        if let Some(trace) = trace.as_mut() {
+            trace.record(Phase::Payment, &payment_result);
+        }
+
+        // the deploy's base cost and the authorization contract's gas are charged as part of the
+        // payment code's, so that they are collected from the payment purse before session code
+        // is executed
+        let base_deploy_cost = Gas::new(U512::from(protocol_data.wasm_costs().base_deploy_cost));
+        if let Some(trace) = trace.as_mut() {
+            trace.record_base_deploy_cost(base_deploy_cost);
+        }
+        let payment_result = {
+            let cost = payment_result.cost() + authorization_cost + base_deploy_cost;
+            payment_result.with_cost(cost)
         };
 
         let payment_result_cost = payment_result.cost();
@@ -1212,7 +2607,7 @@ where
             ) {
                 Ok(key) => key,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return Ok(reject(error.into()));
                 }
             };
 
@@ -1222,7 +2617,7 @@ where
             {
                 Ok(balance) => balance,
                 Err(error) => {
-                    return Ok(ExecutionResult::precondition_failure(error.into()));
+                    return Ok(reject(error.into()));
                 }
             }
         };
@@ -1241,10 +2636,38 @@ where
             ));
         }
 
+        // A sponsor contract has to claim the refund of its payment via the PoS contract's
+        // "set_refund_purse", as the refund would otherwise go to the deploy's account
+        if is_sponsored {
+            let proof_of_stake_contract = match tracking_copy
+                .borrow_mut()
+                .get_contract(correlation_id, Key::URef(proof_of_stake_reference))
+            {
+                Ok(contract) => contract,
+                Err(error) => return Ok(reject(error.into())),
+            };
+            if !proof_of_stake_contract
+                .named_keys()
+                .contains_key(POS_REFUND_PURSE)
+            {
+                return Ok(ExecutionResult::new_payment_code_error(
+                    Error::SponsorRefundPurseNotSet,
+                    max_payment_cost,
+                    account_main_purse_balance,
+                    account_main_purse_balance_key,
+                    rewards_purse_balance_key,
+                ));
+            }
+        }
+
         execution_result_builder.set_payment_execution_result(payment_result);
 
         let post_payment_tc = tracking_copy.borrow();
-        let session_tc = Rc::new(RefCell::new(post_payment_tc.fork()));
+        let session_tc = {
+            let mut session_tc = post_payment_tc.fork();
+            session_tc.set_effect_limits(effect_limits);
+            Rc::new(RefCell::new(session_tc))
+        };
 
         // session_code_spec_2: execute session code
         let session_result = {
@@ -1257,9 +2680,11 @@ where
                 - payment_result_cost;
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
-            executor.exec(
+            let session_start = Instant::now();
+            let session_result = executor.exec(
                 session_module,
                 session.take_args(),
+                session_arg_names,
                 address,
                 &account,
                 authorization_keys.clone(),
@@ -1272,9 +2697,15 @@ where
                 Phase::Session,
                 protocol_data,
                 system_contract_cache,
-            )
+            );
+            executor.record_phase_time(Phase::Session, session_start.elapsed());
+            session_result
         };
 
+        if let Some(trace) = trace.as_mut() {
+            trace.record(Phase::Session, &session_result);
+        }
+
         let post_session_rc = if session_result.is_failure() {
             // If session code fails we do not include its effects,
             // so we start again from the post-payment state.
@@ -1294,8 +2725,15 @@ where
 
             let proof_of_stake_args = {
                 //((gas spent during payment code execution) + (gas spent during session code execution)) * conv_rate
-                let finalize_cost_motes: Motes = Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE).expect("motes overflow");
-                let args = ("finalize_payment", finalize_cost_motes.value(), account_addr);
+                // where the former includes the deploy's base cost
+                let finalize_cost_motes: Motes =
+                    Motes::from_gas(execution_result_builder.total_cost(), CONV_RATE)
+                        .expect("motes overflow");
+                let args = (
+                    METHOD_FINALIZE_PAYMENT,
+                    finalize_cost_motes.value(),
+                    account_addr,
+                );
                 ArgsParser::parse(args)
                     .expect("args should convert to `Vec<CLValue>`")
                     .into_bytes()
@@ -1309,34 +2747,42 @@ where
                 .get_contract(correlation_id, Key::URef(proof_of_stake_reference))
             {
                 Ok(info) => info,
-                Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+                Err(error) => return Ok(reject(error.into())),
             };
 
             let mut proof_of_stake_keys = proof_of_stake_contract.named_keys().to_owned();
 
             let base_key = Key::from(proof_of_stake_reference);
-            let gas_limit = Gas::new(U512::from(std::u64::MAX));
+            let system_context = SystemContext::new(
+                Phase::FinalizePayment,
+                blocktime,
+                deploy_hash,
+                authorization_keys,
+            );
             let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
 
-            executor.exec_finalize(
+            let finalization_start = Instant::now();
+            let finalize_result = executor.exec_finalize(
                 proof_of_stake_module,
                 proof_of_stake_args,
                 &mut proof_of_stake_keys,
                 base_key,
                 &system_account,
-                authorization_keys,
-                blocktime,
-                deploy_hash,
-                gas_limit,
+                &system_context,
                 protocol_version,
                 correlation_id,
                 finalization_tc,
-                Phase::FinalizePayment,
                 protocol_data,
                 system_contract_cache,
-            )
+            );
+            executor.record_phase_time(Phase::FinalizePayment, finalization_start.elapsed());
+            finalize_result
         };
 
+        if let Some(trace) = trace {
+            trace.record(Phase::FinalizePayment, &finalize_result);
+        }
+
         execution_result_builder.set_finalize_execution_result(finalize_result);
 
         // We panic here to indicate that the builder was not used properly.
@@ -1350,68 +2796,1069 @@ where
         Ok(ret)
     }
 
+    /// Commits `effects` on top of `pre_state_hash`.
+    ///
+    /// If `last_seen_sequence_number` is given, the commit is fenced as described in
+    /// [`StateProvider::commit_fenced`].
     pub fn apply_effect(
         &self,
         correlation_id: CorrelationId,
         protocol_version: ProtocolVersion,
         pre_state_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
+        last_seen_sequence_number: Option<u64>,
     ) -> Result<CommitResult, Error>
     where
         Error: From<S::Error>,
     {
-        match self.state.commit(correlation_id, pre_state_hash, effects)? {
-            CommitResult::Success { state_root, .. } => {
-                let bonded_validators =
-                    self.get_bonded_validators(correlation_id, protocol_version, state_root)?;
-                Ok(CommitResult::Success {
-                    state_root,
-                    bonded_validators,
-                })
-            }
+        // resolved before committing, so that nothing is committed on top of a root which
+        // doesn't know `protocol_version`
+        let protocol_data =
+            match self.get_protocol_data_at(correlation_id, pre_state_hash, protocol_version)? {
+                Some(protocol_data) => protocol_data,
+                None => return Ok(CommitResult::RootNotFound),
+            };
+        let notify_commit = self.commit_notifier.has_subscribers();
+        let changed_keys: Vec<Key> = if self.tracks_changed_keys(notify_commit) {
+            effects.keys().cloned().collect()
+        } else {
+            Vec::new()
+        };
+        let commit_result = match last_seen_sequence_number {
+            Some(last_seen_sequence_number) => self.state.commit_fenced(
+                correlation_id,
+                pre_state_hash,
+                effects,
+                last_seen_sequence_number,
+            )?,
+            None => self.state.commit(correlation_id, pre_state_hash, effects)?,
+        };
+        match commit_result {
+            CommitResult::Success {
+                state_root,
+                sequence_number,
+                ..
+            } => self.complete_commit(
+                correlation_id,
+                protocol_version,
+                protocol_data,
+                pre_state_hash,
+                changed_keys,
+                notify_commit,
+                state_root,
+                sequence_number,
+            ),
             commit_result => Ok(commit_result),
         }
     }
 
-    /// Calculates bonded validators at `root_hash` state.
+    /// Commits each `(pre_state_hash, effects)` entry of `entries` like [`apply_effect`] without a
+    /// fence, all in a single storage transaction as described in
+    /// [`StateProvider::batch_commit`].
     ///
-    /// Should only be called with a valid root hash after a successful call to
-    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
-    fn get_bonded_validators(
+    /// [`apply_effect`]: EngineState::apply_effect
+    pub fn apply_effect_batch(
         &self,
         correlation_id: CorrelationId,
         protocol_version: ProtocolVersion,
-        root_hash: Blake2bHash,
-    ) -> Result<HashMap<PublicKey, U512>, Error>
+        entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<BatchCommitResult, Error>
     where
         Error: From<S::Error>,
     {
-        let protocol_data = match self.state.get_protocol_data(protocol_version)? {
-            Some(protocol_data) => protocol_data,
-            None => return Err(Error::InvalidProtocolVersion(protocol_version)),
-        };
-
-        let proof_of_stake = {
-            let tmp = protocol_data.proof_of_stake();
-            Key::URef(tmp).normalize()
+        let first_pre_state_hash = match entries.first() {
+            Some((pre_state_hash, _)) => *pre_state_hash,
+            None => return Ok(BatchCommitResult::Success(Vec::new())),
         };
-
-        let reader = match self.state.checkout(root_hash)? {
-            Some(reader) => reader,
-            None => panic!("get_bonded_validators called with an invalid root hash"),
+        // each entry builds on the one before it, so the first pre-state decides for all of them
+        let protocol_data = match self.get_protocol_data_at(
+            correlation_id,
+            first_pre_state_hash,
+            protocol_version,
+        )? {
+            Some(protocol_data) => protocol_data,
+            None => {
+                return Ok(BatchCommitResult::Failure {
+                    index: 0,
+                    result: CommitResult::RootNotFound,
+                })
+            }
         };
-
-        let contract = match reader.read(correlation_id, &proof_of_stake)? {
-            Some(StoredValue::Contract(contract)) => contract,
-            _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
+        let notify_commit = self.commit_notifier.has_subscribers();
+        let changed_keys: Vec<Vec<Key>> = entries
+            .iter()
+            .map(|(_, effects)| {
+                if self.tracks_changed_keys(notify_commit) {
+                    effects.keys().cloned().collect()
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
+        let results = match self.state.batch_commit(correlation_id, entries)? {
+            BatchCommitResult::Success(results) => results,
+            batch_commit_result => return Ok(batch_commit_result),
         };
 
-        let bonded_validators = contract
-            .named_keys()
-            .keys()
-            .filter_map(|entry| utils::pos_validator_key_name_to_tuple(entry))
-            .collect::<HashMap<PublicKey, U512>>();
+        let mut pre_state_hash = first_pre_state_hash;
+        let mut completed_results = Vec::with_capacity(results.len());
+        for (commit_result, changed_keys) in results.into_iter().zip(changed_keys) {
+            let commit_result = match commit_result {
+                CommitResult::Success {
+                    state_root,
+                    sequence_number,
+                    ..
+                } => {
+                    let commit_result = self.complete_commit(
+                        correlation_id,
+                        protocol_version,
+                        protocol_data,
+                        pre_state_hash,
+                        changed_keys,
+                        notify_commit,
+                        state_root,
+                        sequence_number,
+                    )?;
+                    pre_state_hash = state_root;
+                    commit_result
+                }
+                commit_result => commit_result,
+            };
+            completed_results.push(commit_result);
+        }
+        Ok(BatchCommitResult::Success(completed_results))
+    }
+
+    /// Returns `true` if the keys changed by a commit are needed afterwards, for the mint invariant
+    /// checks or for a commit notification.
+    fn tracks_changed_keys(&self, notify_commit: bool) -> bool {
+        notify_commit || self.config.mint_invariant_checks().is_some()
+    }
+
+    /// Runs the checks due after the successful commit of `changed_keys` onto `pre_state_hash`,
+    /// notifies the commit's subscribers if `notify_commit` is set, and returns its result with the
+    /// bonded validators at `state_root`.
+    #[allow(clippy::too_many_arguments)]
+    fn complete_commit(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        pre_state_hash: Blake2bHash,
+        changed_keys: Vec<Key>,
+        notify_commit: bool,
+        state_root: Blake2bHash,
+        sequence_number: u64,
+    ) -> Result<CommitResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        if let Some(every_nth_commit) = self.config.mint_invariant_checks() {
+            let commit_count = self.commit_count.fetch_add(1, Ordering::Relaxed) + 1;
+            if commit_count % every_nth_commit == 0 {
+                if let Some(violation) = self.check_mint_invariants(
+                    correlation_id,
+                    protocol_version,
+                    pre_state_hash,
+                    state_root,
+                    &changed_keys,
+                )? {
+                    return Err(Error::MintInvariantViolated(violation));
+                }
+            }
+        }
+        let bonded_validators =
+            self.get_bonded_validators(correlation_id, protocol_data, state_root)?;
+        if notify_commit {
+            self.notify_commit(
+                protocol_version,
+                pre_state_hash,
+                changed_keys,
+                state_root,
+                bonded_validators.clone(),
+            );
+        }
+        Ok(CommitResult::Success {
+            state_root,
+            bonded_validators,
+            sequence_number,
+        })
+    }
+
+    /// Queues a notification of the commit of `modified_keys` onto `pre_state_hash` for the
+    /// subscribers to commits.
+    fn notify_commit(
+        &self,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        mut modified_keys: Vec<Key>,
+        post_state_hash: Blake2bHash,
+        bonded_validators: BTreeMap<PublicKey, U512>,
+    ) {
+        modified_keys.sort();
+        self.commit_notifier.notify(CommitNotification {
+            pre_state: pre_state_hash,
+            post_state: post_state_hash,
+            modified_keys,
+            bonded_validators,
+            protocol_version,
+        });
+    }
+
+    /// Notifies the subscribers to commits, if any, of the commit of `effects` onto
+    /// `pre_state_hash` by genesis, an upgrade or a step, if it succeeded.
+    fn notify_effects_commit(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+        pre_state_hash: Blake2bHash,
+        effects: &ExecutionEffect,
+        commit_result: &CommitResult,
+    ) -> Result<(), Error>
+    where
+        Error: From<S::Error>,
+    {
+        if let CommitResult::Success { state_root, .. } = commit_result {
+            if self.commit_notifier.has_subscribers() {
+                let bonded_validators =
+                    self.get_bonded_validators(correlation_id, protocol_data, *state_root)?;
+                self.notify_commit(
+                    protocol_version,
+                    pre_state_hash,
+                    effects.transforms.keys().cloned().collect(),
+                    *state_root,
+                    bonded_validators,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns one page of the state roots recorded in global state with their metadata.
+    ///
+    /// See [`ListRootsRequest`] for the ordering across pages.
+    pub fn run_list_roots(&self, list_roots_request: ListRootsRequest) -> Result<RootsPage, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let page_size = list_roots_request.page_size();
+        let roots = self
+            .state
+            .list_roots(page_size + 1, list_roots_request.page_token())?;
+        Ok(RootsPage::new(roots, page_size))
+    }
+
+    /// Returns the first genesis committed to global state, if any.
+    ///
+    /// Genesis roots are never pruned, so a store which was ever initialized by a genesis keeps
+    /// reporting it.
+    pub fn genesis_record(&self) -> Result<Option<GenesisRecord>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let mut first_genesis: Option<(u64, GenesisRecord)> = None;
+        let mut start_after = None;
+        loop {
+            let roots = self
+                .state
+                .list_roots(list_roots::DEFAULT_PAGE_SIZE, start_after)?;
+            start_after = roots.last().map(|(root, _)| *root);
+            for (root, metadata) in &roots {
+                if let Some(fingerprint) = metadata.genesis_fingerprint {
+                    let is_first = first_genesis
+                        .map(|(created_at, _)| metadata.created_at < created_at)
+                        .unwrap_or(true);
+                    if is_first {
+                        let genesis_record = GenesisRecord {
+                            fingerprint,
+                            root: *root,
+                        };
+                        first_genesis = Some((metadata.created_at, genesis_record));
+                    }
+                }
+            }
+            if roots.len() < list_roots::DEFAULT_PAGE_SIZE {
+                return Ok(first_genesis.map(|(_, genesis_record)| genesis_record));
+            }
+        }
+    }
+
+    /// Returns the serialized trie nodes requested by `trie_nodes_request`, in the requested order,
+    /// or `None` for the nodes which aren't in global state.
+    pub fn read_trie_nodes(
+        &self,
+        trie_nodes_request: &TrieNodesRequest,
+    ) -> Result<Vec<Option<Vec<u8>>>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let nodes = self.state.read_trie_nodes(trie_nodes_request.hashes())?;
+        Ok(nodes)
+    }
+
+    /// Prunes `roots` from global state as described in [`StateProvider::prune_roots`], evicting
+    /// any values cached for them.  Returns the number of trie elements deleted.
+    pub fn prune_roots(
+        &self,
+        correlation_id: CorrelationId,
+        roots: &[Blake2bHash],
+    ) -> Result<usize, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let deleted = self.state.prune_roots(correlation_id, roots)?;
+        if let Some(query_cache) = &self.query_cache {
+            for root in roots {
+                query_cache.evict_root(*root);
+            }
+        }
+        Ok(deleted)
+    }
+
+    /// Makes the commit which resulted in `state_root` as durable as `durability` requires, or as
+    /// the engine's configured durability requires if `durability` is `None`.  Returns whether
+    /// the commit, and every commit made before it, is durable.
+    pub fn make_durable(
+        &self,
+        durability: Option<Durability>,
+        state_root: Blake2bHash,
+    ) -> Result<bool, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let durability = durability.unwrap_or_else(|| self.config.durability());
+        let durable = self.state.make_durable(durability, state_root)?;
+        Ok(durable)
+    }
+
+    /// Returns the root of the latest commit known to be durable, if any.
+    pub fn last_durable_root(&self) -> Option<Blake2bHash> {
+        self.state.last_durable_root()
+    }
+
+    /// Checks that the balances of the mint's purses at `post_state_hash` sum to exactly the total
+    /// supply, returning the violation if not.  `post_state_hash` is the result of committing
+    /// effects which wrote `changed_keys` on top of `pre_state_hash`, and the balances among
+    /// `changed_keys` are reported with the violation.
+    ///
+    /// Returns `None` if either root is not found.  Every purse is read, so this is expensive for
+    /// a large state.
+    pub fn check_mint_invariants(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_version: ProtocolVersion,
+        pre_state_hash: Blake2bHash,
+        post_state_hash: Blake2bHash,
+        changed_keys: &[Key],
+    ) -> Result<Option<MintInvariantViolation>, Error> {
+        let protocol_data =
+            match self.get_protocol_data_at(correlation_id, post_state_hash, protocol_version)? {
+                Some(protocol_data) => protocol_data,
+                None => return Ok(None),
+            };
+        let pre_state = match self.state.checkout(pre_state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let post_state = match self.state.checkout(post_state_hash).map_err(Into::into)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let violation = mint_invariants::check(
+            correlation_id,
+            &pre_state,
+            &post_state,
+            post_state_hash,
+            protocol_data.mint(),
+            protocol_data.proof_of_stake(),
+            changed_keys,
+        )?;
+        Ok(violation)
+    }
+
+    /// Returns the hash of the state holding exactly the given `pairs`, without applying them as
+    /// effects on top of an existing state.
+    ///
+    /// The state is stored if `persist` is set.
+    pub fn compute_state_root(
+        &self,
+        correlation_id: CorrelationId,
+        pairs: Vec<(Key, StoredValue)>,
+        persist: bool,
+    ) -> Result<Blake2bHash, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let state_root = self
+            .state
+            .compute_state_root(correlation_id, pairs, persist)?;
+        Ok(state_root)
+    }
+
+    /// Makes the scheduled calls which are due at the block time of `step_request`, in the order
+    /// of the block times they were scheduled for and then of their ids, and commits the effects.
+    ///
+    /// Each call is made on behalf of the account which scheduled it, against the state left by
+    /// the calls made before it, and with the gas its escrow pays for.  A failed call has its
+    /// effects discarded, but doesn't prevent the other calls from being made.  The cost of each
+    /// call is paid from its escrow to the rewards purse of the PoS contract and the rest of the
+    /// escrow is refunded to the account.  Calls scheduled by the calls made are left for a later
+    /// step.
+    pub fn commit_step(
+        &self,
+        correlation_id: CorrelationId,
+        step_request: StepRequest,
+    ) -> Result<StepResult, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let StepRequest {
+            pre_state_hash,
+            protocol_version,
+            block_time,
+            era_end,
+        } = step_request;
+
+        let tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => Rc::new(RefCell::new(tracking_copy)),
+            None => return Ok(StepResult::RootNotFound),
+        };
+        let protocol_data =
+            match self.get_protocol_data_at(correlation_id, pre_state_hash, protocol_version)? {
+                Some(protocol_data) => protocol_data,
+                None => return Ok(StepResult::RootNotFound),
+            };
+
+        let due_calls = {
+            let mut tracking_copy = tracking_copy.borrow_mut();
+            let mut due_calls = Vec::new();
+            for schedule_id in scheduled_calls::read_index(&mut tracking_copy, correlation_id)? {
+                match scheduled_calls::read_scheduled_call(
+                    &mut tracking_copy,
+                    correlation_id,
+                    schedule_id,
+                )? {
+                    Some(scheduled_call) if scheduled_call.is_due(block_time) => {
+                        due_calls.push((schedule_id, scheduled_call))
+                    }
+                    _ => {}
+                }
+            }
+            due_calls.sort_by_key(|(schedule_id, scheduled_call)| {
+                let not_before: u64 = scheduled_call.not_before().into();
+                (not_before, *schedule_id)
+            });
+            due_calls
+        };
+
+        let mut executed_calls = Vec::with_capacity(due_calls.len());
+        if !due_calls.is_empty() {
+            let executor = Executor::new(self.config);
+            let escrow = Motes::new(U512::from(SCHEDULED_CALL_ESCROW));
+            let gas_limit = Gas::from_motes(escrow, CONV_RATE).unwrap_or_default();
+
+            for (schedule_id, scheduled_call) in due_calls {
+                let execution_result = self.execute_scheduled_call(
+                    correlation_id,
+                    &executor,
+                    Rc::clone(&tracking_copy),
+                    schedule_id,
+                    &scheduled_call,
+                    block_time,
+                    gas_limit,
+                    protocol_version,
+                    protocol_data,
+                );
+                let cost =
+                    Motes::from_gas(execution_result.cost(), CONV_RATE).expect("motes overflow");
+                let refund = Motes::new(escrow.value().saturating_sub(cost.value()));
+                self.settle_scheduled_call(
+                    correlation_id,
+                    &executor,
+                    Rc::clone(&tracking_copy),
+                    schedule_id,
+                    &scheduled_call,
+                    cost,
+                    refund,
+                    block_time,
+                    protocol_version,
+                    protocol_data,
+                )?;
+                executed_calls.push(ExecutedCall {
+                    schedule_id,
+                    execution_result,
+                    cost,
+                    refund,
+                });
+            }
+
+            let mut tracking_copy = tracking_copy.borrow_mut();
+            let remaining_ids: Vec<ScheduleId> =
+                scheduled_calls::read_index(&mut tracking_copy, correlation_id)?
+                    .into_iter()
+                    .filter(|schedule_id| {
+                        !executed_calls
+                            .iter()
+                            .any(|executed_call| executed_call.schedule_id == *schedule_id)
+                    })
+                    .collect();
+            tracking_copy.write(
+                *scheduled_calls::SCHEDULED_CALLS_KEY,
+                scheduled_calls::index_value(remaining_ids)?,
+            );
+        }
+
+        if let Some(era_end) = era_end {
+            self.run_auction(
+                correlation_id,
+                Rc::clone(&tracking_copy),
+                era_end,
+                protocol_version,
+                protocol_data,
+            )?;
+        }
+
+        let effects = tracking_copy.borrow().effect();
+        let commit_result = self
+            .state
+            .commit(
+                correlation_id,
+                pre_state_hash,
+                effects.transforms.to_owned(),
+            )
+            .map_err(Into::into)?;
+        self.notify_effects_commit(
+            correlation_id,
+            protocol_version,
+            protocol_data,
+            pre_state_hash,
+            &effects,
+            &commit_result,
+        )?;
+        let bonded_validators = match commit_result {
+            CommitResult::Success { state_root, .. } => {
+                self.get_bonded_validators(correlation_id, protocol_data, state_root)?
+            }
+            _ => BTreeMap::new(),
+        };
+
+        Ok(StepResult::from_commit_result(
+            commit_result,
+            effects,
+            executed_calls,
+            bonded_validators,
+        ))
+    }
+
+    /// Executes the session code of a system deploy as the system account, in a system context.
+    ///
+    /// System deploys have no payment phase: they aren't metered, and nothing is charged for them.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_system_deploy(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        preprocessor: &Preprocessor,
+        protocol_version: ProtocolVersion,
+        prestate_hash: Blake2bHash,
+        blocktime: BlockTime,
+        session: ExecutableDeployItem,
+        deploy_hash: [u8; 32],
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        trace: Option<&mut ExecutionTrace>,
+    ) -> Result<ExecutionResult, RootNotFound> {
+        let system_account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)
+        {
+            Ok(account) => account,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error.into())),
+        };
+
+        let session_module = match self.get_module(
+            Rc::clone(&tracking_copy),
+            &session,
+            &system_account,
+            correlation_id,
+            preprocessor,
+            &protocol_version,
+        ) {
+            Ok(module) => module,
+            Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+        };
+
+        let protocol_data =
+            match self.get_protocol_data_at(correlation_id, prestate_hash, protocol_version) {
+                Ok(Some(protocol_data)) => protocol_data,
+                Ok(None) => return Err(RootNotFound::new(prestate_hash)),
+                Err(error) => return Ok(ExecutionResult::precondition_failure(error)),
+            };
+
+        let mut authorization_keys = BTreeSet::new();
+        authorization_keys.insert(SYSTEM_ACCOUNT_ADDR);
+        let system_context =
+            SystemContext::new(Phase::System, blocktime, deploy_hash, authorization_keys);
+        let address_generator = Rc::new(RefCell::new(AddressGenerator::new(
+            &deploy_hash,
+            Phase::System,
+        )));
+        let mut named_keys = system_account.named_keys().clone();
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        let system_start = Instant::now();
+        let result: Result<(), execution::Error> = executor.exec_system(
+            session_module,
+            session.take_args(),
+            &mut named_keys,
+            Key::Account(SYSTEM_ACCOUNT_ADDR),
+            &system_account,
+            &system_context,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            protocol_data,
+            system_contract_cache,
+        );
+        executor.record_phase_time(Phase::System, system_start.elapsed());
+
+        let execution_result = match result {
+            Ok(()) => ExecutionResult::Success {
+                effect: tracking_copy.borrow().effect(),
+                cost: Gas::default(),
+                return_value: None,
+            },
+            Err(error) => ExecutionResult::Failure {
+                error: error.into(),
+                effect: Default::default(),
+                cost: Gas::default(),
+            },
+        };
+
+        if let Some(trace) = trace {
+            trace.record(Phase::System, &execution_result);
+        }
+
+        Ok(execution_result)
+    }
+
+    /// Makes `scheduled_call` against a fork of `tracking_copy`, whose changes are applied to
+    /// `tracking_copy` only if the call succeeds.
+    #[allow(clippy::too_many_arguments)]
+    fn execute_scheduled_call(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        schedule_id: ScheduleId,
+        scheduled_call: &ScheduledCall,
+        block_time: BlockTime,
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+    ) -> ExecutionResult {
+        let account = match tracking_copy
+            .borrow_mut()
+            .get_account(correlation_id, scheduled_call.account())
+        {
+            Ok(account) => account,
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
+        };
+        let contract_key = scheduled_call.contract();
+        let named_keys = match tracking_copy
+            .borrow_mut()
+            .get_contract(correlation_id, contract_key)
+        {
+            Ok(contract) => contract.named_keys().to_owned(),
+            Err(error) => return ExecutionResult::precondition_failure(error.into()),
+        };
+        let module = match self.get_module_from_key(
+            Rc::clone(&tracking_copy),
+            contract_key,
+            correlation_id,
+            &protocol_version,
+        ) {
+            Ok(module) => module,
+            Err(error) => return ExecutionResult::precondition_failure(error),
+        };
+        let mut authorization_keys = BTreeSet::new();
+        authorization_keys.insert(account.public_key());
+        let effect_limits = {
+            let wasm_costs = protocol_data.wasm_costs();
+            EffectLimits::new(
+                wasm_costs.max_transforms_per_deploy,
+                wasm_costs.max_effect_bytes_per_deploy,
+            )
+        };
+
+        let (execution_result, changes) = {
+            let base_tracking_copy = tracking_copy.borrow();
+            let call_tracking_copy = {
+                let mut call_tracking_copy = base_tracking_copy.fork();
+                call_tracking_copy.set_effect_limits(effect_limits);
+                Rc::new(RefCell::new(call_tracking_copy))
+            };
+            let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+            let execution_result = executor.exec_contract(
+                module,
+                scheduled_call.args().to_vec(),
+                Vec::new(),
+                named_keys,
+                contract_key,
+                &account,
+                authorization_keys,
+                block_time,
+                schedule_id.value(),
+                gas_limit,
+                protocol_version,
+                correlation_id,
+                Rc::clone(&call_tracking_copy),
+                Phase::Session,
+                protocol_data,
+                system_contract_cache,
+            );
+
+            let changes = if execution_result.is_failure() {
+                None
+            } else {
+                let call_tracking_copy = Rc::try_unwrap(call_tracking_copy)
+                    .unwrap_or_else(|_| panic!("tracking copy of a call shouldn't outlive it"));
+                Some(call_tracking_copy.into_inner().into_changes())
+            };
+            (execution_result, changes)
+        };
+
+        if let Some(changes) = changes {
+            tracking_copy.borrow_mut().apply_changes(changes);
+        }
+        execution_result
+    }
+
+    /// Pays `cost` from the escrow of `scheduled_call` to the rewards purse of the PoS contract,
+    /// and refunds `refund` to the main purse of the account which scheduled the call.
+    #[allow(clippy::too_many_arguments)]
+    fn settle_scheduled_call(
+        &self,
+        correlation_id: CorrelationId,
+        executor: &Executor,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        schedule_id: ScheduleId,
+        scheduled_call: &ScheduledCall,
+        cost: Motes,
+        refund: Motes,
+        block_time: BlockTime,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+    ) -> Result<(), Error> {
+        let mint_reference = protocol_data.mint();
+        let (mint_module, rewards_purse, account_main_purse, system_account) = {
+            let mut tracking_copy = tracking_copy.borrow_mut();
+            let mint_contract =
+                tracking_copy.get_contract(correlation_id, Key::URef(mint_reference))?;
+            let mint_module = engine_wasm_prep::deserialize(mint_contract.bytes())?;
+            let proof_of_stake_contract = tracking_copy
+                .get_contract(correlation_id, Key::URef(protocol_data.proof_of_stake()))?;
+            let rewards_purse = match proof_of_stake_contract.named_keys().get(POS_REWARDS_PURSE) {
+                Some(Key::URef(uref)) => *uref,
+                _ => return Err(Error::Deploy),
+            };
+            let account_main_purse = tracking_copy
+                .get_account(correlation_id, scheduled_call.account())?
+                .main_purse();
+            let system_account = tracking_copy.get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)?;
+            (
+                mint_module,
+                rewards_purse,
+                account_main_purse,
+                system_account,
+            )
+        };
+
+        // the purses are passed to the mint, so the system account must hold them
+        let escrow_purse = scheduled_call.escrow_purse();
+        let mut named_keys = BTreeMap::new();
+        named_keys.insert(ESCROW_PURSE_NAME.to_string(), Key::URef(escrow_purse));
+        named_keys.insert(POS_REWARDS_PURSE.to_string(), Key::URef(rewards_purse));
+        named_keys.insert(
+            ACCOUNT_MAIN_PURSE_NAME.to_string(),
+            Key::URef(account_main_purse),
+        );
+
+        let system_context = {
+            let mut authorization_keys = BTreeSet::new();
+            authorization_keys.insert(SYSTEM_ACCOUNT_ADDR);
+            SystemContext::new(
+                Phase::System,
+                block_time,
+                schedule_id.value(),
+                authorization_keys,
+            )
+        };
+        let address_generator = {
+            let generator = AddressGenerator::new(&schedule_id.value(), Phase::System);
+            Rc::new(RefCell::new(generator))
+        };
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        let (_instance, mut runtime) = executor.create_system_runtime(
+            mint_module,
+            Vec::new(),
+            &mut named_keys,
+            Key::Account(SYSTEM_ACCOUNT_ADDR),
+            &system_account,
+            &system_context,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            protocol_data,
+            system_contract_cache,
+        )?;
+
+        let mint_reference = mint_reference.with_access_rights(AccessRights::READ);
+        for &(target, amount) in &[(rewards_purse, cost), (account_main_purse, refund)] {
+            if amount.value().is_zero() {
+                continue;
+            }
+            let args = ArgsParser::parse((METHOD_TRANSFER, escrow_purse, target, amount.value()))
+                .expect("args should convert to `Vec<CLValue>`")
+                .into_bytes()?;
+            let result: Result<(), mint::Error> = runtime
+                .call_contract(mint_reference.into(), args)?
+                .into_t()
+                .map_err(execution::Error::from)?;
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Runs the auction contract as the system account, selecting the validators of the era ending
+    /// at `era_end`.
+    fn run_auction(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: Rc<RefCell<TrackingCopy<<S as StateProvider>::Reader>>>,
+        era_end: BlockTime,
+        protocol_version: ProtocolVersion,
+        protocol_data: ProtocolData,
+    ) -> Result<(), Error> {
+        let auction_reference = match protocol_data.auction() {
+            Some(auction_reference) => auction_reference,
+            None => return Err(MissingSystemContract(AUCTION.to_string())),
+        };
+        let (auction_module, system_account) = {
+            let mut tracking_copy = tracking_copy.borrow_mut();
+            let auction_contract =
+                tracking_copy.get_contract(correlation_id, Key::URef(auction_reference))?;
+            let auction_module = engine_wasm_prep::deserialize(auction_contract.bytes())?;
+            let system_account = tracking_copy.get_account(correlation_id, SYSTEM_ACCOUNT_ADDR)?;
+            (auction_module, system_account)
+        };
+
+        let era_end_timestamp: u64 = era_end.into();
+        // the auction of an era is run at most once, so its end identifies the execution
+        let deploy_hash = Blake2bHash::new(&era_end_timestamp.to_le_bytes()).value();
+        let system_context = {
+            let mut authorization_keys = BTreeSet::new();
+            authorization_keys.insert(SYSTEM_ACCOUNT_ADDR);
+            SystemContext::new(Phase::System, era_end, deploy_hash, authorization_keys)
+        };
+        let address_generator = {
+            let generator = AddressGenerator::new(&deploy_hash, Phase::System);
+            Rc::new(RefCell::new(generator))
+        };
+        let system_contract_cache = SystemContractCache::clone(&self.system_contract_cache);
+
+        let mut named_keys = BTreeMap::new();
+        let (_instance, mut runtime) = Executor::new(self.config).create_system_runtime(
+            auction_module,
+            Vec::new(),
+            &mut named_keys,
+            Key::Account(SYSTEM_ACCOUNT_ADDR),
+            &system_account,
+            &system_context,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            tracking_copy,
+            protocol_data,
+            system_contract_cache,
+        )?;
+
+        let args = ArgsParser::parse((METHOD_RUN_AUCTION, era_end_timestamp))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()?;
+        let auction_reference = auction_reference.with_access_rights(AccessRights::READ);
+        runtime.call_contract(auction_reference.into(), args)?;
+        Ok(())
+    }
+
+    /// Returns the validators bonded at `state_hash`, as the [`CommitResult::Success`] of the
+    /// commit which produced it reported them, using the protocol data of the version active at
+    /// the root.
+    ///
+    /// Returns `None` if `state_hash` is not found, and [`Error::ProtocolVersionNotRecorded`] if
+    /// the root was committed before active protocol versions were recorded.
+    pub fn get_validator_weights_at(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+    ) -> Result<Option<BTreeMap<PublicKey, U512>>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let reader = match self.state.checkout(state_hash)? {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        let protocol_version = match active_protocol_version::read(correlation_id, &reader)? {
+            Some(protocol_version) => protocol_version,
+            None => return Err(Error::ProtocolVersionNotRecorded(state_hash)),
+        };
+        let protocol_data = match self.get_protocol_data(protocol_version)? {
+            Some(protocol_data) => protocol_data,
+            None => {
+                return Err(Error::ConfigNotFound {
+                    protocol_version,
+                    state_hash,
+                })
+            }
+        };
+        Self::read_bonded_validators(correlation_id, &reader, protocol_data).map(Some)
+    }
+
+    /// Calculates bonded validators at `root_hash` state, in the canonical order of
+    /// [`CommitResult::Success`].
+    ///
+    /// Should only be called with a valid root hash after a successful call to
+    /// [`StateProvider::commit`]. Will panic if called with an invalid root hash.
+    fn get_bonded_validators(
+        &self,
+        correlation_id: CorrelationId,
+        protocol_data: ProtocolData,
+        root_hash: Blake2bHash,
+    ) -> Result<BTreeMap<PublicKey, U512>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let reader = match self.state.checkout(root_hash)? {
+            Some(reader) => reader,
+            None => panic!("get_bonded_validators called with an invalid root hash"),
+        };
+        Self::read_bonded_validators(correlation_id, &reader, protocol_data)
+    }
+
+    /// Reads the bonded validators from the root read by `reader`.
+    ///
+    /// If the protocol data selects the auction, these are the winners of the latest auction, or
+    /// the validators bonded with the PoS contract until the first auction is run.
+    ///
+    /// Fails with [`Error::DuplicateValidatorEntry`] rather than pick one of the stakes if the PoS
+    /// contract holds more than one bond entry for a validator, and with
+    /// [`Error::InvalidValidatorEntry`] rather than drop a validator whose entry can't be read.
+    fn read_bonded_validators(
+        correlation_id: CorrelationId,
+        reader: &S::Reader,
+        protocol_data: ProtocolData,
+    ) -> Result<BTreeMap<PublicKey, U512>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let proof_of_stake = {
+            let tmp = protocol_data.proof_of_stake();
+            Key::URef(tmp).normalize()
+        };
+
+        if let Some(auction) = protocol_data.auction() {
+            if protocol_data.use_auction() {
+                let winners = Self::read_auction_winners(correlation_id, reader, auction)?;
+                if !winners.is_empty() {
+                    return Ok(winners);
+                }
+            }
+        }
+
+        let contract = match reader.read(correlation_id, &proof_of_stake)? {
+            Some(StoredValue::Contract(contract)) => contract,
+            _ => return Err(MissingSystemContract(PROOF_OF_STAKE.to_string())),
+        };
+
+        let mut bonded_validators = BTreeMap::new();
+        for (name, key) in contract.named_keys() {
+            let (validator, balance) = match proof_of_stake::parse_stake_key_name(name) {
+                Ok(Some(StakeKeyName::Legacy(validator, balance))) => (validator, balance),
+                Ok(Some(StakeKeyName::Current(validator))) => {
+                    match reader.read(correlation_id, &key.normalize())? {
+                        Some(StoredValue::CLValue(cl_value)) => match cl_value.into_t::<U512>() {
+                            Ok(balance) => (validator, balance),
+                            Err(error) => {
+                                return Err(Error::InvalidValidatorEntry(format!(
+                                    "stake under {} isn't a U512: {:?}",
+                                    name, error
+                                )))
+                            }
+                        },
+                        Some(stored_value) => {
+                            return Err(Error::InvalidValidatorEntry(format!(
+                                "stake under {} is a {}",
+                                name,
+                                stored_value.type_name()
+                            )))
+                        }
+                        None => {
+                            return Err(Error::InvalidValidatorEntry(format!(
+                                "no stake stored under {}",
+                                name
+                            )))
+                        }
+                    }
+                }
+                // not a stake, e.g. one of the PoS contract's purses
+                Ok(None) => continue,
+                Err(error) => {
+                    return Err(Error::InvalidValidatorEntry(format!(
+                        "malformed stake name {}: {}",
+                        name, error
+                    )))
+                }
+            };
+            // a validator can have both a legacy and a current entry mid-migration; reporting
+            // either stake would be a guess
+            match bonded_validators.entry(validator) {
+                btree_map::Entry::Vacant(entry) => {
+                    entry.insert(balance);
+                }
+                btree_map::Entry::Occupied(_) => {
+                    return Err(Error::DuplicateValidatorEntry(validator))
+                }
+            }
+        }
 
         Ok(bonded_validators)
     }
+
+    /// Reads the validators selected by the latest run of the auction contract at `auction`.
+    fn read_auction_winners(
+        correlation_id: CorrelationId,
+        reader: &S::Reader,
+        auction: URef,
+    ) -> Result<BTreeMap<PublicKey, U512>, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let contract = match reader.read(correlation_id, &Key::URef(auction).normalize())? {
+            Some(StoredValue::Contract(contract)) => contract,
+            _ => return Err(MissingSystemContract(AUCTION.to_string())),
+        };
+        let winners_key = match contract.named_keys().get(WINNERS_KEY) {
+            Some(key) => key.normalize(),
+            None => return Err(Error::Deploy),
+        };
+        match reader.read(correlation_id, &winners_key)? {
+            Some(StoredValue::CLValue(cl_value)) => cl_value.into_t().map_err(|_| Error::Deploy),
+            _ => Err(Error::Deploy),
+        }
+    }
 }
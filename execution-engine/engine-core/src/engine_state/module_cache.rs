@@ -0,0 +1,141 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+
+use parity_wasm::elements::Module;
+
+use engine_shared::newtypes::Blake2bHash;
+use engine_wasm_prep::{wasm_costs::WasmCosts, PreprocessingError, Preprocessor};
+
+/// The most modules held by a [`ModuleCache`].  Once full, the cache is cleared before inserting.
+pub const MAX_CACHED_MODULES: usize = 1_000;
+
+/// A cache of preprocessed session and payment modules, keyed by the hash of their bytes and the
+/// Wasm costs they were instrumented with.
+///
+/// This lets a deploy be preprocessed once when it's validated, rather than again every time it's
+/// executed.
+#[derive(Clone, Default, Debug)]
+pub struct ModuleCache {
+    modules: Arc<RwLock<HashMap<(Blake2bHash, WasmCosts), Module>>>,
+    hits: Arc<AtomicUsize>,
+    misses: Arc<AtomicUsize>,
+}
+
+impl ModuleCache {
+    /// Returns a clone of the module preprocessed from `module_bytes` by `preprocessor`,
+    /// preprocessing and caching it if it isn't already cached.
+    pub fn get_or_preprocess(
+        &self,
+        preprocessor: &Preprocessor,
+        module_bytes: &[u8],
+    ) -> Result<Module, PreprocessingError> {
+        let key = (Blake2bHash::new(module_bytes), *preprocessor.wasm_costs());
+        if let Some(module) = self.modules.read().unwrap().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(module.clone());
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let module = preprocessor.preprocess(module_bytes)?;
+        let mut guarded_map = self.modules.write().unwrap();
+        if guarded_map.len() >= MAX_CACHED_MODULES {
+            guarded_map.clear();
+        }
+        guarded_map.insert(key, module.clone());
+        Ok(module)
+    }
+
+    /// Returns the number of modules currently cached.
+    pub fn len(&self) -> usize {
+        self.modules.read().unwrap().len()
+    }
+
+    /// Returns `true` if no modules are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups which found the module already preprocessed.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups which had to preprocess the module.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_wasm_prep::{wasm_costs::WasmCosts, Preprocessor};
+
+    use super::ModuleCache;
+
+    // (module (memory 1) (export "memory" (memory 0)) (func (export "call")))
+    const MODULE_BYTES: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x03,
+        0x02, 0x01, 0x00, 0x05, 0x03, 0x01, 0x00, 0x01, 0x07, 0x11, 0x02, 0x06, 0x6d, 0x65, 0x6d,
+        0x6f, 0x72, 0x79, 0x02, 0x00, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x00, 0x0a, 0x04, 0x01,
+        0x02, 0x00, 0x0b,
+    ];
+
+    fn wasm_costs() -> WasmCosts {
+        WasmCosts {
+            max_stack_height: 64 * 1024,
+            opcodes_div: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn should_preprocess_once() {
+        let cache = ModuleCache::default();
+        let preprocessor = Preprocessor::new(wasm_costs());
+
+        let module = cache
+            .get_or_preprocess(&preprocessor, MODULE_BYTES)
+            .expect("should preprocess");
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+        let cached_module = cache
+            .get_or_preprocess(&preprocessor, MODULE_BYTES)
+            .expect("should get cached module");
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+        assert_eq!(cached_module, module);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn should_preprocess_again_with_other_wasm_costs() {
+        let cache = ModuleCache::default();
+        let preprocessor = Preprocessor::new(wasm_costs());
+        let other_preprocessor = Preprocessor::new(WasmCosts {
+            regular: 2,
+            ..wasm_costs()
+        });
+
+        cache
+            .get_or_preprocess(&preprocessor, MODULE_BYTES)
+            .expect("should preprocess");
+        cache
+            .get_or_preprocess(&other_preprocessor, MODULE_BYTES)
+            .expect("should preprocess");
+        assert_eq!((cache.hits(), cache.misses()), (0, 2));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn should_not_cache_invalid_module() {
+        let cache = ModuleCache::default();
+        let preprocessor = Preprocessor::new(wasm_costs());
+
+        assert!(cache.get_or_preprocess(&preprocessor, &[1, 2, 3]).is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -1,28 +1,80 @@
-use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_shared::{account::AccountAuthInfo, newtypes::Blake2bHash, stored_value::StoredValue};
 use types::Key;
 
 use crate::tracking_copy::TrackingCopyQueryResult;
 
+/// The last path segment of a query for the [`AccountAuthInfo`] of the account found at the rest
+/// of the path, rather than for the account itself.
+pub const AUTH_PROJECTION: &str = "#auth";
+
+#[derive(Clone)]
 pub enum QueryResult {
     RootNotFound,
     ValueNotFound(String),
     CircularReference(String),
     Success(StoredValue),
+    /// The keys and thresholds of the account queried with [`QueryProjection::AccountAuth`].
+    AccountAuth(AccountAuthInfo),
+}
+
+/// A typed view of the queried value returned instead of the value itself, requested by ending the
+/// query path with the projection's segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryProjection {
+    /// The [`AccountAuthInfo`] of an account, requested by [`AUTH_PROJECTION`].
+    AccountAuth,
 }
 
+impl QueryProjection {
+    /// Splits the projection requested by the last segment of `path` off it, if any.
+    fn split_off(path: &mut Vec<String>) -> Option<Self> {
+        match path.last().map(String::as_str) {
+            Some(AUTH_PROJECTION) => {
+                path.pop();
+                Some(QueryProjection::AccountAuth)
+            }
+            _ => None,
+        }
+    }
+
+    /// Projects the value found by a query.  Results other than a success are left as they are.
+    pub fn apply(self, query_result: QueryResult) -> QueryResult {
+        match (self, query_result) {
+            (QueryProjection::AccountAuth, QueryResult::Success(StoredValue::Account(account))) => {
+                QueryResult::AccountAuth(AccountAuthInfo::from(&account))
+            }
+            (QueryProjection::AccountAuth, QueryResult::Success(value)) => {
+                QueryResult::ValueNotFound(format!(
+                    "{} only applies to accounts, found {}",
+                    AUTH_PROJECTION,
+                    value.type_name()
+                ))
+            }
+            (_, query_result) => query_result,
+        }
+    }
+}
+
+/// A query of the value found by following `path` from `key` at `state_hash`.
+///
+/// A path ending with a projection's segment, e.g. [`AUTH_PROJECTION`], queries the
+/// [`QueryProjection`] of the value found at the rest of the path.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct QueryRequest {
     state_hash: Blake2bHash,
     key: Key,
     path: Vec<String>,
+    projection: Option<QueryProjection>,
 }
 
 impl QueryRequest {
-    pub fn new(state_hash: Blake2bHash, key: Key, path: Vec<String>) -> Self {
+    pub fn new(state_hash: Blake2bHash, key: Key, mut path: Vec<String>) -> Self {
+        let projection = QueryProjection::split_off(&mut path);
         QueryRequest {
             state_hash,
             key,
             path,
+            projection,
         }
     }
 
@@ -34,9 +86,54 @@ impl QueryRequest {
         self.key
     }
 
+    /// Returns the path without the projection's segment.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    pub fn projection(&self) -> Option<QueryProjection> {
+        self.projection
+    }
+}
+
+/// A query of the same key and path at several state roots.
+///
+/// Like for a [`QueryRequest`], the path can end with a projection's segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiRootQueryRequest {
+    state_hashes: Vec<Blake2bHash>,
+    key: Key,
+    path: Vec<String>,
+    projection: Option<QueryProjection>,
+}
+
+impl MultiRootQueryRequest {
+    pub fn new(state_hashes: Vec<Blake2bHash>, key: Key, mut path: Vec<String>) -> Self {
+        let projection = QueryProjection::split_off(&mut path);
+        MultiRootQueryRequest {
+            state_hashes,
+            key,
+            path,
+            projection,
+        }
+    }
+
+    pub fn state_hashes(&self) -> &[Blake2bHash] {
+        &self.state_hashes
+    }
+
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Returns the path without the projection's segment.
     pub fn path(&self) -> &[String] {
         &self.path
     }
+
+    pub fn projection(&self) -> Option<QueryProjection> {
+        self.projection
+    }
 }
 
 impl From<TrackingCopyQueryResult> for QueryResult {
@@ -0,0 +1,239 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use linked_hash_map::LinkedHashMap;
+
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key,
+};
+
+/// The default capacity of a [`QueryCache`], in bytes of serialized values.
+pub const DEFAULT_QUERY_CACHE_CAPACITY: usize = 64 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct QueryCacheKey {
+    state_hash: Blake2bHash,
+    key: Key,
+    path: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    values: LinkedHashMap<QueryCacheKey, Vec<u8>>,
+    weight: usize,
+}
+
+impl Entries {
+    fn remove(&mut self, cache_key: &QueryCacheKey) {
+        if let Some(value) = self.values.remove(cache_key) {
+            self.weight -= value.len();
+        }
+    }
+}
+
+/// A least-recently-used cache of the values found by queries, keyed by the state root, base key
+/// and path queried.
+///
+/// State roots are immutable, so a cached value never goes stale.  Values are held serialized, and
+/// the capacity bounds the total size of the serialized values rather than the number of entries.
+/// Only successful queries are cached: a root which isn't found now may be committed later.
+#[derive(Clone, Debug)]
+pub struct QueryCache {
+    entries: Arc<Mutex<Entries>>,
+    capacity: usize,
+    hits: Arc<AtomicUsize>,
+    misses: Arc<AtomicUsize>,
+}
+
+impl QueryCache {
+    /// Creates an empty cache holding at most `capacity` bytes of serialized values.
+    pub fn new(capacity: usize) -> Self {
+        QueryCache {
+            entries: Default::default(),
+            capacity,
+            hits: Default::default(),
+            misses: Default::default(),
+        }
+    }
+
+    /// Returns the value found by querying `path` from `key` at `state_hash`, if cached, marking it
+    /// as the most recently used.
+    pub fn get(&self, state_hash: Blake2bHash, key: Key, path: &[String]) -> Option<StoredValue> {
+        let cache_key = QueryCacheKey {
+            state_hash,
+            key,
+            path: path.to_vec(),
+        };
+        let mut entries = self.entries.lock().expect("should lock query cache");
+        match entries.values.get_refresh(&cache_key) {
+            Some(bytes) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                // only values which serialized successfully are inserted
+                Some(bytesrepr::deserialize(bytes.clone()).expect("should deserialize value"))
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Caches `value` as the value found by querying `path` from `key` at `state_hash`, evicting
+    /// the least recently used values until the cache is within its capacity.
+    ///
+    /// A value larger than the whole capacity is not cached.
+    pub fn insert(&self, state_hash: Blake2bHash, key: Key, path: &[String], value: &StoredValue) {
+        let bytes = match value.to_bytes() {
+            Ok(bytes) if bytes.len() <= self.capacity => bytes,
+            _ => return,
+        };
+        let cache_key = QueryCacheKey {
+            state_hash,
+            key,
+            path: path.to_vec(),
+        };
+        let mut entries = self.entries.lock().expect("should lock query cache");
+        entries.remove(&cache_key);
+        while entries.weight + bytes.len() > self.capacity {
+            match entries.values.pop_front() {
+                Some((_, evicted)) => entries.weight -= evicted.len(),
+                None => break,
+            }
+        }
+        entries.weight += bytes.len();
+        entries.values.insert(cache_key, bytes);
+    }
+
+    /// Evicts every value cached for `state_hash`, e.g. once the root has been pruned.
+    pub fn evict_root(&self, state_hash: Blake2bHash) {
+        let mut entries = self.entries.lock().expect("should lock query cache");
+        let cache_keys: Vec<QueryCacheKey> = entries
+            .values
+            .keys()
+            .filter(|cache_key| cache_key.state_hash == state_hash)
+            .cloned()
+            .collect();
+        for cache_key in &cache_keys {
+            entries.remove(cache_key);
+        }
+    }
+
+    /// Returns the most bytes of serialized values held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes of serialized values currently cached.
+    pub fn weight(&self) -> usize {
+        self.entries.lock().expect("should lock query cache").weight
+    }
+
+    /// Returns the number of values currently cached.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .expect("should lock query cache")
+            .values
+            .len()
+    }
+
+    /// Returns `true` if no values are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of lookups which found the value cached.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of lookups which didn't find the value cached.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+    use types::{bytesrepr::ToBytes, CLValue, Key};
+
+    use super::QueryCache;
+
+    fn value(n: u64) -> StoredValue {
+        StoredValue::CLValue(CLValue::from_t(n).unwrap())
+    }
+
+    fn value_size() -> usize {
+        value(0).to_bytes().unwrap().len()
+    }
+
+    fn root(n: u8) -> Blake2bHash {
+        Blake2bHash::new(&[n])
+    }
+
+    #[test]
+    fn should_get_inserted_value() {
+        let cache = QueryCache::new(value_size());
+        let path = vec!["a".to_string()];
+
+        assert_eq!(cache.get(root(1), Key::Hash([1; 32]), &path), None);
+        cache.insert(root(1), Key::Hash([1; 32]), &path, &value(1));
+        assert_eq!(
+            cache.get(root(1), Key::Hash([1; 32]), &path),
+            Some(value(1))
+        );
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // each of the root, key and path are part of the cache key
+        assert_eq!(cache.get(root(2), Key::Hash([1; 32]), &path), None);
+        assert_eq!(cache.get(root(1), Key::Hash([2; 32]), &path), None);
+        assert_eq!(cache.get(root(1), Key::Hash([1; 32]), &[]), None);
+        assert_eq!((cache.hits(), cache.misses()), (1, 4));
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_at_capacity() {
+        let cache = QueryCache::new(2 * value_size());
+
+        cache.insert(root(1), Key::Hash([1; 32]), &[], &value(1));
+        cache.insert(root(2), Key::Hash([1; 32]), &[], &value(2));
+        assert_eq!(cache.weight(), 2 * value_size());
+
+        // reading root 1 makes root 2 the least recently used
+        assert!(cache.get(root(1), Key::Hash([1; 32]), &[]).is_some());
+        cache.insert(root(3), Key::Hash([1; 32]), &[], &value(3));
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.weight(), 2 * value_size());
+        assert_eq!(cache.get(root(2), Key::Hash([1; 32]), &[]), None);
+        assert_eq!(cache.get(root(1), Key::Hash([1; 32]), &[]), Some(value(1)));
+        assert_eq!(cache.get(root(3), Key::Hash([1; 32]), &[]), Some(value(3)));
+    }
+
+    #[test]
+    fn should_not_cache_value_larger_than_capacity() {
+        let cache = QueryCache::new(value_size() - 1);
+        cache.insert(root(1), Key::Hash([1; 32]), &[], &value(1));
+        assert!(cache.is_empty());
+        assert_eq!(cache.weight(), 0);
+    }
+
+    #[test]
+    fn should_evict_root() {
+        let cache = QueryCache::new(3 * value_size());
+        cache.insert(root(1), Key::Hash([1; 32]), &[], &value(1));
+        cache.insert(root(1), Key::Hash([2; 32]), &[], &value(2));
+        cache.insert(root(2), Key::Hash([1; 32]), &[], &value(3));
+
+        cache.evict_root(root(1));
+
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.weight(), value_size());
+        assert_eq!(cache.get(root(2), Key::Hash([1; 32]), &[]), Some(value(3)));
+    }
+}
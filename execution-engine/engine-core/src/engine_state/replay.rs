@@ -0,0 +1,180 @@
+use engine_shared::{
+    additive_map::AdditiveMap, gas::Gas, newtypes::Blake2bHash, transform::Transform,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{
+    bytesrepr::{self, ToBytes},
+    BlockTime, Key, Phase, ProtocolVersion,
+};
+
+use super::{deploy_item::DeployItem, execution_result::ExecutionResult};
+
+/// Settings which replace the ones a deploy was originally executed with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReplayOverrides {
+    /// Costs used when preprocessing the deploy's Wasm, instead of the ones of its protocol
+    /// version.
+    pub wasm_costs: Option<WasmCosts>,
+    /// Whether to collect a per-phase [`ExecutionTrace`].
+    pub collect_trace: bool,
+    /// Block time used instead of the one of the original block.
+    pub block_time: Option<BlockTime>,
+}
+
+/// A request to re-execute a single deploy against a historical state root.
+pub struct ReplayRequest {
+    pub parent_state_hash: Blake2bHash,
+    pub block_time: BlockTime,
+    pub deploy_item: DeployItem,
+    pub protocol_version: ProtocolVersion,
+    pub overrides: ReplayOverrides,
+    /// Hash of the effects recorded when the deploy was originally executed, if known.
+    pub recorded_effects_hash: Option<Blake2bHash>,
+}
+
+impl ReplayRequest {
+    pub fn new(
+        parent_state_hash: Blake2bHash,
+        block_time: BlockTime,
+        deploy_item: DeployItem,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        Self {
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+            overrides: Default::default(),
+            recorded_effects_hash: None,
+        }
+    }
+
+    pub fn with_overrides(mut self, overrides: ReplayOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    pub fn with_recorded_effects_hash(mut self, recorded_effects_hash: Blake2bHash) -> Self {
+        self.recorded_effects_hash = Some(recorded_effects_hash);
+        self
+    }
+}
+
+/// The outcome of a single execution phase of a deploy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PhaseTrace {
+    pub phase: Phase,
+    pub cost: Gas,
+    pub error: Option<String>,
+}
+
+/// The outcomes of the execution phases of a deploy, in execution order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    phases: Vec<PhaseTrace>,
+    base_deploy_cost: Gas,
+}
+
+impl ExecutionTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(&mut self, phase: Phase, result: &ExecutionResult) {
+        self.phases.push(PhaseTrace {
+            phase,
+            cost: result.cost(),
+            error: result.as_error().map(ToString::to_string),
+        });
+    }
+
+    pub fn phases(&self) -> &[PhaseTrace] {
+        &self.phases
+    }
+
+    pub(crate) fn record_base_deploy_cost(&mut self, base_deploy_cost: Gas) {
+        self.base_deploy_cost = base_deploy_cost;
+    }
+
+    /// Returns the gas charged for the deploy on top of the cost of its phases, which is zero if
+    /// it didn't get as far as executing its payment code.
+    pub fn base_deploy_cost(&self) -> Gas {
+        self.base_deploy_cost
+    }
+}
+
+/// A replayed deploy.  Its effects are never committed.
+#[derive(Debug)]
+pub struct ReplayedDeploy {
+    pub execution_result: ExecutionResult,
+    pub effects_hash: Blake2bHash,
+    /// Present if `collect_trace` was set in the [`ReplayOverrides`].
+    pub trace: Option<ExecutionTrace>,
+    /// Present if the request held the hash of the originally recorded effects.
+    pub matches_recorded_effects: Option<bool>,
+}
+
+#[derive(Debug)]
+pub enum ReplayResult {
+    RootNotFound,
+    Replayed(ReplayedDeploy),
+}
+
+/// Returns a hash of `transforms` which doesn't depend on their iteration order.
+pub fn effects_hash(
+    transforms: &AdditiveMap<Key, Transform>,
+) -> Result<Blake2bHash, bytesrepr::Error> {
+    let mut sorted_transforms: Vec<(&Key, &Transform)> = transforms.iter().collect();
+    sorted_transforms.sort_by_key(|(key, _)| *key);
+
+    let mut bytes = Vec::new();
+    for (key, transform) in sorted_transforms {
+        bytes.append(&mut key.to_bytes()?);
+        bytes.append(&mut transform_to_bytes(transform)?);
+    }
+    Ok(Blake2bHash::new(&bytes))
+}
+
+fn transform_to_bytes(transform: &Transform) -> Result<Vec<u8>, bytesrepr::Error> {
+    let (tag, mut payload) = match transform {
+        Transform::Identity => (0u8, Vec::new()),
+        Transform::Write(stored_value) => (1, stored_value.to_bytes()?),
+        Transform::AddInt32(value) => (2, value.to_bytes()?),
+        Transform::AddUInt64(value) => (3, value.to_bytes()?),
+        Transform::AddUInt128(value) => (4, value.to_bytes()?),
+        Transform::AddUInt256(value) => (5, value.to_bytes()?),
+        Transform::AddUInt512(value) => (6, value.to_bytes()?),
+        Transform::AddKeys(keys) => (7, keys.to_bytes()?),
+        Transform::Failure(error) => (8, format!("{:?}", error).to_bytes()?),
+        Transform::Prune => (9, Vec::new()),
+    };
+    let mut bytes = vec![tag];
+    bytes.append(&mut payload);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::{additive_map::AdditiveMap, transform::Transform};
+    use types::{Key, U512};
+
+    use super::effects_hash;
+
+    #[test]
+    fn effects_hash_should_depend_on_keys_and_transforms() {
+        let mut transforms = AdditiveMap::new();
+        transforms.insert(Key::Hash([1; 32]), Transform::AddUInt512(U512::one()));
+        transforms.insert(Key::Hash([2; 32]), Transform::Identity);
+        let hash = effects_hash(&transforms).unwrap();
+        assert_eq!(hash, effects_hash(&transforms.clone()).unwrap());
+
+        let mut other_transform = transforms.clone();
+        other_transform.insert(Key::Hash([1; 32]), Transform::AddUInt64(1));
+        assert_ne!(hash, effects_hash(&other_transform).unwrap());
+
+        let mut other_key = AdditiveMap::new();
+        other_key.insert(Key::Hash([1; 32]), Transform::AddUInt512(U512::one()));
+        other_key.insert(Key::Hash([3; 32]), Transform::Identity);
+        assert_ne!(hash, effects_hash(&other_key).unwrap());
+    }
+}
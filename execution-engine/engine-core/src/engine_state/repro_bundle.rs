@@ -0,0 +1,322 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_storage::{
+    global_state::{
+        durability::Durability, BatchCommitResult, CommitResult, StateProvider, StateReader,
+    },
+    protocol_data::ProtocolData,
+    root_metadata_store::RootMetadata,
+    trie::TrieMerkleProof,
+    trie_store::node_cache::TrieNodeCache,
+};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockTime, Key, ProtocolVersion,
+};
+
+use super::{deploy_item::DeployItem, replay::ReplayedDeploy};
+
+/// The values read by a deploy, keyed by the normalized key they were read from.  `None` records a
+/// read of a key which isn't in global state.
+pub(crate) type ReadLog = Rc<RefCell<BTreeMap<Key, Option<StoredValue>>>>;
+
+/// A state provider which records every value read through the readers it checks out.
+pub(crate) struct RecordingStateProvider<'a, S> {
+    state: &'a S,
+    reads: ReadLog,
+}
+
+impl<'a, S> RecordingStateProvider<'a, S> {
+    pub(crate) fn new(state: &'a S) -> Self {
+        RecordingStateProvider {
+            state,
+            reads: Default::default(),
+        }
+    }
+
+    pub(crate) fn reads(&self) -> ReadLog {
+        Rc::clone(&self.reads)
+    }
+
+    fn recording<R>(&self, reader: Option<R>) -> Option<RecordingReader<R>> {
+        reader.map(|reader| RecordingReader {
+            reader,
+            reads: self.reads(),
+        })
+    }
+}
+
+pub(crate) struct RecordingReader<R> {
+    reader: R,
+    reads: ReadLog,
+}
+
+impl<R> StateReader<Key, StoredValue> for RecordingReader<R>
+where
+    R: StateReader<Key, StoredValue>,
+{
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        let value = self.reader.read(correlation_id, key)?;
+        self.reads.borrow_mut().insert(*key, value.clone());
+        Ok(value)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let keys = self.reader.keys_with_prefix(correlation_id, prefix)?;
+        // the replayed state must list the same keys, so their values are recorded too
+        for key in &keys {
+            self.read(correlation_id, key)?;
+        }
+        Ok(keys)
+    }
+}
+
+impl<'a, S: StateProvider> StateProvider for RecordingStateProvider<'a, S> {
+    type Error = S::Error;
+    type Reader = RecordingReader<S::Reader>;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        Ok(self.recording(self.state.checkout(state_hash)?))
+    }
+
+    fn checkout_with_cache(
+        &self,
+        state_hash: Blake2bHash,
+        trie_node_cache: &TrieNodeCache,
+    ) -> Result<Option<Self::Reader>, Self::Error> {
+        Ok(self.recording(
+            self.state
+                .checkout_with_cache(state_hash, trie_node_cache)?,
+        ))
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        self.state.commit(correlation_id, state_hash, effects)
+    }
+
+    fn commit_fenced(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+        last_seen_sequence_number: u64,
+    ) -> Result<CommitResult, Self::Error> {
+        self.state.commit_fenced(
+            correlation_id,
+            state_hash,
+            effects,
+            last_seen_sequence_number,
+        )
+    }
+
+    fn batch_commit(
+        &self,
+        correlation_id: CorrelationId,
+        entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<BatchCommitResult, Self::Error> {
+        self.state.batch_commit(correlation_id, entries)
+    }
+
+    fn compute_state_root(
+        &self,
+        correlation_id: CorrelationId,
+        pairs: Vec<(Key, StoredValue)>,
+        persist: bool,
+    ) -> Result<Blake2bHash, Self::Error> {
+        self.state
+            .compute_state_root(correlation_id, pairs, persist)
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        self.state
+            .put_protocol_data(protocol_version, protocol_data)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        self.state.get_protocol_data(protocol_version)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.state.empty_root()
+    }
+
+    fn list_roots(
+        &self,
+        limit: usize,
+        start_after: Option<Blake2bHash>,
+    ) -> Result<Vec<(Blake2bHash, RootMetadata)>, Self::Error> {
+        self.state.list_roots(limit, start_after)
+    }
+
+    fn record_genesis(
+        &self,
+        root: Blake2bHash,
+        genesis_fingerprint: Blake2bHash,
+    ) -> Result<(), Self::Error> {
+        self.state.record_genesis(root, genesis_fingerprint)
+    }
+
+    fn read_trie_nodes(&self, hashes: &[Blake2bHash]) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        self.state.read_trie_nodes(hashes)
+    }
+
+    fn prune_roots(
+        &self,
+        correlation_id: CorrelationId,
+        roots: &[Blake2bHash],
+    ) -> Result<usize, Self::Error> {
+        self.state.prune_roots(correlation_id, roots)
+    }
+
+    fn make_durable(
+        &self,
+        durability: Durability,
+        state_root: Blake2bHash,
+    ) -> Result<bool, Self::Error> {
+        self.state.make_durable(durability, state_root)
+    }
+
+    fn last_durable_root(&self) -> Option<Blake2bHash> {
+        self.state.last_durable_root()
+    }
+}
+
+/// A request to export the [`ReproBundle`] of a deploy executed against a historical state root.
+pub struct ExportReproBundleRequest {
+    pub parent_state_hash: Blake2bHash,
+    pub block_time: BlockTime,
+    pub deploy_item: DeployItem,
+    pub protocol_version: ProtocolVersion,
+}
+
+impl ExportReproBundleRequest {
+    pub fn new(
+        parent_state_hash: Blake2bHash,
+        block_time: BlockTime,
+        deploy_item: DeployItem,
+        protocol_version: ProtocolVersion,
+    ) -> Self {
+        ExportReproBundleRequest {
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        }
+    }
+}
+
+/// Everything needed to re-execute a deploy away from the node it ran on: the deploy, its block
+/// context, and the part of the parent state it read, each value with a proof against the parent
+/// state hash.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ReproBundle {
+    pub parent_state_hash: Blake2bHash,
+    pub block_time: BlockTime,
+    pub protocol_version: ProtocolVersion,
+    pub protocol_data: ProtocolData,
+    pub deploy_item: DeployItem,
+    /// The values read by the deploy.
+    pub reads: Vec<TrieMerkleProof<Key, StoredValue>>,
+    /// The keys the deploy read which weren't in the parent state.  Their absence isn't proven.
+    pub absent_keys: Vec<Key>,
+    /// Hash of the effects of the deploy when the bundle was exported.
+    pub effects_hash: Blake2bHash,
+}
+
+impl ToBytes for ReproBundle {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.parent_state_hash.to_bytes()?);
+        ret.append(&mut self.block_time.to_bytes()?);
+        ret.append(&mut self.protocol_version.to_bytes()?);
+        ret.append(&mut self.protocol_data.to_bytes()?);
+        ret.append(&mut self.deploy_item.to_bytes()?);
+        ret.append(&mut self.reads.to_bytes()?);
+        ret.append(&mut self.absent_keys.to_bytes()?);
+        ret.append(&mut self.effects_hash.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.parent_state_hash.serialized_length()
+            + self.block_time.serialized_length()
+            + self.protocol_version.serialized_length()
+            + self.protocol_data.serialized_length()
+            + self.deploy_item.serialized_length()
+            + self.reads.serialized_length()
+            + self.absent_keys.serialized_length()
+            + self.effects_hash.serialized_length()
+    }
+}
+
+impl FromBytes for ReproBundle {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (parent_state_hash, rem) = Blake2bHash::from_bytes(bytes)?;
+        let (block_time, rem) = BlockTime::from_bytes(rem)?;
+        let (protocol_version, rem) = ProtocolVersion::from_bytes(rem)?;
+        let (protocol_data, rem) = ProtocolData::from_bytes(rem)?;
+        let (deploy_item, rem) = DeployItem::from_bytes(rem)?;
+        let (reads, rem) = Vec::<TrieMerkleProof<Key, StoredValue>>::from_bytes(rem)?;
+        let (absent_keys, rem) = Vec::<Key>::from_bytes(rem)?;
+        let (effects_hash, rem) = Blake2bHash::from_bytes(rem)?;
+        let bundle = ReproBundle {
+            parent_state_hash,
+            block_time,
+            protocol_version,
+            protocol_data,
+            deploy_item,
+            reads,
+            absent_keys,
+            effects_hash,
+        };
+        Ok((bundle, rem))
+    }
+}
+
+#[derive(Debug)]
+pub enum BundleReplayResult {
+    /// The proof of the value read from this key doesn't lead to the parent state hash.
+    InvalidProof(Key),
+    /// The deploy was replayed, but its effects differ from the ones it had when exported.
+    EffectsMismatch {
+        recorded: Blake2bHash,
+        replayed: Blake2bHash,
+    },
+    Replayed(ReplayedDeploy),
+}
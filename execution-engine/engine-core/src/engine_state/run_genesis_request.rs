@@ -13,6 +13,7 @@ pub struct RunGenesisRequest {
     genesis_config_hash: Blake2bHash,
     protocol_version: ProtocolVersion,
     ee_config: ExecConfig,
+    force_fresh_genesis: bool,
 }
 
 impl RunGenesisRequest {
@@ -25,9 +26,17 @@ impl RunGenesisRequest {
             genesis_config_hash,
             protocol_version,
             ee_config,
+            force_fresh_genesis: false,
         }
     }
 
+    /// Runs genesis even if the store was already initialized by a genesis, creating a root
+    /// unrelated to the existing ones.  Only meant for test networks.
+    pub fn with_force_fresh_genesis(mut self, force_fresh_genesis: bool) -> Self {
+        self.force_fresh_genesis = force_fresh_genesis;
+        self
+    }
+
     pub fn genesis_config_hash(&self) -> Blake2bHash {
         self.genesis_config_hash
     }
@@ -40,6 +49,10 @@ impl RunGenesisRequest {
         &self.ee_config
     }
 
+    pub fn force_fresh_genesis(&self) -> bool {
+        self.force_fresh_genesis
+    }
+
     pub fn take_ee_config(self) -> ExecConfig {
         self.ee_config
     }
@@ -59,5 +72,6 @@ impl Distribution<RunGenesisRequest> for Standard {
         let ee_config = rng.gen();
 
         RunGenesisRequest::new(hash_bytes.into(), protocol_version, ee_config)
+            .with_force_fresh_genesis(rng.gen())
     }
 }
@@ -0,0 +1,234 @@
+//! Contract calls scheduled via the `schedule_call` host function are recorded in global state, so
+//! that a later step can make the ones which have become due.
+//!
+//! Each call is stored under the [`Key::Hash`] of its [`ScheduleId`], and the ids of the calls not
+//! yet made are listed, in the order they were scheduled, under [`SCHEDULED_CALLS_KEY`].
+
+use lazy_static::lazy_static;
+
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue, TypeMismatch};
+use engine_storage::global_state::StateReader;
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockTime, CLType, CLTyped, CLValue, Key, ScheduleId, URef,
+};
+
+use crate::{engine_state, execution, tracking_copy::TrackingCopy};
+
+lazy_static! {
+    /// The key under which the ids of the scheduled calls not yet made are stored.
+    pub static ref SCHEDULED_CALLS_KEY: Key = engine_state::engine_record_key(b"scheduled-calls");
+}
+
+/// The motes moved into escrow when a call is scheduled, i.e. the most a scheduled call can cost.
+pub const SCHEDULED_CALL_ESCROW: u64 = 10_000_000;
+
+/// A call to a stored contract, to be made once the block time reaches `not_before`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScheduledCall {
+    contract: Key,
+    args: Vec<u8>,
+    not_before: BlockTime,
+    account: PublicKey,
+    escrow_purse: URef,
+}
+
+impl ScheduledCall {
+    pub fn new(
+        contract: Key,
+        args: Vec<u8>,
+        not_before: BlockTime,
+        account: PublicKey,
+        escrow_purse: URef,
+    ) -> Self {
+        ScheduledCall {
+            contract,
+            args,
+            not_before,
+            account,
+            escrow_purse,
+        }
+    }
+
+    /// The key of the stored contract to call.
+    pub fn contract(&self) -> Key {
+        self.contract
+    }
+
+    /// The serialized arguments to call the contract with.
+    pub fn args(&self) -> &[u8] {
+        &self.args
+    }
+
+    pub fn not_before(&self) -> BlockTime {
+        self.not_before
+    }
+
+    /// The account which scheduled the call, which the call is made on behalf of and which is
+    /// refunded the unspent escrow.
+    pub fn account(&self) -> PublicKey {
+        self.account
+    }
+
+    /// The purse holding the escrow paying for the call.
+    pub fn escrow_purse(&self) -> URef {
+        self.escrow_purse
+    }
+
+    /// Returns `true` if the call is due at `block_time`.
+    pub fn is_due(&self, block_time: BlockTime) -> bool {
+        self.not_before <= block_time
+    }
+}
+
+impl ToBytes for ScheduledCall {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.contract.to_bytes()?);
+        result.append(&mut self.args.to_bytes()?);
+        result.append(&mut self.not_before.to_bytes()?);
+        result.append(&mut self.account.to_bytes()?);
+        result.append(&mut self.escrow_purse.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.contract.serialized_length()
+            + self.args.serialized_length()
+            + self.not_before.serialized_length()
+            + self.account.serialized_length()
+            + self.escrow_purse.serialized_length()
+    }
+}
+
+impl FromBytes for ScheduledCall {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (contract, bytes) = Key::from_bytes(bytes)?;
+        let (args, bytes) = Vec::<u8>::from_bytes(bytes)?;
+        let (not_before, bytes) = BlockTime::from_bytes(bytes)?;
+        let (account, bytes) = PublicKey::from_bytes(bytes)?;
+        let (escrow_purse, bytes) = URef::from_bytes(bytes)?;
+        let scheduled_call = ScheduledCall::new(contract, args, not_before, account, escrow_purse);
+        Ok((scheduled_call, bytes))
+    }
+}
+
+impl CLTyped for ScheduledCall {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// Returns the key under which the call with the given id is stored.
+pub fn scheduled_call_key(schedule_id: ScheduleId) -> Key {
+    Key::Hash(schedule_id.value())
+}
+
+/// Returns the value stored under [`SCHEDULED_CALLS_KEY`] listing `schedule_ids`.
+pub(crate) fn index_value(schedule_ids: Vec<ScheduleId>) -> Result<StoredValue, execution::Error> {
+    let cl_value = CLValue::from_t(schedule_ids)?;
+    Ok(StoredValue::CLValue(cl_value))
+}
+
+/// Returns the value stored under the key of a scheduled call.
+pub(crate) fn scheduled_call_value(
+    scheduled_call: ScheduledCall,
+) -> Result<StoredValue, execution::Error> {
+    let cl_value = CLValue::from_t(scheduled_call)?;
+    Ok(StoredValue::CLValue(cl_value))
+}
+
+/// Returns the ids of the scheduled calls not yet made, in the order they were scheduled.
+pub(crate) fn read_index<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+) -> Result<Vec<ScheduleId>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    match read_cl_value(tracking_copy, correlation_id, &*SCHEDULED_CALLS_KEY)? {
+        Some(cl_value) => Ok(cl_value.into_t()?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Returns the scheduled call with the given id, if any.
+pub(crate) fn read_scheduled_call<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    schedule_id: ScheduleId,
+) -> Result<Option<ScheduledCall>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    let key = scheduled_call_key(schedule_id);
+    match read_cl_value(tracking_copy, correlation_id, &key)? {
+        Some(cl_value) if *cl_value.cl_type() == ScheduledCall::cl_type() => {
+            Ok(Some(cl_value.into_t()?))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn read_cl_value<R>(
+    tracking_copy: &mut TrackingCopy<R>,
+    correlation_id: CorrelationId,
+    key: &Key,
+) -> Result<Option<CLValue>, execution::Error>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    match tracking_copy
+        .read(correlation_id, key)
+        .map_err(Into::into)?
+    {
+        Some(StoredValue::CLValue(cl_value)) => Ok(Some(cl_value)),
+        Some(other) => Err(execution::Error::TypeMismatch(TypeMismatch::new(
+            "CLValue".to_string(),
+            other.type_name(),
+        ))),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::{bytesrepr, AccessRights};
+
+    use super::*;
+
+    #[test]
+    fn scheduled_call_should_round_trip() {
+        let scheduled_call = ScheduledCall::new(
+            Key::Hash([1; 32]),
+            vec![1, 2, 3],
+            BlockTime::from_millis(100),
+            PublicKey::ed25519_from([2; 32]),
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let bytes = scheduled_call.to_bytes().unwrap();
+        assert_eq!(bytes.len(), scheduled_call.serialized_length());
+        assert_eq!(
+            bytesrepr::deserialize::<ScheduledCall>(bytes).unwrap(),
+            scheduled_call
+        );
+    }
+
+    #[test]
+    fn scheduled_call_should_be_due_from_not_before() {
+        let scheduled_call = ScheduledCall::new(
+            Key::Hash([1; 32]),
+            Vec::new(),
+            BlockTime::from_millis(100),
+            PublicKey::ed25519_from([2; 32]),
+            URef::new([3; 32], AccessRights::READ_ADD_WRITE),
+        );
+        assert!(!scheduled_call.is_due(BlockTime::from_millis(99)));
+        assert!(scheduled_call.is_due(BlockTime::from_millis(100)));
+        assert!(scheduled_call.is_due(BlockTime::from_millis(101)));
+    }
+}
@@ -0,0 +1,114 @@
+use std::{collections::BTreeMap, fmt};
+
+use engine_shared::{motes::Motes, newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::global_state::CommitResult;
+use types::{account::PublicKey, bytesrepr, BlockTime, Key, ProtocolVersion, ScheduleId, U512};
+
+use super::{execution_effect::ExecutionEffect, execution_result::ExecutionResult};
+
+/// A request to make the scheduled calls which are due at `block_time`, on top of
+/// `pre_state_hash`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepRequest {
+    pub pre_state_hash: Blake2bHash,
+    pub protocol_version: ProtocolVersion,
+    pub block_time: BlockTime,
+    /// If set, the auction is run after the scheduled calls to select the validators of the era
+    /// ending at this time.
+    pub era_end: Option<BlockTime>,
+}
+
+impl StepRequest {
+    pub fn new(
+        pre_state_hash: Blake2bHash,
+        protocol_version: ProtocolVersion,
+        block_time: BlockTime,
+    ) -> Self {
+        StepRequest {
+            pre_state_hash,
+            protocol_version,
+            block_time,
+            era_end: None,
+        }
+    }
+
+    /// Runs the auction for the era ending at `era_end` as part of the step.
+    pub fn with_era_end(mut self, era_end: BlockTime) -> Self {
+        self.era_end = Some(era_end);
+        self
+    }
+}
+
+/// A scheduled call made by a step.
+#[derive(Debug)]
+pub struct ExecutedCall {
+    pub schedule_id: ScheduleId,
+    /// The result of the call.  The effects of a failed call are discarded, but its cost is still
+    /// paid from the escrow.
+    pub execution_result: ExecutionResult,
+    /// The motes paid from the escrow for the call.
+    pub cost: Motes,
+    /// The motes of the escrow refunded to the account which scheduled the call.
+    pub refund: Motes,
+}
+
+pub enum StepResult {
+    RootNotFound,
+    KeyNotFound(Key),
+    TypeMismatch(TypeMismatch),
+    Serialization(bytesrepr::Error),
+    Success {
+        post_state_hash: Blake2bHash,
+        effect: ExecutionEffect,
+        /// The calls made, in the order they were made.
+        executed_calls: Vec<ExecutedCall>,
+        /// The bonded validators at `post_state_hash`.
+        bonded_validators: BTreeMap<PublicKey, U512>,
+    },
+}
+
+impl fmt::Display for StepResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            StepResult::RootNotFound => write!(f, "Root not found"),
+            StepResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
+            StepResult::TypeMismatch(type_mismatch) => {
+                write!(f, "Type mismatch: {:?}", type_mismatch)
+            }
+            StepResult::Serialization(error) => write!(f, "Serialization error: {:?}", error),
+            StepResult::Success {
+                post_state_hash,
+                executed_calls,
+                ..
+            } => write!(
+                f,
+                "Success: {} ({} calls made)",
+                post_state_hash,
+                executed_calls.len()
+            ),
+        }
+    }
+}
+
+impl StepResult {
+    pub fn from_commit_result(
+        commit_result: CommitResult,
+        effect: ExecutionEffect,
+        executed_calls: Vec<ExecutedCall>,
+        bonded_validators: BTreeMap<PublicKey, U512>,
+    ) -> Self {
+        match commit_result {
+            CommitResult::RootNotFound => StepResult::RootNotFound,
+            CommitResult::KeyNotFound(key) => StepResult::KeyNotFound(key),
+            CommitResult::TypeMismatch(type_mismatch) => StepResult::TypeMismatch(type_mismatch),
+            CommitResult::Serialization(error) => StepResult::Serialization(error),
+            CommitResult::StaleCommit { .. } => unreachable!("step commits are never fenced"),
+            CommitResult::Success { state_root, .. } => StepResult::Success {
+                post_state_hash: state_root,
+                effect,
+                executed_calls,
+                bonded_validators,
+            },
+        }
+    }
+}
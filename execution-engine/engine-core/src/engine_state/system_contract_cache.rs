@@ -1,40 +1,62 @@
 use std::{
     collections::HashMap,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use parity_wasm::elements::Module;
 
-use types::URef;
+use types::{ProtocolVersion, URef};
 
 /// A cache of deserialized contracts.
+///
+/// Modules are keyed by the protocol version they were loaded for as well as by uref, since an
+/// upgrade replaces the code stored under a system contract's uref.  A module loaded for one
+/// protocol version is never used to execute against a state root of another.
 #[derive(Clone, Default, Debug)]
-pub struct SystemContractCache(Arc<RwLock<HashMap<URef, Module>>>);
+pub struct SystemContractCache {
+    modules: Arc<RwLock<HashMap<(URef, ProtocolVersion), Module>>>,
+    insertions: Arc<AtomicUsize>,
+}
 
 impl SystemContractCache {
-    /// Returns `true` if the cache has a contract corresponding to `uref`.
-    pub fn has(&self, uref: &URef) -> bool {
-        let guarded_map = self.0.read().unwrap();
+    /// Returns `true` if the cache has a contract corresponding to `uref` at `protocol_version`.
+    pub fn has(&self, uref: &URef, protocol_version: ProtocolVersion) -> bool {
+        let guarded_map = self.modules.read().unwrap();
         let uref = uref.remove_access_rights();
-        guarded_map.contains_key(&uref)
+        guarded_map.contains_key(&(uref, protocol_version))
     }
 
-    /// Inserts `contract` into the cache under `uref`.
+    /// Inserts `contract` into the cache under `uref` at `protocol_version`.
     ///
     /// If the cache did not have this key present, `None` is returned.
     ///
     /// If the cache did have this key present, the value is updated, and the old value is returned.
-    pub fn insert(&self, uref: URef, module: Module) -> Option<Module> {
-        let mut guarded_map = self.0.write().unwrap();
+    pub fn insert(
+        &self,
+        uref: URef,
+        protocol_version: ProtocolVersion,
+        module: Module,
+    ) -> Option<Module> {
+        let mut guarded_map = self.modules.write().unwrap();
         let uref = uref.remove_access_rights();
-        guarded_map.insert(uref, module)
+        self.insertions.fetch_add(1, Ordering::Relaxed);
+        guarded_map.insert((uref, protocol_version), module)
     }
 
-    /// Returns a clone of the contract corresponding to `uref`.
-    pub fn get(&self, uref: &URef) -> Option<Module> {
-        let guarded_map = self.0.read().unwrap();
+    /// Returns a clone of the contract corresponding to `uref` at `protocol_version`.
+    pub fn get(&self, uref: &URef, protocol_version: ProtocolVersion) -> Option<Module> {
+        let guarded_map = self.modules.read().unwrap();
         let uref = uref.remove_access_rights();
-        guarded_map.get(&uref).cloned()
+        guarded_map.get(&(uref, protocol_version)).cloned()
+    }
+
+    /// Returns the number of modules inserted into the cache, i.e. the number of times a system
+    /// contract had to be loaded from global state.
+    pub fn insertions(&self) -> usize {
+        self.insertions.load(Ordering::Relaxed)
     }
 }
 
@@ -49,7 +71,9 @@ mod tests {
         engine_state::system_contract_cache::SystemContractCache,
         execution::{AddressGenerator, AddressGeneratorBuilder},
     };
-    use types::{AccessRights, URef};
+    use types::{AccessRights, ProtocolVersion, URef};
+
+    const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
     lazy_static! {
         static ref ADDRESS_GENERATOR: Mutex<AddressGenerator> = Mutex::new(
@@ -70,7 +94,7 @@ mod tests {
 
         let cache = SystemContractCache::default();
 
-        let result = cache.insert(reference, module);
+        let result = cache.insert(reference, PROTOCOL_VERSION, module);
 
         assert!(result.is_none())
     }
@@ -84,7 +108,7 @@ mod tests {
             URef::new(address, AccessRights::READ_ADD_WRITE)
         };
 
-        assert!(!cache.has(&reference))
+        assert!(!cache.has(&reference, PROTOCOL_VERSION))
     }
 
     #[test]
@@ -97,9 +121,9 @@ mod tests {
         };
         let module = Module::default();
 
-        cache.insert(reference, module);
+        cache.insert(reference, PROTOCOL_VERSION, module);
 
-        assert!(cache.has(&reference))
+        assert!(cache.has(&reference, PROTOCOL_VERSION))
     }
 
     #[test]
@@ -112,9 +136,12 @@ mod tests {
         };
         let module = Module::default();
 
-        cache.insert(reference, module);
+        cache.insert(reference, PROTOCOL_VERSION, module);
 
-        assert!(cache.has(&reference.with_access_rights(AccessRights::ADD_WRITE)))
+        assert!(cache.has(
+            &reference.with_access_rights(AccessRights::ADD_WRITE),
+            PROTOCOL_VERSION
+        ))
     }
 
     #[test]
@@ -129,10 +156,11 @@ mod tests {
 
         cache.insert(
             reference.with_access_rights(AccessRights::ADD_WRITE),
+            PROTOCOL_VERSION,
             module,
         );
 
-        assert!(cache.has(&reference))
+        assert!(cache.has(&reference, PROTOCOL_VERSION))
     }
 
     #[test]
@@ -144,7 +172,7 @@ mod tests {
         };
         let cache = SystemContractCache::default();
 
-        let result = cache.get(&reference);
+        let result = cache.get(&reference, PROTOCOL_VERSION);
 
         assert!(result.is_none())
     }
@@ -159,9 +187,9 @@ mod tests {
         };
         let module = Module::default();
 
-        cache.insert(reference, module.clone());
+        cache.insert(reference, PROTOCOL_VERSION, module.clone());
 
-        let result = cache.get(&reference);
+        let result = cache.get(&reference, PROTOCOL_VERSION);
 
         assert_eq!(result, Some(module))
     }
@@ -176,13 +204,16 @@ mod tests {
         };
         let module = Module::default();
 
-        cache.insert(reference, module.clone());
+        cache.insert(reference, PROTOCOL_VERSION, module.clone());
 
-        let result = cache.get(&reference.remove_access_rights());
+        let result = cache.get(&reference.remove_access_rights(), PROTOCOL_VERSION);
 
         assert_eq!(result, Some(module.clone()));
 
-        let result = cache.get(&reference.with_access_rights(AccessRights::ADD_WRITE));
+        let result = cache.get(
+            &reference.with_access_rights(AccessRights::ADD_WRITE),
+            PROTOCOL_VERSION,
+        );
 
         assert_eq!(result, Some(module))
     }
@@ -199,14 +230,15 @@ mod tests {
 
         cache.insert(
             reference.with_access_rights(AccessRights::ADD_WRITE),
+            PROTOCOL_VERSION,
             module.clone(),
         );
 
-        let result = cache.get(&reference);
+        let result = cache.get(&reference, PROTOCOL_VERSION);
 
         assert_eq!(result, Some(module.clone()));
 
-        let result = cache.get(&reference.remove_access_rights());
+        let result = cache.get(&reference.remove_access_rights(), PROTOCOL_VERSION);
 
         assert_eq!(result, Some(module))
     }
@@ -228,15 +260,15 @@ mod tests {
 
         assert_ne!(initial_module, updated_module);
 
-        let result = cache.insert(reference, initial_module.clone());
+        let result = cache.insert(reference, PROTOCOL_VERSION, initial_module.clone());
 
         assert!(result.is_none());
 
-        let result = cache.insert(reference, updated_module.clone());
+        let result = cache.insert(reference, PROTOCOL_VERSION, updated_module.clone());
 
         assert_eq!(result, Some(initial_module));
 
-        let result = cache.get(&reference);
+        let result = cache.get(&reference, PROTOCOL_VERSION);
 
         assert_eq!(result, Some(updated_module))
     }
@@ -258,19 +290,78 @@ mod tests {
 
         assert_ne!(initial_module, updated_module);
 
-        let result = cache.insert(reference, initial_module.clone());
+        let result = cache.insert(reference, PROTOCOL_VERSION, initial_module.clone());
 
         assert!(result.is_none());
 
         let result = cache.insert(
             reference.with_access_rights(AccessRights::ADD_WRITE),
+            PROTOCOL_VERSION,
             updated_module.clone(),
         );
 
         assert_eq!(result, Some(initial_module));
 
-        let result = cache.get(&reference);
+        let result = cache.get(&reference, PROTOCOL_VERSION);
 
         assert_eq!(result, Some(updated_module))
     }
+
+    #[test]
+    pub fn should_keep_modules_of_protocol_versions_apart() {
+        let cache = SystemContractCache::default();
+        let reference = {
+            let mut address_generator = ADDRESS_GENERATOR.lock().unwrap();
+            let address = address_generator.create_address();
+            URef::new(address, AccessRights::READ_ADD_WRITE)
+        };
+        let initial_module = Module::default();
+        let upgraded_module = {
+            let section = NameSection::new(Some(ModuleNameSubsection::new("a_mod")), None, None);
+            let sections = vec![Section::Name(section)];
+            Module::new(sections)
+        };
+        let upgraded_protocol_version = ProtocolVersion::from_parts(2, 0, 0);
+
+        cache.insert(reference, PROTOCOL_VERSION, initial_module.clone());
+        let result = cache.insert(
+            reference,
+            upgraded_protocol_version,
+            upgraded_module.clone(),
+        );
+
+        assert!(result.is_none());
+        assert_eq!(
+            cache.get(&reference, PROTOCOL_VERSION),
+            Some(initial_module)
+        );
+        assert_eq!(
+            cache.get(&reference, upgraded_protocol_version),
+            Some(upgraded_module)
+        );
+        assert!(!cache.has(&reference, ProtocolVersion::from_parts(3, 0, 0)));
+    }
+
+    #[test]
+    pub fn should_count_insertions() {
+        let cache = SystemContractCache::default();
+        let reference = {
+            let mut address_generator = ADDRESS_GENERATOR.lock().unwrap();
+            let address = address_generator.create_address();
+            URef::new(address, AccessRights::READ_ADD_WRITE)
+        };
+
+        assert_eq!(cache.insertions(), 0);
+
+        cache.insert(reference, PROTOCOL_VERSION, Module::default());
+        let _ = cache.get(&reference, PROTOCOL_VERSION);
+        let _ = cache.has(&reference, PROTOCOL_VERSION);
+
+        assert_eq!(cache.insertions(), 1);
+
+        let cloned_cache = cache.clone();
+        cloned_cache.insert(reference, PROTOCOL_VERSION, Module::default());
+
+        assert_eq!(cache.insertions(), 2)
+    }
 }
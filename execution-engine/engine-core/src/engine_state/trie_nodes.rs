@@ -0,0 +1,28 @@
+use engine_shared::newtypes::Blake2bHash;
+
+/// The most trie nodes a single request may ask for.
+pub const MAX_TRIE_NODES_PER_REQUEST: usize = 256;
+
+/// A request for trie nodes by their hashes.
+///
+/// Nodes are read directly from the trie store rather than through a checked out state, so any
+/// node reachable from a retained root is found, whichever state it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieNodesRequest {
+    hashes: Vec<Blake2bHash>,
+}
+
+impl TrieNodesRequest {
+    /// Creates a request for the nodes under `hashes`, or returns `None` if there are more than
+    /// [`MAX_TRIE_NODES_PER_REQUEST`] of them.
+    pub fn new(hashes: Vec<Blake2bHash>) -> Option<Self> {
+        if hashes.len() > MAX_TRIE_NODES_PER_REQUEST {
+            return None;
+        }
+        Some(TrieNodesRequest { hashes })
+    }
+
+    pub fn hashes(&self) -> &[Blake2bHash] {
+        &self.hashes
+    }
+}
@@ -1,7 +1,7 @@
 use std::fmt;
 
-use engine_shared::{newtypes::Blake2bHash, TypeMismatch};
-use engine_storage::global_state::CommitResult;
+use engine_shared::{account::AccountConfig, newtypes::Blake2bHash, TypeMismatch};
+use engine_storage::{global_state::CommitResult, protocol_data::ProtocolData};
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use types::{bytesrepr, Key, ProtocolVersion};
 
@@ -17,6 +17,7 @@ pub enum UpgradeResult {
     Success {
         post_state_hash: Blake2bHash,
         effect: ExecutionEffect,
+        changes: ConfigDiff,
     },
 }
 
@@ -32,26 +33,253 @@ impl fmt::Display for UpgradeResult {
             UpgradeResult::Success {
                 post_state_hash,
                 effect,
-            } => write!(f, "Success: {} {:?}", post_state_hash, effect),
+                changes,
+            } => write!(
+                f,
+                "Success: {} {:?} changes: {}",
+                post_state_hash, effect, changes
+            ),
         }
     }
 }
 
 impl UpgradeResult {
-    pub fn from_commit_result(commit_result: CommitResult, effect: ExecutionEffect) -> Self {
+    pub fn from_commit_result(
+        commit_result: CommitResult,
+        effect: ExecutionEffect,
+        changes: ConfigDiff,
+    ) -> Self {
         match commit_result {
             CommitResult::RootNotFound => UpgradeResult::RootNotFound,
             CommitResult::KeyNotFound(key) => UpgradeResult::KeyNotFound(key),
             CommitResult::TypeMismatch(type_mismatch) => UpgradeResult::TypeMismatch(type_mismatch),
             CommitResult::Serialization(error) => UpgradeResult::Serialization(error),
+            CommitResult::StaleCommit { .. } => {
+                unreachable!("upgrade commits are never fenced")
+            }
             CommitResult::Success { state_root, .. } => UpgradeResult::Success {
                 post_state_hash: state_root,
                 effect,
+                changes,
             },
         }
     }
 }
 
+/// The hashes of the code of the system contracts an upgrade can replace or install.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SystemContractCodeHashes {
+    pub mint: Blake2bHash,
+    pub proof_of_stake: Blake2bHash,
+    pub auction: Option<Blake2bHash>,
+}
+
+/// A protocol parameter changed by an upgrade, with its value before and after the upgrade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParameterChange {
+    pub name: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+impl fmt::Display for ParameterChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {} -> {}", self.name, self.old_value, self.new_value)
+    }
+}
+
+/// The protocol parameters changed by an upgrade.
+///
+/// The old values are those stored for the current protocol version at the pre-state, not those
+/// of the upgrade request which introduced it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    changes: Vec<ParameterChange>,
+}
+
+impl ConfigDiff {
+    /// Lists the parameters whose values differ between the old and the new protocol data and
+    /// system contract code, in a fixed order.
+    pub(crate) fn new(
+        old_protocol_data: &ProtocolData,
+        old_code_hashes: &SystemContractCodeHashes,
+        new_protocol_data: &ProtocolData,
+        new_code_hashes: &SystemContractCodeHashes,
+    ) -> Self {
+        let old_parameters = parameters(old_protocol_data, old_code_hashes);
+        let new_parameters = parameters(new_protocol_data, new_code_hashes);
+        let changes = old_parameters
+            .into_iter()
+            .zip(new_parameters)
+            .filter(|((_, old_value), (_, new_value))| old_value != new_value)
+            .map(|((name, old_value), (_, new_value))| ParameterChange {
+                name: name.to_string(),
+                old_value,
+                new_value,
+            })
+            .collect();
+        ConfigDiff { changes }
+    }
+
+    pub fn changes(&self) -> &[ParameterChange] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.changes.is_empty() {
+            return write!(f, "no changes");
+        }
+        for (index, change) in self.changes.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name and rendered value of each parameter covered by a [`ConfigDiff`], always in
+/// the same order.
+fn parameters(
+    protocol_data: &ProtocolData,
+    code_hashes: &SystemContractCodeHashes,
+) -> Vec<(&'static str, String)> {
+    fn optional<T: ToString>(value: Option<T>) -> String {
+        value.map_or_else(|| String::from("none"), |value| value.to_string())
+    }
+
+    let wasm_costs = protocol_data.wasm_costs();
+    let account_config = protocol_data.account_config();
+    vec![
+        ("wasm_costs.regular", wasm_costs.regular.to_string()),
+        ("wasm_costs.div", wasm_costs.div.to_string()),
+        ("wasm_costs.mul", wasm_costs.mul.to_string()),
+        ("wasm_costs.mem", wasm_costs.mem.to_string()),
+        ("wasm_costs.initial_mem", wasm_costs.initial_mem.to_string()),
+        ("wasm_costs.grow_mem", wasm_costs.grow_mem.to_string()),
+        ("wasm_costs.memcpy", wasm_costs.memcpy.to_string()),
+        (
+            "wasm_costs.max_stack_height",
+            wasm_costs.max_stack_height.to_string(),
+        ),
+        ("wasm_costs.opcodes_mul", wasm_costs.opcodes_mul.to_string()),
+        ("wasm_costs.opcodes_div", wasm_costs.opcodes_div.to_string()),
+        (
+            "wasm_costs.transient_storage",
+            wasm_costs.transient_storage.to_string(),
+        ),
+        (
+            "wasm_costs.max_transient_storage_size",
+            wasm_costs.max_transient_storage_size.to_string(),
+        ),
+        (
+            "wasm_costs.max_transforms_per_deploy",
+            wasm_costs.max_transforms_per_deploy.to_string(),
+        ),
+        (
+            "wasm_costs.max_effect_bytes_per_deploy",
+            wasm_costs.max_effect_bytes_per_deploy.to_string(),
+        ),
+        (
+            "wasm_costs.max_session_return_value_bytes",
+            wasm_costs.max_session_return_value_bytes.to_string(),
+        ),
+        ("wasm_costs.string_byte", wasm_costs.string_byte.to_string()),
+        (
+            "wasm_costs.max_string_length",
+            wasm_costs.max_string_length.to_string(),
+        ),
+        (
+            "wasm_costs.max_revert_data_bytes",
+            wasm_costs.max_revert_data_bytes.to_string(),
+        ),
+        (
+            "wasm_costs.base_deploy_cost",
+            wasm_costs.base_deploy_cost.to_string(),
+        ),
+        (
+            "wasm_costs.rejected_deploy_cost",
+            wasm_costs.rejected_deploy_cost.to_string(),
+        ),
+        (
+            "wasm_costs.strict_session_exports",
+            wasm_costs.strict_session_exports.to_string(),
+        ),
+        (
+            "account_config.default_deployment_threshold",
+            account_config
+                .default_deployment_threshold()
+                .value()
+                .to_string(),
+        ),
+        (
+            "account_config.default_key_management_threshold",
+            account_config
+                .default_key_management_threshold()
+                .value()
+                .to_string(),
+        ),
+        (
+            "account_config.default_identity_key_weight",
+            account_config
+                .default_identity_key_weight()
+                .value()
+                .to_string(),
+        ),
+        ("auction", optional(protocol_data.auction())),
+        ("use_auction", protocol_data.use_auction().to_string()),
+        ("mint_code_hash", code_hashes.mint.to_string()),
+        (
+            "proof_of_stake_code_hash",
+            code_hashes.proof_of_stake.to_string(),
+        ),
+        ("auction_code_hash", optional(code_hashes.auction)),
+    ]
+}
+
+/// Configuration of the auction contract as of an upgrade.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuctionConfig {
+    installer_bytes: Option<Vec<u8>>,
+    validator_slots: u32,
+    use_auction: bool,
+}
+
+impl AuctionConfig {
+    pub fn new(installer_bytes: Option<Vec<u8>>, validator_slots: u32, use_auction: bool) -> Self {
+        AuctionConfig {
+            installer_bytes,
+            validator_slots,
+            use_auction,
+        }
+    }
+
+    /// Bytes of an installer of the auction contract.  An auction contract can only be installed
+    /// once, so this must only be set by the upgrade introducing it.
+    pub fn installer_bytes(&self) -> Option<&[u8]> {
+        let bytes = self.installer_bytes.as_ref()?;
+        Some(bytes.as_slice())
+    }
+
+    /// The number of validators each auction selects, passed to the installer.
+    pub fn validator_slots(&self) -> u32 {
+        self.validator_slots
+    }
+
+    /// Whether the bonded validators are those selected by the auction rather than those staked
+    /// in the PoS contract, as of the new protocol version.
+    pub fn use_auction(&self) -> bool {
+        self.use_auction
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UpgradeConfig {
     pre_state_hash: Blake2bHash,
@@ -61,9 +289,14 @@ pub struct UpgradeConfig {
     upgrade_installer_bytes: Option<Vec<u8>>,
     wasm_costs: Option<WasmCosts>,
     activation_point: Option<ActivationPoint>,
+    new_mint_code: Option<Vec<u8>>,
+    new_pos_code: Option<Vec<u8>>,
+    account_config: Option<AccountConfig>,
+    auction_config: Option<AuctionConfig>,
 }
 
 impl UpgradeConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pre_state_hash: Blake2bHash,
         current_protocol_version: ProtocolVersion,
@@ -72,6 +305,10 @@ impl UpgradeConfig {
         upgrade_installer_bytes: Option<Vec<u8>>,
         wasm_costs: Option<WasmCosts>,
         activation_point: Option<ActivationPoint>,
+        new_mint_code: Option<Vec<u8>>,
+        new_pos_code: Option<Vec<u8>>,
+        account_config: Option<AccountConfig>,
+        auction_config: Option<AuctionConfig>,
     ) -> Self {
         UpgradeConfig {
             pre_state_hash,
@@ -81,6 +318,10 @@ impl UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            new_mint_code,
+            new_pos_code,
+            account_config,
+            auction_config,
         }
     }
 
@@ -113,4 +354,30 @@ impl UpgradeConfig {
     pub fn activation_point(&self) -> Option<u64> {
         self.activation_point
     }
+
+    /// Bytes of an installer for a new version of the mint, which replaces the code of the existing
+    /// mint contract.
+    pub fn new_mint_code(&self) -> Option<&[u8]> {
+        let bytes = self.new_mint_code.as_ref()?;
+        Some(bytes.as_slice())
+    }
+
+    /// Bytes of an installer for a new version of the Proof of Stake contract, which replaces the
+    /// code of the existing Proof of Stake contract.
+    pub fn new_pos_code(&self) -> Option<&[u8]> {
+        let bytes = self.new_pos_code.as_ref()?;
+        Some(bytes.as_slice())
+    }
+
+    /// Configuration of the accounts created by transfers as of the new protocol version.  If not
+    /// set, the configuration of the current protocol version is kept.
+    pub fn account_config(&self) -> Option<AccountConfig> {
+        self.account_config
+    }
+
+    /// Configuration of the auction contract as of the new protocol version.  If not set, the
+    /// auction contract, if any, and whether it is used are kept.
+    pub fn auction_config(&self) -> Option<&AuctionConfig> {
+        self.auction_config.as_ref()
+    }
 }
@@ -0,0 +1,45 @@
+use types::ProtocolVersion;
+
+use super::{deploy_item::DeployItem, error::Error, exit_code::ExitCode};
+
+/// A request to check a deploy can be executed under `protocol_version` before it's included in a
+/// block.  No global state is read, so the checks which depend on the deploy's account are left to
+/// execution.
+pub struct ValidateDeployRequest {
+    pub deploy_item: DeployItem,
+    pub protocol_version: ProtocolVersion,
+}
+
+impl ValidateDeployRequest {
+    pub fn new(deploy_item: DeployItem, protocol_version: ProtocolVersion) -> Self {
+        ValidateDeployRequest {
+            deploy_item,
+            protocol_version,
+        }
+    }
+}
+
+/// The verdict on a validated deploy.
+#[derive(Debug)]
+pub enum ValidateDeployResult {
+    Valid,
+    /// The deploy would fail with the given error when executed.
+    Invalid(Error),
+}
+
+impl ValidateDeployResult {
+    pub fn is_valid(&self) -> bool {
+        match self {
+            ValidateDeployResult::Valid => true,
+            ValidateDeployResult::Invalid(_) => false,
+        }
+    }
+
+    /// Returns the classification of the verdict, as would be given by executing the deploy.
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            ValidateDeployResult::Valid => ExitCode::Success,
+            ValidateDeployResult::Invalid(error) => error.into(),
+        }
+    }
+}
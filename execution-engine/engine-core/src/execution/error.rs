@@ -4,7 +4,7 @@ use parity_wasm::elements;
 use engine_shared::TypeMismatch;
 use types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
-    bytesrepr, system_contract_errors, AccessRights, ApiError, CLValueError, Key, URef,
+    bytesrepr, system_contract_errors, AccessRights, ApiError, CLType, CLValueError, Key, URef,
 };
 
 use crate::resolvers::error::ResolverError;
@@ -44,6 +44,10 @@ pub enum Error {
     /// Reverts execution with a provided status
     #[fail(display = "{}", _0)]
     Revert(ApiError),
+    /// Reverts execution with a provided status and data describing why, e.g. the condition
+    /// which failed
+    #[fail(display = "{}", _0)]
+    RevertWithData(ApiError, Vec<u8>),
     #[fail(display = "{}", _0)]
     AddKeyFailure(AddKeyFailure),
     #[fail(display = "{}", _0)]
@@ -73,6 +77,50 @@ pub enum Error {
     HostBufferEmpty,
     #[fail(display = "Unsupported WASM start")]
     UnsupportedWasmStart,
+    #[fail(display = "Attempted to modify global state from a read-only context")]
+    ReadOnlyContext,
+    #[fail(display = "Host function {} is not available in a system context", _0)]
+    ForbiddenInSystemContext(String),
+    #[fail(
+        display = "Transient storage exceeded its max size of {} bytes",
+        max_size
+    )]
+    TransientStorageExceeded { max_size: u32 },
+    #[fail(display = "Effects exceed the max transform count or size of a deploy")]
+    EffectsTooLarge,
+    #[fail(display = "A value is already stored under the contract hash {:?}", _0)]
+    ContractHashCollision([u8; 32]),
+    #[fail(
+        display = "Argument {} has type {:?} but {:?} was expected",
+        index, found, expected
+    )]
+    ArgumentTypeMismatch {
+        index: usize,
+        expected: CLType,
+        found: CLType,
+    },
+    #[fail(display = "Named key name {} is reserved for the system", _0)]
+    ReservedKeyName(String),
+    #[fail(display = "Function declared pure at {} modified global state", _0)]
+    ImpureDeclaredPure(Key),
+    #[fail(
+        display = "Session return value of {} bytes exceeds the max size of {} bytes",
+        size, max_size
+    )]
+    ReturnValueTooLarge { size: usize, max_size: u32 },
+    #[fail(display = "String passed to a host function is not valid UTF-8")]
+    InvalidUtf8,
+    #[fail(
+        display = "String of {} bytes passed to a host function exceeds the max length of {} bytes",
+        length, max_length
+    )]
+    StringTooLong { length: usize, max_length: u32 },
+    #[fail(
+        display = "Argument exceeds the max nesting depth of {} or the max of {} elements scanned \
+                   for urefs",
+        max_depth, max_elements
+    )]
+    ArgumentTooComplex { max_depth: usize, max_elements: u32 },
 }
 
 impl wasmi::HostError for Error {}
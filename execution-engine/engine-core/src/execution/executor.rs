@@ -2,6 +2,7 @@ use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
     rc::Rc,
+    time::Duration,
 };
 
 use parity_wasm::elements::Module;
@@ -19,9 +20,13 @@ use types::{
 
 use crate::{
     engine_state::{
-        execution_result::ExecutionResult, system_contract_cache::SystemContractCache, EngineConfig,
+        execution_result::ExecutionResult, execution_stats::PhaseTimes,
+        system_contract_cache::SystemContractCache, EngineConfig,
+    },
+    execution::{
+        address_generator::AddressGenerator, Error, ExecutionContextKind, HostCallStats,
+        PureCallCache, SystemContext, FN_STORE_ID_INITIAL,
     },
-    execution::{address_generator::AddressGenerator, Error, FN_STORE_ID_INITIAL},
     runtime::{extract_access_rights_from_keys, instance_and_memory, Runtime},
     runtime_context::{self, RuntimeContext},
     tracking_copy::TrackingCopy,
@@ -70,22 +75,200 @@ macro_rules! on_fail_charge {
 
 pub struct Executor {
     config: EngineConfig,
+    /// The host function calls made by the executions run so far, shared with their contexts.
+    host_call_stats: Rc<RefCell<HostCallStats>>,
+    /// The results of the calls to stored functions declared pure made by the executions run so
+    /// far, shared with their contexts.
+    pure_call_cache: Rc<RefCell<PureCallCache>>,
+    /// The wall time spent in each phase of the deploys executed so far.
+    phase_times: RefCell<PhaseTimes>,
+}
+
+/// Returns `true` if `error` is the trap raised by a call to `ret`.
+fn is_ret(error: &wasmi::Error) -> bool {
+    match error
+        .as_host_error()
+        .and_then(|host_error| host_error.downcast_ref::<Error>())
+    {
+        Some(Error::Ret(_)) => true,
+        _ => false,
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
 impl Executor {
     pub fn new(config: EngineConfig) -> Self {
-        Executor { config }
+        Executor {
+            config,
+            host_call_stats: Default::default(),
+            pure_call_cache: Default::default(),
+            phase_times: Default::default(),
+        }
     }
 
     pub fn config(&self) -> EngineConfig {
         self.config
     }
 
+    /// Returns the host function calls made by the executions run since the last call, and resets
+    /// the tally.
+    pub fn take_host_call_stats(&self) -> HostCallStats {
+        self.host_call_stats.replace(HostCallStats::new())
+    }
+
+    /// Adds `duration` to the time spent in `phase`.
+    pub(crate) fn record_phase_time(&self, phase: Phase, duration: Duration) {
+        self.phase_times.borrow_mut().record(phase, duration);
+    }
+
+    /// Returns the time spent in each phase of the deploys executed since the last call, leaving
+    /// the rest of `elapsed`, the time their execution took as a whole, to preprocessing.  Resets
+    /// the times.
+    pub fn take_phase_times(&self, elapsed: Duration) -> PhaseTimes {
+        let mut phase_times = self.phase_times.replace(PhaseTimes::default());
+        phase_times.preprocess += elapsed.checked_sub(phase_times.total()).unwrap_or_default();
+        phase_times
+    }
+
     pub fn exec<R>(
         &self,
         parity_module: Module,
         args: Vec<u8>,
+        arg_names: Vec<String>,
+        base_key: Key,
+        account: &Account,
+        authorized_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tc: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        self.exec_with_named_keys(
+            parity_module,
+            args,
+            arg_names,
+            account.named_keys().clone(),
+            base_key,
+            account,
+            authorized_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tc,
+            phase,
+            protocol_data,
+            system_contract_cache,
+            false,
+        )
+    }
+
+    /// Executes the stored contract under `contract_key` in its own context, i.e. with its
+    /// `named_keys` rather than those of `account`, as if it was called via `call_contract`.
+    pub fn exec_contract<R>(
+        &self,
+        parity_module: Module,
+        args: Vec<u8>,
+        arg_names: Vec<String>,
+        named_keys: BTreeMap<String, Key>,
+        contract_key: Key,
+        account: &Account,
+        authorized_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tc: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        self.exec_with_named_keys(
+            parity_module,
+            args,
+            arg_names,
+            named_keys,
+            contract_key,
+            account,
+            authorized_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tc,
+            phase,
+            protocol_data,
+            system_contract_cache,
+            false,
+        )
+    }
+
+    /// Executes the authorization contract under `contract_key` of `account` in a read-only
+    /// context of its own, as a [`Phase::Authorization`] check of whether the deploy may proceed.
+    pub fn exec_authorization_contract<R>(
+        &self,
+        parity_module: Module,
+        args: Vec<u8>,
+        named_keys: BTreeMap<String, Key>,
+        contract_key: Key,
+        account: &Account,
+        authorized_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        tc: Rc<RefCell<TrackingCopy<R>>>,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> ExecutionResult
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        self.exec_with_named_keys(
+            parity_module,
+            args,
+            Vec::new(),
+            named_keys,
+            contract_key,
+            account,
+            authorized_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            protocol_version,
+            correlation_id,
+            tc,
+            Phase::Authorization,
+            protocol_data,
+            system_contract_cache,
+            true,
+        )
+    }
+
+    fn exec_with_named_keys<R>(
+        &self,
+        parity_module: Module,
+        args: Vec<u8>,
+        arg_names: Vec<String>,
+        mut named_keys: BTreeMap<String, Key>,
         base_key: Key,
         account: &Account,
         authorized_keys: BTreeSet<PublicKey>,
@@ -98,6 +281,7 @@ impl Executor {
         phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
+        read_only: bool,
     ) -> ExecutionResult
     where
         R: StateReader<Key, StoredValue>,
@@ -106,8 +290,6 @@ impl Executor {
         let (instance, memory) =
             on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
 
-        let mut named_keys = account.named_keys().clone();
-
         let access_rights =
             {
                 let mut keys: Vec<Key> = named_keys.values().cloned().collect();
@@ -133,7 +315,7 @@ impl Executor {
             on_fail_charge!(bytesrepr::deserialize(args), gas, effects_snapshot)
         };
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             tc,
             &mut named_keys,
             access_rights,
@@ -152,6 +334,17 @@ impl Executor {
             phase,
             protocol_data,
         );
+        context.set_read_only(read_only);
+        context.set_host_call_stats(Rc::clone(&self.host_call_stats));
+        context.set_pure_call_cache(Rc::clone(&self.pure_call_cache));
+        context.set_arg_names(
+            arg_names
+                .into_iter()
+                .enumerate()
+                .filter(|(_, name)| !name.is_empty())
+                .map(|(index, name)| (name, index))
+                .collect(),
+        );
 
         let mut runtime = Runtime::new(
             self.config,
@@ -173,6 +366,7 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            return_value: None,
                         }
                     }
                     Err(error) => {
@@ -194,6 +388,7 @@ impl Executor {
                         return ExecutionResult::Success {
                             effect: runtime.context().effect(),
                             cost: runtime.context().gas_counter(),
+                            return_value: None,
                         }
                     }
                     Err(error) => {
@@ -207,18 +402,38 @@ impl Executor {
             }
         }
 
-        on_fail_charge!(
-            instance.invoke_export("call", &[], &mut runtime),
-            runtime.context().gas_counter(),
-            effects_snapshot
-        );
+        // Session code may return a value for the deploy result via `ret`, which is an error in
+        // any other phase
+        let return_value = match instance.invoke_export("call", &[], &mut runtime) {
+            Err(ref error) if phase == Phase::Session && is_ret(error) => {
+                runtime.take_host_buffer()
+            }
+            result => {
+                on_fail_charge!(result, runtime.context().gas_counter(), effects_snapshot);
+                None
+            }
+        };
+
+        if let Some(return_value) = &return_value {
+            let max_size = protocol_data.wasm_costs().max_session_return_value_bytes;
+            let size = return_value.serialized_length();
+            if max_size != 0 && size > max_size as usize {
+                return ExecutionResult::Failure {
+                    error: Error::ReturnValueTooLarge { size, max_size }.into(),
+                    effect: effects_snapshot,
+                    cost: runtime.context().gas_counter(),
+                };
+            }
+        }
 
         ExecutionResult::Success {
             effect: runtime.context().effect(),
             cost: runtime.context().gas_counter(),
+            return_value,
         }
     }
 
+    /// Executes the proof of stake contract to finalize a deploy's payment, in `system_context`.
     pub fn exec_finalize<R>(
         &self,
         parity_module: Module,
@@ -226,14 +441,10 @@ impl Executor {
         named_keys: &mut BTreeMap<String, Key>,
         base_key: Key,
         account: &Account,
-        authorization_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
-        deploy_hash: [u8; 32],
-        gas_limit: Gas,
+        system_context: &SystemContext,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         state: Rc<RefCell<TrackingCopy<R>>>,
-        phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
     ) -> ExecutionResult
@@ -256,7 +467,8 @@ impl Executor {
             };
 
         let address_generator = {
-            let address_generator = AddressGenerator::new(&deploy_hash, phase);
+            let address_generator =
+                AddressGenerator::new(&system_context.deploy_hash(), system_context.phase());
             Rc::new(RefCell::new(address_generator))
         };
         let gas_counter = Gas::default(); // maybe const?
@@ -272,25 +484,29 @@ impl Executor {
             on_fail_charge!(bytesrepr::deserialize(args), gas, effects_snapshot)
         };
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             state,
             &mut named_keys,
             access_rights,
             args.clone(),
-            authorization_keys,
+            system_context.authorization_keys().clone(),
             &account,
             base_key,
-            blocktime,
-            deploy_hash,
-            gas_limit,
+            system_context.blocktime(),
+            system_context.deploy_hash(),
+            system_context.gas_limit(),
             gas_counter,
             FN_STORE_ID_INITIAL,
             address_generator,
             protocol_version,
             correlation_id,
-            phase,
+            system_context.phase(),
             protocol_data,
         );
+        context.set_kind(ExecutionContextKind::System);
+        context.set_args_supplied_by_engine(true);
+        context.set_host_call_stats(Rc::clone(&self.host_call_stats));
+        context.set_pure_call_cache(Rc::clone(&self.pure_call_cache));
 
         let (instance, memory) =
             on_fail_charge!(instance_and_memory(parity_module.clone(), protocol_version));
@@ -314,6 +530,7 @@ impl Executor {
                     return ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        return_value: None,
                     }
                 }
                 Err(error) => {
@@ -332,6 +549,7 @@ impl Executor {
                 return ExecutionResult::Success {
                     effect: runtime.context().effect(),
                     cost: runtime.context().gas_counter(),
+                    return_value: None,
                 }
             }
         };
@@ -343,6 +561,7 @@ impl Executor {
                     return ExecutionResult::Success {
                         effect: runtime.context().effect(),
                         cost: runtime.context().gas_counter(),
+                        return_value: None,
                     };
                 }
                 Error::Revert(status) => {
@@ -388,6 +607,51 @@ impl Executor {
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
     ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        self.create_runtime_of_kind(
+            module,
+            args,
+            keys,
+            base_key,
+            account,
+            authorization_keys,
+            blocktime,
+            deploy_hash,
+            gas_limit,
+            address_generator,
+            protocol_version,
+            correlation_id,
+            state,
+            phase,
+            protocol_data,
+            system_contract_cache,
+            ExecutionContextKind::User,
+        )
+    }
+
+    fn create_runtime_of_kind<'a, R>(
+        &self,
+        module: Module,
+        args: Vec<u8>,
+        keys: &'a mut BTreeMap<String, Key>,
+        base_key: Key,
+        account: &'a Account,
+        authorization_keys: BTreeSet<PublicKey>,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        gas_limit: Gas,
+        address_generator: Rc<RefCell<AddressGenerator>>,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        state: Rc<RefCell<TrackingCopy<R>>>,
+        phase: Phase,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+        kind: ExecutionContextKind,
+    ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
     where
         R: StateReader<Key, StoredValue>,
         R::Error: Into<Error>,
@@ -409,7 +673,7 @@ impl Executor {
 
         let gas_counter = Gas::default();
 
-        let runtime_context = RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             state,
             keys,
             access_rights,
@@ -428,6 +692,10 @@ impl Executor {
             phase,
             protocol_data,
         );
+        runtime_context.set_kind(kind);
+        runtime_context.set_args_supplied_by_engine(kind == ExecutionContextKind::System);
+        runtime_context.set_host_call_stats(Rc::clone(&self.host_call_stats));
+        runtime_context.set_pure_call_cache(Rc::clone(&self.pure_call_cache));
 
         let (instance, memory) = instance_and_memory(module.clone(), protocol_version)?;
 
@@ -442,6 +710,48 @@ impl Executor {
         Ok((instance, runtime))
     }
 
+    /// Creates a runtime executing `module` in `system_context`.
+    pub fn create_system_runtime<'a, R>(
+        &self,
+        module: Module,
+        args: Vec<u8>,
+        keys: &'a mut BTreeMap<String, Key>,
+        base_key: Key,
+        account: &'a Account,
+        system_context: &SystemContext,
+        address_generator: Rc<RefCell<AddressGenerator>>,
+        protocol_version: ProtocolVersion,
+        correlation_id: CorrelationId,
+        state: Rc<RefCell<TrackingCopy<R>>>,
+        protocol_data: ProtocolData,
+        system_contract_cache: SystemContractCache,
+    ) -> Result<(ModuleRef, Runtime<'a, R>), Error>
+    where
+        R: StateReader<Key, StoredValue>,
+        R::Error: Into<Error>,
+    {
+        self.create_runtime_of_kind(
+            module,
+            args,
+            keys,
+            base_key,
+            account,
+            system_context.authorization_keys().clone(),
+            system_context.blocktime(),
+            system_context.deploy_hash(),
+            system_context.gas_limit(),
+            address_generator,
+            protocol_version,
+            correlation_id,
+            state,
+            system_context.phase(),
+            protocol_data,
+            system_contract_cache,
+            ExecutionContextKind::System,
+        )
+    }
+
+    /// Executes `module` in `system_context`, returning the value it returns.
     pub fn exec_system<R, T>(
         &self,
         module: Module,
@@ -449,15 +759,11 @@ impl Executor {
         keys: &mut BTreeMap<String, Key>,
         base_key: Key,
         account: &Account,
-        authorization_keys: BTreeSet<PublicKey>,
-        blocktime: BlockTime,
-        deploy_hash: [u8; 32],
-        gas_limit: Gas,
+        system_context: &SystemContext,
         address_generator: Rc<RefCell<AddressGenerator>>,
         protocol_version: ProtocolVersion,
         correlation_id: CorrelationId,
         state: Rc<RefCell<TrackingCopy<R>>>,
-        phase: Phase,
         protocol_data: ProtocolData,
         system_contract_cache: SystemContractCache,
     ) -> Result<T, Error>
@@ -466,21 +772,17 @@ impl Executor {
         R::Error: Into<Error>,
         T: FromBytes + CLTyped,
     {
-        let (instance, mut runtime) = self.create_runtime(
+        let (instance, mut runtime) = self.create_system_runtime(
             module,
             args,
             keys,
             base_key,
             account,
-            authorization_keys,
-            blocktime,
-            deploy_hash,
-            gas_limit,
+            system_context,
             address_generator,
             protocol_version,
             correlation_id,
             state,
-            phase,
             protocol_data,
             system_contract_cache,
         )?;
@@ -0,0 +1,144 @@
+use std::convert::TryFrom;
+
+use engine_shared::{gas::Gas, logging::log_metric, newtypes::CorrelationId};
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+const METRIC_HOST_FUNCTION_CALLS: &str = "host_function_calls";
+const METRIC_HOST_FUNCTION_GAS: &str = "host_function_gas";
+const TOTAL_METRIC_KEY: &str = "total";
+
+/// The number of calls made to a single host function, and the gas attributed to them.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct HostCallStat {
+    pub calls: u64,
+    /// Gas used while the calls were running, less the gas attributed to the host functions they
+    /// called in turn, e.g. from within a contract run by `call_contract`.
+    pub gas: Gas,
+}
+
+/// Host function calls made during execution, tallied per host function.
+///
+/// These are only ever reported, never written to global state, so they don't affect consensus.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct HostCallStats {
+    /// Indexed by [`FunctionIndex`].
+    stats: Vec<HostCallStat>,
+    total_gas: Gas,
+}
+
+impl HostCallStats {
+    pub fn new() -> Self {
+        HostCallStats::default()
+    }
+
+    /// Records a call to `function_index` which used `gas`.  Calls to the `gas` function aren't
+    /// recorded.
+    pub(crate) fn record(&mut self, function_index: FunctionIndex, gas: Gas) {
+        if function_index.host_function_name().is_none() {
+            return;
+        }
+        let index: usize = function_index.into();
+        if self.stats.len() <= index {
+            self.stats.resize(index + 1, HostCallStat::default());
+        }
+        let stat = &mut self.stats[index];
+        stat.calls += 1;
+        stat.gas = stat.gas + gas;
+        self.total_gas = self.total_gas + gas;
+    }
+
+    /// Adds the calls tallied in `other` to these.
+    pub fn merge(&mut self, other: &HostCallStats) {
+        if self.stats.len() < other.stats.len() {
+            self.stats
+                .resize(other.stats.len(), HostCallStat::default());
+        }
+        for (stat, other_stat) in self.stats.iter_mut().zip(&other.stats) {
+            stat.calls += other_stat.calls;
+            stat.gas = stat.gas + other_stat.gas;
+        }
+        self.total_gas = self.total_gas + other.total_gas;
+    }
+
+    /// Returns the stat of the host function with the given metric name, e.g.
+    /// `"host_function_get_arg"`, if it was called.
+    pub fn get(&self, host_function: &str) -> Option<&HostCallStat> {
+        self.iter()
+            .find(|(name, _)| *name == host_function)
+            .map(|(_, stat)| stat)
+    }
+
+    /// Returns the stats of the host functions which were called, by metric name.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &HostCallStat)> {
+        self.stats
+            .iter()
+            .enumerate()
+            .filter(|(_, stat)| stat.calls > 0)
+            .filter_map(|(index, stat)| {
+                let name = FunctionIndex::try_from(index).ok()?.host_function_name()?;
+                Some((name, stat))
+            })
+    }
+
+    /// Returns the gas attributed to all the calls.
+    pub fn total_gas(&self) -> Gas {
+        self.total_gas
+    }
+
+    /// Logs the number of calls and the gas attributed to each host function called, tagged with
+    /// the name of the host function.
+    pub fn log_metrics(&self, correlation_id: CorrelationId) {
+        for (host_function, stat) in self.iter() {
+            log_metric(
+                correlation_id,
+                METRIC_HOST_FUNCTION_CALLS,
+                host_function,
+                TOTAL_METRIC_KEY,
+                stat.calls as f64,
+            );
+            log_metric(
+                correlation_id,
+                METRIC_HOST_FUNCTION_GAS,
+                host_function,
+                TOTAL_METRIC_KEY,
+                stat.gas.value().as_u64() as f64,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::gas::Gas;
+
+    use super::HostCallStats;
+    use crate::resolvers::v1_function_index::FunctionIndex;
+
+    #[test]
+    fn should_tally_calls_per_host_function() {
+        let mut stats = HostCallStats::new();
+        stats.record(FunctionIndex::GetArgFuncIndex, Gas::new(1.into()));
+        stats.record(FunctionIndex::GetArgFuncIndex, Gas::new(2.into()));
+        stats.record(FunctionIndex::TransferToAccountIndex, Gas::new(10.into()));
+        stats.record(FunctionIndex::GasFuncIndex, Gas::new(100.into()));
+
+        let get_arg = stats.get("host_function_get_arg").unwrap();
+        assert_eq!(get_arg.calls, 2);
+        assert_eq!(get_arg.gas, Gas::new(3.into()));
+        assert_eq!(
+            stats
+                .get("host_function_transfer_to_account")
+                .unwrap()
+                .calls,
+            1
+        );
+        assert_eq!(stats.iter().count(), 2);
+        assert_eq!(stats.total_gas(), Gas::new(13.into()));
+
+        let mut merged = stats.clone();
+        merged.merge(&stats);
+        assert_eq!(merged.get("host_function_get_arg").unwrap().calls, 4);
+        assert_eq!(merged.total_gas(), Gas::new(26.into()));
+    }
+}
@@ -2,16 +2,23 @@ mod address_generator;
 mod error;
 #[macro_use]
 mod executor;
+mod host_call_stats;
+mod pure_calls;
+mod system_context;
 #[cfg(test)]
 mod tests;
+mod transient_storage;
 
 pub use self::{
     address_generator::{AddressGenerator, AddressGeneratorBuilder},
     error::Error,
     executor::Executor,
+    host_call_stats::{HostCallStat, HostCallStats},
+    pure_calls::PureCallCache,
+    system_context::{ExecutionContextKind, SystemContext},
+    transient_storage::TransientStorage,
 };
 
-pub const MINT_NAME: &str = "mint";
-pub const POS_NAME: &str = "pos";
+pub use types::system_contract_names::{MINT_NAME, POS_NAME};
 
 pub(crate) const FN_STORE_ID_INITIAL: u32 = 0;
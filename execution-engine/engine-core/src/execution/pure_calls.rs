@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use engine_shared::{gas::Gas, stored_value::StoredValue};
+use types::{CLValue, Key};
+
+/// The result of a call to a stored function declared pure, along with what the call depended on.
+#[derive(Debug)]
+struct PureCall {
+    result: CLValue,
+    gas: Gas,
+    /// The keys read by the call, each with the value found under it at the time.
+    reads: Vec<(Key, Option<StoredValue>)>,
+}
+
+/// The results of the calls to stored functions declared pure made while executing a single
+/// `ExecuteRequest`, so that repeating a call with the same arguments skips its execution.
+///
+/// A hit is charged the gas the call used when it was first executed, so the cost of a call doesn't
+/// depend on whether an earlier deploy of the request made it already.  As the results are dropped
+/// with the request, they never carry over between blocks.
+///
+/// A result is only served while the values under the keys the call read are unchanged, as each
+/// deploy of the request sees its own writes but not those of the other deploys.
+#[derive(Debug, Default)]
+pub struct PureCallCache {
+    results: HashMap<(Key, Vec<u8>), PureCall>,
+    hits: u64,
+}
+
+impl PureCallCache {
+    pub fn new() -> Self {
+        PureCallCache::default()
+    }
+
+    /// Returns the result of calling the function stored under `key` with `args_bytes`, along with
+    /// the gas the call used, if the call was made already.
+    ///
+    /// `read` is called with each key the call read, and must return the value currently under
+    /// it.  If any value differs from the one the call found, the result is dropped and `None` is
+    /// returned.
+    pub fn get<F, E>(
+        &mut self,
+        key: Key,
+        args_bytes: &[u8],
+        mut read: F,
+    ) -> Result<Option<(CLValue, Gas)>, E>
+    where
+        F: FnMut(&Key) -> Result<Option<StoredValue>, E>,
+    {
+        let cache_key = (key, args_bytes.to_vec());
+        let is_current = match self.results.get(&cache_key) {
+            Some(pure_call) => {
+                let mut is_current = true;
+                for (read_key, value) in &pure_call.reads {
+                    if read(read_key)? != *value {
+                        is_current = false;
+                        break;
+                    }
+                }
+                is_current
+            }
+            None => return Ok(None),
+        };
+        if !is_current {
+            self.results.remove(&cache_key);
+            return Ok(None);
+        }
+        self.hits += 1;
+        Ok(self
+            .results
+            .get(&cache_key)
+            .map(|pure_call| (pure_call.result.clone(), pure_call.gas)))
+    }
+
+    /// Records the `result` of calling the function stored under `key` with `args_bytes`, which
+    /// used `gas` and read the values in `reads`.
+    pub fn insert(
+        &mut self,
+        key: Key,
+        args_bytes: Vec<u8>,
+        result: CLValue,
+        gas: Gas,
+        reads: Vec<(Key, Option<StoredValue>)>,
+    ) {
+        let pure_call = PureCall { result, gas, reads };
+        self.results.insert((key, args_bytes), pure_call);
+    }
+
+    /// Returns the number of calls served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::U512;
+
+    use super::*;
+
+    /// Returns a `read` function finding `value` under every key.
+    fn read_from(
+        value: Option<StoredValue>,
+    ) -> impl FnMut(&Key) -> Result<Option<StoredValue>, ()> {
+        move |_| Ok(value.clone())
+    }
+
+    #[test]
+    fn should_return_result_only_for_same_key_and_args() {
+        let key = Key::Hash([1; 32]);
+        let result = CLValue::from_t(42u64).unwrap();
+        let gas = Gas::new(U512::from(100));
+
+        let mut cache = PureCallCache::new();
+        assert_eq!(cache.get(key, &[1], read_from(None)), Ok(None));
+        cache.insert(key, vec![1], result.clone(), gas, Vec::new());
+
+        assert_eq!(
+            cache.get(key, &[1], read_from(None)),
+            Ok(Some((result, gas)))
+        );
+        assert_eq!(cache.get(key, &[2], read_from(None)), Ok(None));
+        assert_eq!(
+            cache.get(Key::Hash([2; 32]), &[1], read_from(None)),
+            Ok(None)
+        );
+        assert_eq!(cache.hits(), 1);
+    }
+
+    #[test]
+    fn should_drop_result_once_read_value_changes() {
+        let key = Key::Hash([1; 32]);
+        let read_key = Key::Hash([2; 32]);
+        let result = CLValue::from_t(42u64).unwrap();
+        let gas = Gas::new(U512::from(100));
+        let value = Some(StoredValue::CLValue(CLValue::from_t(1u64).unwrap()));
+        let new_value = Some(StoredValue::CLValue(CLValue::from_t(2u64).unwrap()));
+
+        let mut cache = PureCallCache::new();
+        cache.insert(
+            key,
+            vec![1],
+            result.clone(),
+            gas,
+            vec![(read_key, value.clone())],
+        );
+
+        assert_eq!(
+            cache.get(key, &[1], read_from(value.clone())),
+            Ok(Some((result, gas)))
+        );
+        assert_eq!(cache.get(key, &[1], read_from(new_value)), Ok(None));
+        // the stale result is dropped, so it isn't served even once the value is restored
+        assert_eq!(cache.get(key, &[1], read_from(value)), Ok(None));
+        assert_eq!(cache.hits(), 1);
+    }
+}
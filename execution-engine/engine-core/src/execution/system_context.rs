@@ -0,0 +1,223 @@
+use std::collections::BTreeSet;
+
+use engine_shared::gas::Gas;
+use types::{account::PublicKey, BlockTime, Phase, U512};
+
+use crate::resolvers::v1_function_index::FunctionIndex;
+
+/// Whether an execution was initiated by a user deploy or by the engine itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionContextKind {
+    User,
+    System,
+}
+
+impl Default for ExecutionContextKind {
+    fn default() -> Self {
+        ExecutionContextKind::User
+    }
+}
+
+impl ExecutionContextKind {
+    /// Returns whether the host function `func` may be called in a context of this kind.
+    ///
+    /// User contexts may call every host function except `prune`, which removes values from global
+    /// state and is reserved for the engine.  System contexts may only call the host functions
+    /// listed here: they run on behalf of the system account, whose associated keys,
+    /// action thresholds, authorization contract and main purse must not be changed by any system
+    /// contract, and they neither schedule calls nor share transient storage with user code.
+    ///
+    /// A system context may only read its arguments if `args_supplied_by_engine`, i.e. they were
+    /// supplied by the engine or built by system code rather than by a user contract.
+    pub(crate) fn allows(self, func: FunctionIndex, args_supplied_by_engine: bool) -> bool {
+        match self {
+            ExecutionContextKind::User => func != FunctionIndex::PruneFuncIndex,
+            ExecutionContextKind::System => match func {
+                FunctionIndex::GetArgFuncIndex
+                | FunctionIndex::GetArgSizeFuncIndex
+                | FunctionIndex::GetNamedArgFuncIndex
+                | FunctionIndex::GetNamedArgSizeFuncIndex
+                | FunctionIndex::CheckArgTypeIndex => args_supplied_by_engine,
+                FunctionIndex::WriteFuncIndex
+                | FunctionIndex::WriteLocalFuncIndex
+                | FunctionIndex::ReadFuncIndex
+                | FunctionIndex::ReadLocalFuncIndex
+                | FunctionIndex::AddFuncIndex
+                | FunctionIndex::AddLocalFuncIndex
+                | FunctionIndex::NewFuncIndex
+                | FunctionIndex::NewPublicURefIndex
+                | FunctionIndex::RetFuncIndex
+                | FunctionIndex::RevertFuncIndex
+                | FunctionIndex::RevertWithDataFuncIndex
+                | FunctionIndex::CallContractFuncIndex
+                | FunctionIndex::CallContractReadOnlyFuncIndex
+                | FunctionIndex::TryCallContractFuncIndex
+                | FunctionIndex::ReadHostBufferIndex
+                | FunctionIndex::GetKeyFuncIndex
+                | FunctionIndex::HasKeyFuncIndex
+                | FunctionIndex::PutKeyFuncIndex
+                | FunctionIndex::PutKeyIfAbsentIndex
+                | FunctionIndex::RemoveKeyFuncIndex
+                | FunctionIndex::RemoveKeyIfPresentIndex
+                | FunctionIndex::LoadNamedKeysFuncIndex
+                | FunctionIndex::MigrateLocalKeysIndex
+                | FunctionIndex::StoreFnIndex
+                | FunctionIndex::StoreFnAtHashIndex
+                | FunctionIndex::StoreFnWithSpecIndex
+                | FunctionIndex::UpgradeContractAtURefIndex
+                | FunctionIndex::IsValidURefFnIndex
+                | FunctionIndex::AccessRightsFuncIndex
+                | FunctionIndex::GetCallerIndex
+                | FunctionIndex::GetCallerKeyIndex
+                | FunctionIndex::GetBlocktimeIndex
+                | FunctionIndex::GetPhaseIndex
+                | FunctionIndex::GetSystemContractIndex
+                | FunctionIndex::GetMainPurseIndex
+                | FunctionIndex::CreatePurseIndex
+                | FunctionIndex::PurseExistsIndex
+                | FunctionIndex::GetBalanceIndex
+                | FunctionIndex::GetBalanceByURefIndex
+                | FunctionIndex::TransferToAccountIndex
+                | FunctionIndex::TransferFromPurseToAccountIndex
+                | FunctionIndex::TransferFromPurseToPurseIndex
+                | FunctionIndex::GasFuncIndex
+                | FunctionIndex::GetGasCounterIndex
+                | FunctionIndex::PruneFuncIndex => true,
+                #[cfg(feature = "test-support")]
+                FunctionIndex::PrintIndex => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// The parameters of an execution initiated by the engine itself rather than by a user deploy,
+/// i.e. the genesis and upgrade installers and the finalization of a deploy's payment.
+///
+/// Executions in a system context:
+/// * run as the system account with arguments supplied by the engine, never by a deploy,
+/// * are not metered, so they have no gas limit and their cost is never charged to the deploy,
+/// * may only call the host functions [allowed](ExecutionContextKind::allows) for
+///   [`ExecutionContextKind::System`].
+///
+/// A system context can only be constructed inside the engine, and contracts called from a user
+/// deploy always run in a user context, so a deploy can't obtain one.
+#[derive(Clone, Debug)]
+pub struct SystemContext {
+    phase: Phase,
+    blocktime: BlockTime,
+    deploy_hash: [u8; 32],
+    authorization_keys: BTreeSet<PublicKey>,
+}
+
+impl SystemContext {
+    /// # Panics
+    ///
+    /// Panics if `phase` is the authorization, payment or session phase of a user deploy.
+    pub(crate) fn new(
+        phase: Phase,
+        blocktime: BlockTime,
+        deploy_hash: [u8; 32],
+        authorization_keys: BTreeSet<PublicKey>,
+    ) -> Self {
+        match phase {
+            Phase::System | Phase::FinalizePayment => {}
+            Phase::Authorization | Phase::Payment | Phase::Session => {
+                panic!("system context can't be used in {:?} phase", phase)
+            }
+        }
+        SystemContext {
+            phase,
+            blocktime,
+            deploy_hash,
+            authorization_keys,
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn blocktime(&self) -> BlockTime {
+        self.blocktime
+    }
+
+    pub fn deploy_hash(&self) -> [u8; 32] {
+        self.deploy_hash
+    }
+
+    pub fn authorization_keys(&self) -> &BTreeSet<PublicKey> {
+        &self.authorization_keys
+    }
+
+    /// System executions aren't metered, so the limit is never reached.
+    pub fn gas_limit(&self) -> Gas {
+        Gas::new(U512::max_value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_context_should_allow_all_host_functions_but_prune() {
+        let kind = ExecutionContextKind::User;
+        assert!(kind.allows(FunctionIndex::AddAssociatedKeyFuncIndex, false));
+        assert!(kind.allows(FunctionIndex::SetActionThresholdFuncIndex, false));
+        assert!(kind.allows(FunctionIndex::GetArgFuncIndex, false));
+        assert!(!kind.allows(FunctionIndex::PruneFuncIndex, false));
+    }
+
+    #[test]
+    fn system_context_should_allow_prune() {
+        let kind = ExecutionContextKind::System;
+        assert!(kind.allows(FunctionIndex::PruneFuncIndex, false));
+    }
+
+    #[test]
+    fn system_context_should_forbid_key_management() {
+        let kind = ExecutionContextKind::System;
+        assert!(!kind.allows(FunctionIndex::AddAssociatedKeyFuncIndex, true));
+        assert!(!kind.allows(FunctionIndex::RemoveAssociatedKeyFuncIndex, true));
+        assert!(!kind.allows(FunctionIndex::UpdateAssociatedKeyFuncIndex, true));
+        assert!(!kind.allows(FunctionIndex::SetActionThresholdFuncIndex, true));
+        assert!(!kind.allows(FunctionIndex::SetAuthorizationContractIndex, true));
+        assert!(!kind.allows(FunctionIndex::RotateMainPurseIndex, true));
+        assert!(kind.allows(FunctionIndex::CallContractFuncIndex, true));
+    }
+
+    #[test]
+    fn system_context_should_forbid_user_code_interaction() {
+        let kind = ExecutionContextKind::System;
+        assert!(!kind.allows(FunctionIndex::ScheduleCallFuncIndex, true));
+        assert!(!kind.allows(FunctionIndex::PutTransientFuncIndex, true));
+        assert!(!kind.allows(FunctionIndex::GetTransientFuncIndex, true));
+    }
+
+    #[test]
+    fn system_context_should_only_read_args_supplied_by_engine() {
+        let kind = ExecutionContextKind::System;
+        for func in &[
+            FunctionIndex::GetArgFuncIndex,
+            FunctionIndex::GetArgSizeFuncIndex,
+            FunctionIndex::GetNamedArgFuncIndex,
+            FunctionIndex::GetNamedArgSizeFuncIndex,
+            FunctionIndex::CheckArgTypeIndex,
+        ] {
+            assert!(kind.allows(*func, true));
+            assert!(!kind.allows(*func, false));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn should_not_create_system_context_for_session() {
+        SystemContext::new(
+            Phase::Session,
+            BlockTime::default(),
+            [0; 32],
+            BTreeSet::new(),
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use types::CLValue;
+
+use super::Error;
+
+/// Scratch storage shared by all the contracts called during a single execution of session or
+/// payment code.
+///
+/// Values are never written to global state, so they produce no effects, and they are dropped when
+/// the execution ends.
+#[derive(Clone, Debug, Default)]
+pub struct TransientStorage {
+    values: BTreeMap<String, CLValue>,
+    size: usize,
+}
+
+impl TransientStorage {
+    pub fn new() -> Self {
+        TransientStorage::default()
+    }
+
+    /// Returns the value stored under `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&CLValue> {
+        self.values.get(name)
+    }
+
+    /// Stores `value` under `name`, replacing any previous value.
+    ///
+    /// Fails without storing the value if the total size of the names and serialized values held
+    /// would then exceed `max_size` bytes.
+    pub fn put(&mut self, name: String, value: CLValue, max_size: usize) -> Result<(), Error> {
+        let replaced_size = self
+            .values
+            .get(&name)
+            .map(|replaced| entry_size(&name, replaced))
+            .unwrap_or_default();
+        let size = self.size - replaced_size + entry_size(&name, &value);
+        if size > max_size {
+            return Err(Error::TransientStorageExceeded {
+                max_size: max_size as u32,
+            });
+        }
+        self.values.insert(name, value);
+        self.size = size;
+        Ok(())
+    }
+
+    /// Returns the total size in bytes of the names and serialized values held.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+}
+
+fn entry_size(name: &str, value: &CLValue) -> usize {
+    name.len() + value.inner_bytes().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_SIZE: usize = 16;
+
+    #[test]
+    fn should_replace_value_and_its_size() {
+        let mut transient_storage = TransientStorage::new();
+        transient_storage
+            .put("a".to_string(), CLValue::from_t(1u64).unwrap(), MAX_SIZE)
+            .expect("should put");
+        assert_eq!(transient_storage.size(), 9);
+
+        transient_storage
+            .put("a".to_string(), CLValue::from_t(2u32).unwrap(), MAX_SIZE)
+            .expect("should replace");
+        assert_eq!(transient_storage.size(), 5);
+        assert_eq!(
+            transient_storage.get("a").cloned(),
+            Some(CLValue::from_t(2u32).unwrap())
+        );
+        assert!(transient_storage.get("b").is_none());
+    }
+
+    #[test]
+    fn should_not_exceed_max_size() {
+        let mut transient_storage = TransientStorage::new();
+        transient_storage
+            .put("a".to_string(), CLValue::from_t(1u64).unwrap(), MAX_SIZE)
+            .expect("should put");
+
+        let result =
+            transient_storage.put("b".to_string(), CLValue::from_t(1u64).unwrap(), MAX_SIZE);
+        match result {
+            Err(Error::TransientStorageExceeded { max_size }) => {
+                assert_eq!(max_size as usize, MAX_SIZE)
+            }
+            other => panic!("expected transient storage exceeded, got {:?}", other),
+        }
+        assert!(transient_storage.get("b").is_none());
+        assert_eq!(transient_storage.size(), 9);
+    }
+}
@@ -9,11 +9,10 @@ use std::collections::BTreeMap;
 
 use types::Key;
 
+pub use types::{DeployHash, DEPLOY_HASH_LENGTH};
+
 pub const ADDRESS_LENGTH: usize = 32;
-pub const DEPLOY_HASH_LENGTH: usize = 32;
 
 pub type Address = [u8; ADDRESS_LENGTH];
 
-pub type DeployHash = [u8; DEPLOY_HASH_LENGTH];
-
 type KnownKeys = BTreeMap<String, Key>;
@@ -43,10 +43,118 @@ pub enum FunctionIndex {
     GetMainPurseIndex,
     GetArgSizeFuncIndex,
     ReadHostBufferIndex,
+    CallContractReadOnlyFuncIndex,
+    AccessRightsFuncIndex,
+    PutTransientFuncIndex,
+    GetTransientFuncIndex,
+    ScheduleCallFuncIndex,
+    RotateMainPurseFuncIndex,
+    PurseExistsIndex,
+    SetAuthorizationContractIndex,
+    CheckArgTypeIndex,
+    GetCallerKeyIndex,
+    PutKeyIfAbsentIndex,
+    RemoveKeyIfPresentIndex,
+    NewPublicURefIndex,
+    StoreFnWithSpecIndex,
+    RevertWithDataFuncIndex,
+    MigrateLocalKeysIndex,
+    GetBalanceByURefIndex,
+    GetGasCounterIndex,
+    TryCallContractFuncIndex,
+    GetNamedArgSizeFuncIndex,
+    GetNamedArgFuncIndex,
+    PruneFuncIndex,
     #[cfg(feature = "test-support")]
     PrintIndex,
 }
 
+impl FunctionIndex {
+    /// Returns the name under which metrics of this host function are reported, or `None` for the
+    /// `gas` function, which is called by the metering instrumentation rather than by contracts.
+    pub fn host_function_name(self) -> Option<&'static str> {
+        match self {
+            FunctionIndex::GasFuncIndex => None,
+            FunctionIndex::WriteFuncIndex => Some("host_function_write"),
+            FunctionIndex::WriteLocalFuncIndex => Some("host_function_write_local"),
+            FunctionIndex::ReadFuncIndex => Some("host_function_read_value"),
+            FunctionIndex::ReadLocalFuncIndex => Some("host_function_read_value_local"),
+            FunctionIndex::AddFuncIndex => Some("host_function_add"),
+            FunctionIndex::AddLocalFuncIndex => Some("host_function_add_local"),
+            FunctionIndex::NewFuncIndex => Some("host_function_new_uref"),
+            FunctionIndex::RetFuncIndex => Some("host_function_ret"),
+            FunctionIndex::CallContractFuncIndex => Some("host_function_call_contract"),
+            FunctionIndex::GetArgFuncIndex => Some("host_function_get_arg"),
+            FunctionIndex::GetKeyFuncIndex => Some("host_function_get_key"),
+            FunctionIndex::HasKeyFuncIndex => Some("host_function_has_key"),
+            FunctionIndex::PutKeyFuncIndex => Some("host_function_put_key"),
+            FunctionIndex::StoreFnIndex => Some("host_function_store_function"),
+            FunctionIndex::StoreFnAtHashIndex => Some("host_function_store_function_at_hash"),
+            FunctionIndex::IsValidURefFnIndex => Some("host_function_is_valid_uref"),
+            FunctionIndex::RevertFuncIndex => Some("host_function_revert"),
+            FunctionIndex::AddAssociatedKeyFuncIndex => Some("host_function_add_associated_key"),
+            FunctionIndex::RemoveAssociatedKeyFuncIndex => {
+                Some("host_function_remove_associated_key")
+            }
+            FunctionIndex::UpdateAssociatedKeyFuncIndex => {
+                Some("host_function_update_associated_key")
+            }
+            FunctionIndex::SetActionThresholdFuncIndex => {
+                Some("host_function_set_action_threshold")
+            }
+            FunctionIndex::LoadNamedKeysFuncIndex => Some("host_function_load_named_keys"),
+            FunctionIndex::RemoveKeyFuncIndex => Some("host_function_remove_key"),
+            FunctionIndex::GetCallerIndex => Some("host_function_get_caller"),
+            FunctionIndex::GetBlocktimeIndex => Some("host_function_get_blocktime"),
+            FunctionIndex::CreatePurseIndex => Some("host_function_create_purse"),
+            FunctionIndex::TransferToAccountIndex => Some("host_function_transfer_to_account"),
+            FunctionIndex::TransferFromPurseToAccountIndex => {
+                Some("host_function_transfer_from_purse_to_account")
+            }
+            FunctionIndex::TransferFromPurseToPurseIndex => {
+                Some("host_function_transfer_from_purse_to_purse")
+            }
+            FunctionIndex::GetBalanceIndex => Some("host_function_get_balance"),
+            FunctionIndex::GetPhaseIndex => Some("host_function_get_phase"),
+            FunctionIndex::UpgradeContractAtURefIndex => {
+                Some("host_function_upgrade_contract_at_uref")
+            }
+            FunctionIndex::GetSystemContractIndex => Some("host_function_get_system_contract"),
+            FunctionIndex::GetMainPurseIndex => Some("host_function_get_main_purse"),
+            FunctionIndex::GetArgSizeFuncIndex => Some("host_function_get_arg_size"),
+            FunctionIndex::ReadHostBufferIndex => Some("host_function_read_host_buffer"),
+            FunctionIndex::CallContractReadOnlyFuncIndex => {
+                Some("host_function_call_contract_read_only")
+            }
+            FunctionIndex::AccessRightsFuncIndex => Some("host_function_access_rights"),
+            FunctionIndex::PutTransientFuncIndex => Some("host_function_put_transient"),
+            FunctionIndex::GetTransientFuncIndex => Some("host_function_get_transient"),
+            FunctionIndex::ScheduleCallFuncIndex => Some("host_function_schedule_call"),
+            FunctionIndex::RotateMainPurseFuncIndex => Some("host_function_rotate_main_purse"),
+            FunctionIndex::PurseExistsIndex => Some("host_function_purse_exists"),
+            FunctionIndex::SetAuthorizationContractIndex => {
+                Some("host_function_set_authorization_contract")
+            }
+            FunctionIndex::CheckArgTypeIndex => Some("host_function_check_arg_type"),
+            FunctionIndex::GetCallerKeyIndex => Some("host_function_get_caller_key"),
+            FunctionIndex::PutKeyIfAbsentIndex => Some("host_function_put_key_if_absent"),
+            FunctionIndex::RemoveKeyIfPresentIndex => Some("host_function_remove_key_if_present"),
+            FunctionIndex::NewPublicURefIndex => Some("host_function_new_public_uref"),
+            FunctionIndex::StoreFnWithSpecIndex => Some("host_function_store_function_with_spec"),
+            FunctionIndex::RevertWithDataFuncIndex => Some("host_function_revert_with_data"),
+            FunctionIndex::MigrateLocalKeysIndex => Some("host_function_migrate_local_keys"),
+            FunctionIndex::GetBalanceByURefIndex => Some("host_function_get_balance_by_uref"),
+            FunctionIndex::GetGasCounterIndex => Some("host_function_get_gas_counter"),
+            FunctionIndex::TryCallContractFuncIndex => Some("host_function_try_call_contract"),
+            FunctionIndex::GetNamedArgSizeFuncIndex => Some("host_function_get_named_arg_size"),
+            FunctionIndex::GetNamedArgFuncIndex => Some("host_function_get_named_arg"),
+            FunctionIndex::PruneFuncIndex => Some("host_function_prune"),
+            #[cfg(feature = "test-support")]
+            FunctionIndex::PrintIndex => Some("host_function_print"),
+        }
+    }
+}
+
 impl Into<usize> for FunctionIndex {
     fn into(self) -> usize {
         // NOTE: This can't fail as `FunctionIndex` is represented by usize,
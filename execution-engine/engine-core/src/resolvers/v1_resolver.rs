@@ -188,6 +188,94 @@ impl ModuleImportResolver for RuntimeModuleImportResolver {
                 Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
                 FunctionIndex::ReadHostBufferIndex.into(),
             ),
+            "call_contract_read_only" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::CallContractReadOnlyFuncIndex.into(),
+            ),
+            "access_rights" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::AccessRightsFuncIndex.into(),
+            ),
+            "put_transient" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], None),
+                FunctionIndex::PutTransientFuncIndex.into(),
+            ),
+            "get_transient" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetTransientFuncIndex.into(),
+            ),
+            "schedule_call" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], Some(ValueType::I32)),
+                FunctionIndex::ScheduleCallFuncIndex.into(),
+            ),
+            "rotate_main_purse" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::RotateMainPurseFuncIndex.into(),
+            ),
+            "purse_exists" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::PurseExistsIndex.into(),
+            ),
+            "set_authorization_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::SetAuthorizationContractIndex.into(),
+            ),
+            "check_arg_type" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::CheckArgTypeIndex.into(),
+            ),
+            "get_caller_key" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], Some(ValueType::I32)),
+                FunctionIndex::GetCallerKeyIndex.into(),
+            ),
+            "put_key_if_absent" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::PutKeyIfAbsentIndex.into(),
+            ),
+            "remove_key_if_present" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::RemoveKeyIfPresentIndex.into(),
+            ),
+            "new_public_uref" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], None),
+                FunctionIndex::NewPublicURefIndex.into(),
+            ),
+            "store_function_with_spec" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 7][..], None),
+                FunctionIndex::StoreFnWithSpecIndex.into(),
+            ),
+            "revert_with_data" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], None),
+                FunctionIndex::RevertWithDataFuncIndex.into(),
+            ),
+            "migrate_local_keys" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], Some(ValueType::I32)),
+                FunctionIndex::MigrateLocalKeysIndex.into(),
+            ),
+            "get_balance_by_uref" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetBalanceByURefIndex.into(),
+            ),
+            "get_gas_counter" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 1][..], None),
+                FunctionIndex::GetGasCounterIndex.into(),
+            ),
+            "try_call_contract" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 5][..], Some(ValueType::I32)),
+                FunctionIndex::TryCallContractFuncIndex.into(),
+            ),
+            "get_named_arg_size" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 3][..], Some(ValueType::I32)),
+                FunctionIndex::GetNamedArgSizeFuncIndex.into(),
+            ),
+            "get_named_arg" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 4][..], Some(ValueType::I32)),
+                FunctionIndex::GetNamedArgFuncIndex.into(),
+            ),
+            "prune" => FuncInstance::alloc_host(
+                Signature::new(&[ValueType::I32; 2][..], None),
+                FunctionIndex::PruneFuncIndex.into(),
+            ),
             #[cfg(feature = "test-support")]
             "print" => FuncInstance::alloc_host(
                 Signature::new(&[ValueType::I32; 2][..], None),
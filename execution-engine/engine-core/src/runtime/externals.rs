@@ -15,6 +15,12 @@ use engine_storage::global_state::StateReader;
 use super::{args::Args, scoped_instrumenter::ScopedInstrumenter, Error, Runtime};
 use crate::resolvers::v1_function_index::FunctionIndex;
 
+/// Gas charged for a call to the `access_rights` host function.
+const ACCESS_RIGHTS_COST: u32 = 200;
+
+/// Gas charged for a call to the `get_gas_counter` host function.
+const GET_GAS_COUNTER_COST: u32 = 200;
+
 impl<'a, R> Externals for Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
@@ -26,7 +32,52 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        if !self
+            .context
+            .kind()
+            .allows(func, self.context.args_supplied_by_engine())
+        {
+            if !self.context.is_system() {
+                return Err(Error::InvalidContext.into());
+            }
+            return Err(Error::ForbiddenInSystemContext(format!("{:?}", func)).into());
+        }
         let mut scoped_instrumenter = ScopedInstrumenter::new(func);
+        // the `gas` function is called by the metering instrumentation, not tallied
+        if func == FunctionIndex::GasFuncIndex {
+            return self.invoke_host_function(func, args, &mut scoped_instrumenter);
+        }
+
+        let host_call_stats = self.context.host_call_stats();
+        let gas_counter_before = self.context.gas_counter();
+        let attributed_gas_before = host_call_stats.borrow().total_gas();
+
+        let result = self.invoke_host_function(func, args, &mut scoped_instrumenter);
+
+        // gas attributed to host functions called from within this one, e.g. by a contract run by
+        // `call_contract`, isn't attributed to this one as well
+        let gas_used = self.context.gas_counter().value() - gas_counter_before.value();
+        let nested_gas =
+            host_call_stats.borrow().total_gas().value() - attributed_gas_before.value();
+        host_call_stats
+            .borrow_mut()
+            .record(func, Gas::new(gas_used.saturating_sub(nested_gas)));
+
+        result
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<Error>,
+{
+    fn invoke_host_function(
+        &mut self,
+        func: FunctionIndex,
+        args: RuntimeArgs,
+        scoped_instrumenter: &mut ScopedInstrumenter,
+    ) -> Result<Option<RuntimeValue>, Trap> {
         match func {
             FunctionIndex::ReadFuncIndex => {
                 // args(0) = pointer to key in Wasm memory
@@ -51,11 +102,8 @@ where
                 // args(0) = pointer to amount of keys (output)
                 // args(1) = pointer to amount of serialized bytes (output)
                 let (total_keys_ptr, result_size_ptr) = Args::parse(args)?;
-                let ret = self.load_named_keys(
-                    total_keys_ptr,
-                    result_size_ptr,
-                    &mut scoped_instrumenter,
-                )?;
+                let ret =
+                    self.load_named_keys(total_keys_ptr, result_size_ptr, scoped_instrumenter)?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
@@ -138,7 +186,7 @@ where
                 // args(1) = size of value
                 let (value_ptr, value_size): (_, u32) = Args::parse(args)?;
                 scoped_instrumenter.add_property("value_size", value_size);
-                Err(self.ret(value_ptr, value_size as usize, &mut scoped_instrumenter))
+                Err(self.ret(value_ptr, value_size as usize, scoped_instrumenter))
             }
 
             FunctionIndex::CallContractFuncIndex => {
@@ -158,7 +206,8 @@ where
                     key_contract,
                     args_bytes,
                     result_size_ptr,
-                    &mut scoped_instrumenter,
+                    false,
+                    scoped_instrumenter,
                 )?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -311,6 +360,16 @@ where
                 Err(self.revert(status))
             }
 
+            FunctionIndex::RevertWithDataFuncIndex => {
+                // args(0) = status u32
+                // args(1) = pointer to the data in Wasm memory
+                // args(2) = size of the data
+                let (status, data_ptr, data_size): (_, _, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("data_size", data_size);
+
+                Err(self.revert_with_data(status, data_ptr, data_size))
+            }
+
             FunctionIndex::AddAssociatedKeyFuncIndex => {
                 // args(0) = pointer to array of bytes of a public key
                 // args(1) = size of a public key
@@ -467,7 +526,7 @@ where
                     name_size,
                     key_ptr,
                     key_size,
-                    &mut scoped_instrumenter,
+                    scoped_instrumenter,
                 )?;
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
@@ -496,6 +555,280 @@ where
                 Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
             }
 
+            FunctionIndex::CallContractReadOnlyFuncIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to function arguments in Wasm memory
+                // args(3) = size of arguments
+                // args(4) = pointer to result size (output)
+                let (key_ptr, key_size, args_ptr, args_size, result_size_ptr): (_, _, _, u32, _) =
+                    Args::parse(args)?;
+                scoped_instrumenter.add_property("args_size", args_size);
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+
+                let ret = self.call_contract_host_buffer(
+                    key_contract,
+                    args_bytes,
+                    result_size_ptr,
+                    true,
+                    scoped_instrumenter,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::AccessRightsFuncIndex => {
+                // args(0) = pointer to uref in Wasm memory
+                // args(1) = size of uref
+                let (uref_ptr, uref_size) = Args::parse(args)?;
+                self.gas(Gas::new(ACCESS_RIGHTS_COST.into()))?;
+
+                // The access rights fit in the lowest byte; -1 signals an unknown uref.
+                let ret = match self.access_rights(uref_ptr, uref_size)? {
+                    Some(access_rights) => i32::from(access_rights.bits()),
+                    None => -1,
+                };
+                Ok(Some(RuntimeValue::I32(ret)))
+            }
+
+            FunctionIndex::PutTransientFuncIndex => {
+                // args(0) = pointer to name in Wasm memory
+                // args(1) = size of name
+                // args(2) = pointer to value
+                // args(3) = size of value
+                let (name_ptr, name_size, value_ptr, value_size): (_, _, _, u32) =
+                    Args::parse(args)?;
+                scoped_instrumenter.add_property("value_size", value_size);
+                self.put_transient(name_ptr, name_size, value_ptr, value_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::GetTransientFuncIndex => {
+                // args(0) = pointer to name in Wasm memory
+                // args(1) = size of name
+                // args(2) = pointer to output size (output param)
+                let (name_ptr, name_size, output_size_ptr) = Args::parse(args)?;
+                let ret = self.get_transient(name_ptr, name_size, output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::ScheduleCallFuncIndex => {
+                // args(0) = pointer to contract key in Wasm memory
+                // args(1) = size of contract key
+                // args(2) = pointer to function arguments in Wasm memory
+                // args(3) = size of arguments
+                // args(4) = pointer to the block time the call is due at
+                // args(5) = size of the block time
+                // args(6) = pointer to the schedule id (output)
+                let (
+                    key_ptr,
+                    key_size,
+                    args_ptr,
+                    args_size,
+                    not_before_ptr,
+                    not_before_size,
+                    schedule_id_ptr,
+                ) = Args::parse(args)?;
+                let ret = self.schedule_call(
+                    key_ptr,
+                    key_size,
+                    args_ptr,
+                    args_size,
+                    not_before_ptr,
+                    not_before_size,
+                    schedule_id_ptr,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::RotateMainPurseFuncIndex => {
+                // args(0) = pointer to the new main purse (output)
+                let dest_ptr = Args::parse(args)?;
+                let ret = self.rotate_main_purse(dest_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::PurseExistsIndex => {
+                // args(0) = pointer to purse input
+                // args(1) = length of purse
+                let (purse_ptr, purse_size): (_, u32) = Args::parse(args)?;
+                let result = self.purse_exists_host(purse_ptr, purse_size)?;
+                Ok(Some(RuntimeValue::I32(result)))
+            }
+
+            FunctionIndex::SetAuthorizationContractIndex => {
+                // args(0) = pointer to the serialized `Option<Key>` of the contract
+                // args(1) = length of the serialized `Option<Key>`
+                let (contract_ptr, contract_size): (_, u32) = Args::parse(args)?;
+                let ret = self.set_authorization_contract(contract_ptr, contract_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::CheckArgTypeIndex => {
+                // args(0) = index of host runtime arg to check
+                // args(1) = pointer to the serialized expected `CLType`
+                // args(2) = length of the serialized expected `CLType`
+                let (index, cl_type_ptr, cl_type_size): (u32, _, u32) = Args::parse(args)?;
+                let ret = self.check_arg_type(index as usize, cl_type_ptr, cl_type_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetCallerKeyIndex => {
+                // args(0) = pointer where a size of serialized bytes will be stored
+                let output_size = Args::parse(args)?;
+                let ret = self.get_caller_key(output_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::PutKeyIfAbsentIndex => {
+                // args(0) = pointer to key name in Wasm memory
+                // args(1) = size of key name
+                // args(2) = pointer to key in Wasm memory
+                // args(3) = size of key
+                let (name_ptr, name_size, key_ptr, key_size): (_, u32, _, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("name_size", name_size);
+                let ret = self.put_key_if_absent(name_ptr, name_size, key_ptr, key_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::RemoveKeyIfPresentIndex => {
+                // args(0) = pointer to key name in Wasm memory
+                // args(1) = size of key name
+                let (name_ptr, name_size): (_, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("name_size", name_size);
+                let ret = self.remove_key_if_present(name_ptr, name_size)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::NewPublicURefIndex => {
+                // args(0) = pointer to uref destination in Wasm memory
+                // args(1) = pointer to initial value
+                // args(2) = size of initial value
+                let (uref_ptr, value_ptr, value_size): (_, _, u32) = Args::parse(args)?;
+                scoped_instrumenter.add_property("value_size", value_size);
+                self.new_public_uref(uref_ptr, value_ptr, value_size)?;
+                Ok(None)
+            }
+
+            FunctionIndex::StoreFnWithSpecIndex => {
+                // args(0) = pointer to function name in Wasm memory
+                // args(1) = size of the name
+                // args(2) = pointer to named keys to be saved with the function body
+                // args(3) = size of the named keys
+                // args(4) = pointer to the function spec in Wasm memory
+                // args(5) = size of the function spec
+                // args(6) = pointer to a Wasm memory where we will save
+                //           hash of the new function
+                let (
+                    name_ptr,
+                    name_size,
+                    named_keys_ptr,
+                    named_keys_size,
+                    spec_ptr,
+                    spec_size,
+                    hash_ptr,
+                ): (_, u32, _, u32, _, u32, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("name_size", name_size);
+                let fn_bytes = self.get_function_by_name(name_ptr, name_size)?;
+                let contract_size = named_keys_size as usize + fn_bytes.len();
+                scoped_instrumenter.add_property("contract_size", contract_size);
+                let named_keys_bytes = self
+                    .memory
+                    .get(named_keys_ptr, named_keys_size as usize)
+                    .map_err(|e| Error::Interpreter(e.into()))?;
+                let named_keys =
+                    bytesrepr::deserialize(named_keys_bytes).map_err(Error::BytesRepr)?;
+                let spec_bytes = self
+                    .memory
+                    .get(spec_ptr, spec_size as usize)
+                    .map_err(|e| Error::Interpreter(e.into()))?;
+                let spec = bytesrepr::deserialize(spec_bytes).map_err(Error::BytesRepr)?;
+                let contract_hash = self.store_function_with_spec(fn_bytes, named_keys, spec)?;
+                self.function_address(contract_hash, hash_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::MigrateLocalKeysIndex => {
+                // args(0) = pointer to the old seed in Wasm memory
+                // args(1) = pointer to the number of migrated values (output)
+                let (old_seed_ptr, migrated_count_ptr) = Args::parse(args)?;
+                let ret = self.migrate_local_keys(old_seed_ptr, migrated_count_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetBalanceByURefIndex => {
+                // args(0) = pointer to purse input
+                // args(1) = length of purse
+                // args(2) = pointer to output size (output)
+                let (ptr, ptr_size, output_size_ptr): (_, u32, _) = Args::parse(args)?;
+                let ret =
+                    self.get_balance_by_uref_host_buffer(ptr, ptr_size as usize, output_size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetGasCounterIndex => {
+                // args(0) = pointer to Wasm memory where to write.
+                let dest_ptr = Args::parse(args)?;
+                // charged first, so that the counter includes the cost of this call
+                self.gas(Gas::new(GET_GAS_COUNTER_COST.into()))?;
+                self.get_gas_counter(dest_ptr)?;
+                Ok(None)
+            }
+
+            FunctionIndex::TryCallContractFuncIndex => {
+                // args(0) = pointer to key where contract is at in global state
+                // args(1) = size of key
+                // args(2) = pointer to function arguments in Wasm memory
+                // args(3) = size of arguments
+                // args(4) = pointer to result size (output)
+                let (key_ptr, key_size, args_ptr, args_size, result_size_ptr): (_, _, _, u32, _) =
+                    Args::parse(args)?;
+                scoped_instrumenter.add_property("args_size", args_size);
+
+                let key_contract: Key = self.key_from_mem(key_ptr, key_size)?;
+                let args_bytes: Vec<u8> = self.bytes_from_mem(args_ptr, args_size as usize)?;
+
+                let ret = self.try_call_contract_host_buffer(
+                    key_contract,
+                    args_bytes,
+                    result_size_ptr,
+                    scoped_instrumenter,
+                )?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetNamedArgSizeFuncIndex => {
+                // args(0) = pointer to name of host runtime arg to load
+                // args(1) = size of name
+                // args(2) = pointer to a argument size (output)
+                let (name_ptr, name_size, size_ptr): (_, u32, _) = Args::parse(args)?;
+                scoped_instrumenter.add_property("name_size", name_size);
+                let ret = self.get_named_arg_size(name_ptr, name_size, size_ptr)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::GetNamedArgFuncIndex => {
+                // args(0) = pointer to name of host runtime arg to load
+                // args(1) = size of name
+                // args(2) = pointer to destination in Wasm memory
+                // args(3) = size of destination pointer memory
+                let (name_ptr, name_size, dest_ptr, dest_size): (_, u32, _, u32) =
+                    Args::parse(args)?;
+                scoped_instrumenter.add_property("name_size", name_size);
+                scoped_instrumenter.add_property("dest_size", dest_size);
+                let ret = self.get_named_arg(name_ptr, name_size, dest_ptr, dest_size as usize)?;
+                Ok(Some(RuntimeValue::I32(api_error::i32_from(ret))))
+            }
+
+            FunctionIndex::PruneFuncIndex => {
+                // args(0) = pointer to key in Wasm memory
+                // args(1) = size of key
+                let (key_ptr, key_size) = Args::parse(args)?;
+                self.prune(key_ptr, key_size)?;
+                Ok(None)
+            }
+
             #[cfg(feature = "test-support")]
             FunctionIndex::PrintIndex => {
                 let (text_ptr, text_size): (_, u32) = Args::parse(args)?;
@@ -4,12 +4,13 @@ mod mint_internal;
 mod proof_of_stake_internal;
 mod scoped_instrumenter;
 mod standard_payment_internal;
+mod uref_scan;
 
 use std::{
     cmp,
     collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
-    iter::IntoIterator,
+    iter::{self, IntoIterator},
 };
 
 use itertools::Itertools;
@@ -24,21 +25,29 @@ use proof_of_stake::ProofOfStake;
 use standard_payment::StandardPayment;
 use types::{
     account::{ActionType, PublicKey, Weight},
-    bytesrepr::{self, FromBytes, ToBytes},
-    system_contract_errors,
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    is_reserved_key_name, system_contract_errors,
     system_contract_errors::mint,
-    AccessRights, ApiError, CLType, CLTyped, CLValue, Key, ProtocolVersion, SystemContractType,
-    TransferResult, TransferredTo, URef, U128, U256, U512,
+    system_contract_names::METHOD_ARG_INDEX,
+    AccessRights, ApiError, BlockTime, CLType, CLTyped, CLValue, FunctionSpec, Key,
+    ProtocolVersion, ScheduleId, SystemContractType, TransferResult, TransferredTo, URef,
+    KEY_LOCAL_SEED_LENGTH, PURE_FUNCTION_KEY_NAME, U128, U256, U512,
 };
 
 use crate::{
-    engine_state::{system_contract_cache::SystemContractCache, EngineConfig},
+    engine_state::{
+        genesis::POS_PAYMENT_PURSE,
+        scheduled_calls::{ScheduledCall, SCHEDULED_CALL_ESCROW},
+        system_contract_cache::SystemContractCache,
+        EngineConfig, SYSTEM_ACCOUNT_ADDR,
+    },
     execution::{Error, MINT_NAME, POS_NAME},
     resolvers::{create_module_resolver, memory_resolver::MemoryResolver},
     runtime_context::RuntimeContext,
     Address,
 };
 use scoped_instrumenter::ScopedInstrumenter;
+pub use uref_scan::{scan_urefs, URefScan};
 
 pub struct Runtime<'a, R> {
     system_contract_cache: SystemContractCache,
@@ -49,6 +58,15 @@ pub struct Runtime<'a, R> {
     context: RuntimeContext<'a, R>,
 }
 
+/// Returns the first protocol version in which a transfer to a uref which isn't a purse reports
+/// [`mint::Error::DestinationPurseNotFound`] to the calling contract rather than
+/// [`ApiError::Transfer`].
+///
+/// See [`Runtime::transfer_from_purse_to_purse`].
+fn destination_purse_not_found_protocol_version() -> ProtocolVersion {
+    ProtocolVersion::from_parts(1, 1, 0)
+}
+
 /// Rename function called `name` in the `module` to `call`.
 /// wasmi's entrypoint for a contracts is a function called `call`,
 /// so we have to rename function before storing it in the GlobalState.
@@ -134,1213 +152,6 @@ pub fn extract_access_rights_from_keys<I: IntoIterator<Item = Key>>(
         .collect()
 }
 
-#[allow(clippy::cognitive_complexity)]
-fn extract_urefs(cl_value: &CLValue) -> Result<Vec<URef>, Error> {
-    match cl_value.cl_type() {
-        CLType::Bool
-        | CLType::I32
-        | CLType::I64
-        | CLType::U8
-        | CLType::U32
-        | CLType::U64
-        | CLType::U128
-        | CLType::U256
-        | CLType::U512
-        | CLType::Unit
-        | CLType::String
-        | CLType::Any => Ok(vec![]),
-        CLType::Option(ty) => match **ty {
-            CLType::URef => {
-                let opt: Option<URef> = cl_value.to_owned().into_t()?;
-                Ok(opt.into_iter().collect())
-            }
-            CLType::Key => {
-                let opt: Option<Key> = cl_value.to_owned().into_t()?;
-                Ok(opt.into_iter().flat_map(Key::into_uref).collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::List(ty) => match **ty {
-            CLType::URef => Ok(cl_value.to_owned().into_t()?),
-            CLType::Key => {
-                let keys: Vec<Key> = cl_value.to_owned().into_t()?;
-                Ok(keys.into_iter().filter_map(Key::into_uref).collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 1) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 1] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 1] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 2) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 2] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 2] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 3) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 3] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 3] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 4) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 4] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 4] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 5) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 5] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 5] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 6) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 6] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 6] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 7) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 7] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 7] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 8) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 8] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 8] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 9) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 9] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 9] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 10) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 10] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 10] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 11) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 11] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 11] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 12) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 12] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 12] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 13) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 13] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 13] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 14) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 14] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 14] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 15) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 15] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 15] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 16) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 16] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 16] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 17) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 17] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 17] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 18) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 18] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 18] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 19) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 19] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 19] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 20) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 20] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 20] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 21) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 21] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 21] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 22) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 22] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 22] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 23) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 23] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 23] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 24) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 24] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 24] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 25) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 25] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 25] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 26) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 26] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 26] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 27) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 27] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 27] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 28) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 28] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 28] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 29) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 29] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 29] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 30) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 30] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 30] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 31) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 31] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 31] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 32) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 32] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 32] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 64) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 64] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 64] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 128) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 128] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 128] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 256) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 256] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 256] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(ty, 512) => match **ty {
-            CLType::URef => {
-                let arr: [URef; 512] = cl_value.to_owned().into_t()?;
-                Ok(arr.to_vec())
-            }
-            CLType::Key => {
-                let arr: [Key; 512] = cl_value.to_owned().into_t()?;
-                Ok(arr.iter().filter_map(Key::as_uref).cloned().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::FixedList(_ty, _) => Ok(vec![]),
-        CLType::Result { ok, err } => match (&**ok, &**err) {
-            (CLType::URef, CLType::Bool) => {
-                let res: Result<URef, bool> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::I32) => {
-                let res: Result<URef, i32> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::I64) => {
-                let res: Result<URef, i64> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::U8) => {
-                let res: Result<URef, u8> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::U32) => {
-                let res: Result<URef, u32> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::U64) => {
-                let res: Result<URef, u64> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::U128) => {
-                let res: Result<URef, U128> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::U256) => {
-                let res: Result<URef, U256> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::U512) => {
-                let res: Result<URef, U512> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::Unit) => {
-                let res: Result<URef, ()> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::String) => {
-                let res: Result<URef, String> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::URef, CLType::Key) => {
-                let res: Result<URef, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::URef, CLType::URef) => {
-                let res: Result<URef, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(uref) => Ok(vec![uref]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::Key, CLType::Bool) => {
-                let res: Result<Key, bool> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::I32) => {
-                let res: Result<Key, i32> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::I64) => {
-                let res: Result<Key, i64> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::U8) => {
-                let res: Result<Key, u8> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::U32) => {
-                let res: Result<Key, u32> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::U64) => {
-                let res: Result<Key, u64> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::U128) => {
-                let res: Result<Key, U128> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::U256) => {
-                let res: Result<Key, U256> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::U512) => {
-                let res: Result<Key, U512> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::Unit) => {
-                let res: Result<Key, ()> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::String) => {
-                let res: Result<Key, String> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(_) => Ok(vec![]),
-                }
-            }
-            (CLType::Key, CLType::URef) => {
-                let res: Result<Key, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::Key, CLType::Key) => {
-                let res: Result<Key, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(key) => Ok(key.into_uref().into_iter().collect()),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::Bool, CLType::URef) => {
-                let res: Result<bool, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::I32, CLType::URef) => {
-                let res: Result<i32, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::I64, CLType::URef) => {
-                let res: Result<i64, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::U8, CLType::URef) => {
-                let res: Result<u8, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::U32, CLType::URef) => {
-                let res: Result<u32, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::U64, CLType::URef) => {
-                let res: Result<u64, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::U128, CLType::URef) => {
-                let res: Result<U128, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::U256, CLType::URef) => {
-                let res: Result<U256, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::U512, CLType::URef) => {
-                let res: Result<U512, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::Unit, CLType::URef) => {
-                let res: Result<(), URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::String, CLType::URef) => {
-                let res: Result<String, URef> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(uref) => Ok(vec![uref]),
-                }
-            }
-            (CLType::Bool, CLType::Key) => {
-                let res: Result<bool, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::I32, CLType::Key) => {
-                let res: Result<i32, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::I64, CLType::Key) => {
-                let res: Result<i64, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::U8, CLType::Key) => {
-                let res: Result<u8, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::U32, CLType::Key) => {
-                let res: Result<u32, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::U64, CLType::Key) => {
-                let res: Result<u64, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::U128, CLType::Key) => {
-                let res: Result<U128, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::U256, CLType::Key) => {
-                let res: Result<U256, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::U512, CLType::Key) => {
-                let res: Result<U512, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::Unit, CLType::Key) => {
-                let res: Result<(), Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (CLType::String, CLType::Key) => {
-                let res: Result<String, Key> = cl_value.to_owned().into_t()?;
-                match res {
-                    Ok(_) => Ok(vec![]),
-                    Err(key) => Ok(key.into_uref().into_iter().collect()),
-                }
-            }
-            (_, _) => Ok(vec![]),
-        },
-        CLType::Map { key, value } => match (&**key, &**value) {
-            (CLType::URef, CLType::Bool) => {
-                let map: BTreeMap<URef, bool> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::I32) => {
-                let map: BTreeMap<URef, i32> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::I64) => {
-                let map: BTreeMap<URef, i64> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::U8) => {
-                let map: BTreeMap<URef, u8> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::U32) => {
-                let map: BTreeMap<URef, u32> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::U64) => {
-                let map: BTreeMap<URef, u64> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::U128) => {
-                let map: BTreeMap<URef, U128> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::U256) => {
-                let map: BTreeMap<URef, U256> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::U512) => {
-                let map: BTreeMap<URef, U512> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::Unit) => {
-                let map: BTreeMap<URef, ()> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::String) => {
-                let map: BTreeMap<URef, String> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().collect())
-            }
-            (CLType::URef, CLType::Key) => {
-                let map: BTreeMap<URef, Key> = cl_value.to_owned().into_t()?;
-                Ok(map
-                    .keys()
-                    .cloned()
-                    .chain(map.values().cloned().filter_map(Key::into_uref))
-                    .collect())
-            }
-            (CLType::URef, CLType::URef) => {
-                let map: BTreeMap<URef, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().chain(map.values().cloned()).collect())
-            }
-            (CLType::Key, CLType::Bool) => {
-                let map: BTreeMap<Key, bool> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::I32) => {
-                let map: BTreeMap<Key, i32> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::I64) => {
-                let map: BTreeMap<Key, i64> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::U8) => {
-                let map: BTreeMap<Key, u8> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::U32) => {
-                let map: BTreeMap<Key, u32> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::U64) => {
-                let map: BTreeMap<Key, u64> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::U128) => {
-                let map: BTreeMap<Key, U128> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::U256) => {
-                let map: BTreeMap<Key, U256> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::U512) => {
-                let map: BTreeMap<Key, U512> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::Unit) => {
-                let map: BTreeMap<Key, ()> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::String) => {
-                let map: BTreeMap<Key, String> = cl_value.to_owned().into_t()?;
-                Ok(map.keys().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Key, CLType::URef) => {
-                let map: BTreeMap<Key, URef> = cl_value.to_owned().into_t()?;
-                Ok(map
-                    .keys()
-                    .cloned()
-                    .filter_map(Key::into_uref)
-                    .chain(map.values().cloned())
-                    .collect())
-            }
-            (CLType::Key, CLType::Key) => {
-                let map: BTreeMap<Key, Key> = cl_value.to_owned().into_t()?;
-                Ok(map
-                    .keys()
-                    .cloned()
-                    .filter_map(Key::into_uref)
-                    .chain(map.values().cloned().filter_map(Key::into_uref))
-                    .collect())
-            }
-            (CLType::Bool, CLType::URef) => {
-                let map: BTreeMap<bool, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::I32, CLType::URef) => {
-                let map: BTreeMap<i32, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::I64, CLType::URef) => {
-                let map: BTreeMap<i64, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::U8, CLType::URef) => {
-                let map: BTreeMap<u8, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::U32, CLType::URef) => {
-                let map: BTreeMap<u32, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::U64, CLType::URef) => {
-                let map: BTreeMap<u64, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::U128, CLType::URef) => {
-                let map: BTreeMap<U128, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::U256, CLType::URef) => {
-                let map: BTreeMap<U256, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::U512, CLType::URef) => {
-                let map: BTreeMap<U512, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::Unit, CLType::URef) => {
-                let map: BTreeMap<(), URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::String, CLType::URef) => {
-                let map: BTreeMap<String, URef> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().collect())
-            }
-            (CLType::Bool, CLType::Key) => {
-                let map: BTreeMap<bool, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::I32, CLType::Key) => {
-                let map: BTreeMap<i32, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::I64, CLType::Key) => {
-                let map: BTreeMap<i64, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::U8, CLType::Key) => {
-                let map: BTreeMap<u8, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::U32, CLType::Key) => {
-                let map: BTreeMap<u32, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::U64, CLType::Key) => {
-                let map: BTreeMap<u64, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::U128, CLType::Key) => {
-                let map: BTreeMap<U128, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::U256, CLType::Key) => {
-                let map: BTreeMap<U256, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::U512, CLType::Key) => {
-                let map: BTreeMap<U512, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::Unit, CLType::Key) => {
-                let map: BTreeMap<(), Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (CLType::String, CLType::Key) => {
-                let map: BTreeMap<String, Key> = cl_value.to_owned().into_t()?;
-                Ok(map.values().cloned().filter_map(Key::into_uref).collect())
-            }
-            (_, _) => Ok(vec![]),
-        },
-        CLType::Tuple1([ty]) => match **ty {
-            CLType::URef => {
-                let val: (URef,) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            CLType::Key => {
-                let val: (Key,) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            _ => Ok(vec![]),
-        },
-        CLType::Tuple2([ty1, ty2]) => match (&**ty1, &**ty2) {
-            (CLType::URef, CLType::Bool) => {
-                let val: (URef, bool) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::I32) => {
-                let val: (URef, i32) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::I64) => {
-                let val: (URef, i64) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::U8) => {
-                let val: (URef, u8) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::U32) => {
-                let val: (URef, u32) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::U64) => {
-                let val: (URef, u64) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::U128) => {
-                let val: (URef, U128) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::U256) => {
-                let val: (URef, U256) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::U512) => {
-                let val: (URef, U512) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::Unit) => {
-                let val: (URef, ()) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::String) => {
-                let val: (URef, String) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0])
-            }
-            (CLType::URef, CLType::Key) => {
-                let val: (URef, Key) = cl_value.to_owned().into_t()?;
-                let mut res = vec![val.0];
-                res.extend(val.1.into_uref().into_iter());
-                Ok(res)
-            }
-            (CLType::URef, CLType::URef) => {
-                let val: (URef, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.0, val.1])
-            }
-            (CLType::Key, CLType::Bool) => {
-                let val: (Key, bool) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::I32) => {
-                let val: (Key, i32) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::I64) => {
-                let val: (Key, i64) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::U8) => {
-                let val: (Key, u8) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::U32) => {
-                let val: (Key, u32) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::U64) => {
-                let val: (Key, u64) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::U128) => {
-                let val: (Key, U128) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::U256) => {
-                let val: (Key, U256) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::U512) => {
-                let val: (Key, U512) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::Unit) => {
-                let val: (Key, ()) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::String) => {
-                let val: (Key, String) = cl_value.to_owned().into_t()?;
-                Ok(val.0.into_uref().into_iter().collect())
-            }
-            (CLType::Key, CLType::URef) => {
-                let val: (Key, URef) = cl_value.to_owned().into_t()?;
-                let mut res: Vec<URef> = val.0.into_uref().into_iter().collect();
-                res.push(val.1);
-                Ok(res)
-            }
-            (CLType::Key, CLType::Key) => {
-                let val: (Key, Key) = cl_value.to_owned().into_t()?;
-                Ok(val
-                    .0
-                    .into_uref()
-                    .into_iter()
-                    .chain(val.1.into_uref().into_iter())
-                    .collect())
-            }
-            (CLType::Bool, CLType::URef) => {
-                let val: (bool, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::I32, CLType::URef) => {
-                let val: (i32, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::I64, CLType::URef) => {
-                let val: (i64, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::U8, CLType::URef) => {
-                let val: (u8, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::U32, CLType::URef) => {
-                let val: (u32, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::U64, CLType::URef) => {
-                let val: (u64, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::U128, CLType::URef) => {
-                let val: (U128, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::U256, CLType::URef) => {
-                let val: (U256, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::U512, CLType::URef) => {
-                let val: (U512, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::Unit, CLType::URef) => {
-                let val: ((), URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::String, CLType::URef) => {
-                let val: (String, URef) = cl_value.to_owned().into_t()?;
-                Ok(vec![val.1])
-            }
-            (CLType::Bool, CLType::Key) => {
-                let val: (bool, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::I32, CLType::Key) => {
-                let val: (i32, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::I64, CLType::Key) => {
-                let val: (i64, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::U8, CLType::Key) => {
-                let val: (u8, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::U32, CLType::Key) => {
-                let val: (u32, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::U64, CLType::Key) => {
-                let val: (u64, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::U128, CLType::Key) => {
-                let val: (U128, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::U256, CLType::Key) => {
-                let val: (U256, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::U512, CLType::Key) => {
-                let val: (U512, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::Unit, CLType::Key) => {
-                let val: ((), Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (CLType::String, CLType::Key) => {
-                let val: (String, Key) = cl_value.to_owned().into_t()?;
-                Ok(val.1.into_uref().into_iter().collect())
-            }
-            (_, _) => Ok(vec![]),
-        },
-        // TODO: nested matches for Tuple3?
-        CLType::Tuple3(_) => Ok(vec![]),
-        CLType::Key => {
-            let key: Key = cl_value.to_owned().into_t()?; // TODO: optimize?
-            Ok(key.into_uref().into_iter().collect())
-        }
-        CLType::URef => {
-            let uref: URef = cl_value.to_owned().into_t()?; // TODO: optimize?
-            Ok(vec![uref])
-        }
-    }
-}
-
 impl<'a, R> Runtime<'a, R>
 where
     R: StateReader<Key, StoredValue>,
@@ -1385,6 +196,10 @@ where
     /// Intuition about the return value sense is to answer the question 'are we
     /// allowed to continue?'
     fn charge_gas(&mut self, amount: Gas) -> bool {
+        // System executions aren't metered.
+        if self.context.is_system() {
+            return true;
+        }
         let prev = self.context.gas_counter();
         match prev.checked_add(amount) {
             // gas charge overflow protection
@@ -1425,9 +240,27 @@ where
         bytesrepr::deserialize(bytes).map_err(Into::into)
     }
 
-    fn string_from_mem(&self, ptr: u32, size: u32) -> Result<String, Trap> {
+    /// Reads a serialized string (defined as `ptr` and `size` tuple) from Wasm memory.
+    ///
+    /// Every host function taking a string reads it through here: the string must be valid UTF-8
+    /// and no longer than the chainspec's `max_string_length`, and gas is charged for each of its
+    /// bytes.
+    fn string_from_mem(&mut self, ptr: u32, size: u32) -> Result<String, Trap> {
+        let wasm_costs = self.protocol_data().wasm_costs();
+
+        // Checked against the declared size, so an oversized string is never copied out of memory.
+        let length = (size as usize).saturating_sub(U32_SERIALIZED_LENGTH);
+        let max_length = wasm_costs.max_string_length;
+        if max_length != 0 && length > max_length as usize {
+            return Err(Error::StringTooLong { length, max_length }.into());
+        }
+        self.gas(Gas::new(
+            U512::from(wasm_costs.string_byte) * U512::from(length),
+        ))?;
+
         let bytes = self.bytes_from_mem(ptr, size as usize)?;
-        bytesrepr::deserialize(bytes).map_err(|e| Error::BytesRepr(e).into())
+        let string_bytes: Vec<u8> = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        String::from_utf8(string_bytes).map_err(|_| Error::InvalidUtf8.into())
     }
 
     fn get_function_by_name(&mut self, name_ptr: u32, name_size: u32) -> Result<Vec<u8>, Trap> {
@@ -1463,6 +296,16 @@ where
         Ok(self.context.validate_uref(&uref).is_ok())
     }
 
+    fn access_rights(
+        &mut self,
+        uref_ptr: u32,
+        uref_size: u32,
+    ) -> Result<Option<AccessRights>, Trap> {
+        let bytes = self.bytes_from_mem(uref_ptr, uref_size as usize)?;
+        let uref: URef = bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?;
+        Ok(self.context.effective_access_rights(uref.addr()))
+    }
+
     fn get_arg_size(&mut self, index: usize, size_ptr: u32) -> Result<Result<(), ApiError>, Trap> {
         let arg_size = match self.context.args().get(index) {
             Some(arg) if arg.inner_bytes().len() > u32::max_value() as usize => {
@@ -1506,6 +349,66 @@ where
         Ok(Ok(()))
     }
 
+    /// Like [`get_arg_size`](Self::get_arg_size), for the argument named by the string read from
+    /// the Wasm memory rather than the one at a given position.
+    fn get_named_arg_size(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        match self.context.arg_index(&name) {
+            Some(index) => self.get_arg_size(index, size_ptr),
+            None => Ok(Err(ApiError::MissingArgument)),
+        }
+    }
+
+    /// Like [`get_arg`](Self::get_arg), for the argument named by the string read from the Wasm
+    /// memory rather than the one at a given position.
+    fn get_named_arg(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        output_ptr: u32,
+        output_size: usize,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        match self.context.arg_index(&name) {
+            Some(index) => self.get_arg(index, output_ptr, output_size),
+            None => Ok(Err(ApiError::MissingArgument)),
+        }
+    }
+
+    /// Checks that the argument at `index` was passed with the `CLType` read from the Wasm memory.
+    ///
+    /// Arguments passed as [`CLType::Any`] predate typed arguments and are accepted as any type.
+    /// Any other mismatch stops execution with [`Error::ArgumentTypeMismatch`].
+    fn check_arg_type(
+        &mut self,
+        index: usize,
+        cl_type_ptr: u32,
+        cl_type_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let expected: CLType = {
+            let bytes = self.bytes_from_mem(cl_type_ptr, cl_type_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        let found = match self.context.args().get(index) {
+            Some(arg) => arg.cl_type().clone(),
+            None => return Ok(Err(ApiError::MissingArgument)),
+        };
+        if found != CLType::Any && found != expected {
+            return Err(Error::ArgumentTypeMismatch {
+                index,
+                expected,
+                found,
+            }
+            .into());
+        }
+        Ok(Ok(()))
+    }
+
     /// Load the uref known by the given name into the Wasm memory
     fn load_key(
         &mut self,
@@ -1565,16 +468,70 @@ where
         key_size: u32,
     ) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
+        self.validate_key_name(&name)?;
         let key = self.key_from_mem(key_ptr, key_size)?;
         self.context.put_key(name, key).map_err(Into::into)
     }
 
     fn remove_key(&mut self, name_ptr: u32, name_size: u32) -> Result<(), Trap> {
         let name = self.string_from_mem(name_ptr, name_size)?;
+        self.validate_key_name(&name)?;
         self.context.remove_key(&name)?;
         Ok(())
     }
 
+    /// Like [`put_key`](Runtime::put_key), but returns `ApiError::NamedKeyAlreadyExists` instead
+    /// of replacing a key already stored under the name.
+    fn put_key_if_absent(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        key_ptr: u32,
+        key_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        self.validate_key_name(&name)?;
+        if self.context.named_keys_contains_key(&name) {
+            return Ok(Err(ApiError::NamedKeyAlreadyExists));
+        }
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        self.context.put_key(name, key)?;
+        Ok(Ok(()))
+    }
+
+    /// Like [`remove_key`](Runtime::remove_key), but returns `ApiError::NamedKeyNotFound` if no key
+    /// is stored under the name.
+    fn remove_key_if_present(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        self.validate_key_name(&name)?;
+        if !self.context.named_keys_contains_key(&name) {
+            return Ok(Err(ApiError::NamedKeyNotFound));
+        }
+        self.context.remove_key(&name)?;
+        Ok(Ok(()))
+    }
+
+    /// Returns `Error::ReservedKeyName` if `name` is reserved for system-managed named keys and the
+    /// current context may not manage them.
+    ///
+    /// Reserved keys are managed by system execution contexts and by the system contracts, which
+    /// keep their own bookkeeping under reserved names even when called from a user deploy.
+    fn validate_key_name(&self, name: &str) -> Result<(), Error> {
+        let base_key = self.context.base_key();
+        if is_reserved_key_name(name)
+            && !self.context.is_system()
+            && !self.is_mint(base_key)
+            && !self.is_proof_of_stake(base_key)
+        {
+            return Err(Error::ReservedKeyName(name.to_string()));
+        }
+        Ok(())
+    }
+
     /// Writes runtime context's account main purse to [dest_ptr] in the Wasm memory.
     fn get_main_purse(&mut self, dest_ptr: u32) -> Result<(), Trap> {
         let purse = self.context.get_main_purse()?;
@@ -1584,6 +541,80 @@ where
             .map_err(|e| Error::Interpreter(e.into()).into())
     }
 
+    /// Replaces the account's main purse with a new purse holding its full balance, revokes the
+    /// replaced purse, and writes the new purse to [dest_ptr] in the Wasm memory.
+    ///
+    /// The authorization keys must meet the account's key management threshold.  Any failure once
+    /// the new purse has been created is fatal, so that either all or none of the changes are made.
+    fn rotate_main_purse(&mut self, dest_ptr: u32) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_mutable()?;
+
+        if !self
+            .context
+            .account()
+            .can_manage_keys_with(self.context.authorization_keys())
+        {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+
+        let mint_contract_key = self.get_mint_contract_uref().into();
+        let new_main_purse = self.mint_create(mint_contract_key)?;
+        let previous_main_purse = self.context.set_main_purse(new_main_purse)?;
+
+        let balance = self.get_balance(previous_main_purse)?.unwrap_or_default();
+        if !balance.is_zero() {
+            self.mint_transfer(
+                mint_contract_key,
+                previous_main_purse,
+                new_main_purse,
+                balance,
+            )?;
+        }
+        self.mint_revoke(mint_contract_key, previous_main_purse)?;
+
+        let purse_bytes = new_main_purse.into_bytes().map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &purse_bytes)
+            .map_err(|e| Error::Interpreter(e.into()))?;
+        Ok(Ok(()))
+    }
+
+    /// Sets the account's authorization contract to the `Option<Key>` read from the Wasm memory,
+    /// or clears it if the option is `None`.
+    ///
+    /// The authorization keys must meet the account's key management threshold, and a new
+    /// authorization contract must be a stored contract.
+    fn set_authorization_contract(
+        &mut self,
+        contract_ptr: u32,
+        contract_size: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_mutable()?;
+
+        if !self
+            .context
+            .account()
+            .can_manage_keys_with(self.context.authorization_keys())
+        {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+
+        let authorization_contract: Option<Key> = {
+            let bytes = self.bytes_from_mem(contract_ptr, contract_size as usize)?;
+            bytesrepr::deserialize(bytes)?
+        };
+        if let Some(key) = authorization_contract {
+            match self.context.read_gs(&key)? {
+                Some(StoredValue::Contract(_)) => (),
+                _ => return Ok(Err(ApiError::InvalidArgument)),
+            }
+        }
+
+        self.context
+            .set_authorization_contract(authorization_contract)?;
+        Ok(Ok(()))
+    }
+
     /// Writes caller (deploy) account public key to [dest_ptr] in the Wasm
     /// memory.
     fn get_caller(&mut self, output_size: u32) -> Result<Result<(), ApiError>, Trap> {
@@ -1607,6 +638,27 @@ where
         Ok(Ok(()))
     }
 
+    /// Writes the key of the account or contract which called the current context into the host
+    /// buffer, and its serialized size to [output_size] in the Wasm memory.
+    fn get_caller_key(&mut self, output_size: u32) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+        let value = CLValue::from_t(self.context.caller_key()).map_err(Error::CLValue)?;
+        let value_size = value.inner_bytes().len();
+
+        if let Err(error) = self.write_host_buffer(value) {
+            return Ok(Err(error));
+        }
+
+        let output_size_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size, &output_size_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+        Ok(Ok(()))
+    }
+
     /// Writes runtime context's phase to [dest_ptr] in the Wasm memory.
     fn get_phase(&mut self, dest_ptr: u32) -> Result<(), Trap> {
         let phase = self.context.phase();
@@ -1628,6 +680,21 @@ where
             .map_err(|e| Error::Interpreter(e.into()).into())
     }
 
+    /// Writes the gas consumed so far by the deploy to `dest_ptr`, saturating at
+    /// `u64::max_value()`.
+    fn get_gas_counter(&self, dest_ptr: u32) -> Result<(), Trap> {
+        let gas_counter = self.context.gas_counter().value();
+        let gas_counter = if gas_counter > U512::from(u64::max_value()) {
+            u64::max_value()
+        } else {
+            gas_counter.as_u64()
+        };
+        let gas_counter = gas_counter.into_bytes().map_err(Error::BytesRepr)?;
+        self.memory
+            .set(dest_ptr, &gas_counter)
+            .map_err(|e| Error::Interpreter(e.into()).into())
+    }
+
     /// Return some bytes from the memory and terminate the current `sub_call`. Note that the return
     /// type is `Trap`, indicating that this function will always kill the current Wasm instance.
     fn ret(
@@ -1648,8 +715,8 @@ where
                 // enum indicating that the reason for exiting the module was a call to ret.
                 self.host_buffer = bytesrepr::deserialize(buf).ok();
 
-                let urefs = match &self.host_buffer {
-                    Some(buf) => extract_urefs(buf),
+                let urefs = match self.host_buffer.clone() {
+                    Some(buf) => self.scan_for_urefs(&[buf]),
                     None => Ok(vec![]),
                 };
                 match urefs {
@@ -1684,6 +751,24 @@ where
         }
     }
 
+    /// Returns `true` if the arguments passed by this context to the contracts it calls are
+    /// supplied by the engine, i.e. this is a system context whose own arguments were supplied by
+    /// the engine, running the system account's code or a system contract.
+    fn passes_engine_supplied_args(&self) -> bool {
+        if !self.context.is_system() || !self.context.args_supplied_by_engine() {
+            return false;
+        }
+        match self.context.base_key() {
+            Key::Account(public_key) => public_key == SYSTEM_ACCOUNT_ADDR,
+            Key::URef(uref) => self
+                .protocol_data()
+                .system_contracts()
+                .iter()
+                .any(|system_contract| system_contract.addr() == uref.addr()),
+            _ => false,
+        }
+    }
+
     fn get_argument<T: FromBytes + CLTyped>(args: &[CLValue], index: usize) -> Result<T, Error> {
         let arg: CLValue = args
             .get(index)
@@ -1693,6 +778,26 @@ where
             .map_err(|_| Error::Revert(ApiError::InvalidArgument))
     }
 
+    /// Like [`Self::get_argument`], but an argument passed with a type other than `T` fails with
+    /// [`Error::ArgumentTypeMismatch`] naming both types.
+    fn get_typed_argument<T: FromBytes + CLTyped>(
+        args: &[CLValue],
+        index: usize,
+    ) -> Result<T, Error> {
+        let arg: &CLValue = args
+            .get(index)
+            .ok_or_else(|| Error::Revert(ApiError::MissingArgument))?;
+        let expected = T::cl_type();
+        if *arg.cl_type() != CLType::Any && *arg.cl_type() != expected {
+            return Err(Error::ArgumentTypeMismatch {
+                index,
+                expected,
+                found: arg.cl_type().clone(),
+            });
+        }
+        Self::get_argument(args, index)
+    }
+
     fn reverter<T: Into<ApiError>>(error: T) -> Error {
         let api_error: ApiError = error.into();
         Error::Revert(api_error)
@@ -1705,10 +810,10 @@ where
         args: &[CLValue],
         extra_urefs: &[Key],
     ) -> Result<CLValue, Error> {
-        const METHOD_MINT: &str = "mint";
-        const METHOD_CREATE: &str = "create";
-        const METHOD_BALANCE: &str = "balance";
-        const METHOD_TRANSFER: &str = "transfer";
+        use types::system_contract_names::mint::{
+            METHOD_ALLOWANCE, METHOD_APPROVE, METHOD_BALANCE, METHOD_CREATE, METHOD_MINT,
+            METHOD_REVOKE, METHOD_TRANSFER, METHOD_TRANSFER_FROM,
+        };
 
         let state = self.context.state();
         let access_rights = {
@@ -1750,8 +855,15 @@ where
             phase,
             protocol_data,
         );
+        mint_context.set_read_only(self.context.is_read_only());
+        mint_context.set_kind(self.context.kind());
+        mint_context.set_args_supplied_by_engine(self.passes_engine_supplied_args());
+        mint_context.set_transient_storage(self.context.transient_storage());
+        mint_context.set_host_call_stats(self.context.host_call_stats());
+        mint_context.set_pure_call_cache(self.context.pure_call_cache());
+        mint_context.set_caller_key(self.context.base_key());
 
-        let method_name: String = Self::get_argument(&args, 0)?;
+        let method_name: String = Self::get_argument(&args, METHOD_ARG_INDEX as usize)?;
 
         let ret: CLValue = match method_name.as_str() {
             // Type: `fn mint(amount: U512) -> Result<URef, Error>`
@@ -1780,9 +892,41 @@ where
                 let result: Result<(), mint::Error> = mint_context.transfer(source, target, amount);
                 CLValue::from_t(result).map_err(Self::reverter)?
             }
+            // Type: `fn revoke(purse: URef) -> Result<(), Error>`
+            METHOD_REVOKE => {
+                let purse: URef = Self::get_argument(&args, 1)?;
+                let result: Result<(), mint::Error> = mint_context.revoke(purse);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn approve(purse: URef, spender: Key, amount: U512) -> Result<(), Error>`
+            METHOD_APPROVE => {
+                let purse: URef = Self::get_argument(&args, 1)?;
+                let spender: Key = Self::get_argument(&args, 2)?;
+                let amount: U512 = Self::get_argument(&args, 3)?;
+                let result: Result<(), mint::Error> = mint_context.approve(purse, spender, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
+            // Type: `fn allowance(purse: URef, spender: Key) -> U512`
+            METHOD_ALLOWANCE => {
+                let purse: URef = Self::get_argument(&args, 1)?;
+                let spender: Key = Self::get_argument(&args, 2)?;
+                let allowance: U512 = mint_context
+                    .allowance(purse, spender)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(allowance).map_err(Self::reverter)?
+            }
+            // Type: `fn transfer_from(owner: URef, dest: URef, amount: U512) -> Result<(), Error>`
+            METHOD_TRANSFER_FROM => {
+                let owner_purse: URef = Self::get_argument(&args, 1)?;
+                let target: URef = Self::get_argument(&args, 2)?;
+                let amount: U512 = Self::get_argument(&args, 3)?;
+                let result: Result<(), mint::Error> =
+                    mint_context.transfer_from(owner_purse, target, amount);
+                CLValue::from_t(result).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
-        let urefs = extract_urefs(&ret)?;
+        let urefs = scan_urefs(&ret)?.urefs;
         let access_rights = extract_access_rights_from_urefs(urefs);
         self.context.access_rights_extend(access_rights);
         Ok(ret)
@@ -1795,12 +939,14 @@ where
         args: &[CLValue],
         extra_urefs: &[Key],
     ) -> Result<CLValue, Error> {
-        const METHOD_BOND: &str = "bond";
-        const METHOD_UNBOND: &str = "unbond";
-        const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
-        const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
-        const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
-        const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+        use types::system_contract_names::pos::{
+            BOND_ARG_AMOUNT_INDEX, BOND_ARG_PURSE_INDEX, FINALIZE_PAYMENT_ARG_ACCOUNT_INDEX,
+            FINALIZE_PAYMENT_ARG_AMOUNT_SPENT_INDEX, METHOD_BOND, METHOD_FINALIZE_PAYMENT,
+            METHOD_GET_PAYMENT_PURSE, METHOD_GET_PAYMENT_PURSE_BALANCE, METHOD_GET_REFUND_PURSE,
+            METHOD_MIGRATE_RESERVED_KEYS, METHOD_MIGRATE_STAKES, METHOD_SET_REFUND_PURSE,
+            METHOD_SLASH, METHOD_UNBOND, MIGRATE_STAKES_ARG_MAX_COUNT_INDEX,
+            SET_REFUND_PURSE_ARG_PURSE_INDEX, SLASH_ARG_VALIDATOR_INDEX, UNBOND_ARG_AMOUNT_INDEX,
+        };
 
         let state = self.context.state();
         let access_rights = {
@@ -1823,7 +969,7 @@ where
         let phase = self.context.phase();
         let protocol_data = self.context.protocol_data();
 
-        let runtime_context = RuntimeContext::new(
+        let mut runtime_context = RuntimeContext::new(
             state,
             &mut named_keys,
             access_rights,
@@ -1842,6 +988,13 @@ where
             phase,
             protocol_data,
         );
+        runtime_context.set_read_only(self.context.is_read_only());
+        runtime_context.set_kind(self.context.kind());
+        runtime_context.set_args_supplied_by_engine(self.passes_engine_supplied_args());
+        runtime_context.set_transient_storage(self.context.transient_storage());
+        runtime_context.set_host_call_stats(self.context.host_call_stats());
+        runtime_context.set_pure_call_cache(self.context.pure_call_cache());
+        runtime_context.set_caller_key(self.context.base_key());
 
         let mut runtime = Runtime::new(
             self.config,
@@ -1851,7 +1004,7 @@ where
             runtime_context,
         );
 
-        let method_name: String = Self::get_argument(&args, 0)?;
+        let method_name: String = Self::get_argument(&args, METHOD_ARG_INDEX as usize)?;
 
         let ret: CLValue = match method_name.as_str() {
             METHOD_BOND => {
@@ -1861,8 +1014,8 @@ where
                 }
 
                 let validator: PublicKey = runtime.context.get_caller();
-                let amount: U512 = Self::get_argument(&args, 1)?;
-                let source_uref: URef = Self::get_argument(&args, 2)?;
+                let amount: U512 = Self::get_argument(&args, BOND_ARG_AMOUNT_INDEX as usize)?;
+                let source_uref: URef = Self::get_argument(&args, BOND_ARG_PURSE_INDEX as usize)?;
                 runtime
                     .bond(validator, amount, source_uref)
                     .map_err(Self::reverter)?;
@@ -1875,19 +1028,33 @@ where
                 }
 
                 let validator: PublicKey = runtime.context.get_caller();
-                let maybe_amount: Option<U512> = Self::get_argument(&args, 1)?;
+                let maybe_amount: Option<U512> =
+                    Self::get_argument(&args, UNBOND_ARG_AMOUNT_INDEX as usize)?;
                 runtime
                     .unbond(validator, maybe_amount)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_SLASH => {
+                let validator: PublicKey =
+                    Self::get_argument(&args, SLASH_ARG_VALIDATOR_INDEX as usize)?;
+                runtime.slash(validator).map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             METHOD_GET_PAYMENT_PURSE => {
                 let rights_controlled_purse =
                     runtime.get_payment_purse().map_err(Self::reverter)?;
                 CLValue::from_t(rights_controlled_purse).map_err(Self::reverter)?
             }
+            METHOD_GET_PAYMENT_PURSE_BALANCE => {
+                let balance = runtime
+                    .get_payment_purse_balance()
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(balance).map_err(Self::reverter)?
+            }
             METHOD_SET_REFUND_PURSE => {
-                let purse: URef = Self::get_argument(&args, 1)?;
+                let purse: URef =
+                    Self::get_argument(&args, SET_REFUND_PURSE_ARG_PURSE_INDEX as usize)?;
                 runtime.set_refund_purse(purse).map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
@@ -1896,16 +1063,33 @@ where
                 CLValue::from_t(maybe_purse).map_err(Self::reverter)?
             }
             METHOD_FINALIZE_PAYMENT => {
-                let amount_spent: U512 = Self::get_argument(&args, 1)?;
-                let account: PublicKey = Self::get_argument(&args, 2)?;
+                let amount_spent: U512 = Self::get_typed_argument(
+                    &args,
+                    FINALIZE_PAYMENT_ARG_AMOUNT_SPENT_INDEX as usize,
+                )?;
+                let account: PublicKey =
+                    Self::get_typed_argument(&args, FINALIZE_PAYMENT_ARG_ACCOUNT_INDEX as usize)?;
                 runtime
                     .finalize_payment(amount_spent, account)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(()).map_err(Self::reverter)?
             }
+            METHOD_MIGRATE_STAKES => {
+                let max_count: u32 = if args.len() > MIGRATE_STAKES_ARG_MAX_COUNT_INDEX as usize {
+                    Self::get_typed_argument(&args, MIGRATE_STAKES_ARG_MAX_COUNT_INDEX as usize)?
+                } else {
+                    u32::max_value()
+                };
+                let remaining_count = runtime.migrate_stakes(max_count).map_err(Self::reverter)?;
+                CLValue::from_t(remaining_count).map_err(Self::reverter)?
+            }
+            METHOD_MIGRATE_RESERVED_KEYS => {
+                runtime.migrate_reserved_keys().map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)?
+            }
             _ => CLValue::from_t(()).map_err(Self::reverter)?,
         };
-        let urefs = extract_urefs(&ret)?;
+        let urefs = scan_urefs(&ret)?.urefs;
         let access_rights = extract_access_rights_from_urefs(urefs);
         self.context.access_rights_extend(access_rights);
         Ok(ret)
@@ -1922,6 +1106,47 @@ where
 
     /// Calls contract living under a `key`, with supplied `args`.
     pub fn call_contract(&mut self, key: Key, args_bytes: Vec<u8>) -> Result<CLValue, Error> {
+        self.call_contract_with_mode(key, args_bytes, false)
+    }
+
+    /// Calls contract living under a `key`, with supplied `args`, catching a revert of the callee.
+    ///
+    /// If the callee, or any contract it calls in turn, reverts, all the changes they made to
+    /// global state, to our named keys and to the transient storage are discarded and the error
+    /// they reverted with is returned in the inner `Err`.  Gas used by the call is still charged.
+    fn try_call_contract(
+        &mut self,
+        key: Key,
+        args_bytes: Vec<u8>,
+    ) -> Result<Result<CLValue, ApiError>, Error> {
+        let checkpoint = self.context.state().borrow().checkpoint();
+        let named_keys = self.context.named_keys().clone();
+        let transient_storage = self.context.transient_storage().borrow().clone();
+
+        match self.call_contract_with_mode(key, args_bytes, false) {
+            Ok(result) => Ok(Ok(result)),
+            Err(Error::Revert(error)) | Err(Error::RevertWithData(error, _)) => {
+                self.context.state().borrow_mut().restore(checkpoint);
+                self.context.set_named_keys(named_keys);
+                *self.context.transient_storage().borrow_mut() = transient_storage;
+                Ok(Err(error))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Calls contract living under a `key`, with supplied `args`.
+    ///
+    /// If `read_only` is set, or the current context is itself read-only, the callee and any
+    /// contracts it calls in turn are unable to modify global state.
+    fn call_contract_with_mode(
+        &mut self,
+        key: Key,
+        args_bytes: Vec<u8>,
+        read_only: bool,
+    ) -> Result<CLValue, Error> {
+        let read_only = read_only || self.context.is_read_only();
+
         let contract = match self.context.read_gs(&key)? {
             Some(StoredValue::Contract(contract)) => contract,
             Some(_) => {
@@ -1943,20 +1168,111 @@ where
             });
         }
 
-        let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes)?;
+        let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes.clone())?;
+
+        // Urefs nested anywhere in the arguments are passed to the callee, so the caller needn't
+        // list them separately
+        let extra_urefs: Vec<Key> = self
+            .scan_args_for_urefs(&args)?
+            .into_iter()
+            .map(<Key as From<URef>>::from)
+            .collect();
+        // The caller may pass urefs with narrowed access rights, but never with more rights than it
+        // holds. The callee's context is then granted only the rights carried by the passed urefs.
+        for key in &extra_urefs {
+            self.context.validate_key(key)?;
+        }
+
+        if contract.named_keys().contains_key(PURE_FUNCTION_KEY_NAME) {
+            return self.call_pure_contract(key, contract, args_bytes, args, extra_urefs);
+        }
+
+        self.execute_contract(key, contract, args, extra_urefs, read_only)
+    }
 
-        let mut extra_urefs = vec![];
-        // A loop is needed to be able to use the '?' operator
-        for arg in &args {
-            extra_urefs.extend(
-                extract_urefs(arg)?
-                    .into_iter()
-                    .map(<Key as From<URef>>::from),
-            );
+    /// Returns the urefs held by `values`, charging gas for each element scanned to find them.
+    fn scan_for_urefs(&mut self, values: &[CLValue]) -> Result<Vec<URef>, Error> {
+        let mut urefs = vec![];
+        let mut elements = 0u64;
+        for value in values {
+            let scan = scan_urefs(value)?;
+            elements += u64::from(scan.elements);
+            urefs.extend(scan.urefs);
         }
-        for key in &extra_urefs {
-            self.context.validate_key(key)?;
+        let cost = U512::from(uref_scan::SCANNED_ELEMENT_COST) * U512::from(elements);
+        if !self.charge_gas(Gas::new(cost)) {
+            return Err(Error::GasLimit);
+        }
+        Ok(urefs)
+    }
+
+    /// Calls the function declared pure living under `key`, or returns the result of an earlier
+    /// call made with the same `args_bytes` within the current execute request, provided the values
+    /// that call read, including the contract itself, are still the ones this context sees.
+    ///
+    /// The function is executed in a read-only context, and fails with
+    /// [`Error::ImpureDeclaredPure`] if it attempts to modify global state.  A call served from the
+    /// cache is charged the gas used by the call which was executed, so the cost of a call doesn't
+    /// depend on the order in which calls are made.
+    fn call_pure_contract(
+        &mut self,
+        key: Key,
+        contract: Contract,
+        args_bytes: Vec<u8>,
+        args: Vec<CLValue>,
+        extra_urefs: Vec<Key>,
+    ) -> Result<CLValue, Error> {
+        let cache_key = key.normalize();
+        let pure_call_cache = self.context.pure_call_cache();
+
+        let cached_result = pure_call_cache
+            .borrow_mut()
+            .get(cache_key, &args_bytes, |key| {
+                self.context.read_gs_direct(key)
+            })?;
+        if let Some((result, gas)) = cached_result {
+            if !self.charge_gas(gas) {
+                return Err(Error::GasLimit);
+            }
+            // the gas charged for the call includes that of scanning its result
+            let urefs = scan_urefs(&result)?.urefs;
+            self.context
+                .access_rights_extend(extract_access_rights_from_urefs(urefs));
+            return Ok(result);
+        }
+
+        let gas_before = self.context.gas_counter();
+        self.context.state().borrow_mut().record_reads();
+        let result = self.execute_contract(key, contract, args, extra_urefs, true);
+        let read_keys = self.context.state().borrow_mut().take_recorded_reads();
+        let result = match result {
+            Err(Error::ReadOnlyContext) => return Err(Error::ImpureDeclaredPure(key)),
+            result => result?,
+        };
+        let gas = self.context.gas_counter() - gas_before;
+
+        // the call can't write, so the values it read are still the current ones
+        let mut reads = Vec::with_capacity(read_keys.len() + 1);
+        for read_key in iter::once(cache_key).chain(read_keys) {
+            let value = self.context.read_gs_direct(&read_key)?;
+            reads.push((read_key, value));
         }
+        pure_call_cache
+            .borrow_mut()
+            .insert(cache_key, args_bytes, result.clone(), gas, reads);
+        Ok(result)
+    }
+
+    /// Executes the `contract` living under `key`, with supplied `args`.
+    fn execute_contract(
+        &mut self,
+        key: Key,
+        contract: Contract,
+        args: Vec<CLValue>,
+        extra_urefs: Vec<Key>,
+        read_only: bool,
+    ) -> Result<CLValue, Error> {
+        let contract_version = contract.protocol_version();
 
         if !self.config.use_system_contracts() {
             if self.is_mint(key) {
@@ -1977,7 +1293,9 @@ where
         }
 
         let maybe_module = match key {
-            Key::URef(uref) => self.system_contract_cache.get(&uref),
+            Key::URef(uref) => self
+                .system_contract_cache
+                .get(&uref, self.context.protocol_version()),
             _ => None,
         };
 
@@ -2004,7 +1322,7 @@ where
 
         let host_buffer = None;
 
-        let context = RuntimeContext::new(
+        let mut context = RuntimeContext::new(
             self.context.state(),
             &mut named_keys,
             access_rights,
@@ -2023,6 +1341,13 @@ where
             self.context.phase(),
             self.context.protocol_data(),
         );
+        context.set_read_only(read_only);
+        context.set_kind(self.context.kind());
+        context.set_args_supplied_by_engine(self.passes_engine_supplied_args());
+        context.set_transient_storage(self.context.transient_storage());
+        context.set_host_call_stats(self.context.host_call_stats());
+        context.set_pure_call_cache(self.context.pure_call_cache());
+        context.set_caller_key(self.context.base_key());
 
         let mut runtime = Runtime {
             system_contract_cache,
@@ -2040,6 +1365,10 @@ where
         // counter from there to our counter
         self.context.set_gas_counter(runtime.context.gas_counter());
 
+        // The callee, or any contract it called in turn, may have called back into our entity and
+        // modified its named keys
+        self.context.reload_named_keys();
+
         let error = match result {
             Err(error) => error,
             // If `Ok` and the `host_buffer` is `None`, the contract's execution succeeded but did
@@ -2071,6 +1400,32 @@ where
     }
 
     fn call_contract_host_buffer(
+        &mut self,
+        key: Key,
+        args_bytes: Vec<u8>,
+        result_size_ptr: u32,
+        read_only: bool,
+        scoped_instrumenter: &mut ScopedInstrumenter,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        scoped_instrumenter.pause();
+        let result = self.call_contract_with_mode(key, args_bytes, read_only);
+        scoped_instrumenter.unpause();
+        let result = match result {
+            Ok(result) => result,
+            // A read-only callee attempting to modify global state is reported to the caller
+            // rather than trapping the whole deploy
+            Err(Error::ReadOnlyContext) if read_only => return Ok(Err(ApiError::ReadOnlyContext)),
+            Err(error) => return Err(error),
+        };
+        self.set_call_result(result, result_size_ptr)
+    }
+
+    fn try_call_contract_host_buffer(
         &mut self,
         key: Key,
         args_bytes: Vec<u8>,
@@ -2083,9 +1438,21 @@ where
         }
 
         scoped_instrumenter.pause();
-        let result = self.call_contract(key, args_bytes);
+        let result = self.try_call_contract(key, args_bytes);
         scoped_instrumenter.unpause();
-        let result = result?;
+        match result? {
+            Ok(result) => self.set_call_result(result, result_size_ptr),
+            Err(error) => Ok(Err(error)),
+        }
+    }
+
+    /// Writes the `result` of a contract call to the host buffer, and its size to
+    /// `result_size_ptr`.
+    fn set_call_result(
+        &mut self,
+        result: CLValue,
+        result_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
         let result_size = result.inner_bytes().len() as u32; // considered to be safe
 
         // leave the host buffer set to `None` if there's nothing to write there
@@ -2177,6 +1544,29 @@ where
         Ok(new_hash)
     }
 
+    /// Stores a function at a new hash like [`Self::store_function_at_hash`], declaring the
+    /// properties given by `spec`.
+    ///
+    /// A function declared pure is marked by a [`PURE_FUNCTION_KEY_NAME`] entry in its named keys,
+    /// which the caller may not supply itself.
+    fn store_function_with_spec(
+        &mut self,
+        fn_bytes: Vec<u8>,
+        named_keys: BTreeMap<String, Key>,
+        spec: FunctionSpec,
+    ) -> Result<[u8; 32], Error> {
+        for name in named_keys.keys() {
+            self.validate_key_name(name)?;
+        }
+        let contract = Contract::new(fn_bytes, named_keys, self.context.protocol_version());
+        if spec.is_pure() {
+            self.context.store_pure_function_at_hash(contract)
+        } else {
+            self.context
+                .store_function_at_hash(StoredValue::Contract(contract))
+        }
+    }
+
     /// Writes function address (`hash_bytes`) into the Wasm memory (at
     /// `dest_ptr` pointer).
     fn function_address(&mut self, hash_bytes: [u8; 32], dest_ptr: u32) -> Result<(), Trap> {
@@ -2195,6 +1585,23 @@ where
             .map_err(|e| Error::Interpreter(e.into()).into())
     }
 
+    /// Generates new unforgable reference which any context may read and adds it to the context's
+    /// access_rights set.
+    fn new_public_uref(
+        &mut self,
+        uref_ptr: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+        let uref = self
+            .context
+            .new_public_uref(StoredValue::CLValue(cl_value))?;
+        self.memory
+            .set(uref_ptr, &uref.into_bytes().map_err(Error::BytesRepr)?)
+            .map_err(|e| Error::Interpreter(e.into()).into())
+    }
+
     /// Writes `value` under `key` in GlobalState.
     fn write(
         &mut self,
@@ -2210,6 +1617,13 @@ where
             .map_err(Into::into)
     }
 
+    /// Removes the value under the key in Wasm memory from global state once the effects of the
+    /// execution are committed.  Only callable from a system context.
+    fn prune(&mut self, key_ptr: u32, key_size: u32) -> Result<(), Trap> {
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        self.context.prune_gs(key).map_err(Into::into)
+    }
+
     /// Writes `value` under a key derived from `key` in the "local cluster" of
     /// GlobalState
     fn write_local(
@@ -2294,6 +1708,42 @@ where
 
     /// Similar to `read`, this function is for reading from the "local cluster"
     /// of global state
+    /// Copies the values under the local keys of `old_seed`, read from [old_seed_ptr] in the Wasm
+    /// memory, to the local keys of the current context, and writes the number of values copied to
+    /// [migrated_count_ptr].
+    ///
+    /// Meant for contracts whose local data lives under a seed other than their own, e.g. the
+    /// address of a uref the contract was stored under before.  The current context must hold
+    /// `old_seed` as the address of a uref with write access, otherwise
+    /// [`ApiError::PermissionDenied`] is returned.
+    fn migrate_local_keys(
+        &mut self,
+        old_seed_ptr: u32,
+        migrated_count_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        let old_seed_bytes = self.bytes_from_mem(old_seed_ptr, KEY_LOCAL_SEED_LENGTH)?;
+        let mut old_seed = [0u8; KEY_LOCAL_SEED_LENGTH];
+        old_seed.copy_from_slice(&old_seed_bytes);
+
+        let holds_old_seed = self
+            .context
+            .effective_access_rights(old_seed)
+            .map(|rights| rights.contains(AccessRights::WRITE))
+            .unwrap_or(false);
+        if old_seed != self.context.seed() && !holds_old_seed {
+            return Ok(Err(ApiError::PermissionDenied));
+        }
+
+        let migrated_count = self.context.migrate_local_keys(old_seed)?;
+
+        let migrated_count_bytes = migrated_count.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(migrated_count_ptr, &migrated_count_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+
+        Ok(Ok(()))
+    }
+
     fn read_local(
         &mut self,
         key_ptr: u32,
@@ -2325,11 +1775,157 @@ where
         Ok(Ok(()))
     }
 
+    /// Stores a value in the transient storage of the execution, charging gas for each byte of the
+    /// name and serialized value.
+    fn put_transient(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        value_ptr: u32,
+        value_size: u32,
+    ) -> Result<(), Trap> {
+        let name = self.string_from_mem(name_ptr, name_size)?;
+        let cl_value = self.cl_value_from_mem(value_ptr, value_size)?;
+
+        let size = name.len() + cl_value.inner_bytes().len();
+        let cost_per_byte = self.protocol_data().wasm_costs().transient_storage;
+        self.gas(Gas::new(U512::from(cost_per_byte) * U512::from(size)))?;
+
+        self.context
+            .put_transient(name, cl_value)
+            .map_err(Into::into)
+    }
+
+    /// Similar to `read`, this function is for reading from the transient storage of the execution
+    fn get_transient(
+        &mut self,
+        name_ptr: u32,
+        name_size: u32,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Trap> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let name = self.string_from_mem(name_ptr, name_size)?;
+
+        let cl_value = match self.context.get_transient(&name) {
+            Some(cl_value) => cl_value,
+            None => return Ok(Err(ApiError::ValueNotFound)),
+        };
+
+        let value_size = cl_value.inner_bytes().len() as u32;
+        if let Err(error) = self.write_host_buffer(cl_value) {
+            return Ok(Err(error));
+        }
+
+        let value_bytes = value_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &value_bytes) {
+            return Err(Error::Interpreter(error.into()).into());
+        }
+
+        Ok(Ok(()))
+    }
+
+    /// Schedules a call to the stored contract under the given key, to be made once the block time
+    /// reaches the given time, and writes the id of the call to `schedule_id_ptr`.
+    ///
+    /// The escrow paying for the call is moved from the main purse of the account into a new
+    /// purse, which is settled once the call has been made.
+    #[allow(clippy::too_many_arguments)]
+    fn schedule_call(
+        &mut self,
+        key_ptr: u32,
+        key_size: u32,
+        args_ptr: u32,
+        args_size: u32,
+        not_before_ptr: u32,
+        not_before_size: u32,
+        schedule_id_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_mutable()?;
+
+        let key = self.key_from_mem(key_ptr, key_size)?;
+        match self.context.read_gs(&key)? {
+            Some(StoredValue::Contract(_)) => {}
+            _ => return Ok(Err(ApiError::InvalidArgument)),
+        }
+
+        let args_bytes = self.bytes_from_mem(args_ptr, args_size as usize)?;
+        let args: Vec<CLValue> = bytesrepr::deserialize(args_bytes.clone())?;
+        for uref in self.scan_for_urefs(&args)? {
+            self.context.validate_uref(&uref)?;
+        }
+
+        let not_before: BlockTime = {
+            let bytes = self.bytes_from_mem(not_before_ptr, not_before_size as usize)?;
+            bytesrepr::deserialize(bytes)?
+        };
+
+        let source = self.context.get_main_purse()?;
+        let escrow = U512::from(SCHEDULED_CALL_ESCROW);
+        if escrow > self.get_balance(source)?.unwrap_or_default() {
+            return Ok(Err(ApiError::Transfer));
+        }
+        let mint_contract_key = self.get_mint_contract_uref().into();
+        let escrow_purse = self.mint_create(mint_contract_key)?;
+        if self
+            .mint_transfer(mint_contract_key, source, escrow_purse, escrow)
+            .is_err()
+        {
+            return Ok(Err(ApiError::Transfer));
+        }
+
+        let schedule_id = {
+            let address = self
+                .context
+                .address_generator()
+                .borrow_mut()
+                .create_address();
+            ScheduleId::new(address)
+        };
+        let scheduled_call = ScheduledCall::new(
+            key.normalize(),
+            args_bytes,
+            not_before,
+            self.context.account().public_key(),
+            escrow_purse,
+        );
+        self.context.schedule_call(schedule_id, scheduled_call)?;
+
+        self.memory
+            .set(schedule_id_ptr, &schedule_id.value())
+            .map_err(|e| Error::Interpreter(e.into()))?;
+        Ok(Ok(()))
+    }
+
     /// Reverts contract execution with a status specified.
     fn revert(&mut self, status: u32) -> Trap {
         Error::Revert(status.into()).into()
     }
 
+    /// Reverts contract execution with a status specified, attaching the data at `data_ptr`.
+    fn revert_with_data(&mut self, status: u32, data_ptr: u32, data_size: u32) -> Trap {
+        match self.revert_data_from_mem(data_ptr, data_size) {
+            Ok(data) => Error::RevertWithData(status.into(), data).into(),
+            Err(trap) => trap,
+        }
+    }
+
+    /// Reads the data attached to a revert, truncated to the chainspec's `max_revert_data_bytes`.
+    /// Gas is charged for each byte kept.
+    fn revert_data_from_mem(&mut self, data_ptr: u32, data_size: u32) -> Result<Vec<u8>, Trap> {
+        let wasm_costs = self.protocol_data().wasm_costs();
+
+        // Truncated before reading, so oversized data is never copied out of memory.
+        let length = cmp::min(data_size, wasm_costs.max_revert_data_bytes);
+        self.gas(Gas::new(
+            U512::from(wasm_costs.string_byte) * U512::from(length),
+        ))?;
+        Ok(self.bytes_from_mem(data_ptr, length as usize)?)
+    }
+
     fn add_associated_key(
         &mut self,
         public_key_ptr: u32,
@@ -2448,6 +2044,15 @@ where
         self.context.attenuate_uref(standard_payment)
     }
 
+    /// Looks up the public auction contract key in the context's protocol data, if the auction is
+    /// installed.
+    ///
+    /// Returned URef is already attenuated depending on the calling account.
+    fn get_auction_contract_uref(&self) -> Option<URef> {
+        let auction = self.context.protocol_data().auction()?;
+        Some(self.context.attenuate_uref(auction))
+    }
+
     /// Calls the "create" method on the mint contract at the given mint
     /// contract key
     fn mint_create(&mut self, mint_contract_key: Key) -> Result<URef, Error> {
@@ -2463,6 +2068,7 @@ where
     }
 
     fn create_purse(&mut self) -> Result<URef, Error> {
+        self.context.validate_mutable()?;
         let mint_contract_key = self.get_mint_contract_uref().into();
         self.mint_create(mint_contract_key)
     }
@@ -2476,8 +2082,64 @@ where
         target: URef,
         amount: U512,
     ) -> Result<(), Error> {
+        self.validate_payment_purse_isolation(source)?;
+
+        let args_bytes = {
+            let args = (
+                types::system_contract_names::mint::METHOD_TRANSFER,
+                source,
+                target,
+                amount,
+            );
+            ArgsParser::parse(args)?.into_bytes()?
+        };
+
+        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        let result: Result<(), mint::Error> = result.into_t()?;
+        Ok(result.map_err(system_contract_errors::Error::from)?)
+    }
+
+    /// Returns `Error::ForbiddenInSystemContext` if this is a system context other than the PoS
+    /// contract's and `source` is the payment purse.
+    ///
+    /// Finalization is the only system execution which may move the motes paid for a deploy, and
+    /// it does so from within the PoS contract.
+    fn validate_payment_purse_isolation(&mut self, source: URef) -> Result<(), Error> {
+        if !self.context.is_system() || self.is_proof_of_stake(self.context.base_key()) {
+            return Ok(());
+        }
+        let proof_of_stake_key = Key::URef(self.protocol_data().proof_of_stake()).normalize();
+        let payment_purse = match self.context.read_gs_direct(&proof_of_stake_key)? {
+            Some(StoredValue::Contract(contract)) => {
+                contract.named_keys().get(POS_PAYMENT_PURSE).cloned()
+            }
+            _ => None,
+        };
+        match payment_purse {
+            Some(Key::URef(payment_purse)) if payment_purse.addr() == source.addr() => Err(
+                Error::ForbiddenInSystemContext("transfer from the payment purse".to_string()),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Calls the "balance" method on the mint contract at the given mint
+    /// contract key
+    fn mint_balance(&mut self, mint_contract_key: Key, purse: URef) -> Result<Option<U512>, Error> {
+        let args_bytes = {
+            let args = (types::system_contract_names::mint::METHOD_BALANCE, purse);
+            ArgsParser::parse(args)?.into_bytes()?
+        };
+
+        let result = self.call_contract(mint_contract_key, args_bytes)?;
+        Ok(result.into_t()?)
+    }
+
+    /// Calls the "revoke" method on the mint contract at the given mint
+    /// contract key
+    fn mint_revoke(&mut self, mint_contract_key: Key, purse: URef) -> Result<(), Error> {
         let args_bytes = {
-            let args = ("transfer", source, target, amount);
+            let args = ("revoke", purse);
             ArgsParser::parse(args)?.into_bytes()?
         };
 
@@ -2533,7 +2195,9 @@ where
                     }
                 })
                 .collect();
-                let account = Account::create(target, named_keys, target_purse);
+                let account_config = *self.context.protocol_data().account_config();
+                let account =
+                    Account::create_with_config(target, named_keys, target_purse, &account_config);
                 self.context.write_account(target_key, account)?;
                 Ok(Ok(TransferredTo::NewAccount))
             }
@@ -2580,6 +2244,8 @@ where
         target: PublicKey,
         amount: U512,
     ) -> Result<TransferResult, Error> {
+        // Checked up front since a failed transfer is otherwise reported as `ApiError::Transfer`
+        self.context.validate_mutable()?;
         let target_key = Key::Account(target);
         // Look up the account at the given public key's address
         match self.context.read_account(&target_key)? {
@@ -2613,6 +2279,8 @@ where
         amount_ptr: u32,
         amount_size: u32,
     ) -> Result<Result<(), ApiError>, Error> {
+        self.context.validate_mutable()?;
+
         let source: URef = {
             let bytes = self.bytes_from_mem(source_ptr, source_size as usize)?;
             bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
@@ -2630,13 +2298,40 @@ where
 
         let mint_contract_key = self.get_mint_contract_uref().into();
 
-        if self
-            .mint_transfer(mint_contract_key, source, target, amount)
-            .is_ok()
-        {
-            Ok(Ok(()))
+        match self.mint_transfer(mint_contract_key, source, target, amount) {
+            Ok(()) => Ok(Ok(())),
+            Err(Error::SystemContract(system_contract_errors::Error::Mint(
+                mint::Error::PurseRevoked,
+            ))) => Ok(Err(mint::Error::PurseRevoked.into())),
+            Err(Error::SystemContract(system_contract_errors::Error::Mint(
+                mint::Error::DestinationPurseNotFound,
+            ))) if self.context.protocol_version()
+                >= destination_purse_not_found_protocol_version() =>
+            {
+                Ok(Err(mint::Error::DestinationPurseNotFound.into()))
+            }
+            Err(_) => Ok(Err(ApiError::Transfer)),
+        }
+    }
+
+    /// Returns `true` if `purse` was created by the mint, i.e. the mint associates it with a
+    /// balance.
+    fn purse_exists(&mut self, purse: URef) -> Result<bool, Error> {
+        let seed = self.get_mint_contract_uref().addr();
+        let key = purse.addr().into_bytes()?;
+        Ok(self.context.read_ls_with_seed(seed, &key)?.is_some())
+    }
+
+    /// Returns 0 if the purse serialized in Wasm memory exists, or 1 otherwise.
+    fn purse_exists_host(&mut self, purse_ptr: u32, purse_size: u32) -> Result<i32, Trap> {
+        let purse: URef = {
+            let bytes = self.bytes_from_mem(purse_ptr, purse_size as usize)?;
+            bytesrepr::deserialize(bytes).map_err(Error::BytesRepr)?
+        };
+        if self.purse_exists(purse)? {
+            Ok(0)
         } else {
-            Ok(Err(ApiError::Transfer))
+            Ok(1)
         }
     }
 
@@ -2715,6 +2410,58 @@ where
         Ok(Ok(()))
     }
 
+    /// Asks the mint for the balance of the purse serialized in Wasm memory, writing it to the host
+    /// buffer and its size to [output_size_ptr].
+    ///
+    /// Unlike [`Runtime::get_balance_host_buffer`], the purse is passed to the mint as a call
+    /// argument, so it must be known to the current context with the access rights it carries, and
+    /// the mint answers only if these include [`AccessRights::READ`].  A purse unknown to the mint
+    /// is reported as [`ApiError::InvalidPurse`].
+    fn get_balance_by_uref_host_buffer(
+        &mut self,
+        purse_ptr: u32,
+        purse_size: usize,
+        output_size_ptr: u32,
+    ) -> Result<Result<(), ApiError>, Error> {
+        if !self.can_write_to_host_buffer() {
+            // Exit early if the host buffer is already occupied
+            return Ok(Err(ApiError::HostBufferFull));
+        }
+
+        let purse: URef = {
+            let bytes = self.bytes_from_mem(purse_ptr, purse_size)?;
+            match bytesrepr::deserialize(bytes) {
+                Ok(purse) => purse,
+                Err(error) => return Ok(Err(error.into())),
+            }
+        };
+
+        let mint_contract_key = self.get_mint_contract_uref().into();
+        let balance = match self.mint_balance(mint_contract_key, purse) {
+            Ok(Some(balance)) => balance,
+            Ok(None) => return Ok(Err(ApiError::InvalidPurse)),
+            Err(Error::Revert(api_error)) => return Ok(Err(api_error)),
+            Err(error) => return Err(error),
+        };
+
+        let balance_cl_value = match CLValue::from_t(balance) {
+            Ok(cl_value) => cl_value,
+            Err(error) => return Ok(Err(error.into())),
+        };
+
+        let balance_size = balance_cl_value.inner_bytes().len() as i32;
+        if let Err(error) = self.write_host_buffer(balance_cl_value) {
+            return Ok(Err(error));
+        }
+
+        let balance_size_bytes = balance_size.to_le_bytes(); // Wasm is little-endian
+        if let Err(error) = self.memory.set(output_size_ptr, &balance_size_bytes) {
+            return Err(Error::Interpreter(error.into()));
+        }
+
+        Ok(Ok(()))
+    }
+
     /// If key is in named_keys with AccessRights::Write, processes bytes from calling contract
     /// and writes them at the provided uref, overwriting existing value if any
     fn upgrade_contract_at_uref(
@@ -2761,6 +2508,10 @@ where
             Ok(SystemContractType::Mint) => self.get_mint_contract_uref(),
             Ok(SystemContractType::ProofOfStake) => self.get_pos_contract_uref(),
             Ok(SystemContractType::StandardPayment) => self.get_standard_payment_contract_uref(),
+            Ok(SystemContractType::Auction) => match self.get_auction_contract_uref() {
+                Some(auction) => auction,
+                None => return Ok(Err(ApiError::InvalidSystemContract)),
+            },
             Err(error) => return Ok(Err(error)),
         };
 
@@ -2848,7 +2599,7 @@ mod tests {
 
     use types::{gens::*, CLType, CLValue, Key, URef};
 
-    use super::extract_urefs;
+    use super::scan_urefs;
 
     fn cl_value_with_urefs_arb() -> impl Strategy<Value = (CLValue, Vec<URef>)> {
         // If compiler brings you here it most probably means you've added a variant to `CLType`
@@ -2985,9 +2736,9 @@ mod tests {
 
     proptest! {
         #[test]
-        fn should_extract_urefs((cl_value, urefs) in cl_value_with_urefs_arb()) {
-            let extracted_urefs = extract_urefs(&cl_value).unwrap();
-            assert_eq!(extracted_urefs, urefs);
+        fn should_scan_urefs((cl_value, urefs) in cl_value_with_urefs_arb()) {
+            let scanned_urefs = scan_urefs(&cl_value).unwrap().urefs;
+            assert_eq!(scanned_urefs, urefs);
         }
     }
 }
@@ -1,16 +1,14 @@
-use std::{
-    collections::{BTreeMap, BTreeSet},
-    fmt::Write,
-};
+use std::collections::BTreeMap;
 
 use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use proof_of_stake::{
-    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, Stakes, StakesProvider,
+    MintProvider, ProofOfStake, Queue, QueueProvider, RuntimeProvider, StakeKeyName, Stakes,
+    StakesProvider, STAKE_KEY_PREFIX,
 };
 use types::{
-    account::PublicKey, bytesrepr::ToBytes, system_contract_errors::pos::Error, ApiError,
-    BlockTime, CLValue, Key, Phase, TransferredTo, URef, U512,
+    account::PublicKey, bytesrepr::ToBytes, system_contract_errors::pos::Error, AccessRights,
+    ApiError, BlockTime, CLValue, Key, Phase, TransferredTo, URef, U512,
 };
 
 use crate::{execution, runtime::Runtime};
@@ -126,6 +124,10 @@ where
     fn get_caller(&self) -> PublicKey {
         self.context.get_caller()
     }
+
+    fn get_access_rights(&self, uref: URef) -> Option<AccessRights> {
+        self.context.effective_access_rights(uref.addr())
+    }
 }
 
 impl<'a, R> StakesProvider for Runtime<'a, R>
@@ -133,29 +135,29 @@ where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<execution::Error>,
 {
-    fn read(&self) -> Result<Stakes, Error> {
+    fn read(&mut self) -> Result<Stakes, Error> {
+        let named_keys: Vec<(String, Key)> = self
+            .context
+            .named_keys()
+            .iter()
+            .map(|(name, key)| (name.clone(), *key))
+            .collect();
         let mut stakes = BTreeMap::new();
-        for (name, _) in self.context.named_keys().iter() {
-            let mut split_name = name.split('_');
-            if Some("v") != split_name.next() {
-                continue;
-            }
-            let hex_key = split_name
-                .next()
-                .ok_or(Error::StakesKeyDeserializationFailed)?;
-            if hex_key.len() != 64 {
-                return Err(Error::StakesKeyDeserializationFailed);
-            }
-            let mut key_bytes = [0u8; 32];
-            let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
-                .map_err(|_| Error::StakesKeyDeserializationFailed)?;
-            debug_assert!(_bytes_written == key_bytes.len());
-            let pub_key = PublicKey::ed25519_from(key_bytes);
-            let balance = split_name
-                .next()
-                .and_then(|b| U512::from_dec_str(b).ok())
-                .ok_or(Error::StakesDeserializationFailed)?;
-            stakes.insert(pub_key, balance);
+        for (name, key) in named_keys {
+            let (validator, balance) = match proof_of_stake::parse_stake_key_name(&name)? {
+                None => continue,
+                Some(StakeKeyName::Legacy(validator, balance)) => (validator, balance),
+                Some(StakeKeyName::Current(validator)) => {
+                    let balance = match self.context.read_gs(&key) {
+                        Ok(Some(StoredValue::CLValue(cl_value))) => cl_value
+                            .into_t()
+                            .map_err(|_| Error::StakesDeserializationFailed)?,
+                        _ => return Err(Error::StakesDeserializationFailed),
+                    };
+                    (validator, balance)
+                }
+            };
+            stakes.insert(validator, balance);
         }
         if stakes.is_empty() {
             return Err(Error::StakesNotFound);
@@ -164,37 +166,73 @@ where
     }
 
     fn write(&mut self, stakes: &Stakes) {
-        // Encode the stakes as a set of uref names.
-        let mut new_urefs: BTreeSet<String> = stakes
-            .0
+        let mut new_stakes: BTreeMap<String, U512> = stakes
             .iter()
-            .map(|(pub_key, balance)| {
-                let key_bytes = pub_key.value();
-                let mut hex_key = String::with_capacity(64);
-                for byte in &key_bytes[..32] {
-                    write!(hex_key, "{:02x}", byte).expect("Writing to a string cannot fail");
-                }
-                let mut uref = String::new();
-                uref.write_fmt(format_args!("v_{}_{}", hex_key, balance))
-                    .expect("Writing to a string cannot fail");
-                uref
-            })
+            .map(|(validator, balance)| (proof_of_stake::stake_key_name(validator), *balance))
             .collect();
-        // Remove and add urefs to update the contract's known urefs accordingly.
-        let mut removes = Vec::new();
-        for (name, _) in self.context.named_keys().iter() {
-            if name.starts_with("v_") && !new_urefs.remove(name) {
-                removes.push(name.to_owned())
+        let stake_keys: Vec<(String, Key)> = self
+            .context
+            .named_keys()
+            .iter()
+            .filter(|(name, _)| name.starts_with(STAKE_KEY_PREFIX))
+            .map(|(name, key)| (name.clone(), *key))
+            .collect();
+        // Update the existing stakes in place, and remove the unbonded validators as well as the
+        // stakes stored in the legacy format.
+        for (name, key) in stake_keys {
+            match (new_stakes.remove(&name), key) {
+                (Some(balance), Key::URef(_)) => {
+                    let value = CLValue::from_t(balance).expect("should convert");
+                    self.context
+                        .write_gs(key, StoredValue::CLValue(value))
+                        .expect("should write stake")
+                }
+                (Some(balance), _) => self.put_stake(name, balance),
+                (None, _) => self.context.remove_key(&name).expect("should remove key"),
             }
         }
-        for name in removes.iter() {
-            self.context.remove_key(name).expect("should remove key")
+        for (name, balance) in new_stakes {
+            self.put_stake(name, balance)
         }
-        for name in new_urefs {
-            self.context
-                .put_key(name, Key::Hash([0; 32]))
-                .expect("should put key")
+    }
+
+    fn migrate_legacy(&mut self, max_count: u32) -> Result<u32, Error> {
+        let names: Vec<String> = self.context.named_keys().keys().cloned().collect();
+        let mut migrated_count = 0;
+        let mut remaining_count = 0;
+        for name in names {
+            let (validator, balance) = match proof_of_stake::parse_stake_key_name(&name)? {
+                Some(StakeKeyName::Legacy(validator, balance)) => (validator, balance),
+                None | Some(StakeKeyName::Current(_)) => continue,
+            };
+            if migrated_count == max_count {
+                remaining_count += 1;
+                continue;
+            }
+            self.context.remove_key(&name).expect("should remove key");
+            self.put_stake(proof_of_stake::stake_key_name(&validator), balance);
+            migrated_count += 1;
         }
+        Ok(remaining_count)
+    }
+}
+
+impl<'a, R> Runtime<'a, R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    /// Stores `balance` under a new uref, and puts the uref under `name` in the PoS contract's
+    /// named keys.
+    fn put_stake(&mut self, name: String, balance: U512) {
+        let value = CLValue::from_t(balance).expect("should convert");
+        let uref = self
+            .context
+            .new_uref(StoredValue::CLValue(value))
+            .expect("should create uref");
+        self.context
+            .put_key(name, Key::URef(uref))
+            .expect("should put key")
     }
 }
 
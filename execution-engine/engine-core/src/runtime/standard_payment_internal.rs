@@ -4,15 +4,19 @@ use contract::args_parser::ArgsParser;
 use engine_shared::stored_value::StoredValue;
 use engine_storage::global_state::StateReader;
 use standard_payment::{AccountProvider, MintProvider, ProofOfStakeProvider, StandardPayment};
-use types::{bytesrepr::ToBytes, system_contract_errors, ApiError, Key, URef, U512};
+use types::{
+    bytesrepr::ToBytes, system_contract_errors, system_contract_names::pos, ApiError, Key, URef,
+    U512,
+};
 
 use crate::{execution, runtime::Runtime};
 
 lazy_static! {
-    static ref SERIALIZED_GET_PAYMENT_PURSE: Vec<u8> = ArgsParser::parse(("get_payment_purse",))
-        .expect("args should convert to `Vec<CLValue>`")
-        .into_bytes()
-        .expect("args should serialize");
+    static ref SERIALIZED_GET_PAYMENT_PURSE: Vec<u8> =
+        ArgsParser::parse((pos::METHOD_GET_PAYMENT_PURSE,))
+            .expect("args should convert to `Vec<CLValue>`")
+            .into_bytes()
+            .expect("args should serialize");
 }
 
 impl<'a, R> AccountProvider for Runtime<'a, R>
@@ -0,0 +1,216 @@
+//! Scanning of the arguments passed to a called contract for the urefs they hold, so that the
+//! callee is granted access to them without the caller listing them separately.
+//!
+//! The scan follows the `CLType` of a value through its serialized bytes, so it finds urefs nested
+//! at any depth, e.g. in a `Vec<(String, URef)>`.  It is bounded and deterministic: a value whose
+//! type is nested deeper than [`MAX_SCAN_DEPTH`], or which holds more than
+//! [`MAX_SCANNED_ELEMENTS`] elements, is rejected, and the number of elements scanned is reported
+//! so that it can be charged for.  Values whose type can't hold a uref aren't scanned at all.
+use types::{
+    bytesrepr::{self, FromBytes},
+    CLType, CLValue, Key, URef, U128, U256, U512,
+};
+
+use crate::execution::Error;
+
+/// The max depth to which the types of a scanned value may be nested.
+pub const MAX_SCAN_DEPTH: usize = 8;
+
+/// The max number of elements scanned in a single value, counting each element of a collection
+/// and each component of an option, result or tuple.
+pub const MAX_SCANNED_ELEMENTS: u32 = 10_000;
+
+/// Gas charged for each element scanned.
+pub const SCANNED_ELEMENT_COST: u32 = 10;
+
+/// The urefs found in a value, in the order they appear in its serialized bytes.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct URefScan {
+    pub urefs: Vec<URef>,
+    /// The number of elements scanned to find them.
+    pub elements: u32,
+}
+
+/// Returns the urefs held by `cl_value`, including those held by a `Key::URef`.
+///
+/// Values with a type holding [`CLType::Any`] are opaque, as their encoding can't be followed, and
+/// hold no urefs as far as the scan is concerned.
+pub fn scan_urefs(cl_value: &CLValue) -> Result<URefScan, Error> {
+    let mut scan = URefScan::default();
+    let cl_type = cl_value.cl_type();
+    if !holds(cl_type, &|ty| *ty == CLType::Key || *ty == CLType::URef)
+        || holds(cl_type, &|ty| *ty == CLType::Any)
+    {
+        return Ok(scan);
+    }
+    let rem = scan_value(cl_type, cl_value.inner_bytes(), 0, &mut scan)?;
+    if !rem.is_empty() {
+        return Err(bytesrepr::Error::LeftOverBytes.into());
+    }
+    Ok(scan)
+}
+
+/// Returns `true` if `cl_type` or any type nested in it matches `predicate`.
+fn holds(cl_type: &CLType, predicate: &dyn Fn(&CLType) -> bool) -> bool {
+    if predicate(cl_type) {
+        return true;
+    }
+    match cl_type {
+        CLType::Option(ty) | CLType::List(ty) | CLType::FixedList(ty, _) => holds(ty, predicate),
+        CLType::Result { ok, err } => holds(ok, predicate) || holds(err, predicate),
+        CLType::Map { key, value } => holds(key, predicate) || holds(value, predicate),
+        CLType::Tuple1(types) => types.iter().any(|ty| holds(ty, predicate)),
+        CLType::Tuple2(types) => types.iter().any(|ty| holds(ty, predicate)),
+        CLType::Tuple3(types) => types.iter().any(|ty| holds(ty, predicate)),
+        _ => false,
+    }
+}
+
+/// Scans the value of `cl_type` at the start of `bytes`, returning the bytes which follow it.
+fn scan_value<'a>(
+    cl_type: &CLType,
+    bytes: &'a [u8],
+    depth: usize,
+    scan: &mut URefScan,
+) -> Result<&'a [u8], Error> {
+    scan.elements += 1;
+    if depth > MAX_SCAN_DEPTH || scan.elements > MAX_SCANNED_ELEMENTS {
+        return Err(Error::ArgumentTooComplex {
+            max_depth: MAX_SCAN_DEPTH,
+            max_elements: MAX_SCANNED_ELEMENTS,
+        });
+    }
+
+    let rem = match cl_type {
+        CLType::Bool => bool::from_bytes(bytes)?.1,
+        CLType::I32 => i32::from_bytes(bytes)?.1,
+        CLType::I64 => i64::from_bytes(bytes)?.1,
+        CLType::U8 => u8::from_bytes(bytes)?.1,
+        CLType::U32 => u32::from_bytes(bytes)?.1,
+        CLType::U64 => u64::from_bytes(bytes)?.1,
+        CLType::U128 => U128::from_bytes(bytes)?.1,
+        CLType::U256 => U256::from_bytes(bytes)?.1,
+        CLType::U512 => U512::from_bytes(bytes)?.1,
+        CLType::Unit => bytes,
+        CLType::String => String::from_bytes(bytes)?.1,
+        CLType::Key => {
+            let (key, rem) = Key::from_bytes(bytes)?;
+            scan.urefs.extend(key.into_uref());
+            rem
+        }
+        CLType::URef => {
+            let (uref, rem) = URef::from_bytes(bytes)?;
+            scan.urefs.push(uref);
+            rem
+        }
+        CLType::Option(ty) => match u8::from_bytes(bytes)? {
+            (0, rem) => rem,
+            (1, rem) => scan_value(ty, rem, depth + 1, scan)?,
+            _ => return Err(bytesrepr::Error::Formatting.into()),
+        },
+        CLType::List(ty) => {
+            let (count, mut rem) = u32::from_bytes(bytes)?;
+            for _ in 0..count {
+                rem = scan_value(ty, rem, depth + 1, scan)?;
+            }
+            rem
+        }
+        CLType::FixedList(ty, count) => {
+            let mut rem = bytes;
+            for _ in 0..*count {
+                rem = scan_value(ty, rem, depth + 1, scan)?;
+            }
+            rem
+        }
+        CLType::Result { ok, err } => match u8::from_bytes(bytes)? {
+            (0, rem) => scan_value(err, rem, depth + 1, scan)?,
+            (1, rem) => scan_value(ok, rem, depth + 1, scan)?,
+            _ => return Err(bytesrepr::Error::Formatting.into()),
+        },
+        CLType::Map { key, value } => {
+            let (count, mut rem) = u32::from_bytes(bytes)?;
+            for _ in 0..count {
+                rem = scan_value(key, rem, depth + 1, scan)?;
+                rem = scan_value(value, rem, depth + 1, scan)?;
+            }
+            rem
+        }
+        CLType::Tuple1(types) => scan_tuple(types, bytes, depth, scan)?,
+        CLType::Tuple2(types) => scan_tuple(types, bytes, depth, scan)?,
+        CLType::Tuple3(types) => scan_tuple(types, bytes, depth, scan)?,
+        // Values holding `Any` are never scanned, see `scan_urefs()`
+        CLType::Any => return Err(bytesrepr::Error::Formatting.into()),
+    };
+    Ok(rem)
+}
+
+fn scan_tuple<'a>(
+    types: &[Box<CLType>],
+    bytes: &'a [u8],
+    depth: usize,
+    scan: &mut URefScan,
+) -> Result<&'a [u8], Error> {
+    types
+        .iter()
+        .try_fold(bytes, |rem, ty| scan_value(ty, rem, depth + 1, scan))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use types::{AccessRights, CLValue, Key, URef};
+
+    use super::{scan_urefs, MAX_SCANNED_ELEMENTS, MAX_SCAN_DEPTH};
+    use crate::execution::Error;
+
+    fn uref(byte: u8) -> URef {
+        URef::new([byte; 32], AccessRights::READ_ADD_WRITE)
+    }
+
+    #[test]
+    fn should_find_nested_urefs_in_order() {
+        let value = vec![
+            (String::from("a"), uref(1)),
+            (String::from("b"), uref(2).into_read()),
+        ];
+        let scan = scan_urefs(&CLValue::from_t(value).unwrap()).unwrap();
+        assert_eq!(scan.urefs, vec![uref(1), uref(2).into_read()]);
+        // the list, then each tuple and its two components
+        assert_eq!(scan.elements, 7);
+
+        let mut map = BTreeMap::new();
+        map.insert(String::from("key"), Key::from(uref(3)));
+        map.insert(String::from("hash"), Key::Hash([4; 32]));
+        let value: Option<Result<BTreeMap<String, Key>, u64>> = Some(Ok(map));
+        let scan = scan_urefs(&CLValue::from_t(value).unwrap()).unwrap();
+        assert_eq!(scan.urefs, vec![uref(3)]);
+    }
+
+    #[test]
+    fn should_not_scan_values_which_cannot_hold_urefs() {
+        let value = vec![0u64; MAX_SCANNED_ELEMENTS as usize + 1];
+        let scan = scan_urefs(&CLValue::from_t(value).unwrap()).unwrap();
+        assert!(scan.urefs.is_empty());
+        assert_eq!(scan.elements, 0);
+    }
+
+    #[test]
+    fn should_reject_values_exceeding_the_limits() {
+        let too_many = vec![uref(1); MAX_SCANNED_ELEMENTS as usize];
+        match scan_urefs(&CLValue::from_t(too_many).unwrap()) {
+            Err(Error::ArgumentTooComplex { .. }) => {}
+            other => panic!("expected too complex, got {:?}", other),
+        }
+
+        // eight nested lists, so that the uref is at `MAX_SCAN_DEPTH`
+        assert_eq!(MAX_SCAN_DEPTH, 8);
+        let deepest = vec![vec![vec![vec![vec![vec![vec![vec![uref(1)]]]]]]]];
+        let scan = scan_urefs(&CLValue::from_t(deepest.clone()).unwrap()).unwrap();
+        assert_eq!(scan.urefs, vec![uref(1)]);
+        match scan_urefs(&CLValue::from_t(vec![deepest]).unwrap()) {
+            Err(Error::ArgumentTooComplex { .. }) => {}
+            other => panic!("expected too complex, got {:?}", other),
+        }
+    }
+}
@@ -22,13 +22,20 @@ use types::{
         UpdateKeyFailure, Weight,
     },
     bytesrepr::{self, ToBytes},
-    AccessRights, BlockTime, CLType, CLValue, Key, Phase, ProtocolVersion, URef,
-    KEY_LOCAL_SEED_LENGTH,
+    AccessRights, BlockTime, CLType, CLValue, Key, Phase, ProtocolVersion, ScheduleId, URef,
+    BLAKE2B_DIGEST_LENGTH, KEY_LOCAL_SEED_LENGTH, PURE_FUNCTION_KEY_NAME,
 };
 
 use crate::{
-    engine_state::{execution_effect::ExecutionEffect, SYSTEM_ACCOUNT_ADDR},
-    execution::{AddressGenerator, Error},
+    engine_state::{
+        execution_effect::ExecutionEffect,
+        scheduled_calls::{self, ScheduledCall},
+        SYSTEM_ACCOUNT_ADDR,
+    },
+    execution::{
+        AddressGenerator, Error, ExecutionContextKind, HostCallStats, PureCallCache,
+        TransientStorage,
+    },
     tracking_copy::{AddResult, TrackingCopy},
     Address,
 };
@@ -36,6 +43,10 @@ use crate::{
 #[cfg(test)]
 mod tests;
 
+/// Prefixes the address of a public uref when deriving the key of its record, so that the record
+/// key can't collide with the address of any other entity.
+const PUBLIC_UREF_RECORD_PREFIX: &[u8] = b"public-uref";
+
 /// Attenuates given URef for a given account context.
 ///
 /// System account transfers given URefs into READ_ADD_WRITE access rights,
@@ -50,6 +61,39 @@ pub(crate) fn attenuate_uref_for_account(account: &Account, uref: URef) -> URef
     }
 }
 
+/// Returns the key under which global state records that the uref at `addr` is public.
+///
+/// Only the host writes under such a key, and only when creating a public uref.
+fn public_uref_record_key(addr: Address) -> Key {
+    let mut hasher = VarBlake2b::new(32).unwrap();
+    hasher.input(PUBLIC_UREF_RECORD_PREFIX);
+    hasher.input(&addr);
+    let mut hash_bytes = [0; 32];
+    hasher.variable_result(|hash| hash_bytes.clone_from_slice(hash));
+    Key::Hash(hash_bytes)
+}
+
+/// Returns the serialized prefix shared by all [`Key::Local`]s under `seed`.
+pub(crate) fn local_key_prefix(
+    seed: [u8; KEY_LOCAL_SEED_LENGTH],
+) -> Result<Vec<u8>, bytesrepr::Error> {
+    let mut prefix = Key::Local {
+        seed,
+        hash: Default::default(),
+    }
+    .to_bytes()?;
+    prefix.truncate(prefix.len() - BLAKE2B_DIGEST_LENGTH);
+    Ok(prefix)
+}
+
+/// Returns the first protocol version in which contract hashes are derived from the address
+/// generator of the execution rather than from `fn_store_id`.
+///
+/// See [`RuntimeContext::new_function_address`].
+fn contract_hash_derivation_protocol_version() -> ProtocolVersion {
+    ProtocolVersion::from_parts(1, 1, 0)
+}
+
 /// Holds information specific to the deployed contract.
 pub struct RuntimeContext<'a, R> {
     state: Rc<RefCell<TrackingCopy<R>>>,
@@ -60,10 +104,15 @@ pub struct RuntimeContext<'a, R> {
     // Original account for read only tasks taken before execution
     account: &'a Account,
     args: Vec<CLValue>,
+    // Positions of the args which were named in the deploy, empty for a called contract
+    arg_names: BTreeMap<String, usize>,
     authorization_keys: BTreeSet<PublicKey>,
     // Key pointing to the entity we are currently running
     //(could point at an account or contract in the global state)
     base_key: Key,
+    // Key of the entity which called the one we are currently running, or `base_key` itself for
+    // the top-level context of a deploy
+    caller_key: Key,
     blocktime: BlockTime,
     deploy_hash: [u8; 32],
     gas_limit: Gas,
@@ -74,6 +123,19 @@ pub struct RuntimeContext<'a, R> {
     correlation_id: CorrelationId,
     phase: Phase,
     protocol_data: ProtocolData,
+    // Set when executing a contract called via `call_contract_read_only`, or any contract called
+    // from such a context
+    read_only: bool,
+    kind: ExecutionContextKind,
+    // Set when the arguments were supplied by the engine or built by system code, rather than by a
+    // deploy or a user contract
+    args_supplied_by_engine: bool,
+    // Shared by all the contexts of a single execution, and never written to global state
+    transient_storage: Rc<RefCell<TransientStorage>>,
+    // Shared by all the contexts of a single execution, and never written to global state
+    host_call_stats: Rc<RefCell<HostCallStats>>,
+    // Shared by all the executions of a single `ExecuteRequest`, and never written to global state
+    pure_call_cache: Rc<RefCell<PureCallCache>>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -106,11 +168,13 @@ where
             named_keys,
             access_rights,
             args,
+            arg_names: BTreeMap::new(),
             account,
             authorization_keys,
             blocktime,
             deploy_hash,
             base_key,
+            caller_key: base_key,
             gas_limit,
             gas_counter,
             fn_store_id,
@@ -119,6 +183,12 @@ where
             correlation_id,
             phase,
             protocol_data,
+            read_only: false,
+            kind: ExecutionContextKind::User,
+            args_supplied_by_engine: false,
+            transient_storage: Default::default(),
+            host_call_stats: Default::default(),
+            pure_call_cache: Default::default(),
         }
     }
 
@@ -134,6 +204,11 @@ where
         &self.named_keys
     }
 
+    /// Replaces the named keys, e.g. with those held before a call whose changes were discarded.
+    pub fn set_named_keys(&mut self, named_keys: BTreeMap<String, Key>) {
+        *self.named_keys = named_keys;
+    }
+
     pub fn fn_store_id(&self) -> u32 {
         self.fn_store_id
     }
@@ -153,7 +228,7 @@ where
 
         let contract_value = StoredValue::Contract(contract);
 
-        self.state.borrow_mut().write(key, contract_value);
+        self.write_unsafe(key, contract_value)?;
 
         Ok(())
     }
@@ -163,6 +238,7 @@ where
     /// also persistable map (one that is found in the
     /// TrackingCopy/GlobalState).
     pub fn remove_key(&mut self, name: &str) -> Result<(), Error> {
+        self.validate_mutable()?;
         match self.base_key() {
             public_key @ Key::Account(_) => {
                 let account: Account = {
@@ -172,7 +248,7 @@ where
                 };
                 self.named_keys.remove(name);
                 let account_value = self.account_to_validated_value(account)?;
-                self.state.borrow_mut().write(public_key, account_value);
+                self.write_unsafe(public_key, account_value)?;
                 Ok(())
             }
             contract_uref @ Key::URef(_) => {
@@ -207,6 +283,16 @@ where
         self.account.public_key()
     }
 
+    /// Returns the key of the account or contract which called the current context, as opposed to
+    /// [`get_caller`](Self::get_caller) which always returns the account of the deploy.
+    pub fn caller_key(&self) -> Key {
+        self.caller_key
+    }
+
+    pub(crate) fn set_caller_key(&mut self, caller_key: Key) {
+        self.caller_key = caller_key;
+    }
+
     pub fn get_blocktime(&self) -> BlockTime {
         self.blocktime
     }
@@ -227,10 +313,48 @@ where
         &self.args
     }
 
+    /// Returns the position of the arg named `name`, if any.
+    pub fn arg_index(&self, name: &str) -> Option<usize> {
+        self.arg_names.get(name).copied()
+    }
+
+    /// Names the args of this context by their position.
+    pub fn set_arg_names(&mut self, arg_names: BTreeMap<String, usize>) {
+        self.arg_names = arg_names;
+    }
+
     pub fn address_generator(&self) -> Rc<RefCell<AddressGenerator>> {
         Rc::clone(&self.address_generator)
     }
 
+    pub fn transient_storage(&self) -> Rc<RefCell<TransientStorage>> {
+        Rc::clone(&self.transient_storage)
+    }
+
+    /// Makes this context share `transient_storage` with the context of its caller.
+    pub fn set_transient_storage(&mut self, transient_storage: Rc<RefCell<TransientStorage>>) {
+        self.transient_storage = transient_storage;
+    }
+
+    pub fn host_call_stats(&self) -> Rc<RefCell<HostCallStats>> {
+        Rc::clone(&self.host_call_stats)
+    }
+
+    /// Makes this context tally its host function calls in `host_call_stats`.
+    pub fn set_host_call_stats(&mut self, host_call_stats: Rc<RefCell<HostCallStats>>) {
+        self.host_call_stats = host_call_stats;
+    }
+
+    pub fn pure_call_cache(&self) -> Rc<RefCell<PureCallCache>> {
+        Rc::clone(&self.pure_call_cache)
+    }
+
+    /// Makes this context look up and record the results of calls to functions declared pure in
+    /// `pure_call_cache`.
+    pub fn set_pure_call_cache(&mut self, pure_call_cache: Rc<RefCell<PureCallCache>>) {
+        self.pure_call_cache = pure_call_cache;
+    }
+
     pub fn state(&self) -> Rc<RefCell<TrackingCopy<R>>> {
         Rc::clone(&self.state)
     }
@@ -255,6 +379,12 @@ where
         self.base_key
     }
 
+    /// Returns the seed of the local keys of this context.
+    ///
+    /// The seed depends only on the identity of the base key, never on the code stored under it:
+    /// for a contract stored under a uref it is the uref's address, whatever access rights the
+    /// contract is called with.  Hence a contract replaced by `upgrade_contract_at_uref` keeps
+    /// reading and writing the local keys of the contract it replaced.
     pub fn seed(&self) -> [u8; KEY_LOCAL_SEED_LENGTH] {
         match self.base_key {
             Key::Account(PublicKey::Ed25519(bytes)) => bytes.value(),
@@ -272,6 +402,47 @@ where
         self.correlation_id
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn kind(&self) -> ExecutionContextKind {
+        self.kind
+    }
+
+    pub fn is_system(&self) -> bool {
+        self.kind == ExecutionContextKind::System
+    }
+
+    /// Only the executor's system entry points and sub-calls inheriting their caller's kind may
+    /// set this, so that a user deploy can never obtain a system context.
+    pub(crate) fn set_kind(&mut self, kind: ExecutionContextKind) {
+        self.kind = kind;
+    }
+
+    pub fn args_supplied_by_engine(&self) -> bool {
+        self.args_supplied_by_engine
+    }
+
+    /// Like the kind, only set by the executor's system entry points and by sub-calls, so that
+    /// arguments taken from a user can never be marked as supplied by the engine.
+    pub(crate) fn set_args_supplied_by_engine(&mut self, args_supplied_by_engine: bool) {
+        self.args_supplied_by_engine = args_supplied_by_engine;
+    }
+
+    /// Returns `Error::ReadOnlyContext` if the current context is not allowed to modify global
+    /// state.
+    pub fn validate_mutable(&self) -> Result<(), Error> {
+        if self.read_only {
+            return Err(Error::ReadOnlyContext);
+        }
+        Ok(())
+    }
+
     pub fn phase(&self) -> Phase {
         self.phase
     }
@@ -282,10 +453,21 @@ where
     /// every function generation. If function address was based only on
     /// account's public key and deploy's nonce, then all function addresses
     /// generated within one deploy would have been the same.
+    ///
+    /// From protocol version 1.1.0, `fn_store_id` is replaced by a new address of the address
+    /// generator, which is seeded with the deploy hash and the phase and is shared by all the
+    /// contexts of an execution.  `fn_store_id` restarts from zero in each phase and isn't passed
+    /// back from called contracts, so it can repeat within one deploy, whereas the addresses of the
+    /// generator can't.
     pub fn new_function_address(&mut self) -> Result<[u8; 32], Error> {
-        let mut pre_hash_bytes = Vec::with_capacity(36); //32 bytes for deploy hash + 4 bytes ID
+        let mut pre_hash_bytes = Vec::with_capacity(64); //32 bytes for deploy hash + 32 bytes ID
         pre_hash_bytes.extend_from_slice(&self.deploy_hash);
-        pre_hash_bytes.append(&mut self.fn_store_id().into_bytes()?);
+        if self.protocol_version >= contract_hash_derivation_protocol_version() {
+            let address = self.address_generator.borrow_mut().create_address();
+            pre_hash_bytes.extend_from_slice(&address);
+        } else {
+            pre_hash_bytes.append(&mut self.fn_store_id().into_bytes()?);
+        }
 
         self.inc_fn_store_id();
 
@@ -307,6 +489,27 @@ where
         Ok(uref)
     }
 
+    /// Generates a new uref flagged [`AccessRights::PUBLIC_READ`], stores `value` under it and
+    /// records the uref as public in global state.
+    ///
+    /// The record is what later reads of the uref from other contexts are validated against, so the
+    /// flag can't be forged on a uref which wasn't created public.
+    pub fn new_public_uref(&mut self, value: StoredValue) -> Result<URef, Error> {
+        let uref = {
+            let addr = self.address_generator.borrow_mut().create_address();
+            URef::new(
+                addr,
+                AccessRights::READ_ADD_WRITE | AccessRights::PUBLIC_READ,
+            )
+        };
+        self.insert_uref(uref);
+        self.write_gs(Key::URef(uref), value)?;
+        let public_uref = uref.with_access_rights(AccessRights::READ | AccessRights::PUBLIC_READ);
+        let record = StoredValue::CLValue(CLValue::from_t(public_uref)?);
+        self.write_unsafe(public_uref_record_key(uref.addr()), record)?;
+        Ok(uref)
+    }
+
     /// Puts `key` to the map of named keys of current context.
     pub fn put_key(&mut self, name: String, key: Key) -> Result<(), Error> {
         // No need to perform actual validation on the base key because an account or contract (i.e.
@@ -336,6 +539,7 @@ where
             .borrow_mut()
             .read(self.correlation_id, &key)
             .map_err(Into::into)?;
+        self.validate_effect_limits()?;
 
         if let Some(stored_value) = maybe_stored_value {
             Ok(Some(stored_value.try_into().map_err(Error::TypeMismatch)?))
@@ -347,20 +551,79 @@ where
     pub fn write_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
         let seed = self.seed();
         let key = Key::local(seed, key_bytes);
-        self.state
-            .borrow_mut()
-            .write(key, StoredValue::CLValue(cl_value));
-        Ok(())
+        self.write_unsafe(key, StoredValue::CLValue(cl_value))
+    }
+
+    /// Copies the values under all local keys of `old_seed` to the same local keys of this
+    /// context, returning the number of values copied.
+    ///
+    /// Local keys this context already holds a value under are left as they are, so migrating
+    /// again only copies the values written under `old_seed` since.  The values under `old_seed`
+    /// are left in place.
+    pub fn migrate_local_keys(
+        &mut self,
+        old_seed: [u8; KEY_LOCAL_SEED_LENGTH],
+    ) -> Result<u32, Error> {
+        self.validate_mutable()?;
+
+        let seed = self.seed();
+        if old_seed == seed {
+            return Ok(0);
+        }
+
+        let prefix = local_key_prefix(old_seed)?;
+        let old_keys = {
+            let state = self.state.borrow();
+            (&*state)
+                .keys_with_prefix(self.correlation_id, &prefix)
+                .map_err(Into::into)?
+        };
+
+        let mut migrated_count = 0;
+        for old_key in old_keys {
+            let hash = match old_key {
+                Key::Local { hash, .. } => hash,
+                _ => continue,
+            };
+            let new_key = Key::Local { seed, hash };
+            if self
+                .state
+                .borrow_mut()
+                .read(self.correlation_id, &new_key)
+                .map_err(Into::into)?
+                .is_some()
+            {
+                continue;
+            }
+            let maybe_value = self
+                .state
+                .borrow_mut()
+                .read(self.correlation_id, &old_key)
+                .map_err(Into::into)?;
+            if let Some(value) = maybe_value {
+                self.write_unsafe(new_key, value)?;
+                migrated_count += 1;
+            }
+        }
+        Ok(migrated_count)
     }
 
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
-        self.validate_readable(key)?;
-        self.validate_key(key)?;
+        match key {
+            Key::URef(uref) if uref.is_publicly_readable() => self.validate_public_uref(uref)?,
+            _ => {
+                self.validate_readable(key)?;
+                self.validate_key(key)?;
+            }
+        }
 
-        self.state
+        let maybe_stored_value = self
+            .state
             .borrow_mut()
             .read(self.correlation_id, key)
-            .map_err(Into::into)
+            .map_err(Into::into)?;
+        self.validate_effect_limits()?;
+        Ok(maybe_stored_value)
     }
 
     /// DO NOT EXPOSE THIS VIA THE FFI
@@ -397,10 +660,22 @@ where
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
-        self.state.borrow_mut().write(key, value);
+        self.write_unsafe(key, value)?;
         Ok(())
     }
 
+    /// Removes `key` and its value from global state once the effects of the execution are
+    /// committed.  Only a system context may prune, so this returns `Error::InvalidContext`
+    /// otherwise.
+    pub fn prune_gs(&mut self, key: Key) -> Result<(), Error> {
+        if !self.is_system() {
+            return Err(Error::InvalidContext);
+        }
+        self.validate_mutable()?;
+        self.state.borrow_mut().prune(key);
+        self.validate_effect_limits()
+    }
+
     pub fn read_account(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         if let Key::Account(_) = key {
             self.validate_key(key)?;
@@ -417,7 +692,7 @@ where
         if let Key::Account(_) = key {
             self.validate_key(&key)?;
             let account_value = self.account_to_validated_value(account)?;
-            self.state.borrow_mut().write(key, account_value);
+            self.write_unsafe(key, account_value)?;
             Ok(())
         } else {
             panic!("Do not use this function for writing non-account keys")
@@ -429,12 +704,63 @@ where
         self.new_uref(contract).map(|uref| uref.addr())
     }
 
+    /// Stores `contract` under a new [`Key::Hash`] and returns its hash.
+    ///
+    /// Fails rather than overwriting if a value is already stored under the hash, e.g. by an
+    /// earlier execution of the same deploy.
     pub fn store_function_at_hash(&mut self, contract: StoredValue) -> Result<[u8; 32], Error> {
         let new_hash = self.new_function_address()?;
+        self.write_function_at_hash(new_hash, contract)?;
+        Ok(new_hash)
+    }
+
+    /// Stores `contract` under a new [`Key::Hash`] as a function declared pure, and returns its
+    /// hash.
+    ///
+    /// The contract's named keys gain a [`PURE_FUNCTION_KEY_NAME`] entry referring to the hash.
+    pub fn store_pure_function_at_hash(
+        &mut self,
+        mut contract: Contract,
+    ) -> Result<[u8; 32], Error> {
+        let new_hash = self.new_function_address()?;
+        contract
+            .named_keys_mut()
+            .insert(PURE_FUNCTION_KEY_NAME.to_string(), Key::Hash(new_hash));
+        self.write_function_at_hash(new_hash, StoredValue::Contract(contract))?;
+        Ok(new_hash)
+    }
+
+    fn write_function_at_hash(
+        &mut self,
+        new_hash: [u8; 32],
+        contract: StoredValue,
+    ) -> Result<(), Error> {
         self.validate_value(&contract)?;
         let hash_key = Key::Hash(new_hash);
-        self.state.borrow_mut().write(hash_key, contract);
-        Ok(new_hash)
+        if self.read_gs_direct(&hash_key)?.is_some() {
+            return Err(Error::ContractHashCollision(new_hash));
+        }
+        self.write_unsafe(hash_key, contract)
+    }
+
+    /// Replaces the named keys of the current context with those of the entity under `base_key`, if
+    /// it was modified by a nested call into the same entity, e.g. a contract calling a contract
+    /// which calls the first one back.
+    ///
+    /// An entity's named keys may only be modified by the entity itself, and the modifications
+    /// are visible to all subsequent reads within the same deploy, whether the entity is stored
+    /// under a hash or a uref.
+    pub fn reload_named_keys(&mut self) {
+        let named_keys = match self.state.borrow().modified_named_keys(&self.base_key) {
+            Some(named_keys) => named_keys,
+            None => return,
+        };
+        for key in named_keys.values() {
+            if let Key::URef(uref) = key {
+                self.insert_uref(*uref);
+            }
+        }
+        *self.named_keys = named_keys;
     }
 
     pub fn insert_key(&mut self, name: String, key: Key) {
@@ -521,32 +847,48 @@ where
         self.validate_uref(uref)
     }
 
+    /// Returns the union of all access rights this context holds for `addr`, or `None` if `addr` is
+    /// unknown to this context.
+    pub fn effective_access_rights(&self, addr: Address) -> Option<AccessRights> {
+        let known_rights = self.access_rights.get(&addr).map(|known_rights| {
+            known_rights
+                .iter()
+                .fold(AccessRights::NONE, |acc, rights| acc | *rights)
+        });
+        let main_purse = self.account.main_purse();
+        if main_purse.addr() == addr {
+            return Some(known_rights.unwrap_or(AccessRights::NONE) | main_purse.access_rights());
+        }
+        known_rights
+    }
+
+    /// Validates that `uref` is known to this context with at least the access rights it carries.
+    ///
+    /// A uref may be narrowed before being passed on, i.e. carry any subset of the rights held for
+    /// its address, but a uref carrying rights beyond those held is a `ForgedReference`.
     pub fn validate_uref(&self, uref: &URef) -> Result<(), Error> {
-        if self.account.main_purse().addr() == uref.addr() {
-            // If passed uref matches account's purse then we have to also validate their
-            // access rights.
-            let rights = self.account.main_purse().access_rights();
-            let uref_rights = uref.access_rights();
-            // Access rights of the passed uref, and the account's purse should match
-            if rights & uref_rights == uref_rights {
-                return Ok(());
-            }
+        match self.effective_access_rights(uref.addr()) {
+            Some(held_rights) if held_rights.contains(uref.access_rights()) => Ok(()),
+            _ => Err(Error::ForgedReference(*uref)),
         }
+    }
 
-        // Check if the `key` is known
-        if let Some(known_rights) = self.access_rights.get(&uref.addr()) {
-            let new_rights = uref.access_rights();
-            // check if we have sufficient access rights
-            if known_rights
-                .iter()
-                .any(|right| *right & new_rights == new_rights)
-            {
-                Ok(())
-            } else {
-                Err(Error::ForgedReference(*uref))
-            }
+    /// Validates that `uref`, which carries the `PUBLIC_READ` flag, was created public, i.e. that
+    /// global state records it as public.
+    ///
+    /// Any context may read such a uref, whether or not it is known to the context.
+    fn validate_public_uref(&mut self, uref: &URef) -> Result<(), Error> {
+        let record_key = public_uref_record_key(uref.addr());
+        let is_public = match self.read_gs_direct(&record_key)? {
+            Some(StoredValue::CLValue(cl_value)) => cl_value
+                .into_t::<URef>()
+                .map(|public_uref| public_uref.addr() == uref.addr())
+                .unwrap_or(false),
+            _ => false,
+        };
+        if is_public {
+            Ok(())
         } else {
-            // uref is not known
             Err(Error::ForgedReference(*uref))
         }
     }
@@ -626,11 +968,28 @@ where
     /// values can't be added, either because they're not a Monoid or if the
     /// value stored under `key` has different type, then `TypeMismatch`
     /// errors is returned.
+    ///
+    /// Adding a named key is only valid to the entity under `base_key`, as no entity may modify
+    /// the named keys of another, and updates the named keys of the current context like
+    /// [`put_key`](RuntimeContext::put_key).
     pub fn add_gs(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
         self.validate_addable(&key)?;
         self.validate_key(&key)?;
         self.validate_value(&value)?;
-        self.add_unsafe(key, value)
+        let named_key = match &value {
+            StoredValue::CLValue(cl_value) if *cl_value.cl_type() == types::named_key_type() => {
+                if key.normalize() != self.base_key.normalize() {
+                    return Err(Error::InvalidContext);
+                }
+                Some(cl_value.to_owned().into_t::<(String, Key)>()?)
+            }
+            _ => None,
+        };
+        self.add_unsafe(key, value)?;
+        if let Some((name, named_key)) = named_key {
+            self.insert_key(name, named_key);
+        }
+        Ok(())
     }
 
     pub fn add_ls(&mut self, key_bytes: &[u8], cl_value: CLValue) -> Result<(), Error> {
@@ -639,10 +998,27 @@ where
         self.add_unsafe(key, StoredValue::CLValue(cl_value))
     }
 
+    /// Fails once the effects accumulated exceed the limits set on the tracking copy, so that the
+    /// execution is stopped as soon as possible.
+    fn validate_effect_limits(&self) -> Result<(), Error> {
+        if self.state.borrow().effect_limits_exceeded() {
+            Err(Error::EffectsTooLarge)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn write_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.validate_mutable()?;
+        self.state.borrow_mut().write(key, value);
+        self.validate_effect_limits()
+    }
+
     fn add_unsafe(&mut self, key: Key, value: StoredValue) -> Result<(), Error> {
+        self.validate_mutable()?;
         match self.state.borrow_mut().add(self.correlation_id, key, value) {
             Err(storage_error) => Err(storage_error.into()),
-            Ok(AddResult::Success) => Ok(()),
+            Ok(AddResult::Success) => self.validate_effect_limits(),
             Ok(AddResult::KeyNotFound(key)) => Err(Error::KeyNotFound(key)),
             Ok(AddResult::TypeMismatch(type_mismatch)) => Err(Error::TypeMismatch(type_mismatch)),
             Ok(AddResult::Serialization(error)) => Err(Error::BytesRepr(error)),
@@ -684,7 +1060,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -718,7 +1094,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -756,7 +1132,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -794,7 +1170,7 @@ where
 
         let account_value = self.account_to_validated_value(account)?;
 
-        self.state.borrow_mut().write(key, account_value);
+        self.write_unsafe(key, account_value)?;
 
         Ok(())
     }
@@ -812,7 +1188,7 @@ where
         self.validate_writeable(&key)?;
         self.validate_key(&key)?;
 
-        self.state.borrow_mut().write(key, contract);
+        self.write_unsafe(key, contract)?;
         Ok(())
     }
 
@@ -820,6 +1196,40 @@ where
         self.protocol_data
     }
 
+    /// Stores `value` under `name` in the transient storage of the execution, within the max size
+    /// set by the wasm costs.
+    pub fn put_transient(&mut self, name: String, value: CLValue) -> Result<(), Error> {
+        let max_size = self.protocol_data.wasm_costs().max_transient_storage_size as usize;
+        self.transient_storage
+            .borrow_mut()
+            .put(name, value, max_size)
+    }
+
+    pub fn get_transient(&self, name: &str) -> Option<CLValue> {
+        self.transient_storage.borrow().get(name).cloned()
+    }
+
+    /// Records `scheduled_call` under `schedule_id` and appends the id to the scheduled calls not
+    /// yet made.
+    pub fn schedule_call(
+        &mut self,
+        schedule_id: ScheduleId,
+        scheduled_call: ScheduledCall,
+    ) -> Result<(), Error> {
+        self.validate_mutable()?;
+        let mut schedule_ids =
+            scheduled_calls::read_index(&mut *self.state.borrow_mut(), self.correlation_id)?;
+        schedule_ids.push(schedule_id);
+        self.write_unsafe(
+            scheduled_calls::scheduled_call_key(schedule_id),
+            scheduled_calls::scheduled_call_value(scheduled_call)?,
+        )?;
+        self.write_unsafe(
+            *scheduled_calls::SCHEDULED_CALLS_KEY,
+            scheduled_calls::index_value(schedule_ids)?,
+        )
+    }
+
     /// Attenuates URef for a given account.
     ///
     /// If the account is system account, then given URef receives
@@ -847,4 +1257,43 @@ where
         }
         Ok(self.account().main_purse())
     }
+
+    /// Replaces the main purse of the account in global state, returning the main purse it
+    /// replaces.
+    ///
+    /// Note that [`RuntimeContext::get_main_purse`] keeps returning the main purse the account had
+    /// when the context was created.
+    pub fn set_main_purse(&mut self, main_purse: URef) -> Result<URef, Error> {
+        if !self.is_valid_context() {
+            return Err(Error::InvalidContext);
+        }
+
+        let key = Key::Account(self.account().public_key());
+        let mut account: Account = self.read_gs_typed(&key)?;
+        let previous_main_purse = account.main_purse();
+        account.set_main_purse(main_purse);
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.write_unsafe(key, account_value)?;
+
+        Ok(previous_main_purse)
+    }
+
+    /// Replaces the authorization contract of the account in global state, or clears it if
+    /// `authorization_contract` is `None`.
+    pub fn set_authorization_contract(
+        &mut self,
+        authorization_contract: Option<Key>,
+    ) -> Result<(), Error> {
+        if !self.is_valid_context() {
+            return Err(Error::InvalidContext);
+        }
+
+        let key = Key::Account(self.account().public_key());
+        let mut account: Account = self.read_gs_typed(&key)?;
+        account.set_authorization_contract(authorization_contract);
+
+        let account_value = self.account_to_validated_value(account)?;
+        self.write_unsafe(key, account_value)
+    }
 }
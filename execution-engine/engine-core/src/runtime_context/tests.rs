@@ -29,8 +29,10 @@ use types::{
 
 use super::{attenuate_uref_for_account, Address, Error, RuntimeContext};
 use crate::{
-    engine_state::SYSTEM_ACCOUNT_ADDR, execution::AddressGenerator,
-    runtime::extract_access_rights_from_keys, tracking_copy::TrackingCopy,
+    engine_state::SYSTEM_ACCOUNT_ADDR,
+    execution::{AddressGenerator, ExecutionContextKind},
+    runtime::{extract_access_rights_from_keys, extract_access_rights_from_urefs},
+    tracking_copy::TrackingCopy,
 };
 
 const DEPLOY_HASH: [u8; 32] = [1u8; 32];
@@ -121,7 +123,7 @@ fn mock_runtime_context<'a>(
         BTreeSet::from_iter(vec![PublicKey::ed25519_from([0; 32])]),
         &account,
         base_key,
-        BlockTime::new(0),
+        BlockTime::from_millis(0),
         [1u8; 32],
         Gas::default(),
         Gas::default(),
@@ -452,7 +454,7 @@ fn contract_key_addable_valid() {
         BTreeSet::from_iter(vec![PublicKey::ed25519_from(base_acc_addr)]),
         &account,
         contract_key,
-        BlockTime::new(0),
+        BlockTime::from_millis(0),
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -514,7 +516,7 @@ fn contract_key_addable_invalid() {
         BTreeSet::from_iter(vec![PublicKey::ed25519_from(base_acc_addr)]),
         &account,
         other_contract_key,
-        BlockTime::new(0),
+        BlockTime::from_millis(0),
         DEPLOY_HASH,
         Gas::default(),
         Gas::default(),
@@ -890,6 +892,45 @@ fn can_roundtrip_key_value_pairs_into_local_state() {
     assert!(query_result)
 }
 
+#[test]
+fn migrate_local_keys_copies_values_not_held_yet() {
+    let old_seed = [7u8; KEY_LOCAL_SEED_LENGTH];
+    let query = |mut runtime_context: RuntimeContext<InMemoryGlobalStateView>| {
+        for (key_bytes, value) in &[(b"a", 1u64), (b"b", 2), (b"c", 3)] {
+            let cl_value = CLValue::from_t(*value).unwrap();
+            runtime_context.state().borrow_mut().write(
+                Key::local(old_seed, *key_bytes),
+                StoredValue::CLValue(cl_value),
+            );
+        }
+        let held_value = CLValue::from_t(20u64).unwrap();
+        runtime_context
+            .write_ls(b"b", held_value)
+            .expect("should write_ls");
+
+        let migrated_count = runtime_context.migrate_local_keys(old_seed)?;
+
+        let read = |runtime_context: &mut RuntimeContext<InMemoryGlobalStateView>, key_bytes| {
+            runtime_context
+                .read_ls(key_bytes)
+                .expect("should read_ls")
+                .map(|cl_value| cl_value.into_t::<u64>().unwrap())
+        };
+        let values = (
+            read(&mut runtime_context, b"a"),
+            read(&mut runtime_context, b"b"),
+            read(&mut runtime_context, b"c"),
+        );
+        let again_count = runtime_context.migrate_local_keys(old_seed)?;
+        Ok((migrated_count, values, again_count))
+    };
+    let (migrated_count, values, again_count) =
+        test(HashMap::new(), query).expect("should migrate");
+    assert_eq!(migrated_count, 2);
+    assert_eq!(values, (Some(1), Some(20), Some(3)));
+    assert_eq!(again_count, 0);
+}
+
 #[test]
 fn remove_uref_works() {
     // Test that `remove_uref` removes Key from both ephemeral representation
@@ -955,6 +996,71 @@ fn validate_valid_purse_of_an_account() {
     assert!(runtime_context.validate_uref(&purse).is_err());
 }
 
+#[test]
+fn validate_narrowed_uref() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref = create_uref(&mut rng, AccessRights::READ_ADD_WRITE)
+        .into_uref()
+        .unwrap();
+    let access_rights = extract_access_rights_from_urefs(vec![uref]);
+
+    // Any subset of the held rights may be passed on.
+    for narrowed in &[
+        uref,
+        uref.as_read_only(),
+        uref.as_add_only(),
+        uref.with_access_rights(AccessRights::READ_WRITE),
+        uref.remove_access_rights(),
+    ] {
+        test(access_rights.clone(), |rc| rc.validate_uref(narrowed))
+            .expect("narrowed uref should be valid");
+    }
+
+    // Rights held through several urefs to the same address are combined.
+    let access_rights =
+        extract_access_rights_from_urefs(vec![uref.as_read_only(), uref.as_add_only()]);
+    test(access_rights.clone(), |rc| {
+        rc.validate_uref(&uref.with_access_rights(AccessRights::READ_ADD))
+    })
+    .expect("uref with combined rights should be valid");
+
+    // Escalating beyond the held rights is rejected.
+    assert_forged_reference(test(access_rights, |rc| rc.validate_uref(&uref)));
+}
+
+#[test]
+fn public_uref_readable_without_being_known() {
+    let value = StoredValue::CLValue(CLValue::from_t(42_i32).unwrap());
+    let result = test(HashMap::new(), |mut rc| {
+        let uref = rc.new_public_uref(value.clone())?;
+        assert!(uref.is_publicly_readable());
+        // Forget the uref, as a context which was never given it would.
+        rc.access_rights.remove(&uref.addr());
+
+        let public_uref = uref.with_access_rights(AccessRights::READ | AccessRights::PUBLIC_READ);
+        assert_eq!(rc.read_gs(&Key::URef(public_uref))?, Some(value.clone()));
+
+        // Reading doesn't extend to writing or adding.
+        assert_forged_reference(rc.write_gs(Key::URef(uref), value.clone()));
+        assert_forged_reference(rc.add_gs(Key::URef(uref), value));
+        Ok(())
+    });
+    result.expect("should read public uref");
+}
+
+#[test]
+fn public_read_flag_cannot_be_forged() {
+    let value = StoredValue::CLValue(CLValue::from_t(42_i32).unwrap());
+    let result = test(HashMap::new(), |mut rc| {
+        let uref = rc.new_uref(value)?;
+        rc.access_rights.remove(&uref.addr());
+
+        let forged_uref = uref.with_access_rights(AccessRights::READ | AccessRights::PUBLIC_READ);
+        rc.read_gs(&Key::URef(forged_uref))
+    });
+    assert_forged_reference(result);
+}
+
 #[test]
 fn attenuate_uref_for_system_account() {
     let (_key, account) = mock_account(SYSTEM_ACCOUNT_ADDR);
@@ -974,3 +1080,271 @@ fn attenuate_uref_for_user_account() {
     let access_rights = attenuated_uref.access_rights();
     assert_eq!(access_rights, AccessRights::READ);
 }
+
+#[test]
+fn read_only_context_rejects_mutations() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_key = create_uref(&mut rng, AccessRights::READ_ADD_WRITE);
+    let access_rights = extract_access_rights_from_keys(vec![uref_key]);
+    let query_result = test(access_rights, |mut rc| {
+        rc.set_read_only(true);
+        let value = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+        assert!(rc.read_gs(&uref_key).is_ok());
+        match rc.write_gs(uref_key, value.clone()) {
+            Err(Error::ReadOnlyContext) => (),
+            other => panic!("expected ReadOnlyContext error, got {:?}", other),
+        }
+        match rc.add_gs(uref_key, value) {
+            Err(Error::ReadOnlyContext) => (),
+            other => panic!("expected ReadOnlyContext error, got {:?}", other),
+        }
+        rc.put_key(String::from("key"), uref_key)
+    });
+    match query_result {
+        Err(Error::ReadOnlyContext) => (),
+        other => panic!("expected ReadOnlyContext error, got {:?}", other),
+    }
+}
+
+#[test]
+fn new_context_is_user_context() {
+    let query_result = test(HashMap::new(), |rc| {
+        assert_eq!(rc.kind(), ExecutionContextKind::User);
+        assert!(!rc.is_system());
+        Ok(())
+    });
+    query_result.expect("should be a user context");
+}
+
+#[test]
+fn should_not_prune_in_user_context() {
+    let query_result = test(HashMap::new(), |mut rc| {
+        let key = rc.base_key();
+        let result = rc.prune_gs(key);
+        assert!(rc.read_gs(&key)?.is_some());
+        result
+    });
+    match query_result {
+        Err(Error::InvalidContext) => {}
+        other => panic!("expected InvalidContext, got {:?}", other),
+    }
+}
+
+#[test]
+fn should_prune_in_system_context() {
+    let query_result = test(HashMap::new(), |mut rc| {
+        rc.set_kind(ExecutionContextKind::System);
+        let key = rc.base_key();
+        assert!(rc.read_gs(&key)?.is_some());
+
+        rc.prune_gs(key)?;
+
+        assert_eq!(rc.read_gs(&key)?, None);
+        assert_eq!(
+            rc.effect().transforms.get(&key),
+            Some(&Transform::Prune),
+            "should emit a prune"
+        );
+        Ok(())
+    });
+    query_result.expect("should prune");
+}
+
+/// Stores a contract at hash in each of two contexts of the same execution, e.g. a session and a
+/// contract it calls, both starting with an `fn_store_id` of zero.
+fn store_function_at_hash_in_two_contexts(
+    protocol_version: ProtocolVersion,
+) -> (Result<[u8; 32], Error>, Result<[u8; 32], Error>) {
+    let (key, account) = mock_account(PublicKey::ed25519_from([0; 32]));
+    let tc = Rc::new(RefCell::new(mock_tc(key, account.clone())));
+    let address_generator = Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE)));
+    let contract = StoredValue::Contract(Contract::new(
+        Vec::new(),
+        BTreeMap::new(),
+        ProtocolVersion::V1_0_0,
+    ));
+
+    let store = || {
+        let mut named_keys = BTreeMap::new();
+        let mut runtime_context = RuntimeContext::new(
+            Rc::clone(&tc),
+            &mut named_keys,
+            HashMap::new(),
+            Vec::new(),
+            BTreeSet::from_iter(vec![PublicKey::ed25519_from([0; 32])]),
+            &account,
+            key,
+            BlockTime::from_millis(0),
+            DEPLOY_HASH,
+            Gas::default(),
+            Gas::default(),
+            0,
+            Rc::clone(&address_generator),
+            protocol_version,
+            CorrelationId::new(),
+            PHASE,
+            Default::default(),
+        );
+        runtime_context.store_function_at_hash(contract.clone())
+    };
+    let first_result = store();
+    let second_result = store();
+    (first_result, second_result)
+}
+
+#[test]
+fn store_function_at_hash_should_not_overwrite_existing_value() {
+    let (first_result, second_result) =
+        store_function_at_hash_in_two_contexts(ProtocolVersion::V1_0_0);
+    let first_hash = first_result.expect("should store first contract");
+    match second_result {
+        Err(Error::ContractHashCollision(hash)) => assert_eq!(hash, first_hash),
+        other => panic!("expected contract hash collision, got {:?}", other),
+    }
+}
+
+#[test]
+fn store_function_at_hash_should_derive_distinct_hashes_across_contexts() {
+    let (first_result, second_result) =
+        store_function_at_hash_in_two_contexts(ProtocolVersion::from_parts(1, 1, 0));
+    let first_hash = first_result.expect("should store first contract");
+    let second_hash = second_result.expect("should store second contract");
+    assert_ne!(first_hash, second_hash);
+}
+
+/// Returns a tracking copy holding an account and a contract stored under a uref, along with the
+/// account and the contract's uref.
+fn mock_tc_with_contract_at_uref() -> (
+    Rc<RefCell<TrackingCopy<InMemoryGlobalStateView>>>,
+    Account,
+    Key,
+) {
+    let (account_key, account) = mock_account(PublicKey::ed25519_from([0; 32]));
+    let tc = Rc::new(RefCell::new(mock_tc(account_key, account.clone())));
+    let mut address_generator = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let contract_key = create_uref(&mut address_generator, AccessRights::READ_ADD_WRITE);
+    let contract = StoredValue::Contract(Contract::new(
+        Vec::new(),
+        BTreeMap::new(),
+        ProtocolVersion::V1_0_0,
+    ));
+    tc.borrow_mut().write(contract_key, contract);
+    (tc, account, contract_key)
+}
+
+fn runtime_context_with_tc<'a>(
+    tc: &Rc<RefCell<TrackingCopy<InMemoryGlobalStateView>>>,
+    account: &'a Account,
+    base_key: Key,
+    named_keys: &'a mut BTreeMap<String, Key>,
+    access_rights: HashMap<Address, HashSet<AccessRights>>,
+) -> RuntimeContext<'a, InMemoryGlobalStateView> {
+    RuntimeContext::new(
+        Rc::clone(tc),
+        named_keys,
+        access_rights,
+        Vec::new(),
+        BTreeSet::from_iter(vec![PublicKey::ed25519_from([0; 32])]),
+        account,
+        base_key,
+        BlockTime::from_millis(0),
+        DEPLOY_HASH,
+        Gas::default(),
+        Gas::default(),
+        0,
+        Rc::new(RefCell::new(AddressGenerator::new(&DEPLOY_HASH, PHASE))),
+        ProtocolVersion::V1_0_0,
+        CorrelationId::new(),
+        PHASE,
+        Default::default(),
+    )
+}
+
+#[test]
+fn named_key_added_to_base_key_is_visible() {
+    let named_key =
+        StoredValue::CLValue(CLValue::from_t((String::from("key"), Key::Hash([1; 32]))).unwrap());
+    let query_result = test(HashMap::new(), |mut rc| {
+        let base_key = rc.base_key();
+        rc.add_gs(base_key, named_key)?;
+        Ok(rc.named_keys_get("key").cloned())
+    });
+    assert_eq!(query_result.expect("should add"), Some(Key::Hash([1; 32])));
+}
+
+#[test]
+fn named_key_of_other_entity_is_not_addable() {
+    let (tc, account, contract_key) = mock_tc_with_contract_at_uref();
+    let mut named_keys = BTreeMap::new();
+    let access_rights = extract_access_rights_from_keys(vec![contract_key]);
+    let mut runtime_context = runtime_context_with_tc(
+        &tc,
+        &account,
+        Key::Account(account.public_key()),
+        &mut named_keys,
+        access_rights,
+    );
+
+    let named_key =
+        StoredValue::CLValue(CLValue::from_t((String::from("key"), Key::Hash([1; 32]))).unwrap());
+    match runtime_context.add_gs(contract_key, named_key) {
+        Err(Error::InvalidContext) => (),
+        other => panic!("expected InvalidContext error, got {:?}", other),
+    }
+    assert!(!runtime_context.named_keys_contains_key("key"));
+    match runtime_context.read_gs(&contract_key) {
+        Ok(Some(StoredValue::Contract(contract))) => assert!(contract.named_keys().is_empty()),
+        other => panic!("expected contract, got {:?}", other),
+    }
+}
+
+#[test]
+fn reload_named_keys_should_pick_up_modifications_by_nested_context() {
+    let (tc, account, contract_key) = mock_tc_with_contract_at_uref();
+    let mut outer_named_keys = BTreeMap::new();
+    let mut outer_context = runtime_context_with_tc(
+        &tc,
+        &account,
+        contract_key,
+        &mut outer_named_keys,
+        HashMap::new(),
+    );
+
+    // a nested call into the same contract
+    {
+        let mut inner_named_keys = BTreeMap::new();
+        let mut inner_context = runtime_context_with_tc(
+            &tc,
+            &account,
+            contract_key,
+            &mut inner_named_keys,
+            HashMap::new(),
+        );
+        inner_context
+            .put_key(String::from("key"), Key::Hash([1; 32]))
+            .expect("should put key");
+    }
+    assert!(!outer_context.named_keys_contains_key("key"));
+
+    outer_context.reload_named_keys();
+    assert_eq!(
+        outer_context.named_keys_get("key"),
+        Some(&Key::Hash([1; 32]))
+    );
+}
+
+#[test]
+fn reload_named_keys_should_keep_named_keys_of_unmodified_entity() {
+    let (tc, account, _) = mock_tc_with_contract_at_uref();
+    let mut named_keys = iter::once((String::from("key"), Key::Hash([1; 32]))).collect();
+    let mut runtime_context = runtime_context_with_tc(
+        &tc,
+        &account,
+        Key::Account(account.public_key()),
+        &mut named_keys,
+        HashMap::new(),
+    );
+
+    runtime_context.reload_named_keys();
+    assert!(runtime_context.named_keys_contains_key("key"));
+}
@@ -5,7 +5,7 @@ pub(self) mod meter;
 mod tests;
 
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     convert::From,
     iter,
 };
@@ -19,8 +19,11 @@ use engine_shared::{
     transform::{self, Transform},
     TypeMismatch,
 };
-use engine_storage::global_state::StateReader;
-use types::{bytesrepr, CLType, CLValueError, Key};
+use engine_storage::{global_state::StateReader, trie::TrieMerkleProof};
+use types::{
+    bytesrepr::{self, ToBytes},
+    CLType, CLValueError, Key,
+};
 
 use crate::engine_state::{execution_effect::ExecutionEffect, op::Op};
 
@@ -100,6 +103,7 @@ pub struct TrackingCopyCache<M> {
     current_cache_size: usize,
     reads_cached: LinkedHashMap<Key, StoredValue>,
     muts_cached: HashMap<Key, StoredValue>,
+    pruned: HashSet<Key>,
     meter: M,
 }
 
@@ -114,6 +118,7 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
             current_cache_size: 0,
             reads_cached: LinkedHashMap::new(),
             muts_cached: HashMap::new(),
+            pruned: HashSet::new(),
             meter,
         }
     }
@@ -136,9 +141,24 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
     /// Inserts `key` and `value` pair to Write/Add cache.
     pub fn insert_write(&mut self, key: Key, value: StoredValue) {
+        self.pruned.remove(&key);
         self.muts_cached.insert(key, value);
     }
 
+    /// Marks `key` as pruned, so that it's no longer found in the cache nor read from state.
+    pub fn insert_prune(&mut self, key: Key) {
+        self.muts_cached.remove(&key);
+        if let Some(value) = self.reads_cached.remove(&key) {
+            self.current_cache_size -= Meter::measure(&self.meter, &key, &value);
+        }
+        self.pruned.insert(key);
+    }
+
+    /// Returns `true` if `key` was pruned and not written since.
+    pub fn is_pruned(&self, key: &Key) -> bool {
+        self.pruned.contains(key)
+    }
+
     /// Gets value from `key` in the cache.
     pub fn get(&mut self, key: &Key) -> Option<&StoredValue> {
         if let Some(value) = self.muts_cached.get(&key) {
@@ -147,6 +167,35 @@ impl<M: Meter<Key, StoredValue>> TrackingCopyCache<M> {
 
         self.reads_cached.get_refresh(key).map(|v| &*v)
     }
+
+    /// Gets value from `key` in the Write/Add cache only.
+    pub fn get_mutated(&self, key: &Key) -> Option<&StoredValue> {
+        self.muts_cached.get(key)
+    }
+}
+
+/// Limits on the effects a [`TrackingCopy`] may accumulate.  A limit of zero means no limit.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct EffectLimits {
+    /// Max number of transforms, i.e. of distinct keys read or modified.
+    pub max_transforms: u32,
+    /// Max total size in bytes of the serialized keys and values of the transforms.
+    pub max_effect_bytes: u32,
+}
+
+impl EffectLimits {
+    pub fn new(max_transforms: u32, max_effect_bytes: u32) -> Self {
+        EffectLimits {
+            max_transforms,
+            max_effect_bytes,
+        }
+    }
+
+    fn are_exceeded_by(&self, transform_count: usize, effect_bytes: usize) -> bool {
+        let exceeds = |max: u32, value: usize| max != 0 && value > max as usize;
+        exceeds(self.max_transforms, transform_count)
+            || exceeds(self.max_effect_bytes, effect_bytes)
+    }
 }
 
 pub struct TrackingCopy<R> {
@@ -154,6 +203,32 @@ pub struct TrackingCopy<R> {
     cache: TrackingCopyCache<HeapSize>,
     ops: AdditiveMap<Key, Op>,
     fns: AdditiveMap<Key, Transform>,
+    effect_limits: EffectLimits,
+    /// The size of `fns` as measured by `transform_size()`, kept up to date as transforms are
+    /// added so that the effects never need to be serialized to check the limits.
+    effect_bytes: usize,
+    /// The keys read during each recording started by `record_reads` and not yet taken, innermost
+    /// last.
+    recorded_reads: Vec<BTreeSet<Key>>,
+}
+
+/// The changes made to a fork of a [`TrackingCopy`], taken so that they can be applied to the
+/// tracking copy it was forked from.
+pub struct TrackingCopyChanges {
+    ops: AdditiveMap<Key, Op>,
+    fns: AdditiveMap<Key, Transform>,
+    muts_cached: HashMap<Key, StoredValue>,
+    pruned: HashSet<Key>,
+}
+
+/// The changes made to a [`TrackingCopy`] up to some point, taken so that any changes made after
+/// that point can be discarded via [`TrackingCopy::restore`].
+pub struct TrackingCopyCheckpoint {
+    ops: AdditiveMap<Key, Op>,
+    fns: AdditiveMap<Key, Transform>,
+    effect_bytes: usize,
+    muts_cached: HashMap<Key, StoredValue>,
+    pruned: HashSet<Key>,
 }
 
 #[derive(Debug)]
@@ -186,6 +261,9 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                                                                  * limit? */
             ops: AdditiveMap::new(),
             fns: AdditiveMap::new(),
+            effect_limits: EffectLimits::default(),
+            effect_bytes: 0,
+            recorded_reads: Vec::new(),
         }
     }
 
@@ -193,6 +271,28 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         &self.reader
     }
 
+    /// Sets the limits on the effects accumulated from now on.  Forks don't inherit the limits.
+    pub fn set_effect_limits(&mut self, effect_limits: EffectLimits) {
+        self.effect_limits = effect_limits;
+    }
+
+    /// Returns `true` if the effects accumulated exceed the limits set on this tracking copy.
+    pub fn effect_limits_exceeded(&self) -> bool {
+        self.effect_limits
+            .are_exceeded_by(self.fns.len(), self.effect_bytes)
+    }
+
+    fn insert_transform(&mut self, key: Key, transform: Transform) {
+        let previous_size = self
+            .fns
+            .get(&key)
+            .map(|previous| transform_size(&key, previous))
+            .unwrap_or_default();
+        self.fns.insert_add(key, transform);
+        let size = transform_size(&key, &self.fns[&key]);
+        self.effect_bytes = self.effect_bytes - previous_size + size;
+    }
+
     /// Creates a new TrackingCopy, using this one (including its mutations) as
     /// the base state to read against. The intended use case for this
     /// function is to "snapshot" the current `TrackingCopy` and produce a
@@ -200,20 +300,72 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     /// allows isolating a specific set of changes (those in the new
     /// `TrackingCopy`) from existing changes. Note that mutations to state
     /// caused by new changes (i.e. writes and adds) only impact the new
-    /// `TrackingCopy`, not this one. Note that currently there is no `join` /
-    /// `merge` function to bring changes from a fork back to the main
-    /// `TrackingCopy`. this means the current usage requires repeated
-    /// forking, however we recognize this is sub-optimal and will revisit
-    /// in the future.
+    /// `TrackingCopy`, not this one. The changes can be brought back to this
+    /// `TrackingCopy` once the fork is dropped, via [`TrackingCopy::into_changes`]
+    /// and [`TrackingCopy::apply_changes`].
     pub fn fork(&self) -> TrackingCopy<&TrackingCopy<R>> {
         TrackingCopy::new(self)
     }
 
+    /// Takes the changes made to this tracking copy, so that they can be applied to the tracking
+    /// copy it was forked from.
+    pub fn into_changes(self) -> TrackingCopyChanges {
+        TrackingCopyChanges {
+            ops: self.ops,
+            fns: self.fns,
+            muts_cached: self.cache.muts_cached,
+            pruned: self.cache.pruned,
+        }
+    }
+
+    /// Applies `changes` taken from a fork of this tracking copy, as if they had been made to this
+    /// tracking copy directly.
+    pub fn apply_changes(&mut self, changes: TrackingCopyChanges) {
+        for (key, op) in changes.ops {
+            self.ops.insert_add(key, op);
+        }
+        for (key, transform) in changes.fns {
+            self.insert_transform(key, transform);
+        }
+        for (key, value) in changes.muts_cached {
+            self.cache.insert_write(key, value);
+        }
+        for key in changes.pruned {
+            self.cache.insert_prune(key);
+        }
+    }
+
+    /// Takes a copy of the changes made so far, so that the changes made after this call can be
+    /// discarded.  Unlike a fork, the tracking copy keeps its type, so it can be used where the
+    /// tracking copy is shared with other contexts of an execution.
+    pub fn checkpoint(&self) -> TrackingCopyCheckpoint {
+        TrackingCopyCheckpoint {
+            ops: self.ops.clone(),
+            fns: self.fns.clone(),
+            effect_bytes: self.effect_bytes,
+            muts_cached: self.cache.muts_cached.clone(),
+            pruned: self.cache.pruned.clone(),
+        }
+    }
+
+    /// Discards the changes made since `checkpoint` was taken.  Values read from the reader stay
+    /// cached, as they are unaffected by the changes.
+    pub fn restore(&mut self, checkpoint: TrackingCopyCheckpoint) {
+        self.ops = checkpoint.ops;
+        self.fns = checkpoint.fns;
+        self.effect_bytes = checkpoint.effect_bytes;
+        self.cache.muts_cached = checkpoint.muts_cached;
+        self.cache.pruned = checkpoint.pruned;
+    }
+
     pub fn get(
         &mut self,
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.get(key) {
             return Ok(Some(value.to_owned()));
         }
@@ -225,26 +377,63 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
         }
     }
 
+    /// Returns the named keys of the account or contract under `key` if it was modified through
+    /// this tracking copy.  Unlike [`read`](TrackingCopy::read), no read of `key` is recorded.
+    pub fn modified_named_keys(&self, key: &Key) -> Option<BTreeMap<String, Key>> {
+        match self.cache.get_mutated(&key.normalize())? {
+            StoredValue::Account(account) => Some(account.named_keys().clone()),
+            StoredValue::Contract(contract) => Some(contract.named_keys().clone()),
+            StoredValue::CLValue(_) => None,
+        }
+    }
+
     pub fn read(
         &mut self,
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, R::Error> {
         let normalized_key = key.normalize();
+        for recorded_reads in &mut self.recorded_reads {
+            recorded_reads.insert(normalized_key);
+        }
         if let Some(value) = self.get(correlation_id, &normalized_key)? {
             self.ops.insert_add(normalized_key, Op::Read);
-            self.fns.insert_add(normalized_key, Transform::Identity);
+            self.insert_transform(normalized_key, Transform::Identity);
             Ok(Some(value))
         } else {
             Ok(None)
         }
     }
 
+    /// Starts recording the keys read via [`read`](TrackingCopy::read), including keys holding no
+    /// value, until the matching call to
+    /// [`take_recorded_reads`](TrackingCopy::take_recorded_reads).
+    ///
+    /// Recordings nest: the keys read during an inner recording are recorded by the outer ones too.
+    pub fn record_reads(&mut self) {
+        self.recorded_reads.push(BTreeSet::new());
+    }
+
+    /// Stops the innermost recording started by [`record_reads`](TrackingCopy::record_reads) and
+    /// returns the keys read since it started.
+    pub fn take_recorded_reads(&mut self) -> BTreeSet<Key> {
+        self.recorded_reads.pop().unwrap_or_default()
+    }
+
     pub fn write(&mut self, key: Key, value: StoredValue) {
         let normalized_key = key.normalize();
         self.cache.insert_write(normalized_key, value.clone());
         self.ops.insert_add(normalized_key, Op::Write);
-        self.fns.insert_add(normalized_key, Transform::Write(value));
+        self.insert_transform(normalized_key, Transform::Write(value));
+    }
+
+    /// Removes `key` and its value from global state once the effects are committed.  Until then,
+    /// `key` is no longer found through this tracking copy.
+    pub fn prune(&mut self, key: Key) {
+        let normalized_key = key.normalize();
+        self.cache.insert_prune(normalized_key);
+        self.ops.insert_add(normalized_key, Op::Write);
+        self.insert_transform(normalized_key, Transform::Prune);
     }
 
     /// Ok(None) represents missing key to which we want to "add" some value.
@@ -314,7 +503,7 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
             Ok(new_value) => {
                 self.cache.insert_write(normalized_key, new_value);
                 self.ops.insert_add(normalized_key, Op::Add);
-                self.fns.insert_add(normalized_key, transform);
+                self.insert_transform(normalized_key, transform);
                 Ok(AddResult::Success)
             }
             Err(transform::Error::TypeMismatch(type_mismatch)) => {
@@ -399,6 +588,21 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
     }
 }
 
+/// Returns the size in bytes of `key` and the value carried by `transform` once serialized.
+fn transform_size(key: &Key, transform: &Transform) -> usize {
+    let value_size = match transform {
+        Transform::Identity | Transform::Prune | Transform::Failure(_) => 0,
+        Transform::Write(value) => value.serialized_length(),
+        Transform::AddInt32(value) => value.serialized_length(),
+        Transform::AddUInt64(value) => value.serialized_length(),
+        Transform::AddUInt128(value) => value.serialized_length(),
+        Transform::AddUInt256(value) => value.serialized_length(),
+        Transform::AddUInt512(value) => value.serialized_length(),
+        Transform::AddKeys(named_keys) => named_keys.serialized_length(),
+    };
+    key.serialized_length() + value_size
+}
+
 /// The purpose of this implementation is to allow a "snapshot" mechanism for
 /// TrackingCopy. The state of a TrackingCopy (including the effects of
 /// any transforms it has accumulated) can be read using an immutable
@@ -412,6 +616,9 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
+        if self.cache.is_pruned(key) {
+            return Ok(None);
+        }
         if let Some(value) = self.cache.muts_cached.get(key) {
             return Ok(Some(value.to_owned()));
         }
@@ -421,4 +628,34 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
             Ok(None)
         }
     }
+
+    /// Proves the value committed to global state, ignoring any value written to this
+    /// `TrackingCopy`, which isn't held by a trie yet.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    /// Includes the keys written to this `TrackingCopy` but not yet committed.
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let mut keys = self.reader.keys_with_prefix(correlation_id, prefix)?;
+        keys.retain(|key| !self.cache.is_pruned(key));
+        for key in self.cache.muts_cached.keys() {
+            let has_prefix = key
+                .to_bytes()
+                .map(|bytes| bytes.starts_with(prefix))
+                .unwrap_or(false);
+            if has_prefix && !keys.contains(key) {
+                keys.push(*key);
+            }
+        }
+        Ok(keys)
+    }
 }
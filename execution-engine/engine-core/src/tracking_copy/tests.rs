@@ -1,4 +1,9 @@
-use std::{cell::Cell, collections::BTreeMap, iter, rc::Rc};
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, BTreeSet},
+    iter,
+    rc::Rc,
+};
 
 use assert_matches::assert_matches;
 use proptest::{collection::vec, prelude::*};
@@ -10,7 +15,10 @@ use engine_shared::{
     stored_value::{gens::stored_value_arb, StoredValue},
     transform::Transform,
 };
-use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader};
+use engine_storage::{
+    global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
+    trie::TrieMerkleProof,
+};
 use types::{
     account::{PublicKey, Weight, ED25519_LENGTH},
     gens::*,
@@ -18,7 +26,8 @@ use types::{
 };
 
 use super::{
-    meter::count_meter::Count, AddResult, TrackingCopy, TrackingCopyCache, TrackingCopyQueryResult,
+    meter::count_meter::Count, transform_size, AddResult, EffectLimits, TrackingCopy,
+    TrackingCopyCache, TrackingCopyQueryResult,
 };
 use crate::engine_state::op::Op;
 
@@ -58,6 +67,22 @@ impl StateReader<Key, StoredValue> for CountingDb {
         self.count.set(count + 1);
         Ok(Some(value))
     }
+
+    fn read_with_proof(
+        &self,
+        _correlation_id: CorrelationId,
+        _key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        Ok(None)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        _correlation_id: CorrelationId,
+        _prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        Ok(Vec::new())
+    }
 }
 
 #[test]
@@ -285,6 +310,115 @@ fn tracking_copy_aw() {
     assert_eq!(tc.ops.get(&k), Some(&Op::Write));
 }
 
+#[test]
+fn tracking_copy_apply_fork_changes() {
+    let correlation_id = CorrelationId::new();
+    let db = CountingDb::new_init(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()));
+    let mut tc = TrackingCopy::new(db);
+    let added_key = Key::Hash([0u8; 32]);
+    let written_key = Key::Hash([1u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let _ = tc.add(correlation_id, added_key, one.clone());
+
+    let changes = {
+        let mut fork = tc.fork();
+        let _ = fork.add(correlation_id, added_key, one);
+        fork.write(
+            written_key,
+            StoredValue::CLValue(CLValue::from_t(7_i32).unwrap()),
+        );
+        fork.into_changes()
+    };
+    tc.apply_changes(changes);
+
+    // the transforms of the fork are composed with those already held
+    assert_eq!(tc.fns.len(), 2);
+    assert_eq!(tc.fns.get(&added_key), Some(&Transform::AddInt32(2)));
+    assert_eq!(tc.ops.get(&added_key), Some(&Op::Add));
+    assert_eq!(tc.ops.get(&written_key), Some(&Op::Write));
+    assert_eq!(
+        tc.get(correlation_id, &added_key).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(3_i32).unwrap()))
+    );
+    assert_eq!(
+        tc.get(correlation_id, &written_key).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(7_i32).unwrap()))
+    );
+    assert_eq!(tc.effect_bytes, recomputed_effect_bytes(&tc));
+}
+
+#[test]
+fn tracking_copy_record_reads() {
+    let correlation_id = CorrelationId::new();
+    let db = CountingDb::new_init(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()));
+    let mut tc = TrackingCopy::new(db);
+    let outer_key = Key::Hash([0u8; 32]);
+    let inner_key = Key::Hash([1u8; 32]);
+    let unrecorded_key = Key::Hash([2u8; 32]);
+
+    let _ = tc.read(correlation_id, &unrecorded_key);
+    tc.record_reads();
+    let _ = tc.read(correlation_id, &outer_key);
+    tc.record_reads();
+    let _ = tc.read(correlation_id, &inner_key);
+    let inner_reads = tc.take_recorded_reads();
+    let outer_reads = tc.take_recorded_reads();
+
+    assert_eq!(inner_reads, iter::once(inner_key).collect::<BTreeSet<_>>());
+    assert_eq!(
+        outer_reads,
+        vec![outer_key, inner_key]
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+    );
+    // reads made once all recordings are taken aren't recorded
+    let _ = tc.read(correlation_id, &unrecorded_key);
+    assert!(tc.take_recorded_reads().is_empty());
+}
+
+#[test]
+fn tracking_copy_restore_checkpoint() {
+    let correlation_id = CorrelationId::new();
+    let db = CountingDb::new_init(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()));
+    let mut tc = TrackingCopy::new(db);
+    let added_key = Key::Hash([0u8; 32]);
+    let written_key = Key::Hash([1u8; 32]);
+    let pruned_key = Key::Hash([2u8; 32]);
+
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    let _ = tc.add(correlation_id, added_key, one.clone());
+    let effect_bytes = tc.effect_bytes;
+
+    let checkpoint = tc.checkpoint();
+    let _ = tc.add(correlation_id, added_key, one);
+    tc.write(
+        written_key,
+        StoredValue::CLValue(CLValue::from_t(7_i32).unwrap()),
+    );
+    tc.prune(pruned_key);
+    tc.restore(checkpoint);
+
+    // only the changes made before the checkpoint remain
+    assert_eq!(tc.fns.len(), 1);
+    assert_eq!(tc.fns.get(&added_key), Some(&Transform::AddInt32(1)));
+    assert_eq!(tc.ops.get(&written_key), None);
+    assert_eq!(tc.ops.get(&pruned_key), None);
+    assert_eq!(tc.effect_bytes, effect_bytes);
+    assert_eq!(
+        tc.get(correlation_id, &added_key).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(2_i32).unwrap()))
+    );
+    assert_eq!(
+        tc.get(correlation_id, &written_key).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()))
+    );
+    assert_eq!(
+        tc.get(correlation_id, &pruned_key).unwrap(),
+        Some(StoredValue::CLValue(CLValue::from_t(1_i32).unwrap()))
+    );
+}
+
 proptest! {
     #[test]
     fn query_empty_path(k in key_arb(), missing_key in key_arb(), v in stored_value_arb()) {
@@ -432,6 +566,69 @@ proptest! {
     }
 }
 
+fn recomputed_effect_bytes<R>(tc: &TrackingCopy<R>) -> usize {
+    tc.fns
+        .iter()
+        .map(|(key, transform)| transform_size(key, transform))
+        .sum()
+}
+
+#[test]
+fn tracking_copy_effect_bytes() {
+    let correlation_id = CorrelationId::new();
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let k1 = Key::Hash([1u8; 32]);
+    let k2 = Key::Hash([2u8; 32]);
+
+    tc.read(correlation_id, &k1).unwrap();
+    assert_eq!(tc.effect_bytes, recomputed_effect_bytes(&tc));
+
+    // a write replaces the identity transform
+    let long_string = StoredValue::CLValue(CLValue::from_t("a".repeat(100)).unwrap());
+    tc.write(k1, long_string);
+    assert_eq!(tc.effect_bytes, recomputed_effect_bytes(&tc));
+
+    // a shorter value replaces the size of the longer one
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    tc.write(k1, one.clone());
+    assert_eq!(tc.effect_bytes, recomputed_effect_bytes(&tc));
+
+    let add = tc.add(correlation_id, k2, one);
+    assert_matches!(add, Ok(AddResult::Success));
+    assert_eq!(tc.effect_bytes, recomputed_effect_bytes(&tc));
+}
+
+#[test]
+fn tracking_copy_effect_limits() {
+    let counter = Rc::new(Cell::new(0));
+    let db = CountingDb::new(counter);
+    let mut tc = TrackingCopy::new(db);
+    let one = StoredValue::CLValue(CLValue::from_t(1_i32).unwrap());
+    for i in 0..3 {
+        tc.write(Key::Hash([i; 32]), one.clone());
+    }
+    let effect_bytes = tc.effect_bytes as u32;
+
+    // no limits by default
+    assert!(!tc.effect_limits_exceeded());
+
+    tc.set_effect_limits(EffectLimits::new(3, effect_bytes));
+    assert!(!tc.effect_limits_exceeded());
+
+    tc.set_effect_limits(EffectLimits::new(2, 0));
+    assert!(tc.effect_limits_exceeded());
+
+    tc.set_effect_limits(EffectLimits::new(0, effect_bytes - 1));
+    assert!(tc.effect_limits_exceeded());
+
+    // overwriting a key adds no transform
+    tc.set_effect_limits(EffectLimits::new(3, 0));
+    tc.write(Key::Hash([0; 32]), one);
+    assert!(!tc.effect_limits_exceeded());
+}
+
 #[test]
 fn cache_reads_invalidation() {
     let mut tc_cache = TrackingCopyCache::new(2, Count);
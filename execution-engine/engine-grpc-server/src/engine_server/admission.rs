@@ -0,0 +1,931 @@
+//! Admission control of the requests handled by the execution engine service.
+//!
+//! Requests are split into two classes: those which execute or commit deploys, and those which
+//! query global state.  Each class admits a configurable number of requests to run concurrently
+//! and a configurable number more to wait for one of those to complete.  A request arriving when
+//! its class has no room left is answered immediately with an `Overloaded` failure, holding a hint
+//! of when to retry, rather than being queued.
+//!
+//! Requests wait for admission on the server's worker threads, so the server should have at least
+//! as many threads as the two classes admit in total.  Requests beyond the thread count are queued
+//! by the gRPC layer before they reach admission control.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+use futures::Stream;
+use grpc::{RequestOptions, SingleResponse, StreamingResponse};
+
+use engine_shared::{logging::log_metric, newtypes::CorrelationId};
+
+use super::{
+    ipc::{
+        BalanceRequest, BalanceResponse, BatchCommitRequest, BatchCommitResponse, BidStateRequest,
+        BidStateResponse, BuildStateRequest, BuildStateResponse, ChainspecFingerprintRequest,
+        ChainspecFingerprintResponse, CommitRequest, CommitResponse, DeployResultChunk,
+        DistributeRewardsRequest, DistributeRewardsResponse, ExecuteRequest, ExecuteResponse,
+        ExportReproBundleRequest, ExportReproBundleResponse, GenesisResponse, GetTrieNodeRequest,
+        GetTrieNodeResponse, GetTrieNodesRequest, GetTrieNodesResponse, GetValidatorWeightsRequest,
+        GetValidatorWeightsResponse, ListKeysRequest, ListKeysResponse, ListRootsRequest,
+        ListRootsResponse, MultiRootQueryRequest, MultiRootQueryResponse, Overloaded,
+        PreviewUpgradeRequest, PreviewUpgradeResponse, QueryRequest, QueryResponse,
+        ReplayReproBundleRequest, ReplayReproBundleResponse, ReplayRequest, ReplayResponse,
+        RunGenesisRequest, ServerInfoRequest, ServerInfoResponse, SlashRequest, SlashResponse,
+        UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        ValidateDeployRequest, ValidateDeployResponse,
+    },
+    ipc_grpc::ExecutionEngineService,
+};
+
+const TAG_ADMISSION: &str = "admission";
+const GAUGE_METRIC_KEY: &str = "gauge";
+const COUNTER_METRIC_KEY: &str = "counter";
+
+/// The default of how long an overloaded client is asked to wait before retrying.
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_millis(1000);
+
+/// The class of a request, each of which is admitted independently of the other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestClass {
+    /// `execute`, `execute_stream`, `commit` and `batch_commit`.
+    Exec,
+    /// `query`, `multi_root_query`, `balance`, `get_trie_node` and `get_trie_nodes`.
+    Query,
+}
+
+impl RequestClass {
+    fn metric_names(self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            RequestClass::Exec => (
+                "exec_requests_executing",
+                "exec_requests_queued",
+                "exec_requests_overloaded",
+            ),
+            RequestClass::Query => (
+                "query_requests_executing",
+                "query_requests_queued",
+                "query_requests_overloaded",
+            ),
+        }
+    }
+}
+
+impl Display for RequestClass {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RequestClass::Exec => write!(f, "exec"),
+            RequestClass::Query => write!(f, "query"),
+        }
+    }
+}
+
+/// The number of requests of a class which may be executing and queued at once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClassLimits {
+    max_concurrent: usize,
+    max_queued: usize,
+}
+
+impl ClassLimits {
+    /// Returns limits admitting `max_concurrent` requests to execute at once, and `max_queued` more
+    /// to wait for one of those to complete.
+    ///
+    /// At least one request is always admitted to execute.
+    pub fn new(max_concurrent: usize, max_queued: usize) -> Self {
+        ClassLimits {
+            max_concurrent: max_concurrent.max(1),
+            max_queued,
+        }
+    }
+
+    /// Returns limits which admit every request.
+    pub fn unlimited() -> Self {
+        ClassLimits {
+            max_concurrent: usize::max_value(),
+            max_queued: 0,
+        }
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    pub fn max_queued(&self) -> usize {
+        self.max_queued
+    }
+
+    /// Returns the number of requests admitted at once, whether executing or queued.
+    pub fn max_admitted(&self) -> usize {
+        self.max_concurrent.saturating_add(self.max_queued)
+    }
+}
+
+impl Default for ClassLimits {
+    fn default() -> Self {
+        ClassLimits::unlimited()
+    }
+}
+
+/// Configures the admission of requests to an [`AdmissionControlled`] service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AdmissionConfig {
+    exec_limits: ClassLimits,
+    query_limits: ClassLimits,
+    retry_after: Duration,
+}
+
+impl AdmissionConfig {
+    /// Returns a config admitting every request.
+    pub fn new() -> Self {
+        AdmissionConfig {
+            exec_limits: ClassLimits::unlimited(),
+            query_limits: ClassLimits::unlimited(),
+            retry_after: DEFAULT_RETRY_AFTER,
+        }
+    }
+
+    pub fn with_exec_limits(mut self, exec_limits: ClassLimits) -> Self {
+        self.exec_limits = exec_limits;
+        self
+    }
+
+    pub fn with_query_limits(mut self, query_limits: ClassLimits) -> Self {
+        self.query_limits = query_limits;
+        self
+    }
+
+    /// Sets how long an overloaded client is asked to wait before retrying.
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    pub fn limits(&self, class: RequestClass) -> ClassLimits {
+        match class {
+            RequestClass::Exec => self.exec_limits,
+            RequestClass::Query => self.query_limits,
+        }
+    }
+
+    pub fn retry_after(&self) -> Duration {
+        self.retry_after
+    }
+}
+
+impl Default for AdmissionConfig {
+    fn default() -> Self {
+        AdmissionConfig::new()
+    }
+}
+
+/// The numbers of requests of a class currently executing and queued, and of those turned away so
+/// far.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Depths {
+    pub executing: usize,
+    pub queued: usize,
+    pub overloaded: u64,
+}
+
+/// Admits the requests of one class.
+#[derive(Debug)]
+struct Gate {
+    class: RequestClass,
+    limits: ClassLimits,
+    depths: Mutex<Depths>,
+    slot_released: Condvar,
+}
+
+impl Gate {
+    fn new(class: RequestClass, limits: ClassLimits) -> Self {
+        Gate {
+            class,
+            limits,
+            depths: Mutex::new(Depths::default()),
+            slot_released: Condvar::new(),
+        }
+    }
+
+    /// Admits a request, waiting for a slot to execute in if all are taken but the queue has
+    /// room.  Returns `None` without waiting if the queue is full too.
+    fn admit(self: &Arc<Self>) -> Option<Permit> {
+        let mut depths = self.depths.lock().unwrap();
+        if depths.executing >= self.limits.max_concurrent {
+            if depths.queued >= self.limits.max_queued {
+                depths.overloaded += 1;
+                self.log_metrics(&depths);
+                return None;
+            }
+            depths.queued += 1;
+            self.log_metrics(&depths);
+            while depths.executing >= self.limits.max_concurrent {
+                depths = self.slot_released.wait(depths).unwrap();
+            }
+            depths.queued -= 1;
+        }
+        depths.executing += 1;
+        self.log_metrics(&depths);
+        Some(Permit {
+            gate: Arc::clone(self),
+        })
+    }
+
+    fn release(&self) {
+        let mut depths = self.depths.lock().unwrap();
+        depths.executing -= 1;
+        self.log_metrics(&depths);
+        self.slot_released.notify_one();
+    }
+
+    fn depths(&self) -> Depths {
+        *self.depths.lock().unwrap()
+    }
+
+    fn log_metrics(&self, depths: &Depths) {
+        let correlation_id = CorrelationId::new();
+        let (executing, queued, overloaded) = self.class.metric_names();
+        for (metric, metric_key, value) in &[
+            (executing, GAUGE_METRIC_KEY, depths.executing as f64),
+            (queued, GAUGE_METRIC_KEY, depths.queued as f64),
+            (overloaded, COUNTER_METRIC_KEY, depths.overloaded as f64),
+        ] {
+            log_metric(correlation_id, metric, TAG_ADMISSION, metric_key, *value);
+        }
+    }
+}
+
+/// A slot to execute a request in, released when dropped.
+#[derive(Debug)]
+pub struct Permit {
+    gate: Arc<Gate>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.gate.release()
+    }
+}
+
+/// Admits requests according to an [`AdmissionConfig`].
+#[derive(Clone, Debug)]
+pub struct AdmissionController {
+    exec_gate: Arc<Gate>,
+    query_gate: Arc<Gate>,
+    retry_after: Duration,
+}
+
+impl AdmissionController {
+    pub fn new(config: AdmissionConfig) -> Self {
+        AdmissionController {
+            exec_gate: Arc::new(Gate::new(RequestClass::Exec, config.exec_limits)),
+            query_gate: Arc::new(Gate::new(RequestClass::Query, config.query_limits)),
+            retry_after: config.retry_after,
+        }
+    }
+
+    fn gate(&self, class: RequestClass) -> &Arc<Gate> {
+        match class {
+            RequestClass::Exec => &self.exec_gate,
+            RequestClass::Query => &self.query_gate,
+        }
+    }
+
+    /// Admits a request of the given class, or returns the `Overloaded` failure to answer it with
+    /// if its class has no room left.
+    pub fn admit(&self, class: RequestClass) -> Result<Permit, Overloaded> {
+        self.gate(class).admit().ok_or_else(|| {
+            let mut overloaded = Overloaded::new();
+            overloaded.set_retry_after_millis(self.retry_after.as_millis() as u64);
+            overloaded
+        })
+    }
+
+    /// Returns the current depths of the given class.
+    pub fn depths(&self, class: RequestClass) -> Depths {
+        self.gate(class).depths()
+    }
+}
+
+/// Wraps an [`ExecutionEngineService`], only passing it the requests admitted by an
+/// [`AdmissionController`].
+pub struct AdmissionControlled<E> {
+    service: E,
+    controller: AdmissionController,
+}
+
+impl<E> AdmissionControlled<E> {
+    pub fn new(service: E, config: AdmissionConfig) -> Self {
+        AdmissionControlled {
+            service,
+            controller: AdmissionController::new(config),
+        }
+    }
+
+    pub fn controller(&self) -> &AdmissionController {
+        &self.controller
+    }
+}
+
+impl<E: ExecutionEngineService> ExecutionEngineService for AdmissionControlled<E> {
+    fn commit(
+        &self,
+        request_options: RequestOptions,
+        commit_request: CommitRequest,
+    ) -> SingleResponse<CommitResponse> {
+        match self.controller.admit(RequestClass::Exec) {
+            Ok(_permit) => self.service.commit(request_options, commit_request),
+            Err(overloaded) => {
+                let mut response = CommitResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn batch_commit(
+        &self,
+        request_options: RequestOptions,
+        batch_commit_request: BatchCommitRequest,
+    ) -> SingleResponse<BatchCommitResponse> {
+        match self.controller.admit(RequestClass::Exec) {
+            Ok(_permit) => self
+                .service
+                .batch_commit(request_options, batch_commit_request),
+            Err(overloaded) => {
+                let mut response = BatchCommitResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn query(
+        &self,
+        request_options: RequestOptions,
+        query_request: QueryRequest,
+    ) -> SingleResponse<QueryResponse> {
+        match self.controller.admit(RequestClass::Query) {
+            Ok(_permit) => self.service.query(request_options, query_request),
+            Err(overloaded) => {
+                let mut response = QueryResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn multi_root_query(
+        &self,
+        request_options: RequestOptions,
+        multi_root_query_request: MultiRootQueryRequest,
+    ) -> SingleResponse<MultiRootQueryResponse> {
+        match self.controller.admit(RequestClass::Query) {
+            Ok(_permit) => self
+                .service
+                .multi_root_query(request_options, multi_root_query_request),
+            Err(overloaded) => {
+                let mut response = MultiRootQueryResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn list_keys(
+        &self,
+        request_options: RequestOptions,
+        list_keys_request: ListKeysRequest,
+    ) -> SingleResponse<ListKeysResponse> {
+        self.service.list_keys(request_options, list_keys_request)
+    }
+
+    fn balance(
+        &self,
+        request_options: RequestOptions,
+        balance_request: BalanceRequest,
+    ) -> SingleResponse<BalanceResponse> {
+        match self.controller.admit(RequestClass::Query) {
+            Ok(_permit) => self.service.balance(request_options, balance_request),
+            Err(overloaded) => {
+                let mut response = BalanceResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn list_roots(
+        &self,
+        request_options: RequestOptions,
+        list_roots_request: ListRootsRequest,
+    ) -> SingleResponse<ListRootsResponse> {
+        self.service.list_roots(request_options, list_roots_request)
+    }
+
+    fn get_trie_node(
+        &self,
+        request_options: RequestOptions,
+        get_trie_node_request: GetTrieNodeRequest,
+    ) -> SingleResponse<GetTrieNodeResponse> {
+        match self.controller.admit(RequestClass::Query) {
+            Ok(_permit) => self
+                .service
+                .get_trie_node(request_options, get_trie_node_request),
+            Err(overloaded) => {
+                let mut response = GetTrieNodeResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn get_trie_nodes(
+        &self,
+        request_options: RequestOptions,
+        get_trie_nodes_request: GetTrieNodesRequest,
+    ) -> SingleResponse<GetTrieNodesResponse> {
+        match self.controller.admit(RequestClass::Query) {
+            Ok(_permit) => self
+                .service
+                .get_trie_nodes(request_options, get_trie_nodes_request),
+            Err(overloaded) => {
+                let mut response = GetTrieNodesResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn build_state(
+        &self,
+        request_options: RequestOptions,
+        build_state_request: BuildStateRequest,
+    ) -> SingleResponse<BuildStateResponse> {
+        self.service
+            .build_state(request_options, build_state_request)
+    }
+
+    fn execute(
+        &self,
+        request_options: RequestOptions,
+        exec_request: ExecuteRequest,
+    ) -> SingleResponse<ExecuteResponse> {
+        match self.controller.admit(RequestClass::Exec) {
+            Ok(_permit) => self.service.execute(request_options, exec_request),
+            Err(overloaded) => {
+                let mut response = ExecuteResponse::new();
+                response.set_overloaded(overloaded);
+                SingleResponse::completed(response)
+            }
+        }
+    }
+
+    fn execute_stream(
+        &self,
+        request_options: RequestOptions,
+        exec_request: ExecuteRequest,
+    ) -> StreamingResponse<DeployResultChunk> {
+        match self.controller.admit(RequestClass::Exec) {
+            Ok(permit) => {
+                // Deploys go on executing after the handler returns, so the permit is only
+                // released once the stream is done with.
+                let chunks = self
+                    .service
+                    .execute_stream(request_options, exec_request)
+                    .drop_metadata()
+                    .then(move |chunk| {
+                        let _permit = &permit;
+                        chunk
+                    });
+                StreamingResponse::no_metadata(chunks)
+            }
+            Err(overloaded) => {
+                let mut summary_chunk = DeployResultChunk::new();
+                summary_chunk.mut_summary().set_overloaded(overloaded);
+                StreamingResponse::completed(vec![summary_chunk])
+            }
+        }
+    }
+
+    fn replay(
+        &self,
+        request_options: RequestOptions,
+        replay_request: ReplayRequest,
+    ) -> SingleResponse<ReplayResponse> {
+        self.service.replay(request_options, replay_request)
+    }
+
+    fn export_repro_bundle(
+        &self,
+        request_options: RequestOptions,
+        export_request: ExportReproBundleRequest,
+    ) -> SingleResponse<ExportReproBundleResponse> {
+        self.service
+            .export_repro_bundle(request_options, export_request)
+    }
+
+    fn replay_repro_bundle(
+        &self,
+        request_options: RequestOptions,
+        replay_request: ReplayReproBundleRequest,
+    ) -> SingleResponse<ReplayReproBundleResponse> {
+        self.service
+            .replay_repro_bundle(request_options, replay_request)
+    }
+
+    fn validate_deploy(
+        &self,
+        request_options: RequestOptions,
+        validate_request: ValidateDeployRequest,
+    ) -> SingleResponse<ValidateDeployResponse> {
+        self.service
+            .validate_deploy(request_options, validate_request)
+    }
+
+    fn run_genesis(
+        &self,
+        request_options: RequestOptions,
+        genesis_request: RunGenesisRequest,
+    ) -> SingleResponse<GenesisResponse> {
+        self.service.run_genesis(request_options, genesis_request)
+    }
+
+    fn chainspec_fingerprint(
+        &self,
+        request_options: RequestOptions,
+        fingerprint_request: ChainspecFingerprintRequest,
+    ) -> SingleResponse<ChainspecFingerprintResponse> {
+        self.service
+            .chainspec_fingerprint(request_options, fingerprint_request)
+    }
+
+    fn upgrade(
+        &self,
+        request_options: RequestOptions,
+        upgrade_request: UpgradeRequest,
+    ) -> SingleResponse<UpgradeResponse> {
+        self.service.upgrade(request_options, upgrade_request)
+    }
+
+    fn preview_upgrade(
+        &self,
+        request_options: RequestOptions,
+        preview_upgrade_request: PreviewUpgradeRequest,
+    ) -> SingleResponse<PreviewUpgradeResponse> {
+        self.service
+            .preview_upgrade(request_options, preview_upgrade_request)
+    }
+
+    fn get_validator_weights(
+        &self,
+        request_options: RequestOptions,
+        get_validator_weights_request: GetValidatorWeightsRequest,
+    ) -> SingleResponse<GetValidatorWeightsResponse> {
+        self.service
+            .get_validator_weights(request_options, get_validator_weights_request)
+    }
+
+    fn server_info(
+        &self,
+        request_options: RequestOptions,
+        server_info_request: ServerInfoRequest,
+    ) -> SingleResponse<ServerInfoResponse> {
+        self.service
+            .server_info(request_options, server_info_request)
+    }
+
+    fn bid_state(
+        &self,
+        request_options: RequestOptions,
+        bid_state_request: BidStateRequest,
+    ) -> SingleResponse<BidStateResponse> {
+        self.service.bid_state(request_options, bid_state_request)
+    }
+
+    fn distribute_rewards(
+        &self,
+        request_options: RequestOptions,
+        distribute_rewards_request: DistributeRewardsRequest,
+    ) -> SingleResponse<DistributeRewardsResponse> {
+        self.service
+            .distribute_rewards(request_options, distribute_rewards_request)
+    }
+
+    fn slash(
+        &self,
+        request_options: RequestOptions,
+        slash_request: SlashRequest,
+    ) -> SingleResponse<SlashResponse> {
+        self.service.slash(request_options, slash_request)
+    }
+
+    fn unbond_payout(
+        &self,
+        request_options: RequestOptions,
+        unbond_payout_request: UnbondPayoutRequest,
+    ) -> SingleResponse<UnbondPayoutResponse> {
+        self.service
+            .unbond_payout(request_options, unbond_payout_request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Barrier,
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use super::*;
+
+    /// How long the mock service takes to execute a request.
+    const EXEC_DURATION: Duration = Duration::from_millis(500);
+    const RETRY_AFTER: Duration = Duration::from_millis(250);
+    const MAX_CONCURRENT: usize = 2;
+    const MAX_QUEUED: usize = 2;
+    const REQUEST_COUNT: usize = 8;
+
+    /// A service whose executes are slow and whose queries are fast.
+    struct SlowExecService;
+
+    /// The error returned by the methods the tests don't expect to be called.
+    fn not_mocked(method: &str) -> grpc::Error {
+        grpc::Error::Panic(format!("SlowExecService doesn't mock {}", method))
+    }
+
+    impl ExecutionEngineService for SlowExecService {
+        fn commit(&self, _: RequestOptions, _: CommitRequest) -> SingleResponse<CommitResponse> {
+            SingleResponse::err(not_mocked("commit"))
+        }
+
+        fn batch_commit(
+            &self,
+            _: RequestOptions,
+            _: BatchCommitRequest,
+        ) -> SingleResponse<BatchCommitResponse> {
+            SingleResponse::err(not_mocked("batch_commit"))
+        }
+
+        fn query(&self, _: RequestOptions, _: QueryRequest) -> SingleResponse<QueryResponse> {
+            let mut response = QueryResponse::new();
+            response.set_success(vec![]);
+            SingleResponse::completed(response)
+        }
+
+        fn multi_root_query(
+            &self,
+            _: RequestOptions,
+            _: MultiRootQueryRequest,
+        ) -> SingleResponse<MultiRootQueryResponse> {
+            SingleResponse::err(not_mocked("multi_root_query"))
+        }
+
+        fn list_keys(
+            &self,
+            _: RequestOptions,
+            _: ListKeysRequest,
+        ) -> SingleResponse<ListKeysResponse> {
+            SingleResponse::err(not_mocked("list_keys"))
+        }
+
+        fn balance(&self, _: RequestOptions, _: BalanceRequest) -> SingleResponse<BalanceResponse> {
+            SingleResponse::err(not_mocked("balance"))
+        }
+
+        fn list_roots(
+            &self,
+            _: RequestOptions,
+            _: ListRootsRequest,
+        ) -> SingleResponse<ListRootsResponse> {
+            SingleResponse::err(not_mocked("list_roots"))
+        }
+
+        fn get_trie_node(
+            &self,
+            _: RequestOptions,
+            _: GetTrieNodeRequest,
+        ) -> SingleResponse<GetTrieNodeResponse> {
+            SingleResponse::err(not_mocked("get_trie_node"))
+        }
+
+        fn get_trie_nodes(
+            &self,
+            _: RequestOptions,
+            _: GetTrieNodesRequest,
+        ) -> SingleResponse<GetTrieNodesResponse> {
+            SingleResponse::err(not_mocked("get_trie_nodes"))
+        }
+
+        fn build_state(
+            &self,
+            _: RequestOptions,
+            _: BuildStateRequest,
+        ) -> SingleResponse<BuildStateResponse> {
+            SingleResponse::err(not_mocked("build_state"))
+        }
+
+        fn execute(&self, _: RequestOptions, _: ExecuteRequest) -> SingleResponse<ExecuteResponse> {
+            thread::sleep(EXEC_DURATION);
+            let mut response = ExecuteResponse::new();
+            response.mut_success();
+            SingleResponse::completed(response)
+        }
+
+        fn execute_stream(
+            &self,
+            _: RequestOptions,
+            _: ExecuteRequest,
+        ) -> StreamingResponse<DeployResultChunk> {
+            StreamingResponse::err(not_mocked("execute_stream"))
+        }
+
+        fn replay(&self, _: RequestOptions, _: ReplayRequest) -> SingleResponse<ReplayResponse> {
+            SingleResponse::err(not_mocked("replay"))
+        }
+
+        fn export_repro_bundle(
+            &self,
+            _: RequestOptions,
+            _: ExportReproBundleRequest,
+        ) -> SingleResponse<ExportReproBundleResponse> {
+            SingleResponse::err(not_mocked("export_repro_bundle"))
+        }
+
+        fn replay_repro_bundle(
+            &self,
+            _: RequestOptions,
+            _: ReplayReproBundleRequest,
+        ) -> SingleResponse<ReplayReproBundleResponse> {
+            SingleResponse::err(not_mocked("replay_repro_bundle"))
+        }
+
+        fn validate_deploy(
+            &self,
+            _: RequestOptions,
+            _: ValidateDeployRequest,
+        ) -> SingleResponse<ValidateDeployResponse> {
+            SingleResponse::err(not_mocked("validate_deploy"))
+        }
+
+        fn run_genesis(
+            &self,
+            _: RequestOptions,
+            _: RunGenesisRequest,
+        ) -> SingleResponse<GenesisResponse> {
+            SingleResponse::err(not_mocked("run_genesis"))
+        }
+
+        fn chainspec_fingerprint(
+            &self,
+            _: RequestOptions,
+            _: ChainspecFingerprintRequest,
+        ) -> SingleResponse<ChainspecFingerprintResponse> {
+            SingleResponse::err(not_mocked("chainspec_fingerprint"))
+        }
+
+        fn upgrade(&self, _: RequestOptions, _: UpgradeRequest) -> SingleResponse<UpgradeResponse> {
+            SingleResponse::err(not_mocked("upgrade"))
+        }
+
+        fn preview_upgrade(
+            &self,
+            _: RequestOptions,
+            _: PreviewUpgradeRequest,
+        ) -> SingleResponse<PreviewUpgradeResponse> {
+            SingleResponse::err(not_mocked("preview_upgrade"))
+        }
+
+        fn get_validator_weights(
+            &self,
+            _: RequestOptions,
+            _: GetValidatorWeightsRequest,
+        ) -> SingleResponse<GetValidatorWeightsResponse> {
+            SingleResponse::err(not_mocked("get_validator_weights"))
+        }
+
+        fn server_info(
+            &self,
+            _: RequestOptions,
+            _: ServerInfoRequest,
+        ) -> SingleResponse<ServerInfoResponse> {
+            SingleResponse::err(not_mocked("server_info"))
+        }
+
+        fn bid_state(
+            &self,
+            _: RequestOptions,
+            _: BidStateRequest,
+        ) -> SingleResponse<BidStateResponse> {
+            SingleResponse::err(not_mocked("bid_state"))
+        }
+
+        fn distribute_rewards(
+            &self,
+            _: RequestOptions,
+            _: DistributeRewardsRequest,
+        ) -> SingleResponse<DistributeRewardsResponse> {
+            SingleResponse::err(not_mocked("distribute_rewards"))
+        }
+
+        fn slash(&self, _: RequestOptions, _: SlashRequest) -> SingleResponse<SlashResponse> {
+            SingleResponse::err(not_mocked("slash"))
+        }
+
+        fn unbond_payout(
+            &self,
+            _: RequestOptions,
+            _: UnbondPayoutRequest,
+        ) -> SingleResponse<UnbondPayoutResponse> {
+            SingleResponse::err(not_mocked("unbond_payout"))
+        }
+    }
+
+    fn admission_controlled_service() -> Arc<AdmissionControlled<SlowExecService>> {
+        let config = AdmissionConfig::new()
+            .with_exec_limits(ClassLimits::new(MAX_CONCURRENT, MAX_QUEUED))
+            .with_query_limits(ClassLimits::new(1, 0))
+            .with_retry_after(RETRY_AFTER);
+        Arc::new(AdmissionControlled::new(SlowExecService, config))
+    }
+
+    /// Sends `REQUEST_COUNT` executes at once, returning each response with how long it took.
+    fn flood_with_executes(
+        service: &Arc<AdmissionControlled<SlowExecService>>,
+    ) -> Vec<(ExecuteResponse, Duration)> {
+        let barrier = Arc::new(Barrier::new(REQUEST_COUNT));
+        let handles: Vec<_> = (0..REQUEST_COUNT)
+            .map(|_| {
+                let service = Arc::clone(service);
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    let start = Instant::now();
+                    let response = service
+                        .execute(RequestOptions::new(), ExecuteRequest::new())
+                        .wait_drop_metadata()
+                        .expect("should execute");
+                    (response, start.elapsed())
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("should join"))
+            .collect()
+    }
+
+    #[test]
+    fn should_answer_excess_requests_with_overloaded_promptly() {
+        let service = admission_controlled_service();
+        let responses = flood_with_executes(&service);
+
+        let (admitted, overloaded): (Vec<_>, Vec<_>) = responses
+            .iter()
+            .partition(|(response, _)| response.has_success());
+        assert_eq!(admitted.len(), MAX_CONCURRENT + MAX_QUEUED);
+        assert_eq!(overloaded.len(), REQUEST_COUNT - admitted.len());
+
+        for (response, elapsed) in &overloaded {
+            assert!(response.has_overloaded());
+            assert_eq!(
+                response.get_overloaded().get_retry_after_millis(),
+                RETRY_AFTER.as_millis() as u64
+            );
+            assert!(*elapsed < EXEC_DURATION, "took {:?}", elapsed);
+        }
+        // The queued requests only executed once an executing one completed.
+        let queued_count = admitted
+            .iter()
+            .filter(|(_, elapsed)| *elapsed >= EXEC_DURATION * 2)
+            .count();
+        assert_eq!(queued_count, MAX_QUEUED);
+
+        let depths = service.controller().depths(RequestClass::Exec);
+        assert_eq!(depths.executing, 0);
+        assert_eq!(depths.queued, 0);
+        assert_eq!(depths.overloaded, overloaded.len() as u64);
+    }
+
+    #[test]
+    fn should_admit_queries_while_executes_are_overloaded() {
+        let service = admission_controlled_service();
+        let flooding_service = Arc::clone(&service);
+        let flood = thread::spawn(move || flood_with_executes(&flooding_service));
+
+        // Wait for the executes to take all of their class' slots.
+        while service.controller().depths(RequestClass::Exec).queued < MAX_QUEUED {
+            thread::yield_now();
+        }
+        let response = service
+            .query(RequestOptions::new(), QueryRequest::new())
+            .wait_drop_metadata()
+            .expect("should query");
+        assert!(response.has_success());
+
+        flood.join().expect("should join");
+    }
+}
@@ -0,0 +1,71 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_shared::account::AccountAuthInfo;
+
+use crate::engine_server::{
+    ipc,
+    mappings::{state::weight_from, ParsingError},
+    state::Account_AssociatedKey,
+};
+
+impl From<AccountAuthInfo> for ipc::AccountAuthInfo {
+    fn from(account_auth_info: AccountAuthInfo) -> Self {
+        let mut pb_account_auth_info = ipc::AccountAuthInfo::new();
+        let pb_associated_keys: Vec<Account_AssociatedKey> = account_auth_info
+            .associated_keys
+            .iter()
+            .map(|(public_key, weight)| (public_key, weight).into())
+            .collect();
+        pb_account_auth_info.set_associated_keys(pb_associated_keys.into());
+        pb_account_auth_info
+            .set_deployment_threshold(account_auth_info.deployment_threshold.value().into());
+        pb_account_auth_info.set_key_management_threshold(
+            account_auth_info.key_management_threshold.value().into(),
+        );
+        pb_account_auth_info
+    }
+}
+
+impl TryFrom<ipc::AccountAuthInfo> for AccountAuthInfo {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_account_auth_info: ipc::AccountAuthInfo) -> Result<Self, Self::Error> {
+        let associated_keys = pb_account_auth_info
+            .take_associated_keys()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?;
+        let deployment_threshold = weight_from(
+            pb_account_auth_info.get_deployment_threshold(),
+            "Protobuf AccountAuthInfo::DeploymentThreshold",
+        )?;
+        let key_management_threshold = weight_from(
+            pb_account_auth_info.get_key_management_threshold(),
+            "Protobuf AccountAuthInfo::KeyManagementThreshold",
+        )?;
+        Ok(AccountAuthInfo {
+            associated_keys,
+            deployment_threshold,
+            key_management_threshold,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::account::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(account in gens::account_arb()) {
+            test_utils::protobuf_round_trip::<AccountAuthInfo, ipc::AccountAuthInfo>(
+                AccountAuthInfo::from(&account),
+            );
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_shared::account::AccountConfig;
+use types::account::Weight;
+
+use crate::engine_server::{
+    ipc::{ChainSpec_AccountConfig, ChainSpec_AccountConfig_AccountThresholds},
+    mappings::{MappingError, ParsingError},
+};
+
+fn weight_from_u32(value: u32, name: &str) -> Result<Weight, ParsingError> {
+    let value: u8 = value
+        .try_into()
+        .map_err(|_| ParsingError(format!("{} must not exceed {}.", name, u8::max_value())))?;
+    Ok(Weight::new(value))
+}
+
+impl From<AccountConfig> for ChainSpec_AccountConfig {
+    fn from(account_config: AccountConfig) -> Self {
+        let mut pb_account_config = ChainSpec_AccountConfig::new();
+        {
+            let mut pb_thresholds = ChainSpec_AccountConfig_AccountThresholds::new();
+            pb_thresholds
+                .set_deployment(account_config.default_deployment_threshold().value().into());
+            pb_thresholds.set_key_management(
+                account_config
+                    .default_key_management_threshold()
+                    .value()
+                    .into(),
+            );
+            pb_account_config.set_default_account_thresholds(pb_thresholds);
+        }
+        pb_account_config.set_default_identity_key_weight(
+            account_config.default_identity_key_weight().value().into(),
+        );
+        pb_account_config
+    }
+}
+
+impl TryFrom<ChainSpec_AccountConfig> for AccountConfig {
+    type Error = MappingError;
+
+    fn try_from(pb_account_config: ChainSpec_AccountConfig) -> Result<Self, Self::Error> {
+        let pb_thresholds = pb_account_config.get_default_account_thresholds();
+        let default_deployment_threshold =
+            weight_from_u32(pb_thresholds.get_deployment(), "deployment threshold")?;
+        let default_key_management_threshold = weight_from_u32(
+            pb_thresholds.get_key_management(),
+            "key management threshold",
+        )?;
+        let default_identity_key_weight = weight_from_u32(
+            pb_account_config.get_default_identity_key_weight(),
+            "identity key weight",
+        )?;
+        Ok(AccountConfig::new(
+            default_deployment_threshold,
+            default_key_management_threshold,
+            default_identity_key_weight,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::account::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(account_config in gens::account_config_arb()) {
+            test_utils::protobuf_round_trip::<AccountConfig, ChainSpec_AccountConfig>(account_config);
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_weight_above_u8_max() {
+        let mut pb_account_config: ChainSpec_AccountConfig = AccountConfig::default().into();
+        pb_account_config.set_default_identity_key_weight(256);
+        assert!(AccountConfig::try_from(pb_account_config).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::balance::{BalanceError, BalanceRequest};
+use engine_shared::{motes::Motes, newtypes::BLAKE2B_DIGEST_LENGTH};
+use types::account::PublicKey;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl TryFrom<ipc::BalanceRequest> for BalanceRequest {
+    type Error = MappingError;
+
+    fn try_from(mut balance_request: ipc::BalanceRequest) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = balance_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let public_key =
+            PublicKey::ed25519_try_from(balance_request.get_public_key()).map_err(|_| {
+                MappingError::invalid_public_key_length(balance_request.get_public_key().len())
+            })?;
+
+        let protocol_version = balance_request.take_protocol_version().into();
+
+        Ok(BalanceRequest::new(
+            state_hash,
+            public_key,
+            protocol_version,
+        ))
+    }
+}
+
+impl From<Result<Motes, BalanceError>> for ipc::BalanceResponse {
+    fn from(result: Result<Motes, BalanceError>) -> Self {
+        let mut pb_response = ipc::BalanceResponse::new();
+        match result {
+            Ok(balance) => pb_response.set_success(balance.value().into()),
+            Err(BalanceError::RootNotFound(state_hash)) => pb_response
+                .mut_root_not_found()
+                .set_hash(state_hash.to_vec()),
+            Err(BalanceError::AccountNotFound(key)) => {
+                pb_response.set_account_not_found(key.into())
+            }
+            Err(BalanceError::PurseURefMissing(key)) => {
+                pb_response.set_purse_uref_missing(key.into())
+            }
+            Err(BalanceError::MintBalanceMissing(key)) => {
+                pb_response.set_mint_balance_missing(key.into())
+            }
+            Err(error @ BalanceError::Engine(_)) => pb_response.set_failure(error.to_string()),
+        }
+        pb_response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::newtypes::Blake2bHash;
+    use types::{Key, U512};
+
+    use super::*;
+
+    #[test]
+    fn should_map_balance() {
+        let pb_response: ipc::BalanceResponse = Ok(Motes::new(U512::from(42))).into();
+        assert!(pb_response.has_success());
+        let balance: U512 = pb_response
+            .get_success()
+            .clone()
+            .try_into()
+            .expect("should convert balance");
+        assert_eq!(balance, U512::from(42));
+    }
+
+    #[test]
+    fn should_map_each_failed_hop_with_its_key() {
+        let state_hash = Blake2bHash::new(&[1]);
+        let pb_response: ipc::BalanceResponse = Err(BalanceError::RootNotFound(state_hash)).into();
+        assert_eq!(
+            pb_response.get_root_not_found().get_hash(),
+            state_hash.to_vec().as_slice()
+        );
+
+        let key = Key::Hash([2; 32]);
+        let to_key = |pb_key: &crate::engine_server::state::Key| -> Key {
+            pb_key.clone().try_into().expect("should convert key")
+        };
+
+        let pb_response: ipc::BalanceResponse = Err(BalanceError::AccountNotFound(key)).into();
+        assert_eq!(to_key(pb_response.get_account_not_found()), key);
+
+        let pb_response: ipc::BalanceResponse = Err(BalanceError::PurseURefMissing(key)).into();
+        assert_eq!(to_key(pb_response.get_purse_uref_missing()), key);
+
+        let pb_response: ipc::BalanceResponse = Err(BalanceError::MintBalanceMissing(key)).into();
+        assert_eq!(to_key(pb_response.get_mint_balance_missing()), key);
+    }
+}
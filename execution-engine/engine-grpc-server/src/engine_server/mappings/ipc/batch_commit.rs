@@ -0,0 +1,50 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH},
+    transform::Transform,
+};
+use types::Key;
+
+use crate::engine_server::{
+    ipc::BatchCommitRequest_Entry,
+    mappings::{MappingError, TransformMap},
+};
+
+/// The `(pre_state_hash, effects)` entries of a batch commit, in the order they are applied.
+pub struct BatchCommitEntries(Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>);
+
+impl BatchCommitEntries {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn into_inner(self) -> Vec<(Blake2bHash, AdditiveMap<Key, Transform>)> {
+        self.0
+    }
+}
+
+impl TryFrom<Vec<BatchCommitRequest_Entry>> for BatchCommitEntries {
+    type Error = MappingError;
+
+    fn try_from(pb_entries: Vec<BatchCommitRequest_Entry>) -> Result<Self, Self::Error> {
+        let mut entries = Vec::with_capacity(pb_entries.len());
+        for mut pb_entry in pb_entries {
+            let prestate_hash = pb_entry.get_prestate_hash();
+            let length = prestate_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            let prestate_hash: Blake2bHash = prestate_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?;
+            let effects = TransformMap::try_from(pb_entry.take_effects().into_vec())?.into_inner();
+            entries.push((prestate_hash, effects));
+        }
+        Ok(BatchCommitEntries(entries))
+    }
+}
@@ -29,19 +29,83 @@ impl TryFrom<Bond> for (PublicKey, U512) {
     }
 }
 
+/// Converts the bonded validators of a commit to their Protobuf bonds in the canonical order:
+/// sorted by the bytes of the validators' public keys.
+///
+/// The node hashes the list, so the order is enforced here too rather than left to the order in
+/// which the bonded validators are iterated.
+pub(crate) fn canonical_bonds<I>(bonded_validators: I) -> Vec<Bond>
+where
+    I: IntoIterator<Item = (PublicKey, U512)>,
+{
+    let mut bonded_validators: Vec<(PublicKey, U512)> = bonded_validators.into_iter().collect();
+    bonded_validators.sort_by(|(lhs, _), (rhs, _)| lhs.as_bytes().cmp(rhs.as_bytes()));
+    bonded_validators.into_iter().map(Into::into).collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use proptest::proptest;
+    use proptest::{collection::btree_map, prelude::*, proptest};
+    use protobuf::Message;
 
     use types::gens;
 
     use super::*;
-    use crate::engine_server::mappings::test_utils;
+    use crate::engine_server::{ipc::CommitResult, mappings::test_utils};
+
+    /// The bonds of a commit result holding a stake of 10 for `[1; 32]` and 20 for `[2; 32]`.
+    #[rustfmt::skip]
+    const GOLDEN_COMMIT_RESULT: [u8; 90] = [
+        // bonded_validators[0]: field 2, length 43
+        0x12, 0x2b,
+        // validator_public_key: field 1, length 32
+        0x0a, 0x20,
+        1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+        1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1,
+        // stake: field 2, length 7, value "10", bit_width 512
+        0x12, 0x07, 0x0a, 0x02, b'1', b'0', 0x10, 0x80, 0x04,
+        // bonded_validators[1]: field 2, length 43
+        0x12, 0x2b,
+        0x0a, 0x20,
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+        2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2,
+        0x12, 0x07, 0x0a, 0x02, b'2', b'0', 0x10, 0x80, 0x04,
+    ];
+
+    #[test]
+    fn should_serialize_bonds_in_canonical_order() {
+        let bonded_validators = vec![
+            (PublicKey::ed25519_from([2; 32]), U512::from(20)),
+            (PublicKey::ed25519_from([1; 32]), U512::from(10)),
+        ];
+        let mut commit_result = CommitResult::new();
+        commit_result.set_bonded_validators(canonical_bonds(bonded_validators).into());
+        assert_eq!(
+            commit_result.write_to_bytes().unwrap(),
+            GOLDEN_COMMIT_RESULT.to_vec()
+        );
+    }
 
     proptest! {
         #[test]
         fn round_trip(public_key in gens::public_key_arb(), u512 in gens::u512_arb()) {
             test_utils::protobuf_round_trip::<(PublicKey, U512), Bond>((public_key, u512));
         }
+
+        #[test]
+        fn should_order_bonds_regardless_of_insertion_order(
+            (bonded_validators, shuffled) in btree_map(gens::public_key_arb(), gens::u512_arb(), 0..10)
+                .prop_flat_map(|bonded_validators| {
+                    let bonds: Vec<(PublicKey, U512)> = bonded_validators.into_iter().collect();
+                    (Just(bonds.clone()), Just(bonds).prop_shuffle())
+                })
+        ) {
+            let expected = canonical_bonds(bonded_validators);
+            let actual = canonical_bonds(shuffled);
+            prop_assert_eq!(&actual, &expected);
+            for pair in actual.windows(2) {
+                prop_assert!(pair[0].get_validator_public_key() < pair[1].get_validator_public_key());
+            }
+        }
     }
 }
@@ -0,0 +1,55 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_shared::stored_value::StoredValue;
+use types::Key;
+
+use crate::engine_server::{ipc::BuildStateRequest_Entry, mappings::ParsingError};
+
+impl From<(Key, StoredValue)> for BuildStateRequest_Entry {
+    fn from((key, value): (Key, StoredValue)) -> Self {
+        let mut pb_entry = BuildStateRequest_Entry::new();
+        pb_entry.set_key(key.into());
+        pb_entry.set_value(value.into());
+        pb_entry
+    }
+}
+
+impl TryFrom<BuildStateRequest_Entry> for (Key, StoredValue) {
+    type Error = ParsingError;
+
+    fn try_from(pb_entry: BuildStateRequest_Entry) -> Result<Self, Self::Error> {
+        let pb_key = pb_entry.key.into_option().ok_or_else(|| {
+            ParsingError::from("Protobuf BuildStateRequest.Entry missing Key field")
+        })?;
+        let key = pb_key.try_into()?;
+
+        let pb_value = pb_entry.value.into_option().ok_or_else(|| {
+            ParsingError::from("Protobuf BuildStateRequest.Entry missing StoredValue field")
+        })?;
+        let value = pb_value.try_into()?;
+
+        Ok((key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use engine_shared::stored_value;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            key in types::gens::key_arb(),
+            value in stored_value::gens::stored_value_arb()
+        ) {
+            test_utils::protobuf_round_trip::<(Key, StoredValue), BuildStateRequest_Entry>(
+                (key, value)
+            );
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use engine_core::engine_state::upgrade::ConfigDiff;
+
+use crate::engine_server::ipc::{self, ConfigDiff_Change};
+
+impl From<&ConfigDiff> for ipc::ConfigDiff {
+    fn from(config_diff: &ConfigDiff) -> Self {
+        let mut pb_config_diff = ipc::ConfigDiff::new();
+        let pb_changes: Vec<ConfigDiff_Change> = config_diff
+            .changes()
+            .iter()
+            .map(|change| {
+                let mut pb_change = ConfigDiff_Change::new();
+                pb_change.set_name(change.name.clone());
+                pb_change.set_old_value(change.old_value.clone());
+                pb_change.set_new_value(change.new_value.clone());
+                pb_change
+            })
+            .collect();
+        pb_config_diff.set_changes(pb_changes.into());
+        pb_config_diff
+    }
+}
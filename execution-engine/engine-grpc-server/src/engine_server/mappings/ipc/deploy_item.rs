@@ -4,10 +4,22 @@ use std::{
 };
 
 use engine_core::engine_state::deploy_item::DeployItem;
-use types::account::PublicKey;
+use types::{account::PublicKey, DeployHash};
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
+/// Returns the arg names of `payload`, checking that no name is given to more than one arg.
+fn arg_names(payload: &mut ipc::DeployPayload) -> Result<Vec<String>, MappingError> {
+    let arg_names = payload.take_arg_names().into_vec();
+    let mut seen = BTreeSet::new();
+    for name in arg_names.iter().filter(|name| !name.is_empty()) {
+        if !seen.insert(name) {
+            return Err(MappingError::DuplicateArgName(name.clone()));
+        }
+    }
+    Ok(arg_names)
+}
+
 impl TryFrom<ipc::DeployItem> for DeployItem {
     type Error = MappingError;
 
@@ -15,14 +27,16 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
         let address = PublicKey::ed25519_try_from(pb_deploy_item.get_address())
             .map_err(|_| MappingError::invalid_public_key_length(pb_deploy_item.address.len()))?;
 
-        let session = pb_deploy_item
-            .take_session()
+        let mut pb_session = pb_deploy_item.take_session();
+        let session_arg_names = arg_names(&mut pb_session)?;
+        let session = pb_session
             .payload
             .map(Into::into)
             .ok_or_else(|| MappingError::MissingPayload)?;
 
-        let payment = pb_deploy_item
-            .take_payment()
+        let mut pb_payment = pb_deploy_item.take_payment();
+        let payment_arg_names = arg_names(&mut pb_payment)?;
+        let payment = pb_payment
             .payload
             .map(Into::into)
             .ok_or_else(|| MappingError::MissingPayload)?;
@@ -38,9 +52,13 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             })
             .collect::<Result<BTreeSet<PublicKey>, Self::Error>>()?;
 
-        let deploy_hash = pb_deploy_item.get_deploy_hash().try_into().map_err(|_| {
-            MappingError::invalid_deploy_hash_length(pb_deploy_item.deploy_hash.len())
-        })?;
+        let deploy_hash = pb_deploy_item
+            .get_deploy_hash()
+            .try_into()
+            .map(DeployHash::new)
+            .map_err(|_| {
+                MappingError::invalid_deploy_hash_length(pb_deploy_item.deploy_hash.len())
+            })?;
 
         Ok(DeployItem::new(
             address,
@@ -49,7 +67,9 @@ impl TryFrom<ipc::DeployItem> for DeployItem {
             gas_price,
             authorization_keys,
             deploy_hash,
-        ))
+        )
+        .with_system_deploy(pb_deploy_item.get_system_deploy())
+        .with_arg_names(session_arg_names, payment_arg_names))
     }
 }
 
@@ -57,8 +77,12 @@ impl From<DeployItem> for ipc::DeployItem {
     fn from(deploy_item: DeployItem) -> Self {
         let mut result = ipc::DeployItem::new();
         result.set_address(deploy_item.address.as_bytes().to_vec());
-        result.set_session(deploy_item.session.into());
-        result.set_payment(deploy_item.payment.into());
+        let mut session: ipc::DeployPayload = deploy_item.session.into();
+        session.set_arg_names(deploy_item.session_arg_names.into());
+        result.set_session(session);
+        let mut payment: ipc::DeployPayload = deploy_item.payment.into();
+        payment.set_arg_names(deploy_item.payment_arg_names.into());
+        result.set_payment(payment);
         result.set_gas_price(deploy_item.gas_price);
         result.set_authorization_keys(
             deploy_item
@@ -67,7 +91,130 @@ impl From<DeployItem> for ipc::DeployItem {
                 .map(|key| key.as_bytes().to_vec())
                 .collect(),
         );
-        result.set_deploy_hash(deploy_item.deploy_hash.to_vec());
+        result.set_deploy_hash(deploy_item.deploy_hash.as_bytes().to_vec());
+        result.set_system_deploy(deploy_item.system_deploy);
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeSet, convert::TryFrom};
+
+    use proptest::proptest;
+
+    use engine_core::engine_state::{
+        deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem,
+    };
+    use types::{account::PublicKey, gens, DeployHash};
+
+    use crate::engine_server::{
+        ipc,
+        mappings::{test_utils, MappingError},
+    };
+
+    fn module_bytes() -> ExecutableDeployItem {
+        ExecutableDeployItem::ModuleBytes {
+            module_bytes: vec![],
+            args: vec![],
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn should_round_trip_gas_price(gas_price in test_utils::u64_with_boundaries()) {
+            let deploy_item = DeployItem::new(
+                PublicKey::ed25519_from([1; 32]),
+                module_bytes(),
+                module_bytes(),
+                gas_price,
+                BTreeSet::new(),
+                DeployHash::new([2; 32]),
+            );
+            let parsed = DeployItem::try_from(ipc::DeployItem::from(deploy_item.clone()))
+                .expect("should parse deploy item");
+            assert_eq!(parsed.gas_price, gas_price);
+            assert!(parsed == deploy_item);
+        }
+
+        #[test]
+        fn should_round_trip_deploy_hash(deploy_hash in gens::deploy_hash_arb()) {
+            let deploy_item = DeployItem::new(
+                PublicKey::ed25519_from([1; 32]),
+                module_bytes(),
+                module_bytes(),
+                1,
+                BTreeSet::new(),
+                deploy_hash,
+            );
+            let parsed = DeployItem::try_from(ipc::DeployItem::from(deploy_item))
+                .expect("should parse deploy item");
+            assert_eq!(parsed.deploy_hash, deploy_hash);
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_short_deploy_hash() {
+        let deploy_item = DeployItem::new(
+            PublicKey::ed25519_from([1; 32]),
+            module_bytes(),
+            module_bytes(),
+            1,
+            BTreeSet::new(),
+            DeployHash::new([2; 32]),
+        );
+        let mut pb_deploy_item = ipc::DeployItem::from(deploy_item);
+        pb_deploy_item.set_deploy_hash(vec![2; 31]);
+        match DeployItem::try_from(pb_deploy_item) {
+            Err(MappingError::InvalidDeployHashLength { expected, actual }) => {
+                assert_eq!(expected, 32);
+                assert_eq!(actual, 31);
+            }
+            other => panic!("unexpected result {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn should_round_trip_arg_names() {
+        let deploy_item = DeployItem::new(
+            PublicKey::ed25519_from([1; 32]),
+            module_bytes(),
+            module_bytes(),
+            1,
+            BTreeSet::new(),
+            DeployHash::new([2; 32]),
+        )
+        .with_arg_names(
+            vec![String::new(), String::from("amount")],
+            vec![String::from("amount")],
+        );
+        let parsed = DeployItem::try_from(ipc::DeployItem::from(deploy_item.clone()))
+            .expect("should parse deploy item");
+        assert!(parsed == deploy_item);
+    }
+
+    #[test]
+    fn should_fail_to_parse_duplicate_arg_names() {
+        let deploy_item = DeployItem::new(
+            PublicKey::ed25519_from([1; 32]),
+            module_bytes(),
+            module_bytes(),
+            1,
+            BTreeSet::new(),
+            DeployHash::new([2; 32]),
+        )
+        .with_arg_names(
+            vec![
+                String::new(),
+                String::from("amount"),
+                String::new(),
+                String::from("amount"),
+            ],
+            vec![],
+        );
+        match DeployItem::try_from(ipc::DeployItem::from(deploy_item)) {
+            Err(MappingError::DuplicateArgName(name)) => assert_eq!(name, "amount"),
+            other => panic!("unexpected result {:?}", other.map(|_| ())),
+        }
+    }
+}
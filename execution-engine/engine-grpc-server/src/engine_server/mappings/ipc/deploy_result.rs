@@ -1,23 +1,64 @@
 use engine_core::{
     engine_state::{
         execution_effect::ExecutionEffect, execution_result::ExecutionResult,
-        Error as EngineStateError,
+        execution_stats::ExecutionStats, exit_code::ExitCode, Error as EngineStateError,
     },
     execution::Error as ExecutionError,
 };
 use engine_shared::gas::Gas;
 
-use crate::engine_server::ipc::{DeployError_OutOfGasError, DeployResult};
+use crate::engine_server::ipc::{
+    DeployError_OutOfGasError, DeployResult, DeployResult_ExitCode, WasmErrorLocation,
+};
 
 impl From<ExecutionResult> for DeployResult {
     fn from(execution_result: ExecutionResult) -> DeployResult {
-        match execution_result {
-            ExecutionResult::Success { effect, cost } => detail::execution_success(effect, cost),
+        let exit_code = execution_result.exit_code();
+        let mut pb_deploy_result = match execution_result {
+            ExecutionResult::Success {
+                effect,
+                cost,
+                return_value,
+            } => {
+                let mut pb_deploy_result = detail::execution_success(effect, cost);
+                if let Some(return_value) = return_value {
+                    pb_deploy_result.set_session_return_value(return_value.into());
+                }
+                pb_deploy_result
+            }
             ExecutionResult::Failure {
                 error,
                 effect,
                 cost,
             } => (error, effect, cost).into(),
+        };
+        pb_deploy_result.set_exit_code(exit_code.into());
+        pb_deploy_result.set_exit_code_detail(exit_code.code());
+        pb_deploy_result
+    }
+}
+
+/// Attaches the host function calls and global state reads made by the deploy, if they were
+/// collected.
+impl From<(ExecutionResult, Option<ExecutionStats>)> for DeployResult {
+    fn from((execution_result, execution_stats): (ExecutionResult, Option<ExecutionStats>)) -> Self {
+        let mut pb_deploy_result: DeployResult = execution_result.into();
+        if let Some(execution_stats) = execution_stats {
+            pb_deploy_result.set_host_call_stats(execution_stats.host_call_stats.into());
+            pb_deploy_result.set_read_stats(execution_stats.read_stats.into());
+        }
+        pb_deploy_result
+    }
+}
+
+impl From<ExitCode> for DeployResult_ExitCode {
+    fn from(exit_code: ExitCode) -> Self {
+        match exit_code {
+            ExitCode::Success => DeployResult_ExitCode::SUCCESS,
+            ExitCode::Precondition(_) => DeployResult_ExitCode::PRECONDITION,
+            ExitCode::OutOfGas => DeployResult_ExitCode::OUT_OF_GAS,
+            ExitCode::UserRevert(_) => DeployResult_ExitCode::USER_REVERT,
+            ExitCode::SystemError(_) => DeployResult_ExitCode::SYSTEM_ERROR,
         }
     }
 }
@@ -30,13 +71,26 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             // all reported as "wasm errors".
             error @ EngineStateError::InvalidHashLength { .. }
             | error @ EngineStateError::InvalidPublicKeyLength { .. }
+            | error @ EngineStateError::InvalidDeployItem(_)
             | error @ EngineStateError::InvalidProtocolVersion { .. }
+            | error @ EngineStateError::ConfigNotFound { .. }
+            | error @ EngineStateError::ProtocolVersionNotRecorded(_)
             | error @ EngineStateError::InvalidUpgradeConfig
-            | error @ EngineStateError::WasmPreprocessing(_)
+            | error @ EngineStateError::InvalidAccountConfig
             | error @ EngineStateError::WasmSerialization(_)
             | error @ EngineStateError::Exec(ExecutionError::DeploymentAuthorizationFailure)
-            | error @ EngineStateError::Authorization => {
-                detail::precondition_error(error.to_string())
+            | error @ EngineStateError::Authorization
+            | error @ EngineStateError::SponsorContractAsSession
+            | error @ EngineStateError::AuthorizationContractRejected(_)
+            | error @ EngineStateError::InvalidSystemDeploy
+            | error @ EngineStateError::BlockTimeBeforeGenesis { .. }
+            | error @ EngineStateError::BlockTimeTooFarInFuture { .. } => {
+                detail::precondition_error(error.to_string(), cost)
+            }
+            EngineStateError::WasmPreprocessing(preprocessing_error) => {
+                let location = preprocessing_error.location().clone();
+                let msg = EngineStateError::WasmPreprocessing(preprocessing_error).to_string();
+                detail::wasm_precondition_error(msg, location.into(), cost)
             }
             EngineStateError::Storage(storage_error) => {
                 detail::execution_error(storage_error, effect, cost)
@@ -48,8 +102,15 @@ impl From<(EngineStateError, ExecutionEffect, Gas)> for DeployResult {
             | error @ EngineStateError::Deploy
             | error @ EngineStateError::Finalization
             | error @ EngineStateError::Serialization(_)
-            | error @ EngineStateError::Mint(_) => detail::execution_error(error, effect, cost),
+            | error @ EngineStateError::Mint(_)
+            | error @ EngineStateError::MintInvariantViolated(_)
+            | error @ EngineStateError::DuplicateValidatorEntry(_)
+            | error @ EngineStateError::InvalidValidatorEntry(_)
+            | error @ EngineStateError::SponsorRefundPurseNotSet => {
+                detail::execution_error(error, effect, cost)
+            }
             EngineStateError::Exec(exec_error) => (exec_error, effect, cost).into(),
+            EngineStateError::BlockGasLimitExhausted => detail::skipped_block_gas_exhausted(),
         }
     }
 }
@@ -64,6 +125,12 @@ impl From<(ExecutionError, ExecutionEffect, Gas)> for DeployResult {
             ExecutionError::Revert(status) => {
                 detail::execution_error(status.to_string(), effect, cost)
             }
+            ExecutionError::RevertWithData(status, data) => {
+                let mut pb_deploy_result =
+                    detail::execution_error(status.to_string(), effect, cost);
+                pb_deploy_result.set_revert_data(data);
+                pb_deploy_result
+            }
             ExecutionError::Interpreter(error) => detail::execution_error(error, effect, cost),
             // TODO(mateusz.gorski): Be more specific about execution errors
             other => detail::execution_error(format!("{:?}", other), effect, cost),
@@ -72,7 +139,7 @@ impl From<(ExecutionError, ExecutionEffect, Gas)> for DeployResult {
 }
 
 mod detail {
-    use super::{DeployError_OutOfGasError, DeployResult, ExecutionEffect, Gas};
+    use super::{DeployError_OutOfGasError, DeployResult, ExecutionEffect, Gas, WasmErrorLocation};
 
     /// Constructs an instance of `DeployResult` with no error set, i.e. a successful
     /// result.
@@ -81,10 +148,34 @@ mod detail {
     }
 
     /// Constructs an instance of `DeployResult` with an error set to
-    /// `ProtobufPreconditionFailure`.
-    pub(super) fn precondition_error(msg: String) -> DeployResult {
+    /// `ProtobufPreconditionFailure`, reporting the uncollected `cost` of the rejected deploy.
+    pub(super) fn precondition_error(msg: String, cost: Gas) -> DeployResult {
         let mut pb_deploy_result = DeployResult::new();
-        pb_deploy_result.mut_precondition_failure().set_message(msg);
+        let pb_precondition_failure = pb_deploy_result.mut_precondition_failure();
+        pb_precondition_failure.set_message(msg);
+        pb_precondition_failure.set_cost(cost.value().into());
+        pb_deploy_result
+    }
+
+    /// Constructs an instance of `DeployResult` with an error set to
+    /// `ProtobufPreconditionFailure`, including where in the Wasm module preprocessing failed.
+    pub(super) fn wasm_precondition_error(
+        msg: String,
+        location: WasmErrorLocation,
+        cost: Gas,
+    ) -> DeployResult {
+        let mut pb_deploy_result = precondition_error(msg, cost);
+        pb_deploy_result
+            .mut_precondition_failure()
+            .set_wasm_error_location(location);
+        pb_deploy_result
+    }
+
+    /// Constructs an instance of `DeployResult` marked as skipped because the block gas limit would
+    /// have been exceeded.
+    pub(super) fn skipped_block_gas_exhausted() -> DeployResult {
+        let mut pb_deploy_result = DeployResult::new();
+        pb_deploy_result.mut_skipped_block_gas_exhausted();
         pb_deploy_result
     }
 
@@ -142,8 +233,12 @@ mod detail {
 mod tests {
     use std::convert::TryInto;
 
+    use protobuf::ProtobufEnum;
+
     use engine_shared::{additive_map::AdditiveMap, transform::Transform};
-    use types::{bytesrepr::Error as BytesReprError, AccessRights, ApiError, Key, URef, U512};
+    use types::{
+        bytesrepr::Error as BytesReprError, AccessRights, ApiError, CLValue, Key, URef, U512,
+    };
 
     use super::*;
 
@@ -162,6 +257,7 @@ mod tests {
         let execution_result = ExecutionResult::Success {
             effect: execution_effect,
             cost,
+            return_value: None,
         };
         let mut ipc_deploy_result: DeployResult = execution_result.into();
         assert!(ipc_deploy_result.has_execution_result());
@@ -180,6 +276,24 @@ mod tests {
                 .unwrap()
         };
         assert_eq!(input_transforms, ipc_transforms);
+        assert!(!ipc_deploy_result.has_session_return_value());
+    }
+
+    #[test]
+    fn deploy_result_to_ipc_success_with_return_value() {
+        let return_value = CLValue::from_t(Key::Hash([42; 32])).expect("should create value");
+        let execution_result = ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::default(),
+            return_value: Some(return_value.clone()),
+        };
+        let mut ipc_deploy_result: DeployResult = execution_result.into();
+        assert!(ipc_deploy_result.has_execution_result());
+        let ipc_return_value: CLValue = ipc_deploy_result
+            .take_session_return_value()
+            .try_into()
+            .expect("should map to CLValue");
+        assert_eq!(ipc_return_value, return_value);
     }
 
     fn test_cost<E: Into<EngineStateError>>(expected_cost: Gas, error: E) -> Gas {
@@ -221,6 +335,63 @@ mod tests {
         assert_eq!(test_cost(cost, forged_ref_error), cost);
     }
 
+    fn exit_code_of<E: Into<EngineStateError>>(error: E) -> (DeployResult_ExitCode, u32) {
+        let execution_failure = ExecutionResult::Failure {
+            error: error.into(),
+            effect: Default::default(),
+            cost: Gas::default(),
+        };
+        let ipc_deploy_result: DeployResult = execution_failure.into();
+        (
+            ipc_deploy_result.get_exit_code(),
+            ipc_deploy_result.get_exit_code_detail(),
+        )
+    }
+
+    // Golden values: the exit codes are relied upon by clients and must not change.
+    #[test]
+    fn exit_codes_should_be_stable() {
+        assert_eq!(DeployResult_ExitCode::SUCCESS.value(), 0);
+        assert_eq!(DeployResult_ExitCode::PRECONDITION.value(), 1);
+        assert_eq!(DeployResult_ExitCode::OUT_OF_GAS.value(), 2);
+        assert_eq!(DeployResult_ExitCode::USER_REVERT.value(), 3);
+        assert_eq!(DeployResult_ExitCode::SYSTEM_ERROR.value(), 4);
+
+        let success: DeployResult = ExecutionResult::Success {
+            effect: Default::default(),
+            cost: Gas::default(),
+            return_value: None,
+        }
+        .into();
+        assert_eq!(success.get_exit_code(), DeployResult_ExitCode::SUCCESS);
+        assert_eq!(success.get_exit_code_detail(), 0);
+
+        assert_eq!(
+            exit_code_of(EngineStateError::Authorization),
+            (DeployResult_ExitCode::PRECONDITION, 5)
+        );
+        assert_eq!(
+            exit_code_of(ExecutionError::GasLimit),
+            (DeployResult_ExitCode::OUT_OF_GAS, 0)
+        );
+        assert_eq!(
+            exit_code_of(ExecutionError::Revert(ApiError::User(7))),
+            (DeployResult_ExitCode::USER_REVERT, 7)
+        );
+        assert_eq!(
+            exit_code_of(ExecutionError::Revert(ApiError::InvalidArgument)),
+            (DeployResult_ExitCode::SYSTEM_ERROR, 15)
+        );
+        assert_eq!(
+            exit_code_of(EngineStateError::InsufficientPayment),
+            (DeployResult_ExitCode::SYSTEM_ERROR, 3)
+        );
+        assert_eq!(
+            exit_code_of(EngineStateError::InvalidDeployItem(String::new())),
+            (DeployResult_ExitCode::PRECONDITION, 1)
+        );
+    }
+
     #[test]
     fn revert_error_maps_to_execution_error() {
         let expected_revert = ApiError::UnexpectedContractRefVariant;
@@ -250,4 +421,51 @@ mod tests {
             expected_revert.to_string(),
         );
     }
+
+    #[test]
+    fn revert_with_data_maps_to_execution_error_with_revert_data() {
+        let expected_revert = ApiError::User(3);
+        let revert_data = b"src/main.rs:10: amount > 0".to_vec();
+        let revert_error = ExecutionError::RevertWithData(expected_revert, revert_data.clone());
+        let exec_result = ExecutionResult::Failure {
+            error: EngineStateError::Exec(revert_error),
+            effect: Default::default(),
+            cost: Gas::default(),
+        };
+        let ipc_result: DeployResult = exec_result.into();
+        assert_eq!(
+            ipc_result
+                .get_execution_result()
+                .get_error()
+                .get_exec_error()
+                .get_message(),
+            expected_revert.to_string(),
+        );
+        assert_eq!(ipc_result.get_revert_data(), revert_data.as_slice());
+        assert_eq!(
+            ipc_result.get_exit_code(),
+            DeployResult_ExitCode::USER_REVERT
+        );
+        assert_eq!(ipc_result.get_exit_code_detail(), 3);
+    }
+
+    #[test]
+    fn wasm_preprocessing_error_maps_to_precondition_failure_with_location() {
+        let preprocessing_error = engine_wasm_prep::deserialize(&[0x00, 0x61, 0x73, 0x6d, 0x02])
+            .expect_err("should fail to deserialize");
+        let mut ipc_result: DeployResult = ExecutionResult::precondition_failure(
+            EngineStateError::WasmPreprocessing(preprocessing_error),
+        )
+        .into();
+        assert!(ipc_result.has_precondition_failure());
+        let precondition_failure = ipc_result.mut_precondition_failure();
+        assert!(precondition_failure
+            .get_message()
+            .ends_with("(at header section, byte offset 0)"));
+        let location = precondition_failure.take_wasm_error_location();
+        assert_eq!(location.get_section(), "header");
+        assert!(location.has_byte_offset());
+        assert_eq!(location.get_byte_offset().get_value(), 0);
+        assert!(!location.has_function_index());
+    }
 }
@@ -0,0 +1,50 @@
+use engine_storage::global_state::durability::Durability;
+
+use crate::engine_server::ipc;
+
+/// `SERVER_DEFAULT` maps to `None`, i.e. the durability the server was started with.
+impl From<ipc::Durability> for Option<Durability> {
+    fn from(pb_durability: ipc::Durability) -> Self {
+        match pb_durability {
+            ipc::Durability::SERVER_DEFAULT => None,
+            ipc::Durability::STRICT => Some(Durability::Strict),
+            ipc::Durability::BATCHED => Some(Durability::Batched),
+            ipc::Durability::VOLATILE => Some(Durability::Volatile),
+        }
+    }
+}
+
+impl From<Durability> for ipc::Durability {
+    fn from(durability: Durability) -> Self {
+        match durability {
+            Durability::Strict => ipc::Durability::STRICT,
+            Durability::Batched => ipc::Durability::BATCHED,
+            Durability::Volatile => ipc::Durability::VOLATILE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for durability in &[
+            Durability::Strict,
+            Durability::Batched,
+            Durability::Volatile,
+        ] {
+            let pb_durability = ipc::Durability::from(*durability);
+            assert_eq!(Option::<Durability>::from(pb_durability), Some(*durability));
+        }
+    }
+
+    #[test]
+    fn server_default_should_map_to_none() {
+        assert_eq!(
+            Option::<Durability>::from(ipc::Durability::SERVER_DEFAULT),
+            None
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
 use engine_core::engine_state::genesis::{ExecConfig, GenesisAccount};
+use engine_shared::account::AccountConfig;
+use types::BlockTime;
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -19,13 +21,24 @@ impl TryFrom<ipc::ChainSpec_GenesisConfig_ExecConfig> for ExecConfig {
         let mint_initializer_bytes = pb_exec_config.take_mint_installer();
         let proof_of_stake_initializer_bytes = pb_exec_config.take_pos_installer();
         let standard_payment_installer_bytes = pb_exec_config.take_standard_payment_installer();
-        Ok(ExecConfig::new(
+        let account_config = if !pb_exec_config.has_account_config() {
+            AccountConfig::default()
+        } else {
+            pb_exec_config.take_account_config().try_into()?
+        };
+        let mut exec_config = ExecConfig::new(
             mint_initializer_bytes,
             proof_of_stake_initializer_bytes,
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
-        ))
+            account_config,
+        );
+        exec_config.set_genesis_timestamp(BlockTime::from_millis(
+            pb_exec_config.get_genesis_timestamp(),
+        ));
+        exec_config.set_strict_genesis_bonding(!pb_exec_config.get_lenient_genesis_bonding());
+        Ok(exec_config)
     }
 }
 
@@ -49,6 +62,9 @@ impl From<ExecConfig> for ipc::ChainSpec_GenesisConfig_ExecConfig {
         pb_exec_config
             .mut_costs()
             .set_wasm(exec_config.wasm_costs().into());
+        pb_exec_config.set_account_config(exec_config.account_config().into());
+        pb_exec_config.set_genesis_timestamp(exec_config.genesis_timestamp().as_millis());
+        pb_exec_config.set_lenient_genesis_bonding(!exec_config.strict_genesis_bonding());
         pb_exec_config
     }
 }
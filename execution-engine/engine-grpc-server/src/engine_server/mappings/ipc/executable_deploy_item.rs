@@ -29,6 +29,18 @@ impl From<DeployPayload_oneof_payload> for ExecutableDeployItem {
                     args: pb_stored_contract_uref.args,
                 }
             }
+            DeployPayload_oneof_payload::sponsor_contract_hash(pb_sponsor_contract_hash) => {
+                ExecutableDeployItem::SponsorContractByHash {
+                    hash: pb_sponsor_contract_hash.hash,
+                    args: pb_sponsor_contract_hash.args,
+                }
+            }
+            DeployPayload_oneof_payload::sponsor_contract_name(pb_sponsor_contract_name) => {
+                ExecutableDeployItem::SponsorContractByName {
+                    name: pb_sponsor_contract_name.stored_contract_name,
+                    args: pb_sponsor_contract_name.args,
+                }
+            }
         }
     }
 }
@@ -57,6 +69,16 @@ impl From<ExecutableDeployItem> for DeployPayload {
                 inner.set_uref(uref);
                 inner.set_args(args);
             }
+            ExecutableDeployItem::SponsorContractByHash { hash, args } => {
+                let inner = result.mut_sponsor_contract_hash();
+                inner.set_hash(hash);
+                inner.set_args(args);
+            }
+            ExecutableDeployItem::SponsorContractByName { name, args } => {
+                let inner = result.mut_sponsor_contract_name();
+                inner.set_stored_contract_name(name);
+                inner.set_args(args);
+            }
         }
         result
     }
@@ -1,9 +1,13 @@
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    iter::FromIterator,
+};
 
 use engine_core::engine_state::{
     execute_request::ExecuteRequest, execution_result::ExecutionResult,
 };
-use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use engine_shared::{gas::Gas, newtypes::BLAKE2B_DIGEST_LENGTH};
+use types::BlockTime;
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
@@ -28,7 +32,7 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
             })?
         };
 
-        let block_time = request.get_block_time();
+        let block_time = BlockTime::from_millis(request.get_block_time());
 
         let deploys = Into::<Vec<_>>::into(request.take_deploys())
             .into_iter()
@@ -41,12 +45,16 @@ impl TryFrom<ipc::ExecuteRequest> for ExecuteRequest {
 
         let protocol_version = request.take_protocol_version().into();
 
-        Ok(ExecuteRequest::new(
-            parent_state_hash,
-            block_time,
-            deploys,
-            protocol_version,
-        ))
+        let exec_request =
+            ExecuteRequest::new(parent_state_hash, block_time, deploys, protocol_version)
+                .with_collect_stats(request.get_collect_stats());
+
+        match request.get_block_gas_limit() {
+            0 => Ok(exec_request),
+            block_gas_limit => {
+                Ok(exec_request.with_block_gas_limit(Gas::new(block_gas_limit.into())))
+            }
+        }
     }
 }
 
@@ -54,7 +62,7 @@ impl From<ExecuteRequest> for ipc::ExecuteRequest {
     fn from(req: ExecuteRequest) -> Self {
         let mut result = ipc::ExecuteRequest::new();
         result.set_parent_state_hash(req.parent_state_hash.to_vec());
-        result.set_block_time(req.block_time);
+        result.set_block_time(req.block_time.as_millis());
         result.set_deploys(
             req.deploys
                 .into_iter()
@@ -65,6 +73,118 @@ impl From<ExecuteRequest> for ipc::ExecuteRequest {
                 .collect(),
         );
         result.set_protocol_version(req.protocol_version.into());
+        if let Some(block_gas_limit) = req.block_gas_limit {
+            result.set_block_gas_limit(block_gas_limit.value().as_u64());
+        }
+        result.set_collect_stats(req.collect_stats);
         result
     }
 }
+
+/// Builds the response to a request whose deploys all ran, or were skipped, against an existing
+/// parent.
+///
+/// The `success` result is always set, including for an empty block where `results` is empty:
+/// the node treats a response without a result as an error.
+impl From<Vec<ExecutionResult>> for ipc::ExecuteResponse {
+    fn from(results: Vec<ExecutionResult>) -> Self {
+        results
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<ipc::DeployResult>>()
+            .into()
+    }
+}
+
+impl From<Vec<ipc::DeployResult>> for ipc::ExecuteResponse {
+    fn from(deploy_results: Vec<ipc::DeployResult>) -> Self {
+        let mut exec_response = ipc::ExecuteResponse::new();
+        exec_response
+            .mut_success()
+            .set_deploy_results(FromIterator::from_iter(deploy_results));
+        exec_response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use proptest::proptest;
+    use protobuf::Message;
+
+    use engine_core::engine_state::{
+        execute_request::ExecuteRequest, execution_result::ExecutionResult,
+    };
+    use engine_shared::gas::Gas;
+    use types::{BlockTime, ProtocolVersion};
+
+    use crate::engine_server::{ipc, mappings::test_utils};
+
+    /// An empty block's response: `success` (field 1) holding an `ExecResult` with no deploy
+    /// results.
+    const GOLDEN_EMPTY_EXECUTE_RESPONSE: [u8; 2] = [0x0a, 0x00];
+
+    #[test]
+    fn should_serialize_empty_block_response_as_success() {
+        let exec_response = ipc::ExecuteResponse::from(Vec::<ExecutionResult>::new());
+        assert!(exec_response.has_success());
+        assert_eq!(
+            exec_response.write_to_bytes().unwrap(),
+            GOLDEN_EMPTY_EXECUTE_RESPONSE.to_vec()
+        );
+    }
+
+    #[test]
+    fn should_parse_empty_block_response_as_success() {
+        let exec_response: ipc::ExecuteResponse =
+            protobuf::parse_from_bytes(&GOLDEN_EMPTY_EXECUTE_RESPONSE).unwrap();
+        assert!(exec_response.has_success());
+        assert!(exec_response.get_success().get_deploy_results().is_empty());
+    }
+
+    #[test]
+    fn should_round_trip_empty_block_request() {
+        let parent_state_hash = [1u8; 32].into();
+        let block_time = BlockTime::from_millis(42);
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let exec_request =
+            ExecuteRequest::new(parent_state_hash, block_time, vec![], protocol_version);
+
+        let parsed = ExecuteRequest::try_from(ipc::ExecuteRequest::from(exec_request))
+            .expect("should parse empty block request");
+
+        assert_eq!(parsed.parent_state_hash, parent_state_hash);
+        assert_eq!(parsed.block_time, block_time);
+        assert!(parsed.deploys.is_empty());
+        assert_eq!(parsed.protocol_version, protocol_version);
+        assert_eq!(parsed.block_gas_limit, None);
+        assert!(!parsed.collect_stats);
+    }
+
+    proptest! {
+        #[test]
+        fn should_round_trip_numeric_fields(
+            block_time in test_utils::u64_with_boundaries(),
+            block_gas_limit in test_utils::u64_with_boundaries()
+        ) {
+            let block_time = BlockTime::from_millis(block_time);
+            // A block gas limit of zero is sent as no limit.
+            let block_gas_limit = match block_gas_limit {
+                0 => None,
+                block_gas_limit => Some(Gas::new(block_gas_limit.into())),
+            };
+            let mut exec_request =
+                ExecuteRequest::new([1u8; 32].into(), block_time, vec![], ProtocolVersion::V1_0_0);
+            if let Some(block_gas_limit) = block_gas_limit {
+                exec_request = exec_request.with_block_gas_limit(block_gas_limit);
+            }
+
+            let parsed = ExecuteRequest::try_from(ipc::ExecuteRequest::from(exec_request))
+                .expect("should parse request");
+
+            assert_eq!(parsed.block_time, block_time);
+            assert_eq!(parsed.block_gas_limit, block_gas_limit);
+        }
+    }
+}
@@ -23,6 +23,12 @@ impl From<(Key, Op)> for OpEntry {
     }
 }
 
+impl From<(&Key, &Op)> for OpEntry {
+    fn from((key, op): (&Key, &Op)) -> OpEntry {
+        (*key, *op).into()
+    }
+}
+
 impl From<ExecutionEffect> for ipc::ExecutionEffect {
     fn from(execution_effect: ExecutionEffect) -> ipc::ExecutionEffect {
         let mut pb_execution_effect = ipc::ExecutionEffect::new();
@@ -40,3 +46,51 @@ impl From<ExecutionEffect> for ipc::ExecutionEffect {
         pb_execution_effect
     }
 }
+
+/// Converts a borrowed execution effect, copying each written value once, straight into its
+/// protobuf message.  Prefer converting an owned `ExecutionEffect` where possible, as that moves
+/// the written values instead.
+impl From<&ExecutionEffect> for ipc::ExecutionEffect {
+    fn from(execution_effect: &ExecutionEffect) -> ipc::ExecutionEffect {
+        let mut pb_execution_effect = ipc::ExecutionEffect::new();
+
+        let pb_op_map: Vec<OpEntry> = execution_effect.ops.iter().map(Into::into).collect();
+        pb_execution_effect.set_op_map(pb_op_map.into());
+
+        let pb_transform_map: Vec<ProbufTransformEntry> =
+            execution_effect.transforms.iter().map(Into::into).collect();
+        pb_execution_effect.set_transform_map(pb_transform_map.into());
+
+        pb_execution_effect
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, proptest};
+
+    use engine_shared::{additive_map::AdditiveMap, transform::gens};
+    use types::gens::key_arb;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn borrowed_conversion_matches(entries in vec((key_arb(), gens::transform_arb()), 0..10)) {
+            let mut ops = AdditiveMap::new();
+            let mut transforms = AdditiveMap::new();
+            for (key, transform) in entries {
+                ops.insert(key, Op::Write);
+                transforms.insert(key, transform);
+            }
+            let execution_effect = ExecutionEffect::new(ops, transforms);
+
+            let pb_execution_effect = ipc::ExecutionEffect::from(&execution_effect);
+            test_utils::assert_same_protobuf_bytes(
+                &pb_execution_effect,
+                &execution_effect.into(),
+            );
+        }
+    }
+}
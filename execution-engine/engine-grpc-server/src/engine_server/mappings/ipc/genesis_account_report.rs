@@ -0,0 +1,66 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::genesis::GenesisAccountReport;
+use engine_shared::motes::Motes;
+use types::account::PublicKey;
+
+use crate::engine_server::{ipc, mappings::MappingError};
+
+impl From<GenesisAccountReport> for ipc::GenesisAccountReport {
+    fn from(account_report: GenesisAccountReport) -> Self {
+        let mut pb_account_report = ipc::GenesisAccountReport::new();
+
+        pb_account_report.set_public_key(account_report.public_key.as_bytes().to_vec());
+        pb_account_report.set_account_key(account_report.account_key.into());
+        pb_account_report.set_main_purse(account_report.main_purse_uref.into());
+        pb_account_report.set_balance_uref(account_report.balance_uref.into());
+        pb_account_report.set_initial_balance(account_report.initial_balance.value().into());
+        pb_account_report.set_bonded_amount(account_report.bonded_amount.value().into());
+
+        pb_account_report
+    }
+}
+
+impl TryFrom<ipc::GenesisAccountReport> for GenesisAccountReport {
+    type Error = MappingError;
+
+    fn try_from(mut pb_account_report: ipc::GenesisAccountReport) -> Result<Self, Self::Error> {
+        let public_key =
+            PublicKey::ed25519_try_from(pb_account_report.get_public_key()).map_err(|_| {
+                MappingError::invalid_public_key_length(pb_account_report.public_key.len())
+            })?;
+        let account_key = pb_account_report.take_account_key().try_into()?;
+        let main_purse_uref = pb_account_report.take_main_purse().try_into()?;
+        let balance_uref = pb_account_report.take_balance_uref().try_into()?;
+        let initial_balance = pb_account_report
+            .take_initial_balance()
+            .try_into()
+            .map(Motes::new)?;
+        let bonded_amount = pb_account_report
+            .take_bonded_amount()
+            .try_into()
+            .map(Motes::new)?;
+        Ok(GenesisAccountReport {
+            public_key,
+            account_key,
+            main_purse_uref,
+            balance_uref,
+            initial_balance,
+            bonded_amount,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    #[test]
+    fn round_trip() {
+        let account_report = rand::random();
+        test_utils::protobuf_round_trip::<GenesisAccountReport, ipc::GenesisAccountReport>(
+            account_report,
+        );
+    }
+}
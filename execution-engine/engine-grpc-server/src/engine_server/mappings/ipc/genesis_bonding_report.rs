@@ -0,0 +1,100 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::genesis::{GenesisBondingError, GenesisBondingReport};
+use engine_shared::motes::Motes;
+use types::account::PublicKey;
+
+use crate::engine_server::{
+    ipc::{
+        self, GenesisBondingReport_Skipped_BondedAmountExceedsBalance,
+        GenesisBondingReport_Skipped_oneof_reason, GenesisBondingReport_oneof_outcome,
+    },
+    mappings::MappingError,
+};
+
+impl From<GenesisBondingReport> for ipc::GenesisBondingReport {
+    fn from(bonding_report: GenesisBondingReport) -> Self {
+        let mut pb_bonding_report = ipc::GenesisBondingReport::new();
+        pb_bonding_report.set_public_key(bonding_report.key().as_bytes().to_vec());
+
+        match bonding_report {
+            GenesisBondingReport::Bonded { amount, .. } => {
+                pb_bonding_report
+                    .mut_bonded()
+                    .set_amount(amount.value().into());
+            }
+            GenesisBondingReport::Skipped {
+                reason:
+                    GenesisBondingError::BondedAmountExceedsBalance {
+                        balance,
+                        bonded_amount,
+                    },
+                ..
+            } => {
+                let pb_reason = pb_bonding_report
+                    .mut_skipped()
+                    .mut_bonded_amount_exceeds_balance();
+                pb_reason.set_balance(balance.value().into());
+                pb_reason.set_bonded_amount(bonded_amount.value().into());
+            }
+        }
+
+        pb_bonding_report
+    }
+}
+
+impl TryFrom<ipc::GenesisBondingReport> for GenesisBondingReport {
+    type Error = MappingError;
+
+    fn try_from(mut pb_bonding_report: ipc::GenesisBondingReport) -> Result<Self, Self::Error> {
+        let key =
+            PublicKey::ed25519_try_from(pb_bonding_report.get_public_key()).map_err(|_| {
+                MappingError::invalid_public_key_length(pb_bonding_report.public_key.len())
+            })?;
+        match pb_bonding_report.outcome.take() {
+            Some(GenesisBondingReport_oneof_outcome::bonded(mut pb_bonded)) => {
+                let amount = pb_bonded.take_amount().try_into().map(Motes::new)?;
+                Ok(GenesisBondingReport::Bonded { key, amount })
+            }
+            Some(GenesisBondingReport_oneof_outcome::skipped(mut pb_skipped)) => {
+                let reason = match pb_skipped.reason.take() {
+                    Some(
+                        GenesisBondingReport_Skipped_oneof_reason::bonded_amount_exceeds_balance(
+                            pb_reason,
+                        ),
+                    ) => bonded_amount_exceeds_balance(pb_reason)?,
+                    None => return Err(MappingError::MissingPayload),
+                };
+                Ok(GenesisBondingReport::Skipped { key, reason })
+            }
+            None => Err(MappingError::MissingPayload),
+        }
+    }
+}
+
+fn bonded_amount_exceeds_balance(
+    mut pb_reason: GenesisBondingReport_Skipped_BondedAmountExceedsBalance,
+) -> Result<GenesisBondingError, MappingError> {
+    let balance = pb_reason.take_balance().try_into().map(Motes::new)?;
+    let bonded_amount = pb_reason.take_bonded_amount().try_into().map(Motes::new)?;
+    Ok(GenesisBondingError::BondedAmountExceedsBalance {
+        balance,
+        bonded_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    #[test]
+    fn round_trip() {
+        for _ in 0..10 {
+            let bonding_report = rand::random();
+            test_utils::protobuf_round_trip::<GenesisBondingReport, ipc::GenesisBondingReport>(
+                bonding_report,
+            );
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use engine_core::execution::HostCallStats;
+
+use crate::engine_server::ipc::{self, HostCallStats_Entry};
+
+impl From<HostCallStats> for ipc::HostCallStats {
+    fn from(host_call_stats: HostCallStats) -> Self {
+        let mut pb_host_call_stats = ipc::HostCallStats::new();
+        for (host_function, stat) in host_call_stats.iter() {
+            let mut pb_entry = HostCallStats_Entry::new();
+            pb_entry.set_host_function(host_function.to_string());
+            pb_entry.set_calls(stat.calls);
+            pb_entry.set_gas(stat.gas.value().into());
+            pb_host_call_stats.mut_entries().push(pb_entry);
+        }
+        pb_host_call_stats
+    }
+}
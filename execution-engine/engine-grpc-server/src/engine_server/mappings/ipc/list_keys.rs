@@ -0,0 +1,66 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::list_keys::{ListKeysRequest, NamedKeysPage};
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+
+use crate::engine_server::{
+    ipc::{self, ListKeysRequest_PageToken, ListKeysResponse_Page},
+    mappings::{self, MappingError},
+    state::NamedKey,
+};
+
+impl TryFrom<ipc::ListKeysRequest> for ListKeysRequest {
+    type Error = MappingError;
+
+    fn try_from(mut list_keys_request: ipc::ListKeysRequest) -> Result<Self, Self::Error> {
+        let state_hash = {
+            let state_hash = list_keys_request.get_state_hash();
+            let length = state_hash.len();
+            if length != BLAKE2B_DIGEST_LENGTH {
+                return Err(MappingError::InvalidStateHashLength {
+                    expected: BLAKE2B_DIGEST_LENGTH,
+                    actual: length,
+                });
+            }
+            state_hash
+                .try_into()
+                .map_err(|_| MappingError::TryFromSlice)?
+        };
+
+        let key = list_keys_request
+            .take_base_key()
+            .try_into()
+            .map_err(MappingError::Parsing)?;
+
+        let path = list_keys_request.take_path().into_vec();
+
+        let page_size = mappings::convert_number(list_keys_request.get_page_size(), "page size")?;
+
+        let request = ListKeysRequest::new(state_hash, key, path, page_size);
+
+        if list_keys_request.has_page_token() {
+            let last_name = list_keys_request.take_page_token().take_last_name();
+            Ok(request.with_page_token(last_name))
+        } else {
+            Ok(request)
+        }
+    }
+}
+
+impl From<NamedKeysPage> for ListKeysResponse_Page {
+    fn from(named_keys_page: NamedKeysPage) -> Self {
+        let mut pb_page = ListKeysResponse_Page::new();
+        let pb_named_keys: Vec<NamedKey> = named_keys_page
+            .named_keys
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        pb_page.set_named_keys(pb_named_keys.into());
+        if let Some(last_name) = named_keys_page.next_page_token {
+            let mut pb_page_token = ListKeysRequest_PageToken::new();
+            pb_page_token.set_last_name(last_name);
+            pb_page.set_next_page_token(pb_page_token);
+        }
+        pb_page
+    }
+}
@@ -0,0 +1,64 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::list_roots::{ListRootsRequest, RootsPage};
+use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+
+use crate::engine_server::{
+    ipc::{self, ListRootsRequest_PageToken, ListRootsResponse_Page, ListRootsResponse_Root},
+    mappings::{self, MappingError},
+};
+
+impl TryFrom<ipc::ListRootsRequest> for ListRootsRequest {
+    type Error = MappingError;
+
+    fn try_from(list_roots_request: ipc::ListRootsRequest) -> Result<Self, Self::Error> {
+        let page_size = mappings::convert_number(list_roots_request.get_page_size(), "page size")?;
+        let request = ListRootsRequest::new(page_size);
+
+        if !list_roots_request.has_page_token() {
+            return Ok(request);
+        }
+
+        let last_state_hash = list_roots_request.get_page_token().get_last_state_hash();
+        let length = last_state_hash.len();
+        if length != BLAKE2B_DIGEST_LENGTH {
+            return Err(MappingError::InvalidStateHashLength {
+                expected: BLAKE2B_DIGEST_LENGTH,
+                actual: length,
+            });
+        }
+        let page_token = last_state_hash
+            .try_into()
+            .map_err(|_| MappingError::TryFromSlice)?;
+        Ok(request.with_page_token(page_token))
+    }
+}
+
+impl From<RootsPage> for ListRootsResponse_Page {
+    fn from(roots_page: RootsPage) -> Self {
+        let mut pb_page = ListRootsResponse_Page::new();
+        let pb_roots: Vec<ListRootsResponse_Root> = roots_page
+            .roots
+            .into_iter()
+            .map(|(state_hash, metadata)| {
+                let mut pb_root = ListRootsResponse_Root::new();
+                pb_root.set_state_hash(state_hash.to_vec());
+                pb_root.set_created_at(metadata.created_at);
+                if let Some(parent_hint) = metadata.parent_hint {
+                    pb_root.set_parent_hint(parent_hint.to_vec());
+                }
+                if let Some(genesis_fingerprint) = metadata.genesis_fingerprint {
+                    pb_root.set_genesis_fingerprint(genesis_fingerprint.to_vec());
+                }
+                pb_root
+            })
+            .collect();
+        pb_page.set_roots(pb_roots.into());
+        if let Some(last_state_hash) = roots_page.next_page_token {
+            let mut pb_page_token = ListRootsRequest_PageToken::new();
+            pb_page_token.set_last_state_hash(last_state_hash.to_vec());
+            pb_page.set_next_page_token(pb_page_token);
+        }
+        pb_page
+    }
+}
@@ -1,16 +1,39 @@
 //! Functions for converting between CasperLabs types and their Protobuf equivalents which are
 //! defined in protobuf/io/casperlabs/ipc/ipc.proto
 
+mod account_auth_info;
+mod account_config;
+mod balance;
+mod batch_commit;
 mod bond;
+mod build_state_request;
+mod config_diff;
 mod deploy_item;
 mod deploy_result;
+mod durability;
 mod exec_config;
 mod executable_deploy_item;
 mod execute_request;
 mod execution_effect;
 mod genesis_account;
+mod genesis_account_report;
+mod genesis_bonding_report;
 mod genesis_config;
+mod host_call_stats;
+mod list_keys;
+mod list_roots;
 mod query_request;
+mod query_response;
+mod read_stats;
+mod replay;
+mod request_stats;
 mod run_genesis_request;
+mod trie_nodes;
 mod upgrade_request;
+mod validate_deploy;
 mod wasm_costs;
+mod wasm_error_location;
+
+pub use batch_commit::BatchCommitEntries;
+pub(crate) use bond::canonical_bonds;
+pub(crate) use trie_nodes::trie_nodes;
@@ -1,27 +1,28 @@
 use std::convert::{TryFrom, TryInto};
 
-use engine_core::engine_state::query::QueryRequest;
-use engine_shared::newtypes::BLAKE2B_DIGEST_LENGTH;
+use engine_core::engine_state::query::{MultiRootQueryRequest, QueryRequest};
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
 
 use crate::engine_server::{ipc, mappings::MappingError};
 
+fn parse_state_hash(state_hash: &[u8]) -> Result<Blake2bHash, MappingError> {
+    let length = state_hash.len();
+    if length != BLAKE2B_DIGEST_LENGTH {
+        return Err(MappingError::InvalidStateHashLength {
+            expected: BLAKE2B_DIGEST_LENGTH,
+            actual: length,
+        });
+    }
+    state_hash
+        .try_into()
+        .map_err(|_| MappingError::TryFromSlice)
+}
+
 impl TryFrom<ipc::QueryRequest> for QueryRequest {
     type Error = MappingError;
 
     fn try_from(mut query_request: ipc::QueryRequest) -> Result<Self, Self::Error> {
-        let state_hash = {
-            let state_hash = query_request.get_state_hash();
-            let length = state_hash.len();
-            if length != BLAKE2B_DIGEST_LENGTH {
-                return Err(MappingError::InvalidStateHashLength {
-                    expected: BLAKE2B_DIGEST_LENGTH,
-                    actual: length,
-                });
-            }
-            state_hash
-                .try_into()
-                .map_err(|_| MappingError::TryFromSlice)?
-        };
+        let state_hash = parse_state_hash(query_request.get_state_hash())?;
 
         let key = query_request
             .take_base_key()
@@ -33,3 +34,26 @@ impl TryFrom<ipc::QueryRequest> for QueryRequest {
         Ok(QueryRequest::new(state_hash, key, path))
     }
 }
+
+impl TryFrom<ipc::MultiRootQueryRequest> for MultiRootQueryRequest {
+    type Error = MappingError;
+
+    fn try_from(
+        mut multi_root_query_request: ipc::MultiRootQueryRequest,
+    ) -> Result<Self, Self::Error> {
+        let state_hashes = multi_root_query_request
+            .get_state_hashes()
+            .iter()
+            .map(|state_hash| parse_state_hash(state_hash))
+            .collect::<Result<Vec<Blake2bHash>, MappingError>>()?;
+
+        let key = multi_root_query_request
+            .take_base_key()
+            .try_into()
+            .map_err(MappingError::Parsing)?;
+
+        let path = multi_root_query_request.take_path().into_vec();
+
+        Ok(MultiRootQueryRequest::new(state_hashes, key, path))
+    }
+}
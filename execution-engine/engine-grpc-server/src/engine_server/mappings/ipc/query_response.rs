@@ -0,0 +1,89 @@
+use engine_shared::{contract::Contract, newtypes::Blake2bHash, stored_value::StoredValue};
+use types::bytesrepr::ToBytes;
+
+use crate::engine_server::{
+    ipc::{QueryResponse_ContractSummary, QueryResponse_ValueType},
+    mappings::{self, state::NamedKeyMap},
+    state::{self, NamedKey},
+};
+
+/// Describes the type of `stored_value` without serializing it, so that the type of a large value
+/// can be returned without copying the value into the response.
+impl From<&StoredValue> for QueryResponse_ValueType {
+    fn from(stored_value: &StoredValue) -> Self {
+        let mut pb_value_type = QueryResponse_ValueType::new();
+        match stored_value {
+            StoredValue::CLValue(cl_value) => {
+                pb_value_type.set_cl_type(cl_value.cl_type().clone().into())
+            }
+            StoredValue::Account(_) => pb_value_type.set_account(state::Unit::new()),
+            StoredValue::Contract(_) => pb_value_type.set_contract(state::Unit::new()),
+        }
+        pb_value_type.set_serialized_size(mappings::usize_to_u64(stored_value.serialized_length()));
+        pb_value_type
+    }
+}
+
+/// Summarizes `contract` without its wasm bytes, which can be megabytes, so that querying a
+/// contract doesn't copy the whole module into the response.
+impl From<&Contract> for QueryResponse_ContractSummary {
+    fn from(contract: &Contract) -> Self {
+        let mut pb_contract_summary = QueryResponse_ContractSummary::new();
+        pb_contract_summary.set_body_size(mappings::usize_to_u64(contract.bytes().len()));
+        pb_contract_summary.set_body_hash(Blake2bHash::new(contract.bytes()).to_vec());
+        let named_keys: Vec<NamedKey> = NamedKeyMap::new(contract.named_keys().clone()).into();
+        pb_contract_summary.set_named_keys(named_keys.into());
+        pb_contract_summary.set_protocol_version(contract.protocol_version().into());
+        pb_contract_summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use proptest::proptest;
+
+    use engine_shared::{contract, stored_value::gens};
+    use types::ProtocolVersion;
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn should_describe_stored_value(stored_value in gens::stored_value_arb()) {
+            let pb_value_type = QueryResponse_ValueType::from(&stored_value);
+            let serialized_size = stored_value.to_bytes().expect("should serialize").len();
+            assert_eq!(pb_value_type.get_serialized_size(), mappings::usize_to_u64(serialized_size));
+            match stored_value {
+                StoredValue::CLValue(cl_value) => {
+                    assert_eq!(*pb_value_type.get_cl_type(), cl_value.cl_type().clone().into())
+                }
+                StoredValue::Account(_) => assert!(pb_value_type.has_account()),
+                StoredValue::Contract(_) => assert!(pb_value_type.has_contract()),
+            }
+        }
+
+        #[test]
+        fn should_summarize_contract(contract in contract::gens::contract_arb()) {
+            let mut pb_contract_summary = QueryResponse_ContractSummary::from(&contract);
+            assert_eq!(
+                pb_contract_summary.get_body_size(),
+                mappings::usize_to_u64(contract.bytes().len())
+            );
+            assert_eq!(
+                pb_contract_summary.get_body_hash(),
+                Blake2bHash::new(contract.bytes()).to_vec().as_slice()
+            );
+            let named_keys: NamedKeyMap = pb_contract_summary
+                .take_named_keys()
+                .into_vec()
+                .try_into()
+                .expect("should parse named keys");
+            assert_eq!(&named_keys.into_inner(), contract.named_keys());
+            let protocol_version: ProtocolVersion =
+                pb_contract_summary.take_protocol_version().into();
+            assert_eq!(protocol_version, contract.protocol_version());
+        }
+    }
+}
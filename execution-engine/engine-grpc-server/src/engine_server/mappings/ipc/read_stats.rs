@@ -0,0 +1,15 @@
+use engine_storage::global_state::read_stats::ReadStats;
+
+use crate::engine_server::ipc;
+
+impl From<ReadStats> for ipc::ReadStats {
+    fn from(read_stats: ReadStats) -> Self {
+        let mut pb_read_stats = ipc::ReadStats::new();
+        pb_read_stats.set_reads(read_stats.reads);
+        pb_read_stats.set_nodes_visited(read_stats.nodes_visited);
+        pb_read_stats.set_bytes_read(read_stats.bytes_read);
+        pb_read_stats.set_cache_hits(read_stats.cache_hits);
+        pb_read_stats.set_max_nodes_per_read(read_stats.max_nodes_per_read);
+        pb_read_stats
+    }
+}
@@ -0,0 +1,151 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::{
+    replay::{PhaseTrace, ReplayOverrides, ReplayRequest, ReplayedDeploy},
+    repro_bundle::{BundleReplayResult, ExportReproBundleRequest},
+};
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
+use types::{BlockTime, Phase};
+
+use crate::engine_server::{
+    ipc::{
+        self, ReplayReproBundleResponse, ReplayReproBundleResponse_EffectsMismatch,
+        ReplayResponse_EffectsMatch, ReplayResponse_PhaseTrace, ReplayResponse_PhaseTrace_Phase,
+        ReplayResponse_ReplayResult,
+    },
+    mappings::MappingError,
+};
+
+fn to_state_hash(hash: &[u8]) -> Result<Blake2bHash, MappingError> {
+    let length = hash.len();
+    if length != BLAKE2B_DIGEST_LENGTH {
+        return Err(MappingError::InvalidStateHashLength {
+            expected: BLAKE2B_DIGEST_LENGTH,
+            actual: length,
+        });
+    }
+    hash.try_into().map_err(|_| MappingError::TryFromSlice)
+}
+
+impl TryFrom<ipc::ReplayRequest> for ReplayRequest {
+    type Error = MappingError;
+
+    fn try_from(mut replay_request: ipc::ReplayRequest) -> Result<Self, Self::Error> {
+        let parent_state_hash = to_state_hash(replay_request.get_parent_state_hash())?;
+        let block_time = BlockTime::from_millis(replay_request.get_block_time());
+        let deploy_item = replay_request.take_deploy().try_into()?;
+        let protocol_version = replay_request.take_protocol_version().into();
+
+        let overrides = {
+            let mut pb_overrides = replay_request.take_overrides();
+            let wasm_costs = if pb_overrides.has_wasm_costs() {
+                Some(pb_overrides.take_wasm_costs().into())
+            } else {
+                None
+            };
+            let block_time = if pb_overrides.has_block_time() {
+                Some(BlockTime::from_millis(
+                    pb_overrides.get_block_time().get_block_time(),
+                ))
+            } else {
+                None
+            };
+            ReplayOverrides {
+                wasm_costs,
+                collect_trace: pb_overrides.get_collect_trace(),
+                block_time,
+            }
+        };
+
+        let request =
+            ReplayRequest::new(parent_state_hash, block_time, deploy_item, protocol_version)
+                .with_overrides(overrides);
+
+        let recorded_effects_hash = replay_request.get_recorded_effects_hash();
+        if recorded_effects_hash.is_empty() {
+            Ok(request)
+        } else {
+            let recorded_effects_hash = to_state_hash(recorded_effects_hash)?;
+            Ok(request.with_recorded_effects_hash(recorded_effects_hash))
+        }
+    }
+}
+
+impl From<Phase> for ReplayResponse_PhaseTrace_Phase {
+    fn from(phase: Phase) -> Self {
+        match phase {
+            Phase::System => ReplayResponse_PhaseTrace_Phase::SYSTEM,
+            Phase::Payment => ReplayResponse_PhaseTrace_Phase::PAYMENT,
+            Phase::Session => ReplayResponse_PhaseTrace_Phase::SESSION,
+            Phase::FinalizePayment => ReplayResponse_PhaseTrace_Phase::FINALIZE_PAYMENT,
+            Phase::Authorization => ReplayResponse_PhaseTrace_Phase::AUTHORIZATION,
+        }
+    }
+}
+
+impl From<PhaseTrace> for ReplayResponse_PhaseTrace {
+    fn from(phase_trace: PhaseTrace) -> Self {
+        let mut pb_phase_trace = ReplayResponse_PhaseTrace::new();
+        pb_phase_trace.set_phase(phase_trace.phase.into());
+        pb_phase_trace.set_cost(phase_trace.cost.value().into());
+        if let Some(error) = phase_trace.error {
+            pb_phase_trace.set_error(error);
+        }
+        pb_phase_trace
+    }
+}
+
+impl From<ReplayedDeploy> for ReplayResponse_ReplayResult {
+    fn from(replayed_deploy: ReplayedDeploy) -> Self {
+        let mut pb_replay_result = ReplayResponse_ReplayResult::new();
+        pb_replay_result.set_deploy_result(replayed_deploy.execution_result.into());
+        pb_replay_result.set_effects_hash(replayed_deploy.effects_hash.to_vec());
+        if let Some(matches) = replayed_deploy.matches_recorded_effects {
+            let mut effects_match = ReplayResponse_EffectsMatch::new();
+            effects_match.set_matches(matches);
+            pb_replay_result.set_effects_match(effects_match);
+        }
+        if let Some(trace) = replayed_deploy.trace {
+            let pb_trace = trace.phases().iter().cloned().map(Into::into).collect();
+            pb_replay_result.set_trace(pb_trace);
+            pb_replay_result.set_base_deploy_cost(trace.base_deploy_cost().value().into());
+        }
+        pb_replay_result
+    }
+}
+
+impl TryFrom<ipc::ExportReproBundleRequest> for ExportReproBundleRequest {
+    type Error = MappingError;
+
+    fn try_from(mut export_request: ipc::ExportReproBundleRequest) -> Result<Self, Self::Error> {
+        let parent_state_hash = to_state_hash(export_request.get_parent_state_hash())?;
+        let block_time = BlockTime::from_millis(export_request.get_block_time());
+        let deploy_item = export_request.take_deploy().try_into()?;
+        let protocol_version = export_request.take_protocol_version().into();
+        Ok(ExportReproBundleRequest::new(
+            parent_state_hash,
+            block_time,
+            deploy_item,
+            protocol_version,
+        ))
+    }
+}
+
+impl From<BundleReplayResult> for ReplayReproBundleResponse {
+    fn from(bundle_replay_result: BundleReplayResult) -> Self {
+        let mut pb_response = ReplayReproBundleResponse::new();
+        match bundle_replay_result {
+            BundleReplayResult::Replayed(replayed_deploy) => {
+                pb_response.set_success(replayed_deploy.into())
+            }
+            BundleReplayResult::InvalidProof(key) => pb_response.set_invalid_proof(key.into()),
+            BundleReplayResult::EffectsMismatch { recorded, replayed } => {
+                let mut effects_mismatch = ReplayReproBundleResponse_EffectsMismatch::new();
+                effects_mismatch.set_recorded_effects_hash(recorded.to_vec());
+                effects_mismatch.set_replayed_effects_hash(replayed.to_vec());
+                pb_response.set_effects_mismatch(effects_mismatch);
+            }
+        }
+        pb_response
+    }
+}
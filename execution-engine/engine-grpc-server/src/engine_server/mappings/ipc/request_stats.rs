@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+use engine_core::engine_state::execution_stats::RequestStats;
+
+use crate::engine_server::ipc::{
+    self, ExecuteRequestStats_PhaseTimes, ExecuteRequestStats_SlowestDeploy,
+};
+
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros() as u64
+}
+
+impl From<RequestStats> for ipc::ExecuteRequestStats {
+    fn from(request_stats: RequestStats) -> Self {
+        let mut pb_phase_times = ExecuteRequestStats_PhaseTimes::new();
+        let phase_times = request_stats.phase_times;
+        pb_phase_times.set_preprocess_micros(micros(phase_times.preprocess));
+        pb_phase_times.set_payment_micros(micros(phase_times.payment));
+        pb_phase_times.set_session_micros(micros(phase_times.session));
+        pb_phase_times.set_finalization_micros(micros(phase_times.finalization));
+        pb_phase_times
+            .set_protobuf_conversion_micros(micros(request_stats.protobuf_conversion_time));
+
+        let mut pb_request_stats = ipc::ExecuteRequestStats::new();
+        pb_request_stats.set_total_micros(micros(request_stats.total_time));
+        pb_request_stats.set_phase_times(pb_phase_times);
+        pb_request_stats.set_deploy_count(request_stats.deploy_count);
+        pb_request_stats.set_total_gas(request_stats.total_gas.value().into());
+        if let Some((deploy_hash, duration)) = request_stats.slowest_deploy {
+            let mut pb_slowest_deploy = ExecuteRequestStats_SlowestDeploy::new();
+            pb_slowest_deploy.set_deploy_hash(deploy_hash.as_bytes().to_vec());
+            pb_slowest_deploy.set_duration_micros(micros(duration));
+            pb_request_stats.set_slowest_deploy(pb_slowest_deploy);
+        }
+        pb_request_stats
+    }
+}
@@ -16,7 +16,8 @@ impl TryFrom<ipc::RunGenesisRequest> for RunGenesisRequest {
             hash.into(),
             run_genesis_request.take_protocol_version().into(),
             run_genesis_request.take_ee_config().try_into()?,
-        ))
+        )
+        .with_force_fresh_genesis(run_genesis_request.get_force_fresh_genesis()))
     }
 }
 
@@ -25,6 +26,7 @@ impl From<RunGenesisRequest> for ipc::RunGenesisRequest {
         let mut res = ipc::RunGenesisRequest::new();
         res.set_genesis_config_hash(run_genesis_request.genesis_config_hash().value().to_vec());
         res.set_protocol_version(run_genesis_request.protocol_version().into());
+        res.set_force_fresh_genesis(run_genesis_request.force_fresh_genesis());
         res.set_ee_config(run_genesis_request.take_ee_config().into());
         res
     }
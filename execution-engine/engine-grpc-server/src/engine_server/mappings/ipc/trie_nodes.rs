@@ -0,0 +1,116 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::trie_nodes::{TrieNodesRequest, MAX_TRIE_NODES_PER_REQUEST};
+use engine_shared::newtypes::{Blake2bHash, BLAKE2B_DIGEST_LENGTH};
+
+use crate::engine_server::{
+    ipc::{self, GetTrieNodesResponse_Node, GetTrieNodesResponse_Nodes},
+    mappings::MappingError,
+};
+
+fn node_hash(bytes: &[u8]) -> Result<Blake2bHash, MappingError> {
+    let length = bytes.len();
+    if length != BLAKE2B_DIGEST_LENGTH {
+        return Err(MappingError::InvalidStateHashLength {
+            expected: BLAKE2B_DIGEST_LENGTH,
+            actual: length,
+        });
+    }
+    bytes.try_into().map_err(|_| MappingError::TryFromSlice)
+}
+
+fn trie_nodes_request(hashes: Vec<Blake2bHash>) -> Result<TrieNodesRequest, MappingError> {
+    let actual = hashes.len();
+    TrieNodesRequest::new(hashes).ok_or(MappingError::TooManyTrieNodes {
+        max: MAX_TRIE_NODES_PER_REQUEST,
+        actual,
+    })
+}
+
+impl TryFrom<ipc::GetTrieNodeRequest> for TrieNodesRequest {
+    type Error = MappingError;
+
+    fn try_from(get_trie_node_request: ipc::GetTrieNodeRequest) -> Result<Self, Self::Error> {
+        let hash = node_hash(get_trie_node_request.get_node_hash())?;
+        trie_nodes_request(vec![hash])
+    }
+}
+
+impl TryFrom<ipc::GetTrieNodesRequest> for TrieNodesRequest {
+    type Error = MappingError;
+
+    fn try_from(get_trie_nodes_request: ipc::GetTrieNodesRequest) -> Result<Self, Self::Error> {
+        let hashes = get_trie_nodes_request
+            .get_node_hashes()
+            .iter()
+            .map(|bytes| node_hash(bytes))
+            .collect::<Result<Vec<_>, _>>()?;
+        trie_nodes_request(hashes)
+    }
+}
+
+/// Pairs the `nodes` read for `request` with the requested hashes, marking the missing ones as not
+/// found.
+pub(crate) fn trie_nodes(
+    request: &TrieNodesRequest,
+    nodes: Vec<Option<Vec<u8>>>,
+) -> GetTrieNodesResponse_Nodes {
+    let pb_nodes: Vec<GetTrieNodesResponse_Node> = request
+        .hashes()
+        .iter()
+        .zip(nodes)
+        .map(|(hash, maybe_node)| {
+            let mut pb_node = GetTrieNodesResponse_Node::new();
+            match maybe_node {
+                Some(node) => pb_node.set_node(node),
+                None => pb_node.mut_not_found().set_node_hash(hash.to_vec()),
+            }
+            pb_node
+        })
+        .collect();
+    let mut pb_nodes_message = GetTrieNodesResponse_Nodes::new();
+    pb_nodes_message.set_nodes(pb_nodes.into());
+    pb_nodes_message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_limit_hashes_per_request() {
+        let hash = vec![1; BLAKE2B_DIGEST_LENGTH];
+
+        let mut pb_request = ipc::GetTrieNodesRequest::new();
+        pb_request.set_node_hashes(vec![hash.clone(); MAX_TRIE_NODES_PER_REQUEST].into());
+        let request = TrieNodesRequest::try_from(pb_request).expect("should be within the limit");
+        assert_eq!(request.hashes().len(), MAX_TRIE_NODES_PER_REQUEST);
+
+        let mut pb_request = ipc::GetTrieNodesRequest::new();
+        pb_request.set_node_hashes(vec![hash; MAX_TRIE_NODES_PER_REQUEST + 1].into());
+        match TrieNodesRequest::try_from(pb_request) {
+            Err(MappingError::TooManyTrieNodes { max, actual }) => {
+                assert_eq!(max, MAX_TRIE_NODES_PER_REQUEST);
+                assert_eq!(actual, MAX_TRIE_NODES_PER_REQUEST + 1);
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn should_mark_missing_nodes_as_not_found() {
+        let found = Blake2bHash::new(&[1]);
+        let missing = Blake2bHash::new(&[2]);
+        let request = TrieNodesRequest::new(vec![found, missing]).unwrap();
+
+        let pb_nodes = trie_nodes(&request, vec![Some(vec![1, 2, 3]), None]);
+
+        let pb_nodes = pb_nodes.get_nodes();
+        assert_eq!(pb_nodes.len(), 2);
+        assert_eq!(pb_nodes[0].get_node(), &[1, 2, 3]);
+        assert_eq!(
+            pb_nodes[1].get_not_found().get_node_hash(),
+            missing.to_vec().as_slice()
+        );
+    }
+}
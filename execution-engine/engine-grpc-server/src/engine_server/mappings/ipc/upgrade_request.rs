@@ -1,6 +1,6 @@
 use std::convert::{TryFrom, TryInto};
 
-use engine_core::engine_state::upgrade::UpgradeConfig;
+use engine_core::engine_state::upgrade::{AuctionConfig, UpgradeConfig};
 use types::ProtocolVersion;
 
 use crate::engine_server::{ipc::UpgradeRequest, mappings::MappingError};
@@ -40,6 +40,39 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
         } else {
             Some(upgrade_point.get_activation_point().rank)
         };
+        let new_mint_code = upgrade_point.take_new_mint_code();
+        let new_mint_code = if new_mint_code.is_empty() {
+            None
+        } else {
+            Some(new_mint_code)
+        };
+        let new_pos_code = upgrade_point.take_new_pos_code();
+        let new_pos_code = if new_pos_code.is_empty() {
+            None
+        } else {
+            Some(new_pos_code)
+        };
+        let account_config = if !upgrade_point.has_new_account_config() {
+            None
+        } else {
+            Some(upgrade_point.take_new_account_config().try_into()?)
+        };
+        let auction_config = if !upgrade_point.has_new_auction_config() {
+            None
+        } else {
+            let mut pb_auction_config = upgrade_point.take_new_auction_config();
+            let installer_bytes = pb_auction_config.take_installer();
+            let installer_bytes = if installer_bytes.is_empty() {
+                None
+            } else {
+                Some(installer_bytes)
+            };
+            Some(AuctionConfig::new(
+                installer_bytes,
+                pb_auction_config.get_validator_slots(),
+                pb_auction_config.get_use_auction(),
+            ))
+        };
 
         Ok(UpgradeConfig::new(
             pre_state_hash,
@@ -49,6 +82,10 @@ impl TryFrom<UpgradeRequest> for UpgradeConfig {
             upgrade_installer_bytes,
             wasm_costs,
             activation_point,
+            new_mint_code,
+            new_pos_code,
+            account_config,
+            auction_config,
         ))
     }
 }
@@ -0,0 +1,66 @@
+use std::convert::{TryFrom, TryInto};
+
+use engine_core::engine_state::validate_deploy::{ValidateDeployRequest, ValidateDeployResult};
+
+use crate::engine_server::{
+    ipc::{self, ValidateDeployResponse_Verdict},
+    mappings::MappingError,
+};
+
+impl TryFrom<ipc::ValidateDeployRequest> for ValidateDeployRequest {
+    type Error = MappingError;
+
+    fn try_from(mut validate_request: ipc::ValidateDeployRequest) -> Result<Self, Self::Error> {
+        let deploy_item = validate_request.take_deploy_item().try_into()?;
+        let protocol_version = validate_request.take_protocol_version().into();
+        Ok(ValidateDeployRequest::new(deploy_item, protocol_version))
+    }
+}
+
+impl From<ValidateDeployResult> for ValidateDeployResponse_Verdict {
+    fn from(validate_result: ValidateDeployResult) -> Self {
+        let exit_code = validate_result.exit_code();
+        let mut pb_verdict = ValidateDeployResponse_Verdict::new();
+        pb_verdict.set_exit_code(exit_code.into());
+        pb_verdict.set_exit_code_detail(exit_code.code());
+        if let ValidateDeployResult::Invalid(error) = validate_result {
+            pb_verdict.set_message(error.to_string());
+        }
+        pb_verdict
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_core::engine_state::{
+        exit_code::{ExitCode, PreconditionReason},
+        validate_deploy::ValidateDeployResult,
+        Error,
+    };
+
+    use crate::engine_server::ipc::{DeployResult_ExitCode, ValidateDeployResponse_Verdict};
+
+    #[test]
+    fn valid_deploy_should_map_to_success() {
+        let pb_verdict = ValidateDeployResponse_Verdict::from(ValidateDeployResult::Valid);
+        assert_eq!(pb_verdict.get_exit_code(), DeployResult_ExitCode::SUCCESS);
+        assert_eq!(pb_verdict.get_exit_code_detail(), 0);
+        assert!(pb_verdict.get_message().is_empty());
+    }
+
+    #[test]
+    fn invalid_deploy_should_map_to_precondition() {
+        let pb_verdict = ValidateDeployResponse_Verdict::from(ValidateDeployResult::Invalid(
+            Error::Authorization,
+        ));
+        assert_eq!(
+            pb_verdict.get_exit_code(),
+            DeployResult_ExitCode::PRECONDITION
+        );
+        assert_eq!(
+            pb_verdict.get_exit_code_detail(),
+            ExitCode::Precondition(PreconditionReason::Authorization).code()
+        );
+        assert_eq!(pb_verdict.get_message(), Error::Authorization.to_string());
+    }
+}
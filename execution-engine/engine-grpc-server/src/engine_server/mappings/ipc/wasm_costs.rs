@@ -15,6 +15,17 @@ impl From<WasmCosts> for ChainSpec_CostTable_WasmCosts {
             max_stack_height: wasm_costs.max_stack_height,
             opcodes_mul: wasm_costs.opcodes_mul,
             opcodes_div: wasm_costs.opcodes_div,
+            transient_storage: wasm_costs.transient_storage,
+            max_transient_storage_size: wasm_costs.max_transient_storage_size,
+            max_transforms_per_deploy: wasm_costs.max_transforms_per_deploy,
+            max_effect_bytes_per_deploy: wasm_costs.max_effect_bytes_per_deploy,
+            max_session_return_value_bytes: wasm_costs.max_session_return_value_bytes,
+            string_byte: wasm_costs.string_byte,
+            max_string_length: wasm_costs.max_string_length,
+            max_revert_data_bytes: wasm_costs.max_revert_data_bytes,
+            base_deploy_cost: wasm_costs.base_deploy_cost,
+            rejected_deploy_cost: wasm_costs.rejected_deploy_cost,
+            strict_session_exports: wasm_costs.strict_session_exports,
             ..Default::default()
         }
     }
@@ -33,6 +44,17 @@ impl From<ChainSpec_CostTable_WasmCosts> for WasmCosts {
             max_stack_height: pb_wasm_costs.max_stack_height,
             opcodes_mul: pb_wasm_costs.opcodes_mul,
             opcodes_div: pb_wasm_costs.opcodes_div,
+            transient_storage: pb_wasm_costs.transient_storage,
+            max_transient_storage_size: pb_wasm_costs.max_transient_storage_size,
+            max_transforms_per_deploy: pb_wasm_costs.max_transforms_per_deploy,
+            max_effect_bytes_per_deploy: pb_wasm_costs.max_effect_bytes_per_deploy,
+            max_session_return_value_bytes: pb_wasm_costs.max_session_return_value_bytes,
+            string_byte: pb_wasm_costs.string_byte,
+            max_string_length: pb_wasm_costs.max_string_length,
+            max_revert_data_bytes: pb_wasm_costs.max_revert_data_bytes,
+            base_deploy_cost: pb_wasm_costs.base_deploy_cost,
+            rejected_deploy_cost: pb_wasm_costs.rejected_deploy_cost,
+            strict_session_exports: pb_wasm_costs.strict_session_exports,
         }
     }
 }
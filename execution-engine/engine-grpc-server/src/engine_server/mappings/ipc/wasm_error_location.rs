@@ -0,0 +1,34 @@
+use engine_wasm_prep::ErrorLocation;
+
+use crate::engine_server::{
+    ipc::{self, WasmErrorLocation_Index},
+    mappings,
+};
+
+fn index(value: u64) -> WasmErrorLocation_Index {
+    let mut pb_index = WasmErrorLocation_Index::new();
+    pb_index.set_value(value);
+    pb_index
+}
+
+impl From<ErrorLocation> for ipc::WasmErrorLocation {
+    fn from(location: ErrorLocation) -> Self {
+        let mut pb_location = ipc::WasmErrorLocation::new();
+        if let Some(section) = location.section {
+            pb_location.set_section(section.to_string());
+        }
+        if let Some(function_index) = location.function_index {
+            pb_location.set_function_index(index(function_index.into()));
+        }
+        if let Some(instruction_index) = location.instruction_index {
+            pb_location.set_instruction_index(index(instruction_index.into()));
+        }
+        if let Some(byte_offset) = location.byte_offset {
+            pb_location.set_byte_offset(index(mappings::usize_to_u64(byte_offset)));
+        }
+        if let Some(name) = location.name {
+            pb_location.set_name(name);
+        }
+        pb_location
+    }
+}
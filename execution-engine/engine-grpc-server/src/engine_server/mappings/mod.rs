@@ -5,7 +5,7 @@ mod state;
 mod transforms;
 
 use std::{
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     fmt::{self, Display, Formatter},
     string::ToString,
 };
@@ -13,6 +13,8 @@ use std::{
 use engine_core::{engine_state, DEPLOY_HASH_LENGTH};
 use types::account::ED25519_LENGTH;
 
+pub use ipc::BatchCommitEntries;
+pub(crate) use ipc::{canonical_bonds, trie_nodes};
 pub use transforms::TransformMap;
 
 /// Try to convert a `Vec<u8>` to a 32-byte array.
@@ -33,14 +35,33 @@ pub(crate) fn vec_to_array64(input: Vec<u8>, input_name: &str) -> Result<[u8; 64
     Ok(result)
 }
 
+/// Try to convert the numeric `value` of the Protobuf field `field_name` into a domain type,
+/// failing rather than truncating or wrapping if it's out of range.
+pub(crate) fn convert_number<T, U>(value: T, field_name: &str) -> Result<U, ParsingError>
+where
+    T: Copy + Display,
+    U: TryFrom<T>,
+{
+    U::try_from(value)
+        .map_err(|_| ParsingError(format!("{} of {} is out of range.", field_name, value)))
+}
+
+/// Converts a length or offset into a Protobuf `uint64`.
+pub(crate) fn usize_to_u64(value: usize) -> u64 {
+    // A `usize` is at most 64 bits wide on every supported target, so this never truncates.
+    value as u64
+}
+
 #[derive(Debug)]
 pub enum MappingError {
     InvalidStateHashLength { expected: usize, actual: usize },
     InvalidPublicKeyLength { expected: usize, actual: usize },
     InvalidDeployHashLength { expected: usize, actual: usize },
+    TooManyTrieNodes { max: usize, actual: usize },
     Parsing(ParsingError),
     InvalidStateHash(String),
     MissingPayload,
+    DuplicateArgName(String),
     TryFromSlice,
 }
 
@@ -69,7 +90,10 @@ impl From<MappingError> for engine_state::Error {
             MappingError::InvalidStateHashLength { expected, actual } => {
                 engine_state::Error::InvalidHashLength { expected, actual }
             }
-            _ => engine_state::Error::Deploy,
+            MappingError::InvalidPublicKeyLength { expected, actual } => {
+                engine_state::Error::InvalidPublicKeyLength { expected, actual }
+            }
+            error => engine_state::Error::InvalidDeployItem(error.to_string()),
         }
     }
 }
@@ -92,9 +116,15 @@ impl Display for MappingError {
                 "Invalid deploy hash length: expected {}, actual {}",
                 expected, actual
             ),
+            MappingError::TooManyTrieNodes { max, actual } => write!(
+                f,
+                "Too many trie nodes requested: at most {}, actual {}",
+                max, actual
+            ),
             MappingError::Parsing(ParsingError(message)) => write!(f, "Parsing error: {}", message),
             MappingError::InvalidStateHash(message) => write!(f, "Invalid hash: {}", message),
             MappingError::MissingPayload => write!(f, "Missing payload"),
+            MappingError::DuplicateArgName(name) => write!(f, "Duplicate arg name: {}", name),
             MappingError::TryFromSlice => write!(f, "Unable to convert from slice"),
         }
     }
@@ -113,6 +143,12 @@ impl<T: ToString> From<T> for ParsingError {
 pub mod test_utils {
     use std::{any, convert::TryFrom, fmt::Debug};
 
+    use proptest::{
+        prelude::{any, Just, Strategy},
+        prop_oneof,
+    };
+    use protobuf::Message;
+
     /// Checks that domain object `original` can be converted into a corresponding protobuf object
     /// and back, and that the conversions yield an equal object to `original`.
     pub fn protobuf_round_trip<T, U>(original: T)
@@ -131,11 +167,45 @@ pub mod test_utils {
         });
         assert_eq!(original, parsed);
     }
+
+    /// Checks that `pb_object` serializes to exactly the same bytes as `expected`, e.g. when one
+    /// was converted from a borrowed domain object and the other from an owned one.
+    pub fn assert_same_protobuf_bytes<M: Message>(pb_object: &M, expected: &M) {
+        let bytes = pb_object
+            .write_to_bytes()
+            .expect("Expected serializing protobuf object to succeed.");
+        let expected_bytes = expected
+            .write_to_bytes()
+            .expect("Expected serializing protobuf object to succeed.");
+        assert_eq!(bytes, expected_bytes);
+    }
+
+    /// Generates `u32`s, picking one of the boundary values `0`, `u32::max_value() - 1` and
+    /// `u32::max_value()` half of the time.
+    pub fn u32_with_boundaries() -> impl Strategy<Value = u32> {
+        prop_oneof![
+            1 => Just(0),
+            1 => Just(u32::max_value() - 1),
+            1 => Just(u32::max_value()),
+            3 => any::<u32>(),
+        ]
+    }
+
+    /// Generates `u64`s, picking one of the boundary values `0`, `u64::max_value() - 1` and
+    /// `u64::max_value()` half of the time.
+    pub fn u64_with_boundaries() -> impl Strategy<Value = u64> {
+        prop_oneof![
+            1 => Just(0),
+            1 => Just(u64::max_value() - 1),
+            1 => Just(u64::max_value()),
+            3 => any::<u64>(),
+        ]
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::vec_to_array;
+    use super::{convert_number, vec_to_array};
 
     #[test]
     fn vec_to_array_test() {
@@ -144,4 +214,19 @@ mod tests {
         assert!(vec_to_array(vec![1; 31], "").is_err());
         assert!(vec_to_array(vec![1; 33], "").is_err());
     }
+
+    #[test]
+    fn convert_number_test() {
+        assert_eq!(Ok(255u8), convert_number(255u32, ""));
+        assert_eq!(Ok(u64::max_value()), convert_number(u64::max_value(), ""));
+        assert!(convert_number::<u32, u8>(256, "").is_err());
+        assert!(convert_number::<i32, u8>(-1, "").is_err());
+        assert!(convert_number::<u64, i64>(u64::max_value(), "").is_err());
+
+        let error = convert_number::<u64, u32>(u64::max_value(), "page size").unwrap_err();
+        assert_eq!(
+            error.0,
+            "page size of 18446744073709551615 is out of range."
+        );
+    }
 }
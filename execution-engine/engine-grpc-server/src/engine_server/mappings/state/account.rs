@@ -7,7 +7,7 @@ use std::{
 use engine_shared::account::{Account, ActionThresholds, AssociatedKeys};
 use types::account::{PublicKey, Weight};
 
-use super::NamedKeyMap;
+use super::{named_keys_to_pb, NamedKeyMap};
 use crate::engine_server::{
     mappings::{self, ParsingError},
     state::{self, Account_AssociatedKey, NamedKey},
@@ -15,30 +15,48 @@ use crate::engine_server::{
 
 impl From<Account> for state::Account {
     fn from(mut account: Account) -> Self {
-        let mut pb_account = state::Account::new();
-
-        pb_account.set_public_key(account.public_key().as_bytes().to_vec());
-
         let named_keys = mem::replace(account.named_keys_mut(), BTreeMap::new());
         let pb_named_keys: Vec<NamedKey> = NamedKeyMap::new(named_keys).into();
+        let mut pb_account = account_without_named_keys(&account);
         pb_account.set_named_keys(pb_named_keys.into());
+        pb_account
+    }
+}
+
+impl From<&Account> for state::Account {
+    fn from(account: &Account) -> Self {
+        let mut pb_account = account_without_named_keys(account);
+        pb_account.set_named_keys(named_keys_to_pb(account.named_keys()).into());
+        pb_account
+    }
+}
 
-        pb_account.set_main_purse(account.main_purse().into());
+/// Converts every field of `account` other than its named keys, which are left for the caller to
+/// either move or copy.
+fn account_without_named_keys(account: &Account) -> state::Account {
+    let mut pb_account = state::Account::new();
 
-        let associated_keys: Vec<Account_AssociatedKey> =
-            account.get_associated_keys().map(Into::into).collect();
-        pb_account.set_associated_keys(associated_keys.into());
+    pb_account.set_public_key(account.public_key().as_bytes().to_vec());
 
-        {
-            let deployment = u32::from(account.action_thresholds().deployment().value());
-            let key_management = u32::from(account.action_thresholds().key_management().value());
-            let pb_action_thresholds = pb_account.mut_action_thresholds();
-            pb_action_thresholds.set_deployment_threshold(deployment);
-            pb_action_thresholds.set_key_management_threshold(key_management)
-        }
+    pb_account.set_main_purse(account.main_purse().into());
 
-        pb_account
+    let associated_keys: Vec<Account_AssociatedKey> =
+        account.get_associated_keys().map(Into::into).collect();
+    pb_account.set_associated_keys(associated_keys.into());
+
+    {
+        let deployment = u32::from(account.action_thresholds().deployment().value());
+        let key_management = u32::from(account.action_thresholds().key_management().value());
+        let pb_action_thresholds = pb_account.mut_action_thresholds();
+        pb_action_thresholds.set_deployment_threshold(deployment);
+        pb_action_thresholds.set_key_management_threshold(key_management)
     }
+
+    if let Some(authorization_contract) = account.authorization_contract() {
+        pb_account.set_authorization_contract(authorization_contract.into());
+    }
+
+    pb_account
 }
 
 impl TryFrom<state::Account> for Account {
@@ -91,13 +109,19 @@ impl TryFrom<state::Account> for Account {
             .map_err(ParsingError::from)?
         };
 
-        let account = Account::new(
+        let authorization_contract = match pb_account.authorization_contract.into_option() {
+            Some(pb_key) => Some(pb_key.try_into()?),
+            None => None,
+        };
+
+        let mut account = Account::new(
             PublicKey::ed25519_from(public_key),
             named_keys.into_inner(),
             main_purse,
             associated_keys,
             action_thresholds,
         );
+        account.set_authorization_contract(authorization_contract);
         Ok(account)
     }
 }
@@ -126,7 +150,7 @@ impl TryFrom<Account_AssociatedKey> for (PublicKey, Weight) {
     }
 }
 
-fn weight_from(value: u32, value_name: &str) -> Result<Weight, ParsingError> {
+pub(crate) fn weight_from(value: u32, value_name: &str) -> Result<Weight, ParsingError> {
     let weight = u8::try_from(value).map_err(|_| {
         ParsingError(format!(
             "Unable to convert {} to u8 while parsing {}",
@@ -141,6 +165,7 @@ mod tests {
     use proptest::proptest;
 
     use engine_shared::account::gens;
+    use types::gens::{public_key_arb, weight_arb};
 
     use super::*;
     use crate::engine_server::mappings::test_utils;
@@ -150,5 +175,19 @@ mod tests {
         fn round_trip(account in gens::account_arb()) {
             test_utils::protobuf_round_trip::<Account, state::Account>(account);
         }
+
+        #[test]
+        fn borrowed_conversion_matches(account in gens::account_arb()) {
+            let pb_account = state::Account::from(&account);
+            test_utils::assert_same_protobuf_bytes(&pb_account, &account.into());
+        }
+
+        #[test]
+        fn associated_key_round_trip(public_key in public_key_arb(), weight in weight_arb()) {
+            let pb_associated_key = Account_AssociatedKey::from((&public_key, &weight));
+            let parsed = <(PublicKey, Weight)>::try_from(pb_associated_key)
+                .expect("Expected transforming Account_AssociatedKey to succeed.");
+            assert_eq!((public_key, weight), parsed);
+        }
     }
 }
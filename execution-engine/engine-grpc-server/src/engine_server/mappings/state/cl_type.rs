@@ -142,3 +142,20 @@ impl TryFrom<state::CLType> for CLType {
         Ok(cl_type)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::gens;
+
+    use super::*;
+    use crate::engine_server::mappings::test_utils;
+
+    proptest! {
+        #[test]
+        fn round_trip(cl_type in gens::cl_type_arb()) {
+            test_utils::protobuf_round_trip::<CLType, state::CLType>(cl_type);
+        }
+    }
+}
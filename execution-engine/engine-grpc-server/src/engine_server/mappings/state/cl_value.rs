@@ -16,6 +16,16 @@ impl From<CLValue> for state::CLValue {
     }
 }
 
+impl From<&CLValue> for state::CLValue {
+    fn from(cl_value: &CLValue) -> Self {
+        let mut pb_value = state::CLValue::new();
+        pb_value.set_cl_type(cl_value.cl_type().clone().into());
+        pb_value.set_serialized_value(cl_value.inner_bytes().clone());
+
+        pb_value
+    }
+}
+
 impl TryFrom<state::CLValue> for CLValue {
     type Error = ParsingError;
 
@@ -39,5 +49,11 @@ mod tests {
         fn round_trip(cl_value in gens::cl_value_arb()) {
             test_utils::protobuf_round_trip::<CLValue, state::CLValue>(cl_value);
         }
+
+        #[test]
+        fn borrowed_conversion_matches(cl_value in gens::cl_value_arb()) {
+            let pb_value = state::CLValue::from(&cl_value);
+            test_utils::assert_same_protobuf_bytes(&pb_value, &cl_value.into());
+        }
     }
 }
@@ -2,7 +2,7 @@ use std::convert::{TryFrom, TryInto};
 
 use engine_shared::contract::Contract;
 
-use super::NamedKeyMap;
+use super::{named_keys_to_pb, NamedKeyMap};
 use crate::engine_server::{
     mappings::ParsingError,
     state::{self, NamedKey},
@@ -20,6 +20,16 @@ impl From<Contract> for state::Contract {
     }
 }
 
+impl From<&Contract> for state::Contract {
+    fn from(contract: &Contract) -> Self {
+        let mut pb_contract = state::Contract::new();
+        pb_contract.set_body(contract.bytes().to_vec());
+        pb_contract.set_named_keys(named_keys_to_pb(contract.named_keys()).into());
+        pb_contract.set_protocol_version(contract.protocol_version().into());
+        pb_contract
+    }
+}
+
 impl TryFrom<state::Contract> for Contract {
     type Error = ParsingError;
 
@@ -45,5 +55,11 @@ mod tests {
         fn round_trip(contract in gens::contract_arb()) {
             test_utils::protobuf_round_trip::<Contract, state::Contract>(contract);
         }
+
+        #[test]
+        fn borrowed_conversion_matches(contract in gens::contract_arb()) {
+            let pb_contract = state::Contract::from(&contract);
+            test_utils::assert_same_protobuf_bytes(&pb_contract, &contract.into());
+        }
     }
 }
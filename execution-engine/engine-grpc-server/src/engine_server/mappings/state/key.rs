@@ -1,7 +1,8 @@
 use std::convert::{TryFrom, TryInto};
 
 use types::{
-    account::PublicKey, Key, BLAKE2B_DIGEST_LENGTH, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH,
+    account::PublicKey, ContractHash, Key, BLAKE2B_DIGEST_LENGTH, KEY_LOCAL_LENGTH,
+    KEY_LOCAL_SEED_LENGTH,
 };
 
 use crate::engine_server::{
@@ -19,9 +20,7 @@ impl From<Key> for state::Key {
                 pb_key.set_address(pb_account);
             }
             Key::Hash(hash) => {
-                let mut pb_hash = Key_Hash::new();
-                pb_hash.set_hash(hash.to_vec());
-                pb_key.set_hash(pb_hash);
+                pb_key.set_hash(ContractHash::new(hash).into());
             }
             Key::URef(uref) => {
                 pb_key.set_uref(uref.into());
@@ -52,10 +51,7 @@ impl TryFrom<state::Key> for Key {
                 let account = mappings::vec_to_array(pb_account.account, "Protobuf Key::Account")?;
                 Key::Account(PublicKey::ed25519_from(account))
             }
-            Key_oneof_value::hash(pb_hash) => {
-                let hash = mappings::vec_to_array(pb_hash.hash, "Protobuf Key::Hash")?;
-                Key::Hash(hash)
-            }
+            Key_oneof_value::hash(pb_hash) => ContractHash::try_from(pb_hash)?.into(),
             Key_oneof_value::uref(pb_uref) => {
                 let uref = pb_uref.try_into()?;
                 Key::URef(uref)
@@ -73,6 +69,23 @@ impl TryFrom<state::Key> for Key {
     }
 }
 
+impl From<ContractHash> for Key_Hash {
+    fn from(contract_hash: ContractHash) -> Self {
+        let mut pb_hash = Key_Hash::new();
+        pb_hash.set_hash(contract_hash.as_bytes().to_vec());
+        pb_hash
+    }
+}
+
+impl TryFrom<Key_Hash> for ContractHash {
+    type Error = ParsingError;
+
+    fn try_from(pb_hash: Key_Hash) -> Result<Self, Self::Error> {
+        let hash = mappings::vec_to_array(pb_hash.hash, "Protobuf Key::Hash")?;
+        Ok(ContractHash::new(hash))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use proptest::proptest;
@@ -87,5 +100,17 @@ mod tests {
         fn round_trip(key in gens::key_arb()) {
             test_utils::protobuf_round_trip::<Key, state::Key>(key);
         }
+
+        #[test]
+        fn contract_hash_round_trip(contract_hash in gens::contract_hash_arb()) {
+            test_utils::protobuf_round_trip::<ContractHash, Key_Hash>(contract_hash);
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse_short_contract_hash() {
+        let mut pb_hash = Key_Hash::new();
+        pb_hash.set_hash(vec![1; 31]);
+        assert!(ContractHash::try_from(pb_hash).is_err());
     }
 }
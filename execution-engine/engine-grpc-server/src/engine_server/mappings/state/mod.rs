@@ -9,7 +9,9 @@ mod contract;
 mod key;
 mod named_key;
 mod protocol_version;
+mod purse_id;
 mod stored_value;
 mod uref;
 
-pub(crate) use named_key::NamedKeyMap;
+pub(crate) use account::weight_from;
+pub(crate) use named_key::{named_keys_to_pb, NamedKeyMap};
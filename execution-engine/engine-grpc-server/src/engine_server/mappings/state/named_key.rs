@@ -16,6 +16,12 @@ impl From<(String, Key)> for NamedKey {
     }
 }
 
+impl From<(&String, &Key)> for NamedKey {
+    fn from((name, key): (&String, &Key)) -> Self {
+        (name.clone(), *key).into()
+    }
+}
+
 impl TryFrom<NamedKey> for (String, Key) {
     type Error = ParsingError;
 
@@ -26,6 +32,11 @@ impl TryFrom<NamedKey> for (String, Key) {
     }
 }
 
+/// Converts the borrowed named keys of an account or contract, without taking ownership of them.
+pub(crate) fn named_keys_to_pb(named_keys: &BTreeMap<String, Key>) -> Vec<NamedKey> {
+    named_keys.iter().map(Into::into).collect()
+}
+
 /// Thin wrapper to allow us to implement `From` and `TryFrom` helpers to convert to and from
 /// `BTreeMap<String, Key>` and `Vec<NamedKey>`.
 #[derive(Clone, PartialEq, Debug)]
@@ -76,6 +87,12 @@ mod tests {
             test_utils::protobuf_round_trip::<(String, Key), NamedKey>((string, key));
         }
 
+        #[test]
+        fn borrowed_conversion_matches(string in "\\PC*", key in gens::key_arb()) {
+            let pb_named_key = NamedKey::from((&string, &key));
+            test_utils::assert_same_protobuf_bytes(&pb_named_key, &NamedKey::from((string, key)));
+        }
+
         #[test]
         fn map_round_trip(named_keys in gens::named_keys_arb(10)) {
             let named_key_map = NamedKeyMap(named_keys);
@@ -26,14 +26,18 @@ impl From<state::ProtocolVersion> for ProtocolVersion {
 
 #[cfg(test)]
 mod tests {
-    use proptest::{prelude::any, proptest};
+    use proptest::proptest;
 
     use super::*;
     use crate::engine_server::mappings::test_utils;
 
     proptest! {
         #[test]
-        fn round_trip((major, minor, patch) in any::<(u32, u32, u32)>()) {
+        fn round_trip(
+            major in test_utils::u32_with_boundaries(),
+            minor in test_utils::u32_with_boundaries(),
+            patch in test_utils::u32_with_boundaries()
+        ) {
             let protocol_version = ProtocolVersion::from_parts(major, minor, patch);
             test_utils::protobuf_round_trip::<ProtocolVersion, state::ProtocolVersion>(
                 protocol_version,
@@ -0,0 +1,54 @@
+use std::convert::{TryFrom, TryInto};
+
+use types::URef;
+
+use crate::engine_server::{mappings::ParsingError, state};
+
+impl From<URef> for state::PurseId {
+    fn from(purse: URef) -> Self {
+        let mut pb_purse_id = state::PurseId::new();
+        pb_purse_id.set_uref(purse.into());
+        pb_purse_id
+    }
+}
+
+impl TryFrom<state::PurseId> for URef {
+    type Error = ParsingError;
+
+    fn try_from(mut pb_purse_id: state::PurseId) -> Result<Self, Self::Error> {
+        if !pb_purse_id.has_uref() {
+            return Err(ParsingError::from("Protobuf PurseId has no URef"));
+        }
+        // Rejects access rights which aren't defined by the Protobuf enum.
+        pb_purse_id.take_uref().try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::{gens, UREF_ADDR_LENGTH};
+
+    use super::*;
+    use crate::engine_server::{mappings::test_utils, state::Key_URef};
+
+    proptest! {
+        #[test]
+        fn round_trip(purse in gens::uref_arb()) {
+            test_utils::protobuf_round_trip::<URef, state::PurseId>(purse);
+        }
+    }
+
+    #[test]
+    fn should_fail_to_parse() {
+        let empty_pb_purse_id = state::PurseId::new();
+        assert!(URef::try_from(empty_pb_purse_id).is_err());
+
+        let mut pb_uref_invalid_addr = Key_URef::new();
+        pb_uref_invalid_addr.set_uref(vec![1; UREF_ADDR_LENGTH - 1]);
+        let mut pb_purse_id = state::PurseId::new();
+        pb_purse_id.set_uref(pb_uref_invalid_addr);
+        assert!(URef::try_from(pb_purse_id).is_err());
+    }
+}
@@ -21,6 +21,20 @@ impl From<StoredValue> for state::StoredValue {
     }
 }
 
+impl From<&StoredValue> for state::StoredValue {
+    fn from(value: &StoredValue) -> Self {
+        let mut pb_value = state::StoredValue::new();
+
+        match value {
+            StoredValue::CLValue(cl_value) => pb_value.set_cl_value(cl_value.into()),
+            StoredValue::Account(account) => pb_value.set_account(account.into()),
+            StoredValue::Contract(contract) => pb_value.set_contract(contract.into()),
+        }
+
+        pb_value
+    }
+}
+
 impl TryFrom<state::StoredValue> for StoredValue {
     type Error = ParsingError;
 
@@ -59,5 +73,11 @@ mod tests {
         fn round_trip(value in gens::stored_value_arb()) {
             test_utils::protobuf_round_trip::<StoredValue, state::StoredValue>(value);
         }
+
+        #[test]
+        fn borrowed_conversion_matches(value in gens::stored_value_arb()) {
+            let pb_value = state::StoredValue::from(&value);
+            test_utils::assert_same_protobuf_bytes(&pb_value, &value.into());
+        }
     }
 }
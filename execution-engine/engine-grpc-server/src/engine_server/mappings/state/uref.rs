@@ -1,5 +1,7 @@
 use std::convert::TryFrom;
 
+use protobuf::{Message, ProtobufEnum};
+
 use types::{AccessRights, URef};
 
 use crate::engine_server::{
@@ -7,19 +9,14 @@ use crate::engine_server::{
     state::{Key_URef, Key_URef_AccessRights},
 };
 
+/// The field number of `access_rights` in the `Key.URef` Protobuf message.
+const ACCESS_RIGHTS_FIELD_NUMBER: u32 = 2;
+
 impl From<AccessRights> for Key_URef_AccessRights {
     fn from(access_rights: AccessRights) -> Self {
-        match access_rights {
-            AccessRights::NONE => Key_URef_AccessRights::NONE,
-            AccessRights::READ => Key_URef_AccessRights::READ,
-            AccessRights::WRITE => Key_URef_AccessRights::WRITE,
-            AccessRights::ADD => Key_URef_AccessRights::ADD,
-            AccessRights::READ_ADD => Key_URef_AccessRights::READ_ADD,
-            AccessRights::READ_WRITE => Key_URef_AccessRights::READ_WRITE,
-            AccessRights::ADD_WRITE => Key_URef_AccessRights::ADD_WRITE,
-            AccessRights::READ_ADD_WRITE => Key_URef_AccessRights::READ_ADD_WRITE,
-            _ => Key_URef_AccessRights::NONE,
-        }
+        // The numeric values of the Protobuf enum are the bits of the domain `AccessRights`.
+        Key_URef_AccessRights::from_i32(access_rights.bits().into())
+            .expect("access rights never have reserved bits set")
     }
 }
 
@@ -37,18 +34,24 @@ impl TryFrom<Key_URef> for URef {
     type Error = ParsingError;
 
     fn try_from(pb_uref: Key_URef) -> Result<Self, Self::Error> {
+        // An access rights value not defined by the enum is parsed as the default `NONE` and kept
+        // as an unknown field, so it has to be rejected explicitly.
+        if pb_uref
+            .get_unknown_fields()
+            .get(ACCESS_RIGHTS_FIELD_NUMBER)
+            .is_some()
+        {
+            return Err(ParsingError::from(
+                "Protobuf URef has non-canonical access rights",
+            ));
+        }
+
         let addr = mappings::vec_to_array(pb_uref.uref, "Protobuf URef addr")?;
 
-        let access_rights = match pb_uref.access_rights {
-            Key_URef_AccessRights::NONE => AccessRights::NONE,
-            Key_URef_AccessRights::READ => AccessRights::READ,
-            Key_URef_AccessRights::WRITE => AccessRights::WRITE,
-            Key_URef_AccessRights::ADD => AccessRights::ADD,
-            Key_URef_AccessRights::READ_ADD => AccessRights::READ_ADD,
-            Key_URef_AccessRights::READ_WRITE => AccessRights::READ_WRITE,
-            Key_URef_AccessRights::ADD_WRITE => AccessRights::ADD_WRITE,
-            Key_URef_AccessRights::READ_ADD_WRITE => AccessRights::READ_ADD_WRITE,
-        };
+        let bits =
+            mappings::convert_number(pb_uref.access_rights.value(), "Protobuf URef access rights")?;
+        let access_rights = AccessRights::from_bits(bits)
+            .ok_or_else(|| ParsingError::from("Protobuf URef has non-canonical access rights"))?;
 
         let uref = URef::new(addr, access_rights);
 
@@ -58,11 +61,47 @@ impl TryFrom<Key_URef> for URef {
 
 #[cfg(test)]
 mod tests {
-    use types::UREF_ADDR_LENGTH;
+    use types::{ACCESS_RIGHTS_RESERVED_BITS, UREF_ADDR_LENGTH};
 
     use super::*;
     use crate::engine_server::mappings::test_utils;
 
+    /// Returns a Protobuf-encoded URef with the given raw `access_rights` value.
+    fn encode_pb_uref(addr: [u8; UREF_ADDR_LENGTH], access_rights: u8) -> Vec<u8> {
+        let mut pb_uref = Key_URef::new();
+        pb_uref.set_uref(addr.to_vec());
+        let mut bytes = pb_uref.write_to_bytes().unwrap();
+        // Field 2 with wire type 0 (varint), followed by the value as a varint.
+        bytes.push(u8::try_from(ACCESS_RIGHTS_FIELD_NUMBER << 3).unwrap());
+        if access_rights < 0x80 {
+            bytes.push(access_rights);
+        } else {
+            bytes.push(access_rights | 0x80);
+            bytes.push(access_rights >> 7);
+        }
+        bytes
+    }
+
+    #[test]
+    fn should_only_parse_canonical_access_rights() {
+        let addr: [u8; UREF_ADDR_LENGTH] = rand::random();
+        for bits in 0..=u8::max_value() {
+            let pb_uref: Key_URef = protobuf::parse_from_bytes(&encode_pb_uref(addr, bits))
+                .expect("should decode Protobuf");
+            match URef::try_from(pb_uref) {
+                Ok(uref) => {
+                    assert_eq!(bits & ACCESS_RIGHTS_RESERVED_BITS, 0);
+                    assert_eq!(uref.addr(), addr);
+                    assert_eq!(uref.access_rights().bits(), bits);
+                    // The Protobuf and bytesrepr encodings agree on the rights bits.
+                    let pb_access_rights: Key_URef_AccessRights = uref.access_rights().into();
+                    assert_eq!(pb_access_rights.value(), i32::from(bits));
+                }
+                Err(_) => assert_ne!(bits & ACCESS_RIGHTS_RESERVED_BITS, 0),
+            }
+        }
+    }
+
     #[test]
     fn round_trip() {
         for access_rights in &[
@@ -76,6 +115,9 @@ mod tests {
         ] {
             let uref = URef::new(rand::random(), *access_rights);
             test_utils::protobuf_round_trip::<URef, Key_URef>(uref);
+
+            let public_uref = uref.with_access_rights(*access_rights | AccessRights::PUBLIC_READ);
+            test_utils::protobuf_round_trip::<URef, Key_URef>(public_uref);
         }
 
         let uref = URef::new(rand::random(), AccessRights::READ).remove_access_rights();
@@ -7,40 +7,69 @@ use engine_shared::{
 use types::{CLType, CLValue, U128, U256, U512};
 
 use crate::engine_server::{
-    mappings::{state::NamedKeyMap, ParsingError},
+    mappings::{
+        state::{named_keys_to_pb, NamedKeyMap},
+        ParsingError,
+    },
     state::NamedKey,
     transforms::{self, Transform_oneof_transform_instance},
 };
 
 impl From<Transform> for transforms::Transform {
     fn from(transform: Transform) -> Self {
+        match transform {
+            // the written value and the added keys are moved rather than copied
+            Transform::Write(value) => {
+                let mut pb_transform = transforms::Transform::new();
+                pb_transform.mut_write().set_value(value.into());
+                pb_transform
+            }
+            Transform::AddKeys(keys_map) => {
+                let mut pb_transform = transforms::Transform::new();
+                let pb_named_keys: Vec<NamedKey> = NamedKeyMap::new(keys_map).into();
+                pb_transform.mut_add_keys().set_value(pb_named_keys.into());
+                pb_transform
+            }
+            other => (&other).into(),
+        }
+    }
+}
+
+impl From<&Transform> for transforms::Transform {
+    fn from(transform: &Transform) -> Self {
         let mut pb_transform = transforms::Transform::new();
         match transform {
             Transform::Identity => {
                 pb_transform.set_identity(Default::default());
             }
             Transform::AddInt32(i) => {
-                pb_transform.mut_add_i32().set_value(i);
+                pb_transform.mut_add_i32().set_value(*i);
             }
             Transform::AddUInt64(u) => {
-                pb_transform.mut_add_u64().set_value(u);
+                pb_transform.mut_add_u64().set_value(*u);
             }
             Transform::Write(value) => {
                 pb_transform.mut_write().set_value(value.into());
             }
             Transform::AddKeys(keys_map) => {
-                let pb_named_keys: Vec<NamedKey> = NamedKeyMap::new(keys_map).into();
-                pb_transform.mut_add_keys().set_value(pb_named_keys.into());
+                pb_transform
+                    .mut_add_keys()
+                    .set_value(named_keys_to_pb(keys_map).into());
+            }
+            Transform::Failure(transform_error) => {
+                pb_transform.set_failure(transform_error.clone().into())
             }
-            Transform::Failure(transform_error) => pb_transform.set_failure(transform_error.into()),
             Transform::AddUInt128(uint128) => {
-                pb_transform.mut_add_big_int().set_value(uint128.into());
+                pb_transform.mut_add_big_int().set_value((*uint128).into());
             }
             Transform::AddUInt256(uint256) => {
-                pb_transform.mut_add_big_int().set_value(uint256.into());
+                pb_transform.mut_add_big_int().set_value((*uint256).into());
             }
             Transform::AddUInt512(uint512) => {
-                pb_transform.mut_add_big_int().set_value(uint512.into());
+                pb_transform.mut_add_big_int().set_value((*uint512).into());
+            }
+            Transform::Prune => {
+                pb_transform.set_prune(Default::default());
             }
         };
         pb_transform
@@ -99,6 +128,7 @@ impl TryFrom<transforms::Transform> for Transform {
                 let error = TransformError::try_from(pb_failure)?;
                 Transform::Failure(error)
             }
+            Transform_oneof_transform_instance::prune(_) => Transform::Prune,
         };
         Ok(transform)
     }
@@ -118,5 +148,11 @@ mod tests {
         fn round_trip(transform in gens::transform_arb()) {
             test_utils::protobuf_round_trip::<Transform, transforms::Transform>(transform);
         }
+
+        #[test]
+        fn borrowed_conversion_matches(transform in gens::transform_arb()) {
+            let pb_transform = transforms::Transform::from(&transform);
+            test_utils::assert_same_protobuf_bytes(&pb_transform, &transform.into());
+        }
     }
 }
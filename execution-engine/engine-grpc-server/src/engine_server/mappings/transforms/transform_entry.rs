@@ -14,6 +14,15 @@ impl From<(Key, Transform)> for TransformEntry {
     }
 }
 
+impl From<(&Key, &Transform)> for TransformEntry {
+    fn from((key, transform): (&Key, &Transform)) -> Self {
+        let mut pb_transform_entry = TransformEntry::new();
+        pb_transform_entry.set_key((*key).into());
+        pb_transform_entry.set_transform(transform.into());
+        pb_transform_entry
+    }
+}
+
 impl TryFrom<TransformEntry> for (Key, Transform) {
     type Error = ParsingError;
 
@@ -51,5 +60,14 @@ mod tests {
         ) {
             test_utils::protobuf_round_trip::<(Key, Transform), TransformEntry>((key, transform));
         }
+
+        #[test]
+        fn borrowed_conversion_matches(
+            key in types::gens::key_arb(),
+            transform in transform::gens::transform_arb()
+        ) {
+            let pb_transform_entry = TransformEntry::from((&key, &transform));
+            test_utils::assert_same_protobuf_bytes(&pb_transform_entry, &(key, transform).into());
+        }
     }
 }
@@ -14,59 +14,131 @@ include!(concat!(
     env!("OUT_DIR"),
     "/../../../../generated_protobuf/transforms.rs"
 ));
+pub mod admission;
 pub mod mappings;
+pub mod socket_path;
 
 use std::{
+    cmp,
     collections::BTreeMap,
     convert::{TryFrom, TryInto},
     fmt::Debug,
-    io::ErrorKind,
-    iter::FromIterator,
     marker::{Send, Sync},
-    time::Instant,
+    path::Path,
+    thread,
+    time::{Duration, Instant},
 };
 
-use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse};
+use futures::{sync::mpsc, Stream};
+use grpc::{Error as GrpcError, RequestOptions, ServerBuilder, SingleResponse, StreamingResponse};
 use log::{info, warn, Level};
 
 use engine_core::engine_state::{
+    balance::{BalanceError, BalanceRequest},
     execute_request::ExecuteRequest,
-    genesis::GenesisResult,
-    query::{QueryRequest, QueryResult},
+    genesis::{GenesisConfig, GenesisResult},
+    list_keys::{ListKeysRequest, ListKeysResult},
+    list_roots::ListRootsRequest,
+    query::{MultiRootQueryRequest, QueryRequest, QueryResult},
+    replay::{ReplayRequest, ReplayResult},
+    repro_bundle::{ExportReproBundleRequest, ReproBundle},
     run_genesis_request::RunGenesisRequest,
+    trie_nodes::TrieNodesRequest,
     upgrade::{UpgradeConfig, UpgradeResult},
+    validate_deploy::ValidateDeployRequest,
     EngineState, Error as EngineError,
 };
 use engine_shared::{
-    logging::{self, log_duration},
+    logging::{self, log_metric},
     newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_storage::{
+    global_state::{
+        durability::Durability, storage_timer, BatchCommitResult, CommitResult, StateProvider,
+    },
+    store_metadata_store::STORE_SCHEMA_VERSION,
+};
+use types::{
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion,
 };
-use engine_storage::global_state::{CommitResult, StateProvider};
-use types::{bytesrepr::ToBytes, ProtocolVersion};
 
 use self::{
     ipc::{
-        BidStateRequest, BidStateResponse, CommitRequest, CommitResponse, DistributeRewardsRequest,
-        DistributeRewardsResponse, ExecuteResponse, GenesisResponse, QueryResponse, SlashRequest,
-        SlashResponse, UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        BalanceResponse, BatchCommitRequest, BatchCommitResponse, BidStateRequest,
+        BidStateResponse, BuildStateResponse, ChainspecFingerprintRequest,
+        ChainspecFingerprintResponse, CommitRequest, CommitResponse, DeployResult,
+        DeployResultChunk, DistributeRewardsRequest, DistributeRewardsResponse, ExecuteResponse,
+        ExecuteStreamSummary_Counts, ExportReproBundleResponse, GenesisResponse,
+        GetTrieNodeRequest, GetTrieNodeResponse, GetTrieNodesRequest, GetTrieNodesResponse,
+        GetValidatorWeightsRequest, GetValidatorWeightsResponse, ListKeysResponse,
+        ListRootsResponse, MultiRootQueryResponse, MultiRootQueryResponse_Result,
+        MultiRootQueryResponse_Results, PreviewUpgradeRequest, PreviewUpgradeResponse,
+        QueryResponse, QueryResponse_ValueTooLarge, ReplayReproBundleResponse, ReplayResponse,
+        RootNotFound, ServerInfoRequest, ServerInfoResponse, SlashRequest, SlashResponse,
+        UnbondPayoutRequest, UnbondPayoutResponse, UpgradeRequest, UpgradeResponse,
+        ValidateDeployResponse,
     },
     ipc_grpc::{ExecutionEngineService, ExecutionEngineServiceServer},
-    mappings::{ParsingError, TransformMap},
+    mappings::{self, BatchCommitEntries, ParsingError, TransformMap},
+    socket_path::{ServerStartError, SocketPathState},
 };
 
 const METRIC_DURATION_COMMIT: &str = "commit_duration";
+const METRIC_DURATION_BATCH_COMMIT: &str = "batch_commit_duration";
 const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_EXEC_STREAM: &str = "exec_stream_duration";
 const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_MULTI_ROOT_QUERY: &str = "multi_root_query_duration";
+const METRIC_DURATION_LIST_KEYS: &str = "list_keys_duration";
+const METRIC_DURATION_BALANCE: &str = "balance_duration";
+const METRIC_DURATION_LIST_ROOTS: &str = "list_roots_duration";
+const METRIC_DURATION_GET_TRIE_NODE: &str = "get_trie_node_duration";
+const METRIC_DURATION_GET_TRIE_NODES: &str = "get_trie_nodes_duration";
+const METRIC_DURATION_BUILD_STATE: &str = "build_state_duration";
+const METRIC_DURATION_REPLAY: &str = "replay_duration";
+const METRIC_DURATION_EXPORT_REPRO_BUNDLE: &str = "export_repro_bundle_duration";
+const METRIC_DURATION_REPLAY_REPRO_BUNDLE: &str = "replay_repro_bundle_duration";
+const METRIC_DURATION_VALIDATE_DEPLOY: &str = "validate_deploy_duration";
 const METRIC_DURATION_GENESIS: &str = "genesis_duration";
+const METRIC_DURATION_CHAINSPEC_FINGERPRINT: &str = "chainspec_fingerprint_duration";
 const METRIC_DURATION_UPGRADE: &str = "upgrade_duration";
+const METRIC_DURATION_PREVIEW_UPGRADE: &str = "preview_upgrade_duration";
+const METRIC_DURATION_GET_VALIDATOR_WEIGHTS: &str = "get_validator_weights_duration";
+const METRIC_DURATION_SERVER_INFO: &str = "server_info_duration";
+const METRIC_DURATION_STORAGE: &str = "storage_duration";
+
+const METRIC_QUERY_CACHE_HITS: &str = "query_cache_hits";
+const METRIC_QUERY_CACHE_MISSES: &str = "query_cache_misses";
+const METRIC_QUERY_CACHE_WEIGHT: &str = "query_cache_weight_bytes";
+const GAUGE_METRIC_KEY: &str = "gauge";
 
 const TAG_RESPONSE_COMMIT: &str = "commit_response";
+const TAG_RESPONSE_BATCH_COMMIT: &str = "batch_commit_response";
 const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_EXEC_STREAM: &str = "exec_stream_response";
 const TAG_RESPONSE_QUERY: &str = "query_response";
+const TAG_RESPONSE_MULTI_ROOT_QUERY: &str = "multi_root_query_response";
+const TAG_RESPONSE_LIST_KEYS: &str = "list_keys_response";
+const TAG_RESPONSE_BALANCE: &str = "balance_response";
+const TAG_RESPONSE_LIST_ROOTS: &str = "list_roots_response";
+const TAG_RESPONSE_GET_TRIE_NODE: &str = "get_trie_node_response";
+const TAG_RESPONSE_GET_TRIE_NODES: &str = "get_trie_nodes_response";
+const TAG_RESPONSE_BUILD_STATE: &str = "build_state_response";
+const TAG_RESPONSE_REPLAY: &str = "replay_response";
+const TAG_RESPONSE_EXPORT_REPRO_BUNDLE: &str = "export_repro_bundle_response";
+const TAG_RESPONSE_REPLAY_REPRO_BUNDLE: &str = "replay_repro_bundle_response";
+const TAG_RESPONSE_VALIDATE_DEPLOY: &str = "validate_deploy_response";
 const TAG_RESPONSE_GENESIS: &str = "genesis_response";
+const TAG_RESPONSE_CHAINSPEC_FINGERPRINT: &str = "chainspec_fingerprint_response";
 const TAG_RESPONSE_UPGRADE: &str = "upgrade_response";
+const TAG_RESPONSE_PREVIEW_UPGRADE: &str = "preview_upgrade_response";
+const TAG_RESPONSE_GET_VALIDATOR_WEIGHTS: &str = "get_validator_weights_response";
+const TAG_RESPONSE_SERVER_INFO: &str = "server_info_response";
 
 const UNIMPLEMENTED: &str = "unimplemented";
+const EXEC_STREAM_CLOSED: &str = "execute stream closed unexpectedly";
 
 const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 
@@ -77,7 +149,7 @@ const DEFAULT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 // (outer layer) leading to cleaner design.
 impl<S> ExecutionEngineService for EngineState<S>
 where
-    S: StateProvider,
+    S: StateProvider + Clone + Send + Sync + 'static,
     EngineError: From<S::Error>,
     S::Error: Into<engine_core::execution::Error> + Debug,
 {
@@ -89,6 +161,19 @@ where
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
+        let max_value_size = match query_request.get_max_value_size_bytes() {
+            0 => None,
+            max_value_size => Some(max_value_size),
+        };
+        let range = if query_request.has_range() {
+            let range = query_request.get_range();
+            Some((range.get_offset(), range.get_length()))
+        } else {
+            None
+        };
+        let type_only = query_request.get_type_only();
+        let include_contract_bytes = query_request.get_include_contract_bytes();
+
         let request: QueryRequest = match query_request.try_into() {
             Ok(ret) => ret,
             Err(err) => {
@@ -108,20 +193,40 @@ where
 
         let result = self.run_query(correlation_id, request);
 
+        if let Some(query_cache) = self.query_cache() {
+            for (metric, value) in &[
+                (METRIC_QUERY_CACHE_HITS, query_cache.hits()),
+                (METRIC_QUERY_CACHE_MISSES, query_cache.misses()),
+                (METRIC_QUERY_CACHE_WEIGHT, query_cache.weight()),
+            ] {
+                log_metric(
+                    correlation_id,
+                    metric,
+                    TAG_RESPONSE_QUERY,
+                    GAUGE_METRIC_KEY,
+                    *value as f64,
+                );
+            }
+        }
+
         let response = match result {
-            Ok(QueryResult::Success(value)) => {
+            Ok(QueryResult::Success(value)) if type_only => {
+                info!("query successful; correlation_id: {}", correlation_id);
                 let mut result = ipc::QueryResponse::new();
-                match value.to_bytes() {
-                    Ok(serialized_value) => {
-                        info!("query successful; correlation_id: {}", correlation_id);
-                        result.set_success(serialized_value);
-                    }
-                    Err(error_msg) => {
-                        let log_message = format!("Failed to serialize StoredValue: {}", error_msg);
-                        warn!("{}", log_message);
-                        result.set_failure(log_message);
-                    }
-                }
+                result.set_value_type((&value).into());
+                result
+            }
+            Ok(QueryResult::Success(value)) => query_success_response(
+                correlation_id,
+                &value,
+                max_value_size,
+                range,
+                include_contract_bytes,
+            ),
+            Ok(QueryResult::AccountAuth(account_auth_info)) => {
+                info!("query successful; correlation_id: {}", correlation_id);
+                let mut result = ipc::QueryResponse::new();
+                result.set_account_auth(account_auth_info.into());
                 result
             }
             Ok(QueryResult::ValueNotFound(msg)) => {
@@ -143,68 +248,733 @@ where
                 result.set_failure(msg);
                 result
             }
-            Err(err) => {
-                let log_message = format!("{:?}", err);
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ipc::QueryResponse::new();
+                result.set_failure(log_message);
+                result
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_QUERY,
+            TAG_RESPONSE_QUERY,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn multi_root_query(
+        &self,
+        _request_options: RequestOptions,
+        multi_root_query_request: ipc::MultiRootQueryRequest,
+    ) -> SingleResponse<MultiRootQueryResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let include_contract_bytes = multi_root_query_request.get_include_contract_bytes();
+        let request: MultiRootQueryRequest = match multi_root_query_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = MultiRootQueryResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_MULTI_ROOT_QUERY,
+                    TAG_RESPONSE_MULTI_ROOT_QUERY,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+        let state_hashes = request.state_hashes().to_vec();
+
+        let mut response = MultiRootQueryResponse::new();
+        match self.run_multi_root_query(correlation_id, request) {
+            Ok(query_results) => {
+                let results = state_hashes
+                    .into_iter()
+                    .zip(query_results)
+                    .map(|(state_hash, query_result)| {
+                        let mut result = MultiRootQueryResponse_Result::new();
+                        match query_result {
+                            QueryResult::Success(value) => {
+                                result.set_query_response(query_success_response(
+                                    correlation_id,
+                                    &value,
+                                    None,
+                                    None,
+                                    include_contract_bytes,
+                                ))
+                            }
+                            QueryResult::AccountAuth(account_auth_info) => {
+                                let mut query_response = ipc::QueryResponse::new();
+                                query_response.set_account_auth(account_auth_info.into());
+                                result.set_query_response(query_response);
+                            }
+                            QueryResult::ValueNotFound(msg)
+                            | QueryResult::CircularReference(msg) => {
+                                info!("{}", msg);
+                                let mut query_response = ipc::QueryResponse::new();
+                                query_response.set_failure(msg);
+                                result.set_query_response(query_response);
+                            }
+                            QueryResult::RootNotFound => {
+                                info!("Root not found: {}", state_hash);
+                                let mut root_not_found = RootNotFound::new();
+                                root_not_found.set_hash(state_hash.to_vec());
+                                result.set_missing_root(root_not_found);
+                            }
+                        }
+                        result
+                    })
+                    .collect();
+                let mut success = MultiRootQueryResponse_Results::new();
+                success.set_results(results);
+                response.set_success(success);
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_MULTI_ROOT_QUERY,
+            TAG_RESPONSE_MULTI_ROOT_QUERY,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn list_keys(
+        &self,
+        _request_options: RequestOptions,
+        list_keys_request: ipc::ListKeysRequest,
+    ) -> SingleResponse<ListKeysResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: ListKeysRequest = match list_keys_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ListKeysResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_LIST_KEYS,
+                    TAG_RESPONSE_LIST_KEYS,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let mut response = ListKeysResponse::new();
+        match self.run_list_keys(correlation_id, request) {
+            Ok(ListKeysResult::Success(named_keys_page)) => {
+                info!("list keys successful; correlation_id: {}", correlation_id);
+                response.set_success(named_keys_page.into());
+            }
+            Ok(ListKeysResult::ValueNotFound(msg)) | Ok(ListKeysResult::NoNamedKeys(msg)) => {
+                info!("{}", msg);
+                response.set_failure(msg);
+            }
+            Ok(ListKeysResult::RootNotFound) => {
+                let log_message = "Root not found";
+                info!("{}", log_message);
+                response.set_failure(log_message.to_string());
+            }
+            Ok(ListKeysResult::CircularReference(msg)) => {
+                warn!("{}", msg);
+                response.set_failure(msg);
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_LIST_KEYS,
+            TAG_RESPONSE_LIST_KEYS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn balance(
+        &self,
+        _request_options: RequestOptions,
+        balance_request: ipc::BalanceRequest,
+    ) -> SingleResponse<BalanceResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: BalanceRequest = match balance_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = BalanceResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BALANCE,
+                    TAG_RESPONSE_BALANCE,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let result = self.get_main_purse_balance(
+            correlation_id,
+            request.protocol_version(),
+            request.state_hash(),
+            request.public_key(),
+        );
+        match &result {
+            Ok(_) => info!("balance successful; correlation_id: {}", correlation_id),
+            Err(err @ BalanceError::Engine(_)) => warn!("{}", err),
+            Err(err) => info!("{}", err),
+        }
+        let response: BalanceResponse = result.into();
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_BALANCE,
+            TAG_RESPONSE_BALANCE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn list_roots(
+        &self,
+        _request_options: RequestOptions,
+        list_roots_request: ipc::ListRootsRequest,
+    ) -> SingleResponse<ListRootsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let request: ListRootsRequest = match list_roots_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = ListRootsResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_LIST_ROOTS,
+                    TAG_RESPONSE_LIST_ROOTS,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let mut response = ListRootsResponse::new();
+        match self.run_list_roots(request) {
+            Ok(roots_page) => {
+                info!("list roots successful; correlation_id: {}", correlation_id);
+                response.set_success(roots_page.into());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_LIST_ROOTS,
+            TAG_RESPONSE_LIST_ROOTS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn get_trie_node(
+        &self,
+        _request_options: RequestOptions,
+        get_trie_node_request: GetTrieNodeRequest,
+    ) -> SingleResponse<GetTrieNodeResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = GetTrieNodeResponse::new();
+        match TrieNodesRequest::try_from(get_trie_node_request) {
+            Ok(request) => match self.read_trie_nodes(&request) {
+                Ok(mut nodes) => match nodes.pop() {
+                    Some(Some(node)) => response.set_success(node),
+                    _ => {
+                        let node_hash = request.hashes()[0];
+                        info!("Trie node not found: {}", node_hash);
+                        response.mut_not_found().set_node_hash(node_hash.to_vec());
+                    }
+                },
+                Err(error) => {
+                    let log_message = error.to_string();
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+            },
+            Err(error) => {
+                let log_message = error.to_string();
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_TRIE_NODE,
+            TAG_RESPONSE_GET_TRIE_NODE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn get_trie_nodes(
+        &self,
+        _request_options: RequestOptions,
+        get_trie_nodes_request: GetTrieNodesRequest,
+    ) -> SingleResponse<GetTrieNodesResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = GetTrieNodesResponse::new();
+        match TrieNodesRequest::try_from(get_trie_nodes_request) {
+            Ok(request) => match self.read_trie_nodes(&request) {
+                Ok(nodes) => response.set_success(mappings::trie_nodes(&request, nodes)),
+                Err(error) => {
+                    let log_message = error.to_string();
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+            },
+            Err(error) => {
+                let log_message = error.to_string();
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_TRIE_NODES,
+            TAG_RESPONSE_GET_TRIE_NODES,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn build_state(
+        &self,
+        _request_options: RequestOptions,
+        mut build_state_request: ipc::BuildStateRequest,
+    ) -> SingleResponse<BuildStateResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let persist = build_state_request.get_persist();
+        let pairs: Vec<(Key, StoredValue)> = match build_state_request
+            .take_entries()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, ParsingError>>()
+        {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                let mut result = BuildStateResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BUILD_STATE,
+                    TAG_RESPONSE_BUILD_STATE,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+
+        let mut response = BuildStateResponse::new();
+        match self.compute_state_root(correlation_id, pairs, persist) {
+            Ok(state_hash) => {
+                info!(
+                    "build state successful; correlation_id: {}, state_hash: {}",
+                    correlation_id, state_hash
+                );
+                response.set_state_hash(state_hash.to_vec());
+            }
+            Err(err) => {
+                let log_message = format!("{:?}", err);
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_BUILD_STATE,
+            TAG_RESPONSE_BUILD_STATE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn execute(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> SingleResponse<ExecuteResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let exec_request: ExecuteRequest = match exec_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_EXEC,
+                    TAG_RESPONSE_EXEC,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(err);
+            }
+        };
+
+        // the time spent converting from and to protobuf is only reported if stats are collected
+        let mut protobuf_conversion_time = start.elapsed();
+
+        // an empty block still has its parent validated and is answered with an empty success
+        let mut deploy_results = Vec::new();
+        let result = self.run_execute_with(
+            correlation_id,
+            exec_request,
+            |execution_result, execution_stats| {
+                let conversion_start = Instant::now();
+                deploy_results.push(DeployResult::from((execution_result, execution_stats)));
+                protobuf_conversion_time += conversion_start.elapsed();
+            },
+        );
+        let exec_response = match result {
+            Ok(maybe_request_stats) => {
+                let conversion_start = Instant::now();
+                let mut exec_response = ExecuteResponse::from(deploy_results);
+                if let Some(mut request_stats) = maybe_request_stats {
+                    request_stats.protobuf_conversion_time =
+                        protobuf_conversion_time + conversion_start.elapsed();
+                    request_stats.total_time = start.elapsed();
+                    exec_response.set_request_stats(request_stats.into());
+                }
+                exec_response
+            }
+            Err(error) => {
+                info!("deploy results error: RootNotFound");
+                let mut exec_response = ExecuteResponse::new();
+                exec_response.mut_missing_parent().set_hash(error.to_vec());
+                exec_response
+            }
+        };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_EXEC,
+            TAG_RESPONSE_EXEC,
+            start.elapsed(),
+        );
+        SingleResponse::completed(exec_response)
+    }
+
+    fn execute_stream(
+        &self,
+        _request_options: RequestOptions,
+        exec_request: ipc::ExecuteRequest,
+    ) -> StreamingResponse<DeployResultChunk> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let exec_request: ExecuteRequest = match exec_request.try_into() {
+            Ok(ret) => ret,
+            Err(mut err) => {
+                let mut summary_chunk = DeployResultChunk::new();
+                summary_chunk
+                    .mut_summary()
+                    .set_missing_parent(err.take_missing_parent());
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_EXEC_STREAM,
+                    TAG_RESPONSE_EXEC_STREAM,
+                    start.elapsed(),
+                );
+                return StreamingResponse::completed(vec![summary_chunk]);
+            }
+        };
+
+        // Execution runs on its own thread so that each result can be sent to the client while
+        // the remaining deploys are still executing.  The engine state shares its underlying
+        // global state and system contract cache with the clone.
+        let engine_state = self.clone();
+        let (sender, receiver) = mpsc::unbounded();
+
+        thread::spawn(move || {
+            let mut counts = ExecuteStreamSummary_Counts::new();
+
+            let result = engine_state.run_execute_with(
+                correlation_id,
+                exec_request,
+                |execution_result, execution_stats| {
+                    let deploy_result: DeployResult = (execution_result, execution_stats).into();
+                    counts.set_deploy_count(counts.get_deploy_count() + 1);
+                    if deploy_result.has_precondition_failure() {
+                        counts.set_precondition_failure_count(
+                            counts.get_precondition_failure_count() + 1,
+                        );
+                    } else if deploy_result.has_skipped_block_gas_exhausted() {
+                        counts.set_skipped_count(counts.get_skipped_count() + 1);
+                    } else if deploy_result.get_execution_result().has_error() {
+                        counts.set_execution_error_count(counts.get_execution_error_count() + 1);
+                    }
+
+                    let mut chunk = DeployResultChunk::new();
+                    chunk.set_deploy_result(deploy_result);
+                    // The receiver is only dropped if the client cancelled the stream, in which
+                    // case the remaining results are discarded.
+                    let _ = sender.unbounded_send(chunk);
+                },
+            );
+
+            let mut summary_chunk = DeployResultChunk::new();
+            match result {
+                Ok(_) => summary_chunk.mut_summary().set_success(counts),
+                Err(error) => {
+                    info!("deploy results error: RootNotFound");
+                    summary_chunk
+                        .mut_summary()
+                        .mut_missing_parent()
+                        .set_hash(error.to_vec());
+                }
+            }
+            let _ = sender.unbounded_send(summary_chunk);
+
+            log_duration(
+                correlation_id,
+                METRIC_DURATION_EXEC_STREAM,
+                TAG_RESPONSE_EXEC_STREAM,
+                start.elapsed(),
+            );
+        });
+
+        StreamingResponse::no_metadata(receiver.map_err(|()| GrpcError::Other(EXEC_STREAM_CLOSED)))
+    }
+
+    fn replay(
+        &self,
+        _request_options: RequestOptions,
+        replay_request: ipc::ReplayRequest,
+    ) -> SingleResponse<ReplayResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let replay_request: ReplayRequest = match replay_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{}", err);
+                warn!("{}", log_message);
+                let mut result = ReplayResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_REPLAY,
+                    TAG_RESPONSE_REPLAY,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+        let parent_state_hash = replay_request.parent_state_hash;
+
+        let mut replay_response = ReplayResponse::new();
+        match self.replay_deploy(correlation_id, replay_request) {
+            Ok(ReplayResult::Replayed(replayed_deploy)) => {
+                replay_response.set_success(replayed_deploy.into())
+            }
+            Ok(ReplayResult::RootNotFound) => {
+                info!("replay error: RootNotFound");
+                replay_response
+                    .mut_missing_parent()
+                    .set_hash(parent_state_hash.to_vec());
+            }
+            Err(error) => {
+                let log_message = format!("{}", error);
+                warn!("{}", log_message);
+                replay_response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_REPLAY,
+            TAG_RESPONSE_REPLAY,
+            start.elapsed(),
+        );
+        SingleResponse::completed(replay_response)
+    }
+
+    fn export_repro_bundle(
+        &self,
+        _request_options: RequestOptions,
+        export_request: ipc::ExportReproBundleRequest,
+    ) -> SingleResponse<ExportReproBundleResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let export_request: ExportReproBundleRequest = match export_request.try_into() {
+            Ok(ret) => ret,
+            Err(err) => {
+                let log_message = format!("{}", err);
+                warn!("{}", log_message);
+                let mut result = ExportReproBundleResponse::new();
+                result.set_failure(log_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_EXPORT_REPRO_BUNDLE,
+                    TAG_RESPONSE_EXPORT_REPRO_BUNDLE,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(result);
+            }
+        };
+        let parent_state_hash = export_request.parent_state_hash;
+
+        let mut export_response = ExportReproBundleResponse::new();
+        let export_result = EngineState::export_repro_bundle(self, correlation_id, export_request)
+            .and_then(|bundle| match bundle {
+                Some(bundle) => Ok(Some(bundle.to_bytes()?)),
+                None => Ok(None),
+            });
+        match export_result {
+            Ok(Some(bundle_bytes)) => export_response.set_bundle(bundle_bytes),
+            Ok(None) => {
+                info!("export repro bundle error: RootNotFound");
+                export_response
+                    .mut_missing_parent()
+                    .set_hash(parent_state_hash.to_vec());
+            }
+            Err(error) => {
+                let log_message = format!("{}", error);
                 warn!("{}", log_message);
-                let mut result = ipc::QueryResponse::new();
-                result.set_failure(log_message);
-                result
+                export_response.set_failure(log_message);
             }
-        };
+        }
 
         log_duration(
             correlation_id,
-            METRIC_DURATION_QUERY,
-            TAG_RESPONSE_QUERY,
+            METRIC_DURATION_EXPORT_REPRO_BUNDLE,
+            TAG_RESPONSE_EXPORT_REPRO_BUNDLE,
             start.elapsed(),
         );
+        SingleResponse::completed(export_response)
+    }
 
-        SingleResponse::completed(response)
+    fn replay_repro_bundle(
+        &self,
+        _request_options: RequestOptions,
+        replay_request: ipc::ReplayReproBundleRequest,
+    ) -> SingleResponse<ReplayReproBundleResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let replay_response =
+            match bytesrepr::deserialize::<ReproBundle>(replay_request.get_bundle().to_vec())
+                .map_err(EngineError::from)
+                .and_then(|bundle| EngineState::replay_repro_bundle(self, correlation_id, &bundle))
+            {
+                Ok(bundle_replay_result) => bundle_replay_result.into(),
+                Err(error) => {
+                    let log_message = format!("{}", error);
+                    warn!("{}", log_message);
+                    let mut replay_response = ReplayReproBundleResponse::new();
+                    replay_response.set_failure(log_message);
+                    replay_response
+                }
+            };
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_REPLAY_REPRO_BUNDLE,
+            TAG_RESPONSE_REPLAY_REPRO_BUNDLE,
+            start.elapsed(),
+        );
+        SingleResponse::completed(replay_response)
     }
 
-    fn execute(
+    fn validate_deploy(
         &self,
         _request_options: RequestOptions,
-        exec_request: ipc::ExecuteRequest,
-    ) -> SingleResponse<ExecuteResponse> {
+        validate_request: ipc::ValidateDeployRequest,
+    ) -> SingleResponse<ValidateDeployResponse> {
         let start = Instant::now();
         let correlation_id = CorrelationId::new();
 
-        let exec_request: ExecuteRequest = match exec_request.try_into() {
+        let validate_request: ValidateDeployRequest = match validate_request.try_into() {
             Ok(ret) => ret,
             Err(err) => {
-                return SingleResponse::completed(err);
-            }
-        };
-
-        let mut exec_response = ExecuteResponse::new();
-
-        let results = match self.run_execute(correlation_id, exec_request) {
-            Ok(results) => results,
-            Err(error) => {
-                info!("deploy results error: RootNotFound");
-                exec_response.mut_missing_parent().set_hash(error.to_vec());
+                let log_message = format!("{}", err);
+                warn!("{}", log_message);
+                let mut result = ValidateDeployResponse::new();
+                result.set_failure(log_message);
                 log_duration(
                     correlation_id,
-                    METRIC_DURATION_EXEC,
-                    TAG_RESPONSE_EXEC,
+                    METRIC_DURATION_VALIDATE_DEPLOY,
+                    TAG_RESPONSE_VALIDATE_DEPLOY,
                     start.elapsed(),
                 );
-                return SingleResponse::completed(exec_response);
+                return SingleResponse::completed(result);
             }
         };
 
-        let protobuf_results_iter = results.into_iter().map(Into::into);
-        exec_response
-            .mut_success()
-            .set_deploy_results(FromIterator::from_iter(protobuf_results_iter));
+        let mut validate_response = ValidateDeployResponse::new();
+        match EngineState::validate_deploy(self, validate_request) {
+            Ok(validate_result) => validate_response.set_verdict(validate_result.into()),
+            Err(error) => {
+                let log_message = format!("{}", error);
+                warn!("{}", log_message);
+                validate_response.set_failure(log_message);
+            }
+        }
+
         log_duration(
             correlation_id,
-            METRIC_DURATION_EXEC,
-            TAG_RESPONSE_EXEC,
+            METRIC_DURATION_VALIDATE_DEPLOY,
+            TAG_RESPONSE_VALIDATE_DEPLOY,
             start.elapsed(),
         );
-        SingleResponse::completed(exec_response)
+        SingleResponse::completed(validate_response)
     }
 
     fn commit(
@@ -234,6 +1004,12 @@ where
                 commit_response
                     .mut_failed_transform()
                     .set_message(error_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_COMMIT,
+                    TAG_RESPONSE_COMMIT,
+                    start.elapsed(),
+                );
                 return SingleResponse::completed(commit_response);
             }
             Ok(hash) => hash,
@@ -247,6 +1023,12 @@ where
                 commit_response
                     .mut_failed_transform()
                     .set_message(error_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_COMMIT,
+                    TAG_RESPONSE_COMMIT,
+                    start.elapsed(),
+                );
                 return SingleResponse::completed(commit_response);
             }
             Ok(transforms) => transforms.into_inner(),
@@ -254,65 +1036,148 @@ where
 
         // "Apply" effects to global state
         let commit_response = {
-            let mut ret = CommitResponse::new();
+            let last_seen_sequence_number = if commit_request.has_fence() {
+                Some(
+                    commit_request
+                        .get_fence()
+                        .get_last_seen_commit_sequence_number(),
+                )
+            } else {
+                None
+            };
 
-            match self.apply_effect(correlation_id, protocol_version, pre_state_hash, transforms) {
-                Ok(CommitResult::Success {
-                    state_root,
-                    bonded_validators,
-                }) => {
-                    let properties = {
-                        let mut tmp = BTreeMap::new();
-                        tmp.insert("post-state-hash", format!("{:?}", state_root));
-                        tmp.insert("success", true.to_string());
-                        tmp
-                    };
-                    logging::log_details(
-                        Level::Info,
-                        "effects applied; new state hash is: {post-state-hash}".to_owned(),
-                        properties,
-                    );
+            let commit_result = self.apply_effect(
+                correlation_id,
+                protocol_version,
+                pre_state_hash,
+                transforms,
+                last_seen_sequence_number,
+            );
+            let mut commit_response = commit_response(pre_state_hash, commit_result);
+            if commit_response.has_success() {
+                let durability = commit_request.get_durability().into();
+                let commit_result = commit_response.mut_success();
+                let durable = make_durable(self, durability, commit_result.get_poststate_hash());
+                commit_result.set_durable(durable);
+            }
+            commit_response
+        };
 
-                    let bonds = bonded_validators.into_iter().map(Into::into).collect();
-                    let commit_result = ret.mut_success();
-                    commit_result.set_poststate_hash(state_root.to_vec());
-                    commit_result.set_bonded_validators(bonds);
-                }
-                Ok(CommitResult::RootNotFound) => {
-                    warn!("RootNotFound");
-                    ret.mut_missing_prestate().set_hash(pre_state_hash.to_vec());
-                }
-                Ok(CommitResult::KeyNotFound(key)) => {
-                    warn!("{:?} not found", key);
-                    ret.set_key_not_found(key.into());
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_COMMIT,
+            TAG_RESPONSE_COMMIT,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(commit_response)
+    }
+
+    fn batch_commit(
+        &self,
+        _request_options: RequestOptions,
+        mut batch_commit_request: BatchCommitRequest,
+    ) -> SingleResponse<BatchCommitResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let protocol_version = {
+            let protocol_version = batch_commit_request.take_protocol_version().into();
+            if protocol_version < DEFAULT_PROTOCOL_VERSION {
+                DEFAULT_PROTOCOL_VERSION
+            } else {
+                protocol_version
+            }
+        };
+
+        let entries =
+            match BatchCommitEntries::try_from(batch_commit_request.take_entries().into_vec()) {
+                Ok(entries) if entries.is_empty() => Err("Batch commit has no entries".to_string()),
+                Ok(entries) => Ok(entries.into_inner()),
+                Err(error) => Err(format!("Could not parse batch commit entries: {}", error)),
+            };
+        let entries = match entries {
+            Ok(entries) => entries,
+            Err(error_message) => {
+                warn!("{}", error_message);
+                let mut batch_commit_response = BatchCommitResponse::new();
+                batch_commit_response
+                    .mut_failure()
+                    .mut_commit_failure()
+                    .mut_failed_transform()
+                    .set_message(error_message);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_BATCH_COMMIT,
+                    TAG_RESPONSE_BATCH_COMMIT,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(batch_commit_response);
+            }
+        };
+
+        let pre_state_hashes: Vec<Blake2bHash> = entries
+            .iter()
+            .map(|(pre_state_hash, _)| *pre_state_hash)
+            .collect();
+        let batch_commit_response = {
+            let mut ret = BatchCommitResponse::new();
+            match self.apply_effect_batch(correlation_id, protocol_version, entries) {
+                Ok(BatchCommitResult::Success(commit_results)) => {
+                    let success = ret.mut_success();
+                    for (pre_state_hash, commit_result) in
+                        pre_state_hashes.into_iter().zip(commit_results)
+                    {
+                        let mut response = commit_response(pre_state_hash, Ok(commit_result));
+                        let result = response.take_success();
+                        success.set_poststate_hash(result.get_poststate_hash().to_vec());
+                        success.mut_results().push(result);
+                    }
+                    // made durable once, as a whole, along with every entry before the last
+                    let durability = batch_commit_request.get_durability().into();
+                    let durable = make_durable(self, durability, success.get_poststate_hash());
+                    for result in success.mut_results().iter_mut() {
+                        result.set_durable(durable);
+                    }
                 }
-                Ok(CommitResult::TypeMismatch(type_mismatch)) => {
-                    warn!("{:?}", type_mismatch);
-                    ret.set_type_mismatch(type_mismatch.into());
+                Ok(BatchCommitResult::Failure { index, result }) => {
+                    warn!("Batch commit failed at entry {}: {}", index, result);
+                    let failure = ret.mut_failure();
+                    failure.set_index(index as u32);
+                    failure
+                        .set_commit_failure(commit_response(pre_state_hashes[index], Ok(result)));
                 }
-                Ok(CommitResult::Serialization(error)) => {
-                    warn!("{:?}", error);
-                    ret.mut_failed_transform()
-                        .set_message(format!("{:?}", error));
+                Ok(BatchCommitResult::PreStateMismatch {
+                    index,
+                    expected,
+                    found,
+                }) => {
+                    warn!(
+                        "Batch commit entry {} has pre-state {} instead of {}",
+                        index, found, expected
+                    );
+                    let failure = ret.mut_failure();
+                    failure.set_index(index as u32);
+                    let prestate_mismatch = failure.mut_prestate_mismatch();
+                    prestate_mismatch.set_expected_prestate_hash(expected.to_vec());
+                    prestate_mismatch.set_prestate_hash(found.to_vec());
                 }
                 Err(error) => {
-                    warn!("State error {:?} when applying transforms", error);
-                    ret.mut_failed_transform()
-                        .set_message(format!("{:?}", error));
+                    let failure = ret.mut_failure();
+                    failure.set_commit_failure(commit_response(pre_state_hashes[0], Err(error)));
                 }
             }
-
             ret
         };
 
         log_duration(
             correlation_id,
-            METRIC_DURATION_COMMIT,
-            TAG_RESPONSE_COMMIT,
+            METRIC_DURATION_BATCH_COMMIT,
+            TAG_RESPONSE_BATCH_COMMIT,
             start.elapsed(),
         );
 
-        SingleResponse::completed(commit_response)
+        SingleResponse::completed(batch_commit_response)
     }
 
     fn run_genesis(
@@ -331,30 +1196,87 @@ where
 
                 let mut genesis_response = GenesisResponse::new();
                 genesis_response.mut_failed_deploy().set_message(err_msg);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GENESIS,
+                    TAG_RESPONSE_GENESIS,
+                    start.elapsed(),
+                );
                 return SingleResponse::completed(genesis_response);
             }
         };
         let genesis_config_hash = run_genesis_request.genesis_config_hash();
         let protocol_version = run_genesis_request.protocol_version();
         let ee_config = run_genesis_request.ee_config();
+        let force_fresh_genesis = run_genesis_request.force_fresh_genesis();
+        let exec_config_fingerprint = match ee_config.fingerprint() {
+            Ok(fingerprint) => fingerprint,
+            Err(error) => {
+                let err_msg = format!("Failed to fingerprint genesis config: {:?}", error);
+                warn!("{}", err_msg);
+
+                let mut genesis_response = GenesisResponse::new();
+                genesis_response.mut_failed_deploy().set_message(err_msg);
+                log_duration(
+                    correlation_id,
+                    METRIC_DURATION_GENESIS,
+                    TAG_RESPONSE_GENESIS,
+                    start.elapsed(),
+                );
+                return SingleResponse::completed(genesis_response);
+            }
+        };
 
         let genesis_response = match self.commit_genesis(
             correlation_id,
             genesis_config_hash,
             protocol_version,
             ee_config,
+            force_fresh_genesis,
         ) {
             Ok(GenesisResult::Success {
                 post_state_hash,
                 effect,
+                account_reports,
+                bonding_reports,
             }) => {
                 let success_message = format!("run_genesis successful: {}", post_state_hash);
                 info!("{}", success_message);
+                // every later commit builds on genesis, so it's synced whatever the durability
+                make_durable(self, Some(Durability::Strict), &post_state_hash.value());
 
                 let mut genesis_response = GenesisResponse::new();
                 let genesis_result = genesis_response.mut_success();
                 genesis_result.set_poststate_hash(post_state_hash.to_vec());
                 genesis_result.set_effect(effect.into());
+                genesis_result.set_exec_config_fingerprint(exec_config_fingerprint.to_vec());
+                genesis_result.set_account_reports(
+                    account_reports
+                        .into_iter()
+                        .map(Into::into)
+                        .collect::<Vec<_>>()
+                        .into(),
+                );
+                genesis_result.set_bonding_reports(
+                    bonding_reports
+                        .into_iter()
+                        .map(Into::into)
+                        .collect::<Vec<_>>()
+                        .into(),
+                );
+                genesis_response
+            }
+            Ok(GenesisResult::StoreAlreadyInitialized(genesis_record)) => {
+                warn!(
+                    "run_genesis refused: store already initialized by genesis {} at root {}",
+                    genesis_record.fingerprint, genesis_record.root
+                );
+
+                let mut genesis_response = GenesisResponse::new();
+                let store_already_initialized = genesis_response.mut_store_already_initialized();
+                store_already_initialized
+                    .set_existing_fingerprint(genesis_record.fingerprint.to_vec());
+                store_already_initialized.set_existing_root(genesis_record.root.to_vec());
                 genesis_response
             }
             Ok(genesis_result) => {
@@ -385,6 +1307,54 @@ where
         SingleResponse::completed(genesis_response)
     }
 
+    fn chainspec_fingerprint(
+        &self,
+        _request_options: RequestOptions,
+        mut chainspec_fingerprint_request: ChainspecFingerprintRequest,
+    ) -> SingleResponse<ChainspecFingerprintResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = ChainspecFingerprintResponse::new();
+        let genesis_config: Result<GenesisConfig, _> = chainspec_fingerprint_request
+            .take_genesis_config()
+            .try_into();
+        match genesis_config {
+            Ok(genesis_config) => {
+                let fingerprints = genesis_config.fingerprint().and_then(|fingerprint| {
+                    let exec_config_fingerprint = genesis_config.ee_config().fingerprint()?;
+                    Ok((fingerprint, exec_config_fingerprint))
+                });
+                match fingerprints {
+                    Ok((fingerprint, exec_config_fingerprint)) => {
+                        let success = response.mut_success();
+                        success.set_fingerprint(fingerprint.to_vec());
+                        success.set_exec_config_fingerprint(exec_config_fingerprint.to_vec());
+                    }
+                    Err(error) => {
+                        let log_message = format!("{:?}", error);
+                        warn!("{}", log_message);
+                        response.set_failure(log_message);
+                    }
+                }
+            }
+            Err(error) => {
+                let log_message = error.to_string();
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_CHAINSPEC_FINGERPRINT,
+            TAG_RESPONSE_CHAINSPEC_FINGERPRINT,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn upgrade(
         &self,
         _request_options: RequestOptions,
@@ -417,12 +1387,19 @@ where
             Ok(UpgradeResult::Success {
                 post_state_hash,
                 effect,
+                changes,
             }) => {
-                info!("upgrade successful: {}", post_state_hash);
+                info!(
+                    "upgrade successful: {}, changes: {}",
+                    post_state_hash, changes
+                );
+                // as for genesis, every later commit builds on the upgrade
+                make_durable(self, Some(Durability::Strict), &post_state_hash.value());
                 let mut ret = UpgradeResponse::new();
                 let upgrade_result = ret.mut_success();
                 upgrade_result.set_post_state_hash(post_state_hash.to_vec());
                 upgrade_result.set_effect(effect.into());
+                upgrade_result.set_changes((&changes).into());
                 ret
             }
             Ok(upgrade_result) => {
@@ -453,6 +1430,123 @@ where
         SingleResponse::completed(upgrade_response)
     }
 
+    fn preview_upgrade(
+        &self,
+        _request_options: RequestOptions,
+        mut preview_upgrade_request: PreviewUpgradeRequest,
+    ) -> SingleResponse<PreviewUpgradeResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = PreviewUpgradeResponse::new();
+        let upgrade_config: Result<UpgradeConfig, _> =
+            preview_upgrade_request.take_upgrade().try_into();
+        match upgrade_config {
+            Ok(upgrade_config) => match self.diff_upgrade(correlation_id, &upgrade_config) {
+                Ok(Some(changes)) => response.set_changes((&changes).into()),
+                Ok(None) => {
+                    let log_message =
+                        format!("Root not found: {}", upgrade_config.pre_state_hash());
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+                Err(error) => {
+                    let log_message = error.to_string();
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+            },
+            Err(error) => {
+                let log_message = error.to_string();
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_PREVIEW_UPGRADE,
+            TAG_RESPONSE_PREVIEW_UPGRADE,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn get_validator_weights(
+        &self,
+        _request_options: RequestOptions,
+        get_validator_weights_request: GetValidatorWeightsRequest,
+    ) -> SingleResponse<GetValidatorWeightsResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = GetValidatorWeightsResponse::new();
+        match get_validator_weights_request.get_state_hash().try_into() {
+            Ok(state_hash) => match self.get_validator_weights_at(correlation_id, state_hash) {
+                Ok(Some(bonded_validators)) => {
+                    let bonds = mappings::canonical_bonds(bonded_validators).into();
+                    response.mut_success().set_bonded_validators(bonds);
+                }
+                Ok(None) => {
+                    info!("Root not found: {}", state_hash);
+                    response.mut_missing_state().set_hash(state_hash.to_vec());
+                }
+                Err(error) => {
+                    let log_message = error.to_string();
+                    warn!("{}", log_message);
+                    response.set_failure(log_message);
+                }
+            },
+            Err(_) => {
+                let log_message = "Could not parse state hash".to_string();
+                warn!("{}", log_message);
+                response.set_failure(log_message);
+            }
+        }
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_GET_VALIDATOR_WEIGHTS,
+            TAG_RESPONSE_GET_VALIDATOR_WEIGHTS,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
+    fn server_info(
+        &self,
+        _request_options: RequestOptions,
+        _server_info_request: ServerInfoRequest,
+    ) -> SingleResponse<ServerInfoResponse> {
+        let start = Instant::now();
+        let correlation_id = CorrelationId::new();
+
+        let mut response = ServerInfoResponse::new();
+        response.set_durability(self.config().durability().into());
+        if let Some(last_durable_root) = self.last_durable_root() {
+            response.set_last_durable_root(last_durable_root.to_vec());
+        }
+        match self.genesis_record() {
+            Ok(Some(genesis_record)) => {
+                response.set_genesis_fingerprint(genesis_record.fingerprint.to_vec())
+            }
+            Ok(None) => (),
+            Err(error) => warn!("failed to read genesis record: {}", error),
+        }
+        response.set_store_schema_version(STORE_SCHEMA_VERSION);
+
+        log_duration(
+            correlation_id,
+            METRIC_DURATION_SERVER_INFO,
+            TAG_RESPONSE_SERVER_INFO,
+            start.elapsed(),
+        );
+
+        SingleResponse::completed(response)
+    }
+
     fn bid_state(
         &self,
         _request_options: RequestOptions,
@@ -486,24 +1580,213 @@ where
     }
 }
 
-// Helper method which returns single DeployResult that is set to be a
-// WasmError.
+/// Logs the duration of a request, followed by the time spent in storage on its behalf under the
+/// same tag.
+fn log_duration(correlation_id: CorrelationId, metric: &str, tag: &str, duration: Duration) {
+    logging::log_duration(correlation_id, metric, tag, duration);
+    logging::log_duration(
+        correlation_id,
+        METRIC_DURATION_STORAGE,
+        tag,
+        storage_timer::take_storage_time(correlation_id),
+    );
+}
+
+/// Builds the response to a successful query.
+///
+/// If `range` is given as `(offset, length)`, only that part of the serialized value is returned,
+/// truncated to the end of the value.  If the returned bytes would exceed `max_value_size`, the
+/// value isn't serialized at all; its size and type are returned instead.  A contract is only
+/// returned in full if `include_contract_bytes` is set, otherwise it is summarized without its wasm
+/// bytes.
+fn query_success_response(
+    correlation_id: CorrelationId,
+    value: &StoredValue,
+    max_value_size: Option<u64>,
+    range: Option<(u64, u64)>,
+    include_contract_bytes: bool,
+) -> QueryResponse {
+    let mut result = ipc::QueryResponse::new();
+
+    // A contract's wasm bytes are only copied into the response if asked for.
+    if let StoredValue::Contract(contract) = value {
+        if !include_contract_bytes {
+            info!("query successful; correlation_id: {}", correlation_id);
+            result.set_contract_summary(contract.into());
+            return result;
+        }
+    }
+
+    // `serialized_length` doesn't allocate, so this is checked before serializing the value.
+    let actual_size = value.serialized_length();
+    let (start, end) = match range {
+        Some((offset, length)) => {
+            let start = cmp::min(offset, actual_size as u64);
+            let end = cmp::min(offset.saturating_add(length), actual_size as u64);
+            (start as usize, end as usize)
+        }
+        None => (0, actual_size),
+    };
+
+    if let Some(max_value_size) = max_value_size {
+        if (end - start) as u64 > max_value_size {
+            info!(
+                "query value too large: {} bytes; correlation_id: {}",
+                actual_size, correlation_id
+            );
+            let mut value_too_large = QueryResponse_ValueTooLarge::new();
+            value_too_large.set_actual_size(actual_size as u64);
+            value_too_large.set_type_tag(value.type_name());
+            result.set_value_too_large(value_too_large);
+            return result;
+        }
+    }
+
+    match value.to_bytes() {
+        Ok(mut serialized_value) => {
+            info!("query successful; correlation_id: {}", correlation_id);
+            serialized_value.truncate(end);
+            serialized_value.drain(..start);
+            result.set_success(serialized_value);
+            result.set_success_value_type(value.into());
+        }
+        Err(error_msg) => {
+            let log_message = format!("Failed to serialize StoredValue: {}", error_msg);
+            warn!("{}", log_message);
+            result.set_failure(log_message);
+        }
+    }
+    result
+}
+
+/// Makes the commit which resulted in `state_root` as durable as `durability` requires, returning
+/// whether it's durable.  Failing to sync storage doesn't undo the commit, so it's logged and the
+/// commit reported as not durable.
+fn make_durable<S>(
+    engine_state: &EngineState<S>,
+    durability: Option<Durability>,
+    state_root: &[u8],
+) -> bool
+where
+    S: StateProvider,
+    EngineError: From<S::Error>,
+{
+    let state_root = match Blake2bHash::try_from(state_root) {
+        Ok(state_root) => state_root,
+        Err(_) => return false,
+    };
+    match engine_state.make_durable(durability, state_root) {
+        Ok(durable) => durable,
+        Err(error) => {
+            warn!("Failed to make commit {} durable: {}", state_root, error);
+            false
+        }
+    }
+}
+
+/// Maps the result of a commit onto `pre_state_hash` to its response.
+fn commit_response(
+    pre_state_hash: Blake2bHash,
+    commit_result: Result<CommitResult, EngineError>,
+) -> CommitResponse {
+    let mut ret = CommitResponse::new();
+    match commit_result {
+        Ok(CommitResult::Success {
+            state_root,
+            bonded_validators,
+            sequence_number,
+        }) => {
+            let properties = {
+                let mut tmp = BTreeMap::new();
+                tmp.insert("post-state-hash", format!("{:?}", state_root));
+                tmp.insert("success", true.to_string());
+                tmp
+            };
+            logging::log_details(
+                Level::Info,
+                "effects applied; new state hash is: {post-state-hash}".to_owned(),
+                properties,
+            );
+
+            let bonds = mappings::canonical_bonds(bonded_validators).into();
+            let commit_result = ret.mut_success();
+            commit_result.set_poststate_hash(state_root.to_vec());
+            commit_result.set_bonded_validators(bonds);
+            commit_result.set_commit_sequence_number(sequence_number);
+        }
+        Ok(CommitResult::StaleCommit {
+            last_seen_sequence_number,
+            current_sequence_number,
+        }) => {
+            warn!(
+                "StaleCommit: last seen sequence number {} but current is {}",
+                last_seen_sequence_number, current_sequence_number
+            );
+            let stale_commit = ret.mut_stale_commit();
+            stale_commit.set_last_seen_commit_sequence_number(last_seen_sequence_number);
+            stale_commit.set_current_commit_sequence_number(current_sequence_number);
+        }
+        Ok(CommitResult::RootNotFound) => {
+            warn!("RootNotFound");
+            ret.mut_missing_prestate().set_hash(pre_state_hash.to_vec());
+        }
+        Ok(CommitResult::KeyNotFound(key)) => {
+            warn!("{:?} not found", key);
+            ret.set_key_not_found(key.into());
+        }
+        Ok(CommitResult::TypeMismatch(type_mismatch)) => {
+            warn!("{:?}", type_mismatch);
+            ret.set_type_mismatch(type_mismatch.into());
+        }
+        Ok(CommitResult::Serialization(error)) => {
+            warn!("{:?}", error);
+            ret.mut_failed_transform()
+                .set_message(format!("{:?}", error));
+        }
+        Err(error) => {
+            warn!("State error {:?} when applying transforms", error);
+            ret.mut_failed_transform()
+                .set_message(format!("{:?}", error));
+        }
+    }
+    ret
+}
+
+/// Returns a builder of a server listening on the unix socket at `socket`.
+///
+/// # Panics
+///
+/// Panics if the socket path is invalid; see [`try_new`].
 pub fn new<E: ExecutionEngineService + Sync + Send + 'static>(
     socket: &str,
     thread_count: usize,
     e: E,
 ) -> ServerBuilder {
-    let socket_path = std::path::Path::new(socket);
+    try_new(socket, thread_count, e, false).unwrap_or_else(|error| panic!("{}", error))
+}
 
-    if let Err(e) = std::fs::remove_file(socket_path) {
-        if e.kind() != ErrorKind::NotFound {
-            panic!("failed to remove old socket file: {:?}", e);
-        }
+/// Returns a builder of a server listening on the unix socket at `socket`, after checking that the
+/// socket can be bound there and removing any socket left by a previous run.
+///
+/// If `create_socket_dir` is set, the directory of the socket is created if it doesn't exist.
+pub fn try_new<E: ExecutionEngineService + Sync + Send + 'static>(
+    socket: &str,
+    thread_count: usize,
+    e: E,
+    create_socket_dir: bool,
+) -> Result<ServerBuilder, ServerStartError> {
+    match socket_path::prepare(Path::new(socket), create_socket_dir)? {
+        SocketPathState::Vacant => {}
+        SocketPathState::StaleSocketRemoved => info!("removed stale socket file: {}", socket),
+        SocketPathState::FileRemoved => warn!("removed file at socket path: {}", socket),
     }
 
     let mut server = ServerBuilder::new_plain();
-    server.http.set_unix_addr(socket.to_owned()).unwrap();
+    server
+        .http
+        .set_unix_addr(socket.to_owned())
+        .map_err(GrpcError::from)?;
     server.http.set_cpu_pool_threads(thread_count);
     server.add_service(ExecutionEngineServiceServer::new_service_def(e));
-    server
+    Ok(server)
 }
@@ -0,0 +1,309 @@
+//! Validation of the unix socket path the server listens on, done before the server is built so
+//! that a bad path fails with a clear error rather than inside the grpc crate.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, io,
+    os::unix::fs::FileTypeExt,
+    path::{Path, PathBuf},
+};
+
+use grpc::Error as GrpcError;
+
+use engine_storage::store_metadata_store::StoreVersionError;
+
+/// The max length in bytes of a unix socket path, i.e. the size of `sun_path` less the terminating
+/// nul byte.
+#[cfg(target_os = "linux")]
+pub const MAX_SOCKET_PATH_LENGTH: usize = 107;
+#[cfg(not(target_os = "linux"))]
+pub const MAX_SOCKET_PATH_LENGTH: usize = 103;
+
+// Exit codes of the binary, following sysexits.h
+const EX_USAGE: i32 = 64;
+const EX_NOINPUT: i32 = 66;
+const EX_SOFTWARE: i32 = 70;
+const EX_IOERR: i32 = 74;
+const EX_NOPERM: i32 = 77;
+const EX_CONFIG: i32 = 78;
+
+/// An error preventing the server from starting.
+#[derive(Debug)]
+pub enum ServerStartError {
+    /// The socket path is longer than the platform allows.
+    SocketPathTooLong { path: PathBuf, length: usize },
+    /// The directory of the socket doesn't exist, and wasn't to be created.
+    SocketDirMissing(PathBuf),
+    /// The socket path is an existing directory.
+    SocketPathIsDirectory(PathBuf),
+    /// The server may not remove the existing socket, or create the directory of the socket.
+    PermissionDenied { path: PathBuf, error: io::Error },
+    /// Any other I/O error while preparing the socket path.
+    Io { path: PathBuf, error: io::Error },
+    /// The grpc server failed to start.
+    Grpc(GrpcError),
+    /// The stores in the data directory can't be opened by this version of the engine.
+    Store {
+        path: PathBuf,
+        error: StoreVersionError,
+    },
+}
+
+impl ServerStartError {
+    /// Returns the exit code of the binary for this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ServerStartError::SocketPathTooLong { .. }
+            | ServerStartError::SocketPathIsDirectory(_) => EX_USAGE,
+            ServerStartError::SocketDirMissing(_) => EX_NOINPUT,
+            ServerStartError::PermissionDenied { .. } => EX_NOPERM,
+            ServerStartError::Io { .. } => EX_IOERR,
+            ServerStartError::Grpc(_) => EX_SOFTWARE,
+            ServerStartError::Store { error, .. } => match error {
+                StoreVersionError::IncompatibleStoreVersion { .. }
+                | StoreVersionError::NoMigration { .. } => EX_CONFIG,
+                StoreVersionError::MigrationFailed { .. } | StoreVersionError::Storage(_) => {
+                    EX_IOERR
+                }
+            },
+        }
+    }
+
+    fn from_io_error(path: &Path, error: io::Error) -> Self {
+        let path = path.to_path_buf();
+        match error.kind() {
+            io::ErrorKind::PermissionDenied => ServerStartError::PermissionDenied { path, error },
+            _ => ServerStartError::Io { path, error },
+        }
+    }
+}
+
+impl Display for ServerStartError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ServerStartError::SocketPathTooLong { path, length } => write!(
+                f,
+                "socket path {} is {} bytes long, but the max length is {} bytes",
+                path.display(),
+                length,
+                MAX_SOCKET_PATH_LENGTH
+            ),
+            ServerStartError::SocketDirMissing(path) => write!(
+                f,
+                "socket directory {} doesn't exist (use --create-socket-dir to create it)",
+                path.display()
+            ),
+            ServerStartError::SocketPathIsDirectory(path) => {
+                write!(f, "socket path {} is a directory", path.display())
+            }
+            ServerStartError::PermissionDenied { path, error } => {
+                write!(f, "permission denied for {}: {}", path.display(), error)
+            }
+            ServerStartError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            ServerStartError::Grpc(error) => write!(f, "failed to start grpc server: {}", error),
+            ServerStartError::Store { path, error } => {
+                write!(f, "can't open the stores in {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl From<GrpcError> for ServerStartError {
+    fn from(error: GrpcError) -> Self {
+        ServerStartError::Grpc(error)
+    }
+}
+
+/// What was found at a valid socket path.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SocketPathState {
+    /// Nothing was at the path.
+    Vacant,
+    /// A socket left by a previous run was removed.
+    StaleSocketRemoved,
+    /// A file which isn't a socket was removed.
+    FileRemoved,
+}
+
+/// Checks that a socket can be bound at `path`, removing any file left there.
+///
+/// If `create_dir` is set, the directory of the socket is created if it doesn't exist.
+pub fn prepare(path: &Path, create_dir: bool) -> Result<SocketPathState, ServerStartError> {
+    let length = path.as_os_str().len();
+    if length > MAX_SOCKET_PATH_LENGTH {
+        return Err(ServerStartError::SocketPathTooLong {
+            path: path.to_path_buf(),
+            length,
+        });
+    }
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if !dir.is_dir() {
+            if !create_dir {
+                return Err(ServerStartError::SocketDirMissing(dir.to_path_buf()));
+            }
+            fs::create_dir_all(dir).map_err(|error| ServerStartError::from_io_error(dir, error))?;
+        }
+    }
+
+    let file_type = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata.file_type(),
+        Err(ref error) if error.kind() == io::ErrorKind::NotFound => {
+            return Ok(SocketPathState::Vacant)
+        }
+        Err(error) => return Err(ServerStartError::from_io_error(path, error)),
+    };
+    if file_type.is_dir() {
+        return Err(ServerStartError::SocketPathIsDirectory(path.to_path_buf()));
+    }
+
+    fs::remove_file(path).map_err(|error| ServerStartError::from_io_error(path, error))?;
+    if file_type.is_socket() {
+        Ok(SocketPathState::StaleSocketRemoved)
+    } else {
+        Ok(SocketPathState::FileRemoved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs::File, os::unix::net::UnixListener};
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const SOCKET_NAME: &str = "socket";
+
+    #[test]
+    fn should_accept_vacant_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SOCKET_NAME);
+        assert_eq!(prepare(&path, false).unwrap(), SocketPathState::Vacant);
+    }
+
+    #[test]
+    fn should_remove_stale_socket() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SOCKET_NAME);
+        drop(UnixListener::bind(&path).unwrap());
+
+        assert_eq!(
+            prepare(&path, false).unwrap(),
+            SocketPathState::StaleSocketRemoved
+        );
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn should_remove_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SOCKET_NAME);
+        File::create(&path).unwrap();
+
+        assert_eq!(prepare(&path, false).unwrap(), SocketPathState::FileRemoved);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn should_reject_too_long_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("a".repeat(MAX_SOCKET_PATH_LENGTH));
+
+        match prepare(&path, true) {
+            Err(ServerStartError::SocketPathTooLong { length, .. }) => {
+                assert!(length > MAX_SOCKET_PATH_LENGTH)
+            }
+            other => panic!("expected socket path too long, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn should_reject_missing_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("missing");
+        let path = dir.join(SOCKET_NAME);
+
+        match prepare(&path, false) {
+            Err(ServerStartError::SocketDirMissing(missing_dir)) => assert_eq!(missing_dir, dir),
+            other => panic!("expected socket dir missing, got {:?}", other),
+        }
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn should_create_missing_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("missing").join("nested");
+        let path = dir.join(SOCKET_NAME);
+
+        assert_eq!(prepare(&path, true).unwrap(), SocketPathState::Vacant);
+        assert!(dir.is_dir());
+    }
+
+    #[test]
+    fn should_reject_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join(SOCKET_NAME);
+        fs::create_dir(&path).unwrap();
+
+        match prepare(&path, false) {
+            Err(ServerStartError::SocketPathIsDirectory(_)) => {}
+            other => panic!("expected socket path is directory, got {:?}", other),
+        }
+        assert!(path.is_dir());
+    }
+
+    #[test]
+    fn should_classify_permission_denied() {
+        // The permissions of a temp dir can't be relied upon to fail when run as root, so the
+        // error is classified directly.
+        let path = Path::new(SOCKET_NAME);
+        let error = io::Error::from(io::ErrorKind::PermissionDenied);
+        let start_error = ServerStartError::from_io_error(path, error);
+        match start_error {
+            ServerStartError::PermissionDenied { .. } => {}
+            other => panic!("expected permission denied, got {:?}", other),
+        }
+
+        let error = io::Error::from(io::ErrorKind::Other);
+        match ServerStartError::from_io_error(path, error) {
+            ServerStartError::Io { .. } => {}
+            other => panic!("expected io error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn exit_codes_should_be_distinct_per_cause() {
+        let path = PathBuf::from(SOCKET_NAME);
+        let exit_codes = [
+            ServerStartError::SocketPathTooLong {
+                path: path.clone(),
+                length: MAX_SOCKET_PATH_LENGTH + 1,
+            }
+            .exit_code(),
+            ServerStartError::SocketDirMissing(path.clone()).exit_code(),
+            ServerStartError::PermissionDenied {
+                path: path.clone(),
+                error: io::Error::from(io::ErrorKind::PermissionDenied),
+            }
+            .exit_code(),
+            ServerStartError::Io {
+                path: path.clone(),
+                error: io::Error::from(io::ErrorKind::Other),
+            }
+            .exit_code(),
+            ServerStartError::Store {
+                path,
+                error: StoreVersionError::IncompatibleStoreVersion {
+                    store: 3,
+                    binary: 2,
+                },
+            }
+            .exit_code(),
+        ];
+        for (index, exit_code) in exit_codes.iter().enumerate() {
+            assert!(!exit_codes[index + 1..].contains(exit_code));
+        }
+    }
+}
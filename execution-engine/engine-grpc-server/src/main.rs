@@ -1,5 +1,6 @@
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     fs,
     path::PathBuf,
     str::FromStr,
@@ -7,33 +8,49 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use clap::{App, Arg, ArgMatches};
 use dirs::home_dir;
 use engine_core::engine_state::{EngineConfig, EngineState};
 use lmdb::DatabaseFlags;
-use log::{error, info, Level, LevelFilter};
+use log::{error, info, warn, Level, LevelFilter};
 
 use engine_shared::{
     logging::{self, Settings, Style},
+    newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
     socket,
 };
 use engine_storage::{
-    global_state::lmdb::LmdbGlobalState, transaction_source::lmdb::LmdbEnvironment,
+    global_state::{
+        durability::{Durability, SyncBatching},
+        lmdb::LmdbGlobalState,
+        storage_timer::StorageTimer,
+    },
+    transaction_source::lmdb::LmdbEnvironment,
     trie_store::lmdb::LmdbTrieStore,
 };
 
-use casperlabs_engine_grpc_server::engine_server;
-use engine_storage::protocol_data_store::lmdb::LmdbProtocolDataStore;
+use casperlabs_engine_grpc_server::engine_server::{
+    self,
+    admission::{AdmissionConfig, AdmissionControlled, ClassLimits, RequestClass},
+    socket_path::ServerStartError,
+};
+use engine_storage::{
+    commit_sequence_store::lmdb::LmdbCommitSequenceStore,
+    protocol_data_store::lmdb::LmdbProtocolDataStore,
+    root_metadata_store::lmdb::LmdbRootMetadataStore,
+    staging_store::lmdb::LmdbStagingStore,
+    store_metadata_store::lmdb::{check_store_version, LmdbStoreMetadataStore},
+};
 
 // exe / proc
 const PROC_NAME: &str = "casperlabs-engine-grpc-server";
+const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
 const APP_NAME: &str = "CasperLabs Execution Engine Server";
 const SERVER_LISTENING_TEMPLATE: &str = "{listener} is listening on socket: {socket}";
-const SERVER_START_EXPECT: &str = "failed to start Execution Engine Server";
 
 // data-dir / lmdb
 const ARG_DATA_DIR: &str = "data-dir";
@@ -47,6 +64,10 @@ const CREATE_DATA_DIR_EXPECT: &str = "Could not create directory";
 const LMDB_ENVIRONMENT_EXPECT: &str = "Could not create LmdbEnvironment";
 const LMDB_TRIE_STORE_EXPECT: &str = "Could not create LmdbTrieStore";
 const LMDB_PROTOCOL_DATA_STORE_EXPECT: &str = "Could not create LmdbProtocolDataStore";
+const LMDB_COMMIT_SEQUENCE_STORE_EXPECT: &str = "Could not create LmdbCommitSequenceStore";
+const LMDB_ROOT_METADATA_STORE_EXPECT: &str = "Could not create LmdbRootMetadataStore";
+const LMDB_STAGING_STORE_EXPECT: &str = "Could not create LmdbStagingStore";
+const LMDB_STORE_METADATA_STORE_EXPECT: &str = "Could not create LmdbStoreMetadataStore";
 const LMDB_GLOBAL_STATE_EXPECT: &str = "Could not create LmdbGlobalState";
 
 // pages / lmdb
@@ -60,11 +81,21 @@ const GET_PAGES_EXPECT: &str = "Could not parse pages argument";
 // 805306368000 / 4096 = 196608000
 const DEFAULT_PAGES: usize = 196_608_000;
 
+// commit batch size / lmdb
+const ARG_COMMIT_BATCH_SIZE: &str = "commit-batch-size-bytes";
+const ARG_COMMIT_BATCH_SIZE_DEFAULT: &str = "67108864";
+const ARG_COMMIT_BATCH_SIZE_VALUE: &str = "BYTES";
+const ARG_COMMIT_BATCH_SIZE_HELP: &str =
+    "Sets how many bytes of trie nodes a commit writes per lmdb transaction";
+const ARG_COMMIT_BATCH_SIZE_EXPECT: &str = "expected valid commit batch size";
+
 // socket
 const ARG_SOCKET: &str = "socket";
 const ARG_SOCKET_HELP: &str =
     "Path to socket.  Note that this path is independent of the data directory.";
 const ARG_SOCKET_EXPECT: &str = "socket required";
+const ARG_CREATE_SOCKET_DIR: &str = "create-socket-dir";
+const ARG_CREATE_SOCKET_DIR_HELP: &str = "Creates the directory of the socket if it doesn't exist";
 
 // log level
 const ARG_LOG_LEVEL: &str = "log-level";
@@ -107,6 +138,94 @@ const ARG_ENABLE_BONDING: &str = "enable-bonding";
 const ARG_ENABLE_BONDING_SHORT: &str = "b";
 const ARG_ENABLE_BONDING_HELP: &str = "Enable bonding";
 
+// block time
+const ARG_MAX_BLOCK_TIME_SKEW: &str = "max-block-time-skew-millis";
+const ARG_MAX_BLOCK_TIME_SKEW_DEFAULT: &str = "86400000";
+const ARG_MAX_BLOCK_TIME_SKEW_VALUE: &str = "MILLIS";
+const ARG_MAX_BLOCK_TIME_SKEW_HELP: &str =
+    "Sets how far ahead of this machine's clock the block time of an execute request may be";
+const ARG_MAX_BLOCK_TIME_SKEW_EXPECT: &str = "expected valid max block time skew";
+
+// query cache
+const ARG_QUERY_CACHE_CAPACITY: &str = "query-cache-capacity-bytes";
+const ARG_QUERY_CACHE_CAPACITY_DEFAULT: &str = "67108864";
+const ARG_QUERY_CACHE_CAPACITY_VALUE: &str = "BYTES";
+const ARG_QUERY_CACHE_CAPACITY_HELP: &str =
+    "Sets the most bytes of serialized values held by the query result cache";
+const ARG_QUERY_CACHE_CAPACITY_EXPECT: &str = "expected valid query cache capacity";
+const ARG_DISABLE_QUERY_CACHE: &str = "disable-query-cache";
+const ARG_DISABLE_QUERY_CACHE_HELP: &str = "Disables caching of query results";
+
+// mint invariants
+const ARG_CHECK_MINT_INVARIANTS: &str = "check-mint-invariants-every";
+const ARG_CHECK_MINT_INVARIANTS_VALUE: &str = "COMMITS";
+const ARG_CHECK_MINT_INVARIANTS_HELP: &str =
+    "Debugging only: checks after every COMMITS commits that the mint's purses hold exactly the \
+     total supply, failing the commit if not";
+const ARG_CHECK_MINT_INVARIANTS_EXPECT: &str = "expected valid number of commits";
+
+// preload system contracts
+const ARG_PRELOAD_SYSTEM_CONTRACTS: &str = "preload-system-contracts";
+const ARG_PRELOAD_SYSTEM_CONTRACTS_VALUE: &str = "STATE_HASH";
+const ARG_PRELOAD_SYSTEM_CONTRACTS_HELP: &str =
+    "Loads the mint and proof of stake contracts active at the given hex-encoded state root \
+     before listening, so that the first execution doesn't pay for loading them";
+const ARG_PRELOAD_SYSTEM_CONTRACTS_EXPECT: &str = "expected valid hex-encoded state hash";
+
+// admission control
+const ARG_MAX_CONCURRENT_EXEC: &str = "max-concurrent-exec-requests";
+const ARG_MAX_CONCURRENT_EXEC_HELP: &str =
+    "Sets how many execute and commit requests may run at once; unlimited if not set";
+const ARG_MAX_QUEUED_EXEC: &str = "max-queued-exec-requests";
+const ARG_MAX_QUEUED_EXEC_HELP: &str =
+    "Sets how many more execute and commit requests may wait to run before further ones are \
+     answered as overloaded";
+const ARG_MAX_CONCURRENT_QUERY: &str = "max-concurrent-query-requests";
+const ARG_MAX_CONCURRENT_QUERY_HELP: &str =
+    "Sets how many query requests may run at once; unlimited if not set";
+const ARG_MAX_QUEUED_QUERY: &str = "max-queued-query-requests";
+const ARG_MAX_QUEUED_QUERY_HELP: &str =
+    "Sets how many more query requests may wait to run before further ones are answered as \
+     overloaded";
+const ARG_MAX_REQUESTS_VALUE: &str = "NUM";
+const ARG_MAX_QUEUED_DEFAULT: &str = "0";
+const ARG_MAX_REQUESTS_EXPECT: &str = "expected valid number of requests";
+const ARG_OVERLOADED_RETRY_AFTER: &str = "overloaded-retry-after-millis";
+const ARG_OVERLOADED_RETRY_AFTER_DEFAULT: &str = "1000";
+const ARG_OVERLOADED_RETRY_AFTER_VALUE: &str = "MILLIS";
+const ARG_OVERLOADED_RETRY_AFTER_HELP: &str =
+    "Sets how long clients are asked to wait before retrying a request answered as overloaded";
+const ARG_OVERLOADED_RETRY_AFTER_EXPECT: &str = "expected valid retry delay";
+
+// durability
+const ARG_DURABILITY: &str = "durability";
+const ARG_DURABILITY_VALUE: &str = "LEVEL";
+const ARG_DURABILITY_HELP: &str =
+    "Sets how durably commits are written to disk unless a commit asks otherwise: strict syncs \
+     after every commit, batched syncs every few commits and volatile never syncs, so is only fit \
+     for test networks";
+const DURABILITY_STRICT: &str = "strict";
+const DURABILITY_BATCHED: &str = "batched";
+const DURABILITY_VOLATILE: &str = "volatile";
+const ARG_MAX_SYNC_DELAY: &str = "durability-max-sync-delay-millis";
+const ARG_MAX_SYNC_DELAY_DEFAULT: &str = "1000";
+const ARG_MAX_SYNC_DELAY_VALUE: &str = "MILLIS";
+const ARG_MAX_SYNC_DELAY_HELP: &str = "Sets the longest time batched commits are left unsynced";
+const ARG_MAX_SYNC_DELAY_EXPECT: &str = "expected valid max sync delay";
+const ARG_MAX_UNSYNCED_COMMITS: &str = "durability-max-unsynced-commits";
+const ARG_MAX_UNSYNCED_COMMITS_DEFAULT: &str = "100";
+const ARG_MAX_UNSYNCED_COMMITS_VALUE: &str = "COMMITS";
+const ARG_MAX_UNSYNCED_COMMITS_HELP: &str = "Sets the most batched commits left unsynced";
+const ARG_MAX_UNSYNCED_COMMITS_EXPECT: &str = "expected valid number of commits";
+
+// storage timing
+const ARG_SLOW_STORAGE_OPERATION: &str = "slow-storage-operation-millis";
+const ARG_SLOW_STORAGE_OPERATION_DEFAULT: &str = "500";
+const ARG_SLOW_STORAGE_OPERATION_VALUE: &str = "MILLIS";
+const ARG_SLOW_STORAGE_OPERATION_HELP: &str =
+    "Sets how long a storage operation may take before it is logged as slow";
+const ARG_SLOW_STORAGE_OPERATION_EXPECT: &str = "expected valid slow storage operation threshold";
+
 // runnable
 const SIGINT_HANDLE_EXPECT: &str = "Error setting Ctrl-C handler";
 const RUNNABLE_CHECK_INTERVAL_SECONDS: u64 = 3;
@@ -122,20 +241,45 @@ fn main() {
 
     let socket = get_socket(&arg_matches);
 
-    match socket.remove_file() {
-        Err(e) => panic!("failed to remove old socket file: {:?}", e),
-        Ok(_) => info!("removing old socket file"),
-    };
+    let create_socket_dir = arg_matches.is_present(ARG_CREATE_SOCKET_DIR);
 
     let data_dir = get_data_dir(&arg_matches);
 
     let map_size = get_map_size(&arg_matches);
 
+    let commit_batch_size = get_commit_batch_size(&arg_matches);
+
     let thread_count = get_thread_count(&arg_matches);
 
     let engine_config: EngineConfig = get_engine_config(&arg_matches);
 
-    let _server = get_grpc_server(&socket, data_dir, map_size, thread_count, engine_config);
+    let sync_batching = get_sync_batching(&arg_matches);
+
+    let storage_timer = get_storage_timer(&arg_matches);
+
+    let preload_state_hash = get_preload_state_hash(&arg_matches);
+
+    let admission_config = get_admission_config(&arg_matches, thread_count);
+
+    let _server = match get_grpc_server(
+        &socket,
+        create_socket_dir,
+        data_dir,
+        map_size,
+        commit_batch_size,
+        thread_count,
+        engine_config,
+        sync_batching,
+        storage_timer,
+        preload_state_hash,
+        admission_config,
+    ) {
+        Ok(server) => server,
+        Err(error) => {
+            error!("failed to start Execution Engine Server: {}", error);
+            std::process::exit(error.exit_code());
+        }
+    };
 
     log_listening_message(&socket);
 
@@ -218,6 +362,14 @@ fn get_args() -> ArgMatches<'static> {
                 .help(ARG_PAGES_HELP)
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name(ARG_COMMIT_BATCH_SIZE)
+                .long(ARG_COMMIT_BATCH_SIZE)
+                .takes_value(true)
+                .default_value(ARG_COMMIT_BATCH_SIZE_DEFAULT)
+                .value_name(ARG_COMMIT_BATCH_SIZE_VALUE)
+                .help(ARG_COMMIT_BATCH_SIZE_HELP),
+        )
         .arg(
             Arg::with_name(ARG_THREAD_COUNT)
                 .short(ARG_THREAD_COUNT_SHORT)
@@ -239,12 +391,125 @@ fn get_args() -> ArgMatches<'static> {
                 .long(ARG_ENABLE_BONDING)
                 .help(ARG_ENABLE_BONDING_HELP),
         )
+        .arg(
+            Arg::with_name(ARG_MAX_BLOCK_TIME_SKEW)
+                .long(ARG_MAX_BLOCK_TIME_SKEW)
+                .takes_value(true)
+                .default_value(ARG_MAX_BLOCK_TIME_SKEW_DEFAULT)
+                .value_name(ARG_MAX_BLOCK_TIME_SKEW_VALUE)
+                .help(ARG_MAX_BLOCK_TIME_SKEW_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_QUERY_CACHE_CAPACITY)
+                .long(ARG_QUERY_CACHE_CAPACITY)
+                .takes_value(true)
+                .default_value(ARG_QUERY_CACHE_CAPACITY_DEFAULT)
+                .value_name(ARG_QUERY_CACHE_CAPACITY_VALUE)
+                .help(ARG_QUERY_CACHE_CAPACITY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_DISABLE_QUERY_CACHE)
+                .long(ARG_DISABLE_QUERY_CACHE)
+                .help(ARG_DISABLE_QUERY_CACHE_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_CHECK_MINT_INVARIANTS)
+                .long(ARG_CHECK_MINT_INVARIANTS)
+                .takes_value(true)
+                .value_name(ARG_CHECK_MINT_INVARIANTS_VALUE)
+                .help(ARG_CHECK_MINT_INVARIANTS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_PRELOAD_SYSTEM_CONTRACTS)
+                .long(ARG_PRELOAD_SYSTEM_CONTRACTS)
+                .takes_value(true)
+                .value_name(ARG_PRELOAD_SYSTEM_CONTRACTS_VALUE)
+                .help(ARG_PRELOAD_SYSTEM_CONTRACTS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_CONCURRENT_EXEC)
+                .long(ARG_MAX_CONCURRENT_EXEC)
+                .takes_value(true)
+                .value_name(ARG_MAX_REQUESTS_VALUE)
+                .help(ARG_MAX_CONCURRENT_EXEC_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_QUEUED_EXEC)
+                .long(ARG_MAX_QUEUED_EXEC)
+                .takes_value(true)
+                .default_value(ARG_MAX_QUEUED_DEFAULT)
+                .value_name(ARG_MAX_REQUESTS_VALUE)
+                .help(ARG_MAX_QUEUED_EXEC_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_CONCURRENT_QUERY)
+                .long(ARG_MAX_CONCURRENT_QUERY)
+                .takes_value(true)
+                .value_name(ARG_MAX_REQUESTS_VALUE)
+                .help(ARG_MAX_CONCURRENT_QUERY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_QUEUED_QUERY)
+                .long(ARG_MAX_QUEUED_QUERY)
+                .takes_value(true)
+                .default_value(ARG_MAX_QUEUED_DEFAULT)
+                .value_name(ARG_MAX_REQUESTS_VALUE)
+                .help(ARG_MAX_QUEUED_QUERY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_OVERLOADED_RETRY_AFTER)
+                .long(ARG_OVERLOADED_RETRY_AFTER)
+                .takes_value(true)
+                .default_value(ARG_OVERLOADED_RETRY_AFTER_DEFAULT)
+                .value_name(ARG_OVERLOADED_RETRY_AFTER_VALUE)
+                .help(ARG_OVERLOADED_RETRY_AFTER_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_DURABILITY)
+                .long(ARG_DURABILITY)
+                .takes_value(true)
+                .possible_value(DURABILITY_STRICT)
+                .possible_value(DURABILITY_BATCHED)
+                .possible_value(DURABILITY_VOLATILE)
+                .default_value(DURABILITY_STRICT)
+                .value_name(ARG_DURABILITY_VALUE)
+                .help(ARG_DURABILITY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_SYNC_DELAY)
+                .long(ARG_MAX_SYNC_DELAY)
+                .takes_value(true)
+                .default_value(ARG_MAX_SYNC_DELAY_DEFAULT)
+                .value_name(ARG_MAX_SYNC_DELAY_VALUE)
+                .help(ARG_MAX_SYNC_DELAY_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_MAX_UNSYNCED_COMMITS)
+                .long(ARG_MAX_UNSYNCED_COMMITS)
+                .takes_value(true)
+                .default_value(ARG_MAX_UNSYNCED_COMMITS_DEFAULT)
+                .value_name(ARG_MAX_UNSYNCED_COMMITS_VALUE)
+                .help(ARG_MAX_UNSYNCED_COMMITS_HELP),
+        )
+        .arg(
+            Arg::with_name(ARG_SLOW_STORAGE_OPERATION)
+                .long(ARG_SLOW_STORAGE_OPERATION)
+                .takes_value(true)
+                .default_value(ARG_SLOW_STORAGE_OPERATION_DEFAULT)
+                .value_name(ARG_SLOW_STORAGE_OPERATION_VALUE)
+                .help(ARG_SLOW_STORAGE_OPERATION_HELP),
+        )
         .arg(
             Arg::with_name(ARG_SOCKET)
                 .required(true)
                 .help(ARG_SOCKET_HELP)
                 .index(1),
         )
+        .arg(
+            Arg::with_name(ARG_CREATE_SOCKET_DIR)
+                .long(ARG_CREATE_SOCKET_DIR)
+                .help(ARG_CREATE_SOCKET_DIR_HELP),
+        )
         .get_matches()
 }
 
@@ -291,6 +556,14 @@ fn get_map_size(arg_matches: &ArgMatches) -> usize {
     page_size * pages
 }
 
+fn get_commit_batch_size(arg_matches: &ArgMatches) -> usize {
+    arg_matches
+        .value_of(ARG_COMMIT_BATCH_SIZE)
+        .map(usize::from_str)
+        .expect(ARG_COMMIT_BATCH_SIZE_EXPECT)
+        .expect(ARG_COMMIT_BATCH_SIZE_EXPECT)
+}
+
 fn get_thread_count(arg_matches: &ArgMatches) -> usize {
     arg_matches
         .value_of(ARG_THREAD_COUNT)
@@ -304,34 +577,212 @@ fn get_engine_config(arg_matches: &ArgMatches) -> EngineConfig {
     // feature flags go here
     let use_system_contracts = arg_matches.is_present(ARG_USE_SYSTEM_CONTRACTS);
     let enable_bonding = arg_matches.is_present(ARG_ENABLE_BONDING);
-    EngineConfig::new()
+    let max_block_time_skew = arg_matches
+        .value_of(ARG_MAX_BLOCK_TIME_SKEW)
+        .map(u64::from_str)
+        .expect(ARG_MAX_BLOCK_TIME_SKEW_EXPECT)
+        .map(Duration::from_millis)
+        .expect(ARG_MAX_BLOCK_TIME_SKEW_EXPECT);
+    let mut engine_config = EngineConfig::new()
         .with_use_system_contracts(use_system_contracts)
         .with_enable_bonding(enable_bonding)
+        .with_max_block_time_skew(max_block_time_skew);
+    if !arg_matches.is_present(ARG_DISABLE_QUERY_CACHE) {
+        let query_cache_capacity = arg_matches
+            .value_of(ARG_QUERY_CACHE_CAPACITY)
+            .map(usize::from_str)
+            .expect(ARG_QUERY_CACHE_CAPACITY_EXPECT)
+            .expect(ARG_QUERY_CACHE_CAPACITY_EXPECT);
+        engine_config = engine_config.with_query_cache_capacity(query_cache_capacity);
+    }
+    if let Some(every_nth_commit) = arg_matches.value_of(ARG_CHECK_MINT_INVARIANTS) {
+        let every_nth_commit =
+            u64::from_str(every_nth_commit).expect(ARG_CHECK_MINT_INVARIANTS_EXPECT);
+        engine_config = engine_config.with_mint_invariant_checks(every_nth_commit);
+    }
+    let durability = match arg_matches.value_of(ARG_DURABILITY) {
+        Some(DURABILITY_BATCHED) => Durability::Batched,
+        Some(DURABILITY_VOLATILE) => {
+            warn!("commits are never synced to disk, so may be lost: only fit for test networks");
+            Durability::Volatile
+        }
+        _ => Durability::Strict,
+    };
+    engine_config.with_durability(durability)
+}
+
+/// Returns the [`SyncBatching`] of commits made with [`Durability::Batched`].
+fn get_sync_batching(arg_matches: &ArgMatches) -> SyncBatching {
+    let max_sync_delay = arg_matches
+        .value_of(ARG_MAX_SYNC_DELAY)
+        .map(u64::from_str)
+        .expect(ARG_MAX_SYNC_DELAY_EXPECT)
+        .map(Duration::from_millis)
+        .expect(ARG_MAX_SYNC_DELAY_EXPECT);
+    let max_unsynced_commits = arg_matches
+        .value_of(ARG_MAX_UNSYNCED_COMMITS)
+        .map(u64::from_str)
+        .expect(ARG_MAX_UNSYNCED_COMMITS_EXPECT)
+        .expect(ARG_MAX_UNSYNCED_COMMITS_EXPECT);
+    SyncBatching {
+        max_sync_delay,
+        max_unsynced_commits,
+    }
+}
+
+/// Returns the [`StorageTimer`] warning about slow storage operations.
+fn get_storage_timer(arg_matches: &ArgMatches) -> StorageTimer {
+    let slow_operation_threshold = arg_matches
+        .value_of(ARG_SLOW_STORAGE_OPERATION)
+        .map(u64::from_str)
+        .expect(ARG_SLOW_STORAGE_OPERATION_EXPECT)
+        .map(Duration::from_millis)
+        .expect(ARG_SLOW_STORAGE_OPERATION_EXPECT);
+    StorageTimer::new(slow_operation_threshold)
+}
+
+/// Parses the preload-system-contracts argument, if present.
+fn get_preload_state_hash(arg_matches: &ArgMatches) -> Option<Blake2bHash> {
+    arg_matches
+        .value_of(ARG_PRELOAD_SYSTEM_CONTRACTS)
+        .map(|state_hash| {
+            let bytes = base16::decode(state_hash).expect(ARG_PRELOAD_SYSTEM_CONTRACTS_EXPECT);
+            Blake2bHash::try_from(bytes.as_slice()).expect(ARG_PRELOAD_SYSTEM_CONTRACTS_EXPECT)
+        })
+}
+
+/// Parses the limits of one class of requests, which is unlimited unless its max concurrent
+/// requests argument is present.
+fn get_class_limits(
+    arg_matches: &ArgMatches,
+    max_concurrent_arg: &str,
+    max_queued_arg: &str,
+) -> ClassLimits {
+    let parse = |arg| usize::from_str(arg).expect(ARG_MAX_REQUESTS_EXPECT);
+    match arg_matches.value_of(max_concurrent_arg) {
+        Some(max_concurrent) => {
+            let max_queued = arg_matches
+                .value_of(max_queued_arg)
+                .expect(ARG_MAX_REQUESTS_EXPECT);
+            ClassLimits::new(parse(max_concurrent), parse(max_queued))
+        }
+        None => ClassLimits::unlimited(),
+    }
+}
+
+/// Returns an [`AdmissionConfig`], warning if the server has too few threads for it.
+fn get_admission_config(arg_matches: &ArgMatches, thread_count: usize) -> AdmissionConfig {
+    let retry_after = arg_matches
+        .value_of(ARG_OVERLOADED_RETRY_AFTER)
+        .map(u64::from_str)
+        .expect(ARG_OVERLOADED_RETRY_AFTER_EXPECT)
+        .map(Duration::from_millis)
+        .expect(ARG_OVERLOADED_RETRY_AFTER_EXPECT);
+    let admission_config = AdmissionConfig::new()
+        .with_exec_limits(get_class_limits(
+            arg_matches,
+            ARG_MAX_CONCURRENT_EXEC,
+            ARG_MAX_QUEUED_EXEC,
+        ))
+        .with_query_limits(get_class_limits(
+            arg_matches,
+            ARG_MAX_CONCURRENT_QUERY,
+            ARG_MAX_QUEUED_QUERY,
+        ))
+        .with_retry_after(retry_after);
+
+    // exec and query requests share the server's threads, so an admitted request only starts
+    // promptly if there is a thread for every request admitted across the limited classes
+    let max_admitted = [RequestClass::Exec, RequestClass::Query]
+        .iter()
+        .map(|class| admission_config.limits(*class).max_admitted())
+        .filter(|max_admitted| *max_admitted != usize::max_value())
+        .fold(0usize, usize::saturating_add);
+    if max_admitted > thread_count {
+        warn!(
+            "up to {} requests are admitted, but only {} threads serve requests",
+            max_admitted, thread_count
+        );
+    }
+    admission_config
 }
 
 /// Builds and returns a gRPC server.
+#[allow(clippy::too_many_arguments)]
 fn get_grpc_server(
     socket: &socket::Socket,
+    create_socket_dir: bool,
     data_dir: PathBuf,
     map_size: usize,
+    commit_batch_size: usize,
     thread_count: usize,
     engine_config: EngineConfig,
-) -> grpc::Server {
-    let engine_state = get_engine_state(data_dir, map_size, engine_config);
+    sync_batching: SyncBatching,
+    storage_timer: StorageTimer,
+    preload_state_hash: Option<Blake2bHash>,
+    admission_config: AdmissionConfig,
+) -> Result<grpc::Server, ServerStartError> {
+    let engine_state = get_engine_state(
+        data_dir,
+        map_size,
+        commit_batch_size,
+        engine_config,
+        sync_batching,
+        storage_timer,
+    )?;
+
+    if let Some(state_hash) = preload_state_hash {
+        preload_system_contracts(&engine_state, state_hash);
+    }
+
+    engine_server::try_new(
+        socket.as_str(),
+        thread_count,
+        AdmissionControlled::new(engine_state, admission_config),
+        create_socket_dir,
+    )?
+    .build()
+    .map_err(ServerStartError::from)
+}
 
-    engine_server::new(socket.as_str(), thread_count, engine_state)
-        .build()
-        .expect(SERVER_START_EXPECT)
+/// Loads the system contracts active at `state_hash` into the engine's cache.
+///
+/// Failures are only logged, as the node may go on to execute against a different root.
+fn preload_system_contracts(engine_state: &EngineState<LmdbGlobalState>, state_hash: Blake2bHash) {
+    let start = Instant::now();
+    match engine_state.preload_system_contracts(CorrelationId::new(), state_hash) {
+        Ok(Some(protocol_version)) => info!(
+            "preloaded system contracts of protocol version {} at state root {} in {:?}",
+            protocol_version,
+            state_hash,
+            start.elapsed()
+        ),
+        Ok(None) => warn!(
+            "not preloading system contracts: state root {} not found or records no protocol \
+             version",
+            state_hash
+        ),
+        Err(error) => warn!(
+            "failed to preload system contracts at state root {}: {}",
+            state_hash, error
+        ),
+    }
 }
 
-/// Builds and returns engine global state
+/// Builds and returns engine global state, failing if the stores in `data_dir` can't be opened by
+/// this version of the engine.
 fn get_engine_state(
     data_dir: PathBuf,
     map_size: usize,
+    commit_batch_size: usize,
     engine_config: EngineConfig,
-) -> EngineState<LmdbGlobalState> {
+    sync_batching: SyncBatching,
+    storage_timer: StorageTimer,
+) -> Result<EngineState<LmdbGlobalState>, ServerStartError> {
     let environment = {
-        let ret = LmdbEnvironment::new(&data_dir, map_size).expect(LMDB_ENVIRONMENT_EXPECT);
+        let ret =
+            LmdbEnvironment::new_with_durability(&data_dir, map_size, engine_config.durability())
+                .expect(LMDB_ENVIRONMENT_EXPECT);
         Arc::new(ret)
     };
 
@@ -341,16 +792,62 @@ fn get_engine_state(
         Arc::new(ret)
     };
 
+    let store_metadata_store =
+        LmdbStoreMetadataStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_STORE_METADATA_STORE_EXPECT);
+    let store_metadata = check_store_version(
+        &environment,
+        &store_metadata_store,
+        &trie_store,
+        ENGINE_VERSION,
+    )
+    .map_err(|error| ServerStartError::Store {
+        path: data_dir.clone(),
+        error,
+    })?;
+    info!(
+        "opened stores of schema version {} last initialized by engine version {}",
+        store_metadata.schema_version, store_metadata.engine_version
+    );
+
     let protocol_data_store = {
         let ret = LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
             .expect(LMDB_PROTOCOL_DATA_STORE_EXPECT);
         Arc::new(ret)
     };
 
-    let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-        .expect(LMDB_GLOBAL_STATE_EXPECT);
+    let commit_sequence_store = {
+        let ret = LmdbCommitSequenceStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_COMMIT_SEQUENCE_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let root_metadata_store = {
+        let ret = LmdbRootMetadataStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_ROOT_METADATA_STORE_EXPECT);
+        Arc::new(ret)
+    };
+
+    let staging_store = {
+        let ret = LmdbStagingStore::new(&environment, None, DatabaseFlags::empty())
+            .expect(LMDB_STAGING_STORE_EXPECT);
+        Arc::new(ret)
+    };
 
-    EngineState::new(global_state, engine_config)
+    let global_state = LmdbGlobalState::empty(
+        environment,
+        trie_store,
+        protocol_data_store,
+        commit_sequence_store,
+        root_metadata_store,
+        staging_store,
+    )
+    .expect(LMDB_GLOBAL_STATE_EXPECT)
+    .with_commit_batch_size(commit_batch_size)
+    .with_sync_batching(sync_batching)
+    .with_storage_timer(storage_timer);
+
+    Ok(EngineState::new(global_state, engine_config))
 }
 
 /// Builds and returns log settings
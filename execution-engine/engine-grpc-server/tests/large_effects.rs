@@ -0,0 +1,131 @@
+//! Counts the bytes allocated while turning a deploy which writes many large values into the
+//! protobuf `DeployResult` sent to the node, to check the written values aren't copied on the way.
+//!
+//! This is kept in its own test binary as it installs a counting global allocator.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use protobuf::Message;
+
+use engine_core::engine_state::{
+    execution_effect::ExecutionEffect,
+    execution_result::{ExecutionResult, ExecutionResultBuilder},
+    op::Op,
+};
+use engine_grpc_server::engine_server::ipc;
+use engine_shared::{
+    additive_map::AdditiveMap, gas::Gas, newtypes::CorrelationId, stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, StateProvider};
+use types::{CLValue, Key};
+
+const WRITE_COUNT: usize = 1_000;
+const VALUE_SIZE: usize = 64 * 1024;
+const PAYLOAD_SIZE: usize = WRITE_COUNT * VALUE_SIZE;
+
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the result of `f` and the number of bytes allocated while running it.
+fn allocated_bytes_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATED_BYTES.load(Ordering::SeqCst);
+    let result = f();
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::SeqCst) - before;
+    (result, allocated_bytes)
+}
+
+fn large_writes() -> ExecutionEffect {
+    let mut ops = AdditiveMap::new();
+    let mut transforms = AdditiveMap::new();
+    for index in 0..WRITE_COUNT {
+        let mut hash = [0u8; 32];
+        hash[..8].copy_from_slice(&(index as u64).to_le_bytes());
+        let key = Key::Hash(hash);
+        let cl_value = CLValue::from_t(vec![index as u8; VALUE_SIZE]).expect("should create value");
+        ops.insert(key, Op::Write);
+        transforms.insert(key, Transform::Write(StoredValue::CLValue(cl_value)));
+    }
+    ExecutionEffect::new(ops, transforms)
+}
+
+fn success(effect: ExecutionEffect) -> ExecutionResult {
+    ExecutionResult::Success {
+        effect,
+        cost: Gas::default(),
+        return_value: None,
+    }
+}
+
+#[test]
+fn should_not_copy_large_writes_into_deploy_result() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let reader = global_state
+        .checkout(global_state.empty_root_hash)
+        .expect("should checkout")
+        .expect("should have empty root");
+
+    let session_effect = large_writes();
+    let expected_pb_effect = ipc::ExecutionEffect::from(&session_effect);
+
+    // merging the payment, session and finalize effects and converting the result moves the
+    // written values, so allocates only a small fraction of their size
+    let mut builder = ExecutionResultBuilder::new();
+    builder
+        .set_payment_execution_result(success(ExecutionEffect::default()))
+        .set_session_execution_result(success(session_effect))
+        .set_finalize_execution_result(success(ExecutionEffect::default()));
+    let (mut deploy_result, allocated_bytes) = allocated_bytes_during(|| {
+        let execution_result = builder
+            .build(&reader, CorrelationId::new())
+            .expect("should build execution result");
+        ipc::DeployResult::from(execution_result)
+    });
+    assert!(
+        allocated_bytes < PAYLOAD_SIZE / 10,
+        "converting {} bytes of writes allocated {} bytes",
+        PAYLOAD_SIZE,
+        allocated_bytes
+    );
+
+    let pb_effect = deploy_result.mut_execution_result().take_effects();
+    assert_eq!(pb_effect.get_transform_map().len(), WRITE_COUNT);
+
+    // serializing copies the written values exactly once, into the output buffer
+    let (bytes, allocated_bytes) = allocated_bytes_during(|| {
+        pb_effect
+            .write_to_bytes()
+            .expect("should serialize effects")
+    });
+    assert!(
+        allocated_bytes < PAYLOAD_SIZE + PAYLOAD_SIZE / 10,
+        "serializing {} bytes of writes allocated {} bytes",
+        PAYLOAD_SIZE,
+        allocated_bytes
+    );
+
+    let mut expected_transforms = expected_pb_effect.get_transform_map().to_vec();
+    let mut transforms = pb_effect.get_transform_map().to_vec();
+    expected_transforms.sort_by_key(|entry| entry.get_key().write_to_bytes().unwrap());
+    transforms.sort_by_key(|entry| entry.get_key().write_to_bytes().unwrap());
+    assert_eq!(transforms, expected_transforms);
+    assert!(bytes.len() > PAYLOAD_SIZE);
+}
@@ -1,5 +1,7 @@
+mod account_config;
 mod action_thresholds;
 mod associated_keys;
+mod auth_info;
 
 use std::collections::{BTreeMap, BTreeSet};
 
@@ -12,8 +14,10 @@ use types::{
     AccessRights, Key, URef,
 };
 
+pub use account_config::{AccountConfig, ACCOUNT_CONFIG_SERIALIZED_LENGTH};
 pub use action_thresholds::ActionThresholds;
 pub use associated_keys::AssociatedKeys;
+pub use auth_info::AccountAuthInfo;
 
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub struct Account {
@@ -22,6 +26,7 @@ pub struct Account {
     main_purse: URef,
     associated_keys: AssociatedKeys,
     action_thresholds: ActionThresholds,
+    authorization_contract: Option<Key>,
 }
 
 impl Account {
@@ -38,12 +43,25 @@ impl Account {
             main_purse,
             associated_keys,
             action_thresholds,
+            authorization_contract: None,
         }
     }
 
     pub fn create(account: PublicKey, named_keys: BTreeMap<String, Key>, main_purse: URef) -> Self {
-        let associated_keys = AssociatedKeys::new(account, Weight::new(1));
-        let action_thresholds: ActionThresholds = Default::default();
+        Account::create_with_config(account, named_keys, main_purse, &AccountConfig::default())
+    }
+
+    /// Creates a new account with the associated key and action thresholds given by
+    /// `account_config`.
+    pub fn create_with_config(
+        account: PublicKey,
+        named_keys: BTreeMap<String, Key>,
+        main_purse: URef,
+        account_config: &AccountConfig,
+    ) -> Self {
+        let associated_keys =
+            AssociatedKeys::new(account, account_config.default_identity_key_weight());
+        let action_thresholds = account_config.default_action_thresholds();
         Account::new(
             account,
             named_keys,
@@ -73,6 +91,23 @@ impl Account {
         self.main_purse
     }
 
+    pub fn set_main_purse(&mut self, main_purse: URef) {
+        self.main_purse = main_purse;
+    }
+
+    /// Returns the key of the stored contract which must approve deploys sent by this account, if
+    /// any.
+    ///
+    /// Deploys authorized with keys meeting the key management threshold don't run the contract,
+    /// so it can always be replaced or cleared.
+    pub fn authorization_contract(&self) -> Option<Key> {
+        self.authorization_contract
+    }
+
+    pub fn set_authorization_contract(&mut self, authorization_contract: Option<Key>) {
+        self.authorization_contract = authorization_contract;
+    }
+
     /// Returns an [`AccessRights::ADD`]-only version of the [`URef`].
     pub fn main_purse_add_only(&self) -> URef {
         URef::new(self.main_purse.addr(), AccessRights::ADD)
@@ -207,6 +242,11 @@ impl ToBytes for Account {
         result.append(&mut self.main_purse.to_bytes()?);
         result.append(&mut self.associated_keys.to_bytes()?);
         result.append(&mut self.action_thresholds.to_bytes()?);
+        // a trailing field which is omitted when unset, so accounts without an authorization
+        // contract serialize as they did before the field was added
+        if let Some(authorization_contract) = self.authorization_contract {
+            result.append(&mut authorization_contract.to_bytes()?);
+        }
         Ok(result)
     }
 
@@ -216,6 +256,10 @@ impl ToBytes for Account {
             + self.main_purse.serialized_length()
             + self.associated_keys.serialized_length()
             + self.action_thresholds.serialized_length()
+            + self
+                .authorization_contract
+                .map(|key| key.serialized_length())
+                .unwrap_or_default()
     }
 }
 
@@ -226,6 +270,14 @@ impl FromBytes for Account {
         let (main_purse, rem) = URef::from_bytes(rem)?;
         let (associated_keys, rem) = AssociatedKeys::from_bytes(rem)?;
         let (action_thresholds, rem) = ActionThresholds::from_bytes(rem)?;
+        // an account is only ever serialized as the last field of a stored value, so any remaining
+        // bytes hold the authorization contract
+        let (authorization_contract, rem) = if rem.is_empty() {
+            (None, rem)
+        } else {
+            let (key, rem) = Key::from_bytes(rem)?;
+            (Some(key), rem)
+        };
         Ok((
             Account {
                 public_key,
@@ -233,6 +285,7 @@ impl FromBytes for Account {
                 main_purse,
                 associated_keys,
                 action_thresholds,
+                authorization_contract,
             },
             rem,
         ))
@@ -244,10 +297,11 @@ pub mod gens {
 
     use types::{
         account::MAX_ASSOCIATED_KEYS,
-        gens::{named_keys_arb, public_key_arb, uref_arb},
+        gens::{key_arb, named_keys_arb, public_key_arb, uref_arb},
     };
 
     use super::*;
+    pub use crate::account::account_config::gens::account_config_arb;
     use crate::account::{
         action_thresholds::gens::action_thresholds_arb, associated_keys::gens::associated_keys_arb,
     };
@@ -255,19 +309,25 @@ pub mod gens {
     prop_compose! {
         pub fn account_arb()(
             pub_key in public_key_arb(),
-            urefs in named_keys_arb(3),
+            urefs in named_keys_arb(0..10),
             purse in uref_arb(),
             thresholds in action_thresholds_arb(),
-            mut associated_keys in associated_keys_arb(MAX_ASSOCIATED_KEYS - 1),
+            mut associated_keys in associated_keys_arb(0..MAX_ASSOCIATED_KEYS),
+            authorization_contract in proptest::option::of(key_arb()),
         ) -> Account {
-                associated_keys.add_key(pub_key, Weight::new(1)).unwrap();
-                Account::new(
+                // the account's own key may already have been generated as an associated key
+                if !associated_keys.contains_key(&pub_key) {
+                    associated_keys.add_key(pub_key, Weight::new(1)).unwrap();
+                }
+                let mut account = Account::new(
                     pub_key,
                     urefs,
                     purse,
                     associated_keys,
                     thresholds,
-                )
+                );
+                account.set_authorization_contract(authorization_contract);
+                account
         }
     }
 }
@@ -645,4 +705,33 @@ mod tests {
             .update_associated_key(key_1, Weight::new(1))
             .expect("should work");
     }
+
+    #[test]
+    fn should_omit_unset_authorization_contract_from_bytes() {
+        let identity_key = PublicKey::ed25519_from([1u8; 32]);
+        let mut account = Account::create(
+            identity_key,
+            BTreeMap::new(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+        );
+        let bytes = account.to_bytes().expect("should serialize");
+
+        let mut legacy_bytes = identity_key.to_bytes().unwrap();
+        legacy_bytes.append(&mut BTreeMap::<String, Key>::new().to_bytes().unwrap());
+        legacy_bytes.append(&mut account.main_purse().to_bytes().unwrap());
+        legacy_bytes.append(&mut account.associated_keys.to_bytes().unwrap());
+        legacy_bytes.append(&mut account.action_thresholds().to_bytes().unwrap());
+        assert_eq!(bytes, legacy_bytes);
+
+        account.set_authorization_contract(Some(Key::Hash([2u8; 32])));
+        let bytes = account.to_bytes().expect("should serialize");
+        assert_eq!(
+            bytes.len(),
+            legacy_bytes.len() + Key::Hash([2u8; 32]).serialized_length()
+        );
+        assert_eq!(
+            bytesrepr::deserialize::<Account>(bytes).expect("should deserialize"),
+            account
+        );
+    }
 }
@@ -0,0 +1,151 @@
+use types::{
+    account::{Weight, WEIGHT_SERIALIZED_LENGTH},
+    bytesrepr::{self, Error, FromBytes, ToBytes},
+};
+
+use super::ActionThresholds;
+
+pub const ACCOUNT_CONFIG_SERIALIZED_LENGTH: usize = 3 * WEIGHT_SERIALIZED_LENGTH;
+
+/// Configuration of the accounts created by a transfer to a public key which has no account yet.
+///
+/// A new account has its public key as its only associated key, with a weight of
+/// `default_identity_key_weight`, and the `default_action_thresholds`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct AccountConfig {
+    default_deployment_threshold: Weight,
+    default_key_management_threshold: Weight,
+    default_identity_key_weight: Weight,
+}
+
+impl AccountConfig {
+    pub fn new(
+        default_deployment_threshold: Weight,
+        default_key_management_threshold: Weight,
+        default_identity_key_weight: Weight,
+    ) -> Self {
+        AccountConfig {
+            default_deployment_threshold,
+            default_key_management_threshold,
+            default_identity_key_weight,
+        }
+    }
+
+    /// Returns the action thresholds of a new account.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the config is not [valid](AccountConfig::is_valid).
+    pub fn default_action_thresholds(&self) -> ActionThresholds {
+        ActionThresholds::new(
+            self.default_deployment_threshold,
+            self.default_key_management_threshold,
+        )
+        .expect("account config should be valid")
+    }
+
+    pub fn default_deployment_threshold(&self) -> Weight {
+        self.default_deployment_threshold
+    }
+
+    pub fn default_key_management_threshold(&self) -> Weight {
+        self.default_key_management_threshold
+    }
+
+    pub fn default_identity_key_weight(&self) -> Weight {
+        self.default_identity_key_weight
+    }
+
+    /// Returns `true` if the thresholds are consistent, and the identity key of a new account can
+    /// meet both of them on its own, so that the account is usable.
+    pub fn is_valid(&self) -> bool {
+        self.default_deployment_threshold <= self.default_key_management_threshold
+            && self.default_key_management_threshold <= self.default_identity_key_weight
+    }
+}
+
+impl Default for AccountConfig {
+    fn default() -> Self {
+        AccountConfig {
+            default_deployment_threshold: Weight::new(1),
+            default_key_management_threshold: Weight::new(1),
+            default_identity_key_weight: Weight::new(1),
+        }
+    }
+}
+
+impl ToBytes for AccountConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = bytesrepr::unchecked_allocate_buffer(self);
+        result.append(&mut self.default_deployment_threshold.to_bytes()?);
+        result.append(&mut self.default_key_management_threshold.to_bytes()?);
+        result.append(&mut self.default_identity_key_weight.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        ACCOUNT_CONFIG_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for AccountConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (default_deployment_threshold, rem) = Weight::from_bytes(bytes)?;
+        let (default_key_management_threshold, rem) = Weight::from_bytes(rem)?;
+        let (default_identity_key_weight, rem) = Weight::from_bytes(rem)?;
+        let ret = AccountConfig {
+            default_deployment_threshold,
+            default_key_management_threshold,
+            default_identity_key_weight,
+        };
+        Ok((ret, rem))
+    }
+}
+
+pub mod gens {
+    use proptest::prelude::*;
+
+    use types::account::Weight;
+
+    use super::AccountConfig;
+
+    pub fn account_config_arb() -> impl Strategy<Value = AccountConfig> {
+        (any::<u8>(), any::<u8>(), any::<u8>()).prop_map(
+            |(deployment_threshold, key_management_threshold, identity_key_weight)| {
+                AccountConfig::new(
+                    Weight::new(deployment_threshold),
+                    Weight::new(key_management_threshold),
+                    Weight::new(identity_key_weight),
+                )
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_should_be_valid() {
+        assert!(AccountConfig::default().is_valid());
+    }
+
+    #[test]
+    fn should_not_be_valid_if_identity_key_cannot_meet_thresholds() {
+        let account_config = AccountConfig::new(Weight::new(1), Weight::new(2), Weight::new(1));
+        assert!(!account_config.is_valid());
+    }
+
+    #[test]
+    fn should_not_be_valid_with_deployment_above_key_management_threshold() {
+        let account_config = AccountConfig::new(Weight::new(2), Weight::new(1), Weight::new(2));
+        assert!(!account_config.is_valid());
+    }
+
+    #[test]
+    fn serialization_roundtrip() {
+        let account_config = AccountConfig::new(Weight::new(1), Weight::new(2), Weight::new(3));
+        bytesrepr::test_serialization_roundtrip(&account_config);
+    }
+}
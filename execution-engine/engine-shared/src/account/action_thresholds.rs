@@ -119,13 +119,25 @@ pub mod gens {
 
     use super::ActionThresholds;
 
+    use types::gens::weight_arb;
+
     pub fn action_thresholds_arb() -> impl Strategy<Value = ActionThresholds> {
-        Just(Default::default())
+        (weight_arb(), weight_arb()).prop_map(|(first, second)| {
+            let (deployment, key_management) = if first <= second {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            ActionThresholds::new(deployment, key_management)
+                .expect("deployment threshold should not exceed key management threshold")
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::proptest;
+
     use super::*;
 
     #[test]
@@ -146,4 +158,13 @@ mod tests {
         let action_thresholds = ActionThresholds::new(Weight::new(1), Weight::new(42)).unwrap();
         bytesrepr::test_serialization_roundtrip(&action_thresholds);
     }
+
+    proptest! {
+        #[test]
+        fn generated_serialization_roundtrip(
+            action_thresholds in gens::action_thresholds_arb()
+        ) {
+            bytesrepr::test_serialization_roundtrip(&action_thresholds);
+        }
+    }
 }
@@ -129,13 +129,15 @@ impl FromBytes for AssociatedKeys {
 }
 
 pub mod gens {
-    use proptest::prelude::*;
+    use proptest::{collection::SizeRange, prelude::*};
 
     use types::gens::{public_key_arb, weight_arb};
 
     use super::AssociatedKeys;
 
-    pub fn associated_keys_arb(size: usize) -> impl Strategy<Value = AssociatedKeys> {
+    pub fn associated_keys_arb(
+        size: impl Into<SizeRange>,
+    ) -> impl Strategy<Value = AssociatedKeys> {
         proptest::collection::btree_map(public_key_arb(), weight_arb(), size).prop_map(|keys| {
             let mut associated_keys = AssociatedKeys::default();
             keys.into_iter().for_each(|(k, v)| {
@@ -150,12 +152,14 @@ pub mod gens {
 mod tests {
     use std::{collections::BTreeSet, iter::FromIterator};
 
+    use proptest::proptest;
+
     use types::{
         account::{AddKeyFailure, PublicKey, Weight, ED25519_LENGTH, MAX_ASSOCIATED_KEYS},
         bytesrepr,
     };
 
-    use super::AssociatedKeys;
+    use super::{gens, AssociatedKeys};
 
     #[test]
     fn associated_keys_add() {
@@ -327,4 +331,21 @@ mod tests {
             .unwrap();
         bytesrepr::test_serialization_roundtrip(&keys);
     }
+
+    proptest! {
+        #[test]
+        fn generated_serialization_roundtrip(
+            keys in gens::associated_keys_arb(0..=MAX_ASSOCIATED_KEYS)
+        ) {
+            bytesrepr::test_serialization_roundtrip(&keys);
+        }
+
+        #[test]
+        fn generated_max_size_serialization_roundtrip(
+            keys in gens::associated_keys_arb(MAX_ASSOCIATED_KEYS)
+        ) {
+            assert_eq!(keys.len(), MAX_ASSOCIATED_KEYS);
+            bytesrepr::test_serialization_roundtrip(&keys);
+        }
+    }
 }
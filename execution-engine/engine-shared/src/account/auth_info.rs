@@ -0,0 +1,28 @@
+use types::account::{PublicKey, Weight};
+
+use super::Account;
+
+/// The keys and thresholds which authorize the deploys and the key management of an account.
+///
+/// Unlike [`Account`], its layout doesn't change with the other fields of an account, so that it
+/// can be relied upon by clients and tests checking the keys of an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountAuthInfo {
+    /// The associated keys with their weights, in the order of the keys.
+    pub associated_keys: Vec<(PublicKey, Weight)>,
+    pub deployment_threshold: Weight,
+    pub key_management_threshold: Weight,
+}
+
+impl From<&Account> for AccountAuthInfo {
+    fn from(account: &Account) -> Self {
+        AccountAuthInfo {
+            associated_keys: account
+                .get_associated_keys()
+                .map(|(public_key, weight)| (*public_key, *weight))
+                .collect(),
+            deployment_threshold: *account.action_thresholds().deployment(),
+            key_management_threshold: *account.action_thresholds().key_management(),
+        }
+    }
+}
@@ -57,7 +57,9 @@ impl Contract {
 impl ToBytes for Contract {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
-        result.append(&mut self.bytes.to_bytes()?);
+        // the Wasm bytes are copied straight into `result` rather than via an intermediate buffer
+        result.append(&mut (self.bytes.len() as u32).to_bytes()?);
+        result.extend_from_slice(&self.bytes);
         result.append(&mut self.named_keys.to_bytes()?);
         result.append(&mut self.protocol_version.to_bytes()?);
         Ok(result)
@@ -93,12 +95,46 @@ pub mod gens {
 
     use super::Contract;
 
+    /// The most named keys a generated [`Contract`] has.
+    pub const MAX_NAMED_KEYS: usize = 20;
+
+    /// Generates contract bytes of up to 1kB of arbitrary bytes, or of the length of typical
+    /// contracts, i.e. up to 200kB, of a single repeated byte.
+    pub fn contract_bytes_arb() -> impl Strategy<Value = Vec<u8>> {
+        prop_oneof![
+            vec(any::<u8>(), 1..1_000),
+            (any::<u8>(), 1_000..200_000usize).prop_map(|(byte, len)| vec![byte; len]),
+        ]
+    }
+
     pub fn contract_arb() -> impl Strategy<Value = Contract> {
-        protocol_version_arb().prop_flat_map(move |protocol_version_arb| {
-            named_keys_arb(20).prop_flat_map(move |urefs| {
-                vec(any::<u8>(), 1..1000)
-                    .prop_map(move |body| Contract::new(body, urefs.clone(), protocol_version_arb))
+        (
+            contract_bytes_arb(),
+            named_keys_arb(0..=MAX_NAMED_KEYS),
+            protocol_version_arb(),
+        )
+            .prop_map(|(bytes, named_keys, protocol_version)| {
+                Contract::new(bytes, named_keys, protocol_version)
             })
-        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::proptest;
+
+    use types::bytesrepr::ToBytes;
+
+    use super::gens;
+
+    proptest! {
+        #[test]
+        fn serialization_matches_serialized_fields(contract in gens::contract_arb()) {
+            let mut expected = contract.bytes.to_bytes().unwrap();
+            expected.append(&mut contract.named_keys.to_bytes().unwrap());
+            expected.append(&mut contract.protocol_version.to_bytes().unwrap());
+            assert_eq!(contract.to_bytes().unwrap(), expected);
+            assert_eq!(contract.serialized_length(), expected.len());
+        }
     }
 }
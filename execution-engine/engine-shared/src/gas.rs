@@ -18,6 +18,10 @@ impl Gas {
         self.0
     }
 
+    /// Converts `motes` to gas at `conv_rate` motes per unit of gas.
+    ///
+    /// Rounds down, so that the gas bought with an amount of motes never costs more than that
+    /// amount. Returns `None` if `conv_rate` is zero.
     pub fn from_motes(motes: Motes, conv_rate: u64) -> Option<Self> {
         motes
             .value()
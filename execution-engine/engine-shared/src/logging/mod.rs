@@ -148,6 +148,8 @@ pub fn log_metric(
 
     let mut properties = BTreeMap::new();
     properties.insert("correlation_id", correlation_id.to_string());
+    properties.insert("metric", metric.to_string());
+    properties.insert("tag", tag.to_string());
     properties.insert("time-series-data", tsd_metric);
     properties.insert(metric_key, format!("{:?}", metric_value));
     properties.insert(
@@ -22,6 +22,10 @@ impl Motes {
         self.0
     }
 
+    /// Converts `gas` to motes at `conv_rate` motes per unit of gas.
+    ///
+    /// As the conversion rate is a whole number of motes, the conversion is exact and never needs
+    /// rounding. Returns `None` on overflow.
     pub fn from_gas(gas: Gas, conv_rate: u64) -> Option<Self> {
         gas.value()
             .checked_mul(U512::from(conv_rate))
@@ -199,3 +203,40 @@ mod tests {
         assert!(maybe.is_none(), "should be none due to overflow");
     }
 }
+
+#[cfg(test)]
+mod proptests {
+    use proptest::prelude::*;
+
+    use types::U512;
+
+    use crate::{gas::Gas, motes::Motes};
+
+    proptest! {
+        #[test]
+        fn should_convert_gas_to_motes_exactly(gas in any::<u64>(), conv_rate in 1u64..) {
+            let motes = Motes::from_gas(Gas::new(U512::from(gas)), conv_rate)
+                .expect("should not overflow");
+            prop_assert_eq!(motes.value() % conv_rate, U512::zero());
+            let round_trip = Gas::from_motes(motes, conv_rate).expect("should have gas");
+            prop_assert_eq!(round_trip.value(), U512::from(gas));
+        }
+
+        #[test]
+        fn should_conserve_payment_between_fee_and_refund(
+            payment in any::<u64>(),
+            conv_rate in 1u64..1_000,
+            spent_fraction in 0u64..=100,
+        ) {
+            let payment = Motes::new(U512::from(payment));
+            // the gas limit of a deploy is all the gas its payment pays for
+            let gas_limit = Gas::from_motes(payment, conv_rate).expect("should have gas");
+            let gas_spent = Gas::new(gas_limit.value() * spent_fraction / 100);
+
+            let fee = Motes::from_gas(gas_spent, conv_rate).expect("should not overflow");
+            prop_assert!(fee <= payment);
+            let refund = payment - fee;
+            prop_assert_eq!(fee + refund, payment);
+        }
+    }
+}
@@ -111,7 +111,7 @@ impl FromBytes for Blake2bHash {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, Serialize)]
 pub struct CorrelationId(Uuid);
 
 impl CorrelationId {
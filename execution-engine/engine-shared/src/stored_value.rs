@@ -1,8 +1,8 @@
-use std::convert::TryFrom;
+use std::{collections::BTreeMap, convert::TryFrom};
 
 use types::{
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
-    CLValue,
+    CLValue, Key,
 };
 
 use crate::{account::Account, contract::Contract, TypeMismatch};
@@ -43,6 +43,15 @@ impl StoredValue {
         }
     }
 
+    /// Returns the named keys of an account or contract, or `None` for a `CLValue`.
+    pub fn named_keys(&self) -> Option<&BTreeMap<String, Key>> {
+        match self {
+            StoredValue::CLValue(_) => None,
+            StoredValue::Account(account) => Some(account.named_keys()),
+            StoredValue::Contract(contract) => Some(contract.named_keys()),
+        }
+    }
+
     pub fn type_name(&self) -> String {
         match self {
             StoredValue::CLValue(cl_value) => format!("{:?}", cl_value.cl_type()),
@@ -25,6 +25,17 @@ pub fn wasm_costs_mock() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        transient_storage: 1,
+        max_transient_storage_size: 1024 * 1024,
+        max_transforms_per_deploy: 0,
+        max_effect_bytes_per_deploy: 0,
+        max_session_return_value_bytes: 0,
+        string_byte: 1,
+        max_string_length: 1024,
+        max_revert_data_bytes: 256,
+        base_deploy_cost: 0,
+        rejected_deploy_cost: 0,
+        strict_session_exports: false,
     }
 }
 
@@ -40,5 +51,16 @@ pub fn wasm_costs_free() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 1,
         opcodes_div: 1,
+        transient_storage: 0,
+        max_transient_storage_size: 1024 * 1024,
+        max_transforms_per_deploy: 0,
+        max_effect_bytes_per_deploy: 0,
+        max_session_return_value_bytes: 0,
+        string_byte: 0,
+        max_string_length: 1024,
+        max_revert_data_bytes: 256,
+        base_deploy_cost: 0,
+        rejected_deploy_cost: 0,
+        strict_session_exports: false,
     }
 }
@@ -59,6 +59,8 @@ pub enum Transform {
     AddUInt256(U256),
     AddUInt512(U512),
     AddKeys(BTreeMap<String, Key>),
+    /// Removes the key and its value from global state when committed.
+    Prune,
     Failure(Error),
 }
 
@@ -136,6 +138,10 @@ where
 }
 
 impl Transform {
+    /// Returns the value resulting from applying the transform to `stored_value`.
+    ///
+    /// [`Transform::Prune`] leaves no value, so it can't be applied and has to be handled by the
+    /// caller.
     pub fn apply(self, stored_value: StoredValue) -> Result<StoredValue, Error> {
         match self {
             Transform::Identity => Ok(stored_value),
@@ -160,6 +166,10 @@ impl Transform {
                     Err(TypeMismatch::new(expected, found).into())
                 }
             },
+            Transform::Prune => {
+                let expected = "Transform leaving a value".to_string();
+                Err(TypeMismatch::new(expected, "Prune".to_string()).into())
+            }
             Transform::Failure(error) => Err(error),
         }
     }
@@ -206,7 +216,12 @@ impl Add for Transform {
             (Transform::Identity, b) => b,
             (a @ Transform::Failure(_), _) => a,
             (_, b @ Transform::Failure(_)) => b,
+            (_, Transform::Prune) => Transform::Prune,
             (_, b @ Transform::Write(_)) => b,
+            // only a write can follow a prune, as there is no value left to add to
+            (Transform::Prune, b) => {
+                Transform::Failure(TypeMismatch::new("Write".to_owned(), format!("{:?}", b)).into())
+            }
             (Transform::Write(v), b) => {
                 // second transform changes value being written
                 match b.apply(v) {
@@ -271,6 +286,8 @@ impl Default for Transform {
 pub mod gens {
     use proptest::{collection::vec, prelude::*};
 
+    use types::gens::named_keys_arb;
+
     use super::Transform;
     use crate::stored_value::gens::stored_value_arb;
 
@@ -291,6 +308,8 @@ pub mod gens {
                 buf.copy_from_slice(&u);
                 Transform::AddUInt512(buf.into())
             }),
+            named_keys_arb(10).prop_map(Transform::AddKeys),
+            Just(Transform::Prune),
         ]
     }
 }
@@ -422,6 +441,24 @@ mod tests {
         uint_overflow_test::<U512>();
     }
 
+    #[test]
+    fn prune_should_merge_with_other_transforms() {
+        let write = Transform::Write(StoredValue::CLValue(CLValue::from_t(ONE_I32).unwrap()));
+
+        assert_eq!(Transform::Prune + write.clone(), write);
+        assert_eq!(write + Transform::Prune, Transform::Prune);
+        assert_eq!(
+            Transform::AddInt32(ONE_I32) + Transform::Prune,
+            Transform::Prune
+        );
+        assert_eq!(Transform::Identity + Transform::Prune, Transform::Prune);
+        assert_eq!(Transform::Prune + Transform::Identity, Transform::Prune);
+        match Transform::Prune + Transform::AddInt32(ONE_I32) {
+            Transform::Failure(Error::TypeMismatch(_)) => (),
+            other => panic!("adding after a prune should fail, got {:?}", other),
+        }
+    }
+
     #[test]
     fn addition_between_mismatched_types_should_fail() {
         fn assert_yields_type_mismatch_error(stored_value: StoredValue) {
@@ -259,6 +259,9 @@ fn log_via_log_metric(expect_output: bool) {
             msgs[0].properties.get(metric_key).unwrap()
         );
 
+        assert_eq!(metric, msgs[0].properties.get("metric").unwrap());
+        assert_eq!(tag, msgs[0].properties.get("tag").unwrap());
+
         assert!(msgs[0].properties.contains_key("time-series-data"));
 
         assert_eq!(
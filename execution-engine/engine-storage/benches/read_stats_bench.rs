@@ -0,0 +1,58 @@
+//! Compares reads which don't count their work against counted ones, to check that reads only pay
+//! for counting when stats are collected.
+#![feature(test)]
+
+extern crate test;
+
+use test::{black_box, Bencher};
+
+use casperlabs_engine_storage::global_state::{
+    in_memory::{InMemoryGlobalState, InMemoryGlobalStateView},
+    read_stats::{CountingReader, CountingStateProvider},
+    StateProvider, StateReader,
+};
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use types::{CLValue, Key};
+
+const KEY_COUNT: u8 = 100;
+
+fn key(i: u8) -> Key {
+    Key::Hash([i; 32])
+}
+
+fn state() -> (InMemoryGlobalState, Blake2bHash) {
+    let pairs: Vec<(Key, StoredValue)> = (0..KEY_COUNT)
+        .map(|i| {
+            let value = CLValue::from_t(u64::from(i)).unwrap();
+            (key(i), StoredValue::CLValue(value))
+        })
+        .collect();
+    InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs).unwrap()
+}
+
+fn read_all<R: StateReader<Key, StoredValue>>(reader: &R, correlation_id: CorrelationId) {
+    for i in 0..KEY_COUNT {
+        black_box(reader.read(correlation_id, &key(i)).ok());
+    }
+}
+
+#[bench]
+fn read_uncounted(b: &mut Bencher) {
+    let (state, root_hash) = state();
+    let reader: InMemoryGlobalStateView = state.checkout(root_hash).unwrap().unwrap();
+    let correlation_id = CorrelationId::new();
+    b.iter(|| read_all(&reader, correlation_id));
+}
+
+#[bench]
+fn read_counted(b: &mut Bencher) {
+    let (state, root_hash) = state();
+    let counting_state = CountingStateProvider::new(&state);
+    let reader: CountingReader<InMemoryGlobalStateView> =
+        counting_state.checkout(root_hash).unwrap().unwrap();
+    let correlation_id = CorrelationId::new();
+    b.iter(|| read_all(&reader, correlation_id));
+}
@@ -0,0 +1,35 @@
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    commit_sequence_store::{self, CommitSequenceStore},
+    error::in_memory::Error,
+    store::Store,
+    transaction_source::in_memory::InMemoryEnvironment,
+};
+
+/// An in-memory commit sequence store
+pub struct InMemoryCommitSequenceStore {
+    maybe_name: Option<String>,
+}
+
+impl InMemoryCommitSequenceStore {
+    pub fn new(_env: &InMemoryEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", commit_sequence_store::NAME, name))
+            .unwrap_or_else(|| String::from(commit_sequence_store::NAME));
+        InMemoryCommitSequenceStore {
+            maybe_name: Some(name),
+        }
+    }
+}
+
+impl Store<Blake2bHash, u64> for InMemoryCommitSequenceStore {
+    type Error = Error;
+    type Handle = Option<String>;
+
+    fn handle(&self) -> Self::Handle {
+        self.maybe_name.to_owned()
+    }
+}
+
+impl CommitSequenceStore for InMemoryCommitSequenceStore {}
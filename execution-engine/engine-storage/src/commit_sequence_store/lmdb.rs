@@ -0,0 +1,54 @@
+use lmdb::{Database, DatabaseFlags};
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    commit_sequence_store::{self, CommitSequenceStore},
+    error,
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed commit sequence store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbCommitSequenceStore {
+    db: Database,
+}
+
+impl LmdbCommitSequenceStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbCommitSequenceStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbCommitSequenceStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", commit_sequence_store::NAME, name))
+            .unwrap_or_else(|| String::from(commit_sequence_store::NAME))
+    }
+}
+
+impl Store<Blake2bHash, u64> for LmdbCommitSequenceStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl CommitSequenceStore for LmdbCommitSequenceStore {}
@@ -0,0 +1,15 @@
+//! A store for persisting the number of commits applied on top of each state root.
+use engine_shared::newtypes::Blake2bHash;
+
+pub mod in_memory;
+pub mod lmdb;
+
+use crate::store::Store;
+
+const NAME: &str = "COMMIT_SEQUENCE_STORE";
+
+/// An entity which persists the latest commit sequence number of each state root.
+///
+/// A state root which is absent has had no commits applied on top of it, i.e. its sequence number
+/// is zero.
+pub trait CommitSequenceStore: Store<Blake2bHash, u64> {}
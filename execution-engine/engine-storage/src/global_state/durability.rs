@@ -0,0 +1,225 @@
+//! How durably commits are written to storage.
+//!
+//! Storage is synced to disk, i.e. fsynced, to make commits durable: a commit which is durable
+//! survives a crash of the machine, while one which isn't may be lost, along with every commit
+//! applied after it.  A node replaying the commits applied after the last durable root recovers
+//! the state lost in a crash.
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use engine_shared::newtypes::Blake2bHash;
+
+/// The default longest time commits are left unsynced by [`Durability::Batched`].
+pub const DEFAULT_MAX_SYNC_DELAY: Duration = Duration::from_millis(1_000);
+/// The default largest number of commits left unsynced by [`Durability::Batched`].
+pub const DEFAULT_MAX_UNSYNCED_COMMITS: u64 = 100;
+
+/// How durably a commit is written to storage before it is reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Durability {
+    /// Storage is synced after every commit, so every reported commit is durable.
+    Strict,
+    /// Storage is synced once the commits applied since it was last synced are as many, or as
+    /// old, as allowed by the state's [`SyncBatching`].
+    Batched,
+    /// Storage is never explicitly synced.  Only for test networks whose commits can be replayed.
+    Volatile,
+}
+
+impl Default for Durability {
+    fn default() -> Self {
+        Durability::Strict
+    }
+}
+
+/// The limits on the commits left unsynced by [`Durability::Batched`]: storage is synced once
+/// `max_unsynced_commits` commits have been applied since it was last synced, or after
+/// `max_sync_delay`.
+///
+/// Storage is only synced when a commit is made, so a commit made after a quiet period is synced
+/// straight away.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SyncBatching {
+    pub max_sync_delay: Duration,
+    pub max_unsynced_commits: u64,
+}
+
+impl Default for SyncBatching {
+    fn default() -> Self {
+        SyncBatching {
+            max_sync_delay: DEFAULT_MAX_SYNC_DELAY,
+            max_unsynced_commits: DEFAULT_MAX_UNSYNCED_COMMITS,
+        }
+    }
+}
+
+struct SyncState {
+    last_sync: Instant,
+    unsynced_commits: u64,
+    last_durable_root: Option<Blake2bHash>,
+}
+
+/// Decides when storage is synced after a commit, and tracks the last root made durable.
+pub struct DurabilityTracker {
+    sync_batching: SyncBatching,
+    state: Mutex<SyncState>,
+}
+
+impl DurabilityTracker {
+    /// Creates a tracker for a storage whose latest durable root is `last_durable_root`.
+    pub fn new(sync_batching: SyncBatching, last_durable_root: Option<Blake2bHash>) -> Self {
+        DurabilityTracker {
+            sync_batching,
+            state: Mutex::new(SyncState {
+                last_sync: Instant::now(),
+                unsynced_commits: 0,
+                last_durable_root,
+            }),
+        }
+    }
+
+    /// Records a commit made with `durability` which resulted in `state_root`, running `sync` if
+    /// storage has to be synced now.  Returns whether the commit, and every commit made before
+    /// it, is durable.
+    ///
+    /// Storage which is synced by every commit is described by `syncs_commits`, in which case
+    /// every commit is durable whatever its `durability`.
+    pub fn record_commit<E, F>(
+        &self,
+        durability: Durability,
+        state_root: Blake2bHash,
+        syncs_commits: bool,
+        sync: F,
+    ) -> Result<bool, E>
+    where
+        F: FnOnce() -> Result<(), E>,
+    {
+        // held while syncing, so that the syncs are made one at a time
+        let mut state = self.state.lock();
+        if !syncs_commits {
+            state.unsynced_commits += 1;
+            let sync_now = match durability {
+                Durability::Strict => true,
+                Durability::Batched => {
+                    state.unsynced_commits >= self.sync_batching.max_unsynced_commits
+                        || state.last_sync.elapsed() >= self.sync_batching.max_sync_delay
+                }
+                Durability::Volatile => false,
+            };
+            if !sync_now {
+                return Ok(false);
+            }
+            sync()?;
+        }
+        state.last_sync = Instant::now();
+        state.unsynced_commits = 0;
+        state.last_durable_root = Some(state_root);
+        Ok(true)
+    }
+
+    /// Returns the root of the latest commit known to be durable, if any.
+    pub fn last_durable_root(&self) -> Option<Blake2bHash> {
+        self.state.lock().last_durable_root
+    }
+
+    pub fn sync_batching(&self) -> SyncBatching {
+        self.sync_batching
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn root_1() -> Blake2bHash {
+        [1; 32].into()
+    }
+
+    fn record_commit(
+        tracker: &DurabilityTracker,
+        durability: Durability,
+        syncs_commits: bool,
+    ) -> (bool, bool) {
+        let synced = Cell::new(false);
+        let durable = tracker
+            .record_commit::<(), _>(durability, root_1(), syncs_commits, || {
+                synced.set(true);
+                Ok(())
+            })
+            .unwrap();
+        (durable, synced.get())
+    }
+
+    #[test]
+    fn strict_commits_should_be_synced() {
+        let tracker = DurabilityTracker::new(SyncBatching::default(), None);
+        assert_eq!(
+            record_commit(&tracker, Durability::Strict, false),
+            (true, true)
+        );
+        assert_eq!(tracker.last_durable_root(), Some(root_1()));
+    }
+
+    #[test]
+    fn batched_commits_should_be_synced_every_max_unsynced_commits() {
+        let sync_batching = SyncBatching {
+            max_sync_delay: Duration::from_secs(3_600),
+            max_unsynced_commits: 3,
+        };
+        let tracker = DurabilityTracker::new(sync_batching, None);
+        for _ in 0..2 {
+            for _ in 0..2 {
+                assert_eq!(
+                    record_commit(&tracker, Durability::Batched, false),
+                    (false, false)
+                );
+                assert_eq!(tracker.last_durable_root(), None);
+            }
+            assert_eq!(
+                record_commit(&tracker, Durability::Batched, false),
+                (true, true)
+            );
+        }
+        assert_eq!(tracker.last_durable_root(), Some(root_1()));
+    }
+
+    #[test]
+    fn batched_commits_should_be_synced_after_max_sync_delay() {
+        let sync_batching = SyncBatching {
+            max_sync_delay: Duration::from_millis(0),
+            max_unsynced_commits: u64::max_value(),
+        };
+        let tracker = DurabilityTracker::new(sync_batching, None);
+        assert_eq!(
+            record_commit(&tracker, Durability::Batched, false),
+            (true, true)
+        );
+    }
+
+    #[test]
+    fn volatile_commits_should_never_be_synced() {
+        let sync_batching = SyncBatching {
+            max_sync_delay: Duration::from_millis(0),
+            max_unsynced_commits: 1,
+        };
+        let tracker = DurabilityTracker::new(sync_batching, None);
+        assert_eq!(
+            record_commit(&tracker, Durability::Volatile, false),
+            (false, false)
+        );
+        assert_eq!(tracker.last_durable_root(), None);
+    }
+
+    #[test]
+    fn commits_to_storage_syncing_every_commit_should_be_durable() {
+        let tracker = DurabilityTracker::new(SyncBatching::default(), None);
+        assert_eq!(
+            record_commit(&tracker, Durability::Volatile, true),
+            (true, false)
+        );
+        assert_eq!(tracker.last_durable_root(), Some(root_1()));
+    }
+}
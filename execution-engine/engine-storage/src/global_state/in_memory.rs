@@ -1,4 +1,6 @@
-use std::{ops::Deref, sync::Arc};
+use std::{cell::Cell, collections::BTreeMap, ops::Deref, sync::Arc, thread, time::Duration};
+
+use failure::Fail;
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -6,30 +8,101 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
-use types::{Key, ProtocolVersion};
+use types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    Key, ProtocolVersion,
+};
 
 use crate::{
+    commit_sequence_store::in_memory::InMemoryCommitSequenceStore,
     error::{self, in_memory},
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        batch_commit, commit, compute_state_root,
+        durability::{Durability, DurabilityTracker, SyncBatching},
+        list_roots, prune_roots,
+        read_stats::{self, ReadStats},
+        read_trie_nodes, record_empty_root, record_genesis, record_root,
+        storage_timer::{StorageOperation, StorageTimer},
+        BatchCommitResult, CommitResult, StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::in_memory::InMemoryProtocolDataStore,
+    root_metadata_store::{in_memory::InMemoryRootMetadataStore, RootMetadata},
     store::Store,
     transaction_source::{
         in_memory::{InMemoryEnvironment, InMemoryReadTransaction},
-        Transaction, TransactionSource,
+        Transaction, TransactionSource, Writable,
     },
-    trie::{operations::create_hashed_empty_trie, Trie},
+    trie::{operations::create_hashed_empty_trie, Trie, TrieMerkleProof},
     trie_store::{
         in_memory::InMemoryTrieStore,
-        operations::{self, read, ReadResult, WriteResult},
+        node_cache::{CachingReadTransaction, NodeReadSink, TrieNodeCache},
+        operations::{self, keys_with_prefix, read, read_with_proof, ReadResult, WriteResult},
     },
 };
 
+/// Identifies the bytes of a global state fixture.
+const FIXTURE_MAGIC: &[u8] = b"EE-GS-FIXTURE";
+
+/// The version of the format written by [`InMemoryGlobalState::to_fixture`].
+///
+/// It must be bumped whenever the format changes, including the serialization of the tries or root
+/// metadata it holds, so that fixtures written in an older format are regenerated rather than
+/// loaded.
+pub const FIXTURE_FORMAT_VERSION: u32 = 1;
+
+/// The data of a global state fixture: the pairs of each database of the environment, by name.
+type FixtureDatabases = BTreeMap<Option<String>, BTreeMap<Vec<u8>, Vec<u8>>>;
+
+#[derive(Debug, Fail, PartialEq, Eq)]
+pub enum FixtureError {
+    #[fail(display = "Not a fixture")]
+    NotAFixture,
+
+    #[fail(
+        display = "Fixture has format version {} but version {} is required; regenerate the \
+                   fixture",
+        found, required
+    )]
+    UnsupportedVersion { found: u32, required: u32 },
+
+    #[fail(display = "{}", _0)]
+    Storage(#[fail(cause)] error::Error),
+}
+
+impl From<error::Error> for FixtureError {
+    fn from(error: error::Error) -> Self {
+        FixtureError::Storage(error)
+    }
+}
+
+impl From<in_memory::Error> for FixtureError {
+    fn from(error: in_memory::Error) -> Self {
+        FixtureError::Storage(error.into())
+    }
+}
+
+impl From<bytesrepr::Error> for FixtureError {
+    fn from(error: bytesrepr::Error) -> Self {
+        FixtureError::Storage(error.into())
+    }
+}
+
+#[derive(Clone)]
 pub struct InMemoryGlobalState {
     pub environment: Arc<InMemoryEnvironment>,
     pub trie_store: Arc<InMemoryTrieStore>,
     pub protocol_data_store: Arc<InMemoryProtocolDataStore>,
+    pub commit_sequence_store: Arc<InMemoryCommitSequenceStore>,
+    pub root_metadata_store: Arc<InMemoryRootMetadataStore>,
     pub empty_root_hash: Blake2bHash,
+    /// Nothing is ever written to disk, but commits are tracked as if they were, so that they are
+    /// reported durable like those of an LMDB-backed state whose environment isn't synced by every
+    /// commit.
+    durability_tracker: Arc<DurabilityTracker>,
+    storage_timer: StorageTimer,
+    /// Test hook delaying every read of the state's views.
+    read_delay: Option<Duration>,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -37,6 +110,10 @@ pub struct InMemoryGlobalStateView {
     pub environment: Arc<InMemoryEnvironment>,
     pub store: Arc<InMemoryTrieStore>,
     pub root_hash: Blake2bHash,
+    /// If set, trie nodes are read through this cache.
+    pub trie_node_cache: Option<TrieNodeCache>,
+    storage_timer: StorageTimer,
+    read_delay: Option<Duration>,
 }
 
 impl InMemoryGlobalState {
@@ -45,6 +122,8 @@ impl InMemoryGlobalState {
         let environment = Arc::new(InMemoryEnvironment::new());
         let trie_store = Arc::new(InMemoryTrieStore::new(&environment, None));
         let protocol_data_store = Arc::new(InMemoryProtocolDataStore::new(&environment, None));
+        let commit_sequence_store = Arc::new(InMemoryCommitSequenceStore::new(&environment, None));
+        let root_metadata_store = Arc::new(InMemoryRootMetadataStore::new(&environment, None));
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
             let mut txn = environment.create_read_write_txn()?;
@@ -52,10 +131,17 @@ impl InMemoryGlobalState {
             txn.commit()?;
             root_hash
         };
+        record_empty_root::<InMemoryEnvironment, InMemoryRootMetadataStore, error::Error>(
+            &environment,
+            &root_metadata_store,
+            root_hash,
+        )?;
         Ok(InMemoryGlobalState::new(
             environment,
             trie_store,
             protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
             root_hash,
         ))
     }
@@ -66,16 +152,48 @@ impl InMemoryGlobalState {
         environment: Arc<InMemoryEnvironment>,
         trie_store: Arc<InMemoryTrieStore>,
         protocol_data_store: Arc<InMemoryProtocolDataStore>,
+        commit_sequence_store: Arc<InMemoryCommitSequenceStore>,
+        root_metadata_store: Arc<InMemoryRootMetadataStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         InMemoryGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
             empty_root_hash,
+            durability_tracker: Arc::new(DurabilityTracker::new(
+                SyncBatching::default(),
+                Some(empty_root_hash),
+            )),
+            storage_timer: StorageTimer::default(),
+            read_delay: None,
         }
     }
 
+    /// Sets the limits on the commits left unsynced by commits made with
+    /// [`Durability::Batched`].
+    pub fn with_sync_batching(mut self, sync_batching: SyncBatching) -> Self {
+        let last_durable_root = self.durability_tracker.last_durable_root();
+        self.durability_tracker =
+            Arc::new(DurabilityTracker::new(sync_batching, last_durable_root));
+        self
+    }
+
+    /// Sets the timer of the storage operations, which warns about the slow ones.
+    pub fn with_storage_timer(mut self, storage_timer: StorageTimer) -> Self {
+        self.storage_timer = storage_timer;
+        self
+    }
+
+    /// Delays every read of the state's views by `read_delay`, e.g. to test the warnings about
+    /// slow operations.
+    pub fn with_read_delay(mut self, read_delay: Duration) -> Self {
+        self.read_delay = Some(read_delay);
+        self
+    }
+
     /// Creates a state from a given set of `Key, StoredValue` pairs.
     pub fn from_pairs(
         correlation_id: CorrelationId,
@@ -102,10 +220,145 @@ impl InMemoryGlobalState {
                     WriteResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
                 }
             }
+            record_root(
+                &mut txn,
+                &*state.root_metadata_store,
+                current_root,
+                RootMetadata::now(None),
+            )?;
             txn.commit()?;
         }
         Ok((state, current_root))
     }
+
+    /// Serializes the whole state, i.e. every trie along with the root metadata, protocol data and
+    /// commit sequence numbers, so it can be reloaded by [`from_fixture`](Self::from_fixture).
+    ///
+    /// The output only depends on the contents of the state, not on the order they were written
+    /// in.
+    pub fn to_fixture(&self) -> Result<Vec<u8>, error::Error> {
+        let databases: FixtureDatabases = self
+            .environment
+            .dump()?
+            .into_iter()
+            .map(|(name, pairs)| (name, pairs.into_iter().collect()))
+            .collect();
+        let mut ret = FIXTURE_MAGIC.to_vec();
+        ret.append(&mut FIXTURE_FORMAT_VERSION.to_bytes()?);
+        ret.append(&mut self.empty_root_hash.to_bytes()?);
+        ret.append(&mut databases.to_bytes()?);
+        Ok(ret)
+    }
+
+    /// Recreates a state serialized by [`to_fixture`](Self::to_fixture).
+    ///
+    /// Fails with [`FixtureError::UnsupportedVersion`] if the fixture was written in a different
+    /// format version.
+    pub fn from_fixture(bytes: &[u8]) -> Result<Self, FixtureError> {
+        if !bytes.starts_with(FIXTURE_MAGIC) {
+            return Err(FixtureError::NotAFixture);
+        }
+        let (version, rem) = u32::from_bytes(&bytes[FIXTURE_MAGIC.len()..])?;
+        if version != FIXTURE_FORMAT_VERSION {
+            return Err(FixtureError::UnsupportedVersion {
+                found: version,
+                required: FIXTURE_FORMAT_VERSION,
+            });
+        }
+        let (empty_root_hash, rem) = Blake2bHash::from_bytes(rem)?;
+        let (databases, rem) = FixtureDatabases::from_bytes(rem)?;
+        if !rem.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes.into());
+        }
+
+        let environment = Arc::new(InMemoryEnvironment::new());
+        {
+            let mut txn = environment.create_read_write_txn()?;
+            for (name, pairs) in databases {
+                for (key, value) in pairs {
+                    txn.write(name.clone(), &key, &value)?;
+                }
+            }
+            txn.commit()?;
+        }
+        let trie_store = Arc::new(InMemoryTrieStore::new(&environment, None));
+        let protocol_data_store = Arc::new(InMemoryProtocolDataStore::new(&environment, None));
+        let commit_sequence_store = Arc::new(InMemoryCommitSequenceStore::new(&environment, None));
+        let root_metadata_store = Arc::new(InMemoryRootMetadataStore::new(&environment, None));
+        Ok(InMemoryGlobalState::new(
+            environment,
+            trie_store,
+            protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            empty_root_hash,
+        ))
+    }
+
+    fn checkout_view(
+        &self,
+        state_hash: Blake2bHash,
+        trie_node_cache: Option<&TrieNodeCache>,
+    ) -> Result<Option<InMemoryGlobalStateView>, error::Error> {
+        let txn = CachingReadTransaction::new(self.environment.create_read_txn()?, trie_node_cache);
+        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| InMemoryGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+            trie_node_cache: trie_node_cache.cloned(),
+            storage_timer: self.storage_timer,
+            read_delay: self.read_delay,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+}
+
+impl InMemoryGlobalStateView {
+    /// Reads the value of `key`, tallying the trie nodes visited in `node_reads`.
+    fn read_with_node_reads<N: NodeReadSink + ?Sized>(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        node_reads: &N,
+    ) -> Result<Option<StoredValue>, error::Error> {
+        let key_bytes = || key.to_bytes().unwrap_or_default();
+        self.storage_timer.time(
+            correlation_id,
+            StorageOperation::Read,
+            key_bytes,
+            || -> Result<_, error::Error> {
+                if let Some(read_delay) = self.read_delay {
+                    thread::sleep(read_delay);
+                }
+                let txn = CachingReadTransaction::new(
+                    self.environment.create_read_txn()?,
+                    self.trie_node_cache.as_ref(),
+                )
+                .with_node_reads(node_reads);
+                let ret = match read::<
+                    Key,
+                    StoredValue,
+                    CachingReadTransaction<InMemoryReadTransaction, N>,
+                    InMemoryTrieStore,
+                    error::Error,
+                >(
+                    correlation_id,
+                    &txn,
+                    self.store.deref(),
+                    &self.root_hash,
+                    key,
+                )? {
+                    ReadResult::Found(value) => Some(value),
+                    ReadResult::NotFound => None,
+                    ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+                };
+                txn.commit()?;
+                Ok(ret)
+            },
+        )
+    }
 }
 
 impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
@@ -116,26 +369,90 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
-        let txn = self.environment.create_read_txn()?;
-        let ret = match read::<
-            Key,
-            StoredValue,
-            InMemoryReadTransaction,
-            InMemoryTrieStore,
-            Self::Error,
-        >(
+        self.read_with_node_reads(correlation_id, key, &())
+    }
+
+    fn read_counted(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        stats: &Cell<ReadStats>,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        read_stats::count_read(stats, || {
+            self.read_with_node_reads(correlation_id, key, stats)
+        })
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let key_bytes = || key.to_bytes().unwrap_or_default();
+        self.storage_timer.time(
             correlation_id,
-            &txn,
-            self.store.deref(),
-            &self.root_hash,
-            key,
-        )? {
-            ReadResult::Found(value) => Some(value),
-            ReadResult::NotFound => None,
-            ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
-        };
-        txn.commit()?;
-        Ok(ret)
+            StorageOperation::ReadWithProof,
+            key_bytes,
+            || -> Result<_, Self::Error> {
+                let txn = CachingReadTransaction::new(
+                    self.environment.create_read_txn()?,
+                    self.trie_node_cache.as_ref(),
+                );
+                let ret = match read_with_proof::<
+                    Key,
+                    StoredValue,
+                    CachingReadTransaction<InMemoryReadTransaction>,
+                    InMemoryTrieStore,
+                    Self::Error,
+                >(
+                    correlation_id,
+                    &txn,
+                    self.store.deref(),
+                    &self.root_hash,
+                    key,
+                )? {
+                    ReadResult::Found(proof) => Some(proof),
+                    ReadResult::NotFound => None,
+                    ReadResult::RootNotFound => panic!("InMemoryGlobalState has invalid root"),
+                };
+                txn.commit()?;
+                Ok(ret)
+            },
+        )
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let key_bytes = || prefix.to_vec();
+        self.storage_timer.time(
+            correlation_id,
+            StorageOperation::KeysWithPrefix,
+            key_bytes,
+            || -> Result<_, Self::Error> {
+                let txn = CachingReadTransaction::new(
+                    self.environment.create_read_txn()?,
+                    self.trie_node_cache.as_ref(),
+                );
+                let keys = keys_with_prefix::<
+                    Key,
+                    StoredValue,
+                    CachingReadTransaction<InMemoryReadTransaction>,
+                    InMemoryTrieStore,
+                >(
+                    correlation_id,
+                    &txn,
+                    self.store.deref(),
+                    &self.root_hash,
+                    prefix,
+                )
+                .collect::<Result<Vec<Key>, _>>()?;
+                txn.commit()?;
+                Ok(keys)
+            },
+        )
     }
 }
 
@@ -145,16 +462,15 @@ impl StateProvider for InMemoryGlobalState {
     type Reader = InMemoryGlobalStateView;
 
     fn checkout(&self, prestate_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
-        let txn = self.environment.create_read_txn()?;
-        let maybe_root: Option<Trie<Key, StoredValue>> =
-            self.trie_store.get(&txn, &prestate_hash)?;
-        let maybe_state = maybe_root.map(|_| InMemoryGlobalStateView {
-            environment: Arc::clone(&self.environment),
-            store: Arc::clone(&self.trie_store),
-            root_hash: prestate_hash,
-        });
-        txn.commit()?;
-        Ok(maybe_state)
+        self.checkout_view(prestate_hash, None)
+    }
+
+    fn checkout_with_cache(
+        &self,
+        prestate_hash: Blake2bHash,
+        trie_node_cache: &TrieNodeCache,
+    ) -> Result<Option<Self::Reader>, Self::Error> {
+        self.checkout_view(prestate_hash, Some(trie_node_cache))
     }
 
     fn commit(
@@ -163,14 +479,118 @@ impl StateProvider for InMemoryGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<InMemoryEnvironment, InMemoryTrieStore, _, Self::Error>(
+        let key_bytes = || prestate_hash.to_vec();
+        let commit_result =
+            self.storage_timer
+                .time(correlation_id, StorageOperation::Commit, key_bytes, || {
+                    commit::<
+                        InMemoryEnvironment,
+                        InMemoryTrieStore,
+                        InMemoryCommitSequenceStore,
+                        InMemoryRootMetadataStore,
+                        _,
+                        Self::Error,
+                    >(
+                        &self.environment,
+                        &self.trie_store,
+                        &self.commit_sequence_store,
+                        &self.root_metadata_store,
+                        correlation_id,
+                        prestate_hash,
+                        effects,
+                        None,
+                    )
+                })?;
+        Ok(commit_result)
+    }
+
+    fn commit_fenced(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+        last_seen_sequence_number: u64,
+    ) -> Result<CommitResult, Self::Error> {
+        let key_bytes = || prestate_hash.to_vec();
+        let commit_result =
+            self.storage_timer
+                .time(correlation_id, StorageOperation::Commit, key_bytes, || {
+                    commit::<
+                        InMemoryEnvironment,
+                        InMemoryTrieStore,
+                        InMemoryCommitSequenceStore,
+                        InMemoryRootMetadataStore,
+                        _,
+                        Self::Error,
+                    >(
+                        &self.environment,
+                        &self.trie_store,
+                        &self.commit_sequence_store,
+                        &self.root_metadata_store,
+                        correlation_id,
+                        prestate_hash,
+                        effects,
+                        Some(last_seen_sequence_number),
+                    )
+                })?;
+        Ok(commit_result)
+    }
+
+    fn batch_commit(
+        &self,
+        correlation_id: CorrelationId,
+        entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<BatchCommitResult, Self::Error> {
+        let first_prestate_hash = entries.first().map(|(prestate_hash, _)| *prestate_hash);
+        let key_bytes = || {
+            first_prestate_hash
+                .map(|hash| hash.to_vec())
+                .unwrap_or_default()
+        };
+        let batch_commit_result = self.storage_timer.time(
+            correlation_id,
+            StorageOperation::BatchCommit,
+            key_bytes,
+            || {
+                batch_commit::<
+                    InMemoryEnvironment,
+                    InMemoryTrieStore,
+                    InMemoryCommitSequenceStore,
+                    InMemoryRootMetadataStore,
+                    _,
+                    Self::Error,
+                >(
+                    &self.environment,
+                    &self.trie_store,
+                    &self.commit_sequence_store,
+                    &self.root_metadata_store,
+                    correlation_id,
+                    entries,
+                )
+            },
+        )?;
+        Ok(batch_commit_result)
+    }
+
+    fn compute_state_root(
+        &self,
+        correlation_id: CorrelationId,
+        pairs: Vec<(Key, StoredValue)>,
+        persist: bool,
+    ) -> Result<Blake2bHash, Self::Error> {
+        compute_state_root::<
+            InMemoryEnvironment,
+            InMemoryTrieStore,
+            InMemoryRootMetadataStore,
+            Self::Error,
+        >(
             &self.environment,
             &self.trie_store,
+            &self.root_metadata_store,
             correlation_id,
-            prestate_hash,
-            effects,
-        )?;
-        Ok(commit_result)
+            pairs,
+            persist,
+        )
     }
 
     fn put_protocol_data(
@@ -197,11 +617,85 @@ impl StateProvider for InMemoryGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn list_roots(
+        &self,
+        limit: usize,
+        start_after: Option<Blake2bHash>,
+    ) -> Result<Vec<(Blake2bHash, RootMetadata)>, Self::Error> {
+        list_roots::<InMemoryEnvironment, InMemoryRootMetadataStore, Self::Error>(
+            &self.environment,
+            &self.root_metadata_store,
+            limit,
+            start_after,
+        )
+    }
+
+    fn record_genesis(
+        &self,
+        root: Blake2bHash,
+        genesis_fingerprint: Blake2bHash,
+    ) -> Result<(), Self::Error> {
+        record_genesis::<InMemoryEnvironment, InMemoryRootMetadataStore, Self::Error>(
+            &self.environment,
+            &self.root_metadata_store,
+            root,
+            genesis_fingerprint,
+        )
+    }
+
+    fn read_trie_nodes(&self, hashes: &[Blake2bHash]) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        read_trie_nodes::<InMemoryEnvironment, InMemoryTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            hashes,
+        )
+    }
+
+    fn prune_roots(
+        &self,
+        correlation_id: CorrelationId,
+        roots: &[Blake2bHash],
+    ) -> Result<usize, Self::Error> {
+        prune_roots::<
+            InMemoryEnvironment,
+            InMemoryTrieStore,
+            InMemoryCommitSequenceStore,
+            InMemoryRootMetadataStore,
+            Self::Error,
+        >(
+            &self.environment,
+            &self.trie_store,
+            &self.commit_sequence_store,
+            &self.root_metadata_store,
+            correlation_id,
+            self.empty_root_hash,
+            roots,
+        )
+    }
+
+    fn make_durable(
+        &self,
+        durability: Durability,
+        state_root: Blake2bHash,
+    ) -> Result<bool, Self::Error> {
+        self.durability_tracker
+            .record_commit(durability, state_root, false, || Ok(()))
+    }
+
+    fn last_durable_root(&self) -> Option<Blake2bHash> {
+        self.durability_tracker.last_durable_root()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use types::{account::PublicKey, CLValue};
+    use std::{collections::HashMap, time::Duration};
+
+    use proptest::{collection::vec, prelude::*};
+
+    use engine_shared::stored_value::gens::stored_value_arb;
+    use types::{account::PublicKey, gens::key_arb, CLValue};
 
     use super::*;
 
@@ -253,6 +747,43 @@ mod tests {
         .unwrap()
     }
 
+    #[test]
+    fn durable_flag_follows_durability() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+        let state = state.with_sync_batching(SyncBatching {
+            max_sync_delay: Duration::from_secs(3_600),
+            max_unsynced_commits: 2,
+        });
+        assert_eq!(state.last_durable_root(), Some(state.empty_root()));
+
+        let mut post_state_hashes = Vec::new();
+        for TestPair { key, value } in create_test_pairs_updated().iter().cloned() {
+            let mut effects = AdditiveMap::new();
+            effects.insert(key, Transform::Write(value));
+            match state.commit(correlation_id, root_hash, effects).unwrap() {
+                CommitResult::Success { state_root, .. } => post_state_hashes.push(state_root),
+                _ => panic!("commit failed"),
+            }
+        }
+
+        assert!(!state
+            .make_durable(Durability::Batched, post_state_hashes[0])
+            .unwrap());
+        assert_eq!(state.last_durable_root(), Some(state.empty_root()));
+        assert!(state
+            .make_durable(Durability::Batched, post_state_hashes[1])
+            .unwrap());
+        assert_eq!(state.last_durable_root(), Some(post_state_hashes[1]));
+        assert!(!state
+            .make_durable(Durability::Volatile, post_state_hashes[2])
+            .unwrap());
+        assert!(state
+            .make_durable(Durability::default(), post_state_hashes[2])
+            .unwrap());
+        assert_eq!(state.last_durable_root(), Some(post_state_hashes[2]));
+    }
+
     #[test]
     fn reads_from_a_checkout_return_expected_values() {
         let correlation_id = CorrelationId::new();
@@ -343,6 +874,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reads_through_a_shared_cache_return_values_of_each_root() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state();
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+
+        let updated_hash = match state.commit(correlation_id, root_hash, effects).unwrap() {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let trie_node_cache = TrieNodeCache::new();
+        let original_checkout = state
+            .checkout_with_cache(root_hash, &trie_node_cache)
+            .unwrap()
+            .unwrap();
+        let updated_checkout = state
+            .checkout_with_cache(updated_hash, &trie_node_cache)
+            .unwrap()
+            .unwrap();
+        assert!(!trie_node_cache.is_empty());
+
+        for (original, updated) in create_test_pairs().iter().zip(test_pairs_updated.iter()) {
+            assert_eq!(
+                Some(original.value.clone()),
+                original_checkout
+                    .read(correlation_id, &original.key)
+                    .unwrap()
+            );
+            assert_eq!(
+                Some(updated.value.clone()),
+                updated_checkout.read(correlation_id, &updated.key).unwrap()
+            );
+        }
+
+        let fake_hash: Blake2bHash = [1u8; 32].into();
+        assert!(state
+            .checkout_with_cache(fake_hash, &trie_node_cache)
+            .unwrap()
+            .is_none());
+    }
+
     #[test]
     fn initial_state_has_the_expected_hash() {
         let correlation_id = CorrelationId::new();
@@ -353,4 +933,227 @@ mod tests {
         let (_, root_hash) = InMemoryGlobalState::from_pairs(correlation_id, &[]).unwrap();
         assert_eq!(expected_bytes, root_hash.to_vec())
     }
+
+    fn commit_chain(state: &InMemoryGlobalState, count: u8) -> Vec<Blake2bHash> {
+        let correlation_id = CorrelationId::new();
+        let mut roots = Vec::new();
+        let mut current_root = state.empty_root();
+        for i in 0..count {
+            let mut effects = AdditiveMap::new();
+            effects.insert(
+                Key::Account(PublicKey::ed25519_from([i; 32])),
+                Transform::Write(StoredValue::CLValue(CLValue::from_t(i32::from(i)).unwrap())),
+            );
+            current_root = match state.commit(correlation_id, current_root, effects).unwrap() {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            };
+            roots.push(current_root);
+        }
+        roots
+    }
+
+    fn listed_roots(state: &InMemoryGlobalState, page_size: usize) -> Vec<Blake2bHash> {
+        let mut result = Vec::new();
+        let mut start_after = None;
+        loop {
+            let page = state.list_roots(page_size, start_after).unwrap();
+            assert!(page.len() <= page_size);
+            match page.last() {
+                Some((last, _)) => start_after = Some(*last),
+                None => return result,
+            }
+            result.extend(page.into_iter().map(|(root, _)| root));
+        }
+    }
+
+    #[test]
+    fn lists_committed_roots_with_parent_hints() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let roots = commit_chain(&state, 5);
+
+        let mut expected: Vec<Blake2bHash> = roots.clone();
+        expected.push(state.empty_root());
+        expected.sort();
+        for page_size in &[1, 2, 6, 10] {
+            assert_eq!(listed_roots(&state, *page_size), expected);
+        }
+
+        let metadata: HashMap<Blake2bHash, RootMetadata> =
+            state.list_roots(10, None).unwrap().into_iter().collect();
+        assert_eq!(metadata[&state.empty_root()].parent_hint, None);
+        assert_eq!(metadata[&roots[0]].parent_hint, Some(state.empty_root()));
+        for pair in roots.windows(2) {
+            assert_eq!(metadata[&pair[1]].parent_hint, Some(pair[0]));
+        }
+    }
+
+    #[test]
+    fn pruned_roots_are_not_listed_and_retained_roots_still_read() {
+        let correlation_id = CorrelationId::new();
+        let state = InMemoryGlobalState::empty().unwrap();
+        let roots = commit_chain(&state, 5);
+        let pruned = [roots[1], roots[3]];
+
+        let deleted = state.prune_roots(correlation_id, &pruned).unwrap();
+        assert!(deleted > 0);
+
+        let listed = listed_roots(&state, 2);
+        for root in &pruned {
+            assert!(!listed.contains(root));
+            assert!(state.checkout(*root).unwrap().is_none());
+        }
+
+        for (i, root) in roots.iter().enumerate() {
+            if pruned.contains(root) {
+                continue;
+            }
+            assert!(listed.contains(root));
+            let checkout = state.checkout(*root).unwrap().unwrap();
+            // each root holds the accounts written by its own commit and all those before it
+            for j in 0..=i as u8 {
+                let key = Key::Account(PublicKey::ed25519_from([j; 32]));
+                let expected = StoredValue::CLValue(CLValue::from_t(i32::from(j)).unwrap());
+                assert_eq!(Some(expected), checkout.read(correlation_id, &key).unwrap());
+            }
+        }
+
+        // pruning again deletes nothing, and the empty root is never pruned
+        assert_eq!(state.prune_roots(correlation_id, &pruned).unwrap(), 0);
+        assert_eq!(
+            state
+                .prune_roots(correlation_id, &[state.empty_root()])
+                .unwrap(),
+            0
+        );
+        assert!(state.checkout(state.empty_root()).unwrap().is_some());
+    }
+
+    #[test]
+    fn genesis_roots_are_marked_and_never_pruned() {
+        let correlation_id = CorrelationId::new();
+        let state = InMemoryGlobalState::empty().unwrap();
+        let roots = commit_chain(&state, 2);
+        let genesis_fingerprint = Blake2bHash::new(&[1]);
+        state.record_genesis(roots[0], genesis_fingerprint).unwrap();
+
+        let metadata: HashMap<Blake2bHash, RootMetadata> =
+            state.list_roots(10, None).unwrap().into_iter().collect();
+        assert_eq!(
+            metadata[&roots[0]].genesis_fingerprint,
+            Some(genesis_fingerprint)
+        );
+        assert_eq!(metadata[&roots[0]].parent_hint, Some(state.empty_root()));
+        assert_eq!(metadata[&roots[1]].genesis_fingerprint, None);
+
+        state.prune_roots(correlation_id, &roots).unwrap();
+        let mut expected = vec![state.empty_root(), roots[0]];
+        expected.sort();
+        assert_eq!(listed_roots(&state, 10), expected);
+        assert!(state.checkout(roots[0]).unwrap().is_some());
+    }
+
+    #[test]
+    fn state_loaded_from_fixture_matches_original() {
+        let correlation_id = CorrelationId::new();
+        let state = InMemoryGlobalState::empty().unwrap();
+        let roots = commit_chain(&state, 3);
+        let fixture = state.to_fixture().unwrap();
+
+        let loaded = InMemoryGlobalState::from_fixture(&fixture).unwrap();
+        assert_eq!(loaded.empty_root(), state.empty_root());
+        assert_eq!(loaded.to_fixture().unwrap(), fixture);
+        assert_eq!(
+            loaded.list_roots(10, None).unwrap(),
+            state.list_roots(10, None).unwrap()
+        );
+
+        let last_root = *roots.last().unwrap();
+        let effects: AdditiveMap<Key, Transform> = create_test_pairs()
+            .iter()
+            .cloned()
+            .map(|TestPair { key, value }| (key, Transform::Write(value)))
+            .collect();
+        let commit = |state: &InMemoryGlobalState| match state
+            .commit(correlation_id, last_root, effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        assert_eq!(commit(&loaded), commit(&state));
+    }
+
+    #[test]
+    fn fixture_of_another_format_version_fails_to_load() {
+        let state = InMemoryGlobalState::empty().unwrap();
+        let mut fixture = state.to_fixture().unwrap();
+        let old_version = FIXTURE_FORMAT_VERSION - 1;
+        fixture.splice(
+            FIXTURE_MAGIC.len()..FIXTURE_MAGIC.len() + 4,
+            old_version.to_bytes().unwrap(),
+        );
+
+        let error = InMemoryGlobalState::from_fixture(&fixture).err().unwrap();
+        assert_eq!(
+            error,
+            FixtureError::UnsupportedVersion {
+                found: old_version,
+                required: FIXTURE_FORMAT_VERSION,
+            }
+        );
+        assert!(error.to_string().contains("regenerate"));
+
+        let error = InMemoryGlobalState::from_fixture(&[1, 2, 3]).err().unwrap();
+        assert_eq!(error, FixtureError::NotAFixture);
+    }
+
+    proptest! {
+        #[test]
+        fn computed_state_root_matches_committed_writes(
+            pairs in vec((key_arb(), stored_value_arb()), 0..20)
+        ) {
+            let correlation_id = CorrelationId::new();
+            let state = InMemoryGlobalState::empty().unwrap();
+
+            let effects: AdditiveMap<Key, Transform> = pairs
+                .iter()
+                .cloned()
+                .map(|(key, value)| (key.normalize(), Transform::Write(value)))
+                .collect();
+            let expected_values: Vec<(Key, StoredValue)> = effects
+                .iter()
+                .map(|(key, transform)| match transform {
+                    Transform::Write(value) => (*key, value.clone()),
+                    _ => unreachable!(),
+                })
+                .collect();
+            let committed_hash = match state
+                .commit(correlation_id, state.empty_root(), effects)
+                .unwrap()
+            {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            };
+
+            let other_state = InMemoryGlobalState::empty().unwrap();
+            let unpersisted_hash = other_state
+                .compute_state_root(correlation_id, pairs.clone(), false)
+                .unwrap();
+            prop_assert_eq!(unpersisted_hash, committed_hash);
+            if !pairs.is_empty() {
+                // nothing is stored unless asked to
+                prop_assert!(other_state.checkout(unpersisted_hash).unwrap().is_none());
+            }
+
+            let computed_hash = other_state
+                .compute_state_root(correlation_id, pairs, true)
+                .unwrap();
+            prop_assert_eq!(computed_hash, committed_hash);
+            let checkout = other_state.checkout(computed_hash).unwrap().unwrap();
+            for (key, value) in expected_values {
+                prop_assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+            }
+        }
+    }
 }
@@ -1,4 +1,6 @@
-use std::{ops::Deref, sync::Arc};
+use std::{cell::Cell, ops::Deref, sync::Arc};
+
+use parking_lot::{Mutex, MutexGuard};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -6,27 +8,56 @@ use engine_shared::{
     stored_value::StoredValue,
     transform::Transform,
 };
-use types::{Key, ProtocolVersion};
+use types::{bytesrepr::ToBytes, Key, ProtocolVersion};
 
 use crate::{
+    commit_sequence_store::lmdb::LmdbCommitSequenceStore,
     error,
-    global_state::{commit, CommitResult, StateProvider, StateReader},
+    global_state::{
+        batch_commit, commit, compute_state_root,
+        durability::{Durability, DurabilityTracker, SyncBatching},
+        latest_root, list_roots, prune_roots,
+        read_stats::{self, ReadStats},
+        read_trie_nodes, record_empty_root, record_genesis,
+        storage_timer::{StorageOperation, StorageTimer},
+        BatchCommitResult, CommitResult, StateProvider, StateReader,
+    },
     protocol_data::ProtocolData,
     protocol_data_store::lmdb::LmdbProtocolDataStore,
+    root_metadata_store::{lmdb::LmdbRootMetadataStore, RootMetadata},
+    staging_store::lmdb::LmdbStagingStore,
     store::Store,
-    transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
-    trie::{operations::create_hashed_empty_trie, Trie},
+    transaction_source::{
+        lmdb::{reclaim_staged, LmdbBatchingEnvironment, LmdbEnvironment},
+        Transaction, TransactionSource,
+    },
+    trie::{operations::create_hashed_empty_trie, Trie, TrieMerkleProof},
     trie_store::{
         lmdb::LmdbTrieStore,
-        operations::{read, ReadResult},
+        node_cache::{CachingReadTransaction, NodeReadSink, TrieNodeCache},
+        operations::{keys_with_prefix, read, read_with_proof, ReadResult},
     },
 };
 
+/// The default number of bytes of trie nodes written per LMDB transaction by a commit.
+pub const DEFAULT_COMMIT_BATCH_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Clone)]
 pub struct LmdbGlobalState {
     pub environment: Arc<LmdbEnvironment>,
     pub trie_store: Arc<LmdbTrieStore>,
     pub protocol_data_store: Arc<LmdbProtocolDataStore>,
+    pub commit_sequence_store: Arc<LmdbCommitSequenceStore>,
+    pub root_metadata_store: Arc<LmdbRootMetadataStore>,
+    pub staging_store: Arc<LmdbStagingStore>,
     pub empty_root_hash: Blake2bHash,
+    commit_batch_size: usize,
+    /// Held by the writers of the trie store, so that none lands between the batches of a commit.
+    write_lock: Arc<Mutex<()>>,
+    /// Test hook making commits fail after writing this many batches, as if the process crashed.
+    crash_after_batches: Option<usize>,
+    durability_tracker: Arc<DurabilityTracker>,
+    storage_timer: StorageTimer,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -34,15 +65,25 @@ pub struct LmdbGlobalStateView {
     pub environment: Arc<LmdbEnvironment>,
     pub store: Arc<LmdbTrieStore>,
     pub root_hash: Blake2bHash,
+    /// If set, trie nodes are read through this cache.
+    pub trie_node_cache: Option<TrieNodeCache>,
+    storage_timer: StorageTimer,
 }
 
 impl LmdbGlobalState {
-    /// Creates an empty state from an existing environment and trie_store.
+    /// Creates an empty state from an existing environment and stores.
+    ///
+    /// The trie nodes staged by a commit which was interrupted, e.g. by a crash, are deleted first.
+    /// Every root found in the stores is durable, so the latest of them is the last durable root.
     pub fn empty(
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        commit_sequence_store: Arc<LmdbCommitSequenceStore>,
+        root_metadata_store: Arc<LmdbRootMetadataStore>,
+        staging_store: Arc<LmdbStagingStore>,
     ) -> Result<Self, error::Error> {
+        reclaim_staged(&environment, trie_store.handle(), staging_store.handle())?;
         let root_hash: Blake2bHash = {
             let (root_hash, root) = create_hashed_empty_trie::<Key, StoredValue>()?;
             let mut txn = environment.create_read_write_txn()?;
@@ -50,12 +91,29 @@ impl LmdbGlobalState {
             txn.commit()?;
             root_hash
         };
-        Ok(LmdbGlobalState::new(
+        record_empty_root::<LmdbEnvironment, LmdbRootMetadataStore, error::Error>(
+            &environment,
+            &root_metadata_store,
+            root_hash,
+        )?;
+        let last_durable_root = latest_root::<LmdbEnvironment, LmdbRootMetadataStore, error::Error>(
+            &environment,
+            &root_metadata_store,
+        )?;
+        let mut state = LmdbGlobalState::new(
             environment,
             trie_store,
             protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            staging_store,
             root_hash,
-        ))
+        );
+        state.durability_tracker = Arc::new(DurabilityTracker::new(
+            SyncBatching::default(),
+            last_durable_root,
+        ));
+        Ok(state)
     }
 
     /// Creates a state from an existing environment, store, and root_hash.
@@ -64,15 +122,132 @@ impl LmdbGlobalState {
         environment: Arc<LmdbEnvironment>,
         trie_store: Arc<LmdbTrieStore>,
         protocol_data_store: Arc<LmdbProtocolDataStore>,
+        commit_sequence_store: Arc<LmdbCommitSequenceStore>,
+        root_metadata_store: Arc<LmdbRootMetadataStore>,
+        staging_store: Arc<LmdbStagingStore>,
         empty_root_hash: Blake2bHash,
     ) -> Self {
         LmdbGlobalState {
             environment,
             trie_store,
             protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            staging_store,
             empty_root_hash,
+            commit_batch_size: DEFAULT_COMMIT_BATCH_SIZE,
+            write_lock: Arc::new(Mutex::new(())),
+            crash_after_batches: None,
+            durability_tracker: Arc::new(DurabilityTracker::new(
+                SyncBatching::default(),
+                Some(empty_root_hash),
+            )),
+            storage_timer: StorageTimer::default(),
         }
     }
+
+    /// Sets the number of bytes of trie nodes a commit writes per LMDB transaction before starting
+    /// another one.  The new root only becomes visible once the final transaction is committed.
+    pub fn with_commit_batch_size(mut self, commit_batch_size: usize) -> Self {
+        self.commit_batch_size = commit_batch_size;
+        self
+    }
+
+    /// Sets the limits on the commits left unsynced by commits made with
+    /// [`Durability::Batched`].
+    pub fn with_sync_batching(mut self, sync_batching: SyncBatching) -> Self {
+        let last_durable_root = self.durability_tracker.last_durable_root();
+        self.durability_tracker =
+            Arc::new(DurabilityTracker::new(sync_batching, last_durable_root));
+        self
+    }
+
+    /// Sets the timer of the storage operations, which warns about the slow ones.
+    pub fn with_storage_timer(mut self, storage_timer: StorageTimer) -> Self {
+        self.storage_timer = storage_timer;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_crash_after_batches(mut self, crash_after_batches: usize) -> Self {
+        self.crash_after_batches = Some(crash_after_batches);
+        self
+    }
+
+    fn lock_writes(&self) -> MutexGuard<()> {
+        self.write_lock.lock()
+    }
+
+    fn batching_environment(&self) -> LmdbBatchingEnvironment {
+        LmdbBatchingEnvironment::new(
+            &self.environment,
+            self.trie_store.handle(),
+            self.staging_store.handle(),
+            self.commit_batch_size,
+        )
+        .with_crash_after_batches(self.crash_after_batches)
+    }
+
+    fn checkout_view(
+        &self,
+        state_hash: Blake2bHash,
+        trie_node_cache: Option<&TrieNodeCache>,
+    ) -> Result<Option<LmdbGlobalStateView>, error::Error> {
+        let txn = CachingReadTransaction::new(self.environment.create_read_txn()?, trie_node_cache);
+        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
+        let maybe_state = maybe_root.map(|_| LmdbGlobalStateView {
+            environment: Arc::clone(&self.environment),
+            store: Arc::clone(&self.trie_store),
+            root_hash: state_hash,
+            trie_node_cache: trie_node_cache.cloned(),
+            storage_timer: self.storage_timer,
+        });
+        txn.commit()?;
+        Ok(maybe_state)
+    }
+}
+
+impl LmdbGlobalStateView {
+    /// Reads the value of `key`, tallying the trie nodes visited in `node_reads`.
+    fn read_with_node_reads<N: NodeReadSink + ?Sized>(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        node_reads: &N,
+    ) -> Result<Option<StoredValue>, error::Error> {
+        let key_bytes = || key.to_bytes().unwrap_or_default();
+        self.storage_timer.time(
+            correlation_id,
+            StorageOperation::Read,
+            key_bytes,
+            || -> Result<_, error::Error> {
+                let txn = CachingReadTransaction::new(
+                    self.environment.create_read_txn()?,
+                    self.trie_node_cache.as_ref(),
+                )
+                .with_node_reads(node_reads);
+                let ret = match read::<
+                    Key,
+                    StoredValue,
+                    CachingReadTransaction<lmdb::RoTransaction, N>,
+                    LmdbTrieStore,
+                    error::Error,
+                >(
+                    correlation_id,
+                    &txn,
+                    self.store.deref(),
+                    &self.root_hash,
+                    key,
+                )? {
+                    ReadResult::Found(value) => Some(value),
+                    ReadResult::NotFound => None,
+                    ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+                };
+                txn.commit()?;
+                Ok(ret)
+            },
+        )
+    }
 }
 
 impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
@@ -83,20 +258,90 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         correlation_id: CorrelationId,
         key: &Key,
     ) -> Result<Option<StoredValue>, Self::Error> {
-        let txn = self.environment.create_read_txn()?;
-        let ret = match read::<Key, StoredValue, lmdb::RoTransaction, LmdbTrieStore, Self::Error>(
+        self.read_with_node_reads(correlation_id, key, &())
+    }
+
+    fn read_counted(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        stats: &Cell<ReadStats>,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        read_stats::count_read(stats, || {
+            self.read_with_node_reads(correlation_id, key, stats)
+        })
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        let key_bytes = || key.to_bytes().unwrap_or_default();
+        self.storage_timer.time(
             correlation_id,
-            &txn,
-            self.store.deref(),
-            &self.root_hash,
-            key,
-        )? {
-            ReadResult::Found(value) => Some(value),
-            ReadResult::NotFound => None,
-            ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
-        };
-        txn.commit()?;
-        Ok(ret)
+            StorageOperation::ReadWithProof,
+            key_bytes,
+            || -> Result<_, Self::Error> {
+                let txn = CachingReadTransaction::new(
+                    self.environment.create_read_txn()?,
+                    self.trie_node_cache.as_ref(),
+                );
+                let ret = match read_with_proof::<
+                    Key,
+                    StoredValue,
+                    CachingReadTransaction<lmdb::RoTransaction>,
+                    LmdbTrieStore,
+                    Self::Error,
+                >(
+                    correlation_id,
+                    &txn,
+                    self.store.deref(),
+                    &self.root_hash,
+                    key,
+                )? {
+                    ReadResult::Found(proof) => Some(proof),
+                    ReadResult::NotFound => None,
+                    ReadResult::RootNotFound => panic!("LmdbGlobalState has invalid root"),
+                };
+                txn.commit()?;
+                Ok(ret)
+            },
+        )
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        let key_bytes = || prefix.to_vec();
+        self.storage_timer.time(
+            correlation_id,
+            StorageOperation::KeysWithPrefix,
+            key_bytes,
+            || -> Result<_, Self::Error> {
+                let txn = CachingReadTransaction::new(
+                    self.environment.create_read_txn()?,
+                    self.trie_node_cache.as_ref(),
+                );
+                let keys = keys_with_prefix::<
+                    Key,
+                    StoredValue,
+                    CachingReadTransaction<lmdb::RoTransaction>,
+                    LmdbTrieStore,
+                >(
+                    correlation_id,
+                    &txn,
+                    self.store.deref(),
+                    &self.root_hash,
+                    prefix,
+                )
+                .collect::<Result<Vec<Key>, _>>()?;
+                txn.commit()?;
+                Ok(keys)
+            },
+        )
     }
 }
 
@@ -106,15 +351,15 @@ impl StateProvider for LmdbGlobalState {
     type Reader = LmdbGlobalStateView;
 
     fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
-        let txn = self.environment.create_read_txn()?;
-        let maybe_root: Option<Trie<Key, StoredValue>> = self.trie_store.get(&txn, &state_hash)?;
-        let maybe_state = maybe_root.map(|_| LmdbGlobalStateView {
-            environment: Arc::clone(&self.environment),
-            store: Arc::clone(&self.trie_store),
-            root_hash: state_hash,
-        });
-        txn.commit()?;
-        Ok(maybe_state)
+        self.checkout_view(state_hash, None)
+    }
+
+    fn checkout_with_cache(
+        &self,
+        state_hash: Blake2bHash,
+        trie_node_cache: &TrieNodeCache,
+    ) -> Result<Option<Self::Reader>, Self::Error> {
+        self.checkout_view(state_hash, Some(trie_node_cache))
     }
 
     fn commit(
@@ -123,14 +368,117 @@ impl StateProvider for LmdbGlobalState {
         prestate_hash: Blake2bHash,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error> {
-        let commit_result = commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+        let _write_guard = self.lock_writes();
+        let key_bytes = || prestate_hash.to_vec();
+        let commit_result =
+            self.storage_timer
+                .time(correlation_id, StorageOperation::Commit, key_bytes, || {
+                    commit::<
+                        LmdbBatchingEnvironment,
+                        LmdbTrieStore,
+                        LmdbCommitSequenceStore,
+                        LmdbRootMetadataStore,
+                        _,
+                        Self::Error,
+                    >(
+                        &self.batching_environment(),
+                        &self.trie_store,
+                        &self.commit_sequence_store,
+                        &self.root_metadata_store,
+                        correlation_id,
+                        prestate_hash,
+                        effects,
+                        None,
+                    )
+                })?;
+        Ok(commit_result)
+    }
+
+    fn commit_fenced(
+        &self,
+        correlation_id: CorrelationId,
+        prestate_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+        last_seen_sequence_number: u64,
+    ) -> Result<CommitResult, Self::Error> {
+        let _write_guard = self.lock_writes();
+        let key_bytes = || prestate_hash.to_vec();
+        let commit_result =
+            self.storage_timer
+                .time(correlation_id, StorageOperation::Commit, key_bytes, || {
+                    commit::<
+                        LmdbBatchingEnvironment,
+                        LmdbTrieStore,
+                        LmdbCommitSequenceStore,
+                        LmdbRootMetadataStore,
+                        _,
+                        Self::Error,
+                    >(
+                        &self.batching_environment(),
+                        &self.trie_store,
+                        &self.commit_sequence_store,
+                        &self.root_metadata_store,
+                        correlation_id,
+                        prestate_hash,
+                        effects,
+                        Some(last_seen_sequence_number),
+                    )
+                })?;
+        Ok(commit_result)
+    }
+
+    fn batch_commit(
+        &self,
+        correlation_id: CorrelationId,
+        entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<BatchCommitResult, Self::Error> {
+        let _write_guard = self.lock_writes();
+        let first_prestate_hash = entries.first().map(|(prestate_hash, _)| *prestate_hash);
+        let key_bytes = || {
+            first_prestate_hash
+                .map(|hash| hash.to_vec())
+                .unwrap_or_default()
+        };
+        let batch_commit_result = self.storage_timer.time(
+            correlation_id,
+            StorageOperation::BatchCommit,
+            key_bytes,
+            || {
+                batch_commit::<
+                    LmdbEnvironment,
+                    LmdbTrieStore,
+                    LmdbCommitSequenceStore,
+                    LmdbRootMetadataStore,
+                    _,
+                    Self::Error,
+                >(
+                    &self.environment,
+                    &self.trie_store,
+                    &self.commit_sequence_store,
+                    &self.root_metadata_store,
+                    correlation_id,
+                    entries,
+                )
+            },
+        )?;
+        Ok(batch_commit_result)
+    }
+
+    fn compute_state_root(
+        &self,
+        correlation_id: CorrelationId,
+        pairs: Vec<(Key, StoredValue)>,
+        persist: bool,
+    ) -> Result<Blake2bHash, Self::Error> {
+        let _write_guard = self.lock_writes();
+        compute_state_root::<LmdbEnvironment, LmdbTrieStore, LmdbRootMetadataStore, Self::Error>(
             &self.environment,
             &self.trie_store,
+            &self.root_metadata_store,
             correlation_id,
-            prestate_hash,
-            effects,
-        )?;
-        Ok(commit_result)
+            pairs,
+            persist,
+        )
     }
 
     fn put_protocol_data(
@@ -157,16 +505,95 @@ impl StateProvider for LmdbGlobalState {
     fn empty_root(&self) -> Blake2bHash {
         self.empty_root_hash
     }
+
+    fn list_roots(
+        &self,
+        limit: usize,
+        start_after: Option<Blake2bHash>,
+    ) -> Result<Vec<(Blake2bHash, RootMetadata)>, Self::Error> {
+        list_roots::<LmdbEnvironment, LmdbRootMetadataStore, Self::Error>(
+            &self.environment,
+            &self.root_metadata_store,
+            limit,
+            start_after,
+        )
+    }
+
+    fn record_genesis(
+        &self,
+        root: Blake2bHash,
+        genesis_fingerprint: Blake2bHash,
+    ) -> Result<(), Self::Error> {
+        record_genesis::<LmdbEnvironment, LmdbRootMetadataStore, Self::Error>(
+            &self.environment,
+            &self.root_metadata_store,
+            root,
+            genesis_fingerprint,
+        )
+    }
+
+    fn read_trie_nodes(&self, hashes: &[Blake2bHash]) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        read_trie_nodes::<LmdbEnvironment, LmdbTrieStore, Self::Error>(
+            &self.environment,
+            &self.trie_store,
+            hashes,
+        )
+    }
+
+    fn prune_roots(
+        &self,
+        correlation_id: CorrelationId,
+        roots: &[Blake2bHash],
+    ) -> Result<usize, Self::Error> {
+        let _write_guard = self.lock_writes();
+        prune_roots::<
+            LmdbEnvironment,
+            LmdbTrieStore,
+            LmdbCommitSequenceStore,
+            LmdbRootMetadataStore,
+            Self::Error,
+        >(
+            &self.environment,
+            &self.trie_store,
+            &self.commit_sequence_store,
+            &self.root_metadata_store,
+            correlation_id,
+            self.empty_root_hash,
+            roots,
+        )
+    }
+
+    fn make_durable(
+        &self,
+        durability: Durability,
+        state_root: Blake2bHash,
+    ) -> Result<bool, Self::Error> {
+        self.durability_tracker.record_commit(
+            durability,
+            state_root,
+            self.environment.syncs_commits(),
+            || self.environment.sync().map_err(Into::into),
+        )
+    }
+
+    fn last_durable_root(&self) -> Option<Blake2bHash> {
+        self.durability_tracker.last_durable_root()
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{path::Path, thread, time::Duration};
+
     use lmdb::DatabaseFlags;
-    use tempfile::tempdir;
+    use proptest::{collection::vec, prelude::*};
+    use tempfile::{tempdir, TempDir};
 
-    use types::{account::PublicKey, CLValue};
+    use engine_shared::stored_value::gens::stored_value_arb;
+    use types::{account::PublicKey, gens::key_arb, CLValue};
 
     use crate::{
+        transaction_source::Iterable,
         trie_store::operations::{write, WriteResult},
         TEST_MAP_SIZE,
     };
@@ -220,7 +647,23 @@ mod tests {
         let protocol_data_store = Arc::new(
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
         );
-        let ret = LmdbGlobalState::empty(environment, trie_store, protocol_data_store).unwrap();
+        let commit_sequence_store = Arc::new(
+            LmdbCommitSequenceStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let root_metadata_store = Arc::new(
+            LmdbRootMetadataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let staging_store =
+            Arc::new(LmdbStagingStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let ret = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            staging_store,
+        )
+        .unwrap();
         let mut current_root = ret.empty_root_hash;
         {
             let mut txn = ret.environment.create_read_write_txn().unwrap();
@@ -249,6 +692,67 @@ mod tests {
         (ret, current_root)
     }
 
+    /// Opens the state stored at `path`, creating it if need be.
+    fn open_state(path: &Path) -> LmdbGlobalState {
+        open_state_with_durability(path, Durability::default())
+    }
+
+    /// Opens the state stored at `path` for commits made with `durability`, creating it if need be.
+    fn open_state_with_durability(path: &Path, durability: Durability) -> LmdbGlobalState {
+        let environment = Arc::new(
+            LmdbEnvironment::new_with_durability(&path.to_path_buf(), *TEST_MAP_SIZE, durability)
+                .unwrap(),
+        );
+        let trie_store =
+            Arc::new(LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        let protocol_data_store = Arc::new(
+            LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let commit_sequence_store = Arc::new(
+            LmdbCommitSequenceStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let root_metadata_store = Arc::new(
+            LmdbRootMetadataStore::new(&environment, None, DatabaseFlags::empty()).unwrap(),
+        );
+        let staging_store =
+            Arc::new(LmdbStagingStore::new(&environment, None, DatabaseFlags::empty()).unwrap());
+        LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            staging_store,
+        )
+        .unwrap()
+    }
+
+    fn create_empty_state() -> (LmdbGlobalState, TempDir) {
+        let temp_dir = tempdir().unwrap();
+        let state = open_state(temp_dir.path());
+        (state, temp_dir)
+    }
+
+    /// Returns the effects of a genesis creating `count` accounts.
+    fn create_genesis_effects(count: u32) -> AdditiveMap<Key, Transform> {
+        (0..count)
+            .map(|index| {
+                let mut bytes = [0u8; 32];
+                bytes[..4].copy_from_slice(&index.to_le_bytes());
+                let key = Key::Account(PublicKey::ed25519_from(bytes));
+                let value = StoredValue::CLValue(CLValue::from_t(index).unwrap());
+                (key, Transform::Write(value))
+            })
+            .collect()
+    }
+
+    fn read_keys(state: &LmdbGlobalState, handle: lmdb::Database) -> Vec<Vec<u8>> {
+        let txn = state.environment.create_read_txn().unwrap();
+        let pairs = txn.read_range(handle, None, usize::max_value()).unwrap();
+        txn.commit().unwrap();
+        pairs.into_iter().map(|(key, _)| key).collect()
+    }
+
     #[test]
     fn reads_from_a_checkout_return_expected_values() {
         let correlation_id = CorrelationId::new();
@@ -339,4 +843,284 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn fenced_commit_should_be_rejected_after_another_commit_to_same_root() {
+        let correlation_id = CorrelationId::new();
+        let (state, root_hash) = create_test_state();
+
+        let effects = |pairs: &[TestPair]| -> AdditiveMap<Key, Transform> {
+            pairs
+                .iter()
+                .cloned()
+                .map(|TestPair { key, value }| (key, Transform::Write(value)))
+                .collect()
+        };
+
+        match state
+            .commit_fenced(
+                correlation_id,
+                root_hash,
+                effects(&create_test_pairs_updated()),
+                0,
+            )
+            .unwrap()
+        {
+            CommitResult::Success {
+                sequence_number, ..
+            } => assert_eq!(sequence_number, 1),
+            _ => panic!("commit failed"),
+        }
+
+        let conflicting_effects = effects(&create_test_pairs_updated()[2..]);
+        match state
+            .commit_fenced(correlation_id, root_hash, conflicting_effects.clone(), 0)
+            .unwrap()
+        {
+            CommitResult::StaleCommit {
+                last_seen_sequence_number,
+                current_sequence_number,
+            } => {
+                assert_eq!(last_seen_sequence_number, 0);
+                assert_eq!(current_sequence_number, 1);
+            }
+            _ => panic!("fenced commit should be stale"),
+        }
+
+        // Unfenced commits are applied regardless of the sequence number.
+        match state
+            .commit(correlation_id, root_hash, conflicting_effects)
+            .unwrap()
+        {
+            CommitResult::Success {
+                sequence_number, ..
+            } => assert_eq!(sequence_number, 2),
+            _ => panic!("commit failed"),
+        }
+    }
+
+    #[test]
+    fn commit_in_batches_matches_commit_in_single_transaction() {
+        let correlation_id = CorrelationId::new();
+        let effects = create_genesis_effects(500);
+
+        let (state, _temp_dir) = create_empty_state();
+        let expected_hash = match state
+            .commit(correlation_id, state.empty_root(), effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let (batched_state, _batched_temp_dir) = create_empty_state();
+        let batched_state = batched_state.with_commit_batch_size(4096);
+        let batched_hash = match batched_state
+            .commit(correlation_id, batched_state.empty_root(), effects.clone())
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+        assert_eq!(batched_hash, expected_hash);
+
+        let checkout = batched_state.checkout(batched_hash).unwrap().unwrap();
+        for (key, transform) in effects.iter() {
+            let value = match transform {
+                Transform::Write(value) => value.clone(),
+                _ => unreachable!(),
+            };
+            assert_eq!(Some(value), checkout.read(correlation_id, key).unwrap());
+        }
+        assert!(read_keys(&batched_state, batched_state.staging_store.handle()).is_empty());
+    }
+
+    #[test]
+    fn failed_commit_in_batches_deletes_staged_nodes() {
+        let correlation_id = CorrelationId::new();
+        let (state, _temp_dir) = create_empty_state();
+        let state = state.with_commit_batch_size(4096);
+        let trie_keys = read_keys(&state, state.trie_store.handle());
+
+        let mut effects = create_genesis_effects(500);
+        effects.insert(Key::Hash([1u8; 32]), Transform::AddInt32(1));
+        match state
+            .commit(correlation_id, state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::KeyNotFound(_) => (),
+            _ => panic!("commit should fail"),
+        }
+
+        assert_eq!(read_keys(&state, state.trie_store.handle()), trie_keys);
+        assert!(read_keys(&state, state.staging_store.handle()).is_empty());
+    }
+
+    #[test]
+    fn interrupted_commit_in_batches_is_reclaimed_on_restart() {
+        let correlation_id = CorrelationId::new();
+        let temp_dir = tempdir().unwrap();
+        let state = open_state(temp_dir.path());
+        let root_hash = match state
+            .commit(
+                correlation_id,
+                state.empty_root(),
+                create_genesis_effects(10),
+            )
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        };
+
+        let crashing_state = state
+            .clone()
+            .with_commit_batch_size(4096)
+            .with_crash_after_batches(2);
+        assert!(crashing_state
+            .commit(correlation_id, root_hash, create_genesis_effects(500))
+            .is_err());
+        let staged_keys = read_keys(&state, state.staging_store.handle());
+        assert!(!staged_keys.is_empty());
+
+        // the previous root is left intact
+        let checkout = state.checkout(root_hash).unwrap().unwrap();
+        for (key, transform) in create_genesis_effects(10).iter() {
+            let value = match transform {
+                Transform::Write(value) => value.clone(),
+                _ => unreachable!(),
+            };
+            assert_eq!(Some(value), checkout.read(correlation_id, key).unwrap());
+        }
+        drop(checkout);
+        drop(crashing_state);
+        drop(state);
+
+        let state = open_state(temp_dir.path());
+        assert!(read_keys(&state, state.staging_store.handle()).is_empty());
+        let trie_keys = read_keys(&state, state.trie_store.handle());
+        for staged_key in staged_keys {
+            assert!(!trie_keys.contains(&staged_key));
+        }
+        assert!(state.checkout(root_hash).unwrap().is_some());
+    }
+
+    /// Commits the genesis effects of `count` accounts onto the empty root, returning the new root.
+    fn commit_genesis(state: &LmdbGlobalState, count: u32) -> Blake2bHash {
+        let effects = create_genesis_effects(count);
+        match state
+            .commit(CorrelationId::new(), state.empty_root(), effects)
+            .unwrap()
+        {
+            CommitResult::Success { state_root, .. } => state_root,
+            _ => panic!("commit failed"),
+        }
+    }
+
+    #[test]
+    fn strict_durability_is_the_default() {
+        assert_eq!(Durability::default(), Durability::Strict);
+
+        let (state, _temp_dir) = create_empty_state();
+        assert!(state.environment.syncs_commits());
+        assert_eq!(state.last_durable_root(), Some(state.empty_root()));
+
+        // every commit is synced, whatever the durability it was made with
+        let root_hash = commit_genesis(&state, 1);
+        assert!(state.make_durable(Durability::Volatile, root_hash).unwrap());
+        assert_eq!(state.last_durable_root(), Some(root_hash));
+    }
+
+    #[test]
+    fn batched_commits_are_durable_once_synced() {
+        let temp_dir = tempdir().unwrap();
+        let state = open_state_with_durability(temp_dir.path(), Durability::Batched)
+            .with_sync_batching(SyncBatching {
+                max_sync_delay: Duration::from_secs(3_600),
+                max_unsynced_commits: 2,
+            });
+        assert!(!state.environment.syncs_commits());
+
+        let first_root = commit_genesis(&state, 1);
+        assert!(!state.make_durable(Durability::Batched, first_root).unwrap());
+        assert_eq!(state.last_durable_root(), Some(state.empty_root()));
+
+        let second_root = commit_genesis(&state, 2);
+        assert!(state
+            .make_durable(Durability::Batched, second_root)
+            .unwrap());
+        assert_eq!(state.last_durable_root(), Some(second_root));
+
+        let volatile_root = commit_genesis(&state, 3);
+        assert!(!state
+            .make_durable(Durability::Volatile, volatile_root)
+            .unwrap());
+        let strict_root = commit_genesis(&state, 4);
+        assert!(state.make_durable(Durability::Strict, strict_root).unwrap());
+        assert_eq!(state.last_durable_root(), Some(strict_root));
+    }
+
+    #[test]
+    fn latest_root_is_durable_after_reopening() {
+        let temp_dir = tempdir().unwrap();
+        let state = open_state_with_durability(temp_dir.path(), Durability::Volatile);
+        // roots are ordered by their creation time in milliseconds
+        thread::sleep(Duration::from_millis(2));
+        let root_hash = commit_genesis(&state, 1);
+        assert!(!state.make_durable(Durability::Volatile, root_hash).unwrap());
+        assert_eq!(state.last_durable_root(), Some(state.empty_root()));
+        drop(state);
+
+        let state = open_state_with_durability(temp_dir.path(), Durability::Volatile);
+        assert_eq!(state.last_durable_root(), Some(root_hash));
+    }
+
+    proptest! {
+        #[test]
+        fn computed_state_root_matches_committed_writes(
+            pairs in vec((key_arb(), stored_value_arb()), 0..20)
+        ) {
+            let correlation_id = CorrelationId::new();
+            let (state, _temp_dir) = create_empty_state();
+
+            let effects: AdditiveMap<Key, Transform> = pairs
+                .iter()
+                .cloned()
+                .map(|(key, value)| (key.normalize(), Transform::Write(value)))
+                .collect();
+            let expected_values: Vec<(Key, StoredValue)> = effects
+                .iter()
+                .map(|(key, transform)| match transform {
+                    Transform::Write(value) => (*key, value.clone()),
+                    _ => unreachable!(),
+                })
+                .collect();
+            let committed_hash = match state
+                .commit(correlation_id, state.empty_root(), effects)
+                .unwrap()
+            {
+                CommitResult::Success { state_root, .. } => state_root,
+                _ => panic!("commit failed"),
+            };
+
+            let (other_state, _other_temp_dir) = create_empty_state();
+            let unpersisted_hash = other_state
+                .compute_state_root(correlation_id, pairs.clone(), false)
+                .unwrap();
+            prop_assert_eq!(unpersisted_hash, committed_hash);
+            if !pairs.is_empty() {
+                // nothing is stored unless asked to
+                prop_assert!(other_state.checkout(unpersisted_hash).unwrap().is_none());
+            }
+
+            let computed_hash = other_state
+                .compute_state_root(correlation_id, pairs, true)
+                .unwrap();
+            prop_assert_eq!(computed_hash, committed_hash);
+            let checkout = other_state.checkout(computed_hash).unwrap().unwrap();
+            for (key, value) in expected_values {
+                prop_assert_eq!(Some(value), checkout.read(correlation_id, &key).unwrap());
+            }
+        }
+    }
 }
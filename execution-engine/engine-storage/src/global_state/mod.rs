@@ -1,7 +1,16 @@
+pub mod durability;
 pub mod in_memory;
 pub mod lmdb;
-
-use std::{collections::HashMap, fmt, hash::BuildHasher, time::Instant};
+pub mod read_stats;
+pub mod storage_timer;
+
+use std::{
+    cell::Cell,
+    collections::{BTreeMap, HashSet},
+    fmt,
+    hash::BuildHasher,
+    time::Instant,
+};
 
 use engine_shared::{
     additive_map::AdditiveMap,
@@ -11,14 +20,23 @@ use engine_shared::{
     transform::{self, Transform},
     TypeMismatch,
 };
-use types::{account::PublicKey, bytesrepr, Key, ProtocolVersion, U512};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, ToBytes},
+    Key, ProtocolVersion, U512,
+};
 
 use crate::{
+    commit_sequence_store::CommitSequenceStore,
+    global_state::{durability::Durability, read_stats::ReadStats},
     protocol_data::ProtocolData,
-    transaction_source::{Transaction, TransactionSource},
-    trie::Trie,
+    root_metadata_store::{RootMetadata, RootMetadataStore},
+    store::Store,
+    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    trie::{Pointer, Trie, TrieMerkleProof},
     trie_store::{
-        operations::{read, write, ReadResult, WriteResult},
+        node_cache::TrieNodeCache,
+        operations::{self, delete, read, write, DeleteResult, ReadResult, WriteResult},
         TrieStore,
     },
     GAUGE_METRIC_KEY,
@@ -30,6 +48,13 @@ const GLOBAL_STATE_COMMIT_DURATION: &str = "global_state_commit_duration";
 const GLOBAL_STATE_COMMIT_READ_DURATION: &str = "global_state_commit_read_duration";
 const GLOBAL_STATE_COMMIT_WRITE_DURATION: &str = "global_state_commit_write_duration";
 const COMMIT: &str = "commit";
+const GLOBAL_STATE_BATCH_COMMIT_DURATION: &str = "global_state_batch_commit_duration";
+const BATCH_COMMIT: &str = "batch_commit";
+const GLOBAL_STATE_COMPUTE_STATE_ROOT_DURATION: &str = "global_state_compute_state_root_duration";
+const COMPUTE_STATE_ROOT: &str = "compute_state_root";
+const GLOBAL_STATE_PRUNE_DURATION: &str = "global_state_prune_duration";
+const GLOBAL_STATE_PRUNE_DELETED: &str = "global_state_prune_deleted";
+const PRUNE: &str = "prune";
 
 /// A reader of state
 pub trait StateReader<K, V> {
@@ -38,6 +63,34 @@ pub trait StateReader<K, V> {
 
     /// Returns the state value from the corresponding key
     fn read(&self, correlation_id: CorrelationId, key: &K) -> Result<Option<V>, Self::Error>;
+
+    /// Returns the state value from the corresponding key like [`read`](StateReader::read),
+    /// tallying the read and the trie nodes it visits in `stats`.
+    ///
+    /// Readers which don't read from a trie only count the read itself.
+    fn read_counted(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+        stats: &Cell<ReadStats>,
+    ) -> Result<Option<V>, Self::Error> {
+        read_stats::count_read(stats, || self.read(correlation_id, key))
+    }
+
+    /// Returns the state value from the corresponding key with a proof that it is held by the trie
+    /// of this state, or `None` if the key isn't found.
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &K,
+    ) -> Result<Option<TrieMerkleProof<K, V>>, Self::Error>;
+
+    /// Returns the keys whose serialized form starts with `prefix`
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<K>, Self::Error>;
 }
 
 #[derive(Debug)]
@@ -45,11 +98,21 @@ pub enum CommitResult {
     RootNotFound,
     Success {
         state_root: Blake2bHash,
-        bonded_validators: HashMap<PublicKey, U512>,
+        /// The stakes of the bonded validators, in the canonical order: sorted by the bytes of the
+        /// validators' public keys.  The node hashes the list, so the order must never change.
+        bonded_validators: BTreeMap<PublicKey, U512>,
+        /// The number of commits applied on top of the pre-state, including this one.
+        sequence_number: u64,
     },
     KeyNotFound(Key),
     TypeMismatch(TypeMismatch),
     Serialization(bytesrepr::Error),
+    /// A fenced commit was rejected because other commits were applied on top of the pre-state
+    /// since the caller last saw its sequence number.
+    StaleCommit {
+        last_seen_sequence_number: u64,
+        current_sequence_number: u64,
+    },
 }
 
 impl fmt::Display for CommitResult {
@@ -59,16 +122,64 @@ impl fmt::Display for CommitResult {
             CommitResult::Success {
                 state_root,
                 bonded_validators,
+                sequence_number,
             } => write!(
                 f,
-                "Success: state_root: {}, bonded_validators: {:?}",
-                state_root, bonded_validators
+                "Success: state_root: {}, bonded_validators: {:?}, sequence_number: {}",
+                state_root, bonded_validators, sequence_number
             ),
             CommitResult::KeyNotFound(key) => write!(f, "Key not found: {}", key),
             CommitResult::TypeMismatch(type_mismatch) => {
                 write!(f, "Type mismatch: {:?}", type_mismatch)
             }
             CommitResult::Serialization(error) => write!(f, "Serialization: {:?}", error),
+            CommitResult::StaleCommit {
+                last_seen_sequence_number,
+                current_sequence_number,
+            } => write!(
+                f,
+                "Stale commit: last seen sequence number: {}, current sequence number: {}",
+                last_seen_sequence_number, current_sequence_number
+            ),
+        }
+    }
+}
+
+/// The outcome of applying a batch of commits in a single transaction.  Unless it is a success,
+/// none of the commits in the batch was applied.
+#[derive(Debug)]
+pub enum BatchCommitResult {
+    /// The result of each commit, in the order of the batch.  Each is a
+    /// [`CommitResult::Success`].
+    Success(Vec<CommitResult>),
+    /// The commit at `index` failed with `result`.
+    Failure { index: usize, result: CommitResult },
+    /// The pre-state of the commit at `index` isn't the post-state of the commit before it.
+    PreStateMismatch {
+        index: usize,
+        expected: Blake2bHash,
+        found: Blake2bHash,
+    },
+}
+
+impl fmt::Display for BatchCommitResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            BatchCommitResult::Success(results) => {
+                write!(f, "Success: {} commits applied", results.len())
+            }
+            BatchCommitResult::Failure { index, result } => {
+                write!(f, "Commit {} failed: {}", index, result)
+            }
+            BatchCommitResult::PreStateMismatch {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Commit {} has pre-state {} but the commit before it resulted in {}",
+                index, found, expected
+            ),
         }
     }
 }
@@ -91,6 +202,15 @@ pub trait StateProvider {
     /// Checkouts to the post state of a specific block.
     fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error>;
 
+    /// Checkouts to the post state of a specific block like [`checkout`](StateProvider::checkout),
+    /// but the returned reader reads trie nodes through `trie_node_cache`, which can be shared
+    /// with the readers of other post states.
+    fn checkout_with_cache(
+        &self,
+        state_hash: Blake2bHash,
+        trie_node_cache: &TrieNodeCache,
+    ) -> Result<Option<Self::Reader>, Self::Error>;
+
     /// Applies changes and returns a new post state hash.
     /// block_hash is used for computing a deterministic and unique keys.
     fn commit(
@@ -100,6 +220,37 @@ pub trait StateProvider {
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<CommitResult, Self::Error>;
 
+    /// Applies changes like [`commit`](StateProvider::commit), but only if the current commit
+    /// sequence number of `state_hash` is `last_seen_sequence_number`.  Otherwise nothing is
+    /// applied and [`CommitResult::StaleCommit`] is returned.
+    fn commit_fenced(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+        last_seen_sequence_number: u64,
+    ) -> Result<CommitResult, Self::Error>;
+
+    /// Applies each `(state_hash, effects)` entry like [`commit`](StateProvider::commit), all in a
+    /// single storage transaction.  Each entry's pre-state must be the post-state of the entry
+    /// before it.  If any entry fails, nothing in the batch is applied.
+    fn batch_commit(
+        &self,
+        correlation_id: CorrelationId,
+        entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<BatchCommitResult, Self::Error>;
+
+    /// Returns the hash of the state holding exactly the given `pairs`, which is the hash of the
+    /// state produced by writing them onto the empty root.
+    ///
+    /// The state is stored if `persist` is set, and can then be checked out.
+    fn compute_state_root(
+        &self,
+        correlation_id: CorrelationId,
+        pairs: Vec<(Key, StoredValue)>,
+        persist: bool,
+    ) -> Result<Blake2bHash, Self::Error>;
+
     fn put_protocol_data(
         &self,
         protocol_version: ProtocolVersion,
@@ -112,37 +263,465 @@ pub trait StateProvider {
     ) -> Result<Option<ProtocolData>, Self::Error>;
 
     fn empty_root(&self) -> Blake2bHash;
+
+    /// Returns at most `limit` of the state roots known to the store with their metadata, in the
+    /// byte order of the root hashes, starting with the first root after `start_after`.
+    ///
+    /// Roots created by commits, by genesis and by upgrades are listed until they are pruned.
+    fn list_roots(
+        &self,
+        limit: usize,
+        start_after: Option<Blake2bHash>,
+    ) -> Result<Vec<(Blake2bHash, RootMetadata)>, Self::Error>;
+
+    /// Marks `root` as created by a genesis whose config has the fingerprint `genesis_fingerprint`,
+    /// recording it first if it isn't listed yet.
+    fn record_genesis(
+        &self,
+        root: Blake2bHash,
+        genesis_fingerprint: Blake2bHash,
+    ) -> Result<(), Self::Error>;
+
+    /// Returns the serialized trie elements stored under `hashes`, in the same order, or `None` for
+    /// the hashes which aren't in the store.
+    ///
+    /// Elements are looked up directly by hash, so no state needs to be checked out and any element
+    /// reachable from some retained root can be read.
+    fn read_trie_nodes(&self, hashes: &[Blake2bHash]) -> Result<Vec<Option<Vec<u8>>>, Self::Error>;
+
+    /// Prunes `roots`: they are no longer listed or can be checked out, and the trie elements
+    /// which are only reachable from them are deleted.  Returns the number of elements deleted.
+    ///
+    /// The empty root and the roots created by genesis are never pruned.  Elements reachable from a
+    /// root which isn't listed, e.g. one created before roots were recorded, are deleted unless
+    /// a listed root reaches them too.
+    fn prune_roots(
+        &self,
+        correlation_id: CorrelationId,
+        roots: &[Blake2bHash],
+    ) -> Result<usize, Self::Error>;
+
+    /// Makes the commits applied so far durable as required by `durability`, `state_root` being
+    /// the post-state of the latest of them.  Returns whether they are durable, i.e. whether they
+    /// would survive a crash.
+    fn make_durable(
+        &self,
+        durability: Durability,
+        state_root: Blake2bHash,
+    ) -> Result<bool, Self::Error>;
+
+    /// Returns the post-state of the latest commit known to be durable.
+    ///
+    /// After a restart, this is the latest root created before the restart, as every root found in
+    /// storage then is durable.
+    fn last_durable_root(&self) -> Option<Blake2bHash>;
+}
+
+/// Records `metadata` for `root` unless it is already recorded.
+fn record_root<T, M>(
+    txn: &mut T,
+    root_store: &M,
+    root: Blake2bHash,
+    metadata: RootMetadata,
+) -> Result<(), M::Error>
+where
+    T: Readable<Handle = M::Handle> + Writable<Handle = M::Handle>,
+    M: RootMetadataStore,
+    M::Error: From<T::Error>,
+{
+    if root_store.get(txn, &root)?.is_none() {
+        root_store.put(txn, &root, &metadata)?;
+    }
+    Ok(())
+}
+
+/// Records the empty root, which was created from scratch, unless it is already recorded.
+pub fn record_empty_root<'a, R, M, E>(
+    environment: &'a R,
+    root_store: &M,
+    empty_root: Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = M::Handle>,
+    M: RootMetadataStore,
+    M::Error: From<R::Error>,
+    E: From<R::Error> + From<M::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    record_root(&mut txn, root_store, empty_root, RootMetadata::now(None))?;
+    txn.commit()?;
+    Ok(())
+}
+
+/// Returns at most `limit` of the roots recorded in `root_store`, starting after `start_after`.
+pub fn list_roots<'a, R, M, E>(
+    environment: &'a R,
+    root_store: &M,
+    limit: usize,
+    start_after: Option<Blake2bHash>,
+) -> Result<Vec<(Blake2bHash, RootMetadata)>, E>
+where
+    R: TransactionSource<'a, Handle = M::Handle>,
+    M: RootMetadataStore,
+    M::Error: From<R::Error>,
+    E: From<R::Error> + From<M::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let roots = root_store.list(&txn, start_after, limit)?;
+    txn.commit()?;
+    Ok(roots)
+}
+
+/// Marks `root` in `root_store` as created by the genesis with `genesis_fingerprint`.
+pub fn record_genesis<'a, R, M, E>(
+    environment: &'a R,
+    root_store: &M,
+    root: Blake2bHash,
+    genesis_fingerprint: Blake2bHash,
+) -> Result<(), E>
+where
+    R: TransactionSource<'a, Handle = M::Handle>,
+    M: RootMetadataStore,
+    M::Error: From<R::Error>,
+    E: From<R::Error> + From<M::Error>,
+{
+    let mut txn = environment.create_read_write_txn()?;
+    let mut metadata = root_store
+        .get(&txn, &root)?
+        .unwrap_or_else(|| RootMetadata::now(None));
+    metadata.genesis_fingerprint = Some(genesis_fingerprint);
+    root_store.put(&mut txn, &root, &metadata)?;
+    txn.commit()?;
+    Ok(())
+}
+
+/// Returns the most recently created of the roots recorded in `root_store`, if any.
+pub fn latest_root<'a, R, M, E>(
+    environment: &'a R,
+    root_store: &M,
+) -> Result<Option<Blake2bHash>, E>
+where
+    R: TransactionSource<'a, Handle = M::Handle>,
+    M: RootMetadataStore,
+    M::Error: From<R::Error>,
+    E: From<R::Error> + From<M::Error>,
+{
+    let roots = list_roots::<R, M, E>(environment, root_store, usize::max_value(), None)?;
+    Ok(roots
+        .into_iter()
+        .max_by_key(|(_, metadata)| metadata.created_at)
+        .map(|(root, _)| root))
+}
+
+/// Returns the serialized trie elements stored in `store` under `hashes`, in a single transaction.
+pub fn read_trie_nodes<'a, R, S, E>(
+    environment: &'a R,
+    store: &S,
+    hashes: &[Blake2bHash],
+) -> Result<Vec<Option<Vec<u8>>>, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error>,
+{
+    let txn = environment.create_read_txn()?;
+    let mut nodes = Vec::with_capacity(hashes.len());
+    for hash in hashes {
+        let key_bytes = hash.to_bytes().map_err(S::Error::from)?;
+        nodes.push(txn.read(store.handle(), &key_bytes)?);
+    }
+    txn.commit()?;
+    Ok(nodes)
+}
+
+/// Returns the hashes of the elements a trie element points to.
+fn children(trie: &Trie<Key, StoredValue>) -> Vec<Blake2bHash> {
+    match trie {
+        Trie::Leaf { .. } => Vec::new(),
+        Trie::Node { pointer_block } => pointer_block[..]
+            .iter()
+            .filter_map(|maybe_pointer| maybe_pointer.as_ref().map(Pointer::hash).cloned())
+            .collect(),
+        Trie::Extension { pointer, .. } => vec![*pointer.hash()],
+    }
+}
+
+/// Prunes `roots` as described in [`StateProvider::prune_roots`], in a single transaction.
+///
+/// Every element reachable from the retained roots is marked first, then the elements reachable
+/// from the pruned roots are deleted, except for the marked ones and their descendants.
+#[allow(clippy::too_many_arguments)]
+pub fn prune_roots<'a, R, S, C, M, E>(
+    environment: &'a R,
+    store: &S,
+    sequence_store: &C,
+    root_store: &M,
+    correlation_id: CorrelationId,
+    empty_root: Blake2bHash,
+    roots: &[Blake2bHash],
+) -> Result<usize, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    C: CommitSequenceStore<Handle = S::Handle>,
+    C::Error: From<R::Error>,
+    M: RootMetadataStore<Handle = S::Handle>,
+    M::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<C::Error> + From<M::Error>,
+{
+    let start = Instant::now();
+    let mut txn = environment.create_read_write_txn()?;
+
+    let listed_roots = root_store.list(&txn, None, usize::max_value())?;
+    let genesis_roots: HashSet<Blake2bHash> = listed_roots
+        .iter()
+        .filter(|(_, metadata)| metadata.genesis_fingerprint.is_some())
+        .map(|(root, _)| *root)
+        .collect();
+    let pruned: HashSet<Blake2bHash> = roots
+        .iter()
+        .filter(|root| **root != empty_root && !genesis_roots.contains(root))
+        .cloned()
+        .collect();
+    let mut retained_roots = vec![empty_root];
+    retained_roots.extend(
+        listed_roots
+            .into_iter()
+            .map(|(root, _)| root)
+            .filter(|root| !pruned.contains(root)),
+    );
+
+    let mut marked = HashSet::new();
+    let mut stack = retained_roots;
+    while let Some(hash) = stack.pop() {
+        if !marked.insert(hash) {
+            continue;
+        }
+        if let Some(trie) = store.get(&txn, &hash)? {
+            stack.extend(children(&trie));
+        }
+    }
+
+    let mut deleted = HashSet::new();
+    let mut stack: Vec<Blake2bHash> = pruned.iter().cloned().collect();
+    while let Some(hash) = stack.pop() {
+        if marked.contains(&hash) || deleted.contains(&hash) {
+            continue;
+        }
+        if let Some(trie) = store.get(&txn, &hash)? {
+            stack.extend(children(&trie));
+            store.delete(&mut txn, &hash)?;
+            deleted.insert(hash);
+        }
+    }
+
+    for root in &pruned {
+        root_store.delete(&mut txn, root)?;
+        sequence_store.delete(&mut txn, root)?;
+    }
+
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_DURATION,
+        PRUNE,
+        start.elapsed(),
+    );
+
+    log_metric(
+        correlation_id,
+        GLOBAL_STATE_PRUNE_DELETED,
+        PRUNE,
+        GAUGE_METRIC_KEY,
+        deleted.len() as f64,
+    );
+
+    Ok(deleted.len())
 }
 
-pub fn commit<'a, R, S, H, E>(
+/// Applies `effects` on top of `prestate_hash` and increments the commit sequence number of
+/// `prestate_hash`.
+///
+/// If `last_seen_sequence_number` is given and differs from the current sequence number, nothing is
+/// applied.  The check and the increment happen in the same transaction as the writes.
+#[allow(clippy::too_many_arguments)]
+pub fn commit<'a, R, S, C, M, H, E>(
     environment: &'a R,
     store: &S,
+    sequence_store: &C,
+    root_store: &M,
     correlation_id: CorrelationId,
     prestate_hash: Blake2bHash,
     effects: AdditiveMap<Key, Transform, H>,
+    last_seen_sequence_number: Option<u64>,
 ) -> Result<CommitResult, E>
 where
     R: TransactionSource<'a, Handle = S::Handle>,
     S: TrieStore<Key, StoredValue>,
     S::Error: From<R::Error>,
-    E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    C: CommitSequenceStore<Handle = S::Handle>,
+    C::Error: From<R::Error>,
+    M: RootMetadataStore<Handle = S::Handle>,
+    M::Error: From<R::Error>,
+    E: From<R::Error>
+        + From<S::Error>
+        + From<C::Error>
+        + From<M::Error>
+        + From<types::bytesrepr::Error>,
     H: BuildHasher,
 {
+    let start = Instant::now();
+    let mut txn = environment.create_read_write_txn()?;
+
+    let commit_result = commit_in_txn::<_, _, _, _, _, E>(
+        &mut txn,
+        store,
+        sequence_store,
+        root_store,
+        correlation_id,
+        prestate_hash,
+        effects,
+        last_seen_sequence_number,
+    )?;
+    if let CommitResult::Success { .. } = commit_result {
+        txn.commit()?;
+    }
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_COMMIT_DURATION,
+        COMMIT,
+        start.elapsed(),
+    );
+
+    Ok(commit_result)
+}
+
+/// Applies each `(prestate_hash, effects)` entry of `entries` as an unfenced [`commit`], all in a
+/// single transaction which is only committed if every entry succeeds.
+///
+/// Each entry's pre-state must be the post-state of the entry before it.
+pub fn batch_commit<'a, R, S, C, M, H, E>(
+    environment: &'a R,
+    store: &S,
+    sequence_store: &C,
+    root_store: &M,
+    correlation_id: CorrelationId,
+    entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform, H>)>,
+) -> Result<BatchCommitResult, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    C: CommitSequenceStore<Handle = S::Handle>,
+    C::Error: From<R::Error>,
+    M: RootMetadataStore<Handle = S::Handle>,
+    M::Error: From<R::Error>,
+    E: From<R::Error>
+        + From<S::Error>
+        + From<C::Error>
+        + From<M::Error>
+        + From<types::bytesrepr::Error>,
+    H: BuildHasher,
+{
+    let start = Instant::now();
     let mut txn = environment.create_read_write_txn()?;
+
+    let mut results = Vec::with_capacity(entries.len());
+    let mut expected_prestate_hash = None;
+    for (index, (prestate_hash, effects)) in entries.into_iter().enumerate() {
+        if let Some(expected) = expected_prestate_hash {
+            if prestate_hash != expected {
+                return Ok(BatchCommitResult::PreStateMismatch {
+                    index,
+                    expected,
+                    found: prestate_hash,
+                });
+            }
+        }
+        let commit_result = commit_in_txn::<_, _, _, _, _, E>(
+            &mut txn,
+            store,
+            sequence_store,
+            root_store,
+            correlation_id,
+            prestate_hash,
+            effects,
+            None,
+        )?;
+        match commit_result {
+            CommitResult::Success { state_root, .. } => {
+                expected_prestate_hash = Some(state_root);
+                results.push(commit_result);
+            }
+            result => return Ok(BatchCommitResult::Failure { index, result }),
+        }
+    }
+    txn.commit()?;
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_BATCH_COMMIT_DURATION,
+        BATCH_COMMIT,
+        start.elapsed(),
+    );
+
+    Ok(BatchCommitResult::Success(results))
+}
+
+/// Applies `effects` on top of `prestate_hash` within `txn` as described in [`commit`], leaving it
+/// to the caller to commit the transaction, or to drop it if the result isn't a success.
+#[allow(clippy::too_many_arguments)]
+fn commit_in_txn<T, S, C, M, H, E>(
+    txn: &mut T,
+    store: &S,
+    sequence_store: &C,
+    root_store: &M,
+    correlation_id: CorrelationId,
+    prestate_hash: Blake2bHash,
+    effects: AdditiveMap<Key, Transform, H>,
+    last_seen_sequence_number: Option<u64>,
+) -> Result<CommitResult, E>
+where
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<T::Error>,
+    C: CommitSequenceStore<Handle = S::Handle>,
+    C::Error: From<T::Error>,
+    M: RootMetadataStore<Handle = S::Handle>,
+    M::Error: From<T::Error>,
+    E: From<S::Error> + From<C::Error> + From<M::Error> + From<types::bytesrepr::Error>,
+    H: BuildHasher,
+{
     let mut state_root = prestate_hash;
 
-    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&txn, &state_root)?;
+    let maybe_root: Option<Trie<Key, StoredValue>> = store.get(&*txn, &state_root)?;
 
     if maybe_root.is_none() {
         return Ok(CommitResult::RootNotFound);
     };
 
+    let current_sequence_number = sequence_store
+        .get(&*txn, &prestate_hash)?
+        .unwrap_or_default();
+    if let Some(last_seen_sequence_number) = last_seen_sequence_number {
+        if last_seen_sequence_number != current_sequence_number {
+            return Ok(CommitResult::StaleCommit {
+                last_seen_sequence_number,
+                current_sequence_number,
+            });
+        }
+    }
+
     let start = Instant::now();
     let mut reads: i32 = 0;
     let mut writes: i32 = 0;
 
     for (key, transform) in effects.into_iter() {
-        let read_result = read::<_, _, _, _, E>(correlation_id, &txn, store, &state_root, &key)?;
+        let read_result = read::<_, _, _, _, E>(correlation_id, &*txn, store, &state_root, &key)?;
 
         log_duration(
             correlation_id,
@@ -155,6 +734,18 @@ where
 
         let value = match (read_result, transform) {
             (ReadResult::NotFound, Transform::Write(new_value)) => new_value,
+            // There is nothing to prune.
+            (ReadResult::NotFound, Transform::Prune) => continue,
+            (ReadResult::Found(_), Transform::Prune) => {
+                match delete::<_, _, _, _, E>(correlation_id, txn, store, &state_root, &key)? {
+                    DeleteResult::Deleted(root_hash) => {
+                        state_root = root_hash;
+                        writes += 1;
+                    }
+                    other => panic!("should delete the key just read: {:?}", other),
+                }
+                continue;
+            }
             (ReadResult::NotFound, _) => {
                 return Ok(CommitResult::KeyNotFound(key));
             }
@@ -166,7 +757,7 @@ where
         };
 
         let write_result =
-            write::<_, _, _, _, E>(correlation_id, &mut txn, store, &state_root, &key, &value)?;
+            write::<_, _, _, _, E>(correlation_id, txn, store, &state_root, &key, &value)?;
 
         log_duration(
             correlation_id,
@@ -185,14 +776,15 @@ where
         }
     }
 
-    txn.commit()?;
+    let sequence_number = current_sequence_number + 1;
+    sequence_store.put(txn, &prestate_hash, &sequence_number)?;
 
-    log_duration(
-        correlation_id,
-        GLOBAL_STATE_COMMIT_DURATION,
-        COMMIT,
-        start.elapsed(),
-    );
+    let parent_hint = if state_root == prestate_hash {
+        None
+    } else {
+        Some(prestate_hash)
+    };
+    record_root(txn, root_store, state_root, RootMetadata::now(parent_hint))?;
 
     log_metric(
         correlation_id,
@@ -215,5 +807,51 @@ where
     Ok(CommitResult::Success {
         state_root,
         bonded_validators,
+        sequence_number,
     })
 }
+
+/// Builds the trie holding exactly the given `pairs` and returns its root hash, storing all its
+/// elements and recording the root in a single transaction if `persist` is set.
+pub fn compute_state_root<'a, R, S, M, E>(
+    environment: &'a R,
+    store: &S,
+    root_store: &M,
+    correlation_id: CorrelationId,
+    pairs: Vec<(Key, StoredValue)>,
+    persist: bool,
+) -> Result<Blake2bHash, E>
+where
+    R: TransactionSource<'a, Handle = S::Handle>,
+    S: TrieStore<Key, StoredValue>,
+    S::Error: From<R::Error>,
+    M: RootMetadataStore<Handle = S::Handle>,
+    M::Error: From<R::Error>,
+    E: From<R::Error> + From<S::Error> + From<M::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+
+    let pairs = pairs
+        .into_iter()
+        .map(|(key, value)| (key.normalize(), value))
+        .collect();
+    let (state_root, elements) = operations::build::<Key, StoredValue>(pairs)?;
+
+    if persist {
+        let mut txn = environment.create_read_write_txn()?;
+        for (hash, element) in elements.iter() {
+            store.put(&mut txn, hash, element)?;
+        }
+        record_root(&mut txn, root_store, state_root, RootMetadata::now(None))?;
+        txn.commit()?;
+    }
+
+    log_duration(
+        correlation_id,
+        GLOBAL_STATE_COMPUTE_STATE_ROOT_DURATION,
+        COMPUTE_STATE_ROOT,
+        start.elapsed(),
+    );
+
+    Ok(state_root)
+}
@@ -0,0 +1,311 @@
+//! Counting the work done by global state reads, to find reads which visit many more trie nodes
+//! than their keys would suggest.
+use std::{cell::Cell, rc::Rc};
+
+use engine_shared::{
+    additive_map::AdditiveMap,
+    logging::log_metric,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use types::{Key, ProtocolVersion};
+
+use crate::{
+    global_state::{
+        durability::Durability, BatchCommitResult, CommitResult, StateProvider, StateReader,
+    },
+    protocol_data::ProtocolData,
+    root_metadata_store::RootMetadata,
+    trie::TrieMerkleProof,
+    trie_store::node_cache::{NodeReadSink, TrieNodeCache},
+    GAUGE_METRIC_KEY,
+};
+
+const GLOBAL_STATE_READS: &str = "global_state_reads";
+const GLOBAL_STATE_READ_NODES_VISITED: &str = "global_state_read_nodes_visited";
+const GLOBAL_STATE_READ_BYTES: &str = "global_state_read_bytes";
+const GLOBAL_STATE_READ_CACHE_HITS: &str = "global_state_read_cache_hits";
+const GLOBAL_STATE_READ_MAX_NODES: &str = "global_state_read_max_nodes";
+const BLOCK: &str = "block";
+
+/// The work done by a sequence of global state reads.
+///
+/// The bytes read and cache hits depend on which trie nodes happen to be cached, which differs
+/// between nodes and between runs, so these are only fit for logs and metrics, never for gas or
+/// anything else execution depends on.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ReadStats {
+    /// The number of values read, i.e. of calls to [`StateReader::read`].
+    pub reads: u64,
+    /// The number of trie nodes visited by the reads, whether read from storage or from a cache.
+    pub nodes_visited: u64,
+    /// The number of bytes of trie nodes read from storage.
+    pub bytes_read: u64,
+    /// The number of trie nodes found in a [`TrieNodeCache`] rather than read from storage.
+    pub cache_hits: u64,
+    /// The most trie nodes visited by a single read.
+    pub max_nodes_per_read: u64,
+}
+
+impl ReadStats {
+    pub fn new() -> Self {
+        ReadStats::default()
+    }
+
+    /// Adds the reads tallied in `other` to these.
+    pub fn merge(&mut self, other: &ReadStats) {
+        self.reads += other.reads;
+        self.nodes_visited += other.nodes_visited;
+        self.bytes_read += other.bytes_read;
+        self.cache_hits += other.cache_hits;
+        self.max_nodes_per_read = self.max_nodes_per_read.max(other.max_nodes_per_read);
+    }
+
+    /// Logs the totals of a block's reads as metrics.
+    pub fn log_metrics(&self, correlation_id: CorrelationId) {
+        let metrics = [
+            (GLOBAL_STATE_READS, self.reads),
+            (GLOBAL_STATE_READ_NODES_VISITED, self.nodes_visited),
+            (GLOBAL_STATE_READ_BYTES, self.bytes_read),
+            (GLOBAL_STATE_READ_CACHE_HITS, self.cache_hits),
+            (GLOBAL_STATE_READ_MAX_NODES, self.max_nodes_per_read),
+        ];
+        for (metric, value) in metrics.iter() {
+            log_metric(
+                correlation_id,
+                metric,
+                BLOCK,
+                GAUGE_METRIC_KEY,
+                *value as f64,
+            );
+        }
+    }
+}
+
+impl NodeReadSink for Cell<ReadStats> {
+    fn record_node_read(&self, bytes: usize) {
+        let mut stats = self.get();
+        stats.nodes_visited += 1;
+        stats.bytes_read += bytes as u64;
+        self.set(stats);
+    }
+
+    fn record_cache_hit(&self) {
+        let mut stats = self.get();
+        stats.nodes_visited += 1;
+        stats.cache_hits += 1;
+        self.set(stats);
+    }
+}
+
+/// Runs the logical read `read`, whose trie nodes are tallied in `stats`, and counts it.
+pub(crate) fn count_read<T>(stats: &Cell<ReadStats>, read: impl FnOnce() -> T) -> T {
+    let nodes_visited_before = stats.get().nodes_visited;
+    let result = read();
+    let mut after = stats.get();
+    after.reads += 1;
+    after.max_nodes_per_read = after
+        .max_nodes_per_read
+        .max(after.nodes_visited - nodes_visited_before);
+    stats.set(after);
+    result
+}
+
+/// A state provider whose readers count the work done by their reads.
+///
+/// Providers which aren't wrapped don't count anything, so reads only pay for counting when stats
+/// are collected.
+pub struct CountingStateProvider<'a, S> {
+    state: &'a S,
+    stats: Rc<Cell<ReadStats>>,
+}
+
+impl<'a, S> CountingStateProvider<'a, S> {
+    pub fn new(state: &'a S) -> Self {
+        CountingStateProvider {
+            state,
+            stats: Default::default(),
+        }
+    }
+
+    /// Returns the shared tally of the reads made through the readers this provider checked out.
+    pub fn stats(&self) -> Rc<Cell<ReadStats>> {
+        Rc::clone(&self.stats)
+    }
+
+    fn counting<R>(&self, reader: Option<R>) -> Option<CountingReader<R>> {
+        reader.map(|reader| CountingReader {
+            reader,
+            stats: self.stats(),
+        })
+    }
+}
+
+/// A reader which counts the work done by its reads.
+pub struct CountingReader<R> {
+    reader: R,
+    stats: Rc<Cell<ReadStats>>,
+}
+
+impl<R> StateReader<Key, StoredValue> for CountingReader<R>
+where
+    R: StateReader<Key, StoredValue>,
+{
+    type Error = R::Error;
+
+    fn read(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        self.reader.read_counted(correlation_id, key, &self.stats)
+    }
+
+    fn read_counted(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+        stats: &Cell<ReadStats>,
+    ) -> Result<Option<StoredValue>, Self::Error> {
+        self.reader.read_counted(correlation_id, key, stats)
+    }
+
+    fn read_with_proof(
+        &self,
+        correlation_id: CorrelationId,
+        key: &Key,
+    ) -> Result<Option<TrieMerkleProof<Key, StoredValue>>, Self::Error> {
+        self.reader.read_with_proof(correlation_id, key)
+    }
+
+    fn keys_with_prefix(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.reader.keys_with_prefix(correlation_id, prefix)
+    }
+}
+
+impl<'a, S: StateProvider> StateProvider for CountingStateProvider<'a, S> {
+    type Error = S::Error;
+    type Reader = CountingReader<S::Reader>;
+
+    fn checkout(&self, state_hash: Blake2bHash) -> Result<Option<Self::Reader>, Self::Error> {
+        Ok(self.counting(self.state.checkout(state_hash)?))
+    }
+
+    fn checkout_with_cache(
+        &self,
+        state_hash: Blake2bHash,
+        trie_node_cache: &TrieNodeCache,
+    ) -> Result<Option<Self::Reader>, Self::Error> {
+        Ok(self.counting(
+            self.state
+                .checkout_with_cache(state_hash, trie_node_cache)?,
+        ))
+    }
+
+    fn commit(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+    ) -> Result<CommitResult, Self::Error> {
+        self.state.commit(correlation_id, state_hash, effects)
+    }
+
+    fn commit_fenced(
+        &self,
+        correlation_id: CorrelationId,
+        state_hash: Blake2bHash,
+        effects: AdditiveMap<Key, Transform>,
+        last_seen_sequence_number: u64,
+    ) -> Result<CommitResult, Self::Error> {
+        self.state.commit_fenced(
+            correlation_id,
+            state_hash,
+            effects,
+            last_seen_sequence_number,
+        )
+    }
+
+    fn batch_commit(
+        &self,
+        correlation_id: CorrelationId,
+        entries: Vec<(Blake2bHash, AdditiveMap<Key, Transform>)>,
+    ) -> Result<BatchCommitResult, Self::Error> {
+        self.state.batch_commit(correlation_id, entries)
+    }
+
+    fn compute_state_root(
+        &self,
+        correlation_id: CorrelationId,
+        pairs: Vec<(Key, StoredValue)>,
+        persist: bool,
+    ) -> Result<Blake2bHash, Self::Error> {
+        self.state
+            .compute_state_root(correlation_id, pairs, persist)
+    }
+
+    fn put_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+        protocol_data: &ProtocolData,
+    ) -> Result<(), Self::Error> {
+        self.state
+            .put_protocol_data(protocol_version, protocol_data)
+    }
+
+    fn get_protocol_data(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Option<ProtocolData>, Self::Error> {
+        self.state.get_protocol_data(protocol_version)
+    }
+
+    fn empty_root(&self) -> Blake2bHash {
+        self.state.empty_root()
+    }
+
+    fn list_roots(
+        &self,
+        limit: usize,
+        start_after: Option<Blake2bHash>,
+    ) -> Result<Vec<(Blake2bHash, RootMetadata)>, Self::Error> {
+        self.state.list_roots(limit, start_after)
+    }
+
+    fn record_genesis(
+        &self,
+        root: Blake2bHash,
+        genesis_fingerprint: Blake2bHash,
+    ) -> Result<(), Self::Error> {
+        self.state.record_genesis(root, genesis_fingerprint)
+    }
+
+    fn read_trie_nodes(&self, hashes: &[Blake2bHash]) -> Result<Vec<Option<Vec<u8>>>, Self::Error> {
+        self.state.read_trie_nodes(hashes)
+    }
+
+    fn prune_roots(
+        &self,
+        correlation_id: CorrelationId,
+        roots: &[Blake2bHash],
+    ) -> Result<usize, Self::Error> {
+        self.state.prune_roots(correlation_id, roots)
+    }
+
+    fn make_durable(
+        &self,
+        durability: Durability,
+        state_root: Blake2bHash,
+    ) -> Result<bool, Self::Error> {
+        self.state.make_durable(durability, state_root)
+    }
+
+    fn last_durable_root(&self) -> Option<Blake2bHash> {
+        self.state.last_durable_root()
+    }
+}
@@ -0,0 +1,165 @@
+//! Timing of the storage operations run on behalf of a request.
+//!
+//! Operations slower than a configurable threshold are logged as warnings carrying the correlation
+//! id of the request, and the time spent in storage is summed per correlation id, so that it can be
+//! reported along with the duration of the whole request.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+use lazy_static::lazy_static;
+use log::Level;
+use parking_lot::Mutex;
+
+use engine_shared::{logging, newtypes::CorrelationId};
+
+/// The default duration from which a storage operation is logged as slow.
+pub const DEFAULT_SLOW_OPERATION_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// How many bytes of the key of a slow operation are logged.
+const KEY_PREFIX_LENGTH: usize = 8;
+/// Beyond this many correlation ids whose storage time wasn't taken, all the times are dropped, so
+/// that those of requests which never report them don't accumulate.
+const MAX_PENDING_CORRELATION_IDS: usize = 10_000;
+const SLOW_OPERATION_TEMPLATE: &str =
+    "slow storage {operation} took {duration_millis} ms; key prefix: {key_prefix}";
+
+lazy_static! {
+    static ref STORAGE_TIMES: Mutex<HashMap<CorrelationId, Duration>> = Mutex::new(HashMap::new());
+}
+
+/// A kind of timed storage operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageOperation {
+    Read,
+    ReadWithProof,
+    KeysWithPrefix,
+    Commit,
+    BatchCommit,
+}
+
+impl Display for StorageOperation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StorageOperation::Read => write!(f, "read"),
+            StorageOperation::ReadWithProof => write!(f, "read_with_proof"),
+            StorageOperation::KeysWithPrefix => write!(f, "keys_with_prefix"),
+            StorageOperation::Commit => write!(f, "commit"),
+            StorageOperation::BatchCommit => write!(f, "batch_commit"),
+        }
+    }
+}
+
+/// Times storage operations, warning about those which take at least its threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageTimer {
+    slow_operation_threshold: Duration,
+}
+
+impl StorageTimer {
+    pub fn new(slow_operation_threshold: Duration) -> Self {
+        StorageTimer {
+            slow_operation_threshold,
+        }
+    }
+
+    pub fn slow_operation_threshold(&self) -> Duration {
+        self.slow_operation_threshold
+    }
+
+    /// Runs `f`, the `operation` on a key, adding the time it takes to the storage time of
+    /// `correlation_id`.
+    ///
+    /// `key_bytes` is only called if the operation is slow, to log the prefix of the key.
+    pub fn time<T, F, B>(
+        &self,
+        correlation_id: CorrelationId,
+        operation: StorageOperation,
+        key_bytes: B,
+        f: F,
+    ) -> T
+    where
+        F: FnOnce() -> T,
+        B: FnOnce() -> Vec<u8>,
+    {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+
+        add_storage_time(correlation_id, duration);
+        if duration >= self.slow_operation_threshold {
+            log_slow_operation(correlation_id, operation, &key_bytes(), duration);
+        }
+        result
+    }
+}
+
+impl Default for StorageTimer {
+    fn default() -> Self {
+        StorageTimer::new(DEFAULT_SLOW_OPERATION_THRESHOLD)
+    }
+}
+
+/// Returns the time spent in the storage operations timed for `correlation_id` so far, and resets
+/// it.
+pub fn take_storage_time(correlation_id: CorrelationId) -> Duration {
+    STORAGE_TIMES
+        .lock()
+        .remove(&correlation_id)
+        .unwrap_or_default()
+}
+
+fn add_storage_time(correlation_id: CorrelationId, duration: Duration) {
+    let mut storage_times = STORAGE_TIMES.lock();
+    if storage_times.len() >= MAX_PENDING_CORRELATION_IDS
+        && !storage_times.contains_key(&correlation_id)
+    {
+        storage_times.clear();
+    }
+    *storage_times.entry(correlation_id).or_default() += duration;
+}
+
+fn log_slow_operation(
+    correlation_id: CorrelationId,
+    operation: StorageOperation,
+    key: &[u8],
+    duration: Duration,
+) {
+    let key_prefix: String = key
+        .iter()
+        .take(KEY_PREFIX_LENGTH)
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    let mut properties = BTreeMap::new();
+    properties.insert("correlation_id", correlation_id.to_string());
+    properties.insert("operation", operation.to_string());
+    properties.insert("duration_millis", duration.as_millis().to_string());
+    properties.insert("key_prefix", key_prefix);
+    logging::log_details(Level::Warn, SLOW_OPERATION_TEMPLATE.to_owned(), properties);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_sum_storage_time_per_correlation_id() {
+        let timer = StorageTimer::default();
+        let correlation_id = CorrelationId::new();
+        let other_correlation_id = CorrelationId::new();
+
+        for _ in 0..2 {
+            timer.time(correlation_id, StorageOperation::Read, Vec::new, || {
+                std::thread::sleep(Duration::from_millis(5))
+            });
+        }
+
+        assert!(take_storage_time(correlation_id) >= Duration::from_millis(10));
+        assert_eq!(take_storage_time(correlation_id), Duration::default());
+        assert_eq!(take_storage_time(other_correlation_id), Duration::default());
+    }
+}
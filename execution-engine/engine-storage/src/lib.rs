@@ -1,9 +1,13 @@
 // modules
+pub mod commit_sequence_store;
 pub mod error;
 pub mod global_state;
 pub mod protocol_data;
 pub mod protocol_data_store;
+pub mod root_metadata_store;
+pub mod staging_store;
 pub mod store;
+pub mod store_metadata_store;
 pub mod transaction_source;
 pub mod trie;
 pub mod trie_store;
@@ -12,7 +16,7 @@ pub mod trie_store;
 use lazy_static::lazy_static;
 
 pub(crate) const GAUGE_METRIC_KEY: &str = "gauge";
-const MAX_DBS: u32 = 2;
+const MAX_DBS: u32 = 6;
 
 #[cfg(test)]
 lazy_static! {
@@ -1,11 +1,15 @@
+use engine_shared::account::{AccountConfig, ACCOUNT_CONFIG_SERIALIZED_LENGTH};
 use engine_wasm_prep::wasm_costs::{WasmCosts, WASM_COSTS_SERIALIZED_LENGTH};
 use types::{
-    bytesrepr::{self, FromBytes, ToBytes},
+    bytesrepr::{self, FromBytes, ToBytes, BOOL_SERIALIZED_LENGTH},
     AccessRights, URef, UREF_SERIALIZED_LENGTH,
 };
 
-const PROTOCOL_DATA_SERIALIZED_LENGTH: usize =
-    WASM_COSTS_SERIALIZED_LENGTH + 3 * UREF_SERIALIZED_LENGTH;
+/// The serialized length of the fields other than the auction contract's optional uref.
+const PROTOCOL_DATA_SERIALIZED_LENGTH: usize = WASM_COSTS_SERIALIZED_LENGTH
+    + 3 * UREF_SERIALIZED_LENGTH
+    + ACCOUNT_CONFIG_SERIALIZED_LENGTH
+    + BOOL_SERIALIZED_LENGTH;
 const DEFAULT_UREF_ADDRESS: [u8; 32] = [0; 32];
 
 /// Represents a protocol's data. Intended to be associated with a given protocol version.
@@ -15,6 +19,9 @@ pub struct ProtocolData {
     mint: URef,
     proof_of_stake: URef,
     standard_payment: URef,
+    account_config: AccountConfig,
+    auction: Option<URef>,
+    use_auction: bool,
 }
 
 /// Provides a default instance with non existing urefs and empty costs table.
@@ -28,6 +35,9 @@ impl Default for ProtocolData {
             mint: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
             proof_of_stake: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
             standard_payment: URef::new(DEFAULT_UREF_ADDRESS, AccessRights::READ),
+            account_config: AccountConfig::default(),
+            auction: None,
+            use_auction: false,
         }
     }
 }
@@ -39,15 +49,27 @@ impl ProtocolData {
         mint: URef,
         proof_of_stake: URef,
         standard_payment: URef,
+        account_config: AccountConfig,
     ) -> Self {
         ProtocolData {
             wasm_costs,
             mint,
             proof_of_stake,
             standard_payment,
+            account_config,
+            auction: None,
+            use_auction: false,
         }
     }
 
+    /// Returns `self` with the given auction contract, and with the bonded validators read from
+    /// its winners rather than from the PoS stakes if `use_auction` is `true`.
+    pub fn with_auction(mut self, auction: Option<URef>, use_auction: bool) -> Self {
+        self.auction = auction;
+        self.use_auction = use_auction;
+        self
+    }
+
     /// Creates a new, partially-valid [`ProtocolData`] value where only the mint URef is known.
     ///
     /// Used during `commit_genesis` before all system contracts' URefs are known.
@@ -92,9 +114,25 @@ impl ProtocolData {
         self.standard_payment
     }
 
+    /// Gets the configuration of the accounts created by transfers.
+    pub fn account_config(&self) -> &AccountConfig {
+        &self.account_config
+    }
+
+    /// Gets the auction contract, if one has been installed.
+    pub fn auction(&self) -> Option<URef> {
+        self.auction
+    }
+
+    /// Returns `true` if the bonded validators are those selected by the auction contract rather
+    /// than those staked in the PoS contract.
+    pub fn use_auction(&self) -> bool {
+        self.use_auction
+    }
+
     /// Retrieves all valid system contracts stored in protocol version
     pub fn system_contracts(&self) -> Vec<URef> {
-        let mut vec = Vec::with_capacity(3);
+        let mut vec = Vec::with_capacity(4);
         if self.mint.addr() != DEFAULT_UREF_ADDRESS {
             vec.push(self.mint)
         }
@@ -104,6 +142,9 @@ impl ProtocolData {
         if self.standard_payment.addr() != DEFAULT_UREF_ADDRESS {
             vec.push(self.standard_payment)
         }
+        if let Some(auction) = self.auction {
+            vec.push(auction)
+        }
         vec
     }
 }
@@ -115,11 +156,14 @@ impl ToBytes for ProtocolData {
         ret.append(&mut self.mint.to_bytes()?);
         ret.append(&mut self.proof_of_stake.to_bytes()?);
         ret.append(&mut self.standard_payment.to_bytes()?);
+        ret.append(&mut self.account_config.to_bytes()?);
+        ret.append(&mut self.auction.to_bytes()?);
+        ret.append(&mut self.use_auction.to_bytes()?);
         Ok(ret)
     }
 
     fn serialized_length(&self) -> usize {
-        PROTOCOL_DATA_SERIALIZED_LENGTH
+        PROTOCOL_DATA_SERIALIZED_LENGTH + self.auction.serialized_length()
     }
 }
 
@@ -129,12 +173,29 @@ impl FromBytes for ProtocolData {
         let (mint, rem) = URef::from_bytes(rem)?;
         let (proof_of_stake, rem) = URef::from_bytes(rem)?;
         let (standard_payment, rem) = URef::from_bytes(rem)?;
+        // protocol data stored before the account config was added doesn't have one
+        let (account_config, rem) = if rem.is_empty() {
+            (AccountConfig::default(), rem)
+        } else {
+            AccountConfig::from_bytes(rem)?
+        };
+        // nor does protocol data stored before the auction contract was added
+        let (auction, use_auction, rem) = if rem.is_empty() {
+            (None, false, rem)
+        } else {
+            let (auction, rem) = Option::<URef>::from_bytes(rem)?;
+            let (use_auction, rem) = bool::from_bytes(rem)?;
+            (auction, use_auction, rem)
+        };
         Ok((
             ProtocolData {
                 wasm_costs,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                account_config,
+                auction,
+                use_auction,
             },
             rem,
         ))
@@ -143,8 +204,9 @@ impl FromBytes for ProtocolData {
 
 #[cfg(test)]
 pub(crate) mod gens {
-    use proptest::prop_compose;
+    use proptest::{bool, option, prop_compose};
 
+    use engine_shared::account::gens::account_config_arb;
     use engine_wasm_prep::wasm_costs::gens as wasm_costs_gens;
     use types::gens;
 
@@ -156,12 +218,18 @@ pub(crate) mod gens {
             mint in gens::uref_arb(),
             proof_of_stake in gens::uref_arb(),
             standard_payment in gens::uref_arb(),
+            account_config in account_config_arb(),
+            auction in option::of(gens::uref_arb()),
+            use_auction in bool::ANY,
         ) -> ProtocolData {
             ProtocolData {
                 wasm_costs,
                 mint,
                 proof_of_stake,
                 standard_payment,
+                account_config,
+                auction,
+                use_auction,
             }
         }
     }
@@ -171,8 +239,12 @@ pub(crate) mod gens {
 mod tests {
     use proptest::proptest;
 
+    use engine_shared::account::{AccountConfig, ACCOUNT_CONFIG_SERIALIZED_LENGTH};
     use engine_wasm_prep::wasm_costs::WasmCosts;
-    use types::{bytesrepr, AccessRights, URef};
+    use types::{
+        bytesrepr::{self, ToBytes, BOOL_SERIALIZED_LENGTH},
+        AccessRights, URef,
+    };
 
     use super::{gens, ProtocolData};
 
@@ -188,6 +260,17 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            transient_storage: 1,
+            max_transient_storage_size: 1024 * 1024,
+            max_transforms_per_deploy: 0,
+            max_effect_bytes_per_deploy: 0,
+            max_session_return_value_bytes: 0,
+            string_byte: 1,
+            max_string_length: 1024,
+            max_revert_data_bytes: 256,
+            base_deploy_cost: 0,
+            rejected_deploy_cost: 0,
+            strict_session_exports: false,
         }
     }
 
@@ -203,6 +286,17 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            transient_storage: 0,
+            max_transient_storage_size: 1024 * 1024,
+            max_transforms_per_deploy: 0,
+            max_effect_bytes_per_deploy: 0,
+            max_session_return_value_bytes: 0,
+            string_byte: 0,
+            max_string_length: 1024,
+            max_revert_data_bytes: 256,
+            base_deploy_cost: 0,
+            rejected_deploy_cost: 0,
+            strict_session_exports: false,
         }
     }
 
@@ -218,6 +312,7 @@ mod tests {
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                AccountConfig::default(),
             )
         };
         let free = {
@@ -230,17 +325,58 @@ mod tests {
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                AccountConfig::default(),
             )
         };
         bytesrepr::test_serialization_roundtrip(&mock);
         bytesrepr::test_serialization_roundtrip(&free);
     }
 
+    #[test]
+    fn should_deserialize_without_account_config() {
+        let protocol_data = ProtocolData::new(
+            wasm_costs_mock(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([1u8; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([2u8; 32], AccessRights::READ_ADD_WRITE),
+            AccountConfig::default(),
+        );
+        let mut bytes = protocol_data.to_bytes().expect("should serialize");
+        bytes.truncate(
+            bytes.len()
+                - ACCOUNT_CONFIG_SERIALIZED_LENGTH
+                - protocol_data.auction().serialized_length()
+                - BOOL_SERIALIZED_LENGTH,
+        );
+        let deserialized: ProtocolData = bytesrepr::deserialize(bytes).expect("should deserialize");
+        assert_eq!(deserialized, protocol_data);
+    }
+
+    #[test]
+    fn should_deserialize_without_auction() {
+        let protocol_data = ProtocolData::new(
+            wasm_costs_mock(),
+            URef::new([0u8; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([1u8; 32], AccessRights::READ_ADD_WRITE),
+            URef::new([2u8; 32], AccessRights::READ_ADD_WRITE),
+            AccountConfig::default(),
+        );
+        let mut bytes = protocol_data.to_bytes().expect("should serialize");
+        bytes.truncate(
+            bytes.len() - protocol_data.auction().serialized_length() - BOOL_SERIALIZED_LENGTH,
+        );
+        let deserialized: ProtocolData = bytesrepr::deserialize(bytes).expect("should deserialize");
+        assert_eq!(deserialized, protocol_data);
+        assert_eq!(deserialized.auction(), None);
+        assert!(!deserialized.use_auction());
+    }
+
     #[test]
     fn should_return_all_system_contracts() {
         let mint_reference = URef::new([197u8; 32], AccessRights::READ_ADD_WRITE);
         let proof_of_stake_reference = URef::new([198u8; 32], AccessRights::READ_ADD_WRITE);
         let standard_payment_reference = URef::new([199u8; 32], AccessRights::READ_ADD_WRITE);
+        let auction_reference = URef::new([200u8; 32], AccessRights::READ_ADD_WRITE);
         let protocol_data = {
             let costs = wasm_costs_mock();
             ProtocolData::new(
@@ -248,7 +384,9 @@ mod tests {
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                AccountConfig::default(),
             )
+            .with_auction(Some(auction_reference), false)
         };
 
         let actual = {
@@ -257,10 +395,11 @@ mod tests {
             items
         };
 
-        assert_eq!(actual.len(), 3);
+        assert_eq!(actual.len(), 4);
         assert_eq!(actual[0], mint_reference);
         assert_eq!(actual[1], proof_of_stake_reference);
         assert_eq!(actual[2], standard_payment_reference);
+        assert_eq!(actual[3], auction_reference);
     }
 
     #[test]
@@ -277,6 +416,7 @@ mod tests {
                 mint_reference,
                 proof_of_stake_reference,
                 standard_payment_reference,
+                AccountConfig::default(),
             )
         };
 
@@ -0,0 +1,35 @@
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error::in_memory::Error,
+    root_metadata_store::{self, RootMetadata, RootMetadataStore},
+    store::Store,
+    transaction_source::in_memory::InMemoryEnvironment,
+};
+
+/// An in-memory root metadata store
+pub struct InMemoryRootMetadataStore {
+    maybe_name: Option<String>,
+}
+
+impl InMemoryRootMetadataStore {
+    pub fn new(_env: &InMemoryEnvironment, maybe_name: Option<&str>) -> Self {
+        let name = maybe_name
+            .map(|name| format!("{}-{}", root_metadata_store::NAME, name))
+            .unwrap_or_else(|| String::from(root_metadata_store::NAME));
+        InMemoryRootMetadataStore {
+            maybe_name: Some(name),
+        }
+    }
+}
+
+impl Store<Blake2bHash, RootMetadata> for InMemoryRootMetadataStore {
+    type Error = Error;
+    type Handle = Option<String>;
+
+    fn handle(&self) -> Self::Handle {
+        self.maybe_name.to_owned()
+    }
+}
+
+impl RootMetadataStore for InMemoryRootMetadataStore {}
@@ -0,0 +1,54 @@
+use lmdb::{Database, DatabaseFlags};
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error,
+    root_metadata_store::{self, RootMetadata, RootMetadataStore},
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed root metadata store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbRootMetadataStore {
+    db: Database,
+}
+
+impl LmdbRootMetadataStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbRootMetadataStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbRootMetadataStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", root_metadata_store::NAME, name))
+            .unwrap_or_else(|| String::from(root_metadata_store::NAME))
+    }
+}
+
+impl Store<Blake2bHash, RootMetadata> for LmdbRootMetadataStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl RootMetadataStore for LmdbRootMetadataStore {}
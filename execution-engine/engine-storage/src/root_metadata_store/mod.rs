@@ -0,0 +1,153 @@
+//! A store for persisting metadata about each state root, recorded when the root is created.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+pub mod in_memory;
+pub mod lmdb;
+
+use crate::{store::Store, transaction_source::Iterable};
+
+const NAME: &str = "ROOT_METADATA_STORE";
+
+/// Metadata about a state root.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RootMetadata {
+    /// When the root was first created, in milliseconds since the Unix epoch by the engine's
+    /// clock.
+    pub created_at: u64,
+    /// The root the effects creating this root were committed on top of, or `None` if it was
+    /// created from scratch, e.g. the empty root or a root computed from a set of values.
+    ///
+    /// Only a hint: the parent may have been pruned since, and the same root can be created by
+    /// commits on top of different parents, of which only the first is recorded.
+    pub parent_hint: Option<Blake2bHash>,
+    /// The fingerprint of the genesis config which created the root, or `None` if it wasn't
+    /// created by genesis.
+    pub genesis_fingerprint: Option<Blake2bHash>,
+}
+
+impl RootMetadata {
+    /// Returns the metadata of a root created now.
+    pub fn now(parent_hint: Option<Blake2bHash>) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+        RootMetadata {
+            created_at,
+            parent_hint,
+            genesis_fingerprint: None,
+        }
+    }
+}
+
+impl ToBytes for RootMetadata {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.created_at.to_bytes()?);
+        result.append(&mut self.parent_hint.to_bytes()?);
+        result.append(&mut self.genesis_fingerprint.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.created_at.serialized_length()
+            + self.parent_hint.serialized_length()
+            + self.genesis_fingerprint.serialized_length()
+    }
+}
+
+impl FromBytes for RootMetadata {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (created_at, rem) = u64::from_bytes(bytes)?;
+        let (parent_hint, rem) = Option::<Blake2bHash>::from_bytes(rem)?;
+        // metadata recorded before genesis roots were marked ends here
+        let (genesis_fingerprint, rem) = if rem.is_empty() {
+            (None, rem)
+        } else {
+            Option::<Blake2bHash>::from_bytes(rem)?
+        };
+        Ok((
+            RootMetadata {
+                created_at,
+                parent_hint,
+                genesis_fingerprint,
+            },
+            rem,
+        ))
+    }
+}
+
+/// An entity which persists the metadata of each known state root.
+///
+/// A state root is listed from its creation until it is pruned.
+pub trait RootMetadataStore: Store<Blake2bHash, RootMetadata> {
+    /// Returns at most `limit` roots with their metadata in the byte order of the root hashes,
+    /// starting with the first root after `start_after`, or with the first root if it is `None`.
+    fn list<T>(
+        &self,
+        txn: &T,
+        start_after: Option<Blake2bHash>,
+        limit: usize,
+    ) -> Result<Vec<(Blake2bHash, RootMetadata)>, Self::Error>
+    where
+        T: Iterable<Handle = Self::Handle>,
+        Self::Error: From<T::Error>,
+    {
+        let start_after = match start_after {
+            Some(start_after) => Some(start_after.to_bytes()?),
+            None => None,
+        };
+        let mut ret = Vec::new();
+        for (key_bytes, value_bytes) in
+            txn.read_range(self.handle(), start_after.as_deref(), limit)?
+        {
+            let root: Blake2bHash = bytesrepr::deserialize(key_bytes)?;
+            let metadata: RootMetadata = bytesrepr::deserialize(value_bytes)?;
+            ret.push((root, metadata));
+        }
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use engine_shared::newtypes::Blake2bHash;
+    use types::bytesrepr::{self, ToBytes};
+
+    use super::RootMetadata;
+
+    #[test]
+    fn should_serialize_root_metadata() {
+        for parent_hint in vec![None, Some(Blake2bHash::new(&[1]))] {
+            for genesis_fingerprint in vec![None, Some(Blake2bHash::new(&[2]))] {
+                let metadata = RootMetadata {
+                    created_at: 1_600_000_000_000,
+                    parent_hint,
+                    genesis_fingerprint,
+                };
+                bytesrepr::test_serialization_roundtrip(&metadata);
+            }
+        }
+    }
+
+    #[test]
+    fn should_deserialize_root_metadata_without_genesis_fingerprint() {
+        let created_at: u64 = 1_600_000_000_000;
+        let parent_hint = Some(Blake2bHash::new(&[1]));
+        let mut bytes = created_at.to_bytes().unwrap();
+        bytes.append(&mut parent_hint.to_bytes().unwrap());
+
+        let metadata: RootMetadata = bytesrepr::deserialize(bytes).unwrap();
+        assert_eq!(
+            metadata,
+            RootMetadata {
+                created_at,
+                parent_hint,
+                genesis_fingerprint: None,
+            }
+        );
+    }
+}
@@ -0,0 +1,54 @@
+use lmdb::{Database, DatabaseFlags};
+
+use engine_shared::newtypes::Blake2bHash;
+
+use crate::{
+    error,
+    staging_store::{self, StagingStore},
+    store::Store,
+    transaction_source::lmdb::LmdbEnvironment,
+};
+
+/// An LMDB-backed staging store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbStagingStore {
+    db: Database,
+}
+
+impl LmdbStagingStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbStagingStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbStagingStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", staging_store::NAME, name))
+            .unwrap_or_else(|| String::from(staging_store::NAME))
+    }
+}
+
+impl Store<Blake2bHash, ()> for LmdbStagingStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl StagingStore for LmdbStagingStore {}
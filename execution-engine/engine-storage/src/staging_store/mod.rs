@@ -0,0 +1,18 @@
+//! A store for recording the trie nodes written by the intermediate batches of a commit which
+//! hasn't been published yet.
+//!
+//! Only the LMDB-backed global state splits commits into batches, so there is no in-memory
+//! implementation.
+use engine_shared::newtypes::Blake2bHash;
+
+pub mod lmdb;
+
+use crate::store::Store;
+
+const NAME: &str = "STAGING_STORE";
+
+/// An entity which persists the hashes of the trie nodes newly written by a commit in progress.
+///
+/// The store is empty unless a commit is in progress, or one was interrupted, in which case the
+/// recorded nodes are unreachable from any published root and can be deleted.
+pub trait StagingStore: Store<Blake2bHash, ()> {}
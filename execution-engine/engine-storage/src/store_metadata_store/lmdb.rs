@@ -0,0 +1,381 @@
+use lmdb::{Database, DatabaseFlags, RwTransaction};
+use log::info;
+
+use crate::{
+    error,
+    store::Store,
+    store_metadata_store::{
+        self, StoreMetadata, StoreMetadataStore, StoreVersionError, LEGACY_STORE_SCHEMA_VERSION,
+        STORE_SCHEMA_VERSION,
+    },
+    transaction_source::{lmdb::LmdbEnvironment, Iterable, Transaction, TransactionSource},
+    trie_store::lmdb::LmdbTrieStore,
+};
+
+/// An LMDB-backed store metadata store.
+///
+/// Wraps [`lmdb::Database`].
+#[derive(Debug, Clone)]
+pub struct LmdbStoreMetadataStore {
+    db: Database,
+}
+
+impl LmdbStoreMetadataStore {
+    pub fn new(
+        env: &LmdbEnvironment,
+        maybe_name: Option<&str>,
+        flags: DatabaseFlags,
+    ) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().create_db(Some(&name), flags)?;
+        Ok(LmdbStoreMetadataStore { db })
+    }
+
+    pub fn open(env: &LmdbEnvironment, maybe_name: Option<&str>) -> Result<Self, error::Error> {
+        let name = Self::name(maybe_name);
+        let db = env.env().open_db(Some(&name))?;
+        Ok(LmdbStoreMetadataStore { db })
+    }
+
+    fn name(maybe_name: Option<&str>) -> String {
+        maybe_name
+            .map(|name| format!("{}-{}", store_metadata_store::NAME, name))
+            .unwrap_or_else(|| String::from(store_metadata_store::NAME))
+    }
+}
+
+impl Store<(), StoreMetadata> for LmdbStoreMetadataStore {
+    type Error = error::Error;
+
+    type Handle = Database;
+
+    fn handle(&self) -> Self::Handle {
+        self.db
+    }
+}
+
+impl StoreMetadataStore for LmdbStoreMetadataStore {}
+
+/// A migration of the stores from one schema version to the next.
+pub struct Migration {
+    /// The schema version migrated from, which the migration raises by one.
+    pub from_version: u32,
+    /// What the migration changes, for the logs.
+    pub description: &'static str,
+    /// Migrates the stores of `environment` within the given transaction.
+    pub migrate: fn(&LmdbEnvironment, &mut RwTransaction) -> Result<(), error::Error>,
+}
+
+/// The migrations of the stores to [`STORE_SCHEMA_VERSION`], in order.
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from_version: LEGACY_STORE_SCHEMA_VERSION,
+    description: "record the store metadata",
+    migrate: record_store_metadata,
+}];
+
+/// The stores didn't change, so there is nothing to migrate but the metadata, which is written
+/// once all the migrations are done.
+fn record_store_metadata(
+    _environment: &LmdbEnvironment,
+    _txn: &mut RwTransaction,
+) -> Result<(), error::Error> {
+    Ok(())
+}
+
+/// Checks the schema version of the stores of `environment`, initializing the metadata of new
+/// stores and running the [`MIGRATIONS`] of stores with an older schema.
+///
+/// Has to be called before anything else reads from or writes to the stores.
+pub fn check_store_version(
+    environment: &LmdbEnvironment,
+    store_metadata_store: &LmdbStoreMetadataStore,
+    trie_store: &LmdbTrieStore,
+    engine_version: &str,
+) -> Result<StoreMetadata, StoreVersionError> {
+    check_store_version_with_migrations(
+        environment,
+        store_metadata_store,
+        trie_store,
+        engine_version,
+        STORE_SCHEMA_VERSION,
+        MIGRATIONS,
+    )
+}
+
+/// Checks the schema version of the stores of `environment` against `binary_version`, running
+/// the given `migrations` of stores with an older schema.
+///
+/// Stores without metadata are new if they hold no trie node, and of the legacy schema otherwise.
+/// The migrations run in a single transaction, so that either all of them are applied, or the
+/// stores are left as they were.
+pub fn check_store_version_with_migrations(
+    environment: &LmdbEnvironment,
+    store_metadata_store: &LmdbStoreMetadataStore,
+    trie_store: &LmdbTrieStore,
+    engine_version: &str,
+    binary_version: u32,
+    migrations: &[Migration],
+) -> Result<StoreMetadata, StoreVersionError> {
+    let mut txn = environment.create_read_write_txn()?;
+    let metadata = match store_metadata_store.get(&txn, &())? {
+        Some(metadata) => metadata,
+        None if txn.read_range(trie_store.handle(), None, 1)?.is_empty() => {
+            let metadata = StoreMetadata::now(binary_version, engine_version);
+            store_metadata_store.put(&mut txn, &(), &metadata)?;
+            txn.commit()?;
+            return Ok(metadata);
+        }
+        None => StoreMetadata::now(LEGACY_STORE_SCHEMA_VERSION, engine_version),
+    };
+
+    if metadata.schema_version > binary_version {
+        return Err(StoreVersionError::IncompatibleStoreVersion {
+            store: metadata.schema_version,
+            binary: binary_version,
+        });
+    }
+    if metadata.schema_version == binary_version {
+        return Ok(metadata);
+    }
+
+    let mut schema_version = metadata.schema_version;
+    while schema_version < binary_version {
+        let migration = migrations
+            .iter()
+            .find(|migration| migration.from_version == schema_version)
+            .ok_or_else(|| StoreVersionError::NoMigration {
+                store: metadata.schema_version,
+                binary: binary_version,
+            })?;
+        info!(
+            "migrating the stores from schema version {}: {}",
+            schema_version, migration.description
+        );
+        (migration.migrate)(environment, &mut txn).map_err(|error| {
+            StoreVersionError::MigrationFailed {
+                from_version: schema_version,
+                error,
+            }
+        })?;
+        schema_version += 1;
+    }
+
+    let migrated_metadata = StoreMetadata {
+        schema_version,
+        engine_version: engine_version.to_string(),
+        ..metadata
+    };
+    store_metadata_store.put(&mut txn, &(), &migrated_metadata)?;
+    txn.commit()?;
+    Ok(migrated_metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tempfile::{tempdir, TempDir};
+
+    use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+    use types::Key;
+
+    use super::*;
+    use crate::{trie::Trie, TEST_MAP_SIZE};
+
+    const ENGINE_VERSION: &str = "1.2.3";
+
+    static TEST_MIGRATION_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    struct TestStores {
+        environment: LmdbEnvironment,
+        store_metadata_store: LmdbStoreMetadataStore,
+        trie_store: LmdbTrieStore,
+        _temp_dir: TempDir,
+    }
+
+    impl TestStores {
+        fn new() -> Self {
+            let temp_dir = tempdir().unwrap();
+            let environment =
+                LmdbEnvironment::new(&temp_dir.path().to_path_buf(), *TEST_MAP_SIZE).unwrap();
+            let store_metadata_store =
+                LmdbStoreMetadataStore::new(&environment, None, DatabaseFlags::empty()).unwrap();
+            let trie_store =
+                LmdbTrieStore::new(&environment, None, DatabaseFlags::empty()).unwrap();
+            TestStores {
+                environment,
+                store_metadata_store,
+                trie_store,
+                _temp_dir: temp_dir,
+            }
+        }
+
+        fn check(
+            &self,
+            binary_version: u32,
+            migrations: &[Migration],
+        ) -> Result<StoreMetadata, StoreVersionError> {
+            check_store_version_with_migrations(
+                &self.environment,
+                &self.store_metadata_store,
+                &self.trie_store,
+                ENGINE_VERSION,
+                binary_version,
+                migrations,
+            )
+        }
+
+        fn recorded_metadata(&self) -> Option<StoreMetadata> {
+            let txn = self.environment.create_read_txn().unwrap();
+            self.store_metadata_store.get(&txn, &()).unwrap()
+        }
+
+        fn record_schema_version(&self, schema_version: u32) {
+            let mut metadata = self.recorded_metadata().unwrap();
+            metadata.schema_version = schema_version;
+            let mut txn = self.environment.create_read_write_txn().unwrap();
+            self.store_metadata_store
+                .put(&mut txn, &(), &metadata)
+                .unwrap();
+            txn.commit().unwrap();
+        }
+
+        fn write_trie_node(&self) {
+            let trie: Trie<Key, StoredValue> = Trie::Node {
+                pointer_block: Default::default(),
+            };
+            let mut txn = self.environment.create_read_write_txn().unwrap();
+            self.trie_store
+                .put(&mut txn, &Blake2bHash::new(&[1]), &trie)
+                .unwrap();
+            txn.commit().unwrap();
+        }
+    }
+
+    fn count_test_migration_run(
+        _environment: &LmdbEnvironment,
+        _txn: &mut RwTransaction,
+    ) -> Result<(), error::Error> {
+        TEST_MIGRATION_RUNS.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn fail_test_migration(
+        _environment: &LmdbEnvironment,
+        _txn: &mut RwTransaction,
+    ) -> Result<(), error::Error> {
+        Err(error::Error::Poison)
+    }
+
+    #[test]
+    fn should_record_metadata_of_new_stores() {
+        let stores = TestStores::new();
+
+        let metadata = stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap();
+
+        assert_eq!(metadata.schema_version, STORE_SCHEMA_VERSION);
+        assert_eq!(metadata.engine_version, ENGINE_VERSION);
+        assert_eq!(stores.recorded_metadata(), Some(metadata.clone()));
+        // opening the stores again leaves the metadata as it was
+        assert_eq!(
+            stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap(),
+            metadata
+        );
+    }
+
+    #[test]
+    fn should_reject_stores_with_newer_schema() {
+        let stores = TestStores::new();
+        stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap();
+        stores.record_schema_version(STORE_SCHEMA_VERSION + 1);
+
+        match stores.check(STORE_SCHEMA_VERSION, MIGRATIONS) {
+            Err(StoreVersionError::IncompatibleStoreVersion { store, binary }) => {
+                assert_eq!(store, STORE_SCHEMA_VERSION + 1);
+                assert_eq!(binary, STORE_SCHEMA_VERSION);
+            }
+            other => panic!("expected incompatible store version, got {:?}", other),
+        }
+        assert_eq!(
+            stores.recorded_metadata().unwrap().schema_version,
+            STORE_SCHEMA_VERSION + 1
+        );
+    }
+
+    #[test]
+    fn should_migrate_legacy_stores() {
+        let stores = TestStores::new();
+        stores.write_trie_node();
+
+        let metadata = stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap();
+
+        assert_eq!(metadata.schema_version, STORE_SCHEMA_VERSION);
+        assert_eq!(stores.recorded_metadata(), Some(metadata));
+    }
+
+    #[test]
+    fn should_run_registered_migration() {
+        let stores = TestStores::new();
+        let metadata = stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap();
+        let migrations = [Migration {
+            from_version: STORE_SCHEMA_VERSION,
+            description: "count the runs of the test migration",
+            migrate: count_test_migration_run,
+        }];
+        let runs_before = TEST_MIGRATION_RUNS.load(Ordering::SeqCst);
+
+        let migrated_metadata = stores.check(STORE_SCHEMA_VERSION + 1, &migrations).unwrap();
+
+        assert_eq!(TEST_MIGRATION_RUNS.load(Ordering::SeqCst), runs_before + 1);
+        assert_eq!(migrated_metadata.schema_version, STORE_SCHEMA_VERSION + 1);
+        assert_eq!(migrated_metadata.created_at, metadata.created_at);
+        assert_eq!(stores.recorded_metadata(), Some(migrated_metadata));
+
+        // the stores are now at the binary's version, so the migration doesn't run again
+        stores.check(STORE_SCHEMA_VERSION + 1, &migrations).unwrap();
+        assert_eq!(TEST_MIGRATION_RUNS.load(Ordering::SeqCst), runs_before + 1);
+    }
+
+    #[test]
+    fn should_leave_stores_unchanged_if_migration_fails() {
+        let stores = TestStores::new();
+        stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap();
+        let migrations = [
+            Migration {
+                from_version: STORE_SCHEMA_VERSION,
+                description: "count the runs of the test migration",
+                migrate: count_test_migration_run,
+            },
+            Migration {
+                from_version: STORE_SCHEMA_VERSION + 1,
+                description: "fail",
+                migrate: fail_test_migration,
+            },
+        ];
+
+        match stores.check(STORE_SCHEMA_VERSION + 2, &migrations) {
+            Err(StoreVersionError::MigrationFailed { from_version, .. }) => {
+                assert_eq!(from_version, STORE_SCHEMA_VERSION + 1)
+            }
+            other => panic!("expected failed migration, got {:?}", other),
+        }
+        assert_eq!(
+            stores.recorded_metadata().unwrap().schema_version,
+            STORE_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn should_reject_stores_without_migration() {
+        let stores = TestStores::new();
+        stores.check(STORE_SCHEMA_VERSION, MIGRATIONS).unwrap();
+
+        match stores.check(STORE_SCHEMA_VERSION + 1, MIGRATIONS) {
+            Err(StoreVersionError::NoMigration { store, binary }) => {
+                assert_eq!(store, STORE_SCHEMA_VERSION);
+                assert_eq!(binary, STORE_SCHEMA_VERSION + 1);
+            }
+            other => panic!("expected no migration, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//! A store for the single record describing the data directory as a whole, i.e. the schema of the
+//! stores it holds and the engine which initialized it.
+//!
+//! The record is checked when the data directory is opened, so that stores written by an engine
+//! with a different encoding are either migrated or rejected before anything is read from them.
+//! Only the LMDB-backed stores persist across runs, so there is no in-memory implementation.
+use std::{
+    fmt::{self, Display, Formatter},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+pub mod lmdb;
+
+use crate::{error, store::Store};
+
+const NAME: &str = "STORE_METADATA_STORE";
+
+/// The schema version of the stores written by this version of the engine.
+pub const STORE_SCHEMA_VERSION: u32 = 2;
+
+/// The schema version of stores written before the store metadata was recorded.
+pub const LEGACY_STORE_SCHEMA_VERSION: u32 = 1;
+
+/// Metadata about a data directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoreMetadata {
+    /// The version of the schema of the stores.
+    pub schema_version: u32,
+    /// The version of the engine which last initialized or migrated the stores.
+    pub engine_version: String,
+    /// When the record was first written, in milliseconds since the Unix epoch by the engine's
+    /// clock.
+    pub created_at: u64,
+}
+
+impl StoreMetadata {
+    /// Returns the metadata of stores of `schema_version` initialized now by `engine_version`.
+    pub fn now(schema_version: u32, engine_version: &str) -> Self {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+        StoreMetadata {
+            schema_version,
+            engine_version: engine_version.to_string(),
+            created_at,
+        }
+    }
+}
+
+impl ToBytes for StoreMetadata {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        result.append(&mut self.schema_version.to_bytes()?);
+        result.append(&mut self.engine_version.to_bytes()?);
+        result.append(&mut self.created_at.to_bytes()?);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.schema_version.serialized_length()
+            + self.engine_version.serialized_length()
+            + self.created_at.serialized_length()
+    }
+}
+
+impl FromBytes for StoreMetadata {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (schema_version, rem) = u32::from_bytes(bytes)?;
+        let (engine_version, rem) = String::from_bytes(rem)?;
+        let (created_at, rem) = u64::from_bytes(rem)?;
+        Ok((
+            StoreMetadata {
+                schema_version,
+                engine_version,
+                created_at,
+            },
+            rem,
+        ))
+    }
+}
+
+/// An entity which persists the metadata of the data directory under the unit key.
+pub trait StoreMetadataStore: Store<(), StoreMetadata> {}
+
+/// An error preventing the stores of a data directory from being opened.
+#[derive(Debug)]
+pub enum StoreVersionError {
+    /// The stores were written with a schema newer than this engine supports.
+    IncompatibleStoreVersion { store: u32, binary: u32 },
+    /// The stores were written with an older schema, but no migration from it is registered.
+    NoMigration { store: u32, binary: u32 },
+    /// A migration failed, leaving the stores at the schema they had before the migrations.
+    MigrationFailed {
+        from_version: u32,
+        error: error::Error,
+    },
+    /// The metadata couldn't be read or written.
+    Storage(error::Error),
+}
+
+impl Display for StoreVersionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StoreVersionError::IncompatibleStoreVersion { store, binary } => write!(
+                f,
+                "the stores have schema version {}, but this engine only supports schema versions \
+                 up to {}; run an engine which supports schema version {}, or start from an empty \
+                 data directory",
+                store, binary, store
+            ),
+            StoreVersionError::NoMigration { store, binary } => write!(
+                f,
+                "the stores have schema version {}, which this engine can't migrate to schema \
+                 version {}; migrate them with an older engine first, or start from an empty \
+                 data directory",
+                store, binary
+            ),
+            StoreVersionError::MigrationFailed {
+                from_version,
+                error,
+            } => write!(
+                f,
+                "failed to migrate the stores from schema version {}, which were left unchanged: \
+                 {}",
+                from_version, error
+            ),
+            StoreVersionError::Storage(error) => {
+                write!(f, "failed to access the store metadata: {}", error)
+            }
+        }
+    }
+}
+
+impl From<error::Error> for StoreVersionError {
+    fn from(error: error::Error) -> Self {
+        StoreVersionError::Storage(error)
+    }
+}
+
+impl From<::lmdb::Error> for StoreVersionError {
+    fn from(error: ::lmdb::Error) -> Self {
+        StoreVersionError::Storage(error.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use types::bytesrepr;
+
+    use super::StoreMetadata;
+
+    #[test]
+    fn should_serialize_store_metadata() {
+        let metadata = StoreMetadata {
+            schema_version: 2,
+            engine_version: String::from("0.18.2"),
+            created_at: 1_600_000_000_000,
+        };
+        bytesrepr::test_serialization_roundtrip(&metadata);
+    }
+}
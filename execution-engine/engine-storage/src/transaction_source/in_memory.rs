@@ -5,7 +5,7 @@ use std::{
 
 use crate::{
     error::in_memory::Error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    transaction_source::{Iterable, Readable, Transaction, TransactionSource, Writable},
 };
 
 /// A marker for use in a mutex which represents the capability to perform a
@@ -16,6 +16,29 @@ type WriteLock<'a> = MutexGuard<'a, WriteCapability>;
 
 type BytesMap = HashMap<Vec<u8>, Vec<u8>>;
 
+/// Returns at most `limit` of the pairs of `sub_view` in the byte order of their keys, starting
+/// after `start_after`.
+fn read_range(
+    sub_view: Option<&BytesMap>,
+    start_after: Option<&[u8]>,
+    limit: usize,
+) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let sub_view = match sub_view {
+        Some(sub_view) => sub_view,
+        None => return Vec::new(),
+    };
+    let mut pairs: Vec<(&Vec<u8>, &Vec<u8>)> = sub_view
+        .iter()
+        .filter(|(key, _)| start_after.map_or(true, |start_after| key.as_slice() > start_after))
+        .collect();
+    pairs.sort_unstable_by(|(key_1, _), (key_2, _)| key_1.cmp(key_2));
+    pairs
+        .into_iter()
+        .take(limit)
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
 type PoisonError<'a> = sync::PoisonError<MutexGuard<'a, HashMap<Option<String>, BytesMap>>>;
 
 /// A read transaction for the in-memory trie store.
@@ -54,6 +77,17 @@ impl Readable for InMemoryReadTransaction {
     }
 }
 
+impl Iterable for InMemoryReadTransaction {
+    fn read_range(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        Ok(read_range(self.view.get(&handle), start_after, limit))
+    }
+}
+
 /// A read-write transaction for the in-memory trie store.
 pub struct InMemoryReadWriteTransaction<'a> {
     view: HashMap<Option<String>, BytesMap>,
@@ -99,12 +133,32 @@ impl<'a> Readable for InMemoryReadWriteTransaction<'a> {
     }
 }
 
+impl<'a> Iterable for InMemoryReadWriteTransaction<'a> {
+    fn read_range(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        Ok(read_range(self.view.get(&handle), start_after, limit))
+    }
+}
+
 impl<'a> Writable for InMemoryReadWriteTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
         let sub_view = self.view.entry(handle).or_default();
         sub_view.insert(key.to_vec(), value.to_vec());
         Ok(())
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        // the whole view replaces the stored data on commit, so removing the pair from the view
+        // removes it from the store
+        if let Some(sub_view) = self.view.get_mut(&handle) {
+            sub_view.remove(key);
+        }
+        Ok(())
+    }
 }
 
 /// An environment for the in-memory trie store.
@@ -136,6 +190,12 @@ impl InMemoryEnvironment {
         let ret = data.get(&name).cloned();
         Ok(ret)
     }
+
+    /// Returns a copy of all the data of the environment, by database name.
+    pub fn dump(&self) -> Result<HashMap<Option<String>, BytesMap>, PoisonError> {
+        let data = self.data.lock()?;
+        Ok(data.clone())
+    }
 }
 
 impl<'a> TransactionSource<'a> for InMemoryEnvironment {
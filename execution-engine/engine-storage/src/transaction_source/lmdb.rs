@@ -1,13 +1,38 @@
 use std::path::PathBuf;
 
-use lmdb::{self, Database, Environment, RoTransaction, RwTransaction, WriteFlags};
+use lmdb::{
+    self, Cursor, Database, Environment, EnvironmentFlags, RoTransaction, RwTransaction, WriteFlags,
+};
 
 use crate::{
     error,
-    transaction_source::{Readable, Transaction, TransactionSource, Writable},
+    global_state::durability::Durability,
+    transaction_source::{Iterable, Readable, Transaction, TransactionSource, Writable},
     MAX_DBS,
 };
 
+/// Returns at most `limit` pairs of the database in the byte order of their keys, starting after
+/// `start_after`.
+fn read_range<T: lmdb::Transaction>(
+    txn: &T,
+    handle: Database,
+    start_after: Option<&[u8]>,
+    limit: usize,
+) -> Result<Vec<(Vec<u8>, Vec<u8>)>, lmdb::Error> {
+    let mut cursor = txn.open_ro_cursor(handle)?;
+    let iter = match start_after {
+        Some(start_after) => cursor.iter_from(start_after),
+        None => cursor.iter_start(),
+    };
+    let ret = iter
+        // `iter_from` starts at the first key greater than or equal to `start_after`
+        .filter(|(key, _)| start_after.map_or(true, |start_after| *key != start_after))
+        .take(limit)
+        .map(|(key, value)| (key.to_vec(), value.to_vec()))
+        .collect();
+    Ok(ret)
+}
+
 impl<'a> Transaction for RoTransaction<'a> {
     type Error = lmdb::Error;
 
@@ -28,6 +53,17 @@ impl<'a> Readable for RoTransaction<'a> {
     }
 }
 
+impl<'a> Iterable for RoTransaction<'a> {
+    fn read_range(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        read_range(self, handle, start_after, limit)
+    }
+}
+
 impl<'a> Transaction for RwTransaction<'a> {
     type Error = lmdb::Error;
 
@@ -48,11 +84,29 @@ impl<'a> Readable for RwTransaction<'a> {
     }
 }
 
+impl<'a> Iterable for RwTransaction<'a> {
+    fn read_range(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        read_range(self, handle, start_after, limit)
+    }
+}
+
 impl<'a> Writable for RwTransaction<'a> {
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
         self.put(handle, &key, &value, WriteFlags::empty())
             .map_err(Into::into)
     }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        match self.del(handle, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => Ok(()),
+            Err(error) => Err(error),
+        }
+    }
 }
 
 /// The environment for an LMDB-backed trie store.
@@ -62,16 +116,52 @@ impl<'a> Writable for RwTransaction<'a> {
 pub struct LmdbEnvironment {
     path: PathBuf,
     env: Environment,
+    syncs_commits: bool,
 }
 
 impl LmdbEnvironment {
     pub fn new(path: &PathBuf, map_size: usize) -> Result<Self, error::Error> {
+        Self::new_with_durability(path, map_size, Durability::Strict)
+    }
+
+    /// Opens an environment for commits made with `durability`.
+    ///
+    /// Unless `durability` is [`Durability::Strict`], the environment doesn't sync its
+    /// transactions to disk when they are committed, and is only synced by [`sync`](Self::sync).
+    /// A crash may then undo the transactions committed since the last sync, while the integrity
+    /// of the database is kept as long as the filesystem preserves the order of writes.
+    pub fn new_with_durability(
+        path: &PathBuf,
+        map_size: usize,
+        durability: Durability,
+    ) -> Result<Self, error::Error> {
+        let syncs_commits = durability == Durability::Strict;
+        let flags = if syncs_commits {
+            EnvironmentFlags::empty()
+        } else {
+            EnvironmentFlags::NO_SYNC
+        };
         let env = Environment::new()
+            .set_flags(flags)
             .set_max_dbs(MAX_DBS)
             .set_map_size(map_size)
             .open(path)?;
         let path = path.to_owned();
-        Ok(LmdbEnvironment { path, env })
+        Ok(LmdbEnvironment {
+            path,
+            env,
+            syncs_commits,
+        })
+    }
+
+    /// Returns `true` if every transaction is synced to disk when it is committed.
+    pub fn syncs_commits(&self) -> bool {
+        self.syncs_commits
+    }
+
+    /// Syncs the transactions committed so far to disk.
+    pub fn sync(&self) -> Result<(), lmdb::Error> {
+        self.env.sync(true)
     }
 
     pub fn path(&self) -> &PathBuf {
@@ -100,3 +190,220 @@ impl<'a> TransactionSource<'a> for LmdbEnvironment {
         self.env.begin_rw_txn()
     }
 }
+
+/// The number of staged keys deleted per transaction by [`reclaim_staged`].
+const RECLAIM_BATCH_LEN: usize = 10_000;
+
+/// Deletes the keys recorded in `staging_db` from `staged_db`, along with the records themselves,
+/// returning the number of keys deleted.
+///
+/// Keys are deleted in transactions of at most [`RECLAIM_BATCH_LEN`] keys each, and an interrupted
+/// reclaim can be resumed by running it again.
+pub fn reclaim_staged(
+    environment: &LmdbEnvironment,
+    staged_db: Database,
+    staging_db: Database,
+) -> Result<usize, lmdb::Error> {
+    let mut reclaimed = 0;
+    loop {
+        let mut txn = environment.env.begin_rw_txn()?;
+        let staged = read_range(&txn, staging_db, None, RECLAIM_BATCH_LEN)?;
+        if staged.is_empty() {
+            return Ok(reclaimed);
+        }
+        for (key, _) in &staged {
+            Writable::delete(&mut txn, staged_db, key)?;
+            Writable::delete(&mut txn, staging_db, key)?;
+        }
+        lmdb::Transaction::commit(txn)?;
+        reclaimed += staged.len();
+    }
+}
+
+/// A source of [`LmdbBatchedTransaction`]s over an [`LmdbEnvironment`].
+pub struct LmdbBatchingEnvironment<'e> {
+    environment: &'e LmdbEnvironment,
+    staged_db: Database,
+    staging_db: Database,
+    batch_size: usize,
+    crash_after_batches: Option<usize>,
+}
+
+impl<'e> LmdbBatchingEnvironment<'e> {
+    /// Creates a source of transactions which commit their writes in batches of about `batch_size`
+    /// bytes, recording the keys newly written to `staged_db` by intermediate batches in
+    /// `staging_db`.
+    pub fn new(
+        environment: &'e LmdbEnvironment,
+        staged_db: Database,
+        staging_db: Database,
+        batch_size: usize,
+    ) -> Self {
+        LmdbBatchingEnvironment {
+            environment,
+            staged_db,
+            staging_db,
+            batch_size,
+            crash_after_batches: None,
+        }
+    }
+
+    /// Makes transactions fail right after committing their `crash_after_batches`th intermediate
+    /// batch, leaving the staged keys behind as a crash of the process would.
+    pub(crate) fn with_crash_after_batches(mut self, crash_after_batches: Option<usize>) -> Self {
+        self.crash_after_batches = crash_after_batches;
+        self
+    }
+}
+
+impl<'a, 'e: 'a> TransactionSource<'a> for LmdbBatchingEnvironment<'e> {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    type ReadTransaction = RoTransaction<'a>;
+
+    type ReadWriteTransaction = LmdbBatchedTransaction<'a>;
+
+    fn create_read_txn(&'a self) -> Result<RoTransaction<'a>, Self::Error> {
+        self.environment.create_read_txn()
+    }
+
+    fn create_read_write_txn(&'a self) -> Result<LmdbBatchedTransaction<'a>, Self::Error> {
+        let txn = self.environment.create_read_write_txn()?;
+        Ok(LmdbBatchedTransaction {
+            environment: self.environment,
+            txn: Some(txn),
+            staged_db: self.staged_db,
+            staging_db: self.staging_db,
+            batch_size: self.batch_size,
+            batch_bytes: 0,
+            new_keys: Vec::new(),
+            batches: 0,
+            crash_after_batches: self.crash_after_batches,
+            abandoned: false,
+        })
+    }
+}
+
+/// A read-write transaction which commits its writes in batches of about `batch_size` bytes,
+/// rather than in a single LMDB transaction.
+///
+/// Before an intermediate batch is committed, the keys it newly writes to the staged database are
+/// recorded in the staging database.  Committing the transaction clears the staging database in
+/// its final batch, whereas dropping it deletes the staged keys again, so that the writes of a
+/// transaction which isn't committed are eventually undone, if need be by [`reclaim_staged`] after
+/// a crash.
+///
+/// Intermediate batches are visible to other transactions, so writers of the staged database must
+/// not interleave with a batched transaction.
+pub struct LmdbBatchedTransaction<'a> {
+    environment: &'a LmdbEnvironment,
+    /// The current batch, only `None` while switching to the next one.
+    txn: Option<RwTransaction<'a>>,
+    staged_db: Database,
+    staging_db: Database,
+    batch_size: usize,
+    batch_bytes: usize,
+    /// The keys newly written to the staged database by the current batch.
+    new_keys: Vec<Vec<u8>>,
+    /// The number of intermediate batches committed so far.
+    batches: usize,
+    crash_after_batches: Option<usize>,
+    /// Whether the staged keys are left behind when dropped.
+    abandoned: bool,
+}
+
+impl<'a> LmdbBatchedTransaction<'a> {
+    fn txn(&self) -> &RwTransaction<'a> {
+        self.txn.as_ref().expect("should have a batch")
+    }
+
+    /// Commits the current batch, recording the keys it newly wrote, and starts the next one.
+    fn commit_batch(&mut self) -> Result<(), lmdb::Error> {
+        let mut txn = self.txn.take().expect("should have a batch");
+        for key in self.new_keys.drain(..) {
+            txn.put(self.staging_db, &key, b"", WriteFlags::empty())?;
+        }
+        lmdb::Transaction::commit(txn)?;
+        self.batches += 1;
+        self.batch_bytes = 0;
+
+        if self.crash_after_batches == Some(self.batches) {
+            self.abandoned = true;
+            return Err(lmdb::Error::Panic);
+        }
+        self.txn = Some(self.environment.env.begin_rw_txn()?);
+        Ok(())
+    }
+}
+
+impl<'a> Transaction for LmdbBatchedTransaction<'a> {
+    type Error = lmdb::Error;
+
+    type Handle = Database;
+
+    fn commit(mut self) -> Result<(), Self::Error> {
+        let mut txn = self.txn.take().expect("should have a batch");
+        if self.batches > 0 {
+            txn.clear_db(self.staging_db)?;
+        }
+        lmdb::Transaction::commit(txn)?;
+        // the staged keys are now published
+        self.batches = 0;
+        Ok(())
+    }
+}
+
+impl<'a> Readable for LmdbBatchedTransaction<'a> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        Readable::read(self.txn(), handle, key)
+    }
+}
+
+impl<'a> Iterable for LmdbBatchedTransaction<'a> {
+    fn read_range(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error> {
+        read_range(self.txn(), handle, start_after, limit)
+    }
+}
+
+impl<'a> Writable for LmdbBatchedTransaction<'a> {
+    fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error> {
+        let txn = self.txn.as_mut().expect("should have a batch");
+        if handle == self.staged_db {
+            match lmdb::Transaction::get(txn, handle, &key) {
+                Ok(_) => (),
+                Err(lmdb::Error::NotFound) => self.new_keys.push(key.to_vec()),
+                Err(error) => return Err(error),
+            }
+        }
+        txn.put(handle, &key, &value, WriteFlags::empty())?;
+
+        self.batch_bytes += key.len() + value.len();
+        if self.batch_bytes >= self.batch_size {
+            self.commit_batch()?;
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error> {
+        let txn = self.txn.as_mut().expect("should have a batch");
+        Writable::delete(txn, handle, key)
+    }
+}
+
+impl<'a> Drop for LmdbBatchedTransaction<'a> {
+    fn drop(&mut self) {
+        // aborts the current batch before starting the transactions of the reclaim
+        self.txn.take();
+        if self.batches > 0 && !self.abandoned {
+            // should this fail, the staged keys are reclaimed on the next start instead
+            let _ = reclaim_staged(self.environment, self.staged_db, self.staging_db);
+        }
+    }
+}
@@ -31,6 +31,23 @@ pub trait Readable: Transaction {
 pub trait Writable: Transaction {
     /// Inserts a key-value pair into a given [`Transaction::Handle`].
     fn write(&mut self, handle: Self::Handle, key: &[u8], value: &[u8]) -> Result<(), Self::Error>;
+
+    /// Removes the key-value pair with the given key from a given [`Transaction::Handle`], if
+    /// present.
+    fn delete(&mut self, handle: Self::Handle, key: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// A transaction with the capability to list the contents of a given
+/// [`Handle`](Transaction::Handle) in the byte order of the keys.
+pub trait Iterable: Readable {
+    /// Returns at most `limit` key-value pairs from a given [`Transaction::Handle`], starting with
+    /// the first key greater than `start_after`, or with the first key if it is `None`.
+    fn read_range(
+        &self,
+        handle: Self::Handle,
+        start_after: Option<&[u8]>,
+        limit: usize,
+    ) -> Result<Vec<(Vec<u8>, Vec<u8>)>, Self::Error>;
 }
 
 /// A source of transactions e.g. values that implement [`Readable`]
@@ -44,10 +61,10 @@ pub trait TransactionSource<'a> {
     type Handle;
 
     /// Represents the type of read transactions.
-    type ReadTransaction: Readable<Error = Self::Error, Handle = Self::Handle>;
+    type ReadTransaction: Iterable<Error = Self::Error, Handle = Self::Handle>;
 
     /// Represents the type of read-write transactions.
-    type ReadWriteTransaction: Readable<Error = Self::Error, Handle = Self::Handle>
+    type ReadWriteTransaction: Iterable<Error = Self::Error, Handle = Self::Handle>
         + Writable<Error = Self::Error, Handle = Self::Handle>;
 
     /// Creates a read transaction.
@@ -0,0 +1,96 @@
+use engine_shared::newtypes::Blake2bHash;
+use types::bytesrepr::{self, FromBytes, ToBytes};
+
+use crate::trie::{Pointer, Trie};
+
+/// A proof that a key/value pair is held by the trie with a given root hash.
+///
+/// It consists of the trie nodes on the path from the root down to the leaf holding the pair, root
+/// first.  The leaf itself isn't included as it is rebuilt from the pair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieMerkleProof<K, V> {
+    key: K,
+    value: V,
+    path: Vec<Trie<K, V>>,
+}
+
+impl<K, V> TrieMerkleProof<K, V> {
+    pub fn new(key: K, value: V, path: Vec<Trie<K, V>>) -> Self {
+        TrieMerkleProof { key, value, path }
+    }
+
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+
+    pub fn path(&self) -> &[Trie<K, V>] {
+        &self.path
+    }
+
+    pub fn into_pair(self) -> (K, V) {
+        (self.key, self.value)
+    }
+}
+
+impl<K, V> TrieMerkleProof<K, V>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    /// Returns the root hash of the trie this proof was taken from, or `None` if one of the nodes
+    /// of the path doesn't point at the next one, i.e. the proof has been tampered with.
+    pub fn compute_state_hash(&self) -> Result<Option<Blake2bHash>, bytesrepr::Error> {
+        let leaf: Trie<K, V> = Trie::leaf(self.key.clone(), self.value.clone());
+        let mut hash = Blake2bHash::new(&leaf.to_bytes()?);
+        for node in self.path.iter().rev() {
+            let points_at_hash = |pointer: &Pointer| *pointer.hash() == hash;
+            let is_parent = match node {
+                Trie::Leaf { .. } => false,
+                Trie::Node { pointer_block } => pointer_block[..]
+                    .iter()
+                    .filter_map(Option::as_ref)
+                    .any(points_at_hash),
+                Trie::Extension { pointer, .. } => points_at_hash(pointer),
+            };
+            if !is_parent {
+                return Ok(None);
+            }
+            hash = Blake2bHash::new(&node.to_bytes()?);
+        }
+        Ok(Some(hash))
+    }
+
+    /// Returns `true` if the pair of this proof is held by the trie with root `state_hash`.
+    pub fn verify(&self, state_hash: &Blake2bHash) -> Result<bool, bytesrepr::Error> {
+        Ok(self.compute_state_hash()? == Some(*state_hash))
+    }
+}
+
+impl<K: ToBytes, V: ToBytes> ToBytes for TrieMerkleProof<K, V> {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut ret = bytesrepr::allocate_buffer(self)?;
+        ret.append(&mut self.key.to_bytes()?);
+        ret.append(&mut self.value.to_bytes()?);
+        ret.append(&mut self.path.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.value.serialized_length()
+            + self.path.serialized_length()
+    }
+}
+
+impl<K: FromBytes, V: FromBytes> FromBytes for TrieMerkleProof<K, V> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem) = K::from_bytes(bytes)?;
+        let (value, rem) = V::from_bytes(rem)?;
+        let (path, rem) = Vec::<Trie<K, V>>::from_bytes(rem)?;
+        Ok((TrieMerkleProof { key, value, path }, rem))
+    }
+}
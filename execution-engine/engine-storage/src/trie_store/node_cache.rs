@@ -0,0 +1,129 @@
+//! A cache of serialized trie nodes which can be shared by the readers of several state roots.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::transaction_source::{Readable, Transaction};
+
+/// A cache of the serialized trie nodes read from a trie store, keyed by their serialized hashes.
+///
+/// Trie nodes are immutable and stored at their hashes, so a node read while walking from one state
+/// root is valid for every other root sharing it.  Reading the same key at several roots through a
+/// shared cache reads the upper nodes common to those roots only once.
+///
+/// The cache only grows, so it should be dropped once the reads it serves are done.
+#[derive(Clone, Default)]
+pub struct TrieNodeCache(Arc<Mutex<HashMap<Vec<u8>, Vec<u8>>>>);
+
+impl TrieNodeCache {
+    pub fn new() -> Self {
+        TrieNodeCache::default()
+    }
+
+    /// Returns the number of cached nodes.
+    pub fn len(&self) -> usize {
+        self.0.lock().expect("should lock trie node cache").len()
+    }
+
+    /// Returns `true` if no nodes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.0
+            .lock()
+            .expect("should lock trie node cache")
+            .get(key)
+            .cloned()
+    }
+
+    fn insert(&self, key: &[u8], value: Vec<u8>) {
+        self.0
+            .lock()
+            .expect("should lock trie node cache")
+            .insert(key.to_vec(), value);
+    }
+}
+
+/// Tallies the values read by a [`CachingReadTransaction`].
+pub trait NodeReadSink {
+    /// Records a value of `bytes` bytes read from the wrapped transaction.
+    fn record_node_read(&self, bytes: usize);
+
+    /// Records a value found in the cache.
+    fn record_cache_hit(&self);
+}
+
+/// Discards the tallies, so that transactions which aren't counted compile to the same code as
+/// before counting existed.
+impl NodeReadSink for () {
+    #[inline(always)]
+    fn record_node_read(&self, _bytes: usize) {}
+
+    #[inline(always)]
+    fn record_cache_hit(&self) {}
+}
+
+/// A read transaction which looks values up in a [`TrieNodeCache`] before reading them from the
+/// wrapped transaction, and caches the values it reads.
+///
+/// Only values which are found are cached.  The handle is not part of the cache key, so the
+/// transaction must only be used to read from a single trie store.
+pub struct CachingReadTransaction<'a, T, N: ?Sized = ()> {
+    txn: T,
+    cache: Option<&'a TrieNodeCache>,
+    node_reads: &'a N,
+}
+
+impl<'a, T> CachingReadTransaction<'a, T> {
+    /// Wraps `txn`.  If `cache` is `None`, reads go straight to `txn`.
+    pub fn new(txn: T, cache: Option<&'a TrieNodeCache>) -> Self {
+        CachingReadTransaction {
+            txn,
+            cache,
+            node_reads: &(),
+        }
+    }
+
+    /// Makes the transaction tally the values it reads, and the ones it finds in the cache, in
+    /// `node_reads`.
+    pub fn with_node_reads<N: NodeReadSink + ?Sized>(
+        self,
+        node_reads: &'a N,
+    ) -> CachingReadTransaction<'a, T, N> {
+        CachingReadTransaction {
+            txn: self.txn,
+            cache: self.cache,
+            node_reads,
+        }
+    }
+}
+
+impl<'a, T: Transaction, N: ?Sized> Transaction for CachingReadTransaction<'a, T, N> {
+    type Error = T::Error;
+
+    type Handle = T::Handle;
+
+    fn commit(self) -> Result<(), Self::Error> {
+        self.txn.commit()
+    }
+}
+
+impl<'a, T: Readable, N: NodeReadSink + ?Sized> Readable for CachingReadTransaction<'a, T, N> {
+    fn read(&self, handle: Self::Handle, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
+        if let Some(value) = self.cache.and_then(|cache| cache.get(key)) {
+            self.node_reads.record_cache_hit();
+            return Ok(Some(value));
+        }
+        let maybe_value = self.txn.read(handle, key)?;
+        if let Some(value) = &maybe_value {
+            self.node_reads.record_node_read(value.len());
+            if let Some(cache) = self.cache {
+                cache.insert(key, value.clone());
+            }
+        }
+        Ok(maybe_value)
+    }
+}
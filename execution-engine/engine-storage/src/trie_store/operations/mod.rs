@@ -1,7 +1,12 @@
 #[cfg(test)]
 mod tests;
 
-use std::{cmp, collections::VecDeque, mem, time::Instant};
+use std::{
+    cmp,
+    collections::{BTreeMap, VecDeque},
+    mem,
+    time::Instant,
+};
 
 use engine_shared::{
     logging::{log_duration, log_metric},
@@ -11,7 +16,7 @@ use types::bytesrepr::{self, FromBytes, ToBytes};
 
 use crate::{
     transaction_source::{Readable, Writable},
-    trie::{self, Parents, Pointer, Trie, RADIX},
+    trie::{self, Parents, Pointer, Trie, TrieMerkleProof, RADIX},
     trie_store::TrieStore,
     GAUGE_METRIC_KEY,
 };
@@ -22,11 +27,13 @@ const TRIE_STORE_SCAN_DURATION: &str = "trie_store_scan_duration";
 const TRIE_STORE_SCAN_GETS: &str = "trie_store_scan_gets";
 const TRIE_STORE_WRITE_DURATION: &str = "trie_store_write_duration";
 const TRIE_STORE_WRITE_PUTS: &str = "trie_store_write_puts";
+const TRIE_STORE_DELETE_DURATION: &str = "trie_store_delete_duration";
 const READ: &str = "read";
 const GET: &str = "get";
 const SCAN: &str = "scan";
 const WRITE: &str = "write";
 const PUT: &str = "put";
+const DELETE: &str = "delete";
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum ReadResult<V> {
@@ -122,9 +129,10 @@ where
                                 start.elapsed(),
                             );
                             panic!(
-                                "No trie value at key: {:?} (reading from key: {:?})",
+                                "No trie value at key: {:?} (reading from key: {:?}; correlation id: {})",
                                 pointer.hash(),
-                                key
+                                key,
+                                correlation_id
                             );
                         }
                     },
@@ -172,9 +180,10 @@ where
                                 start.elapsed(),
                             );
                             panic!(
-                                "No trie value at key: {:?} (reading from key: {:?})",
+                                "No trie value at key: {:?} (reading from key: {:?}; correlation id: {})",
                                 pointer.hash(),
-                                key
+                                key,
+                                correlation_id
                             );
                         }
                     }
@@ -199,6 +208,72 @@ where
     }
 }
 
+/// Returns the value of `key` at `root` like [`read`], together with the trie nodes on the path
+/// from `root` to it, which prove the value is held by the trie.
+pub fn read_with_proof<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<ReadResult<TrieMerkleProof<K, V>>, E>
+where
+    K: ToBytes + FromBytes + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let path: Vec<u8> = key.to_bytes()?;
+
+    let mut depth: usize = 0;
+    let mut current: Trie<K, V> = match store.get(txn, root)? {
+        Some(root) => root,
+        None => return Ok(ReadResult::RootNotFound),
+    };
+    let mut proof_path: Vec<Trie<K, V>> = Vec::new();
+
+    loop {
+        let next_hash: Blake2bHash = match &current {
+            Trie::Leaf { key: leaf_key, .. } if leaf_key == key => break,
+            Trie::Leaf { .. } => return Ok(ReadResult::NotFound),
+            Trie::Node { pointer_block } => {
+                let index: usize = path[depth].into();
+                match pointer_block[index] {
+                    Some(pointer) => {
+                        depth += 1;
+                        *pointer.hash()
+                    }
+                    None => return Ok(ReadResult::NotFound),
+                }
+            }
+            Trie::Extension { affix, pointer } => {
+                if path[depth..depth + affix.len()] != affix[..] {
+                    return Ok(ReadResult::NotFound);
+                }
+                depth += affix.len();
+                *pointer.hash()
+            }
+        };
+        let next = match store.get(txn, &next_hash)? {
+            Some(next) => next,
+            None => panic!(
+                "No trie value at key: {:?} (reading from key: {:?}; correlation id: {})",
+                next_hash, key, correlation_id
+            ),
+        };
+        proof_path.push(mem::replace(&mut current, next));
+    }
+
+    match current {
+        Trie::Leaf { key, value } => Ok(ReadResult::Found(TrieMerkleProof::new(
+            key, value, proof_path,
+        ))),
+        _ => unreachable!("the loop only breaks at a leaf"),
+    }
+}
+
 struct TrieScan<K, V> {
     tip: Trie<K, V>,
     parents: Parents<K, V>,
@@ -308,9 +383,10 @@ where
                             start.elapsed(),
                         );
                         panic!(
-                            "No trie value at key: {:?} (reading from path: {:?})",
+                            "No trie value at key: {:?} (reading from path: {:?}; correlation id: {})",
                             pointer.hash(),
-                            path
+                            path,
+                            correlation_id
                         );
                     }
                 }
@@ -360,9 +436,10 @@ where
                             start.elapsed(),
                         );
                         panic!(
-                            "No trie value at key: {:?} (reading from path: {:?})",
+                            "No trie value at key: {:?} (reading from path: {:?}; correlation id: {})",
                             pointer.hash(),
-                            path
+                            path,
+                            correlation_id
                         );
                     }
                 }
@@ -731,6 +808,231 @@ where
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeleteResult {
+    Deleted(Blake2bHash),
+    DoesNotExist,
+    RootNotFound,
+}
+
+/// Removes the leaf holding `key` from the trie at `root`, and returns the root of the trie
+/// without it.
+///
+/// The trie is restructured as if `key` had never been written: a node left with a single child
+/// is replaced by that child, so the new root is the one produced by writing the remaining pairs
+/// onto the empty root.  The elements of the trie at `root` are left in place, so it still holds
+/// the deleted leaf.
+pub fn delete<K, V, T, S, E>(
+    correlation_id: CorrelationId,
+    txn: &mut T,
+    store: &S,
+    root: &Blake2bHash,
+    key: &K,
+) -> Result<DeleteResult, E>
+where
+    K: ToBytes + FromBytes + Clone + Eq + std::fmt::Debug,
+    V: ToBytes + FromBytes + Clone + Eq,
+    T: Readable<Handle = S::Handle> + Writable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let start = Instant::now();
+
+    let current_root = match store.get(txn, root)? {
+        Some(current_root) => current_root,
+        None => return Ok(DeleteResult::RootNotFound),
+    };
+    let path: Vec<u8> = key.to_bytes()?;
+    let TrieScan { tip, mut parents } =
+        scan::<K, V, T, S, E>(correlation_id, txn, store, &path, &current_root)?;
+    match tip {
+        Trie::Leaf {
+            key: ref leaf_key, ..
+        } if leaf_key == key => (),
+        _ => return Ok(DeleteResult::DoesNotExist),
+    }
+
+    let (index, mut pointer_block) = match parents.pop() {
+        Some((index, Trie::Node { pointer_block })) => (index, pointer_block),
+        _ => panic!("A leaf should have a node for its parent"),
+    };
+    pointer_block[index.into()] = None;
+    let remaining_children: Vec<(usize, Pointer)> = pointer_block[..]
+        .iter()
+        .enumerate()
+        .filter_map(|(child_index, maybe_pointer)| {
+            maybe_pointer.map(|pointer| (child_index, pointer))
+        })
+        .collect();
+
+    let new_elements = match remaining_children.as_slice() {
+        // The root stays a node however few children it has left.
+        [(child_index, pointer)] if !parents.is_empty() => {
+            collapse_node::<K, V, T, S, E>(txn, store, *child_index, *pointer, parents)?
+        }
+        _ => rehash(Trie::Node { pointer_block }, parents)?,
+    };
+
+    let mut root_hash = root.to_owned();
+    for (hash, element) in new_elements.iter() {
+        store.put(txn, hash, element)?;
+        root_hash = *hash;
+    }
+    log_duration(
+        correlation_id,
+        TRIE_STORE_DELETE_DURATION,
+        DELETE,
+        start.elapsed(),
+    );
+    Ok(DeleteResult::Deleted(root_hash))
+}
+
+/// Replaces a node left with the single child at `child_index` by that child, and returns the
+/// new elements from there up to the root.
+///
+/// A leaf moves up into the closest node above, removing any extension in between.  Anything else
+/// is reached through an extension, merged with the extensions directly above and below it.
+#[allow(clippy::type_complexity)]
+fn collapse_node<K, V, T, S, E>(
+    txn: &T,
+    store: &S,
+    child_index: usize,
+    pointer: Pointer,
+    mut parents: Parents<K, V>,
+) -> Result<Vec<(Blake2bHash, Trie<K, V>)>, E>
+where
+    K: ToBytes + FromBytes + Clone,
+    V: ToBytes + FromBytes + Clone,
+    T: Readable<Handle = S::Handle>,
+    S: TrieStore<K, V>,
+    S::Error: From<T::Error>,
+    E: From<S::Error> + From<types::bytesrepr::Error>,
+{
+    let maybe_parent_affix = match parents.last() {
+        Some((_, Trie::Extension { affix, .. })) => Some(affix.to_owned()),
+        _ => None,
+    };
+    if maybe_parent_affix.is_some() {
+        parents.pop();
+    }
+
+    if let Pointer::LeafPointer(_) = pointer {
+        let (index, mut pointer_block) = match parents.pop() {
+            Some((index, Trie::Node { pointer_block })) => (index, pointer_block),
+            _ => panic!("An extension should have a node for its parent"),
+        };
+        pointer_block[index.into()] = Some(pointer);
+        return Ok(rehash(Trie::Node { pointer_block }, parents)?);
+    }
+
+    let mut affix = maybe_parent_affix.unwrap_or_default();
+    affix.push(child_index as u8);
+    let child_pointer = match store.get(txn, pointer.hash())? {
+        Some(Trie::Extension {
+            affix: child_affix,
+            pointer: child_pointer,
+        }) => {
+            affix.extend(child_affix);
+            child_pointer
+        }
+        Some(Trie::Node { .. }) => pointer,
+        Some(Trie::Leaf { .. }) => panic!("A node pointer should not point to a leaf"),
+        None => panic!("No trie value at key: {:?}", pointer.hash()),
+    };
+    Ok(rehash(Trie::extension(affix, child_pointer), parents)?)
+}
+
+/// Returns the root hash of the trie holding exactly the given `pairs`, along with all the
+/// elements of that trie, children first.
+///
+/// The trie is built bottom-up from the pairs sorted by key, rather than by writing each pair in
+/// turn, and is identical to the trie produced by writing the pairs onto an empty root.  If a key
+/// is given more than once, its last value is used.
+#[allow(clippy::type_complexity)]
+pub fn build<K, V>(
+    pairs: Vec<(K, V)>,
+) -> Result<(Blake2bHash, Vec<(Blake2bHash, Trie<K, V>)>), bytesrepr::Error>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    let mut sorted_leaves = BTreeMap::new();
+    for (key, value) in pairs {
+        let path = key.to_bytes()?;
+        sorted_leaves.insert(path, Trie::leaf(key, value));
+    }
+    let leaves: Vec<(Vec<u8>, Trie<K, V>)> = sorted_leaves.into_iter().collect();
+
+    let mut elements = Vec::new();
+    let root_hash = build_node(&leaves, 0, &mut elements)?;
+    Ok((root_hash, elements))
+}
+
+/// Adds to `elements` the node at `depth` holding the sorted `leaves`, and returns its hash.
+fn build_node<K, V>(
+    leaves: &[(Vec<u8>, Trie<K, V>)],
+    depth: usize,
+    elements: &mut Vec<(Blake2bHash, Trie<K, V>)>,
+) -> Result<Blake2bHash, bytesrepr::Error>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    let mut indexed_pointers = Vec::new();
+    let mut remaining = leaves;
+    while let Some((path, _)) = remaining.first() {
+        let index = path[depth];
+        let child_count = remaining
+            .iter()
+            .take_while(|(path, _)| path[depth] == index)
+            .count();
+        let (children, rest) = remaining.split_at(child_count);
+        let pointer = build_child(children, depth + 1, elements)?;
+        indexed_pointers.push((index.into(), pointer));
+        remaining = rest;
+    }
+    let node = Trie::node(&indexed_pointers);
+    let node_hash = Blake2bHash::new(&node.to_bytes()?);
+    elements.push((node_hash, node));
+    Ok(node_hash)
+}
+
+/// Adds to `elements` the child at `depth` of a node holding the sorted `leaves`, which all share
+/// the path to that child, and returns a pointer to it.
+///
+/// The child is a leaf if there is a single one, or else a node, preceded by an extension if the
+/// leaves share more of their paths.
+fn build_child<K, V>(
+    leaves: &[(Vec<u8>, Trie<K, V>)],
+    depth: usize,
+    elements: &mut Vec<(Blake2bHash, Trie<K, V>)>,
+) -> Result<Pointer, bytesrepr::Error>
+where
+    K: ToBytes + Clone,
+    V: ToBytes + Clone,
+{
+    if let [(_, leaf)] = leaves {
+        let leaf_hash = Blake2bHash::new(&leaf.to_bytes()?);
+        elements.push((leaf_hash, leaf.to_owned()));
+        return Ok(Pointer::LeafPointer(leaf_hash));
+    }
+
+    // As the leaves are sorted, the path they all share is the one shared by the first and last.
+    let first_path = &leaves[0].0;
+    let last_path = &leaves[leaves.len() - 1].0;
+    let affix = common_prefix(&first_path[depth..], &last_path[depth..]);
+    let node_hash = build_node(leaves, depth + affix.len(), elements)?;
+    if affix.is_empty() {
+        return Ok(Pointer::NodePointer(node_hash));
+    }
+
+    let extension = Trie::extension(affix, Pointer::NodePointer(node_hash));
+    let extension_hash = Blake2bHash::new(&extension.to_bytes()?);
+    elements.push((extension_hash, extension));
+    Ok(Pointer::NodePointer(extension_hash))
+}
+
 enum KeysIteratorState<K, V, S: TrieStore<K, V>> {
     /// Iterate normally
     Ok,
@@ -902,7 +1204,6 @@ where
 /// Returns the iterator over the keys in the subtrie matching `prefix`.
 ///
 /// The root should be the apex of the trie.
-#[allow(dead_code)]
 pub fn keys_with_prefix<'a, 'b, K, V, T, S>(
     _correlation_id: CorrelationId,
     txn: &'b T,
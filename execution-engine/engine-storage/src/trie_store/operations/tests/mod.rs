@@ -19,12 +19,12 @@ use crate::{
         in_memory::InMemoryEnvironment, lmdb::LmdbEnvironment, Readable, Transaction,
         TransactionSource,
     },
-    trie::{Pointer, Trie},
+    trie::{Pointer, Trie, TrieMerkleProof},
     trie_store::{
         self,
         in_memory::InMemoryTrieStore,
         lmdb::LmdbTrieStore,
-        operations::{self, read, write, ReadResult, WriteResult},
+        operations::{self, read, write, DeleteResult, ReadResult, WriteResult},
         TrieStore,
     },
     TEST_MAP_SIZE,
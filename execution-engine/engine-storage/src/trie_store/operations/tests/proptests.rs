@@ -76,6 +76,85 @@ fn in_memory_roundtrip_succeeds(pairs: &[(TestKey, TestValue)]) -> bool {
     .unwrap()
 }
 
+fn build_matches_writes(pairs: &[(TestKey, TestValue)]) -> bool {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+
+    let written_root_hash = write_pairs::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &empty_root_hash,
+        pairs,
+    )
+    .unwrap()
+    .last()
+    .cloned()
+    .unwrap_or(empty_root_hash);
+
+    let (built_root_hash, _) = operations::build(pairs.to_vec()).unwrap();
+    built_root_hash == written_root_hash
+}
+
+/// Deletes the first key written from the resulting trie, checking the new root is the one `build`
+/// gives for the other pairs and the old root still holds the deleted key.
+fn delete_matches_build_of_remaining_pairs(pairs: &[(TestKey, TestValue)]) -> bool {
+    let correlation_id = CorrelationId::new();
+    let (empty_root_hash, tries) = TEST_TRIE_GENERATORS[0]().unwrap();
+    let context = InMemoryTestContext::new(&tries).unwrap();
+    let deleted_key = match pairs.first() {
+        Some((key, _)) => *key,
+        None => return true,
+    };
+
+    let written_root_hash = *write_pairs::<_, _, _, _, in_memory::Error>(
+        correlation_id,
+        &context.environment,
+        &context.store,
+        &empty_root_hash,
+        pairs,
+    )
+    .unwrap()
+    .last()
+    .unwrap();
+
+    let mut txn = context.environment.create_read_write_txn().unwrap();
+    let deleted_root_hash = match operations::delete::<_, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &mut txn,
+        &context.store,
+        &written_root_hash,
+        &deleted_key,
+    )
+    .unwrap()
+    {
+        DeleteResult::Deleted(root_hash) => root_hash,
+        other => panic!("should delete {:?}: {:?}", deleted_key, other),
+    };
+    let still_readable = match read::<_, TestValue, _, _, in_memory::Error>(
+        correlation_id,
+        &txn,
+        &context.store,
+        &written_root_hash,
+        &deleted_key,
+    )
+    .unwrap()
+    {
+        ReadResult::Found(_) => true,
+        _ => false,
+    };
+    txn.commit().unwrap();
+
+    let remaining_pairs: Vec<(TestKey, TestValue)> = pairs
+        .iter()
+        .filter(|(key, _)| *key != deleted_key)
+        .cloned()
+        .collect();
+    let (built_root_hash, _) = operations::build(remaining_pairs).unwrap();
+    still_readable && built_root_hash == deleted_root_hash
+}
+
 fn test_key_arb() -> impl Strategy<Value = TestKey> {
     array::uniform7(any::<u8>()).prop_map(TestKey)
 }
@@ -94,4 +173,14 @@ proptest! {
     fn prop_lmdb_roundtrip_succeeds(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
         assert!(lmdb_roundtrip_succeeds(&inputs));
     }
+
+    #[test]
+    fn prop_build_matches_writes(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
+        assert!(build_matches_writes(&inputs));
+    }
+
+    #[test]
+    fn prop_delete_matches_build_of_remaining_pairs(inputs in vec((test_key_arb(), test_value_arb()), get_range())) {
+        assert!(delete_matches_build_of_remaining_pairs(&inputs));
+    }
 }
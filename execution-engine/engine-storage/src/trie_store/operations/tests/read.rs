@@ -126,3 +126,102 @@ mod full_tries {
         }
     }
 }
+
+mod proofs {
+    //! Here we read each leaf of the "partial" tries with a proof, and check the proof leads back
+    //! to the root it was read from, unless its value is tampered with.
+
+    use super::*;
+
+    fn check_proofs<'a, R, S, E>(
+        environment: &'a R,
+        store: &S,
+        root: &Blake2bHash,
+        present: &[TestTrie],
+        absent: &[TestTrie],
+    ) -> Result<(), E>
+    where
+        R: TransactionSource<'a, Handle = S::Handle>,
+        S: TrieStore<TestKey, TestValue>,
+        S::Error: From<R::Error>,
+        E: From<R::Error> + From<S::Error> + From<types::bytesrepr::Error>,
+    {
+        let correlation_id = CorrelationId::new();
+        let txn: R::ReadTransaction = environment.create_read_txn()?;
+
+        for leaf in present {
+            let (key, value) = match leaf {
+                Trie::Leaf { key, value } => (key, value),
+                _ => panic!("leaves should only contain leaves"),
+            };
+            let proof = match operations::read_with_proof::<_, _, _, _, E>(
+                correlation_id,
+                &txn,
+                store,
+                root,
+                key,
+            )? {
+                ReadResult::Found(proof) => proof,
+                _ => panic!("should find {:?}", key),
+            };
+            assert_eq!(proof.value(), value);
+            assert!(proof.verify(root)?);
+
+            let mut tampered_value = *value;
+            tampered_value.0[0] ^= 1;
+            let tampered_proof = TrieMerkleProof::new(*key, tampered_value, proof.path().to_vec());
+            assert!(!tampered_proof.verify(root)?);
+        }
+
+        for leaf in absent {
+            let key = leaf.key().expect("should be a leaf");
+            let result = operations::read_with_proof::<_, _, _, _, E>(
+                correlation_id,
+                &txn,
+                store,
+                root,
+                key,
+            )?;
+            assert_eq!(result, ReadResult::NotFound);
+        }
+
+        txn.commit()?;
+        Ok(())
+    }
+
+    #[test]
+    fn lmdb_proofs_from_n_leaf_partial_trie_lead_to_root() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let (root_hash, tries) = generator().unwrap();
+            let context = LmdbTestContext::new(&tries).unwrap();
+            let (used, unused) = TEST_LEAVES.split_at(num_leaves);
+
+            check_proofs::<_, _, error::Error>(
+                &context.environment,
+                &context.store,
+                &root_hash,
+                used,
+                unused,
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn in_memory_proofs_from_n_leaf_partial_trie_lead_to_root() {
+        for (num_leaves, generator) in TEST_TRIE_GENERATORS.iter().enumerate() {
+            let (root_hash, tries) = generator().unwrap();
+            let context = InMemoryTestContext::new(&tries).unwrap();
+            let (used, unused) = TEST_LEAVES.split_at(num_leaves);
+
+            check_proofs::<_, _, in_memory::Error>(
+                &context.environment,
+                &context.store,
+                &root_hash,
+                used,
+                unused,
+            )
+            .unwrap();
+        }
+    }
+}
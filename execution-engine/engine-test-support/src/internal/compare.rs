@@ -0,0 +1,394 @@
+//! Support for comparing the results of executing a corpus of deploys on two differently
+//! configured engines, e.g. with different cost tables or feature flags.
+//!
+//! A corpus is a directory holding a serialized `ipc::RunGenesisRequest` in [`GENESIS_FILE`] and
+//! any number of serialized `ipc::ExecuteRequest`s in files named with [`EXEC_REQUEST_SUFFIX`].
+//! The execute requests are run in the order of their file names, each on the post-state of the
+//! previous one, with the effects of all their deploys committed in turn.  Their parent state
+//! hashes are ignored.
+//!
+//! Running a [`Corpus`] with [`compare`] gives a [`ComparisonReport`] naming, per deploy, the
+//! change in cost, the change of exit code and the first key at which the effects differ.
+
+use std::{
+    collections::BTreeSet,
+    convert::TryInto,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+use protobuf::Message;
+
+use engine_core::engine_state::{
+    execute_request::ExecuteRequest, exit_code::ExitCode, run_genesis_request::RunGenesisRequest,
+    EngineConfig,
+};
+use engine_grpc_server::engine_server::ipc;
+use engine_shared::{
+    additive_map::AdditiveMap, gas::Gas, stored_value::StoredValue, transform::Transform,
+};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{CLType, Key};
+
+use crate::internal::{AdditiveMapDiff, InMemoryWasmTestBuilder};
+
+/// The name of the file holding the genesis request of a corpus.
+pub const GENESIS_FILE: &str = "genesis.pb";
+/// The suffix of the names of the files holding the execute requests of a corpus.
+pub const EXEC_REQUEST_SUFFIX: &str = ".exec.pb";
+
+/// A genesis request and a sequence of named execute requests to run after it.
+#[derive(Clone, Debug)]
+pub struct Corpus {
+    run_genesis_request: RunGenesisRequest,
+    exec_requests: Vec<(String, ipc::ExecuteRequest)>,
+}
+
+impl Corpus {
+    pub fn new(run_genesis_request: RunGenesisRequest) -> Self {
+        Corpus {
+            run_genesis_request,
+            exec_requests: Vec::new(),
+        }
+    }
+
+    /// Appends `exec_request` under `name`, which is also the stem of its file name once written.
+    pub fn push<T: Into<String>>(&mut self, name: T, exec_request: ExecuteRequest) {
+        self.exec_requests.push((name.into(), exec_request.into()));
+    }
+
+    pub fn run_genesis_request(&self) -> &RunGenesisRequest {
+        &self.run_genesis_request
+    }
+
+    /// Returns the names of the execute requests, in the order they're run.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.exec_requests.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Reads a corpus from the directory at `path`.
+    pub fn read_from_dir<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let run_genesis_request = {
+            let bytes = fs::read(path.join(GENESIS_FILE))?;
+            let proto: ipc::RunGenesisRequest = parse(GENESIS_FILE, &bytes)?;
+            proto
+                .try_into()
+                .map_err(|error| invalid_data(GENESIS_FILE, format!("{:?}", error)))?
+        };
+
+        let mut file_names = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let file_name = entry?.file_name().to_string_lossy().into_owned();
+            if file_name.ends_with(EXEC_REQUEST_SUFFIX) {
+                file_names.push(file_name);
+            }
+        }
+        file_names.sort();
+
+        let mut corpus = Corpus::new(run_genesis_request);
+        for file_name in file_names {
+            let bytes = fs::read(path.join(&file_name))?;
+            let proto: ipc::ExecuteRequest = parse(&file_name, &bytes)?;
+            // Only to validate the request; it's converted again each time it's run.
+            let _: ExecuteRequest = proto
+                .clone()
+                .try_into()
+                .map_err(|error| invalid_data(&file_name, format!("{:?}", error)))?;
+            let name = file_name.trim_end_matches(EXEC_REQUEST_SUFFIX).to_string();
+            corpus.exec_requests.push((name, proto));
+        }
+        Ok(corpus)
+    }
+
+    /// Writes the corpus to the directory at `path`, creating it if necessary.
+    pub fn write_to_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        fs::create_dir_all(path)?;
+        let run_genesis_request: ipc::RunGenesisRequest = self.run_genesis_request.clone().into();
+        fs::write(path.join(GENESIS_FILE), serialize(&run_genesis_request)?)?;
+        for (name, exec_request) in &self.exec_requests {
+            let file_name = format!("{}{}", name, EXEC_REQUEST_SUFFIX);
+            fs::write(path.join(file_name), serialize(exec_request)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// The configuration of one of the two engines being compared.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EngineVariant {
+    engine_config: EngineConfig,
+    wasm_costs: Option<WasmCosts>,
+}
+
+impl EngineVariant {
+    pub fn new(engine_config: EngineConfig) -> Self {
+        EngineVariant {
+            engine_config,
+            wasm_costs: None,
+        }
+    }
+
+    /// Runs genesis with `wasm_costs` rather than the costs in the corpus' genesis request.
+    pub fn with_wasm_costs(mut self, wasm_costs: WasmCosts) -> Self {
+        self.wasm_costs = Some(wasm_costs);
+        self
+    }
+
+    /// Runs the corpus, returning the outcome of each deploy.
+    pub fn run(&self, corpus: &Corpus) -> Vec<DeployOutcome> {
+        let global_state = InMemoryGlobalState::empty().expect("should create global state");
+        let empty_root_hash = global_state.empty_root_hash.to_vec();
+        let mut builder =
+            InMemoryWasmTestBuilder::new(global_state, self.engine_config, empty_root_hash);
+
+        let run_genesis_request = match self.wasm_costs {
+            Some(wasm_costs) => {
+                let mut ee_config = corpus.run_genesis_request.ee_config().clone();
+                ee_config.set_wasm_costs(wasm_costs);
+                RunGenesisRequest::new(
+                    corpus.run_genesis_request.genesis_config_hash(),
+                    corpus.run_genesis_request.protocol_version(),
+                    ee_config,
+                )
+            }
+            None => corpus.run_genesis_request.clone(),
+        };
+        builder.run_genesis(&run_genesis_request);
+
+        let mut outcomes = Vec::new();
+        for (index, (name, proto)) in corpus.exec_requests.iter().enumerate() {
+            let exec_request: ExecuteRequest = proto
+                .clone()
+                .try_into()
+                .unwrap_or_else(|_| panic!("should convert execute request {}", name));
+            builder.exec(exec_request);
+
+            let exec_response = builder
+                .get_exec_response(index)
+                .expect("should have exec response")
+                .clone();
+            for (deploy_index, result) in exec_response.iter().enumerate() {
+                let name = if exec_response.len() == 1 {
+                    name.clone()
+                } else {
+                    format!("{}[{}]", name, deploy_index)
+                };
+                let transforms = result.effect().transforms.clone();
+                outcomes.push(DeployOutcome {
+                    name,
+                    cost: result.cost(),
+                    exit_code: result.exit_code(),
+                    transforms: transforms.clone(),
+                });
+                let post_state_hash = builder.get_post_state_hash();
+                builder.commit_effects(post_state_hash, transforms);
+            }
+        }
+        outcomes
+    }
+}
+
+/// The outcome of executing a single deploy of a corpus.
+#[derive(Clone, Debug)]
+pub struct DeployOutcome {
+    pub name: String,
+    pub cost: Gas,
+    pub exit_code: ExitCode,
+    pub transforms: AdditiveMap<Key, Transform>,
+}
+
+/// A difference between the outcomes of a deploy on the left and right engines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeployDifference {
+    Cost {
+        left: Gas,
+        right: Gas,
+    },
+    ExitCode {
+        left: ExitCode,
+        right: ExitCode,
+    },
+    /// The effects differ, the smallest differing key being `first_differing_key`.
+    Effects {
+        first_differing_key: Key,
+    },
+    /// The deploy was only executed by the left engine.
+    LeftOnly,
+    /// The deploy was only executed by the right engine.
+    RightOnly,
+}
+
+impl Display for DeployDifference {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            DeployDifference::Cost { left, right } if right >= left => write!(
+                formatter,
+                "cost {} -> {} (+{})",
+                left,
+                right,
+                *right - *left
+            ),
+            DeployDifference::Cost { left, right } => write!(
+                formatter,
+                "cost {} -> {} (-{})",
+                left,
+                right,
+                *left - *right
+            ),
+            DeployDifference::ExitCode { left, right } => {
+                write!(formatter, "exit code {:?} -> {:?}", left, right)
+            }
+            DeployDifference::Effects {
+                first_differing_key,
+            } => write!(formatter, "effects differ at {}", first_differing_key),
+            DeployDifference::LeftOnly => write!(formatter, "only executed by left engine"),
+            DeployDifference::RightOnly => write!(formatter, "only executed by right engine"),
+        }
+    }
+}
+
+/// The differences between the outcomes of a single deploy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeployComparison {
+    pub name: String,
+    pub differences: Vec<DeployDifference>,
+}
+
+impl DeployComparison {
+    /// Compares the outcomes of the same deploy.
+    ///
+    /// If the costs differ, so do the amounts moved between purses to pay for the deploy, so
+    /// effects differing only by U512 values written or added to the same keys aren't reported.
+    pub fn new(left: &DeployOutcome, right: &DeployOutcome) -> Self {
+        let mut differences = Vec::new();
+        if left.cost != right.cost {
+            differences.push(DeployDifference::Cost {
+                left: left.cost,
+                right: right.cost,
+            });
+        }
+        if left.exit_code != right.exit_code {
+            differences.push(DeployDifference::ExitCode {
+                left: left.exit_code,
+                right: right.exit_code,
+            });
+        }
+
+        let diff = AdditiveMapDiff::new(left.transforms.clone(), right.transforms.clone());
+        let differing_keys: BTreeSet<&Key> =
+            diff.left().keys().chain(diff.right().keys()).collect();
+        let first_differing_key = differing_keys.into_iter().find(|key| {
+            left.cost == right.cost
+                || !is_balance_transform(diff.left().get(*key))
+                || !is_balance_transform(diff.right().get(*key))
+        });
+        if let Some(first_differing_key) = first_differing_key {
+            differences.push(DeployDifference::Effects {
+                first_differing_key: *first_differing_key,
+            });
+        }
+
+        DeployComparison {
+            name: left.name.clone(),
+            differences,
+        }
+    }
+
+    pub fn is_unchanged(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// The comparisons of all deploys of a corpus which changed, in the order they were run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ComparisonReport {
+    comparisons: Vec<DeployComparison>,
+}
+
+impl ComparisonReport {
+    pub fn new(left: &[DeployOutcome], right: &[DeployOutcome]) -> Self {
+        let mut comparisons = Vec::new();
+        for left_outcome in left {
+            let comparison = match right
+                .iter()
+                .find(|outcome| outcome.name == left_outcome.name)
+            {
+                Some(right_outcome) => DeployComparison::new(left_outcome, right_outcome),
+                None => DeployComparison {
+                    name: left_outcome.name.clone(),
+                    differences: vec![DeployDifference::LeftOnly],
+                },
+            };
+            comparisons.push(comparison);
+        }
+        for right_outcome in right {
+            if !left
+                .iter()
+                .any(|outcome| outcome.name == right_outcome.name)
+            {
+                comparisons.push(DeployComparison {
+                    name: right_outcome.name.clone(),
+                    differences: vec![DeployDifference::RightOnly],
+                });
+            }
+        }
+        comparisons.retain(|comparison| !comparison.is_unchanged());
+        ComparisonReport { comparisons }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.comparisons.is_empty()
+    }
+
+    pub fn comparisons(&self) -> &[DeployComparison] {
+        &self.comparisons
+    }
+}
+
+impl Display for ComparisonReport {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        for comparison in &self.comparisons {
+            writeln!(formatter, "{}:", comparison.name)?;
+            for difference in &comparison.differences {
+                writeln!(formatter, "  {}", difference)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Runs `corpus` on both engines and compares the outcomes.
+pub fn compare(corpus: &Corpus, left: EngineVariant, right: EngineVariant) -> ComparisonReport {
+    ComparisonReport::new(&left.run(corpus), &right.run(corpus))
+}
+
+fn is_balance_transform(maybe_transform: Option<&Transform>) -> bool {
+    match maybe_transform {
+        Some(Transform::AddUInt512(_)) => true,
+        Some(Transform::Write(StoredValue::CLValue(cl_value))) => {
+            *cl_value.cl_type() == CLType::U512
+        }
+        _ => false,
+    }
+}
+
+fn parse<M: Message>(file_name: &str, bytes: &[u8]) -> io::Result<M> {
+    protobuf::parse_from_bytes(bytes).map_err(|error| invalid_data(file_name, error.to_string()))
+}
+
+fn serialize<M: Message>(message: &M) -> io::Result<Vec<u8>> {
+    message
+        .write_to_bytes()
+        .map_err(|error| io::Error::new(io::ErrorKind::Other, error.to_string()))
+}
+
+fn invalid_data(file_name: &str, error: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", file_name, error),
+    )
+}
@@ -0,0 +1,374 @@
+//! Builds the contracts of this repo on demand, so that tests don't depend on an external build
+//! step having been run, nor run against stale Wasm.
+//!
+//! Building is opt-in: it's enabled by setting the environment variable `CL_BUILD_CONTRACTS=1`.
+//! A contract is built with cargo into a cache under the target directory, keyed by a hash of its
+//! sources, so it's only rebuilt once its sources change.  Concurrent builds of a contract, e.g. by
+//! tests running in parallel, are serialized by a file lock and the contract is built only once.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    env,
+    ffi::OsString,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use fs2::FileExt;
+use lazy_static::lazy_static;
+
+use engine_shared::newtypes::Blake2bHash;
+
+/// The environment variable which enables building contracts on demand when set to `1`.
+pub const BUILD_CONTRACTS_ENV_VAR: &str = "CL_BUILD_CONTRACTS";
+
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+const WASM_EXTENSION: &str = "wasm";
+const LOCK_FILE_NAME: &str = ".lock";
+const ENABLE_BONDING_FEATURE: &str = "enable-bonding";
+/// The crates which every contract is built against, and so whose sources are part of every
+/// contract's source hash.
+const SHARED_CRATES: [&str; 2] = ["contract", "types"];
+
+lazy_static! {
+    static ref WORKSPACE_PATH: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("CARGO_MANIFEST_DIR should have parent")
+        .to_path_buf();
+    static ref TARGET_PATH: PathBuf = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| WORKSPACE_PATH.join("target"));
+    // The cache of built Wasm files, i.e. '<target>/contract-cache/<wasm name>/<hash>.wasm'.
+    static ref CONTRACT_CACHE_PATH: PathBuf = TARGET_PATH.join("contract-cache");
+    // Contracts are built in their own target directory, as the test runner may hold the lock on
+    // the default one.
+    static ref CONTRACT_BUILD_TARGET_PATH: PathBuf = TARGET_PATH.join("contract-build");
+    static ref CONTRACT_CRATES: BTreeMap<String, ContractCrate> = find_contract_crates();
+    static ref CHECKED_FOR_STALENESS: Mutex<BTreeSet<String>> = Mutex::new(BTreeSet::new());
+}
+
+/// A contract crate of this repo which compiles to a Wasm file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractCrate {
+    package: String,
+    wasm_name: String,
+    path: PathBuf,
+    features: Vec<String>,
+}
+
+impl ContractCrate {
+    /// Returns the name of the crate's package.
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    /// Returns the name of the Wasm file the crate compiles to, e.g. `do_nothing.wasm`.
+    pub fn wasm_name(&self) -> &str {
+        &self.wasm_name
+    }
+
+    /// Returns the directory of the crate.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the command building the crate with the workspace's Makefile.
+    pub fn build_command(&self) -> String {
+        format!(
+            "make -C {} build-contract-rs/{}",
+            WORKSPACE_PATH.display(),
+            self.package
+        )
+    }
+
+    /// Returns the hash of the sources the crate's Wasm is built from: the crate's own, those of
+    /// the crates shared by all contracts and the features built with.
+    pub fn source_hash(&self) -> String {
+        let mut source_paths = source_files(&self.path);
+        for shared_crate in SHARED_CRATES.iter() {
+            source_paths.extend(source_files(&WORKSPACE_PATH.join(shared_crate)));
+        }
+        source_paths.insert(WORKSPACE_PATH.join("rust-toolchain"));
+
+        let mut sources = self.features.join(",").into_bytes();
+        for source_path in source_paths {
+            let relative_path = source_path
+                .strip_prefix(&*WORKSPACE_PATH)
+                .unwrap_or(&source_path);
+            sources.extend(relative_path.to_string_lossy().as_bytes());
+            sources.push(0);
+            sources.extend(fs::read(&source_path).unwrap_or_default());
+            sources.push(0);
+        }
+        format!("{:x}", Blake2bHash::new(&sources))
+    }
+
+    /// Returns the path of the cached Wasm built from the crate's current sources, whether or not
+    /// it has been built yet.
+    pub fn cached_wasm_path(&self) -> PathBuf {
+        CONTRACT_CACHE_PATH.join(&self.wasm_name).join(format!(
+            "{}.{}",
+            self.source_hash(),
+            WASM_EXTENSION
+        ))
+    }
+
+    fn newest_source_time(&self) -> Option<SystemTime> {
+        source_files(&self.path)
+            .iter()
+            .filter_map(|path| {
+                fs::metadata(path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .max()
+    }
+}
+
+/// Returns `true` if building contracts on demand is enabled by [`BUILD_CONTRACTS_ENV_VAR`].
+pub fn is_enabled() -> bool {
+    env::var(BUILD_CONTRACTS_ENV_VAR).map_or(false, |value| value == "1")
+}
+
+/// Returns the contract crate of this repo which compiles to `wasm_file`, if any.
+///
+/// Only bare file names, e.g. `do_nothing.wasm`, name a contract crate.
+pub fn find_contract_crate<T: AsRef<Path>>(wasm_file: T) -> Option<ContractCrate> {
+    let wasm_file = wasm_file.as_ref();
+    if wasm_file.parent() != Some(Path::new("")) {
+        return None;
+    }
+    CONTRACT_CRATES.get(&*wasm_file.to_string_lossy()).cloned()
+}
+
+/// Returns the path of `contract_crate`'s Wasm built from its current sources, building it first
+/// unless it's cached.
+///
+/// Panics if the build fails.
+pub fn build_contract(contract_crate: &ContractCrate) -> PathBuf {
+    let cached_wasm_path = contract_crate.cached_wasm_path();
+    if cached_wasm_path.is_file() {
+        return cached_wasm_path;
+    }
+
+    let cache_path = cached_wasm_path
+        .parent()
+        .expect("cached Wasm should have parent");
+    fs::create_dir_all(cache_path)
+        .unwrap_or_else(|error| panic!("should create {}: {}", cache_path.display(), error));
+    let lock_file = File::create(cache_path.join(LOCK_FILE_NAME))
+        .and_then(|lock_file| lock_file.lock_exclusive().map(|_| lock_file))
+        .unwrap_or_else(|error| panic!("should lock {}: {}", cache_path.display(), error));
+
+    // another test may have built the contract while this one waited for the lock
+    if !cached_wasm_path.is_file() {
+        run_cargo_build(contract_crate);
+        let built_wasm_path = CONTRACT_BUILD_TARGET_PATH
+            .join(WASM_TARGET)
+            .join("release")
+            .join(&contract_crate.wasm_name);
+        // copied then renamed so that the cache never holds a partially written Wasm file
+        let partial_wasm_path = cached_wasm_path.with_extension("partial");
+        fs::copy(&built_wasm_path, &partial_wasm_path)
+            .and_then(|_| fs::rename(&partial_wasm_path, &cached_wasm_path))
+            .unwrap_or_else(|error| {
+                panic!(
+                    "should cache {} as {}: {}",
+                    built_wasm_path.display(),
+                    cached_wasm_path.display(),
+                    error
+                )
+            });
+    }
+
+    let _ = lock_file.unlock();
+    cached_wasm_path
+}
+
+/// Prints a warning, once per contract, if `wasm_path` is older than `contract_crate`'s sources,
+/// i.e. it was probably built before they were last changed.
+pub(crate) fn warn_if_stale(contract_crate: &ContractCrate, wasm_path: &Path) {
+    let first_check = CHECKED_FOR_STALENESS
+        .lock()
+        .expect("should lock stale checks")
+        .insert(contract_crate.wasm_name.clone());
+    if !first_check {
+        return;
+    }
+    let wasm_time = match fs::metadata(wasm_path).and_then(|metadata| metadata.modified()) {
+        Ok(wasm_time) => wasm_time,
+        Err(_) => return,
+    };
+    match contract_crate.newest_source_time() {
+        Some(source_time) if source_time > wasm_time => eprintln!(
+            "warning: {} is older than the sources of {}; rebuild it with `{}` or set {}=1",
+            wasm_path.display(),
+            contract_crate.package,
+            contract_crate.build_command(),
+            BUILD_CONTRACTS_ENV_VAR
+        ),
+        _ => (),
+    }
+}
+
+fn run_cargo_build(contract_crate: &ContractCrate) {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let mut command = Command::new(cargo);
+    command
+        .current_dir(&*WORKSPACE_PATH)
+        .arg("build")
+        .arg("--release")
+        .args(&["--package", &contract_crate.package])
+        .args(&["--target", WASM_TARGET])
+        .arg("--target-dir")
+        .arg(&*CONTRACT_BUILD_TARGET_PATH);
+    if !contract_crate.features.is_empty() {
+        command.args(&["--features", &contract_crate.features.join(",")]);
+    }
+
+    let output = command.output().unwrap_or_else(|error| {
+        panic!(
+            "should run cargo to build {}: {}",
+            contract_crate.package, error
+        )
+    });
+    if !output.status.success() {
+        panic!(
+            "\nFailed to build {} from {}:\n{}\n",
+            contract_crate.wasm_name,
+            contract_crate.path.display(),
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
+
+/// Returns the contract crates under 'contracts/', keyed by the name of the Wasm file each
+/// compiles to.  Empty if the contracts' sources aren't available, e.g. when this crate is used
+/// from crates.io.
+fn find_contract_crates() -> BTreeMap<String, ContractCrate> {
+    let mut contract_crates = BTreeMap::new();
+    let categories = match fs::read_dir(WORKSPACE_PATH.join("contracts")) {
+        Ok(categories) => categories,
+        Err(_) => return contract_crates,
+    };
+    for category in categories.filter_map(Result::ok) {
+        let crate_paths = match fs::read_dir(category.path()) {
+            Ok(crate_paths) => crate_paths,
+            Err(_) => continue,
+        };
+        for crate_path in crate_paths.filter_map(Result::ok).map(|entry| entry.path()) {
+            if let Ok(manifest) = fs::read_to_string(crate_path.join("Cargo.toml")) {
+                if let Some(contract_crate) = parse_manifest(&manifest, crate_path) {
+                    contract_crates.insert(contract_crate.wasm_name.clone(), contract_crate);
+                }
+            }
+        }
+    }
+    contract_crates
+}
+
+/// Parses the package name, binary name and features of a contract crate's manifest.  Only the
+/// few keys needed are read, so a TOML parser isn't.
+fn parse_manifest(manifest: &str, path: PathBuf) -> Option<ContractCrate> {
+    let mut package = None;
+    let mut bin_name = None;
+    let mut has_enable_bonding = false;
+    let mut section = "";
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            section = line;
+            continue;
+        }
+        let mut key_value = line.splitn(2, '=').map(str::trim);
+        let (key, value) = match (key_value.next(), key_value.next()) {
+            (Some(key), Some(value)) => (key, value.trim_matches('"')),
+            _ => continue,
+        };
+        match (section, key) {
+            ("[package]", "name") => package = Some(value.to_string()),
+            ("[[bin]]", "name") if bin_name.is_none() => bin_name = Some(value.to_string()),
+            ("[features]", ENABLE_BONDING_FEATURE) => has_enable_bonding = true,
+            _ => (),
+        }
+    }
+
+    let mut features = Vec::new();
+    if cfg!(feature = "enable-bonding") && has_enable_bonding {
+        features.push(ENABLE_BONDING_FEATURE.to_string());
+    }
+    Some(ContractCrate {
+        package: package?,
+        wasm_name: format!("{}.{}", bin_name?, WASM_EXTENSION),
+        path,
+        features,
+    })
+}
+
+/// Returns the source files of the crate at `crate_path`: its manifest and everything under
+/// 'src/'.
+fn source_files(crate_path: &Path) -> BTreeSet<PathBuf> {
+    fn visit(path: &Path, files: &mut BTreeSet<PathBuf>) -> io::Result<()> {
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                visit(&path, files)?;
+            } else {
+                files.insert(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeSet::new();
+    files.insert(crate_path.join("Cargo.toml"));
+    let _ = visit(&crate_path.join("src"), &mut files);
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_manifest, ContractCrate};
+
+    #[test]
+    fn should_parse_contract_manifest() {
+        let manifest = r#"
+[package]
+name = "ee-550-regression"
+version = "0.1.0"
+
+[[bin]]
+name = "ee_550_regression"
+path = "src/main.rs"
+
+[dependencies]
+contract = { path = "../../../contract", package = "casperlabs-contract" }
+"#;
+        assert_eq!(
+            parse_manifest(manifest, PathBuf::from("contracts/test/ee-550-regression")),
+            Some(ContractCrate {
+                package: "ee-550-regression".to_string(),
+                wasm_name: "ee_550_regression.wasm".to_string(),
+                path: PathBuf::from("contracts/test/ee-550-regression"),
+                features: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn should_not_parse_library_manifest() {
+        let manifest = r#"
+[package]
+name = "create-test-node-shared"
+
+[lib]
+crate-type = ["lib"]
+"#;
+        assert_eq!(parse_manifest(manifest, PathBuf::new()), None);
+    }
+}
@@ -1,11 +1,14 @@
 use std::{collections::BTreeSet, path::Path};
 
 use contract::args_parser::ArgsParser;
-use engine_core::{
-    engine_state::{deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem},
-    DeployHash,
+use engine_core::engine_state::{
+    deploy_item::DeployItem, executable_deploy_item::ExecutableDeployItem,
+};
+use types::{
+    account::PublicKey,
+    bytesrepr::{self, ToBytes},
+    CLValue, ContractHash, DeployHash, URef,
 };
-use types::{account::PublicKey, bytesrepr::ToBytes, URef};
 
 use crate::internal::utils;
 
@@ -17,6 +20,9 @@ struct DeployItemData {
     pub gas_price: u64,
     pub authorization_keys: BTreeSet<PublicKey>,
     pub deploy_hash: DeployHash,
+    pub system_deploy: bool,
+    pub session_arg_names: Vec<String>,
+    pub payment_arg_names: Vec<String>,
 }
 
 pub struct DeployItemBuilder {
@@ -49,7 +55,12 @@ impl DeployItemBuilder {
         self.with_payment_bytes(module_bytes, args)
     }
 
-    pub fn with_stored_payment_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
+    pub fn with_stored_payment_hash(
+        mut self,
+        hash: impl Into<ContractHash>,
+        args: impl ArgsParser,
+    ) -> Self {
+        let hash = hash.into().as_bytes().to_vec();
         let args = Self::serialize_args(args);
         self.deploy_item.payment_code =
             Some(ExecutableDeployItem::StoredContractByHash { hash, args });
@@ -87,6 +98,27 @@ impl DeployItemBuilder {
         self
     }
 
+    pub fn with_sponsor_payment_hash(
+        mut self,
+        hash: impl Into<ContractHash>,
+        args: impl ArgsParser,
+    ) -> Self {
+        let hash = hash.into().as_bytes().to_vec();
+        let args = Self::serialize_args(args);
+        self.deploy_item.payment_code =
+            Some(ExecutableDeployItem::SponsorContractByHash { hash, args });
+        self
+    }
+
+    pub fn with_sponsor_payment_named_key(mut self, name: &str, args: impl ArgsParser) -> Self {
+        let args = Self::serialize_args(args);
+        self.deploy_item.payment_code = Some(ExecutableDeployItem::SponsorContractByName {
+            name: name.to_owned(),
+            args,
+        });
+        self
+    }
+
     pub fn with_session_bytes(mut self, module_bytes: Vec<u8>, args: impl ArgsParser) -> Self {
         let args = Self::serialize_args(args);
         self.deploy_item.session_code =
@@ -99,7 +131,12 @@ impl DeployItemBuilder {
         self.with_session_bytes(module_bytes, args)
     }
 
-    pub fn with_stored_session_hash(mut self, hash: Vec<u8>, args: impl ArgsParser) -> Self {
+    pub fn with_stored_session_hash(
+        mut self,
+        hash: impl Into<ContractHash>,
+        args: impl ArgsParser,
+    ) -> Self {
+        let hash = hash.into().as_bytes().to_vec();
         let args = Self::serialize_args(args);
         self.deploy_item.session_code =
             Some(ExecutableDeployItem::StoredContractByHash { hash, args });
@@ -137,6 +174,55 @@ impl DeployItemBuilder {
         self
     }
 
+    /// Sets the session argument at `index` to `purse`.
+    ///
+    /// The session code must already be set.  If `index` equals the number of its arguments, the
+    /// purse is appended.
+    pub fn with_purse_arg(mut self, index: usize, purse: URef) -> Self {
+        let session_code = self
+            .deploy_item
+            .session_code
+            .as_mut()
+            .expect("should set session code before purse arg");
+        let args = match session_code {
+            ExecutableDeployItem::ModuleBytes { args, .. }
+            | ExecutableDeployItem::StoredContractByHash { args, .. }
+            | ExecutableDeployItem::StoredContractByName { args, .. }
+            | ExecutableDeployItem::StoredContractByURef { args, .. }
+            | ExecutableDeployItem::SponsorContractByHash { args, .. }
+            | ExecutableDeployItem::SponsorContractByName { args, .. } => args,
+        };
+
+        let mut cl_values: Vec<CLValue> =
+            bytesrepr::deserialize(args.clone()).expect("should deserialize args");
+        let purse = CLValue::from_t(purse).expect("should convert purse");
+        if index < cl_values.len() {
+            cl_values[index] = purse;
+        } else if index == cl_values.len() {
+            cl_values.push(purse);
+        } else {
+            panic!(
+                "purse arg index {} is beyond the {} session args",
+                index,
+                cl_values.len()
+            );
+        }
+        *args = cl_values.into_bytes().expect("should serialize args");
+        self
+    }
+
+    /// Names the session args by position, an empty name leaving an arg unnamed.
+    pub fn with_session_arg_names(mut self, names: &[&str]) -> Self {
+        self.deploy_item.session_arg_names = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /// Names the payment args by position, an empty name leaving an arg unnamed.
+    pub fn with_payment_arg_names(mut self, names: &[&str]) -> Self {
+        self.deploy_item.payment_arg_names = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
     pub fn with_authorization_keys<T: Clone + Into<PublicKey>>(
         mut self,
         authorization_keys: &[T],
@@ -154,8 +240,15 @@ impl DeployItemBuilder {
         self
     }
 
-    pub fn with_deploy_hash(mut self, hash: [u8; 32]) -> Self {
-        self.deploy_item.deploy_hash = hash;
+    pub fn with_deploy_hash(mut self, hash: impl Into<DeployHash>) -> Self {
+        self.deploy_item.deploy_hash = hash.into();
+        self
+    }
+
+    /// Marks the deploy as a system deploy.  Its payment code may be left unset, as it is never run.
+    /// The address defaults to the system account already.
+    pub fn as_system_deploy(mut self) -> Self {
+        self.deploy_item.system_deploy = true;
         self
     }
 
@@ -166,10 +259,20 @@ impl DeployItemBuilder {
                 .address
                 .unwrap_or_else(|| PublicKey::ed25519_from([0u8; 32])),
             session: self.deploy_item.session_code.unwrap(),
-            payment: self.deploy_item.payment_code.unwrap(),
+            payment: match self.deploy_item.payment_code {
+                Some(payment_code) => payment_code,
+                None if self.deploy_item.system_deploy => ExecutableDeployItem::ModuleBytes {
+                    module_bytes: vec![],
+                    args: Self::serialize_args(()),
+                },
+                None => panic!("should set payment code"),
+            },
             gas_price: self.deploy_item.gas_price,
             authorization_keys: self.deploy_item.authorization_keys,
             deploy_hash: self.deploy_item.deploy_hash,
+            system_deploy: self.deploy_item.system_deploy,
+            session_arg_names: self.deploy_item.session_arg_names,
+            payment_arg_names: self.deploy_item.payment_arg_names,
         }
     }
 
@@ -0,0 +1,228 @@
+//! Support for detecting changes to the results of executing a fixed set of "canary" contracts.
+//!
+//! The outcome of each canary, i.e. the post-state hash and the cost of its execution, is recorded
+//! in a checked-in [`DeterminismManifest`].  Any change to an outcome is reported as a
+//! [`ManifestDiff`].  If such a change is intended, e.g. because of a new protocol version, the
+//! manifest can be regenerated by setting the environment variable named by
+//! [`REGENERATE_MANIFEST_ENV_VAR`] when running the checks.
+
+use std::{
+    collections::BTreeMap,
+    env,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+};
+
+use engine_core::{engine_state::EngineState, execution};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_shared::gas::Gas;
+use engine_storage::global_state::StateProvider;
+use types::U512;
+
+use crate::internal::{utils, WasmTestBuilder};
+
+/// If this environment variable is set, [`DeterminismManifest::check_against_file`] overwrites the
+/// manifest rather than comparing against it.
+pub const REGENERATE_MANIFEST_ENV_VAR: &str = "CL_REGENERATE_DETERMINISM_MANIFEST";
+
+const COMMENT_PREFIX: char = '#';
+const HEADER: &str = "\
+# Outcomes of the determinism canaries: <canary name> <post-state hash> <cost>
+#
+# Generated file.  To regenerate after an intended change, run the determinism tests with
+# CL_REGENERATE_DETERMINISM_MANIFEST=1 set.
+";
+
+/// The outcome of executing a single canary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CanaryOutcome {
+    pub post_state_hash: Vec<u8>,
+    pub cost: Gas,
+}
+
+impl CanaryOutcome {
+    /// Returns the outcome of the last deploy executed by `builder`, and its current post-state
+    /// hash.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `builder` hasn't executed any deploys.
+    pub fn from_builder<S>(builder: &WasmTestBuilder<S>) -> Self
+    where
+        S: StateProvider,
+        S::Error: Into<execution::Error>,
+        EngineState<S>: ExecutionEngineService,
+    {
+        let exec_response = builder
+            .get_exec_responses()
+            .last()
+            .expect("should have executed a deploy");
+        let cost = utils::get_exec_costs(exec_response)
+            .into_iter()
+            .fold(Gas::default(), |total, cost| total + cost);
+        CanaryOutcome {
+            post_state_hash: builder.get_post_state_hash(),
+            cost,
+        }
+    }
+}
+
+impl Display for CanaryOutcome {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        for byte in &self.post_state_hash {
+            write!(formatter, "{:02x}", byte)?;
+        }
+        write!(formatter, " {}", self.cost.value())
+    }
+}
+
+/// The recorded outcomes of a set of canaries, keyed by canary name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeterminismManifest {
+    outcomes: BTreeMap<String, CanaryOutcome>,
+}
+
+impl DeterminismManifest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `outcome` for the canary `name`, replacing any previously recorded one.
+    pub fn insert<T: Into<String>>(&mut self, name: T, outcome: CanaryOutcome) {
+        self.outcomes.insert(name.into(), outcome);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&CanaryOutcome> {
+        self.outcomes.get(name)
+    }
+
+    /// Parses a manifest in the format written by [`DeterminismManifest::to_string`].
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut manifest = DeterminismManifest::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(COMMENT_PREFIX) {
+                continue;
+            }
+            let invalid_line = |reason: &str| format!("line {}: {}: {}", index + 1, reason, line);
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let (name, hash, cost) = match fields.as_slice() {
+                [name, hash, cost] => (name, hash, cost),
+                _ => return Err(invalid_line("expected three fields")),
+            };
+            let post_state_hash = parse_hex(hash).ok_or_else(|| invalid_line("invalid hash"))?;
+            let cost = U512::from_dec_str(cost).map_err(|_| invalid_line("invalid cost"))?;
+            manifest.insert(
+                *name,
+                CanaryOutcome {
+                    post_state_hash,
+                    cost: Gas::new(cost),
+                },
+            );
+        }
+        Ok(manifest)
+    }
+
+    pub fn read_from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::parse(&text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Returns the differences between `self`, the expected outcomes, and `actual`.
+    pub fn diff(&self, actual: &DeterminismManifest) -> ManifestDiff {
+        let mut lines = Vec::new();
+        for (name, expected_outcome) in &self.outcomes {
+            match actual.get(name) {
+                Some(actual_outcome) if actual_outcome == expected_outcome => {}
+                Some(actual_outcome) => {
+                    lines.push(format!("- {} {}", name, expected_outcome));
+                    lines.push(format!("+ {} {}", name, actual_outcome));
+                }
+                None => lines.push(format!("- {} {}", name, expected_outcome)),
+            }
+        }
+        for (name, actual_outcome) in &actual.outcomes {
+            if !self.outcomes.contains_key(name) {
+                lines.push(format!("+ {} {}", name, actual_outcome));
+            }
+        }
+        ManifestDiff { lines }
+    }
+
+    /// Compares `self`, the actual outcomes, against the manifest stored at `path`.
+    ///
+    /// If the environment variable named by [`REGENERATE_MANIFEST_ENV_VAR`] is set, the stored
+    /// manifest is overwritten with `self` instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a readable diff if the outcomes differ, or if the stored manifest can't be read.
+    pub fn check_against_file<P: AsRef<Path>>(&self, path: P) {
+        let path = path.as_ref();
+        if env::var_os(REGENERATE_MANIFEST_ENV_VAR).is_some() {
+            self.write_to_file(path)
+                .unwrap_or_else(|error| panic!("should write {}: {}", path.display(), error));
+            return;
+        }
+
+        let expected = DeterminismManifest::read_from_file(path)
+            .unwrap_or_else(|error| panic!("should read {}: {}", path.display(), error));
+        let diff = expected.diff(self);
+        if !diff.is_empty() {
+            panic!(
+                "canary outcomes differ from {}:\n{}\nIf this change is intended, rerun with {}=1 \
+                 set to regenerate the manifest.",
+                path.display(),
+                diff,
+                REGENERATE_MANIFEST_ENV_VAR
+            );
+        }
+    }
+}
+
+impl Display for DeterminismManifest {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}", HEADER)?;
+        for (name, outcome) in &self.outcomes {
+            writeln!(formatter, "{} {}", name, outcome)?;
+        }
+        Ok(())
+    }
+}
+
+/// The differences between two manifests, one line per changed canary outcome, prefixed with "-"
+/// for the expected outcome and "+" for the actual one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ManifestDiff {
+    lines: Vec<String>,
+}
+
+impl ManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+}
+
+impl Display for ManifestDiff {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        for line in &self.lines {
+            writeln!(formatter, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
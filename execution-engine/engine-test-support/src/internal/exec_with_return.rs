@@ -11,7 +11,7 @@ use engine_core::{
     runtime_context::RuntimeContext,
 };
 use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
-use engine_shared::{gas::Gas, newtypes::CorrelationId};
+use engine_shared::{account::AccountConfig, gas::Gas, newtypes::CorrelationId};
 use engine_storage::{global_state::StateProvider, protocol_data::ProtocolData};
 use engine_wasm_prep::Preprocessor;
 use types::{
@@ -27,16 +27,16 @@ const INIT_FN_STORE_ID: u32 = 0;
 /// output. It is essentially the same functionality as `Executor::exec`, but the return value of
 /// the contract is returned along with the effects. The purpose of this function is to test
 /// installer contracts used in the new genesis process.
-#[allow(clippy::too_many_arguments)]
+///
+/// The contract is granted access to the urefs held by `args`, as a called contract would be.
 pub fn exec<S, T>(
     config: EngineConfig,
     builder: &mut WasmTestBuilder<S>,
     address: PublicKey,
     wasm_file: &str,
-    block_time: u64,
+    block_time: BlockTime,
     deploy_hash: [u8; 32],
     args: impl ArgsParser,
-    extra_urefs: Vec<URef>,
 ) -> Option<(T, Vec<URef>, ExecutionEffect)>
 where
     S: StateProvider,
@@ -76,7 +76,10 @@ where
 
     let access_rights = {
         let mut ret = runtime::extract_access_rights_from_keys(named_keys.values().cloned());
-        let extras = runtime::extract_access_rights_from_urefs(extra_urefs.into_iter());
+        let extra_urefs = arguments
+            .iter()
+            .flat_map(|arg| runtime::scan_urefs(arg).expect("should scan args").urefs);
+        let extras = runtime::extract_access_rights_from_urefs(extra_urefs);
         ret.extend(extras.into_iter());
         ret
     };
@@ -85,7 +88,13 @@ where
         let mint = builder.get_mint_contract_uref();
         let pos = builder.get_mint_contract_uref();
         let standard_payment = builder.get_standard_payment_contract_uref();
-        ProtocolData::new(*DEFAULT_WASM_COSTS, mint, pos, standard_payment)
+        ProtocolData::new(
+            *DEFAULT_WASM_COSTS,
+            mint,
+            pos,
+            standard_payment,
+            AccountConfig::default(),
+        )
     };
 
     let context = RuntimeContext::new(
@@ -96,7 +105,7 @@ where
         BTreeSet::new(),
         &account,
         base_key,
-        BlockTime::new(block_time),
+        block_time,
         deploy_hash,
         gas_limit,
         gas_counter,
@@ -3,8 +3,11 @@ use std::convert::TryInto;
 use rand::Rng;
 
 use contract::args_parser::ArgsParser;
-use engine_core::engine_state::{deploy_item::DeployItem, execute_request::ExecuteRequest};
-use types::{account::PublicKey, ProtocolVersion};
+use engine_core::engine_state::{
+    deploy_item::DeployItem, execute_request::ExecuteRequest, SYSTEM_ACCOUNT_ADDR,
+};
+use engine_shared::gas::Gas;
+use types::{account::PublicKey, BlockTime, ContractHash, ProtocolVersion};
 
 use crate::internal::{DeployItemBuilder, DEFAULT_BLOCK_TIME, DEFAULT_PAYMENT};
 
@@ -31,7 +34,7 @@ impl ExecuteRequestBuilder {
         self
     }
 
-    pub fn with_block_time(mut self, block_time: u64) -> Self {
+    pub fn with_block_time(mut self, block_time: BlockTime) -> Self {
         self.execute_request.block_time = block_time;
         self
     }
@@ -41,6 +44,16 @@ impl ExecuteRequestBuilder {
         self
     }
 
+    pub fn with_block_gas_limit(mut self, block_gas_limit: Gas) -> Self {
+        self.execute_request.block_gas_limit = Some(block_gas_limit);
+        self
+    }
+
+    pub fn with_collect_stats(mut self, collect_stats: bool) -> Self {
+        self.execute_request.collect_stats = collect_stats;
+        self
+    }
+
     pub fn build(self) -> ExecuteRequest {
         self.execute_request
     }
@@ -64,9 +77,29 @@ impl ExecuteRequestBuilder {
         ExecuteRequestBuilder::new().push_deploy(deploy)
     }
 
+    /// A request with a single system deploy, run from the system account with no payment.
+    pub fn system(session_file: &str, session_args: impl ArgsParser) -> Self {
+        let mut rng = rand::thread_rng();
+        let deploy_hash: [u8; 32] = rng.gen();
+
+        let deploy = DeployItemBuilder::new()
+            .with_address(SYSTEM_ACCOUNT_ADDR)
+            .with_session_code(session_file, session_args)
+            .with_deploy_hash(deploy_hash)
+            .as_system_deploy()
+            .build();
+
+        ExecuteRequestBuilder::new().push_deploy(deploy)
+    }
+
+    /// A request for an empty block, i.e. one with no deploys, on top of `parent_hash`.
+    pub fn empty_block(parent_hash: &[u8]) -> Self {
+        ExecuteRequestBuilder::new().with_pre_state_hash(parent_hash)
+    }
+
     pub fn contract_call_by_hash(
         sender: PublicKey,
-        contract_hash: [u8; 32],
+        contract_hash: impl Into<ContractHash>,
         args: impl ArgsParser,
     ) -> Self {
         let mut rng = rand::thread_rng();
@@ -74,7 +107,7 @@ impl ExecuteRequestBuilder {
 
         let deploy = DeployItemBuilder::new()
             .with_address(sender)
-            .with_stored_session_hash(contract_hash.to_vec(), args)
+            .with_stored_session_hash(contract_hash, args)
             .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
             .with_authorization_keys(&[sender])
             .with_deploy_hash(deploy_hash)
@@ -1,5 +1,8 @@
 mod additive_map_diff;
+pub mod compare;
+pub mod contract_build;
 mod deploy_item_builder;
+pub mod determinism;
 pub mod exec_with_return;
 mod execute_request_builder;
 mod upgrade_request_builder;
@@ -13,9 +16,9 @@ use engine_core::engine_state::{
     genesis::{ExecConfig, GenesisAccount, GenesisConfig},
     run_genesis_request::RunGenesisRequest,
 };
-use engine_shared::{motes::Motes, newtypes::Blake2bHash, test_utils};
+use engine_shared::{account::AccountConfig, motes::Motes, newtypes::Blake2bHash, test_utils};
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::{account::PublicKey, ProtocolVersion, U512};
+use types::{account::PublicKey, BlockTime, ProtocolVersion, U512};
 
 use super::{DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE};
 pub use additive_map_diff::AdditiveMapDiff;
@@ -33,9 +36,12 @@ pub const STANDARD_PAYMENT_CONTRACT: &str = "standard_payment.wasm";
 
 pub const DEFAULT_CHAIN_NAME: &str = "gerald";
 pub const DEFAULT_GENESIS_TIMESTAMP: u64 = 0;
-pub const DEFAULT_BLOCK_TIME: u64 = 0;
+pub const DEFAULT_BLOCK_TIME: BlockTime = BlockTime::from_millis(DEFAULT_GENESIS_TIMESTAMP);
 pub const MOCKED_ACCOUNT_ADDRESS: PublicKey = PublicKey::ed25519_from([48u8; 32]);
 
+#[deprecated(
+    note = "use `DEFAULT_ACCOUNT_ADDR` from the crate root; this will be removed in the next release"
+)]
 pub const DEFAULT_ACCOUNT_KEY: PublicKey = DEFAULT_ACCOUNT_ADDR;
 
 lazy_static! {
@@ -62,13 +68,16 @@ lazy_static! {
         standard_payment_installer_bytes =
             utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
 
-        ExecConfig::new(
+        let mut exec_config = ExecConfig::new(
             mint_installer_bytes,
             pos_installer_bytes,
             standard_payment_installer_bytes,
             DEFAULT_ACCOUNTS.clone(),
             *DEFAULT_WASM_COSTS,
-        )
+            AccountConfig::default(),
+        );
+        exec_config.set_genesis_timestamp(BlockTime::from_millis(DEFAULT_GENESIS_TIMESTAMP));
+        exec_config
     };
     pub static ref DEFAULT_GENESIS_CONFIG: GenesisConfig = {
         GenesisConfig::new(
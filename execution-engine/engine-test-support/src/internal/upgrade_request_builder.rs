@@ -1,10 +1,11 @@
 use engine_grpc_server::engine_server::{
     ipc::{
-        ChainSpec_ActivationPoint, ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint,
-        DeployCode, UpgradeRequest,
+        ChainSpec_AccountConfig, ChainSpec_ActivationPoint, ChainSpec_AuctionConfig,
+        ChainSpec_CostTable_WasmCosts, ChainSpec_UpgradePoint, DeployCode, UpgradeRequest,
     },
     state,
 };
+use engine_shared::account::AccountConfig;
 use engine_wasm_prep::wasm_costs::WasmCosts;
 use types::ProtocolVersion;
 
@@ -15,6 +16,10 @@ pub struct UpgradeRequestBuilder {
     upgrade_installer: DeployCode,
     new_costs: Option<ChainSpec_CostTable_WasmCosts>,
     activation_point: ChainSpec_ActivationPoint,
+    new_mint_code: Vec<u8>,
+    new_pos_code: Vec<u8>,
+    new_account_config: Option<ChainSpec_AccountConfig>,
+    new_auction_config: Option<ChainSpec_AuctionConfig>,
 }
 
 impl UpgradeRequestBuilder {
@@ -54,10 +59,52 @@ impl UpgradeRequestBuilder {
         new_costs.set_max_stack_height(wasm_costs.max_stack_height);
         new_costs.set_mem(wasm_costs.mem);
         new_costs.set_memcpy(wasm_costs.memcpy);
+        new_costs.set_transient_storage(wasm_costs.transient_storage);
+        new_costs.set_max_transient_storage_size(wasm_costs.max_transient_storage_size);
+        new_costs.set_max_transforms_per_deploy(wasm_costs.max_transforms_per_deploy);
+        new_costs.set_max_effect_bytes_per_deploy(wasm_costs.max_effect_bytes_per_deploy);
+        new_costs.set_max_session_return_value_bytes(wasm_costs.max_session_return_value_bytes);
+        new_costs.set_string_byte(wasm_costs.string_byte);
+        new_costs.set_max_string_length(wasm_costs.max_string_length);
+        new_costs.set_max_revert_data_bytes(wasm_costs.max_revert_data_bytes);
+        new_costs.set_base_deploy_cost(wasm_costs.base_deploy_cost);
+        new_costs.set_rejected_deploy_cost(wasm_costs.rejected_deploy_cost);
+        new_costs.set_strict_session_exports(wasm_costs.strict_session_exports);
         self.new_costs = Some(new_costs);
         self
     }
 
+    pub fn with_new_mint_code(mut self, new_mint_code: Vec<u8>) -> Self {
+        self.new_mint_code = new_mint_code;
+        self
+    }
+
+    pub fn with_new_pos_code(mut self, new_pos_code: Vec<u8>) -> Self {
+        self.new_pos_code = new_pos_code;
+        self
+    }
+
+    pub fn with_new_account_config(mut self, account_config: AccountConfig) -> Self {
+        self.new_account_config = Some(account_config.into());
+        self
+    }
+
+    /// Sets the configuration of the auction contract.  `installer_bytes` should be empty unless
+    /// the upgrade installs the auction contract.
+    pub fn with_new_auction_config(
+        mut self,
+        installer_bytes: Vec<u8>,
+        validator_slots: u32,
+        use_auction: bool,
+    ) -> Self {
+        let mut new_auction_config = ChainSpec_AuctionConfig::new();
+        new_auction_config.set_installer(installer_bytes);
+        new_auction_config.set_validator_slots(validator_slots);
+        new_auction_config.set_use_auction(use_auction);
+        self.new_auction_config = Some(new_auction_config);
+        self
+    }
+
     pub fn with_activation_point(mut self, rank: u64) -> Self {
         self.activation_point = {
             let mut ret = ChainSpec_ActivationPoint::new();
@@ -81,6 +128,14 @@ impl UpgradeRequestBuilder {
         }
         upgrade_point.set_protocol_version(self.new_protocol_version);
         upgrade_point.set_upgrade_installer(self.upgrade_installer);
+        upgrade_point.set_new_mint_code(self.new_mint_code);
+        upgrade_point.set_new_pos_code(self.new_pos_code);
+        if let Some(new_account_config) = self.new_account_config {
+            upgrade_point.set_new_account_config(new_account_config);
+        }
+        if let Some(new_auction_config) = self.new_auction_config {
+            upgrade_point.set_new_auction_config(new_auction_config);
+        }
 
         let mut upgrade_request = UpgradeRequest::new();
         upgrade_request.set_protocol_version(self.current_protocol_version);
@@ -98,6 +153,10 @@ impl Default for UpgradeRequestBuilder {
             upgrade_installer: Default::default(),
             new_costs: None,
             activation_point: Default::default(),
+            new_mint_code: Default::default(),
+            new_pos_code: Default::default(),
+            new_account_config: None,
+            new_auction_config: None,
         }
     }
 }
@@ -2,6 +2,7 @@ use std::{
     env, fs,
     path::{Path, PathBuf},
     rc::Rc,
+    time::UNIX_EPOCH,
 };
 
 use lazy_static::lazy_static;
@@ -13,13 +14,17 @@ use engine_core::engine_state::{
     Error,
 };
 use engine_shared::{
-    account::Account, additive_map::AdditiveMap, gas::Gas, stored_value::StoredValue,
+    account::{Account, AccountConfig},
+    additive_map::AdditiveMap,
+    gas::Gas,
+    newtypes::Blake2bHash,
+    stored_value::StoredValue,
     transform::Transform,
 };
 use types::Key;
 
 use crate::internal::{
-    DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP,
+    contract_build, DEFAULT_CHAIN_NAME, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_GENESIS_TIMESTAMP,
     DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT, POS_INSTALL_CONTRACT,
     STANDARD_PAYMENT_INSTALL_CONTRACT,
 };
@@ -73,37 +78,101 @@ fn get_compiled_wasm_paths() -> Vec<PathBuf> {
     ret
 }
 
-/// Reads a given compiled contract file based on path
-pub fn read_wasm_file_bytes<T: AsRef<Path>>(contract_file: T) -> Vec<u8> {
+/// Returns the path of a given compiled contract file.
+///
+/// If building contracts on demand is enabled (see [`contract_build`]) and `contract_file` names
+/// the Wasm of a contract crate in this repo, e.g. `do_nothing.wasm`, the contract is built from
+/// its current sources.  Otherwise the file is looked for in the directories of prebuilt Wasm,
+/// then as a path in its own right.  Panics if it isn't found, listing the locations tried and how
+/// to build it.
+pub fn contract_path<T: AsRef<Path>>(contract_file: T) -> PathBuf {
+    let contract_file = contract_file.as_ref();
+    let maybe_contract_crate = contract_build::find_contract_crate(contract_file);
+    if let Some(contract_crate) = &maybe_contract_crate {
+        if contract_build::is_enabled() {
+            return contract_build::build_contract(contract_crate);
+        }
+    }
+
     let mut attempted_paths = vec![];
 
-    if contract_file.as_ref().is_relative() {
+    if contract_file.is_relative() {
         // Find first path to a given file found in a list of paths
         for wasm_path in WASM_PATHS.iter() {
-            let mut filename = wasm_path.clone();
-            filename.push(contract_file.as_ref());
-            if let Ok(wasm_bytes) = fs::read(&filename) {
-                return wasm_bytes;
+            let filename = wasm_path.join(contract_file);
+            if filename.is_file() {
+                if let Some(contract_crate) = &maybe_contract_crate {
+                    contract_build::warn_if_stale(contract_crate, &filename);
+                }
+                return filename;
             }
             attempted_paths.push(filename);
         }
     }
     // Try just opening in case the arg is a valid path relative to current working dir, or is a
     // valid absolute path.
-    if let Ok(wasm_bytes) = fs::read(contract_file.as_ref()) {
-        return wasm_bytes;
+    if contract_file.is_file() {
+        return contract_file.to_owned();
     }
-    attempted_paths.push(contract_file.as_ref().to_owned());
+    attempted_paths.push(contract_file.to_owned());
 
-    let mut error_msg =
-        "\nFailed to open compiled Wasm file.  Tried the following locations:\n".to_string();
+    let mut error_msg = format!(
+        "\nFailed to find compiled Wasm file {}.  Tried the following locations:\n",
+        contract_file.display()
+    );
     for attempted_path in attempted_paths {
         error_msg = format!("{}    - {}\n", error_msg, attempted_path.display());
     }
+    if let Some(contract_crate) = maybe_contract_crate {
+        error_msg = format!(
+            "{}\nBuild it with:\n    {}\nor set {}=1 to build contracts on demand.\n",
+            error_msg,
+            contract_crate.build_command(),
+            contract_build::BUILD_CONTRACTS_ENV_VAR
+        );
+    }
 
     panic!("{}\n", error_msg);
 }
 
+/// Reads a given compiled contract file based on path, see [`contract_path`].
+pub fn read_wasm_file_bytes<T: AsRef<Path>>(contract_file: T) -> Vec<u8> {
+    let path = contract_path(contract_file);
+    fs::read(&path).unwrap_or_else(|error| panic!("should read {}: {}", path.display(), error))
+}
+
+/// Returns a key identifying what a fixture setup depends on, for use with
+/// [`InMemoryWasmTestBuilder::from_cached_fixture`](crate::internal::InMemoryWasmTestBuilder::from_cached_fixture).
+///
+/// It covers the running test binary, which is rebuilt whenever the engine or the setup code
+/// changes, the genesis install contracts, and the given contract files.
+pub fn fixture_source_key<T: AsRef<Path>>(contract_files: &[T]) -> Vec<u8> {
+    let exe_path = env::current_exe().expect("should get path of test binary");
+    let exe_metadata = fs::metadata(&exe_path)
+        .unwrap_or_else(|error| panic!("should read {}: {}", exe_path.display(), error));
+    let exe_modified = exe_metadata
+        .modified()
+        .ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|since_epoch| since_epoch.as_nanos())
+        .unwrap_or_default();
+
+    let mut bytes = exe_path.to_string_lossy().into_owned().into_bytes();
+    bytes.extend_from_slice(&exe_metadata.len().to_le_bytes());
+    bytes.extend_from_slice(&exe_modified.to_le_bytes());
+    for install_contract in &[
+        MINT_INSTALL_CONTRACT,
+        POS_INSTALL_CONTRACT,
+        STANDARD_PAYMENT_INSTALL_CONTRACT,
+    ] {
+        bytes.append(&mut read_wasm_file_bytes(install_contract));
+    }
+    for contract_file in contract_files {
+        bytes.append(&mut read_wasm_file_bytes(contract_file));
+    }
+    Blake2bHash::new(&bytes).to_vec()
+}
+
 pub fn create_exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
     let mint_installer_bytes = read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
     let proof_of_stake_installer_bytes = read_wasm_file_bytes(POS_INSTALL_CONTRACT);
@@ -115,6 +184,7 @@ pub fn create_exec_config(accounts: Vec<GenesisAccount>) -> ExecConfig {
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
+        AccountConfig::default(),
     )
 }
 
@@ -1,57 +1,73 @@
 use std::{
-    collections::HashMap,
     convert::{TryFrom, TryInto},
     ffi::OsStr,
     fs,
-    path::PathBuf,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
 };
 
 use grpc::RequestOptions;
 use lmdb::DatabaseFlags;
-use log::LevelFilter;
 
 use engine_core::{
     engine_state::{
-        execute_request::ExecuteRequest, execution_result::ExecutionResult,
-        run_genesis_request::RunGenesisRequest, EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
+        self,
+        balance::BalanceError,
+        execute_request::ExecuteRequest,
+        execution_result::ExecutionResult,
+        execution_stats::{ExecutionStats, RequestStats},
+        exit_code::ExitCode,
+        genesis::{GenesisAccountReport, GenesisBondingReport},
+        query::AUTH_PROJECTION,
+        run_genesis_request::RunGenesisRequest,
+        step::{StepRequest, StepResult},
+        EngineConfig, EngineState, SYSTEM_ACCOUNT_ADDR,
     },
-    execution,
+    execution::{self, HostCallStats},
 };
 use engine_grpc_server::engine_server::{
     ipc::{
-        CommitRequest, CommitResponse, GenesisResponse, QueryRequest, UpgradeRequest,
-        UpgradeResponse,
+        BatchCommitRequest, BatchCommitRequest_Entry, BatchCommitResponse, CommitRequest,
+        CommitResponse, GenesisResponse, GetValidatorWeightsRequest, MultiRootQueryRequest,
+        QueryRequest, UpgradeRequest, UpgradeResponse,
     },
     ipc_grpc::ExecutionEngineService,
     mappings::{MappingError, TransformMap},
     transforms::TransformEntry,
 };
 use engine_shared::{
-    account::Account,
+    account::{Account, AccountAuthInfo},
     additive_map::AdditiveMap,
     contract::Contract,
     gas::Gas,
-    logging::{self, Settings, Style},
+    motes::Motes,
     newtypes::{Blake2bHash, CorrelationId},
     os::get_page_size,
     stored_value::StoredValue,
     transform::Transform,
 };
 use engine_storage::{
-    global_state::{in_memory::InMemoryGlobalState, lmdb::LmdbGlobalState, StateProvider},
+    commit_sequence_store::lmdb::LmdbCommitSequenceStore,
+    global_state::{
+        in_memory::{FixtureError, InMemoryGlobalState},
+        lmdb::LmdbGlobalState,
+        read_stats::ReadStats,
+        StateProvider,
+    },
     protocol_data_store::lmdb::LmdbProtocolDataStore,
+    root_metadata_store::lmdb::LmdbRootMetadataStore,
+    staging_store::lmdb::LmdbStagingStore,
     transaction_source::lmdb::LmdbEnvironment,
     trie_store::lmdb::LmdbTrieStore,
 };
 use types::{
     account::PublicKey,
-    bytesrepr::{self, ToBytes},
-    CLValue, Key, URef, U512,
+    bytesrepr::{self, FromBytes, ToBytes},
+    BlockTime, CLValue, Key, ProtocolVersion, URef, U512,
 };
 
-use crate::internal::utils;
+use crate::internal::{utils, DEFAULT_PROTOCOL_VERSION};
 
 /// LMDB initial map size is calculated based on DEFAULT_LMDB_PAGES and systems page size.
 ///
@@ -62,6 +78,13 @@ const DEFAULT_LMDB_PAGES: usize = 128_000;
 /// the behavior of `get_data_dir()` in "engine-grpc-server/src/main.rs".
 const GLOBAL_STATE_DIR: &str = "global_state";
 
+/// Identifies the bytes of a fixture written by [`InMemoryWasmTestBuilder::save_fixture`].
+const FIXTURE_MAGIC: &[u8] = b"EE-BUILDER-FIXTURE";
+
+/// The version of the format of the builder's own fields in a fixture.  The global state within
+/// it is versioned separately.
+const FIXTURE_FORMAT_VERSION: u32 = 1;
+
 pub type InMemoryWasmTestBuilder = WasmTestBuilder<InMemoryGlobalState>;
 pub type LmdbWasmTestBuilder = WasmTestBuilder<LmdbGlobalState>;
 
@@ -71,29 +94,50 @@ pub struct WasmTestBuilder<S> {
     engine_state: Rc<EngineState<S>>,
     /// [`ExecutionResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
     exec_responses: Vec<Vec<Rc<ExecutionResult>>>,
+    /// Correlation ids used by subsequent exec calls i.e. `exec_correlation_ids[0]` is for first
+    /// exec call etc.
+    exec_correlation_ids: Vec<CorrelationId>,
+    /// Host function calls and global state reads made by the deploys of subsequent exec calls,
+    /// if their requests collected stats
+    execution_stats: Vec<Vec<Option<ExecutionStats>>>,
+    /// Summaries of subsequent exec calls, if their requests collected stats
+    request_stats: Vec<Option<RequestStats>>,
     upgrade_responses: Vec<UpgradeResponse>,
+    /// [`StepResult`] is wrapped in [`Rc`] to work around a missing [`Clone`] implementation
+    step_results: Vec<Rc<StepResult>>,
     genesis_hash: Option<Vec<u8>>,
     post_state_hash: Option<Vec<u8>>,
+    /// Post-state hashes of the commits made by the builder, in order.
+    post_state_hashes: Vec<Vec<u8>>,
     /// Cached transform maps after subsequent successful runs i.e. `transforms[0]` is for first
     /// exec call etc.
     transforms: Vec<AdditiveMap<Key, Transform>>,
-    bonded_validators: Vec<HashMap<PublicKey, U512>>,
+    /// The bonded validators returned by the commits made by the builder, in order, each in the
+    /// canonical order in which they were received.
+    bonded_validators: Vec<Vec<(PublicKey, U512)>>,
     /// Cached genesis transforms
     genesis_account: Option<Account>,
     /// Genesis transforms
     genesis_transforms: Option<AdditiveMap<Key, Transform>>,
+    /// Reports of the accounts created by genesis
+    genesis_account_reports: Option<Vec<GenesisAccountReport>>,
+    /// Reports of bonding the genesis validators
+    genesis_bonding_reports: Option<Vec<GenesisBondingReport>>,
     /// Mint contract uref
     mint_contract_uref: Option<URef>,
     /// PoS contract uref
     pos_contract_uref: Option<URef>,
     /// Standard payment contract uref
     standard_payment_uref: Option<URef>,
+    /// Every how many commits the mint's invariant is checked, if at all
+    mint_invariant_checks: Option<u64>,
+    /// The number of commits made by the builder
+    commit_count: u64,
 }
 
 impl<S> WasmTestBuilder<S> {
     fn initialize_logging() {
-        let log_settings = Settings::new(LevelFilter::Warn).with_style(Style::HumanReadable);
-        let _ = logging::initialize(log_settings);
+        crate::logging::initialize();
     }
 }
 
@@ -110,16 +154,25 @@ impl Default for InMemoryWasmTestBuilder {
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_correlation_ids: Vec::new(),
+            execution_stats: Vec::new(),
+            request_stats: Vec::new(),
             upgrade_responses: Vec::new(),
+            step_results: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
+            post_state_hashes: Vec::new(),
             transforms: Vec::new(),
             bonded_validators: Vec::new(),
             genesis_account: None,
             genesis_transforms: None,
+            genesis_account_reports: None,
+            genesis_bonding_reports: None,
             mint_contract_uref: None,
             pos_contract_uref: None,
             standard_payment_uref: None,
+            mint_invariant_checks: None,
+            commit_count: 0,
         }
     }
 }
@@ -131,16 +184,25 @@ impl<S> Clone for WasmTestBuilder<S> {
         WasmTestBuilder {
             engine_state: Rc::clone(&self.engine_state),
             exec_responses: self.exec_responses.clone(),
+            exec_correlation_ids: self.exec_correlation_ids.clone(),
+            execution_stats: self.execution_stats.clone(),
+            request_stats: self.request_stats.clone(),
             upgrade_responses: self.upgrade_responses.clone(),
+            step_results: self.step_results.clone(),
             genesis_hash: self.genesis_hash.clone(),
             post_state_hash: self.post_state_hash.clone(),
+            post_state_hashes: self.post_state_hashes.clone(),
             transforms: self.transforms.clone(),
             bonded_validators: self.bonded_validators.clone(),
             genesis_account: self.genesis_account.clone(),
             genesis_transforms: self.genesis_transforms.clone(),
+            genesis_account_reports: self.genesis_account_reports.clone(),
+            genesis_bonding_reports: self.genesis_bonding_reports.clone(),
             mint_contract_uref: self.mint_contract_uref,
             pos_contract_uref: self.pos_contract_uref,
             standard_payment_uref: self.standard_payment_uref,
+            mint_invariant_checks: self.mint_invariant_checks,
+            commit_count: self.commit_count,
         }
     }
 }
@@ -173,6 +235,122 @@ impl InMemoryWasmTestBuilder {
     }
 }
 
+impl InMemoryWasmTestBuilder {
+    /// Saves the global state of the builder to `path`, along with the details it learned from
+    /// genesis, so tests can start from it using [`from_fixture_file`](Self::from_fixture_file)
+    /// rather than running the same setup again.
+    ///
+    /// The responses, transforms and stats of the requests run so far aren't saved.
+    pub fn save_fixture<P: AsRef<Path>>(&self, path: P) {
+        self.write_fixture_file(path.as_ref(), &[]);
+    }
+
+    /// Loads a builder saved by [`save_fixture`](Self::save_fixture), using the default engine
+    /// config.
+    ///
+    /// Fails with [`FixtureError::UnsupportedVersion`] if the fixture was saved in an older format,
+    /// in which case it needs regenerating.
+    pub fn from_fixture_file<P: AsRef<Path>>(path: P) -> Result<Self, FixtureError> {
+        Self::read_fixture_file(path.as_ref()).map(|(builder, _source_key)| builder)
+    }
+
+    /// Loads the builder saved at `path` if it was saved with `source_key`.  Otherwise runs
+    /// `setup`, and saves the builder it returns at `path` for the next run.
+    ///
+    /// `source_key` should change whenever anything `setup` depends on does, e.g. as returned by
+    /// [`utils::fixture_source_key`].  Fixtures which fail to load, e.g. as they were saved in an
+    /// older format, are regenerated too.
+    pub fn from_cached_fixture<P, F>(path: P, source_key: &[u8], setup: F) -> Self
+    where
+        P: AsRef<Path>,
+        F: FnOnce() -> Self,
+    {
+        let path = path.as_ref();
+        if path.exists() {
+            if let Ok((builder, saved_source_key)) = Self::read_fixture_file(path) {
+                if saved_source_key == source_key {
+                    return builder;
+                }
+            }
+        }
+        let builder = setup();
+        builder.write_fixture_file(path, source_key);
+        builder
+    }
+
+    fn fixture_bytes(&self, source_key: &[u8]) -> Result<Vec<u8>, bytesrepr::Error> {
+        let global_state = self
+            .engine_state
+            .state()
+            .to_fixture()
+            .expect("should serialize global state");
+        let mut ret = FIXTURE_MAGIC.to_vec();
+        ret.append(&mut FIXTURE_FORMAT_VERSION.to_bytes()?);
+        ret.append(&mut source_key.to_vec().to_bytes()?);
+        ret.append(&mut self.genesis_hash.to_bytes()?);
+        ret.append(&mut self.post_state_hash.to_bytes()?);
+        ret.append(&mut self.genesis_account.to_bytes()?);
+        ret.append(&mut self.mint_contract_uref.to_bytes()?);
+        ret.append(&mut self.pos_contract_uref.to_bytes()?);
+        ret.append(&mut self.standard_payment_uref.to_bytes()?);
+        ret.append(&mut self.bonded_validators.to_bytes()?);
+        ret.append(&mut global_state.to_bytes()?);
+        Ok(ret)
+    }
+
+    fn write_fixture_file(&self, path: &Path, source_key: &[u8]) {
+        let bytes = self
+            .fixture_bytes(source_key)
+            .expect("should serialize fixture");
+        // tests may load the fixture while it's being written, so it's renamed into place
+        let temp_path = path.with_extension(format!("{}.tmp", rand::random::<u64>()));
+        fs::write(&temp_path, bytes)
+            .unwrap_or_else(|error| panic!("should write {}: {}", temp_path.display(), error));
+        fs::rename(&temp_path, path)
+            .unwrap_or_else(|error| panic!("should write {}: {}", path.display(), error));
+    }
+
+    fn read_fixture_file(path: &Path) -> Result<(Self, Vec<u8>), FixtureError> {
+        let bytes = fs::read(path)
+            .unwrap_or_else(|error| panic!("should read {}: {}", path.display(), error));
+        if !bytes.starts_with(FIXTURE_MAGIC) {
+            return Err(FixtureError::NotAFixture);
+        }
+        let (version, rem) = u32::from_bytes(&bytes[FIXTURE_MAGIC.len()..])?;
+        if version != FIXTURE_FORMAT_VERSION {
+            return Err(FixtureError::UnsupportedVersion {
+                found: version,
+                required: FIXTURE_FORMAT_VERSION,
+            });
+        }
+        let (source_key, rem) = Vec::<u8>::from_bytes(rem)?;
+        let (genesis_hash, rem) = Option::<Vec<u8>>::from_bytes(rem)?;
+        let (post_state_hash, rem) = Option::<Vec<u8>>::from_bytes(rem)?;
+        let (genesis_account, rem) = Option::<Account>::from_bytes(rem)?;
+        let (mint_contract_uref, rem) = Option::<URef>::from_bytes(rem)?;
+        let (pos_contract_uref, rem) = Option::<URef>::from_bytes(rem)?;
+        let (standard_payment_uref, rem) = Option::<URef>::from_bytes(rem)?;
+        let (bonded_validators, rem) = Vec::<Vec<(PublicKey, U512)>>::from_bytes(rem)?;
+        let (global_state, rem) = Vec::<u8>::from_bytes(rem)?;
+        if !rem.is_empty() {
+            return Err(bytesrepr::Error::LeftOverBytes.into());
+        }
+        let global_state = InMemoryGlobalState::from_fixture(&global_state)?;
+
+        let mut builder = Self::default();
+        let engine_config = *builder.engine_state.config();
+        builder.engine_state = Rc::new(EngineState::new(global_state, engine_config));
+        builder.genesis_hash = genesis_hash;
+        builder.post_state_hash = post_state_hash;
+        builder.genesis_account = genesis_account;
+        builder.mint_contract_uref = mint_contract_uref;
+        builder.pos_contract_uref = pos_contract_uref;
+        builder.standard_payment_uref = standard_payment_uref;
+        builder.bonded_validators = bonded_validators;
+        Ok((builder, source_key))
+    }
+}
+
 impl LmdbWasmTestBuilder {
     pub fn new_with_config<T: AsRef<OsStr> + ?Sized>(
         data_dir: &T,
@@ -193,22 +371,50 @@ impl LmdbWasmTestBuilder {
             LmdbProtocolDataStore::new(&environment, None, DatabaseFlags::empty())
                 .expect("should create LmdbProtocolDataStore"),
         );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
+        let commit_sequence_store = Arc::new(
+            LmdbCommitSequenceStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbCommitSequenceStore"),
+        );
+        let root_metadata_store = Arc::new(
+            LmdbRootMetadataStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbRootMetadataStore"),
+        );
+        let staging_store = Arc::new(
+            LmdbStagingStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbStagingStore"),
+        );
+        let global_state = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            staging_store,
+        )
+        .expect("should create LmdbGlobalState");
         let engine_state = EngineState::new(global_state, engine_config);
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_correlation_ids: Vec::new(),
+            execution_stats: Vec::new(),
+            request_stats: Vec::new(),
             upgrade_responses: Vec::new(),
+            step_results: Vec::new(),
             genesis_hash: None,
             post_state_hash: None,
+            post_state_hashes: Vec::new(),
             transforms: Vec::new(),
             bonded_validators: Vec::new(),
             genesis_account: None,
             genesis_transforms: None,
+            genesis_account_reports: None,
+            genesis_bonding_reports: None,
             mint_contract_uref: None,
             pos_contract_uref: None,
             standard_payment_uref: None,
+            mint_invariant_checks: None,
+            commit_count: 0,
         }
     }
 
@@ -254,22 +460,50 @@ impl LmdbWasmTestBuilder {
             LmdbProtocolDataStore::open(&environment, None)
                 .expect("should open LmdbProtocolDataStore"),
         );
-        let global_state = LmdbGlobalState::empty(environment, trie_store, protocol_data_store)
-            .expect("should create LmdbGlobalState");
+        let commit_sequence_store = Arc::new(
+            LmdbCommitSequenceStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbCommitSequenceStore"),
+        );
+        let root_metadata_store = Arc::new(
+            LmdbRootMetadataStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbRootMetadataStore"),
+        );
+        let staging_store = Arc::new(
+            LmdbStagingStore::new(&environment, None, DatabaseFlags::empty())
+                .expect("should create LmdbStagingStore"),
+        );
+        let global_state = LmdbGlobalState::empty(
+            environment,
+            trie_store,
+            protocol_data_store,
+            commit_sequence_store,
+            root_metadata_store,
+            staging_store,
+        )
+        .expect("should create LmdbGlobalState");
         let engine_state = EngineState::new(global_state, engine_config);
         WasmTestBuilder {
             engine_state: Rc::new(engine_state),
             exec_responses: Vec::new(),
+            exec_correlation_ids: Vec::new(),
+            execution_stats: Vec::new(),
+            request_stats: Vec::new(),
             upgrade_responses: Vec::new(),
+            step_results: Vec::new(),
             genesis_hash: None,
             post_state_hash: Some(post_state_hash),
+            post_state_hashes: Vec::new(),
             transforms: Vec::new(),
             bonded_validators: Vec::new(),
             genesis_account: None,
             genesis_transforms: None,
+            genesis_account_reports: None,
+            genesis_bonding_reports: None,
             mint_contract_uref: None,
             pos_contract_uref: None,
             standard_payment_uref: None,
+            mint_invariant_checks: None,
+            commit_count: 0,
         }
     }
 
@@ -296,9 +530,14 @@ where
         WasmTestBuilder {
             engine_state: result.0.engine_state,
             exec_responses: Vec::new(),
+            exec_correlation_ids: Vec::new(),
+            execution_stats: Vec::new(),
+            request_stats: Vec::new(),
             upgrade_responses: Vec::new(),
+            step_results: Vec::new(),
             genesis_hash: result.0.genesis_hash,
             post_state_hash: result.0.post_state_hash,
+            post_state_hashes: result.0.post_state_hashes,
             transforms: Vec::new(),
             bonded_validators: result.0.bonded_validators,
             genesis_account: result.0.genesis_account,
@@ -306,9 +545,26 @@ where
             pos_contract_uref: result.0.pos_contract_uref,
             standard_payment_uref: result.0.standard_payment_uref,
             genesis_transforms: result.0.genesis_transforms,
+            genesis_account_reports: result.0.genesis_account_reports,
+            genesis_bonding_reports: result.0.genesis_bonding_reports,
+            mint_invariant_checks: result.0.mint_invariant_checks,
+            commit_count: result.0.commit_count,
         }
     }
 
+    /// Checks after every commit that the mint's purses hold exactly the total supply, panicking
+    /// with the purses changed by the commit if not.
+    pub fn with_mint_invariants(&mut self) -> &mut Self {
+        self.with_sampled_mint_invariants(1)
+    }
+
+    /// Like [`with_mint_invariants`](WasmTestBuilder::with_mint_invariants), but only checks after
+    /// every `every_nth_commit`th commit, as walking all purses of a large state is slow.
+    pub fn with_sampled_mint_invariants(&mut self, every_nth_commit: u64) -> &mut Self {
+        self.mint_invariant_checks = Some(every_nth_commit.max(1));
+        self
+    }
+
     pub fn run_genesis(&mut self, run_genesis_request: &RunGenesisRequest) -> &mut Self {
         let system_account = Key::Account(SYSTEM_ACCOUNT_ADDR);
         let run_genesis_request_proto = run_genesis_request
@@ -327,6 +583,12 @@ where
                 genesis_response.get_failed_deploy().to_owned()
             );
         }
+        if genesis_response.has_store_already_initialized() {
+            panic!(
+                "genesis refused: {:?}",
+                genesis_response.get_store_already_initialized().to_owned()
+            );
+        }
 
         let state_root_hash: Blake2bHash = genesis_response
             .get_success()
@@ -335,6 +597,8 @@ where
             .expect("Unable to get root hash");
 
         let transforms = get_genesis_transforms(&genesis_response);
+        let account_reports = get_genesis_account_reports(&genesis_response);
+        let bonding_reports = get_genesis_bonding_reports(&genesis_response);
 
         let genesis_account =
             utils::get_account(&transforms, &system_account).expect("Unable to get system account");
@@ -352,6 +616,8 @@ where
         self.standard_payment_uref = Some(protocol_data.standard_payment());
         self.genesis_account = Some(genesis_account);
         self.genesis_transforms = Some(transforms);
+        self.genesis_account_reports = Some(account_reports);
+        self.genesis_bonding_reports = Some(bonding_reports);
         self
     }
 
@@ -382,6 +648,51 @@ where
         bytesrepr::deserialize(query_response.take_success()).map_err(|err| format!("{}", err))
     }
 
+    /// Queries `base_key` and `path` at the post-state of each commit made by the builder, in a
+    /// single multi-root query.  Returns one result per commit, in order.
+    pub fn query_at_all_post_states(
+        &self,
+        base_key: Key,
+        path: &[&str],
+    ) -> Vec<Result<StoredValue, String>> {
+        let mut multi_root_query_request = MultiRootQueryRequest::new();
+        multi_root_query_request.set_base_key(base_key.into());
+        let path_vec: Vec<String> = path.iter().map(|s| String::from(*s)).collect();
+        multi_root_query_request.set_path(path_vec.into());
+        multi_root_query_request.set_state_hashes(self.post_state_hashes.clone().into());
+        multi_root_query_request.set_include_contract_bytes(true);
+
+        let mut multi_root_query_response = self
+            .engine_state
+            .multi_root_query(RequestOptions::new(), multi_root_query_request)
+            .wait_drop_metadata()
+            .expect("should get multi-root query response");
+
+        if multi_root_query_response.has_failure() {
+            panic!(
+                "multi-root query failed: {}",
+                multi_root_query_response.take_failure()
+            );
+        }
+
+        multi_root_query_response
+            .take_success()
+            .take_results()
+            .into_iter()
+            .map(|mut result| {
+                if result.has_missing_root() {
+                    return Err(format!("Root not found: {:?}", result.get_missing_root()));
+                }
+                let mut query_response = result.take_query_response();
+                if query_response.has_failure() {
+                    return Err(query_response.take_failure());
+                }
+                bytesrepr::deserialize(query_response.take_success())
+                    .map_err(|err| format!("{}", err))
+            })
+            .collect()
+    }
+
     pub fn exec(&mut self, mut exec_request: ExecuteRequest) -> &mut Self {
         let exec_request = {
             let hash = self
@@ -392,12 +703,19 @@ where
                 hash.as_slice().try_into().expect("expected a valid hash");
             exec_request
         };
-        let exec_response = self
-            .engine_state
-            .run_execute(CorrelationId::new(), exec_request);
-        assert!(exec_response.is_ok());
-        // Parse deploy results
-        let execution_results = exec_response.as_ref().unwrap();
+        let correlation_id = CorrelationId::new();
+        self.exec_correlation_ids.push(correlation_id);
+        let mut execution_results = Vec::new();
+        let mut execution_stats = Vec::new();
+        let exec_response = self.engine_state.run_execute_with(
+            correlation_id,
+            exec_request,
+            |execution_result, stats| {
+                execution_results.push(execution_result);
+                execution_stats.push(stats);
+            },
+        );
+        let request_stats = exec_response.expect("should find the parent state");
         // Cache transformations
         self.transforms.extend(
             execution_results
@@ -405,7 +723,9 @@ where
                 .map(|res| res.effect().transforms.clone()),
         );
         self.exec_responses
-            .push(exec_response.unwrap().into_iter().map(Rc::new).collect());
+            .push(execution_results.into_iter().map(Rc::new).collect());
+        self.execution_stats.push(execution_stats);
+        self.request_stats.push(request_stats);
         self
     }
 
@@ -437,6 +757,20 @@ where
             .expect("Should have commit response")
     }
 
+    /// Sends a raw batch commit request of `(prestate_hash, effects)` entries, which are applied in
+    /// order in a single storage transaction.
+    pub fn batch_commit_transforms(
+        &self,
+        entries: &[(Vec<u8>, AdditiveMap<Key, Transform>)],
+    ) -> BatchCommitResponse {
+        let batch_commit_request = create_batch_commit_request(entries);
+
+        self.engine_state
+            .batch_commit(RequestOptions::new(), batch_commit_request)
+            .wait_drop_metadata()
+            .expect("Should have batch commit response")
+    }
+
     /// Runs a commit request, expects a successful response, and
     /// overwrites existing cached post state hash with a new one.
     pub fn commit_effects(
@@ -444,7 +778,8 @@ where
         prestate_hash: Vec<u8>,
         effects: AdditiveMap<Key, Transform>,
     ) -> &mut Self {
-        let mut commit_response = self.commit_transforms(prestate_hash, effects);
+        let changed_keys: Vec<Key> = effects.keys().cloned().collect();
+        let mut commit_response = self.commit_transforms(prestate_hash.clone(), effects);
         if !commit_response.has_success() {
             panic!(
                 "Expected commit success but received a failure instead: {:?}",
@@ -452,17 +787,49 @@ where
             );
         }
         let mut commit_success = commit_response.take_success();
-        self.post_state_hash = Some(commit_success.take_poststate_hash().to_vec());
+        let post_state_hash = commit_success.take_poststate_hash().to_vec();
+        self.commit_count += 1;
+        if let Some(every_nth_commit) = self.mint_invariant_checks {
+            if self.commit_count % every_nth_commit == 0 {
+                self.assert_mint_invariants(&prestate_hash, &post_state_hash, &changed_keys);
+            }
+        }
+        self.post_state_hashes.push(post_state_hash.clone());
+        self.post_state_hash = Some(post_state_hash);
         let bonded_validators = commit_success
             .take_bonded_validators()
             .into_iter()
             .map(TryInto::try_into)
-            .collect::<Result<HashMap<PublicKey, U512>, MappingError>>()
+            .collect::<Result<Vec<(PublicKey, U512)>, MappingError>>()
             .unwrap();
         self.bonded_validators.push(bonded_validators);
         self
     }
 
+    fn assert_mint_invariants(
+        &self,
+        prestate_hash: &[u8],
+        post_state_hash: &[u8],
+        changed_keys: &[Key],
+    ) {
+        let pre_state_hash: Blake2bHash = prestate_hash.try_into().expect("expected a valid hash");
+        let post_state_hash: Blake2bHash =
+            post_state_hash.try_into().expect("expected a valid hash");
+        let maybe_violation = self
+            .engine_state
+            .check_mint_invariants(
+                CorrelationId::new(),
+                *DEFAULT_PROTOCOL_VERSION,
+                pre_state_hash,
+                post_state_hash,
+                changed_keys,
+            )
+            .expect("should check mint invariants");
+        if let Some(violation) = maybe_violation {
+            panic!("Mint invariant violated: {}", violation);
+        }
+    }
+
     pub fn upgrade_with_upgrade_request(
         &mut self,
         upgrade_request: &mut UpgradeRequest,
@@ -488,6 +855,67 @@ where
         self
     }
 
+    /// Makes the scheduled calls due at `block_time` on the latest post-state hash, and replaces
+    /// the post-state hash with the one resulting from the step.
+    pub fn step(&mut self, protocol_version: ProtocolVersion, block_time: BlockTime) -> &mut Self
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        self.commit_step(protocol_version, block_time, None)
+    }
+
+    /// Like [`step`](Self::step), but also runs the auction for the era ending at `era_end`.
+    pub fn step_with_era_end(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        block_time: BlockTime,
+        era_end: BlockTime,
+    ) -> &mut Self
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        self.commit_step(protocol_version, block_time, Some(era_end))
+    }
+
+    fn commit_step(
+        &mut self,
+        protocol_version: ProtocolVersion,
+        block_time: BlockTime,
+        era_end: Option<BlockTime>,
+    ) -> &mut Self
+    where
+        engine_state::Error: From<S::Error>,
+    {
+        let pre_state_hash = self
+            .post_state_hash
+            .clone()
+            .expect("expected post_state_hash")
+            .as_slice()
+            .try_into()
+            .expect("expected a valid hash");
+        let mut step_request = StepRequest::new(pre_state_hash, protocol_version, block_time);
+        if let Some(era_end) = era_end {
+            step_request = step_request.with_era_end(era_end);
+        }
+        let step_result = self
+            .engine_state
+            .commit_step(CorrelationId::new(), step_request)
+            .expect("should step");
+
+        match &step_result {
+            StepResult::Success {
+                post_state_hash, ..
+            } => {
+                let post_state_hash = post_state_hash.to_vec();
+                self.post_state_hashes.push(post_state_hash.clone());
+                self.post_state_hash = Some(post_state_hash);
+            }
+            step_result => panic!("Expected step success but received: {}", step_result),
+        }
+        self.step_results.push(Rc::new(step_result));
+        self
+    }
+
     /// Expects a successful run and caches transformations
     pub fn expect_success(&mut self) -> &mut Self {
         // Check first result, as only first result is interesting for a simple test
@@ -524,10 +952,37 @@ where
         self.transforms.clone()
     }
 
-    pub fn get_bonded_validators(&self) -> Vec<HashMap<PublicKey, U512>> {
+    /// Gets the bonded validators returned by each commit, sorted by the bytes of the validators'
+    /// public keys.
+    pub fn get_bonded_validators(&self) -> Vec<Vec<(PublicKey, U512)>> {
         self.bonded_validators.clone()
     }
 
+    /// Gets the validators bonded at `state_hash` via `get_validator_weights`, sorted by the bytes
+    /// of the validators' public keys.  Panics if the request fails.
+    pub fn get_validator_weights_at(&self, state_hash: &[u8]) -> Vec<(PublicKey, U512)> {
+        let mut get_validator_weights_request = GetValidatorWeightsRequest::new();
+        get_validator_weights_request.set_state_hash(state_hash.to_vec());
+        let mut get_validator_weights_response = self
+            .engine_state
+            .get_validator_weights(RequestOptions::new(), get_validator_weights_request)
+            .wait_drop_metadata()
+            .expect("should get validator weights response");
+        if !get_validator_weights_response.has_success() {
+            panic!(
+                "Expected validator weights but received {:?}",
+                get_validator_weights_response
+            );
+        }
+        get_validator_weights_response
+            .take_success()
+            .take_bonded_validators()
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<Vec<(PublicKey, U512)>, MappingError>>()
+            .unwrap()
+    }
+
     /// Gets genesis account (if present)
     pub fn get_genesis_account(&self) -> &Account {
         self.genesis_account
@@ -557,6 +1012,21 @@ where
             .expect("should have genesis transforms")
     }
 
+    /// Returns the reports of the genesis accounts, in the order given in the genesis config.
+    pub fn get_genesis_account_reports(&self) -> &[GenesisAccountReport] {
+        self.genesis_account_reports
+            .as_ref()
+            .expect("Unable to obtain genesis account reports. Please run genesis first.")
+    }
+
+    /// Returns the reports of bonding the genesis validators, in the order given in the genesis
+    /// config.
+    pub fn get_genesis_bonding_reports(&self) -> &[GenesisBondingReport] {
+        self.genesis_bonding_reports
+            .as_ref()
+            .expect("Unable to obtain genesis bonding reports. Please run genesis first.")
+    }
+
     pub fn get_genesis_hash(&self) -> Vec<u8> {
         self.genesis_hash
             .clone()
@@ -569,6 +1039,11 @@ where
             .expect("Should have post-state hash.")
     }
 
+    /// Returns the post-state hash of each commit made by the builder, in order.
+    pub fn get_post_state_hashes(&self) -> &[Vec<u8>] {
+        &self.post_state_hashes
+    }
+
     pub fn get_engine_state(&self) -> &EngineState<S> {
         &self.engine_state
     }
@@ -581,6 +1056,12 @@ where
         self.exec_responses.get(index)
     }
 
+    /// Returns the correlation id used by the exec call at `index`, which is attached to the log
+    /// messages and metrics emitted during that call.
+    pub fn get_exec_correlation_id(&self, index: usize) -> Option<CorrelationId> {
+        self.exec_correlation_ids.get(index).copied()
+    }
+
     pub fn get_exec_responses_count(&self) -> usize {
         self.exec_responses.len()
     }
@@ -589,6 +1070,14 @@ where
         self.upgrade_responses.get(index)
     }
 
+    pub fn get_upgrade_responses_count(&self) -> usize {
+        self.upgrade_responses.len()
+    }
+
+    pub fn get_step_result(&self, index: usize) -> Option<&StepResult> {
+        self.step_results.get(index).map(AsRef::as_ref)
+    }
+
     pub fn finish(&self) -> WasmTestResult<S> {
         WasmTestResult(self.clone())
     }
@@ -621,6 +1110,24 @@ where
             .expect("should parse balance into a U512")
     }
 
+    /// Returns the balance of the main purse of the account of `public_key` at the latest
+    /// post-state, or which hop of the read failed.
+    pub fn get_account_balance_result(&self, public_key: PublicKey) -> Result<Motes, BalanceError> {
+        let post_state_hash = self
+            .post_state_hash
+            .as_ref()
+            .expect("builder must have a post-state hash")
+            .as_slice()
+            .try_into()
+            .expect("should be a valid hash");
+        self.engine_state.get_main_purse_balance(
+            CorrelationId::new(),
+            *DEFAULT_PROTOCOL_VERSION,
+            post_state_hash,
+            public_key,
+        )
+    }
+
     pub fn get_account(&self, public_key: PublicKey) -> Option<Account> {
         let account_value = self
             .query(None, Key::Account(public_key), &[])
@@ -633,6 +1140,36 @@ where
         }
     }
 
+    /// Returns the associated keys and action thresholds of the account at `public_key`, queried
+    /// through the `#auth` projection.
+    pub fn get_account_auth(&self, public_key: PublicKey) -> Option<AccountAuthInfo> {
+        let post_state = self
+            .post_state_hash
+            .clone()
+            .expect("builder must have a post-state hash");
+        let query_request = create_query_request(
+            post_state,
+            Key::Account(public_key),
+            vec![AUTH_PROJECTION.to_string()],
+        );
+
+        let mut query_response = self
+            .engine_state
+            .query(RequestOptions::new(), query_request)
+            .wait_drop_metadata()
+            .expect("should get query response");
+
+        if !query_response.has_account_auth() {
+            return None;
+        }
+
+        let account_auth_info = query_response
+            .take_account_auth()
+            .try_into()
+            .expect("should parse account auth info");
+        Some(account_auth_info)
+    }
+
     pub fn get_contract(&self, contract_uref: URef) -> Option<Contract> {
         let contract_value: StoredValue = self
             .query(None, Key::URef(contract_uref), &[])
@@ -657,6 +1194,52 @@ where
         Some(utils::get_error_message(response))
     }
 
+    /// Returns the exit code of the first deploy of the exec call at `exec_index`.
+    pub fn get_exit_code(&self, exec_index: usize) -> Option<ExitCode> {
+        let response = self.get_exec_response(exec_index)?;
+        response.get(0).map(|result| result.exit_code())
+    }
+
+    /// Returns the value passed to `ret` by the session code of the first deploy of the exec call
+    /// at `exec_index`, if the deploy succeeded and its session code returned one.
+    pub fn get_session_return_value(&self, exec_index: usize) -> Option<CLValue> {
+        let response = self.get_exec_response(exec_index)?;
+        response.get(0)?.return_value().cloned()
+    }
+
+    /// Returns the data attached to the revert of the first deploy of the exec call at
+    /// `exec_index`, if the deploy's code reverted with data.
+    pub fn get_revert_data(&self, exec_index: usize) -> Option<Vec<u8>> {
+        let response = self.get_exec_response(exec_index)?;
+        response.get(0)?.revert_data().map(<[u8]>::to_vec)
+    }
+
+    /// Returns the host function calls made by the first deploy of the exec call at `exec_index`,
+    /// if its request [collected stats](ExecuteRequest::collect_stats).
+    pub fn get_host_call_stats(&self, exec_index: usize) -> Option<&HostCallStats> {
+        self.get_execution_stats(exec_index)
+            .map(|execution_stats| &execution_stats.host_call_stats)
+    }
+
+    /// Returns the global state reads made by the first deploy of the exec call at `exec_index`,
+    /// if its request [collected stats](ExecuteRequest::collect_stats).
+    pub fn get_read_stats(&self, exec_index: usize) -> Option<&ReadStats> {
+        self.get_execution_stats(exec_index)
+            .map(|execution_stats| &execution_stats.read_stats)
+    }
+
+    /// Returns the stats collected for the first deploy of the exec call at `exec_index`, if its
+    /// request [collected stats](ExecuteRequest::collect_stats).
+    pub fn get_execution_stats(&self, exec_index: usize) -> Option<&ExecutionStats> {
+        self.execution_stats.get(exec_index)?.get(0)?.as_ref()
+    }
+
+    /// Returns the summary of the exec call at `exec_index`, if its request
+    /// [collected stats](ExecuteRequest::collect_stats).
+    pub fn get_request_stats(&self, exec_index: usize) -> Option<&RequestStats> {
+        self.request_stats.get(exec_index)?.as_ref()
+    }
+
     pub fn exec_commit_finish(&mut self, execute_request: ExecuteRequest) -> WasmTestResult<S> {
         self.exec(execute_request)
             .expect_success()
@@ -671,6 +1254,7 @@ fn create_query_request(post_state: Vec<u8>, base_key: Key, path: Vec<String>) -
     query_request.set_state_hash(post_state);
     query_request.set_base_key(base_key.into());
     query_request.set_path(path.into());
+    query_request.set_include_contract_bytes(true);
 
     query_request
 }
@@ -680,10 +1264,7 @@ fn create_commit_request(
     prestate_hash: &[u8],
     effects: &AdditiveMap<Key, Transform>,
 ) -> CommitRequest {
-    let effects: Vec<TransformEntry> = effects
-        .iter()
-        .map(|(k, t)| (k.to_owned(), t.to_owned()).into())
-        .collect();
+    let effects: Vec<TransformEntry> = effects.iter().map(Into::into).collect();
 
     let mut commit_request = CommitRequest::new();
     commit_request.set_prestate_hash(prestate_hash.to_vec());
@@ -691,6 +1272,25 @@ fn create_commit_request(
     commit_request
 }
 
+fn create_batch_commit_request(
+    entries: &[(Vec<u8>, AdditiveMap<Key, Transform>)],
+) -> BatchCommitRequest {
+    let entries: Vec<BatchCommitRequest_Entry> = entries
+        .iter()
+        .map(|(prestate_hash, effects)| {
+            let effects: Vec<TransformEntry> = effects.iter().map(Into::into).collect();
+            let mut entry = BatchCommitRequest_Entry::new();
+            entry.set_prestate_hash(prestate_hash.to_vec());
+            entry.set_effects(effects.into());
+            entry
+        })
+        .collect();
+
+    let mut batch_commit_request = BatchCommitRequest::new();
+    batch_commit_request.set_entries(entries.into());
+    batch_commit_request
+}
+
 #[allow(clippy::implicit_hasher)]
 fn get_genesis_transforms(genesis_response: &GenesisResponse) -> AdditiveMap<Key, Transform> {
     let commit_transforms: TransformMap = genesis_response
@@ -702,3 +1302,25 @@ fn get_genesis_transforms(genesis_response: &GenesisResponse) -> AdditiveMap<Key
         .expect("should convert");
     commit_transforms.into_inner()
 }
+
+fn get_genesis_account_reports(genesis_response: &GenesisResponse) -> Vec<GenesisAccountReport> {
+    genesis_response
+        .get_success()
+        .get_account_reports()
+        .iter()
+        .cloned()
+        .map(GenesisAccountReport::try_from)
+        .collect::<Result<_, _>>()
+        .expect("should convert")
+}
+
+fn get_genesis_bonding_reports(genesis_response: &GenesisResponse) -> Vec<GenesisBondingReport> {
+    genesis_response
+        .get_success()
+        .get_bonding_reports()
+        .iter()
+        .cloned()
+        .map(GenesisBondingReport::try_from)
+        .collect::<Result<_, _>>()
+        .expect("should convert")
+}
@@ -64,15 +64,29 @@
 
 mod code;
 mod error;
-// This module is not intended to be used by third party crates.
+// This module is an implementation detail, and isn't covered by semver: third party crates should
+// only use the items re-exported from the crate root.
 #[doc(hidden)]
 pub mod internal;
+pub mod logging;
+pub mod prelude;
 mod session;
 mod test_context;
 mod value;
 
 pub use code::Code;
 pub use error::{Error, Result};
+pub use internal::{
+    utils::{
+        create_exec_config, create_genesis_config, create_run_genesis_request, get_account,
+        get_error_message, get_exec_costs, get_precondition_failure, get_success_result,
+        read_wasm_file_bytes,
+    },
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder,
+    UpgradeRequestBuilder, WasmTestBuilder, WasmTestResult, DEFAULT_ACCOUNTS, DEFAULT_BLOCK_TIME,
+    DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+    DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS, STANDARD_PAYMENT_CONTRACT,
+};
 pub use session::{Session, SessionBuilder};
 pub use test_context::{TestContext, TestContextBuilder};
 pub use types::account::PublicKey;
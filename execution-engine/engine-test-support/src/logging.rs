@@ -0,0 +1,271 @@
+//! Capturing of the log messages and metrics emitted via `casperlabs_engine_shared::logging`, so
+//! that tests can assert on them.
+//!
+//! ```no_run
+//! use casperlabs_engine_test_support::logging;
+//!
+//! let capture = logging::capture();
+//! // ... run the code under test on this thread ...
+//! let exec_durations = capture
+//!     .entries()
+//!     .with_metric("exec_duration")
+//!     .with_tag("exec_response");
+//! assert_eq!(exec_durations.len(), 1);
+//! ```
+
+use std::{cell::RefCell, collections::BTreeMap, marker::PhantomData, slice, sync::Once};
+
+use log::{
+    kv::{self, Key, Value, Visitor},
+    Level, LevelFilter, Log, Metadata, Record,
+};
+
+use engine_shared::{
+    logging::{self, Settings, Style, TerminalLogger},
+    newtypes::CorrelationId,
+};
+
+const METRIC_TARGET: &str = "METRIC";
+const CORRELATION_ID_KEY: &str = "correlation_id";
+const METRIC_KEY: &str = "metric";
+const TAG_KEY: &str = "tag";
+const MESSAGE_KEY: &str = "message";
+
+static INITIALIZE_LOGGER: Once = Once::new();
+
+thread_local! {
+    /// The entries captured on this thread, or `None` if no capture is active on this thread.
+    static CAPTURED_ENTRIES: RefCell<Option<Vec<LogEntry>>> = RefCell::new(None);
+}
+
+/// Installs the global logger used by the test support, which prints warnings and errors to the
+/// terminal and feeds the captures active on the logging thread.
+///
+/// Has no effect if called more than once, or if a different global logger is already installed.
+pub(crate) fn initialize() {
+    INITIALIZE_LOGGER.call_once(|| {
+        let settings = Settings::new(LevelFilter::Warn).with_style(Style::HumanReadable);
+        let logger = Box::new(CapturingLogger {
+            terminal_logger: TerminalLogger::new(&settings),
+        });
+        if logging::initialize_with_logger(logger, settings).is_ok() {
+            // The capturing logger filters by itself, depending on whether a capture is active.
+            log::set_max_level(LevelFilter::Trace);
+        }
+    });
+}
+
+/// Starts capturing the log messages and metrics emitted on the current thread until the returned
+/// guard is dropped.
+///
+/// As entries are only captured from the thread which called `capture()`, tests running in
+/// parallel don't see each other's entries.
+///
+/// # Panics
+///
+/// Panics if a capture is already active on the current thread.
+pub fn capture() -> LogCapture {
+    initialize();
+    CAPTURED_ENTRIES.with(|captured_entries| {
+        let mut captured_entries = captured_entries.borrow_mut();
+        assert!(
+            captured_entries.is_none(),
+            "log capture already active on this thread"
+        );
+        *captured_entries = Some(Vec::new());
+    });
+    LogCapture {
+        _not_send: PhantomData,
+    }
+}
+
+/// A guard which captures the log messages and metrics emitted on its thread while it is alive.
+///
+/// Created by [`capture`].
+#[derive(Debug)]
+pub struct LogCapture {
+    // The entries are held in a thread-local, so the guard must be dropped on its own thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl LogCapture {
+    /// Returns the entries captured so far, in the order they were emitted.
+    pub fn entries(&self) -> LogEntries {
+        let entries = CAPTURED_ENTRIES.with(|captured_entries| {
+            captured_entries
+                .borrow()
+                .as_ref()
+                .cloned()
+                .unwrap_or_default()
+        });
+        LogEntries(entries)
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        CAPTURED_ENTRIES.with(|captured_entries| *captured_entries.borrow_mut() = None);
+    }
+}
+
+/// A log message or metric captured while a [`LogCapture`] was active.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    level: Level,
+    is_metric: bool,
+    message: String,
+    properties: BTreeMap<String, String>,
+}
+
+impl LogEntry {
+    fn new(record: &Record) -> Self {
+        let mut properties = PropertiesVisitor::default();
+        let _ = record.key_values().visit(&mut properties);
+        let properties = properties.0;
+        let message = match properties.get(MESSAGE_KEY) {
+            Some(message) => message.clone(),
+            None => record.args().to_string(),
+        };
+        LogEntry {
+            level: record.level(),
+            is_metric: record.target() == METRIC_TARGET,
+            message,
+            properties,
+        }
+    }
+
+    /// Returns the level of the entry.  Metrics are logged at [`Level::Info`].
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// Returns whether the entry is a metric rather than a log message.
+    pub fn is_metric(&self) -> bool {
+        self.is_metric
+    }
+
+    /// Returns the message of the entry.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the value of the property `key`, if any.
+    pub fn property(&self, key: &str) -> Option<&str> {
+        self.properties.get(key).map(String::as_str)
+    }
+
+    /// Returns the correlation id of the entry, if any.
+    pub fn correlation_id(&self) -> Option<&str> {
+        self.property(CORRELATION_ID_KEY)
+    }
+
+    /// Returns the name of the metric, if the entry is a metric.
+    pub fn metric(&self) -> Option<&str> {
+        self.property(METRIC_KEY)
+    }
+
+    /// Returns the tag of the metric, if the entry is a metric.
+    pub fn tag(&self) -> Option<&str> {
+        self.property(TAG_KEY)
+    }
+}
+
+/// A list of captured entries, which can be narrowed down by chaining filters.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogEntries(Vec<LogEntry>);
+
+impl LogEntries {
+    /// Keeps only the entries at `level`.
+    pub fn with_level(self, level: Level) -> Self {
+        self.filter(|entry| entry.level() == level)
+    }
+
+    /// Keeps only the metrics called `metric`.
+    pub fn with_metric(self, metric: &str) -> Self {
+        self.filter(|entry| entry.metric() == Some(metric))
+    }
+
+    /// Keeps only the metrics tagged `tag`.
+    pub fn with_tag(self, tag: &str) -> Self {
+        self.filter(|entry| entry.tag() == Some(tag))
+    }
+
+    /// Keeps only the entries emitted with `correlation_id`.
+    pub fn with_correlation_id(self, correlation_id: CorrelationId) -> Self {
+        let correlation_id = correlation_id.to_string();
+        self.filter(|entry| entry.correlation_id() == Some(correlation_id.as_str()))
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the entries.
+    pub fn iter(&self) -> slice::Iter<LogEntry> {
+        self.0.iter()
+    }
+
+    fn filter<F: Fn(&LogEntry) -> bool>(self, predicate: F) -> Self {
+        LogEntries(self.0.into_iter().filter(predicate).collect())
+    }
+}
+
+impl IntoIterator for LogEntries {
+    type Item = LogEntry;
+    type IntoIter = std::vec::IntoIter<LogEntry>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+fn is_capturing() -> bool {
+    CAPTURED_ENTRIES.with(|captured_entries| captured_entries.borrow().is_some())
+}
+
+/// Outputs via a [`TerminalLogger`] and additionally records every entry emitted on a thread with
+/// an active [`LogCapture`].
+struct CapturingLogger {
+    terminal_logger: TerminalLogger,
+}
+
+impl Log for CapturingLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.terminal_logger.enabled(metadata) || is_capturing()
+    }
+
+    fn log(&self, record: &Record) {
+        CAPTURED_ENTRIES.with(|captured_entries| {
+            if let Some(entries) = captured_entries.borrow_mut().as_mut() {
+                entries.push(LogEntry::new(record));
+            }
+        });
+        self.terminal_logger.log(record);
+    }
+
+    fn flush(&self) {}
+}
+
+#[derive(Default)]
+struct PropertiesVisitor(BTreeMap<String, String>);
+
+impl<'kvs> Visitor<'kvs> for PropertiesVisitor {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        // As in the `TerminalLogger`, the value was generated via its `Debug` impl, so the
+        // surrounding quotation marks and the escaping are undone.
+        let value = value
+            .to_string()
+            .trim_matches('"')
+            .replace(r#"\'"#, r#"'"#)
+            .replace(r#"\""#, r#"""#)
+            .replace(r#"\\"#, r#"\"#);
+        self.0.insert(key.to_string(), value);
+        Ok(())
+    }
+}
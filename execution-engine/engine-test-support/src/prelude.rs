@@ -0,0 +1,15 @@
+//! The items most tests need, for glob importing:
+//!
+//! ```no_run
+//! use casperlabs_engine_test_support::prelude::*;
+//!
+//! let mut builder = InMemoryWasmTestBuilder::default();
+//! builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+//! ```
+
+pub use crate::{
+    Code, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder,
+    PublicKey, Session, SessionBuilder, TestContext, TestContextBuilder, UpgradeRequestBuilder,
+    Value, WasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_PAYMENT,
+    DEFAULT_RUN_GENESIS_REQUEST,
+};
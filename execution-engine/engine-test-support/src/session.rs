@@ -32,7 +32,7 @@ impl SessionBuilder {
             Code::URef(uref) => {
                 di_builder.with_stored_session_uref_addr(uref.to_vec(), session_args)
             }
-            Code::Hash(hash) => di_builder.with_stored_session_hash(hash.to_vec(), session_args),
+            Code::Hash(hash) => di_builder.with_stored_session_hash(hash, session_args),
         };
         Self {
             er_builder: Default::default(),
@@ -54,9 +54,7 @@ impl SessionBuilder {
             Code::URef(uref) => self
                 .di_builder
                 .with_stored_payment_uref_addr(uref.to_vec(), args),
-            Code::Hash(hash) => self
-                .di_builder
-                .with_stored_payment_hash(hash.to_vec(), args),
+            Code::Hash(hash) => self.di_builder.with_stored_payment_hash(hash, args),
         };
         self
     }
@@ -82,9 +80,10 @@ impl SessionBuilder {
     /// Builds the [`Session`].
     pub fn build(self) -> Session {
         let mut rng = rand::thread_rng();
+        let deploy_hash: [u8; 32] = rng.gen();
         let execute_request = self
             .er_builder
-            .push_deploy(self.di_builder.with_deploy_hash(rng.gen()).build())
+            .push_deploy(self.di_builder.with_deploy_hash(deploy_hash).build())
             .build();
         Session {
             inner: execute_request,
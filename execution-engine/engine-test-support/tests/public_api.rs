@@ -0,0 +1,115 @@
+//! Refers to every item of the supported public API, so that removing or renaming one of them fails
+//! to compile.
+
+use std::rc::Rc;
+
+use casperlabs_engine_test_support::{
+    create_exec_config, create_genesis_config, create_run_genesis_request, get_account,
+    get_error_message, get_exec_costs, get_precondition_failure, get_success_result,
+    read_wasm_file_bytes, Code, DeployItemBuilder, Error, ExecuteRequestBuilder, Hash,
+    InMemoryWasmTestBuilder, LmdbWasmTestBuilder, PublicKey, Result, Session, SessionBuilder,
+    TestContext, TestContextBuilder, URefAddr, UpgradeRequestBuilder, Value, WasmTestBuilder,
+    WasmTestResult, DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+    DEFAULT_BLOCK_TIME, DEFAULT_EXEC_CONFIG, DEFAULT_GENESIS_CONFIG, DEFAULT_PAYMENT,
+    DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS,
+    STANDARD_PAYMENT_CONTRACT,
+};
+use engine_core::engine_state::{
+    execution_result::ExecutionResult,
+    genesis::{ExecConfig, GenesisAccount, GenesisConfig},
+    run_genesis_request::RunGenesisRequest,
+    Error as EngineStateError,
+};
+use engine_shared::{account::Account, additive_map::AdditiveMap, gas::Gas, transform::Transform};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use types::{BlockTime, Key};
+
+fn assert_type<T>() {}
+
+#[test]
+fn public_types_should_be_available() {
+    assert_type::<Code>();
+    assert_type::<Error>();
+    assert_type::<Result<()>>();
+    assert_type::<Hash>();
+    assert_type::<URefAddr>();
+    assert_type::<PublicKey>();
+    assert_type::<Session>();
+    assert_type::<SessionBuilder>();
+    assert_type::<TestContext>();
+    assert_type::<TestContextBuilder>();
+    assert_type::<Value>();
+    assert_type::<DeployItemBuilder>();
+    assert_type::<ExecuteRequestBuilder>();
+    assert_type::<UpgradeRequestBuilder>();
+    assert_type::<InMemoryWasmTestBuilder>();
+    assert_type::<LmdbWasmTestBuilder>();
+    assert_type::<WasmTestBuilder<InMemoryGlobalState>>();
+    assert_type::<WasmTestResult<InMemoryGlobalState>>();
+}
+
+#[test]
+fn public_functions_should_be_available() {
+    let _: fn() -> DeployItemBuilder = DeployItemBuilder::new;
+    let _: fn() -> ExecuteRequestBuilder = ExecuteRequestBuilder::new;
+    let _: fn() -> UpgradeRequestBuilder = UpgradeRequestBuilder::new;
+    let _: fn() -> InMemoryWasmTestBuilder = InMemoryWasmTestBuilder::default;
+    let _: fn(&str) -> LmdbWasmTestBuilder = LmdbWasmTestBuilder::new::<str>;
+
+    let _: fn(Vec<GenesisAccount>) -> ExecConfig = create_exec_config;
+    let _: fn(Vec<GenesisAccount>) -> GenesisConfig = create_genesis_config;
+    let _: fn(Vec<GenesisAccount>) -> RunGenesisRequest = create_run_genesis_request;
+    let _: fn(&'static str) -> Vec<u8> = read_wasm_file_bytes::<&'static str>;
+    let _: fn(&AdditiveMap<Key, Transform>, &Key) -> Option<Account> = get_account;
+    let _: fn(Vec<Rc<ExecutionResult>>) -> Vec<Gas> =
+        get_exec_costs::<Rc<ExecutionResult>, Vec<Rc<ExecutionResult>>>;
+    let _: fn(Vec<Rc<ExecutionResult>>) -> String =
+        get_error_message::<Rc<ExecutionResult>, Vec<Rc<ExecutionResult>>>;
+    let _: for<'a> fn(&'a [Rc<ExecutionResult>]) -> &'a ExecutionResult = get_success_result;
+    let _: for<'a> fn(&'a [Rc<ExecutionResult>]) -> &'a EngineStateError = get_precondition_failure;
+}
+
+#[test]
+fn public_constants_should_be_available() {
+    // the lazily initialized constants are only referred to, as initializing some of them reads
+    // the system contracts' Wasm files
+    let _ = &DEFAULT_ACCOUNTS;
+    let _ = &DEFAULT_EXEC_CONFIG;
+    let _ = &DEFAULT_GENESIS_CONFIG;
+    let _ = &DEFAULT_PAYMENT;
+    let _ = &DEFAULT_PROTOCOL_VERSION;
+    let _ = &DEFAULT_RUN_GENESIS_REQUEST;
+    let _ = &DEFAULT_WASM_COSTS;
+
+    let _: PublicKey = DEFAULT_ACCOUNT_ADDR;
+    let _: u64 = DEFAULT_ACCOUNT_INITIAL_BALANCE;
+    let _: BlockTime = DEFAULT_BLOCK_TIME;
+    let _: &str = STANDARD_PAYMENT_CONTRACT;
+}
+
+mod prelude {
+    use casperlabs_engine_test_support::prelude::*;
+    use engine_storage::global_state::in_memory::InMemoryGlobalState;
+
+    use super::assert_type;
+
+    #[test]
+    fn prelude_should_be_available() {
+        assert_type::<Code>();
+        assert_type::<Session>();
+        assert_type::<SessionBuilder>();
+        assert_type::<TestContext>();
+        assert_type::<TestContextBuilder>();
+        assert_type::<Value>();
+        assert_type::<DeployItemBuilder>();
+        assert_type::<ExecuteRequestBuilder>();
+        assert_type::<UpgradeRequestBuilder>();
+        assert_type::<InMemoryWasmTestBuilder>();
+        assert_type::<LmdbWasmTestBuilder>();
+        assert_type::<WasmTestBuilder<InMemoryGlobalState>>();
+        let _: PublicKey = DEFAULT_ACCOUNT_ADDR;
+        let _: u64 = DEFAULT_ACCOUNT_INITIAL_BALANCE;
+        let _ = &DEFAULT_PAYMENT;
+        let _ = &DEFAULT_RUN_GENESIS_REQUEST;
+    }
+}
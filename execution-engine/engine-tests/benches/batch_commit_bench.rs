@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use tempfile::TempDir;
+
+use engine_core::engine_state::EngineConfig;
+use engine_shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform};
+use engine_test_support::internal::{LmdbWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use types::{CLValue, Key};
+
+/// Number of blocks committed per iteration.
+const BLOCK_COUNT: u64 = 10;
+/// Number of keys written by each block.
+const WRITES_PER_BLOCK: u64 = 100;
+
+fn make_key(block: u64, write: u64) -> Key {
+    let mut result = [0; 32];
+    result[..8].copy_from_slice(&block.to_be_bytes());
+    result[8..16].copy_from_slice(&write.to_be_bytes());
+    Key::Hash(result)
+}
+
+fn block_effects(block: u64) -> AdditiveMap<Key, Transform> {
+    let mut effects = AdditiveMap::new();
+    for write in 0..WRITES_PER_BLOCK {
+        let stored_value =
+            StoredValue::CLValue(CLValue::from_t(write).expect("should create value"));
+        effects.insert(make_key(block, write), Transform::Write(stored_value));
+    }
+    effects
+}
+
+/// Commits a chain of blocks one by one, returning each block's pre-state hash alongside its
+/// effects.
+fn prepare_chain(builder: &mut LmdbWasmTestBuilder) -> Vec<(Vec<u8>, AdditiveMap<Key, Transform>)> {
+    (0..BLOCK_COUNT)
+        .map(|block| {
+            let prestate_hash = builder.get_post_state_hash();
+            let effects = block_effects(block);
+            builder.commit_effects(prestate_hash.clone(), effects.clone());
+            (prestate_hash, effects)
+        })
+        .collect()
+}
+
+pub fn batch_commit_bench(c: &mut Criterion) {
+    let data_dir = TempDir::new().expect("should create temp dir");
+    let mut builder = LmdbWasmTestBuilder::new_with_config(data_dir.path(), EngineConfig::new());
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let entries = prepare_chain(&mut builder);
+
+    let mut group = c.benchmark_group("commit");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+    group.throughput(Throughput::Elements(BLOCK_COUNT));
+
+    // Re-committing the same chain yields the same roots, so both cases repeat identical work.
+    group.bench_function(format!("sequential/{}", BLOCK_COUNT), |b| {
+        b.iter(|| {
+            for (prestate_hash, effects) in &entries {
+                let response = builder.commit_transforms(prestate_hash.clone(), effects.clone());
+                assert!(response.has_success(), "{:?}", response);
+            }
+        })
+    });
+
+    group.bench_function(format!("batch/{}", BLOCK_COUNT), |b| {
+        b.iter(|| {
+            let response = builder.batch_commit_transforms(&entries);
+            assert!(response.has_success(), "{:?}", response);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, batch_commit_bench);
+criterion_main!(benches);
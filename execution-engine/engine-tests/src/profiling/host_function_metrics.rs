@@ -155,7 +155,7 @@ fn run_test(root_hash: Vec<u8>, repetitions: usize, data_dir: &Path) {
 
         let deploy = DeployItemBuilder::new()
             .with_address(account_1_public_key)
-            .with_deploy_hash(rng.gen())
+            .with_deploy_hash(rng.gen::<[u8; 32]>())
             .with_session_code(
                 HOST_FUNCTION_METRICS_CONTRACT,
                 (
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+use assert_matches::assert_matches;
+
+use engine_core::engine_state::balance::BalanceError;
+use engine_shared::{
+    account::Account,
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_test_support::{
+    internal::{InMemoryWasmTestBuilder, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr::ToBytes, AccessRights, CLValue, Key, URef};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const PURSE_ADDR: [u8; 32] = [2u8; 32];
+const BALANCE_ADDR: [u8; 32] = [3u8; 32];
+
+/// Commits an account whose main purse is unknown to the mint.
+fn commit_account_with_unknown_purse(builder: &mut InMemoryWasmTestBuilder) {
+    let main_purse = URef::new(PURSE_ADDR, AccessRights::READ_ADD_WRITE);
+    let account = Account::create(ACCOUNT_1_ADDR, BTreeMap::new(), main_purse);
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::Account(ACCOUNT_1_ADDR),
+        Transform::Write(StoredValue::Account(account)),
+    );
+    builder.commit_effects(builder.get_post_state_hash(), effects);
+}
+
+/// The key under which the mint records the balance uref of the purse of `PURSE_ADDR`.
+fn balance_mapping_key(builder: &InMemoryWasmTestBuilder) -> Key {
+    let mint = builder.get_mint_contract_uref();
+    let purse_bytes = PURSE_ADDR.to_bytes().expect("should serialize purse addr");
+    Key::local(mint.addr(), &purse_bytes)
+}
+
+#[ignore]
+#[test]
+fn should_get_main_purse_balance_of_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let expected_balance = builder.get_purse_balance(account.main_purse());
+
+    let balance = builder
+        .get_account_balance_result(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get balance");
+    assert_eq!(balance.value(), expected_balance);
+}
+
+#[ignore]
+#[test]
+fn should_fail_on_missing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_root = Blake2bHash::new(b"missing root");
+    let result = builder.get_engine_state().get_main_purse_balance(
+        CorrelationId::new(),
+        *DEFAULT_PROTOCOL_VERSION,
+        missing_root,
+        DEFAULT_ACCOUNT_ADDR,
+    );
+    assert_matches!(result, Err(BalanceError::RootNotFound(root)) if root == missing_root);
+}
+
+#[ignore]
+#[test]
+fn should_fail_on_missing_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let result = builder.get_account_balance_result(ACCOUNT_1_ADDR);
+    assert_matches!(
+        result,
+        Err(BalanceError::AccountNotFound(key)) if key == Key::Account(ACCOUNT_1_ADDR)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_on_purse_unknown_to_mint() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    commit_account_with_unknown_purse(&mut builder);
+
+    let expected_key = balance_mapping_key(&builder);
+    let result = builder.get_account_balance_result(ACCOUNT_1_ADDR);
+    assert_matches!(
+        result,
+        Err(BalanceError::PurseURefMissing(key)) if key == expected_key
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_on_purse_without_mint_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    commit_account_with_unknown_purse(&mut builder);
+
+    // the mint records a balance uref for the purse, but the uref holds no balance
+    let balance_key = Key::URef(URef::new(BALANCE_ADDR, AccessRights::READ_ADD_WRITE));
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        balance_mapping_key(&builder),
+        Transform::Write(StoredValue::CLValue(
+            CLValue::from_t(balance_key).expect("should create CLValue"),
+        )),
+    );
+    builder.commit_effects(builder.get_post_state_hash(), effects);
+
+    let result = builder.get_account_balance_result(ACCOUNT_1_ADDR);
+    assert_matches!(
+        result,
+        Err(BalanceError::MintBalanceMissing(key)) if key == balance_key.normalize()
+    );
+}
@@ -0,0 +1,71 @@
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{BuildStateRequest, BuildStateRequest_Entry, BuildStateResponse},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::internal::InMemoryWasmTestBuilder;
+use types::{CLValue, Key};
+
+const ENTRY_COUNT: u8 = 100;
+
+fn entries() -> Vec<(Key, StoredValue)> {
+    (0..ENTRY_COUNT)
+        .map(|index| {
+            let key = Key::Hash([index; 32]);
+            let value = StoredValue::CLValue(CLValue::from_t(u64::from(index)).unwrap());
+            (key, value)
+        })
+        .collect()
+}
+
+fn build_state(
+    builder: &InMemoryWasmTestBuilder,
+    entries: Vec<BuildStateRequest_Entry>,
+    persist: bool,
+) -> BuildStateResponse {
+    let mut build_state_request = BuildStateRequest::new();
+    build_state_request.set_entries(entries.into());
+    build_state_request.set_persist(persist);
+
+    builder
+        .get_engine_state()
+        .build_state(RequestOptions::new(), build_state_request)
+        .wait_drop_metadata()
+        .expect("should build state")
+}
+
+#[ignore]
+#[test]
+fn should_build_queryable_state() {
+    let builder = InMemoryWasmTestBuilder::default();
+    let pb_entries: Vec<BuildStateRequest_Entry> = entries().into_iter().map(Into::into).collect();
+
+    let response = build_state(&builder, pb_entries.clone(), false);
+    assert!(response.has_state_hash(), "{:?}", response.get_failure());
+    let unpersisted_state_hash = response.get_state_hash().to_vec();
+
+    let response = build_state(&builder, pb_entries, true);
+    assert!(response.has_state_hash(), "{:?}", response.get_failure());
+    let state_hash = response.get_state_hash().to_vec();
+    assert_eq!(state_hash, unpersisted_state_hash);
+
+    for (key, value) in entries() {
+        let queried_value = builder
+            .query(Some(state_hash.clone()), key, &[])
+            .expect("should query");
+        assert_eq!(queried_value, value);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_build_state_from_incomplete_entry() {
+    let builder = InMemoryWasmTestBuilder::default();
+    let mut pb_entry = BuildStateRequest_Entry::new();
+    pb_entry.set_key(Key::Hash([0; 32]).into());
+
+    let response = build_state(&builder, vec![pb_entry], true);
+    assert!(response.has_failure());
+}
@@ -0,0 +1,319 @@
+use std::{collections::BTreeMap, convert::TryFrom, iter};
+
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{
+        CommitRequest, CommitRequest_CommitFence, CommitResponse, Durability, ServerInfoRequest,
+        ServerInfoResponse, TransformEntry,
+    },
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform};
+use engine_storage::store_metadata_store::STORE_SCHEMA_VERSION;
+use engine_test_support::{
+    internal::{
+        InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{CLValue, Key, ProtocolVersion};
+
+const KEY: Key = Key::Hash([42; 32]);
+
+fn commit(
+    builder: &InMemoryWasmTestBuilder,
+    prestate_hash: Vec<u8>,
+    value: u64,
+    last_seen_commit_sequence_number: Option<u64>,
+    durability: Durability,
+) -> CommitResponse {
+    let stored_value = StoredValue::CLValue(CLValue::from_t(value).expect("should create value"));
+    let effect: TransformEntry = (KEY, Transform::Write(stored_value)).into();
+
+    let mut commit_request = CommitRequest::new();
+    commit_request.set_prestate_hash(prestate_hash);
+    commit_request.set_effects(vec![effect].into());
+    commit_request.set_protocol_version((*DEFAULT_PROTOCOL_VERSION).into());
+    if let Some(last_seen_commit_sequence_number) = last_seen_commit_sequence_number {
+        let mut fence = CommitRequest_CommitFence::new();
+        fence.set_last_seen_commit_sequence_number(last_seen_commit_sequence_number);
+        commit_request.set_fence(fence);
+    }
+    commit_request.set_durability(durability);
+
+    builder
+        .get_engine_state()
+        .commit(RequestOptions::new(), commit_request)
+        .wait_drop_metadata()
+        .expect("should commit")
+}
+
+fn server_info(builder: &InMemoryWasmTestBuilder) -> ServerInfoResponse {
+    builder
+        .get_engine_state()
+        .server_info(RequestOptions::new(), ServerInfoRequest::new())
+        .wait_drop_metadata()
+        .expect("should get server info")
+}
+
+#[ignore]
+#[test]
+fn should_reject_second_fenced_commit_to_same_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let prestate_hash = builder.get_post_state_hash();
+
+    let first_response = commit(
+        &builder,
+        prestate_hash.clone(),
+        1,
+        Some(0),
+        Durability::SERVER_DEFAULT,
+    );
+    assert!(first_response.has_success(), "{:?}", first_response);
+    assert_eq!(first_response.get_success().get_commit_sequence_number(), 1);
+
+    let second_response = commit(
+        &builder,
+        prestate_hash,
+        2,
+        Some(0),
+        Durability::SERVER_DEFAULT,
+    );
+    assert!(second_response.has_stale_commit(), "{:?}", second_response);
+    let stale_commit = second_response.get_stale_commit();
+    assert_eq!(stale_commit.get_last_seen_commit_sequence_number(), 0);
+    assert_eq!(stale_commit.get_current_commit_sequence_number(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_apply_unfenced_commits_to_same_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let prestate_hash = builder.get_post_state_hash();
+
+    let first_response = commit(
+        &builder,
+        prestate_hash.clone(),
+        1,
+        None,
+        Durability::SERVER_DEFAULT,
+    );
+    assert!(first_response.has_success(), "{:?}", first_response);
+
+    let second_response = commit(&builder, prestate_hash, 2, None, Durability::SERVER_DEFAULT);
+    assert!(second_response.has_success(), "{:?}", second_response);
+    assert_eq!(
+        second_response.get_success().get_commit_sequence_number(),
+        2
+    );
+    assert_ne!(
+        first_response.get_success().get_poststate_hash(),
+        second_response.get_success().get_poststate_hash()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_report_whether_commit_is_durable() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let prestate_hash = builder.get_post_state_hash();
+    assert_eq!(server_info(&builder).get_durability(), Durability::STRICT);
+
+    // strict by default
+    let first_response = commit(
+        &builder,
+        prestate_hash.clone(),
+        1,
+        None,
+        Durability::SERVER_DEFAULT,
+    );
+    assert!(first_response.get_success().get_durable());
+    let first_root = first_response.get_success().get_poststate_hash();
+    assert_eq!(server_info(&builder).get_last_durable_root(), first_root);
+
+    // a volatile commit leaves the last durable root behind
+    let second_response = commit(&builder, prestate_hash, 2, None, Durability::VOLATILE);
+    assert!(second_response.has_success(), "{:?}", second_response);
+    assert!(!second_response.get_success().get_durable());
+    assert_eq!(server_info(&builder).get_last_durable_root(), first_root);
+}
+
+#[ignore]
+#[test]
+fn should_make_genesis_and_upgrade_durable() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let genesis_hash = builder.get_genesis_hash();
+    assert_eq!(
+        server_info(&builder).get_last_durable_root(),
+        &genesis_hash[..]
+    );
+
+    // a volatile commit leaves the last durable root behind, until the upgrade is synced
+    let response = commit(&builder, genesis_hash, 1, None, Durability::VOLATILE);
+    assert!(!response.get_success().get_durable());
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(ProtocolVersion::from_parts(1, 1, 0))
+        .with_activation_point(1)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have upgrade response");
+    assert!(upgrade_response.has_success(), "{:?}", upgrade_response);
+    assert_eq!(
+        server_info(&builder).get_last_durable_root(),
+        upgrade_response.get_success().get_post_state_hash()
+    );
+}
+
+/// Returns the effects of `count` blocks, the `n`th of which writes `n` under `KEY`.
+fn block_effects(count: u64) -> Vec<AdditiveMap<Key, Transform>> {
+    (1..=count)
+        .map(|value| {
+            let stored_value =
+                StoredValue::CLValue(CLValue::from_t(value).expect("should create value"));
+            let mut effects = AdditiveMap::new();
+            effects.insert(KEY, Transform::Write(stored_value));
+            effects
+        })
+        .collect()
+}
+
+/// Commits `blocks` one by one after genesis, and returns the genesis root and the post-state of
+/// each block.
+fn commit_one_by_one(blocks: &[AdditiveMap<Key, Transform>]) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let genesis_hash = builder.get_post_state_hash();
+    let post_state_hashes = blocks
+        .iter()
+        .map(|effects| {
+            let prestate_hash = builder.get_post_state_hash();
+            builder.commit_effects(prestate_hash, effects.clone());
+            builder.get_post_state_hash()
+        })
+        .collect();
+    (genesis_hash, post_state_hashes)
+}
+
+#[ignore]
+#[test]
+fn should_batch_commit_chain_of_blocks() {
+    let blocks = block_effects(3);
+    let (genesis_hash, post_state_hashes) = commit_one_by_one(&blocks);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let prestate_hashes =
+        iter::once(genesis_hash).chain(post_state_hashes.iter().take(blocks.len() - 1).cloned());
+    let entries: Vec<(Vec<u8>, AdditiveMap<Key, Transform>)> =
+        prestate_hashes.zip(blocks).collect();
+
+    let response = builder.batch_commit_transforms(&entries);
+
+    assert!(response.has_success(), "{:?}", response);
+    let success = response.get_success();
+    assert_eq!(
+        success.get_poststate_hash(),
+        post_state_hashes.last().unwrap().as_slice()
+    );
+    let batch_post_state_hashes: Vec<Vec<u8>> = success
+        .get_results()
+        .iter()
+        .map(|result| result.get_poststate_hash().to_vec())
+        .collect();
+    assert_eq!(batch_post_state_hashes, post_state_hashes);
+    let value = builder
+        .query(post_state_hashes.last().cloned(), KEY, &[])
+        .expect("should query batch post-state");
+    assert_eq!(
+        CLValue::try_from(value).expect("should be a CLValue"),
+        CLValue::from_t(3u64).expect("should create value")
+    );
+}
+
+#[ignore]
+#[test]
+fn should_apply_nothing_when_batch_entry_fails() {
+    let mut blocks = block_effects(2);
+    let (genesis_hash, post_state_hashes) = commit_one_by_one(&blocks[..1]);
+    // adding named keys to a `CLValue` is a type mismatch
+    blocks[1].insert(KEY, Transform::AddKeys(BTreeMap::new()));
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let entries = vec![
+        (genesis_hash.clone(), blocks[0].clone()),
+        (post_state_hashes[0].clone(), blocks[1].clone()),
+    ];
+
+    let response = builder.batch_commit_transforms(&entries);
+
+    assert!(response.has_failure(), "{:?}", response);
+    let failure = response.get_failure();
+    assert_eq!(failure.get_index(), 1);
+    assert!(
+        failure.get_commit_failure().has_type_mismatch(),
+        "{:?}",
+        failure
+    );
+    // the first entry was rolled back with the failing one
+    assert!(builder
+        .query(Some(post_state_hashes[0].clone()), KEY, &[])
+        .is_err());
+    assert!(builder
+        .query(Some(genesis_hash), Key::Account(DEFAULT_ACCOUNT_ADDR), &[])
+        .is_ok());
+}
+
+#[ignore]
+#[test]
+fn should_reject_batch_entry_not_built_on_previous_entry() {
+    let blocks = block_effects(2);
+    let (genesis_hash, post_state_hashes) = commit_one_by_one(&blocks[..1]);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let entries = vec![
+        (genesis_hash.clone(), blocks[0].clone()),
+        (genesis_hash.clone(), blocks[1].clone()),
+    ];
+
+    let response = builder.batch_commit_transforms(&entries);
+
+    assert!(response.has_failure(), "{:?}", response);
+    let failure = response.get_failure();
+    assert_eq!(failure.get_index(), 1);
+    let prestate_mismatch = failure.get_prestate_mismatch();
+    assert_eq!(
+        prestate_mismatch.get_expected_prestate_hash(),
+        post_state_hashes[0].as_slice()
+    );
+    assert_eq!(
+        prestate_mismatch.get_prestate_hash(),
+        genesis_hash.as_slice()
+    );
+    assert!(builder
+        .query(Some(post_state_hashes[0].clone()), KEY, &[])
+        .is_err());
+}
+
+#[ignore]
+#[test]
+fn should_report_store_schema_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    assert_eq!(
+        server_info(&builder).get_store_schema_version(),
+        STORE_SCHEMA_VERSION
+    );
+}
@@ -0,0 +1,142 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use engine_core::engine_state::{commit_notifications::CommitReceiver, EngineConfig};
+use engine_shared::{
+    additive_map::AdditiveMap, newtypes::Blake2bHash, stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, CLValue, Key, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const KEY: Key = Key::Hash([42; 32]);
+
+/// Returns a builder which hasn't run genesis yet, the root it starts from, and a receiver
+/// subscribed to its commits.
+fn setup(engine_config: EngineConfig) -> (InMemoryWasmTestBuilder, Blake2bHash, CommitReceiver) {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root_hash = global_state.empty_root_hash;
+    let builder =
+        InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root_hash.to_vec());
+    let receiver = builder.get_engine_state().subscribe_commits();
+    (builder, empty_root_hash, receiver)
+}
+
+fn to_hash(bytes: &[u8]) -> Blake2bHash {
+    Blake2bHash::try_from(bytes).expect("should convert hash")
+}
+
+fn write_effects(value: u64) -> AdditiveMap<Key, Transform> {
+    let stored_value = StoredValue::CLValue(CLValue::from_t(value).expect("should create value"));
+    let mut effects = AdditiveMap::new();
+    effects.insert(KEY, Transform::Write(stored_value));
+    effects
+}
+
+#[ignore]
+#[test]
+fn should_notify_genesis_and_commits_in_order() {
+    let (mut builder, empty_root_hash, receiver) = setup(EngineConfig::new());
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let genesis_hash = to_hash(&builder.get_genesis_hash());
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    let transfer_hash = to_hash(&builder.get_post_state_hash());
+    let transfer_keys = builder.get_transforms()[0]
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    builder.commit_effects(builder.get_post_state_hash(), write_effects(1));
+    let write_hash = to_hash(&builder.get_post_state_hash());
+
+    let bonded_validators: BTreeMap<PublicKey, U512> = builder
+        .get_bonded_validators()
+        .last()
+        .cloned()
+        .expect("should have bonded validators")
+        .into_iter()
+        .collect();
+
+    let genesis = receiver.try_recv().expect("should notify genesis");
+    assert_eq!(genesis.pre_state, empty_root_hash);
+    assert_eq!(genesis.post_state, genesis_hash);
+    assert!(genesis
+        .modified_keys
+        .contains(&Key::Account(DEFAULT_ACCOUNT_ADDR)));
+    assert_eq!(genesis.protocol_version, *DEFAULT_PROTOCOL_VERSION);
+
+    let transfer = receiver.try_recv().expect("should notify transfer");
+    assert_eq!(transfer.pre_state, genesis_hash);
+    assert_eq!(transfer.post_state, transfer_hash);
+    let mut expected_keys = transfer_keys;
+    expected_keys.sort();
+    assert_eq!(transfer.modified_keys, expected_keys);
+    assert!(transfer
+        .modified_keys
+        .contains(&Key::Account(ACCOUNT_1_ADDR)));
+    assert_eq!(transfer.bonded_validators, bonded_validators);
+    assert_eq!(transfer.protocol_version, *DEFAULT_PROTOCOL_VERSION);
+
+    let write = receiver.try_recv().expect("should notify write");
+    assert_eq!(write.pre_state, transfer_hash);
+    assert_eq!(write.post_state, write_hash);
+    assert_eq!(write.modified_keys, vec![KEY]);
+    assert_eq!(write.bonded_validators, bonded_validators);
+
+    assert!(receiver.try_recv().is_none());
+    assert_eq!(receiver.lagged(), 0);
+}
+
+#[ignore]
+#[test]
+fn should_not_delay_commits_for_slow_subscriber() {
+    const CAPACITY: usize = 2;
+    const COMMIT_COUNT: u64 = 5;
+
+    let (mut builder, _, slow_receiver) =
+        setup(EngineConfig::new().with_commit_notification_capacity(CAPACITY));
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let fast_receiver = builder.get_engine_state().subscribe_commits();
+
+    // the slow receiver never takes its notifications, so every commit finds its queue full
+    for value in 1..=COMMIT_COUNT {
+        builder.commit_effects(builder.get_post_state_hash(), write_effects(value));
+        let notification = fast_receiver
+            .try_recv()
+            .expect("should notify fast receiver");
+        assert_eq!(
+            notification.post_state,
+            to_hash(&builder.get_post_state_hash())
+        );
+    }
+    assert_eq!(fast_receiver.lagged(), 0);
+
+    // genesis and the commits after it, less those which fit the queue
+    let expected_lagged = 1 + COMMIT_COUNT - CAPACITY as u64;
+    assert_eq!(slow_receiver.lagged(), expected_lagged);
+    let post_state_hashes = builder.get_post_state_hashes();
+    for post_state_hash in &post_state_hashes[post_state_hashes.len() - CAPACITY..] {
+        let notification = slow_receiver
+            .try_recv()
+            .expect("should keep latest notifications");
+        assert_eq!(notification.post_state, to_hash(post_state_hash));
+    }
+    assert!(slow_receiver.try_recv().is_none());
+}
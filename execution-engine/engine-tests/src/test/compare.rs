@@ -0,0 +1,165 @@
+//! Compares the outcomes of a corpus of deploys on two differently configured engines.  See
+//! [`engine_test_support::internal::compare`] for the corpus format.
+//!
+//! `compare_corpus` runs the corpus in the directory named by [`CORPUS_DIR_ENV_VAR`], first
+//! writing the seed corpus there if it doesn't hold one yet, or runs the seed corpus if the
+//! variable isn't set.
+
+use std::{env, path::Path};
+
+use contract::args_parser::ArgsParser;
+use engine_core::engine_state::{deploy_item::DeployItem, EngineConfig};
+use engine_test_support::{
+    internal::{
+        compare::{self, ComparisonReport, Corpus, DeployDifference, EngineVariant, GENESIS_FILE},
+        DeployItemBuilder, ExecuteRequestBuilder, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+/// If set, names the directory of the corpus run by `compare_corpus`.
+const CORPUS_DIR_ENV_VAR: &str = "CL_COMPARE_CORPUS_DIR";
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CANARIES: [&str; 5] = [
+    "canary_big_int_math.wasm",
+    "canary_bytesrepr.wasm",
+    "canary_deep_branching.wasm",
+    "canary_host_buffer.wasm",
+    "canary_memory_growth.wasm",
+];
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000_000;
+
+fn deploy(
+    from: PublicKey,
+    session_wasm: &str,
+    args: impl ArgsParser,
+    deploy_hash: u8,
+) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(from)
+        .with_session_code(session_wasm, args)
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[from])
+        .with_deploy_hash([deploy_hash; 32])
+        .build()
+}
+
+/// Deploys of contracts used across the engine tests, with fixed deploy hashes so that the
+/// addresses of the URefs they create are reproducible.
+fn seed_corpus() -> Corpus {
+    let mut corpus = Corpus::new(DEFAULT_RUN_GENESIS_REQUEST.clone());
+
+    let transfer = deploy(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+        1,
+    );
+    corpus.push(
+        "01_transfer_to_account",
+        ExecuteRequestBuilder::from_deploy_item(transfer).build(),
+    );
+
+    let do_nothing = deploy(ACCOUNT_1_ADDR, CONTRACT_DO_NOTHING, (), 2);
+    corpus.push(
+        "02_do_nothing",
+        ExecuteRequestBuilder::from_deploy_item(do_nothing).build(),
+    );
+
+    for (index, canary) in CANARIES.iter().enumerate() {
+        let deploy_hash = 3 + index as u8;
+        let canary_deploy = deploy(DEFAULT_ACCOUNT_ADDR, canary, (), deploy_hash);
+        corpus.push(
+            format!("{:02}_{}", deploy_hash, canary.trim_end_matches(".wasm")),
+            ExecuteRequestBuilder::from_deploy_item(canary_deploy).build(),
+        );
+    }
+    corpus
+}
+
+/// Returns the corpus in the directory named by [`CORPUS_DIR_ENV_VAR`], or the seed corpus.
+fn load_corpus() -> Corpus {
+    let corpus_dir = match env::var_os(CORPUS_DIR_ENV_VAR) {
+        Some(corpus_dir) => corpus_dir,
+        None => return seed_corpus(),
+    };
+    let corpus_dir = Path::new(&corpus_dir);
+    if !corpus_dir.join(GENESIS_FILE).exists() {
+        seed_corpus()
+            .write_to_dir(corpus_dir)
+            .unwrap_or_else(|error| panic!("should write {}: {}", corpus_dir.display(), error));
+    }
+    Corpus::read_from_dir(corpus_dir)
+        .unwrap_or_else(|error| panic!("should read {}: {}", corpus_dir.display(), error))
+}
+
+fn default_engine_variant() -> EngineVariant {
+    EngineVariant::new(
+        EngineConfig::new()
+            .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+            .with_enable_bonding(cfg!(feature = "enable-bonding")),
+    )
+}
+
+#[ignore]
+#[test]
+fn compare_corpus() {
+    let corpus = load_corpus();
+    let report = compare::compare(&corpus, default_engine_variant(), default_engine_variant());
+    assert!(
+        report.is_empty(),
+        "outcomes differ between identical engines:\n{}",
+        report
+    );
+}
+
+#[ignore]
+#[test]
+fn should_report_only_cost_deltas_for_changed_cost_table() {
+    let corpus = seed_corpus();
+    let mut wasm_costs = *DEFAULT_WASM_COSTS;
+    wasm_costs.regular += 1;
+
+    let report = compare::compare(
+        &corpus,
+        default_engine_variant(),
+        default_engine_variant().with_wasm_costs(wasm_costs),
+    );
+
+    assert_eq!(
+        report.comparisons().len(),
+        corpus.names().count(),
+        "every deploy should cost more:\n{}",
+        report
+    );
+    for comparison in report.comparisons() {
+        match comparison.differences.as_slice() {
+            [DeployDifference::Cost { left, right }] if right > left => {}
+            _ => panic!("expected only a cost increase:\n{}", report),
+        }
+    }
+    assert!(report.to_string().contains("cost"));
+}
+
+#[ignore]
+#[test]
+fn should_run_corpus_read_back_from_dir_identically() {
+    let corpus_dir = tempfile::tempdir().expect("should create temp dir");
+    let corpus = seed_corpus();
+    corpus
+        .write_to_dir(corpus_dir.path())
+        .expect("should write corpus");
+    let read_corpus = Corpus::read_from_dir(corpus_dir.path()).expect("should read corpus");
+
+    assert!(corpus.names().eq(read_corpus.names()));
+    let report = ComparisonReport::new(
+        &default_engine_variant().run(&corpus),
+        &default_engine_variant().run(&read_corpus),
+    );
+    assert!(report.is_empty(), "{}", report);
+}
@@ -0,0 +1,84 @@
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{system_contract_errors::mint, ApiError, URef, U512};
+
+const CONTRACT_MAIN_PURSE_LEAK: &str = "main_purse_leak.wasm";
+const CONTRACT_MAIN_PURSE_LEAK_CALLER: &str = "main_purse_leak_caller.wasm";
+const CONTRACT_ROTATE_MAIN_PURSE: &str = "rotate_main_purse.wasm";
+const STOLEN_AMOUNT: u64 = 1_000;
+
+fn main_purse(builder: &InMemoryWasmTestBuilder) -> URef {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse()
+}
+
+#[ignore]
+#[test]
+fn should_rotate_leaked_main_purse() {
+    let leak_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_MAIN_PURSE_LEAK, ()).build();
+    let steal_request = || {
+        ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_MAIN_PURSE_LEAK_CALLER,
+            (U512::from(STOLEN_AMOUNT),),
+        )
+        .build()
+    };
+    let rotate_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_ROTATE_MAIN_PURSE, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(leak_request)
+        .expect_success()
+        .commit()
+        // the contract holding the leaked purse can transfer from it
+        .exec(steal_request())
+        .expect_success()
+        .commit();
+
+    let previous_main_purse = main_purse(&builder);
+    let previous_balance = builder.get_purse_balance(previous_main_purse);
+
+    builder.exec(rotate_request).expect_success().commit();
+
+    let new_main_purse = main_purse(&builder);
+    assert_ne!(new_main_purse.addr(), previous_main_purse.addr());
+
+    let rotation_cost =
+        Motes::from_gas(builder.exec_costs(2)[0], CONV_RATE).expect("should convert gas to motes");
+    let new_balance = builder.get_purse_balance(new_main_purse);
+    assert_eq!(new_balance, previous_balance - rotation_cost.value());
+    assert_eq!(builder.get_purse_balance(previous_main_purse), U512::zero());
+
+    // the contract can no longer transfer from the revoked purse
+    builder.exec(steal_request()).commit();
+
+    let error_message = builder
+        .exec_error_message(3)
+        .expect("should have exec response");
+    let expected_error = ApiError::from(mint::Error::PurseRevoked);
+    assert!(
+        error_message.contains(&format!("{:?}", expected_error)),
+        "expected {:?}, got: {}",
+        expected_error,
+        error_message
+    );
+
+    // the failed deploy was paid from the new main purse, which is otherwise intact
+    let failed_steal_cost =
+        Motes::from_gas(builder.exec_costs(3)[0], CONV_RATE).expect("should convert gas to motes");
+    assert_eq!(
+        builder.get_purse_balance(new_main_purse),
+        new_balance - failed_steal_cost.value()
+    );
+}
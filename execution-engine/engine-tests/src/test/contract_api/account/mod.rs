@@ -1,4 +1,5 @@
 mod associated_keys;
 mod authorized_keys;
 mod key_management_thresholds;
+mod main_purse_rotation;
 mod named_keys;
@@ -104,3 +104,28 @@ fn should_run_named_keys_contract() {
     assert!(!account.named_keys().contains_key(KEY1));
     assert!(!account.named_keys().contains_key(KEY2));
 }
+
+#[ignore]
+#[test]
+fn should_overwrite_named_key_with_put_key() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    run_command(&mut builder, COMMAND_CREATE_UREF1);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let first_uref = *account.named_keys().get(KEY1).expect("should have key");
+
+    run_command(&mut builder, COMMAND_CREATE_UREF1);
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    let second_uref = *account.named_keys().get(KEY1).expect("should have key");
+    assert_ne!(first_uref, second_uref);
+    let value: String = read_value(&mut builder, second_uref);
+    assert_eq!(value, "Hello, world!");
+}
@@ -0,0 +1,100 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{ApiError, CLValue, Key};
+
+const CONTRACT_VIEW_FUNCTION_STORED: &str = "view_function_stored.wasm";
+const CONTRACT_VIEW_FUNCTION_CALLER: &str = "view_function_caller.wasm";
+const CONTRACT_NAME: &str = "view_function_stored";
+const RESULT_KEY_NAME: &str = "result";
+const ERROR_KEY_NAME: &str = "error";
+const WRITTEN_KEY_NAME: &str = "written";
+const METHOD_READ: &str = "read";
+const METHOD_WRITE: &str = "write";
+const METHOD_CREATE_PURSE: &str = "create_purse";
+const METHOD_NESTED_WRITE: &str = "nested_write";
+const EXPECTED_VALUE: u64 = 42;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_VIEW_FUNCTION_STORED, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_view_function(builder: &mut InMemoryWasmTestBuilder, method: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_VIEW_FUNCTION_CALLER,
+        (method,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn query_account_named_key(builder: &InMemoryWasmTestBuilder, name: &str) -> CLValue {
+    match builder.query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name]) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value,
+        other => panic!("expected CLValue under {}, got {:?}", name, other),
+    }
+}
+
+fn assert_read_only_context_error(builder: &InMemoryWasmTestBuilder) {
+    let error: u32 = query_account_named_key(builder, ERROR_KEY_NAME)
+        .into_t()
+        .expect("should be u32");
+    assert_eq!(error, u32::from(ApiError::ReadOnlyContext));
+
+    // Nothing was written by the callee.
+    let result = builder.query(
+        None,
+        Key::Account(DEFAULT_ACCOUNT_ADDR),
+        &[CONTRACT_NAME, WRITTEN_KEY_NAME],
+    );
+    assert!(result.is_err(), "{:?}", result);
+}
+
+#[ignore]
+#[test]
+fn should_return_value_from_read_only_call() {
+    let mut builder = setup();
+    call_view_function(&mut builder, METHOD_READ);
+
+    let value: u64 = query_account_named_key(&builder, RESULT_KEY_NAME)
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(value, EXPECTED_VALUE);
+}
+
+#[ignore]
+#[test]
+fn should_return_read_only_context_error_to_caller_on_write() {
+    let mut builder = setup();
+    call_view_function(&mut builder, METHOD_WRITE);
+    assert_read_only_context_error(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_return_read_only_context_error_to_caller_on_create_purse() {
+    let mut builder = setup();
+    call_view_function(&mut builder, METHOD_CREATE_PURSE);
+    assert_read_only_context_error(&builder);
+}
+
+#[ignore]
+#[test]
+fn should_inherit_read_only_context_in_nested_call() {
+    let mut builder = setup();
+    call_view_function(&mut builder, METHOD_NESTED_WRITE);
+    assert_read_only_context_error(&builder);
+}
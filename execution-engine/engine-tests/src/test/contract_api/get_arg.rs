@@ -1,11 +1,10 @@
 use contract::args_parser::ArgsParser;
+use engine_core::engine_state::exit_code::{ExitCode, SystemErrorKind};
 use engine_test_support::{
-    internal::{
-        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
-    },
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{ApiError, U512};
+use types::U512;
 
 #[derive(Debug)]
 #[repr(u16)]
@@ -17,12 +16,13 @@ enum GetArgContractError {
 }
 
 const CONTRACT_GET_ARG: &str = "get_arg.wasm";
+const CONTRACT_GET_TYPED_ARG: &str = "get_typed_arg.wasm";
 const ARG0_VALUE: &str = "Hello, world!";
 const ARG1_VALUE: u64 = 42;
 
-/// Calls get_arg contract and returns Ok(()) in case no error, or String which is the error message
-/// returned by the engine
-fn call_get_arg(args: impl ArgsParser) -> Result<(), String> {
+/// Calls get_arg contract and returns Ok(()) in case no error, or the exit code of the failed
+/// deploy
+fn call_get_arg(args: impl ArgsParser) -> Result<(), ExitCode> {
     let exec_request =
         ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_GET_ARG, args).build();
     let result = InMemoryWasmTestBuilder::default()
@@ -35,14 +35,12 @@ fn call_get_arg(args: impl ArgsParser) -> Result<(), String> {
         return Ok(());
     }
 
-    let response = result
+    let exit_code = result
         .builder()
-        .get_exec_response(0)
+        .get_exit_code(0)
         .expect("should have a response");
 
-    let error_message = utils::get_error_message(response);
-
-    Err(error_message)
+    Err(exit_code)
 }
 
 #[ignore]
@@ -55,36 +53,78 @@ fn should_use_passed_argument() {
 #[ignore]
 #[test]
 fn should_revert_with_missing_arg() {
-    assert!(call_get_arg(())
-        .expect_err("should fail")
-        .contains(&format!(
-            "{:?}",
-            ApiError::User(GetArgContractError::MissingArgument0 as u16),
-        )));
-    assert!(call_get_arg((String::from(ARG0_VALUE),))
-        .expect_err("should fail")
-        .contains(&format!(
-            "{:?}",
-            ApiError::User(GetArgContractError::MissingArgument1 as u16),
-        )));
+    assert_eq!(
+        call_get_arg(()),
+        Err(ExitCode::UserRevert(
+            GetArgContractError::MissingArgument0 as u16
+        ))
+    );
+    assert_eq!(
+        call_get_arg((String::from(ARG0_VALUE),)),
+        Err(ExitCode::UserRevert(
+            GetArgContractError::MissingArgument1 as u16
+        ))
+    );
 }
 
 #[ignore]
 #[test]
 fn should_revert_with_invalid_argument() {
-    assert!(call_get_arg((U512::from(123),))
-        .expect_err("should fail")
-        .contains(&format!(
-            "{:?}",
-            ApiError::User(GetArgContractError::InvalidArgument0 as u16)
-        )));
-    assert!(call_get_arg((
-        String::from(ARG0_VALUE),
-        String::from("this is expected to be U512")
-    ))
-    .expect_err("should fail")
-    .contains(&format!(
-        "{:?}",
-        ApiError::User(GetArgContractError::InvalidArgument1 as u16),
-    )));
+    assert_eq!(
+        call_get_arg((U512::from(123),)),
+        Err(ExitCode::UserRevert(
+            GetArgContractError::InvalidArgument0 as u16
+        ))
+    );
+    assert_eq!(
+        call_get_arg((
+            String::from(ARG0_VALUE),
+            String::from("this is expected to be U512")
+        )),
+        Err(ExitCode::UserRevert(
+            GetArgContractError::InvalidArgument1 as u16
+        ))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_read_typed_argument_passed_with_matching_type() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_TYPED_ARG,
+        (U512::from(ARG1_VALUE),),
+    )
+    .build();
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+fn should_name_both_types_when_typed_argument_mismatches() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_TYPED_ARG,
+        (ARG1_VALUE,),
+    )
+    .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    assert_eq!(
+        builder.get_exit_code(0),
+        Some(ExitCode::SystemError(SystemErrorKind::ArgumentTypeMismatch))
+    );
+    let message = builder
+        .exec_error_message(0)
+        .expect("should have an error message");
+    assert!(message.contains("U512"), "{}", message);
+    assert!(message.contains("U64"), "{}", message);
 }
@@ -0,0 +1,124 @@
+use std::convert::TryFrom;
+
+use lazy_static::lazy_static;
+
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, system_contract_errors::mint, ApiError, CLValue, Key, U512};
+
+const CONTRACT_GET_BALANCE_BY_UREF: &str = "get_balance_by_uref.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const CHECKER_CONTRACT_NAME: &str = "balance_checker";
+const CHECKED_BALANCE_KEY_NAME: &str = "checked_balance";
+const METHOD_INSTALL: &str = "install";
+const METHOD_CHECK: &str = "check";
+
+lazy_static! {
+    static ref PURSE_AMOUNT: U512 = U512::from(12_345);
+}
+
+/// Installs the balance checker from account 1, returning the builder and the checker's key.
+fn setup() -> (InMemoryWasmTestBuilder, Key) {
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT),
+    )
+    .build();
+    let install_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_GET_BALANCE_BY_UREF,
+        (METHOD_INSTALL,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(fund_request)
+        .expect_success()
+        .commit()
+        .exec(install_request)
+        .expect_success()
+        .commit();
+
+    let checker = *builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1")
+        .named_keys()
+        .get(CHECKER_CONTRACT_NAME)
+        .expect("should have balance checker");
+    (builder, checker)
+}
+
+/// Has the default account create a purse and pass it to the checker of account 1.
+fn check_balance(builder: &mut InMemoryWasmTestBuilder, checker: Key, case: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_BALANCE_BY_UREF,
+        (METHOD_CHECK, checker, case, *PURSE_AMOUNT),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn query_checked_balance(builder: &InMemoryWasmTestBuilder) -> Option<U512> {
+    let stored_value = builder
+        .query(
+            None,
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            &[CHECKED_BALANCE_KEY_NAME],
+        )
+        .expect("should have checked balance");
+    CLValue::try_from(stored_value)
+        .expect("should be a CLValue")
+        .into_t()
+        .expect("should be an Option<U512>")
+}
+
+#[ignore]
+#[test]
+fn should_get_balance_of_other_accounts_purse_by_read_only_uref() {
+    let (mut builder, checker) = setup();
+
+    check_balance(&mut builder, checker, "read");
+
+    assert!(!builder.is_error(), "{:?}", builder.exec_error_message(2));
+    assert_eq!(query_checked_balance(&builder), Some(*PURSE_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_get_no_balance_of_unknown_purse_by_uref() {
+    let (mut builder, checker) = setup();
+
+    check_balance(&mut builder, checker, "not_a_purse");
+
+    assert!(!builder.is_error(), "{:?}", builder.exec_error_message(2));
+    assert_eq!(query_checked_balance(&builder), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_get_balance_by_uref_without_read_access() {
+    let (mut builder, checker) = setup();
+
+    check_balance(&mut builder, checker, "add");
+
+    let expected_error = ApiError::from(mint::Error::InvalidAccessRights);
+    let error_message = builder
+        .exec_error_message(2)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains(&format!("{:?}", expected_error)),
+        "expected {:?}, got: {}",
+        expected_error,
+        error_message
+    );
+}
@@ -2,6 +2,7 @@ use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
 };
+use types::BlockTime;
 
 const CONTRACT_GET_BLOCKTIME: &str = "get_blocktime.wasm";
 
@@ -14,7 +15,7 @@ fn should_run_get_blocktime_contract() {
         CONTRACT_GET_BLOCKTIME,
         (block_time,),
     )
-    .with_block_time(block_time)
+    .with_block_time(BlockTime::from_millis(block_time))
     .build();
     InMemoryWasmTestBuilder::default()
         .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
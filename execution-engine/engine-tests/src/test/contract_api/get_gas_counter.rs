@@ -0,0 +1,49 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, U512};
+
+const CONTRACT_GET_GAS_COUNTER: &str = "get_gas_counter.wasm";
+const GAS_COUNTER_READINGS_KEY_NAME: &str = "gas_counter_readings";
+const ITERATIONS: u32 = 10;
+
+#[ignore]
+#[test]
+fn should_get_monotonically_increasing_gas_counter() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_GET_GAS_COUNTER,
+        (ITERATIONS,),
+    )
+    .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let readings: Vec<u64> = match builder.query(
+        None,
+        Key::Account(DEFAULT_ACCOUNT_ADDR),
+        &[GAS_COUNTER_READINGS_KEY_NAME],
+    ) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be Vec<u64>"),
+        other => panic!("unexpected query result {:?}", other),
+    };
+
+    assert_eq!(readings.len(), ITERATIONS as usize);
+    assert!(readings[0] > 0, "the first call should be charged for");
+    for pair in readings.windows(2) {
+        assert!(
+            pair[0] < pair[1],
+            "gas counter should increase: {:?}",
+            readings
+        );
+    }
+    // the session's gas is part of the cost of the deploy
+    let cost = builder.exec_costs(0)[0];
+    assert!(cost.value() >= U512::from(*readings.last().unwrap()));
+}
@@ -0,0 +1,76 @@
+use engine_core::engine_state::exit_code::ExitCode;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::U512;
+
+#[derive(Debug)]
+#[repr(u16)]
+enum GetNamedArgContractError {
+    MissingAmount = 1,
+}
+
+const CONTRACT_GET_NAMED_ARG: &str = "get_named_arg.wasm";
+const ARG0_VALUE: &str = "Hello, world!";
+const AMOUNT_VALUE: u64 = 42;
+
+/// Calls get_named_arg contract with its session args named `arg_names`, and returns Ok(()) in case
+/// no error, or the exit code of the failed deploy
+fn call_get_named_arg(arg_names: &[&str]) -> Result<(), ExitCode> {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_GET_NAMED_ARG,
+            (String::from(ARG0_VALUE), U512::from(AMOUNT_VALUE)),
+        )
+        .with_session_arg_names(arg_names)
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit()
+        .finish();
+
+    if !result.builder().is_error() {
+        return Ok(());
+    }
+
+    let exit_code = result
+        .builder()
+        .get_exit_code(0)
+        .expect("should have a response");
+
+    Err(exit_code)
+}
+
+#[ignore]
+#[test]
+fn should_read_args_by_name_and_position() {
+    call_get_named_arg(&["", "amount"])
+        .expect("should read the unnamed arg by position and the named one by name");
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_missing_named_arg() {
+    assert_eq!(
+        call_get_named_arg(&[]),
+        Err(ExitCode::UserRevert(
+            GetNamedArgContractError::MissingAmount as u16
+        ))
+    );
+    assert_eq!(
+        call_get_named_arg(&["", "value"]),
+        Err(ExitCode::UserRevert(
+            GetNamedArgContractError::MissingAmount as u16
+        ))
+    );
+}
@@ -0,0 +1,139 @@
+use engine_core::engine_state::exit_code::{ExitCode, SystemErrorKind};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::U512;
+
+const TEN_MEGABYTES: u32 = 10 * 1024 * 1024;
+
+/// Returns session code which passes the bytes at the start of its memory to `host_function` as a
+/// string of `size` serialized bytes.  The bytes are a little-endian `u32` length prefix of
+/// `length` followed by `string_bytes`.
+fn session_passing_string(
+    host_function: &str,
+    length: u32,
+    string_bytes: &[u8],
+    size: u32,
+) -> Vec<u8> {
+    let (signature, extra_args) = match host_function {
+        "has_key" => ("(param i32 i32) (result i32)", "call $host drop"),
+        "put_key" => (
+            "(param i32 i32 i32 i32)",
+            "i32.const 0 i32.const 0 call $host",
+        ),
+        _ => panic!("unexpected host function {}", host_function),
+    };
+    let data: String = length
+        .to_le_bytes()
+        .iter()
+        .chain(string_bytes)
+        .map(|byte| format!("\\{:02x}", byte))
+        .collect();
+    let wat = format!(
+        r#"
+(module
+    (import "env" "{}" (func $host {}))
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (data (i32.const 0) "{}")
+    (func (export "call")
+      i32.const 0
+      i32.const {}
+      {}))
+"#,
+        host_function, signature, data, size, extra_args
+    );
+    wabt::wat2wasm(wat).expect("should parse")
+}
+
+/// Executes each of `sessions` in its own deploy, returning the builder.
+fn exec_sessions(sessions: Vec<Vec<u8>>) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    for (index, session) in sessions.into_iter().enumerate() {
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_bytes(session, ())
+            .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([index as u8 + 1; 32])
+            .build();
+        let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(exec_request).commit();
+    }
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_reject_name_with_invalid_utf8() {
+    let invalid_utf8 = [0xff, 0xfe, 0xfd];
+    let size = 4 + invalid_utf8.len() as u32;
+    let builder = exec_sessions(vec![
+        session_passing_string("has_key", 3, &invalid_utf8, size),
+        session_passing_string("put_key", 3, &invalid_utf8, size),
+    ]);
+
+    for exec_index in 0..2 {
+        assert_eq!(
+            builder.get_exit_code(exec_index),
+            Some(ExitCode::SystemError(SystemErrorKind::InvalidUtf8))
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_reject_name_longer_than_max_string_length() {
+    let max_length = DEFAULT_WASM_COSTS.max_string_length;
+    assert_ne!(max_length, 0, "test requires a max string length");
+    let too_long = vec![b'a'; max_length as usize + 1];
+    let builder = exec_sessions(vec![
+        session_passing_string("put_key", max_length + 1, &too_long, 4 + max_length + 1),
+        // larger than the Wasm memory, so this must be rejected before the name is read
+        session_passing_string("has_key", TEN_MEGABYTES, &[], 4 + TEN_MEGABYTES),
+    ]);
+
+    for exec_index in 0..2 {
+        assert_eq!(
+            builder.get_exit_code(exec_index),
+            Some(ExitCode::SystemError(SystemErrorKind::StringTooLong))
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_charge_for_each_byte_of_a_name() {
+    let short_name = b"abc";
+    let long_name = b"abcdefghijklm";
+    let builder = exec_sessions(vec![
+        session_passing_string(
+            "has_key",
+            short_name.len() as u32,
+            short_name,
+            4 + short_name.len() as u32,
+        ),
+        session_passing_string(
+            "has_key",
+            long_name.len() as u32,
+            long_name,
+            4 + long_name.len() as u32,
+        ),
+    ]);
+    for exec_index in 0..2 {
+        assert_eq!(builder.get_exit_code(exec_index), Some(ExitCode::Success));
+    }
+
+    let short_name_cost = builder.exec_costs(0)[0];
+    let long_name_cost = builder.exec_costs(1)[0];
+    let extra_bytes = long_name.len() - short_name.len();
+    let expected_extra_cost =
+        Gas::new(U512::from(DEFAULT_WASM_COSTS.string_byte) * U512::from(extra_bytes));
+    assert_eq!(long_name_cost, short_name_cost + expected_extra_cost);
+}
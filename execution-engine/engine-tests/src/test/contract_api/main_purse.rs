@@ -1,15 +1,18 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
 use engine_shared::stored_value::StoredValue;
 use engine_test_support::{
     internal::{
-        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
         DEFAULT_RUN_GENESIS_REQUEST,
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::PublicKey, Key};
+use types::{account::PublicKey, Key, URef, U512};
 
 const CONTRACT_MAIN_PURSE: &str = "main_purse.wasm";
+const CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE: &str = "transfer_main_purse_to_new_purse.wasm";
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const NEW_PURSE_NAME: &str = "purse:1";
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
 
 #[ignore]
@@ -68,3 +71,51 @@ fn should_run_main_purse_contract_account_1() {
 
     builder.exec(exec_request_2).expect_success().commit();
 }
+
+fn main_purse_deploy_request(purse: URef) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_MAIN_PURSE, ())
+        .with_purse_arg(0, purse)
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    ExecuteRequestBuilder::from_deploy_item(deploy).build()
+}
+
+#[ignore]
+#[test]
+fn should_pass_purse_args_to_session_code() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_MAIN_PURSE_TO_NEW_PURSE,
+        (NEW_PURSE_NAME, U512::from(1)),
+    )
+    .build();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should get account");
+    let new_purse = default_account
+        .named_keys()
+        .get(NEW_PURSE_NAME)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have new purse");
+
+    // The contract only succeeds if its purse arg is the account's main purse.
+    builder
+        .exec(main_purse_deploy_request(default_account.main_purse()))
+        .expect_success()
+        .commit();
+    assert!(builder
+        .exec(main_purse_deploy_request(new_purse))
+        .is_error());
+}
@@ -1,17 +1,34 @@
 mod account;
+mod call_contract_read_only;
 mod create_purse;
 mod get_arg;
+mod get_balance_by_uref;
 mod get_blocktime;
 mod get_caller;
+mod get_gas_counter;
+mod get_named_arg;
 mod get_phase;
+mod host_strings;
 mod list_named_keys;
+mod local_key_migration;
 mod local_state;
 mod main_purse;
+mod named_keys_isolation;
 mod mint_purse;
+mod nested_uref_args;
+mod public_uref;
+mod pure_function;
+mod purse_existence;
+mod reserved_key_names;
 mod revert;
+mod scheduled_calls;
+mod session_return_value;
 mod subcall;
 mod transfer;
 mod transfer_purse_to_account;
 mod transfer_purse_to_purse;
 mod transfer_stored;
 mod transfer_u512_stored;
+mod transient_storage;
+mod try_call_contract;
+mod uref_narrowing;
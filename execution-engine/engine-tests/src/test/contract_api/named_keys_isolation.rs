@@ -0,0 +1,113 @@
+use std::collections::BTreeMap;
+
+use engine_core::engine_state::exit_code::{ExitCode, SystemErrorKind};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_NAMED_KEYS_ISOLATION: &str = "named_keys_isolation.wasm";
+const CONTRACT_NAMED_KEYS_ISOLATION_CALL: &str = "named_keys_isolation_call.wasm";
+const HASH_CONTRACT_NAME: &str = "isolation_hash";
+const UREF_CONTRACT_NAME: &str = "isolation_uref";
+const METHOD_PUT: &str = "put";
+const METHOD_PUT_VIA: &str = "put_via";
+const METHOD_ADD: &str = "add";
+const KEY_NAME: &str = "isolated";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_NAMED_KEYS_ISOLATION, ())
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call(builder: &mut InMemoryWasmTestBuilder, method: &str, contract_name: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_NAMED_KEYS_ISOLATION_CALL,
+        (method, contract_name, KEY_NAME),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn account_named_keys(builder: &InMemoryWasmTestBuilder) -> BTreeMap<String, Key> {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .clone()
+}
+
+fn contract_named_keys(
+    builder: &InMemoryWasmTestBuilder,
+    contract_name: &str,
+) -> BTreeMap<String, Key> {
+    let contract_key: Key = account_named_keys(builder)[contract_name];
+    match builder.query(None, contract_key, &[]) {
+        Ok(StoredValue::Contract(contract)) => contract.named_keys().clone(),
+        other => panic!("should have contract, got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_confine_put_key_to_called_contract() {
+    for contract_name in &[HASH_CONTRACT_NAME, UREF_CONTRACT_NAME] {
+        let mut builder = setup();
+        call(&mut builder, METHOD_PUT, contract_name);
+        builder.expect_success();
+
+        let (contract_has_key, account_has_key): (bool, bool) = builder
+            .get_session_return_value(1)
+            .expect("should return value")
+            .into_t()
+            .expect("should be a pair of bools");
+        assert!(contract_has_key, "{}", contract_name);
+        assert!(!account_has_key, "{}", contract_name);
+
+        assert!(contract_named_keys(&builder, contract_name).contains_key(KEY_NAME));
+        assert!(!account_named_keys(&builder).contains_key(KEY_NAME));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_see_put_key_of_reentrant_call() {
+    for contract_name in &[HASH_CONTRACT_NAME, UREF_CONTRACT_NAME] {
+        let mut builder = setup();
+        call(&mut builder, METHOD_PUT_VIA, contract_name);
+        builder.expect_success();
+
+        let has_key: bool = builder
+            .get_session_return_value(1)
+            .expect("should return value")
+            .into_t()
+            .expect("should be a bool");
+        assert!(has_key, "{}", contract_name);
+        assert!(contract_named_keys(&builder, contract_name).contains_key(KEY_NAME));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_add_named_key_to_other_entity() {
+    let mut builder = setup();
+    call(&mut builder, METHOD_ADD, UREF_CONTRACT_NAME);
+
+    // holding an ADD uref to a contract doesn't allow modifying its named keys
+    assert_eq!(
+        builder.get_exit_code(1),
+        Some(ExitCode::SystemError(SystemErrorKind::InvalidAccess))
+    );
+    assert!(!contract_named_keys(&builder, UREF_CONTRACT_NAME).contains_key(KEY_NAME));
+}
@@ -0,0 +1,52 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_NESTED_UREF_ARGS: &str = "nested_uref_args.wasm";
+const UREF_NAMES: [&str; 3] = ["uref_a", "uref_b", "uref_c"];
+const WRITTEN_VALUE: u64 = 42;
+
+fn run(narrow: bool) -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_NESTED_UREF_ARGS, (narrow,))
+            .build();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_pass_urefs_nested_in_args_to_callee() {
+    let mut builder = run(false);
+    builder.expect_success().commit();
+
+    for name in UREF_NAMES.iter() {
+        let value: u64 = match builder.query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name]) {
+            Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be u64"),
+            other => panic!("unexpected query result {:?}", other),
+        };
+        assert_eq!(value, WRITTEN_VALUE, "{} should have been written", name);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_pass_nested_urefs_with_narrowed_rights_only() {
+    let builder = run(true);
+
+    // the callee was only granted read access to the urefs, so its writes fail
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_message.contains("Invalid access rights"),
+        "{}",
+        error_message
+    );
+}
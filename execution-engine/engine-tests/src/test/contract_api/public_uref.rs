@@ -0,0 +1,124 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, AccessRights, CLValue, Key, URef, U512};
+
+const CONTRACT_PUBLIC_UREF_PUBLISH: &str = "public_uref_publish.wasm";
+const CONTRACT_PUBLIC_UREF_READ: &str = "public_uref_read.wasm";
+const CONTRACT_PUBLIC_UREF_WRITE: &str = "public_uref_write.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const PRICE_KEY: &str = "price";
+const SECRET_KEY: &str = "secret";
+const PRICE: u64 = 42;
+
+/// Funds `ACCOUNT_1_ADDR` and publishes `PRICE` from the default account.
+fn setup() -> InMemoryWasmTestBuilder {
+    let fund_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT),
+    )
+    .build();
+    let publish_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PUBLIC_UREF_PUBLISH,
+        (U512::from(PRICE),),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(fund_request)
+        .expect_success()
+        .commit()
+        .exec(publish_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn get_uref(builder: &InMemoryWasmTestBuilder, account: PublicKey, name: &str) -> URef {
+    let account = builder.get_account(account).expect("should have account");
+    account
+        .named_keys()
+        .get(name)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have uref")
+}
+
+fn assert_forged_reference(builder: &InMemoryWasmTestBuilder, exec_index: usize) {
+    let error_message = builder
+        .exec_error_message(exec_index)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains("ForgedReference"),
+        "expected ForgedReference, got: {}",
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_read_public_uref_of_another_account() {
+    let mut builder = setup();
+    let price_uref = get_uref(&builder, DEFAULT_ACCOUNT_ADDR, PRICE_KEY);
+    assert!(price_uref.is_publicly_readable());
+
+    let read_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_PUBLIC_UREF_READ, (price_uref,))
+            .build();
+    builder.exec(read_request).expect_success().commit();
+
+    let read_price = builder
+        .query(None, Key::Account(ACCOUNT_1_ADDR), &[PRICE_KEY])
+        .expect("should have price");
+    let expected_price = StoredValue::CLValue(CLValue::from_t(U512::from(PRICE)).unwrap());
+    assert_eq!(read_price, expected_price);
+}
+
+#[ignore]
+#[test]
+fn should_not_write_public_uref_of_another_account() {
+    let mut builder = setup();
+    let price_uref = get_uref(&builder, DEFAULT_ACCOUNT_ADDR, PRICE_KEY);
+
+    let write_request = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_PUBLIC_UREF_WRITE,
+        (price_uref, U512::zero()),
+    )
+    .build();
+    builder.exec(write_request).commit();
+
+    assert_forged_reference(&builder, 2);
+    let price = builder
+        .query(None, Key::URef(price_uref), &[])
+        .expect("should have price");
+    let expected_price = StoredValue::CLValue(CLValue::from_t(U512::from(PRICE)).unwrap());
+    assert_eq!(price, expected_price);
+}
+
+#[ignore]
+#[test]
+fn should_not_read_private_uref_with_forged_public_flag() {
+    let mut builder = setup();
+    let secret_uref = get_uref(&builder, DEFAULT_ACCOUNT_ADDR, SECRET_KEY);
+    assert!(!secret_uref.is_publicly_readable());
+    let forged_uref =
+        secret_uref.with_access_rights(AccessRights::READ | AccessRights::PUBLIC_READ);
+
+    let read_request =
+        ExecuteRequestBuilder::standard(ACCOUNT_1_ADDR, CONTRACT_PUBLIC_UREF_READ, (forged_uref,))
+            .build();
+    builder.exec(read_request).commit();
+
+    assert_forged_reference(&builder, 2);
+}
@@ -0,0 +1,238 @@
+use std::time::{Duration, Instant};
+
+use engine_core::engine_state::{
+    deploy_item::DeployItem,
+    exit_code::{ExitCode, SystemErrorKind},
+};
+use engine_shared::{gas::Gas, stored_value::StoredValue};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{Key, PURE_FUNCTION_KEY_NAME, U512};
+
+const CONTRACT_PURE_FUNCTION_STORED: &str = "pure_function_stored.wasm";
+const CONTRACT_PURE_FUNCTION_CALLER: &str = "pure_function_caller.wasm";
+const CONTRACT_PURE_FUNCTION_READ_CALLER: &str = "pure_function_read_caller.wasm";
+const PURE_FUNCTION_NAME: &str = "pure_function";
+const PLAIN_FUNCTION_NAME: &str = "plain_function";
+const IMPURE_FUNCTION_NAME: &str = "impure_function";
+const RESULT_KEY_NAME: &str = "result";
+const WRITTEN_KEY_NAME: &str = "written";
+const CALL_COUNT: u32 = 10;
+const BASE: u64 = 42;
+const READ_VALUE: u64 = 42;
+const PAYMENT: u64 = 1_000_000_000;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_PURE_FUNCTION_STORED, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_function(index: u8, function_name: &str, call_count: u32) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::new().push_deploy(caller_deploy(index, function_name, call_count))
+}
+
+fn caller_deploy(index: u8, function_name: &str, call_count: u32) -> DeployItem {
+    let session_args = (String::from(function_name), call_count, BASE);
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_PURE_FUNCTION_CALLER, session_args)
+        .with_empty_payment_bytes((U512::from(PAYMENT),))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([index; 32])
+        .build()
+}
+
+/// A deploy checking the pure function reading the `read_value` key returns `expected`, then
+/// writing `new_value` under the key and checking the function returns it.
+fn read_caller_deploy(index: u8, expected: u64, new_value: u64) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_PURE_FUNCTION_READ_CALLER, (expected, new_value))
+        .with_empty_payment_bytes((U512::from(PAYMENT),))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([index; 32])
+        .build()
+}
+
+/// Executes a deploy calling `function_name` `call_count` times and returns its cost.
+fn call_cost(builder: &mut InMemoryWasmTestBuilder, function_name: &str, call_count: u32) -> Gas {
+    let exec_request = call_function(call_count as u8, function_name, call_count).build();
+    builder.exec(exec_request).expect_success();
+    let exec_index = builder.get_exec_responses_count() - 1;
+    builder.exec_costs(exec_index)[0]
+}
+
+fn query_result(builder: &InMemoryWasmTestBuilder) -> u64 {
+    match builder.query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[RESULT_KEY_NAME]) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be u64"),
+        other => panic!(
+            "expected CLValue under {}, got {:?}",
+            RESULT_KEY_NAME, other
+        ),
+    }
+}
+
+fn time_call(builder: &mut InMemoryWasmTestBuilder, function_name: &str) -> Duration {
+    let exec_request = call_function(0, function_name, CALL_COUNT).build();
+    let start = Instant::now();
+    builder.exec(exec_request).expect_success();
+    start.elapsed()
+}
+
+#[ignore]
+#[test]
+fn should_return_same_result_as_plain_function() {
+    let mut builder = setup();
+
+    let exec_request = call_function(0, PURE_FUNCTION_NAME, CALL_COUNT).build();
+    builder.exec(exec_request).expect_success().commit();
+    let pure_result = query_result(&builder);
+
+    let exec_request = call_function(1, PLAIN_FUNCTION_NAME, 1).build();
+    builder.exec(exec_request).expect_success().commit();
+    let plain_result = query_result(&builder);
+
+    assert_eq!(pure_result, plain_result);
+}
+
+#[ignore]
+#[test]
+fn should_charge_same_gas_for_each_call_in_deploy() {
+    let mut builder = setup();
+
+    let cost_of_one = call_cost(&mut builder, PURE_FUNCTION_NAME, 1);
+    let cost_of_two = call_cost(&mut builder, PURE_FUNCTION_NAME, 2);
+    let cost_of_ten = call_cost(&mut builder, PURE_FUNCTION_NAME, CALL_COUNT);
+
+    // Calls served from the cache are charged as much as the call which was executed.
+    let cost_per_call = cost_of_two - cost_of_one;
+    assert_eq!(
+        cost_of_ten - cost_of_one,
+        cost_per_call * Gas::new(U512::from(CALL_COUNT - 1))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_charge_same_gas_for_each_deploy_in_block() {
+    let mut builder = setup();
+
+    let exec_request = (0..CALL_COUNT as u8)
+        .fold(ExecuteRequestBuilder::new(), |exec_request, index| {
+            exec_request.push_deploy(caller_deploy(index, PURE_FUNCTION_NAME, 1))
+        })
+        .build();
+    builder.exec(exec_request).expect_success();
+
+    let costs = builder.exec_costs(1);
+    assert_eq!(costs.len(), CALL_COUNT as usize);
+    assert!(costs.iter().all(|cost| *cost == costs[0]), "{:?}", costs);
+}
+
+#[ignore]
+#[test]
+fn should_run_repeated_pure_calls_faster() {
+    let mut builder = setup();
+
+    let pure_elapsed = time_call(&mut builder, PURE_FUNCTION_NAME);
+    let plain_elapsed = time_call(&mut builder, PLAIN_FUNCTION_NAME);
+
+    // Only the first of the pure calls is executed.
+    assert!(
+        pure_elapsed * 2 < plain_elapsed,
+        "pure: {:?}, plain: {:?}",
+        pure_elapsed,
+        plain_elapsed
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_if_function_declared_pure_modifies_global_state() {
+    let mut builder = setup();
+
+    let exec_request = call_function(0, IMPURE_FUNCTION_NAME, 1).build();
+    builder.exec(exec_request).commit();
+
+    assert_eq!(
+        builder.get_exit_code(1),
+        Some(ExitCode::SystemError(SystemErrorKind::InvalidAccess))
+    );
+    let message = builder
+        .exec_error_message(1)
+        .expect("should have an error message");
+    assert!(message.contains("declared pure"), "{}", message);
+
+    let result = builder.query(
+        None,
+        Key::Account(DEFAULT_ACCOUNT_ADDR),
+        &[IMPURE_FUNCTION_NAME, WRITTEN_KEY_NAME],
+    );
+    assert!(result.is_err(), "{:?}", result);
+}
+
+#[ignore]
+#[test]
+fn should_mark_only_function_declared_pure() {
+    let builder = setup();
+
+    let function_key = |name: &str| {
+        builder
+            .get_account(DEFAULT_ACCOUNT_ADDR)
+            .expect("should have account")
+            .named_keys()
+            .get(name)
+            .cloned()
+            .expect("should have function key")
+    };
+    let named_keys = |name: &str| match builder.query(None, function_key(name), &[]) {
+        Ok(StoredValue::Contract(contract)) => contract.take_named_keys(),
+        other => panic!("expected Contract under {}, got {:?}", name, other),
+    };
+
+    assert_eq!(
+        named_keys(PURE_FUNCTION_NAME).get(PURE_FUNCTION_KEY_NAME),
+        Some(&function_key(PURE_FUNCTION_NAME))
+    );
+    assert!(!named_keys(PLAIN_FUNCTION_NAME).contains_key(PURE_FUNCTION_KEY_NAME));
+}
+
+#[ignore]
+#[test]
+fn should_not_serve_pure_call_once_read_value_changes() {
+    let mut builder = setup();
+
+    // Each deploy of a request executes against the parent state, so the second deploy doesn't see
+    // the value written by the first one, even though the first one called the pure function with
+    // the same arguments after its write.
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(read_caller_deploy(0, READ_VALUE, READ_VALUE + 1))
+        .push_deploy(read_caller_deploy(1, READ_VALUE, READ_VALUE + 2))
+        .build();
+    builder.exec(exec_request);
+
+    let exec_index = builder.get_exec_responses_count() - 1;
+    let results = builder
+        .get_exec_response(exec_index)
+        .expect("should have exec response");
+    assert_eq!(results.len(), 2);
+    assert!(
+        results.iter().all(|result| !result.is_failure()),
+        "{:?}",
+        results
+    );
+}
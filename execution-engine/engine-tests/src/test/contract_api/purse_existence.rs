@@ -0,0 +1,122 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::upgrade::ActivationPoint;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    bytesrepr::FromBytes, system_contract_errors::mint, ApiError, CLTyped, CLValue, Key,
+    ProtocolVersion, U512,
+};
+
+const CONTRACT_PURSE_EXISTENCE: &str = "purse_existence.wasm";
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+fn query_named_value<T: CLTyped + FromBytes>(builder: &InMemoryWasmTestBuilder, name: &str) -> T {
+    let stored_value = builder
+        .query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), &[name])
+        .unwrap_or_else(|error| panic!("should have {}: {}", name, error));
+    CLValue::try_from(stored_value)
+        .expect("should be a CLValue")
+        .into_t()
+        .expect("should have the expected type")
+}
+
+fn run_purse_existence(protocol_version: ProtocolVersion) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    if protocol_version != ProtocolVersion::V1_0_0 {
+        let mut upgrade_request = UpgradeRequestBuilder::new()
+            .with_current_protocol_version(ProtocolVersion::V1_0_0)
+            .with_new_protocol_version(protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .build();
+        builder.upgrade_with_upgrade_request(&mut upgrade_request);
+        assert!(
+            builder
+                .get_upgrade_response(0)
+                .expect("should have response")
+                .has_success(),
+            "expected successful upgrade"
+        );
+    }
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_PURSE_EXISTENCE, ())
+            .with_protocol_version(protocol_version)
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_report_purse_existence_and_balance() {
+    let builder = run_purse_existence(ProtocolVersion::V1_0_0);
+
+    assert!(query_named_value::<bool>(&builder, "main_purse_exists"));
+    assert!(query_named_value::<bool>(&builder, "created_purse_exists"));
+    assert_eq!(
+        query_named_value::<Option<U512>>(&builder, "created_purse_balance"),
+        Some(U512::one())
+    );
+
+    assert!(!query_named_value::<bool>(&builder, "not_a_purse_exists"));
+    assert_eq!(
+        query_named_value::<Option<U512>>(&builder, "not_a_purse_balance"),
+        None
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_transfer_to_non_purse_with_transfer_error_before_1_1_0() {
+    let builder = run_purse_existence(ProtocolVersion::V1_0_0);
+
+    assert_eq!(
+        query_named_value::<String>(&builder, "transfer_result"),
+        format!("{:?}", Result::<(), _>::Err(ApiError::Transfer))
+    );
+    assert_eq!(
+        query_named_value::<String>(&builder, "mint_transfer_result"),
+        format!(
+            "{:?}",
+            Result::<(), _>::Err(mint::Error::DestinationPurseNotFound)
+        )
+    );
+    // the failed transfers didn't make a purse of the destination
+    assert!(!query_named_value::<bool>(
+        &builder,
+        "not_a_purse_exists_after_transfers"
+    ));
+}
+
+#[ignore]
+#[test]
+fn should_fail_transfer_to_non_purse_with_destination_purse_not_found() {
+    let builder = run_purse_existence(ProtocolVersion::from_parts(1, 1, 0));
+
+    assert_eq!(
+        query_named_value::<String>(&builder, "transfer_result"),
+        format!(
+            "{:?}",
+            Result::<(), _>::Err(ApiError::from(mint::Error::DestinationPurseNotFound))
+        )
+    );
+    assert_eq!(
+        query_named_value::<String>(&builder, "mint_transfer_result"),
+        format!(
+            "{:?}",
+            Result::<(), _>::Err(mint::Error::DestinationPurseNotFound)
+        )
+    );
+    assert!(!query_named_value::<bool>(
+        &builder,
+        "not_a_purse_exists_after_transfers"
+    ));
+}
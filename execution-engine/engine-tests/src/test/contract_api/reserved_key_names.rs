@@ -0,0 +1,113 @@
+use std::convert::TryFrom;
+
+use contract::args_parser::ArgsParser;
+use engine_core::engine_state::{
+    exit_code::{ExitCode, SystemErrorKind},
+    upgrade::ActivationPoint,
+};
+use engine_grpc_server::engine_server::ipc::DeployCode;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr::ToBytes, CLValue, Key, ProtocolVersion};
+
+const CONTRACT_PUT_NAMED_KEY: &str = "put_named_key.wasm";
+const RESERVED_NAME: &str = "__system/x";
+const USER_NAME: &str = "system_x";
+const VALUE: u64 = 42;
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const SYSTEM_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
+
+fn put_named_key(builder: &mut InMemoryWasmTestBuilder, name: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PUT_NAMED_KEY,
+        (String::from(name),),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn query_value(builder: &InMemoryWasmTestBuilder, account: PublicKey, name: &str) -> u64 {
+    let stored_value = builder
+        .query(None, Key::Account(account), &[name])
+        .expect("should query named key");
+    CLValue::try_from(stored_value)
+        .expect("should be a CLValue")
+        .into_t()
+        .expect("should be a u64")
+}
+
+#[ignore]
+#[test]
+fn should_not_put_reserved_key_name_from_user_context() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    put_named_key(&mut builder, RESERVED_NAME);
+
+    assert_eq!(
+        builder.get_exit_code(0),
+        Some(ExitCode::SystemError(SystemErrorKind::InvalidAccess))
+    );
+    let message = builder
+        .exec_error_message(0)
+        .expect("should have an error message");
+    assert!(message.contains(RESERVED_NAME), "{}", message);
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account.named_keys().contains_key(RESERVED_NAME));
+}
+
+#[ignore]
+#[test]
+fn should_put_unreserved_key_name_from_user_context() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    put_named_key(&mut builder, USER_NAME);
+
+    assert!(!builder.is_error());
+    assert_eq!(
+        query_value(&builder, DEFAULT_ACCOUNT_ADDR, USER_NAME),
+        VALUE
+    );
+}
+
+#[ignore]
+#[test]
+fn should_put_reserved_key_name_from_system_context() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let mut upgrade_request = {
+        let mut installer_code = DeployCode::new();
+        installer_code.set_code(utils::read_wasm_file_bytes(CONTRACT_PUT_NAMED_KEY));
+        installer_code.set_args(
+            ArgsParser::parse((String::from(RESERVED_NAME),))
+                .expect("should parse args")
+                .into_bytes()
+                .expect("should serialize args"),
+        );
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(ProtocolVersion::from_parts(1, 1, 0))
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_installer_code(installer_code)
+            .build()
+    };
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+    // reserved keys can still be read through a query
+    assert_eq!(query_value(&builder, SYSTEM_ADDR, RESERVED_NAME), VALUE);
+}
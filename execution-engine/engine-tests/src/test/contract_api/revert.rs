@@ -1,9 +1,15 @@
+use engine_core::engine_state::exit_code::ExitCode;
 use engine_test_support::{
-    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS,
+    },
     DEFAULT_ACCOUNT_ADDR,
 };
+use types::ApiError;
 
 const REVERT_WASM: &str = "revert.wasm";
+const USER_ERROR_CODE: u16 = 7;
 
 #[ignore]
 #[test]
@@ -16,3 +22,81 @@ fn should_revert() {
         .commit()
         .is_error();
 }
+
+/// Returns session code which reverts with a user error, attaching `data`.
+fn session_reverting_with_data(data: &[u8]) -> Vec<u8> {
+    let status = u32::from(ApiError::User(USER_ERROR_CODE));
+    let escaped_data: String = data.iter().map(|byte| format!("\\{:02x}", byte)).collect();
+    let wat = format!(
+        r#"
+(module
+    (import "env" "revert_with_data" (func $revert_with_data (param i32 i32 i32)))
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (data (i32.const 0) "{}")
+    (func (export "call")
+      i32.const {}
+      i32.const 0
+      i32.const {}
+      call $revert_with_data))
+"#,
+        escaped_data,
+        status,
+        data.len()
+    );
+    wabt::wat2wasm(wat).expect("should parse")
+}
+
+/// Executes `session` in each of `count` deploys, returning the builder.
+fn exec_session(session: Vec<u8>, count: u8) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    for index in 0..count {
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_bytes(session.clone(), ())
+            .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([index + 1; 32])
+            .build();
+        let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+        builder.exec(exec_request).commit();
+    }
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_revert_with_data() {
+    let data = b"src/main.rs:12: amount > U512::zero()";
+    let builder = exec_session(session_reverting_with_data(data), 1);
+
+    assert_eq!(
+        builder.get_exit_code(0),
+        Some(ExitCode::UserRevert(USER_ERROR_CODE))
+    );
+    assert_eq!(builder.get_revert_data(0), Some(data.to_vec()));
+}
+
+#[ignore]
+#[test]
+fn should_truncate_revert_data_longer_than_max_revert_data_bytes() {
+    let max_length = DEFAULT_WASM_COSTS.max_revert_data_bytes as usize;
+    assert_ne!(max_length, 0, "test requires a max revert data size");
+    let data: Vec<u8> = (0..2 * max_length)
+        .map(|index| b'a' + (index % 26) as u8)
+        .collect();
+    let builder = exec_session(session_reverting_with_data(&data), 2);
+
+    // the same leading bytes are kept by every execution
+    for exec_index in 0..2 {
+        assert_eq!(
+            builder.get_exit_code(exec_index),
+            Some(ExitCode::UserRevert(USER_ERROR_CODE))
+        );
+        assert_eq!(
+            builder.get_revert_data(exec_index),
+            Some(data[..max_length].to_vec())
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use engine_core::engine_state::{
+    genesis::POS_REWARDS_PURSE,
+    scheduled_calls::SCHEDULED_CALL_ESCROW,
+    step::{ExecutedCall, StepResult},
+};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{BlockTime, Key, URef, U512};
+
+const CONTRACT_SCHEDULED_CALL: &str = "scheduled_call.wasm";
+const CONTRACT_NAME: &str = "scheduled_call_stored";
+const CALLED_NAME: &str = "called";
+const NOT_BEFORE: u64 = 100;
+const VALUE: u64 = 42;
+
+fn schedule_call(builder: &mut InMemoryWasmTestBuilder, not_before: u64, value: u64) -> Key {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_SCHEDULED_CALL,
+        (not_before, value),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    *account
+        .named_keys()
+        .get(CONTRACT_NAME)
+        .expect("should have contract key")
+}
+
+fn called_value(builder: &InMemoryWasmTestBuilder, contract_key: Key) -> Option<u64> {
+    match builder.query(None, contract_key, &[CALLED_NAME]) {
+        Ok(StoredValue::CLValue(cl_value)) => Some(cl_value.into_t().expect("should be u64")),
+        Ok(other) => panic!("expected CLValue but received {:?}", other),
+        Err(_) => None,
+    }
+}
+
+fn executed_calls(builder: &InMemoryWasmTestBuilder, index: usize) -> &[ExecutedCall] {
+    match builder
+        .get_step_result(index)
+        .expect("should have step result")
+    {
+        StepResult::Success { executed_calls, .. } => executed_calls,
+        step_result => panic!("expected step success but received {}", step_result),
+    }
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+fn rewards_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let rewards_purse: URef = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(POS_REWARDS_PURSE)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have rewards purse");
+    builder.get_purse_balance(rewards_purse)
+}
+
+#[ignore]
+#[test]
+fn should_make_scheduled_call_once_due() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let contract_key = schedule_call(&mut builder, NOT_BEFORE, VALUE);
+
+    builder.step(
+        *DEFAULT_PROTOCOL_VERSION,
+        BlockTime::from_millis(NOT_BEFORE - 1),
+    );
+    assert!(executed_calls(&builder, 0).is_empty());
+    assert_eq!(called_value(&builder, contract_key), None);
+
+    let main_purse_balance_before = main_purse_balance(&builder);
+    let rewards_purse_balance_before = rewards_purse_balance(&builder);
+
+    builder.step(
+        *DEFAULT_PROTOCOL_VERSION,
+        BlockTime::from_millis(NOT_BEFORE),
+    );
+    let (cost, refund) = {
+        let executed_calls = executed_calls(&builder, 1);
+        assert_eq!(executed_calls.len(), 1);
+        let executed_call = &executed_calls[0];
+        assert!(executed_call.execution_result.is_success());
+        (executed_call.cost.value(), executed_call.refund.value())
+    };
+    assert_eq!(called_value(&builder, contract_key), Some(VALUE));
+
+    assert!(!cost.is_zero());
+    assert_eq!(cost + refund, U512::from(SCHEDULED_CALL_ESCROW));
+    assert_eq!(
+        main_purse_balance(&builder),
+        main_purse_balance_before + refund
+    );
+    assert_eq!(
+        rewards_purse_balance(&builder),
+        rewards_purse_balance_before + cost
+    );
+
+    // The call is made only once.
+    builder.step(
+        *DEFAULT_PROTOCOL_VERSION,
+        BlockTime::from_millis(NOT_BEFORE + 1),
+    );
+    assert!(executed_calls(&builder, 2).is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_charge_failed_scheduled_call_without_applying_its_effects() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    // The stored contract reverts when passed a zero value.
+    let failing_contract_key = schedule_call(&mut builder, NOT_BEFORE, 0);
+    let contract_key = schedule_call(&mut builder, NOT_BEFORE + 1, VALUE);
+
+    builder.step(
+        *DEFAULT_PROTOCOL_VERSION,
+        BlockTime::from_millis(NOT_BEFORE + 1),
+    );
+
+    let executed_calls = executed_calls(&builder, 0);
+    assert_eq!(executed_calls.len(), 2);
+
+    let failed_call = &executed_calls[0];
+    assert!(failed_call.execution_result.is_failure());
+    assert!(!failed_call.cost.value().is_zero());
+    assert_eq!(
+        failed_call.cost.value() + failed_call.refund.value(),
+        U512::from(SCHEDULED_CALL_ESCROW)
+    );
+    assert_eq!(called_value(&builder, failing_contract_key), None);
+
+    // A failed call doesn't prevent the calls after it being made.
+    assert!(executed_calls[1].execution_result.is_success());
+    assert_eq!(called_value(&builder, contract_key), Some(VALUE));
+}
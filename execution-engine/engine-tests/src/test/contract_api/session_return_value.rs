@@ -0,0 +1,158 @@
+use engine_core::engine_state::{
+    exit_code::{ExitCode, SystemErrorKind},
+    genesis::ExecConfig,
+    run_genesis_request::RunGenesisRequest,
+};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{AccessRights, CLValue, Key, URef};
+
+const CONTRACT_PURSE_HOLDER_STORED: &str = "purse_holder_stored.wasm";
+const CONTRACT_PURSE_HOLDER_STORED_CALLER: &str = "purse_holder_stored_caller.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const PURSE_HOLDER_STORED_CONTRACT_NAME: &str = "purse_holder_stored";
+const METHOD_VERSION: &str = "version";
+
+fn run_genesis_request(max_session_return_value_bytes: u32) -> RunGenesisRequest {
+    let wasm_costs = WasmCosts {
+        max_session_return_value_bytes,
+        ..*DEFAULT_WASM_COSTS
+    };
+    let exec_config = ExecConfig::new(
+        DEFAULT_EXEC_CONFIG.mint_installer_bytes().to_vec(),
+        DEFAULT_EXEC_CONFIG
+            .proof_of_stake_installer_bytes()
+            .to_vec(),
+        DEFAULT_EXEC_CONFIG
+            .standard_payment_installer_bytes()
+            .to_vec(),
+        DEFAULT_EXEC_CONFIG.accounts().to_vec(),
+        wasm_costs,
+        DEFAULT_EXEC_CONFIG.account_config(),
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+/// The serialized size of the contract key returned by purse-holder-stored.
+fn contract_key_size() -> usize {
+    let key = Key::URef(URef::new([0; 32], AccessRights::READ_ADD_WRITE));
+    CLValue::from_t(key)
+        .expect("should create value")
+        .serialized_length()
+}
+
+fn store_purse_holder(builder: &mut InMemoryWasmTestBuilder) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_PURSE_HOLDER_STORED, ())
+            .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_return_value_from_session() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    store_purse_holder(&mut builder);
+    builder.expect_success();
+
+    let returned_key: Key = builder
+        .get_session_return_value(0)
+        .expect("should return contract key")
+        .into_t()
+        .expect("should be a key");
+
+    // the deploy's effects are kept, so the returned key matches the one stored in named keys
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        account.named_keys().get(PURSE_HOLDER_STORED_CONTRACT_NAME),
+        Some(&returned_key)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_return_value_without_ret() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    assert_eq!(builder.get_session_return_value(0), None);
+}
+
+#[ignore]
+#[test]
+fn should_not_return_value_of_called_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    store_purse_holder(&mut builder);
+    let stored_key: Key = builder
+        .get_session_return_value(0)
+        .expect("should return contract key")
+        .into_t()
+        .expect("should be a key");
+    let stored_uref = *stored_key.as_uref().expect("should have uref");
+
+    // the called contract's `ret` still only returns its value to the session code
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PURSE_HOLDER_STORED_CALLER,
+        (stored_uref, METHOD_VERSION),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(builder.get_session_return_value(1), None);
+}
+
+#[ignore]
+#[test]
+fn should_return_value_of_max_size() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(contract_key_size() as u32));
+    store_purse_holder(&mut builder);
+    builder.expect_success();
+
+    assert!(builder.get_session_return_value(0).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_fail_when_return_value_exceeds_max_size() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(contract_key_size() as u32 - 1));
+    store_purse_holder(&mut builder);
+
+    assert_eq!(
+        builder.get_exit_code(0),
+        Some(ExitCode::SystemError(SystemErrorKind::ReturnValueTooLarge))
+    );
+    assert_eq!(builder.get_session_return_value(0), None);
+
+    // the session code's effects are discarded along with its return value
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(!account
+        .named_keys()
+        .contains_key(PURSE_HOLDER_STORED_CONTRACT_NAME));
+}
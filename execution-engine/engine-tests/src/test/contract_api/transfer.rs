@@ -322,6 +322,31 @@ fn should_fail_when_insufficient_funds() {
     );
 }
 
+#[ignore]
+#[test]
+fn should_fail_when_amount_argument_missing() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR,),
+    )
+    .build();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let error_msg = builder
+        .exec_error_message(0)
+        .expect("should have error message");
+    assert!(
+        error_msg.contains(&format!("{:?}", ApiError::MissingArgument)),
+        error_msg
+    );
+}
+
 #[ignore]
 #[test]
 fn should_transfer_total_amount() {
@@ -1,11 +1,8 @@
 use engine_core::engine_state::CONV_RATE;
 use engine_shared::motes::Motes;
 use engine_test_support::{
-    internal::{
-        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+    get_success_result, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE, DEFAULT_RUN_GENESIS_REQUEST,
 };
 use types::{account::PublicKey, U512};
 
@@ -47,7 +44,7 @@ fn should_transfer_to_account_stored() {
         .get_exec_response(0)
         .expect("there should be a response")
         .clone();
-    let mut result = utils::get_success_result(&response);
+    let mut result = get_success_result(&response);
     let gas = result.cost();
     let motes_alpha = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
 
@@ -60,12 +57,12 @@ fn should_transfer_to_account_stored() {
     let exec_request = {
         let deploy = DeployItemBuilder::new()
             .with_address(DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(contract_hash.to_vec(), (ACCOUNT_1_ADDR, transferred_amount))
+            .with_stored_session_hash(contract_hash, (ACCOUNT_1_ADDR, transferred_amount))
             .with_payment_code(
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -83,7 +80,7 @@ fn should_transfer_to_account_stored() {
         .expect("there should be a response")
         .clone();
 
-    result = utils::get_success_result(&response);
+    result = get_success_result(&response);
     let gas = result.cost();
     let motes_bravo = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
 
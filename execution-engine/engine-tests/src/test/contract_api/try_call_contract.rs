@@ -0,0 +1,111 @@
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{ApiError, CLValue, Key};
+
+const CONTRACT_TRY_CALL_CONTRACT_STORED: &str = "try_call_contract_stored.wasm";
+const CONTRACT_TRY_CALL_CONTRACT_CALLER: &str = "try_call_contract_caller.wasm";
+const CONTRACT_NAME: &str = "try_call_contract_stored";
+const VALUE_KEY_NAME: &str = "value";
+const WRITTEN_KEY_NAME: &str = "written";
+const RESULT_KEY_NAME: &str = "result";
+const ERROR_KEY_NAME: &str = "error";
+const METHOD_SUCCEED: &str = "succeed";
+const METHOD_REVERT: &str = "revert";
+const INITIAL_VALUE: u64 = 0;
+const WRITTEN_VALUE: u64 = 42;
+const REVERT_ERROR: u16 = 100;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRY_CALL_CONTRACT_STORED,
+        (),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn try_call(builder: &mut InMemoryWasmTestBuilder, method: &str) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRY_CALL_CONTRACT_CALLER,
+        (method,),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn query(builder: &InMemoryWasmTestBuilder, path: &[&str]) -> Option<CLValue> {
+    match builder.query(None, Key::Account(DEFAULT_ACCOUNT_ADDR), path) {
+        Ok(StoredValue::CLValue(cl_value)) => Some(cl_value),
+        Ok(other) => panic!("expected CLValue under {:?}, got {:?}", path, other),
+        Err(_) => None,
+    }
+}
+
+fn callee_value(builder: &InMemoryWasmTestBuilder) -> u64 {
+    query(builder, &[CONTRACT_NAME, VALUE_KEY_NAME])
+        .expect("should have value")
+        .into_t()
+        .expect("should be u64")
+}
+
+#[ignore]
+#[test]
+fn should_return_value_from_try_call() {
+    let mut builder = setup();
+    try_call(&mut builder, METHOD_SUCCEED);
+
+    let result: u64 = query(&builder, &[RESULT_KEY_NAME])
+        .expect("should have result")
+        .into_t()
+        .expect("should be u64");
+    assert_eq!(result, WRITTEN_VALUE);
+    assert!(query(&builder, &[ERROR_KEY_NAME]).is_none());
+
+    // the callee's changes are kept
+    assert_eq!(callee_value(&builder), WRITTEN_VALUE);
+    assert!(query(&builder, &[CONTRACT_NAME, WRITTEN_KEY_NAME]).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_return_callee_revert_to_caller_and_discard_its_changes() {
+    let mut builder = setup();
+    let contract_key = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()[CONTRACT_NAME];
+    let value_key = match builder.query(None, contract_key, &[]) {
+        Ok(StoredValue::Contract(contract)) => contract.named_keys()[VALUE_KEY_NAME],
+        other => panic!("expected contract, got {:?}", other),
+    };
+
+    try_call(&mut builder, METHOD_REVERT);
+
+    // the caller carried on after the callee reverted
+    let error: u32 = query(&builder, &[ERROR_KEY_NAME])
+        .expect("should have error")
+        .into_t()
+        .expect("should be u32");
+    assert_eq!(error, u32::from(ApiError::User(REVERT_ERROR)));
+    assert!(query(&builder, &[RESULT_KEY_NAME]).is_none());
+
+    // none of the callee's changes made it into the effects of the deploy
+    let transforms = builder.get_transforms();
+    let transforms = transforms.last().expect("should have transforms");
+    assert!(transforms.get(&contract_key.normalize()).is_none());
+    assert!(transforms.get(&value_key.normalize()).is_none());
+    assert_eq!(callee_value(&builder), INITIAL_VALUE);
+    assert!(query(&builder, &[CONTRACT_NAME, WRITTEN_KEY_NAME]).is_none());
+}
@@ -0,0 +1,76 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::ApiError;
+
+const CONTRACT_UREF_NARROWING_STORED: &str = "uref_narrowing_stored.wasm";
+const CONTRACT_UREF_NARROWING_CALLER: &str = "uref_narrowing_caller.wasm";
+const TRANSFER_CONTRACT_NAME: &str = "transfer_from";
+const ESCALATE_CONTRACT_NAME: &str = "escalate";
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_UREF_NARROWING_STORED, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+fn call_with_purse(builder: &mut InMemoryWasmTestBuilder, contract_name: &str, read_only: bool) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_UREF_NARROWING_CALLER,
+        (contract_name, read_only),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+#[ignore]
+#[test]
+fn should_allow_callee_to_transfer_from_purse_passed_with_full_rights() {
+    let mut builder = setup();
+    call_with_purse(&mut builder, TRANSFER_CONTRACT_NAME, false);
+    builder.expect_success();
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_callee_to_transfer_from_read_only_purse() {
+    let mut builder = setup();
+    call_with_purse(&mut builder, TRANSFER_CONTRACT_NAME, true);
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec response");
+    let expected_error = format!("{:?}", ApiError::Transfer);
+    assert!(
+        error_message.contains(&expected_error),
+        "expected {}, got: {}",
+        expected_error,
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_rights_escalation_when_calling_contract() {
+    let mut builder = setup();
+    call_with_purse(&mut builder, ESCALATE_CONTRACT_NAME, true);
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains("ForgedReference"),
+        "expected ForgedReference, got: {}",
+        error_message
+    );
+}
@@ -0,0 +1,50 @@
+use std::{fs, path::PathBuf};
+
+use engine_test_support::internal::contract_build;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+/// Restores the contents of a source file when dropped, even if the test fails.
+struct RestoreOnDrop {
+    path: PathBuf,
+    contents: Vec<u8>,
+}
+
+impl Drop for RestoreOnDrop {
+    fn drop(&mut self) {
+        fs::write(&self.path, &self.contents).expect("should restore source file");
+    }
+}
+
+#[ignore]
+#[test]
+fn should_rebuild_contract_once_its_sources_change() {
+    let contract_crate =
+        contract_build::find_contract_crate(CONTRACT_DO_NOTHING).expect("should find crate");
+    let built_path = contract_build::build_contract(&contract_crate);
+    assert!(built_path.is_file());
+    // unchanged sources are served from the cache
+    assert_eq!(contract_build::build_contract(&contract_crate), built_path);
+
+    let source_path = contract_crate.path().join("src").join("main.rs");
+    let contents = fs::read(&source_path).expect("should read source file");
+    let _restore = RestoreOnDrop {
+        path: source_path.clone(),
+        contents: contents.clone(),
+    };
+    let mut touched_contents = contents;
+    touched_contents
+        .extend_from_slice(b"\n// touched by should_rebuild_contract_once_its_sources_change\n");
+    fs::write(&source_path, touched_contents).expect("should touch source file");
+
+    let rebuilt_path = contract_crate.cached_wasm_path();
+    assert_ne!(rebuilt_path, built_path);
+    // left by a previous run of this test
+    let _ = fs::remove_file(&rebuilt_path);
+
+    assert_eq!(
+        contract_build::build_contract(&contract_crate),
+        rebuilt_path
+    );
+    assert!(rebuilt_path.is_file());
+}
@@ -0,0 +1,207 @@
+use assert_matches::assert_matches;
+
+use contract::args_parser::ArgsParser;
+use engine_core::{
+    engine_state::{
+        exit_code::{ExitCode, PreconditionReason},
+        Error,
+    },
+    execution,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, ApiError, BlockTime};
+
+const CONTRACT_AUTHORIZATION_CONTRACT: &str = "authorization_contract.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const METHOD_INSTALL: &str = "install";
+const METHOD_CLEAR: &str = "clear";
+const OPERATOR_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const NOT_BEFORE: u64 = 10_000;
+const TOO_EARLY: BlockTime = BlockTime::from_millis(NOT_BEFORE - 1);
+const LATE_ENOUGH: BlockTime = BlockTime::from_millis(NOT_BEFORE);
+/// The code the authorization contract reverts with before `NOT_BEFORE`.
+const ERROR_TOO_EARLY: u16 = 2;
+
+fn exec_with_key(
+    builder: &mut InMemoryWasmTestBuilder,
+    key: PublicKey,
+    block_time: BlockTime,
+    session_code: &str,
+    session_args: impl ArgsParser,
+) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_session_code(session_code, session_args)
+        .with_authorization_keys(&[key])
+        .with_deploy_hash([builder.get_exec_responses_count() as u8 + 1; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy)
+        .with_block_time(block_time)
+        .build();
+    builder.exec(exec_request).commit();
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let install_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_AUTHORIZATION_CONTRACT,
+        (METHOD_INSTALL, OPERATOR_ADDR, NOT_BEFORE),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(install_request)
+        .expect_success()
+        .commit();
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert!(account.authorization_contract().is_some());
+    builder
+}
+
+fn assert_rejected_too_early(builder: &InMemoryWasmTestBuilder, index: usize) {
+    let response = builder
+        .get_exec_response(index)
+        .expect("should have exec response");
+    assert_matches!(
+        utils::get_precondition_failure(response),
+        Error::AuthorizationContractRejected(execution::Error::Revert(ApiError::User(
+            ERROR_TOO_EARLY
+        )))
+    );
+    assert_eq!(
+        builder.get_exit_code(index),
+        Some(ExitCode::Precondition(
+            PreconditionReason::AuthorizationContractRejected
+        ))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_until_authorization_contract_approves() {
+    let mut builder = setup();
+
+    exec_with_key(
+        &mut builder,
+        OPERATOR_ADDR,
+        TOO_EARLY,
+        CONTRACT_DO_NOTHING,
+        (),
+    );
+    assert_rejected_too_early(&builder, 1);
+
+    exec_with_key(
+        &mut builder,
+        OPERATOR_ADDR,
+        LATE_ENOUGH,
+        CONTRACT_DO_NOTHING,
+        (),
+    );
+    assert!(!builder.is_error(), "{:?}", builder.exec_error_message(2));
+}
+
+#[ignore]
+#[test]
+fn should_charge_authorization_contract_gas_with_payment() {
+    let mut builder = setup();
+
+    // keys meeting the key management threshold skip the authorization contract
+    exec_with_key(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        LATE_ENOUGH,
+        CONTRACT_DO_NOTHING,
+        (),
+    );
+    exec_with_key(
+        &mut builder,
+        OPERATOR_ADDR,
+        LATE_ENOUGH,
+        CONTRACT_DO_NOTHING,
+        (),
+    );
+
+    let admin_cost = builder.exec_costs(1)[0];
+    let operator_cost = builder.exec_costs(2)[0];
+    assert!(
+        operator_cost > admin_cost,
+        "{:?} should include the authorization contract's gas on top of {:?}",
+        operator_cost,
+        admin_cost
+    );
+}
+
+#[ignore]
+#[test]
+fn should_clear_rejecting_authorization_contract_with_key_management_keys() {
+    let mut builder = setup();
+
+    // the operator can't clear the authorization contract, as it rejects the deploy doing so
+    exec_with_key(
+        &mut builder,
+        OPERATOR_ADDR,
+        TOO_EARLY,
+        CONTRACT_AUTHORIZATION_CONTRACT,
+        (METHOD_CLEAR,),
+    );
+    assert_rejected_too_early(&builder, 1);
+
+    // the admin key meets the key management threshold, so its deploy isn't checked
+    exec_with_key(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        TOO_EARLY,
+        CONTRACT_AUTHORIZATION_CONTRACT,
+        (METHOD_CLEAR,),
+    );
+    assert!(!builder.is_error(), "{:?}", builder.exec_error_message(2));
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(account.authorization_contract(), None);
+
+    exec_with_key(
+        &mut builder,
+        OPERATOR_ADDR,
+        TOO_EARLY,
+        CONTRACT_DO_NOTHING,
+        (),
+    );
+    assert!(!builder.is_error(), "{:?}", builder.exec_error_message(3));
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_operator_to_set_authorization_contract() {
+    let mut builder = setup();
+
+    exec_with_key(
+        &mut builder,
+        OPERATOR_ADDR,
+        LATE_ENOUGH,
+        CONTRACT_AUTHORIZATION_CONTRACT,
+        (METHOD_CLEAR,),
+    );
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains(&format!("{:?}", ApiError::PermissionDenied)),
+        "expected {:?}, got: {}",
+        ApiError::PermissionDenied,
+        error_message
+    );
+}
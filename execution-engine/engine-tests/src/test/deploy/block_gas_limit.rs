@@ -0,0 +1,100 @@
+use grpc::RequestOptions;
+
+use engine_core::engine_state::{deploy_item::DeployItem, execute_request::ExecuteRequest};
+use engine_grpc_server::engine_server::{ipc, ipc_grpc::ExecutionEngineService};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+
+fn do_nothing_deploy(address: PublicKey, deploy_hash: [u8; 32]) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(address)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[address])
+        .with_deploy_hash(deploy_hash)
+        .build()
+}
+
+/// Two deploys by the default account followed by one by account 1.
+fn block_request_builder(builder: &InMemoryWasmTestBuilder) -> ExecuteRequestBuilder {
+    ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .push_deploy(do_nothing_deploy(DEFAULT_ACCOUNT_ADDR, [1; 32]))
+        .push_deploy(do_nothing_deploy(DEFAULT_ACCOUNT_ADDR, [2; 32]))
+        .push_deploy(do_nothing_deploy(ACCOUNT_1_ADDR, [3; 32]))
+}
+
+fn account_1_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should get account 1");
+    builder.get_purse_balance(account_1.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_skip_deploys_exceeding_block_gas_limit() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT * 2),
+    )
+    .build();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    let account_1_pre_balance = account_1_balance(&builder);
+
+    // Without a limit, all deploys are executed.
+    builder.exec(block_request_builder(&builder).build());
+    let unlimited_results = builder
+        .get_exec_response(1)
+        .expect("should have unlimited results");
+    assert!(unlimited_results.iter().all(|result| result.is_success()));
+    let first_deploy_cost = unlimited_results[0].cost();
+
+    // The limit fits the first deploy's actual cost plus the second deploy's declared maximum, but
+    // not the third deploy's declared maximum on top of the first two deploys' costs.
+    let declared_gas_limit = do_nothing_deploy(ACCOUNT_1_ADDR, [3; 32]).declared_gas_limit();
+    let exec_request: ExecuteRequest = block_request_builder(&builder)
+        .with_block_gas_limit(first_deploy_cost + declared_gas_limit)
+        .build();
+    let mut exec_response = builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), exec_request.into())
+        .wait_drop_metadata()
+        .expect("should execute");
+    assert!(exec_response.has_success());
+    let deploy_results = exec_response.mut_success().take_deploy_results();
+
+    assert_eq!(deploy_results.len(), 3);
+    for deploy_result in &deploy_results[..2] {
+        assert!(deploy_result.has_execution_result(), "{:?}", deploy_result);
+        assert!(!deploy_result.get_execution_result().has_error());
+    }
+    let skipped_result: &ipc::DeployResult = &deploy_results[2];
+    assert!(
+        skipped_result.has_skipped_block_gas_exhausted(),
+        "{:?}",
+        skipped_result
+    );
+
+    // Committing the block leaves the balance of the skipped deploy's account untouched.
+    let first_deploy_effects = unlimited_results[0].effect().transforms.clone();
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, first_deploy_effects);
+    assert_eq!(account_1_balance(&builder), account_1_pre_balance);
+}
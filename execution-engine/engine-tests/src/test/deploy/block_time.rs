@@ -0,0 +1,107 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use engine_core::engine_state::{
+    exit_code::{ExitCode, PreconditionReason},
+    run_genesis_request::RunGenesisRequest,
+    EngineConfig,
+};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::BlockTime;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const GENESIS_TIMESTAMP_SECONDS: u64 = 1_600_000_000;
+const MAX_BLOCK_TIME_SKEW: Duration = Duration::from_secs(60 * 60);
+
+fn run_genesis_request(genesis_timestamp: BlockTime) -> RunGenesisRequest {
+    let mut exec_config = DEFAULT_EXEC_CONFIG.clone();
+    exec_config.set_genesis_timestamp(genesis_timestamp);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn exec_do_nothing(builder: &mut InMemoryWasmTestBuilder, block_time: BlockTime) {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ())
+            .with_block_time(block_time)
+            .build();
+    builder.exec(exec_request).commit();
+}
+
+fn assert_invalid_block_time(builder: &InMemoryWasmTestBuilder, index: usize, message: &str) {
+    assert_eq!(
+        builder.get_exit_code(index),
+        Some(ExitCode::Precondition(PreconditionReason::InvalidBlockTime))
+    );
+    let error_message = builder
+        .exec_error_message(index)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains(message),
+        "expected {:?}, got: {}",
+        message,
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_seconds_scale_block_time_on_millis_scale_chain() {
+    let genesis_timestamp =
+        BlockTime::from_seconds(GENESIS_TIMESTAMP_SECONDS).expect("should convert seconds");
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(genesis_timestamp));
+
+    // the block time of a block a minute after genesis, mistakenly sent in seconds
+    exec_do_nothing(
+        &mut builder,
+        BlockTime::from_millis(GENESIS_TIMESTAMP_SECONDS + 60),
+    );
+    assert_invalid_block_time(&builder, 0, "before the genesis timestamp");
+    assert!(builder.exec_costs(0)[0].value().is_zero());
+
+    exec_do_nothing(
+        &mut builder,
+        BlockTime::from_seconds(GENESIS_TIMESTAMP_SECONDS + 60).expect("should convert seconds"),
+    );
+    assert_eq!(builder.get_exit_code(1), Some(ExitCode::Success));
+
+    // the genesis timestamp itself is a valid block time
+    exec_do_nothing(&mut builder, genesis_timestamp);
+    assert_eq!(builder.get_exit_code(2), Some(ExitCode::Success));
+}
+
+#[ignore]
+#[test]
+fn should_reject_block_time_too_far_in_the_future() {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_max_block_time_skew(MAX_BLOCK_TIME_SKEW);
+    let empty_root_hash = global_state.empty_root_hash.to_vec();
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root_hash);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let now = BlockTime::default().saturating_add(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("should get time since epoch"),
+    );
+
+    exec_do_nothing(&mut builder, now.saturating_add(MAX_BLOCK_TIME_SKEW * 24));
+    assert_invalid_block_time(&builder, 0, "later than the latest accepted time");
+
+    exec_do_nothing(&mut builder, now);
+    assert_eq!(builder.get_exit_code(1), Some(ExitCode::Success));
+}
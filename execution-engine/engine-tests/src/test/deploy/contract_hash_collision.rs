@@ -0,0 +1,80 @@
+use engine_core::engine_state::exit_code::{ExitCode, SystemErrorKind};
+use engine_shared::stored_value::StoredValue;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const DO_NOTHING_STORED_CONTRACT_NAME: &str = "do_nothing_stored";
+const DESTINATION_HASH: &str = "hash";
+
+/// Executes and commits a deploy storing the do-nothing contract at hash.
+fn store_at_hash(builder: &mut InMemoryWasmTestBuilder, deploy_hash: [u8; 32]) {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING_STORED, (DESTINATION_HASH,))
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(deploy_hash)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+    builder.exec(exec_request).commit();
+}
+
+fn stored_contract_key(builder: &InMemoryWasmTestBuilder) -> Key {
+    builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()[DO_NOTHING_STORED_CONTRACT_NAME]
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_overwrite_contract_stored_by_replayed_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    store_at_hash(&mut builder, [1; 32]);
+    assert_eq!(builder.get_exit_code(0), Some(ExitCode::Success));
+    let contract_key = stored_contract_key(&builder);
+
+    // the same deploy derives the same contract hash
+    store_at_hash(&mut builder, [1; 32]);
+    assert_eq!(
+        builder.get_exit_code(1),
+        Some(ExitCode::SystemError(
+            SystemErrorKind::ContractHashCollision
+        ))
+    );
+    assert_eq!(stored_contract_key(&builder), contract_key);
+}
+
+#[ignore]
+#[test]
+fn should_store_identical_contracts_of_distinct_deploys_at_distinct_hashes() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    store_at_hash(&mut builder, [1; 32]);
+    assert_eq!(builder.get_exit_code(0), Some(ExitCode::Success));
+    let first_contract_key = stored_contract_key(&builder);
+
+    store_at_hash(&mut builder, [2; 32]);
+    assert_eq!(builder.get_exit_code(1), Some(ExitCode::Success));
+    let second_contract_key = stored_contract_key(&builder);
+
+    assert_ne!(first_contract_key, second_contract_key);
+    let contract_bytes = |contract_key: Key| match builder.query(None, contract_key, &[]) {
+        Ok(StoredValue::Contract(contract)) => contract.bytes().to_vec(),
+        other => panic!("expected contract, got {:?}", other),
+    };
+    assert_eq!(
+        contract_bytes(first_contract_key),
+        contract_bytes(second_contract_key)
+    );
+}
@@ -0,0 +1,131 @@
+use engine_core::engine_state::{
+    execution_result::ExecutionResult,
+    exit_code::{ExitCode, SystemErrorKind},
+    genesis::ExecConfig,
+    run_genesis_request::RunGenesisRequest,
+};
+use engine_shared::{gas::Gas, stored_value::StoredValue, transform::Transform};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{account::PublicKey, CLType, U512};
+
+const CONTRACT_WRITE_N_KEYS: &str = "write_n_keys.wasm";
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const KEY_COUNT: u64 = 100;
+
+fn run_genesis_request(max_transforms_per_deploy: u32) -> RunGenesisRequest {
+    let wasm_costs = WasmCosts {
+        max_transforms_per_deploy,
+        ..*DEFAULT_WASM_COSTS
+    };
+    let exec_config = ExecConfig::new(
+        DEFAULT_EXEC_CONFIG.mint_installer_bytes().to_vec(),
+        DEFAULT_EXEC_CONFIG
+            .proof_of_stake_installer_bytes()
+            .to_vec(),
+        DEFAULT_EXEC_CONFIG
+            .standard_payment_installer_bytes()
+            .to_vec(),
+        DEFAULT_EXEC_CONFIG.accounts().to_vec(),
+        wasm_costs,
+        DEFAULT_EXEC_CONFIG.account_config(),
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+/// Executes a block of a deploy writing `KEY_COUNT` keys followed by a transfer to account 1, and
+/// commits it.
+fn exec_block(max_transforms_per_deploy: u32) -> InMemoryWasmTestBuilder {
+    let write_n_keys_deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_WRITE_N_KEYS, (KEY_COUNT,))
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    let transfer_deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(1)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([2; 32])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new()
+        .push_deploy(write_n_keys_deploy)
+        .push_deploy(transfer_deploy)
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request(max_transforms_per_deploy))
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+/// Returns the number of `u64` values written by the deploy, i.e. of the keys written by the
+/// session code.
+fn written_u64_count(result: &ExecutionResult) -> usize {
+    result
+        .effect()
+        .transforms
+        .values()
+        .filter(|transform| match transform {
+            Transform::Write(StoredValue::CLValue(cl_value)) => *cl_value.cl_type() == CLType::U64,
+            _ => false,
+        })
+        .count()
+}
+
+#[ignore]
+#[test]
+fn should_fail_deploy_exceeding_max_transforms() {
+    let builder = exec_block(KEY_COUNT as u32 - 1);
+    let results = builder.get_exec_response(0).expect("should have results");
+    assert_eq!(results.len(), 2);
+
+    let write_n_keys_result = &results[0];
+    assert_eq!(
+        write_n_keys_result.exit_code(),
+        ExitCode::SystemError(SystemErrorKind::EffectsTooLarge)
+    );
+    // the session effects are discarded, but the gas used is charged
+    assert_eq!(written_u64_count(write_n_keys_result), 0);
+    assert!(write_n_keys_result.cost() > Gas::default());
+
+    assert!(results[1].is_success(), "{:?}", results[1]);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+}
+
+#[ignore]
+#[test]
+fn should_succeed_deploy_within_max_transforms() {
+    let builder = exec_block(KEY_COUNT as u32 + 1);
+    let results = builder.get_exec_response(0).expect("should have results");
+    assert_eq!(results.len(), 2);
+
+    let write_n_keys_result = &results[0];
+    assert!(
+        write_n_keys_result.is_success(),
+        "{:?}",
+        write_n_keys_result
+    );
+    assert_eq!(written_u64_count(write_n_keys_result), KEY_COUNT as usize);
+
+    assert!(results[1].is_success(), "{:?}", results[1]);
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_some());
+}
@@ -0,0 +1,59 @@
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{ipc::ExecuteResponse, ipc_grpc::ExecutionEngineService};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    logging,
+};
+
+const METRIC_DURATION_EXEC: &str = "exec_duration";
+const TAG_RESPONSE_EXEC: &str = "exec_response";
+const MISSING_PARENT_HASH: [u8; 32] = [1; 32];
+
+fn execute_empty_block(builder: &InMemoryWasmTestBuilder, parent_hash: &[u8]) -> ExecuteResponse {
+    let exec_request = ExecuteRequestBuilder::empty_block(parent_hash).build();
+    builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), exec_request.into())
+        .wait_drop_metadata()
+        .expect("should execute")
+}
+
+#[ignore]
+#[test]
+fn should_execute_empty_block_on_existing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let capture = logging::capture();
+    let exec_response = execute_empty_block(&builder, &builder.get_post_state_hash());
+
+    assert!(exec_response.has_success(), "{:?}", exec_response);
+    assert!(exec_response.get_success().get_deploy_results().is_empty());
+    let exec_durations = capture
+        .entries()
+        .with_metric(METRIC_DURATION_EXEC)
+        .with_tag(TAG_RESPONSE_EXEC);
+    assert_eq!(exec_durations.len(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_report_missing_parent_for_empty_block() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let capture = logging::capture();
+    let exec_response = execute_empty_block(&builder, &MISSING_PARENT_HASH);
+
+    assert!(exec_response.has_missing_parent(), "{:?}", exec_response);
+    assert_eq!(
+        exec_response.get_missing_parent().get_hash(),
+        &MISSING_PARENT_HASH[..]
+    );
+    let exec_durations = capture
+        .entries()
+        .with_metric(METRIC_DURATION_EXEC)
+        .with_tag(TAG_RESPONSE_EXEC);
+    assert_eq!(exec_durations.len(), 1);
+}
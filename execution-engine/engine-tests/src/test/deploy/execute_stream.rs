@@ -0,0 +1,150 @@
+use std::{convert::TryFrom, time::Instant};
+
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{self, DeployResult, DeployResultChunk},
+    ipc_grpc::ExecutionEngineService,
+    mappings::TransformMap,
+};
+use engine_shared::{additive_map::AdditiveMap, transform::Transform};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_SLOW_DEPLOY: &str = "slow_deploy.wasm";
+const SLOW_DEPLOY_ITERATIONS: u64 = 200_000;
+const DEPLOY_COUNT: u8 = 4;
+
+fn slow_deploys_request(builder: &InMemoryWasmTestBuilder) -> ipc::ExecuteRequest {
+    let mut exec_request_builder =
+        ExecuteRequestBuilder::new().with_pre_state_hash(&builder.get_post_state_hash());
+
+    for i in 0..DEPLOY_COUNT {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(CONTRACT_SLOW_DEPLOY, (SLOW_DEPLOY_ITERATIONS,))
+            .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([i + 1; 32])
+            .build();
+        exec_request_builder = exec_request_builder.push_deploy(deploy);
+    }
+
+    exec_request_builder.build().into()
+}
+
+fn transforms(deploy_result: &DeployResult) -> AdditiveMap<Key, Transform> {
+    let transform_map = deploy_result
+        .get_execution_result()
+        .get_effects()
+        .get_transform_map()
+        .to_vec();
+    TransformMap::try_from(transform_map)
+        .expect("should convert")
+        .into_inner()
+}
+
+#[ignore]
+#[test]
+fn should_stream_same_results_as_execute() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let engine_state = builder.get_engine_state();
+
+    let execute_response = engine_state
+        .execute(RequestOptions::new(), slow_deploys_request(&builder))
+        .wait_drop_metadata()
+        .expect("should execute");
+    let expected_results = execute_response.get_success().get_deploy_results();
+
+    let chunks = engine_state
+        .execute_stream(RequestOptions::new(), slow_deploys_request(&builder))
+        .wait_drop_metadata()
+        .collect::<Result<Vec<DeployResultChunk>, _>>()
+        .expect("should stream");
+
+    let (summary_chunk, deploy_chunks) = chunks.split_last().expect("should have summary");
+    assert!(summary_chunk.has_summary());
+    let counts = summary_chunk.get_summary().get_success();
+    assert_eq!(counts.get_deploy_count(), u32::from(DEPLOY_COUNT));
+    assert_eq!(counts.get_precondition_failure_count(), 0);
+    assert_eq!(counts.get_execution_error_count(), 0);
+
+    assert_eq!(deploy_chunks.len(), expected_results.len());
+    for (chunk, expected) in deploy_chunks.iter().zip(expected_results) {
+        assert!(chunk.has_deploy_result());
+        let actual = chunk.get_deploy_result();
+        assert!(!actual.get_execution_result().has_error());
+        assert_eq!(
+            actual.get_execution_result().get_cost(),
+            expected.get_execution_result().get_cost()
+        );
+        assert_eq!(transforms(actual), transforms(expected));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_stream_results_incrementally() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let engine_state = builder.get_engine_state();
+
+    let mut chunks = engine_state
+        .execute_stream(RequestOptions::new(), slow_deploys_request(&builder))
+        .wait_drop_metadata();
+
+    let start = Instant::now();
+    let first_chunk = chunks
+        .next()
+        .expect("should have first chunk")
+        .expect("should receive first chunk");
+    let first_chunk_elapsed = start.elapsed();
+    assert!(first_chunk.has_deploy_result());
+
+    let remaining_chunks = chunks
+        .collect::<Result<Vec<DeployResultChunk>, _>>()
+        .expect("should receive remaining chunks");
+    let last_chunk_elapsed = start.elapsed();
+
+    assert_eq!(remaining_chunks.len(), usize::from(DEPLOY_COUNT));
+    assert!(remaining_chunks.last().unwrap().has_summary());
+
+    // The remaining deploys were still executing when the first result arrived, so receiving the
+    // rest of the stream must take longer than receiving the first result.
+    assert!(
+        last_chunk_elapsed > first_chunk_elapsed * 2,
+        "first result after {:?}, stream finished after {:?}",
+        first_chunk_elapsed,
+        last_chunk_elapsed
+    );
+}
+
+#[ignore]
+#[test]
+fn should_stream_missing_parent_summary() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_parent = [255u8; 32];
+    let mut exec_request = slow_deploys_request(&builder);
+    exec_request.set_parent_state_hash(missing_parent.to_vec());
+
+    let chunks = builder
+        .get_engine_state()
+        .execute_stream(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .collect::<Result<Vec<DeployResultChunk>, _>>()
+        .expect("should stream");
+
+    assert_eq!(chunks.len(), 1);
+    let summary = chunks[0].get_summary();
+    assert!(summary.has_missing_parent());
+    assert_eq!(summary.get_missing_parent().get_hash(), &missing_parent[..]);
+}
@@ -0,0 +1,66 @@
+use engine_core::engine_state::EngineConfig;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    // the mint runs as a contract, so the gas it uses is attributed to the transfer
+    let engine_config = EngineConfig::new().with_use_system_contracts(true);
+    let empty_root_hash = global_state.empty_root_hash.to_vec();
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root_hash);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn exec_transfer(builder: &mut InMemoryWasmTestBuilder, collect_stats: bool) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .with_collect_stats(collect_stats)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_collect_host_call_stats_of_transfer() {
+    let mut builder = setup();
+    exec_transfer(&mut builder, true);
+
+    let host_call_stats = builder
+        .get_host_call_stats(0)
+        .expect("should have host call stats");
+
+    let get_arg = host_call_stats
+        .get("host_function_get_arg")
+        .expect("should have called get_arg");
+    assert!(get_arg.calls >= 2);
+
+    let transfer_to_account = host_call_stats
+        .get("host_function_transfer_to_account")
+        .expect("should have called transfer_to_account");
+    assert_eq!(transfer_to_account.calls, 1);
+    assert!(!transfer_to_account.gas.value().is_zero());
+
+    // the gas used by the Wasm code of the deploy itself isn't attributed to any host function
+    assert!(host_call_stats.total_gas() < builder.exec_costs(0)[0]);
+}
+
+#[ignore]
+#[test]
+fn should_not_collect_host_call_stats_unless_requested() {
+    let mut builder = setup();
+    exec_transfer(&mut builder, false);
+
+    assert!(builder.get_host_call_stats(0).is_none());
+}
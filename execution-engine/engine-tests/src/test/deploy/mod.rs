@@ -1,3 +1,20 @@
+mod authorization_contract;
+mod block_gas_limit;
+mod block_time;
+mod contract_hash_collision;
+mod deploy_overhead;
+mod effect_limits;
+mod empty_block;
+mod execute_stream;
+mod host_call_stats;
 mod non_standard_payment;
 mod preconditions;
+mod read_stats;
+mod replay;
+mod repro_bundle;
+mod request_stats;
+mod sponsored_payment;
 mod stored_contracts;
+mod strict_session_exports;
+mod system_deploy;
+mod validate_deploy;
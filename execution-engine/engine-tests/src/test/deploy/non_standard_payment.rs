@@ -3,11 +3,8 @@ use std::convert::TryFrom;
 use engine_core::engine_state::CONV_RATE;
 use engine_shared::motes::Motes;
 use engine_test_support::{
-    internal::{
-        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    get_success_result, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_RUN_GENESIS_REQUEST,
 };
 use types::{account::PublicKey, bytesrepr::ToBytes, CLValue, Key, U512};
 
@@ -39,7 +36,7 @@ fn should_charge_non_main_purse() {
                 (account_1_public_key, account_1_funding_amount),
             )
             .with_empty_payment_bytes((payment_purse_amount,))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1; 32])
             .build();
 
@@ -136,7 +133,7 @@ fn should_charge_non_main_purse() {
         .expect("there should be a response")
         .clone();
 
-    let result = utils::get_success_result(&response);
+    let result = get_success_result(&response);
     let gas = result.cost();
     let motes = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
 
@@ -0,0 +1,169 @@
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{self, DeployResult},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_storage::{
+    global_state::{
+        in_memory::InMemoryGlobalState,
+        read_stats::{CountingStateProvider, ReadStats},
+        StateProvider, StateReader,
+    },
+    trie_store::node_cache::TrieNodeCache,
+};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, CLValue, Key, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+/// The number of branches on the path to [`deep_key`].
+const DEPTH: usize = 20;
+
+/// The key whose path branches at each of its first `DEPTH` bytes.
+fn deep_key() -> Key {
+    Key::Hash([0; 32])
+}
+
+/// The key which branches off the path to [`deep_key`] at byte `index` of the hash.
+fn branch_key(index: usize) -> Key {
+    let mut hash = [0; 32];
+    hash[index] = 1;
+    Key::Hash(hash)
+}
+
+/// Returns a state holding [`deep_key`] and the `DEPTH` keys branching off its path.
+fn deep_state() -> (InMemoryGlobalState, Blake2bHash) {
+    let pairs: Vec<(Key, StoredValue)> = (0..DEPTH)
+        .map(branch_key)
+        .chain(Some(deep_key()))
+        .enumerate()
+        .map(|(i, key)| {
+            let value = CLValue::from_t(i as u64).expect("should create value");
+            (key, StoredValue::CLValue(value))
+        })
+        .collect();
+    InMemoryGlobalState::from_pairs(CorrelationId::new(), &pairs).expect("should create state")
+}
+
+fn read_counted(
+    state: &InMemoryGlobalState,
+    root_hash: Blake2bHash,
+    key: &Key,
+    trie_node_cache: Option<&TrieNodeCache>,
+) -> ReadStats {
+    let counting_state = CountingStateProvider::new(state);
+    let reader = match trie_node_cache {
+        Some(trie_node_cache) => counting_state.checkout_with_cache(root_hash, trie_node_cache),
+        None => counting_state.checkout(root_hash),
+    }
+    .expect("should checkout")
+    .expect("should have root");
+    let value = reader.read(CorrelationId::new(), key).expect("should read");
+    assert!(value.is_some());
+    let stats = counting_state.stats();
+    stats.get()
+}
+
+fn exec_transfer_request(builder: &InMemoryWasmTestBuilder, collect_stats: bool) -> DeployResult {
+    let exec_request: ipc::ExecuteRequest = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .with_pre_state_hash(&builder.get_post_state_hash())
+    .with_collect_stats(collect_stats)
+    .build()
+    .into();
+    let mut execute_response = builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should execute");
+    assert!(execute_response.has_success());
+    execute_response
+        .take_success()
+        .take_deploy_results()
+        .into_vec()
+        .remove(0)
+}
+
+#[test]
+fn should_visit_a_node_per_branch_on_the_path_of_a_read() {
+    let (state, root_hash) = deep_state();
+
+    let deep_stats = read_counted(&state, root_hash, &deep_key(), None);
+    assert_eq!(deep_stats.reads, 1);
+    assert!(deep_stats.nodes_visited > DEPTH as u64);
+    // the root and the leaf are visited on top of the branches
+    assert!(deep_stats.nodes_visited <= DEPTH as u64 + 3);
+    assert_eq!(deep_stats.max_nodes_per_read, deep_stats.nodes_visited);
+    assert!(deep_stats.bytes_read > 0);
+    assert_eq!(deep_stats.cache_hits, 0);
+
+    // the key branching off first is only a few nodes deep
+    let shallow_stats = read_counted(&state, root_hash, &branch_key(0), None);
+    assert!(shallow_stats.nodes_visited <= 4);
+}
+
+#[test]
+fn should_count_cached_nodes_as_visited_without_reading_them() {
+    let (state, root_hash) = deep_state();
+    let trie_node_cache = TrieNodeCache::new();
+
+    let first_stats = read_counted(&state, root_hash, &deep_key(), Some(&trie_node_cache));
+    let second_stats = read_counted(&state, root_hash, &deep_key(), Some(&trie_node_cache));
+
+    assert_eq!(second_stats.nodes_visited, first_stats.nodes_visited);
+    assert_eq!(second_stats.cache_hits, second_stats.nodes_visited);
+    assert_eq!(second_stats.bytes_read, 0);
+}
+
+#[ignore]
+#[test]
+fn should_collect_read_stats_of_transfer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+    )
+    .with_collect_stats(true)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let read_stats = builder.get_read_stats(0).expect("should have read stats");
+    assert!(read_stats.reads > 0);
+    assert!(read_stats.nodes_visited >= read_stats.reads);
+    assert!(read_stats.max_nodes_per_read > 0);
+    assert!(read_stats.bytes_read > 0);
+}
+
+#[ignore]
+#[test]
+fn should_report_read_stats_in_deploy_result_only_if_requested() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let deploy_result = exec_transfer_request(&builder, true);
+    assert!(deploy_result.has_host_call_stats());
+    assert!(deploy_result.has_read_stats());
+    let read_stats = deploy_result.get_read_stats();
+    assert!(read_stats.get_reads() > 0);
+    assert!(read_stats.get_nodes_visited() >= read_stats.get_reads());
+    assert!(read_stats.get_max_nodes_per_read() > 0);
+
+    let deploy_result = exec_transfer_request(&builder, false);
+    assert!(!deploy_result.has_host_call_stats());
+    assert!(!deploy_result.has_read_stats());
+}
@@ -0,0 +1,173 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::{
+    deploy_item::DeployItem,
+    execution_result::ExecutionResult,
+    replay::{self, ReplayOverrides, ReplayRequest, ReplayResult, ReplayedDeploy},
+};
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue, transform::Transform};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::wasm_costs::WasmCosts;
+use types::{account::PublicKey, BlockTime, CLType, Phase, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const BLOCK_TIME: BlockTime = BlockTime::from_millis(42);
+
+fn transfer_deploy() -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build()
+}
+
+/// Executes and commits the transfer, returning the pre-state hash.
+fn commit_transfer(builder: &mut InMemoryWasmTestBuilder) -> Vec<u8> {
+    let pre_state_hash = builder.get_post_state_hash();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(transfer_deploy())
+        .with_block_time(BLOCK_TIME)
+        .build();
+    builder.exec(exec_request).expect_success().commit();
+    pre_state_hash
+}
+
+fn recorded_result(builder: &InMemoryWasmTestBuilder) -> &ExecutionResult {
+    builder
+        .get_exec_response(0)
+        .and_then(|exec_response| exec_response.first())
+        .expect("should have recorded result")
+}
+
+fn replay(
+    builder: &InMemoryWasmTestBuilder,
+    pre_state_hash: &[u8],
+    overrides: ReplayOverrides,
+    recorded_result: &ExecutionResult,
+) -> ReplayedDeploy {
+    let recorded_effects_hash =
+        replay::effects_hash(&recorded_result.effect().transforms).expect("should hash effects");
+    let replay_request = ReplayRequest::new(
+        pre_state_hash.try_into().expect("should be a state hash"),
+        BLOCK_TIME,
+        transfer_deploy(),
+        *DEFAULT_PROTOCOL_VERSION,
+    )
+    .with_overrides(overrides)
+    .with_recorded_effects_hash(recorded_effects_hash);
+
+    match builder
+        .get_engine_state()
+        .replay_deploy(CorrelationId::new(), replay_request)
+        .expect("should replay")
+    {
+        ReplayResult::Replayed(replayed_deploy) => replayed_deploy,
+        ReplayResult::RootNotFound => panic!("should find pre-state root"),
+    }
+}
+
+fn is_balance_change(transform: &Transform) -> bool {
+    match transform {
+        Transform::AddUInt512(_) => true,
+        Transform::Write(StoredValue::CLValue(cl_value)) => *cl_value.cl_type() == CLType::U512,
+        _ => false,
+    }
+}
+
+#[ignore]
+#[test]
+fn should_replay_committed_transfer_with_matching_effects() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let pre_state_hash = commit_transfer(&mut builder);
+    let recorded_result = recorded_result(&builder);
+    let post_state_hash = builder.get_post_state_hash();
+
+    let overrides = ReplayOverrides {
+        collect_trace: true,
+        ..Default::default()
+    };
+    let replayed_deploy = replay(&builder, &pre_state_hash, overrides, recorded_result);
+
+    assert!(replayed_deploy.execution_result.is_success());
+    assert_eq!(replayed_deploy.matches_recorded_effects, Some(true));
+    assert_eq!(
+        replayed_deploy.execution_result.cost(),
+        recorded_result.cost()
+    );
+
+    let trace = replayed_deploy.trace.expect("should have trace");
+    let phases: Vec<Phase> = trace
+        .phases()
+        .iter()
+        .map(|phase_trace| phase_trace.phase)
+        .collect();
+    assert_eq!(
+        phases,
+        vec![Phase::Payment, Phase::Session, Phase::FinalizePayment]
+    );
+    assert!(trace
+        .phases()
+        .iter()
+        .all(|phase_trace| phase_trace.error.is_none()));
+
+    // Replaying doesn't commit anything.
+    assert_eq!(builder.get_post_state_hash(), post_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_replay_with_doubled_costs_and_same_effects() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let pre_state_hash = commit_transfer(&mut builder);
+    let recorded_result = recorded_result(&builder);
+
+    let doubled_wasm_costs = WasmCosts {
+        regular: DEFAULT_WASM_COSTS.regular * 2,
+        div: DEFAULT_WASM_COSTS.div * 2,
+        mul: DEFAULT_WASM_COSTS.mul * 2,
+        mem: DEFAULT_WASM_COSTS.mem * 2,
+        grow_mem: DEFAULT_WASM_COSTS.grow_mem * 2,
+        ..*DEFAULT_WASM_COSTS
+    };
+    let overrides = ReplayOverrides {
+        wasm_costs: Some(doubled_wasm_costs),
+        ..Default::default()
+    };
+    let replayed_deploy = replay(&builder, &pre_state_hash, overrides, recorded_result);
+
+    let replayed_result = replayed_deploy.execution_result;
+    assert!(replayed_result.is_success());
+    assert!(replayed_deploy.trace.is_none());
+    assert_ne!(replayed_result.cost(), recorded_result.cost());
+
+    // The motes charged for the deploy depend on its cost, so only the purse balances involved in
+    // payment may differ; every other effect must be identical.
+    assert_eq!(replayed_deploy.matches_recorded_effects, Some(false));
+    let recorded_transforms = &recorded_result.effect().transforms;
+    let replayed_transforms = &replayed_result.effect().transforms;
+    assert_eq!(replayed_transforms.len(), recorded_transforms.len());
+    for (key, recorded_transform) in recorded_transforms.iter() {
+        let replayed_transform = replayed_transforms.get(key).expect("should have same keys");
+        if replayed_transform != recorded_transform {
+            assert!(
+                is_balance_change(recorded_transform) && is_balance_change(replayed_transform),
+                "unexpected difference under {:?}",
+                key
+            );
+        }
+    }
+}
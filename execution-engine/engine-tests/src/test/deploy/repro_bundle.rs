@@ -0,0 +1,115 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::{
+    deploy_item::DeployItem,
+    repro_bundle::{BundleReplayResult, ExportReproBundleRequest, ReproBundle},
+};
+use engine_shared::{newtypes::CorrelationId, stored_value::StoredValue};
+use engine_storage::trie::TrieMerkleProof;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, bytesrepr, BlockTime, CLValue, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000;
+const BLOCK_TIME: BlockTime = BlockTime::from_millis(42);
+
+fn transfer_deploy() -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (ACCOUNT_1_ADDR, U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build()
+}
+
+/// Commits the transfer and exports its bundle against the pre-state.
+fn export_transfer_bundle() -> (InMemoryWasmTestBuilder, ReproBundle) {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let pre_state_hash = builder.get_post_state_hash();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(transfer_deploy())
+        .with_block_time(BLOCK_TIME)
+        .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let export_request = ExportReproBundleRequest::new(
+        pre_state_hash
+            .as_slice()
+            .try_into()
+            .expect("should be a state hash"),
+        BLOCK_TIME,
+        transfer_deploy(),
+        *DEFAULT_PROTOCOL_VERSION,
+    );
+    let bundle = builder
+        .get_engine_state()
+        .export_repro_bundle(CorrelationId::new(), export_request)
+        .expect("should export")
+        .expect("should find pre-state root");
+    (builder, bundle)
+}
+
+#[ignore]
+#[test]
+fn should_replay_transfer_from_serialized_bundle() {
+    let (builder, bundle) = export_transfer_bundle();
+    assert!(!bundle.reads.is_empty());
+
+    let bytes = bytesrepr::serialize(bundle.clone()).expect("should serialize");
+    let deserialized: ReproBundle = bytesrepr::deserialize(bytes).expect("should deserialize");
+    assert!(deserialized == bundle);
+
+    let recorded_result = builder
+        .get_exec_response(0)
+        .and_then(|exec_response| exec_response.first())
+        .expect("should have recorded result");
+
+    match builder
+        .get_engine_state()
+        .replay_repro_bundle(CorrelationId::new(), &deserialized)
+        .expect("should replay")
+    {
+        BundleReplayResult::Replayed(replayed_deploy) => {
+            assert!(replayed_deploy.execution_result.is_success());
+            assert_eq!(replayed_deploy.matches_recorded_effects, Some(true));
+            assert_eq!(
+                replayed_deploy.execution_result.cost(),
+                recorded_result.cost()
+            );
+        }
+        other => panic!("should replay bundle, got {:?}", other),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_reject_bundle_with_tampered_value() {
+    let (builder, mut bundle) = export_transfer_bundle();
+
+    let proof = bundle.reads.remove(0);
+    let tampered_key = *proof.key();
+    let tampered_value =
+        StoredValue::CLValue(CLValue::from_t(U512::max_value()).expect("should create CLValue"));
+    let tampered_proof = TrieMerkleProof::new(tampered_key, tampered_value, proof.path().to_vec());
+    bundle.reads.insert(0, tampered_proof);
+
+    match builder
+        .get_engine_state()
+        .replay_repro_bundle(CorrelationId::new(), &bundle)
+        .expect("should replay")
+    {
+        BundleReplayResult::InvalidProof(key) => assert_eq!(key, tampered_key),
+        other => panic!("should reject tampered proof, got {:?}", other),
+    }
+}
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use grpc::RequestOptions;
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_grpc_server::engine_server::{ipc, ipc_grpc::ExecutionEngineService};
+use engine_shared::gas::Gas;
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::DeployHash;
+
+const CONTRACT_SLOW_DEPLOY: &str = "slow_deploy.wasm";
+/// The iterations of each deploy of the request, the last of which is by far the slowest.
+const DEPLOY_ITERATIONS: [u64; 3] = [1_000, 1_000, 500_000];
+
+fn deploy_hash(index: usize) -> DeployHash {
+    DeployHash::new([index as u8 + 1; 32])
+}
+
+fn slow_deploys_request(builder: &InMemoryWasmTestBuilder, collect_stats: bool) -> ExecuteRequest {
+    let mut exec_request_builder = ExecuteRequestBuilder::new()
+        .with_pre_state_hash(&builder.get_post_state_hash())
+        .with_collect_stats(collect_stats);
+
+    for (i, iterations) in DEPLOY_ITERATIONS.iter().enumerate() {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_session_code(CONTRACT_SLOW_DEPLOY, (*iterations,))
+            .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash(deploy_hash(i))
+            .build();
+        exec_request_builder = exec_request_builder.push_deploy(deploy);
+    }
+
+    exec_request_builder.build()
+}
+
+/// Asserts that `parts` add up to at most `total`, and to no less than 80% of it.
+fn assert_sums_approximately_to(parts: Duration, total: Duration) {
+    assert!(parts <= total, "{:?} exceeds {:?}", parts, total);
+    assert!(
+        parts >= total * 4 / 5,
+        "{:?} is too far below {:?}",
+        parts,
+        total
+    );
+}
+
+#[ignore]
+#[test]
+fn should_summarize_request_with_phase_times_adding_up_to_total() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = slow_deploys_request(&builder, true);
+    builder.exec(exec_request).expect_success().commit();
+
+    let request_stats = builder
+        .get_request_stats(0)
+        .expect("should have request stats");
+    assert_eq!(request_stats.deploy_count, DEPLOY_ITERATIONS.len() as u32);
+
+    let costs = builder.exec_costs(0);
+    let total_gas = costs.iter().fold(Gas::default(), |sum, cost| sum + *cost);
+    assert_eq!(request_stats.total_gas, total_gas);
+
+    let phase_times = request_stats.phase_times;
+    assert!(phase_times.payment > Duration::default());
+    assert!(phase_times.session > Duration::default());
+    assert!(phase_times.finalization > Duration::default());
+    // only the gRPC server converts from and to protobuf
+    assert_eq!(request_stats.protobuf_conversion_time, Duration::default());
+    assert_sums_approximately_to(phase_times.total(), request_stats.total_time);
+
+    let (slowest_deploy_hash, slowest_duration) = request_stats
+        .slowest_deploy
+        .expect("should have a slowest deploy");
+    assert_eq!(
+        slowest_deploy_hash,
+        deploy_hash(DEPLOY_ITERATIONS.len() - 1)
+    );
+    assert!(slowest_duration <= phase_times.total());
+}
+
+#[ignore]
+#[test]
+fn should_not_affect_costs_or_effects_when_collecting_request_stats() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let exec_request = slow_deploys_request(&builder, false);
+    builder.exec(exec_request).expect_success();
+    let exec_request = slow_deploys_request(&builder, true);
+    builder.exec(exec_request).expect_success();
+
+    assert!(builder.get_request_stats(0).is_none());
+    assert!(builder.get_request_stats(1).is_some());
+    assert_eq!(builder.exec_costs(0), builder.exec_costs(1));
+    assert_eq!(builder.get_transforms()[0], builder.get_transforms()[1]);
+}
+
+#[ignore]
+#[test]
+fn should_report_request_stats_in_execute_response_only_if_requested() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let engine_state = builder.get_engine_state();
+
+    let exec_request: ipc::ExecuteRequest = slow_deploys_request(&builder, true).into();
+    let execute_response = engine_state
+        .execute(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should execute");
+    assert!(execute_response.has_success());
+    assert!(execute_response.has_request_stats());
+
+    let request_stats = execute_response.get_request_stats();
+    assert_eq!(
+        request_stats.get_deploy_count(),
+        DEPLOY_ITERATIONS.len() as u32
+    );
+    let phase_times = request_stats.get_phase_times();
+    let phase_micros = phase_times.get_preprocess_micros()
+        + phase_times.get_payment_micros()
+        + phase_times.get_session_micros()
+        + phase_times.get_finalization_micros()
+        + phase_times.get_protobuf_conversion_micros();
+    assert_sums_approximately_to(
+        Duration::from_micros(phase_micros),
+        Duration::from_micros(request_stats.get_total_micros()),
+    );
+    assert_eq!(
+        request_stats.get_slowest_deploy().get_deploy_hash(),
+        deploy_hash(DEPLOY_ITERATIONS.len() - 1).as_bytes()
+    );
+
+    let exec_request: ipc::ExecuteRequest = slow_deploys_request(&builder, false).into();
+    let execute_response = engine_state
+        .execute(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should execute");
+    assert!(execute_response.has_success());
+    assert!(!execute_response.has_request_stats());
+}
@@ -0,0 +1,156 @@
+use engine_core::engine_state::{
+    executable_deploy_item::ExecutableDeployItem, execute_request::ExecuteRequest,
+    genesis::GenesisAccount, CONV_RATE,
+};
+use engine_shared::{motes::Motes, stored_value::StoredValue};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_PAYMENT,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, Key, URef, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const DO_NOTHING_WASM: &str = "do_nothing.wasm";
+const SPONSOR_STORED_WASM: &str = "sponsor_stored.wasm";
+const SPONSOR_CONTRACT_NAME: &str = "sponsor_stored";
+const SPONSOR_PURSE_NAME: &str = "sponsor_purse";
+
+/// Runs genesis with an additional account 1 which has no funds, and stores the sponsor contract
+/// funded with `sponsor_funding` from the default account.  Returns the sponsor contract's hash
+/// and purse.
+fn setup(builder: &mut InMemoryWasmTestBuilder, sponsor_funding: U512) -> ([u8; 32], URef) {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        tmp.push(GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            Motes::zero(),
+            Motes::zero(),
+        ));
+        tmp
+    };
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        SPONSOR_STORED_WASM,
+        (sponsor_funding,),
+    )
+    .build();
+
+    builder
+        .run_genesis(&utils::create_run_genesis_request(accounts))
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let sponsor_hash = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .named_keys()
+        .get(SPONSOR_CONTRACT_NAME)
+        .and_then(|key| key.into_hash())
+        .expect("should have sponsor contract hash");
+    let sponsor_purse = match builder.query(None, Key::Hash(sponsor_hash), &[]) {
+        Ok(StoredValue::Contract(contract)) => contract
+            .named_keys()
+            .get(SPONSOR_PURSE_NAME)
+            .and_then(|key| key.into_uref())
+            .expect("should have sponsor purse"),
+        other => panic!("expected sponsor contract, got {:?}", other),
+    };
+    (sponsor_hash, sponsor_purse)
+}
+
+fn sponsored_exec_request(sponsor_hash: [u8; 32]) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(ACCOUNT_1_ADDR)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_sponsor_payment_hash(sponsor_hash, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[ACCOUNT_1_ADDR])
+        .with_deploy_hash([1; 32])
+        .build();
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
+fn main_purse_balance(builder: &InMemoryWasmTestBuilder, account: PublicKey) -> U512 {
+    let account = builder.get_account(account).expect("should have account");
+    builder.get_purse_balance(account.main_purse())
+}
+
+#[ignore]
+#[test]
+fn should_pay_for_deploy_of_account_without_funds_from_sponsor_contract() {
+    let sponsor_funding = *DEFAULT_PAYMENT * 2;
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let (sponsor_hash, sponsor_purse) = setup(&mut builder, sponsor_funding);
+    assert_eq!(main_purse_balance(&builder, ACCOUNT_1_ADDR), U512::zero());
+
+    builder
+        .exec(sponsored_exec_request(sponsor_hash))
+        .expect_success()
+        .commit();
+
+    // the sponsor was charged only for the cost of the deploy, as the rest was refunded to the
+    // sponsor purse rather than to the account
+    let cost = builder
+        .exec_costs(1)
+        .into_iter()
+        .fold(Default::default(), |total, cost| total + cost);
+    let cost = Motes::from_gas(cost, CONV_RATE).expect("should convert cost to motes");
+    assert_eq!(
+        builder.get_purse_balance(sponsor_purse),
+        sponsor_funding - cost.value()
+    );
+    assert_eq!(main_purse_balance(&builder, ACCOUNT_1_ADDR), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_raise_insufficient_payment_when_sponsor_purse_is_underfunded() {
+    let sponsor_funding = U512::one();
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let (sponsor_hash, _sponsor_purse) = setup(&mut builder, sponsor_funding);
+
+    builder.exec(sponsored_exec_request(sponsor_hash)).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains("InsufficientPayment"),
+        "expected insufficient payment, got: {}",
+        error_message
+    );
+    assert_eq!(main_purse_balance(&builder, ACCOUNT_1_ADDR), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_not_use_sponsor_contract_as_session() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let (sponsor_hash, _sponsor_purse) = setup(&mut builder, *DEFAULT_PAYMENT);
+
+    let mut deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(DO_NOTHING_WASM, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    deploy.session = ExecutableDeployItem::SponsorContractByHash {
+        hash: sponsor_hash.to_vec(),
+        args: deploy.session.take_args(),
+    };
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.exec(exec_request).commit();
+
+    let error_message = builder
+        .exec_error_message(1)
+        .expect("should have exec response");
+    assert!(
+        error_message.contains("SponsorContractAsSession"),
+        "expected sponsor contract as session error, got: {}",
+        error_message
+    );
+}
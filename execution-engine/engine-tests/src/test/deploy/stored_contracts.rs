@@ -6,7 +6,7 @@ use engine_shared::{motes::Motes, stored_value::StoredValue, transform::Transfor
 use engine_test_support::{
     internal::{
         utils, AdditiveMapDiff, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        UpgradeRequestBuilder, DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
+        UpgradeRequestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
@@ -67,7 +67,7 @@ fn should_exec_non_stored_code() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1; 32])
             .build();
 
@@ -158,10 +158,10 @@ fn should_exec_stored_code_by_hash() {
                 (account_1_public_key, U512::from(transferred_amount)),
             )
             .with_stored_payment_hash(
-                stored_payment_contract_hash.to_vec(),
+                stored_payment_contract_hash,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -252,7 +252,7 @@ fn should_exec_stored_code_by_named_hash() {
                 STANDARD_PAYMENT_CONTRACT_NAME,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -316,7 +316,7 @@ fn should_exec_stored_code_by_named_uref() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1; 32])
             .build();
 
@@ -358,7 +358,7 @@ fn should_exec_stored_code_by_named_uref() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -442,7 +442,7 @@ fn should_exec_payment_and_session_stored_code() {
                 STANDARD_PAYMENT_CONTRACT_NAME,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -477,7 +477,7 @@ fn should_exec_payment_and_session_stored_code() {
                 STANDARD_PAYMENT_CONTRACT_NAME,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([3; 32])
             .build();
 
@@ -536,7 +536,7 @@ fn should_produce_same_transforms_by_uref_or_named_uref() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1u8; 32])
             .build();
 
@@ -571,7 +571,7 @@ fn should_produce_same_transforms_by_uref_or_named_uref() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2u8; 32])
             .build();
 
@@ -593,7 +593,7 @@ fn should_produce_same_transforms_by_uref_or_named_uref() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1u8; 32])
             .build();
 
@@ -616,7 +616,7 @@ fn should_produce_same_transforms_by_uref_or_named_uref() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2u8; 32])
             .build();
 
@@ -653,7 +653,7 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
                     &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                     (U512::from(payment_purse_amount),),
                 )
-                .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
                 .with_deploy_hash([1; 32])
                 .build();
 
@@ -673,7 +673,7 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
                     &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                     (U512::from(payment_purse_amount),),
                 )
-                .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
                 .with_deploy_hash([2; 32])
                 .build();
 
@@ -710,10 +710,10 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
                     (account_1_public_key, U512::from(transferred_amount)),
                 )
                 .with_stored_payment_hash(
-                    stored_payment_contract_hash.to_vec(),
+                    stored_payment_contract_hash,
                     (U512::from(payment_purse_amount),),
                 )
-                .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
                 .with_deploy_hash([3; 32])
                 .build();
 
@@ -737,7 +737,7 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
                     &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                     (U512::from(payment_purse_amount),),
                 )
-                .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
                 .with_deploy_hash(deploy_hash)
                 .build();
 
@@ -755,7 +755,7 @@ fn should_have_equivalent_transforms_with_stored_contract_pointers() {
                     &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                     (U512::from(payment_purse_amount),),
                 )
-                .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
                 .with_deploy_hash([3; 32])
                 .build();
 
@@ -881,7 +881,7 @@ fn should_fail_payment_stored_at_named_key_with_incompatible_major_version() {
                 STANDARD_PAYMENT_CONTRACT_NAME,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -959,10 +959,10 @@ fn should_fail_payment_stored_at_hash_with_incompatible_major_version() {
             .with_address(DEFAULT_ACCOUNT_ADDR)
             .with_session_code(&format!("{}.wasm", DO_NOTHING_NAME), ())
             .with_stored_payment_hash(
-                stored_payment_contract_hash.to_vec(),
+                stored_payment_contract_hash,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -1044,7 +1044,7 @@ fn should_fail_payment_stored_at_uref_with_incompatible_major_version() {
                 stored_payment_contract_uref,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -1125,7 +1125,7 @@ fn should_fail_session_stored_at_named_key_with_incompatible_major_version() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -1201,12 +1201,12 @@ fn should_fail_session_stored_at_hash_with_incompatible_major_version() {
     let exec_request_stored_payment = {
         let deploy = DeployItemBuilder::new()
             .with_address(DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(do_nothing_contract_hash.to_vec(), ())
+            .with_stored_session_hash(do_nothing_contract_hash, ())
             .with_payment_code(
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -1288,7 +1288,7 @@ fn should_fail_session_stored_at_uref_with_incompatible_major_version() {
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -1393,10 +1393,10 @@ fn should_execute_stored_payment_and_session_code_with_new_major_version() {
             .with_address(DEFAULT_ACCOUNT_ADDR)
             .with_stored_session_uref(do_nothing_stored_uref, ())
             .with_stored_payment_hash(
-                standard_payment_stored_hash.to_vec(),
+                standard_payment_stored_hash,
                 (U512::from(payment_purse_amount),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([3; 32])
             .build();
 
@@ -0,0 +1,80 @@
+use engine_core::engine_state::{
+    genesis::ExecConfig, run_genesis_request::RunGenesisRequest, Error,
+};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_EXEC_CONFIG,
+        DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION, DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use engine_wasm_prep::{wasm_costs::WasmCosts, PreprocessingError};
+
+/// Session code which also exports `revert_test_ext`, the entry point it stores.
+const CONTRACT_SUBCALL_REVERT_DEFINE: &str = "subcall_revert_define.wasm";
+const REVERT_TEST_EXT: &str = "revert_test_ext";
+
+fn run_genesis_request(strict_session_exports: bool) -> RunGenesisRequest {
+    let wasm_costs = WasmCosts {
+        strict_session_exports,
+        ..*DEFAULT_WASM_COSTS
+    };
+    let exec_config = ExecConfig::new(
+        DEFAULT_EXEC_CONFIG.mint_installer_bytes().to_vec(),
+        DEFAULT_EXEC_CONFIG
+            .proof_of_stake_installer_bytes()
+            .to_vec(),
+        DEFAULT_EXEC_CONFIG
+            .standard_payment_installer_bytes()
+            .to_vec(),
+        DEFAULT_EXEC_CONFIG.accounts().to_vec(),
+        wasm_costs,
+        DEFAULT_EXEC_CONFIG.account_config(),
+    );
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+fn exec_subcall_revert_define(strict_session_exports: bool) -> InMemoryWasmTestBuilder {
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_SUBCALL_REVERT_DEFINE, ())
+            .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&run_genesis_request(strict_session_exports))
+        .exec(exec_request)
+        .commit();
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_reject_session_code_with_unexpected_exports_in_strict_mode() {
+    let builder = exec_subcall_revert_define(true);
+
+    let response = builder
+        .get_exec_response(0)
+        .expect("there should be a response");
+    match utils::get_precondition_failure(response) {
+        Error::WasmPreprocessing(PreprocessingError::UnexpectedExports(names)) => {
+            assert_eq!(*names, vec![REVERT_TEST_EXT.to_string()])
+        }
+        error => panic!("unexpected precondition failure: {}", error),
+    }
+}
+
+#[ignore]
+#[test]
+fn should_allow_session_code_with_other_exports_by_default() {
+    let builder = exec_subcall_revert_define(false);
+
+    assert!(!builder.is_error());
+    let default_account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert!(default_account.named_keys().contains_key("revert_test"));
+}
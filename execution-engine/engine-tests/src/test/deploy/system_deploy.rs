@@ -0,0 +1,215 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::{
+    exit_code::{ExitCode, PreconditionReason},
+    genesis::{GenesisAccount, POS_BONDING_PURSE, POS_REWARDS_PURSE},
+    SYSTEM_ACCOUNT_ADDR,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, CLValue, Key, U512};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const CONTRACT_POS_SLASH: &str = "pos_slash.wasm";
+const CONTRACT_PRUNE_KEY: &str = "prune_key.wasm";
+const CONTRACT_PUT_NAMED_KEY: &str = "put_named_key.wasm";
+const PRUNED_KEY_NAME: &str = "pruned";
+const VALIDATOR_1_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const VALIDATOR_2_ADDR: PublicKey = PublicKey::ed25519_from([43; 32]);
+const VALIDATOR_1_STAKE: u64 = 50_000;
+const VALIDATOR_2_STAKE: u64 = 60_000;
+
+fn get_stake(builder: &InMemoryWasmTestBuilder, validator: PublicKey) -> Option<U512> {
+    let pos_contract = builder.get_pos_contract();
+    let stake_key_name = format!("v_{}", base16::encode_lower(&validator.as_bytes()));
+    let stake_key = pos_contract.named_keys().get(&stake_key_name)?;
+    let stake = builder
+        .query(None, *stake_key, &[])
+        .expect("should have stake");
+    let cl_value = CLValue::try_from(stake).expect("should be a CLValue");
+    Some(cl_value.into_t().expect("should be a U512"))
+}
+
+fn get_pos_purse_balance(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> U512 {
+    let purse = builder
+        .get_pos_contract()
+        .named_keys()
+        .get(purse_name)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should find PoS purse");
+    builder.get_purse_balance(purse)
+}
+
+fn put_value_to_prune(builder: &mut InMemoryWasmTestBuilder) -> Key {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_PUT_NAMED_KEY,
+        (String::from(PRUNED_KEY_NAME),),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let key = *builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(PRUNED_KEY_NAME)
+        .expect("should have named key");
+    builder.query(None, key, &[]).expect("should have value");
+    key
+}
+
+fn setup_validators() -> InMemoryWasmTestBuilder {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    for (validator, stake) in &[
+        (VALIDATOR_1_ADDR, VALIDATOR_1_STAKE),
+        (VALIDATOR_2_ADDR, VALIDATOR_2_STAKE),
+    ] {
+        let stake = Motes::new(U512::from(*stake));
+        accounts.push(GenesisAccount::new(*validator, stake, stake));
+    }
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_reject_user_deploy_from_system_account() {
+    let exec_request =
+        ExecuteRequestBuilder::standard(SYSTEM_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    assert_eq!(
+        builder.get_exit_code(0),
+        Some(ExitCode::Precondition(
+            PreconditionReason::InvalidSystemDeploy
+        ))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_reject_system_deploy_from_user_account() {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .as_system_deploy()
+        .build();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    assert_eq!(
+        builder.get_exit_code(0),
+        Some(ExitCode::Precondition(
+            PreconditionReason::InvalidSystemDeploy
+        ))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_slash_validator_from_system_deploy() {
+    let mut builder = setup_validators();
+    let bonding_purse_balance_before = get_pos_purse_balance(&builder, POS_BONDING_PURSE);
+    let rewards_purse_balance_before = get_pos_purse_balance(&builder, POS_REWARDS_PURSE);
+    assert_eq!(
+        get_stake(&builder, VALIDATOR_1_ADDR),
+        Some(U512::from(VALIDATOR_1_STAKE))
+    );
+
+    let exec_request =
+        ExecuteRequestBuilder::system(CONTRACT_POS_SLASH, (VALIDATOR_1_ADDR,)).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert_eq!(get_stake(&builder, VALIDATOR_1_ADDR), None);
+    assert_eq!(
+        get_stake(&builder, VALIDATOR_2_ADDR),
+        Some(U512::from(VALIDATOR_2_STAKE))
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_BONDING_PURSE),
+        bonding_purse_balance_before - U512::from(VALIDATOR_1_STAKE)
+    );
+    assert_eq!(
+        get_pos_purse_balance(&builder, POS_REWARDS_PURSE),
+        rewards_purse_balance_before + U512::from(VALIDATOR_1_STAKE)
+    );
+
+    // system deploys aren't charged for
+    let response = builder
+        .get_exec_response(0)
+        .expect("should have exec response");
+    assert_eq!(response[0].cost().value(), U512::zero());
+}
+
+#[ignore]
+#[test]
+fn should_not_slash_validator_from_user_deploy() {
+    let mut builder = setup_validators();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_SLASH,
+        (VALIDATOR_1_ADDR,),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_error());
+    assert_eq!(
+        get_stake(&builder, VALIDATOR_1_ADDR),
+        Some(U512::from(VALIDATOR_1_STAKE))
+    );
+}
+
+#[ignore]
+#[test]
+fn should_prune_key_from_system_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let key = put_value_to_prune(&mut builder);
+
+    let exec_request = ExecuteRequestBuilder::system(CONTRACT_PRUNE_KEY, (key,)).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    assert!(builder.query(None, key, &[]).is_err());
+}
+
+#[ignore]
+#[test]
+fn should_not_prune_key_from_user_deploy() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let key = put_value_to_prune(&mut builder);
+
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_PRUNE_KEY, (key,)).build();
+    builder.exec(exec_request).commit();
+
+    assert!(builder.is_error());
+    builder
+        .query(None, key, &[])
+        .expect("should still have value");
+}
@@ -0,0 +1,112 @@
+use engine_core::engine_state::{
+    deploy_item::DeployItem,
+    exit_code::{ExitCode, PreconditionReason},
+    validate_deploy::{ValidateDeployRequest, ValidateDeployResult},
+};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_PROTOCOL_VERSION, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+
+const CONTRACT_WAT_WITH_FLOATS: &str = r#"
+(module
+    (memory (;0;) 1)
+    (export "memory" (memory 0))
+    (func (export "call")
+      f32.const 1.5
+      f32.const 2.5
+      f32.add
+      drop))
+"#;
+
+fn deploy_with_session(session: impl FnOnce(DeployItemBuilder) -> DeployItemBuilder) -> DeployItem {
+    session(DeployItemBuilder::new())
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([1; 32])
+        .build()
+}
+
+fn validate(builder: &InMemoryWasmTestBuilder, deploy_item: DeployItem) -> ValidateDeployResult {
+    let validate_request = ValidateDeployRequest::new(deploy_item, *DEFAULT_PROTOCOL_VERSION);
+    builder
+        .get_engine_state()
+        .validate_deploy(validate_request)
+        .expect("should validate deploy")
+}
+
+#[ignore]
+#[test]
+fn should_not_preprocess_validated_deploy_again_when_executed() {
+    let deploy_item =
+        deploy_with_session(|builder| builder.with_session_code(CONTRACT_DO_NOTHING, ()));
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let validate_result = validate(&builder, deploy_item.clone());
+    assert!(validate_result.is_valid());
+    assert_eq!(validate_result.exit_code(), ExitCode::Success);
+
+    let (hits, misses) = {
+        let module_cache = builder.get_engine_state().module_cache();
+        (module_cache.hits(), module_cache.misses())
+    };
+    assert_eq!(misses, 1);
+
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let module_cache = builder.get_engine_state().module_cache();
+    assert_eq!(module_cache.misses(), misses);
+    assert_eq!(module_cache.hits(), hits + 1);
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_with_floats_as_invalid_wasm() {
+    let wasm_binary = wabt::wat2wasm(CONTRACT_WAT_WITH_FLOATS).expect("should parse");
+    let deploy_item = deploy_with_session(|builder| builder.with_session_bytes(wasm_binary, ()));
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let validate_result = validate(&builder, deploy_item.clone());
+    assert!(!validate_result.is_valid());
+    assert_eq!(
+        validate_result.exit_code(),
+        ExitCode::Precondition(PreconditionReason::InvalidWasm)
+    );
+    assert!(builder.get_engine_state().module_cache().is_empty());
+
+    // executing the deploy fails the same way
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(deploy_item).build();
+    builder.exec(exec_request).commit();
+    assert_eq!(builder.get_exit_code(0), Some(validate_result.exit_code()));
+}
+
+#[ignore]
+#[test]
+fn should_reject_deploy_without_authorization_keys() {
+    let deploy_item = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_deploy_hash([1; 32])
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let validate_result = validate(&builder, deploy_item);
+    assert_eq!(
+        validate_result.exit_code(),
+        ExitCode::Precondition(PreconditionReason::Authorization)
+    );
+}
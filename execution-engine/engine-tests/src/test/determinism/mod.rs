@@ -0,0 +1,97 @@
+//! Canary contracts whose post-state hashes and costs must not change unless intended, e.g. by a
+//! new protocol version.  See [`engine_test_support::internal::determinism`] for how to regenerate
+//! the manifest.
+
+use engine_core::{
+    engine_state::{EngineConfig, EngineState},
+    execution,
+};
+use engine_grpc_server::engine_server::ipc_grpc::ExecutionEngineService;
+use engine_storage::global_state::StateProvider;
+use engine_test_support::{
+    internal::{
+        determinism::{CanaryOutcome, DeterminismManifest},
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, LmdbWasmTestBuilder,
+        WasmTestBuilder, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+
+const MANIFEST_PATH: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/src/test/determinism/manifest.txt"
+);
+
+const CANARIES: [&str; 5] = [
+    "canary_big_int_math.wasm",
+    "canary_bytesrepr.wasm",
+    "canary_deep_branching.wasm",
+    "canary_host_buffer.wasm",
+    "canary_memory_growth.wasm",
+];
+
+/// Deploy hashes seed the addresses of new URefs, so they're fixed to keep post-state hashes
+/// reproducible.
+const CANARY_DEPLOY_HASH: [u8; 32] = [42; 32];
+
+fn run_canary<S>(builder: &mut WasmTestBuilder<S>, canary: &str) -> CanaryOutcome
+where
+    S: StateProvider,
+    S::Error: Into<execution::Error>,
+    EngineState<S>: ExecutionEngineService,
+{
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_session_code(canary, ())
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash(CANARY_DEPLOY_HASH)
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder.exec(exec_request).expect_success().commit();
+    CanaryOutcome::from_builder(builder)
+}
+
+fn run_canary_in_memory(canary: &str) -> CanaryOutcome {
+    run_canary(&mut InMemoryWasmTestBuilder::default(), canary)
+}
+
+fn run_canary_in_lmdb(canary: &str) -> CanaryOutcome {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let mut builder = LmdbWasmTestBuilder::new_with_config(data_dir.path(), EngineConfig::new());
+    run_canary(&mut builder, canary)
+}
+
+#[ignore]
+#[test]
+fn should_run_canaries_deterministically() {
+    for canary in CANARIES.iter() {
+        let first_outcome = run_canary_in_memory(canary);
+        let second_outcome = run_canary_in_memory(canary);
+        assert_eq!(
+            first_outcome, second_outcome,
+            "{} differs between in-memory runs",
+            canary
+        );
+
+        let lmdb_outcome = run_canary_in_lmdb(canary);
+        assert_eq!(
+            first_outcome, lmdb_outcome,
+            "{} differs between in-memory and LMDB backends",
+            canary
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_match_canary_manifest() {
+    let mut manifest = DeterminismManifest::new();
+    for canary in CANARIES.iter() {
+        let name = canary.trim_end_matches(".wasm");
+        manifest.insert(name, run_canary_in_memory(canary));
+    }
+    manifest.check_against_file(MANIFEST_PATH);
+}
@@ -6,7 +6,9 @@ use engine_test_support::internal::{
     utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder as TestBuilder,
     DEFAULT_RUN_GENESIS_REQUEST,
 };
-use types::{account::PublicKey, bytesrepr::FromBytes, ApiError, CLTyped, CLValue, Key, U512};
+use types::{
+    account::PublicKey, bytesrepr::FromBytes, ApiError, BlockTime, CLTyped, CLValue, Key, U512,
+};
 
 const TRANFER_TO_ACCOUNT_WASM: &str = "transfer_to_account_u512.wasm";
 const VESTING_CONTRACT_WASM: &str = "vesting_smart_contract.wasm";
@@ -155,7 +157,7 @@ impl VestingTest {
                 vesting_config.admin_release_duration,
             ),
         )
-        .with_block_time(self.current_timestamp)
+        .with_block_time(BlockTime::from_millis(self.current_timestamp))
         .build();
         self.builder.exec(request);
         self
@@ -167,7 +169,7 @@ impl VestingTest {
             self.get_proxy_hash(),
             (self.get_vesting_hash(), method::PAUSE),
         )
-        .with_block_time(self.current_timestamp)
+        .with_block_time(BlockTime::from_millis(self.current_timestamp))
         .build();
         self.builder.exec(request);
         self
@@ -179,7 +181,7 @@ impl VestingTest {
             self.get_proxy_hash(),
             (self.get_vesting_hash(), method::UNPAUSE),
         )
-        .with_block_time(self.current_timestamp)
+        .with_block_time(BlockTime::from_millis(self.current_timestamp))
         .build();
         self.builder.exec(request);
         self
@@ -191,7 +193,7 @@ impl VestingTest {
             self.get_proxy_hash(),
             (self.get_vesting_hash(), method::WITHDRAW_PROXY, amount),
         )
-        .with_block_time(self.current_timestamp)
+        .with_block_time(BlockTime::from_millis(self.current_timestamp))
         .build();
         self.builder.exec(request);
         self
@@ -203,7 +205,7 @@ impl VestingTest {
             self.get_proxy_hash(),
             (self.get_vesting_hash(), method::ADMIN_RELEASE_PROXY),
         )
-        .with_block_time(self.current_timestamp)
+        .with_block_time(BlockTime::from_millis(self.current_timestamp))
         .build();
         self.builder.exec(request);
         self
@@ -217,7 +219,7 @@ impl VestingTest {
     ) -> Self {
         let request =
             ExecuteRequestBuilder::standard(sender, TRANFER_TO_ACCOUNT_WASM, (recipient, amount))
-                .with_block_time(self.current_timestamp)
+                .with_block_time(BlockTime::from_millis(self.current_timestamp))
                 .build();
         self.builder.exec(request).expect_success().commit();
         self
@@ -1,8 +1,9 @@
+use engine_core::engine_state::exit_code::ExitCode;
 use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::PublicKey, ApiError, U512};
+use types::{account::PublicKey, U512};
 
 const FAUCET_CONTRACT: &str = "faucet.wasm";
 const NEW_ACCOUNT_ADDR: PublicKey = PublicKey::ed25519_from([99u8; 32]);
@@ -63,11 +64,5 @@ fn should_fail_if_already_funded() {
         .commit()
         .exec(exec_request_2); // should fail
 
-    let error_msg = builder
-        .exec_error_message(1)
-        .expect("should have error message");
-    assert!(
-        error_msg.contains(&format!("{:?}", ApiError::User(1))),
-        error_msg
-    );
+    assert_eq!(builder.get_exit_code(1), Some(ExitCode::UserRevert(1)));
 }
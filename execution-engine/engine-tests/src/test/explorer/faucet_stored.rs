@@ -1,11 +1,9 @@
+use engine_core::engine_state::exit_code::ExitCode;
 use engine_test_support::{
-    internal::{
-        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_RUN_GENESIS_REQUEST,
 };
-use types::{account::PublicKey, ApiError, U512};
+use types::{account::PublicKey, U512};
 
 const FAUCET: &str = "faucet";
 const STANDARD_PAYMENT_CONTRACT_NAME: &str = "standard_payment";
@@ -51,12 +49,12 @@ fn should_get_funds_from_faucet_stored() {
     let exec_request = {
         let deploy = DeployItemBuilder::new()
             .with_address(DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(contract_hash.to_vec(), (NEW_ACCOUNT_ADDR, amount))
+            .with_stored_session_hash(contract_hash, (NEW_ACCOUNT_ADDR, amount))
             .with_payment_code(
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(10_000_000),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -99,12 +97,12 @@ fn should_fail_if_already_funded() {
     let exec_request = {
         let deploy = DeployItemBuilder::new()
             .with_address(DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(contract_hash.to_vec(), (NEW_ACCOUNT_ADDR, amount))
+            .with_stored_session_hash(contract_hash, (NEW_ACCOUNT_ADDR, amount))
             .with_payment_code(
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(10_000_000),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -117,12 +115,12 @@ fn should_fail_if_already_funded() {
     let exec_request = {
         let deploy = DeployItemBuilder::new()
             .with_address(DEFAULT_ACCOUNT_ADDR)
-            .with_stored_session_hash(contract_hash.to_vec(), (NEW_ACCOUNT_ADDR, amount))
+            .with_stored_session_hash(contract_hash, (NEW_ACCOUNT_ADDR, amount))
             .with_payment_code(
                 &format!("{}.wasm", STANDARD_PAYMENT_CONTRACT_NAME),
                 (U512::from(10_000_000),),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -131,13 +129,9 @@ fn should_fail_if_already_funded() {
 
     builder.exec(exec_request);
 
-    let exec_response = builder
-        .get_exec_response(2)
-        .expect("Expected to be called after run()");
-
-    let error_message = utils::get_error_message(exec_response);
-    assert!(
-        error_message.contains(&format!("{:?}", ApiError::User(1))),
+    assert_eq!(
+        builder.get_exit_code(2),
+        Some(ExitCode::UserRevert(1)),
         "should have reverted with user error 1 (already funded)"
     );
 }
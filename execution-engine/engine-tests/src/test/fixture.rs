@@ -0,0 +1,138 @@
+use std::{cell::Cell, fs};
+
+use engine_core::engine_state::deploy_item::DeployItem;
+use engine_storage::global_state::in_memory::FixtureError;
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([43; 32]);
+const TRANSFER_AMOUNT: u64 = 1_000_000_000;
+const FIXTURE_FILE: &str = "setup.fixture";
+/// The length of the magic bytes at the start of a builder fixture, which are followed by its
+/// format version.
+const FIXTURE_MAGIC_LENGTH: usize = 18;
+
+fn transfer_deploy(from: PublicKey, to: PublicKey, deploy_hash: [u8; 32]) -> DeployItem {
+    DeployItemBuilder::new()
+        .with_address(from)
+        .with_session_code(
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (to, U512::from(TRANSFER_AMOUNT)),
+        )
+        .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[from])
+        .with_deploy_hash(deploy_hash)
+        .build()
+}
+
+/// Runs genesis and funds account 1.
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(transfer_deploy(
+        DEFAULT_ACCOUNT_ADDR,
+        ACCOUNT_1_ADDR,
+        [1; 32],
+    ))
+    .build();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    builder
+}
+
+/// Runs a transfer from account 1 to account 2 and returns the resulting post-state hash.
+fn run_follow_up_deploy(builder: &mut InMemoryWasmTestBuilder) -> Vec<u8> {
+    let exec_request = ExecuteRequestBuilder::from_deploy_item(transfer_deploy(
+        ACCOUNT_1_ADDR,
+        ACCOUNT_2_ADDR,
+        [2; 32],
+    ))
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+    builder.get_post_state_hash()
+}
+
+#[ignore]
+#[test]
+fn should_behave_identically_when_loaded_from_fixture() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let fixture_path = data_dir.path().join(FIXTURE_FILE);
+
+    let mut built = setup();
+    built.save_fixture(&fixture_path);
+    let mut loaded =
+        InMemoryWasmTestBuilder::from_fixture_file(&fixture_path).expect("should load fixture");
+
+    assert_eq!(loaded.get_post_state_hash(), built.get_post_state_hash());
+    assert_eq!(loaded.get_genesis_hash(), built.get_genesis_hash());
+    assert_eq!(
+        loaded.get_pos_contract_uref(),
+        built.get_pos_contract_uref()
+    );
+    assert_eq!(
+        loaded.get_account(ACCOUNT_1_ADDR),
+        built.get_account(ACCOUNT_1_ADDR)
+    );
+
+    let built_post_state_hash = run_follow_up_deploy(&mut built);
+    let loaded_post_state_hash = run_follow_up_deploy(&mut loaded);
+    assert_eq!(loaded_post_state_hash, built_post_state_hash);
+}
+
+#[ignore]
+#[test]
+fn should_regenerate_cached_fixture_when_sources_change() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let fixture_path = data_dir.path().join(FIXTURE_FILE);
+    let source_key = utils::fixture_source_key(&[CONTRACT_TRANSFER_TO_ACCOUNT]);
+    let setup_count = Cell::new(0);
+    let counted_setup = || {
+        setup_count.set(setup_count.get() + 1);
+        setup()
+    };
+
+    let first =
+        InMemoryWasmTestBuilder::from_cached_fixture(&fixture_path, &source_key, counted_setup);
+    assert_eq!(setup_count.get(), 1);
+
+    let second =
+        InMemoryWasmTestBuilder::from_cached_fixture(&fixture_path, &source_key, counted_setup);
+    assert_eq!(setup_count.get(), 1);
+    assert_eq!(second.get_post_state_hash(), first.get_post_state_hash());
+
+    let mut changed_source_key = source_key.clone();
+    changed_source_key.push(0);
+    InMemoryWasmTestBuilder::from_cached_fixture(&fixture_path, &changed_source_key, counted_setup);
+    assert_eq!(setup_count.get(), 2);
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_load_fixture_of_older_format() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let fixture_path = data_dir.path().join(FIXTURE_FILE);
+    setup().save_fixture(&fixture_path);
+
+    let mut bytes = fs::read(&fixture_path).expect("should read fixture");
+    bytes[FIXTURE_MAGIC_LENGTH..FIXTURE_MAGIC_LENGTH + 4].copy_from_slice(&0u32.to_le_bytes());
+    fs::write(&fixture_path, bytes).expect("should write fixture");
+
+    let error = InMemoryWasmTestBuilder::from_fixture_file(&fixture_path)
+        .err()
+        .expect("should fail to load");
+    match error {
+        FixtureError::UnsupportedVersion { found: 0, .. } => {}
+        _ => panic!("unexpected error: {}", error),
+    }
+    assert!(error.to_string().contains("regenerate"));
+}
@@ -0,0 +1,137 @@
+use std::convert::TryFrom;
+
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{ListRootsRequest, ListRootsRequest_PageToken, ListRootsResponse_Root},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{
+    additive_map::AdditiveMap,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_test_support::internal::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use types::{CLValue, Key};
+
+const COUNTER_KEY: Key = Key::Hash([45; 32]);
+const BLOCK_COUNT: i32 = 5;
+const PAGE_SIZE: u32 = 2;
+
+/// Commits `BLOCK_COUNT` blocks after genesis, the `n`th of which writes the counter as `n`.
+fn commit_counter_blocks(builder: &mut InMemoryWasmTestBuilder) {
+    for block in 1..=BLOCK_COUNT {
+        let mut effects = AdditiveMap::new();
+        effects.insert(
+            COUNTER_KEY,
+            Transform::Write(StoredValue::CLValue(
+                CLValue::from_t(block).expect("should create value"),
+            )),
+        );
+        let post_state_hash = builder.get_post_state_hash();
+        builder.commit_effects(post_state_hash, effects);
+    }
+}
+
+/// Walks every page of the listed roots.
+fn list_roots(builder: &InMemoryWasmTestBuilder) -> Vec<ListRootsResponse_Root> {
+    let mut result = Vec::new();
+    let mut page_token: Option<ListRootsRequest_PageToken> = None;
+    loop {
+        let mut list_roots_request = ListRootsRequest::new();
+        list_roots_request.set_page_size(PAGE_SIZE);
+        if let Some(page_token) = page_token.take() {
+            list_roots_request.set_page_token(page_token);
+        }
+
+        let mut response = builder
+            .get_engine_state()
+            .list_roots(RequestOptions::new(), list_roots_request)
+            .wait_drop_metadata()
+            .expect("should list roots");
+        assert!(response.has_success(), "{:?}", response.get_failure());
+        let mut page = response.take_success();
+        assert!(page.get_roots().len() <= PAGE_SIZE as usize);
+        result.extend(page.take_roots().into_iter());
+        if !page.has_next_page_token() {
+            return result;
+        }
+        page_token = Some(page.take_next_page_token());
+    }
+}
+
+fn listed_hashes(builder: &InMemoryWasmTestBuilder) -> Vec<Vec<u8>> {
+    list_roots(builder)
+        .iter()
+        .map(|root| root.get_state_hash().to_vec())
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_list_genesis_and_committed_roots_with_parent_hints() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let genesis_hash = builder.get_genesis_hash();
+    commit_counter_blocks(&mut builder);
+
+    let roots = list_roots(&builder);
+    let hashes: Vec<Vec<u8>> = roots
+        .iter()
+        .map(|root| root.get_state_hash().to_vec())
+        .collect();
+    let mut sorted_hashes = hashes.clone();
+    sorted_hashes.sort();
+    assert_eq!(hashes, sorted_hashes, "roots should be listed in order");
+    assert!(hashes.contains(&genesis_hash));
+
+    let mut parent = genesis_hash;
+    for post_state_hash in builder.get_post_state_hashes() {
+        let root = roots
+            .iter()
+            .find(|root| root.get_state_hash() == post_state_hash.as_slice())
+            .expect("committed root should be listed");
+        assert_eq!(root.get_parent_hint(), parent.as_slice());
+        assert!(root.get_created_at() > 0);
+        parent = post_state_hash.clone();
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_list_pruned_roots() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    commit_counter_blocks(&mut builder);
+
+    let post_state_hashes = builder.get_post_state_hashes().to_vec();
+    let pruned = vec![post_state_hashes[1].clone(), post_state_hashes[3].clone()];
+    let pruned_roots: Vec<Blake2bHash> = pruned
+        .iter()
+        .map(|hash| Blake2bHash::try_from(hash.as_slice()).expect("should be a hash"))
+        .collect();
+    builder
+        .get_engine_state()
+        .prune_roots(CorrelationId::new(), &pruned_roots)
+        .expect("should prune roots");
+
+    let listed = listed_hashes(&builder);
+    for hash in &pruned {
+        assert!(!listed.contains(hash));
+        assert!(builder.query(Some(hash.clone()), COUNTER_KEY, &[]).is_err());
+    }
+
+    for (index, hash) in post_state_hashes.iter().enumerate() {
+        if pruned.contains(hash) {
+            continue;
+        }
+        assert!(listed.contains(hash));
+        let value = builder
+            .query(Some(hash.clone()), COUNTER_KEY, &[])
+            .expect("retained root should still be queried");
+        let expected =
+            StoredValue::CLValue(CLValue::from_t(index as i32 + 1).expect("should create value"));
+        assert_eq!(value, expected);
+    }
+}
@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use grpc::RequestOptions;
+use log::Level;
+
+use engine_core::engine_state::EngineConfig;
+use engine_grpc_server::engine_server::{
+    ipc::{ExecuteRequest, QueryRequest},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_storage::global_state::{in_memory::InMemoryGlobalState, storage_timer::StorageTimer};
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    logging, DEFAULT_ACCOUNT_ADDR,
+};
+use types::Key;
+
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const METRIC_DURATION_EXEC: &str = "exec_duration";
+const METRIC_DURATION_QUERY: &str = "query_duration";
+const METRIC_DURATION_STORAGE: &str = "storage_duration";
+const TAG_RESPONSE_EXEC: &str = "exec_response";
+const TAG_RESPONSE_QUERY: &str = "query_response";
+const SLOW_STORAGE_OPERATION_THRESHOLD: Duration = Duration::from_millis(50);
+const READ_DELAY: Duration = Duration::from_millis(100);
+
+fn execute(builder: &InMemoryWasmTestBuilder, parent_state_hash: Vec<u8>) {
+    let mut exec_request = ExecuteRequest::new();
+    exec_request.set_parent_state_hash(parent_state_hash);
+    let exec_response = builder
+        .get_engine_state()
+        .execute(RequestOptions::new(), exec_request)
+        .wait_drop_metadata()
+        .expect("should execute");
+    assert!(exec_response.has_missing_parent());
+}
+
+#[ignore]
+#[test]
+fn should_emit_exec_duration_metric_for_invalid_exec_request() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let capture = logging::capture();
+    execute(&builder, vec![1, 2, 3]);
+
+    let exec_durations = capture
+        .entries()
+        .with_metric(METRIC_DURATION_EXEC)
+        .with_tag(TAG_RESPONSE_EXEC);
+    assert_eq!(exec_durations.len(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_emit_exec_duration_metric_for_missing_parent() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let capture = logging::capture();
+    execute(&builder, vec![1; 32]);
+
+    let exec_durations = capture
+        .entries()
+        .with_metric(METRIC_DURATION_EXEC)
+        .with_tag(TAG_RESPONSE_EXEC);
+    assert_eq!(exec_durations.len(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_not_share_correlation_ids_between_execs() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let capture = logging::capture();
+    for _ in 0..2 {
+        let exec_request =
+            ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_DO_NOTHING, ()).build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let first_correlation_id = builder
+        .get_exec_correlation_id(0)
+        .expect("should have first correlation id");
+    let second_correlation_id = builder
+        .get_exec_correlation_id(1)
+        .expect("should have second correlation id");
+    assert_ne!(first_correlation_id, second_correlation_id);
+
+    // Both execs emitted entries which can be joined with their exec via its correlation id.
+    let entries = capture.entries();
+    assert!(!entries
+        .clone()
+        .with_correlation_id(first_correlation_id)
+        .is_empty());
+    assert!(!entries
+        .with_correlation_id(second_correlation_id)
+        .is_empty());
+}
+
+#[ignore]
+#[test]
+fn should_warn_about_slow_storage_read_with_correlation_id_of_query() {
+    let global_state = InMemoryGlobalState::empty()
+        .expect("should create global state")
+        .with_storage_timer(StorageTimer::new(SLOW_STORAGE_OPERATION_THRESHOLD))
+        .with_read_delay(READ_DELAY);
+    let empty_root_hash = global_state.empty_root_hash;
+    let builder =
+        InMemoryWasmTestBuilder::new(global_state, EngineConfig::new(), empty_root_hash.to_vec());
+
+    let capture = logging::capture();
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(empty_root_hash.to_vec());
+    query_request.set_base_key(Key::Hash([42; 32]).into());
+    let query_response = builder
+        .get_engine_state()
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should query");
+    assert!(query_response.has_failure());
+
+    let entries = capture.entries();
+    let query_duration = entries
+        .clone()
+        .with_metric(METRIC_DURATION_QUERY)
+        .with_tag(TAG_RESPONSE_QUERY);
+    assert_eq!(query_duration.len(), 1);
+    let correlation_id = query_duration
+        .iter()
+        .next()
+        .and_then(|entry| entry.correlation_id())
+        .expect("should have correlation id")
+        .to_string();
+
+    let warnings = entries.clone().with_level(Level::Warn);
+    let slow_reads = warnings
+        .iter()
+        .filter(|entry| entry.property("operation") == Some("read"))
+        .collect::<Vec<_>>();
+    assert!(!slow_reads.is_empty());
+    assert!(slow_reads
+        .iter()
+        .all(|slow_read| slow_read.correlation_id() == Some(correlation_id.as_str())));
+
+    let storage_duration = entries
+        .with_metric(METRIC_DURATION_STORAGE)
+        .with_tag(TAG_RESPONSE_QUERY);
+    assert_eq!(storage_duration.len(), 1);
+    assert_eq!(
+        storage_duration
+            .iter()
+            .next()
+            .and_then(|entry| entry.correlation_id()),
+        Some(correlation_id.as_str())
+    );
+}
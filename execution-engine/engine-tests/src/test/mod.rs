@@ -1,7 +1,23 @@
+mod balance;
+mod build_state;
+mod commit;
+mod commit_notifications;
+mod compare;
 mod contract_api;
+mod contract_build;
 mod deploy;
+mod determinism;
 mod examples;
 mod explorer;
+mod fixture;
+mod list_keys;
+mod list_roots;
+mod logging;
+mod multi_root_query;
+mod prune;
+mod query;
 mod regression;
+mod scenario;
 mod system_contracts;
+mod trie_nodes;
 mod upgrade;
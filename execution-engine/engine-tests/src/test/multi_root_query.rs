@@ -0,0 +1,127 @@
+use grpc::RequestOptions;
+
+use engine_grpc_server::engine_server::{
+    ipc::{MultiRootQueryRequest, MultiRootQueryResponse_Result},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform};
+use engine_test_support::internal::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use types::{bytesrepr, CLValue, Key};
+
+const COUNTER_KEY: Key = Key::Hash([44; 32]);
+const BLOCK_COUNT: i32 = 5;
+
+/// Commits `BLOCK_COUNT` blocks after genesis, the first of which writes the counter as 1 and the
+/// others increment it.
+fn commit_counter_blocks(builder: &mut InMemoryWasmTestBuilder) {
+    for block in 0..BLOCK_COUNT {
+        let transform = if block == 0 {
+            Transform::Write(StoredValue::CLValue(
+                CLValue::from_t(1_i32).expect("should create value"),
+            ))
+        } else {
+            Transform::AddInt32(1)
+        };
+        let mut effects = AdditiveMap::new();
+        effects.insert(COUNTER_KEY, transform);
+        let post_state_hash = builder.get_post_state_hash();
+        builder.commit_effects(post_state_hash, effects);
+    }
+}
+
+fn counter_value(result: Result<StoredValue, String>) -> i32 {
+    match result {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be i32"),
+        other => panic!("expected counter value, got {:?}", other),
+    }
+}
+
+fn multi_root_query(
+    builder: &InMemoryWasmTestBuilder,
+    state_hashes: Vec<Vec<u8>>,
+) -> Vec<MultiRootQueryResponse_Result> {
+    let mut multi_root_query_request = MultiRootQueryRequest::new();
+    multi_root_query_request.set_base_key(COUNTER_KEY.into());
+    multi_root_query_request.set_state_hashes(state_hashes.into());
+
+    let mut multi_root_query_response = builder
+        .get_engine_state()
+        .multi_root_query(RequestOptions::new(), multi_root_query_request)
+        .wait_drop_metadata()
+        .expect("should get multi-root query response");
+    assert!(multi_root_query_response.has_success());
+    multi_root_query_response
+        .take_success()
+        .take_results()
+        .into_vec()
+}
+
+#[ignore]
+#[test]
+fn should_query_counter_at_all_post_states() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    commit_counter_blocks(&mut builder);
+
+    let values: Vec<i32> = builder
+        .query_at_all_post_states(COUNTER_KEY, &[])
+        .into_iter()
+        .map(counter_value)
+        .collect();
+
+    assert_eq!(values, vec![1, 2, 3, 4, 5]);
+}
+
+#[ignore]
+#[test]
+fn should_return_same_result_for_repeated_roots() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    commit_counter_blocks(&mut builder);
+
+    let post_state_hash = builder.get_post_state_hash();
+    let results = multi_root_query(
+        &builder,
+        vec![
+            post_state_hash.clone(),
+            post_state_hash.clone(),
+            post_state_hash,
+        ],
+    );
+
+    assert_eq!(results.len(), 3);
+    for mut result in results {
+        let value: StoredValue =
+            bytesrepr::deserialize(result.take_query_response().take_success())
+                .expect("should deserialize value");
+        assert_eq!(counter_value(Ok(value)), BLOCK_COUNT);
+    }
+}
+
+#[ignore]
+#[test]
+fn should_return_root_not_found_per_missing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    commit_counter_blocks(&mut builder);
+
+    let missing_root = vec![1u8; 32];
+    let genesis_hash = builder.get_genesis_hash();
+    let post_state_hash = builder.get_post_state_hash();
+    let results = multi_root_query(
+        &builder,
+        vec![genesis_hash, missing_root.clone(), post_state_hash],
+    );
+
+    assert_eq!(results.len(), 3);
+    // the counter doesn't exist at genesis
+    assert!(results[0].get_query_response().has_failure());
+    assert_eq!(
+        results[1].get_missing_root().get_hash(),
+        missing_root.as_slice()
+    );
+    let value: StoredValue =
+        bytesrepr::deserialize(results[2].get_query_response().get_success().to_vec())
+            .expect("should deserialize value");
+    assert_eq!(counter_value(Ok(value)), BLOCK_COUNT);
+}
@@ -0,0 +1,124 @@
+use engine_shared::{additive_map::AdditiveMap, stored_value::StoredValue, transform::Transform};
+use engine_test_support::internal::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use types::{CLValue, Key};
+
+const KEY: Key = Key::Hash([42; 32]);
+const OTHER_KEY: Key = Key::Hash([43; 32]);
+
+fn write_transform(value: i32) -> Transform {
+    Transform::Write(StoredValue::CLValue(
+        CLValue::from_t(value).expect("should create value"),
+    ))
+}
+
+fn commit(builder: &mut InMemoryWasmTestBuilder, transforms: Vec<(Key, Transform)>) -> Vec<u8> {
+    let mut effects = AdditiveMap::new();
+    for (key, transform) in transforms {
+        effects.insert_add(key, transform);
+    }
+    let prestate_hash = builder.get_post_state_hash();
+    builder.commit_effects(prestate_hash, effects);
+    builder.get_post_state_hash()
+}
+
+fn query_value(builder: &InMemoryWasmTestBuilder, post_state: Vec<u8>, key: Key) -> Option<i32> {
+    match builder.query(Some(post_state), key, &[]) {
+        Ok(StoredValue::CLValue(cl_value)) => Some(cl_value.into_t().expect("should be i32")),
+        Ok(other) => panic!("unexpected stored value {:?}", other),
+        Err(_) => None,
+    }
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+#[ignore]
+#[test]
+fn should_not_find_pruned_key_at_new_root_only() {
+    let mut builder = setup();
+    let genesis_hash = builder.get_genesis_hash();
+    let written_hash = commit(
+        &mut builder,
+        vec![(KEY, write_transform(1)), (OTHER_KEY, write_transform(2))],
+    );
+
+    let pruned_hash = commit(&mut builder, vec![(KEY, Transform::Prune)]);
+
+    assert_ne!(pruned_hash, written_hash);
+    assert_eq!(query_value(&builder, pruned_hash.clone(), KEY), None);
+    assert_eq!(
+        query_value(&builder, pruned_hash.clone(), OTHER_KEY),
+        Some(2)
+    );
+    // the roots committed before the prune still serve the value
+    assert_eq!(query_value(&builder, written_hash, KEY), Some(1));
+    assert_eq!(query_value(&builder, genesis_hash.clone(), KEY), None);
+
+    // pruning every key written since genesis leads back to the genesis root
+    let genesis_again_hash = commit(&mut builder, vec![(OTHER_KEY, Transform::Prune)]);
+    assert_eq!(genesis_again_hash, genesis_hash);
+    assert_eq!(query_value(&builder, pruned_hash, OTHER_KEY), Some(2));
+}
+
+#[ignore]
+#[test]
+fn should_leave_root_unchanged_when_pruning_missing_key() {
+    let mut builder = setup();
+    let written_hash = commit(&mut builder, vec![(KEY, write_transform(1))]);
+
+    let pruned_hash = commit(&mut builder, vec![(OTHER_KEY, Transform::Prune)]);
+
+    assert_eq!(pruned_hash, written_hash);
+}
+
+#[ignore]
+#[test]
+fn should_write_after_prune_in_same_commit() {
+    let mut builder = setup();
+    commit(&mut builder, vec![(KEY, write_transform(1))]);
+
+    let post_state_hash = commit(
+        &mut builder,
+        vec![(KEY, Transform::Prune), (KEY, write_transform(2))],
+    );
+
+    assert_eq!(query_value(&builder, post_state_hash, KEY), Some(2));
+}
+
+#[ignore]
+#[test]
+fn should_prune_after_write_in_same_commit() {
+    let mut builder = setup();
+    let written_hash = commit(&mut builder, vec![(KEY, write_transform(1))]);
+
+    let post_state_hash = commit(
+        &mut builder,
+        vec![(KEY, write_transform(2)), (KEY, Transform::Prune)],
+    );
+
+    assert_eq!(query_value(&builder, post_state_hash, KEY), None);
+    assert_eq!(query_value(&builder, written_hash, KEY), Some(1));
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_commit_add_after_prune() {
+    let mut builder = setup();
+    let written_hash = commit(&mut builder, vec![(KEY, write_transform(1))]);
+    let mut effects = AdditiveMap::new();
+    effects.insert_add(KEY, Transform::Prune);
+    effects.insert_add(KEY, Transform::AddInt32(1));
+    match effects.get(&KEY) {
+        Some(Transform::Failure(_)) => {}
+        other => panic!("expected a failure, got {:?}", other),
+    }
+
+    let response = builder.commit_transforms(written_hash.clone(), effects);
+
+    assert!(!response.has_success(), "{:?}", response);
+    assert_eq!(builder.get_post_state_hash(), written_hash);
+    assert_eq!(query_value(&builder, written_hash, KEY), Some(1));
+}
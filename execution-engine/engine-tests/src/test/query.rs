@@ -0,0 +1,384 @@
+use std::collections::BTreeMap;
+
+use grpc::RequestOptions;
+use protobuf::Message;
+
+use engine_core::engine_state::{query_cache::DEFAULT_QUERY_CACHE_CAPACITY, EngineConfig};
+use engine_grpc_server::engine_server::{
+    ipc::{QueryRequest, QueryRequest_BytesRange, QueryResponse},
+    ipc_grpc::ExecutionEngineService,
+    state,
+};
+use engine_shared::{
+    additive_map::AdditiveMap, contract::Contract, newtypes::Blake2bHash,
+    stored_value::StoredValue, transform::Transform,
+};
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::{
+    internal::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    bytesrepr::{self, ToBytes},
+    CLType, CLValue, Key, ProtocolVersion, U512,
+};
+
+const LARGE_VALUE_KEY: Key = Key::Hash([42; 32]);
+const U512_VALUE_KEY: Key = Key::Hash([43; 32]);
+const LARGE_CONTRACT_KEY: Key = Key::Hash([44; 32]);
+const LARGE_VALUE_SIZE: usize = 2 * 1024 * 1024;
+const MAX_VALUE_SIZE: u64 = 1024;
+const MAX_CONTRACT_SUMMARY_RESPONSE_SIZE: u32 = 4 * 1024;
+
+fn store_large_value(builder: &mut InMemoryWasmTestBuilder) -> StoredValue {
+    let value = vec![255u8; LARGE_VALUE_SIZE];
+    let stored_value = StoredValue::CLValue(CLValue::from_t(value).expect("should create value"));
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(LARGE_VALUE_KEY, Transform::Write(stored_value.clone()));
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, effects);
+
+    stored_value
+}
+
+fn query(
+    builder: &InMemoryWasmTestBuilder,
+    max_value_size: u64,
+    range: Option<(u64, u64)>,
+) -> QueryResponse {
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(builder.get_post_state_hash());
+    query_request.set_base_key(LARGE_VALUE_KEY.into());
+    query_request.set_max_value_size_bytes(max_value_size);
+    if let Some((offset, length)) = range {
+        let mut bytes_range = QueryRequest_BytesRange::new();
+        bytes_range.set_offset(offset);
+        bytes_range.set_length(length);
+        query_request.set_range(bytes_range);
+    }
+
+    builder
+        .get_engine_state()
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should query")
+}
+
+fn store_large_contract(builder: &mut InMemoryWasmTestBuilder) -> Contract {
+    let mut named_keys = BTreeMap::new();
+    named_keys.insert("large_value".to_string(), LARGE_VALUE_KEY);
+    let contract = Contract::new(
+        vec![255u8; LARGE_VALUE_SIZE],
+        named_keys,
+        ProtocolVersion::V1_0_0,
+    );
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        LARGE_CONTRACT_KEY,
+        Transform::Write(StoredValue::Contract(contract.clone())),
+    );
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, effects);
+
+    contract
+}
+
+fn query_contract(
+    builder: &InMemoryWasmTestBuilder,
+    include_contract_bytes: bool,
+) -> QueryResponse {
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(builder.get_post_state_hash());
+    query_request.set_base_key(LARGE_CONTRACT_KEY.into());
+    query_request.set_include_contract_bytes(include_contract_bytes);
+
+    builder
+        .get_engine_state()
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should query")
+}
+
+fn query_type_only(builder: &InMemoryWasmTestBuilder, base_key: Key) -> QueryResponse {
+    let mut query_request = QueryRequest::new();
+    query_request.set_state_hash(builder.get_post_state_hash());
+    query_request.set_base_key(base_key.into());
+    query_request.set_type_only(true);
+
+    builder
+        .get_engine_state()
+        .query(RequestOptions::new(), query_request)
+        .wait_drop_metadata()
+        .expect("should query")
+}
+
+fn serialized_size(builder: &InMemoryWasmTestBuilder, base_key: Key) -> u64 {
+    let stored_value = builder
+        .query(None, base_key, &[])
+        .expect("should have value");
+    stored_value.serialized_length() as u64
+}
+
+#[ignore]
+#[test]
+fn should_return_value_too_large_instead_of_value() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let stored_value = store_large_value(&mut builder);
+
+    let response = query(&builder, MAX_VALUE_SIZE, None);
+
+    assert!(
+        response.has_value_too_large(),
+        "{:?}",
+        response.get_failure()
+    );
+    let value_too_large = response.get_value_too_large();
+    assert_eq!(
+        value_too_large.get_actual_size(),
+        stored_value.serialized_length() as u64
+    );
+    assert_eq!(value_too_large.get_type_tag(), stored_value.type_name());
+}
+
+#[ignore]
+#[test]
+fn should_query_large_value_in_ranged_chunks() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let stored_value = store_large_value(&mut builder);
+
+    let actual_size = query(&builder, MAX_VALUE_SIZE, None)
+        .get_value_too_large()
+        .get_actual_size();
+    let chunk_size = (actual_size + 1) / 2;
+
+    let mut serialized_value = Vec::new();
+    for offset in &[0, chunk_size] {
+        let mut response = query(&builder, chunk_size, Some((*offset, chunk_size)));
+        assert!(response.has_success(), "{:?}", response.get_failure());
+        serialized_value.extend(response.take_success());
+    }
+
+    assert_eq!(serialized_value.len() as u64, actual_size);
+    let reassembled_value: StoredValue =
+        bytesrepr::deserialize(serialized_value).expect("should deserialize");
+    assert_eq!(reassembled_value, stored_value);
+}
+
+#[ignore]
+#[test]
+fn should_return_only_type_of_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+
+    let response = query_type_only(&builder, account_key);
+
+    assert!(response.has_value_type(), "{:?}", response.get_failure());
+    let value_type = response.get_value_type();
+    assert!(value_type.has_account());
+    assert_eq!(
+        value_type.get_serialized_size(),
+        serialized_size(&builder, account_key)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_only_type_of_contract() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let contract_key = Key::URef(builder.get_pos_contract_uref());
+
+    let response = query_type_only(&builder, contract_key);
+
+    assert!(response.has_value_type(), "{:?}", response.get_failure());
+    let value_type = response.get_value_type();
+    assert!(value_type.has_contract());
+    assert_eq!(
+        value_type.get_serialized_size(),
+        serialized_size(&builder, contract_key)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_only_type_of_cl_value() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let stored_value =
+        StoredValue::CLValue(CLValue::from_t(U512::from(42)).expect("should create value"));
+    let mut effects = AdditiveMap::new();
+    effects.insert(U512_VALUE_KEY, Transform::Write(stored_value.clone()));
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, effects);
+
+    let response = query_type_only(&builder, U512_VALUE_KEY);
+
+    assert!(response.has_value_type(), "{:?}", response.get_failure());
+    let value_type = response.get_value_type();
+    assert_eq!(*value_type.get_cl_type(), state::CLType::from(CLType::U512));
+    assert_eq!(
+        value_type.get_serialized_size(),
+        stored_value.serialized_length() as u64
+    );
+}
+
+#[ignore]
+#[test]
+fn should_return_type_alongside_value() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let stored_value = store_large_value(&mut builder);
+
+    let response = query(&builder, 0, None);
+
+    assert!(response.has_success(), "{:?}", response.get_failure());
+    let value_type = response.get_success_value_type();
+    assert_eq!(
+        *value_type.get_cl_type(),
+        state::CLType::from(CLType::List(Box::new(CLType::U8)))
+    );
+    assert_eq!(
+        value_type.get_serialized_size(),
+        stored_value.serialized_length() as u64
+    );
+}
+
+fn builder_with_query_cache(capacity: usize) -> InMemoryWasmTestBuilder {
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let empty_root_hash = global_state.empty_root_hash.to_vec();
+    let engine_config = EngineConfig::new()
+        .with_use_system_contracts(cfg!(feature = "use-system-contracts"))
+        .with_enable_bonding(cfg!(feature = "enable-bonding"))
+        .with_query_cache_capacity(capacity);
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, empty_root_hash);
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    builder
+}
+
+fn query_cache_counts(builder: &InMemoryWasmTestBuilder) -> (usize, usize) {
+    let query_cache = builder
+        .get_engine_state()
+        .query_cache()
+        .expect("should have query cache");
+    (query_cache.hits(), query_cache.misses())
+}
+
+#[ignore]
+#[test]
+fn should_return_identical_results_cached_and_uncached() {
+    let mut uncached_builder = InMemoryWasmTestBuilder::default();
+    uncached_builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    assert!(uncached_builder.get_engine_state().query_cache().is_none());
+    let cached_builder = builder_with_query_cache(DEFAULT_QUERY_CACHE_CAPACITY);
+    assert_eq!(
+        cached_builder.get_post_state_hash(),
+        uncached_builder.get_post_state_hash()
+    );
+
+    let pos_contract_key = Key::URef(uncached_builder.get_pos_contract_uref());
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let queries: &[(Key, &[&str])] = &[
+        (pos_contract_key, &[]),
+        (account_key, &[]),
+        (account_key, &["pos"]),
+        (account_key, &["missing"]),
+    ];
+
+    for _ in 0..2 {
+        for (base_key, path) in queries {
+            assert_eq!(
+                cached_builder.query(None, *base_key, path),
+                uncached_builder.query(None, *base_key, path)
+            );
+        }
+    }
+
+    // only the three values found are cached, so the missing one is looked up both times
+    assert_eq!(query_cache_counts(&cached_builder), (3, 5));
+}
+
+#[ignore]
+#[test]
+fn should_evict_least_recently_used_query_result_at_capacity() {
+    let uncached_builder = {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+        builder
+    };
+    let account_key = Key::Account(DEFAULT_ACCOUNT_ADDR);
+    let pos_contract_key = Key::URef(uncached_builder.get_pos_contract_uref());
+    let capacity = serialized_size(&uncached_builder, account_key)
+        .max(serialized_size(&uncached_builder, pos_contract_key)) as usize;
+
+    // the cache only has room for one of the values
+    let builder = builder_with_query_cache(capacity);
+    let account = builder.query(None, account_key, &[]);
+    assert_eq!(builder.query(None, account_key, &[]), account);
+    assert_eq!(query_cache_counts(&builder), (1, 1));
+
+    let pos_contract = builder.query(None, pos_contract_key, &[]);
+    assert_eq!(query_cache_counts(&builder), (1, 2));
+
+    assert_eq!(builder.query(None, account_key, &[]), account);
+    assert_eq!(query_cache_counts(&builder), (1, 3));
+    assert_eq!(builder.query(None, pos_contract_key, &[]), pos_contract);
+    assert_eq!(query_cache_counts(&builder), (1, 4));
+
+    let query_cache = builder
+        .get_engine_state()
+        .query_cache()
+        .expect("should have query cache");
+    assert_eq!(query_cache.len(), 1);
+    assert!(query_cache.weight() <= capacity);
+}
+
+#[ignore]
+#[test]
+fn should_summarize_contract_without_its_bytes_by_default() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let contract = store_large_contract(&mut builder);
+
+    let response = query_contract(&builder, false);
+
+    assert!(
+        response.has_contract_summary(),
+        "{:?}",
+        response.get_failure()
+    );
+    assert!(
+        response.compute_size() < MAX_CONTRACT_SUMMARY_RESPONSE_SIZE,
+        "response of {} bytes",
+        response.compute_size()
+    );
+    let contract_summary = response.get_contract_summary();
+    assert_eq!(
+        contract_summary.get_body_size(),
+        contract.bytes().len() as u64
+    );
+    assert_eq!(
+        contract_summary.get_body_hash(),
+        Blake2bHash::new(contract.bytes()).to_vec().as_slice()
+    );
+    assert_eq!(contract_summary.get_named_keys().len(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_return_whole_contract_when_asked_for_its_bytes() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let contract = store_large_contract(&mut builder);
+
+    let mut response = query_contract(&builder, true);
+
+    assert!(response.has_success(), "{:?}", response.get_failure());
+    let stored_value: StoredValue =
+        bytesrepr::deserialize(response.take_success()).expect("should deserialize");
+    assert_eq!(stored_value, StoredValue::Contract(contract));
+}
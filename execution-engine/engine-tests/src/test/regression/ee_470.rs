@@ -1,7 +1,7 @@
 use engine_storage::global_state::in_memory::InMemoryGlobalState;
 use engine_test_support::{
-    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
-    DEFAULT_ACCOUNT_ADDR,
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_RUN_GENESIS_REQUEST,
 };
 
 const CONTRACT_LOCAL_STATE: &str = "local_state.wasm";
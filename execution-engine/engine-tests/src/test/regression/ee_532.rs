@@ -1,5 +1,5 @@
 use engine_core::engine_state::Error;
-use engine_test_support::internal::{
+use engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
 };
 use types::account::PublicKey;
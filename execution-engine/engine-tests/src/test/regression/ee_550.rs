@@ -1,11 +1,11 @@
 use engine_test_support::{
-    internal::{
-        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
-        DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST, STANDARD_PAYMENT_CONTRACT,
+};
+use types::{
+    account::{PublicKey, Weight, MAX_ASSOCIATED_KEYS},
+    DeployHash,
 };
-use types::account::PublicKey;
 
 const PASS_INIT_REMOVE: &str = "init_remove";
 const PASS_TEST_REMOVE: &str = "test_remove";
@@ -13,8 +13,10 @@ const PASS_INIT_UPDATE: &str = "init_update";
 const PASS_TEST_UPDATE: &str = "test_update";
 
 const CONTRACT_EE_550_REGRESSION: &str = "ee_550_regression.wasm";
-const KEY_2_ADDR: [u8; 32] = [101; 32];
-const DEPLOY_HASH: [u8; 32] = [42; 32];
+const CONTRACT_ADD_UPDATE_ASSOCIATED_KEY: &str = "add_update_associated_key.wasm";
+const KEY_1_ADDR: PublicKey = PublicKey::ed25519_from([100; 32]);
+const KEY_2_ADDR: PublicKey = PublicKey::ed25519_from([101; 32]);
+const DEPLOY_HASH: DeployHash = DeployHash::new([42; 32]);
 
 #[ignore]
 #[test]
@@ -34,7 +36,7 @@ fn should_run_ee_550_remove_with_saturated_threshold_regression() {
                 (String::from(PASS_TEST_REMOVE),),
             )
             .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR, PublicKey::ed25519_from(KEY_2_ADDR)])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR, KEY_2_ADDR])
             .with_deploy_hash(DEPLOY_HASH)
             .build();
 
@@ -47,10 +49,35 @@ fn should_run_ee_550_remove_with_saturated_threshold_regression() {
         .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
         .exec(exec_request_1)
         .expect_success()
-        .commit()
-        .exec(exec_request_2)
-        .expect_success()
         .commit();
+
+    let auth_info = builder
+        .get_account_auth(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(
+        auth_info.associated_keys,
+        vec![
+            (DEFAULT_ACCOUNT_ADDR, Weight::new(1)),
+            (KEY_1_ADDR, Weight::new(2)),
+            (KEY_2_ADDR, Weight::new(255)),
+        ]
+    );
+    assert_eq!(auth_info.key_management_threshold, Weight::new(254));
+
+    builder.exec(exec_request_2).expect_success().commit();
+
+    let auth_info = builder
+        .get_account_auth(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(
+        auth_info.associated_keys,
+        vec![
+            (DEFAULT_ACCOUNT_ADDR, Weight::new(1)),
+            (KEY_2_ADDR, Weight::new(255)),
+        ]
+    );
+    assert_eq!(auth_info.deployment_threshold, Weight::new(1));
+    assert_eq!(auth_info.key_management_threshold, Weight::new(254));
 }
 
 #[ignore]
@@ -71,7 +98,7 @@ fn should_run_ee_550_update_with_saturated_threshold_regression() {
                 (String::from(PASS_TEST_UPDATE),),
             )
             .with_payment_code(STANDARD_PAYMENT_CONTRACT, (*DEFAULT_PAYMENT,))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR, PublicKey::ed25519_from(KEY_2_ADDR)])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR, KEY_2_ADDR])
             .with_deploy_hash(DEPLOY_HASH)
             .build();
 
@@ -88,4 +115,62 @@ fn should_run_ee_550_update_with_saturated_threshold_regression() {
         .exec(exec_request_2)
         .expect_success()
         .commit();
+
+    let auth_info = builder
+        .get_account_auth(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(
+        auth_info.associated_keys,
+        vec![
+            (DEFAULT_ACCOUNT_ADDR, Weight::new(1)),
+            (KEY_1_ADDR, Weight::new(1)),
+            (KEY_2_ADDR, Weight::new(255)),
+        ]
+    );
+    assert_eq!(auth_info.deployment_threshold, Weight::new(1));
+    assert_eq!(auth_info.key_management_threshold, Weight::new(254));
+}
+
+#[ignore]
+#[test]
+fn should_report_max_associated_keys_in_account_auth() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // The default account is itself an associated key, so adding one fewer key than the maximum
+    // saturates it.
+    let added_keys: Vec<PublicKey> = (1..MAX_ASSOCIATED_KEYS as u8)
+        .map(|i| PublicKey::ed25519_from([200 + i; 32]))
+        .collect();
+    for key in &added_keys {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
+            (*key,),
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let auth_info = builder
+        .get_account_auth(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(auth_info.associated_keys.len(), MAX_ASSOCIATED_KEYS);
+    for key in &added_keys {
+        assert!(auth_info.associated_keys.contains(&(*key, Weight::new(2))));
+    }
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
+        (KEY_1_ADDR,),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+    assert!(builder.is_error());
+
+    let auth_info_after = builder
+        .get_account_auth(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(auth_info_after, auth_info);
 }
@@ -1,10 +1,7 @@
 use engine_shared::stored_value::StoredValue;
 use engine_test_support::{
-    internal::{
-        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
-        DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    get_error_message, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
 };
 use types::{account::PublicKey, Key, U512};
 
@@ -78,7 +75,7 @@ fn should_run_ee_572_regression() {
         .expect("should have a response")
         .to_owned();
 
-    let error_message = utils::get_error_message(response);
+    let error_message = get_error_message(response);
 
     assert!(error_message.contains("ForgedReference"));
 }
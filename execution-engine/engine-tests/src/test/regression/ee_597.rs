@@ -1,8 +1,6 @@
 use engine_test_support::{
-    internal::{
-        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    get_error_message, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_RUN_GENESIS_REQUEST,
 };
 use types::ApiError;
 
@@ -27,7 +25,7 @@ fn should_fail_when_bonding_amount_is_zero_ee_597_regression() {
         .expect("should have a response")
         .to_owned();
 
-    let error_message = utils::get_error_message(response);
+    let error_message = get_error_message(response);
 
     if !cfg!(feature = "enable-bonding") {
         assert!(error_message.contains(&format!("{:?}", ApiError::Unhandled)));
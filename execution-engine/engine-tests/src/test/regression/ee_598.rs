@@ -3,11 +3,9 @@ use lazy_static::lazy_static;
 use engine_core::engine_state::genesis::GenesisAccount;
 use engine_shared::motes::Motes;
 use engine_test_support::{
-    internal::{
-        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
-        DEFAULT_PAYMENT, STANDARD_PAYMENT_CONTRACT,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    create_run_genesis_request, get_error_message, DeployItemBuilder, ExecuteRequestBuilder,
+    InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR, DEFAULT_PAYMENT,
+    STANDARD_PAYMENT_CONTRACT,
 };
 use types::{account::PublicKey, ApiError, U512};
 
@@ -35,7 +33,7 @@ fn should_fail_unboding_more_than_it_was_staked_ee_598_regression() {
         tmp
     };
 
-    let run_genesis_request = utils::create_run_genesis_request(accounts);
+    let run_genesis_request = create_run_genesis_request(accounts);
 
     let exec_request_1 = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
@@ -72,7 +70,7 @@ fn should_fail_unboding_more_than_it_was_staked_ee_598_regression() {
         .get_exec_response(1)
         .expect("should have a response")
         .to_owned();
-    let error_message = utils::get_error_message(response);
+    let error_message = get_error_message(response);
 
     if !cfg!(feature = "enable-bonding") {
         assert!(error_message.contains(&format!("{:?}", ApiError::Unhandled)));
@@ -3,11 +3,8 @@ use lazy_static::lazy_static;
 use engine_core::engine_state::CONV_RATE;
 use engine_shared::motes::Motes;
 use engine_test_support::{
-    internal::{
-        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
-        DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR,
+    get_exec_costs, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
 };
 use types::{account::PublicKey, U512};
 
@@ -90,8 +87,8 @@ fn should_not_be_able_to_transfer_funds_with_transfer_purse_to_purse() {
         .builder()
         .get_exec_response(0)
         .expect("should have response");
-    let gas_cost = Motes::from_gas(utils::get_exec_costs(exec_3_response)[0], CONV_RATE)
-        .expect("should convert");
+    let gas_cost =
+        Motes::from_gas(get_exec_costs(exec_3_response)[0], CONV_RATE).expect("should convert");
 
     let error_msg = result_2
         .builder()
@@ -162,8 +159,8 @@ fn should_not_be_able_to_transfer_funds_with_transfer_from_purse_to_account() {
         .get_exec_response(0)
         .expect("should have response");
 
-    let gas_cost = Motes::from_gas(utils::get_exec_costs(exec_3_response)[0], CONV_RATE)
-        .expect("should convert");
+    let gas_cost =
+        Motes::from_gas(get_exec_costs(exec_3_response)[0], CONV_RATE).expect("should convert");
 
     let error_msg = result_2
         .builder()
@@ -244,8 +241,8 @@ fn should_not_be_able_to_transfer_funds_with_transfer_to_account() {
         .get_exec_response(0)
         .expect("should have response");
 
-    let gas_cost = Motes::from_gas(utils::get_exec_costs(exec_3_response)[0], CONV_RATE)
-        .expect("should convert");
+    let gas_cost =
+        Motes::from_gas(get_exec_costs(exec_3_response)[0], CONV_RATE).expect("should convert");
 
     let error_msg = result_2
         .builder()
@@ -320,8 +317,8 @@ fn should_not_be_able_to_get_main_purse_in_invalid_context() {
         .get_exec_response(0)
         .expect("should have response");
 
-    let gas_cost = Motes::from_gas(utils::get_exec_costs(exec_3_response)[0], CONV_RATE)
-        .expect("should convert");
+    let gas_cost =
+        Motes::from_gas(get_exec_costs(exec_3_response)[0], CONV_RATE).expect("should convert");
 
     let error_msg = result_2
         .builder()
@@ -7,8 +7,8 @@ use engine_core::engine_state::{
 };
 use engine_shared::motes::Motes;
 use engine_test_support::{
-    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
-    DEFAULT_ACCOUNT_ADDR,
+    create_run_genesis_request, get_exec_costs, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+    DEFAULT_ACCOUNTS, DEFAULT_ACCOUNT_ADDR,
 };
 use types::{account::PublicKey, Key, URef, U512};
 
@@ -32,7 +32,7 @@ fn get_pos_purse_by_name(builder: &InMemoryWasmTestBuilder, purse_name: &str) ->
 
 fn get_cost(response: &[Rc<ExecutionResult>]) -> U512 {
     let motes = Motes::from_gas(
-        utils::get_exec_costs(response)
+        get_exec_costs(response)
             .into_iter()
             .fold(Default::default(), |i, acc| i + acc),
         CONV_RATE,
@@ -61,7 +61,7 @@ fn should_not_be_able_to_unbond_reward() {
         tmp
     };
 
-    let run_genesis_request = utils::create_run_genesis_request(accounts);
+    let run_genesis_request = create_run_genesis_request(accounts);
     builder.run_genesis(&run_genesis_request);
 
     // First request to put some funds in the reward purse
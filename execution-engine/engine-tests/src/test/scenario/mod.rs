@@ -0,0 +1,191 @@
+//! Runs random sequences of deploys — transfers, account creation, bonding, unbonding, storing and
+//! calling contracts and key management — and checks global state against a model after each one.
+//!
+//! Operations are generated without reference to any state and resolved against the model while
+//! the scenario runs, so proptest can shrink a failing sequence to a minimal one. A failure reports
+//! the minimal sequence as builder code which reproduces it in a regular test.
+//!
+//! The default run is kept short. Set `SCENARIO_SOAK_CASES` to the number of cases for a soak run
+//! with longer sequences.
+
+mod model;
+mod operation;
+mod step;
+
+use std::{
+    collections::BTreeMap,
+    env,
+    panic::{self, AssertUnwindSafe},
+};
+
+use proptest::test_runner::{Config, TestCaseError, TestRunner};
+
+use engine_core::engine_state::{genesis::GenesisAccount, CONV_RATE};
+use engine_shared::motes::Motes;
+use engine_storage::global_state::in_memory::InMemoryGlobalState;
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, WasmTestResult, DEFAULT_ACCOUNTS,
+};
+use types::account::PublicKey;
+
+use self::{
+    model::{Model, ModelAccount},
+    operation::Operation,
+    step::{PublicKeyCode, Step},
+};
+
+const VALIDATOR_1_ADDR: PublicKey = PublicKey::ed25519_from([200; 32]);
+const VALIDATOR_2_ADDR: PublicKey = PublicKey::ed25519_from([201; 32]);
+const VALIDATOR_BALANCE: u64 = 100_000_000_000;
+const VALIDATOR_1_STAKE: u64 = 50_000_000_000;
+const VALIDATOR_2_STAKE: u64 = 20_000_000_000;
+
+const CASES: u32 = 16;
+const MAX_LENGTH: usize = 6;
+const SOAK_CASES_VAR: &str = "SCENARIO_SOAK_CASES";
+/// Stays below 255, so that each step of a sequence has a distinct deploy hash.
+const SOAK_MAX_LENGTH: usize = 100;
+
+fn genesis_accounts() -> Vec<GenesisAccount> {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    for (public_key, stake) in &[
+        (VALIDATOR_1_ADDR, VALIDATOR_1_STAKE),
+        (VALIDATOR_2_ADDR, VALIDATOR_2_STAKE),
+    ] {
+        accounts.push(GenesisAccount::new(
+            *public_key,
+            Motes::new(VALIDATOR_BALANCE.into()),
+            Motes::new((*stake).into()),
+        ));
+    }
+    accounts
+}
+
+/// Returns an error describing the first account or stake which differs from the model.
+fn check_state(builder: &InMemoryWasmTestBuilder, model: &Model) -> Result<(), String> {
+    for expected in &model.accounts {
+        let actual = ModelAccount::from_builder(builder, expected.public_key)
+            .ok_or_else(|| format!("missing account {}", PublicKeyCode(&expected.public_key)))?;
+        if actual != *expected {
+            return Err(format!(
+                "account differs from model:\nexpected: {:?}\nactual:   {:?}",
+                expected, actual
+            ));
+        }
+    }
+
+    let expected_stakes: BTreeMap<PublicKey, _> = model.stakes.0.clone();
+    let actual_stakes: BTreeMap<PublicKey, _> = builder
+        .get_bonded_validators()
+        .last()
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, stake)| !stake.is_zero())
+        .collect();
+    if actual_stakes != expected_stakes {
+        return Err(format!(
+            "stakes differ from model:\nexpected: {:?}\nactual:   {:?}",
+            expected_stakes, actual_stakes
+        ));
+    }
+    Ok(())
+}
+
+/// Executes `step` and updates `model` with its effects.
+fn run_step(
+    builder: &mut InMemoryWasmTestBuilder,
+    model: &mut Model,
+    step: &Step,
+) -> Result<(), String> {
+    builder.exec(step.exec_request()).commit();
+    let exec_index = builder.get_exec_responses_count() - 1;
+    if builder.is_error() == step.expect_success {
+        return Err(format!(
+            "expected {}, got {:?}",
+            if step.expect_success {
+                "success"
+            } else {
+                "failure"
+            },
+            builder.exec_error_message(exec_index)
+        ));
+    }
+
+    let cost = Motes::from_gas(builder.exec_costs(exec_index)[0], CONV_RATE)
+        .expect("should convert gas to motes");
+    model.apply(step, cost.value());
+    check_state(builder, model)
+}
+
+/// Runs `operations` from the state of `genesis`, returning a reproduction of the steps run so far
+/// if the state diverges from the model.
+fn run_scenario(
+    genesis: &WasmTestResult<InMemoryGlobalState>,
+    operations: &[Operation],
+) -> Result<(), String> {
+    let mut builder = InMemoryWasmTestBuilder::from_result(genesis.clone());
+    let genesis_accounts = genesis_accounts();
+    let mut model = Model::new(&builder, &genesis_accounts);
+    let mut steps = Vec::new();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        for operation in operations {
+            if let Some(step) = model.plan(operation, steps.len()) {
+                steps.push(step.clone());
+                run_step(&mut builder, &mut model, &step)?;
+            }
+        }
+        Ok(())
+    }))
+    .unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_default();
+        Err(format!("panicked: {}", message))
+    });
+
+    result.map_err(|message| {
+        let mut reproduction = format!(
+            "{}\n\nreproduce with the genesis accounts {:?} and:\n\n",
+            message, genesis_accounts
+        );
+        for step in &steps {
+            reproduction.push_str(&step.to_string());
+        }
+        reproduction
+    })
+}
+
+#[ignore]
+#[test]
+fn should_match_model_for_random_operation_sequences() {
+    let (cases, max_length) = match env::var(SOAK_CASES_VAR) {
+        Ok(cases) => (
+            cases.parse().expect("should parse soak case count"),
+            SOAK_MAX_LENGTH,
+        ),
+        Err(_) => (CASES, MAX_LENGTH),
+    };
+
+    let genesis = {
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder
+            .with_mint_invariants()
+            .run_genesis(&utils::create_run_genesis_request(genesis_accounts()));
+        builder.finish()
+    };
+
+    let mut runner = TestRunner::new(Config {
+        cases,
+        failure_persistence: None,
+        ..Config::default()
+    });
+    if let Err(error) = runner.run(&operation::operations(max_length), |operations| {
+        run_scenario(&genesis, &operations).map_err(TestCaseError::fail)
+    }) {
+        panic!("{}", error);
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use engine_core::engine_state::genesis::GenesisAccount;
+use engine_test_support::internal::{InMemoryWasmTestBuilder, DEFAULT_PAYMENT};
+use proof_of_stake::Stakes;
+use types::{
+    account::{PublicKey, Weight, MAX_ASSOCIATED_KEYS},
+    system_contract_names::{MINT_NAME, POS_NAME},
+    U512,
+};
+
+use super::{
+    operation::{Operation, ADDED_KEY_COUNT},
+    step::{Session, Step, STORED_CONTRACT_NAME},
+};
+
+/// The first byte of the public keys of the accounts created by a scenario.
+const NEW_ACCOUNT_KEY_BYTE: u8 = 16;
+/// The number of accounts a scenario can create.
+const MAX_NEW_ACCOUNT_COUNT: u8 = 16;
+/// The first byte of the public keys associated with accounts by a scenario.
+const ADDED_KEY_BYTE: u8 = 160;
+const ADDED_KEY_WEIGHT: u8 = 2;
+/// The key which `authorized_keys.wasm` associates with the account before setting thresholds.
+const AUTHORIZED_KEY: PublicKey = PublicKey::ed25519_from([123; 32]);
+const AUTHORIZED_KEY_WEIGHT: u8 = 100;
+
+/// The expected state of an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModelAccount {
+    pub public_key: PublicKey,
+    pub balance: U512,
+    pub named_keys: BTreeSet<String>,
+    pub associated_keys: BTreeMap<PublicKey, Weight>,
+    pub key_management_threshold: Weight,
+    pub deployment_threshold: Weight,
+}
+
+impl ModelAccount {
+    /// Returns the state of an account created by a transfer of `balance`.
+    fn new(public_key: PublicKey, balance: U512) -> Self {
+        ModelAccount {
+            public_key,
+            balance,
+            named_keys: [MINT_NAME, POS_NAME]
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            associated_keys: vec![(public_key, Weight::new(1))].into_iter().collect(),
+            key_management_threshold: Weight::new(1),
+            deployment_threshold: Weight::new(1),
+        }
+    }
+
+    /// Reads the state of an account from global state.
+    pub fn from_builder(builder: &InMemoryWasmTestBuilder, public_key: PublicKey) -> Option<Self> {
+        let account = builder.get_account(public_key)?;
+        Some(ModelAccount {
+            public_key,
+            balance: builder.get_purse_balance(account.main_purse()),
+            named_keys: account.named_keys().keys().cloned().collect(),
+            associated_keys: account
+                .get_associated_keys()
+                .map(|(key, weight)| (*key, *weight))
+                .collect(),
+            key_management_threshold: *account.action_thresholds().key_management(),
+            deployment_threshold: *account.action_thresholds().deployment(),
+        })
+    }
+
+    /// Returns `true` if the account can pay for a deploy which moves `amount` out of its main
+    /// purse.
+    fn can_pay(&self, amount: u64) -> bool {
+        self.balance >= *DEFAULT_PAYMENT + amount
+    }
+}
+
+/// The state a scenario is expected to leave global state in.
+#[derive(Clone, Debug)]
+pub struct Model {
+    pub accounts: Vec<ModelAccount>,
+    pub stakes: Stakes,
+    genesis_account_count: usize,
+    bonding_enabled: bool,
+}
+
+impl Model {
+    /// Reads the initial state of the genesis accounts from global state.
+    pub fn new(builder: &InMemoryWasmTestBuilder, genesis_accounts: &[GenesisAccount]) -> Self {
+        let accounts = genesis_accounts
+            .iter()
+            .map(|account| {
+                ModelAccount::from_builder(builder, account.public_key())
+                    .expect("should have genesis account")
+            })
+            .collect::<Vec<_>>();
+        let stakes = genesis_accounts
+            .iter()
+            .filter(|account| !account.bonded_amount().value().is_zero())
+            .map(|account| (account.public_key(), account.bonded_amount().value()))
+            .collect();
+        Model {
+            genesis_account_count: accounts.len(),
+            accounts,
+            stakes: Stakes(stakes),
+            bonding_enabled: cfg!(feature = "enable-bonding"),
+        }
+    }
+
+    fn pick(&self, index: &proptest::sample::Index) -> &ModelAccount {
+        index.get(&self.accounts)
+    }
+
+    fn account_mut(&mut self, public_key: PublicKey) -> Option<&mut ModelAccount> {
+        self.accounts
+            .iter_mut()
+            .find(|account| account.public_key == public_key)
+    }
+
+    /// Resolves `operation` into the `step_index`th step, or returns `None` if it doesn't apply to
+    /// the current state, e.g. as the account can't pay for it.
+    pub fn plan(&self, operation: &Operation, step_index: usize) -> Option<Step> {
+        let (account, session, expect_success) = match operation {
+            Operation::Transfer {
+                source,
+                target,
+                amount,
+            } => {
+                let account = self.pick(source);
+                let target = self.pick(target).public_key;
+                if target == account.public_key || !account.can_pay(*amount) {
+                    return None;
+                }
+                let session = Session::Transfer {
+                    target,
+                    amount: U512::from(*amount),
+                };
+                (account, session, true)
+            }
+            Operation::CreateAccount { source, amount } => {
+                let new_account_count = self.accounts.len() - self.genesis_account_count;
+                let account = self.pick(source);
+                if new_account_count >= MAX_NEW_ACCOUNT_COUNT as usize || !account.can_pay(*amount)
+                {
+                    return None;
+                }
+                let target =
+                    PublicKey::ed25519_from([NEW_ACCOUNT_KEY_BYTE + new_account_count as u8; 32]);
+                let session = Session::Transfer {
+                    target,
+                    amount: U512::from(*amount),
+                };
+                (account, session, true)
+            }
+            Operation::Bond { validator, amount } => {
+                let account = self.pick(validator);
+                if !account.can_pay(*amount) {
+                    return None;
+                }
+                let expect_success = self.bonding_enabled
+                    && self
+                        .stakes
+                        .validate_bonding(&account.public_key, U512::from(*amount))
+                        .is_ok();
+                (account, Session::Bond { amount: *amount }, expect_success)
+            }
+            Operation::Unbond {
+                validator,
+                maybe_amount,
+            } => {
+                let account = self.pick(validator);
+                if !account.can_pay(0) {
+                    return None;
+                }
+                let expect_success = self.bonding_enabled
+                    && self
+                        .stakes
+                        .clone()
+                        .unbond(&account.public_key, maybe_amount.map(U512::from))
+                        .is_ok();
+                let session = Session::Unbond {
+                    maybe_amount: *maybe_amount,
+                };
+                (account, session, expect_success)
+            }
+            Operation::StoreContract { account } => {
+                let account = self.pick(account);
+                if !account.can_pay(0) {
+                    return None;
+                }
+                (account, Session::StoreContract, true)
+            }
+            Operation::CallContract { account } => {
+                let account = self.pick(account);
+                if !account.can_pay(0) || !account.named_keys.contains(STORED_CONTRACT_NAME) {
+                    return None;
+                }
+                (account, Session::CallContract, true)
+            }
+            Operation::AddKey { account, key } => {
+                let account = self.pick(account);
+                debug_assert!(*key < ADDED_KEY_COUNT);
+                let key = PublicKey::ed25519_from([ADDED_KEY_BYTE + key; 32]);
+                if !account.can_pay(0)
+                    || account.associated_keys.contains_key(&key)
+                    || account.associated_keys.len() >= MAX_ASSOCIATED_KEYS
+                {
+                    return None;
+                }
+                (account, Session::AddKey { key }, true)
+            }
+            Operation::SetThresholds {
+                account,
+                key_management,
+                deployment,
+            } => {
+                let account = self.pick(account);
+                if !account.can_pay(0) {
+                    return None;
+                }
+                let key_management = Weight::new(*key_management);
+                let deployment = Weight::new(*deployment);
+                let can_add_authorized_key = account.associated_keys.contains_key(&AUTHORIZED_KEY)
+                    || account.associated_keys.len() < MAX_ASSOCIATED_KEYS;
+                // The key management threshold is set first, so it can't be lowered below the
+                // current deployment threshold.
+                let expect_success = can_add_authorized_key
+                    && account.deployment_threshold <= key_management
+                    && deployment <= key_management;
+                let session = Session::SetThresholds {
+                    key_management,
+                    deployment,
+                };
+                (account, session, expect_success)
+            }
+        };
+
+        Some(Step {
+            account: account.public_key,
+            // All keys sign, so the account's thresholds are always met.
+            authorization_keys: account.associated_keys.keys().cloned().collect(),
+            session,
+            deploy_hash: [step_index as u8 + 1; 32],
+            expect_success,
+        })
+    }
+
+    /// Applies the effects of executing `step`, which cost its account `cost` motes.
+    pub fn apply(&mut self, step: &Step, cost: U512) {
+        let account = self
+            .account_mut(step.account)
+            .expect("should have account of step");
+        account.balance -= cost;
+        if !step.expect_success {
+            return;
+        }
+
+        match step.session {
+            Session::Transfer { target, amount } => {
+                account.balance -= amount;
+                match self.account_mut(target) {
+                    Some(target) => target.balance += amount,
+                    None => self.accounts.push(ModelAccount::new(target, amount)),
+                }
+            }
+            Session::Bond { amount } => {
+                account.balance -= U512::from(amount);
+                self.stakes
+                    .bond(&step.account, U512::from(amount))
+                    .expect("should bond");
+            }
+            Session::Unbond { maybe_amount } => {
+                // The unbonded stake is paid out to the purse it was bonded from, rather than to
+                // the main purse.
+                self.stakes
+                    .unbond(&step.account, maybe_amount.map(U512::from))
+                    .expect("should unbond");
+            }
+            Session::StoreContract => {
+                account.named_keys.insert(STORED_CONTRACT_NAME.to_string());
+            }
+            Session::CallContract => {}
+            Session::AddKey { key } => {
+                account
+                    .associated_keys
+                    .insert(key, Weight::new(ADDED_KEY_WEIGHT));
+            }
+            Session::SetThresholds {
+                key_management,
+                deployment,
+            } => {
+                account
+                    .associated_keys
+                    .entry(AUTHORIZED_KEY)
+                    .or_insert_with(|| Weight::new(AUTHORIZED_KEY_WEIGHT));
+                account.key_management_threshold = key_management;
+                account.deployment_threshold = deployment;
+            }
+        }
+    }
+}
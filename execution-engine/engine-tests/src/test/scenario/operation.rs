@@ -0,0 +1,94 @@
+use proptest::{collection::vec, option, prelude::*, sample::Index};
+
+/// The largest amount of motes moved by a single transfer, bond or unbond.
+const MAX_AMOUNT: u64 = 10_000_000_000;
+/// The number of distinct keys which can be associated with accounts.
+pub const ADDED_KEY_COUNT: u8 = 8;
+/// The largest threshold which can be set for an action.
+const MAX_THRESHOLD: u8 = 4;
+
+/// An operation of a scenario, before it's resolved against the model.
+///
+/// Accounts are given as indices into the accounts which exist when the operation runs, so that an
+/// operation stays meaningful while the sequence it's part of is shrunk.
+#[derive(Clone, Debug)]
+pub enum Operation {
+    Transfer {
+        source: Index,
+        target: Index,
+        amount: u64,
+    },
+    CreateAccount {
+        source: Index,
+        amount: u64,
+    },
+    Bond {
+        validator: Index,
+        amount: u64,
+    },
+    Unbond {
+        validator: Index,
+        maybe_amount: Option<u64>,
+    },
+    StoreContract {
+        account: Index,
+    },
+    CallContract {
+        account: Index,
+    },
+    AddKey {
+        account: Index,
+        key: u8,
+    },
+    SetThresholds {
+        account: Index,
+        key_management: u8,
+        deployment: u8,
+    },
+}
+
+fn amount() -> impl Strategy<Value = u64> {
+    1..=MAX_AMOUNT
+}
+
+fn thresholds() -> impl Strategy<Value = (u8, u8)> {
+    (1..=MAX_THRESHOLD).prop_flat_map(|key_management| (Just(key_management), 1..=key_management))
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        3 => (any::<Index>(), any::<Index>(), amount()).prop_map(|(source, target, amount)| {
+            Operation::Transfer {
+                source,
+                target,
+                amount,
+            }
+        }),
+        2 => (any::<Index>(), amount())
+            .prop_map(|(source, amount)| Operation::CreateAccount { source, amount }),
+        1 => (any::<Index>(), amount())
+            .prop_map(|(validator, amount)| Operation::Bond { validator, amount }),
+        1 => (any::<Index>(), option::of(amount())).prop_map(|(validator, maybe_amount)| {
+            Operation::Unbond {
+                validator,
+                maybe_amount,
+            }
+        }),
+        1 => any::<Index>().prop_map(|account| Operation::StoreContract { account }),
+        1 => any::<Index>().prop_map(|account| Operation::CallContract { account }),
+        1 => (any::<Index>(), 0..ADDED_KEY_COUNT)
+            .prop_map(|(account, key)| Operation::AddKey { account, key }),
+        1 => (any::<Index>(), thresholds()).prop_map(
+            |(account, (key_management, deployment))| Operation::SetThresholds {
+                account,
+                key_management,
+                deployment,
+            }
+        ),
+    ]
+}
+
+/// Generates sequences of between one and `max_length` operations.
+pub fn operations(max_length: usize) -> impl Strategy<Value = Vec<Operation>> {
+    vec(operation(), 1..=max_length)
+}
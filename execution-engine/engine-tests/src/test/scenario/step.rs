@@ -0,0 +1,198 @@
+use std::fmt::{self, Display, Formatter};
+
+use engine_core::engine_state::execute_request::ExecuteRequest;
+use engine_test_support::internal::{DeployItemBuilder, ExecuteRequestBuilder, DEFAULT_PAYMENT};
+use types::{
+    account::{PublicKey, Weight},
+    U512,
+};
+
+const CONTRACT_TRANSFER: &str = "transfer_to_account_u512.wasm";
+const CONTRACT_BONDING: &str = "bonding.wasm";
+const CONTRACT_UNBONDING: &str = "unbonding.wasm";
+const CONTRACT_DO_NOTHING_STORED: &str = "do_nothing_stored.wasm";
+const CONTRACT_ADD_UPDATE_ASSOCIATED_KEY: &str = "add_update_associated_key.wasm";
+const CONTRACT_AUTHORIZED_KEYS: &str = "authorized_keys.wasm";
+const DESTINATION_HASH: &str = "hash";
+
+/// The named key under which `do_nothing_stored.wasm` stores its contract.
+pub const STORED_CONTRACT_NAME: &str = "do_nothing_stored";
+
+/// The session code of a step.
+#[derive(Clone, Debug)]
+pub enum Session {
+    /// Transfers to an account, creating it if it doesn't exist yet.
+    Transfer {
+        target: PublicKey,
+        amount: U512,
+    },
+    Bond {
+        amount: u64,
+    },
+    Unbond {
+        maybe_amount: Option<u64>,
+    },
+    StoreContract,
+    CallContract,
+    /// Associates `key` with the account, with a weight of 2.
+    AddKey {
+        key: PublicKey,
+    },
+    /// Associates a fixed key with a weight of 100 with the account, then sets its thresholds.
+    SetThresholds {
+        key_management: Weight,
+        deployment: Weight,
+    },
+}
+
+/// An operation resolved against the model into a deploy, along with its expected outcome.
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub account: PublicKey,
+    pub authorization_keys: Vec<PublicKey>,
+    pub session: Session,
+    pub deploy_hash: [u8; 32],
+    pub expect_success: bool,
+}
+
+impl Step {
+    pub fn exec_request(&self) -> ExecuteRequest {
+        let deploy_builder = DeployItemBuilder::new().with_address(self.account);
+        let deploy_builder = match self.session {
+            Session::Transfer { target, amount } => {
+                deploy_builder.with_session_code(CONTRACT_TRANSFER, (target, amount))
+            }
+            Session::Bond { amount } => {
+                deploy_builder.with_session_code(CONTRACT_BONDING, (amount,))
+            }
+            Session::Unbond { maybe_amount } => {
+                deploy_builder.with_session_code(CONTRACT_UNBONDING, (maybe_amount,))
+            }
+            Session::StoreContract => deploy_builder.with_session_code(
+                CONTRACT_DO_NOTHING_STORED,
+                (String::from(DESTINATION_HASH),),
+            ),
+            Session::CallContract => {
+                deploy_builder.with_stored_session_named_key(STORED_CONTRACT_NAME, ())
+            }
+            Session::AddKey { key } => {
+                deploy_builder.with_session_code(CONTRACT_ADD_UPDATE_ASSOCIATED_KEY, (key,))
+            }
+            Session::SetThresholds {
+                key_management,
+                deployment,
+            } => deploy_builder
+                .with_session_code(CONTRACT_AUTHORIZED_KEYS, (key_management, deployment)),
+        };
+        let deploy = deploy_builder
+            .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&self.authorization_keys)
+            .with_deploy_hash(self.deploy_hash)
+            .build();
+        ExecuteRequestBuilder::new().push_deploy(deploy).build()
+    }
+}
+
+/// Formats `public_key` as the Rust expression constructing it.
+pub struct PublicKeyCode<'a>(pub &'a PublicKey);
+
+impl<'a> Display for PublicKeyCode<'a> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let bytes = self.0.as_bytes();
+        if bytes.iter().all(|byte| *byte == bytes[0]) {
+            write!(formatter, "PublicKey::ed25519_from([{}; 32])", bytes[0])
+        } else {
+            write!(formatter, "PublicKey::ed25519_from({:?})", bytes)
+        }
+    }
+}
+
+/// Formats a step as the Rust code executing it with a `builder` in scope.
+impl Display for Step {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let session_code = match self.session {
+            Session::Transfer { target, amount } => format!(
+                ".with_session_code({:?}, ({}, U512::from({})))",
+                CONTRACT_TRANSFER,
+                PublicKeyCode(&target),
+                amount
+            ),
+            Session::Bond { amount } => format!(
+                ".with_session_code({:?}, ({}u64,))",
+                CONTRACT_BONDING, amount
+            ),
+            Session::Unbond {
+                maybe_amount: Some(amount),
+            } => format!(
+                ".with_session_code({:?}, (Some({}u64),))",
+                CONTRACT_UNBONDING, amount
+            ),
+            Session::Unbond { maybe_amount: None } => format!(
+                ".with_session_code({:?}, (None::<u64>,))",
+                CONTRACT_UNBONDING
+            ),
+            Session::StoreContract => format!(
+                ".with_session_code({:?}, (String::from({:?}),))",
+                CONTRACT_DO_NOTHING_STORED, DESTINATION_HASH
+            ),
+            Session::CallContract => format!(
+                ".with_stored_session_named_key({:?}, ())",
+                STORED_CONTRACT_NAME
+            ),
+            Session::AddKey { key } => format!(
+                ".with_session_code({:?}, ({},))",
+                CONTRACT_ADD_UPDATE_ASSOCIATED_KEY,
+                PublicKeyCode(&key)
+            ),
+            Session::SetThresholds {
+                key_management,
+                deployment,
+            } => format!(
+                ".with_session_code({:?}, (Weight::new({}), Weight::new({})))",
+                CONTRACT_AUTHORIZED_KEYS,
+                key_management.value(),
+                deployment.value()
+            ),
+        };
+        let authorization_keys: Vec<String> = self
+            .authorization_keys
+            .iter()
+            .map(|key| PublicKeyCode(key).to_string())
+            .collect();
+
+        writeln!(formatter, "let deploy = DeployItemBuilder::new()")?;
+        writeln!(
+            formatter,
+            "    .with_address({})",
+            PublicKeyCode(&self.account)
+        )?;
+        writeln!(formatter, "    {}", session_code)?;
+        writeln!(
+            formatter,
+            "    .with_empty_payment_bytes((*DEFAULT_PAYMENT,))"
+        )?;
+        writeln!(
+            formatter,
+            "    .with_authorization_keys(&[{}])",
+            authorization_keys.join(", ")
+        )?;
+        writeln!(
+            formatter,
+            "    .with_deploy_hash([{}; 32])",
+            self.deploy_hash[0]
+        )?;
+        writeln!(formatter, "    .build();")?;
+        writeln!(formatter, "builder")?;
+        writeln!(
+            formatter,
+            "    .exec(ExecuteRequestBuilder::new().push_deploy(deploy).build())"
+        )?;
+        if self.expect_success {
+            writeln!(formatter, "    .expect_success()")?;
+            writeln!(formatter, "    .commit();")
+        } else {
+            writeln!(formatter, "    .commit();")?;
+            writeln!(formatter, "assert!(builder.is_error());")
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use engine_core::engine_state::{run_genesis_request::RunGenesisRequest, Error};
+use engine_shared::account::AccountConfig;
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_ACCOUNTS, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PAYMENT, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::{PublicKey, Weight},
+    ProtocolVersion,
+};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const TRANSFER_PURSE_TO_ACCOUNT_WASM: &str = "transfer_purse_to_account.wasm";
+const DEFAULT_ACTIVATION_POINT: u64 = 1;
+
+fn custom_account_config() -> AccountConfig {
+    AccountConfig::new(Weight::new(2), Weight::new(3), Weight::new(3))
+}
+
+fn run_genesis_request(account_config: AccountConfig) -> RunGenesisRequest {
+    let mut exec_config = utils::create_exec_config(DEFAULT_ACCOUNTS.clone());
+    exec_config.set_account_config(account_config);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+/// Transfers to `ACCOUNT_1_ADDR` from the default account, creating account 1, and asserts that
+/// its thresholds and the weight of its key match `account_config`.
+fn assert_transfer_creates_account_with(
+    builder: &mut InMemoryWasmTestBuilder,
+    protocol_version: ProtocolVersion,
+    account_config: AccountConfig,
+) {
+    assert!(builder.get_account(ACCOUNT_1_ADDR).is_none());
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        TRANSFER_PURSE_TO_ACCOUNT_WASM,
+        (ACCOUNT_1_ADDR, *DEFAULT_PAYMENT),
+    )
+    .with_protocol_version(protocol_version)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let account_1 = builder
+        .get_account(ACCOUNT_1_ADDR)
+        .expect("should have account 1");
+    assert_eq!(
+        *account_1.action_thresholds(),
+        account_config.default_action_thresholds()
+    );
+    assert_eq!(
+        account_1.get_associated_key_weight(ACCOUNT_1_ADDR),
+        Some(&account_config.default_identity_key_weight())
+    );
+    assert_eq!(account_1.get_associated_keys().count(), 1);
+}
+
+#[ignore]
+#[test]
+fn should_create_account_from_transfer_with_default_account_config() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    assert_transfer_creates_account_with(
+        &mut builder,
+        *DEFAULT_PROTOCOL_VERSION,
+        AccountConfig::default(),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_create_account_from_transfer_with_account_config_of_genesis() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(custom_account_config()));
+
+    assert_transfer_creates_account_with(
+        &mut builder,
+        *DEFAULT_PROTOCOL_VERSION,
+        custom_account_config(),
+    );
+}
+
+#[ignore]
+#[should_panic(expected = "genesis failure")]
+#[test]
+fn should_fail_genesis_if_identity_key_cannot_meet_thresholds() {
+    let account_config = AccountConfig::new(Weight::new(2), Weight::new(3), Weight::new(2));
+    InMemoryWasmTestBuilder::default().run_genesis(&run_genesis_request(account_config));
+}
+
+#[ignore]
+#[test]
+fn should_create_account_from_transfer_with_account_config_of_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_account_config(custom_account_config())
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    assert!(
+        builder
+            .get_upgrade_response(0)
+            .expect("should have response")
+            .has_success(),
+        "expected success"
+    );
+
+    assert_transfer_creates_account_with(
+        &mut builder,
+        new_protocol_version,
+        custom_account_config(),
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_upgrade_if_identity_key_cannot_meet_thresholds() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = DEFAULT_PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+    let account_config = AccountConfig::new(Weight::new(3), Weight::new(2), Weight::new(3));
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(*DEFAULT_PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_account_config(account_config)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+    assert_eq!(
+        upgrade_response.get_failed_deploy().message,
+        Error::InvalidAccountConfig.to_string()
+    );
+}
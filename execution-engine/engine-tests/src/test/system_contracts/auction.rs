@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+
+use lazy_static::lazy_static;
+
+use contract::args_parser::ArgsParser;
+use engine_core::engine_state::{step::StepResult, upgrade::ActivationPoint};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder,
+        DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{
+    account::PublicKey,
+    system_contract_errors::auction,
+    system_contract_names::auction::{METHOD_ADD_BID, METHOD_RUN_AUCTION, METHOD_WITHDRAW_BID},
+    ApiError, BlockTime, ProtocolVersion, U512,
+};
+
+const CONTRACT_AUCTION_INSTALL: &str = "auction_install.wasm";
+const CONTRACT_AUCTION_BIDS: &str = "auction_bids.wasm";
+const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const VALIDATOR_SLOTS: u32 = 3;
+const DELEGATION_RATE: u8 = 10;
+const BLOCK_TIME: BlockTime = BlockTime::from_millis(1_000);
+const ERA_END_TIMESTAMP: u64 = 2_000;
+const ERA_END: BlockTime = BlockTime::from_millis(ERA_END_TIMESTAMP);
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_3_ADDR: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+const ACCOUNT_4_ADDR: PublicKey = PublicKey::ed25519_from([4u8; 32]);
+
+lazy_static! {
+    static ref NEW_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::from_parts(1, 1, 0);
+    static ref ACCOUNT_INITIAL_BALANCE: U512 = *DEFAULT_PAYMENT * 10;
+    // placed in reverse order of the keys, so that ties aren't broken by the order of the bids
+    static ref BIDS: Vec<(PublicKey, U512)> = vec![
+        (ACCOUNT_4_ADDR, U512::from(200)),
+        (ACCOUNT_3_ADDR, U512::from(200)),
+        (ACCOUNT_2_ADDR, U512::from(200)),
+        (ACCOUNT_1_ADDR, U512::from(300)),
+    ];
+}
+
+fn setup(use_auction: bool) -> InMemoryWasmTestBuilder {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    for (account, _) in BIDS.iter() {
+        let exec_request = ExecuteRequestBuilder::standard(
+            DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            (*account, *ACCOUNT_INITIAL_BALANCE),
+        )
+        .build();
+        builder.exec(exec_request).expect_success().commit();
+    }
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(*NEW_PROTOCOL_VERSION)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_auction_config(
+            utils::read_wasm_file_bytes(CONTRACT_AUCTION_INSTALL),
+            VALIDATOR_SLOTS,
+            use_auction,
+        )
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    builder
+}
+
+/// Calls the auction from `account`, returning the index of the exec response.
+fn call_auction(
+    builder: &mut InMemoryWasmTestBuilder,
+    account: PublicKey,
+    args: impl ArgsParser,
+) -> usize {
+    let exec_request = ExecuteRequestBuilder::standard(account, CONTRACT_AUCTION_BIDS, args)
+        .with_protocol_version(*NEW_PROTOCOL_VERSION)
+        .build();
+    builder.exec(exec_request).commit();
+    builder.get_exec_responses_count() - 1
+}
+
+fn bonded_validators(builder: &InMemoryWasmTestBuilder, index: usize) -> BTreeMap<PublicKey, U512> {
+    match builder
+        .get_step_result(index)
+        .expect("should have step result")
+    {
+        StepResult::Success {
+            bonded_validators, ..
+        } => bonded_validators.clone(),
+        step_result => panic!("expected step success but received {}", step_result),
+    }
+}
+
+fn assert_reverted_with(
+    builder: &InMemoryWasmTestBuilder,
+    exec_index: usize,
+    error: auction::Error,
+) {
+    let error_message = builder
+        .exec_error_message(exec_index)
+        .expect("should have exec response");
+    let expected_error = ApiError::from(error);
+    assert!(
+        error_message.contains(&format!("{:?}", expected_error)),
+        "expected {:?}, got: {}",
+        expected_error,
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_select_top_bidders_at_era_end() {
+    let mut builder = setup(true);
+    for (account, amount) in BIDS.iter() {
+        call_auction(
+            &mut builder,
+            *account,
+            (METHOD_ADD_BID, *amount, DELEGATION_RATE),
+        );
+        builder.expect_success();
+    }
+
+    // until the first auction, the validators bonded with PoS are reported
+    builder.step(*NEW_PROTOCOL_VERSION, BLOCK_TIME);
+    assert!(!bonded_validators(&builder, 0).contains_key(&ACCOUNT_1_ADDR));
+
+    builder.step_with_era_end(*NEW_PROTOCOL_VERSION, BLOCK_TIME, ERA_END);
+
+    // the highest bid wins, and the tie between the other bids is broken by key bytes
+    let mut expected = BTreeMap::new();
+    expected.insert(ACCOUNT_1_ADDR, U512::from(300));
+    expected.insert(ACCOUNT_2_ADDR, U512::from(200));
+    expected.insert(ACCOUNT_3_ADDR, U512::from(200));
+    assert_eq!(bonded_validators(&builder, 1), expected);
+}
+
+#[ignore]
+#[test]
+fn should_report_pos_validators_unless_auction_is_used() {
+    let mut builder = setup(false);
+    for (account, amount) in BIDS.iter() {
+        call_auction(
+            &mut builder,
+            *account,
+            (METHOD_ADD_BID, *amount, DELEGATION_RATE),
+        );
+        builder.expect_success();
+    }
+    builder.step(*NEW_PROTOCOL_VERSION, BLOCK_TIME);
+    builder.step_with_era_end(*NEW_PROTOCOL_VERSION, BLOCK_TIME, ERA_END);
+
+    assert_eq!(
+        bonded_validators(&builder, 0),
+        bonded_validators(&builder, 1)
+    );
+    assert!(!bonded_validators(&builder, 1).contains_key(&ACCOUNT_1_ADDR));
+}
+
+#[ignore]
+#[test]
+fn should_lock_winning_bids_until_next_auction() {
+    let mut builder = setup(true);
+    for (account, amount) in BIDS.iter() {
+        call_auction(
+            &mut builder,
+            *account,
+            (METHOD_ADD_BID, *amount, DELEGATION_RATE),
+        );
+        builder.expect_success();
+    }
+    builder.step_with_era_end(*NEW_PROTOCOL_VERSION, BLOCK_TIME, ERA_END);
+
+    let exec_index = call_auction(&mut builder, ACCOUNT_1_ADDR, (METHOD_WITHDRAW_BID,));
+    assert_reverted_with(&builder, exec_index, auction::Error::BidLocked);
+
+    call_auction(&mut builder, ACCOUNT_4_ADDR, (METHOD_WITHDRAW_BID,));
+    builder.expect_success();
+    let exec_index = call_auction(&mut builder, ACCOUNT_4_ADDR, (METHOD_WITHDRAW_BID,));
+    assert_reverted_with(&builder, exec_index, auction::Error::BidNotFound);
+}
+
+#[ignore]
+#[test]
+fn should_not_run_auction_from_user_account() {
+    let mut builder = setup(true);
+    let exec_index = call_auction(
+        &mut builder,
+        DEFAULT_ACCOUNT_ADDR,
+        (METHOD_RUN_AUCTION, ERA_END_TIMESTAMP),
+    );
+    assert_reverted_with(
+        &builder,
+        exec_index,
+        auction::Error::SystemFunctionCalledByUserAccount,
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_use_auction_without_installing_it() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(*NEW_PROTOCOL_VERSION)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_auction_config(Vec::new(), VALIDATOR_SLOTS, true)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(!upgrade_response.has_success(), "expected failure");
+}
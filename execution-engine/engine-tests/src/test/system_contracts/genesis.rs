@@ -1,21 +1,34 @@
+use grpc::RequestOptions;
+
 use engine_core::engine_state::{
-    genesis::{ExecConfig, GenesisAccount},
+    genesis::{ExecConfig, GenesisAccount, GenesisBondingError, GenesisBondingReport},
     run_genesis_request::RunGenesisRequest,
     SYSTEM_ACCOUNT_ADDR,
 };
-use engine_shared::{motes::Motes, stored_value::StoredValue};
-use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
-    POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+use engine_grpc_server::engine_server::{
+    ipc::{self, ChainSpec_GenesisConfig, ChainspecFingerprintRequest},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{account::AccountConfig, motes::Motes, stored_value::StoredValue};
+use engine_test_support::{
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_GENESIS_CONFIG, DEFAULT_GENESIS_CONFIG_HASH, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST, DEFAULT_WASM_COSTS, MINT_INSTALL_CONTRACT,
+        POS_INSTALL_CONTRACT, STANDARD_PAYMENT_INSTALL_CONTRACT,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
 use types::{account::PublicKey, Key, ProtocolVersion, U512};
 
 #[cfg(feature = "use-system-contracts")]
 const BAD_INSTALL: &str = "standard_payment.wasm";
 
+const CONTRACT_LOCAL_STATE: &str = "local_state.wasm";
 const GENESIS_CONFIG_HASH: [u8; 32] = [127; 32];
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
 const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
+const ACCOUNT_3_ADDR: PublicKey = PublicKey::ed25519_from([3u8; 32]);
 const ACCOUNT_1_BONDED_AMOUNT: u64 = 1_000_000;
 const ACCOUNT_2_BONDED_AMOUNT: u64 = 2_000_000;
 const ACCOUNT_1_BALANCE: u64 = 1_000_000_000;
@@ -60,6 +73,7 @@ fn should_run_genesis() {
         standard_payment_installer_bytes,
         accounts,
         wasm_costs,
+        AccountConfig::default(),
     );
     let run_genesis_request =
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config);
@@ -80,11 +94,12 @@ fn should_run_genesis() {
         .get_account(ACCOUNT_2_ADDR)
         .expect("account 2 should exist");
 
-    let system_account_balance_actual = builder.get_purse_balance(system_account.main_purse());
     let account_1_balance_actual = builder.get_purse_balance(account_1.main_purse());
     let account_2_balance_actual = builder.get_purse_balance(account_2.main_purse());
 
-    assert_eq!(system_account_balance_actual, U512::zero());
+    // the system account has no purse and no keys, so no user deploy can run as it
+    assert_eq!(system_account.main_purse().addr(), [0u8; 32]);
+    assert_eq!(system_account.get_associated_keys().count(), 0);
     assert_eq!(account_1_balance_actual, account_1_balance.value());
     assert_eq!(account_2_balance_actual, account_2_balance.value());
 
@@ -104,6 +119,96 @@ fn should_run_genesis() {
     }
 }
 
+#[ignore]
+#[test]
+fn should_report_default_genesis_account() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let account_reports = builder.get_genesis_account_reports();
+    assert_eq!(account_reports.len(), 1);
+    let account_report = &account_reports[0];
+
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+    assert_eq!(account_report.public_key, DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(
+        account_report.account_key,
+        Key::Account(DEFAULT_ACCOUNT_ADDR)
+    );
+    assert_eq!(account_report.main_purse_uref, account.main_purse());
+    assert_eq!(
+        account_report.initial_balance,
+        Motes::new(DEFAULT_ACCOUNT_INITIAL_BALANCE.into())
+    );
+    assert_eq!(account_report.bonded_amount, Motes::zero());
+
+    let balance = builder
+        .query(None, Key::URef(account_report.balance_uref), &[])
+        .expect("should have balance")
+        .as_cl_value()
+        .cloned()
+        .expect("should be CLValue")
+        .into_t::<U512>()
+        .expect("should be U512");
+    assert_eq!(balance, U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE));
+}
+
+#[ignore]
+#[test]
+fn should_report_bonded_genesis_accounts_in_order() {
+    let account_1 = GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+    );
+    let account_2 = GenesisAccount::new(
+        ACCOUNT_2_ADDR,
+        Motes::new(ACCOUNT_2_BALANCE.into()),
+        Motes::zero(),
+    );
+    let exec_config = ExecConfig::new(
+        utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT),
+        utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT),
+        vec![account_2, account_1],
+        *DEFAULT_WASM_COSTS,
+        AccountConfig::default(),
+    );
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        ProtocolVersion::V1_0_0,
+        exec_config,
+    );
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request);
+
+    let reported: Vec<(PublicKey, Motes, Motes)> = builder
+        .get_genesis_account_reports()
+        .iter()
+        .map(|account_report| {
+            (
+                account_report.public_key,
+                account_report.initial_balance,
+                account_report.bonded_amount,
+            )
+        })
+        .collect();
+    let expected: Vec<(PublicKey, Motes, Motes)> = [account_2, account_1]
+        .iter()
+        .map(|account| {
+            (
+                account.public_key(),
+                account.balance(),
+                account.bonded_amount(),
+            )
+        })
+        .collect();
+    assert_eq!(reported, expected);
+}
+
 #[cfg(feature = "use-system-contracts")]
 #[ignore]
 #[should_panic]
@@ -144,6 +249,7 @@ fn should_fail_if_bad_mint_install_contract_is_provided() {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            AccountConfig::default(),
         );
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
     };
@@ -192,6 +298,40 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
             standard_payment_installer_bytes,
             accounts,
             wasm_costs,
+            AccountConfig::default(),
+        );
+        RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
+    };
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+}
+
+#[ignore]
+#[should_panic(expected = "Duplicate genesis account")]
+#[test]
+fn should_fail_if_account_is_listed_twice() {
+    let run_genesis_request = {
+        let account_1 = GenesisAccount::new(
+            ACCOUNT_1_ADDR,
+            Motes::new(ACCOUNT_1_BALANCE.into()),
+            Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+        );
+        let mint_installer_bytes = utils::read_wasm_file_bytes(MINT_INSTALL_CONTRACT);
+        let pos_installer_bytes = utils::read_wasm_file_bytes(POS_INSTALL_CONTRACT);
+        let standard_payment_installer_bytes =
+            utils::read_wasm_file_bytes(STANDARD_PAYMENT_INSTALL_CONTRACT);
+        let accounts = vec![account_1, account_1];
+        let protocol_version = ProtocolVersion::V1_0_0;
+        let wasm_costs = *DEFAULT_WASM_COSTS;
+        let exec_config = ExecConfig::new(
+            mint_installer_bytes,
+            pos_installer_bytes,
+            standard_payment_installer_bytes,
+            accounts,
+            wasm_costs,
+            AccountConfig::default(),
         );
         RunGenesisRequest::new(GENESIS_CONFIG_HASH.into(), protocol_version, exec_config)
     };
@@ -200,3 +340,181 @@ fn should_fail_if_bad_pos_install_contract_is_provided() {
 
     builder.run_genesis(&run_genesis_request);
 }
+
+/// Returns the default accounts followed by a validator bonded for more than its balance between
+/// two which can be bonded, and an account which isn't a validator.
+fn mixed_validity_accounts() -> Vec<GenesisAccount> {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+    ));
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_2_ADDR,
+        Motes::new(ACCOUNT_2_BALANCE.into()),
+        Motes::new((ACCOUNT_2_BALANCE + 1).into()),
+    ));
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_3_ADDR,
+        Motes::new(ACCOUNT_2_BALANCE.into()),
+        Motes::new(ACCOUNT_2_BONDED_AMOUNT.into()),
+    ));
+    accounts.push(GenesisAccount::new(
+        PublicKey::ed25519_from([4u8; 32]),
+        Motes::new(ACCOUNT_1_BALANCE.into()),
+        Motes::zero(),
+    ));
+    accounts
+}
+
+fn run_genesis_request(accounts: Vec<GenesisAccount>, strict: bool) -> RunGenesisRequest {
+    let mut exec_config = utils::create_exec_config(accounts);
+    exec_config.set_strict_genesis_bonding(strict);
+    RunGenesisRequest::new(
+        *DEFAULT_GENESIS_CONFIG_HASH,
+        *DEFAULT_PROTOCOL_VERSION,
+        exec_config,
+    )
+}
+
+#[ignore]
+#[should_panic(expected = "exceeds its balance")]
+#[test]
+fn should_fail_strict_genesis_if_any_validator_cannot_be_bonded() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(mixed_validity_accounts(), true));
+}
+
+#[ignore]
+#[test]
+fn should_skip_validators_which_cannot_be_bonded_unless_strict() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&run_genesis_request(mixed_validity_accounts(), false));
+
+    let expected_bonding_reports = vec![
+        GenesisBondingReport::Bonded {
+            key: ACCOUNT_1_ADDR,
+            amount: Motes::new(ACCOUNT_1_BONDED_AMOUNT.into()),
+        },
+        GenesisBondingReport::Skipped {
+            key: ACCOUNT_2_ADDR,
+            reason: GenesisBondingError::BondedAmountExceedsBalance {
+                balance: Motes::new(ACCOUNT_2_BALANCE.into()),
+                bonded_amount: Motes::new((ACCOUNT_2_BALANCE + 1).into()),
+            },
+        },
+        GenesisBondingReport::Bonded {
+            key: ACCOUNT_3_ADDR,
+            amount: Motes::new(ACCOUNT_2_BONDED_AMOUNT.into()),
+        },
+    ];
+    let default_bonding_reports: Vec<GenesisBondingReport> =
+        utils::create_exec_config(DEFAULT_ACCOUNTS.clone())
+            .bonding_reports()
+            .collect();
+    assert_eq!(
+        &builder.get_genesis_bonding_reports()[default_bonding_reports.len()..],
+        expected_bonding_reports.as_slice()
+    );
+
+    // the skipped validator's account is still created, just not bonded
+    let account_2_report = builder
+        .get_genesis_account_reports()
+        .iter()
+        .find(|account_report| account_report.public_key == ACCOUNT_2_ADDR)
+        .expect("should report account 2");
+    assert_eq!(
+        account_2_report.initial_balance,
+        Motes::new(ACCOUNT_2_BALANCE.into())
+    );
+    assert_eq!(account_2_report.bonded_amount, Motes::zero());
+
+    // the first commit reports exactly the validators reported as bonded
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_LOCAL_STATE, ()).build();
+    builder.exec(exec_request).expect_success().commit();
+    let mut expected_bonded_validators: Vec<(PublicKey, U512)> = builder
+        .get_genesis_bonding_reports()
+        .iter()
+        .filter_map(|bonding_report| match bonding_report {
+            GenesisBondingReport::Bonded { key, amount } => Some((*key, amount.value())),
+            GenesisBondingReport::Skipped { .. } => None,
+        })
+        .collect();
+    expected_bonded_validators.sort_by_key(|(key, _)| key.as_bytes().to_vec());
+    assert_eq!(
+        builder.get_bonded_validators()[0],
+        expected_bonded_validators
+    );
+}
+
+fn chainspec_fingerprint(
+    builder: &InMemoryWasmTestBuilder,
+    genesis_config: ChainSpec_GenesisConfig,
+) -> (Vec<u8>, Vec<u8>) {
+    let mut chainspec_fingerprint_request = ChainspecFingerprintRequest::new();
+    chainspec_fingerprint_request.set_genesis_config(genesis_config);
+    let mut response = builder
+        .get_engine_state()
+        .chainspec_fingerprint(RequestOptions::new(), chainspec_fingerprint_request)
+        .wait_drop_metadata()
+        .expect("should get fingerprint response");
+    assert!(response.has_success(), "{:?}", response.get_failure());
+    let mut fingerprints = response.take_success();
+    (
+        fingerprints.take_fingerprint(),
+        fingerprints.take_exec_config_fingerprint(),
+    )
+}
+
+#[ignore]
+#[test]
+fn should_fingerprint_chainspec_regardless_of_account_order() {
+    let mut genesis_config = DEFAULT_GENESIS_CONFIG.clone();
+    for (public_key, balance, bonded_amount) in &[
+        (ACCOUNT_1_ADDR, ACCOUNT_1_BALANCE, ACCOUNT_1_BONDED_AMOUNT),
+        (ACCOUNT_2_ADDR, ACCOUNT_2_BALANCE, ACCOUNT_2_BONDED_AMOUNT),
+    ] {
+        genesis_config
+            .ee_config_mut()
+            .push_account(GenesisAccount::new(
+                *public_key,
+                Motes::new((*balance).into()),
+                Motes::new((*bonded_amount).into()),
+            ));
+    }
+    let pb_genesis_config: ChainSpec_GenesisConfig = genesis_config.clone().into();
+    let mut pb_reordered = pb_genesis_config.clone();
+    pb_reordered.mut_ee_config().mut_accounts().reverse();
+    let mut pb_changed = pb_genesis_config.clone();
+    pb_changed.mut_ee_config().mut_accounts()[0]
+        .set_balance(U512::from(ACCOUNT_1_BALANCE + 1).into());
+
+    let builder = InMemoryWasmTestBuilder::default();
+    let (fingerprint, exec_config_fingerprint) = chainspec_fingerprint(&builder, pb_genesis_config);
+    assert_eq!(
+        chainspec_fingerprint(&builder, pb_reordered),
+        (fingerprint.clone(), exec_config_fingerprint.clone())
+    );
+    let (changed_fingerprint, _) = chainspec_fingerprint(&builder, pb_changed);
+    assert_ne!(changed_fingerprint, fingerprint);
+
+    // running genesis reports the same fingerprint for the config it ran
+    let run_genesis_request = RunGenesisRequest::new(
+        GENESIS_CONFIG_HASH.into(),
+        genesis_config.protocol_version(),
+        genesis_config.take_ee_config(),
+    );
+    let pb_run_genesis_request: ipc::RunGenesisRequest = run_genesis_request.into();
+    let genesis_response = builder
+        .get_engine_state()
+        .run_genesis(RequestOptions::new(), pb_run_genesis_request)
+        .wait_drop_metadata()
+        .expect("should get genesis response");
+    assert!(genesis_response.has_success());
+    assert_eq!(
+        genesis_response.get_success().get_exec_config_fingerprint(),
+        exec_config_fingerprint.as_slice()
+    );
+}
@@ -0,0 +1,116 @@
+use grpc::RequestOptions;
+
+use engine_core::engine_state::{genesis::GenesisAccount, run_genesis_request::RunGenesisRequest};
+use engine_grpc_server::engine_server::{
+    ipc::{GenesisResponse, ListRootsRequest, ServerInfoRequest},
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::motes::Motes;
+use engine_test_support::internal::{
+    utils, LmdbWasmTestBuilder, DEFAULT_ACCOUNTS, DEFAULT_RUN_GENESIS_REQUEST,
+};
+use types::{account::PublicKey, U512};
+
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+const ACCOUNT_1_BALANCE: u64 = 1_000_000_000;
+
+/// Returns a genesis request whose config differs from the default one by an extra account.
+fn other_run_genesis_request() -> RunGenesisRequest {
+    let mut accounts = DEFAULT_ACCOUNTS.clone();
+    accounts.push(GenesisAccount::new(
+        ACCOUNT_1_ADDR,
+        Motes::new(U512::from(ACCOUNT_1_BALANCE)),
+        Motes::new(U512::zero()),
+    ));
+    utils::create_run_genesis_request(accounts)
+}
+
+fn run_genesis(
+    builder: &LmdbWasmTestBuilder,
+    run_genesis_request: RunGenesisRequest,
+) -> GenesisResponse {
+    builder
+        .get_engine_state()
+        .run_genesis(RequestOptions::new(), run_genesis_request.into())
+        .wait_drop_metadata()
+        .expect("should get genesis response")
+}
+
+fn genesis_fingerprint(builder: &LmdbWasmTestBuilder) -> Vec<u8> {
+    builder
+        .get_engine_state()
+        .server_info(RequestOptions::new(), ServerInfoRequest::new())
+        .wait_drop_metadata()
+        .expect("should get server info")
+        .take_genesis_fingerprint()
+}
+
+/// Returns the listed roots created by genesis, with their genesis fingerprints.
+fn listed_genesis_roots(builder: &LmdbWasmTestBuilder) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut response = builder
+        .get_engine_state()
+        .list_roots(RequestOptions::new(), ListRootsRequest::new())
+        .wait_drop_metadata()
+        .expect("should list roots");
+    assert!(response.has_success(), "{:?}", response.get_failure());
+    let mut page = response.take_success();
+    assert!(!page.has_next_page_token());
+    page.take_roots()
+        .into_iter()
+        .filter(|root| !root.get_genesis_fingerprint().is_empty())
+        .map(|mut root| (root.take_state_hash(), root.take_genesis_fingerprint()))
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_refuse_genesis_on_initialized_store_unless_forced() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let builder = LmdbWasmTestBuilder::new(data_dir.path());
+    assert!(genesis_fingerprint(&builder).is_empty());
+
+    let mut first_response = run_genesis(&builder, DEFAULT_RUN_GENESIS_REQUEST.clone());
+    assert!(first_response.has_success());
+    let mut first_success = first_response.take_success();
+    let first_root = first_success.take_poststate_hash();
+    let first_fingerprint = first_success.take_exec_config_fingerprint();
+    assert_eq!(genesis_fingerprint(&builder), first_fingerprint);
+
+    let mut refused_response = run_genesis(&builder, other_run_genesis_request());
+    assert!(
+        refused_response.has_store_already_initialized(),
+        "{:?}",
+        refused_response
+    );
+    let refusal = refused_response.take_store_already_initialized();
+    assert_eq!(
+        refusal.get_existing_fingerprint(),
+        first_fingerprint.as_slice()
+    );
+    assert_eq!(refusal.get_existing_root(), first_root.as_slice());
+    assert_eq!(
+        listed_genesis_roots(&builder),
+        vec![(first_root.clone(), first_fingerprint.clone())]
+    );
+
+    let mut forced_response = run_genesis(
+        &builder,
+        other_run_genesis_request().with_force_fresh_genesis(true),
+    );
+    assert!(forced_response.has_success(), "{:?}", forced_response);
+    let mut forced_success = forced_response.take_success();
+    let forced_root = forced_success.take_poststate_hash();
+    let forced_fingerprint = forced_success.take_exec_config_fingerprint();
+    assert_ne!(forced_root, first_root);
+    assert_ne!(forced_fingerprint, first_fingerprint);
+
+    let mut genesis_roots = listed_genesis_roots(&builder);
+    genesis_roots.sort();
+    let mut expected_genesis_roots = vec![
+        (first_root, first_fingerprint.clone()),
+        (forced_root, forced_fingerprint),
+    ];
+    expected_genesis_roots.sort();
+    assert_eq!(genesis_roots, expected_genesis_roots);
+    assert_eq!(genesis_fingerprint(&builder), first_fingerprint);
+}
@@ -0,0 +1,107 @@
+use engine_test_support::{
+    internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{system_contract_errors::mint, ApiError, URef, U512};
+
+const CONTRACT_ALLOWANCE_SPENDER_STORED: &str = "allowance_spender_stored.wasm";
+const CONTRACT_ALLOWANCE_APPROVE: &str = "allowance_approve.wasm";
+const CONTRACT_ALLOWANCE_SPEND_CALLER: &str = "allowance_spend_caller.wasm";
+const TARGET_PURSE_NAME: &str = "allowance_target";
+const APPROVED_AMOUNT: u64 = 100;
+
+fn approve(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ALLOWANCE_APPROVE,
+        (U512::from(amount),),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+fn spend(builder: &mut InMemoryWasmTestBuilder, amount: u64) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ALLOWANCE_SPEND_CALLER,
+        (U512::from(amount),),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+fn setup() -> InMemoryWasmTestBuilder {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_ALLOWANCE_SPENDER_STORED,
+        (),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+    approve(&mut builder, APPROVED_AMOUNT);
+    builder
+}
+
+fn target_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let target_purse: URef = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(TARGET_PURSE_NAME)
+        .expect("should have target purse")
+        .into_uref()
+        .expect("should be uref");
+    builder.get_purse_balance(target_purse)
+}
+
+fn assert_insufficient_allowance(builder: &InMemoryWasmTestBuilder, index: usize) {
+    let error_message = builder
+        .exec_error_message(index)
+        .expect("should have exec response");
+    let expected_error = format!("{:?}", ApiError::from(mint::Error::InsufficientAllowance));
+    assert!(
+        error_message.contains(&expected_error),
+        "expected {}, got: {}",
+        expected_error,
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_transfer_from_purse_up_to_approved_amount() {
+    let mut builder = setup();
+
+    spend(&mut builder, 60);
+    builder.expect_success();
+    assert_eq!(target_purse_balance(&builder), U512::from(60));
+
+    spend(&mut builder, 50);
+    assert_insufficient_allowance(&builder, 3);
+    assert_eq!(target_purse_balance(&builder), U512::from(60));
+
+    spend(&mut builder, 40);
+    builder.expect_success();
+    assert_eq!(target_purse_balance(&builder), U512::from(APPROVED_AMOUNT));
+}
+
+#[ignore]
+#[test]
+fn should_not_transfer_from_purse_after_allowance_revoked() {
+    let mut builder = setup();
+
+    spend(&mut builder, 10);
+    builder.expect_success();
+
+    approve(&mut builder, 0);
+
+    spend(&mut builder, 10);
+    assert_insufficient_allowance(&builder, 4);
+    assert_eq!(target_purse_balance(&builder), U512::from(10));
+}
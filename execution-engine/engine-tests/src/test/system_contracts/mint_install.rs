@@ -28,7 +28,6 @@ fn should_run_mint_install_contract() {
         DEFAULT_BLOCK_TIME,
         DEPLOY_HASH_1,
         (),
-        vec![],
     )
     .expect("should run successfully");
 
@@ -0,0 +1,131 @@
+use std::convert::TryInto;
+
+use engine_core::engine_state::mint_invariants::PurseChange;
+use engine_shared::{
+    additive_map::AdditiveMap, newtypes::CorrelationId, stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{account::PublicKey, CLValue, Key, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
+const ACCOUNT_1_INITIAL_FUND: u64 = 1_000_000_000;
+const BOGUS_BALANCE: u64 = 1;
+
+/// A test double for a faulty contract: effects writing a bogus balance directly to the balance
+/// uref of the default account's main purse, bypassing the mint.  The runtime rejects a contract
+/// forging the uref, so the effects are committed directly.
+fn forged_balance_effects(builder: &InMemoryWasmTestBuilder) -> (Key, AdditiveMap<Key, Transform>) {
+    let balance_uref = builder.get_genesis_account_reports()[0].balance_uref;
+    let balance_key = Key::URef(balance_uref).normalize();
+    let bogus_balance = CLValue::from_t(U512::from(BOGUS_BALANCE)).expect("should create CLValue");
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        balance_key,
+        Transform::Write(StoredValue::CLValue(bogus_balance)),
+    );
+    (balance_key, effects)
+}
+
+#[ignore]
+#[test]
+fn should_hold_mint_invariants_across_transfers() {
+    let exec_request_1 = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_INITIAL_FUND)),
+    )
+    .build();
+    let exec_request_2 = ExecuteRequestBuilder::standard(
+        ACCOUNT_1_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (DEFAULT_ACCOUNT_ADDR, U512::from(1)),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .with_mint_invariants()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request_1)
+        .expect_success()
+        .commit()
+        .exec(exec_request_2)
+        .expect_success()
+        .commit();
+}
+
+#[ignore]
+#[test]
+#[should_panic(expected = "Mint invariant violated")]
+fn should_catch_forged_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .with_mint_invariants()
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (_, effects) = forged_balance_effects(&builder);
+    let prestate_hash = builder.get_post_state_hash();
+    builder.commit_effects(prestate_hash, effects);
+}
+
+#[ignore]
+#[test]
+fn should_report_purse_changed_by_forged_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let (balance_key, effects) = forged_balance_effects(&builder);
+    let changed_keys: Vec<Key> = effects.keys().cloned().collect();
+    let prestate_hash = builder.get_post_state_hash();
+    // the builder doesn't check the invariants unless asked to
+    builder.commit_effects(prestate_hash.clone(), effects);
+    let post_state_hash = builder.get_post_state_hash();
+
+    let violation = builder
+        .get_engine_state()
+        .check_mint_invariants(
+            CorrelationId::new(),
+            *DEFAULT_PROTOCOL_VERSION,
+            prestate_hash.as_slice().try_into().unwrap(),
+            post_state_hash.as_slice().try_into().unwrap(),
+            &changed_keys,
+        )
+        .expect("should check mint invariants")
+        .expect("should violate mint invariants");
+
+    assert_eq!(
+        violation.total_supply - violation.total_balance,
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - BOGUS_BALANCE
+    );
+    assert_eq!(
+        violation.changed_purses,
+        vec![PurseChange {
+            balance_key,
+            name: None,
+            before: Some(U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE)),
+            after: Some(U512::from(BOGUS_BALANCE)),
+        }]
+    );
+}
+
+#[ignore]
+#[test]
+fn should_skip_unsampled_commits() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .with_sampled_mint_invariants(2)
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // only every second commit is checked, so the first doesn't walk the purses
+    let (_, effects) = forged_balance_effects(&builder);
+    let prestate_hash = builder.get_post_state_hash();
+    builder.commit_effects(prestate_hash, effects);
+}
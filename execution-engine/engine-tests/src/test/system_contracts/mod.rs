@@ -1,6 +1,13 @@
+mod auction;
+mod account_config;
 mod genesis;
+mod genesis_store;
 mod mint_install;
+mod mint_allowance;
+mod mint_invariants;
+mod names;
 mod pos_install;
+mod preload;
 mod proof_of_stake;
 mod standard_payment;
 mod standard_payment_install;
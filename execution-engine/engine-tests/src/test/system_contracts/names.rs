@@ -0,0 +1,83 @@
+//! Checks that the PoS contract's entry points and named keys are only spelled out in
+//! [`types::system_contract_names`].
+//!
+//! Only the names which are unique to the PoS contract are checked: "bond", "unbond", "slash" and
+//! the mint's entry points such as "transfer" are also the names of commands and entry points of
+//! other contracts, so a literal of one of them doesn't necessarily refer to a system contract.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use types::system_contract_names::pos::{
+    BONDING_PURSE_KEY, METHOD_FINALIZE_PAYMENT, METHOD_GET_PAYMENT_PURSE,
+    METHOD_GET_PAYMENT_PURSE_BALANCE, METHOD_GET_REFUND_PURSE, METHOD_MIGRATE_RESERVED_KEYS,
+    METHOD_MIGRATE_STAKES, METHOD_SET_REFUND_PURSE, PAYMENT_PURSE_KEY, REFUND_PURSE_KEY,
+    REWARDS_PURSE_KEY,
+};
+
+const SOURCE_DIRS: [&str; 3] = [
+    concat!(env!("CARGO_MANIFEST_DIR"), "/../contracts"),
+    concat!(env!("CARGO_MANIFEST_DIR"), "/../engine-core/src"),
+    concat!(env!("CARGO_MANIFEST_DIR"), "/../proof-of-stake/src"),
+];
+
+const NAMES: [&str; 11] = [
+    METHOD_GET_PAYMENT_PURSE,
+    METHOD_GET_PAYMENT_PURSE_BALANCE,
+    METHOD_SET_REFUND_PURSE,
+    METHOD_GET_REFUND_PURSE,
+    METHOD_FINALIZE_PAYMENT,
+    METHOD_MIGRATE_STAKES,
+    METHOD_MIGRATE_RESERVED_KEYS,
+    BONDING_PURSE_KEY,
+    PAYMENT_PURSE_KEY,
+    REWARDS_PURSE_KEY,
+    REFUND_PURSE_KEY,
+];
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).expect("should read dir") {
+        let path = entry.expect("should read dir entry").path();
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                rust_files(&path, files);
+            }
+        } else if path
+            .extension()
+            .map_or(false, |extension| extension == "rs")
+        {
+            files.push(path);
+        }
+    }
+}
+
+#[test]
+fn should_not_spell_out_pos_names_outside_types() {
+    let mut files = Vec::new();
+    for dir in SOURCE_DIRS.iter() {
+        rust_files(Path::new(dir), &mut files);
+    }
+
+    let mut literals = Vec::new();
+    for file in files {
+        let source = fs::read_to_string(&file).expect("should read source file");
+        for (index, line) in source.lines().enumerate() {
+            if line.trim_start().starts_with("//") {
+                continue;
+            }
+            for name in NAMES.iter() {
+                if line.contains(&format!("\"{}\"", name)) {
+                    literals.push(format!("{}:{}: {}", file.display(), index + 1, name));
+                }
+            }
+        }
+    }
+
+    assert!(
+        literals.is_empty(),
+        "use the constants in types::system_contract_names instead of:\n{}",
+        literals.join("\n")
+    );
+}
@@ -0,0 +1,103 @@
+use std::convert::TryFrom;
+
+use engine_core::engine_state::EngineConfig;
+use engine_shared::newtypes::{Blake2bHash, CorrelationId};
+use engine_test_support::{
+    internal::{
+        ExecuteRequestBuilder, LmdbWasmTestBuilder, DEFAULT_PROTOCOL_VERSION,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{account::PublicKey, U512};
+
+const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
+
+/// Runs genesis, then returns a builder over the same database with a fresh engine state, as
+/// after restarting the server.
+fn restarted_builder(data_dir: &tempfile::TempDir) -> LmdbWasmTestBuilder {
+    let result = LmdbWasmTestBuilder::new(data_dir.path())
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .finish();
+    LmdbWasmTestBuilder::new_with_config_and_result(data_dir.path(), EngineConfig::new(), &result)
+}
+
+fn transfer(builder: &mut LmdbWasmTestBuilder) {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+        (ACCOUNT_1_ADDR, U512::from(1_000)),
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_load_system_contracts_in_first_exec_after_preload() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let mut builder = restarted_builder(&data_dir);
+
+    let state_hash = Blake2bHash::try_from(builder.get_post_state_hash().as_slice())
+        .expect("should convert post state hash");
+    let protocol_version = builder
+        .get_engine_state()
+        .preload_system_contracts(CorrelationId::new(), state_hash)
+        .expect("should preload system contracts")
+        .expect("should find state root");
+    assert_eq!(protocol_version, *DEFAULT_PROTOCOL_VERSION);
+
+    let insertions = builder
+        .get_engine_state()
+        .system_contract_cache()
+        .insertions();
+    assert_eq!(insertions, 2, "should load mint and proof of stake");
+
+    transfer(&mut builder);
+
+    assert_eq!(
+        builder
+            .get_engine_state()
+            .system_contract_cache()
+            .insertions(),
+        insertions
+    );
+}
+
+#[ignore]
+#[test]
+fn should_load_system_contracts_in_first_exec_without_preload() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let mut builder = restarted_builder(&data_dir);
+
+    transfer(&mut builder);
+
+    assert_eq!(
+        builder
+            .get_engine_state()
+            .system_contract_cache()
+            .insertions(),
+        2
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_preload_system_contracts_at_unknown_state_root() {
+    let data_dir = tempfile::tempdir().expect("should create temp dir");
+    let builder = restarted_builder(&data_dir);
+
+    let result = builder
+        .get_engine_state()
+        .preload_system_contracts(CorrelationId::new(), Blake2bHash::new(b"unknown root"))
+        .expect("should not fail");
+    assert!(result.is_none());
+    assert_eq!(
+        builder
+            .get_engine_state()
+            .system_contract_cache()
+            .insertions(),
+        0
+    );
+}
@@ -1,3 +1,5 @@
+use std::convert::TryFrom;
+
 use engine_core::engine_state::{
     genesis::{GenesisAccount, POS_BONDING_PURSE},
     CONV_RATE,
@@ -9,7 +11,7 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
 };
-use types::{account::PublicKey, ApiError, Key, URef, U512};
+use types::{account::PublicKey, ApiError, CLValue, Key, URef, U512};
 
 const CONTRACT_POS_BONDING: &str = "pos_bonding.wasm";
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
@@ -25,9 +27,12 @@ const GENESIS_ACCOUNT_UNBOND_2: u64 = 55_000;
 
 const TEST_BOND: &str = "bond";
 const TEST_BOND_FROM_MAIN_PURSE: &str = "bond-from-main-purse";
+const TEST_BOND_FROM_READ_ONLY_PURSE: &str = "bond-from-read-only-purse";
 const TEST_SEED_NEW_ACCOUNT: &str = "seed_new_account";
 const TEST_UNBOND: &str = "unbond";
 
+const BONDING_PURSE_KEY: &str = "bonding_purse";
+
 fn get_pos_purse_by_name(builder: &InMemoryWasmTestBuilder, purse_name: &str) -> Option<URef> {
     let pos_contract = builder.get_pos_contract();
 
@@ -38,6 +43,28 @@ fn get_pos_purse_by_name(builder: &InMemoryWasmTestBuilder, purse_name: &str) ->
         .cloned()
 }
 
+fn get_stake(builder: &InMemoryWasmTestBuilder, validator: PublicKey) -> Option<U512> {
+    let pos_contract = builder.get_pos_contract();
+    let stake_key_name = format!("v_{}", base16::encode_lower(&validator.as_bytes()));
+    let stake_key = pos_contract.named_keys().get(&stake_key_name)?;
+    let stake = builder
+        .query(None, *stake_key, &[])
+        .expect("should have stake");
+    let cl_value = CLValue::try_from(stake).expect("should be a CLValue");
+    Some(cl_value.into_t().expect("should be a U512"))
+}
+
+fn get_account_bonding_purse(builder: &InMemoryWasmTestBuilder, account: PublicKey) -> URef {
+    builder
+        .get_account(account)
+        .expect("should get account")
+        .named_keys()
+        .get(BONDING_PURSE_KEY)
+        .and_then(Key::as_uref)
+        .cloned()
+        .expect("should have bonding purse")
+}
+
 fn get_pos_bonding_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
     let purse =
         get_pos_purse_by_name(builder, POS_BONDING_PURSE).expect("should find PoS payment purse");
@@ -67,8 +94,6 @@ fn should_run_successful_bond_and_unbond() {
         .get_account(DEFAULT_ACCOUNT_ADDR)
         .expect("should get account 1");
 
-    let pos = builder.get_pos_contract_uref();
-
     let exec_request_1 = ExecuteRequestBuilder::standard(
         DEFAULT_ACCOUNT_ADDR,
         CONTRACT_POS_BONDING,
@@ -90,16 +115,10 @@ fn should_run_successful_bond_and_unbond() {
         .expect("should have exec response");
     let mut genesis_gas_cost = utils::get_exec_costs(exec_response)[0];
 
-    let contract = builder
-        .get_contract(pos.remove_access_rights())
-        .expect("should have contract");
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes()),
-        GENESIS_ACCOUNT_STAKE
+    assert_eq!(
+        get_stake(&builder, DEFAULT_ACCOUNT_ADDR),
+        Some(U512::from(GENESIS_ACCOUNT_STAKE))
     );
-    assert!(contract.named_keys().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
     // the test contract
@@ -149,19 +168,11 @@ fn should_run_successful_bond_and_unbond() {
         .get_account(ACCOUNT_1_ADDR)
         .expect("should get account 1");
 
-    let pos = builder.get_pos_contract_uref();
-
     // Verify that genesis account is in validator queue
-    let contract = builder
-        .get_contract(pos.remove_access_rights())
-        .expect("should have contract");
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_STAKE
+    assert_eq!(
+        get_stake(&builder, ACCOUNT_1_ADDR),
+        Some(U512::from(ACCOUNT_1_STAKE))
     );
-    assert!(contract.named_keys().contains_key(&lookup_key));
 
     // Gensis validator [42; 32] bonded 50k, and genesis account bonded 100k inside
     // the test contract
@@ -210,23 +221,11 @@ fn should_run_successful_bond_and_unbond() {
         U512::from(GENESIS_VALIDATOR_STAKE + GENESIS_ACCOUNT_STAKE + ACCOUNT_1_UNBOND_2)
     );
 
-    let pos_contract = builder.get_pos_contract();
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_STAKE
-    );
-    assert!(!pos_contract.named_keys().contains_key(&lookup_key));
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_UNBOND_2
+    // Account 1 is still tracked in the bonding queue with a decreased stake
+    assert_eq!(
+        get_stake(&builder, ACCOUNT_1_ADDR),
+        Some(U512::from(ACCOUNT_1_UNBOND_2))
     );
-    // Account 1 is still tracked anymore in the bonding queue with different uref
-    // name
-    assert!(pos_contract.named_keys().contains_key(&lookup_key));
 
     //
     // Stage 2b - Genesis unbonds by decreasing less than 50% (and is still in the
@@ -254,6 +253,8 @@ fn should_run_successful_bond_and_unbond() {
         .expect("should have exec response");
     genesis_gas_cost = genesis_gas_cost + utils::get_exec_costs(exec_response)[0];
 
+    // Unbonded funds are paid back to the purse the genesis account bonded from, not its main
+    // purse
     assert_eq!(
         builder.get_purse_balance(default_account.main_purse()),
         U512::from(
@@ -263,9 +264,14 @@ fn should_run_successful_bond_and_unbond() {
                     .value()
                     .as_u64()
                 - ACCOUNT_1_SEED_AMOUNT
-                - GENESIS_ACCOUNT_UNBOND_2
+                - GENESIS_ACCOUNT_STAKE
         ),
     );
+    let genesis_bonding_purse = get_account_bonding_purse(&builder, DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(
+        builder.get_purse_balance(genesis_bonding_purse),
+        U512::from(GENESIS_ACCOUNT_UNBOND_1)
+    );
 
     // POS bonding purse is further decreased
     assert_eq!(
@@ -314,15 +320,8 @@ fn should_run_successful_bond_and_unbond() {
         U512::from(GENESIS_VALIDATOR_STAKE + GENESIS_ACCOUNT_UNBOND_2)
     );
 
-    let pos_contract = builder.get_pos_contract();
-
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(ACCOUNT_1_ADDR.as_bytes()),
-        ACCOUNT_1_UNBOND_2
-    );
     // Account 1 isn't tracked anymore in the bonding queue
-    assert!(!pos_contract.named_keys().contains_key(&lookup_key));
+    assert_eq!(get_stake(&builder, ACCOUNT_1_ADDR), None);
 
     //
     // Stage 3b - Fully unbond account1 with Some(TOTAL_AMOUNT)
@@ -348,7 +347,8 @@ fn should_run_successful_bond_and_unbond() {
         .expect("should have exec response");
     genesis_gas_cost = genesis_gas_cost + utils::get_exec_costs(exec_response)[0];
 
-    // Back to original after funding account1's pursee
+    // The main purse is back to original after funding account1's purse, less the stake which was
+    // paid back to the bonding purse
     assert_eq!(
         result
             .builder()
@@ -360,8 +360,13 @@ fn should_run_successful_bond_and_unbond() {
                     .value()
                     .as_u64()
                 - ACCOUNT_1_SEED_AMOUNT
+                - GENESIS_ACCOUNT_STAKE
         )
     );
+    assert_eq!(
+        builder.get_purse_balance(genesis_bonding_purse),
+        U512::from(GENESIS_ACCOUNT_STAKE)
+    );
 
     // Final balance after two full unbonds is the initial bond valuee
     assert_eq!(
@@ -369,15 +374,10 @@ fn should_run_successful_bond_and_unbond() {
         U512::from(GENESIS_VALIDATOR_STAKE)
     );
 
+    // Genesis isn't tracked anymore in the bonding queue
+    assert_eq!(get_stake(&builder, DEFAULT_ACCOUNT_ADDR), None);
+
     let pos_contract = builder.get_pos_contract();
-    let lookup_key = format!(
-        "v_{}_{}",
-        base16::encode_lower(&DEFAULT_ACCOUNT_ADDR.as_bytes()),
-        GENESIS_ACCOUNT_UNBOND_2
-    );
-    // Genesis is still tracked anymore in the bonding queue with different uref
-    // name
-    assert!(!pos_contract.named_keys().contains_key(&lookup_key));
 
     //
     // Final checks on validator queue
@@ -478,6 +478,43 @@ fn should_fail_bonding_with_insufficient_funds() {
     }
 }
 
+#[ignore]
+#[test]
+fn should_fail_bonding_from_read_only_purse() {
+    let run_genesis_request = utils::create_run_genesis_request(DEFAULT_ACCOUNTS.clone());
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_POS_BONDING,
+        (
+            String::from(TEST_BOND_FROM_READ_ONLY_PURSE),
+            U512::from(GENESIS_ACCOUNT_STAKE),
+        ),
+    )
+    .build();
+
+    let result = InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .commit()
+        .finish();
+
+    let response = result
+        .builder()
+        .get_exec_response(0)
+        .expect("should have a response")
+        .to_owned();
+
+    let error_message = utils::get_error_message(response);
+
+    if !cfg!(feature = "enable-bonding") {
+        assert!(error_message.contains(&format!("{:?}", ApiError::Unhandled)));
+    } else {
+        // pos::Error::InsufficientBondingPurseAccessRights => 30
+        assert!(error_message.contains(&format!("{:?}", ApiError::ProofOfStake(30))));
+    }
+}
+
 #[ignore]
 #[test]
 fn should_fail_unbonding_validator_without_bonding_first() {
@@ -1,13 +1,24 @@
 use num_traits::Zero;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+
+use grpc::RequestOptions;
 
 use engine_core::engine_state::genesis::GenesisAccount;
-use engine_shared::motes::Motes;
+use engine_grpc_server::engine_server::{
+    ipc::GetValidatorWeightsRequest, ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{
+    additive_map::AdditiveMap, contract::Contract, motes::Motes, stored_value::StoredValue,
+    transform::Transform,
+};
 use engine_test_support::{
-    internal::{utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS},
+    internal::{
+        utils, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNTS,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::PublicKey, U512};
+use types::{account::PublicKey, Key, U512};
 
 const CONTRACT_LOCAL_STATE: &str = "local_state.wasm";
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
@@ -18,6 +29,36 @@ const ACCOUNT_2_ADDR: PublicKey = PublicKey::ed25519_from([2u8; 32]);
 const ACCOUNT_2_BALANCE: u64 = 2000;
 const ACCOUNT_2_BOND: u64 = 200;
 
+const ACCOUNT_3_ADDR: PublicKey = PublicKey::ed25519_from([3u8; 32]);
+const ACCOUNT_3_BALANCE: u64 = 2000;
+const ACCOUNT_3_BOND: u64 = 500;
+
+/// Returns the default accounts followed by the given bonded validators, in the given order.
+fn genesis_accounts(validators: &[(PublicKey, u64, u64)]) -> Vec<GenesisAccount> {
+    let mut accounts: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+    for (public_key, balance, bond) in validators {
+        accounts.push(GenesisAccount::new(
+            *public_key,
+            Motes::new((*balance).into()),
+            Motes::new((*bond).into()),
+        ));
+    }
+    accounts
+}
+
+fn committed_bonded_validators(accounts: Vec<GenesisAccount>) -> Vec<(PublicKey, U512)> {
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, CONTRACT_LOCAL_STATE, ()).build();
+
+    InMemoryWasmTestBuilder::default()
+        .run_genesis(&run_genesis_request)
+        .exec(exec_request)
+        .commit()
+        .get_bonded_validators()[0]
+        .clone()
+}
+
 #[ignore]
 #[test]
 fn should_return_bonded_validators() {
@@ -50,7 +91,7 @@ fn should_return_bonded_validators() {
         .get_bonded_validators()[0]
         .clone();
 
-    let expected: HashMap<PublicKey, U512> = {
+    let expected: BTreeMap<PublicKey, U512> = {
         let zero = Motes::zero();
         accounts
             .iter()
@@ -67,5 +108,157 @@ fn should_return_bonded_validators() {
             .collect()
     };
 
-    assert_eq!(actual, expected);
+    assert_eq!(actual, expected.into_iter().collect::<Vec<_>>());
+}
+
+#[ignore]
+#[test]
+fn should_return_bonded_validators_in_canonical_order() {
+    let validators = [
+        (ACCOUNT_3_ADDR, ACCOUNT_3_BALANCE, ACCOUNT_3_BOND),
+        (ACCOUNT_1_ADDR, ACCOUNT_1_BALANCE, ACCOUNT_1_BOND),
+        (ACCOUNT_2_ADDR, ACCOUNT_2_BALANCE, ACCOUNT_2_BOND),
+    ];
+    let mut reversed = validators;
+    reversed.reverse();
+
+    let actual = committed_bonded_validators(genesis_accounts(&validators));
+
+    assert_eq!(
+        actual,
+        vec![
+            (ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_BOND)),
+            (ACCOUNT_2_ADDR, U512::from(ACCOUNT_2_BOND)),
+            (ACCOUNT_3_ADDR, U512::from(ACCOUNT_3_BOND)),
+        ]
+    );
+    // the order doesn't depend on the order in which the validators were bonded
+    assert_eq!(
+        committed_bonded_validators(genesis_accounts(&reversed)),
+        actual
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_commit_with_duplicate_validator_entry() {
+    let accounts = genesis_accounts(&[(ACCOUNT_1_ADDR, ACCOUNT_1_BALANCE, ACCOUNT_1_BOND)]);
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    // a legacy entry alongside the current one, as left by an interrupted migration
+    let (bytes, mut named_keys, protocol_version) = builder.get_pos_contract().destructure();
+    let legacy_name = format!(
+        "v_{}_{}",
+        base16::encode_lower(&ACCOUNT_1_ADDR.as_bytes()),
+        ACCOUNT_1_BOND
+    );
+    named_keys.insert(legacy_name, Key::Hash([0; 32]));
+    let contract = Contract::new(bytes, named_keys, protocol_version);
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::URef(builder.get_pos_contract_uref()).normalize(),
+        Transform::Write(StoredValue::Contract(contract)),
+    );
+
+    let commit_response = builder.commit_transforms(builder.get_post_state_hash(), effects);
+
+    assert!(commit_response.has_failed_transform());
+    let message = commit_response.get_failed_transform().get_message();
+    assert!(
+        message.contains("DuplicateValidatorEntry"),
+        "unexpected failure: {}",
+        message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_fail_commit_with_missing_stake_entry() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // a current-format stake name whose stake was never written
+    let (bytes, mut named_keys, protocol_version) = builder.get_pos_contract().destructure();
+    let stake_name = format!("v_{}", base16::encode_lower(&ACCOUNT_1_ADDR.as_bytes()));
+    named_keys.insert(stake_name, Key::Hash([0; 32]));
+    let contract = Contract::new(bytes, named_keys, protocol_version);
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::URef(builder.get_pos_contract_uref()).normalize(),
+        Transform::Write(StoredValue::Contract(contract)),
+    );
+
+    let commit_response = builder.commit_transforms(builder.get_post_state_hash(), effects);
+
+    assert!(commit_response.has_failed_transform());
+    let message = commit_response.get_failed_transform().get_message();
+    assert!(
+        message.contains("InvalidValidatorEntry"),
+        "unexpected failure: {}",
+        message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_get_validator_weights_at_committed_roots() {
+    let accounts = genesis_accounts(&[(ACCOUNT_1_ADDR, ACCOUNT_1_BALANCE, ACCOUNT_1_BOND)]);
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+    let pre_state_hash = builder.get_post_state_hash();
+
+    // bond a second validator by committing a stake entry to the PoS contract directly
+    let (bytes, mut named_keys, protocol_version) = builder.get_pos_contract().destructure();
+    let stake_name = format!(
+        "v_{}_{}",
+        base16::encode_lower(&ACCOUNT_2_ADDR.as_bytes()),
+        ACCOUNT_2_BOND
+    );
+    named_keys.insert(stake_name, Key::Hash([0; 32]));
+    let contract = Contract::new(bytes, named_keys, protocol_version);
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::URef(builder.get_pos_contract_uref()).normalize(),
+        Transform::Write(StoredValue::Contract(contract)),
+    );
+    builder.commit_effects(pre_state_hash.clone(), effects);
+    let post_state_hash = builder.get_post_state_hash();
+
+    let pre_weights = builder.get_validator_weights_at(&pre_state_hash);
+    let post_weights = builder.get_validator_weights_at(&post_state_hash);
+
+    assert_eq!(
+        pre_weights,
+        vec![(ACCOUNT_1_ADDR, U512::from(ACCOUNT_1_BOND))]
+    );
+    let mut expected = pre_weights;
+    expected.push((ACCOUNT_2_ADDR, U512::from(ACCOUNT_2_BOND)));
+    assert_eq!(post_weights, expected);
+    // the same set the commit reported
+    assert_eq!(builder.get_bonded_validators().last(), Some(&post_weights));
+}
+
+#[ignore]
+#[test]
+fn should_not_get_validator_weights_at_missing_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let missing_state_hash = vec![1u8; 32];
+    let mut get_validator_weights_request = GetValidatorWeightsRequest::new();
+    get_validator_weights_request.set_state_hash(missing_state_hash.clone());
+    let get_validator_weights_response = builder
+        .get_engine_state()
+        .get_validator_weights(RequestOptions::new(), get_validator_weights_request)
+        .wait_drop_metadata()
+        .expect("should get validator weights response");
+
+    assert!(get_validator_weights_response.has_missing_state());
+    assert_eq!(
+        get_validator_weights_response
+            .get_missing_state()
+            .get_hash(),
+        missing_state_hash.as_slice()
+    );
 }
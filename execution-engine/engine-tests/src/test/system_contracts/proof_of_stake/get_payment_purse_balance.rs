@@ -0,0 +1,86 @@
+use engine_core::engine_state::CONV_RATE;
+use engine_shared::{motes::Motes, stored_value::StoredValue};
+use engine_test_support::{
+    internal::{
+        DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
+        DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+};
+use types::{system_contract_errors::pos, ApiError, Key, U512};
+
+const CONTRACT_TOP_UP_PAYMENT: &str = "top_up_payment.wasm";
+const CONTRACT_DO_NOTHING: &str = "do_nothing.wasm";
+const PAYMENT_PURSE_BALANCE_KEY: &str = "payment_purse_balance";
+
+#[ignore]
+#[test]
+fn should_top_up_payment_purse_to_exact_amount() {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code(CONTRACT_DO_NOTHING, ())
+        .with_payment_code(CONTRACT_TOP_UP_PAYMENT, (*DEFAULT_PAYMENT,))
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+    let exec_request = ExecuteRequestBuilder::new().push_deploy(deploy).build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .expect_success()
+        .commit();
+
+    let payment_purse_balance: U512 = match builder.query(
+        None,
+        Key::Account(DEFAULT_ACCOUNT_ADDR),
+        &[PAYMENT_PURSE_BALANCE_KEY],
+    ) {
+        Ok(StoredValue::CLValue(cl_value)) => cl_value.into_t().expect("should be U512"),
+        other => panic!("expected payment purse balance, got {:?}", other),
+    };
+    assert_eq!(payment_purse_balance, *DEFAULT_PAYMENT);
+
+    // the account was charged only for the cost of the deploy
+    let cost = builder
+        .exec_costs(0)
+        .into_iter()
+        .fold(Default::default(), |total, cost| total + cost);
+    let cost = Motes::from_gas(cost, CONV_RATE).expect("should convert cost to motes");
+    let account = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account");
+    assert_eq!(
+        builder.get_purse_balance(account.main_purse()),
+        U512::from(DEFAULT_ACCOUNT_INITIAL_BALANCE) - cost.value()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_not_get_payment_purse_balance_outside_payment() {
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TOP_UP_PAYMENT,
+        (*DEFAULT_PAYMENT,),
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder
+        .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
+        .exec(exec_request)
+        .commit();
+
+    let error_message = builder
+        .exec_error_message(0)
+        .expect("should have exec response");
+    let expected_error = ApiError::from(pos::Error::GetPaymentPurseBalanceCalledOutsidePayment);
+    assert!(
+        error_message.contains(&format!("{:?}", expected_error)),
+        "expected {:?}, got: {}",
+        expected_error,
+        error_message
+    );
+}
@@ -0,0 +1,88 @@
+use engine_core::engine_state::upgrade::ActivationPoint;
+use engine_grpc_server::engine_server::ipc::DeployCode;
+use engine_shared::{
+    additive_map::AdditiveMap, contract::Contract, stored_value::StoredValue, transform::Transform,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        UpgradeRequestBuilder, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
+use types::{system_contract_names::pos::REFUND_PURSE_KEY, Key, ProtocolVersion};
+
+const CONTRACT_POS_RESERVED_KEYS_UPGRADER: &str = "pos_reserved_keys_upgrader.wasm";
+const LEGACY_REFUND_PURSE_NAME: &str = "pos_refund_purse";
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+
+/// Leaves a refund purse under its legacy name in the PoS contract's named keys.
+fn write_legacy_refund_purse(builder: &mut InMemoryWasmTestBuilder) {
+    let (bytes, mut named_keys, protocol_version) = builder.get_pos_contract().destructure();
+    let purse = builder
+        .get_account(DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .main_purse();
+    named_keys.insert(LEGACY_REFUND_PURSE_NAME.to_string(), Key::URef(purse));
+    let contract = Contract::new(bytes, named_keys, protocol_version);
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::URef(builder.get_pos_contract_uref()).normalize(),
+        Transform::Write(StoredValue::Contract(contract)),
+    );
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, effects);
+}
+
+#[ignore]
+#[test]
+fn should_drop_legacy_refund_purse_when_migrating_reserved_keys() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    write_legacy_refund_purse(&mut builder);
+
+    let mut upgrade_request = {
+        let bytes = utils::read_wasm_file_bytes(CONTRACT_POS_RESERVED_KEYS_UPGRADER);
+        let mut installer_code = DeployCode::new();
+        installer_code.set_code(bytes);
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(PROTOCOL_VERSION)
+            .with_new_protocol_version(ProtocolVersion::from_parts(1, 1, 0))
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_installer_code(installer_code)
+            .build()
+    };
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+
+    let named_keys = builder.get_pos_contract().named_keys().clone();
+    assert!(!named_keys.contains_key(LEGACY_REFUND_PURSE_NAME));
+    assert!(!named_keys.contains_key(REFUND_PURSE_KEY));
+
+    // the upgraded PoS contract still sets and unsets the refund purse under its reserved name
+    let exec_request = {
+        let deploy = DeployItemBuilder::new()
+            .with_address(DEFAULT_ACCOUNT_ADDR)
+            .with_deploy_hash([2; 32])
+            .with_session_code("do_nothing.wasm", ())
+            .with_payment_code("pos_refund_purse.wasm", (*DEFAULT_PAYMENT,))
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+            .build();
+        ExecuteRequestBuilder::new()
+            .push_deploy(deploy)
+            .with_protocol_version(ProtocolVersion::from_parts(1, 1, 0))
+            .build()
+    };
+    builder.exec(exec_request).expect_success().commit();
+    assert!(!builder
+        .get_pos_contract()
+        .named_keys()
+        .contains_key(REFUND_PURSE_KEY));
+}
@@ -0,0 +1,234 @@
+use std::{collections::BTreeMap, convert::TryFrom};
+
+use engine_core::engine_state::{genesis::GenesisAccount, upgrade::ActivationPoint};
+use engine_grpc_server::engine_server::ipc::DeployCode;
+use engine_shared::{
+    additive_map::AdditiveMap, contract::Contract, motes::Motes, stored_value::StoredValue,
+    transform::Transform,
+};
+use engine_test_support::internal::{
+    utils, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNTS,
+};
+use types::{account::PublicKey, ArgsParser, CLValue, Key, ProtocolVersion, U512};
+
+const CONTRACT_POS_STAKES_UPGRADER: &str = "pos_stakes_upgrader.wasm";
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
+const VALIDATOR_1_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const VALIDATOR_1_BALANCE: u64 = 100_000;
+const VALIDATOR_1_STAKE: u64 = 50_000;
+const EXTRA_VALIDATOR_COUNT: u8 = 6;
+const MAX_COUNT: u32 = 2;
+
+fn stake_key_name(validator: &PublicKey) -> String {
+    format!("v_{}", base16::encode_lower(&validator.as_bytes()))
+}
+
+/// Returns the stakes which are stored under urefs, ignoring the ones in the legacy format.
+fn get_stakes(
+    builder: &InMemoryWasmTestBuilder,
+    validators: &[PublicKey],
+) -> BTreeMap<PublicKey, U512> {
+    let pos_contract = builder.get_pos_contract();
+    validators
+        .iter()
+        .filter_map(|validator| {
+            let stake_key = pos_contract.named_keys().get(&stake_key_name(validator))?;
+            let stake = builder
+                .query(None, *stake_key, &[])
+                .expect("should have stake");
+            let cl_value = CLValue::try_from(stake).expect("should be a CLValue");
+            Some((*validator, cl_value.into_t().expect("should be a U512")))
+        })
+        .collect()
+}
+
+/// Rewrites the PoS contract's named keys so that the stakes are encoded in the names, as they
+/// were before the stakes were moved into urefs.
+fn write_legacy_stakes(builder: &mut InMemoryWasmTestBuilder, stakes: &BTreeMap<PublicKey, U512>) {
+    let (bytes, mut named_keys, protocol_version) = builder.get_pos_contract().destructure();
+    named_keys.retain(|name, _| !name.starts_with("v_"));
+    for (validator, stake) in stakes {
+        let name = format!("{}_{}", stake_key_name(validator), stake);
+        named_keys.insert(name, Key::Hash([0; 32]));
+    }
+    let contract = Contract::new(bytes, named_keys, protocol_version);
+
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::URef(builder.get_pos_contract_uref()).normalize(),
+        Transform::Write(StoredValue::Contract(contract)),
+    );
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, effects);
+}
+
+/// Returns the number of stakes stored in the legacy format, i.e. with the stake appended to the
+/// name.
+fn get_legacy_stake_count(builder: &InMemoryWasmTestBuilder, validators: &[PublicKey]) -> usize {
+    let pos_contract = builder.get_pos_contract();
+    validators
+        .iter()
+        .filter(|validator| {
+            let prefix = format!("{}_", stake_key_name(validator));
+            pos_contract
+                .named_keys()
+                .keys()
+                .any(|name| name.starts_with(&prefix))
+        })
+        .count()
+}
+
+/// Commits no effects, and returns the bonded validators reported by the commit.
+fn commit_bonded_validators(builder: &mut InMemoryWasmTestBuilder) -> Vec<(PublicKey, U512)> {
+    let post_state_hash = builder.get_post_state_hash();
+    builder.commit_effects(post_state_hash, AdditiveMap::new());
+    builder
+        .get_bonded_validators()
+        .last()
+        .cloned()
+        .expect("should have bonded validators")
+}
+
+fn upgrade_with_stakes_upgrader(
+    builder: &mut InMemoryWasmTestBuilder,
+    current_protocol_version: ProtocolVersion,
+    new_protocol_version: ProtocolVersion,
+    maybe_max_count: Option<u32>,
+) {
+    let mut upgrade_request = {
+        let bytes = utils::read_wasm_file_bytes(CONTRACT_POS_STAKES_UPGRADER);
+        let mut installer_code = DeployCode::new();
+        installer_code.set_code(bytes);
+        if let Some(max_count) = maybe_max_count {
+            installer_code.set_args(
+                ArgsParser::parse((max_count,))
+                    .expect("should parse args")
+                    .into_bytes()
+                    .expect("should serialize args"),
+            );
+        }
+        UpgradeRequestBuilder::new()
+            .with_current_protocol_version(current_protocol_version)
+            .with_new_protocol_version(new_protocol_version)
+            .with_activation_point(DEFAULT_ACTIVATION_POINT)
+            .with_installer_code(installer_code)
+            .build()
+    };
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(builder.get_upgrade_responses_count() - 1)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+}
+
+#[ignore]
+#[test]
+fn should_migrate_legacy_stakes_into_urefs() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_1_ADDR,
+            Motes::new(VALIDATOR_1_BALANCE.into()),
+            Motes::new(VALIDATOR_1_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let validators: Vec<PublicKey> = accounts.iter().map(GenesisAccount::public_key).collect();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    let expected_stakes = get_stakes(&builder, &validators);
+    assert_eq!(
+        expected_stakes.get(&VALIDATOR_1_ADDR),
+        Some(&U512::from(VALIDATOR_1_STAKE))
+    );
+
+    write_legacy_stakes(&mut builder, &expected_stakes);
+    assert!(get_stakes(&builder, &validators).is_empty());
+    // Stakes in the legacy format are still reported as bonded validators.
+    let expected_bonded_validators: Vec<(PublicKey, U512)> =
+        expected_stakes.clone().into_iter().collect();
+    assert_eq!(
+        builder.get_bonded_validators().last(),
+        Some(&expected_bonded_validators)
+    );
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    upgrade_with_stakes_upgrader(&mut builder, PROTOCOL_VERSION, new_protocol_version, None);
+
+    assert_eq!(get_stakes(&builder, &validators), expected_stakes);
+    let stake_key_count = builder
+        .get_pos_contract()
+        .named_keys()
+        .keys()
+        .filter(|name| name.starts_with("v_"))
+        .count();
+    assert_eq!(stake_key_count, expected_stakes.len());
+}
+
+#[ignore]
+#[test]
+fn should_migrate_legacy_stakes_in_batches() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for index in 0..EXTRA_VALIDATOR_COUNT {
+            let account = GenesisAccount::new(
+                PublicKey::ed25519_from([100 + index; 32]),
+                Motes::new(VALIDATOR_1_BALANCE.into()),
+                Motes::new((VALIDATOR_1_STAKE + u64::from(index)).into()),
+            );
+            tmp.push(account);
+        }
+        tmp
+    };
+    let validators: Vec<PublicKey> = accounts.iter().map(GenesisAccount::public_key).collect();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    let expected_stakes = get_stakes(&builder, &validators);
+    let expected_bonded_validators = commit_bonded_validators(&mut builder);
+    assert_eq!(
+        expected_bonded_validators,
+        expected_stakes.clone().into_iter().collect::<Vec<_>>()
+    );
+
+    write_legacy_stakes(&mut builder, &expected_stakes);
+    let mut legacy_stake_count = get_legacy_stake_count(&builder, &validators);
+    assert_eq!(legacy_stake_count, expected_stakes.len());
+    assert!(legacy_stake_count > MAX_COUNT as usize);
+
+    let mut protocol_version = PROTOCOL_VERSION;
+    let mut upgrade_count = 0;
+    while legacy_stake_count > 0 {
+        let new_protocol_version = ProtocolVersion::from_parts(1, 1, upgrade_count);
+        upgrade_with_stakes_upgrader(
+            &mut builder,
+            protocol_version,
+            new_protocol_version,
+            Some(MAX_COUNT),
+        );
+        protocol_version = new_protocol_version;
+        upgrade_count += 1;
+
+        // Each upgrade moves a bounded batch, and the stakes read the same in the meantime.
+        let new_legacy_stake_count = get_legacy_stake_count(&builder, &validators);
+        assert_eq!(
+            new_legacy_stake_count,
+            legacy_stake_count.saturating_sub(MAX_COUNT as usize)
+        );
+        legacy_stake_count = new_legacy_stake_count;
+        assert_eq!(
+            commit_bonded_validators(&mut builder),
+            expected_bonded_validators
+        );
+    }
+
+    let expected_upgrade_count = (expected_stakes.len() as u32 + MAX_COUNT - 1) / MAX_COUNT;
+    assert_eq!(upgrade_count, expected_upgrade_count);
+    assert_eq!(get_stakes(&builder, &validators), expected_stakes);
+}
@@ -2,4 +2,7 @@ mod bonding;
 mod commit_validators;
 mod finalize_payment;
 mod get_payment_purse;
+mod get_payment_purse_balance;
+mod migrate_reserved_keys;
+mod migrate_stakes;
 mod refund_purse;
@@ -1,3 +1,4 @@
+use engine_core::engine_state::execute_request::ExecuteRequest;
 use engine_test_support::{
     internal::{
         DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
@@ -5,9 +6,10 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::PublicKey, U512};
+use types::{account::PublicKey, system_contract_errors::pos, AccessRights, ApiError, U512};
 
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
+const CONTRACT_POS_REFUND_PURSE_ACCESS_RIGHTS: &str = "pos_refund_purse_access_rights.wasm";
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
 
 #[ignore]
@@ -25,6 +27,64 @@ fn should_run_pos_refund_purse_contract_account_1() {
     refund_tests(&mut builder, ACCOUNT_1_ADDR);
 }
 
+#[ignore]
+#[test]
+fn should_set_refund_purse_with_add_rights() {
+    for access_rights in &[
+        AccessRights::ADD,
+        AccessRights::READ_ADD,
+        AccessRights::READ_ADD_WRITE,
+    ] {
+        let mut builder = initialize();
+        builder
+            .exec(refund_purse_access_rights_request(*access_rights))
+            .expect_success()
+            .commit();
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_set_refund_purse_without_add_rights() {
+    for access_rights in &[
+        AccessRights::READ,
+        AccessRights::WRITE,
+        AccessRights::READ_WRITE,
+    ] {
+        let mut builder = initialize();
+        builder
+            .exec(refund_purse_access_rights_request(*access_rights))
+            .commit();
+
+        let error_message = builder
+            .exec_error_message(0)
+            .expect("should have exec response");
+        let expected_error = ApiError::from(pos::Error::InsufficientRefundPurseAccessRights);
+        assert!(
+            error_message.contains(&format!("{:?}", expected_error)),
+            "expected {:?} with {:?}, got: {}",
+            expected_error,
+            access_rights,
+            error_message
+        );
+    }
+}
+
+fn refund_purse_access_rights_request(access_rights: AccessRights) -> ExecuteRequest {
+    let deploy = DeployItemBuilder::new()
+        .with_address(DEFAULT_ACCOUNT_ADDR)
+        .with_deploy_hash([1; 32])
+        .with_session_code("do_nothing.wasm", ())
+        .with_payment_code(
+            CONTRACT_POS_REFUND_PURSE_ACCESS_RIGHTS,
+            (*DEFAULT_PAYMENT, access_rights.bits()),
+        )
+        .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+        .build();
+
+    ExecuteRequestBuilder::new().push_deploy(deploy).build()
+}
+
 fn initialize() -> InMemoryWasmTestBuilder {
     let mut builder = InMemoryWasmTestBuilder::default();
 
@@ -1,18 +1,13 @@
-use assert_matches::assert_matches;
-
-use engine_core::{
-    engine_state::{genesis::POS_REWARDS_PURSE, Error, CONV_RATE, MAX_PAYMENT},
-    execution,
+use engine_core::engine_state::{
+    exit_code::ExitCode, genesis::POS_REWARDS_PURSE, CONV_RATE, MAX_PAYMENT,
 };
 use engine_shared::{motes::Motes, transform::Transform};
 use engine_test_support::{
-    internal::{
-        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
-        DEFAULT_ACCOUNT_KEY, DEFAULT_RUN_GENESIS_REQUEST,
-    },
-    DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+    get_error_message, get_success_result, DeployItemBuilder, ExecuteRequestBuilder,
+    InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_INITIAL_BALANCE,
+    DEFAULT_RUN_GENESIS_REQUEST,
 };
-use types::{account::PublicKey, ApiError, Key, URef, U512};
+use types::{account::PublicKey, Key, URef, U512};
 
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([42u8; 32]);
 const DO_NOTHING_WASM: &str = "do_nothing.wasm";
@@ -52,7 +47,7 @@ fn should_raise_insufficient_payment_when_caller_lacks_minimum_balance() {
         .get_exec_response(1)
         .expect("there should be a response");
 
-    let error_message = utils::get_error_message(account_1_response);
+    let error_message = get_error_message(account_1_response);
 
     assert!(
         error_message.contains("InsufficientPayment"),
@@ -86,7 +81,7 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
                 (account_1_public_key, U512::from(1)),
             )
             .with_empty_payment_bytes((U512::from(1),))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -131,7 +126,7 @@ fn should_raise_insufficient_payment_when_payment_code_does_not_pay_enough() {
         .get_exec_response(0)
         .expect("there should be a response");
 
-    let execution_result = utils::get_success_result(response);
+    let execution_result = get_success_result(response);
     let error_message = format!(
         "{}",
         execution_result.as_error().expect("should have error")
@@ -160,7 +155,7 @@ fn should_raise_insufficient_payment_error_when_out_of_gas() {
                 TRANSFER_PURSE_TO_ACCOUNT_WASM,
                 (account_1_public_key, transferred_amount),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -206,7 +201,7 @@ fn should_raise_insufficient_payment_error_when_out_of_gas() {
         .get_exec_response(0)
         .expect("there should be a response");
 
-    let execution_result = utils::get_success_result(response);
+    let execution_result = get_success_result(response);
     let error_message = format!(
         "{}",
         execution_result.as_error().expect("should have error")
@@ -233,7 +228,7 @@ fn should_forward_payment_execution_runtime_error() {
                 TRANSFER_PURSE_TO_ACCOUNT_WASM,
                 (account_1_public_key, transferred_amount),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -279,12 +274,8 @@ fn should_forward_payment_execution_runtime_error() {
         .get_exec_response(0)
         .expect("there should be a response");
 
-    let execution_result = utils::get_success_result(response);
-    let error = execution_result.as_error().expect("should have error");
-    assert_matches!(
-        error,
-        Error::Exec(execution::Error::Revert(ApiError::User(100)))
-    );
+    let execution_result = get_success_result(response);
+    assert_eq!(execution_result.exit_code(), ExitCode::UserRevert(100));
 }
 
 #[ignore]
@@ -302,7 +293,7 @@ fn should_forward_payment_execution_gas_limit_error() {
                 TRANSFER_PURSE_TO_ACCOUNT_WASM,
                 (account_1_public_key, transferred_amount),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -348,9 +339,8 @@ fn should_forward_payment_execution_gas_limit_error() {
         .get_exec_response(0)
         .expect("there should be a response");
 
-    let execution_result = utils::get_success_result(response);
-    let error = execution_result.as_error().expect("should have error");
-    assert_matches!(error, Error::Exec(execution::Error::GasLimit));
+    let execution_result = get_success_result(response);
+    assert_eq!(execution_result.exit_code(), ExitCode::OutOfGas);
 }
 
 #[ignore]
@@ -369,7 +359,7 @@ fn should_run_out_of_gas_when_session_code_exceeds_gas_limit() {
                 ENDLESS_LOOP_WASM,
                 (account_1_public_key, U512::from(transferred_amount)),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -388,9 +378,8 @@ fn should_run_out_of_gas_when_session_code_exceeds_gas_limit() {
         .get_exec_response(0)
         .expect("there should be a response");
 
-    let execution_result = utils::get_success_result(response);
-    let error = execution_result.as_error().expect("should have error");
-    assert_matches!(error, Error::Exec(execution::Error::GasLimit));
+    let execution_result = get_success_result(response);
+    assert_eq!(execution_result.exit_code(), ExitCode::OutOfGas);
 }
 
 #[ignore]
@@ -404,7 +393,7 @@ fn should_correctly_charge_when_session_code_runs_out_of_gas() {
             .with_deploy_hash([1; 32])
             .with_empty_payment_bytes((U512::from(payment_purse_amount),))
             .with_session_code(ENDLESS_LOOP_WASM, ())
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -433,7 +422,7 @@ fn should_correctly_charge_when_session_code_runs_out_of_gas() {
         .get_exec_response(0)
         .expect("there should be a response");
 
-    let success_result = utils::get_success_result(&response);
+    let success_result = get_success_result(&response);
     let gas = success_result.cost();
     let motes = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
 
@@ -444,9 +433,8 @@ fn should_correctly_charge_when_session_code_runs_out_of_gas() {
         "no net resources should be gained or lost post-distribution"
     );
 
-    let execution_result = utils::get_success_result(response);
-    let error = execution_result.as_error().expect("should have error");
-    assert_matches!(error, Error::Exec(execution::Error::GasLimit));
+    let execution_result = get_success_result(response);
+    assert_eq!(execution_result.exit_code(), ExitCode::OutOfGas);
 }
 
 #[ignore]
@@ -465,7 +453,7 @@ fn should_correctly_charge_when_session_code_fails() {
                 REVERT_WASM,
                 (account_1_public_key, U512::from(transferred_amount)),
             )
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -495,7 +483,7 @@ fn should_correctly_charge_when_session_code_fails() {
         .expect("there should be a response")
         .clone();
 
-    let success_result = utils::get_success_result(&response);
+    let success_result = get_success_result(&response);
     let gas = success_result.cost();
     let motes = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
     let tally = motes.value() + modified_balance;
@@ -522,7 +510,7 @@ fn should_correctly_charge_when_session_code_succeeds() {
                 (account_1_public_key, U512::from(transferred_amount)),
             )
             .with_empty_payment_bytes((U512::from(payment_purse_amount),))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .build();
 
         ExecuteRequestBuilder::new().push_deploy(deploy).build()
@@ -553,7 +541,7 @@ fn should_correctly_charge_when_session_code_succeeds() {
         .expect("there should be a response")
         .clone();
 
-    let success_result = utils::get_success_result(&response);
+    let success_result = get_success_result(&response);
     let gas = success_result.cost();
     let motes = Motes::from_gas(gas, CONV_RATE).expect("should have motes");
     let total = motes.value() + U512::from(transferred_amount);
@@ -600,7 +588,7 @@ fn should_finalize_to_rewards_purse() {
                 (account_1_public_key, U512::from(transferred_amount)),
             )
             .with_empty_payment_bytes((U512::from(payment_purse_amount),))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1; 32])
             .build();
 
@@ -637,7 +625,7 @@ fn independent_standard_payments_should_not_write_the_same_keys() {
                 (account_1_public_key, U512::from(transfer_amount)),
             )
             .with_empty_payment_bytes((U512::from(payment_purse_amount),))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([1; 32])
             .build();
 
@@ -656,7 +644,7 @@ fn independent_standard_payments_should_not_write_the_same_keys() {
             .with_address(DEFAULT_ACCOUNT_ADDR)
             .with_session_code(DO_NOTHING_WASM, ())
             .with_empty_payment_bytes((U512::from(payment_purse_amount),))
-            .with_authorization_keys(&[DEFAULT_ACCOUNT_KEY])
+            .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
             .with_deploy_hash([2; 32])
             .build();
 
@@ -27,7 +27,6 @@ fn should_run_standard_payment_install_contract() {
         DEFAULT_BLOCK_TIME,
         DEPLOY_HASH_1,
         (),
-        vec![],
     )
     .expect("should run successfully");
 
@@ -2,7 +2,7 @@ use assert_matches::assert_matches;
 use lazy_static::lazy_static;
 
 use engine_core::{engine_state::Error, execution};
-use engine_shared::TypeMismatch;
+use engine_shared::stored_value::StoredValue;
 use engine_test_support::{
     internal::{
         ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_PAYMENT,
@@ -10,18 +10,17 @@ use engine_test_support::{
     },
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::{account::PublicKey, URef, U512};
+use types::{account::PublicKey, Key, URef, U512};
 
 const CONTRACT_SYSTEM_CONTRACTS_ACCESS: &str = "system_contracts_access.wasm";
 const CONTRACT_OVERWRITE_UREF_CONTENT: &str = "overwrite_uref_content.wasm";
 const CONTRACT_TRANSFER_TO_ACCOUNT: &str = "transfer_to_account_u512.wasm";
+const REPLACEMENT_DATA: &str = "bawitdaba";
 
-const SYSTEM_ADDR: PublicKey = PublicKey::ed25519_from([0u8; 32]);
 const ACCOUNT_1_ADDR: PublicKey = PublicKey::ed25519_from([1u8; 32]);
 
 lazy_static! {
     static ref ACCOUNT_1_INITIAL_BALANCE: U512 = *DEFAULT_PAYMENT * 10;
-    static ref SYSTEM_INITIAL_BALANCE: U512 = *DEFAULT_PAYMENT * 10;
 }
 
 fn run_test_with_address(builder: &mut InMemoryWasmTestBuilder, address: PublicKey) {
@@ -106,59 +105,29 @@ fn should_not_overwrite_system_contract_uref_as_user() {
 #[ignore]
 #[test]
 fn should_overwrite_system_contract_uref_as_system() {
-    let exec_request_1 = ExecuteRequestBuilder::standard(
-        DEFAULT_ACCOUNT_ADDR,
-        CONTRACT_TRANSFER_TO_ACCOUNT,
-        (SYSTEM_ADDR, *SYSTEM_INITIAL_BALANCE),
-    )
-    .build();
-
     let result = InMemoryWasmTestBuilder::default()
         .run_genesis(&DEFAULT_RUN_GENESIS_REQUEST)
-        .exec(exec_request_1)
-        .expect_success()
-        .commit()
         .finish();
 
     let mint_uref = result.builder().get_mint_contract_uref();
     let pos_uref = result.builder().get_pos_contract_uref();
 
-    let exec_request_2 =
-        ExecuteRequestBuilder::standard(SYSTEM_ADDR, CONTRACT_OVERWRITE_UREF_CONTENT, (mint_uref,))
-            .build();
-
-    let mut new_builder = InMemoryWasmTestBuilder::from_result(result);
-
-    let result_mint = new_builder.clone().exec(exec_request_2).commit().finish();
-
-    let error_msg = result_mint
-        .builder()
-        .exec_error_message(0)
-        .expect("should execute mint overwrite with error");
-    assert!(
-        error_msg.contains("Finalization"),
-        "Expected Error::Finalization, got {}",
-        error_msg
-    );
-
-    let exec_request_3 =
-        ExecuteRequestBuilder::standard(SYSTEM_ADDR, CONTRACT_OVERWRITE_UREF_CONTENT, (pos_uref,))
-            .build();
-
-    let result_pos = new_builder.exec(exec_request_3).commit().finish();
-
-    let error = result_pos
-        .builder()
-        .get_exec_response(0)
-        .expect("should execute pos overwrite with error")
-        .last()
-        .expect("should have last execution")
-        .as_error()
-        .expect("should be error");
-
-    let type_mismatch = assert_matches!(error, Error::Exec(execution::Error::TypeMismatch(type_mismatch)) => type_mismatch);
-    assert_eq!(
-        type_mismatch,
-        &TypeMismatch::new("Contract".to_string(), "String".to_string())
-    );
+    let builder = InMemoryWasmTestBuilder::from_result(result);
+
+    // System deploys are trusted, so nothing stops them from replacing a system contract
+    for uref in &[mint_uref, pos_uref] {
+        let exec_request =
+            ExecuteRequestBuilder::system(CONTRACT_OVERWRITE_UREF_CONTENT, (*uref,)).build();
+        let mut builder = builder.clone();
+        builder.exec(exec_request).expect_success().commit();
+
+        let value = builder
+            .query(None, Key::URef(*uref), &[])
+            .expect("should query overwritten uref");
+        let cl_value = assert_matches!(value, StoredValue::CLValue(cl_value) => cl_value);
+        assert_eq!(
+            cl_value.into_t::<String>().expect("should be a string"),
+            REPLACEMENT_DATA
+        );
+    }
 }
@@ -1,26 +1,62 @@
-use engine_core::engine_state::{upgrade::ActivationPoint, Error};
-use engine_grpc_server::engine_server::ipc::DeployCode;
-#[cfg(feature = "use-system-contracts")]
-use engine_shared::{stored_value::StoredValue, transform::Transform};
-use engine_test_support::internal::{
-    utils, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_RUN_GENESIS_REQUEST,
-    DEFAULT_WASM_COSTS,
+use std::{collections::BTreeMap, convert::TryInto};
+
+use assert_matches::assert_matches;
+use grpc::RequestOptions;
+
+use engine_core::engine_state::{genesis::GenesisAccount, upgrade::ActivationPoint, Error};
+use engine_grpc_server::engine_server::{
+    ipc::{ConfigDiff_Change, DeployCode, PreviewUpgradeRequest, UpgradeRequest},
+    ipc_grpc::ExecutionEngineService,
 };
 #[cfg(feature = "use-system-contracts")]
-use engine_test_support::{internal::ExecuteRequestBuilder, DEFAULT_ACCOUNT_ADDR};
+use engine_shared::transform::Transform;
+use engine_shared::{
+    additive_map::AdditiveMap,
+    motes::Motes,
+    newtypes::{Blake2bHash, CorrelationId},
+    stored_value::StoredValue,
+};
+use engine_test_support::{
+    internal::{
+        utils, DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+        UpgradeRequestBuilder, DEFAULT_ACCOUNTS, DEFAULT_PAYMENT, DEFAULT_RUN_GENESIS_REQUEST,
+        DEFAULT_WASM_COSTS,
+    },
+    DEFAULT_ACCOUNT_ADDR,
+};
 use engine_wasm_prep::wasm_costs::WasmCosts;
-use types::ProtocolVersion;
+use types::{account::PublicKey, ProtocolVersion};
 #[cfg(feature = "use-system-contracts")]
 use types::{CLValue, Key, U512};
 
 const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
 const DEFAULT_ACTIVATION_POINT: ActivationPoint = 1;
 const MODIFIED_SYSTEM_UPGRADER_CONTRACT_NAME: &str = "modified_system_upgrader.wasm";
+const MODIFIED_POS_INSTALL_CONTRACT_NAME: &str = "modified_pos_install.wasm";
+const DO_NOTHING_CONTRACT_NAME: &str = "do_nothing.wasm";
+const POS_VERSION_KEY_NAME: &str = "version";
+const VALIDATOR_1_ADDR: PublicKey = PublicKey::ed25519_from([42; 32]);
+const VALIDATOR_1_BALANCE: u64 = 100_000;
+const VALIDATOR_1_STAKE: u64 = 50_000;
+#[cfg(feature = "use-system-contracts")]
+const MODIFIED_POS_CALLER_CONTRACT_NAME: &str = "modified_pos_caller.wasm";
+#[cfg(feature = "use-system-contracts")]
+const POS_GET_PAYMENT_PURSE_CONTRACT_NAME: &str = "pos_get_payment_purse.wasm";
+#[cfg(feature = "use-system-contracts")]
+const POS_VERSION_OUTPUT_KEY_NAME: &str = "output_version";
 #[cfg(feature = "use-system-contracts")]
 const MODIFIED_MINT_CALLER_CONTRACT_NAME: &str = "modified_mint_caller.wasm";
 #[cfg(feature = "use-system-contracts")]
 const PAYMENT_AMOUNT: u64 = 200_000_000;
 
+fn post_state_hash(builder: &InMemoryWasmTestBuilder) -> Blake2bHash {
+    builder
+        .get_post_state_hash()
+        .as_slice()
+        .try_into()
+        .expect("should be a valid hash")
+}
+
 fn get_upgraded_wasm_costs() -> WasmCosts {
     WasmCosts {
         regular: 1,
@@ -33,6 +69,17 @@ fn get_upgraded_wasm_costs() -> WasmCosts {
         max_stack_height: 64 * 1024,
         opcodes_mul: 3,
         opcodes_div: 8,
+        transient_storage: 1,
+        max_transient_storage_size: 1024 * 1024,
+        max_transforms_per_deploy: 0,
+        max_effect_bytes_per_deploy: 0,
+        max_session_return_value_bytes: 0,
+        string_byte: 1,
+        max_string_length: 1024,
+        max_revert_data_bytes: 256,
+        base_deploy_cost: 1_000,
+        rejected_deploy_cost: 100,
+        strict_session_exports: false,
     }
 }
 
@@ -65,7 +112,11 @@ fn should_upgrade_only_protocol_version() {
 
     let upgraded_wasm_costs = builder
         .get_engine_state()
-        .wasm_costs(new_protocol_version)
+        .wasm_costs(
+            CorrelationId::new(),
+            post_state_hash(&builder),
+            new_protocol_version,
+        )
         .expect("should have result")
         .expect("should have costs");
 
@@ -75,6 +126,118 @@ fn should_upgrade_only_protocol_version() {
     );
 }
 
+/// Upgrades the protocol version only, returning the root preceding the upgrade and the new
+/// version.
+fn upgrade_protocol_version(
+    builder: &mut InMemoryWasmTestBuilder,
+) -> (Blake2bHash, ProtocolVersion) {
+    let pre_upgrade_hash = post_state_hash(builder);
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .build();
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+    assert!(
+        builder
+            .get_upgrade_response(0)
+            .expect("should have response")
+            .has_success(),
+        "expected success"
+    );
+
+    (pre_upgrade_hash, new_protocol_version)
+}
+
+#[ignore]
+#[test]
+fn should_not_resolve_costs_of_new_version_at_pre_upgrade_root() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let (pre_upgrade_hash, new_protocol_version) = upgrade_protocol_version(&mut builder);
+
+    let engine_state = builder.get_engine_state();
+    let result =
+        engine_state.wasm_costs(CorrelationId::new(), pre_upgrade_hash, new_protocol_version);
+    assert_matches!(
+        result,
+        Err(Error::ConfigNotFound { protocol_version, state_hash })
+            if protocol_version == new_protocol_version && state_hash == pre_upgrade_hash
+    );
+
+    let old_wasm_costs = engine_state
+        .wasm_costs(CorrelationId::new(), pre_upgrade_hash, PROTOCOL_VERSION)
+        .expect("should have result")
+        .expect("should have costs");
+    assert_eq!(old_wasm_costs, *DEFAULT_WASM_COSTS);
+}
+
+#[ignore]
+#[test]
+fn should_fail_deploys_against_pre_upgrade_root_with_new_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let (pre_upgrade_hash, new_protocol_version) = upgrade_protocol_version(&mut builder);
+
+    let exec_request = {
+        let mut exec_request_builder = ExecuteRequestBuilder::new()
+            .with_pre_state_hash(&pre_upgrade_hash.to_vec())
+            .with_protocol_version(new_protocol_version);
+        for deploy_hash in &[[1; 32], [2; 32]] {
+            let deploy = DeployItemBuilder::new()
+                .with_address(DEFAULT_ACCOUNT_ADDR)
+                .with_session_code(DO_NOTHING_CONTRACT_NAME, ())
+                .with_empty_payment_bytes((*DEFAULT_PAYMENT,))
+                .with_authorization_keys(&[DEFAULT_ACCOUNT_ADDR])
+                .with_deploy_hash(*deploy_hash)
+                .build();
+            exec_request_builder = exec_request_builder.push_deploy(deploy);
+        }
+        exec_request_builder.build()
+    };
+
+    let results = builder
+        .get_engine_state()
+        .run_execute(CorrelationId::new(), exec_request)
+        .expect("should find pre-upgrade root");
+
+    assert_eq!(results.len(), 2);
+    for result in results {
+        assert!(result.has_precondition_failure());
+        assert_matches!(
+            result.take_error(),
+            Some(Error::ConfigNotFound { protocol_version, state_hash })
+                if protocol_version == new_protocol_version && state_hash == pre_upgrade_hash
+        );
+    }
+}
+
+#[ignore]
+#[test]
+fn should_not_commit_against_pre_upgrade_root_with_new_version() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let (pre_upgrade_hash, new_protocol_version) = upgrade_protocol_version(&mut builder);
+
+    let result = builder.get_engine_state().apply_effect(
+        CorrelationId::new(),
+        new_protocol_version,
+        pre_upgrade_hash,
+        AdditiveMap::new(),
+        None,
+    );
+
+    assert_matches!(
+        result,
+        Err(Error::ConfigNotFound { protocol_version, state_hash })
+            if protocol_version == new_protocol_version && state_hash == pre_upgrade_hash
+    );
+}
+
 #[cfg(feature = "use-system-contracts")]
 #[ignore]
 #[test]
@@ -341,7 +504,11 @@ fn should_allow_only_wasm_costs_patch_version() {
 
     let upgraded_wasm_costs = builder
         .get_engine_state()
-        .wasm_costs(new_protocol_version)
+        .wasm_costs(
+            CorrelationId::new(),
+            post_state_hash(&builder),
+            new_protocol_version,
+        )
         .expect("should have result")
         .expect("should have upgraded costs");
 
@@ -387,7 +554,11 @@ fn should_allow_only_wasm_costs_minor_version() {
 
     let upgraded_wasm_costs = builder
         .get_engine_state()
-        .wasm_costs(new_protocol_version)
+        .wasm_costs(
+            CorrelationId::new(),
+            post_state_hash(&builder),
+            new_protocol_version,
+        )
         .expect("should have result")
         .expect("should have upgraded costs");
 
@@ -397,6 +568,88 @@ fn should_allow_only_wasm_costs_minor_version() {
     );
 }
 
+fn regular_cost_upgrade_request(new_protocol_version: ProtocolVersion) -> UpgradeRequest {
+    let new_costs = WasmCosts {
+        regular: DEFAULT_WASM_COSTS.regular + 1,
+        ..*DEFAULT_WASM_COSTS
+    };
+    UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_costs(new_costs)
+        .build()
+}
+
+fn assert_only_regular_cost_changed(changes: &[ConfigDiff_Change]) {
+    assert_eq!(changes.len(), 1, "expected a single change: {:?}", changes);
+    assert_eq!(changes[0].get_name(), "wasm_costs.regular");
+    assert_eq!(
+        changes[0].get_old_value(),
+        DEFAULT_WASM_COSTS.regular.to_string()
+    );
+    assert_eq!(
+        changes[0].get_new_value(),
+        (DEFAULT_WASM_COSTS.regular + 1).to_string()
+    );
+}
+
+#[ignore]
+#[test]
+fn should_report_changed_parameters_of_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+    let mut upgrade_request = regular_cost_upgrade_request(new_protocol_version);
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(upgrade_response.has_success(), "expected success");
+    assert_only_regular_cost_changed(upgrade_response.get_success().get_changes().get_changes());
+}
+
+#[ignore]
+#[test]
+fn should_preview_changed_parameters_without_committing() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let pre_upgrade_hash = post_state_hash(&builder);
+
+    let sem_ver = PROTOCOL_VERSION.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+    let mut upgrade_request = regular_cost_upgrade_request(new_protocol_version);
+    upgrade_request.set_parent_state_hash(pre_upgrade_hash.to_vec());
+    let mut preview_upgrade_request = PreviewUpgradeRequest::new();
+    preview_upgrade_request.set_upgrade(upgrade_request);
+
+    let preview_upgrade_response = builder
+        .get_engine_state()
+        .preview_upgrade(RequestOptions::new(), preview_upgrade_request)
+        .wait_drop_metadata()
+        .expect("should preview upgrade");
+    assert!(
+        preview_upgrade_response.has_changes(),
+        "expected changes but received {:?}",
+        preview_upgrade_response
+    );
+    assert_only_regular_cost_changed(preview_upgrade_response.get_changes().get_changes());
+
+    // nothing was committed: the new protocol version isn't known at the pre-upgrade root
+    assert_eq!(post_state_hash(&builder), pre_upgrade_hash);
+    let result = builder.get_engine_state().wasm_costs(
+        CorrelationId::new(),
+        pre_upgrade_hash,
+        new_protocol_version,
+    );
+    assert_matches!(result, Err(Error::ConfigNotFound { .. }));
+}
+
 #[cfg(feature = "use-system-contracts")]
 #[ignore]
 #[test]
@@ -474,7 +727,11 @@ fn should_upgrade_system_contract_and_wasm_costs_major() {
 
     let upgraded_wasm_costs = builder
         .get_engine_state()
-        .wasm_costs(new_protocol_version)
+        .wasm_costs(
+            CorrelationId::new(),
+            post_state_hash(&builder),
+            new_protocol_version,
+        )
         .expect("should have result")
         .expect("should have upgraded costs");
 
@@ -515,7 +772,11 @@ fn should_not_downgrade() {
 
     let upgraded_wasm_costs = builder
         .get_engine_state()
-        .wasm_costs(new_protocol_version)
+        .wasm_costs(
+            CorrelationId::new(),
+            post_state_hash(&builder),
+            new_protocol_version,
+        )
         .expect("should have result")
         .expect("should have costs");
 
@@ -636,3 +897,159 @@ fn should_fail_major_upgrade_without_installer() {
         Error::InvalidUpgradeConfig.to_string()
     );
 }
+
+/// Returns the values stored under the PoS contract's stake keys.
+fn get_stakes(builder: &InMemoryWasmTestBuilder) -> BTreeMap<String, StoredValue> {
+    builder
+        .get_pos_contract()
+        .named_keys()
+        .iter()
+        .filter(|(name, _)| name.starts_with("v_"))
+        .map(|(name, key)| {
+            let stake = builder.query(None, *key, &[]).expect("should have stake");
+            (name.clone(), stake)
+        })
+        .collect()
+}
+
+fn upgrade_pos_code(builder: &mut InMemoryWasmTestBuilder, new_protocol_version: ProtocolVersion) {
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_pos_code(utils::read_wasm_file_bytes(
+            MODIFIED_POS_INSTALL_CONTRACT_NAME,
+        ))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(
+        upgrade_response.has_success(),
+        "upgrade_response expected success"
+    );
+}
+
+#[ignore]
+#[test]
+fn should_upgrade_pos_code_in_place() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        let account = GenesisAccount::new(
+            VALIDATOR_1_ADDR,
+            Motes::new(VALIDATOR_1_BALANCE.into()),
+            Motes::new(VALIDATOR_1_STAKE.into()),
+        );
+        tmp.push(account);
+        tmp
+    };
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&utils::create_run_genesis_request(accounts));
+
+    let pos_uref = builder.get_pos_contract_uref();
+    let old_named_keys = builder.get_pos_contract().named_keys().clone();
+    let old_stakes = get_stakes(&builder);
+    assert!(!old_stakes.is_empty());
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    upgrade_pos_code(&mut builder, new_protocol_version);
+
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should read protocol data")
+        .expect("should have protocol data");
+    assert_eq!(protocol_data.proof_of_stake(), pos_uref);
+
+    // The existing named keys, including the purses and stakes, are kept, and the installer's new
+    // named key is added.
+    let pos_contract = builder.get_pos_contract();
+    let new_named_keys = pos_contract.named_keys();
+    for (name, key) in &old_named_keys {
+        assert_eq!(new_named_keys.get(name), Some(key), "named key {}", name);
+    }
+    assert_eq!(new_named_keys.len(), old_named_keys.len() + 1);
+    assert!(new_named_keys.contains_key(POS_VERSION_KEY_NAME));
+    assert_eq!(pos_contract.protocol_version(), new_protocol_version);
+    assert_eq!(get_stakes(&builder), old_stakes);
+}
+
+#[ignore]
+#[test]
+fn should_abort_upgrade_when_system_contract_installer_fails() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    // The installer doesn't return the uref of a new contract.
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(PROTOCOL_VERSION)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_costs(get_upgraded_wasm_costs())
+        .with_new_pos_code(utils::read_wasm_file_bytes(DO_NOTHING_CONTRACT_NAME))
+        .build();
+
+    builder.upgrade_with_upgrade_request(&mut upgrade_request);
+
+    let upgrade_response = builder
+        .get_upgrade_response(0)
+        .expect("should have response");
+    assert!(
+        upgrade_response.has_failed_deploy(),
+        "should have failed deploy"
+    );
+
+    // Not even the new costs were stored.
+    let protocol_data = builder
+        .get_engine_state()
+        .get_protocol_data(new_protocol_version)
+        .expect("should read protocol data");
+    assert!(protocol_data.is_none());
+}
+
+#[cfg(feature = "use-system-contracts")]
+#[ignore]
+#[test]
+fn should_call_old_and_new_entry_points_of_upgraded_pos() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+
+    let new_protocol_version = ProtocolVersion::from_parts(1, 1, 0);
+    upgrade_pos_code(&mut builder, new_protocol_version);
+
+    // The payment code and `pos_get_payment_purse` call the old entry points.
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        POS_GET_PAYMENT_PURSE_CONTRACT_NAME,
+        (U512::from(PAYMENT_AMOUNT),),
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        MODIFIED_POS_CALLER_CONTRACT_NAME,
+        (),
+    )
+    .with_protocol_version(new_protocol_version)
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let version_value = builder
+        .query(
+            None,
+            Key::Account(DEFAULT_ACCOUNT_ADDR),
+            &[POS_VERSION_OUTPUT_KEY_NAME],
+        )
+        .expect("should find version_uref value");
+    assert_eq!(
+        version_value,
+        StoredValue::CLValue(CLValue::from_t("1.1.0".to_string()).unwrap()),
+        "expected new version endpoint output"
+    );
+}
@@ -0,0 +1,118 @@
+use grpc::RequestOptions;
+
+use engine_core::engine_state::trie_nodes::MAX_TRIE_NODES_PER_REQUEST;
+use engine_grpc_server::engine_server::{
+    ipc::{
+        GetTrieNodeRequest, GetTrieNodeResponse, GetTrieNodesRequest, GetTrieNodesResponse,
+        GetTrieNodesResponse_Node,
+    },
+    ipc_grpc::ExecutionEngineService,
+};
+use engine_shared::{newtypes::Blake2bHash, stored_value::StoredValue};
+use engine_storage::trie::Trie;
+use engine_test_support::internal::{InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST};
+use types::{bytesrepr, Key};
+
+const MISSING_NODE_HASH: [u8; 32] = [255; 32];
+
+fn get_trie_node(builder: &InMemoryWasmTestBuilder, node_hash: Vec<u8>) -> GetTrieNodeResponse {
+    let mut get_trie_node_request = GetTrieNodeRequest::new();
+    get_trie_node_request.set_node_hash(node_hash);
+
+    builder
+        .get_engine_state()
+        .get_trie_node(RequestOptions::new(), get_trie_node_request)
+        .wait_drop_metadata()
+        .expect("should get trie node")
+}
+
+fn get_trie_nodes(
+    builder: &InMemoryWasmTestBuilder,
+    node_hashes: Vec<Vec<u8>>,
+) -> GetTrieNodesResponse {
+    let mut get_trie_nodes_request = GetTrieNodesRequest::new();
+    get_trie_nodes_request.set_node_hashes(node_hashes.into());
+
+    builder
+        .get_engine_state()
+        .get_trie_nodes(RequestOptions::new(), get_trie_nodes_request)
+        .wait_drop_metadata()
+        .expect("should get trie nodes")
+}
+
+/// Returns the hashes of the nodes `trie` points to.
+fn children(trie: &Trie<Key, StoredValue>) -> Vec<Vec<u8>> {
+    match trie {
+        Trie::Leaf { .. } => Vec::new(),
+        Trie::Node { pointer_block } => pointer_block[..]
+            .iter()
+            .filter_map(|maybe_pointer| maybe_pointer.as_ref())
+            .map(|pointer| pointer.hash().to_vec())
+            .collect(),
+        Trie::Extension { pointer, .. } => vec![pointer.hash().to_vec()],
+    }
+}
+
+#[ignore]
+#[test]
+fn should_get_root_node_and_its_children_by_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let root_hash = builder.get_post_state_hash();
+
+    let mut response = get_trie_node(&builder, root_hash.clone());
+    assert!(response.has_success(), "{:?}", response);
+    let root_bytes = response.take_success();
+    assert_eq!(Blake2bHash::new(&root_bytes).to_vec(), root_hash);
+
+    let root: Trie<Key, StoredValue> =
+        bytesrepr::deserialize(root_bytes).expect("should deserialize root node");
+    let child_hashes = children(&root);
+    assert!(!child_hashes.is_empty());
+
+    let mut response = get_trie_nodes(&builder, child_hashes.clone());
+    assert!(response.has_success(), "{:?}", response);
+    let nodes: Vec<GetTrieNodesResponse_Node> = response.take_success().take_nodes().into();
+    assert_eq!(nodes.len(), child_hashes.len());
+    for (node, child_hash) in nodes.iter().zip(child_hashes) {
+        assert!(node.has_node(), "{:?}", node);
+        assert_eq!(Blake2bHash::new(node.get_node()).to_vec(), child_hash);
+        let _child: Trie<Key, StoredValue> =
+            bytesrepr::deserialize(node.get_node().to_vec()).expect("should deserialize child");
+    }
+}
+
+#[ignore]
+#[test]
+fn should_report_missing_trie_nodes() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let root_hash = builder.get_post_state_hash();
+
+    let response = get_trie_node(&builder, MISSING_NODE_HASH.to_vec());
+    assert!(response.has_not_found(), "{:?}", response);
+    assert_eq!(
+        response.get_not_found().get_node_hash(),
+        &MISSING_NODE_HASH[..]
+    );
+
+    let mut response = get_trie_nodes(&builder, vec![MISSING_NODE_HASH.to_vec(), root_hash]);
+    assert!(response.has_success(), "{:?}", response);
+    let nodes = response.take_success().take_nodes();
+    assert_eq!(
+        nodes[0].get_not_found().get_node_hash(),
+        &MISSING_NODE_HASH[..]
+    );
+    assert!(nodes[1].has_node());
+}
+
+#[ignore]
+#[test]
+fn should_fail_to_get_more_trie_nodes_than_allowed() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let root_hash = builder.get_post_state_hash();
+
+    let response = get_trie_nodes(&builder, vec![root_hash; MAX_TRIE_NODES_PER_REQUEST + 1]);
+    assert!(response.has_failure(), "{:?}", response);
+}
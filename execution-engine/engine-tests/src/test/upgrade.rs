@@ -3,7 +3,7 @@ use engine_test_support::{
     internal::{ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_RUN_GENESIS_REQUEST},
     DEFAULT_ACCOUNT_ADDR,
 };
-use types::CLValue;
+use types::{ApiError, CLValue, Key, URef};
 
 const DO_NOTHING_STORED_CALLER_CONTRACT_NAME: &str = "do_nothing_stored_caller";
 const DO_NOTHING_STORED_CONTRACT_NAME: &str = "do_nothing_stored";
@@ -21,6 +21,9 @@ const PURSE_HOLDER_STORED_CONTRACT_NAME: &str = "purse_holder_stored";
 const PURSE_HOLDER_STORED_UPGRADER_CONTRACT_NAME: &str = "purse_holder_stored_upgrader";
 const STORE_AT_UREF: &str = "uref";
 const TOTAL_PURSES: usize = 3;
+// user error codes of purse-holder-stored and purse-holder-stored-upgrader
+const PURSE_NAME_ALREADY_EXISTS: u16 = 5;
+const UPGRADED_PURSE_NAME_NOT_FOUND: u16 = 8;
 
 #[ignore]
 #[test]
@@ -474,3 +477,111 @@ fn should_maintain_local_state_across_upgrade() {
         "local state should include elements from the original version and the upgraded version"
     );
 }
+
+/// Stores purse-holder-stored and returns its uref.
+fn store_purse_holder(builder: &mut InMemoryWasmTestBuilder) -> URef {
+    let contract_name = format!("{}.wasm", PURSE_HOLDER_STORED_CONTRACT_NAME);
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, &contract_name, ()).build();
+    let exec_index = builder.get_exec_responses_count();
+    builder.exec(exec_request).expect_success().commit();
+
+    let key: Key = builder
+        .get_session_return_value(exec_index)
+        .expect("should return contract key")
+        .into_t()
+        .expect("should be a key");
+    *key.as_uref().expect("should have uref")
+}
+
+fn call_purse_holder(
+    builder: &mut InMemoryWasmTestBuilder,
+    stored_uref: URef,
+    method: &str,
+    purse_name: &str,
+) {
+    let contract_name = format!("{}.wasm", PURSE_HOLDER_STORED_CALLER_CONTRACT_NAME);
+    let exec_request = ExecuteRequestBuilder::standard(
+        DEFAULT_ACCOUNT_ADDR,
+        &contract_name,
+        (stored_uref, method, purse_name),
+    )
+    .build();
+    builder.exec(exec_request).commit();
+}
+
+/// Asserts the revert data of the exec call at `index` names the failed `condition` of a `require!`
+/// in a contract's `main.rs`.
+fn assert_revert_data_names(builder: &InMemoryWasmTestBuilder, index: usize, condition: &str) {
+    let revert_data = builder
+        .get_revert_data(index)
+        .expect("should have revert data");
+    let revert_data = String::from_utf8(revert_data).expect("should be utf-8");
+    assert!(
+        revert_data.contains("src/main.rs:") && revert_data.ends_with(&format!(": {}", condition)),
+        "expected {} in {}",
+        condition,
+        revert_data
+    );
+}
+
+fn assert_reverted_with(builder: &InMemoryWasmTestBuilder, index: usize, error: ApiError) {
+    let error_message = builder
+        .exec_error_message(index)
+        .expect("should have error message");
+    let expected_error = format!("{:?}", error);
+    assert!(
+        error_message.contains(&expected_error),
+        "expected {} in {}",
+        expected_error,
+        error_message
+    );
+}
+
+#[ignore]
+#[test]
+fn should_revert_when_adding_duplicate_purse_name() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let stored_uref = store_purse_holder(&mut builder);
+
+    call_purse_holder(&mut builder, stored_uref, METHOD_ADD, PURSE_1);
+    builder.expect_success();
+    let contract = builder
+        .get_contract(stored_uref)
+        .expect("should have contract");
+    let purse_key = *contract
+        .named_keys()
+        .get(PURSE_1)
+        .expect("should have purse");
+
+    call_purse_holder(&mut builder, stored_uref, METHOD_ADD, PURSE_1);
+    assert!(builder.is_error());
+    assert_reverted_with(&builder, 2, ApiError::User(PURSE_NAME_ALREADY_EXISTS));
+    assert_revert_data_names(&builder, 2, "!runtime::has_key(&purse_name)");
+
+    // the first purse wasn't replaced
+    let contract = builder
+        .get_contract(stored_uref)
+        .expect("should have contract");
+    assert_eq!(contract.named_keys().get(PURSE_1), Some(&purse_key));
+}
+
+#[ignore]
+#[test]
+fn should_revert_when_removing_missing_purse_name() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&DEFAULT_RUN_GENESIS_REQUEST);
+    let stored_uref = store_purse_holder(&mut builder);
+
+    let contract_name = format!("{}.wasm", PURSE_HOLDER_STORED_UPGRADER_CONTRACT_NAME);
+    let exec_request =
+        ExecuteRequestBuilder::standard(DEFAULT_ACCOUNT_ADDR, &contract_name, (stored_uref,))
+            .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    call_purse_holder(&mut builder, stored_uref, METHOD_REMOVE, PURSE_1);
+    assert!(builder.is_error());
+    assert_reverted_with(&builder, 2, ApiError::User(UPGRADED_PURSE_NAME_NOT_FOUND));
+    assert_revert_data_names(&builder, 2, "runtime::has_key(&purse_name)");
+}
@@ -2,34 +2,196 @@ pub mod wasm_costs;
 
 use std::fmt::{self, Display, Formatter};
 
-use parity_wasm::elements::{self, Module};
-use pwasm_utils::{self, stack_height};
+use parity_wasm::elements::{self, External, FuncBody, ImportCountType, Internal, Module};
+use pwasm_utils::{self, rules, stack_height};
 
 use crate::wasm_costs::WasmCosts;
 
 //NOTE: size of Wasm memory page is 64 KiB
 pub const MEM_PAGES: u32 = 64;
 
+/// The only module contracts may import from.
+const IMPORT_MODULE: &str = "env";
+/// The only function session code may export if session exports are
+/// [strict](WasmCosts::strict_session_exports).
+pub const SESSION_ENTRY_POINT: &str = "call";
+/// Length of the magic number and version at the start of a module.
+const HEADER_LENGTH: usize = 8;
+const CODE_SECTION_ID: u8 = 10;
+
+/// A section of a Wasm module, or its header.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Section {
+    Header,
+    Custom,
+    Type,
+    Import,
+    Function,
+    Table,
+    Memory,
+    Global,
+    Export,
+    Start,
+    Element,
+    Code,
+    Data,
+}
+
+impl Section {
+    fn from_id(id: u8) -> Option<Section> {
+        let section = match id {
+            0 => Section::Custom,
+            1 => Section::Type,
+            2 => Section::Import,
+            3 => Section::Function,
+            4 => Section::Table,
+            5 => Section::Memory,
+            6 => Section::Global,
+            7 => Section::Export,
+            8 => Section::Start,
+            9 => Section::Element,
+            10 => Section::Code,
+            11 => Section::Data,
+            _ => return None,
+        };
+        Some(section)
+    }
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            Section::Header => "header",
+            Section::Custom => "custom",
+            Section::Type => "type",
+            Section::Import => "import",
+            Section::Function => "function",
+            Section::Table => "table",
+            Section::Memory => "memory",
+            Section::Global => "global",
+            Section::Export => "export",
+            Section::Start => "start",
+            Section::Element => "element",
+            Section::Code => "code",
+            Section::Data => "data",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Where in a module preprocessing failed, as far as it could be determined.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ErrorLocation {
+    pub section: Option<Section>,
+    /// Index of the function in the module's function index space, i.e. counting imported
+    /// functions first.
+    pub function_index: Option<u32>,
+    /// Index of the instruction within the function's body.
+    pub instruction_index: Option<u32>,
+    /// Offset in the module bytes of the section, or of the function's body.
+    pub byte_offset: Option<usize>,
+    /// Name of the import, as `module::field`, or of the export.
+    pub name: Option<String>,
+}
+
+impl ErrorLocation {
+    fn in_section(section: Section) -> Self {
+        ErrorLocation {
+            section: Some(section),
+            ..Default::default()
+        }
+    }
+
+    fn is_unknown(&self) -> bool {
+        *self == ErrorLocation::default()
+    }
+}
+
+impl Display for ErrorLocation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(section) = self.section {
+            parts.push(format!("{} section", section));
+        }
+        if let Some(name) = &self.name {
+            parts.push(format!("`{}`", name));
+        }
+        if let Some(function_index) = self.function_index {
+            parts.push(format!("function {}", function_index));
+        }
+        if let Some(instruction_index) = self.instruction_index {
+            parts.push(format!("instruction {}", instruction_index));
+        }
+        if let Some(byte_offset) = self.byte_offset {
+            parts.push(format!("byte offset {}", byte_offset));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
 #[derive(Debug)]
 pub enum PreprocessingError {
-    Deserialize(String),
-    OperationForbiddenByGasRules,
-    StackLimiter,
+    Deserialize {
+        message: String,
+        location: ErrorLocation,
+    },
+    OperationForbiddenByGasRules {
+        location: ErrorLocation,
+    },
+    StackLimiter {
+        message: String,
+        location: ErrorLocation,
+    },
+    /// The module imports from a module other than `env`.
+    InvalidImport {
+        location: ErrorLocation,
+    },
+    /// The module exports a function which it doesn't define or import.
+    InvalidExport {
+        location: ErrorLocation,
+    },
+    /// The module, sent as session code, exports functions other than [`SESSION_ENTRY_POINT`],
+    /// which are named.
+    UnexpectedExports(Vec<String>),
 }
 
-impl From<elements::Error> for PreprocessingError {
-    fn from(error: elements::Error) -> Self {
-        PreprocessingError::Deserialize(error.to_string())
+static EXPORT_SECTION_LOCATION: ErrorLocation = ErrorLocation {
+    section: Some(Section::Export),
+    function_index: None,
+    instruction_index: None,
+    byte_offset: None,
+    name: None,
+};
+
+impl PreprocessingError {
+    /// Returns where in the module preprocessing failed.
+    pub fn location(&self) -> &ErrorLocation {
+        match self {
+            PreprocessingError::Deserialize { location, .. }
+            | PreprocessingError::OperationForbiddenByGasRules { location }
+            | PreprocessingError::StackLimiter { location, .. }
+            | PreprocessingError::InvalidImport { location }
+            | PreprocessingError::InvalidExport { location } => location,
+            PreprocessingError::UnexpectedExports(_) => &EXPORT_SECTION_LOCATION,
+        }
     }
 }
 
 impl Display for PreprocessingError {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match self {
-            PreprocessingError::Deserialize(error) => write!(f, "Deserialization error: {}", error),
-            PreprocessingError::OperationForbiddenByGasRules => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map"),
-            PreprocessingError::StackLimiter => write!(f, "Stack limiter error"),
+            PreprocessingError::Deserialize { message, .. } => write!(f, "Deserialization error: {}", message)?,
+            PreprocessingError::OperationForbiddenByGasRules { .. } => write!(f, "Encountered operation forbidden by gas rules. Consult instruction -> metering config map")?,
+            PreprocessingError::StackLimiter { message, .. } => write!(f, "Stack limiter error: {}", message)?,
+            PreprocessingError::InvalidImport { .. } => write!(f, "Imports from a module other than `{}`", IMPORT_MODULE)?,
+            PreprocessingError::InvalidExport { .. } => write!(f, "Exports a function which doesn't exist")?,
+            PreprocessingError::UnexpectedExports(names) => write!(f, "Session code exports functions other than `{}`: {}", SESSION_ENTRY_POINT, names.join(", "))?,
+        }
+        let location = self.location();
+        if !location.is_unknown() {
+            write!(f, " (at {})", location)?;
         }
+        Ok(())
     }
 }
 
@@ -47,18 +209,449 @@ impl Preprocessor {
         }
     }
 
+    /// Returns the Wasm costs which modules are instrumented with.
+    pub fn wasm_costs(&self) -> &WasmCosts {
+        &self.wasm_costs
+    }
+
     pub fn preprocess(&self, module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
         let module = deserialize(module_bytes)?;
+        validate_imports(&module)?;
+        validate_exports(&module)?;
+        let rules = self.wasm_costs.to_set();
+        // the forbidden instruction is looked for up front, as the gas injector doesn't say which
+        // one it was
+        let forbidden_location = locate_forbidden_instruction(&module, &rules);
         let module = pwasm_utils::externalize_mem(module, None, self.mem_pages);
-        let module = pwasm_utils::inject_gas_counter(module, &self.wasm_costs.to_set())
-            .map_err(|_| PreprocessingError::OperationForbiddenByGasRules)?;
+        let module = pwasm_utils::inject_gas_counter(module, &rules).map_err(|_| {
+            PreprocessingError::OperationForbiddenByGasRules {
+                location: forbidden_location
+                    .unwrap_or_else(|| ErrorLocation::in_section(Section::Code)),
+            }
+        })?;
         let module = stack_height::inject_limiter(module, self.wasm_costs.max_stack_height)
-            .map_err(|_| PreprocessingError::StackLimiter)?;
+            .map_err(|error| PreprocessingError::StackLimiter {
+                message: single_line(format!("{:?}", error)),
+                location: ErrorLocation::in_section(Section::Code),
+            })?;
         Ok(module)
     }
+
+    /// Checks that `module`, sent as session code, exports no function other than
+    /// [`SESSION_ENTRY_POINT`] if session exports are
+    /// [strict](WasmCosts::strict_session_exports).
+    ///
+    /// Other exports usually mean a stored contract's module was sent as session code, whose `call`
+    /// then does something other than intended.
+    pub fn validate_session_exports(&self, module: &Module) -> Result<(), PreprocessingError> {
+        if !self.wasm_costs.strict_session_exports {
+            return Ok(());
+        }
+        let unexpected_exports: Vec<String> = module
+            .export_section()
+            .map(|section| section.entries())
+            .unwrap_or_default()
+            .iter()
+            .filter(|entry| match entry.internal() {
+                Internal::Function(_) => entry.field() != SESSION_ENTRY_POINT,
+                _ => false,
+            })
+            .map(|entry| entry.field().to_string())
+            .collect();
+        if unexpected_exports.is_empty() {
+            Ok(())
+        } else {
+            Err(PreprocessingError::UnexpectedExports(unexpected_exports))
+        }
+    }
 }
 
 // Returns a parity Module from bytes without making modifications or limits
 pub fn deserialize(module_bytes: &[u8]) -> Result<Module, PreprocessingError> {
-    parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(Into::into)
+    parity_wasm::deserialize_buffer::<Module>(module_bytes).map_err(|error| {
+        PreprocessingError::Deserialize {
+            message: single_line(error.to_string()),
+            location: locate_deserialize_error(module_bytes),
+        }
+    })
+}
+
+/// Collapses `message` onto a single line, so that it can be embedded in log lines.
+fn single_line(message: String) -> String {
+    if message.contains('\n') {
+        message.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        message
+    }
+}
+
+fn validate_imports(module: &Module) -> Result<(), PreprocessingError> {
+    let entries = module
+        .import_section()
+        .map(|section| section.entries())
+        .unwrap_or_default();
+    match entries.iter().find(|entry| entry.module() != IMPORT_MODULE) {
+        Some(entry) => Err(PreprocessingError::InvalidImport {
+            location: ErrorLocation {
+                name: Some(format!("{}::{}", entry.module(), entry.field())),
+                ..ErrorLocation::in_section(Section::Import)
+            },
+        }),
+        None => Ok(()),
+    }
+}
+
+fn validate_exports(module: &Module) -> Result<(), PreprocessingError> {
+    let function_count = module.import_count(ImportCountType::Function)
+        + module
+            .function_section()
+            .map(|section| section.entries().len())
+            .unwrap_or_default();
+    let entries = module
+        .export_section()
+        .map(|section| section.entries())
+        .unwrap_or_default();
+    let invalid_export = entries.iter().find(|entry| match entry.internal() {
+        Internal::Function(index) => *index as usize >= function_count,
+        _ => false,
+    });
+    match invalid_export {
+        Some(entry) => Err(PreprocessingError::InvalidExport {
+            location: ErrorLocation {
+                name: Some(entry.field().to_string()),
+                ..ErrorLocation::in_section(Section::Export)
+            },
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Returns the location of the first instruction forbidden by `rules`, if there is one.
+fn locate_forbidden_instruction(module: &Module, rules: &rules::Set) -> Option<ErrorLocation> {
+    let imported_functions = module.import_count(ImportCountType::Function);
+    let bodies = module
+        .code_section()
+        .map(|section| section.bodies())
+        .unwrap_or_default();
+    bodies.iter().enumerate().find_map(|(body_index, body)| {
+        let instruction_index = body
+            .code()
+            .elements()
+            .iter()
+            .position(|instruction| rules.process(instruction).is_err())?;
+        Some(ErrorLocation {
+            function_index: Some((imported_functions + body_index) as u32),
+            instruction_index: Some(instruction_index as u32),
+            ..ErrorLocation::in_section(Section::Code)
+        })
+    })
+}
+
+/// Reads an unsigned LEB128 value from `bytes` at `offset`, advancing `offset` past it.
+fn read_var_u32(bytes: &[u8], offset: &mut usize) -> Option<u32> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        let byte = *bytes.get(*offset)?;
+        *offset += 1;
+        value |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Finds where deserializing `module_bytes` fails by deserializing its sections one at a time,
+/// and the function bodies of the failing section too if it is the code section.
+///
+/// If every section deserializes on its own, the module is inconsistent as a whole, e.g. its
+/// sections are out of order, and the location is unknown.
+fn locate_deserialize_error(module_bytes: &[u8]) -> ErrorLocation {
+    if module_bytes.len() < HEADER_LENGTH
+        || parity_wasm::deserialize_buffer::<Module>(&module_bytes[..HEADER_LENGTH]).is_err()
+    {
+        return ErrorLocation {
+            byte_offset: Some(0),
+            ..ErrorLocation::in_section(Section::Header)
+        };
+    }
+
+    let mut imported_functions = 0;
+    let mut offset = HEADER_LENGTH;
+    while offset < module_bytes.len() {
+        let section_start = offset;
+        let section_id = module_bytes[offset];
+        offset += 1;
+        let section_location = ErrorLocation {
+            section: Section::from_id(section_id),
+            byte_offset: Some(section_start),
+            ..Default::default()
+        };
+        let section_end = match read_var_u32(module_bytes, &mut offset)
+            .and_then(|size| offset.checked_add(size as usize))
+        {
+            Some(section_end) if section_end <= module_bytes.len() => section_end,
+            _ => return section_location,
+        };
+
+        let section_bytes = &module_bytes[section_start..section_end];
+        match parity_wasm::deserialize_buffer::<elements::Section>(section_bytes) {
+            Ok(elements::Section::Import(import_section)) => {
+                imported_functions = import_section
+                    .entries()
+                    .iter()
+                    .filter(|entry| match entry.external() {
+                        External::Function(_) => true,
+                        _ => false,
+                    })
+                    .count();
+            }
+            Ok(_) => {}
+            Err(_) if section_id == CODE_SECTION_ID => {
+                return locate_code_error(module_bytes, offset, imported_functions)
+                    .unwrap_or(section_location);
+            }
+            Err(_) => return section_location,
+        }
+        offset = section_end;
+    }
+    ErrorLocation::default()
+}
+
+/// Finds the function body which fails to deserialize in the code section whose payload starts at
+/// `offset`.
+fn locate_code_error(
+    module_bytes: &[u8],
+    mut offset: usize,
+    imported_functions: usize,
+) -> Option<ErrorLocation> {
+    let body_count = read_var_u32(module_bytes, &mut offset)?;
+    for body_index in 0..body_count as usize {
+        let body_start = offset;
+        let body_location = ErrorLocation {
+            function_index: Some((imported_functions + body_index) as u32),
+            byte_offset: Some(body_start),
+            ..ErrorLocation::in_section(Section::Code)
+        };
+        let body_end = match read_var_u32(module_bytes, &mut offset)
+            .and_then(|size| offset.checked_add(size as usize))
+        {
+            Some(body_end) if body_end <= module_bytes.len() => body_end,
+            _ => return Some(body_location),
+        };
+        if parity_wasm::deserialize_buffer::<FuncBody>(&module_bytes[body_start..body_end]).is_err()
+        {
+            return Some(body_location);
+        }
+        offset = body_end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ErrorLocation, PreprocessingError, Preprocessor, Section};
+    use crate::wasm_costs::WasmCosts;
+
+    /// Offset of the second function body in the module built by [`module_bytes`].
+    const SECOND_BODY_OFFSET: usize = 46;
+
+    /// Builds a module importing one function from `import_module`, and defining two functions:
+    /// an empty one, and one whose body is `nop`, `second_instruction`, `drop`, with
+    /// `second_instruction` five bytes long.  Exports the function at `export_index` under "call".
+    fn module_bytes(
+        import_module: &[u8; 3],
+        export_index: u8,
+        second_instruction: [u8; 5],
+    ) -> Vec<u8> {
+        let mut bytes = vec![
+            0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+            0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: () -> ()
+            0x02, 0x09, 0x01, 0x03, // import section
+        ];
+        bytes.extend_from_slice(import_module);
+        bytes.extend_from_slice(&[
+            0x01, b'f', 0x00, 0x00, // import `f` of type 0
+            0x03, 0x03, 0x02, 0x00, 0x00, // function section: two functions of type 0
+            0x07, 0x08, 0x01, 0x04, b'c', b'a', b'l', b'l', 0x00, // export section
+        ]);
+        bytes.push(export_index);
+        bytes.extend_from_slice(&[
+            0x0a, 0x0e, 0x02, // code section with two bodies
+            0x02, 0x00, 0x0b, // empty body
+            0x09, 0x00, 0x01, // body of 9 bytes with no locals, starting with `nop`
+        ]);
+        bytes.extend_from_slice(&second_instruction);
+        bytes.extend_from_slice(&[
+            0x1a, 0x0b, // drop, end
+        ]);
+        bytes
+    }
+
+    fn i32_const() -> [u8; 5] {
+        // i32.const 0, padded to five bytes
+        [0x41, 0x80, 0x80, 0x80, 0x00]
+    }
+
+    fn preprocess(module_bytes: &[u8]) -> Result<(), PreprocessingError> {
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            max_stack_height: 64 * 1024,
+            opcodes_mul: 1,
+            opcodes_div: 1,
+            ..Default::default()
+        };
+        Preprocessor::new(wasm_costs)
+            .preprocess(module_bytes)
+            .map(|_| ())
+    }
+
+    #[test]
+    fn should_preprocess_valid_module() {
+        preprocess(&module_bytes(b"env", 2, i32_const())).expect("should preprocess");
+    }
+
+    #[test]
+    fn should_locate_corrupted_header() {
+        let mut bytes = module_bytes(b"env", 2, i32_const());
+        bytes[0] = 0xff;
+        let error = preprocess(&bytes).unwrap_err();
+        assert_eq!(
+            *error.location(),
+            ErrorLocation {
+                section: Some(Section::Header),
+                byte_offset: Some(0),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_locate_corrupted_section() {
+        let mut bytes = module_bytes(b"env", 2, i32_const());
+        // not a function type
+        bytes[11] = 0x61;
+        let error = preprocess(&bytes).unwrap_err();
+        assert_eq!(
+            *error.location(),
+            ErrorLocation {
+                section: Some(Section::Type),
+                byte_offset: Some(8),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_locate_corrupted_function_body() {
+        // not an opcode
+        let error = preprocess(&module_bytes(b"env", 2, [0xff, 0, 0, 0, 0])).unwrap_err();
+        match error {
+            PreprocessingError::Deserialize { .. } => {}
+            _ => panic!("unexpected error: {}", error),
+        }
+        assert_eq!(
+            *error.location(),
+            ErrorLocation {
+                section: Some(Section::Code),
+                function_index: Some(2),
+                byte_offset: Some(SECOND_BODY_OFFSET),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_locate_forbidden_instruction() {
+        // f32.const 0
+        let error = preprocess(&module_bytes(b"env", 2, [0x43, 0, 0, 0, 0])).unwrap_err();
+        match error {
+            PreprocessingError::OperationForbiddenByGasRules { .. } => {}
+            _ => panic!("unexpected error: {}", error),
+        }
+        assert_eq!(
+            *error.location(),
+            ErrorLocation {
+                section: Some(Section::Code),
+                function_index: Some(2),
+                instruction_index: Some(1),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_name_invalid_import() {
+        let error = preprocess(&module_bytes(b"foo", 2, i32_const())).unwrap_err();
+        match error {
+            PreprocessingError::InvalidImport { .. } => {}
+            _ => panic!("unexpected error: {}", error),
+        }
+        assert_eq!(error.location().section, Some(Section::Import));
+        assert_eq!(error.location().name.as_deref(), Some("foo::f"));
+    }
+
+    #[test]
+    fn should_name_invalid_export() {
+        // function 3 doesn't exist
+        let error = preprocess(&module_bytes(b"env", 3, i32_const())).unwrap_err();
+        match error {
+            PreprocessingError::InvalidExport { .. } => {}
+            _ => panic!("unexpected error: {}", error),
+        }
+        assert_eq!(error.location().section, Some(Section::Export));
+        assert_eq!(error.location().name.as_deref(), Some("call"));
+    }
+
+    #[test]
+    fn should_reject_unexpected_session_exports_only_if_strict() {
+        let mut bytes = module_bytes(b"env", 2, i32_const());
+        let export_name_offset = bytes
+            .windows(4)
+            .position(|window| window == b"call")
+            .expect("should export call");
+        bytes[export_name_offset..export_name_offset + 4].copy_from_slice(b"main");
+
+        let wasm_costs = WasmCosts {
+            regular: 1,
+            max_stack_height: 64 * 1024,
+            opcodes_mul: 1,
+            opcodes_div: 1,
+            ..Default::default()
+        };
+        let preprocessor = Preprocessor::new(wasm_costs);
+        let module = preprocessor.preprocess(&bytes).expect("should preprocess");
+        preprocessor
+            .validate_session_exports(&module)
+            .expect("should allow any exports");
+
+        let strict_preprocessor = Preprocessor::new(WasmCosts {
+            strict_session_exports: true,
+            ..wasm_costs
+        });
+        match strict_preprocessor.validate_session_exports(&module) {
+            Err(PreprocessingError::UnexpectedExports(names)) => {
+                assert_eq!(names, vec!["main".to_string()])
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+
+        let module = strict_preprocessor
+            .preprocess(&module_bytes(b"env", 2, i32_const()))
+            .expect("should preprocess");
+        strict_preprocessor
+            .validate_session_exports(&module)
+            .expect("should allow call");
+    }
+
+    #[test]
+    fn should_display_location_on_single_line() {
+        let error = preprocess(&module_bytes(b"env", 2, [0xff, 0, 0, 0, 0])).unwrap_err();
+        let message = error.to_string();
+        assert!(!message.contains('\n'));
+        assert!(message.ends_with(&format!(
+            "(at code section, function 2, byte offset {})",
+            SECOND_BODY_OFFSET
+        )));
+    }
 }
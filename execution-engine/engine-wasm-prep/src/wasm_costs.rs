@@ -2,13 +2,14 @@ use std::collections::BTreeMap;
 
 use pwasm_utils::rules::{InstructionType, Metering, Set};
 
-use types::bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH};
+use types::bytesrepr::{self, FromBytes, ToBytes, BOOL_SERIALIZED_LENGTH, U32_SERIALIZED_LENGTH};
 
-const NUM_FIELDS: usize = 10;
-pub const WASM_COSTS_SERIALIZED_LENGTH: usize = NUM_FIELDS * U32_SERIALIZED_LENGTH;
+const NUM_U32_FIELDS: usize = 20;
+pub const WASM_COSTS_SERIALIZED_LENGTH: usize =
+    NUM_U32_FIELDS * U32_SERIALIZED_LENGTH + BOOL_SERIALIZED_LENGTH;
 
 // Taken (partially) from parity-ethereum
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
 pub struct WasmCosts {
     /// Default opcode cost
     pub regular: u32,
@@ -33,6 +34,33 @@ pub struct WasmCosts {
     /// Cost of wasm opcode is calculated as TABLE_ENTRY_COST * `opcodes_mul` /
     /// `opcodes_div`
     pub opcodes_div: u32,
+    /// Transient storage cost, per byte
+    pub transient_storage: u32,
+    /// Max size (in bytes) of the transient storage of a single execution
+    pub max_transient_storage_size: u32,
+    /// Max number of transforms in the effects of payment or session code of a single deploy, or
+    /// zero for no limit
+    pub max_transforms_per_deploy: u32,
+    /// Max size (in bytes) of the effects of payment or session code of a single deploy, or zero
+    /// for no limit
+    pub max_effect_bytes_per_deploy: u32,
+    /// Max size (in bytes) of the value returned by session code, or zero for no limit
+    pub max_session_return_value_bytes: u32,
+    /// Cost, per byte, of a string passed to a host function
+    pub string_byte: u32,
+    /// Max length (in bytes) of a string passed to a host function, or zero for no limit
+    pub max_string_length: u32,
+    /// Max size (in bytes) of the data attached to a revert; longer data is truncated
+    pub max_revert_data_bytes: u32,
+    /// Gas charged to every deploy which passes its preconditions, for loading its account,
+    /// checking its authorization and setting up its execution
+    pub base_deploy_cost: u32,
+    /// Gas accounted to a deploy which fails its preconditions once its account is loaded; only
+    /// reported, as no payment was made to collect it from
+    pub rejected_deploy_cost: u32,
+    /// Whether session code given as module bytes is rejected if it exports any function other
+    /// than `call`, which is usually a stored contract's module sent as session code by mistake
+    pub strict_session_exports: bool,
 }
 
 impl WasmCosts {
@@ -64,6 +92,17 @@ impl ToBytes for WasmCosts {
         ret.append(&mut self.max_stack_height.to_bytes()?);
         ret.append(&mut self.opcodes_mul.to_bytes()?);
         ret.append(&mut self.opcodes_div.to_bytes()?);
+        ret.append(&mut self.transient_storage.to_bytes()?);
+        ret.append(&mut self.max_transient_storage_size.to_bytes()?);
+        ret.append(&mut self.max_transforms_per_deploy.to_bytes()?);
+        ret.append(&mut self.max_effect_bytes_per_deploy.to_bytes()?);
+        ret.append(&mut self.max_session_return_value_bytes.to_bytes()?);
+        ret.append(&mut self.string_byte.to_bytes()?);
+        ret.append(&mut self.max_string_length.to_bytes()?);
+        ret.append(&mut self.max_revert_data_bytes.to_bytes()?);
+        ret.append(&mut self.base_deploy_cost.to_bytes()?);
+        ret.append(&mut self.rejected_deploy_cost.to_bytes()?);
+        ret.append(&mut self.strict_session_exports.to_bytes()?);
         Ok(ret)
     }
 
@@ -84,6 +123,17 @@ impl FromBytes for WasmCosts {
         let (max_stack_height, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_mul, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
         let (opcodes_div, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (transient_storage, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_transient_storage_size, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_transforms_per_deploy, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_effect_bytes_per_deploy, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_session_return_value_bytes, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (string_byte, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_string_length, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (max_revert_data_bytes, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (base_deploy_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (rejected_deploy_cost, rem): (u32, &[u8]) = FromBytes::from_bytes(rem)?;
+        let (strict_session_exports, rem): (bool, &[u8]) = FromBytes::from_bytes(rem)?;
         let wasm_costs = WasmCosts {
             regular,
             div,
@@ -95,13 +145,24 @@ impl FromBytes for WasmCosts {
             max_stack_height,
             opcodes_mul,
             opcodes_div,
+            transient_storage,
+            max_transient_storage_size,
+            max_transforms_per_deploy,
+            max_effect_bytes_per_deploy,
+            max_session_return_value_bytes,
+            string_byte,
+            max_string_length,
+            max_revert_data_bytes,
+            base_deploy_cost,
+            rejected_deploy_cost,
+            strict_session_exports,
         };
         Ok((wasm_costs, rem))
     }
 }
 
 pub mod gens {
-    use proptest::{num, prop_compose};
+    use proptest::{bool, num, prop_compose};
 
     use crate::wasm_costs::WasmCosts;
 
@@ -117,6 +178,17 @@ pub mod gens {
             max_stack_height in num::u32::ANY,
             opcodes_mul in num::u32::ANY,
             opcodes_div in num::u32::ANY,
+            transient_storage in num::u32::ANY,
+            max_transient_storage_size in num::u32::ANY,
+            max_transforms_per_deploy in num::u32::ANY,
+            max_effect_bytes_per_deploy in num::u32::ANY,
+            max_session_return_value_bytes in num::u32::ANY,
+            string_byte in num::u32::ANY,
+            max_string_length in num::u32::ANY,
+            max_revert_data_bytes in num::u32::ANY,
+            base_deploy_cost in num::u32::ANY,
+            rejected_deploy_cost in num::u32::ANY,
+            strict_session_exports in bool::ANY,
         ) -> WasmCosts {
             WasmCosts {
                 regular,
@@ -129,6 +201,17 @@ pub mod gens {
                 max_stack_height,
                 opcodes_mul,
                 opcodes_div,
+                transient_storage,
+                max_transient_storage_size,
+                max_transforms_per_deploy,
+                max_effect_bytes_per_deploy,
+                max_session_return_value_bytes,
+                string_byte,
+                max_string_length,
+                max_revert_data_bytes,
+                base_deploy_cost,
+                rejected_deploy_cost,
+                strict_session_exports,
             }
         }
     }
@@ -155,6 +238,17 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 3,
             opcodes_div: 8,
+            transient_storage: 1,
+            max_transient_storage_size: 1024 * 1024,
+            max_transforms_per_deploy: 0,
+            max_effect_bytes_per_deploy: 0,
+            max_session_return_value_bytes: 0,
+            string_byte: 1,
+            max_string_length: 1024,
+            max_revert_data_bytes: 256,
+            base_deploy_cost: 0,
+            rejected_deploy_cost: 0,
+            strict_session_exports: false,
         }
     }
 
@@ -170,6 +264,17 @@ mod tests {
             max_stack_height: 64 * 1024,
             opcodes_mul: 1,
             opcodes_div: 1,
+            transient_storage: 0,
+            max_transient_storage_size: 1024 * 1024,
+            max_transforms_per_deploy: 0,
+            max_effect_bytes_per_deploy: 0,
+            max_session_return_value_bytes: 0,
+            string_byte: 0,
+            max_string_length: 1024,
+            max_revert_data_bytes: 256,
+            base_deploy_cost: 0,
+            rejected_deploy_cost: 0,
+            strict_session_exports: false,
         }
     }
 
@@ -11,6 +11,34 @@ pub use crate::{runtime_provider::RuntimeProvider, storage_provider::StorageProv
 
 const SYSTEM_ACCOUNT: PublicKey = PublicKey::ed25519_from([0; 32]);
 
+/// Prefixes the local keys marking purses as revoked, so that they can't collide with the local
+/// keys associating purses with their balance urefs.
+const REVOKED_PURSE_PREFIX: u8 = 1;
+
+/// The local key under which the mint records the total motes it has created, i.e. the sum of
+/// the balances of all purses.
+///
+/// Every other local key of the mint associates a purse with its balance uref (stored as a `Key`),
+/// marks a purse as revoked or records an allowance, so the balance urefs can be found by listing
+/// the mint's local keys.
+pub const TOTAL_SUPPLY_LOCAL_KEY: u8 = 2;
+
+/// Prefixes the local keys recording how many motes a spender may still transfer out of a purse.
+const ALLOWANCE_PREFIX: u8 = 3;
+
+fn revoked_purse_key(purse: URef) -> (u8, [u8; 32]) {
+    (REVOKED_PURSE_PREFIX, purse.addr())
+}
+
+fn allowance_key(purse: URef, spender: Key) -> (u8, [u8; 32], Key) {
+    (ALLOWANCE_PREFIX, purse.addr(), spender.normalize())
+}
+
+/// The mint contract.
+///
+/// A purse exists if and only if it was created by [`Mint::mint`]: the mint never creates a purse
+/// or its balance implicitly.  A uref which isn't a purse has no balance, and transfers to it fail
+/// with [`Error::DestinationPurseNotFound`].
 pub trait Mint: RuntimeProvider + StorageProvider {
     fn mint(&mut self, initial_balance: U512) -> Result<URef, Error> {
         let caller = self.get_caller();
@@ -18,6 +46,14 @@ pub trait Mint: RuntimeProvider + StorageProvider {
             return Err(Error::InvalidNonEmptyPurseCreation);
         }
 
+        if !initial_balance.is_zero() {
+            let total_supply = self
+                .total_supply()?
+                .checked_add(initial_balance)
+                .ok_or(Error::TotalSupplyOverflow)?;
+            self.write_local(TOTAL_SUPPLY_LOCAL_KEY, total_supply);
+        }
+
         let balance_uref: Key = self.new_uref(initial_balance).into();
         let purse_key: URef = self.new_uref(());
         let purse_uref_name = purse_key.remove_access_rights().as_string();
@@ -31,7 +67,18 @@ pub trait Mint: RuntimeProvider + StorageProvider {
         Ok(purse_key)
     }
 
+    /// Returns `true` if `purse` was created by the mint.  A revoked purse still exists.
+    fn purse_exists(&mut self, purse: URef) -> Result<bool, Error> {
+        let balance_uref: Option<Key> = self.read_local(&purse.addr())?;
+        Ok(balance_uref.is_some())
+    }
+
+    /// Returns the balance of `purse`, or `None` if it isn't a purse.  `purse` must carry at least
+    /// [`AccessRights::READ`](types::AccessRights::READ).
     fn balance(&mut self, purse: URef) -> Result<Option<U512>, Error> {
+        if !purse.is_readable() {
+            return Err(Error::InvalidAccessRights);
+        }
         let balance_uref: URef = match self.read_local(&purse.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Ok(None),
@@ -43,9 +90,54 @@ pub trait Mint: RuntimeProvider + StorageProvider {
     }
 
     fn transfer(&mut self, source: URef, dest: URef, amount: U512) -> Result<(), Error> {
-        if !source.is_writeable() || !dest.is_addable() {
+        if !source.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        self.move_funds(source, dest, amount)
+    }
+
+    /// Allows `spender` to transfer up to `amount` motes out of `purse` via
+    /// [`Mint::transfer_from`], replacing any previous allowance.  Approving zero revokes it.
+    fn approve(&mut self, purse: URef, spender: Key, amount: U512) -> Result<(), Error> {
+        if !purse.is_writeable() {
             return Err(Error::InvalidAccessRights);
         }
+        if !self.purse_exists(purse)? {
+            return Err(Error::PurseNotFound);
+        }
+        self.write_local(allowance_key(purse, spender), amount);
+        Ok(())
+    }
+
+    /// Returns how many motes `spender` may still transfer out of `purse`.
+    fn allowance(&mut self, purse: URef, spender: Key) -> Result<U512, Error> {
+        let allowance: Option<U512> = self.read_local(&allowance_key(purse, spender))?;
+        Ok(allowance.unwrap_or_default())
+    }
+
+    /// Transfers `amount` motes out of `owner_purse` on behalf of the caller, which must have been
+    /// approved for at least `amount`.  The caller needs no access rights on `owner_purse`; the
+    /// allowance is decremented only if the transfer succeeds.
+    fn transfer_from(&mut self, owner_purse: URef, dest: URef, amount: U512) -> Result<(), Error> {
+        let spender = self.get_caller_key();
+        let allowance = self.allowance(owner_purse, spender)?;
+        if amount > allowance {
+            return Err(Error::InsufficientAllowance);
+        }
+        self.move_funds(owner_purse, dest, amount)?;
+        self.write_local(allowance_key(owner_purse, spender), allowance - amount);
+        Ok(())
+    }
+
+    /// Moves `amount` motes from `source` to `dest`, once the caller has been authorized to spend
+    /// from `source`.
+    fn move_funds(&mut self, source: URef, dest: URef, amount: U512) -> Result<(), Error> {
+        if !dest.is_addable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if self.is_revoked(source)? || self.is_revoked(dest)? {
+            return Err(Error::PurseRevoked);
+        }
         let source_bal: URef = match self.read_local(&source.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
             None => return Err(Error::SourceNotFound),
@@ -59,10 +151,37 @@ pub trait Mint: RuntimeProvider + StorageProvider {
         }
         let dest_bal: URef = match self.read_local(&dest.addr())? {
             Some(key) => TryFrom::<Key>::try_from(key).map_err(|_| Error::InvalidAccessRights)?,
-            None => return Err(Error::DestNotFound),
+            None => return Err(Error::DestinationPurseNotFound),
         };
         self.write(source_bal, source_value - amount)?;
         self.add(dest_bal, amount)?;
         Ok(())
     }
+
+    /// Revokes `purse`, so that any further transfer from or to it fails.  Its balance can still
+    /// be read.
+    fn revoke(&mut self, purse: URef) -> Result<(), Error> {
+        if !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if !self.purse_exists(purse)? {
+            return Err(Error::PurseNotFound);
+        }
+        if self.is_revoked(purse)? {
+            return Err(Error::PurseRevoked);
+        }
+        self.write_local(revoked_purse_key(purse), true);
+        Ok(())
+    }
+
+    /// Returns the total motes created by the mint.
+    fn total_supply(&mut self) -> Result<U512, Error> {
+        let total_supply: Option<U512> = self.read_local(&TOTAL_SUPPLY_LOCAL_KEY)?;
+        Ok(total_supply.unwrap_or_default())
+    }
+
+    fn is_revoked(&mut self, purse: URef) -> Result<bool, Error> {
+        let revoked: Option<bool> = self.read_local(&revoked_purse_key(purse))?;
+        Ok(revoked.unwrap_or_default())
+    }
 }
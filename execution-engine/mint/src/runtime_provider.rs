@@ -3,5 +3,8 @@ use types::{account::PublicKey, Key};
 pub trait RuntimeProvider {
     fn get_caller(&self) -> PublicKey;
 
+    /// Returns the key of the account or contract which called the mint.
+    fn get_caller_key(&self) -> Key;
+
     fn put_key(&mut self, name: &str, key: Key);
 }
@@ -1,4 +1,4 @@
-#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
 extern crate alloc;
 
@@ -6,6 +6,7 @@ mod mint_provider;
 mod queue;
 mod queue_provider;
 mod runtime_provider;
+mod stake_key_name;
 mod stakes;
 mod stakes_provider;
 
@@ -14,21 +15,38 @@ use core::marker::Sized;
 use types::{
     account::PublicKey,
     system_contract_errors::pos::{Error, Result},
-    AccessRights, TransferredTo, URef, U512,
+    AccessRights, URef, U512,
 };
 
 pub use crate::{
-    mint_provider::MintProvider, queue::Queue, queue_provider::QueueProvider,
-    runtime_provider::RuntimeProvider, stakes::Stakes, stakes_provider::StakesProvider,
+    mint_provider::MintProvider,
+    queue::Queue,
+    queue_provider::QueueProvider,
+    runtime_provider::RuntimeProvider,
+    stake_key_name::{
+        parse_stake_key_name, pos_validator_key_name_to_public_key,
+        pos_validator_key_name_to_tuple, stake_key_name, StakeKeyName, STAKE_KEY_PREFIX,
+    },
+    stakes::Stakes,
+    stakes_provider::StakesProvider,
 };
 
 pub trait ProofOfStake:
     MintProvider + QueueProvider + RuntimeProvider + StakesProvider + Sized
 {
+    /// Bonds `amount` from the purse `source`, e.g. a dedicated operational purse rather than the
+    /// main purse of the validator's account.
+    ///
+    /// The caller must pass `ADD` and `WRITE` rights on `source`.  The validator's unbonded stake
+    /// is paid back to `source`, or to the purse of their latest bond if they bond more than once.
     fn bond(&mut self, validator: PublicKey, amount: U512, source: URef) -> Result<()> {
         if amount.is_zero() {
             return Err(Error::BondTooSmall);
         }
+        match self.get_access_rights(source) {
+            Some(access_rights) if access_rights.contains(AccessRights::ADD_WRITE) => {}
+            _ => return Err(Error::InsufficientBondingPurseAccessRights),
+        }
         let target = internal::get_bonding_purse(self)?;
         let timestamp = self.get_block_time();
         // Transfer `amount` from the `source` purse to PoS internal purse. POS_PURSE is a constant,
@@ -36,15 +54,11 @@ pub trait ProofOfStake:
         self.transfer_purse_to_purse(source, target, amount)
             .map_err(|_| Error::BondTransferFailed)?;
         internal::bond(self, amount, validator, timestamp)?;
+        internal::set_payout_purse(self, &validator, source);
 
         // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
         let unbonds = internal::step(self, timestamp)?;
-        for entry in unbonds {
-            let _: TransferredTo = self
-                .transfer_purse_to_account(source, entry.validator, entry.amount)
-                .map_err(|_| Error::BondTransferFailed)?;
-        }
-        Ok(())
+        internal::pay_out(self, target, unbonds)
     }
 
     fn unbond(&mut self, validator: PublicKey, maybe_amount: Option<U512>) -> Result<()> {
@@ -54,11 +68,13 @@ pub trait ProofOfStake:
 
         // TODO: Remove this and set nonzero delays once the system calls `step` in each block.
         let unbonds = internal::step(self, timestamp)?;
-        for entry in unbonds {
-            self.transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
-                .map_err(|_| Error::UnbondTransferFailed)?;
-        }
-        Ok(())
+        internal::pay_out(self, pos_purse, unbonds)
+    }
+
+    /// Removes the whole stake of `validator` as a penalty, moving it from the bonding purse to the
+    /// rewards purse.  Only the system account may slash, i.e. from a system deploy.
+    fn slash(&mut self, validator: PublicKey) -> Result<()> {
+        internal::slash(self, validator)
     }
 
     fn get_payment_purse(&self) -> Result<URef> {
@@ -67,6 +83,11 @@ pub trait ProofOfStake:
         Ok(URef::new(purse.addr(), AccessRights::READ_ADD))
     }
 
+    fn get_payment_purse_balance(&mut self) -> Result<U512> {
+        internal::get_payment_purse_balance(self)
+    }
+
+    /// Sets the purse to receive the refund of the current deploy. An `ADD`-only uref suffices.
     fn set_refund_purse(&mut self, purse: URef) -> Result<()> {
         internal::set_refund(self, purse)
     }
@@ -82,15 +103,34 @@ pub trait ProofOfStake:
     fn finalize_payment(&mut self, amount_spent: U512, account: PublicKey) -> Result<()> {
         internal::finalize_payment(self, amount_spent, account)
     }
+
+    /// Moves at most `max_count` of the stakes which are still encoded in the names of the named
+    /// keys into the stored values, and returns the number of stakes left to move.
+    ///
+    /// Stakes are read in either format, so a validator set too large to migrate within a single
+    /// execution can be migrated in batches over several calls.
+    fn migrate_stakes(&mut self, max_count: u32) -> Result<u32> {
+        self.migrate_legacy(max_count)
+    }
+
+    /// Moves the bookkeeping kept under named keys which predate the namespace reserved for
+    /// system-managed keys into that namespace.
+    fn migrate_reserved_keys(&mut self) -> Result<()> {
+        internal::migrate_reserved_keys(self);
+        Ok(())
+    }
 }
 
 mod internal {
-    use alloc::vec::Vec;
+    use alloc::{format, string::String, vec::Vec};
 
     use types::{
         account::PublicKey,
         system_contract_errors::pos::{Error, PurseLookupError, Result},
-        BlockTime, Key, Phase, URef, U512,
+        system_contract_names::pos::{
+            BONDING_PURSE_KEY, PAYMENT_PURSE_KEY, REFUND_PURSE_KEY, REWARDS_PURSE_KEY,
+        },
+        AccessRights, BlockTime, Key, Phase, URef, U512,
     };
 
     use crate::{
@@ -101,19 +141,12 @@ mod internal {
     /// Account used to run system functions (in particular `finalize_payment`).
     const SYSTEM_ACCOUNT: PublicKey = PublicKey::ed25519_from([0u8; 32]);
 
-    /// The uref name where the PoS purse is stored. It contains all staked motes, and all unbonded
-    /// motes that are yet to be paid out.
-    const BONDING_PURSE_KEY: &str = "pos_bonding_purse";
-
-    /// The uref name where the PoS accepts payment for computation on behalf of validators.
-    const PAYMENT_PURSE_KEY: &str = "pos_payment_purse";
+    /// The prefix of the names under which the PoS stores the purse each validator bonded from,
+    /// followed by the hex-formatted public key of the validator.
+    const PAYOUT_PURSE_KEY_PREFIX: &str = "__system/pos_payout_purse_";
 
-    /// The uref name where the PoS holds validator earnings before distributing them.
-    const REWARDS_PURSE_KEY: &str = "pos_rewards_purse";
-
-    /// The uref name where the PoS will refund unused payment back to the user. The uref this name
-    /// corresponds to is set by the user.
-    const REFUND_PURSE_KEY: &str = "pos_refund_purse";
+    /// The name `REFUND_PURSE_KEY` had before the reserved namespace was introduced.
+    const LEGACY_REFUND_PURSE_KEY: &str = "pos_refund_purse";
 
     /// The time from a bonding request until the bond becomes effective and part of the stake.
     const BOND_DELAY: u64 = 0;
@@ -143,7 +176,7 @@ mod internal {
         let mut stakes = provider.read()?;
         // Simulate applying all earlier bonds. The modified stakes are not written.
         for entry in &queue.0 {
-            stakes.bond(&entry.validator, entry.amount);
+            stakes.bond(&entry.validator, entry.amount)?;
         }
         stakes.validate_bonding(&validator, amount)?;
 
@@ -185,9 +218,10 @@ mod internal {
         let mut bonding_queue = provider.read_bonding();
         let mut unbonding_queue = provider.read_unbonding();
 
-        let bonds = bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(BOND_DELAY)));
+        let bonds =
+            bonding_queue.pop_due(timestamp.saturating_sub(BlockTime::from_millis(BOND_DELAY)));
         let unbonds =
-            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::new(UNBOND_DELAY)));
+            unbonding_queue.pop_due(timestamp.saturating_sub(BlockTime::from_millis(UNBOND_DELAY)));
 
         if !unbonds.is_empty() {
             provider.write_unbonding(unbonding_queue);
@@ -197,7 +231,7 @@ mod internal {
             provider.write_bonding(bonding_queue);
             let mut stakes = provider.read()?;
             for entry in bonds {
-                stakes.bond(&entry.validator, entry.amount);
+                stakes.bond(&entry.validator, entry.amount)?;
             }
             provider.write(&stakes);
         }
@@ -205,6 +239,81 @@ mod internal {
         Ok(unbonds)
     }
 
+    /// Returns the name of the named key under which the purse receiving the unbonded stake of
+    /// `validator` is stored.
+    fn payout_purse_key_name(validator: &PublicKey) -> String {
+        let hex_key = base16::encode_lower(&validator.as_bytes());
+        format!("{}{}", PAYOUT_PURSE_KEY_PREFIX, hex_key)
+    }
+
+    /// Records `purse` as the purse receiving the unbonded stake of `validator`.  Only `ADD`
+    /// rights are kept, which is all the payout requires.
+    pub fn set_payout_purse<R: RuntimeProvider>(
+        runtime_provider: &mut R,
+        validator: &PublicKey,
+        purse: URef,
+    ) {
+        let name = payout_purse_key_name(validator);
+        runtime_provider.put_key(&name, Key::URef(purse.as_add_only()));
+    }
+
+    /// Pays the due `unbonds` out of `pos_purse`, each to the purse the validator bonded from.
+    ///
+    /// Validators with no recorded purse, such as those bonded at genesis, are paid to the main
+    /// purse of their account.  The record is dropped once a validator is no longer bonded.
+    pub fn pay_out<P: MintProvider + RuntimeProvider + StakesProvider>(
+        provider: &mut P,
+        pos_purse: URef,
+        unbonds: Vec<QueueEntry>,
+    ) -> Result<()> {
+        if unbonds.is_empty() {
+            return Ok(());
+        }
+        let stakes = provider.read()?;
+        for entry in unbonds {
+            let name = payout_purse_key_name(&entry.validator);
+            let result = match provider.get_key(&name) {
+                Some(Key::URef(payout_purse)) => {
+                    provider.transfer_purse_to_purse(pos_purse, payout_purse, entry.amount)
+                }
+                _ => provider
+                    .transfer_purse_to_account(pos_purse, entry.validator, entry.amount)
+                    .map(|_| ())
+                    .map_err(|_| ()),
+            };
+            result.map_err(|_| Error::UnbondTransferFailed)?;
+            if !stakes.0.contains_key(&entry.validator) {
+                provider.remove_key(&name);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the stake of `validator` and moves it from the bonding purse to the rewards purse.
+    /// The last bonded validator can't be slashed.
+    pub fn slash<P: MintProvider + RuntimeProvider + StakesProvider>(
+        provider: &mut P,
+        validator: PublicKey,
+    ) -> Result<()> {
+        if provider.get_caller() != SYSTEM_ACCOUNT {
+            return Err(Error::SystemFunctionCalledByUserAccount);
+        }
+
+        let mut stakes = provider.read()?;
+        let amount = stakes.0.remove(&validator).ok_or(Error::NotBonded)?;
+        if stakes.0.is_empty() {
+            return Err(Error::CannotUnbondLastValidator);
+        }
+        provider.write(&stakes);
+        provider.remove_key(&payout_purse_key_name(&validator));
+
+        let bonding_purse = get_bonding_purse(provider)?;
+        let rewards_purse = get_rewards_purse(provider)?;
+        provider
+            .transfer_purse_to_purse(bonding_purse, rewards_purse, amount)
+            .map_err(|_| Error::SlashTransferFailed)
+    }
+
     /// Attempts to look up a purse from the named_keys
     fn get_purse<R: RuntimeProvider>(
         runtime_provider: &R,
@@ -234,17 +343,49 @@ mod internal {
         get_purse::<R>(runtime_provider, REWARDS_PURSE_KEY).map_err(PurseLookupError::rewards)
     }
 
+    /// Returns the balance of the payment purse, i.e. the amount deposited so far for the current
+    /// deploy.  Only available during the payment phase.
+    pub fn get_payment_purse_balance<P: MintProvider + RuntimeProvider>(
+        provider: &mut P,
+    ) -> Result<U512> {
+        if provider.get_phase() != Phase::Payment {
+            return Err(Error::GetPaymentPurseBalanceCalledOutsidePayment);
+        }
+        let payment_purse = get_payment_purse(provider)?;
+        provider
+            .balance(payment_purse)
+            .ok_or(Error::PaymentPurseBalanceNotFound)
+    }
+
     /// Sets the purse where refunds (excess funds not spent to pay for computation) will be sent.
     /// Note that if this function is never called, the default location is the main purse of the
     /// deployer's account.
+    ///
+    /// The caller only needs to pass `ADD` rights on the purse, e.g. a uref narrowed with
+    /// `URef::as_add_only`, which is all the refund transfer requires while finalizing the
+    /// payment.
     pub fn set_refund<R: RuntimeProvider>(runtime_provider: &mut R, purse: URef) -> Result<()> {
         if let Phase::Payment = runtime_provider.get_phase() {
+            match runtime_provider.get_access_rights(purse) {
+                Some(access_rights) if access_rights.contains(AccessRights::ADD) => {}
+                _ => return Err(Error::InsufficientRefundPurseAccessRights),
+            }
             runtime_provider.put_key(REFUND_PURSE_KEY, Key::URef(purse));
             return Ok(());
         }
         Err(Error::SetRefundPurseCalledOutsidePayment)
     }
 
+    /// Removes any refund purse left under its legacy name.
+    ///
+    /// The refund purse is unset at the end of each deploy, so a leftover entry is stale and is
+    /// dropped rather than moved under `REFUND_PURSE_KEY`, where it would receive the next refund.
+    pub fn migrate_reserved_keys<R: RuntimeProvider>(runtime_provider: &mut R) {
+        if runtime_provider.get_key(LEGACY_REFUND_PURSE_KEY).is_some() {
+            runtime_provider.remove_key(LEGACY_REFUND_PURSE_KEY);
+        }
+    }
+
     /// Returns the currently set refund purse.
     pub fn get_refund_purse<R: RuntimeProvider>(runtime_provider: &R) -> Result<Option<URef>> {
         match get_purse::<R>(runtime_provider, REFUND_PURSE_KEY) {
@@ -366,13 +507,17 @@ mod internal {
         }
 
         impl StakesProvider for Provider {
-            fn read(&self) -> Result<Stakes> {
+            fn read(&mut self) -> Result<Stakes> {
                 STAKES.with(|s| Ok(s.borrow().clone()))
             }
 
             fn write(&mut self, stakes: &Stakes) {
                 STAKES.with(|s| s.replace(stakes.clone()));
             }
+
+            fn migrate_legacy(&mut self, _max_count: u32) -> Result<u32> {
+                Ok(0)
+            }
         }
 
         fn assert_stakes(stakes: &[([u8; 32], usize)]) {
@@ -392,28 +537,29 @@ mod internal {
                 &mut provider,
                 U512::from(500),
                 PublicKey::ed25519_from(KEY2),
-                BlockTime::new(1),
+                BlockTime::from_millis(1),
             )
             .expect("bond validator 2");
 
             // Bonding becomes effective only after the delay.
             assert_stakes(&[(KEY1, 1_000)]);
-            step(&mut provider, BlockTime::new(BOND_DELAY)).expect("step 1");
+            step(&mut provider, BlockTime::from_millis(BOND_DELAY)).expect("step 1");
             assert_stakes(&[(KEY1, 1_000)]);
-            step(&mut provider, BlockTime::new(1 + BOND_DELAY)).expect("step 2");
+            step(&mut provider, BlockTime::from_millis(1 + BOND_DELAY)).expect("step 2");
             assert_stakes(&[(KEY1, 1_000), (KEY2, 500)]);
 
             unbond::<Provider>(
                 &mut provider,
                 Some(U512::from(500)),
                 PublicKey::ed25519_from(KEY1),
-                BlockTime::new(2),
+                BlockTime::from_millis(2),
             )
             .expect("partly unbond validator 1");
 
             // Unbonding becomes effective immediately.
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
-            step::<Provider>(&mut provider, BlockTime::new(2 + UNBOND_DELAY)).expect("step 3");
+            step::<Provider>(&mut provider, BlockTime::from_millis(2 + UNBOND_DELAY))
+                .expect("step 3");
             assert_stakes(&[(KEY1, 500), (KEY2, 500)]);
         }
     }
@@ -3,7 +3,7 @@ use core::result;
 
 use types::{
     account::PublicKey,
-    bytesrepr::{self, FromBytes, ToBytes, U64_SERIALIZED_LENGTH},
+    bytesrepr::{self, FromBytes, ToBytes},
     system_contract_errors::pos::{Error, Result},
     BlockTime, CLType, CLTyped, U512,
 };
@@ -30,27 +30,20 @@ impl QueueEntry {
     }
 }
 
+// Serialized as the tuple of its fields.
 impl ToBytes for QueueEntry {
     fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
-        let mut bytes = bytesrepr::allocate_buffer(self)?;
-        bytes.append(&mut self.validator.to_bytes()?);
-        bytes.append(&mut self.amount.to_bytes()?);
-        bytes.append(&mut self.timestamp.to_bytes()?);
-        Ok(bytes)
+        (self.validator, self.amount, self.timestamp).to_bytes()
     }
 
     fn serialized_length(&self) -> usize {
-        self.validator.serialized_length()
-            + self.amount.serialized_length()
-            + self.timestamp.serialized_length()
+        (self.validator, self.amount, self.timestamp).serialized_length()
     }
 }
 
 impl FromBytes for QueueEntry {
     fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
-        let (validator, bytes) = PublicKey::from_bytes(bytes)?;
-        let (amount, bytes) = U512::from_bytes(bytes)?;
-        let (timestamp, bytes) = BlockTime::from_bytes(bytes)?;
+        let ((validator, amount, timestamp), bytes) = FromBytes::from_bytes(bytes)?;
         let entry = QueueEntry {
             validator,
             amount,
@@ -98,31 +91,21 @@ impl Queue {
     }
 }
 
+// Serialized as the list of its entries, matching its `CLType`.
 impl ToBytes for Queue {
     fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
-        let mut bytes = bytesrepr::allocate_buffer(self)?;
-        bytes.append(&mut (self.0.len() as u64).to_bytes()?);
-        for entry in &self.0 {
-            bytes.append(&mut entry.to_bytes()?);
-        }
-        Ok(bytes)
+        self.0.to_bytes()
     }
 
     fn serialized_length(&self) -> usize {
-        U64_SERIALIZED_LENGTH + self.0.iter().map(ToBytes::serialized_length).sum::<usize>()
+        self.0.serialized_length()
     }
 }
 
 impl FromBytes for Queue {
     fn from_bytes(bytes: &[u8]) -> result::Result<(Self, &[u8]), bytesrepr::Error> {
-        let (len, mut bytes) = u64::from_bytes(bytes)?;
-        let mut queue = Vec::new();
-        for _ in 0..len {
-            let (entry, rest) = QueueEntry::from_bytes(bytes)?;
-            bytes = rest;
-            queue.push(entry);
-        }
-        Ok((Queue(queue), bytes))
+        let (entries, bytes) = Vec::<QueueEntry>::from_bytes(bytes)?;
+        Ok((Queue(entries), bytes))
     }
 }
 
@@ -137,7 +120,10 @@ mod tests {
     use alloc::vec;
 
     use types::{
-        account::PublicKey, bytesrepr, system_contract_errors::pos::Error, BlockTime, U512,
+        account::PublicKey,
+        bytesrepr::{self, ToBytes},
+        system_contract_errors::pos::Error,
+        BlockTime, U512,
     };
 
     use super::{Queue, QueueEntry};
@@ -152,15 +138,21 @@ mod tests {
         let val2 = PublicKey::ed25519_from(KEY2);
         let val3 = PublicKey::ed25519_from(KEY3);
         let mut queue: Queue = Default::default();
-        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
-        assert_eq!(Ok(()), queue.push(val2, U512::from(5), BlockTime::new(101)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, U512::from(5), BlockTime::from_millis(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, U512::from(5), BlockTime::from_millis(101))
+        );
         assert_eq!(
             Err(Error::MultipleRequests),
-            queue.push(val1, U512::from(5), BlockTime::new(102))
+            queue.push(val1, U512::from(5), BlockTime::from_millis(102))
         );
         assert_eq!(
             Err(Error::TimeWentBackwards),
-            queue.push(val3, U512::from(5), BlockTime::new(100))
+            queue.push(val3, U512::from(5), BlockTime::from_millis(100))
         );
     }
 
@@ -170,19 +162,32 @@ mod tests {
         let val2 = PublicKey::ed25519_from(KEY2);
         let val3 = PublicKey::ed25519_from(KEY3);
         let mut queue: Queue = Default::default();
-        assert_eq!(Ok(()), queue.push(val1, U512::from(5), BlockTime::new(100)));
-        assert_eq!(Ok(()), queue.push(val2, U512::from(6), BlockTime::new(101)));
-        assert_eq!(Ok(()), queue.push(val3, U512::from(7), BlockTime::new(102)));
+        assert_eq!(
+            Ok(()),
+            queue.push(val1, U512::from(5), BlockTime::from_millis(100))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val2, U512::from(6), BlockTime::from_millis(101))
+        );
+        assert_eq!(
+            Ok(()),
+            queue.push(val3, U512::from(7), BlockTime::from_millis(102))
+        );
         assert_eq!(
             vec![
-                QueueEntry::new(val1, U512::from(5), BlockTime::new(100)),
-                QueueEntry::new(val2, U512::from(6), BlockTime::new(101)),
+                QueueEntry::new(val1, U512::from(5), BlockTime::from_millis(100)),
+                QueueEntry::new(val2, U512::from(6), BlockTime::from_millis(101)),
             ],
-            queue.pop_due(BlockTime::new(101))
+            queue.pop_due(BlockTime::from_millis(101))
         );
         assert_eq!(
-            vec![QueueEntry::new(val3, U512::from(7), BlockTime::new(102)),],
-            queue.pop_due(BlockTime::new(105))
+            vec![QueueEntry::new(
+                val3,
+                U512::from(7),
+                BlockTime::from_millis(102)
+            ),],
+            queue.pop_due(BlockTime::from_millis(105))
         );
     }
 
@@ -192,9 +197,26 @@ mod tests {
         let val2 = PublicKey::ed25519_from(KEY2);
         let val3 = PublicKey::ed25519_from(KEY3);
         let mut queue: Queue = Default::default();
-        queue.push(val1, U512::from(5), BlockTime::new(0)).unwrap();
-        queue.push(val2, U512::from(6), BlockTime::new(1)).unwrap();
-        queue.push(val3, U512::from(7), BlockTime::new(2)).unwrap();
+        queue
+            .push(val1, U512::from(5), BlockTime::from_millis(0))
+            .unwrap();
+        queue
+            .push(val2, U512::from(6), BlockTime::from_millis(1))
+            .unwrap();
+        queue
+            .push(val3, U512::from(7), BlockTime::from_millis(2))
+            .unwrap();
         bytesrepr::test_serialization_roundtrip(&queue);
     }
+
+    #[test]
+    fn should_serialize_as_list_of_tuples() {
+        let val1 = PublicKey::ed25519_from(KEY1);
+        let mut queue: Queue = Default::default();
+        queue
+            .push(val1, U512::from(5), BlockTime::from_millis(0))
+            .unwrap();
+        let entries = vec![(val1, U512::from(5), BlockTime::from_millis(0))];
+        assert_eq!(queue.to_bytes(), entries.to_bytes());
+    }
 }
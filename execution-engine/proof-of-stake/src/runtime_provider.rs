@@ -1,4 +1,4 @@
-use types::{account::PublicKey, BlockTime, Key, Phase};
+use types::{account::PublicKey, AccessRights, BlockTime, Key, Phase, URef};
 
 pub trait RuntimeProvider {
     fn get_key(&self, name: &str) -> Option<Key>;
@@ -12,4 +12,8 @@ pub trait RuntimeProvider {
     fn get_block_time(&self) -> BlockTime;
 
     fn get_caller(&self) -> PublicKey;
+
+    /// Returns the access rights the current context holds for the address of `uref`, or `None`
+    /// if the address is unknown to the current context.
+    fn get_access_rights(&self, uref: URef) -> Option<AccessRights>;
 }
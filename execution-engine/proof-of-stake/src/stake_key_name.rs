@@ -0,0 +1,213 @@
+use alloc::{format, string::String};
+
+use types::{
+    account::PublicKey,
+    system_contract_errors::pos::{Error, Result},
+    U512,
+};
+
+/// The prefix of the names under which the PoS contract stores the validators' stakes.
+pub const STAKE_KEY_PREFIX: &str = "v_";
+
+/// The parsed name of a named key holding a validator's stake.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StakeKeyName {
+    /// A name formatted as "v_<hex-formatted-PublicKey>_<bond-amount>".  The stake is encoded in
+    /// the name itself and the named key points to a placeholder.
+    Legacy(PublicKey, U512),
+    /// A name formatted as "v_<hex-formatted-PublicKey>".  The stake is stored as a `U512` under
+    /// the `URef` the named key points to.
+    Current(PublicKey),
+}
+
+impl StakeKeyName {
+    /// Returns the validator the stake belongs to.
+    pub fn validator(&self) -> PublicKey {
+        match self {
+            StakeKeyName::Legacy(validator, _) | StakeKeyName::Current(validator) => *validator,
+        }
+    }
+}
+
+/// Returns the name of the named key under which the stake of `validator` is stored.
+pub fn stake_key_name(validator: &PublicKey) -> String {
+    let hex_key = base16::encode_lower(&validator.as_bytes());
+    format!("{}{}", STAKE_KEY_PREFIX, hex_key)
+}
+
+/// Parses the name of one of the PoS contract's named keys.
+///
+/// Returns `Ok(None)` if `name` doesn't denote a stake, and an error if it does but is malformed.
+pub fn parse_stake_key_name(name: &str) -> Result<Option<StakeKeyName>> {
+    let mut split_name = name.split('_');
+    if Some("v") != split_name.next() {
+        return Ok(None);
+    }
+    let hex_key = split_name
+        .next()
+        .ok_or(Error::StakesKeyDeserializationFailed)?;
+    if hex_key.len() != 64 {
+        return Err(Error::StakesKeyDeserializationFailed);
+    }
+    let mut key_bytes = [0u8; 32];
+    let _bytes_written = base16::decode_slice(hex_key, &mut key_bytes)
+        .map_err(|_| Error::StakesKeyDeserializationFailed)?;
+    debug_assert!(_bytes_written == key_bytes.len());
+    let validator = PublicKey::ed25519_from(key_bytes);
+
+    let balance = match split_name.next() {
+        None => return Ok(Some(StakeKeyName::Current(validator))),
+        Some(balance) => balance,
+    };
+    if balance.is_empty() || split_name.next().is_some() {
+        return Err(Error::StakesDeserializationFailed);
+    }
+    // `from_dec_str` rejects values which don't fit in a `U512`.
+    let balance = U512::from_dec_str(balance).map_err(|_| Error::StakesDeserializationFailed)?;
+    Ok(Some(StakeKeyName::Legacy(validator, balance)))
+}
+
+/// Parses a name in the legacy "v_<hex-formatted-PublicKey>_<bond-amount>" format back into the
+/// `PublicKey` and bond amount.
+///
+/// Returns `None` for any other name, including ones in the current format.
+pub fn pos_validator_key_name_to_tuple(name: &str) -> Option<(PublicKey, U512)> {
+    match parse_stake_key_name(name) {
+        Ok(Some(StakeKeyName::Legacy(validator, balance))) => Some((validator, balance)),
+        _ => None,
+    }
+}
+
+/// Parses a name in the current "v_<hex-formatted-PublicKey>" format back into the `PublicKey`.
+///
+/// Returns `None` for any other name, including ones in the legacy format.
+pub fn pos_validator_key_name_to_public_key(name: &str) -> Option<PublicKey> {
+    match parse_stake_key_name(name) {
+        Ok(Some(StakeKeyName::Current(validator))) => Some(validator),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::format;
+
+    use types::{
+        account::PublicKey, system_contract_errors::pos::Error,
+        system_contract_names::pos::BONDING_PURSE_KEY, U512,
+    };
+
+    use super::{
+        parse_stake_key_name, pos_validator_key_name_to_public_key,
+        pos_validator_key_name_to_tuple, stake_key_name, StakeKeyName,
+    };
+
+    const KEY: [u8; 32] = [1; 32];
+
+    /// Names which don't denote a stake in either format.
+    fn malformed_names() -> Vec<String> {
+        let hex_key = base16::encode_lower(&KEY);
+        let stake = U512::from(100);
+        vec![
+            String::new(),
+            String::from(BONDING_PURSE_KEY),
+            format!("a_{}_{}", hex_key, stake),
+            format!("_{}_{}", hex_key, stake),
+            format!("v_{}_{}", base16::encode_lower(&[1u8; 31]), stake),
+            format!("v_{}00_{}", hex_key, stake),
+            format!("v_{}0g_{}", base16::encode_lower(&[1u8; 31]), stake),
+            format!("v__{}", stake),
+            format!("v_{}", stake),
+            format!("v_{}_a", hex_key),
+            format!("v_{}_", hex_key),
+            format!("v_{}_1_2", hex_key),
+            format!("v_{}_{}0", hex_key, U512::MAX),
+            format!("V_{}", hex_key),
+        ]
+    }
+
+    #[test]
+    fn should_roundtrip_stake_key_name() {
+        let validator = PublicKey::ed25519_from(KEY);
+        let name = stake_key_name(&validator);
+        assert_eq!(
+            Ok(Some(StakeKeyName::Current(validator))),
+            parse_stake_key_name(&name)
+        );
+    }
+
+    #[test]
+    fn should_parse_legacy_stake_key_name() {
+        let validator = PublicKey::ed25519_from(KEY);
+        let name = format!("{}_{}", stake_key_name(&validator), 100);
+        assert_eq!(
+            Ok(Some(StakeKeyName::Legacy(validator, U512::from(100)))),
+            parse_stake_key_name(&name)
+        );
+    }
+
+    #[test]
+    fn should_ignore_other_names() {
+        assert_eq!(Ok(None), parse_stake_key_name(BONDING_PURSE_KEY));
+        assert_eq!(Ok(None), parse_stake_key_name(""));
+    }
+
+    #[test]
+    fn should_not_parse_malformed_stake_key_names() {
+        let name = stake_key_name(&PublicKey::ed25519_from(KEY));
+
+        let too_long = format!("{}00", name);
+        for malformed_key in &["v", "v_", "v_00", too_long.as_str()] {
+            assert_eq!(
+                Err(Error::StakesKeyDeserializationFailed),
+                parse_stake_key_name(malformed_key)
+            );
+        }
+
+        let too_large = format!("{}_{}0", name, U512::MAX);
+        for malformed_stake in &[
+            format!("{}_", name),
+            format!("{}_a", name),
+            format!("{}_1_2", name),
+            too_large,
+        ] {
+            assert_eq!(
+                Err(Error::StakesDeserializationFailed),
+                parse_stake_key_name(malformed_stake)
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_legacy_name_to_tuple() {
+        let validator = PublicKey::ed25519_from(KEY);
+        let stake = U512::from(100);
+        let name = format!("{}_{}", stake_key_name(&validator), stake);
+        assert_eq!(
+            Some((validator, stake)),
+            pos_validator_key_name_to_tuple(&name)
+        );
+        assert_eq!(None, pos_validator_key_name_to_public_key(&name));
+    }
+
+    #[test]
+    fn should_parse_current_name_to_public_key() {
+        let validator = PublicKey::ed25519_from(KEY);
+        let name = stake_key_name(&validator);
+        assert_eq!(Some(validator), pos_validator_key_name_to_public_key(&name));
+        assert_eq!(None, pos_validator_key_name_to_tuple(&name));
+    }
+
+    #[test]
+    fn should_not_parse_malformed_names_in_either_format() {
+        for name in malformed_names() {
+            assert_eq!(None, pos_validator_key_name_to_tuple(&name), "{}", name);
+            assert_eq!(
+                None,
+                pos_validator_key_name_to_public_key(&name),
+                "{}",
+                name
+            );
+        }
+    }
+}
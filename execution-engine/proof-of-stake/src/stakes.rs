@@ -1,10 +1,6 @@
-use alloc::{
-    collections::{
-        btree_map::{Iter, Values},
-        BTreeMap,
-    },
-    format,
-    string::String,
+use alloc::collections::{
+    btree_map::{Iter, Values},
+    BTreeMap,
 };
 
 use types::{
@@ -48,18 +44,13 @@ impl Stakes {
         self.0.values()
     }
 
-    pub fn strings(&self) -> impl Iterator<Item = String> + '_ {
-        self.iter().map(|(public_key, balance)| {
-            let key_bytes = public_key.as_bytes();
-            let hex_key = base16::encode_lower(&key_bytes);
-            format!("v_{}_{}", hex_key, balance)
+    /// Returns the total stakes, or an error if the sum overflows.
+    pub fn total_bonds(&self) -> Result<U512> {
+        self.values().try_fold(U512::zero(), |sum, stake| {
+            sum.checked_add(*stake).ok_or(Error::ArithmeticOverflow)
         })
     }
 
-    pub fn total_bonds(&self) -> U512 {
-        self.values().fold(U512::zero(), |x, y| x + y)
-    }
-
     /// If `maybe_amount` is `None`, removes all the validator's stakes,
     /// otherwise subtracts the given amount. If the stakes are lower than
     /// the specified amount, it also subtracts all the stakes.
@@ -74,7 +65,7 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let max_decrease = MAX_DECREASE.min(self.sum() * MAX_REL_DECREASE / 1_000_000);
+        let max_decrease = MAX_DECREASE.min(rel_limit(self.total_bonds()?, MAX_REL_DECREASE)?);
 
         if let Some(amount) = maybe_amount {
             // The minimum stake value to not violate the maximum spread.
@@ -110,11 +101,17 @@ impl Stakes {
     }
 
     /// Adds `amount` to the validator's stakes.
-    pub fn bond(&mut self, validator: &PublicKey, amount: U512) {
-        self.0
-            .entry(*validator)
-            .and_modify(|x| *x += amount)
-            .or_insert(amount);
+    ///
+    /// Returns an error, leaving the stakes unchanged, if the validator's stake or the total stakes
+    /// would overflow.
+    pub fn bond(&mut self, validator: &PublicKey, amount: U512) -> Result<()> {
+        self.total_bonds()?
+            .checked_add(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let stake = self.0.entry(*validator).or_insert_with(U512::zero);
+        // Can't overflow, since the stake is at most the total.
+        *stake += amount;
+        Ok(())
     }
 
     /// Returns an error if bonding the specified amount is not allowed.
@@ -127,11 +124,14 @@ impl Stakes {
             .max_without(validator)
             .unwrap_or_else(U512::zero)
             .saturating_sub(MAX_SPREAD);
-        let stake = self.0.get(validator).map(|s| *s + amount).unwrap_or(amount);
+        let stake = match self.0.get(validator) {
+            Some(stake) => stake.checked_add(amount).ok_or(Error::ArithmeticOverflow)?,
+            None => amount,
+        };
         if stake > max || stake < min {
             return Err(Error::SpreadTooHigh);
         }
-        let max_increase = MAX_INCREASE.min(self.sum() * MAX_REL_INCREASE / 1_000_000);
+        let max_increase = MAX_INCREASE.min(rel_limit(self.total_bonds()?, MAX_REL_INCREASE)?);
         if (stake.is_zero() && amount > min.saturating_add(max_increase))
             || (!stake.is_zero() && amount > max_increase)
         {
@@ -159,17 +159,21 @@ impl Stakes {
             .max()
             .cloned()
     }
+}
 
-    /// Returns the total stakes.
-    fn sum(&self) -> U512 {
-        self.0
-            .values()
-            .fold(U512::zero(), |sum, s| sum.saturating_add(*s))
-    }
+/// Returns `millionths` millionths of `total`, rounded down, or an error if the intermediate
+/// product overflows.
+fn rel_limit(total: U512, millionths: u64) -> Result<U512> {
+    total
+        .checked_mul(U512::from(millionths))
+        .map(|product| product / 1_000_000)
+        .ok_or(Error::ArithmeticOverflow)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::btree_map, prelude::*};
+
     use types::{account::PublicKey, system_contract_errors::pos::Error, U512};
 
     use super::Stakes;
@@ -193,7 +197,9 @@ mod tests {
             Ok(()),
             stakes.validate_bonding(&PublicKey::ed25519_from(KEY1), U512::from(5))
         );
-        stakes.bond(&PublicKey::ed25519_from(KEY1), U512::from(5));
+        stakes
+            .bond(&PublicKey::ed25519_from(KEY1), U512::from(5))
+            .expect("should bond");
         assert_eq!(new_stakes(&[(KEY1, 5), (KEY2, 100)]), stakes);
     }
 
@@ -204,7 +210,9 @@ mod tests {
             Ok(()),
             stakes.validate_bonding(&PublicKey::ed25519_from(KEY1), U512::from(4))
         );
-        stakes.bond(&PublicKey::ed25519_from(KEY1), U512::from(4));
+        stakes
+            .bond(&PublicKey::ed25519_from(KEY1), U512::from(4))
+            .expect("should bond");
         assert_eq!(new_stakes(&[(KEY1, 54), (KEY2, 100)]), stakes);
     }
 
@@ -280,4 +288,51 @@ mod tests {
             "Failed to unbond the maximum amount."
         );
     }
+
+    #[test]
+    fn test_bond_overflow() {
+        let mut stakes = new_stakes(&[(KEY1, 1), (KEY2, 1)]);
+        stakes
+            .0
+            .insert(PublicKey::ed25519_from(KEY2), U512::MAX - 1);
+        let expected = stakes.clone();
+        assert_eq!(
+            Err(Error::ArithmeticOverflow),
+            stakes.bond(&PublicKey::ed25519_from(KEY1), U512::one())
+        );
+        assert_eq!(expected, stakes);
+        assert_eq!(
+            Err(Error::ArithmeticOverflow),
+            stakes.validate_bonding(&PublicKey::ed25519_from(KEY2), U512::from(2))
+        );
+    }
+
+    fn stakes_arb() -> impl Strategy<Value = Stakes> {
+        btree_map(
+            any::<[u8; 32]>().prop_map(PublicKey::ed25519_from),
+            any::<u64>().prop_map(U512::from),
+            1..50,
+        )
+        .prop_map(Stakes)
+    }
+
+    proptest! {
+        #[test]
+        fn should_conserve_motes_when_bonding_and_unbonding(
+            mut stakes in stakes_arb(),
+            validator in any::<[u8; 32]>().prop_map(PublicKey::ed25519_from),
+            amount in any::<u64>().prop_map(U512::from)
+        ) {
+            let initial_total = stakes.total_bonds().expect("should sum");
+
+            stakes.bond(&validator, amount).expect("should bond");
+            let bonded_total = stakes.total_bonds().expect("should sum");
+            prop_assert_eq!(bonded_total, initial_total + amount);
+
+            if let Ok(payout) = stakes.unbond(&validator, Some(amount)) {
+                let unbonded_total = stakes.total_bonds().expect("should sum");
+                prop_assert_eq!(unbonded_total + payout, bonded_total);
+            }
+        }
+    }
 }
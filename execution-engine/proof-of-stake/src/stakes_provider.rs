@@ -2,7 +2,11 @@ use crate::{stakes::Stakes, Result};
 
 /// A `StakesProvider` that reads and writes the stakes to/from the contract's known urefs.
 pub trait StakesProvider {
-    fn read(&self) -> Result<Stakes>;
+    fn read(&mut self) -> Result<Stakes>;
 
     fn write(&mut self, stakes: &Stakes);
+
+    /// Moves at most `max_count` of the stakes which are still encoded in the names of the named
+    /// keys into urefs, and returns the number of stakes left in that legacy format.
+    fn migrate_legacy(&mut self, max_count: u32) -> Result<u32>;
 }
@@ -7,9 +7,21 @@ use crate::bytesrepr;
 /// The number of bytes in a serialized [`AccessRights`].
 pub const ACCESS_RIGHTS_SERIALIZED_LENGTH: usize = 1;
 
+/// The bits of a serialized [`AccessRights`] which are zero in the canonical encoding.
+pub const ACCESS_RIGHTS_RESERVED_BITS: u8 = 0b1111_0000;
+
 bitflags! {
     /// A struct which behaves like a set of bitflags to define access rights associated with a
     /// [`URef`](crate::URef).
+    ///
+    /// The canonical encoding, used by both `bytesrepr` and the Protobuf mappings, is a single
+    /// byte where bit 0 is `READ`, bit 1 is `WRITE`, bit 2 is `ADD` and bit 3 is `PUBLIC_READ`.
+    /// The remaining bits are reserved (see [`ACCESS_RIGHTS_RESERVED_BITS`]).
+    ///
+    /// Values stored before the encoding was made canonical may have reserved bits set.
+    /// `bytesrepr` normalizes such legacy values by clearing those bits, so re-encoding a decoded
+    /// value always yields the canonical byte.  The Protobuf mappings never carry legacy values and
+    /// reject them.
     #[allow(clippy::derive_hash_xor_eq)]
     pub struct AccessRights: u8 {
         /// No permissions
@@ -28,6 +40,12 @@ bitflags! {
         const ADD_WRITE      = Self::ADD.bits  | Self::WRITE.bits;
         /// Permission to read, add to, or write the value under the associated `URef`.
         const READ_ADD_WRITE = Self::READ.bits | Self::ADD.bits | Self::WRITE.bits;
+        /// Marks the associated `URef` as readable by any context, including ones which were never
+        /// given the `URef`.
+        ///
+        /// The flag only grants reads, and only if the `URef` was created public, i.e. the flag
+        /// can't be added to an existing `URef`.
+        const PUBLIC_READ = 0b1000;
     }
 }
 
@@ -47,6 +65,11 @@ impl AccessRights {
         self & AccessRights::ADD == AccessRights::ADD
     }
 
+    /// Returns `true` if the `PUBLIC_READ` flag is set.
+    pub fn is_publicly_readable(self) -> bool {
+        self & AccessRights::PUBLIC_READ == AccessRights::PUBLIC_READ
+    }
+
     /// Returns `true` if no flags are set.
     pub fn is_none(self) -> bool {
         self == AccessRights::NONE
@@ -55,7 +78,14 @@ impl AccessRights {
 
 impl core::fmt::Display for AccessRights {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        match *self {
+        let rights = *self - AccessRights::PUBLIC_READ;
+        if self.is_publicly_readable() {
+            return match rights {
+                AccessRights::NONE => write!(f, "PUBLIC"),
+                _ => write!(f, "{}_PUBLIC", rights),
+            };
+        }
+        match rights {
             AccessRights::NONE => write!(f, "NONE"),
             AccessRights::READ => write!(f, "READ"),
             AccessRights::WRITE => write!(f, "WRITE"),
@@ -82,16 +112,55 @@ impl bytesrepr::ToBytes for AccessRights {
 impl bytesrepr::FromBytes for AccessRights {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (id, rem) = u8::from_bytes(bytes)?;
-        match AccessRights::from_bits(id) {
-            Some(rights) => Ok((rights, rem)),
-            None => Err(bytesrepr::Error::Formatting),
-        }
+        Ok((AccessRights::from_bits_truncate(id), rem))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bytesrepr::{FromBytes, ToBytes};
+
+    const ALL_PRIVATE_ACCESS_RIGHTS: [AccessRights; 8] = [
+        AccessRights::NONE,
+        AccessRights::READ,
+        AccessRights::WRITE,
+        AccessRights::ADD,
+        AccessRights::READ_ADD,
+        AccessRights::READ_WRITE,
+        AccessRights::ADD_WRITE,
+        AccessRights::READ_ADD_WRITE,
+    ];
+
+    fn all_access_rights() -> Vec<AccessRights> {
+        ALL_PRIVATE_ACCESS_RIGHTS
+            .iter()
+            .flat_map(|rights| vec![*rights, *rights | AccessRights::PUBLIC_READ])
+            .collect()
+    }
+
+    #[test]
+    fn should_decode_every_byte_to_documented_rights() {
+        for bits in 0..=u8::max_value() {
+            let (access_rights, rem) =
+                AccessRights::from_bytes(&[bits]).expect("should decode access rights");
+            assert!(rem.is_empty());
+            assert!(all_access_rights().contains(&access_rights));
+            let canonical_bits = bits & !ACCESS_RIGHTS_RESERVED_BITS;
+            assert_eq!(access_rights.to_bytes().unwrap(), vec![canonical_bits]);
+        }
+    }
+
+    #[test]
+    fn should_normalize_legacy_encoding() {
+        // READ_ADD with the upper reserved bits set, as found in legacy stored values
+        let legacy_bytes = [0b1010_0101];
+        let (access_rights, rem) =
+            AccessRights::from_bytes(&legacy_bytes).expect("should decode legacy access rights");
+        assert!(rem.is_empty());
+        assert_eq!(access_rights, AccessRights::READ_ADD);
+        assert_eq!(access_rights.to_bytes().unwrap(), vec![0b0000_0101]);
+    }
 
     fn test_readable(right: AccessRights, is_true: bool) {
         assert_eq!(right.is_readable(), is_true)
@@ -137,4 +206,28 @@ mod tests {
         test_addable(AccessRights::WRITE, false);
         test_addable(AccessRights::READ_ADD_WRITE, true);
     }
+
+    #[test]
+    fn should_display_public_read_as_suffix() {
+        assert_eq!(format!("{}", AccessRights::PUBLIC_READ), "PUBLIC");
+        assert_eq!(
+            format!("{}", AccessRights::READ | AccessRights::PUBLIC_READ),
+            "READ_PUBLIC"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                AccessRights::READ_ADD_WRITE | AccessRights::PUBLIC_READ
+            ),
+            "READ_ADD_WRITE_PUBLIC"
+        );
+    }
+
+    #[test]
+    fn test_is_publicly_readable() {
+        for rights in ALL_PRIVATE_ACCESS_RIGHTS.iter() {
+            assert!(!rights.is_publicly_readable());
+            assert!((*rights | AccessRights::PUBLIC_READ).is_publicly_readable());
+        }
+    }
 }
@@ -11,7 +11,7 @@ use crate::{
         TryFromSliceForPublicKeyError, UpdateKeyFailure,
     },
     bytesrepr,
-    system_contract_errors::{mint, pos},
+    system_contract_errors::{auction, mint, pos},
     CLValueError,
 };
 
@@ -27,12 +27,22 @@ const POS_ERROR_OFFSET: u32 = RESERVED_ERROR_MAX - u8::MAX as u32; // 65280..=65
 /// added to them when being converted to a `u32`.
 const MINT_ERROR_OFFSET: u32 = (POS_ERROR_OFFSET - 1) - u8::MAX as u32; // 65024..=65279
 
+/// Auction errors (defined in "types/src/system_contract_errors/auction.rs") will have this value
+/// added to them when being converted to a `u32`.
+const AUCTION_ERROR_OFFSET: u32 = (MINT_ERROR_OFFSET - 1) - u8::MAX as u32; // 64768..=65023
+
 /// Minimum value of user error's inclusive range.
 const USER_ERROR_MIN: u32 = RESERVED_ERROR_MAX + 1;
 
 /// Maximum value of user error's inclusive range.
 const USER_ERROR_MAX: u32 = 2 * RESERVED_ERROR_MAX + 1;
 
+/// Minimum value of auction error's inclusive range.
+const AUCTION_ERROR_MIN: u32 = AUCTION_ERROR_OFFSET;
+
+/// Maximum value of auction error's inclusive range.
+const AUCTION_ERROR_MAX: u32 = MINT_ERROR_OFFSET - 1;
+
 /// Minimum value of Mint error's inclusive range.
 const MINT_ERROR_MIN: u32 = MINT_ERROR_OFFSET;
 
@@ -52,12 +62,13 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 ///
 /// The variants are split into numeric ranges as follows:
 ///
-/// | Inclusive range | Variant(s)                                   |
-/// | ----------------| ---------------------------------------------|
-/// | [1, 65023]      | all except `Mint`, `ProofOfStake` and `User` |
-/// | [65024, 65279]  | `Mint`                                       |
-/// | [65280, 65535]  | `ProofOfStake`                               |
-/// | [65536, 131071] | `User`                                       |
+/// | Inclusive range | Variant(s)                                              |
+/// | ----------------| --------------------------------------------------------|
+/// | [1, 64767]      | all except `Auction`, `Mint`, `ProofOfStake` and `User` |
+/// | [64768, 65023]  | `Auction`                                               |
+/// | [65024, 65279]  | `Mint`                                                  |
+/// | [65280, 65535]  | `ProofOfStake`                                          |
+/// | [65536, 131071] | `User`                                                  |
 ///
 /// ## Mappings
 ///
@@ -172,6 +183,60 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// # show_and_check!(
 /// 34 => HostBufferFull
 /// # );
+/// # show_and_check!(
+/// 35 => AllocLayout
+/// # );
+/// # show_and_check!(
+/// 36 => ReadOnlyContext
+/// # );
+/// # show_and_check!(
+/// 37 => NamedKeyNotFound
+/// # );
+/// # show_and_check!(
+/// 38 => NamedKeyAlreadyExists
+/// # );
+///
+/// // Auction errors:
+/// use casperlabs_types::system_contract_errors::auction::Error as AuctionError;
+/// # show_and_check!(
+/// 64_768 => AuctionError::CallerNotValidator
+/// # );
+/// # show_and_check!(
+/// 64_769 => AuctionError::ZeroBid
+/// # );
+/// # show_and_check!(
+/// 64_770 => AuctionError::DelegationRateTooHigh
+/// # );
+/// # show_and_check!(
+/// 64_771 => AuctionError::BidNotFound
+/// # );
+/// # show_and_check!(
+/// 64_772 => AuctionError::BidLocked
+/// # );
+/// # show_and_check!(
+/// 64_773 => AuctionError::EraEndNotIncreasing
+/// # );
+/// # show_and_check!(
+/// 64_774 => AuctionError::ArithmeticOverflow
+/// # );
+/// # show_and_check!(
+/// 64_775 => AuctionError::BidTransferFailed
+/// # );
+/// # show_and_check!(
+/// 64_776 => AuctionError::WithdrawTransferFailed
+/// # );
+/// # show_and_check!(
+/// 64_777 => AuctionError::SystemFunctionCalledByUserAccount
+/// # );
+/// # show_and_check!(
+/// 64_778 => AuctionError::NamedKeyNotFound
+/// # );
+/// # show_and_check!(
+/// 64_779 => AuctionError::NamedKeyUnexpectedType
+/// # );
+/// # show_and_check!(
+/// 64_780 => AuctionError::InvalidBids
+/// # );
 ///
 /// // Mint errors:
 /// use casperlabs_types::system_contract_errors::mint::Error as MintError;
@@ -182,7 +247,7 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// 65_025 => MintError::SourceNotFound
 /// # );
 /// # show_and_check!(
-/// 65_026 => MintError::DestNotFound
+/// 65_026 => MintError::DestinationPurseNotFound
 /// # );
 /// # show_and_check!(
 /// 65_027 => MintError::InvalidURef
@@ -199,6 +264,15 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// # show_and_check!(
 /// 65_031 => MintError::PurseNotFound
 /// # );
+/// # show_and_check!(
+/// 65_032 => MintError::PurseRevoked
+/// # );
+/// # show_and_check!(
+/// 65_033 => MintError::TotalSupplyOverflow
+/// # );
+/// # show_and_check!(
+/// 65_034 => MintError::InsufficientAllowance
+/// # );
 ///
 /// // Proof of stake errors:
 /// use casperlabs_types::system_contract_errors::pos::Error as PosError;
@@ -283,6 +357,21 @@ const POS_ERROR_MAX: u32 = RESERVED_ERROR_MAX;
 /// # show_and_check!(
 /// 65_306 => PosError::SetRefundPurseCalledOutsidePayment
 /// # );
+/// # show_and_check!(
+/// 65_307 => PosError::ArithmeticOverflow
+/// # );
+/// # show_and_check!(
+/// 65_308 => PosError::InsufficientRefundPurseAccessRights
+/// # );
+/// # show_and_check!(
+/// 65_309 => PosError::GetPaymentPurseBalanceCalledOutsidePayment
+/// # );
+/// # show_and_check!(
+/// 65_310 => PosError::InsufficientBondingPurseAccessRights
+/// # );
+/// # show_and_check!(
+/// 65_311 => PosError::SlashTransferFailed
+/// # );
 ///
 /// // User-defined errors:
 /// # show_and_check!(
@@ -400,6 +489,14 @@ pub enum ApiError {
     HostBufferFull,
     /// Could not lay out an array in memory
     AllocLayout,
+    /// A contract called via `call_contract_read_only` attempted to modify global state.
+    ReadOnlyContext,
+    /// The current context has no named key with the given name.
+    NamedKeyNotFound,
+    /// The current context already has a named key with the given name.
+    NamedKeyAlreadyExists,
+    /// Error specific to the auction contract.
+    Auction(u8),
     /// Error specific to Mint contract.
     Mint(u8),
     /// Error specific to Proof of Stake contract.
@@ -484,6 +581,12 @@ impl From<TryFromSliceForPublicKeyError> for ApiError {
     }
 }
 
+impl From<auction::Error> for ApiError {
+    fn from(error: auction::Error) -> Self {
+        ApiError::Auction(error as u8)
+    }
+}
+
 impl From<mint::Error> for ApiError {
     fn from(error: mint::Error) -> Self {
         ApiError::Mint(error as u8)
@@ -534,6 +637,10 @@ impl From<ApiError> for u32 {
             ApiError::HostBufferEmpty => 33,
             ApiError::HostBufferFull => 34,
             ApiError::AllocLayout => 35,
+            ApiError::ReadOnlyContext => 36,
+            ApiError::NamedKeyNotFound => 37,
+            ApiError::NamedKeyAlreadyExists => 38,
+            ApiError::Auction(value) => AUCTION_ERROR_OFFSET + u32::from(value),
             ApiError::Mint(value) => MINT_ERROR_OFFSET + u32::from(value),
             ApiError::ProofOfStake(value) => POS_ERROR_OFFSET + u32::from(value),
             ApiError::User(value) => RESERVED_ERROR_MAX + 1 + u32::from(value),
@@ -579,9 +686,13 @@ impl From<u32> for ApiError {
             33 => ApiError::HostBufferEmpty,
             34 => ApiError::HostBufferFull,
             35 => ApiError::AllocLayout,
+            36 => ApiError::ReadOnlyContext,
+            37 => ApiError::NamedKeyNotFound,
+            38 => ApiError::NamedKeyAlreadyExists,
             USER_ERROR_MIN..=USER_ERROR_MAX => ApiError::User(value as u16),
             POS_ERROR_MIN..=POS_ERROR_MAX => ApiError::ProofOfStake(value as u8),
             MINT_ERROR_MIN..=MINT_ERROR_MAX => ApiError::Mint(value as u8),
+            AUCTION_ERROR_MIN..=AUCTION_ERROR_MAX => ApiError::Auction(value as u8),
             _ => ApiError::Unhandled,
         }
     }
@@ -627,6 +738,10 @@ impl Debug for ApiError {
             ApiError::HostBufferEmpty => write!(f, "ApiError::HostBufferEmpty")?,
             ApiError::HostBufferFull => write!(f, "ApiError::HostBufferFull")?,
             ApiError::AllocLayout => write!(f, "ApiError::AllocLayout")?,
+            ApiError::ReadOnlyContext => write!(f, "ApiError::ReadOnlyContext")?,
+            ApiError::NamedKeyNotFound => write!(f, "ApiError::NamedKeyNotFound")?,
+            ApiError::NamedKeyAlreadyExists => write!(f, "ApiError::NamedKeyAlreadyExists")?,
+            ApiError::Auction(value) => write!(f, "ApiError::Auction({})", value)?,
             ApiError::Mint(value) => write!(f, "ApiError::Mint({})", value)?,
             ApiError::ProofOfStake(value) => write!(f, "ApiError::ProofOfStake({})", value)?,
             ApiError::User(value) => write!(f, "ApiError::User({})", value)?,
@@ -639,6 +754,7 @@ impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ApiError::User(value) => write!(f, "User error: {}", value),
+            ApiError::Auction(value) => write!(f, "Auction error: {}", value),
             ApiError::Mint(value) => write!(f, "Mint error: {}", value),
             ApiError::ProofOfStake(value) => write!(f, "PoS error: {}", value),
             _ => <Self as Debug>::fmt(&self, f),
@@ -678,6 +794,8 @@ mod tests {
 
     #[test]
     fn error() {
+        assert_eq!(64_768_u32, ApiError::Auction(0).into()); // AUCTION_ERROR_OFFSET == 64,768
+        assert_eq!(65_023_u32, ApiError::Auction(u8::MAX).into());
         assert_eq!(65_024_u32, ApiError::Mint(0).into()); // MINT_ERROR_OFFSET == 65,024
         assert_eq!(65_279_u32, ApiError::Mint(u8::MAX).into());
         assert_eq!(65_280_u32, ApiError::ProofOfStake(0).into()); // POS_ERROR_OFFSET == 65,280
@@ -691,6 +809,11 @@ mod tests {
             "ApiError::Mint(0) [65024]",
             &format!("{:?}", ApiError::Mint(0))
         );
+        assert_eq!(
+            "ApiError::Auction(0) [64768]",
+            &format!("{:?}", ApiError::Auction(0))
+        );
+        assert_eq!("Auction error: 0", &format!("{}", ApiError::Auction(0)));
         assert_eq!("Mint error: 0", &format!("{}", ApiError::Mint(0)));
         assert_eq!("Mint error: 255", &format!("{}", ApiError::Mint(u8::MAX)));
         assert_eq!(
@@ -719,7 +842,7 @@ mod tests {
         assert_eq!(Err(ApiError::Unhandled), result_from(i32::MAX));
         assert_eq!(
             Err(ApiError::Unhandled),
-            result_from(MINT_ERROR_OFFSET as i32 - 1)
+            result_from(AUCTION_ERROR_OFFSET as i32 - 1)
         );
         assert_eq!(Err(ApiError::Unhandled), result_from(-1));
         assert_eq!(Err(ApiError::Unhandled), result_from(i32::MIN));
@@ -760,6 +883,11 @@ mod tests {
         round_trip(Err(ApiError::HostBufferEmpty));
         round_trip(Err(ApiError::HostBufferFull));
         round_trip(Err(ApiError::AllocLayout));
+        round_trip(Err(ApiError::ReadOnlyContext));
+        round_trip(Err(ApiError::NamedKeyNotFound));
+        round_trip(Err(ApiError::NamedKeyAlreadyExists));
+        round_trip(Err(ApiError::Auction(0)));
+        round_trip(Err(ApiError::Auction(u8::MAX)));
         round_trip(Err(ApiError::Mint(0)));
         round_trip(Err(ApiError::Mint(u8::MAX)));
         round_trip(Err(ApiError::ProofOfStake(0)));
@@ -1,18 +1,52 @@
 use alloc::vec::Vec;
+use core::{
+    convert::TryFrom,
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
 
 use crate::bytesrepr::{Error, FromBytes, ToBytes, U64_SERIALIZED_LENGTH};
 
 /// The number of bytes in a serialized [`BlockTime`].
 pub const BLOCKTIME_SERIALIZED_LENGTH: usize = U64_SERIALIZED_LENGTH;
 
-/// A newtype wrapping a [`u64`] which represents the block time.
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd)]
+const MILLIS_PER_SECOND: u64 = 1_000;
+
+/// The time of a block, in milliseconds since the Unix epoch.
+///
+/// Milliseconds are the canonical unit: the block time sent by the node, stored in global state
+/// and returned to contracts by the `get_blocktime` host function are all in milliseconds.  Use
+/// [`BlockTime::from_seconds`] to convert a time in seconds.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct BlockTime(u64);
 
 impl BlockTime {
-    /// Constructs a `BlockTime`.
-    pub fn new(value: u64) -> Self {
-        BlockTime(value)
+    /// Constructs a `BlockTime` from milliseconds since the Unix epoch.
+    pub const fn from_millis(millis: u64) -> Self {
+        BlockTime(millis)
+    }
+
+    /// Constructs a `BlockTime` from seconds since the Unix epoch, or returns `None` if the time
+    /// in milliseconds would overflow.
+    pub fn from_seconds(seconds: u64) -> Option<Self> {
+        seconds.checked_mul(MILLIS_PER_SECOND).map(BlockTime)
+    }
+
+    /// Returns the time in milliseconds since the Unix epoch.
+    pub fn as_millis(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the time in whole seconds since the Unix epoch.
+    pub fn as_seconds(self) -> u64 {
+        self.0 / MILLIS_PER_SECOND
+    }
+
+    /// Saturating addition. Computes `self + duration`, saturating at the greatest representable
+    /// time instead of overflowing.
+    pub fn saturating_add(self, duration: Duration) -> Self {
+        let millis = u64::try_from(duration.as_millis()).unwrap_or(u64::max_value());
+        BlockTime(self.0.saturating_add(millis))
     }
 
     /// Saturating integer subtraction. Computes `self - other`, saturating at `0` instead of
@@ -22,6 +56,12 @@ impl BlockTime {
     }
 }
 
+impl Display for BlockTime {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "{}ms", self.0)
+    }
+}
+
 impl Into<u64> for BlockTime {
     fn into(self) -> u64 {
         self.0
@@ -41,6 +81,67 @@ impl ToBytes for BlockTime {
 impl FromBytes for BlockTime {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
         let (time, rem) = FromBytes::from_bytes(bytes)?;
-        Ok((BlockTime::new(time), rem))
+        Ok((BlockTime::from_millis(time), rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use super::BlockTime;
+    use crate::bytesrepr;
+
+    #[test]
+    fn should_convert_seconds_to_millis() {
+        let block_time = BlockTime::from_seconds(1_600_000_000).unwrap();
+        assert_eq!(block_time, BlockTime::from_millis(1_600_000_000_000));
+        assert_eq!(block_time.as_millis(), 1_600_000_000_000);
+        assert_eq!(block_time.as_seconds(), 1_600_000_000);
+    }
+
+    #[test]
+    fn should_truncate_millis_to_whole_seconds() {
+        assert_eq!(BlockTime::from_millis(1_999).as_seconds(), 1);
+    }
+
+    #[test]
+    fn should_not_convert_overflowing_seconds() {
+        assert_eq!(BlockTime::from_seconds(u64::max_value() / 1_000 + 1), None);
+        assert!(BlockTime::from_seconds(u64::max_value() / 1_000).is_some());
+    }
+
+    #[test]
+    fn should_add_duration() {
+        let block_time = BlockTime::from_millis(1_000);
+        assert_eq!(
+            block_time.saturating_add(Duration::from_secs(2)),
+            BlockTime::from_millis(3_000)
+        );
+        assert_eq!(
+            block_time.saturating_add(Duration::from_millis(1)),
+            BlockTime::from_millis(1_001)
+        );
+        assert_eq!(
+            block_time.saturating_add(Duration::from_secs(u64::max_value())),
+            BlockTime::from_millis(u64::max_value())
+        );
+    }
+
+    #[test]
+    fn should_order_by_time() {
+        assert!(BlockTime::from_millis(999) < BlockTime::from_seconds(1).unwrap());
+        assert!(BlockTime::from_millis(1_001) > BlockTime::from_seconds(1).unwrap());
+    }
+
+    #[test]
+    fn should_serialize_millis() {
+        let block_time = BlockTime::from_seconds(42).unwrap();
+        let bytes = bytesrepr::serialize(block_time).unwrap();
+        assert_eq!(bytes, bytesrepr::serialize(42_000u64).unwrap());
+        assert_eq!(
+            bytesrepr::deserialize::<BlockTime>(bytes).unwrap(),
+            block_time
+        );
     }
 }
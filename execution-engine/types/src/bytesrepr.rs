@@ -1,4 +1,27 @@
 //! Contains serialization and deserialization code for types used throughout the system.
+//!
+//! The host and contracts share these implementations, so a value serialized on one side always
+//! deserializes on the other.  Contracts should use them rather than hand-rolling an encoding for
+//! a standard type.  The encodings are:
+//!
+//! * `()`: no bytes.
+//! * `bool`: a single byte, `0` for `false` or `1` for `true`.
+//! * `u8`, `u16`, `u32`, `u64`, `i32` and `i64`: little-endian.
+//! * [`U128`](crate::U128), [`U256`](crate::U256) and [`U512`](crate::U512): a single byte giving
+//!   the number of bytes which follow, then the little-endian value without its trailing zero
+//!   bytes.
+//! * `String` and `str`: the length in bytes as a `u32`, then the UTF-8 bytes.
+//! * `Vec<T>`: the number of elements as a `u32`, then the elements.
+//! * `[T; N]`: the `N` elements, with no length prefix.
+//! * `Option<T>`: a tag byte, `0` for `None` or `1` for `Some` followed by the value.
+//! * `Result<T, E>`: a tag byte, `0` for `Err` followed by the error or `1` for `Ok` followed by
+//!   the value.
+//! * Tuples of up to eight elements: the elements in order, with no prefix.
+//! * `BTreeMap<K, V>`: the number of entries as a `u32`, then each key followed by its value, in
+//!   ascending order of key.
+//! * `BTreeSet<T>`: the number of elements as a `u32`, then the elements in ascending order.
+//!
+//! The `golden` tests below lock these encodings down.
 
 // Can be removed once https://github.com/rust-lang/rustfmt/issues/3362 is resolved.
 #[rustfmt::skip]
@@ -7,7 +30,11 @@ use alloc::vec;
 use alloc::alloc::{alloc, Layout};
 #[cfg(not(feature = "no-unstable-features"))]
 use alloc::collections::TryReserveError;
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
 use core::mem::{self, MaybeUninit};
 #[cfg(feature = "no-unstable-features")]
 use core::ptr::NonNull;
@@ -638,6 +665,38 @@ where
     }
 }
 
+impl<T: ToBytes> ToBytes for BTreeSet<T> {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut result = allocate_buffer(self)?;
+
+        let num_elements = self.len() as u32;
+        result.append(&mut num_elements.to_bytes()?);
+
+        for element in self.iter() {
+            result.append(&mut element.to_bytes()?);
+        }
+
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U32_SERIALIZED_LENGTH + self.iter().map(ToBytes::serialized_length).sum::<usize>()
+    }
+}
+
+impl<T: FromBytes + Ord> FromBytes for BTreeSet<T> {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (num_elements, mut stream) = u32::from_bytes(bytes)?;
+        let mut result = BTreeSet::new();
+        for _ in 0..num_elements {
+            let (element, rem) = T::from_bytes(stream)?;
+            result.insert(element);
+            stream = rem;
+        }
+        Ok((result, stream))
+    }
+}
+
 impl<T: ToBytes> ToBytes for Option<T> {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         match self {
@@ -776,6 +835,38 @@ impl<T1: FromBytes, T2: FromBytes, T3: FromBytes> FromBytes for (T1, T2, T3) {
     }
 }
 
+macro_rules! impl_to_from_bytes_for_tuple {
+    ($($T:ident $t:ident $index:tt),+) => {
+        impl<$($T: ToBytes),+> ToBytes for ($($T,)+) {
+            fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                let mut result = allocate_buffer(self)?;
+                $(result.append(&mut self.$index.to_bytes()?);)+
+                Ok(result)
+            }
+
+            fn serialized_length(&self) -> usize {
+                0 $(+ self.$index.serialized_length())+
+            }
+        }
+
+        impl<$($T: FromBytes),+> FromBytes for ($($T,)+) {
+            fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+                let remainder = bytes;
+                $(let ($t, remainder) = $T::from_bytes(remainder)?;)+
+                Ok((($($t,)+), remainder))
+            }
+        }
+    };
+}
+
+impl_to_from_bytes_for_tuple!(T1 t1 0, T2 t2 1, T3 t3 2, T4 t4 3);
+impl_to_from_bytes_for_tuple!(T1 t1 0, T2 t2 1, T3 t3 2, T4 t4 3, T5 t5 4);
+impl_to_from_bytes_for_tuple!(T1 t1 0, T2 t2 1, T3 t3 2, T4 t4 3, T5 t5 4, T6 t6 5);
+impl_to_from_bytes_for_tuple!(T1 t1 0, T2 t2 1, T3 t3 2, T4 t4 3, T5 t5 4, T6 t6 5, T7 t7 6);
+impl_to_from_bytes_for_tuple!(
+    T1 t1 0, T2 t2 1, T3 t3 2, T4 t4 3, T5 t5 4, T6 t6 5, T7 t7 6, T8 t8 7
+);
+
 impl ToBytes for str {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         if self.len() > u32::max_value() as usize - U32_SERIALIZED_LENGTH {
@@ -872,11 +963,124 @@ mod tests {
     }
 }
 
+#[cfg(test)]
+mod golden {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        fmt::Debug,
+        string::String,
+        vec,
+        vec::Vec,
+    };
+
+    use super::*;
+    use crate::U512;
+
+    /// Asserts that `value` serializes to exactly `expected`, and deserializes from it.
+    fn assert_golden<T: ToBytes + FromBytes + PartialEq + Debug>(value: T, expected: &[u8]) {
+        assert_eq!(value.to_bytes().unwrap(), expected, "{:?}", value);
+        assert_eq!(value.serialized_length(), expected.len(), "{:?}", value);
+        assert_eq!(deserialize::<T>(expected.to_vec()).unwrap(), value);
+    }
+
+    #[test]
+    fn primitives() {
+        assert_golden((), &[]);
+        assert_golden(false, &[0]);
+        assert_golden(true, &[1]);
+        assert_golden(0x0102u16, &[2, 1]);
+        assert_golden(0x0102_0304u32, &[4, 3, 2, 1]);
+        assert_golden(-2i32, &[254, 255, 255, 255]);
+        assert_golden(0x0102_0304_0506_0708u64, &[8, 7, 6, 5, 4, 3, 2, 1]);
+    }
+
+    #[test]
+    fn big_ints() {
+        assert_golden(U512::zero(), &[0]);
+        assert_golden(U512::from(0x0100), &[2, 0, 1]);
+    }
+
+    #[test]
+    fn strings() {
+        assert_golden(String::new(), &[0, 0, 0, 0]);
+        assert_golden(String::from("abc"), &[3, 0, 0, 0, 97, 98, 99]);
+        assert_eq!(
+            "abc".to_bytes().unwrap(),
+            String::from("abc").to_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn vecs_and_arrays() {
+        assert_golden(Vec::<u16>::new(), &[0, 0, 0, 0]);
+        assert_golden(vec![1u16, 2], &[2, 0, 0, 0, 1, 0, 2, 0]);
+        assert_golden(vec![7u8, 8], &[2, 0, 0, 0, 7, 8]);
+        assert_golden([1u16, 2], &[1, 0, 2, 0]);
+        assert_golden([7u8, 8], &[7, 8]);
+    }
+
+    #[test]
+    fn options_and_results() {
+        assert_golden(Option::<u8>::None, &[0]);
+        assert_golden(Some(7u8), &[1, 7]);
+        assert_golden(Some(Option::<u8>::None), &[1, 0]);
+        assert_golden(Result::<u8, u16>::Ok(7), &[1, 7]);
+        assert_golden(Result::<u8, u16>::Err(0x0102), &[0, 2, 1]);
+    }
+
+    #[test]
+    fn tuples() {
+        assert_golden((7u8,), &[7]);
+        assert_golden((7u8, 0x0102u16), &[7, 2, 1]);
+        assert_golden(
+            (
+                1u8,
+                2u16,
+                3u32,
+                true,
+                (),
+                String::from("a"),
+                Option::<u8>::None,
+                Some(4u8),
+            ),
+            &[1, 2, 0, 3, 0, 0, 0, 1, 1, 0, 0, 0, 97, 0, 1, 4],
+        );
+    }
+
+    #[test]
+    fn maps_and_sets() {
+        let mut map = BTreeMap::new();
+        map.insert(String::from("b"), 2u8);
+        map.insert(String::from("a"), 1u8);
+        assert_golden(map, &[2, 0, 0, 0, 1, 0, 0, 0, 97, 1, 1, 0, 0, 0, 98, 2]);
+
+        let set: BTreeSet<u8> = vec![3, 1, 2].into_iter().collect();
+        assert_golden(set, &[3, 0, 0, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn should_not_deserialize_invalid_tags() {
+        assert_eq!(deserialize::<bool>(vec![2]), Err(Error::Formatting));
+        assert_eq!(
+            deserialize::<Option<u8>>(vec![2, 7]),
+            Err(Error::Formatting)
+        );
+        assert_eq!(
+            deserialize::<Result<u8, u8>>(vec![2, 7]),
+            Err(Error::Formatting)
+        );
+    }
+}
+
 #[cfg(test)]
 mod proptests {
     use std::vec::Vec;
 
-    use proptest::{collection::vec, prelude::*};
+    use proptest::{
+        collection::{btree_map, btree_set, vec},
+        option,
+        prelude::*,
+    };
 
     use crate::{
         bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
@@ -1004,6 +1208,11 @@ mod proptests {
             bytesrepr::test_serialization_roundtrip(&pk);
         }
 
+        #[test]
+        fn test_weight(weight in weight_arb()) {
+            bytesrepr::test_serialization_roundtrip(&weight);
+        }
+
         #[test]
         fn test_result(result in result_arb()) {
             bytesrepr::test_serialization_roundtrip(&result);
@@ -1038,6 +1247,49 @@ mod proptests {
         fn test_tuple3(t in (any::<u8>(),any::<u32>(),any::<i32>())) {
             bytesrepr::test_serialization_roundtrip(&t);
         }
+
+        #[test]
+        fn test_tuple4(t in (any::<u8>(), any::<u32>(), any::<i32>(), u512_arb())) {
+            bytesrepr::test_serialization_roundtrip(&t);
+        }
+
+        #[test]
+        fn test_tuple8(
+            t in (
+                any::<u8>(),
+                any::<u16>(),
+                any::<u32>(),
+                any::<u64>(),
+                any::<i32>(),
+                any::<i64>(),
+                any::<bool>(),
+                "\\PC*",
+            )
+        ) {
+            bytesrepr::test_serialization_roundtrip(&t);
+        }
+
+        #[test]
+        fn test_btree_set(s in btree_set(key_arb(), 0..20)) {
+            bytesrepr::test_serialization_roundtrip(&s);
+        }
+
+        #[test]
+        fn test_nested_map(
+            m in btree_map("\\PC*", option::of((u512_arb(), public_key_arb())), 0..20)
+        ) {
+            bytesrepr::test_serialization_roundtrip(&m);
+        }
+
+        #[test]
+        fn test_nested_vec(
+            v in vec(
+                (public_key_arb(), u512_arb(), option::of(btree_set(any::<u8>(), 0..5))),
+                0..20,
+            )
+        ) {
+            bytesrepr::test_serialization_roundtrip(&v);
+        }
     }
 
     #[test]
@@ -180,6 +180,18 @@ impl CLType {
     }
 }
 
+impl ToBytes for CLType {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = Vec::with_capacity(CLType::serialized_length(self));
+        self.append_bytes(&mut result);
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        CLType::serialized_length(self)
+    }
+}
+
 #[allow(clippy::cognitive_complexity)]
 impl FromBytes for CLType {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
@@ -458,10 +470,12 @@ impl<T1: CLTyped, T2: CLTyped, T3: CLTyped> CLTyped for (T1, T2, T3) {
 mod tests {
     use std::{fmt::Debug, string::ToString};
 
+    use proptest::proptest;
+
     use super::*;
     use crate::{
         bytesrepr::{FromBytes, ToBytes},
-        AccessRights, CLValue,
+        gens, AccessRights, CLValue,
     };
 
     fn round_trip<T: CLTyped + FromBytes + ToBytes + PartialEq + Debug + Clone>(value: &T) {
@@ -476,6 +490,18 @@ mod tests {
         assert_eq!(*value, parsed_value);
     }
 
+    #[test]
+    fn cl_type_should_roundtrip() {
+        for cl_type in vec![
+            CLType::U512,
+            CLType::Any,
+            CLType::Option(Box::new(CLType::Key)),
+            <(String, U512)>::cl_type(),
+        ] {
+            bytesrepr::test_serialization_roundtrip(&cl_type);
+        }
+    }
+
     #[test]
     fn bool_should_work() {
         round_trip(&true);
@@ -682,4 +708,15 @@ mod tests {
         let any = Any("Any test".to_string());
         round_trip(&any);
     }
+
+    proptest! {
+        #[test]
+        fn cl_type_serialization_roundtrip(cl_type in gens::cl_type_arb()) {
+            let mut bytes = Vec::new();
+            cl_type.append_bytes(&mut bytes);
+            assert_eq!(bytes.len(), cl_type.serialized_length());
+            let parsed_cl_type: CLType = bytesrepr::deserialize(bytes).unwrap();
+            assert_eq!(cl_type, parsed_cl_type);
+        }
+    }
 }
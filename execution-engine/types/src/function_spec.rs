@@ -0,0 +1,74 @@
+use alloc::vec::Vec;
+
+use crate::bytesrepr::{Error, FromBytes, ToBytes, BOOL_SERIALIZED_LENGTH};
+
+/// The number of bytes in a serialized [`FunctionSpec`].
+pub const FUNCTION_SPEC_SERIALIZED_LENGTH: usize = BOOL_SERIALIZED_LENGTH;
+
+/// The name of the named key which marks a stored function as declared pure.
+///
+/// The system adds it to the named keys of a function stored with a pure [`FunctionSpec`], where it
+/// refers to the function itself.
+pub const PURE_FUNCTION_KEY_NAME: &str = "__system/pure_function";
+
+/// The properties a stored function is declared to have when it is stored.
+///
+/// A function declared pure may not modify global state, and its result must depend only on its
+/// arguments.  Calls to it are memoized for the rest of the execute request in which they are made:
+/// a repeated call with the same arguments returns the result of the first one without executing
+/// the function again, while still being charged the gas the first call used.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FunctionSpec {
+    is_pure: bool,
+}
+
+impl FunctionSpec {
+    /// Constructs a `FunctionSpec` for a function which isn't declared pure.
+    pub fn new() -> Self {
+        FunctionSpec::default()
+    }
+
+    /// Constructs a `FunctionSpec` for a function declared pure.
+    pub fn pure() -> Self {
+        FunctionSpec { is_pure: true }
+    }
+
+    /// Returns `true` if the function is declared pure.
+    pub fn is_pure(&self) -> bool {
+        self.is_pure
+    }
+}
+
+impl ToBytes for FunctionSpec {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.is_pure.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        FUNCTION_SPEC_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for FunctionSpec {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (is_pure, rem) = FromBytes::from_bytes(bytes)?;
+        Ok((FunctionSpec { is_pure }, rem))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bytesrepr, is_reserved_key_name};
+
+    #[test]
+    fn serialization_roundtrip() {
+        bytesrepr::test_serialization_roundtrip(&FunctionSpec::new());
+        bytesrepr::test_serialization_roundtrip(&FunctionSpec::pure());
+    }
+
+    #[test]
+    fn should_reserve_pure_function_key_name() {
+        assert!(is_reserved_key_name(PURE_FUNCTION_KEY_NAME));
+    }
+}
@@ -2,11 +2,10 @@
 //! [`Proptest`](https://crates.io/crates/proptest).
 #![allow(missing_docs)]
 
-use alloc::{collections::BTreeMap, string::String, vec};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec};
 
 use proptest::{
-    array, bits,
-    collection::{btree_map, vec},
+    collection::{btree_map, vec, SizeRange},
     option,
     prelude::*,
     result,
@@ -14,19 +13,25 @@ use proptest::{
 
 use crate::{
     account::{PublicKey, Weight},
-    AccessRights, CLType, CLValue, Key, Phase, ProtocolVersion, SemVer, URef, U128, U256, U512,
+    AccessRights, CLType, CLValue, ContractHash, DeployHash, Key, Phase, ProtocolVersion, SemVer,
+    URef, U128, U256, U512,
 };
 
+/// Generates arbitrary 32-byte arrays, half of which repeat a single byte.  As shrinking favours
+/// the repeated bytes, minimal counterexamples read as e.g. `[0; 32]` rather than as opaque arrays.
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
-    vec(any::<u8>(), 32).prop_map(|b| {
-        let mut res = [0u8; 32];
-        res.clone_from_slice(b.as_slice());
-        res
-    })
+    prop_oneof![
+        any::<u8>().prop_map(|b| [b; 32]),
+        vec(any::<u8>(), 32).prop_map(|b| {
+            let mut res = [0u8; 32];
+            res.clone_from_slice(b.as_slice());
+            res
+        }),
+    ]
 }
 
-pub fn named_keys_arb(depth: usize) -> impl Strategy<Value = BTreeMap<String, Key>> {
-    btree_map("\\PC*", key_arb(), depth)
+pub fn named_keys_arb(size: impl Into<SizeRange>) -> impl Strategy<Value = BTreeMap<String, Key>> {
+    btree_map("\\PC*", key_arb(), size)
 }
 
 pub fn access_rights_arb() -> impl Strategy<Value = AccessRights> {
@@ -39,6 +44,8 @@ pub fn access_rights_arb() -> impl Strategy<Value = AccessRights> {
         Just(AccessRights::READ_WRITE),
         Just(AccessRights::ADD_WRITE),
         Just(AccessRights::READ_ADD_WRITE),
+        Just(AccessRights::READ | AccessRights::PUBLIC_READ),
+        Just(AccessRights::READ_ADD_WRITE | AccessRights::PUBLIC_READ),
     ]
 }
 
@@ -47,11 +54,12 @@ pub fn phase_arb() -> impl Strategy<Value = Phase> {
         Just(Phase::Payment),
         Just(Phase::Session),
         Just(Phase::FinalizePayment),
+        Just(Phase::Authorization),
     ]
 }
 
 pub fn uref_arb() -> impl Strategy<Value = URef> {
-    (array::uniform32(bits::u8::ANY), access_rights_arb())
+    (u8_slice_32(), access_rights_arb())
         .prop_map(|(id, access_rights)| URef::new(id, access_rights))
 }
 
@@ -64,6 +72,14 @@ pub fn key_arb() -> impl Strategy<Value = Key> {
     ]
 }
 
+pub fn deploy_hash_arb() -> impl Strategy<Value = DeployHash> {
+    u8_slice_32().prop_map(DeployHash::new)
+}
+
+pub fn contract_hash_arb() -> impl Strategy<Value = ContractHash> {
+    u8_slice_32().prop_map(ContractHash::new)
+}
+
 pub fn public_key_arb() -> impl Strategy<Value = PublicKey> {
     u8_slice_32().prop_map(PublicKey::ed25519_from)
 }
@@ -93,6 +109,48 @@ pub fn u512_arb() -> impl Strategy<Value = U512> {
     vec(any::<u8>(), 0..64).prop_map(|b| U512::from_little_endian(b.as_slice()))
 }
 
+pub fn cl_type_arb() -> impl Strategy<Value = CLType> {
+    let leaf = prop_oneof![
+        Just(CLType::Bool),
+        Just(CLType::I32),
+        Just(CLType::I64),
+        Just(CLType::U8),
+        Just(CLType::U32),
+        Just(CLType::U64),
+        Just(CLType::U128),
+        Just(CLType::U256),
+        Just(CLType::U512),
+        Just(CLType::Unit),
+        Just(CLType::String),
+        Just(CLType::Key),
+        Just(CLType::URef),
+        Just(CLType::Any),
+    ];
+    leaf.prop_recursive(4, 32, 3, |inner| {
+        prop_oneof![
+            inner.clone().prop_map(|t| CLType::Option(Box::new(t))),
+            inner.clone().prop_map(|t| CLType::List(Box::new(t))),
+            (inner.clone(), any::<u32>()).prop_map(|(t, len)| CLType::FixedList(Box::new(t), len)),
+            (inner.clone(), inner.clone()).prop_map(|(ok, err)| CLType::Result {
+                ok: Box::new(ok),
+                err: Box::new(err)
+            }),
+            (inner.clone(), inner.clone()).prop_map(|(key, value)| CLType::Map {
+                key: Box::new(key),
+                value: Box::new(value)
+            }),
+            inner.clone().prop_map(|t| CLType::Tuple1([Box::new(t)])),
+            (inner.clone(), inner.clone())
+                .prop_map(|(t0, t1)| CLType::Tuple2([Box::new(t0), Box::new(t1)])),
+            (inner.clone(), inner.clone(), inner).prop_map(|(t0, t1, t2)| CLType::Tuple3([
+                Box::new(t0),
+                Box::new(t1),
+                Box::new(t2)
+            ])),
+        ]
+    })
+}
+
 pub fn cl_value_arb() -> impl Strategy<Value = CLValue> {
     // If compiler brings you here it most probably means you've added a variant to `CLType` enum
     // but forgot to add generator for it.
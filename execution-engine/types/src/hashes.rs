@@ -0,0 +1,200 @@
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+use hex_fmt::HexFmt;
+
+use crate::{
+    bytesrepr::{Error, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// The number of bytes in a [`DeployHash`].
+pub const DEPLOY_HASH_LENGTH: usize = 32;
+
+/// The number of bytes in a [`ContractHash`].
+pub const CONTRACT_HASH_LENGTH: usize = 32;
+
+/// An error parsing a [`DeployHash`] or [`ContractHash`] from its hex form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashParseError {
+    /// The string has a character which isn't a hex digit.
+    InvalidHex,
+    /// The string doesn't encode the expected number of bytes.
+    InvalidLength,
+}
+
+impl Display for HashParseError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            HashParseError::InvalidHex => write!(formatter, "invalid hex digit"),
+            HashParseError::InvalidLength => write!(formatter, "invalid length"),
+        }
+    }
+}
+
+/// Defines a newtype around a hash of `$length` bytes, so that hashes of different things can't
+/// be mixed up.  Its `Display` and `FromStr` forms are lower-case hex, and it serializes exactly
+/// like the bare array, so it can replace one without changing any encoding.
+macro_rules! hash_newtype {
+    ($(#[$meta:meta])* $name:ident, $length:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name([u8; $length]);
+
+        impl $name {
+            /// Constructs a hash from its raw bytes.
+            pub const fn new(value: [u8; $length]) -> Self {
+                $name(value)
+            }
+
+            /// Returns the raw bytes of the hash.
+            pub fn value(self) -> [u8; $length] {
+                self.0
+            }
+
+            /// Returns the raw bytes of the hash as a slice.
+            pub fn as_bytes(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        /// Transitional conversion from the bare array the hash used to be passed as.
+        impl From<[u8; $length]> for $name {
+            fn from(value: [u8; $length]) -> Self {
+                $name(value)
+            }
+        }
+
+        impl From<$name> for [u8; $length] {
+            fn from(hash: $name) -> Self {
+                hash.0
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+                write!(formatter, "{}", HexFmt(&self.0))
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+                write!(formatter, "{}({})", stringify!($name), HexFmt(&self.0))
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = HashParseError;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                if value.len() != 2 * $length {
+                    return Err(HashParseError::InvalidLength);
+                }
+                let mut bytes = [0; $length];
+                base16::decode_slice(value, &mut bytes).map_err(|_| HashParseError::InvalidHex)?;
+                Ok($name(bytes))
+            }
+        }
+
+        impl ToBytes for $name {
+            fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+                self.0.to_bytes()
+            }
+
+            fn serialized_length(&self) -> usize {
+                self.0.serialized_length()
+            }
+        }
+
+        impl FromBytes for $name {
+            fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+                let (value, rem) = FromBytes::from_bytes(bytes)?;
+                Ok(($name(value), rem))
+            }
+        }
+
+        impl CLTyped for $name {
+            fn cl_type() -> CLType {
+                <[u8; $length]>::cl_type()
+            }
+        }
+    };
+}
+
+hash_newtype!(
+    /// The hash identifying a deploy.
+    DeployHash,
+    DEPLOY_HASH_LENGTH
+);
+
+hash_newtype!(
+    /// The hash under which a contract is stored, as wrapped by `Key::Hash`.
+    ContractHash,
+    CONTRACT_HASH_LENGTH
+);
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use proptest::proptest;
+
+    use super::{ContractHash, DeployHash, HashParseError};
+    use crate::{bytesrepr, gens};
+
+    #[test]
+    fn should_display_as_lower_case_hex() {
+        let deploy_hash = DeployHash::new([0xab; 32]);
+        assert_eq!(deploy_hash.to_string(), "ab".repeat(32));
+        assert_eq!(
+            format!("{:?}", deploy_hash),
+            format!("DeployHash({})", "ab".repeat(32))
+        );
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_hex() {
+        assert_eq!(
+            "ab".repeat(31).parse::<DeployHash>(),
+            Err(HashParseError::InvalidLength)
+        );
+        assert_eq!(
+            "ab".repeat(33).parse::<ContractHash>(),
+            Err(HashParseError::InvalidLength)
+        );
+        assert_eq!(
+            "xy".repeat(32).parse::<ContractHash>(),
+            Err(HashParseError::InvalidHex)
+        );
+    }
+
+    #[test]
+    fn should_serialize_like_bare_array() {
+        let bytes = [7; 32];
+        assert_eq!(
+            bytesrepr::serialize(DeployHash::new(bytes)).unwrap(),
+            bytesrepr::serialize(bytes).unwrap()
+        );
+        assert_eq!(
+            bytesrepr::serialize(ContractHash::new(bytes)).unwrap(),
+            bytesrepr::serialize(bytes).unwrap()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn should_round_trip_deploy_hash(deploy_hash in gens::deploy_hash_arb()) {
+            bytesrepr::test_serialization_roundtrip(&deploy_hash);
+            assert_eq!(deploy_hash.to_string().parse(), Ok(deploy_hash));
+        }
+
+        #[test]
+        fn should_round_trip_contract_hash(contract_hash in gens::contract_hash_arb()) {
+            bytesrepr::test_serialization_roundtrip(&contract_hash);
+            assert_eq!(contract_hash.to_string().parse(), Ok(contract_hash));
+        }
+    }
+}
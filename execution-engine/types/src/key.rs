@@ -10,7 +10,7 @@ use hex_fmt::HexFmt;
 use crate::{
     account::PublicKey,
     bytesrepr::{self, Error, FromBytes, ToBytes},
-    ContractRef, URef, UREF_SERIALIZED_LENGTH,
+    ContractHash, ContractRef, URef, UREF_SERIALIZED_LENGTH,
 };
 
 const ACCOUNT_ID: u8 = 0;
@@ -140,6 +140,12 @@ impl Key {
         }
     }
 
+    /// Returns the inner bytes of `self` as a [`ContractHash`] if `self` is of type [`Key::Hash`],
+    /// otherwise returns `None`.
+    pub fn into_contract_hash(self) -> Option<ContractHash> {
+        self.into_hash().map(ContractHash::new)
+    }
+
     /// Returns a reference to the inner [`URef`] if `self` is of type [`Key::URef`], otherwise
     /// returns `None`.
     pub fn as_uref(&self) -> Option<&URef> {
@@ -196,6 +202,12 @@ impl From<URef> for Key {
     }
 }
 
+impl From<ContractHash> for Key {
+    fn from(contract_hash: ContractHash) -> Key {
+        Key::Hash(contract_hash.value())
+    }
+}
+
 impl ToBytes for Key {
     fn to_bytes(&self) -> Result<Vec<u8>, Error> {
         let mut result = bytesrepr::unchecked_allocate_buffer(self);
@@ -369,6 +381,8 @@ mod tests {
         let key1 = Key::Hash(hash);
         assert!(key1.into_account().is_none());
         assert_eq!(key1.into_hash(), Some(hash));
+        assert_eq!(key1.into_contract_hash(), Some(ContractHash::new(hash)));
+        assert_eq!(Key::from(ContractHash::new(hash)), key1);
         assert!(key1.as_uref().is_none());
         assert!(key1.into_local().is_none());
     }
@@ -31,32 +31,45 @@ pub mod bytesrepr;
 mod cl_type;
 mod cl_value;
 mod contract_ref;
+mod function_spec;
 #[cfg(any(feature = "gens", test))]
 pub mod gens;
+mod hashes;
 mod key;
 mod phase;
 mod protocol_version;
+mod reserved_key_name;
+mod schedule_id;
 mod semver;
 pub mod system_contract_errors;
+pub mod system_contract_names;
 pub mod system_contract_type;
 mod transfer_result;
 mod uint;
 mod uref;
 
 pub use crate::uint::{UIntParseError, U128, U256, U512};
-pub use access_rights::{AccessRights, ACCESS_RIGHTS_SERIALIZED_LENGTH};
+pub use access_rights::{
+    AccessRights, ACCESS_RIGHTS_RESERVED_BITS, ACCESS_RIGHTS_SERIALIZED_LENGTH,
+};
 #[doc(inline)]
 pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
 pub use contract_ref::ContractRef;
+pub use function_spec::{FunctionSpec, FUNCTION_SPEC_SERIALIZED_LENGTH, PURE_FUNCTION_KEY_NAME};
+pub use hashes::{
+    ContractHash, DeployHash, HashParseError, CONTRACT_HASH_LENGTH, DEPLOY_HASH_LENGTH,
+};
 #[doc(inline)]
 pub use key::{
     Key, BLAKE2B_DIGEST_LENGTH, KEY_HASH_LENGTH, KEY_LOCAL_LENGTH, KEY_LOCAL_SEED_LENGTH,
 };
 pub use phase::{Phase, PHASE_SERIALIZED_LENGTH};
 pub use protocol_version::{ProtocolVersion, VersionCheckResult};
+pub use reserved_key_name::{is_reserved_key_name, RESERVED_KEY_NAME_PREFIX};
+pub use schedule_id::{ScheduleId, SCHEDULE_ID_LENGTH, SCHEDULE_ID_SERIALIZED_LENGTH};
 pub use semver::SemVer;
 pub use system_contract_type::SystemContractType;
 pub use transfer_result::{TransferResult, TransferredTo};
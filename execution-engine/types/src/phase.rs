@@ -26,6 +26,8 @@ pub enum Phase {
     Session = 2,
     /// Set while finalizing payment at the end of a deploy.
     FinalizePayment = 3,
+    /// Set while executing the authorization contract of the deploy's account, before payment.
+    Authorization = 4,
 }
 
 impl ToBytes for Phase {
@@ -0,0 +1,24 @@
+/// The prefix of the names of named keys which are managed by the system.
+///
+/// Only system execution contexts and the system contracts may add or remove a named key whose name
+/// starts with this prefix, while such keys can still be read like any other named key.
+pub const RESERVED_KEY_NAME_PREFIX: &str = "__system/";
+
+/// Returns `true` if `name` is in the namespace reserved for system-managed named keys.
+pub fn is_reserved_key_name(name: &str) -> bool {
+    name.starts_with(RESERVED_KEY_NAME_PREFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_only_reserve_prefixed_names() {
+        assert!(is_reserved_key_name("__system/pos_refund_purse"));
+        assert!(is_reserved_key_name(RESERVED_KEY_NAME_PREFIX));
+        assert!(!is_reserved_key_name("pos_refund_purse"));
+        assert!(!is_reserved_key_name("__system"));
+        assert!(!is_reserved_key_name("user/__system/x"));
+    }
+}
@@ -0,0 +1,51 @@
+use alloc::vec::Vec;
+
+use crate::{
+    bytesrepr::{Error, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+/// The number of bytes in a [`ScheduleId`].
+pub const SCHEDULE_ID_LENGTH: usize = 32;
+
+/// The number of bytes in a serialized [`ScheduleId`].
+pub const SCHEDULE_ID_SERIALIZED_LENGTH: usize = SCHEDULE_ID_LENGTH;
+
+/// The identifier of a contract call scheduled to run once a given block time is reached.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ScheduleId([u8; SCHEDULE_ID_LENGTH]);
+
+impl ScheduleId {
+    /// Constructs a `ScheduleId`.
+    pub fn new(value: [u8; SCHEDULE_ID_LENGTH]) -> Self {
+        ScheduleId(value)
+    }
+
+    /// Returns the raw bytes of the identifier.
+    pub fn value(self) -> [u8; SCHEDULE_ID_LENGTH] {
+        self.0
+    }
+}
+
+impl ToBytes for ScheduleId {
+    fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.0.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        SCHEDULE_ID_SERIALIZED_LENGTH
+    }
+}
+
+impl FromBytes for ScheduleId {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (value, rem) = FromBytes::from_bytes(bytes)?;
+        Ok((ScheduleId::new(value), rem))
+    }
+}
+
+impl CLTyped for ScheduleId {
+    fn cl_type() -> CLType {
+        <[u8; SCHEDULE_ID_LENGTH]>::cl_type()
+    }
+}
@@ -0,0 +1,78 @@
+//! Home of the auction contract's [`Error`] type.
+use failure::Fail;
+
+use alloc::vec::Vec;
+use core::result;
+
+use crate::{
+    bytesrepr::{self, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped,
+};
+
+/// Errors which can occur while executing the auction contract.
+#[derive(Fail, Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Error {
+    // ===== User errors =====
+    /// A bid was placed or withdrawn on behalf of a validator other than the caller.
+    #[fail(display = "Caller is not the validator")]
+    CallerNotValidator = 0,
+    /// Attempted to bid zero motes.
+    #[fail(display = "Bid is zero")]
+    ZeroBid,
+    /// Attempted to bid with a delegation rate above
+    /// [`MAX_DELEGATION_RATE`](crate::system_contract_names::auction::MAX_DELEGATION_RATE).
+    #[fail(display = "Delegation rate is too high")]
+    DelegationRateTooHigh,
+    /// The given validator has no bid.
+    #[fail(display = "Bid not found")]
+    BidNotFound,
+    /// A validator selected by the latest auction can't withdraw their bid before the next one.
+    #[fail(display = "Bid is locked until the next auction")]
+    BidLocked,
+    /// The auction was run for an era ending no later than the era of the latest auction.
+    #[fail(display = "Era end is not after the latest auction")]
+    EraEndNotIncreasing,
+    /// The bid of a validator would overflow.
+    #[fail(display = "Arithmetic overflow")]
+    ArithmeticOverflow,
+    /// While bidding, the transfer from the source purse to the bids purse failed.
+    #[fail(display = "Bid transfer failed")]
+    BidTransferFailed,
+    /// While withdrawing a bid, the transfer from the bids purse to the validator failed.
+    #[fail(display = "Withdraw transfer failed")]
+    WithdrawTransferFailed,
+    // ===== System errors =====
+    /// The invoked auction function can only be called by the system account.
+    #[fail(display = "System function was called by user account")]
+    SystemFunctionCalledByUserAccount,
+    /// Internal error: a named key of the auction contract wasn't found.
+    #[fail(display = "Named key not found")]
+    NamedKeyNotFound,
+    /// Internal error: a named key of the auction contract had the wrong type or value.
+    #[fail(display = "Named key has unexpected type")]
+    NamedKeyUnexpectedType,
+    /// Internal error: the bids stored in the local state of the contract couldn't be read.
+    #[fail(display = "Bids are invalid")]
+    InvalidBids,
+}
+
+impl CLTyped for Error {
+    fn cl_type() -> CLType {
+        CLType::U8
+    }
+}
+
+impl ToBytes for Error {
+    fn to_bytes(&self) -> result::Result<Vec<u8>, bytesrepr::Error> {
+        let value = *self as u8;
+        value.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+    }
+}
+
+/// An alias for `Result<T, auction::Error>`.
+pub type Result<T> = result::Result<T, Error>;
@@ -20,9 +20,9 @@ pub enum Error {
     /// Source purse not found.
     #[fail(display = "Source not found")]
     SourceNotFound = 1,
-    /// Destination purse not found.
-    #[fail(display = "Destination not found")]
-    DestNotFound = 2,
+    /// Destination purse not found, i.e. the destination uref isn't a purse created by the mint.
+    #[fail(display = "Destination purse not found")]
+    DestinationPurseNotFound = 2,
     /// See [`PurseError::InvalidURef`].
     #[fail(display = "Invalid URef")]
     InvalidURef = 3,
@@ -38,6 +38,16 @@ pub enum Error {
     /// Purse not found while trying to get balance.
     #[fail(display = "Purse not found")]
     PurseNotFound = 7,
+    /// Tried to transfer from or to a purse which has been revoked.
+    #[fail(display = "Purse revoked")]
+    PurseRevoked = 8,
+    /// Creating a purse would overflow the total supply of motes.
+    #[fail(display = "Total supply overflow")]
+    TotalSupplyOverflow = 9,
+    /// The caller of `transfer_from` hasn't been approved to spend the requested amount from the
+    /// owner's purse.
+    #[fail(display = "Insufficient allowance")]
+    InsufficientAllowance = 10,
 }
 
 impl From<PurseError> for Error {
@@ -73,12 +83,17 @@ impl TryFrom<u8> for Error {
         match value {
             d if d == Error::InsufficientFunds as u8 => Ok(Error::InsufficientFunds),
             d if d == Error::SourceNotFound as u8 => Ok(Error::SourceNotFound),
-            d if d == Error::DestNotFound as u8 => Ok(Error::DestNotFound),
+            d if d == Error::DestinationPurseNotFound as u8 => Ok(Error::DestinationPurseNotFound),
             d if d == Error::InvalidURef as u8 => Ok(Error::InvalidURef),
             d if d == Error::InvalidAccessRights as u8 => Ok(Error::InvalidAccessRights),
             d if d == Error::InvalidNonEmptyPurseCreation as u8 => {
                 Ok(Error::InvalidNonEmptyPurseCreation)
             }
+            d if d == Error::Storage as u8 => Ok(Error::Storage),
+            d if d == Error::PurseNotFound as u8 => Ok(Error::PurseNotFound),
+            d if d == Error::PurseRevoked as u8 => Ok(Error::PurseRevoked),
+            d if d == Error::TotalSupplyOverflow as u8 => Ok(Error::TotalSupplyOverflow),
+            d if d == Error::InsufficientAllowance as u8 => Ok(Error::InsufficientAllowance),
             _ => Err(TryFromU8ForError(())),
         }
     }
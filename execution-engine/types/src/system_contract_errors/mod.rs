@@ -1,6 +1,7 @@
 //! Home of error types returned by system contracts.
 use failure::Fail;
 
+pub mod auction;
 pub mod mint;
 pub mod pos;
 
@@ -13,6 +14,9 @@ pub enum Error {
     /// Contains a [`pos::Error`].
     #[fail(display = "Proof of Stake error: {}", _0)]
     Pos(pos::Error),
+    /// Contains an [`auction::Error`].
+    #[fail(display = "Auction error: {}", _0)]
+    Auction(auction::Error),
 }
 
 impl From<mint::Error> for Error {
@@ -26,3 +30,9 @@ impl From<pos::Error> for Error {
         Error::Pos(error)
     }
 }
+
+impl From<auction::Error> for Error {
+    fn from(error: auction::Error) -> Error {
+        Error::Auction(error)
+    }
+}
@@ -105,6 +105,24 @@ pub enum Error {
     /// deploy, but was called by the session code.
     #[fail(display = "Set refund purse was called outside payment")]
     SetRefundPurseCalledOutsidePayment,
+    /// Internal error: a stake or reward calculation overflowed.
+    #[fail(display = "Arithmetic overflow")]
+    ArithmeticOverflow,
+    /// PoS contract's "set_refund_purse" method was called with a purse on which the caller
+    /// doesn't hold `ADD` rights.
+    #[fail(display = "Insufficient access rights on refund purse")]
+    InsufficientRefundPurseAccessRights,
+    /// PoS contract's "get_payment_purse_balance" method was called outside the payment phase.
+    #[fail(display = "Get payment purse balance was called outside payment")]
+    GetPaymentPurseBalanceCalledOutsidePayment,
+    /// PoS contract's "bond" method was called with a purse on which the caller doesn't hold `ADD`
+    /// and `WRITE` rights.
+    #[fail(display = "Insufficient access rights on bonding purse")]
+    InsufficientBondingPurseAccessRights,
+    /// While slashing, the transfer from the Proof of Stake internal purse to the rewards purse
+    /// failed.
+    #[fail(display = "Slash transfer failed")]
+    SlashTransferFailed,
 }
 
 impl CLTyped for Error {
@@ -0,0 +1,34 @@
+//! Names of the entry points, arguments and named keys of the auction contract.
+
+/// Places or raises a bid of the caller to become a validator:
+/// `fn add_bid(validator: PublicKey, amount: U512, delegation_rate: u8, purse: URef)`.  The amount
+/// is moved from the purse into the auction's bids purse.
+pub const METHOD_ADD_BID: &str = "add_bid";
+/// Withdraws the whole bid of the caller, paying it back to their main purse:
+/// `fn withdraw_bid(validator: PublicKey)`.
+pub const METHOD_WITHDRAW_BID: &str = "withdraw_bid";
+/// Selects the validators of the era ending at the given timestamp from the current bids:
+/// `fn run_auction(era_end_timestamp: u64)`.  Only the system account may run the auction.
+pub const METHOD_RUN_AUCTION: &str = "run_auction";
+
+/// Index of the `validator` argument of [`METHOD_ADD_BID`] and [`METHOD_WITHDRAW_BID`].
+pub const BID_ARG_VALIDATOR_INDEX: u32 = 1;
+/// Index of the `amount` argument of [`METHOD_ADD_BID`].
+pub const ADD_BID_ARG_AMOUNT_INDEX: u32 = 2;
+/// Index of the `delegation_rate` argument of [`METHOD_ADD_BID`].
+pub const ADD_BID_ARG_DELEGATION_RATE_INDEX: u32 = 3;
+/// Index of the `purse` argument of [`METHOD_ADD_BID`].
+pub const ADD_BID_ARG_PURSE_INDEX: u32 = 4;
+/// Index of the `era_end_timestamp` argument of [`METHOD_RUN_AUCTION`].
+pub const RUN_AUCTION_ARG_ERA_END_TIMESTAMP_INDEX: u32 = 1;
+
+/// The largest delegation rate a bid can set, as a percentage.
+pub const MAX_DELEGATION_RATE: u8 = 100;
+
+/// Name of the purse holding the motes of all current bids.
+pub const BIDS_PURSE_KEY: &str = "auction_bids_purse";
+/// Name of the uref holding the number of validators selected by each auction, as a `u32`.
+pub const VALIDATOR_SLOTS_KEY: &str = "auction_validator_slots";
+/// Name of the uref holding the validators selected by the latest auction with their bids, as a
+/// `BTreeMap<PublicKey, U512>`.
+pub const WINNERS_KEY: &str = "auction_winners";
@@ -0,0 +1,22 @@
+//! Names of the entry points of the Mint contract.
+
+/// Mints new tokens into a new purse: `fn mint(amount: U512) -> Result<URef, Error>`.
+pub const METHOD_MINT: &str = "mint";
+/// Creates a new empty purse: `fn create() -> URef`.
+pub const METHOD_CREATE: &str = "create";
+/// Returns the balance of a purse: `fn balance(purse: URef) -> Option<U512>`.
+pub const METHOD_BALANCE: &str = "balance";
+/// Transfers tokens between purses:
+/// `fn transfer(source: URef, target: URef, amount: U512) -> Result<(), Error>`.
+pub const METHOD_TRANSFER: &str = "transfer";
+/// Revokes all allowances granted on a purse: `fn revoke(purse: URef) -> Result<(), Error>`.
+pub const METHOD_REVOKE: &str = "revoke";
+/// Allows a spender to transfer from a purse:
+/// `fn approve(purse: URef, spender: Key, amount: U512) -> Result<(), Error>`.
+pub const METHOD_APPROVE: &str = "approve";
+/// Returns the remaining allowance of a spender on a purse:
+/// `fn allowance(purse: URef, spender: Key) -> U512`.
+pub const METHOD_ALLOWANCE: &str = "allowance";
+/// Transfers tokens from a purse on which the caller holds an allowance:
+/// `fn transfer_from(owner_purse: URef, target: URef, amount: U512) -> Result<(), Error>`.
+pub const METHOD_TRANSFER_FROM: &str = "transfer_from";
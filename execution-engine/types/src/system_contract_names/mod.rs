@@ -0,0 +1,20 @@
+//! Home of the well-known names used to call the system contracts.
+//!
+//! The system contracts select the entry point to run by the method name passed as the first
+//! argument of a call, so a misspelled name only shows up as a failure at runtime.  Contracts, the
+//! system contracts themselves and the engine should all refer to these constants rather than
+//! repeating the names as string literals.
+
+pub mod auction;
+pub mod mint;
+pub mod pos;
+pub mod standard_payment;
+
+/// Name of the reference to the Mint contract in the named keys.
+pub const MINT_NAME: &str = "mint";
+/// Name of the reference to the Proof of Stake contract in the named keys.
+pub const POS_NAME: &str = "pos";
+
+/// Index of the argument holding the name of the entry point in a call to the Mint or the Proof of
+/// Stake contract.
+pub const METHOD_ARG_INDEX: u32 = 0;
@@ -0,0 +1,55 @@
+//! Names of the entry points, arguments and named keys of the Proof of Stake contract.
+
+/// Bonds the caller: `fn bond(amount: U512, purse: URef)`.
+pub const METHOD_BOND: &str = "bond";
+/// Unbonds the caller: `fn unbond(amount: Option<U512>)`.
+pub const METHOD_UNBOND: &str = "unbond";
+/// Slashes a validator: `fn slash(validator: PublicKey)`.
+pub const METHOD_SLASH: &str = "slash";
+/// Returns the purse into which payment code pays: `fn get_payment_purse() -> URef`.
+pub const METHOD_GET_PAYMENT_PURSE: &str = "get_payment_purse";
+/// Returns the balance of the payment purse: `fn get_payment_purse_balance() -> U512`.
+pub const METHOD_GET_PAYMENT_PURSE_BALANCE: &str = "get_payment_purse_balance";
+/// Sets the purse to which unspent payment is refunded: `fn set_refund_purse(purse: URef)`.
+pub const METHOD_SET_REFUND_PURSE: &str = "set_refund_purse";
+/// Returns the purse to which unspent payment is refunded:
+/// `fn get_refund_purse() -> Option<URef>`.
+pub const METHOD_GET_REFUND_PURSE: &str = "get_refund_purse";
+/// Pays the validators and refunds the rest of the payment once a deploy has been executed:
+/// `fn finalize_payment(amount_spent: U512, account: PublicKey)`.
+pub const METHOD_FINALIZE_PAYMENT: &str = "finalize_payment";
+/// Moves the stakes stored in the legacy format to urefs, and returns the number left to move:
+/// `fn migrate_stakes(max_count: u32) -> u32`.  At most `max_count` stakes are moved, or all of
+/// them if it is omitted.
+pub const METHOD_MIGRATE_STAKES: &str = "migrate_stakes";
+/// Moves the named keys of the contract into the reserved namespace:
+/// `fn migrate_reserved_keys()`.
+pub const METHOD_MIGRATE_RESERVED_KEYS: &str = "migrate_reserved_keys";
+
+/// Index of the `amount` argument of [`METHOD_BOND`].
+pub const BOND_ARG_AMOUNT_INDEX: u32 = 1;
+/// Index of the `purse` argument of [`METHOD_BOND`].
+pub const BOND_ARG_PURSE_INDEX: u32 = 2;
+/// Index of the `amount` argument of [`METHOD_UNBOND`].
+pub const UNBOND_ARG_AMOUNT_INDEX: u32 = 1;
+/// Index of the `validator` argument of [`METHOD_SLASH`].
+pub const SLASH_ARG_VALIDATOR_INDEX: u32 = 1;
+/// Index of the `purse` argument of [`METHOD_SET_REFUND_PURSE`].
+pub const SET_REFUND_PURSE_ARG_PURSE_INDEX: u32 = 1;
+/// Index of the `amount_spent` argument of [`METHOD_FINALIZE_PAYMENT`].
+pub const FINALIZE_PAYMENT_ARG_AMOUNT_SPENT_INDEX: u32 = 1;
+/// Index of the `account` argument of [`METHOD_FINALIZE_PAYMENT`].
+pub const FINALIZE_PAYMENT_ARG_ACCOUNT_INDEX: u32 = 2;
+/// Index of the `max_count` argument, which may be omitted, of [`METHOD_MIGRATE_STAKES`].
+pub const MIGRATE_STAKES_ARG_MAX_COUNT_INDEX: u32 = 1;
+
+/// Name of the purse holding all staked motes, and all unbonded motes yet to be paid out.
+pub const BONDING_PURSE_KEY: &str = "pos_bonding_purse";
+/// Name of the purse into which payment for computation is made on behalf of validators.
+pub const PAYMENT_PURSE_KEY: &str = "pos_payment_purse";
+/// Name of the purse holding validator earnings before they are distributed.
+pub const REWARDS_PURSE_KEY: &str = "pos_rewards_purse";
+/// Name of the purse set by the payment code to receive the refund of unspent payment.
+///
+/// It is reserved so that only the Proof of Stake contract can set it.
+pub const REFUND_PURSE_KEY: &str = "__system/pos_refund_purse";
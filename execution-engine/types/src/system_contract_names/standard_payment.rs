@@ -0,0 +1,4 @@
+//! Names of the arguments of the standard payment contract.
+
+/// Index of the `amount` argument of the standard payment contract: `fn pay(amount: U512)`.
+pub const ARG_AMOUNT_INDEX: u32 = 0;
@@ -19,6 +19,8 @@ pub enum SystemContractType {
     ProofOfStake,
     /// Standard Payment contract.
     StandardPayment,
+    /// Auction contract, only available once installed by an upgrade.
+    Auction,
 }
 
 /// Name of mint system contract
@@ -27,6 +29,8 @@ pub const MINT: &str = "mint";
 pub const PROOF_OF_STAKE: &str = "proof of stake";
 /// Name of standard payment system contract
 pub const STANDARD_PAYMENT: &str = "standard payment";
+/// Name of auction system contract
+pub const AUCTION: &str = "auction";
 
 impl From<SystemContractType> for u32 {
     fn from(system_contract_type: SystemContractType) -> u32 {
@@ -34,6 +38,7 @@ impl From<SystemContractType> for u32 {
             SystemContractType::Mint => 0,
             SystemContractType::ProofOfStake => 1,
             SystemContractType::StandardPayment => 2,
+            SystemContractType::Auction => 3,
         }
     }
 }
@@ -47,6 +52,7 @@ impl TryFrom<u32> for SystemContractType {
             0 => Ok(SystemContractType::Mint),
             1 => Ok(SystemContractType::ProofOfStake),
             2 => Ok(SystemContractType::StandardPayment),
+            3 => Ok(SystemContractType::Auction),
             _ => Err(ApiError::InvalidSystemContract),
         }
     }
@@ -58,6 +64,7 @@ impl Display for SystemContractType {
             SystemContractType::Mint => write!(f, "{}", MINT),
             SystemContractType::ProofOfStake => write!(f, "{}", PROOF_OF_STAKE),
             SystemContractType::StandardPayment => write!(f, "{}", STANDARD_PAYMENT),
+            SystemContractType::Auction => write!(f, "{}", AUCTION),
         }
     }
 }
@@ -92,6 +99,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_index_of_auction_contract() {
+        let index: u32 = SystemContractType::Auction.into();
+        assert_eq!(index, 3u32);
+        assert_eq!(SystemContractType::Auction.to_string(), AUCTION);
+    }
+
     #[test]
     fn create_mint_variant_from_int() {
         let mint = SystemContractType::try_from(0).ok().unwrap();
@@ -110,9 +124,14 @@ mod tests {
         assert_eq!(pos, SystemContractType::StandardPayment);
     }
 
+    #[test]
+    fn create_auction_variant_from_int() {
+        let auction = SystemContractType::try_from(3).ok().unwrap();
+        assert_eq!(auction, SystemContractType::Auction);
+    }
+
     #[test]
     fn create_unknown_system_contract_variant() {
-        assert!(SystemContractType::try_from(3).is_err());
         assert!(SystemContractType::try_from(4).is_err());
         assert!(SystemContractType::try_from(10).is_err());
         assert!(SystemContractType::try_from(u32::max_value()).is_err());
@@ -64,6 +64,24 @@ impl URef {
         URef(self.0, AccessRights::READ_ADD_WRITE)
     }
 
+    /// Returns a read-only view of this [`URef`]: the same address, with
+    /// [`AccessRights::READ`] permission.
+    ///
+    /// Passing the returned `URef` to a called contract lets the callee read the value under it
+    /// without being able to write or add to it.
+    pub fn as_read_only(&self) -> URef {
+        URef(self.0, AccessRights::READ)
+    }
+
+    /// Returns an add-only view of this [`URef`]: the same address, with [`AccessRights::ADD`]
+    /// permission.
+    ///
+    /// Passing the returned `URef` to a called contract lets the callee add to the value under it,
+    /// e.g. deposit into a purse, without being able to read or withdraw from it.
+    pub fn as_add_only(&self) -> URef {
+        URef(self.0, AccessRights::ADD)
+    }
+
     /// Returns `true` if the access rights are `Some` and
     /// [`is_writeable`](AccessRights::is_writeable) is `true` for them.
     pub fn is_writeable(self) -> bool {
@@ -76,6 +94,12 @@ impl URef {
         self.1.is_addable()
     }
 
+    /// Returns `true` if the access rights are `Some` and
+    /// [`is_publicly_readable`](AccessRights::is_publicly_readable) is `true` for them.
+    pub fn is_publicly_readable(self) -> bool {
+        self.1.is_publicly_readable()
+    }
+
     /// Formats the address and access rights of the [`URef`] in an unique way that could be used as
     /// a name when storing the given `URef` in a global state.
     pub fn as_string(&self) -> String {
@@ -164,5 +188,24 @@ mod tests {
             uref_c.as_string(),
             "uref-0000000000000000000000000000000000000000000000000000000000000000-000"
         );
+
+        let uref_d = URef::new(addr_array, AccessRights::READ | AccessRights::PUBLIC_READ);
+        assert_eq!(
+            uref_d.as_string(),
+            "uref-0000000000000000000000000000000000000000000000000000000000000000-011"
+        );
+    }
+
+    #[test]
+    fn should_narrow_access_rights() {
+        let uref = URef::new([7u8; 32], AccessRights::READ_ADD_WRITE);
+
+        let read_only = uref.as_read_only();
+        assert_eq!(read_only.addr(), uref.addr());
+        assert_eq!(read_only.access_rights(), AccessRights::READ);
+
+        let add_only = uref.as_add_only();
+        assert_eq!(add_only.addr(), uref.addr());
+        assert_eq!(add_only.access_rights(), AccessRights::ADD);
     }
 }
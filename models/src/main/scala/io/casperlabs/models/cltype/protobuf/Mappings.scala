@@ -407,6 +407,10 @@ object Mappings {
         lift(fromProto(k).map(CLValueInstance.Key.apply))
       case state.CLValueInstance.Value.Value.Uref(u) =>
         lift(fromProto(u).map(CLValueInstance.URef.apply))
+      case state.CLValueInstance.Value.Value.PurseId(state.PurseId(maybeUref)) =>
+        maybeUref
+          .map(u => lift(fromProto(u).map(CLValueInstance.URef.apply)))
+          .getOrElse(raise(Error.MissingValue))
 
       case state.CLValueInstance.Value.Value.BytesValue(bytes) =>
         val u8Instances = bytes.toByteArray.map(CLValueInstance.U8.apply)
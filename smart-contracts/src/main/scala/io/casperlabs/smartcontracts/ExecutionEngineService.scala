@@ -108,6 +108,8 @@ class GrpcExecutionEngineService[F[_]: Defer: Concurrent: Log: TaskLift: Metrics
               s"Missing states: ${Base16.encode(missing.toByteArray)}"
             )
           )
+        case ExecuteResponse.Result.Overloaded(Overloaded(retryAfterMillis)) =>
+          Left(new SmartContractEngineError(s"Overloaded: retry after $retryAfterMillis ms"))
       }
     }
 
@@ -199,6 +201,14 @@ class GrpcExecutionEngineService[F[_]: Defer: Concurrent: Log: TaskLift: Metrics
             Left(SmartContractEngineError(s"Key not found in global state: $value"))
           case CommitResponse.Result.TypeMismatch(err) =>
             Left(SmartContractEngineError(err.toString))
+          case CommitResponse.Result.StaleCommit(staleCommit) =>
+            Left(
+              SmartContractEngineError(
+                s"Stale commit: last seen sequence number ${staleCommit.lastSeenCommitSequenceNumber} but current is ${staleCommit.currentCommitSequenceNumber}"
+              )
+            )
+          case CommitResponse.Result.Overloaded(Overloaded(retryAfterMillis)) =>
+            Left(SmartContractEngineError(s"Overloaded: retry after $retryAfterMillis ms"))
         }
       }
     }
@@ -209,7 +219,11 @@ class GrpcExecutionEngineService[F[_]: Defer: Concurrent: Log: TaskLift: Metrics
       path: Seq[String],
       protocolVersion: ProtocolVersion
   ): F[Either[Throwable, StoredValue]] =
-    sendMessage(QueryRequest(state, Some(baseKey), path, Some(protocolVersion)), _.query) {
+    sendMessage(
+      // the node deserializes the whole `StoredValue`, so contracts are requested in full
+      QueryRequest(state, Some(baseKey), path, Some(protocolVersion), includeContractBytes = true),
+      _.query
+    ) {
       _.result match {
         case QueryResponse.Result.Success(bytes) =>
           FromBytes
@@ -218,6 +232,14 @@ class GrpcExecutionEngineService[F[_]: Defer: Concurrent: Log: TaskLift: Metrics
 
         case QueryResponse.Result.Empty        => Left(SmartContractEngineError("empty response"))
         case QueryResponse.Result.Failure(err) => Left(SmartContractEngineError(err))
+        case QueryResponse.Result.ValueTooLarge(tooLarge) =>
+          Left(
+            SmartContractEngineError(
+              s"Value of type ${tooLarge.typeTag} is too large: ${tooLarge.actualSize} bytes"
+            )
+          )
+        case QueryResponse.Result.Overloaded(Overloaded(retryAfterMillis)) =>
+          Left(SmartContractEngineError(s"Overloaded: retry after $retryAfterMillis ms"))
       }
     }
 }